@@ -62,6 +62,7 @@ engine = {}
 ---@field sprite SpriteInfo|nil
 ---@field animation AnimationInfo|nil
 ---@field signals SignalSet|nil
+---@field blackboard table<string, boolean|number|string|Vector2>|nil AI memory; entity values are IDs
 ---@field phase string|nil
 ---@field time_in_phase number|nil
 ---@field previous_phase string|nil Only in on_enter
@@ -91,6 +92,8 @@ engine = {}
 ---@field a CollisionEntity
 ---@field b CollisionEntity
 ---@field sides CollisionSides
+---@field depth number Penetration depth along the normal (smaller overlap extent)
+---@field normal Vector2 Unit contact normal pointing from A toward B
 
 ---State of a single digital button
 ---@class DigitalButtonState
@@ -193,7 +196,10 @@ engine = {}
 ---@alias BoxSide "left" | "right" | "top" | "bottom"
 
 ---Function category
----@alias Category "base" | "asset" | "spawn" | "audio" | "signal" | "phase" | "entity" | "group" | "camera" | "collision" | "animation" | "render"
+---@alias Category "base" | "asset" | "spawn" | "audio" | "signal" | "phase" | "entity" | "group" | "camera" | "collision" | "animation" | "render" | "world" | "clock" | "weather" | "inventory" | "ui" | "save" | "debug"
+
+---Color-vision palette remap applied as the last post-process pass
+---@alias ColorblindMode "none" | "protanopia" | "deuteranopia" | "tritanopia" | "grayscale"
 
 ---Comparison operator for animation rules
 ---@alias ComparisonOp "lt" | "le" | "gt" | "ge" | "eq" | "ne"
@@ -216,6 +222,9 @@ engine = {}
 ---Time-to-live specification (number, {min,max} table, or 'none')
 ---@alias TtlSpec "none"
 
+---Window mode set with engine.set_window_mode
+---@alias WindowMode "windowed" | "borderless" | "fullscreen"
+
 -- ==================== Callback Signatures ====================
 -- These are the callback functions your Lua scripts should define.
 -- They are called by the engine at appropriate times.
@@ -286,6 +295,10 @@ function menu_callback(menu_id, item_id, item_index) end
 ---@param scene_name string
 function engine.change_scene(scene_name) end
 
+---Get the order in which queued subsystem commands (asset, map, world, visibility, fog, clock, weather, music, formation, inventory, ui, savegame, debug) are applied at the end of each frame
+---@return string[]
+function engine.command_order() end
+
 ---General purpose logging
 ---@param message string
 function engine.log(message) end
@@ -306,11 +319,29 @@ function engine.log_info(message) end
 ---@param message string
 function engine.log_warn(message) end
 
+---Register a function called as fn(path) after a changed script is hot-reloaded (pass nil to remove it)
+---@param callback function|nil
+function engine.on_reload(callback) end
+
 ---Quit the game engine (sets quit_game flag)
 function engine.quit() end
 
+---Write the current game configuration (video, audio, accessibility and system settings) to config.ini
+function engine.save_config() end
+
 -- ==================== Asset Loading ====================
 
+---Render text with a loaded font into a static texture usable as a sprite. `color` is {r, g, b, a?} (default white)
+---@param id string
+---@param text string
+---@param font string
+---@param size number
+---@param color table|nil
+function engine.bake_text_texture(id, text, font, size, color) end
+
+---Log every loaded texture, font, sound and music track with its estimated memory, entity references and last-used frame
+function engine.dump_asset_usage() end
+
 ---Load a font from file
 ---@param id string
 ---@param path string
@@ -337,6 +368,16 @@ function engine.load_sound(id, path) end
 ---@param filter string|nil
 function engine.load_texture(id, path, filter) end
 
+---Get the size of a loaded texture as {width, height} (nil if unknown; textures loaded or baked this frame show up next frame)
+---@param id string
+---@return table|nil
+function engine.texture_size(id) end
+
+---Set the auto-tiling rules applied when tiles of a tileset are edited at runtime ({mode = "bitmask16"|"blob47", tiles = {[mask] = id}, members?, layers?, border_filled?}); nil removes them
+---@param tileset string
+---@param rules table|nil
+function engine.tilemap_set_autotile(tileset, rules) end
+
 -- ==================== Entity Spawning ====================
 
 ---Clone a registered entity with optional overrides
@@ -344,12 +385,62 @@ function engine.load_texture(id, path, filter) end
 ---@return EntityBuilder
 function engine.clone(source_key) end
 
+---Get a spawn point of the current scene as {name, x, y, properties} (nil if unknown; points spawned this frame show up next frame)
+---@param name string
+---@return table|nil
+function engine.get_spawn_point(name) end
+
 ---Create a new entity builder
 ---@return EntityBuilder
 function engine.spawn() end
 
+---Clone the entity registered as `source_key` onto `count` points of an area in one call. opts: {shape? ("rect" (default): x, y top-left, w, h; "circle": x, y center, radius), count? (default 1), distribution? ("uniform" (default), "poisson" (random but kept apart) or "grid" (evenly spaced)), seed? (integer, repeatable layouts)}. Returns the number of clones queued
+---@param source_key string
+---@param opts table
+---@return integer
+function engine.spawn_prefab_area(source_key, opts) end
+
 -- ==================== Audio Playback ====================
 
+---Fade the music channel in from silence over `seconds`; call it right after play_music
+---@param seconds number
+function engine.fade_in_music(seconds) end
+
+---Fade all playing music out over `seconds`, then stop it. play_music during the fade cancels it
+---@param seconds number
+function engine.fade_out_music(seconds) end
+
+---Get the current master volume (0.0-1.0)
+---@return number
+function engine.get_volume_master() end
+
+---Get the current music volume (0.0-1.0)
+---@return number
+function engine.get_volume_music() end
+
+---Get the current sound effects volume (0.0-1.0)
+---@return number
+function engine.get_volume_sfx() end
+
+---Set the tempo of a music track so its playback emits beat/bar events (beats_per_bar defaults to 4; bpm 0 disables)
+---@param id string
+---@param bpm number
+---@param beats_per_bar integer|nil
+function engine.music_set_bpm(id, bpm, beats_per_bar) end
+
+---Enable/disable pattern/row/order reports for a loaded .xm/.mod music track
+---@param id string
+---@param enabled boolean
+function engine.music_set_tracker_events(id, enabled) end
+
+---Register a function called as fn(id, beat, bar, beat_in_bar) on every music beat (pass nil to remove it)
+---@param callback function|nil
+function engine.on_beat(callback) end
+
+---Register a function called as fn(id, order, pattern, row) whenever a tracker music track enters a new row (row 0 marks an order change; pass nil to remove it)
+---@param callback function|nil
+function engine.on_tracker_row(callback) end
+
 ---Pause a specific music track
 ---@param id string
 function engine.pause_music(id) end
@@ -377,6 +468,24 @@ function engine.resume_music(id) end
 ---@param vol number
 function engine.set_music_volume(id, vol) end
 
+---Play a playlist of loaded music ids whenever `scene` is entered, crossfading from the previous music and moving to the next track when one ends. opts: {shuffle? (default false), crossfade? (seconds, default 1), volume? (default 1)}. A scene with the same playlist keeps the music playing; an empty list fades it out; nil forgets the scene's playlist
+---@param scene string
+---@param tracks string[]|nil
+---@param opts table|nil
+function engine.set_scene_music(scene, tracks, opts) end
+
+---Set the master volume (0.0-1.0, clamped; persisted by save_config)
+---@param vol number
+function engine.set_volume_master(vol) end
+
+---Set the music volume (0.0-1.0, clamped; persisted by save_config)
+---@param vol number
+function engine.set_volume_music(vol) end
+
+---Set the sound effects volume (0.0-1.0, clamped; persisted by save_config)
+---@param vol number
+function engine.set_volume_sfx(vol) end
+
 ---Stop all playing music
 function engine.stop_all_music() end
 
@@ -514,6 +623,29 @@ function engine.phase_transition(entity_id, phase) end
 ---@param enabled boolean
 function engine.collision_entity_add_force(entity_id, name, x, y, enabled) end
 
+---Remove every key from an entity's AI blackboard
+---@param entity_id integer
+function engine.collision_entity_blackboard_clear(entity_id) end
+
+---Remove a key from an entity's AI blackboard
+---@param entity_id integer
+---@param key string
+function engine.collision_entity_blackboard_remove(entity_id, key) end
+
+---Write an AI blackboard value (boolean, number, string or {x, y} table) on an entity, forgetting it after ttl seconds if given. Inserts a Blackboard if missing
+---@param entity_id integer
+---@param key string
+---@param value boolean|number|string|table
+---@param ttl number|nil
+function engine.collision_entity_blackboard_set(entity_id, key, value, ttl) end
+
+---Remember another entity's ID on an entity's AI blackboard, forgetting it after ttl seconds if given
+---@param entity_id integer
+---@param key string
+---@param target_id integer
+---@param ttl number|nil
+function engine.collision_entity_blackboard_set_entity(entity_id, key, target_id, ttl) end
+
 ---Despawn an entity
 ---@param entity_id integer
 function engine.collision_entity_despawn(entity_id) end
@@ -658,6 +790,38 @@ function engine.collision_entity_set_animation(entity_id, animation_key) end
 ---@param zoom number|nil
 function engine.collision_entity_set_camera_target(entity_id, priority, zoom) end
 
+---Enable/disable collision detection for an entity without removing its collider
+---@param entity_id integer
+---@param enabled boolean
+function engine.collision_entity_set_collider_enabled(entity_id, enabled) end
+
+---Enable/disable an entity's force area (wind zone, current, conveyor)
+---@param entity_id integer
+---@param enabled boolean
+function engine.collision_entity_set_force_area_enabled(entity_id, enabled) end
+
+---Start an entity's cooldown over from its full duration
+---@param entity_id integer
+function engine.collision_entity_start_cooldown(entity_id) end
+
+---Make an entity's cooldown ready now
+---@param entity_id integer
+function engine.collision_entity_reset_cooldown(entity_id) end
+
+---Remove the distance and pin joints of an entity, setting it free
+---@param entity_id integer
+function engine.collision_entity_remove_joints(entity_id) end
+
+---Launch an entity with :with_height() upward at speed (world units per second), in the air or not
+---@param entity_id integer
+---@param speed number
+function engine.collision_entity_jump(entity_id, speed) end
+
+---Set the faux-3D height of an entity with :with_height()
+---@param entity_id integer
+---@param height number
+function engine.collision_entity_set_height(entity_id, height) end
+
 ---Enable or disable a named force on an entity
 ---@param entity_id integer
 ---@param name string
@@ -696,6 +860,17 @@ function engine.collision_entity_set_gui_progress_max(entity_id, max) end
 ---@param max_speed number|nil
 function engine.collision_entity_set_max_speed(entity_id, max_speed) end
 
+---Set entity mass (forces, force areas, wind and impulses are divided by it)
+---@param entity_id integer
+---@param mass number
+function engine.collision_entity_set_mass(entity_id, mass) end
+
+---Change entity velocity by (x, y) divided by its mass
+---@param entity_id integer
+---@param x number
+---@param y number
+function engine.collision_entity_apply_impulse(entity_id, x, y) end
+
 ---Set the parent of an entity for transform hierarchy
 ---@param entity_id integer
 ---@param parent_id integer
@@ -857,6 +1032,44 @@ function engine.collision_entity_unfreeze(entity_id) end
 ---@param entity_id integer
 function engine.collision_release_stuckto(entity_id) end
 
+---Show or hide a tilemap layer by name. Hidden layers despawn their tile entities
+---@param entity_id integer
+---@param layer string
+---@param visible boolean
+function engine.collision_tilemap_set_layer_visible(entity_id, layer, visible) end
+
+---Place atlas tile tile_id at cell (x, y) of a tilemap layer, replacing the tile there. A nil tile_id clears the cell
+---@param entity_id integer
+---@param layer string
+---@param x integer
+---@param y integer
+---@param tile_id integer|nil
+function engine.collision_tilemap_set_tile(entity_id, layer, x, y, tile_id) end
+
+---Clear every pixel of a terrain within radius of world point (x, y). Collisions follow the hole at once; the texture updates before the next frame is drawn
+---@param entity_id integer
+---@param x number
+---@param y number
+---@param radius number
+function engine.collision_terrain_carve_circle(entity_id, x, y, radius) end
+
+---Disturb a water surface at world x: a positive velocity pushes the water down, a negative one lifts it. The wave spreads from there
+---@param entity_id integer
+---@param x number
+---@param velocity number
+function engine.collision_water_splash(entity_id, x, velocity) end
+
+---Move every entity whose group or tag is `group` by (dx, dy). Children of moved entities follow their parent
+---@param group string
+---@param dx number
+---@param dy number
+function engine.collision_group_translate(group, dx, dy) end
+
+---Show or hide the sprites and texts of every entity whose group or tag is `group`. Hidden entities still move, collide and animate
+---@param group string
+---@param visible boolean
+function engine.collision_group_set_visible(group, visible) end
+
 ---Add a named acceleration force to an entity
 ---@param entity_id integer
 ---@param name string
@@ -865,6 +1078,29 @@ function engine.collision_release_stuckto(entity_id) end
 ---@param enabled boolean
 function engine.entity_add_force(entity_id, name, x, y, enabled) end
 
+---Remove every key from an entity's AI blackboard
+---@param entity_id integer
+function engine.entity_blackboard_clear(entity_id) end
+
+---Remove a key from an entity's AI blackboard
+---@param entity_id integer
+---@param key string
+function engine.entity_blackboard_remove(entity_id, key) end
+
+---Write an AI blackboard value (boolean, number, string or {x, y} table) on an entity, forgetting it after ttl seconds if given. Inserts a Blackboard if missing
+---@param entity_id integer
+---@param key string
+---@param value boolean|number|string|table
+---@param ttl number|nil
+function engine.entity_blackboard_set(entity_id, key, value, ttl) end
+
+---Remember another entity's ID on an entity's AI blackboard, forgetting it after ttl seconds if given
+---@param entity_id integer
+---@param key string
+---@param target_id integer
+---@param ttl number|nil
+function engine.entity_blackboard_set_entity(entity_id, key, target_id, ttl) end
+
 ---Despawn an entity
 ---@param entity_id integer
 function engine.entity_despawn(entity_id) end
@@ -1009,6 +1245,38 @@ function engine.entity_set_animation(entity_id, animation_key) end
 ---@param zoom number|nil
 function engine.entity_set_camera_target(entity_id, priority, zoom) end
 
+---Enable/disable collision detection for an entity without removing its collider
+---@param entity_id integer
+---@param enabled boolean
+function engine.entity_set_collider_enabled(entity_id, enabled) end
+
+---Enable/disable an entity's force area (wind zone, current, conveyor)
+---@param entity_id integer
+---@param enabled boolean
+function engine.entity_set_force_area_enabled(entity_id, enabled) end
+
+---Start an entity's cooldown over from its full duration
+---@param entity_id integer
+function engine.entity_start_cooldown(entity_id) end
+
+---Make an entity's cooldown ready now
+---@param entity_id integer
+function engine.entity_reset_cooldown(entity_id) end
+
+---Remove the distance and pin joints of an entity, setting it free
+---@param entity_id integer
+function engine.entity_remove_joints(entity_id) end
+
+---Launch an entity with :with_height() upward at speed (world units per second), in the air or not
+---@param entity_id integer
+---@param speed number
+function engine.entity_jump(entity_id, speed) end
+
+---Set the faux-3D height of an entity with :with_height()
+---@param entity_id integer
+---@param height number
+function engine.entity_set_height(entity_id, height) end
+
 ---Enable or disable a named force on an entity
 ---@param entity_id integer
 ---@param name string
@@ -1047,6 +1315,17 @@ function engine.entity_set_gui_progress_max(entity_id, max) end
 ---@param max_speed number|nil
 function engine.entity_set_max_speed(entity_id, max_speed) end
 
+---Set entity mass (forces, force areas, wind and impulses are divided by it)
+---@param entity_id integer
+---@param mass number
+function engine.entity_set_mass(entity_id, mass) end
+
+---Change entity velocity by (x, y) divided by its mass
+---@param entity_id integer
+---@param x number
+---@param y number
+function engine.entity_apply_impulse(entity_id, x, y) end
+
 ---Set the parent of an entity for transform hierarchy
 ---@param entity_id integer
 ---@param parent_id integer
@@ -1208,20 +1487,119 @@ function engine.entity_unfreeze(entity_id) end
 ---@param entity_id integer
 function engine.release_stuckto(entity_id) end
 
+---Show or hide a tilemap layer by name. Hidden layers despawn their tile entities
+---@param entity_id integer
+---@param layer string
+---@param visible boolean
+function engine.tilemap_set_layer_visible(entity_id, layer, visible) end
+
+---Place atlas tile tile_id at cell (x, y) of a tilemap layer, replacing the tile there. A nil tile_id clears the cell
+---@param entity_id integer
+---@param layer string
+---@param x integer
+---@param y integer
+---@param tile_id integer|nil
+function engine.tilemap_set_tile(entity_id, layer, x, y, tile_id) end
+
+---Clear every pixel of a terrain within radius of world point (x, y). Collisions follow the hole at once; the texture updates before the next frame is drawn
+---@param entity_id integer
+---@param x number
+---@param y number
+---@param radius number
+function engine.terrain_carve_circle(entity_id, x, y, radius) end
+
+---Disturb a water surface at world x: a positive velocity pushes the water down, a negative one lifts it. The wave spreads from there
+---@param entity_id integer
+---@param x number
+---@param velocity number
+function engine.water_splash(entity_id, x, velocity) end
+
+---Move every entity whose group or tag is `group` by (dx, dy). Children of moved entities follow their parent
+---@param group string
+---@param dx number
+---@param dy number
+function engine.group_translate(group, dx, dy) end
+
+---Show or hide the sprites and texts of every entity whose group or tag is `group`. Hidden entities still move, collide and animate
+---@param group string
+---@param visible boolean
+function engine.group_set_visible(group, visible) end
+
 -- ==================== Group Tracking ====================
 
 ---Stop tracking all entity groups
 function engine.clear_tracked_groups() end
 
+---Get the ids of entities with a group or tags whose position lies inside the rectangle, optionally only those matching `group` (group name or tag). Uses the spatial index as of the start of the frame
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@param group string|nil
+---@return integer[]
+function engine.entities_in_rect(x, y, w, h, group) end
+
+---Check whether an entity carries a tag added with :with_tags() (the group name is not a tag). Reflects tags as of the start of the frame
+---@param entity_id integer
+---@param tag string
+---@return boolean
+function engine.entity_has_tag(entity_id, tag) end
+
+---Add an entity to the next free slot of a formation, taking it out of any other formation. The formation moves its MapPosition from then on
+---@param name string
+---@param entity_id integer
+function engine.formation_add(name, entity_id) end
+
+---Create (or replace) the formation `name` led by `leader_id`, with slots laid out as a "line", "v" or "circle" `spacing` units apart. Add members with engine.formation_add(). opts: {catch_up? (how fast members close the gap to their slot, default 6; 0 snaps), rotate? (turn the slots with the leader's rotation, default false)}
+---@param name string
+---@param leader_id integer
+---@param pattern string
+---@param spacing number
+---@param opts table|nil
+function engine.formation_create(name, leader_id, pattern, spacing, opts) end
+
+---Disband a formation, leaving its members where they are
+---@param name string
+function engine.formation_disband(name) end
+
+---Take an entity out of a formation; the members behind it move up a slot
+---@param name string
+---@param entity_id integer
+function engine.formation_remove(name, entity_id) end
+
+---Get {count, cx, cy, min_x, min_y, max_x, max_y} for a group registered with track_group_bounds (nil otherwise); values are from the start of this frame's update
+---@param name string
+---@return table|nil
+function engine.group_bounds(name) end
+
+---Get the entity id and distance of the member of a track_group_bounds group closest to (x, y), or nil if the group is empty or not aggregated
+---@param name string
+---@param x number
+---@param y number
+---@return integer|nil, number|nil
+function engine.group_nearest(name, x, y) end
+
 ---Check if a group is being tracked
 ---@param name string
 ---@return boolean
 function engine.has_tracked_group(name) end
 
+---Get the id and distance of the entity closest to (x, y), optionally only those matching `group` (group name or tag) and within `max_dist`, or nil if none. Uses the spatial index as of the start of the frame
+---@param x number
+---@param y number
+---@param group string|nil
+---@param max_dist number|nil
+---@return integer|nil, number|nil
+function engine.nearest_entity(x, y, group, max_dist) end
+
 ---Start tracking a named entity group
 ---@param name string
 function engine.track_group(name) end
 
+---Track a named entity group and compute its centroid, bounding box and member positions every frame (see group_bounds/group_nearest)
+---@param name string
+function engine.track_group_bounds(name) end
+
 ---Stop tracking a named entity group
 ---@param name string
 function engine.untrack_group(name) end
@@ -1276,6 +1654,34 @@ function engine.camera_follow_set_spring(stiffness, damping) end
 ---@param speed number
 function engine.camera_follow_set_zoom_speed(speed) end
 
+---Blend the view from the camera target to world point (x, y) over duration seconds and hold it there until camera_release_pan()
+---@param x number
+---@param y number
+---@param duration number
+---@param easing string|nil
+function engine.camera_pan_to(x, y, duration, easing) end
+
+---Blend the view back from a camera_pan_to() point to the camera target over duration seconds
+---@param duration number
+---@param easing string|nil
+function engine.camera_release_pan(duration, easing) end
+
+---Shake the camera: intensity 0-1 (trauma, squared into up to 12 px and 2 degrees of jitter) fading out over duration seconds. A weaker shake never cuts a stronger one short
+---@param intensity number
+---@param duration number
+function engine.camera_shake(intensity, duration) end
+
+---Zoom in by amount (0.1 = 10%) at once and ease back out over duration seconds
+---@param amount number
+---@param duration number
+function engine.camera_zoom_punch(amount, duration) end
+
+---Ease the camera effect zoom factor (multiplying the camera zoom) to zoom over duration seconds and keep it; 1 restores the camera zoom. easing: tween easing name (default "linear")
+---@param zoom number
+---@param duration number
+---@param easing string|nil
+function engine.camera_zoom_to(zoom, duration, easing) end
+
 ---Get the current 2D camera state (target, offset, rotation, zoom). Returns values from the start of this frame after camera_follow_system has run. If called in the same callback as set_camera(), returns pre-override values. Only available during on_update callbacks; returns defaults (zoom=1) from on_setup / on_switch_scene. Each call returns a new table; cache locally if reading multiple fields.
 ---@return table
 function engine.get_camera() end
@@ -1284,6 +1690,13 @@ function engine.get_camera() end
 ---@return table
 function engine.get_camera_view_rect() end
 
+---Get the cell (x, y) of tilemap `entity_id` under a game-screen point such as input.analog.mouse_x/mouse_y, or nil outside the map. Handles orthogonal, isometric and hex projections and the root's position, scale and rotation. Uses the camera and tilemap state from the start of the frame
+---@param entity_id integer
+---@param x number
+---@param y number
+---@return integer|nil
+function engine.screen_to_tile(entity_id, x, y) end
+
 ---Set the 2D camera target, offset, rotation and zoom
 ---@param target_x number
 ---@param target_y number
@@ -1293,8 +1706,52 @@ function engine.get_camera_view_rect() end
 ---@param zoom number
 function engine.set_camera(target_x, target_y, offset_x, offset_y, rotation, zoom) end
 
+---Get the world position of the centre of cell (x, y) of tilemap `entity_id`, or nil if it is not a spawned tilemap
+---@param entity_id integer
+---@param x integer
+---@param y integer
+---@return number|nil
+function engine.tile_to_world(entity_id, x, y) end
+
 -- ==================== Collision Commands ====================
 
+---Check whether entity `a` can see entity `b`: inside a's :with_visibility() polygon if it has one, otherwise with no occluder between them. Entities need a visibility polygon, a group or tags to be found. Uses the state from the start of the frame
+---@param a integer
+---@param b integer
+---@return boolean
+function engine.can_see(a, b) end
+
+---Remove every Lua collision rule
+function engine.clear_collision_rules() end
+
+---Blend the view from the camera target to world point (x, y) over duration seconds and hold it there until camera_release_pan() (collision context)
+---@param x number
+---@param y number
+---@param duration number
+---@param easing string|nil
+function engine.collision_camera_pan_to(x, y, duration, easing) end
+
+---Blend the view back from a camera_pan_to() point to the camera target over duration seconds (collision context)
+---@param duration number
+---@param easing string|nil
+function engine.collision_camera_release_pan(duration, easing) end
+
+---Shake the camera: intensity 0-1 (trauma, squared into up to 12 px and 2 degrees of jitter) fading out over duration seconds. A weaker shake never cuts a stronger one short (collision context)
+---@param intensity number
+---@param duration number
+function engine.collision_camera_shake(intensity, duration) end
+
+---Zoom in by amount (0.1 = 10%) at once and ease back out over duration seconds (collision context)
+---@param amount number
+---@param duration number
+function engine.collision_camera_zoom_punch(amount, duration) end
+
+---Ease the camera effect zoom factor (multiplying the camera zoom) to zoom over duration seconds and keep it; 1 restores the camera zoom. easing: tween easing name (default "linear") (collision context)
+---@param zoom number
+---@param duration number
+---@param easing string|nil
+function engine.collision_camera_zoom_to(zoom, duration, easing) end
+
 ---Clear a world signal flag (collision context)
 ---@param key string
 function engine.collision_clear_flag(key) end
@@ -1375,6 +1832,28 @@ function engine.collision_spawn() end
 ---@param key string
 function engine.collision_toggle_flag(key) end
 
+---Call the global function callback(ctx) when the two groups collide, until the next scene switch removes the rule. Sides take the same patterns as with_lua_collision_rule
+---@param group_a string|string[]
+---@param group_b string|string[]
+---@param callback string
+function engine.on_collision_scene(group_a, group_b, callback) end
+
+---Remove the Lua collision rules declared for a group pair (either order). Patterns are compared as written, e.g. "enemy_*"
+---@param group_a string
+---@param group_b string
+function engine.remove_collision_rule(group_a, group_b) end
+
+---Enable or disable the Lua collision rules declared for a group pair (either order) without removing them
+---@param group_a string
+---@param group_b string
+---@param enabled boolean
+function engine.set_collision_rule_enabled(group_a, group_b, enabled) end
+
+---Block line of sight with the colliders of entities labeled `label` (group or tag) and the tiles of the `solid_layers` tilemap layers. Used by :with_visibility() polygons and engine.can_see(); call with no arguments to clear
+---@param label string|nil
+---@param solid_layers string[]|nil
+function engine.set_occluders(label, solid_layers) end
+
 -- ==================== Animation Registration ====================
 
 ---Register an animation definition
@@ -1391,18 +1870,65 @@ function engine.register_animation(id, tex_key, pos_x, pos_y, horizontal_displac
 
 -- ==================== Rendering & Shaders ====================
 
+---Switch off a non-essential system ("particles", "weather", "color_grading") from the next frame; persisted by save_config
+---@param name string
+function engine.disable_system(name) end
+
+---Switch a system turned off by disable_system() or the config file back on
+---@param name string
+function engine.enable_system(name) end
+
+---Disable the fog of war, keeping the revealed cells
+function engine.fog_disable() end
+
+---Enable the fog of war with `cell_size`-unit cells. Entities built with :with_vision() reveal the cells around them; tiles of the `solid_layers` tilemap layers block sources created with `blocked`. Revealed cells are kept per scene
+---@param cell_size number
+---@param solid_layers string[]|nil
+function engine.fog_enable(cell_size, solid_layers) end
+
+---Forget every fog-of-war cell revealed in the current scene
+function engine.fog_reset() end
+
+---Get whether automatic quality scaling is enabled
+---@return boolean
+function engine.get_auto_quality() end
+
 ---Get current background clear color
 ---@return table
 function engine.get_background_color() end
 
+---Get the frame cap used while the window is unfocused (0 = no throttling)
+---@return integer
+function engine.get_background_fps() end
+
+---Get the current colorblind palette remap mode
+---@return string
+function engine.get_colorblind_mode() end
+
 ---Get current fullscreen state
 ---@return boolean
 function engine.get_fullscreen() end
 
+---Get whether the high-contrast UI mode is enabled
+---@return boolean
+function engine.get_high_contrast_ui() end
+
+---Get the 0-based index of the monitor used for fullscreen
+---@return integer
+function engine.get_monitor() end
+
+---Get the connected monitors as an array of {index, name, width, height, refresh_rate} (index is 0-based, as in set_window_mode)
+---@return table
+function engine.get_monitors() end
+
 ---Get whether the camera/view rect is snapped to integer pixels
 ---@return boolean
 function engine.get_pixel_snap_camera() end
 
+---Get the render target supersampling factor
+---@return integer
+function engine.get_render_scale() end
+
 ---Get current internal render resolution
 ---@return table
 function engine.get_render_size() end
@@ -1415,12 +1941,39 @@ function engine.get_target_fps() end
 ---@return boolean
 function engine.get_vsync() end
 
+---Get the current window mode ("windowed", "borderless" or "fullscreen")
+---@return string
+function engine.get_window_mode() end
+
+---Check whether the world point (x, y) has been revealed by a vision source in the current scene. Always true while the fog of war is disabled. Uses the fog state from the start of the frame
+---@param x number
+---@param y number
+---@return boolean
+function engine.is_revealed(x, y) end
+
+---Get whether a non-essential system is enabled
+---@param name string
+---@return boolean
+function engine.is_system_enabled(name) end
+
 ---Load a shader (at least one of vs_path/fs_path required)
 ---@param id string
 ---@param vs_path string|nil
 ---@param fs_path string|nil
 function engine.load_shader(id, vs_path, fs_path) end
 
+---Register a function called as fn(focused) when the window gains or loses focus (pass nil to remove it)
+---@param callback function|nil
+function engine.on_focus_changed(callback) end
+
+---Register a function called as fn(level, old_level, frame_ms) when automatic quality scaling changes level; 0 is full quality (pass nil to remove it)
+---@param callback function|nil
+function engine.on_quality_changed(callback) end
+
+---Register a function called as fn(width, height, old_width, old_height) after the internal render resolution changes (pass nil to remove it)
+---@param callback function|nil
+function engine.on_resolution_changed(callback) end
+
 ---Clear a uniform on post-process shader
 ---@param name string
 function engine.post_process_clear_uniform(name) end
@@ -1456,12 +2009,29 @@ function engine.post_process_set_vec4(name, x, y, z, w) end
 ---@param shader_ids string[]|nil
 function engine.post_process_shader(shader_ids) end
 
----Set background clear color (RGB 0-255)
+---Step particle density and post-processing down while frames are over budget, and back up when they recover; turning it off restores full quality
+---@param enabled boolean
+function engine.set_auto_quality(enabled) end
+
+---Set background clear color (RGB 0-255)
 ---@param r integer
 ---@param g integer
 ---@param b integer
 function engine.set_background_color(r, g, b) end
 
+---Set the frame cap used while the window is unfocused (0 disables throttling)
+---@param fps integer
+function engine.set_background_fps(fps) end
+
+---Grade the final image with a 256x16 LUT texture (16x16x16 color cube, load it with "bilinear"), applied after the post-process chain. nil removes grading. `fade` crossfades over that many seconds (default 0). Survives scene switches
+---@param tex_key string|nil
+---@param fade number|nil
+function engine.set_color_lut(tex_key, fade) end
+
+---Set the colorblind palette remap run after the post-process chain ("none", "protanopia", "deuteranopia", "tritanopia", "grayscale")
+---@param mode string
+function engine.set_colorblind_mode(mode) end
+
 ---Set fullscreen mode
 ---@param enabled boolean
 function engine.set_fullscreen(enabled) end
@@ -1561,10 +2131,32 @@ function engine.set_gui_theme_progress_bar(theme_key, part, tex_key, source_x, s
 ---@param a integer
 function engine.set_gui_theme_text_shadow(theme_key, dx, dy, r, g, b, a) end
 
+---Draw screen-space text on opaque plates and menus in a fixed high-contrast color pair
+---@param enabled boolean
+function engine.set_high_contrast_ui(enabled) end
+
+---Switch the internal render resolution at runtime, recreating the render target and firing on_resolution_changed (min 120x120, max 7680x4320)
+---@param width integer
+---@param height integer
+function engine.set_internal_resolution(width, height) end
+
+---Show or hide a render layer: "background", "gameplay", "fx", "ui", "debug" or a layer number 0-255. Hidden entities keep updating. Survives scene switches
+---@param layer string|integer
+---@param visible boolean
+function engine.set_layer_visible(layer, visible) end
+
 ---Snap the camera/view rect to integer pixels before rendering (reduces sprite atlas bleeding; disable for smooth rotation/zoom)
 ---@param enabled boolean
 function engine.set_pixel_snap_camera(enabled) end
 
+---Set how the current scene is redrawn: "full" (every frame, the default) or "dirty_rects" (only regions whose entities changed). Resets to "full" on scene switch
+---@param mode string
+function engine.set_render_mode(mode) end
+
+---Set the render target supersampling factor (1-4): the scene is drawn at that multiple of the render size and downscaled to the window; logical coordinates do not change
+---@param scale integer
+function engine.set_render_scale(scale) end
+
 ---Set internal render resolution (min 120x120, max 7680x4320)
 ---@param width integer
 ---@param height integer
@@ -1574,7 +2166,7 @@ function engine.set_render_size(width, height) end
 ---@param filter string
 function engine.set_render_target_filter(filter) end
 
----Set target FPS (nil resets to 60)
+---Set target FPS (0 = uncapped, nil resets to 60)
 ---@param fps integer|nil
 function engine.set_target_fps(fps) end
 
@@ -1582,6 +2174,221 @@ function engine.set_target_fps(fps) end
 ---@param enabled boolean
 function engine.set_vsync(enabled) end
 
+---Set the window mode ("windowed", "borderless", "fullscreen") and optionally the 0-based monitor index used for fullscreen
+---@param mode string
+---@param monitor integer|nil
+function engine.set_window_mode(mode, monitor) end
+
+-- ==================== Secondary Worlds ====================
+
+---Create (or replace) a secondary world rendered into a width x height texture. Main-world sprites whose tex_key is the world's name draw that texture.
+---@param name string
+---@param width integer
+---@param height integer
+function engine.world_create(name, width, height) end
+
+---Remove a secondary world and all its entities
+---@param name string
+function engine.world_destroy(name) end
+
+---Set the clear color of a secondary world's texture
+---@param name string
+---@param r integer
+---@param g integer
+---@param b integer
+function engine.world_set_background(name, r, g, b) end
+
+---Center a secondary world's camera on (x, y) with an optional zoom (default 1)
+---@param name string
+---@param x number
+---@param y number
+---@param zoom number|nil
+function engine.world_set_camera(name, x, y, zoom) end
+
+---Pause or resume a secondary world's simulation (it keeps rendering)
+---@param name string
+---@param paused boolean
+function engine.world_set_paused(name, paused) end
+
+---Create an entity builder that spawns into a secondary world. Only position, sprite, rotation, scale, tint, z-index, velocity, tweens and TTL have an effect there; callbacks and collisions do not run.
+---@param name string
+---@return EntityBuilder
+function engine.world_spawn(name) end
+
+-- ==================== World Clock ====================
+
+---Call the global function `callback(day, time)` every in-game day when the world clock reaches `time` ("HH:MM")
+---@param time string
+---@param callback string
+function engine.at_time(time, callback) end
+
+---Remove every engine.at_time() entry calling `callback`
+---@param callback string
+function engine.cancel_at_time(callback) end
+
+---Stop drawing the day/night overlay
+function engine.clock_disable_ambient() end
+
+---Stop the world clock and its scheduled events
+function engine.clock_pause() end
+
+---Restart the world clock after clock_pause()
+function engine.clock_resume() end
+
+---Darken the world at night with an (r, g, b, a) overlay, faded in and out over an hour centred on `sunset` and `sunrise` ("HH:MM")
+---@param sunrise string
+---@param sunset string
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer
+function engine.clock_set_ambient(sunrise, sunset, r, g, b, a) end
+
+---Set how many in-game minutes pass per second of world time (default 1)
+---@param minutes_per_second number
+function engine.clock_set_speed(minutes_per_second) end
+
+---Jump the world clock to `time` ("HH:MM") without firing the events in between
+---@param time string
+function engine.clock_set_time(time) end
+
+---Get the world clock state at the start of the frame: {time = "HH:MM", hour, minute, day, speed, paused, daylight} (daylight goes from 0 at night to 1 at day)
+---@return table
+function engine.get_clock() end
+
+-- ==================== Weather ====================
+
+---Get the weather at the start of the frame: {kind, intensity, target_intensity, wind_x, wind_y} (intensity is the current, possibly fading, value)
+---@return table
+function engine.get_weather() end
+
+---Fade `kind` ("clear", "rain" or "snow") to `intensity` (0 to 1, default 1) and the other kinds out over `transition` seconds (default 0)
+---@param kind string
+---@param intensity number|nil
+---@param transition number|nil
+function engine.set_weather(kind, intensity, transition) end
+
+---Fade the wind to an (x, y) acceleration in units/s² over `transition` seconds. It leans the rain and snow and pushes the rigid bodies matching `mask` (default "*")
+---@param x number
+---@param y number
+---@param transition number|nil
+---@param mask string|nil
+function engine.set_wind(x, y, transition, mask) end
+
+-- ==================== Inventory ====================
+
+---Get a registered item as {id, name, icon, max_stack, properties} (nil if unknown; items registered this frame show up next frame)
+---@param id string
+---@return table|nil
+function engine.get_item(id) end
+
+---Add `count` (default 1) of a registered item to an entity's inventory, topping up its stacks first. What does not fit is reported to on_inventory_full
+---@param entity_id integer
+---@param item string
+---@param count integer|nil
+function engine.inventory_add(entity_id, item, count) end
+
+---Empty every slot of an entity's inventory
+---@param entity_id integer
+function engine.inventory_clear(entity_id) end
+
+---Count an item across an entity's inventory at the start of the frame (0 without inventory)
+---@param entity_id integer
+---@param item string
+---@return integer
+function engine.inventory_count(entity_id, item) end
+
+---Remove up to `count` (default 1) of an item from an entity's inventory, emptying the last stacks first
+---@param entity_id integer
+---@param item string
+---@param count integer|nil
+function engine.inventory_remove(entity_id, item, count) end
+
+---Get an entity's inventory at the start of the frame as an array of {item, count} tables, with false for empty slots (nil without inventory)
+---@param entity_id integer
+---@return table|nil
+function engine.inventory_slots(entity_id) end
+
+---Swap the contents of two slots (1-based) of an entity's inventory
+---@param entity_id integer
+---@param a integer
+---@param b integer
+function engine.inventory_swap(entity_id, a, b) end
+
+---Register a function called as fn(entity_id, item, delta, count) whenever items are added to (delta > 0) or removed from (delta < 0) an inventory (pass nil to remove it)
+---@param callback function|nil
+function engine.on_inventory_changed(callback) end
+
+---Register a function called as fn(entity_id, item, leftover) when items did not fit in an inventory (pass nil to remove it)
+---@param callback function|nil
+function engine.on_inventory_full(callback) end
+
+---Define (or redefine) an item: {name?, icon? (texture key), icon_rect? ({x, y, w, h} in the texture), max_stack? (default 1), properties? (booleans, numbers, strings or {x, y} tables)}
+---@param id string
+---@param def table|nil
+function engine.register_item(id, def) end
+
+-- ==================== UI Screens ====================
+
+---Slide cutscene letterbox bars in (true) or out (false) over duration seconds (default 0.4). While on, gameplay input actions read as released; back (for skipping) and the debug/fullscreen toggles still work
+---@param enabled boolean
+---@param duration number|nil
+function engine.cinematic_mode(enabled, duration) end
+
+---Get the best n high scores (all by default) as a list of {name, score}, highest first
+---@param n integer|nil
+---@return table
+function engine.get_high_scores(n) end
+
+---After idle_seconds (default 30) without input in the current scene, switch to the scene of the replay file and play its recorded input; any input or the end of the replay returns here. nil replay_path turns attract mode off
+---@param replay_path string|nil
+---@param idle_seconds number|nil
+function engine.set_attract_mode(replay_path, idle_seconds) end
+
+---Scroll credits up the screen: {lines = {"text" | {text, style? ("title", "heading", "normal"), color? ({r, g, b, a?})}, ...} | file = path ("# " title, "## " heading lines), on_done?, speed? (pixels/s, default 40), fast_forward? (multiplier while action_1/action_2 is held, default 4), x?, font?, font_size?, spacing?}. on_done is called when the last line is gone
+---@param spec table
+function engine.show_credits(spec) end
+
+---Open a results screen: {rows = {{label, value? | signal?, decimals?}, ...}, on_done?, x?, y?, value_offset?, spacing?, font?, font_size?, duration? (seconds per row, default 1)}. Values count up row by row; confirm skips the count, then closes the screen and calls on_done
+---@param spec table
+function engine.show_results(spec) end
+
+---Submit a score to the high score table. If it qualifies, a three-letter name entry opens (up/down picks a letter, left/right or back moves, confirm moves on); on_done is then called with (name, rank), or (nil, nil) if the score didn't make the table
+---@param score integer
+---@param on_done string|nil
+function engine.submit_score(score, on_done) end
+
+-- ==================== Save Games ====================
+
+---Restore a slot written by save_game: world signals are replaced (the scene and group counts are kept) and saved entities still registered under their name get their position and signals back. Applied at the end of the frame
+---@param slot string
+function engine.load_game(slot) end
+
+---Write the world signals, plus the position and signals of every persistent entity registered by name, to saves/save_<slot>.json. Slot names use letters, digits, '_' and '-'
+---@param slot string
+function engine.save_game(slot) end
+
+---Forget what was saved for the with_restore_on_return entities of every scene, including the current visit (e.g. on "new game")
+function engine.scene_state_clear() end
+
+---Forget what was saved for the with_restore_on_return entities of a scene, so its next visit spawns them fresh. Forgetting the current scene also drops this visit (e.g. before restarting the level)
+---@param scene string
+function engine.scene_state_forget(scene) end
+
+-- ==================== Debugging ====================
+
+---Get last frame's ECS statistics as {entities, archetypes, spawned, despawned, component_bytes, components = {{name, entities, bytes}, ...}} (components largest first)
+---@return table
+function engine.ecs_stats() end
+
+---Record every entity (group and components) and resource under a name, replacing an older snapshot of that name. Taken when this frame's commands are applied
+---@param name string
+function engine.snapshot(name) end
+
+---Log what changed since the named snapshot: entities spawned and despawned, components added or removed, resources inserted or removed
+---@param name string
+function engine.snapshot_diff(name) end
+
 -- ==================== input ====================
 
 ---Add an extra key binding for an action (supports multi-bind)
@@ -1589,16 +2396,57 @@ function engine.set_vsync(enabled) end
 ---@param key string
 function engine.add_binding(action, key) end
 
+---Drive local player 1-4 with a device: "keyboard", "keyboard_left", "keyboard_right" or "gamepad_1" to "gamepad_4"; nil leaves the player without one. Entities with that player's index read its input
+---@param player integer
+---@param device string|nil
+function engine.assign_device(player, device) end
+
+---Bind a logical action to a key, replacing its bindings (same as rebind_action). Keys may also use raylib names such as "KEY_SPACE"
+---@param action string
+---@param key string
+function engine.bind_action(action, key) end
+
+---Define (or redefine) an input context as the list of action names live while it is active; the debug and fullscreen toggles are always live
+---@param name string
+---@param actions string[]
+function engine.define_input_context(name, actions) end
+
+---Define (or redefine) a named input sequence: a list of steps entered within window seconds (default 2), each an action name or a chord of names joined with '+' ("main_down+main_right"). "konami" is predefined
+---@param name string
+---@param steps string[]
+---@param window number|nil
+function engine.define_sequence(name, steps, window) end
+
+---Write the input stats recorded so far to a JSON file
+---@param path string
+function engine.dump_input_stats(path) end
+
 ---Get the first key binding for an action as a string (nil if unbound)
 ---@param action string
 ---@return string|nil
 function engine.get_binding(action) end
 
+---Call fn(name) whenever the named input sequence is entered; nil removes it
+---@param name string
+---@param callback function|nil
+function engine.on_sequence(name, callback) end
+
+---Return to the input context below the active one
+function engine.pop_input_context() end
+
+---Make an input context ("gameplay", "menu", "dialogue" or one from define_input_context) the active one; actions it doesn't list read as released until it is popped. The stack is cleared on scene change
+---@param name string
+function engine.push_input_context(name) end
+
 ---Rebind a logical action to a new key (replaces existing binding)
 ---@param action string
 ---@param key string
 function engine.rebind_action(action, key) end
 
+---Start (true) or stop (false) recording per-scene action press counts and hold times for playtest analysis
+---@param enabled boolean
+function engine.record_input_stats(enabled) end
+
 -- ==================== Entity Builder ====================
 
 ---@class EntityBuilder
@@ -1629,6 +2477,27 @@ function EntityBuilder:with_animation_controller(fallback_key) end
 ---@return EntityBuilder
 function EntityBuilder:with_animation_rule(condition_table, set_key) end
 
+---Pull rigid bodies labeled `target` (group or tag) within `radius` toward this entity with an acceleration of `strength` units/s² (negative repels)
+---@param radius number
+---@param strength number
+---@param target string
+---@return EntityBuilder
+function EntityBuilder:with_attractor(radius, strength, target) end
+
+---Play sound effect `sound_id` on an entity event: "spawn", "despawn" (not on scene changes), "collision" or "collision:<group>" (when contact starts). The trigger can't play again for `cooldown` seconds (default 0). Can be called multiple times
+---@param event string
+---@param sound_id string
+---@param cooldown number|nil
+---@return EntityBuilder
+function EntityBuilder:with_audio_trigger(event, sound_id, cooldown) end
+
+---Draw a translucent ellipse on the ground under this entity's sprite, shrinking and fading to 40% as the entity rises. opts: {opacity? (0-1, default 0.5), offset_x?, offset_y? (ellipse centre relative to the position), height_signal? (key of this entity's signals holding its height above the ground), ground_y? (world Y the ellipse stays on; the height is the distance down to it), max_height? (height of the smallest ellipse, default 64)}
+---@param width number
+---@param height number
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_blob_shadow(width, height, opts) end
+
 ---Mark entity as camera follow target (higher priority wins). zoom is the desired camera zoom when this target wins (default 1.0).
 ---@param priority integer|nil
 ---@param zoom number|nil
@@ -1649,6 +2518,43 @@ function EntityBuilder:with_collider(width, height, origin_x, origin_y) end
 ---@return EntityBuilder
 function EntityBuilder:with_collider_offset(offset_x, offset_y) end
 
+---Make the entity a conveyor carrying overlapping rigid bodies whose group or tags match `mask` (default "*") at a constant velocity without changing their own. The area is the entity's collider unless :with_force_area_rect() is used.
+---@param vel_x number
+---@param vel_y number
+---@param mask string|nil
+---@return EntityBuilder
+function EntityBuilder:with_conveyor(vel_x, vel_y, mask) end
+
+---Add a cooldown of `duration` seconds whose remaining fraction (1 just started, 0 ready) is written every frame to the entity's scalar signal `signal`, ready to bind to a bar. Start it with engine.entity_start_cooldown(). opts: {auto_reset? (start over as soon as it elapses, default false), started? (begin cooling down instead of ready, default false), world? (also write the fraction to the world signal `signal`, which :with_gui_progress_bar_signal_binding() reads, default false)}
+---@param duration number
+---@param signal string
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_cooldown(duration, signal, opts) end
+
+---Keep this entity between `min_length` (default `length`, a rigid rod; 0 for a leash) and `length` from entity `target_id`. Rigid bodies are moved by the joint and keep swinging or orbiting; anything else is a fixed anchor. `stiffness` (0-1, default 1) below 1 makes it springy
+---@param target_id integer
+---@param length number
+---@param min_length number|nil
+---@param stiffness number|nil
+---@return EntityBuilder
+function EntityBuilder:with_distance_joint(target_id, length, min_length, stiffness) end
+
+---Make the entity a force area accelerating overlapping rigid bodies whose group or tags match `mask` (default "*"). The area is the entity's collider unless :with_force_area_rect() is used.
+---@param accel_x number
+---@param accel_y number
+---@param mask string|nil
+---@return EntityBuilder
+function EntityBuilder:with_force_area(accel_x, accel_y, mask) end
+
+---Set the force area's rectangle relative to the entity position instead of using its collider
+---@param x number
+---@param y number
+---@param width number
+---@param height number
+---@return EntityBuilder
+function EntityBuilder:with_force_area_rect(x, y, width, height) end
+
 ---Set friction (creates RigidBody if needed)
 ---@param friction number
 ---@return EntityBuilder
@@ -1658,6 +2564,15 @@ function EntityBuilder:with_friction(friction) end
 ---@return EntityBuilder
 function EntityBuilder:with_frozen() end
 
+---Stagger the grid cells' appearance: preset "scale_in" or "drop_in", `delay` seconds per step of `order` ("cells", "rows", "columns", "diagonal"), `height` is the drop_in fall (default 64)
+---@param preset string
+---@param delay number
+---@param duration number
+---@param order string|nil
+---@param height number|nil
+---@return EntityBuilder
+function EntityBuilder:with_grid_intro(preset, delay, duration, order, height) end
+
 ---Spawn entities from a JSON grid layout
 ---@param path string
 ---@param group string
@@ -1710,6 +2625,14 @@ function EntityBuilder:with_gui_image_hover_offset(offset_x, offset_y) end
 ---@return EntityBuilder
 function EntityBuilder:with_gui_image_pressed_offset(offset_x, offset_y) end
 
+---Set GuiInventoryGrid component: draws the Inventory of `inventory_id` as `columns` slots per row of `cell_size` pixels, `spacing` apart (default 2), with item icons and stack counts. Slots use the theme's label nine-patch (panel if unset); see :with_gui_theme_key(). Requires :with_screen_position() (or :with_parent()+:with_gui_offset()) and :with_zindex() to render.
+---@param inventory_id integer
+---@param columns integer
+---@param cell_size number
+---@param spacing number|nil
+---@return EntityBuilder
+function EntityBuilder:with_gui_inventory_grid(inventory_id, columns, cell_size, spacing) end
+
 ---Set GuiLabel component; gui_label_spawn_system spawns a caption DynamicText child on Added<GuiLabel>, themed via the named theme looked up in GuiThemeStore (see engine.set_gui_theme_font / :with_gui_theme_key). An empty `text` skips spawning the caption entirely (captionless label). Requires :with_screen_position() (or :with_parent()+:with_gui_offset()) and :with_zindex() to render.
 ---@param width number
 ---@param height number
@@ -1754,7 +2677,7 @@ function EntityBuilder:with_gui_progress_bar_signal_binding(key) end
 ---@return EntityBuilder
 function EntityBuilder:with_gui_progress_bar_vertical() end
 
----Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar (default "default"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar() first.
+---Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar/GuiInventoryGrid (default "default"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar()/:with_gui_inventory_grid() first.
 ---@param key string
 ---@return EntityBuilder
 function EntityBuilder:with_gui_theme_key(key) end
@@ -1765,13 +2688,92 @@ function EntityBuilder:with_gui_theme_key(key) end
 ---@return EntityBuilder
 function EntityBuilder:with_gui_window(width, height) end
 
----Add collision callback between two groups
----@param group_a string
----@param group_b string
+---Give the entity a faux-3D height: the sprite is drawn height pixels above its position, which stays on the ground for collisions and sorting, and falls back under its own gravity. Landing triggers :with_on_land() and bounces. opts: {gravity? (default 980, 0 hovers), bounce? (share of the landing speed kept, 0-1, default 0), velocity? (initial upward speed)}
+---@param height number
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_height(height, opts) end
+
+---Give the entity a damage-dealing box (offset from its position) used whenever the current animation has no hitbox frame data
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return EntityBuilder
+function EntityBuilder:with_hitbox(x, y, w, h) end
+
+---Set the hitbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.
+---@param animation_key string
+---@param frame integer
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return EntityBuilder
+function EntityBuilder:with_hitbox_frame(animation_key, frame, x, y, w, h) end
+
+---Import hitbox frames from the slice named `slice` (default "hitbox") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.
+---@param path string
+---@param slice string|nil
+---@return EntityBuilder
+function EntityBuilder:with_hitbox_aseprite(path, slice) end
+
+---Only hit hurtbox owners whose group or tags match `mask` (default "*")
+---@param mask string
+---@return EntityBuilder
+function EntityBuilder:with_hitbox_mask(mask) end
+
+---Give the entity a damage-receiving box (offset from its position) used whenever the current animation has no hurtbox frame data
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return EntityBuilder
+function EntityBuilder:with_hurtbox(x, y, w, h) end
+
+---Set the hurtbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.
+---@param animation_key string
+---@param frame integer
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return EntityBuilder
+function EntityBuilder:with_hurtbox_frame(animation_key, frame, x, y, w, h) end
+
+---Import hurtbox frames from the slice named `slice` (default "hurtbox") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.
+---@param path string
+---@param slice string|nil
+---@return EntityBuilder
+function EntityBuilder:with_hurtbox_aseprite(path, slice) end
+
+---Show a prompt above this entity while an interactor (group or tags matching `mask`, default "player") is in range, and call `callback` as fn(ctx, input, interactor_id) when it presses `action` (default "action_1"). Table fields: prompt, callback?, radius? (default: overlap with this entity's collider), mask?, action?, icon? (texture key), offset? ({x, y}, default {0, -16}), theme? (GuiThemeStore key).
+---@param table table
+---@return EntityBuilder
+function EntityBuilder:with_interactable(table) end
+
+---Give the entity an Inventory with `slots` empty slots. Fill it with engine.inventory_add().
+---@param slots integer
+---@return EntityBuilder
+function EntityBuilder:with_inventory(slots) end
+
+---Add collision callback between two groups. Each side is a group/tag name, a pattern ("*" matches anything, "enemy_*" matches by prefix, "a|b" lists alternatives) or a table of names
+---@param group_a string|string[]
+---@param group_b string|string[]
 ---@param callback string
 ---@return EntityBuilder
 function EntityBuilder:with_lua_collision_rule(group_a, group_b, callback) end
 
+---Set whether the collision rule stops lower-priority matching rules from running (default true)
+---@param exclusive boolean
+---@return EntityBuilder
+function EntityBuilder:with_lua_collision_rule_exclusive(exclusive) end
+
+---Set the dispatch priority of the collision rule; when several rules match one collision, higher priorities run first (default 0)
+---@param priority integer
+---@return EntityBuilder
+function EntityBuilder:with_lua_collision_rule_priority(priority) end
+
 ---Attach a one-shot Lua setup callback. The named function is called once (Added<LuaSetup>) with the entity context. Fires the frame after spawn; child entities added inside the callback appear the following frame.
 ---@param callback string
 ---@return EntityBuilder
@@ -1783,6 +2785,11 @@ function EntityBuilder:with_lua_setup(callback) end
 ---@return EntityBuilder
 function EntityBuilder:with_lua_timer(duration, callback) end
 
+---Set mass dividing forces, force areas, wind and impulses (creates RigidBody if needed)
+---@param mass number
+---@return EntityBuilder
+function EntityBuilder:with_mass(mass) end
+
 ---Set max speed clamp (creates RigidBody if needed)
 ---@param speed number
 ---@return EntityBuilder
@@ -1859,11 +2866,45 @@ function EntityBuilder:with_menu_visible_count(count) end
 ---@return EntityBuilder
 function EntityBuilder:with_mouse_controlled(follow_x, follow_y) end
 
+---Stretch texture `tex_key` over a width x height panel keeping `border` pixels unscaled at the corners (NPatch), in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). :with_tint() tints it. opts: {left?, top?, right?, bottom? (per-side borders, default `border`), source? ({x, y, w, h} region of the texture, default the whole texture), offset_x?, offset_y? (top-left corner relative to the position)}
+---@param tex_key string
+---@param width number
+---@param height number
+---@param border integer
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_nine_slice(tex_key, width, height, border, opts) end
+
+---While this entity is outside the camera view, draw an arrow on the screen edge pointing toward it. opts: {color? ({r, g, b, a?}, default yellow), size? (arrow length in pixels, default 12), margin? (gap to the screen edge, default 8), icon? (texture key drawn inside the arrow), distance? (world units per displayed unit; shows the distance from the camera centre), theme? (GuiThemeStore key for the distance font)}
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_offscreen_indicator(opts) end
+
 ---Attach a callback fired exactly once when the entity's non-looped animation first reaches its last frame. Signature: fn(ctx, input). Looped animations never trigger it.
 ---@param fn_name string
 ---@return EntityBuilder
 function EntityBuilder:with_on_animation_end(fn_name) end
 
+---Attach a callback fired once per swing for each entity this entity's hitbox strikes. Signature: fn(ctx, input, target_id).
+---@param fn_name string
+---@return EntityBuilder
+function EntityBuilder:with_on_hit(fn_name) end
+
+---Attach a callback fired each time the entity's :with_height() comes down to the ground, bounces included. Signature: fn(ctx, input, impact_speed).
+---@param fn_name string
+---@return EntityBuilder
+function EntityBuilder:with_on_land(fn_name) end
+
+---Despawn this entity when entity `owner_id` is despawned (health bars, shadows, attached effects). Ownership doesn't move the entity; combine with :with_stuckto() or :with_parent() for that
+---@param owner_id integer
+---@return EntityBuilder
+function EntityBuilder:with_owner(owner_id) end
+
+---Recolor the sprite through an N×2 palette texture: each color of the top row becomes the color below it. World-space sprites only; an entity shader takes precedence
+---@param palette_tex_key string
+---@return EntityBuilder
+function EntityBuilder:with_palette_swap(palette_tex_key) end
+
 ---Set parent entity for transform hierarchy
 ---@param parent_id integer
 ---@return EntityBuilder
@@ -1874,6 +2915,13 @@ function EntityBuilder:with_parent(parent_id) end
 ---@return EntityBuilder
 function EntityBuilder:with_particle_emitter(table) end
 
+---Move the entity along waypoints at `speed` units per second. `points` is a list of {x, y} tables (at least 2). opts: {spline? (Catmull-Rom curve through the points, default false), closed? (join the last point to the first), loop? ("once" (default), "loop", "ping_pong"), easing? (tween easing over the whole path), orient? (rotate to face the direction of travel), rotation_offset? (degrees added when orienting)}
+---@param points table
+---@param speed number
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_path_follow(points, speed, opts) end
+
 ---Survive scene transitions
 ---@return EntityBuilder
 function EntityBuilder:with_persistent() end
@@ -1898,13 +2946,110 @@ function EntityBuilder:with_persistent() end
 ---```
 ---@param table PhaseDefinition
 ---@return EntityBuilder
-function EntityBuilder:with_phase(table) end
+function EntityBuilder:with_phase(table) end
+
+---Keep this entity at (offset_x, offset_y) from entity `target_id`. Unlike :with_stuckto(), both rigid bodies are moved, so pushing either drags the other. `stiffness` (0-1, default 1) below 1 makes it springy
+---@param target_id integer
+---@param offset_x number
+---@param offset_y number
+---@param stiffness number|nil
+---@return EntityBuilder
+function EntityBuilder:with_pin_joint(target_id, offset_x, offset_y, stiffness) end
+
+---Only report collisions where the sprite's opaque pixels overlap; the alpha mask comes from the sprite's texture as loaded by engine.load_texture
+---@return EntityBuilder
+function EntityBuilder:with_pixel_collision() end
+
+---Make local player 1-4 the owner: the entity's input control and menu follow the device given to that player with engine.assign_device()
+---@param player integer
+---@return EntityBuilder
+function EntityBuilder:with_player(player) end
+
+---Make this entity a door or teleporter: confirming its interaction fades the screen out, moves the interactor to {x, y} or the named `spawn_point`, or switches to `scene` and places every entity matching the interaction mask on that scene's `spawn_point`, then fades back in. Adds an interaction prompt (`prompt`, default "Enter") unless :with_interactable() sets one. Table fields: x?, y?, spawn_point?, scene?, fade? (seconds per half, default 0.3), prompt?.
+---@param table table
+---@return EntityBuilder
+function EntityBuilder:with_portal(table) end
+
+---Set world position
+---@param x number
+---@param y number
+---@return EntityBuilder
+function EntityBuilder:with_position(x, y) end
+
+---Draw a plain-colored bar filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). opts: {value? (default 0), max? (default 1), color? ({r, g, b, a?} fill, default lime), background_color? ({r, g, b, a?} or false, default dark gray), background? (texture key stretched under the fill), offset_x?, offset_y? (top-left corner relative to the position), direction? ("horizontal", "horizontal_reversed", "vertical" or "vertical_reversed"), signal? (key copied into value every frame, integer or scalar), entity? (entity id whose signals hold `signal`; WorldSignals when omitted)}
+---@param width number
+---@param height number
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_progress_bar(width, height, opts) end
+
+---Draw a ring (or a pie, without inner_radius) filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). The position is the centre. opts: the options of :with_progress_bar() except direction, plus {inner_radius? (default 0), start_angle? (degrees clockwise from the right, default -90 = top), counter_clockwise? (boolean)}
+---@param radius number
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_radial_progress(radius, opts) end
+
+---Set render layer ("background", "gameplay", "fx", "ui", "debug" or 0-255) toggled by engine.set_layer_visible(); draw order still follows :with_zindex()
+---@param layer string|integer
+---@return EntityBuilder
+function EntityBuilder:with_render_layer(layer) end
+
+---Remember this entity when its scene is left and restore it on re-entry: despawned entities stay despawned, and position and signals are restored unless disabled. `key` must be unique within the scene and the same every time it is spawned.
+---@param key string
+---@param restore_position boolean|nil
+---@param restore_signals boolean|nil
+---@return EntityBuilder
+function EntityBuilder:with_restore_on_return(key, restore_position, restore_signals) end
+
+---Make the entity a rope of total `length` split into `segments` segments (default one per 8 units), simulated as a chain of points under gravity. Attach its ends with :with_rope_start()/:with_rope_start_entity() and :with_rope_end()/:with_rope_end_entity(); unattached ends hang loose
+---@param length number
+---@param segments integer|nil
+---@return EntityBuilder
+function EntityBuilder:with_rope(length, segments) end
+
+---Pin the rope's end to world point (x, y)
+---@param x number
+---@param y number
+---@return EntityBuilder
+function EntityBuilder:with_rope_end(x, y) end
+
+---Attach the rope's end to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along
+---@param entity_id integer
+---@param offset_x number|nil
+---@param offset_y number|nil
+---@return EntityBuilder
+function EntityBuilder:with_rope_end_entity(entity_id, offset_x, offset_y) end
+
+---Tune the rope: fraction of a stretch corrected per pass (0-1, default 1), fraction of velocity lost per second (default 0.5), correction passes per frame (default 8) and gravity (default 0, 980)
+---@param stiffness number
+---@param damping number
+---@param iterations integer|nil
+---@param gravity_x number|nil
+---@param gravity_y number|nil
+---@return EntityBuilder
+function EntityBuilder:with_rope_physics(stiffness, damping, iterations, gravity_x, gravity_y) end
 
----Set world position
+---Pin the rope's start to world point (x, y)
 ---@param x number
 ---@param y number
 ---@return EntityBuilder
-function EntityBuilder:with_position(x, y) end
+function EntityBuilder:with_rope_start(x, y) end
+
+---Attach the rope's start to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along
+---@param entity_id integer
+---@param offset_x number|nil
+---@param offset_y number|nil
+---@return EntityBuilder
+function EntityBuilder:with_rope_start_entity(entity_id, offset_x, offset_y) end
+
+---Draw the rope `thickness` wide in a color (RGBA 0-255)
+---@param thickness number
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return EntityBuilder
+function EntityBuilder:with_rope_style(thickness, r, g, b, a) end
 
 ---Set rotation in degrees
 ---@param degrees number
@@ -1923,6 +3068,17 @@ function EntityBuilder:with_scale(sx, sy) end
 ---@return EntityBuilder
 function EntityBuilder:with_screen_position(x, y) end
 
+---Add a sensor ray that sets signal flag `flag` while it hits a collider whose group or tags match `mask` (default "*"). Can be called several times; adds Signals.
+---@param flag string
+---@param offset_x number
+---@param offset_y number
+---@param dir_x number
+---@param dir_y number
+---@param length number
+---@param mask string|nil
+---@return EntityBuilder
+function EntityBuilder:with_sensor(flag, offset_x, offset_y, dir_x, dir_y, length, mask) end
+
 ---Set per-entity shader with optional uniforms
 ---@param shader_key string
 ---@param uniforms table|nil
@@ -1976,6 +3132,17 @@ function EntityBuilder:with_signal_string(key, value) end
 ---@return EntityBuilder
 function EntityBuilder:with_signals() end
 
+---Make the collider a right-triangle ramp with its right angle in `corner` ("bottom_left", "bottom_right", "top_left" or "top_right"); boxes touching it are pushed straight out onto the slanted side
+---@param corner string
+---@return EntityBuilder
+function EntityBuilder:with_slope(corner) end
+
+---Name this entity's position as a spawn point that portals can lead to and engine.get_spawn_point() returns, with optional `properties` (string keys; integer, number, string or boolean values).
+---@param name string
+---@param properties table?
+---@return EntityBuilder
+function EntityBuilder:with_spawn_point(name, properties) end
+
 ---Set sprite
 ---@param tex_key string
 ---@param width number
@@ -2016,6 +3183,16 @@ function EntityBuilder:with_stuckto_offset(offset_x, offset_y) end
 ---@return EntityBuilder
 function EntityBuilder:with_stuckto_stored_velocity(vx, vy) end
 
+---Add extra tag labels matched by collision rules and group tracking
+---@param tags string[]
+---@return EntityBuilder
+function EntityBuilder:with_tags(tags) end
+
+---Make this entity destructible terrain: a copy of texture tex_key drawn with its top-left at the entity's position, with a collider and pixel collision. Carve it with engine.terrain_carve_circle()
+---@param tex_key string
+---@return EntityBuilder
+function EntityBuilder:with_terrain(tex_key) end
+
 ---Set DynamicText component
 ---@param content string
 ---@param font string
@@ -2027,10 +3204,22 @@ function EntityBuilder:with_stuckto_stored_velocity(vx, vy) end
 ---@return EntityBuilder
 function EntityBuilder:with_text(content, font, font_size, r, g, b, a) end
 
----Spawn a tilemap root. All tile entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap.
+---Scroll `content` glyph by glyph along a path, starting at the far end of the track and starting over when the text has left it. `path` is "sine" (default), "wave" or "circle" (around this entity). opts: {width? (track length, default 640), amplitude? (wave height or circle radius, default font_size), wavelength? (default 256), spacing? (between glyphs, default font_size), color? ({r, g, b, a?}, default white)}
+---@param content string
+---@param font string
+---@param font_size number
+---@param speed number
+---@param path string|nil
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_text_scroller(content, font, font_size, speed, path, opts) end
+
+---Spawn a tilemap root from a Tilesetter directory or a Tiled `.tmj` map. All tile and object entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap. `projection` ("orthogonal", "isometric" or "hex") overrides the one in the tilemap file. The tiles of the layers named in `solid_layers` get colliders (one box per horizontal run of tiles, in a group named after the layer), kept up to date by tile edits; orthogonal maps only.
 ---@param path string
+---@param projection string|nil
+---@param solid_layers string[]|nil
 ---@return EntityBuilder
-function EntityBuilder:with_tilemap(path) end
+function EntityBuilder:with_tilemap(path, projection, solid_layers) end
 
 ---Set color tint (RGBA 0-255)
 ---@param r integer
@@ -2161,6 +3350,67 @@ function EntityBuilder:with_tween_screen_position_on_finished(callback) end
 ---@return EntityBuilder
 function EntityBuilder:with_velocity(vx, vy) end
 
+---Shadow-cast a visibility polygon of `radius` from this entity against the occluders (see engine.set_occluders). A `fov` below 360 degrees turns it into a cone facing the entity's rotation.
+---@param radius number
+---@param fov number|nil
+---@return EntityBuilder
+function EntityBuilder:with_visibility(radius, fov) end
+
+---Draw the visibility polygon filled with a color (RGBA 0-255), as a light or vision cone
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return EntityBuilder
+function EntityBuilder:with_visibility_fill(r, g, b, a) end
+
+---Reveal fog-of-war cells within `radius` of this entity (see engine.fog_enable). With `blocked`, tiles of the fog's solid layers block the line of sight.
+---@param radius number
+---@param blocked boolean|nil
+---@return EntityBuilder
+function EntityBuilder:with_vision(radius, blocked) end
+
+---Set the upward acceleration of fully submerged bodies, the fraction of their velocity removed per second and optionally how hard entering bodies splash
+---@param buoyancy number
+---@param drag number
+---@param splash number|nil
+---@return EntityBuilder
+function EntityBuilder:with_water_buoyancy(buoyancy, drag, splash) end
+
+---Set the water's fill color (RGBA 0-255)
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return EntityBuilder
+function EntityBuilder:with_water_color(r, g, b, a) end
+
+---Tune the water surface: pull back to rest, pull between neighbouring columns, damping and optional column spacing (default 8)
+---@param stiffness number
+---@param tension number
+---@param damping number
+---@param spacing number|nil
+---@return EntityBuilder
+function EntityBuilder:with_water_springs(stiffness, tension, damping, spacing) end
+
+---Make the entity a body of water `width` wide and `depth` deep below its position, with a spring-simulated surface that splashes when rigid bodies whose group or tags match `mask` (default "*") enter it and floats them while submerged
+---@param width number
+---@param depth number
+---@param mask string|nil
+---@return EntityBuilder
+function EntityBuilder:with_water_surface(width, depth, mask) end
+
+---Push the entity's rigid body with the global wind times `factor` (default 1), whatever the wind mask
+---@param factor number|nil
+---@return EntityBuilder
+function EntityBuilder:with_wind(factor) end
+
+---Keep this UI entity's screen position on entity `target_id` as seen through the camera, updated every frame; the entity is despawned with its target. opts: {offset_x?, offset_y? (screen pixels added to the target's position), clamp? (keep the UI on screen, this many pixels from the edges)}. Requires :with_screen_position() (any value) and :with_zindex() to render.
+---@param target_id integer
+---@param opts table|nil
+---@return EntityBuilder
+function EntityBuilder:with_world_anchor(target_id, opts) end
+
 ---Set render order
 ---@param z number
 ---@return EntityBuilder
@@ -2204,6 +3454,27 @@ function CollisionEntityBuilder:with_animation_controller(fallback_key) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_animation_rule(condition_table, set_key) end
 
+---Pull rigid bodies labeled `target` (group or tag) within `radius` toward this entity with an acceleration of `strength` units/s² (negative repels)
+---@param radius number
+---@param strength number
+---@param target string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_attractor(radius, strength, target) end
+
+---Play sound effect `sound_id` on an entity event: "spawn", "despawn" (not on scene changes), "collision" or "collision:<group>" (when contact starts). The trigger can't play again for `cooldown` seconds (default 0). Can be called multiple times
+---@param event string
+---@param sound_id string
+---@param cooldown number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_audio_trigger(event, sound_id, cooldown) end
+
+---Draw a translucent ellipse on the ground under this entity's sprite, shrinking and fading to 40% as the entity rises. opts: {opacity? (0-1, default 0.5), offset_x?, offset_y? (ellipse centre relative to the position), height_signal? (key of this entity's signals holding its height above the ground), ground_y? (world Y the ellipse stays on; the height is the distance down to it), max_height? (height of the smallest ellipse, default 64)}
+---@param width number
+---@param height number
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_blob_shadow(width, height, opts) end
+
 ---Mark entity as camera follow target (higher priority wins). zoom is the desired camera zoom when this target wins (default 1.0).
 ---@param priority integer|nil
 ---@param zoom number|nil
@@ -2224,6 +3495,43 @@ function CollisionEntityBuilder:with_collider(width, height, origin_x, origin_y)
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_collider_offset(offset_x, offset_y) end
 
+---Make the entity a conveyor carrying overlapping rigid bodies whose group or tags match `mask` (default "*") at a constant velocity without changing their own. The area is the entity's collider unless :with_force_area_rect() is used.
+---@param vel_x number
+---@param vel_y number
+---@param mask string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_conveyor(vel_x, vel_y, mask) end
+
+---Add a cooldown of `duration` seconds whose remaining fraction (1 just started, 0 ready) is written every frame to the entity's scalar signal `signal`, ready to bind to a bar. Start it with engine.entity_start_cooldown(). opts: {auto_reset? (start over as soon as it elapses, default false), started? (begin cooling down instead of ready, default false), world? (also write the fraction to the world signal `signal`, which :with_gui_progress_bar_signal_binding() reads, default false)}
+---@param duration number
+---@param signal string
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_cooldown(duration, signal, opts) end
+
+---Keep this entity between `min_length` (default `length`, a rigid rod; 0 for a leash) and `length` from entity `target_id`. Rigid bodies are moved by the joint and keep swinging or orbiting; anything else is a fixed anchor. `stiffness` (0-1, default 1) below 1 makes it springy
+---@param target_id integer
+---@param length number
+---@param min_length number|nil
+---@param stiffness number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_distance_joint(target_id, length, min_length, stiffness) end
+
+---Make the entity a force area accelerating overlapping rigid bodies whose group or tags match `mask` (default "*"). The area is the entity's collider unless :with_force_area_rect() is used.
+---@param accel_x number
+---@param accel_y number
+---@param mask string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_force_area(accel_x, accel_y, mask) end
+
+---Set the force area's rectangle relative to the entity position instead of using its collider
+---@param x number
+---@param y number
+---@param width number
+---@param height number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_force_area_rect(x, y, width, height) end
+
 ---Set friction (creates RigidBody if needed)
 ---@param friction number
 ---@return CollisionEntityBuilder
@@ -2233,6 +3541,15 @@ function CollisionEntityBuilder:with_friction(friction) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_frozen() end
 
+---Stagger the grid cells' appearance: preset "scale_in" or "drop_in", `delay` seconds per step of `order` ("cells", "rows", "columns", "diagonal"), `height` is the drop_in fall (default 64)
+---@param preset string
+---@param delay number
+---@param duration number
+---@param order string|nil
+---@param height number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_grid_intro(preset, delay, duration, order, height) end
+
 ---Spawn entities from a JSON grid layout
 ---@param path string
 ---@param group string
@@ -2285,6 +3602,14 @@ function CollisionEntityBuilder:with_gui_image_hover_offset(offset_x, offset_y)
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_gui_image_pressed_offset(offset_x, offset_y) end
 
+---Set GuiInventoryGrid component: draws the Inventory of `inventory_id` as `columns` slots per row of `cell_size` pixels, `spacing` apart (default 2), with item icons and stack counts. Slots use the theme's label nine-patch (panel if unset); see :with_gui_theme_key(). Requires :with_screen_position() (or :with_parent()+:with_gui_offset()) and :with_zindex() to render.
+---@param inventory_id integer
+---@param columns integer
+---@param cell_size number
+---@param spacing number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_gui_inventory_grid(inventory_id, columns, cell_size, spacing) end
+
 ---Set GuiLabel component; gui_label_spawn_system spawns a caption DynamicText child on Added<GuiLabel>, themed via the named theme looked up in GuiThemeStore (see engine.set_gui_theme_font / :with_gui_theme_key). An empty `text` skips spawning the caption entirely (captionless label). Requires :with_screen_position() (or :with_parent()+:with_gui_offset()) and :with_zindex() to render.
 ---@param width number
 ---@param height number
@@ -2329,7 +3654,7 @@ function CollisionEntityBuilder:with_gui_progress_bar_signal_binding(key) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_gui_progress_bar_vertical() end
 
----Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar (default "default"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar() first.
+---Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar/GuiInventoryGrid (default "default"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar()/:with_gui_inventory_grid() first.
 ---@param key string
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_gui_theme_key(key) end
@@ -2340,13 +3665,92 @@ function CollisionEntityBuilder:with_gui_theme_key(key) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_gui_window(width, height) end
 
----Add collision callback between two groups
----@param group_a string
----@param group_b string
+---Give the entity a faux-3D height: the sprite is drawn height pixels above its position, which stays on the ground for collisions and sorting, and falls back under its own gravity. Landing triggers :with_on_land() and bounces. opts: {gravity? (default 980, 0 hovers), bounce? (share of the landing speed kept, 0-1, default 0), velocity? (initial upward speed)}
+---@param height number
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_height(height, opts) end
+
+---Give the entity a damage-dealing box (offset from its position) used whenever the current animation has no hitbox frame data
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hitbox(x, y, w, h) end
+
+---Set the hitbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.
+---@param animation_key string
+---@param frame integer
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hitbox_frame(animation_key, frame, x, y, w, h) end
+
+---Import hitbox frames from the slice named `slice` (default "hitbox") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.
+---@param path string
+---@param slice string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hitbox_aseprite(path, slice) end
+
+---Only hit hurtbox owners whose group or tags match `mask` (default "*")
+---@param mask string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hitbox_mask(mask) end
+
+---Give the entity a damage-receiving box (offset from its position) used whenever the current animation has no hurtbox frame data
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hurtbox(x, y, w, h) end
+
+---Set the hurtbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.
+---@param animation_key string
+---@param frame integer
+---@param x number
+---@param y number
+---@param w number
+---@param h number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hurtbox_frame(animation_key, frame, x, y, w, h) end
+
+---Import hurtbox frames from the slice named `slice` (default "hurtbox") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.
+---@param path string
+---@param slice string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_hurtbox_aseprite(path, slice) end
+
+---Show a prompt above this entity while an interactor (group or tags matching `mask`, default "player") is in range, and call `callback` as fn(ctx, input, interactor_id) when it presses `action` (default "action_1"). Table fields: prompt, callback?, radius? (default: overlap with this entity's collider), mask?, action?, icon? (texture key), offset? ({x, y}, default {0, -16}), theme? (GuiThemeStore key).
+---@param table table
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_interactable(table) end
+
+---Give the entity an Inventory with `slots` empty slots. Fill it with engine.inventory_add().
+---@param slots integer
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_inventory(slots) end
+
+---Add collision callback between two groups. Each side is a group/tag name, a pattern ("*" matches anything, "enemy_*" matches by prefix, "a|b" lists alternatives) or a table of names
+---@param group_a string|string[]
+---@param group_b string|string[]
 ---@param callback string
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_lua_collision_rule(group_a, group_b, callback) end
 
+---Set whether the collision rule stops lower-priority matching rules from running (default true)
+---@param exclusive boolean
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_lua_collision_rule_exclusive(exclusive) end
+
+---Set the dispatch priority of the collision rule; when several rules match one collision, higher priorities run first (default 0)
+---@param priority integer
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_lua_collision_rule_priority(priority) end
+
 ---Attach a one-shot Lua setup callback. The named function is called once (Added<LuaSetup>) with the entity context. Fires the frame after spawn; child entities added inside the callback appear the following frame.
 ---@param callback string
 ---@return CollisionEntityBuilder
@@ -2358,6 +3762,11 @@ function CollisionEntityBuilder:with_lua_setup(callback) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_lua_timer(duration, callback) end
 
+---Set mass dividing forces, force areas, wind and impulses (creates RigidBody if needed)
+---@param mass number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_mass(mass) end
+
 ---Set max speed clamp (creates RigidBody if needed)
 ---@param speed number
 ---@return CollisionEntityBuilder
@@ -2434,11 +3843,45 @@ function CollisionEntityBuilder:with_menu_visible_count(count) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_mouse_controlled(follow_x, follow_y) end
 
+---Stretch texture `tex_key` over a width x height panel keeping `border` pixels unscaled at the corners (NPatch), in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). :with_tint() tints it. opts: {left?, top?, right?, bottom? (per-side borders, default `border`), source? ({x, y, w, h} region of the texture, default the whole texture), offset_x?, offset_y? (top-left corner relative to the position)}
+---@param tex_key string
+---@param width number
+---@param height number
+---@param border integer
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_nine_slice(tex_key, width, height, border, opts) end
+
+---While this entity is outside the camera view, draw an arrow on the screen edge pointing toward it. opts: {color? ({r, g, b, a?}, default yellow), size? (arrow length in pixels, default 12), margin? (gap to the screen edge, default 8), icon? (texture key drawn inside the arrow), distance? (world units per displayed unit; shows the distance from the camera centre), theme? (GuiThemeStore key for the distance font)}
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_offscreen_indicator(opts) end
+
 ---Attach a callback fired exactly once when the entity's non-looped animation first reaches its last frame. Signature: fn(ctx, input). Looped animations never trigger it.
 ---@param fn_name string
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_on_animation_end(fn_name) end
 
+---Attach a callback fired once per swing for each entity this entity's hitbox strikes. Signature: fn(ctx, input, target_id).
+---@param fn_name string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_on_hit(fn_name) end
+
+---Attach a callback fired each time the entity's :with_height() comes down to the ground, bounces included. Signature: fn(ctx, input, impact_speed).
+---@param fn_name string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_on_land(fn_name) end
+
+---Despawn this entity when entity `owner_id` is despawned (health bars, shadows, attached effects). Ownership doesn't move the entity; combine with :with_stuckto() or :with_parent() for that
+---@param owner_id integer
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_owner(owner_id) end
+
+---Recolor the sprite through an N×2 palette texture: each color of the top row becomes the color below it. World-space sprites only; an entity shader takes precedence
+---@param palette_tex_key string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_palette_swap(palette_tex_key) end
+
 ---Set parent entity for transform hierarchy
 ---@param parent_id integer
 ---@return CollisionEntityBuilder
@@ -2449,6 +3892,13 @@ function CollisionEntityBuilder:with_parent(parent_id) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_particle_emitter(table) end
 
+---Move the entity along waypoints at `speed` units per second. `points` is a list of {x, y} tables (at least 2). opts: {spline? (Catmull-Rom curve through the points, default false), closed? (join the last point to the first), loop? ("once" (default), "loop", "ping_pong"), easing? (tween easing over the whole path), orient? (rotate to face the direction of travel), rotation_offset? (degrees added when orienting)}
+---@param points table
+---@param speed number
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_path_follow(points, speed, opts) end
+
 ---Survive scene transitions
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_persistent() end
@@ -2475,12 +3925,109 @@ function CollisionEntityBuilder:with_persistent() end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_phase(table) end
 
+---Keep this entity at (offset_x, offset_y) from entity `target_id`. Unlike :with_stuckto(), both rigid bodies are moved, so pushing either drags the other. `stiffness` (0-1, default 1) below 1 makes it springy
+---@param target_id integer
+---@param offset_x number
+---@param offset_y number
+---@param stiffness number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_pin_joint(target_id, offset_x, offset_y, stiffness) end
+
+---Only report collisions where the sprite's opaque pixels overlap; the alpha mask comes from the sprite's texture as loaded by engine.load_texture
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_pixel_collision() end
+
+---Make local player 1-4 the owner: the entity's input control and menu follow the device given to that player with engine.assign_device()
+---@param player integer
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_player(player) end
+
+---Make this entity a door or teleporter: confirming its interaction fades the screen out, moves the interactor to {x, y} or the named `spawn_point`, or switches to `scene` and places every entity matching the interaction mask on that scene's `spawn_point`, then fades back in. Adds an interaction prompt (`prompt`, default "Enter") unless :with_interactable() sets one. Table fields: x?, y?, spawn_point?, scene?, fade? (seconds per half, default 0.3), prompt?.
+---@param table table
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_portal(table) end
+
 ---Set world position
 ---@param x number
 ---@param y number
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_position(x, y) end
 
+---Draw a plain-colored bar filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). opts: {value? (default 0), max? (default 1), color? ({r, g, b, a?} fill, default lime), background_color? ({r, g, b, a?} or false, default dark gray), background? (texture key stretched under the fill), offset_x?, offset_y? (top-left corner relative to the position), direction? ("horizontal", "horizontal_reversed", "vertical" or "vertical_reversed"), signal? (key copied into value every frame, integer or scalar), entity? (entity id whose signals hold `signal`; WorldSignals when omitted)}
+---@param width number
+---@param height number
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_progress_bar(width, height, opts) end
+
+---Draw a ring (or a pie, without inner_radius) filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). The position is the centre. opts: the options of :with_progress_bar() except direction, plus {inner_radius? (default 0), start_angle? (degrees clockwise from the right, default -90 = top), counter_clockwise? (boolean)}
+---@param radius number
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_radial_progress(radius, opts) end
+
+---Set render layer ("background", "gameplay", "fx", "ui", "debug" or 0-255) toggled by engine.set_layer_visible(); draw order still follows :with_zindex()
+---@param layer string|integer
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_render_layer(layer) end
+
+---Remember this entity when its scene is left and restore it on re-entry: despawned entities stay despawned, and position and signals are restored unless disabled. `key` must be unique within the scene and the same every time it is spawned.
+---@param key string
+---@param restore_position boolean|nil
+---@param restore_signals boolean|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_restore_on_return(key, restore_position, restore_signals) end
+
+---Make the entity a rope of total `length` split into `segments` segments (default one per 8 units), simulated as a chain of points under gravity. Attach its ends with :with_rope_start()/:with_rope_start_entity() and :with_rope_end()/:with_rope_end_entity(); unattached ends hang loose
+---@param length number
+---@param segments integer|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope(length, segments) end
+
+---Pin the rope's end to world point (x, y)
+---@param x number
+---@param y number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_end(x, y) end
+
+---Attach the rope's end to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along
+---@param entity_id integer
+---@param offset_x number|nil
+---@param offset_y number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_end_entity(entity_id, offset_x, offset_y) end
+
+---Tune the rope: fraction of a stretch corrected per pass (0-1, default 1), fraction of velocity lost per second (default 0.5), correction passes per frame (default 8) and gravity (default 0, 980)
+---@param stiffness number
+---@param damping number
+---@param iterations integer|nil
+---@param gravity_x number|nil
+---@param gravity_y number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_physics(stiffness, damping, iterations, gravity_x, gravity_y) end
+
+---Pin the rope's start to world point (x, y)
+---@param x number
+---@param y number
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_start(x, y) end
+
+---Attach the rope's start to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along
+---@param entity_id integer
+---@param offset_x number|nil
+---@param offset_y number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_start_entity(entity_id, offset_x, offset_y) end
+
+---Draw the rope `thickness` wide in a color (RGBA 0-255)
+---@param thickness number
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_rope_style(thickness, r, g, b, a) end
+
 ---Set rotation in degrees
 ---@param degrees number
 ---@return CollisionEntityBuilder
@@ -2498,6 +4045,17 @@ function CollisionEntityBuilder:with_scale(sx, sy) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_screen_position(x, y) end
 
+---Add a sensor ray that sets signal flag `flag` while it hits a collider whose group or tags match `mask` (default "*"). Can be called several times; adds Signals.
+---@param flag string
+---@param offset_x number
+---@param offset_y number
+---@param dir_x number
+---@param dir_y number
+---@param length number
+---@param mask string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_sensor(flag, offset_x, offset_y, dir_x, dir_y, length, mask) end
+
 ---Set per-entity shader with optional uniforms
 ---@param shader_key string
 ---@param uniforms table|nil
@@ -2551,6 +4109,17 @@ function CollisionEntityBuilder:with_signal_string(key, value) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_signals() end
 
+---Make the collider a right-triangle ramp with its right angle in `corner` ("bottom_left", "bottom_right", "top_left" or "top_right"); boxes touching it are pushed straight out onto the slanted side
+---@param corner string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_slope(corner) end
+
+---Name this entity's position as a spawn point that portals can lead to and engine.get_spawn_point() returns, with optional `properties` (string keys; integer, number, string or boolean values).
+---@param name string
+---@param properties table?
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_spawn_point(name, properties) end
+
 ---Set sprite
 ---@param tex_key string
 ---@param width number
@@ -2591,6 +4160,16 @@ function CollisionEntityBuilder:with_stuckto_offset(offset_x, offset_y) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_stuckto_stored_velocity(vx, vy) end
 
+---Add extra tag labels matched by collision rules and group tracking
+---@param tags string[]
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_tags(tags) end
+
+---Make this entity destructible terrain: a copy of texture tex_key drawn with its top-left at the entity's position, with a collider and pixel collision. Carve it with engine.terrain_carve_circle()
+---@param tex_key string
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_terrain(tex_key) end
+
 ---Set DynamicText component
 ---@param content string
 ---@param font string
@@ -2602,10 +4181,22 @@ function CollisionEntityBuilder:with_stuckto_stored_velocity(vx, vy) end
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_text(content, font, font_size, r, g, b, a) end
 
----Spawn a tilemap root. All tile entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap.
+---Scroll `content` glyph by glyph along a path, starting at the far end of the track and starting over when the text has left it. `path` is "sine" (default), "wave" or "circle" (around this entity). opts: {width? (track length, default 640), amplitude? (wave height or circle radius, default font_size), wavelength? (default 256), spacing? (between glyphs, default font_size), color? ({r, g, b, a?}, default white)}
+---@param content string
+---@param font string
+---@param font_size number
+---@param speed number
+---@param path string|nil
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_text_scroller(content, font, font_size, speed, path, opts) end
+
+---Spawn a tilemap root from a Tilesetter directory or a Tiled `.tmj` map. All tile and object entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap. `projection` ("orthogonal", "isometric" or "hex") overrides the one in the tilemap file. The tiles of the layers named in `solid_layers` get colliders (one box per horizontal run of tiles, in a group named after the layer), kept up to date by tile edits; orthogonal maps only.
 ---@param path string
+---@param projection string|nil
+---@param solid_layers string[]|nil
 ---@return CollisionEntityBuilder
-function CollisionEntityBuilder:with_tilemap(path) end
+function CollisionEntityBuilder:with_tilemap(path, projection, solid_layers) end
 
 ---Set color tint (RGBA 0-255)
 ---@param r integer
@@ -2736,6 +4327,67 @@ function CollisionEntityBuilder:with_tween_screen_position_on_finished(callback)
 ---@return CollisionEntityBuilder
 function CollisionEntityBuilder:with_velocity(vx, vy) end
 
+---Shadow-cast a visibility polygon of `radius` from this entity against the occluders (see engine.set_occluders). A `fov` below 360 degrees turns it into a cone facing the entity's rotation.
+---@param radius number
+---@param fov number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_visibility(radius, fov) end
+
+---Draw the visibility polygon filled with a color (RGBA 0-255), as a light or vision cone
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_visibility_fill(r, g, b, a) end
+
+---Reveal fog-of-war cells within `radius` of this entity (see engine.fog_enable). With `blocked`, tiles of the fog's solid layers block the line of sight.
+---@param radius number
+---@param blocked boolean|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_vision(radius, blocked) end
+
+---Set the upward acceleration of fully submerged bodies, the fraction of their velocity removed per second and optionally how hard entering bodies splash
+---@param buoyancy number
+---@param drag number
+---@param splash number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_water_buoyancy(buoyancy, drag, splash) end
+
+---Set the water's fill color (RGBA 0-255)
+---@param r integer
+---@param g integer
+---@param b integer
+---@param a integer|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_water_color(r, g, b, a) end
+
+---Tune the water surface: pull back to rest, pull between neighbouring columns, damping and optional column spacing (default 8)
+---@param stiffness number
+---@param tension number
+---@param damping number
+---@param spacing number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_water_springs(stiffness, tension, damping, spacing) end
+
+---Make the entity a body of water `width` wide and `depth` deep below its position, with a spring-simulated surface that splashes when rigid bodies whose group or tags match `mask` (default "*") enter it and floats them while submerged
+---@param width number
+---@param depth number
+---@param mask string|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_water_surface(width, depth, mask) end
+
+---Push the entity's rigid body with the global wind times `factor` (default 1), whatever the wind mask
+---@param factor number|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_wind(factor) end
+
+---Keep this UI entity's screen position on entity `target_id` as seen through the camera, updated every frame; the entity is despawned with its target. opts: {offset_x?, offset_y? (screen pixels added to the target's position), clamp? (keep the UI on screen, this many pixels from the edges)}. Requires :with_screen_position() (any value) and :with_zindex() to render.
+---@param target_id integer
+---@param opts table|nil
+---@return CollisionEntityBuilder
+function CollisionEntityBuilder:with_world_anchor(target_id, opts) end
+
 ---Set render order
 ---@param z number
 ---@return CollisionEntityBuilder
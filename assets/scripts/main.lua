@@ -154,7 +154,8 @@ function on_enter_play()
     engine.set_integer("high_score", 0)
     engine.set_integer("lives", 3)
     engine.set_integer("level", 1)
-    engine.set_string("scene", "menu") -- Start at menu scene
+    -- Start at the project's default_scene (aberred.toml), falling back to the menu
+    engine.set_string("scene", engine.get_string("scene") or "menu")
 
     return "Hello from Lua! Ready to play."
 end
@@ -0,0 +1,42 @@
+-- stress/common.lua
+-- Shared setup for the built-in stress scenes (cargo run --release -- --stress <scene>).
+-- Frame-time summaries are logged by the engine every few seconds; build with
+-- --features tracy to see the "frame_time_ms" plot in the profiler.
+
+local M = {}
+
+M.SCREEN_W = 800
+M.SCREEN_H = 450
+M.SPRITE_W = 32
+M.SPRITE_H = 32
+
+--- Load the shared sprite texture. Call from on_setup().
+function M.load_assets()
+    engine.load_texture("stress-sprite", "./assets/textures/bunnymark/raybunny.png")
+end
+
+--- Uncapped frame rate, fixed camera and a fixed random seed so runs are comparable.
+function M.setup()
+    engine.set_render_size(M.SCREEN_W, M.SCREEN_H)
+    engine.set_vsync(false)
+    engine.set_target_fps(0) -- 0 = unlimited
+    engine.set_camera(0, 0, 0, 0, 0.0, 1.0)
+    engine.set_background_color(20, 20, 20)
+    math.randomseed(1234)
+end
+
+--- Random point inside the screen, leaving room for a sprite.
+--- @return number x, number y
+function M.random_point()
+    return math.random() * (M.SCREEN_W - M.SPRITE_W), math.random() * (M.SCREEN_H - M.SPRITE_H)
+end
+
+--- Handle the back action: quit the stress run.
+--- @param input InputSnapshot
+function M.check_quit(input)
+    if input.digital.back.just_pressed then
+        engine.set_flag("quit_game")
+    end
+end
+
+return M
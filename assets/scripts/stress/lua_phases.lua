@@ -0,0 +1,52 @@
+-- stress/lua_phases.lua
+-- Stress scene: 1 000 entities whose phase on_update callback runs in Lua every frame
+-- and bounces the entity off the screen edges. Measures per-entity Lua call overhead.
+
+local C = require("stress.common")
+
+local ENTITY_COUNT = 1000
+
+function on_setup()
+    C.load_assets()
+end
+
+function on_enter_play()
+    engine.set_string("scene", "stress_lua_phases")
+    return "stress: lua phases"
+end
+
+function stress_phase_on_update(ctx, _, _)
+    local pos, vel = ctx.pos, ctx.vel
+    if not pos or not vel then return end
+    local vx, vy = vel.x, vel.y
+    if (pos.x < 0 and vx < 0) or (pos.x > C.SCREEN_W - C.SPRITE_W and vx > 0) then
+        vx = -vx
+    end
+    if (pos.y < 0 and vy < 0) or (pos.y > C.SCREEN_H - C.SPRITE_H and vy > 0) then
+        vy = -vy
+    end
+    if vx ~= vel.x or vy ~= vel.y then
+        engine.entity_set_velocity(ctx.id, vx, vy)
+    end
+end
+
+function on_switch_scene(_)
+    C.setup()
+    for _ = 1, ENTITY_COUNT do
+        local x, y = C.random_point()
+        engine.spawn()
+            :with_sprite("stress-sprite", C.SPRITE_W, C.SPRITE_H, 0, 0)
+            :with_position(x, y)
+            :with_velocity(math.random() * 480 - 240, math.random() * 480 - 240)
+            :with_phase({
+                initial = "move",
+                phases  = { move = { on_update = "stress_phase_on_update" } },
+            })
+            :build()
+    end
+    engine.log_info("stress: spawned " .. ENTITY_COUNT .. " Lua phase entities")
+end
+
+function on_update_stress_lua_phases(input, _)
+    C.check_quit(input)
+end
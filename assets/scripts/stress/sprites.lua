@@ -0,0 +1,36 @@
+-- stress/sprites.lua
+-- Stress scene: 10 000 tinted sprites ping-ponging across the screen.
+-- Measures sprite rendering plus the tween system.
+
+local C = require("stress.common")
+
+local SPRITE_COUNT = 10000
+
+function on_setup()
+    C.load_assets()
+end
+
+function on_enter_play()
+    engine.set_string("scene", "stress_sprites")
+    return "stress: sprites"
+end
+
+function on_switch_scene(_)
+    C.setup()
+    for _ = 1, SPRITE_COUNT do
+        local x, y = C.random_point()
+        local tx, ty = C.random_point()
+        engine.spawn()
+            :with_sprite("stress-sprite", C.SPRITE_W, C.SPRITE_H, 0, 0)
+            :with_position(x, y)
+            :with_tint(math.random(50, 240), math.random(80, 240), math.random(100, 240), 255)
+            :with_tween_position(x, y, tx, ty, 1 + math.random() * 3)
+            :with_tween_position_loop("ping_pong")
+            :build()
+    end
+    engine.log_info("stress: spawned " .. SPRITE_COUNT .. " sprites")
+end
+
+function on_update_stress_sprites(input, _)
+    C.check_quit(input)
+end
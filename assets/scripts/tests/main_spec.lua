@@ -0,0 +1,30 @@
+-- main_spec.lua
+-- Example specs for main.lua, run headless with:
+--   cargo run -- --run-lua-tests
+
+test("on_enter_play initializes the world signals", function()
+    assert_signal("score", 0)
+    assert_signal("high_score", 0)
+    assert_signal("lives", 3)
+    assert_signal("level", 1)
+    assert_signal("scene", "menu")
+end)
+
+test("signals set by a case persist across frames", function()
+    engine.set_integer("score", 100)
+    step_frames(10)
+    assert_signal("score", 100)
+end)
+
+test("spawned entities move with their velocity", function()
+    engine.track_group_bounds("probe")
+    spawn_test_entity{
+        key = "probe",
+        group = "probe",
+        position = { 0, 0 },
+        velocity = { 120, 0 },
+    }
+    step_frames(30)
+    local bounds = engine.group_bounds("probe")
+    assert(math.abs(bounds.cx - 60) < 0.01, "expected x = 60, got " .. tostring(bounds.cx))
+end)
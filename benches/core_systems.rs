@@ -0,0 +1,93 @@
+//! Criterion benches for the per-frame systems most sensitive to entity count.
+//!
+//! Run with `cargo bench --bench core_systems`. Each bench builds a headless
+//! world (no window) and times one schedule run per iteration.
+
+use std::hint::black_box;
+
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use raylib::prelude::Vector2;
+
+use aberredengine::components::boxcollider::BoxCollider;
+use aberredengine::components::group::Group;
+use aberredengine::components::mapposition::MapPosition;
+use aberredengine::components::rigidbody::RigidBody;
+use aberredengine::events::audio::AudioCmd;
+use aberredengine::resources::framearena::FrameArena;
+use aberredengine::resources::screensize::ScreenSize;
+use aberredengine::resources::worldtime::WorldTime;
+use aberredengine::systems::collision_detector::collision_detector;
+use aberredengine::systems::frame_arena::reset_frame_arena;
+use aberredengine::systems::movement::movement;
+
+const FRAME_DT: f32 = 1.0 / 60.0;
+
+fn make_world() -> World {
+    let mut world = World::new();
+    world.insert_resource(WorldTime {
+        elapsed: 0.0,
+        delta: FRAME_DT,
+        real_delta: FRAME_DT,
+        time_scale: 1.0,
+        frame_count: 0,
+    });
+    world.insert_resource(ScreenSize { w: 800, h: 450 });
+    world.init_resource::<Messages<AudioCmd>>();
+    world.insert_resource(FrameArena::default());
+    world
+}
+
+/// Spread `count` entities over an 800x450 area with a fixed seed so every run
+/// sees the same overlap pattern.
+fn random_positions(count: usize) -> Vec<Vector2> {
+    let mut rng = fastrand::Rng::with_seed(0xABE);
+    (0..count)
+        .map(|_| Vector2::new(rng.f32() * 800.0, rng.f32() * 450.0))
+        .collect()
+}
+
+fn bench_collision_detector(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collision_detector");
+    for count in [500, 1_000, 2_000, 5_000] {
+        let mut world = make_world();
+        for (i, pos) in random_positions(count).into_iter().enumerate() {
+            let name = if i % 2 == 0 { "a" } else { "b" };
+            world.spawn((
+                MapPosition::new(pos.x, pos.y),
+                BoxCollider::new(8.0, 8.0),
+                Group::new(name),
+            ));
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems((collision_detector, reset_frame_arena).chain());
+
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| schedule.run(black_box(&mut world)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_movement(c: &mut Criterion) {
+    let mut group = c.benchmark_group("movement");
+    for count in [1_000, 10_000] {
+        let mut world = make_world();
+        for pos in random_positions(count) {
+            let mut rb = RigidBody::new();
+            rb.velocity = Vector2::new(pos.y - 225.0, pos.x - 400.0);
+            world.spawn((MapPosition::new(pos.x, pos.y), rb));
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems(movement);
+
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| schedule.run(black_box(&mut world)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_collision_detector, bench_movement);
+criterion_main!(benches);
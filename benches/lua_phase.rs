@@ -0,0 +1,88 @@
+//! Criterion bench for per-entity Lua phase callbacks.
+//!
+//! Run with `cargo bench --bench lua_phase`. Times one `lua_phase_system` run
+//! over N entities whose `on_update` callback reads the context and returns.
+
+use std::hint::black_box;
+
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rustc_hash::FxHashMap;
+
+use aberredengine::components::luaphase::{LuaPhase, PhaseCallbacks};
+use aberredengine::components::mapposition::MapPosition;
+use aberredengine::events::audio::AudioCmd;
+use aberredengine::resources::animationstore::AnimationStore;
+use aberredengine::resources::input::InputState;
+use aberredengine::resources::lua_runtime::LuaRuntime;
+use aberredengine::resources::systemsstore::SystemsStore;
+use aberredengine::resources::worldsignals::WorldSignals;
+use aberredengine::resources::worldtime::WorldTime;
+use aberredengine::systems::luaphase::lua_phase_system;
+
+fn make_world() -> World {
+    let mut world = World::new();
+    world.insert_resource(WorldTime {
+        elapsed: 0.0,
+        delta: 1.0 / 60.0,
+        real_delta: 1.0 / 60.0,
+        time_scale: 1.0,
+        frame_count: 0,
+    });
+    world.init_resource::<Messages<AudioCmd>>();
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(SystemsStore::new());
+    world.insert_resource(InputState::default());
+    world.insert_resource(AnimationStore {
+        animations: Default::default(),
+    });
+    let lua_runtime = LuaRuntime::new().expect("LuaRuntime::new");
+    lua_runtime
+        .lua()
+        .load(
+            r#"
+            function bench_phase_on_update(ctx, input, dt)
+                if ctx.pos and ctx.pos.x > 1e9 then
+                    return "never"
+                end
+            end
+        "#,
+        )
+        .exec()
+        .expect("load bench callback");
+    world.insert_non_send(lua_runtime);
+    world
+}
+
+fn bench_lua_phase_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lua_phase_on_update");
+    for count in [100, 1_000] {
+        let mut world = make_world();
+        for i in 0..count {
+            let mut phases = FxHashMap::default();
+            phases.insert(
+                "move".into(),
+                PhaseCallbacks {
+                    on_enter: None,
+                    on_update: Some("bench_phase_on_update".into()),
+                    on_exit: None,
+                },
+            );
+            world.spawn((
+                MapPosition::new(i as f32, 0.0),
+                LuaPhase::new("move", phases),
+            ));
+        }
+        let mut schedule = Schedule::default();
+        schedule.add_systems(lua_phase_system);
+
+        group.bench_with_input(BenchmarkId::from_parameter(count), &count, |b, _| {
+            b.iter(|| schedule.run(black_box(&mut world)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_lua_phase_update);
+criterion_main!(benches);
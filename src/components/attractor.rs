@@ -0,0 +1,46 @@
+//! Magnet-style attraction of nearby rigid bodies.
+//!
+//! An [`Attractor`] pulls every non-frozen [`RigidBody`](super::rigidbody::RigidBody)
+//! labeled with its `target` group or tag, within `radius` of the attractor,
+//! toward itself. The pull is an acceleration of `strength` units/s² along the
+//! direction to the attractor, applied by
+//! [`attractor_system`](crate::systems::attractor::attractor_system) before
+//! movement — the classic coin magnet.
+//!
+//! Candidates are looked up through the
+//! [`SpatialIndex`](crate::resources::spatialindex::SpatialIndex), so only
+//! bodies in nearby cells are visited.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("player"),
+//!     MapPosition::new(100.0, 100.0),
+//!     Attractor::new(96.0, 2000.0, "coin"),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Pulls labeled rigid bodies within `radius` toward the entity.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Attractor {
+    /// Reach in world units, measured between pivots.
+    pub radius: f32,
+    /// Acceleration toward the attractor in units/s². Negative repels.
+    pub strength: f32,
+    /// Group name or tag of the bodies affected.
+    pub target: String,
+}
+
+impl Attractor {
+    /// Create an attractor for bodies labeled `target`.
+    pub fn new(radius: f32, strength: f32, target: impl Into<String>) -> Self {
+        Self {
+            radius: radius.max(0.0),
+            strength,
+            target: target.into(),
+        }
+    }
+}
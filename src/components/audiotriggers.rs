@@ -0,0 +1,199 @@
+//! Sounds played on entity events without scripting.
+//!
+//! An [`AudioTriggers`] component lists sound effects to play when its entity
+//! is spawned, is despawned, or starts touching an entity of a group. Each
+//! trigger has its own cooldown so bursts (a bullet grazing several walls,
+//! a swarm dying at once) don't stack the same sound.
+//!
+//! A collision trigger plays when contact starts, not on every frame of the
+//! overlap. Scene changes don't play despawn sounds.
+//!
+//! # Usage from Lua
+//!
+//! ```lua
+//! engine.spawn()
+//!     :with_group("coin")
+//!     :with_audio_trigger("spawn", "coin_appear")
+//!     :with_audio_trigger("despawn", "coin_pickup")
+//!     :with_audio_trigger("collision:wall", "coin_bounce", 0.2)
+//!     :build()
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::audiotriggers`] – plays the sounds
+
+use bevy_ecs::prelude::Component;
+
+/// Entity event that plays an [`AudioTrigger`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioTriggerEvent {
+    /// The entity was spawned (or the component was added).
+    Spawned,
+    /// The entity was despawned outside of a scene change.
+    Despawned,
+    /// The entity started touching an entity of this group, or of any
+    /// group when `None`.
+    Collided(Option<String>),
+}
+
+impl AudioTriggerEvent {
+    /// Parses `"spawn"`, `"despawn"`, `"collision"` or `"collision:<group>"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "spawn" => Some(AudioTriggerEvent::Spawned),
+            "despawn" => Some(AudioTriggerEvent::Despawned),
+            "collision" => Some(AudioTriggerEvent::Collided(None)),
+            _ => name
+                .strip_prefix("collision:")
+                .filter(|group| !group.is_empty())
+                .map(|group| AudioTriggerEvent::Collided(Some(group.to_string()))),
+        }
+    }
+
+    /// Whether touching an entity of `group` fires this event.
+    pub fn matches_collision(&self, group: Option<&str>) -> bool {
+        match self {
+            AudioTriggerEvent::Collided(None) => true,
+            AudioTriggerEvent::Collided(Some(wanted)) => group == Some(wanted.as_str()),
+            _ => false,
+        }
+    }
+}
+
+/// One sound effect bound to an [`AudioTriggerEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioTrigger {
+    pub event: AudioTriggerEvent,
+    /// Sound effect id, as loaded with `engine.load_sound`.
+    pub sound: String,
+    /// Seconds after playing before the trigger can play again.
+    pub cooldown: f32,
+    /// Seconds left of the running cooldown.
+    remaining: f32,
+    /// Touched a matching entity this frame / last frame.
+    touching: bool,
+    touched: bool,
+}
+
+impl AudioTrigger {
+    pub fn new(event: AudioTriggerEvent, sound: impl Into<String>, cooldown: f32) -> Self {
+        Self {
+            event,
+            sound: sound.into(),
+            cooldown: cooldown.max(0.0),
+            remaining: 0.0,
+            touching: false,
+            touched: false,
+        }
+    }
+
+    /// Starts the cooldown and returns the sound if the trigger is ready.
+    pub fn fire(&mut self) -> Option<&str> {
+        if self.remaining > 0.0 {
+            return None;
+        }
+        self.remaining = self.cooldown;
+        Some(&self.sound)
+    }
+}
+
+/// Sound effects played on this entity's spawn, despawn and collisions.
+#[derive(Component, Debug, Clone, Default)]
+pub struct AudioTriggers {
+    pub triggers: Vec<AudioTrigger>,
+}
+
+impl AudioTriggers {
+    /// Adds a trigger, builder style.
+    pub fn with(
+        mut self,
+        event: AudioTriggerEvent,
+        sound: impl Into<String>,
+        cooldown: f32,
+    ) -> Self {
+        self.push(event, sound, cooldown);
+        self
+    }
+
+    pub fn push(&mut self, event: AudioTriggerEvent, sound: impl Into<String>, cooldown: f32) {
+        self.triggers
+            .push(AudioTrigger::new(event, sound, cooldown));
+    }
+
+    /// Fires every ready trigger of `event` and returns their sounds.
+    pub fn fire(&mut self, event: &AudioTriggerEvent) -> Vec<String> {
+        self.triggers
+            .iter_mut()
+            .filter(|trigger| trigger.event == *event)
+            .filter_map(|trigger| trigger.fire().map(str::to_string))
+            .collect()
+    }
+
+    /// Records contact with an entity of `group` and returns the sounds of
+    /// the collision triggers whose contact starts now.
+    pub fn touch(&mut self, group: Option<&str>) -> Vec<String> {
+        let mut sounds = Vec::new();
+        for trigger in &mut self.triggers {
+            if !trigger.event.matches_collision(group) || trigger.touching {
+                continue;
+            }
+            trigger.touching = true;
+            if !trigger.touched
+                && let Some(sound) = trigger.fire()
+            {
+                sounds.push(sound.to_string());
+            }
+        }
+        sounds
+    }
+
+    /// Advances cooldowns by `delta` seconds and starts a new contact frame.
+    pub fn tick(&mut self, delta: f32) {
+        for trigger in &mut self.triggers {
+            trigger.remaining = (trigger.remaining - delta).max(0.0);
+            trigger.touched = trigger.touching;
+            trigger.touching = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collision_plays_once_per_contact_within_cooldown() {
+        let wall = AudioTriggerEvent::parse("collision:wall").unwrap();
+        let mut triggers = AudioTriggers::default().with(wall, "thud", 1.0);
+
+        assert_eq!(triggers.touch(Some("wall")), vec!["thud"]);
+        assert!(triggers.touch(Some("wall")).is_empty());
+        triggers.tick(0.5);
+        // Still touching: no new contact.
+        assert!(triggers.touch(Some("wall")).is_empty());
+        triggers.tick(0.5);
+        triggers.tick(0.1);
+        assert!(triggers.touch(Some("enemy")).is_empty());
+        assert_eq!(triggers.touch(Some("wall")), vec!["thud"]);
+
+        triggers.tick(0.1);
+        triggers.tick(0.1);
+        // New contact, but still cooling down.
+        assert!(triggers.touch(Some("wall")).is_empty());
+    }
+
+    #[test]
+    fn parses_event_names() {
+        assert_eq!(
+            AudioTriggerEvent::parse("spawn"),
+            Some(AudioTriggerEvent::Spawned)
+        );
+        assert_eq!(
+            AudioTriggerEvent::parse("collision"),
+            Some(AudioTriggerEvent::Collided(None))
+        );
+        assert_eq!(AudioTriggerEvent::parse("collision:"), None);
+        assert_eq!(AudioTriggerEvent::parse("hit"), None);
+    }
+}
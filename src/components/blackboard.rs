@@ -0,0 +1,295 @@
+//! Per-entity AI working memory.
+//!
+//! The [`Blackboard`] component is a typed key-value store for AI decision
+//! making ("last_seen_player", "patrol_target", "alert_level"). It is kept
+//! separate from [`Signals`](super::signals::Signals) so AI state never
+//! collides with gameplay signal keys that animation rules, collision
+//! callbacks or the HUD depend on.
+//!
+//! Entries can carry a time-to-live: a remembered position that should be
+//! forgotten after three seconds is written with
+//! [`set_with_ttl`](Blackboard::set_with_ttl) and removed by
+//! [`blackboard_ttl_system`](crate::systems::blackboard::blackboard_ttl_system)
+//! once it expires.
+//!
+//! AI systems read it with a plain `Query<&Blackboard>`. Lua reads it through
+//! `ctx.blackboard` in phase and timer callbacks and writes it with
+//! `engine.entity_blackboard_set(...)`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use aberredengine::components::blackboard::Blackboard;
+//!
+//! let mut bb = Blackboard::default();
+//! bb.set("alert_level", 2);
+//! bb.set("state", "patrol");
+//! bb.set_with_ttl("heard_noise", true, 1.5);
+//!
+//! assert_eq!(bb.get_integer("alert_level"), Some(2));
+//! bb.tick(2.0);
+//! assert!(!bb.contains("heard_noise"));
+//! ```
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+
+/// A typed blackboard value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlackboardValue {
+    Scalar(f32),
+    Integer(i32),
+    Bool(bool),
+    Text(String),
+    Vec2(Vector2),
+    Entity(Entity),
+}
+
+/// One blackboard entry: value plus optional remaining lifetime in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlackboardEntry {
+    pub value: BlackboardValue,
+    pub ttl: Option<f32>,
+}
+
+impl From<f32> for BlackboardValue {
+    fn from(v: f32) -> Self {
+        BlackboardValue::Scalar(v)
+    }
+}
+
+impl From<i32> for BlackboardValue {
+    fn from(v: i32) -> Self {
+        BlackboardValue::Integer(v)
+    }
+}
+
+impl From<bool> for BlackboardValue {
+    fn from(v: bool) -> Self {
+        BlackboardValue::Bool(v)
+    }
+}
+
+impl From<&str> for BlackboardValue {
+    fn from(v: &str) -> Self {
+        BlackboardValue::Text(v.to_string())
+    }
+}
+
+impl From<String> for BlackboardValue {
+    fn from(v: String) -> Self {
+        BlackboardValue::Text(v)
+    }
+}
+
+impl From<Vector2> for BlackboardValue {
+    fn from(v: Vector2) -> Self {
+        BlackboardValue::Vec2(v)
+    }
+}
+
+impl From<Entity> for BlackboardValue {
+    fn from(v: Entity) -> Self {
+        BlackboardValue::Entity(v)
+    }
+}
+
+/// Typed key-value AI memory with optional per-entry TTL.
+#[derive(Debug, Clone, Component, Default)]
+pub struct Blackboard {
+    entries: FxHashMap<String, BlackboardEntry>,
+    /// Number of entries with a TTL, so the TTL system can skip boards without any.
+    expiring: usize,
+}
+
+impl Blackboard {
+    /// Store `value` under `key` with no expiry, replacing any previous entry.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<BlackboardValue>) {
+        self.insert(key.into(), value.into(), None);
+    }
+
+    /// Store `value` under `key`, removing it after `ttl` seconds.
+    pub fn set_with_ttl(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<BlackboardValue>,
+        ttl: f32,
+    ) {
+        self.insert(key.into(), value.into(), Some(ttl));
+    }
+
+    fn insert(&mut self, key: String, value: BlackboardValue, ttl: Option<f32>) {
+        let new = BlackboardEntry { value, ttl };
+        if new.ttl.is_some() {
+            self.expiring += 1;
+        }
+        if let Some(old) = self.entries.insert(key, new)
+            && old.ttl.is_some()
+        {
+            self.expiring -= 1;
+        }
+    }
+
+    /// Raw value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&BlackboardValue> {
+        self.entries.get(key).map(|e| &e.value)
+    }
+
+    pub fn get_scalar(&self, key: &str) -> Option<f32> {
+        match self.get(key)? {
+            BlackboardValue::Scalar(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_integer(&self, key: &str) -> Option<i32> {
+        match self.get(key)? {
+            BlackboardValue::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            BlackboardValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            BlackboardValue::Text(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn get_vec2(&self, key: &str) -> Option<Vector2> {
+        match self.get(key)? {
+            BlackboardValue::Vec2(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn get_entity(&self, key: &str) -> Option<Entity> {
+        match self.get(key)? {
+            BlackboardValue::Entity(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Seconds left before `key` expires, or `None` if it is missing or permanent.
+    pub fn remaining_ttl(&self, key: &str) -> Option<f32> {
+        self.entries.get(key).and_then(|e| e.ttl)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Remove `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<BlackboardValue> {
+        let old = self.entries.remove(key)?;
+        if old.ttl.is_some() {
+            self.expiring -= 1;
+        }
+        Some(old.value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.expiring = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// `true` if any entry has a TTL.
+    pub fn has_expiring(&self) -> bool {
+        self.expiring > 0
+    }
+
+    /// Iterate over `(key, entry)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BlackboardEntry)> {
+        self.entries.iter().map(|(k, e)| (k.as_str(), e))
+    }
+
+    /// Count down every TTL by `dt` seconds and drop expired entries.
+    /// Returns the number of entries removed.
+    pub fn tick(&mut self, dt: f32) -> usize {
+        if self.expiring == 0 {
+            return 0;
+        }
+        let before = self.entries.len();
+        self.entries.retain(|_, e| match e.ttl.as_mut() {
+            Some(ttl) => {
+                *ttl -= dt;
+                *ttl > 0.0
+            }
+            None => true,
+        });
+        let removed = before - self.entries.len();
+        self.expiring -= removed;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_getters_only_match_their_type() {
+        let mut bb = Blackboard::default();
+        bb.set("speed", 1.5);
+        bb.set("target", Entity::from_raw_u32(42).unwrap());
+        bb.set("home", Vector2 { x: 3.0, y: 4.0 });
+        bb.set("state", "patrol");
+
+        assert_eq!(bb.get_scalar("speed"), Some(1.5));
+        assert_eq!(bb.get_integer("speed"), None);
+        assert_eq!(
+            bb.get_entity("target"),
+            Some(Entity::from_raw_u32(42).unwrap())
+        );
+        assert_eq!(bb.get_vec2("home"), Some(Vector2 { x: 3.0, y: 4.0 }));
+        assert_eq!(bb.get_str("state"), Some("patrol"));
+        assert_eq!(bb.get_bool("missing"), None);
+    }
+
+    #[test]
+    fn tick_expires_only_ttl_entries() {
+        let mut bb = Blackboard::default();
+        bb.set("permanent", true);
+        bb.set_with_ttl("short", 1, 0.5);
+        bb.set_with_ttl("long", 2, 2.0);
+
+        assert_eq!(bb.tick(1.0), 1);
+        assert!(bb.contains("permanent"));
+        assert!(!bb.contains("short"));
+        assert!((bb.remaining_ttl("long").unwrap() - 1.0).abs() < 1e-6);
+
+        assert_eq!(bb.tick(1.0), 1);
+        assert!(!bb.has_expiring());
+        assert_eq!(bb.len(), 1);
+    }
+
+    #[test]
+    fn overwriting_and_removing_keeps_expiring_count() {
+        let mut bb = Blackboard::default();
+        bb.set_with_ttl("noise", true, 1.0);
+        bb.set("noise", false);
+        assert!(!bb.has_expiring());
+
+        bb.set_with_ttl("noise", true, 1.0);
+        assert!(bb.has_expiring());
+        assert_eq!(bb.remove("noise"), Some(BlackboardValue::Bool(true)));
+        assert!(!bb.has_expiring());
+        assert_eq!(bb.tick(5.0), 0);
+    }
+}
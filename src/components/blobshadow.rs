@@ -0,0 +1,163 @@
+//! Ground shadow blobs.
+//!
+//! A [`BlobShadow`] draws a translucent ellipse on the ground under an
+//! entity's [`Sprite`](super::sprite::Sprite), right before the sprite, so
+//! it lands over ground tiles drawn at a lower [`ZIndex`](super::zindex::ZIndex)
+//! and under the sprite itself. Unlike the drop [`Shadow`](super::shadow::Shadow),
+//! which repeats the sprite's silhouette, a blob tells how high above the
+//! ground the entity is: it shrinks and fades as the entity rises, down to
+//! [`BLOB_MIN_FACTOR`] of its size and opacity at `max_height`.
+//!
+//! The height comes from one of:
+//!
+//! - the entity's faux-3D [`Height`](super::height::Height), which lifts the
+//!   sprite while the position stays on the ground — used over the two
+//!   below when present;
+//! - `height_signal`, a scalar (or integer) of the entity's
+//!   [`Signals`](super::signals::Signals) — a top-down jump arc that raises
+//!   the sprite while the position stays on the ground;
+//! - `ground_y`, a fixed world Y the blob stays on while the entity moves
+//!   above it — a platformer character over a flat floor. The height is the
+//!   distance from the entity down to `ground_y`.
+//!
+//! With neither, the blob stays at full size under the entity.
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Color, Vector2};
+
+use crate::components::signals::Signals;
+use crate::key::Key;
+
+/// Share of its size and opacity a blob keeps at `max_height`.
+pub const BLOB_MIN_FACTOR: f32 = 0.4;
+
+/// Ellipse drawn under the entity. See the module docs.
+#[derive(Component, Clone, Debug)]
+pub struct BlobShadow {
+    /// Ellipse width and height on the ground.
+    pub size: Vector2,
+    /// Ellipse centre relative to the entity position (or to `ground_y`).
+    pub offset: Vector2,
+    /// Opacity on the ground, `0.0`–`1.0`.
+    pub opacity: f32,
+    /// World Y the blob stays on, whatever the entity's Y.
+    pub ground_y: Option<f32>,
+    /// Key of the entity's `Signals` holding its height above the ground.
+    pub height_signal: Option<Key>,
+    /// Height at which the blob is smallest and faintest.
+    pub max_height: f32,
+}
+
+/// A [`BlobShadow`] resolved for drawing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlobShadowShape {
+    pub center: Vector2,
+    pub radius_h: f32,
+    pub radius_v: f32,
+    pub color: Color,
+}
+
+impl BlobShadow {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            size: Vector2::new(width.max(0.0), height.max(0.0)),
+            offset: Vector2::zero(),
+            opacity: 0.5,
+            ground_y: None,
+            height_signal: None,
+            max_height: 64.0,
+        }
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_ground_y(mut self, ground_y: f32) -> Self {
+        self.ground_y = Some(ground_y);
+        self
+    }
+
+    pub fn with_height_signal(mut self, key: impl Into<Key>) -> Self {
+        self.height_signal = Some(key.into());
+        self
+    }
+
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    /// Height of an entity at `pos` above the ground; never negative.
+    pub fn height(&self, pos: Vector2, signals: Option<&Signals>) -> f32 {
+        let height = match (&self.height_signal, signals) {
+            (Some(key), Some(signals)) => signals
+                .get_scalar(key)
+                .or_else(|| signals.get_integer(key).map(|h| h as f32))
+                .unwrap_or(0.0),
+            (Some(_), None) => 0.0,
+            (None, _) => self.ground_y.map_or(0.0, |ground| ground - pos.y),
+        };
+        height.max(0.0)
+    }
+
+    /// Where and how the blob of an entity at `pos` is drawn.
+    pub fn shape(&self, pos: Vector2, signals: Option<&Signals>) -> BlobShadowShape {
+        self.shape_at_height(pos, self.height(pos, signals))
+    }
+
+    /// Where and how the blob of an entity at `pos`, `height` above the
+    /// ground, is drawn.
+    pub fn shape_at_height(&self, pos: Vector2, height: f32) -> BlobShadowShape {
+        let t = if self.max_height > 0.0 {
+            (height.max(0.0) / self.max_height).min(1.0)
+        } else {
+            0.0
+        };
+        let factor = 1.0 - t * (1.0 - BLOB_MIN_FACTOR);
+        BlobShadowShape {
+            center: Vector2::new(
+                pos.x + self.offset.x,
+                self.ground_y.unwrap_or(pos.y) + self.offset.y,
+            ),
+            radius_h: self.size.x * 0.5 * factor,
+            radius_v: self.size.y * 0.5 * factor,
+            color: Color::new(0, 0, 0, (self.opacity * factor * 255.0).round() as u8),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blobs_shrink_and_fade_with_height() {
+        let blob = BlobShadow::new(20.0, 8.0).with_max_height(50.0);
+        let on_ground = blob.shape(Vector2::new(10.0, 30.0), None);
+        assert_eq!(on_ground.center, Vector2::new(10.0, 30.0));
+        assert_eq!((on_ground.radius_h, on_ground.radius_v), (10.0, 4.0));
+        assert_eq!(on_ground.color.a, 128);
+
+        let mut signals = Signals::default();
+        signals.set_scalar("height", 100.0);
+        let jumping = blob
+            .clone()
+            .with_height_signal("height")
+            .shape(Vector2::new(10.0, 30.0), Some(&signals));
+        assert!((jumping.radius_h - 10.0 * BLOB_MIN_FACTOR).abs() < 1e-4);
+
+        // Pinned to the floor, half way up
+        let floor = blob
+            .with_ground_y(100.0)
+            .shape(Vector2::new(0.0, 75.0), None);
+        assert_eq!(floor.center, Vector2::new(0.0, 100.0));
+        assert!((floor.radius_h - 7.0).abs() < 1e-4);
+    }
+}
@@ -15,6 +15,12 @@
 //!
 //! The AABB is computed as: `(position - origin + offset)` to `(position - origin + offset + size)`
 //!
+//! # Disabling
+//!
+//! Insert [`ColliderDisabled`] to make the collision detector ignore an entity
+//! (invulnerability frames, ghost phases) while keeping its `BoxCollider`
+//! parameters intact. Remove the marker to re-enable it.
+//!
 //! # Related
 //!
 //! - [`crate::systems::collision_detector`] – collision detection system
@@ -100,6 +106,10 @@ impl BoxCollider {
     }
 }
 
+/// Marker that excludes an entity's [`BoxCollider`] from collision detection.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ColliderDisabled;
+
 #[cfg(test)]
 mod tests {
     use super::*;
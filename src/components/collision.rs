@@ -7,8 +7,25 @@
 //! # Group-Based Collision
 //!
 //! Collision rules match entities by their [`Group`](super::group::Group)
-//! component. When two entities collide, the observer looks up rules that match
-//! both groups and invokes the corresponding callback.
+//! component or any of their [`Tags`](super::tags::Tags). When two entities
+//! collide, the observer looks up rules that match both entities and invokes
+//! the corresponding callback.
+//!
+//! # Group Patterns
+//!
+//! Each side of a rule is a pattern rather than a plain name:
+//!
+//! - `"brick"` – exact group name or tag
+//! - `"*"` – any labeled entity
+//! - `"enemy_*"` – `*` matches any run of characters
+//! - `"enemy|boss"` – `|` separates alternatives; any may match
+//!
+//! When several rules match one collision they run in descending
+//! [`priority`](CollisionRule::priority) order (ties keep spawn order). An
+//! [`exclusive`](CollisionRule::exclusive) rule (the default) stops dispatch
+//! after its callback; non-exclusive rules let lower-priority matches run too.
+//! A rule with [`enabled`](CollisionRule::enabled) set to `false` is skipped
+//! entirely, which lets a game toggle an interaction without respawning it.
 //!
 //! # Example
 //!
@@ -62,17 +79,25 @@ pub type CollisionCallback =
 ///
 /// When a collision is detected between entities with groups matching
 /// `group_a` and `group_b`, the `callback` is invoked with the entities and
-/// collision context.
+/// collision context. Both sides accept the patterns described in the
+/// [module docs](self#group-patterns).
 #[derive(Component, Clone, Debug)]
 pub struct CollisionRule<C = CollisionCallback> {
-    /// First group name to match.
+    /// First group pattern to match.
     pub group_a: String,
-    /// Second group name to match.
+    /// Second group pattern to match.
     pub group_b: String,
     /// Callback payload — a Rust fn pointer for `CollisionRule`, or a
     /// [`LuaCollisionCallback`](crate::components::luacollision::LuaCollisionCallback)
     /// for `LuaCollisionRule`.
     pub callback: C,
+    /// Dispatch order among rules matching the same collision; higher runs first.
+    pub priority: i32,
+    /// When `true` (the default), no further rules run for the collision after
+    /// this one.
+    pub exclusive: bool,
+    /// Disabled rules never match. Defaults to `true`.
+    pub enabled: bool,
 }
 
 impl<C> CollisionRule<C> {
@@ -82,9 +107,38 @@ impl<C> CollisionRule<C> {
             group_a: group_a.into(),
             group_b: group_b.into(),
             callback,
+            priority: 0,
+            exclusive: true,
+            enabled: true,
         }
     }
 
+    /// Set the dispatch priority (higher runs first).
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set whether this rule stops lower-priority matches from running.
+    pub fn with_exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Set whether this rule is active.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns `true` if this rule was declared for the pattern pair
+    /// `(group_a, group_b)`, in either order. Patterns are compared verbatim,
+    /// not matched.
+    pub fn is_for_pair(&self, group_a: &str, group_b: &str) -> bool {
+        (self.group_a == group_a && self.group_b == group_b)
+            || (self.group_a == group_b && self.group_b == group_a)
+    }
+
     /// Check if this rule matches the given groups and return entities in order.
     ///
     /// Returns `Some((entity_a, entity_b))` if the rule matches, with entities
@@ -96,8 +150,33 @@ impl<C> CollisionRule<C> {
         group_a: &str,
         group_b: &str,
     ) -> Option<(Entity, Entity)> {
+        if !self.enabled {
+            return None;
+        }
         match_groups(&self.group_a, &self.group_b, ent_a, ent_b, group_a, group_b)
     }
+
+    /// Like [`match_and_order`](Self::match_and_order), but each entity may
+    /// carry several labels (its group plus any [`Tags`](super::tags::Tags)).
+    pub fn match_labels_and_order(
+        &self,
+        ent_a: Entity,
+        ent_b: Entity,
+        labels_a: &[&str],
+        labels_b: &[&str],
+    ) -> Option<(Entity, Entity)> {
+        if !self.enabled {
+            return None;
+        }
+        match_labels(
+            &self.group_a,
+            &self.group_b,
+            ent_a,
+            ent_b,
+            labels_a,
+            labels_b,
+        )
+    }
 }
 
 impl CollisionRule<CollisionCallback> {
@@ -116,6 +195,39 @@ impl CollisionRule<CollisionCallback> {
     }
 }
 
+/// Check whether a single label satisfies a rule pattern.
+///
+/// See the [module docs](self#group-patterns) for the syntax. A pattern
+/// without `*` or `|` is compared for exact equality.
+pub fn pattern_matches(pattern: &str, label: &str) -> bool {
+    pattern
+        .split('|')
+        .any(|alt| glob_matches(alt.as_bytes(), label.as_bytes()))
+}
+
+/// `*`-only glob match (no escapes, no `?`).
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((sp, st)) = star {
+            // Let the last `*` absorb one more character and retry.
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
 /// Check if a collision rule's groups match the given group names and return
 /// entities ordered to match `rule_a` and `rule_b`.
 ///
@@ -128,15 +240,36 @@ pub fn match_groups(
     ga: &str,
     gb: &str,
 ) -> Option<(Entity, Entity)> {
-    if rule_a == ga && rule_b == gb {
+    if pattern_matches(rule_a, ga) && pattern_matches(rule_b, gb) {
+        Some((ent_a, ent_b))
+    } else if pattern_matches(rule_a, gb) && pattern_matches(rule_b, ga) {
+        Some((ent_b, ent_a))
+    } else {
+        None
+    }
+}
+
+/// Multi-label variant of [`match_groups`]: the rule matches when one entity
+/// carries `rule_a` among its labels and the other carries `rule_b`.
+pub fn match_labels(
+    rule_a: &str,
+    rule_b: &str,
+    ent_a: Entity,
+    ent_b: Entity,
+    labels_a: &[&str],
+    labels_b: &[&str],
+) -> Option<(Entity, Entity)> {
+    let side = |pattern: &str, labels: &[&str]| labels.iter().any(|l| pattern_matches(pattern, l));
+    if side(rule_a, labels_a) && side(rule_b, labels_b) {
         Some((ent_a, ent_b))
-    } else if rule_a == gb && rule_b == ga {
+    } else if side(rule_a, labels_b) && side(rule_b, labels_a) {
         Some((ent_b, ent_a))
     } else {
         None
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BoxSide {
     Left,
     Right,
@@ -523,6 +656,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_match_labels_any_tag() {
+        let ent_a = Entity::from_bits(1);
+        let ent_b = Entity::from_bits(2);
+        // Entity B is an "enemy" that is also tagged "flying"
+        let (la, lb) = (&["bullet"][..], &["enemy", "flying"][..]);
+        assert_eq!(
+            match_labels("flying", "bullet", ent_a, ent_b, la, lb),
+            Some((ent_b, ent_a))
+        );
+        assert_eq!(
+            match_labels("bullet", "flying", ent_a, ent_b, la, lb),
+            Some((ent_a, ent_b))
+        );
+        assert_eq!(match_labels("bullet", "ground", ent_a, ent_b, la, lb), None);
+    }
+
+    #[test]
+    fn test_pattern_matches_wildcards_and_lists() {
+        assert!(pattern_matches("*", "anything"));
+        assert!(pattern_matches("enemy_*", "enemy_bat"));
+        assert!(pattern_matches("enemy_*", "enemy_"));
+        assert!(!pattern_matches("enemy_*", "enemy"));
+        assert!(pattern_matches("*_boss", "final_boss"));
+        assert!(pattern_matches("e*y", "enemy"));
+        assert!(pattern_matches("player|ally", "ally"));
+        assert!(pattern_matches("brick|enemy_*", "enemy_bat"));
+        assert!(!pattern_matches("brick|enemy_*", "player"));
+        assert!(!pattern_matches("ball", "balls"));
+    }
+
+    #[test]
+    fn test_match_groups_wildcard_orders_entities() {
+        let ent_a = Entity::from_bits(1);
+        let ent_b = Entity::from_bits(2);
+        assert_eq!(
+            match_groups(
+                "player_projectile",
+                "enemy_*",
+                ent_a,
+                ent_b,
+                "enemy_bat",
+                "player_projectile"
+            ),
+            Some((ent_b, ent_a))
+        );
+    }
+
+    #[test]
+    fn collision_rule_defaults_to_exclusive_priority_zero() {
+        let rule = CollisionRule::new("a", "b", ()).with_priority(5);
+        assert_eq!(rule.priority, 5);
+        assert!(rule.exclusive);
+        assert!(!rule.with_exclusive(false).exclusive);
+    }
+
+    #[test]
+    fn disabled_rule_never_matches() {
+        let a = Entity::from_bits(1);
+        let b = Entity::from_bits(2);
+        let rule = CollisionRule::new("ball", "brick", ()).with_enabled(false);
+        assert_eq!(rule.match_and_order(a, b, "ball", "brick"), None);
+        assert_eq!(
+            rule.match_labels_and_order(a, b, &["ball"], &["brick"]),
+            None
+        );
+        assert!(rule.is_for_pair("brick", "ball"));
+        assert!(!rule.is_for_pair("ball", "*"));
+    }
+
     #[test]
     fn collision_rule_rust_ctor_accepts_fn_without_cast() {
         fn cb(_: Entity, _: Entity, _: &BoxSides, _: &BoxSides, _: &mut GameCtx) {}
@@ -0,0 +1,151 @@
+//! Ability and power-up cooldowns.
+//!
+//! A [`Cooldown`] counts down from its `duration` once started. Every frame
+//! `cooldown_system` writes the remaining fraction — `1.0` just started,
+//! `0.0` ready — to a scalar signal on the entity's
+//! [`Signals`](super::signals::Signals), and optionally to
+//! [`WorldSignals`](crate::resources::worldsignals::WorldSignals) under the
+//! same key. HUD widgets bind a bar or radial fill to that signal instead of
+//! timing the cooldown in scripts.
+//!
+//! A manual cooldown stays ready until it is started again; an auto-reset
+//! cooldown starts over as soon as it elapses.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("player"),
+//!     Cooldown::new(3.0, "dash_cd").with_world_signal(),
+//! ));
+//! // The dash button's bar: max 1.0, filled by the fraction.
+//! commands.spawn((
+//!     ScreenPosition::new(8.0, 8.0),
+//!     ZIndex(10.0),
+//!     GuiProgressBar::new(48.0, 6.0, 0.0, 1.0).with_signal_binding("dash_cd"),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::cooldown::cooldown_system`] – countdown and signal mirroring
+
+use bevy_ecs::prelude::Component;
+
+use crate::key::Key;
+
+/// Countdown gating an action. See the module docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Cooldown {
+    /// Seconds from start to ready.
+    pub duration: f32,
+    /// Seconds left; `0.0` when ready.
+    pub remaining: f32,
+    /// Start over as soon as the cooldown elapses.
+    pub auto_reset: bool,
+    /// Scalar signal receiving the remaining fraction.
+    pub signal: Key,
+    /// Also write the fraction to `WorldSignals`.
+    pub world_signal: bool,
+}
+
+impl Cooldown {
+    /// A ready cooldown of `duration` seconds mirrored to signal `signal`.
+    pub fn new(duration: f32, signal: impl Into<Key>) -> Self {
+        Self {
+            duration: duration.max(0.0),
+            remaining: 0.0,
+            auto_reset: false,
+            signal: signal.into(),
+            world_signal: false,
+        }
+    }
+
+    pub fn with_auto_reset(mut self) -> Self {
+        self.auto_reset = true;
+        self
+    }
+
+    pub fn with_world_signal(mut self) -> Self {
+        self.world_signal = true;
+        self
+    }
+
+    /// Starts cooling down right away instead of ready.
+    pub fn started(mut self) -> Self {
+        self.start();
+        self
+    }
+
+    /// Starts the countdown over from the full duration.
+    pub fn start(&mut self) {
+        self.remaining = self.duration;
+    }
+
+    /// Makes the cooldown ready now.
+    pub fn reset(&mut self) {
+        self.remaining = 0.0;
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    /// Remaining share of the duration, from `1.0` (just started) to `0.0`
+    /// (ready).
+    pub fn fraction(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.remaining / self.duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Advances the countdown by `delta` seconds. Returns `true` on the tick
+    /// the cooldown elapses.
+    pub fn tick(&mut self, delta: f32) -> bool {
+        if self.is_ready() {
+            return false;
+        }
+        self.remaining -= delta;
+        if self.remaining > 0.0 {
+            return false;
+        }
+        // Carry the overshoot into the next cycle so the period stays exact.
+        self.remaining = if self.auto_reset && self.duration > 0.0 {
+            self.duration - (-self.remaining) % self.duration
+        } else {
+            0.0
+        };
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_cooldown_waits_to_be_started_again() {
+        let mut cd = Cooldown::new(2.0, "cd");
+        assert!(cd.is_ready());
+        assert!(!cd.tick(1.0));
+
+        cd.start();
+        assert_eq!(cd.fraction(), 1.0);
+        assert!(!cd.tick(0.5));
+        assert_eq!(cd.fraction(), 0.75);
+        assert!(cd.tick(1.5));
+        assert!(cd.is_ready());
+        assert!(!cd.tick(5.0));
+        assert_eq!(cd.fraction(), 0.0);
+    }
+
+    #[test]
+    fn auto_reset_cooldown_starts_over() {
+        let mut cd = Cooldown::new(1.0, "cd").with_auto_reset().started();
+        assert!(cd.tick(1.25));
+        assert!((cd.remaining - 0.75).abs() < 1e-6);
+        assert!(!cd.is_ready());
+    }
+}
@@ -0,0 +1,242 @@
+//! Scrolling credits.
+//!
+//! A [`CreditsRoll`] stacks styled lines in screen space, each centred
+//! horizontally, and scrolls them from the bottom of the screen up at
+//! `speed` pixels per second. Holding `action_1` or `action_2` scrolls
+//! `fast_forward` times faster. Once the last line leaves the top of the
+//! screen the roll despawns and triggers a
+//! [`CreditsFinished`](crate::events::credits::CreditsFinished) event.
+//!
+//! Lines can come from code, from Lua or from a text file, where `# ` starts
+//! a title line, `## ` a heading line and an empty line leaves a gap:
+//!
+//! ```text
+//! # MY GAME
+//!
+//! ## Programming
+//! Jane Doe
+//! ```
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn(CreditsRoll::load("./assets/credits.txt")?.with_callback("credits_done"));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::credits::credits_roll_system`] – spawns, scrolls and closes rolls
+
+use std::path::Path;
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Color;
+
+/// Look of a credits line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreditsStyle {
+    /// Twice the font size, in the heading color.
+    Title,
+    /// One and a half times the font size, in the heading color.
+    Heading,
+    #[default]
+    Normal,
+}
+
+impl CreditsStyle {
+    /// Parse a Lua style name; unknown names are `Normal`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "title" => CreditsStyle::Title,
+            "heading" => CreditsStyle::Heading,
+            _ => CreditsStyle::Normal,
+        }
+    }
+
+    fn scale(self) -> f32 {
+        match self {
+            CreditsStyle::Title => 2.0,
+            CreditsStyle::Heading => 1.5,
+            CreditsStyle::Normal => 1.0,
+        }
+    }
+}
+
+/// One line of a [`CreditsRoll`]; empty text leaves a gap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditsLine {
+    pub text: String,
+    pub style: CreditsStyle,
+    /// Overrides the roll's color for this line.
+    pub color: Option<Color>,
+}
+
+impl CreditsLine {
+    pub fn new(text: impl Into<String>, style: CreditsStyle) -> Self {
+        Self {
+            text: text.into(),
+            style,
+            color: None,
+        }
+    }
+}
+
+/// Screen-space credits scrolling upwards, closed when the last line is gone.
+#[derive(Component, Debug, Clone)]
+pub struct CreditsRoll {
+    pub lines: Vec<CreditsLine>,
+    /// Horizontal centre of the lines; the screen centre when `None`.
+    pub center_x: Option<f32>,
+    /// Scroll speed in pixels per second.
+    pub speed: f32,
+    /// Speed multiplier while `action_1` or `action_2` is held.
+    pub fast_forward: f32,
+    pub font: String,
+    /// Size of normal lines; titles and headings are scaled from it.
+    pub font_size: f32,
+    pub color: Color,
+    /// Color of title and heading lines.
+    pub heading_color: Color,
+    /// Line height as a multiple of the line's font size.
+    pub line_spacing: f32,
+    pub z_index: f32,
+    /// Lua function called when the roll ends.
+    pub callback: Option<String>,
+    /// Pixels scrolled so far.
+    pub scroll: f32,
+    /// Y of the first line before scrolling, set to the screen height on open.
+    pub(crate) start_y: f32,
+    /// Text entity of each line; `None` for gaps.
+    pub(crate) texts: Vec<Option<Entity>>,
+    pub(crate) opened: bool,
+}
+
+impl CreditsRoll {
+    pub fn new(lines: Vec<CreditsLine>) -> Self {
+        Self {
+            lines,
+            center_x: None,
+            speed: 40.0,
+            fast_forward: 4.0,
+            font: "default".into(),
+            font_size: 20.0,
+            color: Color::WHITE,
+            heading_color: Color::YELLOW,
+            line_spacing: 1.4,
+            z_index: 100.0,
+            callback: None,
+            scroll: 0.0,
+            start_y: 0.0,
+            texts: Vec::new(),
+            opened: false,
+        }
+    }
+
+    /// Lines from credits text: `# ` titles, `## ` headings, plain lines.
+    pub fn parse(text: &str) -> Vec<CreditsLine> {
+        text.lines()
+            .map(|line| {
+                let line = line.trim_end();
+                if let Some(rest) = line.strip_prefix("## ") {
+                    CreditsLine::new(rest, CreditsStyle::Heading)
+                } else if let Some(rest) = line.strip_prefix("# ") {
+                    CreditsLine::new(rest, CreditsStyle::Title)
+                } else {
+                    CreditsLine::new(line, CreditsStyle::Normal)
+                }
+            })
+            .collect()
+    }
+
+    /// Reads the lines from a credits text file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read credits {}: {e}", path.display()))?;
+        Ok(Self::new(Self::parse(&text)))
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn with_callback(mut self, callback: impl Into<String>) -> Self {
+        self.callback = Some(callback.into());
+        self
+    }
+
+    /// Font size of line `index`.
+    pub fn line_font_size(&self, index: usize) -> f32 {
+        self.font_size * self.lines[index].style.scale()
+    }
+
+    /// Color of line `index`.
+    pub fn line_color(&self, index: usize) -> Color {
+        let line = &self.lines[index];
+        line.color.unwrap_or(match line.style {
+            CreditsStyle::Normal => self.color,
+            _ => self.heading_color,
+        })
+    }
+
+    /// Top of each line relative to the first one.
+    pub fn line_offsets(&self) -> Vec<f32> {
+        let mut y = 0.0;
+        (0..self.lines.len())
+            .map(|index| {
+                let top = y;
+                y += self.line_font_size(index) * self.line_spacing;
+                top
+            })
+            .collect()
+    }
+
+    /// Height of all lines together.
+    pub fn total_height(&self) -> f32 {
+        (0..self.lines.len())
+            .map(|index| self.line_font_size(index) * self.line_spacing)
+            .sum()
+    }
+
+    /// Whether the last line has scrolled past the top of the screen.
+    pub fn is_finished(&self) -> bool {
+        self.scroll >= self.start_y + self.total_height()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_styles_and_gaps() {
+        let lines = CreditsRoll::parse("# GAME\n\n## Code\nJane Doe  \n");
+        assert_eq!(
+            lines,
+            [
+                CreditsLine::new("GAME", CreditsStyle::Title),
+                CreditsLine::new("", CreditsStyle::Normal),
+                CreditsLine::new("Code", CreditsStyle::Heading),
+                CreditsLine::new("Jane Doe", CreditsStyle::Normal),
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_stacks_scaled_lines() {
+        let mut roll = CreditsRoll::new(CreditsRoll::parse("# A\n## B\nC"));
+        roll.font_size = 10.0;
+        roll.line_spacing = 1.0;
+        assert_eq!(roll.line_offsets(), [0.0, 20.0, 35.0]);
+        assert_eq!(roll.total_height(), 45.0);
+        assert_eq!(roll.line_color(0), Color::YELLOW);
+        assert_eq!(roll.line_color(2), Color::WHITE);
+
+        roll.start_y = 100.0;
+        roll.scroll = 144.0;
+        assert!(!roll.is_finished());
+        roll.scroll = 145.0;
+        assert!(roll.is_finished());
+    }
+}
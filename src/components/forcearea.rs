@@ -0,0 +1,157 @@
+//! Force-field areas: wind zones, water currents and conveyor belts.
+//!
+//! A [`ForceArea`] pushes every overlapping [`RigidBody`](super::rigidbody::RigidBody)
+//! whose group or tags match its mask, once per frame, via
+//! [`force_area_system`](crate::systems::forcearea::force_area_system):
+//!
+//! - `acceleration` is integrated into the body's velocity (`velocity += a * dt`),
+//!   so friction and `max_speed` still apply — good for wind and currents.
+//! - `velocity` is a constant displacement added straight to the body's
+//!   position (`pos += v * dt`) without touching its velocity — a conveyor
+//!   belt carries the player while their own controls stay unchanged.
+//!
+//! The area is `rect` (relative to the entity's world position) when set,
+//! otherwise the entity's [`BoxCollider`](super::boxcollider::BoxCollider).
+//! Bodies with a collider overlap by AABB; bodies without one by their pivot.
+//! Frozen bodies are skipped.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Wind zone
+//! commands.spawn((
+//!     MapPosition::new(200.0, 0.0),
+//!     ForceArea::acceleration(Vector2::new(-300.0, 0.0))
+//!         .with_rect(Rectangle::new(0.0, 0.0, 100.0, 240.0))
+//!         .with_mask("player|crate"),
+//! ));
+//!
+//! // Conveyor belt shaped like its collider
+//! commands.spawn((
+//!     Group::new("ground"),
+//!     MapPosition::new(64.0, 200.0),
+//!     BoxCollider::new(96.0, 16.0),
+//!     ForceArea::conveyor(Vector2::new(60.0, 0.0)),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::forcearea`] – applies the areas each frame
+//! - [`crate::components::collision::pattern_matches`] – mask syntax
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Rectangle, Vector2};
+
+/// Area applying an acceleration and/or a velocity offset to rigid bodies
+/// inside it.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ForceArea {
+    /// Acceleration added to overlapping bodies' velocity (units/s²).
+    pub acceleration: Vector2,
+    /// Displacement per second added to overlapping bodies' position.
+    pub velocity: Vector2,
+    /// Area relative to the entity's world position; `None` uses the
+    /// entity's `BoxCollider`.
+    pub rect: Option<Rectangle>,
+    /// Group pattern of bodies affected (`"*"` by default).
+    pub mask: String,
+    /// Disabled areas affect nothing.
+    pub enabled: bool,
+}
+
+impl Default for ForceArea {
+    fn default() -> Self {
+        Self {
+            acceleration: Vector2::zero(),
+            velocity: Vector2::zero(),
+            rect: None,
+            mask: "*".into(),
+            enabled: true,
+        }
+    }
+}
+
+impl ForceArea {
+    /// Area accelerating bodies inside it (wind, currents).
+    pub fn acceleration(acceleration: Vector2) -> Self {
+        Self {
+            acceleration,
+            ..Self::default()
+        }
+    }
+
+    /// Area carrying bodies inside it at a constant velocity (conveyors).
+    pub fn conveyor(velocity: Vector2) -> Self {
+        Self {
+            velocity,
+            ..Self::default()
+        }
+    }
+
+    /// Use an explicit rectangle instead of the entity's collider.
+    pub fn with_rect(mut self, rect: Rectangle) -> Self {
+        self.rect = Some(rect);
+        self
+    }
+
+    /// Restrict the area to bodies whose group or tags match `mask`.
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Set the initial enabled state.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// World-space area for an entity at `world_pos`, given its optional
+    /// collider rectangle. `None` when there is neither a rect nor a collider.
+    pub fn world_rect(&self, world_pos: Vector2, collider: Option<Rectangle>) -> Option<Rectangle> {
+        match self.rect {
+            Some(r) => Some(Rectangle::new(
+                world_pos.x + r.x,
+                world_pos.y + r.y,
+                r.width,
+                r.height,
+            )),
+            None => collider,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructors_set_one_effect_and_match_everything() {
+        let wind = ForceArea::acceleration(Vector2::new(-10.0, 0.0));
+        assert_eq!(wind.velocity, Vector2::zero());
+        assert_eq!(wind.mask, "*");
+        assert!(wind.enabled);
+
+        let belt = ForceArea::conveyor(Vector2::new(5.0, 0.0)).with_enabled(false);
+        assert_eq!(belt.acceleration, Vector2::zero());
+        assert!(!belt.enabled);
+    }
+
+    #[test]
+    fn world_rect_prefers_explicit_rect() {
+        let collider = Rectangle::new(1.0, 2.0, 3.0, 4.0);
+        let area = ForceArea::conveyor(Vector2::zero());
+        assert_eq!(
+            area.world_rect(Vector2::new(10.0, 10.0), Some(collider)),
+            Some(collider)
+        );
+        assert_eq!(area.world_rect(Vector2::zero(), None), None);
+
+        let area = area.with_rect(Rectangle::new(-5.0, 0.0, 10.0, 2.0));
+        assert_eq!(
+            area.world_rect(Vector2::new(10.0, 10.0), Some(collider)),
+            Some(Rectangle::new(5.0, 10.0, 10.0, 2.0))
+        );
+    }
+}
@@ -14,7 +14,9 @@ use raylib::math::Vector2;
 /// This component is automatically managed by the transform propagation system.
 /// For root entities (no parent), it mirrors the local MapPosition/Rotation/Scale.
 /// For child entities, it contains the composed result of the full ancestor chain.
-#[derive(Component, Clone, Copy, Debug)]
+/// It is only written when its value changes, so `Changed<GlobalTransform2D>`
+/// means the entity actually moved.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub struct GlobalTransform2D {
     /// World-space position.
     pub position: Vector2,
@@ -33,3 +35,27 @@ impl Default for GlobalTransform2D {
         }
     }
 }
+
+impl GlobalTransform2D {
+    /// Map a point from this entity's local space to world space.
+    pub fn transform_point(&self, local: Vector2) -> Vector2 {
+        let scaled = Vector2 {
+            x: local.x * self.scale.x,
+            y: local.y * self.scale.y,
+        };
+        self.position + scaled.rotated(self.rotation_degrees.to_radians())
+    }
+
+    /// Map a world-space point into this entity's local space. Returns `None`
+    /// if either scale axis is zero.
+    pub fn inverse_transform_point(&self, world: Vector2) -> Option<Vector2> {
+        if self.scale.x == 0.0 || self.scale.y == 0.0 {
+            return None;
+        }
+        let unrotated = (world - self.position).rotated(-self.rotation_degrees.to_radians());
+        Some(Vector2 {
+            x: unrotated.x / self.scale.x,
+            y: unrotated.y / self.scale.y,
+        })
+    }
+}
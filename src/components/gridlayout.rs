@@ -8,11 +8,19 @@
 //!
 //! This is useful for tile-based games where level layouts are defined
 //! externally (e.g., Arkanoid brick patterns, puzzle grids).
+//!
+//! An optional [`GridIntro`] staggers the appearance of the spawned cells:
+//! each one plays a short tween preset, delayed by its position in the
+//! chosen [`IntroOrder`].
 
 use bevy_ecs::prelude::*;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
+use crate::components::portal::SpawnPointCell;
+use crate::components::signals::Signals;
+use crate::components::tween::Easing;
+
 /// A grid layout component that spawns child entities in a grid formation when spawned.
 #[derive(Component, Debug, Clone)]
 pub struct GridLayout {
@@ -24,6 +32,8 @@ pub struct GridLayout {
     pub z_index: f32,
     /// whether this layout has been initialized
     pub spawned: bool,
+    /// Optional staggered intro played by the spawned cells.
+    pub intro: Option<GridIntro>,
 }
 
 impl GridLayout {
@@ -34,8 +44,100 @@ impl GridLayout {
             group: group.into(),
             z_index,
             spawned: false,
+            intro: None,
         }
     }
+
+    /// Plays `intro` on every cell as the layout spawns.
+    pub fn with_intro(mut self, intro: GridIntro) -> Self {
+        self.intro = Some(intro);
+        self
+    }
+}
+
+/// Tween preset played by each cell of a [`GridIntro`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntroPreset {
+    /// Grows from zero to full size.
+    ScaleIn,
+    /// Falls into its cell from `height` pixels above.
+    DropIn { height: f32 },
+}
+
+/// Order in which the cells of a [`GridIntro`] start their tween.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IntroOrder {
+    /// One cell after another, row by row, left to right.
+    #[default]
+    Cells,
+    /// Whole rows at once, top row first.
+    Rows,
+    /// Whole columns at once, left column first.
+    Columns,
+    /// Diagonal waves from the top-left corner.
+    Diagonal,
+}
+
+impl std::str::FromStr for IntroOrder {
+    type Err = std::convert::Infallible;
+
+    /// Parse a Lua string into an `IntroOrder`. Unknown strings default to `Cells`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "rows" => IntroOrder::Rows,
+            "columns" => IntroOrder::Columns,
+            "diagonal" => IntroOrder::Diagonal,
+            _ => IntroOrder::Cells,
+        })
+    }
+}
+
+/// Staggered appearance of the cells spawned by a [`GridLayout`].
+///
+/// Cell `n` in the chosen order waits `n * step` seconds, then plays the
+/// preset over `duration` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridIntro {
+    pub preset: IntroPreset,
+    /// Delay added per step of the order, in seconds.
+    pub step: f32,
+    /// Length of each cell's tween, in seconds.
+    pub duration: f32,
+    pub order: IntroOrder,
+    pub easing: Easing,
+}
+
+impl GridIntro {
+    pub fn new(preset: IntroPreset, step: f32, duration: f32) -> Self {
+        Self {
+            preset,
+            step: step.max(0.0),
+            duration: duration.max(0.0),
+            order: IntroOrder::Cells,
+            easing: Easing::CubicOut,
+        }
+    }
+
+    pub fn with_order(mut self, order: IntroOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Start delay of the `index`-th spawned cell, found at `col`, `row`.
+    pub fn delay(&self, index: usize, col: usize, row: usize) -> f32 {
+        let steps = match self.order {
+            IntroOrder::Cells => index,
+            IntroOrder::Rows => row,
+            IntroOrder::Columns => col,
+            IntroOrder::Diagonal => col + row,
+        };
+        steps as f32 * self.step
+    }
 }
 
 /// Structure representing the grid layout data loaded from JSON.
@@ -47,6 +149,10 @@ pub struct GridLayoutData {
     pub cell_height: f32,
     pub grid: Vec<String>,
     pub legend: FxHashMap<char, Option<GridCell>>,
+    /// Named positions at cell centres, spawned as
+    /// [`SpawnPoint`](crate::components::portal::SpawnPoint) entities.
+    #[serde(default)]
+    pub spawn_points: Vec<SpawnPointCell>,
 }
 
 /// Structure representing a single cell in the grid layout.
@@ -58,7 +164,7 @@ pub struct GridCell {
 }
 
 /// Enum representing possible value types for grid cell properties.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum GridValue {
     Int(i64),
@@ -67,6 +173,25 @@ pub enum GridValue {
     Bool(bool),
 }
 
+/// A [`Signals`] component holding `properties`: integers, scalars, strings,
+/// and a flag for each `true` boolean.
+pub fn properties_to_signals(properties: &FxHashMap<String, GridValue>) -> Signals {
+    let mut signals = Signals::default();
+    for (key, value) in properties {
+        match value {
+            GridValue::Int(v) => signals.set_integer(key, *v as i32),
+            GridValue::Float(v) => signals.set_scalar(key, *v as f32),
+            GridValue::String(v) => signals.set_string(key, v.clone()),
+            GridValue::Bool(v) => {
+                if *v {
+                    signals.set_flag(key);
+                }
+            }
+        }
+    }
+    signals
+}
+
 impl GridLayoutData {
     /// Loads grid layout data from a JSON file at the specified path.
     pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
@@ -75,16 +200,27 @@ impl GridLayoutData {
         Ok(layout_data)
     }
 
+    /// World position of the centre of the cell at `col`, `row`.
+    pub fn cell_center(&self, col: i64, row: i64) -> (f32, f32) {
+        (
+            self.offset_x + (col as f32 * self.cell_width) + (self.cell_width * 0.5),
+            self.offset_y + (row as f32 * self.cell_height) + (self.cell_height * 0.5),
+        )
+    }
+
     /// Iterate over all defined cells with their world positions
     pub fn iter_cells(&self) -> impl Iterator<Item = (f32, f32, &GridCell)> {
+        self.iter_cells_indexed()
+            .map(|(_, _, x, y, cell)| (x, y, cell))
+    }
+
+    /// Iterate over all defined cells with their column, row and world position
+    pub fn iter_cells_indexed(&self) -> impl Iterator<Item = (usize, usize, f32, f32, &GridCell)> {
         self.grid.iter().enumerate().flat_map(move |(row, line)| {
             line.chars().enumerate().filter_map(move |(col, ch)| {
                 if let Some(Some(cell)) = self.legend.get(&ch) {
-                    let x =
-                        self.offset_x + (col as f32 * self.cell_width) + (self.cell_width * 0.5);
-                    let y =
-                        self.offset_y + (row as f32 * self.cell_height) + (self.cell_height * 0.5);
-                    Some((x, y, cell))
+                    let (x, y) = self.cell_center(col as i64, row as i64);
+                    Some((col, row, x, y, cell))
                 } else {
                     None
                 }
@@ -92,3 +228,38 @@ impl GridLayoutData {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_points_parse_and_sit_on_cell_centres() {
+        let data: GridLayoutData = serde_json::from_str(
+            r#"{
+                "offset_x": 10.0, "offset_y": 20.0, "cell_width": 16.0, "cell_height": 8.0,
+                "grid": ["R."], "legend": {"R": {"texture_key": "brick"}, ".": null},
+                "spawn_points": [{"name": "player_start", "x": 1, "y": 2, "properties": {"hp": 3}}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(data.iter_cells().count(), 1);
+        let point = &data.spawn_points[0];
+        assert_eq!(point.name, "player_start");
+        assert_eq!(point.properties.get("hp"), Some(&GridValue::Int(3)));
+        assert_eq!(data.cell_center(point.x, point.y), (34.0, 40.0));
+    }
+
+    #[test]
+    fn intro_delay_follows_the_order() {
+        let intro = GridIntro::new(IntroPreset::ScaleIn, 0.25, 0.3);
+        assert_eq!(intro.delay(4, 1, 1), 1.0);
+        let rows = intro.with_order(IntroOrder::Rows);
+        assert_eq!(rows.delay(4, 1, 2), 0.5);
+        let columns = intro.with_order(IntroOrder::Columns);
+        assert_eq!(columns.delay(4, 3, 2), 0.75);
+        let diagonal = intro.with_order("diagonal".parse().unwrap());
+        assert_eq!(diagonal.delay(0, 2, 3), 1.25);
+        assert_eq!("spiral".parse::<IntroOrder>().unwrap(), IntroOrder::Cells);
+    }
+}
@@ -30,21 +30,24 @@ use core::str;
 
 use bevy_ecs::prelude::Component;
 
+use crate::key::Key;
+
 /// Tag component used to group entities under a named label.
 ///
 /// Useful for filtering queries or broadcasting actions to a set of entities
-/// that share a common semantic group.
-#[derive(Component, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Group(pub String);
+/// that share a common semantic group. The name is an interned [`Key`], so
+/// copying and comparing groups never touches the string.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Group(pub Key);
 
 impl Group {
     /// Create a new group with the given name.
-    pub fn new(name: impl Into<String>) -> Self {
+    pub fn new(name: impl Into<Key>) -> Self {
         Group(name.into())
     }
 
     /// Get the name of the group.
     pub fn name(&self) -> &str {
-        &self.0
+        self.0.as_str()
     }
 }
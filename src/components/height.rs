@@ -0,0 +1,131 @@
+//! Faux-3D height for top-down games.
+//!
+//! A [`Height`] lifts an entity's [`Sprite`](super::sprite::Sprite) above
+//! its [`MapPosition`](super::mapposition::MapPosition), which stays on the
+//! ground: collisions, z-sorting and camera follow keep working on the ground
+//! position while the sprite is drawn `Height` pixels higher. A drop
+//! [`Shadow`](super::shadow::Shadow) stays on the ground under the lifted
+//! sprite, and a [`BlobShadow`](super::blobshadow::BlobShadow) shrinks and
+//! fades with the height.
+//!
+//! A [`ZVelocity`] moves the height under its own `gravity`, for jumps and
+//! thrown objects. `height_system` brings the entity down to height `0`,
+//! triggers a [`LandedEvent`](crate::events::landing::LandedEvent) and
+//! bounces it back up with `bounce` of its landing speed. When the entity
+//! has [`Signals`](super::signals::Signals), the system also publishes the
+//! height as the `height` scalar and sets the `airborne` flag while it is
+//! above the ground, for animation rules and scripts.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // A crate tossed up: it lands and bounces before coming to rest.
+//! commands.spawn((
+//!     MapPosition::new(100.0, 80.0),
+//!     ZIndex(1.0),
+//!     Sprite { /* ... */ },
+//!     Height(0.0),
+//!     ZVelocity::new(980.0).with_bounce(0.4).launched(300.0),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Landing speeds below this come to rest instead of bouncing.
+pub const MIN_BOUNCE_SPEED: f32 = 30.0;
+
+/// Gravity of [`ZVelocity::default`], in world units per second squared.
+pub const DEFAULT_Z_GRAVITY: f32 = 980.0;
+
+/// Height of the sprite above the entity's ground position, in world units.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Height(pub f32);
+
+/// Vertical motion of a [`Height`]. See the module docs.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ZVelocity {
+    /// Upward speed in world units per second; negative while falling.
+    pub velocity: f32,
+    /// Downward acceleration in world units per second squared.
+    pub gravity: f32,
+    /// Share of the landing speed kept when bouncing, `0.0`–`1.0`.
+    pub bounce: f32,
+}
+
+impl Default for ZVelocity {
+    fn default() -> Self {
+        Self::new(DEFAULT_Z_GRAVITY)
+    }
+}
+
+impl ZVelocity {
+    pub fn new(gravity: f32) -> Self {
+        Self {
+            velocity: 0.0,
+            gravity,
+            bounce: 0.0,
+        }
+    }
+
+    pub fn with_bounce(mut self, bounce: f32) -> Self {
+        self.bounce = bounce.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Start with an upward `speed`.
+    pub fn launched(mut self, speed: f32) -> Self {
+        self.velocity = speed;
+        self
+    }
+
+    /// Whether an entity at `height` sits still on the ground.
+    pub fn is_resting(&self, height: f32) -> bool {
+        height <= 0.0 && self.velocity <= 0.0
+    }
+
+    /// Move `height` by `delta` seconds. Returns the landing speed when the
+    /// entity touches the ground this step, bouncing it if fast enough.
+    pub fn step(&mut self, height: &mut f32, delta: f32) -> Option<f32> {
+        self.velocity -= self.gravity * delta;
+        *height += self.velocity * delta;
+        if *height > 0.0 || self.velocity > 0.0 {
+            return None;
+        }
+        let impact = -self.velocity;
+        *height = 0.0;
+        let rebound = impact * self.bounce;
+        self.velocity = if rebound >= MIN_BOUNCE_SPEED {
+            rebound
+        } else {
+            0.0
+        };
+        Some(impact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jumps_land_bounce_and_rest() {
+        let mut z = ZVelocity::new(100.0).with_bounce(0.5).launched(100.0);
+        let mut height = 0.0;
+        let mut landings = Vec::new();
+        for _ in 0..400 {
+            if z.is_resting(height) {
+                break;
+            }
+            if let Some(impact) = z.step(&mut height, 0.01) {
+                landings.push(impact);
+            }
+            assert!(height >= 0.0);
+        }
+        // Lands at about its launch speed, then bounces at half of it until
+        // the rebound drops under MIN_BOUNCE_SPEED.
+        assert_eq!(landings.len(), 2);
+        assert!((landings[0] - 100.0).abs() < 2.0);
+        assert!((landings[1] - 50.0).abs() < 2.0);
+        assert!(z.is_resting(height));
+    }
+}
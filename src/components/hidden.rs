@@ -0,0 +1,15 @@
+//! Per-entity visibility marker.
+//!
+//! An entity with [`Hidden`] keeps moving, colliding, animating and running
+//! its callbacks; the render system just skips its sprite and text, in the
+//! world pass and the screen-space pass alike. Hide or show whole groups at
+//! once with [`SetGroupVisible`](crate::systems::group::SetGroupVisible).
+//!
+//! GUI widgets (windows, buttons, labels, progress bars) are shown and hidden
+//! by adding and removing their `ScreenPosition` instead.
+
+use bevy_ecs::prelude::Component;
+
+/// Leave this entity's sprite and text out of rendering. See the module docs.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Hidden;
@@ -0,0 +1,349 @@
+//! Animation-driven hitboxes and hurtboxes.
+//!
+//! A [`Hitbox`] is the part of an entity that deals damage (a fist, a sword
+//! arc); a [`Hurtbox`] is the part that can receive it. Both hold
+//! [`FrameBoxes`]: a rectangle per frame of each animation, relative to the
+//! entity's world position, plus an optional default used when the current
+//! animation has no frame data. Frames without a rectangle are inactive, so
+//! a punch is only "live" on its impact frames.
+//!
+//! [`hitbox_system`](crate::systems::hitbox::hitbox_system) tests active
+//! hitboxes only against active hurtboxes — never against plain
+//! [`BoxCollider`](super::boxcollider::BoxCollider)s — and triggers a
+//! [`HitEvent`](crate::events::hit::HitEvent) for each new contact. A hitbox
+//! hits each target at most once per swing: the list of struck entities is
+//! reset whenever the hitbox goes inactive, the animation changes or a looping
+//! animation wraps around to an earlier frame.
+//!
+//! Frame data can be drawn in Aseprite as slices (e.g. one named `hitbox`)
+//! and imported from the sprite sheet's JSON export with
+//! [`FrameBoxes::import_aseprite`]; each frame tag becomes an animation key.
+//!
+//! Rectangles are mirrored around the pivot when the entity's
+//! [`Sprite`](super::sprite::Sprite) is flipped, so one set of frame data
+//! serves both facing directions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut hitbox = Hitbox::new().with_mask("enemy");
+//! hitbox.boxes.set_frame("punch", 2, Rectangle::new(8.0, -20.0, 14.0, 8.0));
+//! hitbox.boxes.set_frame("punch", 3, Rectangle::new(8.0, -20.0, 18.0, 8.0));
+//!
+//! commands.spawn((
+//!     Group::new("player"),
+//!     MapPosition::new(100.0, 100.0),
+//!     Animation::new("idle"),
+//!     hitbox,
+//!     Hurtbox::new(Rectangle::new(-8.0, -32.0, 16.0, 32.0)),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Rectangle, Vector2};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use smallvec::SmallVec;
+
+/// Per-animation-frame rectangles relative to the entity position.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameBoxes {
+    /// Rectangle used when the current animation has no frame data (or the
+    /// entity has no animation). `None` means inactive.
+    pub default: Option<Rectangle>,
+    /// Animation key → rectangle per frame index (`None` = inactive frame).
+    pub frames: FxHashMap<String, Vec<Option<Rectangle>>>,
+}
+
+impl FrameBoxes {
+    /// Frame boxes that are always `rect`.
+    pub fn always(rect: Rectangle) -> Self {
+        Self {
+            default: Some(rect),
+            frames: FxHashMap::default(),
+        }
+    }
+
+    /// Set the rectangle for `frame` of `animation_key`. Earlier frames
+    /// without data stay inactive.
+    pub fn set_frame(&mut self, animation_key: impl Into<String>, frame: usize, rect: Rectangle) {
+        let frames = self.frames.entry(animation_key.into()).or_default();
+        if frames.len() <= frame {
+            frames.resize(frame + 1, None);
+        }
+        frames[frame] = Some(rect);
+    }
+
+    /// Import per-frame rectangles from the slice named `slice` of an
+    /// Aseprite sprite sheet JSON export (`--data` with `--list-tags` and
+    /// `--list-slices`). Returns the number of animations imported.
+    ///
+    /// Every frame tag becomes an animation key, with frame indices counted
+    /// from the tag's first frame. As in Aseprite, a slice key holds from its
+    /// frame until the next key; frames before the first key and keys with an
+    /// empty size are inactive. Tags without an active frame are skipped, so
+    /// their animations keep using `default`. Bounds are in sheet-frame pixels
+    /// and are made relative to the entity position by subtracting `origin`
+    /// (the sprite's origin).
+    pub fn import_aseprite(
+        &mut self,
+        json: &str,
+        slice: &str,
+        origin: Vector2,
+    ) -> Result<usize, String> {
+        #[derive(Deserialize)]
+        struct Sheet {
+            meta: Meta,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Meta {
+            #[serde(default)]
+            frame_tags: Vec<FrameTag>,
+            #[serde(default)]
+            slices: Vec<Slice>,
+        }
+        #[derive(Deserialize)]
+        struct FrameTag {
+            name: String,
+            from: usize,
+            to: usize,
+        }
+        #[derive(Deserialize)]
+        struct Slice {
+            name: String,
+            keys: Vec<SliceKey>,
+        }
+        #[derive(Deserialize)]
+        struct SliceKey {
+            frame: usize,
+            bounds: Bounds,
+        }
+        #[derive(Deserialize)]
+        struct Bounds {
+            x: f32,
+            y: f32,
+            w: f32,
+            h: f32,
+        }
+
+        let sheet: Sheet = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let Some(slice) = sheet.meta.slices.iter().find(|s| s.name == slice) else {
+            return Err(format!("no slice named '{slice}'"));
+        };
+        let mut keys: Vec<&SliceKey> = slice.keys.iter().collect();
+        keys.sort_by_key(|key| key.frame);
+        let rect_at = |frame: usize| {
+            let key = keys.iter().rev().find(|key| key.frame <= frame)?;
+            let b = &key.bounds;
+            (b.w > 0.0 && b.h > 0.0)
+                .then(|| Rectangle::new(b.x - origin.x, b.y - origin.y, b.w, b.h))
+        };
+
+        let mut imported = 0;
+        for tag in &sheet.meta.frame_tags {
+            let rects: Vec<Option<Rectangle>> = (tag.from..=tag.to).map(&rect_at).collect();
+            if rects.iter().any(Option::is_some) {
+                self.frames.insert(tag.name.clone(), rects);
+                imported += 1;
+            }
+        }
+        Ok(imported)
+    }
+
+    /// Local rectangle active for the given animation state, if any.
+    ///
+    /// Animations listed in `frames` use their per-frame data exclusively
+    /// (frames past the end are inactive); others fall back to `default`.
+    pub fn active(&self, animation: Option<(&str, usize)>) -> Option<Rectangle> {
+        if let Some((key, frame)) = animation
+            && let Some(frames) = self.frames.get(key)
+        {
+            return frames.get(frame).copied().flatten();
+        }
+        self.default
+    }
+
+    /// World-space version of [`active`](Self::active), mirrored around the
+    /// pivot on flipped axes.
+    pub fn world_rect(
+        &self,
+        animation: Option<(&str, usize)>,
+        world_pos: Vector2,
+        flip_h: bool,
+        flip_v: bool,
+    ) -> Option<Rectangle> {
+        let mut r = self.active(animation)?;
+        if flip_h {
+            r.x = -(r.x + r.width);
+        }
+        if flip_v {
+            r.y = -(r.y + r.height);
+        }
+        r.x += world_pos.x;
+        r.y += world_pos.y;
+        Some(r)
+    }
+}
+
+/// Damage-dealing area of an entity.
+#[derive(Component, Debug, Clone)]
+pub struct Hitbox {
+    pub boxes: FrameBoxes,
+    /// Group pattern of hurtbox owners this hitbox can hit (`"*"` by default).
+    pub mask: String,
+    /// Entities already hit during the current swing.
+    pub hits: SmallVec<[Entity; 4]>,
+    /// Animation key of the current swing, used to reset `hits`.
+    pub swing_key: Option<String>,
+    /// Animation frame seen last, used to reset `hits` when a looping
+    /// animation wraps around.
+    pub swing_frame: usize,
+}
+
+impl Default for Hitbox {
+    fn default() -> Self {
+        Self {
+            boxes: FrameBoxes::default(),
+            mask: "*".into(),
+            hits: SmallVec::new(),
+            swing_key: None,
+            swing_frame: 0,
+        }
+    }
+}
+
+impl Hitbox {
+    /// Hitbox with no frame data yet (inactive until frames or a default are set).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hitbox that is always `rect`.
+    pub fn always(rect: Rectangle) -> Self {
+        Self {
+            boxes: FrameBoxes::always(rect),
+            ..Self::default()
+        }
+    }
+
+    /// Restrict hits to hurtbox owners whose group or tags match `mask`.
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Add a rectangle for one frame of an animation.
+    pub fn with_frame(
+        mut self,
+        animation_key: impl Into<String>,
+        frame: usize,
+        rect: Rectangle,
+    ) -> Self {
+        self.boxes.set_frame(animation_key, frame, rect);
+        self
+    }
+}
+
+/// Damage-receiving area of an entity.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct Hurtbox {
+    pub boxes: FrameBoxes,
+}
+
+impl Hurtbox {
+    /// Hurtbox that is always `rect`.
+    pub fn new(rect: Rectangle) -> Self {
+        Self {
+            boxes: FrameBoxes::always(rect),
+        }
+    }
+
+    /// Add a rectangle for one frame of an animation.
+    pub fn with_frame(
+        mut self,
+        animation_key: impl Into<String>,
+        frame: usize,
+        rect: Rectangle,
+    ) -> Self {
+        self.boxes.set_frame(animation_key, frame, rect);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_uses_frames_for_known_animations_only() {
+        let mut boxes = FrameBoxes::always(Rectangle::new(0.0, 0.0, 4.0, 4.0));
+        boxes.set_frame("punch", 2, Rectangle::new(8.0, 0.0, 10.0, 2.0));
+
+        assert_eq!(boxes.active(Some(("punch", 0))), None);
+        assert_eq!(
+            boxes.active(Some(("punch", 2))),
+            Some(Rectangle::new(8.0, 0.0, 10.0, 2.0))
+        );
+        assert_eq!(boxes.active(Some(("punch", 5))), None);
+        assert_eq!(boxes.active(Some(("idle", 0))), boxes.default);
+        assert_eq!(boxes.active(None), boxes.default);
+    }
+
+    #[test]
+    fn import_aseprite_holds_slice_keys_per_tag() {
+        let json = r##"{
+            "frames": [],
+            "meta": {
+                "frameTags": [
+                    { "name": "idle", "from": 0, "to": 1, "direction": "forward" },
+                    { "name": "punch", "from": 2, "to": 5, "direction": "forward" }
+                ],
+                "slices": [
+                    { "name": "hurtbox", "keys": [
+                        { "frame": 0, "bounds": { "x": 0, "y": 0, "w": 16, "h": 16 } }
+                    ] },
+                    { "name": "hitbox", "color": "#ff0000ff", "keys": [
+                        { "frame": 4, "bounds": { "x": 20, "y": 6, "w": 4, "h": 2 } },
+                        { "frame": 3, "bounds": { "x": 18, "y": 6, "w": 6, "h": 2 } },
+                        { "frame": 5, "bounds": { "x": 0, "y": 0, "w": 0, "h": 0 } }
+                    ] }
+                ]
+            }
+        }"##;
+        let mut boxes = FrameBoxes::default();
+        let imported = boxes
+            .import_aseprite(json, "hitbox", Vector2::new(16.0, 16.0))
+            .unwrap();
+
+        assert_eq!(imported, 1, "idle has no active hitbox frame");
+        assert_eq!(
+            boxes.frames["punch"],
+            vec![
+                None,
+                Some(Rectangle::new(2.0, -10.0, 6.0, 2.0)),
+                Some(Rectangle::new(4.0, -10.0, 4.0, 2.0)),
+                None,
+            ]
+        );
+        assert!(
+            boxes
+                .import_aseprite(json, "sword", Vector2::zero())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn world_rect_mirrors_on_flip() {
+        let boxes = FrameBoxes::always(Rectangle::new(8.0, -4.0, 10.0, 2.0));
+        let pos = Vector2::new(100.0, 50.0);
+        assert_eq!(
+            boxes.world_rect(None, pos, false, false),
+            Some(Rectangle::new(108.0, 46.0, 10.0, 2.0))
+        );
+        assert_eq!(
+            boxes.world_rect(None, pos, true, true),
+            Some(Rectangle::new(82.0, 52.0, 10.0, 2.0))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! "Press to interact" prompts.
+//!
+//! An [`Interactable`] entity waits for an *interactor* — any entity whose
+//! group or tags match its `mask` (`"player"` by default) — to come within
+//! range: a radius around the entity's position, or an overlap with the
+//! entity's [`BoxCollider`](super::boxcollider::BoxCollider). When several
+//! interactables are in range of the same interactor, only the closest one is
+//! focused.
+//!
+//! While focused, the render system shows the prompt (text and optional icon)
+//! above the entity, and pressing the `action` triggers an
+//! [`InteractEvent`](crate::events::interact::InteractEvent). Lua scripts name
+//! a `callback`, called as `fn(ctx, input, interactor_id)` with `ctx`
+//! describing the interactable entity.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("chest"),
+//!     MapPosition::new(200.0, 120.0),
+//!     Interactable::new("Open").with_radius(24.0).with_callback("on_open_chest"),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::interaction`] – range checks, focus and the action press
+//! - [`crate::components::collision::pattern_matches`] – mask syntax
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::Vector2;
+
+use crate::components::gui_themed::Themed;
+use crate::events::input::InputAction;
+use crate::key::Key;
+use crate::resources::guitheme::DEFAULT_GUI_THEME_KEY;
+
+/// How an interactor counts as in range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteractRange {
+    /// Interactor position within this distance of the entity's position.
+    Radius(f32),
+    /// Interactor collider (or position, without one) overlapping the
+    /// entity's collider. Entities without a collider are never in range.
+    Collider,
+}
+
+/// An entity the player can interact with. See the module docs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Interactable {
+    pub range: InteractRange,
+    /// Group pattern of the entities that can interact (`"player"` by default).
+    pub mask: String,
+    /// Prompt text shown while focused.
+    pub prompt: String,
+    /// Texture key of an icon drawn left of the prompt text.
+    pub icon: Option<Key>,
+    /// Prompt anchor relative to the entity's position; the prompt is
+    /// centred horizontally on it and sits above it.
+    pub prompt_offset: Vector2,
+    /// Action that triggers the interaction.
+    pub action: InputAction,
+    /// Lua function called on interaction.
+    pub callback: Option<String>,
+    pub theme_key: Arc<str>,
+    /// Whether an interactor has this entity focused. Set every frame by
+    /// [`interaction_system`](crate::systems::interaction::interaction_system).
+    pub focused: bool,
+}
+
+impl Interactable {
+    /// An interactable using its collider as range, triggered by
+    /// [`InputAction::Action1`].
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            range: InteractRange::Collider,
+            mask: "player".into(),
+            prompt: prompt.into(),
+            icon: None,
+            prompt_offset: Vector2::new(0.0, -16.0),
+            action: InputAction::Action1,
+            callback: None,
+            theme_key: Arc::from(DEFAULT_GUI_THEME_KEY),
+            focused: false,
+        }
+    }
+
+    /// Use a radius around the entity's position as range.
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.range = InteractRange::Radius(radius.max(0.0));
+        self
+    }
+
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    pub fn with_icon(mut self, tex_key: impl Into<Key>) -> Self {
+        self.icon = Some(tex_key.into());
+        self
+    }
+
+    pub fn with_prompt_offset(mut self, offset: Vector2) -> Self {
+        self.prompt_offset = offset;
+        self
+    }
+
+    pub fn with_action(mut self, action: InputAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn with_callback(mut self, callback: impl Into<String>) -> Self {
+        self.callback = Some(callback.into());
+        self
+    }
+
+    pub fn with_theme_key(mut self, key: impl Into<Arc<str>>) -> Self {
+        self.theme_key = key.into();
+        self
+    }
+}
+
+impl Themed for Interactable {
+    fn theme_key_mut(&mut self) -> &mut Arc<str> {
+        &mut self.theme_key
+    }
+}
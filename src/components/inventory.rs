@@ -0,0 +1,307 @@
+//! Item storage and its grid widget.
+//!
+//! An [`Inventory`] is a fixed number of slots, each holding one stack of a
+//! registered item (see [`ItemRegistry`](crate::resources::itemregistry::ItemRegistry)).
+//! Adding fills the existing stacks of that item up to their `max_stack`
+//! first, then empty slots in order; removing takes from the last stacks
+//! first. Both report how many items actually moved, so a full inventory is
+//! never an error.
+//!
+//! The engine's [`give_item`](crate::systems::inventory::give_item) and
+//! [`take_item`](crate::systems::inventory::take_item) wrap these methods and
+//! trigger the inventory events; Lua uses `engine.inventory_add()` and friends.
+//!
+//! A [`GuiInventoryGrid`] draws another entity's inventory in screen space:
+//! one themed nine-patch per slot, the item icons centred in them and the
+//! stack counts in the bottom-right corner.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let bag = commands.spawn((Group::new("player"), Inventory::new(12))).id();
+//! commands.spawn((
+//!     GuiInventoryGrid::new(bag, 4, 24.0),
+//!     ScreenPosition::new(8.0, 8.0),
+//!     ZIndex(100.0),
+//! ));
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::gui_themed::Themed;
+use crate::resources::guitheme::DEFAULT_GUI_THEME_KEY;
+use crate::resources::itemregistry::ItemDef;
+
+/// A stack of identical items in one slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: Arc<str>,
+    pub count: u32,
+}
+
+/// Fixed-size item storage. See the module docs.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Inventory {
+    /// An empty inventory with `capacity` slots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: vec![None; capacity],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    pub fn slot(&self, index: usize) -> Option<&ItemStack> {
+        self.slots.get(index).and_then(Option::as_ref)
+    }
+
+    /// Total number of `item` across all slots.
+    pub fn count(&self, item: &str) -> u32 {
+        self.stacks_of(item).map(|s| s.count).sum()
+    }
+
+    /// `true` if every slot is taken (stacks may still have room).
+    pub fn is_full(&self) -> bool {
+        self.slots.iter().all(Option::is_some)
+    }
+
+    /// Add up to `count` items of `def`, topping up its stacks before using
+    /// empty slots. Returns how many were added.
+    pub fn add(&mut self, def: &ItemDef, count: u32) -> u32 {
+        let max_stack = def.max_stack.max(1);
+        let mut left = count;
+        for stack in self.slots.iter_mut().flatten() {
+            if left == 0 {
+                break;
+            }
+            if stack.item == def.id && stack.count < max_stack {
+                let moved = left.min(max_stack - stack.count);
+                stack.count += moved;
+                left -= moved;
+            }
+        }
+        for slot in self.slots.iter_mut().filter(|s| s.is_none()) {
+            if left == 0 {
+                break;
+            }
+            let moved = left.min(max_stack);
+            *slot = Some(ItemStack {
+                item: def.id.clone(),
+                count: moved,
+            });
+            left -= moved;
+        }
+        count - left
+    }
+
+    /// Remove up to `count` of `item`, emptying the last stacks first.
+    /// Returns how many were removed.
+    pub fn remove(&mut self, item: &str, count: u32) -> u32 {
+        let mut left = count;
+        for slot in self.slots.iter_mut().rev() {
+            if left == 0 {
+                break;
+            }
+            let Some(stack) = slot.as_mut().filter(|s| &*s.item == item) else {
+                continue;
+            };
+            let moved = left.min(stack.count);
+            stack.count -= moved;
+            left -= moved;
+            if stack.count == 0 {
+                *slot = None;
+            }
+        }
+        count - left
+    }
+
+    /// Swap the contents of two slots. Out-of-range indices are ignored.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a < self.slots.len() && b < self.slots.len() {
+            self.slots.swap(a, b);
+        }
+    }
+
+    /// Empty every slot and return what was removed, per item.
+    pub fn clear(&mut self) -> Vec<ItemStack> {
+        let mut removed: Vec<ItemStack> = Vec::new();
+        for stack in self.slots.iter_mut().filter_map(Option::take) {
+            match removed.iter_mut().find(|r| r.item == stack.item) {
+                Some(total) => total.count += stack.count,
+                None => removed.push(stack),
+            }
+        }
+        removed
+    }
+
+    fn stacks_of<'a>(&'a self, item: &'a str) -> impl Iterator<Item = &'a ItemStack> {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(move |s| &*s.item == item)
+    }
+}
+
+/// Screen-space grid showing the slots of the [`Inventory`] on `inventory`.
+/// Drawn by the render system at the entity's `ScreenPosition` and `ZIndex`,
+/// using the theme's `label` nine-patch for the slots (its `panel` when the
+/// theme has no label patch) and its font for the stack counts.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct GuiInventoryGrid {
+    /// Entity carrying the inventory to show.
+    pub inventory: Entity,
+    /// Slots per row (at least 1).
+    pub columns: u32,
+    /// Size of one slot, in pixels.
+    pub cell_size: f32,
+    /// Gap between slots, in pixels.
+    pub spacing: f32,
+    pub theme_key: Arc<str>,
+}
+
+impl GuiInventoryGrid {
+    pub fn new(inventory: Entity, columns: u32, cell_size: f32) -> Self {
+        Self {
+            inventory,
+            columns: columns.max(1),
+            cell_size: cell_size.max(0.0),
+            spacing: 2.0,
+            theme_key: Arc::from(DEFAULT_GUI_THEME_KEY),
+        }
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.max(0.0);
+        self
+    }
+
+    pub fn with_theme_key(mut self, key: impl Into<Arc<str>>) -> Self {
+        self.theme_key = key.into();
+        self
+    }
+
+    /// Screen rectangle of slot `index` for a grid placed at `origin`.
+    pub fn cell_rect(&self, origin: Vector2, index: usize) -> Rectangle {
+        let columns = self.columns.max(1) as usize;
+        let step = self.cell_size + self.spacing;
+        Rectangle {
+            x: origin.x + (index % columns) as f32 * step,
+            y: origin.y + (index / columns) as f32 * step,
+            width: self.cell_size,
+            height: self.cell_size,
+        }
+    }
+
+    /// Index of the slot under `point` for a grid of `capacity` slots placed
+    /// at `origin`, if any (the gaps between slots hit nothing).
+    pub fn slot_at(&self, origin: Vector2, capacity: usize, point: Vector2) -> Option<usize> {
+        let step = self.cell_size + self.spacing;
+        if step <= 0.0 || point.x < origin.x || point.y < origin.y {
+            return None;
+        }
+        let (dx, dy) = (point.x - origin.x, point.y - origin.y);
+        let (column, row) = ((dx / step) as usize, (dy / step) as usize);
+        let inside =
+            dx - column as f32 * step <= self.cell_size && dy - row as f32 * step <= self.cell_size;
+        let index = row * self.columns.max(1) as usize + column;
+        (inside && column < self.columns as usize && index < capacity).then_some(index)
+    }
+}
+
+impl Themed for GuiInventoryGrid {
+    fn theme_key_mut(&mut self) -> &mut Arc<str> {
+        &mut self.theme_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn potion() -> ItemDef {
+        ItemDef::new("potion").with_max_stack(5)
+    }
+
+    #[test]
+    fn add_tops_up_stacks_then_fills_empty_slots() {
+        let mut inv = Inventory::new(3);
+        assert_eq!(inv.add(&potion(), 3), 3);
+        assert_eq!(inv.add(&ItemDef::new("sword"), 1), 1);
+        assert_eq!(inv.add(&potion(), 4), 4);
+        assert_eq!(inv.slot(0).map(|s| s.count), Some(5));
+        assert_eq!(inv.slot(2).map(|s| s.count), Some(2));
+        assert_eq!(inv.count("potion"), 7);
+        assert!(inv.is_full());
+
+        // Only the three free places left in the last stack.
+        assert_eq!(inv.add(&potion(), 10), 3);
+        assert_eq!(inv.add(&ItemDef::new("shield"), 1), 0);
+    }
+
+    #[test]
+    fn remove_empties_the_last_stacks_first() {
+        let mut inv = Inventory::new(3);
+        inv.add(&potion(), 8);
+        assert_eq!(inv.remove("potion", 4), 4);
+        assert_eq!(inv.slot(0).map(|s| s.count), Some(4));
+        assert!(inv.slot(1).is_none());
+        assert_eq!(inv.remove("potion", 10), 4);
+        assert_eq!(inv.count("potion"), 0);
+        assert_eq!(inv.remove("sword", 1), 0);
+    }
+
+    #[test]
+    fn swap_and_clear() {
+        let mut inv = Inventory::new(4);
+        inv.add(&potion(), 6);
+        inv.add(&ItemDef::new("sword"), 1);
+        inv.swap(0, 3);
+        inv.swap(0, 9);
+        assert!(inv.slot(0).is_none());
+        assert_eq!(inv.slot(3).map(|s| s.count), Some(5));
+
+        let removed = inv.clear();
+        assert_eq!(removed.len(), 2);
+        assert_eq!(
+            removed
+                .iter()
+                .find(|s| &*s.item == "potion")
+                .map(|s| s.count),
+            Some(6)
+        );
+        assert!(inv.slots().iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn grid_maps_slots_to_cells() {
+        let grid = GuiInventoryGrid::new(Entity::from_bits(1), 3, 20.0).with_spacing(4.0);
+        let origin = Vector2::new(10.0, 10.0);
+        let cell = grid.cell_rect(origin, 4);
+        assert_eq!((cell.x, cell.y), (34.0, 34.0));
+        assert_eq!(grid.slot_at(origin, 6, Vector2::new(40.0, 40.0)), Some(4));
+        assert_eq!(
+            grid.slot_at(origin, 6, Vector2::new(32.0, 12.0)),
+            None,
+            "gap"
+        );
+        assert_eq!(
+            grid.slot_at(origin, 4, Vector2::new(40.0, 40.0)),
+            None,
+            "past capacity"
+        );
+        assert_eq!(grid.slot_at(origin, 6, Vector2::new(5.0, 12.0)), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Joint constraints between two bodies.
+//!
+//! A joint lives on one entity and names another, its `target`. The
+//! [`joint_system`](crate::systems::joint::joint_system) solves all joints
+//! right after [`movement`](crate::systems::movement::movement), so the
+//! bodies keep their relationship whatever velocities moved them:
+//!
+//! - [`DistanceJoint`] keeps the two positions between `min` and `max` apart
+//!   — a rod when both are equal, a leash or chain when `min` is 0. A shield
+//!   on a rod orbits the ball it is jointed to.
+//! - [`PinJoint`] keeps the entity at `offset` from the target, like a weld.
+//!   Unlike [`StuckTo`](super::stuckto::StuckTo), both bodies move: pushing
+//!   either drags the other along.
+//!
+//! A body with a non-frozen [`RigidBody`](super::rigidbody::RigidBody) is
+//! moved by its joints, and the correction is added to its velocity so it
+//! carries on along the constraint (swinging, orbiting) instead of bouncing
+//! back. A body without one, or frozen, is an immovable anchor. When both
+//! are movable, each takes half of the correction.
+//!
+//! Joints work on [`MapPosition`](super::mapposition::MapPosition) and are
+//! meant for entities without a parent. A joint whose target was despawned
+//! does nothing.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Shield orbiting the ball at 24 units
+//! commands.spawn((
+//!     MapPosition::new(124.0, 100.0),
+//!     RigidBody::new(),
+//!     DistanceJoint::rod(ball, 24.0),
+//! ));
+//!
+//! // Bumper welded on top of the paddle
+//! commands.spawn((
+//!     MapPosition::new(100.0, 188.0),
+//!     RigidBody::new(),
+//!     PinJoint::new(paddle, Vector2::new(0.0, -12.0)),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::joint`] – the solver
+//! - [`crate::components::rope`] – flexible chains of many points
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::Vector2;
+
+/// Keeps the entity between `min` and `max` from its target. See the module
+/// docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct DistanceJoint {
+    pub target: Entity,
+    /// Shortest allowed distance.
+    pub min: f32,
+    /// Longest allowed distance.
+    pub max: f32,
+    /// Fraction of a violation corrected per solver pass, from 0 to 1.
+    pub stiffness: f32,
+}
+
+impl DistanceJoint {
+    /// Joint allowing any distance between `min` and `max`.
+    pub fn new(target: Entity, min: f32, max: f32) -> Self {
+        let min = min.max(0.0);
+        Self {
+            target,
+            min,
+            max: max.max(min),
+            stiffness: 1.0,
+        }
+    }
+
+    /// Rigid rod of `length`.
+    pub fn rod(target: Entity, length: f32) -> Self {
+        Self::new(target, length, length)
+    }
+
+    /// Leash: at most `length` away, any closer.
+    pub fn leash(target: Entity, length: f32) -> Self {
+        Self::new(target, 0.0, length)
+    }
+
+    /// Set the fraction corrected per pass; below 1 the joint is springy.
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Move `a` (this entity) and `b` (the target) so they are within range,
+    /// each by its share `wa`/`wb` of the correction.
+    pub fn solve(&self, a: &mut Vector2, b: &mut Vector2, wa: f32, wb: f32) {
+        let delta = *b - *a;
+        let distance = delta.length();
+        let wanted = distance.clamp(self.min, self.max);
+        if distance == wanted || distance == 0.0 || wa + wb == 0.0 {
+            return;
+        }
+        let correction = delta * ((distance - wanted) / distance * self.stiffness);
+        *a += correction * (wa / (wa + wb));
+        *b -= correction * (wb / (wa + wb));
+    }
+}
+
+/// Keeps the entity at `offset` from its target. See the module docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct PinJoint {
+    pub target: Entity,
+    /// Wanted position of this entity relative to the target.
+    pub offset: Vector2,
+    /// Fraction of a violation corrected per solver pass, from 0 to 1.
+    pub stiffness: f32,
+}
+
+impl PinJoint {
+    pub fn new(target: Entity, offset: Vector2) -> Self {
+        Self {
+            target,
+            offset,
+            stiffness: 1.0,
+        }
+    }
+
+    /// Set the fraction corrected per pass; below 1 the joint is springy.
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Move `a` (this entity) and `b` (the target) toward `a == b + offset`,
+    /// each by its share `wa`/`wb` of the correction.
+    pub fn solve(&self, a: &mut Vector2, b: &mut Vector2, wa: f32, wb: f32) {
+        if wa + wb == 0.0 {
+            return;
+        }
+        let error = (*b + self.offset - *a) * self.stiffness;
+        *a += error * (wa / (wa + wb));
+        *b -= error * (wb / (wa + wb));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> Entity {
+        Entity::from_raw_u32(1).unwrap()
+    }
+
+    #[test]
+    fn rod_pulls_and_pushes_to_its_length() {
+        let rod = DistanceJoint::rod(target(), 10.0);
+        let (mut a, mut b) = (Vector2::zero(), Vector2::new(30.0, 0.0));
+        rod.solve(&mut a, &mut b, 1.0, 1.0);
+        assert_eq!((a, b), (Vector2::new(10.0, 0.0), Vector2::new(20.0, 0.0)));
+
+        let (mut a, mut b) = (Vector2::zero(), Vector2::new(4.0, 0.0));
+        rod.solve(&mut a, &mut b, 1.0, 0.0);
+        assert_eq!((a, b), (Vector2::new(-6.0, 0.0), Vector2::new(4.0, 0.0)));
+    }
+
+    #[test]
+    fn leash_leaves_closer_bodies_alone() {
+        let leash = DistanceJoint::leash(target(), 10.0);
+        let (mut a, mut b) = (Vector2::zero(), Vector2::new(0.0, 6.0));
+        leash.solve(&mut a, &mut b, 1.0, 1.0);
+        assert_eq!((a, b), (Vector2::zero(), Vector2::new(0.0, 6.0)));
+    }
+
+    #[test]
+    fn pin_moves_the_movable_side_to_the_offset() {
+        let pin = PinJoint::new(target(), Vector2::new(0.0, -12.0));
+        let (mut a, mut b) = (Vector2::new(5.0, 5.0), Vector2::new(100.0, 200.0));
+        pin.solve(&mut a, &mut b, 1.0, 0.0);
+        assert_eq!(a, Vector2::new(100.0, 188.0));
+
+        let (mut a, mut b) = (Vector2::zero(), Vector2::new(0.0, 12.0));
+        pin.solve(&mut a, &mut b, 0.0, 0.0);
+        assert_eq!(a, Vector2::zero());
+    }
+}
@@ -0,0 +1,41 @@
+//! Lua callback component fired when the entity's hitbox strikes a hurtbox.
+//!
+//! Attach this component to an entity with a
+//! [`Hitbox`](crate::components::hitbox::Hitbox) to call a Lua function for
+//! every [`HitEvent`](crate::events::hit::HitEvent) it causes.
+//!
+//! # Lua callback signature
+//!
+//! ```lua
+//! function on_punch_hit(ctx, input, target_id)
+//!     engine.entity_signal_set_integer(target_id, "hp", 0)
+//! end
+//! ```
+//!
+//! `ctx` is the attacker's entity context, as in phase callbacks.
+//!
+//! # Usage from Lua
+//!
+//! ```lua
+//! engine.spawn()
+//!     :with_hitbox_frame("punch", 2, 8, -20, 14, 8)
+//!     :with_on_hit("on_punch_hit")
+//!     :build()
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Attaches a Lua callback to be called when the entity's hitbox hits something.
+#[derive(Component, Clone, Debug)]
+pub struct LuaOnHit {
+    /// Name of the Lua function to call.
+    pub callback: String,
+}
+
+impl LuaOnHit {
+    pub fn new(callback: impl Into<String>) -> Self {
+        Self {
+            callback: callback.into(),
+        }
+    }
+}
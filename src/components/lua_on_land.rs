@@ -0,0 +1,41 @@
+//! Lua callback component fired when the entity lands.
+//!
+//! Attach this component to an entity with a
+//! [`ZVelocity`](crate::components::height::ZVelocity) to call a Lua function
+//! for every [`LandedEvent`](crate::events::landing::LandedEvent) it causes.
+//!
+//! # Lua callback signature
+//!
+//! ```lua
+//! function on_player_land(ctx, input, impact_speed)
+//!     if impact_speed > 400 then
+//!         engine.camera_shake(0.3, 0.2)
+//!     end
+//! end
+//! ```
+//!
+//! # Usage from Lua
+//!
+//! ```lua
+//! engine.spawn()
+//!     :with_height(0, 900)
+//!     :with_on_land("on_player_land")
+//!     :build()
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Attaches a Lua callback to be called when the entity lands.
+#[derive(Component, Clone, Debug)]
+pub struct LuaOnLand {
+    /// Name of the Lua function to call.
+    pub callback: String,
+}
+
+impl LuaOnLand {
+    pub fn new(callback: impl Into<String>) -> Self {
+        Self {
+            callback: callback.into(),
+        }
+    }
+}
@@ -13,6 +13,7 @@ use bevy_ecs::prelude::{Component, Entity};
 use raylib::prelude::{Color, Vector2};
 use rustc_hash::FxHashMap;
 
+use crate::resources::accessibility::{HIGH_CONTRAST_SELECTED, HIGH_CONTRAST_TEXT};
 use crate::systems::GameCtx;
 
 /// Type alias for a Rust menu selection callback.
@@ -138,6 +139,15 @@ impl Menu {
         self.selected_color = selected;
         self
     }
+    /// Unselected and selected item colors, replaced by the fixed
+    /// high-contrast pair when `high_contrast` is set.
+    pub fn item_colors(&self, high_contrast: bool) -> (Color, Color) {
+        if high_contrast {
+            (HIGH_CONTRAST_TEXT, HIGH_CONTRAST_SELECTED)
+        } else {
+            (self.normal_color, self.selected_color)
+        }
+    }
     pub fn with_dynamic_text(mut self, dynamic: bool) -> Self {
         for item in &mut self.items {
             item.dynamic_text = dynamic;
@@ -276,6 +286,24 @@ mod tests {
         assert_eq!(menu.selected_color, Color::GREEN);
     }
 
+    #[test]
+    fn test_menu_item_colors_high_contrast() {
+        let menu = Menu::new(
+            &sample_labels(),
+            Vector2::zero(),
+            "arcade",
+            16.0,
+            20.0,
+            true,
+        )
+        .with_colors(Color::RED, Color::GREEN);
+        assert_eq!(menu.item_colors(false), (Color::RED, Color::GREEN));
+        assert_eq!(
+            menu.item_colors(true),
+            (HIGH_CONTRAST_TEXT, HIGH_CONTRAST_SELECTED)
+        );
+    }
+
     #[test]
     fn test_menu_with_dynamic_text_false() {
         let menu = Menu::new(
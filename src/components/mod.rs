@@ -6,12 +6,19 @@
 //!
 //! Submodules overview:
 //! - [`animation`] – playback state and a rule-based controller for sprite animations
-//! - [`boxcollider`] – axis-aligned rectangular collider for collision detection
+//! - [`attractor`] – magnet pulling labeled rigid bodies within a radius
+//! - [`audiotriggers`] – sound effects played on spawn, despawn and collision start, with cooldowns
+//! - [`blackboard`] – typed key-value AI memory with per-entry TTL
+//! - [`blobshadow`] – ground ellipse under a sprite, shrinking and fading with the entity's height
+//! - [`boxcollider`] – axis-aligned rectangular collider for collision detection, plus the `ColliderDisabled` marker
 //! - [`cameratarget`] – marks an entity as a candidate for camera following
 //! - [`collision`] – collision callback rules and context for collision observers
+//! - [`cooldown`] – ability cooldowns mirroring their remaining fraction to a signal
+//! - [`credits`] – styled credits lines scrolling up the screen with fast-forward
 //! - [`dynamictext`] – text component for rendering variable strings
 //! - [`emittedparticle`] – marker for entities spawned by a particle emitter
 //! - [`entityshader`] – per-entity shader for custom rendering effects
+//! - [`forcearea`] – wind, current and conveyor areas pushing overlapping rigid bodies
 //! - [`gridlayout`] – data-driven grid spawner for tile-based layouts
 //! - [`group`] – tag component for grouping entities by name
 //! - [`guibutton`] – marker selecting the nine-patch button skin in rendering; hit-test/click state lives in [`guiinteractable`]
@@ -21,35 +28,73 @@
 //! - [`guioffset`] – child positioning offset for GUI hierarchies, resolved by `gui_layout_system`
 //! - [`guiprogressbar`] – themed progress bar (nine-patch track + fill, signal-bound value, four direction variants)
 //! - [`guiwindow`] – static themed GUI window panel, rendered as a nine-patch background
+//! - [`height`] – faux-3D height lifting a sprite off the ground, with jump/throw gravity
+//! - [`hidden`] – marker leaving an entity's sprite and text out of rendering
+//! - [`hitbox`] – animation-frame driven hitboxes and hurtboxes
+//! - [`interactable`] – "press to interact" prompts shown when the player is in range
 //! - [`inputcontrolled`] – input-driven movement intent for keyboard and mouse
+//! - [`inventory`] – slot-based item storage and the screen-space grid that shows it
+//! - [`joint`] – distance and pin constraints between two bodies
 //! - [`mapposition`] – world-space position (pivot) for an entity
 //! - [`menu`] – interactive menu component and actions
-//! - [`persistent`] – marker for entities that persist across scene changes
+//! - [`nameentry`] – arcade three-letter name selector for the high score table
+//! - [`nineslice`] – texture stretched over a panel of any size with fixed-size corners (NPatch)
+//! - [`offscreenindicator`] – edge-of-screen arrow pointing at an off-screen entity
+//! - [`ownedby`] – despawn an entity together with its owner
+//! - [`paletteswap`] – recolor a sprite through a palette texture
+//! - [`pathfollow`] – move along a polyline or Catmull-Rom spline through waypoints
+//! - [`persistent`] – markers for entities that persist across scene changes or belong to the engine
 //! - [`luaphase`] – *(feature = "lua")* Lua-based state machine with enter/update/exit callbacks
 //! - [`luasetup`] – *(feature = "lua")* one-shot entity setup callback fired on `Added<LuaSetup>`
 //! - [`phase`] – Rust-based state machine with enter/update/exit function-pointer callbacks
+//! - [`pixelcollision`] – marker refining box collisions with the sprite's alpha mask
+//! - [`playerindex`] – local player that owns an entity and drives it with their own device
+//! - [`portal`] – doors/teleporters to a position or another scene, and named spawn points
 //! - [`position2d`] – generic 2D position component shared by [`mapposition`] and [`screenposition`]
+//! - [`progress`] – plain-colored progress bars and radial rings, bindable to signals
+//! - [`renderlayer`] – layer number used to hide whole groups of drawables at runtime
+//! - [`restoreonreturn`] – marker for entities whose state is restored when their scene is re-entered
+//! - [`results`] – end-of-level table of counting-up values closed by confirm
 //! - [`rigidbody`] – simple kinematic body storing velocity
+//! - [`rope`] – Verlet point chain hanging between two anchors
 //! - [`rotation`] – rotation angle in degrees
 //! - [`scale`] – 2D scale factor for sprites
 //! - [`screenposition`] – screen-space position for UI elements
+//! - [`sensor`] – ray sensors writing ground/wall hit flags into Signals
 //! - [`signalbinding`] – binds UI text to signal values for reactive updates
 //! - [`signals`] – per-entity signal storage for cross-system communication
+//! - [`slope`] – right-triangle colliders for ramps, resolved onto their slanted side
 //! - [`sprite`] – 2D sprite rendering component
 //! - [`stuckto`] – attaches an entity's position to another entity
-//! - [`tilemap`] – tilemap root entity; spawns tile children from a directory path
+//! - [`tags`] – extra labels so an entity can match several group names
+//! - [`terrain`] – destructible bitmap surface and its carvable pixel copy
+//! - [`textscroller`] – demo-style text scrolling glyph by glyph along a sine, wave or circle
+//! - [`tilemap`] – tilemap root entity and its editable per-layer tile layout
 //! - [`tint`] – color tint for rendering sprites and text
 //! - [`luatimer`] – *(feature = "lua")* Lua callback timer for delayed actions
 //! - [`tween`] – animated interpolation of position, rotation, and scale
+//! - [`visibilitypolygon`] – shadow-cast visible area, drawable as a light or vision cone
+//! - [`visionsource`] – sight radius revealing fog-of-war cells
+//! - [`watersurface`] – spring-simulated water line with splashes and buoyancy
+//! - [`weather`] – markers of the engine-spawned weather emitters, particles and wind
+//! - [`windresponse`] – opt-in response of a rigid body to the global wind
+//! - [`worldanchorui`] – screen-space UI following a world entity through the camera
 //! - [`zindex`] – rendering order hint for 2D drawing
 
 pub mod animation;
+pub mod attractor;
+pub mod audiotriggers;
+pub mod blackboard;
+pub mod blobshadow;
 pub mod boxcollider;
 pub mod cameratarget;
 pub mod collision;
+pub mod cooldown;
+pub mod credits;
 pub mod dynamictext;
 pub mod emittedparticle;
 pub mod entityshader;
+pub mod forcearea;
 pub mod globaltransform2d;
 pub mod gridlayout;
 pub mod group;
@@ -62,10 +107,20 @@ pub mod guiprogressbar;
 pub mod gui_themed;
 pub mod guiwindow;
 pub use gui_themed::Themed;
+pub mod height;
+pub mod hidden;
+pub mod hitbox;
 pub mod inputcontrolled;
+pub mod interactable;
+pub mod inventory;
+pub mod joint;
 #[cfg(feature = "lua")]
 pub mod lua_on_animation_end;
 #[cfg(feature = "lua")]
+pub mod lua_on_hit;
+#[cfg(feature = "lua")]
+pub mod lua_on_land;
+#[cfg(feature = "lua")]
 pub mod lua_on_tween_finished;
 #[cfg(feature = "lua")]
 pub mod luacollision;
@@ -77,22 +132,47 @@ pub mod luasetup;
 pub mod luatimer;
 pub mod mapposition;
 pub mod menu;
+pub mod nameentry;
+pub mod nineslice;
+pub mod offscreenindicator;
+pub mod ownedby;
+pub mod paletteswap;
 pub mod particleemitter;
+pub mod pathfollow;
 pub mod persistent;
 pub mod phase;
+pub mod pixelcollision;
+pub mod playerindex;
+pub mod portal;
 pub mod position2d;
+pub mod progress;
+pub mod renderlayer;
+pub mod restoreonreturn;
+pub mod results;
 pub mod rigidbody;
+pub mod rope;
 pub mod rotation;
 pub mod scale;
 pub mod screenposition;
+pub mod sensor;
 pub mod shadow;
 pub mod signalbinding;
 pub mod signals;
+pub mod slope;
 pub mod sprite;
 pub mod stuckto;
+pub mod tags;
+pub mod terrain;
+pub mod textscroller;
 pub mod tilemap;
 pub mod timer;
 pub mod tint;
 pub mod ttl;
 pub mod tween;
+pub mod visibilitypolygon;
+pub mod visionsource;
+pub mod watersurface;
+pub mod weather;
+pub mod windresponse;
+pub mod worldanchorui;
 pub mod zindex;
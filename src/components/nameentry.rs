@@ -0,0 +1,116 @@
+//! Arcade-style name entry for the high score table.
+//!
+//! A [`NameEntry`] shows three letters in screen space. Up/down cycles the
+//! selected letter through [`NAME_ENTRY_ALPHABET`], left/right (or `back`)
+//! moves between letters and confirm (`action_1`/`action_2`) moves on, the
+//! last confirm inserting the name and score into
+//! [`HighScores`](crate::resources::highscores::HighScores).
+//!
+//! # Related
+//!
+//! - [`crate::systems::nameentry`] – input handling and the `SubmitScore` command
+//! - [`crate::events::highscore`] – the event triggered when entry ends
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Color, Vector2};
+
+/// Characters a name letter cycles through.
+pub const NAME_ENTRY_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789.";
+
+/// Letters in a high score name.
+pub const NAME_LENGTH: usize = 3;
+
+/// Three-letter name selector for a score that made the table.
+#[derive(Component, Debug, Clone)]
+pub struct NameEntry {
+    /// Score recorded with the name.
+    pub score: i64,
+    /// Index into [`NAME_ENTRY_ALPHABET`] of each letter.
+    pub letters: [usize; NAME_LENGTH],
+    /// Letter being edited.
+    pub cursor: usize,
+    /// Screen position of the first letter.
+    pub position: Vector2,
+    pub font: String,
+    pub font_size: f32,
+    pub color: Color,
+    /// Color of the letter being edited.
+    pub highlight: Color,
+    pub z_index: f32,
+    /// Lua function called when the name is entered.
+    pub callback: Option<String>,
+    /// Text entity of each letter, spawned on the first frame.
+    pub(crate) texts: Vec<Entity>,
+}
+
+impl NameEntry {
+    pub fn new(score: i64) -> Self {
+        Self {
+            score,
+            letters: [0; NAME_LENGTH],
+            cursor: 0,
+            position: Vector2::new(40.0, 40.0),
+            font: "default".into(),
+            font_size: 32.0,
+            color: Color::WHITE,
+            highlight: Color::YELLOW,
+            z_index: 100.0,
+            callback: None,
+            texts: Vec::new(),
+        }
+    }
+
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position = Vector2::new(x, y);
+        self
+    }
+
+    pub fn with_callback(mut self, callback: impl Into<String>) -> Self {
+        self.callback = Some(callback.into());
+        self
+    }
+
+    /// Moves the selected letter `steps` places along the alphabet, wrapping.
+    pub fn cycle(&mut self, steps: i32) {
+        let len = NAME_ENTRY_ALPHABET.len() as i32;
+        let letter = &mut self.letters[self.cursor];
+        *letter = (*letter as i32 + steps).rem_euclid(len) as usize;
+    }
+
+    /// Selects the letter `steps` places away, stopping at both ends.
+    pub fn move_cursor(&mut self, steps: i32) {
+        self.cursor = (self.cursor as i32 + steps).clamp(0, NAME_LENGTH as i32 - 1) as usize;
+    }
+
+    /// Character of letter `index`.
+    pub fn letter(&self, index: usize) -> char {
+        NAME_ENTRY_ALPHABET[self.letters[index]] as char
+    }
+
+    /// The name as entered so far.
+    pub fn name(&self) -> String {
+        (0..NAME_LENGTH).map(|i| self.letter(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_wrap_and_cursor_stops_at_the_ends() {
+        let mut entry = NameEntry::new(100);
+        assert_eq!(entry.name(), "AAA");
+        entry.cycle(-1);
+        assert_eq!(entry.letter(0), '.');
+        entry.cycle(2);
+        assert_eq!(entry.letter(0), 'A');
+
+        entry.move_cursor(-1);
+        assert_eq!(entry.cursor, 0);
+        entry.move_cursor(5);
+        assert_eq!(entry.cursor, 2);
+        entry.cycle(25);
+        assert_eq!(entry.name(), "AAZ");
+    }
+}
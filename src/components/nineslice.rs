@@ -0,0 +1,95 @@
+//! Nine-slice (NPatch) panels.
+//!
+//! A [`NineSlice`] stretches a texture over a rectangle of any size while
+//! keeping its corners intact: the four corners are drawn unscaled, the edges
+//! stretch along one axis and the centre along both. Dialog boxes, tooltips
+//! and HUD frames can use one small bordered image at every size, without a
+//! GUI theme. It is drawn with raylib's NPatch drawing, in both spaces:
+//!
+//! - with a [`MapPosition`](super::mapposition::MapPosition) it is drawn in
+//!   the world pass, over sprites and texts (a sign above an NPC);
+//! - with a [`ScreenPosition`](super::screenposition::ScreenPosition) it is
+//!   drawn with the UI, sorted by [`ZIndex`](super::zindex::ZIndex), under
+//!   sprites and texts at the same `ZIndex`.
+//!
+//! Either way the entity needs a `ZIndex`. The panel's top-left corner sits
+//! at the entity position plus `offset`. A [`Tint`](super::tint::Tint) on
+//! the entity tints the whole panel.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // A 200x80 dialog box from a 24x24 frame image with 8-pixel borders.
+//! commands.spawn((
+//!     ScreenPosition::new(60.0, 140.0),
+//!     ZIndex(5.0),
+//!     NineSlice::new("dialog_frame", 200.0, 80.0, 8),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::key::Key;
+
+/// Texture stretched over `size` with fixed borders. See the module docs.
+#[derive(Component, Clone, Debug)]
+pub struct NineSlice {
+    pub tex_key: Key,
+    /// Region of the texture holding the nine slices. `None` uses the whole
+    /// texture.
+    pub source: Option<Rectangle>,
+    /// Border widths in texture pixels, kept unscaled at the corners.
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub size: Vector2,
+    /// Top-left corner relative to the entity position.
+    pub offset: Vector2,
+}
+
+impl NineSlice {
+    /// A `width` x `height` panel with the same `border` on all four sides.
+    pub fn new(tex_key: impl Into<Key>, width: f32, height: f32, border: i32) -> Self {
+        let border = border.max(0);
+        Self {
+            tex_key: tex_key.into(),
+            source: None,
+            left: border,
+            top: border,
+            right: border,
+            bottom: border,
+            size: Vector2::new(width.max(0.0), height.max(0.0)),
+            offset: Vector2::zero(),
+        }
+    }
+
+    pub fn with_borders(mut self, left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        self.left = left.max(0);
+        self.top = top.max(0);
+        self.right = right.max(0);
+        self.bottom = bottom.max(0);
+        self
+    }
+
+    pub fn with_source(mut self, source: Rectangle) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+
+    /// Rectangle covered when the entity is at `pos`.
+    pub fn dest(&self, pos: Vector2) -> Rectangle {
+        Rectangle::new(
+            pos.x + self.offset.x,
+            pos.y + self.offset.y,
+            self.size.x,
+            self.size.y,
+        )
+    }
+}
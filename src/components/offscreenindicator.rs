@@ -0,0 +1,160 @@
+//! Edge-of-screen arrows pointing at off-screen entities.
+//!
+//! An entity with an [`OffscreenIndicator`] that is outside the camera view
+//! gets an arrow on the screen edge, on the line from the screen centre to
+//! the entity, pointing toward it: objectives, the exit, or an incoming
+//! threat. An optional icon sits just inside the arrow, and the distance from
+//! the camera centre can be printed next to it with the font of a GUI theme.
+//! Nothing is drawn while the entity is on screen.
+//!
+//! Indicators are drawn on the UI layer, above gameplay sprites.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("exit"),
+//!     MapPosition::new(2400.0, 96.0),
+//!     OffscreenIndicator::new(Color::GOLD)
+//!         .with_icon("icon_door")
+//!         .with_distance(16.0),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::resources::camera2d::Camera2DRes::world_to_screen`] – entity to screen mapping
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Color, Vector2};
+
+use crate::components::gui_themed::Themed;
+use crate::key::Key;
+use crate::resources::guitheme::DEFAULT_GUI_THEME_KEY;
+
+/// Default length of the arrow, in screen pixels.
+pub const DEFAULT_ARROW_SIZE: f32 = 12.0;
+/// Default gap between the arrow tip and the screen edge, in screen pixels.
+pub const DEFAULT_EDGE_MARGIN: f32 = 8.0;
+
+/// Arrow shown at the screen edge while this entity is off screen. See the
+/// module docs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct OffscreenIndicator {
+    pub color: Color,
+    /// Length of the arrow from base to tip.
+    pub size: f32,
+    /// Gap between the arrow tip and the screen edge.
+    pub margin: f32,
+    /// Texture key of an icon drawn just inside the arrow.
+    pub icon: Option<Key>,
+    /// World units per displayed unit of distance, e.g. 16 for tiles of 16
+    /// pixels. `None` hides the distance.
+    pub distance_unit: Option<f32>,
+    /// Theme whose font draws the distance.
+    pub theme_key: Arc<str>,
+}
+
+impl OffscreenIndicator {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            size: DEFAULT_ARROW_SIZE,
+            margin: DEFAULT_EDGE_MARGIN,
+            icon: None,
+            distance_unit: None,
+            theme_key: Arc::from(DEFAULT_GUI_THEME_KEY),
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size.max(1.0);
+        self
+    }
+
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin.max(0.0);
+        self
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<Key>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Shows the distance from the camera centre, divided by `unit`.
+    pub fn with_distance(mut self, unit: f32) -> Self {
+        self.distance_unit = (unit > 0.0).then_some(unit);
+        self
+    }
+
+    pub fn with_theme_key(mut self, key: impl Into<Arc<str>>) -> Self {
+        self.theme_key = key.into();
+        self
+    }
+
+    /// Where the arrow tip goes for an entity at screen position `target`
+    /// on a `screen` of that size, and the unit direction it points in.
+    /// `None` while the entity is on screen.
+    pub fn edge_placement(&self, target: Vector2, screen: Vector2) -> Option<(Vector2, Vector2)> {
+        if (0.0..=screen.x).contains(&target.x) && (0.0..=screen.y).contains(&target.y) {
+            return None;
+        }
+        let center = screen * 0.5;
+        let offset = target - center;
+        let half = Vector2::new(
+            (center.x - self.margin).max(0.0),
+            (center.y - self.margin).max(0.0),
+        );
+        // Shrink the centre-to-target line until it touches the inset edge.
+        let scale_x = if offset.x != 0.0 {
+            half.x / offset.x.abs()
+        } else {
+            f32::INFINITY
+        };
+        let scale_y = if offset.y != 0.0 {
+            half.y / offset.y.abs()
+        } else {
+            f32::INFINITY
+        };
+        let tip = center + offset * scale_x.min(scale_y);
+        Some((tip, offset.normalized()))
+    }
+}
+
+impl Themed for OffscreenIndicator {
+    fn theme_key_mut(&mut self) -> &mut Arc<str> {
+        &mut self.theme_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrow_sits_on_the_inset_edge_facing_the_entity() {
+        let indicator = OffscreenIndicator::new(Color::RED).with_margin(10.0);
+        let screen = Vector2::new(320.0, 180.0);
+        assert!(
+            indicator
+                .edge_placement(Vector2::new(100.0, 50.0), screen)
+                .is_none()
+        );
+
+        let (tip, dir) = indicator
+            .edge_placement(Vector2::new(960.0, 90.0), screen)
+            .unwrap();
+        assert_eq!((tip.x, tip.y), (310.0, 90.0));
+        assert_eq!((dir.x, dir.y), (1.0, 0.0));
+
+        // Up and to the left: the top edge is reached first.
+        let (tip, _) = indicator
+            .edge_placement(Vector2::new(-160.0, -360.0), screen)
+            .unwrap();
+        assert!((tip.y - 10.0).abs() < 1e-4);
+        assert!(tip.x > 10.0 && tip.x < 160.0);
+    }
+}
@@ -0,0 +1,40 @@
+//! Owner/owned lifetime link.
+//!
+//! An entity with [`OwnedBy`] lives only as long as its owner: once the
+//! owner no longer exists,
+//! [`owned_by_cleanup_system`](crate::systems::ownedby::owned_by_cleanup_system)
+//! despawns it, and whatever it owns in turn, in the same frame. Health
+//! bars, shadows and attached effects use it so a kill doesn't leave them
+//! behind.
+//!
+//! Unlike a `ChildOf` parent, an owner does not move or transform what it
+//! owns; pair it with `StuckTo` or `WorldAnchorUI` for that. `WorldAnchorUI`
+//! already despawns with its target, so anchored UI doesn't need an owner.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     MapPosition::new(0.0, 0.0),
+//!     Sprite::new("sparks", 16.0, 16.0),
+//!     StuckTo::new(enemy),
+//!     OwnedBy(enemy),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::ownedby::owned_by_cleanup_system`] – the cascade
+
+use bevy_ecs::prelude::{Component, Entity};
+
+/// Despawns this entity once the wrapped owner entity is gone. See the
+/// module docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnedBy(pub Entity);
+
+impl OwnedBy {
+    pub fn owner(&self) -> Entity {
+        self.0
+    }
+}
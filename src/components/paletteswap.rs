@@ -0,0 +1,62 @@
+//! Palette swap component.
+//!
+//! A [`PaletteSwap`] recolors an entity's sprite through a palette texture,
+//! so enemy color variants and player skins reuse one spritesheet. The
+//! palette is an N×2 texture (N up to 256): the top row lists the sprite's
+//! original colors and the bottom row the color each one becomes. Pixels
+//! whose color is not in the top row are drawn unchanged, and the sprite's
+//! alpha and [`Tint`](super::tint::Tint) still apply.
+//!
+//! Only world-space sprites are recolored. An
+//! [`EntityShader`](super::entityshader::EntityShader) on the same entity
+//! takes precedence over the swap.
+
+use bevy_ecs::prelude::Component;
+use std::sync::Arc;
+
+/// [`ShaderStore`](crate::resources::shaderstore::ShaderStore) key of the
+/// built-in palette swap shader.
+pub const PALETTE_SWAP_SHADER_KEY: &str = "__palette_swap";
+
+/// Recolor the entity's sprite with the palette texture `palette`. See the
+/// module docs.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct PaletteSwap {
+    /// Texture key of the N×2 palette.
+    pub palette: Arc<str>,
+}
+
+impl PaletteSwap {
+    pub fn new(palette: impl Into<Arc<str>>) -> Self {
+        Self {
+            palette: palette.into(),
+        }
+    }
+}
+
+/// Fragment shader of the palette swap. Colors are matched per channel
+/// within half a step of 8-bit precision.
+pub const PALETTE_SWAP_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D uPalette;
+uniform vec4 colDiffuse;
+
+out vec4 finalColor;
+
+void main() {
+    vec4 texel = texture(texture0, fragTexCoord);
+    int size = min(textureSize(uPalette, 0).x, 256);
+    for (int i = 0; i < size; i++) {
+        vec3 from = texelFetch(uPalette, ivec2(i, 0), 0).rgb;
+        if (all(lessThan(abs(from - texel.rgb), vec3(0.002)))) {
+            vec4 to = texelFetch(uPalette, ivec2(i, 1), 0);
+            texel = vec4(to.rgb, texel.a * to.a);
+            break;
+        }
+    }
+    finalColor = texel * colDiffuse * fragColor;
+}
+"#;
@@ -0,0 +1,314 @@
+//! Movement along waypoint paths.
+//!
+//! A [`PathFollow`] component moves its entity's
+//! [`MapPosition`](super::mapposition::MapPosition) along a path through
+//! world-space waypoints, either straight from point to point or on a
+//! Catmull-Rom spline that passes smoothly through every waypoint. The entity
+//! travels at `speed` units per second, with the tween [`Easing`] and
+//! [`LoopMode`] options applied to the whole path, and can turn to face its
+//! direction of travel.
+//!
+//! Patrol routes use a closed path with [`LoopMode::Loop`]; back-and-forth
+//! hazards use an open path with [`LoopMode::PingPong`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! let route = vec![
+//!     Vector2::new(100.0, 100.0),
+//!     Vector2::new(300.0, 80.0),
+//!     Vector2::new(300.0, 240.0),
+//! ];
+//! commands.spawn((
+//!     MapPosition::new(100.0, 100.0),
+//!     PathFollow::new(route, 80.0)
+//!         .with_spline(true)
+//!         .with_closed(true)
+//!         .with_loop_mode(LoopMode::Loop)
+//!         .with_orient(true),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::pathfollow`] – moves the entities
+//! - [`crate::components::tween`] – easing and loop modes
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::Vector2;
+
+use crate::components::tween::{Easing, LoopMode};
+use crate::systems::tween::ease;
+
+/// Straight segments each spline segment is approximated with.
+const SPLINE_SUBDIVISIONS: usize = 16;
+
+/// Moves the entity along a polyline or spline through waypoints.
+#[derive(Component, Debug, Clone)]
+pub struct PathFollow {
+    /// World-space waypoints.
+    points: Vec<Vector2>,
+    /// Pass through the waypoints on a Catmull-Rom spline instead of
+    /// straight segments.
+    spline: bool,
+    /// Connect the last waypoint back to the first.
+    closed: bool,
+    /// Units per second along the path.
+    pub speed: f32,
+    pub easing: Easing,
+    pub loop_mode: LoopMode,
+    /// Set the entity's rotation to its direction of travel.
+    pub orient: bool,
+    /// Degrees added to the direction of travel when orienting; 0 faces
+    /// right.
+    pub rotation_offset: f32,
+    /// The path flattened into points, and the distance along the path of
+    /// each of them.
+    samples: Vec<Vector2>,
+    distances: Vec<f32>,
+    time: f32,
+    forward: bool,
+    finished: bool,
+}
+
+impl PathFollow {
+    pub fn new(points: Vec<Vector2>, speed: f32) -> Self {
+        let mut follow = Self {
+            points,
+            spline: false,
+            closed: false,
+            speed,
+            easing: Easing::Linear,
+            loop_mode: LoopMode::Once,
+            orient: false,
+            rotation_offset: 0.0,
+            samples: Vec::new(),
+            distances: Vec::new(),
+            time: 0.0,
+            forward: true,
+            finished: false,
+        };
+        follow.flatten();
+        follow
+    }
+
+    pub fn with_spline(mut self, spline: bool) -> Self {
+        self.spline = spline;
+        self.flatten();
+        self
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self.flatten();
+        self
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.loop_mode = loop_mode;
+        self
+    }
+
+    pub fn with_orient(mut self, orient: bool) -> Self {
+        self.orient = orient;
+        self
+    }
+
+    pub fn with_rotation_offset(mut self, degrees: f32) -> Self {
+        self.rotation_offset = degrees;
+        self
+    }
+
+    pub fn points(&self) -> &[Vector2] {
+        &self.points
+    }
+
+    /// Total length of the path.
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// `true` once a [`LoopMode::Once`] path has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances along the path by `delta` seconds and returns the new
+    /// position and the direction of travel (zero on a path without length).
+    pub fn advance(&mut self, delta: f32) -> (Vector2, Vector2) {
+        let length = self.length();
+        let duration = if self.speed > 0.0 {
+            length / self.speed
+        } else {
+            f32::INFINITY
+        };
+        if !self.finished && duration > 0.0 {
+            self.time += if self.forward { delta } else { -delta };
+            let past_end = if self.forward {
+                self.time >= duration
+            } else {
+                self.time <= 0.0
+            };
+            if past_end {
+                match self.loop_mode {
+                    LoopMode::Once => {
+                        self.time = self.time.clamp(0.0, duration);
+                        self.finished = true;
+                    }
+                    LoopMode::Loop => self.time = self.time.rem_euclid(duration),
+                    LoopMode::PingPong => {
+                        self.time = if self.time >= duration {
+                            2.0 * duration - self.time
+                        } else {
+                            -self.time
+                        }
+                        .clamp(0.0, duration);
+                        self.forward = !self.forward;
+                    }
+                }
+            }
+        }
+        let t = if duration.is_finite() && duration > 0.0 {
+            self.time / duration
+        } else {
+            0.0
+        };
+        let (position, mut direction) = self.sample(ease(self.easing, t) * length);
+        if !self.forward {
+            direction = -direction;
+        }
+        (position, direction)
+    }
+
+    /// Point `distance` units along the path and the path's direction there.
+    pub fn sample(&self, distance: f32) -> (Vector2, Vector2) {
+        match self.samples.len() {
+            0 => return (Vector2::zero(), Vector2::zero()),
+            1 => return (self.samples[0], Vector2::zero()),
+            _ => {}
+        }
+        let distance = distance.clamp(0.0, self.length());
+        let end = self
+            .distances
+            .partition_point(|&d| d < distance)
+            .clamp(1, self.samples.len() - 1);
+        let (a, b) = (self.samples[end - 1], self.samples[end]);
+        let span = self.distances[end] - self.distances[end - 1];
+        let t = if span > 0.0 {
+            (distance - self.distances[end - 1]) / span
+        } else {
+            0.0
+        };
+        (a.lerp(b, t), (b - a).normalized())
+    }
+
+    /// Rebuilds `samples` and `distances` from the waypoints.
+    fn flatten(&mut self) {
+        let points = &self.points;
+        let n = points.len();
+        let mut samples = Vec::new();
+        if n > 0 {
+            let segments = if self.closed { n } else { n - 1 };
+            samples.push(points[0]);
+            for i in 0..segments {
+                let at = |k: isize| {
+                    let k = if self.closed {
+                        k.rem_euclid(n as isize)
+                    } else {
+                        k.clamp(0, n as isize - 1)
+                    };
+                    points[k as usize]
+                };
+                let i = i as isize;
+                let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+                if self.spline {
+                    for step in 1..=SPLINE_SUBDIVISIONS {
+                        let t = step as f32 / SPLINE_SUBDIVISIONS as f32;
+                        samples.push(catmull_rom(p0, p1, p2, p3, t));
+                    }
+                } else {
+                    samples.push(p2);
+                }
+            }
+        }
+        let mut distances = Vec::with_capacity(samples.len());
+        let mut total = 0.0;
+        for (i, point) in samples.iter().enumerate() {
+            if i > 0 {
+                total += (*point - samples[i - 1]).length();
+            }
+            distances.push(total);
+        }
+        self.samples = samples;
+        self.distances = distances;
+    }
+}
+
+/// Uniform Catmull-Rom spline between `p1` and `p2` at `t` in `[0, 1]`.
+fn catmull_rom(p0: Vector2, p1: Vector2, p2: Vector2, p3: Vector2, t: f32) -> Vector2 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<Vector2> {
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 10.0),
+            Vector2::new(0.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn polyline_moves_at_speed_and_loops_closed_paths() {
+        let mut follow = PathFollow::new(square(), 10.0);
+        assert_eq!(follow.length(), 30.0);
+        let (pos, dir) = follow.advance(1.5);
+        assert_eq!((pos.x, pos.y), (10.0, 5.0));
+        assert_eq!((dir.x, dir.y), (0.0, 1.0));
+        follow.advance(5.0);
+        assert!(follow.is_finished());
+
+        let mut patrol = PathFollow::new(square(), 10.0)
+            .with_closed(true)
+            .with_loop_mode(LoopMode::Loop);
+        assert_eq!(patrol.length(), 40.0);
+        let (pos, _) = patrol.advance(4.5);
+        assert_eq!((pos.x, pos.y), (5.0, 0.0));
+        assert!(!patrol.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_reverses_direction() {
+        let line = vec![Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)];
+        let mut follow = PathFollow::new(line, 10.0).with_loop_mode(LoopMode::PingPong);
+        let (pos, dir) = follow.advance(1.25);
+        assert_eq!((pos.x, dir.x), (7.5, -1.0));
+        let (pos, dir) = follow.advance(1.0);
+        assert_eq!((pos.x, dir.x), (2.5, 1.0));
+    }
+
+    #[test]
+    fn spline_passes_through_waypoints() {
+        let follow = PathFollow::new(square(), 1.0).with_spline(true);
+        assert!(follow.length() > 30.0);
+        let (start, _) = follow.sample(0.0);
+        let (end, _) = follow.sample(follow.length());
+        assert_eq!((start.x, start.y), (0.0, 0.0));
+        assert!((end.x - 0.0).abs() < 1e-4 && (end.y - 10.0).abs() < 1e-4);
+    }
+}
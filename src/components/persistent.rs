@@ -3,8 +3,28 @@
 //! Entities with the [`Persistent`] component will not be despawned when
 //! switching scenes. Use this for global state, audio controllers, or any
 //! entity that must survive scene transitions.
+//!
+//! Observers and registered systems are entities too. Once the engine has
+//! spawned its own (and the builder's `add_observer` ones),
+//! [`protect_engine_entities`] tags every observer and registered system
+//! with [`EngineInternal`] as well as `Persistent`. Scene cleanup skips them
+//! even if a spawn site forgot `Persistent`, and despawning one anyway logs a
+//! warning. Observers spawned later, e.g. scene-scoped ones from an
+//! `on_enter` callback, are left untagged and cleaned up as before.
+//!
+//! [`SceneCommandsExt`] spells that out: `commands.add_scene_observer(...)`
+//! and `commands.register_scene_system(...)` tag what they spawn with
+//! [`SceneScoped`], which [`protect_engine_entities`] never touches, so the
+//! next scene switch tears them down and rules don't pile up across levels.
 
-use bevy_ecs::prelude::{Component, Without};
+use bevy_ecs::lifecycle::HookContext;
+use bevy_ecs::observer::IntoObserver;
+use bevy_ecs::prelude::{
+    Commands, Component, Entity, EntityCommands, IntoSystem, Observer, Or, With, Without, World,
+};
+use bevy_ecs::system::{SystemId, SystemIdMarker};
+use bevy_ecs::world::DeferredWorld;
+use log::warn;
 
 /// Tag component used to mark entities that should persist across scene changes.
 ///
@@ -12,7 +32,139 @@ use bevy_ecs::prelude::{Component, Without};
 #[derive(Component, Clone, Debug)]
 pub struct Persistent;
 
+/// Marker for the observer and registered-system entities the engine relies
+/// on. Never matched by [`CleanableEntity`]; despawning one logs a warning.
+#[derive(Component, Clone, Debug)]
+#[component(on_despawn = warn_engine_internal_despawn)]
+pub struct EngineInternal;
+
+fn warn_engine_internal_despawn(world: DeferredWorld, ctx: HookContext) {
+    let entity = world.entity(ctx.entity);
+    let kind = if entity.contains::<Observer>() {
+        "observer"
+    } else if entity.contains::<SystemIdMarker>() {
+        "registered system"
+    } else {
+        "entity"
+    };
+    warn!(
+        "Engine-internal {kind} {} was despawned; it will no longer run. \
+         Exclude EngineInternal (or use CleanableEntity) in despawn queries",
+        ctx.entity
+    );
+}
+
+/// Marker for observers, systems and rules that live only as long as the
+/// scene that created them. Never tagged [`EngineInternal`].
+#[derive(Component, Clone, Debug)]
+pub struct SceneScoped;
+
 /// Query filter for entities eligible for scene-cleanup/quit despawn: not
-/// [`Persistent`], and not one of bevy's resource-backed entities (which
-/// `Query<Entity, ...>` would otherwise also match in bevy_ecs 0.19+).
-pub type CleanableEntity = (Without<Persistent>, Without<bevy_ecs::resource::IsResource>);
+/// [`Persistent`] or [`EngineInternal`], and not one of bevy's resource-backed
+/// entities (which `Query<Entity, ...>` would otherwise also match in
+/// bevy_ecs 0.19+).
+pub type CleanableEntity = (
+    Without<Persistent>,
+    Without<EngineInternal>,
+    Without<bevy_ecs::resource::IsResource>,
+);
+
+/// Tag every observer and registered system not yet tagged with
+/// [`EngineInternal`] and [`Persistent`], except [`SceneScoped`] ones.
+/// Returns how many were tagged.
+pub fn protect_engine_entities(world: &mut World) -> usize {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, (
+            Or<(With<Observer>, With<SystemIdMarker>)>,
+            Without<EngineInternal>,
+            Without<SceneScoped>,
+        )>()
+        .iter(world)
+        .collect();
+    for &entity in &entities {
+        world
+            .entity_mut(entity)
+            .insert((EngineInternal, Persistent));
+    }
+    entities.len()
+}
+
+/// Scene-lifetime observers and systems, despawned on the next scene switch.
+pub trait SceneCommandsExt {
+    /// Spawn an observer tagged [`SceneScoped`].
+    fn add_scene_observer<M>(&mut self, observer: impl IntoObserver<M>) -> EntityCommands<'_>;
+
+    /// Register a one-shot system tagged [`SceneScoped`]. Its id stops being
+    /// valid once the scene is left.
+    fn register_scene_system<M>(
+        &mut self,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> SystemId;
+}
+
+impl SceneCommandsExt for Commands<'_, '_> {
+    fn add_scene_observer<M>(&mut self, observer: impl IntoObserver<M>) -> EntityCommands<'_> {
+        let mut entity = self.add_observer(observer);
+        entity.insert(SceneScoped);
+        entity
+    }
+
+    fn register_scene_system<M>(
+        &mut self,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> SystemId {
+        let id = self.register_system(system);
+        self.entity(id.entity()).insert(SceneScoped);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::{Commands, Event, On, Query};
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Event)]
+    struct Ping;
+
+    fn clean(mut commands: Commands, query: Query<Entity, CleanableEntity>) {
+        for entity in query.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    #[test]
+    fn engine_observers_and_systems_survive_cleanup() {
+        let mut world = World::new();
+        let observer = world.spawn(Observer::new(|_: On<Ping>| {})).id();
+        let system = world.register_system(|| {});
+        let sprite = world.spawn_empty().id();
+        assert_eq!(protect_engine_entities(&mut world), 2);
+        // A scene-scoped observer spawned after startup stays cleanable
+        let scene_observer = world.spawn(Observer::new(|_: On<Ping>| {})).id();
+
+        world.run_system_once(clean).unwrap();
+        assert!(world.get::<EngineInternal>(observer).is_some());
+        assert!(world.get::<Persistent>(system.entity()).is_some());
+        assert!(world.get_entity(sprite).is_err());
+        assert!(world.get_entity(scene_observer).is_err());
+        assert_eq!(protect_engine_entities(&mut world), 0);
+    }
+
+    #[test]
+    fn scene_observers_and_systems_are_never_protected() {
+        let mut world = World::new();
+        let (observer, system) = world
+            .run_system_once(|mut commands: Commands| {
+                let observer = commands.add_scene_observer(|_: On<Ping>| {}).id();
+                (observer, commands.register_scene_system(|| {}))
+            })
+            .unwrap();
+        assert_eq!(protect_engine_entities(&mut world), 0);
+
+        world.run_system_once(clean).unwrap();
+        assert!(world.get_entity(observer).is_err());
+        assert!(world.get_entity(system.entity()).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! Pixel-perfect collision marker.
+//!
+//! [`PixelCollision`] refines an entity's [`BoxCollider`](super::boxcollider::BoxCollider)
+//! with its sprite's alpha: when two colliders overlap, the
+//! [`collision_detector`](crate::systems::collision_detector::collision_detector)
+//! only reports the collision if a solid texel of the sprite's current frame
+//! lies inside the other collider (or touches a solid texel of the other
+//! sprite, when both are marked). Useful for odd-shaped bosses and terrain
+//! with holes blasted into it.
+//!
+//! The mask comes from [`AlphaMasks`](crate::resources::alphamask::AlphaMasks),
+//! keyed by the sprite's texture. The sprite's position, scale, rotation and
+//! flips are honored. An entity without a [`Sprite`](super::sprite::Sprite)
+//! or without a mask for its texture collides by its box alone.
+//!
+//! The test samples one point per world unit of the overlap, so keep pixel
+//! collision for entities whose overlaps stay small.
+
+use bevy_ecs::prelude::Component;
+
+/// Test this entity's collisions against its sprite's alpha mask. See the
+/// module docs.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PixelCollision;
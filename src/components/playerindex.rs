@@ -0,0 +1,16 @@
+//! Player ownership of entities for local multiplayer.
+//!
+//! An entity with a [`PlayerIndex`] reads the input of that player from
+//! [`PlayerInputs`](crate::resources::playerinput::PlayerInputs) instead of
+//! the shared [`InputState`](crate::resources::input::InputState):
+//! [`InputControlled`](super::inputcontrolled::InputControlled) and
+//! [`AccelerationControlled`](super::inputcontrolled::AccelerationControlled)
+//! entities move with their player's directions, and a
+//! [`Menu`](super::menu::Menu) only reacts to its player's presses.
+//! Entities without one keep reading the shared state.
+
+use bevy_ecs::prelude::Component;
+
+/// The local player (0-based) that owns and controls this entity.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PlayerIndex(pub u8);
@@ -0,0 +1,131 @@
+//! Doors, teleporters and named spawn points.
+//!
+//! A [`Portal`] is an [`Interactable`](super::interactable::Interactable)
+//! that moves whoever confirms it: pressing the interactable's action while
+//! focused fades the screen out, then either moves the interactor to a
+//! position in the current scene, or switches scenes and places every entity
+//! matching the interactable's mask on a named spawn point of the new scene,
+//! and fades back in.
+//!
+//! A [`SpawnPoint`] names a position, with optional properties (facing,
+//! which enemy to spawn, ...). Spawn points come from map files
+//! (`spawn_point` on an entity), the `spawn_points` lists of tilemap and
+//! [`GridLayout`](super::gridlayout::GridLayout) JSON (see
+//! [`SpawnPointCell`]) or Lua (`:with_spawn_point(name, properties)`), and
+//! are collected into the
+//! [`SpawnPoints`](crate::resources::spawnpoints::SpawnPoints) registry, which
+//! Lua reads with `engine.get_spawn_point(name)`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     MapPosition::new(320.0, 96.0),
+//!     BoxCollider::new(16.0, 16.0),
+//!     Interactable::new("Enter"),
+//!     Portal::new(PortalDestination::Scene {
+//!         scene: "cave".into(),
+//!         spawn_point: "entrance".into(),
+//!     }),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::portal`] – the transition and the spawn point registry
+//! - [`crate::resources::portaltransition`] – fade state of the running transition
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Color, Vector2};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::components::gridlayout::GridValue;
+
+/// Default duration of each half (out and in) of the fade, in seconds.
+pub const DEFAULT_PORTAL_FADE: f32 = 0.3;
+
+/// Where a [`Portal`] leads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PortalDestination {
+    /// A world position in the current scene.
+    Position(Vector2),
+    /// A spawn point in the current scene.
+    SpawnPoint(String),
+    /// A spawn point in another scene.
+    Scene { scene: String, spawn_point: String },
+}
+
+/// Moves the interactor, or switches scenes, on interaction. See the module
+/// docs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Portal {
+    pub destination: PortalDestination,
+    /// Duration of each half of the fade, in seconds; `0` jumps instantly.
+    pub fade: f32,
+    /// Colour the screen fades to.
+    pub fade_color: Color,
+}
+
+impl Portal {
+    pub fn new(destination: PortalDestination) -> Self {
+        Self {
+            destination,
+            fade: DEFAULT_PORTAL_FADE,
+            fade_color: Color::BLACK,
+        }
+    }
+
+    pub fn with_fade(mut self, seconds: f32) -> Self {
+        self.fade = seconds.max(0.0);
+        self
+    }
+
+    pub fn with_fade_color(mut self, color: Color) -> Self {
+        self.fade_color = color;
+        self
+    }
+}
+
+/// A named position portals and scenes can place entities on. The position
+/// is the entity's world position.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SpawnPoint {
+    pub name: String,
+    pub properties: FxHashMap<String, GridValue>,
+}
+
+impl SpawnPoint {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            properties: FxHashMap::default(),
+        }
+    }
+
+    pub fn with_property(mut self, key: impl Into<String>, value: GridValue) -> Self {
+        self.properties.insert(key.into(), value);
+        self
+    }
+}
+
+/// A spawn point declared at the centre of cell `(x, y)` of a tilemap or grid
+/// layout file: `{"name": "player_start", "x": 3, "y": 12, "properties": {...}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnPointCell {
+    pub name: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(default)]
+    pub properties: FxHashMap<String, GridValue>,
+}
+
+impl SpawnPointCell {
+    /// The [`SpawnPoint`] component for this declaration.
+    pub fn to_spawn_point(&self) -> SpawnPoint {
+        SpawnPoint {
+            name: self.name.clone(),
+            properties: self.properties.clone(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Plain-colored progress bars and radial progress rings.
+//!
+//! [`ProgressBar`] and [`RadialProgress`] draw a fill proportional to
+//! `value / max` with raylib shapes, over an optional background color and
+//! background texture. Unlike the themed
+//! [`GuiProgressBar`](super::guiprogressbar::GuiProgressBar) they need no GUI
+//! theme and work in both spaces:
+//!
+//! - with a [`MapPosition`](super::mapposition::MapPosition) they are drawn in
+//!   the world pass, over sprites and texts (boss health on the boss);
+//! - with a [`ScreenPosition`](super::screenposition::ScreenPosition) they are
+//!   drawn with the UI, sorted by [`ZIndex`](super::zindex::ZIndex) (loading
+//!   screens, ability cooldowns).
+//!
+//! Either way the entity needs a `ZIndex`. A bar's top-left corner and a
+//! ring's centre sit at the entity position plus `offset`.
+//!
+//! With a [`ProgressSignal`], `progress_signal_update_system` copies a signal
+//! into `value` every frame: a `WorldSignals` key, or a key of another
+//! entity's [`Signals`](super::signals::Signals), such as the fraction a
+//! [`Cooldown`](super::cooldown::Cooldown) publishes.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // A cooldown ring around the dash icon, emptying as the dash recharges.
+//! commands.spawn((
+//!     ScreenPosition::new(24.0, 24.0),
+//!     ZIndex(10.0),
+//!     RadialProgress::new(16.0, 1.0)
+//!         .with_inner_radius(12.0)
+//!         .with_colors(Color::SKYBLUE, Some(Color::DARKGRAY))
+//!         .with_signal(ProgressSignal::entity(player, "dash_cd")),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::progress::progress_signal_update_system`] – signal binding
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Color, Rectangle, Vector2};
+
+use crate::components::guiprogressbar::ProgressBarDirection;
+use crate::components::signalbinding::SignalSource;
+use crate::key::Key;
+
+/// Arc segments used for a full ring; partial arcs use a share of them.
+pub const RADIAL_SEGMENTS: i32 = 48;
+
+/// Signal copied into a progress `value` every frame.
+#[derive(Clone, Debug)]
+pub struct ProgressSignal {
+    pub key: Key,
+    pub source: SignalSource,
+}
+
+impl ProgressSignal {
+    /// A `WorldSignals` key.
+    pub fn world(key: impl Into<Key>) -> Self {
+        Self {
+            key: key.into(),
+            source: SignalSource::World,
+        }
+    }
+
+    /// A key of `entity`'s `Signals`.
+    pub fn entity(entity: Entity, key: impl Into<Key>) -> Self {
+        Self {
+            key: key.into(),
+            source: SignalSource::Entity(entity),
+        }
+    }
+}
+
+/// Rectangular bar filled to `value / max`. See the module docs.
+#[derive(Component, Clone, Debug)]
+pub struct ProgressBar {
+    pub size: Vector2,
+    pub value: f32,
+    pub max: f32,
+    pub direction: ProgressBarDirection,
+    /// Top-left corner relative to the entity position.
+    pub offset: Vector2,
+    pub fill_color: Color,
+    /// Color of the empty part. `None` leaves it transparent.
+    pub background_color: Option<Color>,
+    /// Texture stretched over the whole bar, under the fill.
+    pub background: Option<Key>,
+    pub signal: Option<ProgressSignal>,
+}
+
+impl ProgressBar {
+    pub fn new(width: f32, height: f32, max: f32) -> Self {
+        Self {
+            size: Vector2::new(width.max(0.0), height.max(0.0)),
+            value: 0.0,
+            max: max.max(0.0),
+            direction: ProgressBarDirection::Horizontal,
+            offset: Vector2::zero(),
+            fill_color: Color::LIME,
+            background_color: Some(Color::DARKGRAY),
+            background: None,
+            signal: None,
+        }
+    }
+
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: ProgressBarDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+
+    pub fn with_colors(mut self, fill: Color, background: Option<Color>) -> Self {
+        self.fill_color = fill;
+        self.background_color = background;
+        self
+    }
+
+    pub fn with_background(mut self, tex_key: impl Into<Key>) -> Self {
+        self.background = Some(tex_key.into());
+        self
+    }
+
+    pub fn with_signal(mut self, signal: ProgressSignal) -> Self {
+        self.signal = Some(signal);
+        self
+    }
+
+    /// `value / max`, clamped to `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        fraction(self.value, self.max)
+    }
+
+    /// The whole bar for an entity at `pos`.
+    pub fn rect(&self, pos: Vector2) -> Rectangle {
+        let corner = pos + self.offset;
+        Rectangle::new(corner.x, corner.y, self.size.x, self.size.y)
+    }
+
+    /// The filled part of [`rect`](Self::rect), grown from the edge
+    /// `direction` starts at.
+    pub fn fill_rect(&self, pos: Vector2) -> Rectangle {
+        let Rectangle {
+            x,
+            y,
+            width: w,
+            height: h,
+        } = self.rect(pos);
+        let ratio = self.fraction();
+        match self.direction {
+            ProgressBarDirection::Horizontal => Rectangle::new(x, y, w * ratio, h),
+            ProgressBarDirection::HorizontalReversed => {
+                Rectangle::new(x + w * (1.0 - ratio), y, w * ratio, h)
+            }
+            ProgressBarDirection::Vertical => {
+                Rectangle::new(x, y + h * (1.0 - ratio), w, h * ratio)
+            }
+            ProgressBarDirection::VerticalReversed => Rectangle::new(x, y, w, h * ratio),
+        }
+    }
+}
+
+/// Ring (or pie, with no inner radius) filled to `value / max`, sweeping
+/// from `start_angle`. See the module docs.
+#[derive(Component, Clone, Debug)]
+pub struct RadialProgress {
+    pub radius: f32,
+    /// `0.0` draws a pie instead of a ring.
+    pub inner_radius: f32,
+    pub value: f32,
+    pub max: f32,
+    /// Where the fill starts, in degrees clockwise from the right; `-90.0`
+    /// is the top.
+    pub start_angle: f32,
+    /// Sweep clockwise on screen (default) or counter-clockwise.
+    pub clockwise: bool,
+    /// Centre relative to the entity position.
+    pub offset: Vector2,
+    pub fill_color: Color,
+    /// Color of the empty part. `None` leaves it transparent.
+    pub background_color: Option<Color>,
+    /// Texture stretched over the ring's bounding square, under the fill.
+    pub background: Option<Key>,
+    pub signal: Option<ProgressSignal>,
+}
+
+impl RadialProgress {
+    pub fn new(radius: f32, max: f32) -> Self {
+        Self {
+            radius: radius.max(0.0),
+            inner_radius: 0.0,
+            value: 0.0,
+            max: max.max(0.0),
+            start_angle: -90.0,
+            clockwise: true,
+            offset: Vector2::zero(),
+            fill_color: Color::LIME,
+            background_color: Some(Color::DARKGRAY),
+            background: None,
+            signal: None,
+        }
+    }
+
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn with_inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius.clamp(0.0, self.radius);
+        self
+    }
+
+    pub fn with_start_angle(mut self, degrees: f32) -> Self {
+        self.start_angle = degrees;
+        self
+    }
+
+    pub fn counter_clockwise(mut self) -> Self {
+        self.clockwise = false;
+        self
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+
+    pub fn with_colors(mut self, fill: Color, background: Option<Color>) -> Self {
+        self.fill_color = fill;
+        self.background_color = background;
+        self
+    }
+
+    pub fn with_background(mut self, tex_key: impl Into<Key>) -> Self {
+        self.background = Some(tex_key.into());
+        self
+    }
+
+    pub fn with_signal(mut self, signal: ProgressSignal) -> Self {
+        self.signal = Some(signal);
+        self
+    }
+
+    /// `value / max`, clamped to `[0, 1]`.
+    pub fn fraction(&self) -> f32 {
+        fraction(self.value, self.max)
+    }
+
+    /// Start and end angle of the filled arc, in raylib's degrees.
+    pub fn fill_angles(&self) -> (f32, f32) {
+        let sweep = 360.0 * self.fraction();
+        if self.clockwise {
+            (self.start_angle, self.start_angle + sweep)
+        } else {
+            (self.start_angle - sweep, self.start_angle)
+        }
+    }
+}
+
+fn fraction(value: f32, max: f32) -> f32 {
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_fill_grows_from_the_direction_edge() {
+        let bar = ProgressBar::new(100.0, 10.0, 50.0)
+            .with_value(25.0)
+            .with_offset(-50.0, -20.0);
+        let pos = Vector2::new(200.0, 100.0);
+        assert_eq!(bar.rect(pos), Rectangle::new(150.0, 80.0, 100.0, 10.0));
+        assert_eq!(bar.fill_rect(pos), Rectangle::new(150.0, 80.0, 50.0, 10.0));
+
+        let bar = bar.with_direction(ProgressBarDirection::HorizontalReversed);
+        assert_eq!(bar.fill_rect(pos), Rectangle::new(200.0, 80.0, 50.0, 10.0));
+        let bar = bar
+            .with_direction(ProgressBarDirection::Vertical)
+            .with_value(80.0);
+        assert_eq!(bar.fill_rect(pos), Rectangle::new(150.0, 80.0, 100.0, 10.0));
+    }
+
+    #[test]
+    fn radial_fill_sweeps_from_the_start_angle() {
+        let ring = RadialProgress::new(16.0, 4.0).with_value(1.0);
+        assert_eq!(ring.fill_angles(), (-90.0, 0.0));
+        assert_eq!(ring.counter_clockwise().fill_angles(), (-180.0, -90.0));
+        assert_eq!(RadialProgress::new(16.0, 0.0).fraction(), 0.0);
+    }
+}
@@ -0,0 +1,83 @@
+//! Render layer component.
+//!
+//! A [`RenderLayer`] groups drawables so a whole layer can be hidden at
+//! runtime with the
+//! [`LayerVisibility`](crate::resources::layervisibility::LayerVisibility)
+//! resource. Layers do not change the draw order; that is still
+//! [`ZIndex`](super::zindex::ZIndex).
+//!
+//! Five layers are named; any other number from 0 to 255 can be used as well:
+//!
+//! | Layer | Number | Default for |
+//! |-------|--------|-------------|
+//! | `background` | 0 | |
+//! | `gameplay` | 1 | world sprites and texts without a `RenderLayer` |
+//! | `fx` | 2 | weather particles |
+//! | `ui` | 3 | screen-space entities without a `RenderLayer`, GUI widgets and interaction prompts |
+//! | `debug` | 4 | debug-mode shapes and bounds |
+
+use bevy_ecs::prelude::Component;
+
+/// Render layer of a sprite or text entity. See the module docs.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderLayer(pub u8);
+
+impl RenderLayer {
+    pub const BACKGROUND: Self = Self(0);
+    pub const GAMEPLAY: Self = Self(1);
+    pub const FX: Self = Self(2);
+    pub const UI: Self = Self(3);
+    pub const DEBUG: Self = Self(4);
+
+    const NAMES: [(&'static str, Self); 5] = [
+        ("background", Self::BACKGROUND),
+        ("gameplay", Self::GAMEPLAY),
+        ("fx", Self::FX),
+        ("ui", Self::UI),
+        ("debug", Self::DEBUG),
+    ];
+
+    /// Parse a layer name (`"background"`, `"gameplay"`, `"fx"`, `"ui"`,
+    /// `"debug"`) or a layer number (`"0"` to `"255"`).
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, layer)| *layer)
+            .or_else(|| name.parse().ok().map(Self))
+    }
+
+    /// Name of a named layer, `None` for the others.
+    pub fn name(self) -> Option<&'static str> {
+        Self::NAMES
+            .iter()
+            .find(|(_, layer)| *layer == self)
+            .map(|(n, _)| *n)
+    }
+}
+
+impl Default for RenderLayer {
+    fn default() -> Self {
+        Self::GAMEPLAY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_numbers() {
+        assert_eq!(RenderLayer::parse("fx"), Some(RenderLayer::FX));
+        assert_eq!(RenderLayer::parse("ui"), Some(RenderLayer(3)));
+        assert_eq!(RenderLayer::parse("200"), Some(RenderLayer(200)));
+        assert_eq!(RenderLayer::parse("256"), None);
+        assert_eq!(RenderLayer::parse("sky"), None);
+    }
+
+    #[test]
+    fn only_named_layers_have_names() {
+        assert_eq!(RenderLayer::DEBUG.name(), Some("debug"));
+        assert_eq!(RenderLayer(9).name(), None);
+    }
+}
@@ -0,0 +1,66 @@
+//! Scene re-entry restoration marker.
+//!
+//! Entities with [`RestoreOnReturn`] have their state remembered by
+//! [`SceneState`](crate::resources::scenestate::SceneState) when their scene
+//! is left, and restored when the scene is entered again — so a backtracking
+//! player finds doors still open and collected items still gone.
+//!
+//! The `key` identifies the entity across visits (entity IDs change every
+//! time a scene is respawned), so it must be unique within the scene and
+//! stable between spawns, e.g. `"door_3"` or `"coin_12_4"`.
+//!
+//! What is saved:
+//! - whether the entity still exists (despawned entities are despawned again
+//!   on re-entry)
+//! - its [`MapPosition`](super::mapposition::MapPosition), unless `position` is `false`
+//! - its [`Signals`](super::signals::Signals), unless `signals` is `false`
+//!
+//! [`Persistent`](super::persistent::Persistent) entities are never
+//! despawned on scene switches and are ignored.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("door"),
+//!     MapPosition::new(320.0, 96.0),
+//!     Signals::default(),
+//!     RestoreOnReturn::new("door_3").with_position(false),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Marks an entity whose state survives leaving and re-entering its scene.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct RestoreOnReturn {
+    /// Stable identifier of the entity within its scene.
+    pub key: String,
+    /// Save and restore the entity's `MapPosition`.
+    pub position: bool,
+    /// Save and restore the entity's `Signals`.
+    pub signals: bool,
+}
+
+impl RestoreOnReturn {
+    /// Restore existence, position and signals for the entity named `key`.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            position: true,
+            signals: true,
+        }
+    }
+
+    /// Whether to save and restore the entity's position.
+    pub fn with_position(mut self, position: bool) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Whether to save and restore the entity's signals.
+    pub fn with_signals(mut self, signals: bool) -> Self {
+        self.signals = signals;
+        self
+    }
+}
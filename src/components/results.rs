@@ -0,0 +1,195 @@
+//! Results screen shown at the end of a level or a game.
+//!
+//! A [`ResultsScreen`] lists label/value rows in screen space. The values
+//! count up from zero one row after another; the first confirm press
+//! (`action_1` or `action_2`) skips to the final numbers and the next one
+//! closes the screen and triggers a
+//! [`ResultsDoneEvent`](crate::events::results::ResultsDoneEvent).
+//!
+//! Row values are either fixed numbers or the name of a
+//! [`WorldSignals`](crate::resources::worldsignals::WorldSignals) integer or
+//! scalar, read once when the screen opens.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn(
+//!     ResultsScreen::new(vec![
+//!         ResultRow::new("Score", ResultValue::Signal("score".into())),
+//!         ResultRow::new("Time", ResultValue::Number(83.4)).with_decimals(1),
+//!     ])
+//!     .with_callback("on_results_done"),
+//! );
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::results::results_screen_system`] – spawns the texts, counts and closes
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Color, Vector2};
+
+/// Where a row's final value comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultValue {
+    /// A fixed number.
+    Number(f64),
+    /// A world signal integer or scalar, read when the screen opens.
+    Signal(String),
+}
+
+/// One label/value line of a [`ResultsScreen`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultRow {
+    pub label: String,
+    pub value: ResultValue,
+    /// Digits shown after the decimal point.
+    pub decimals: usize,
+}
+
+impl ResultRow {
+    pub fn new(label: impl Into<String>, value: ResultValue) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            decimals: 0,
+        }
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+}
+
+/// Screen-space table of results whose numbers count up, closed by confirm.
+#[derive(Component, Debug, Clone)]
+pub struct ResultsScreen {
+    pub rows: Vec<ResultRow>,
+    /// Screen position of the first label.
+    pub position: Vector2,
+    /// Horizontal distance from the labels to the values.
+    pub value_offset: f32,
+    /// Vertical distance between rows.
+    pub row_spacing: f32,
+    pub font: String,
+    pub font_size: f32,
+    pub color: Color,
+    pub z_index: f32,
+    /// Seconds each row takes to count up.
+    pub count_duration: f32,
+    /// Lua function called when the screen is closed.
+    pub callback: Option<String>,
+    /// Seconds since the count started.
+    pub elapsed: f32,
+    /// Resolved final value of each row, filled when the screen opens.
+    pub(crate) targets: Vec<f64>,
+    /// Label and value text entities of each row.
+    pub(crate) texts: Vec<(Entity, Entity)>,
+    pub(crate) opened: bool,
+}
+
+impl ResultsScreen {
+    pub fn new(rows: Vec<ResultRow>) -> Self {
+        Self {
+            rows,
+            position: Vector2::new(40.0, 40.0),
+            value_offset: 240.0,
+            row_spacing: 32.0,
+            font: "default".into(),
+            font_size: 24.0,
+            color: Color::WHITE,
+            z_index: 100.0,
+            count_duration: 1.0,
+            callback: None,
+            elapsed: 0.0,
+            targets: Vec::new(),
+            texts: Vec::new(),
+            opened: false,
+        }
+    }
+
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position = Vector2::new(x, y);
+        self
+    }
+
+    pub fn with_callback(mut self, callback: impl Into<String>) -> Self {
+        self.callback = Some(callback.into());
+        self
+    }
+
+    /// Seconds until every row shows its final value.
+    pub fn total_duration(&self) -> f32 {
+        self.rows.len() as f32 * self.count_duration
+    }
+
+    /// Whether some row is still counting up.
+    pub fn is_counting(&self) -> bool {
+        self.elapsed < self.total_duration()
+    }
+
+    /// How far row `index` has counted, from 0 to 1.
+    pub fn row_progress(&self, index: usize) -> f32 {
+        if self.count_duration <= 0.0 {
+            return 1.0;
+        }
+        ((self.elapsed - index as f32 * self.count_duration) / self.count_duration).clamp(0.0, 1.0)
+    }
+
+    /// Text shown for row `index` at the current progress.
+    pub fn value_text(&self, index: usize) -> String {
+        let target = self.targets.get(index).copied().unwrap_or(0.0);
+        let decimals = self.rows.get(index).map_or(0, |r| r.decimals);
+        let progress = self.row_progress(index) as f64;
+        let value = if progress >= 1.0 {
+            target
+        } else {
+            let scale = 10f64.powi(decimals as i32);
+            (target * progress * scale).trunc() / scale
+        };
+        format!("{value:.decimals$}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen() -> ResultsScreen {
+        let mut screen = ResultsScreen::new(vec![
+            ResultRow::new("Score", ResultValue::Number(1000.0)),
+            ResultRow::new("Time", ResultValue::Number(12.5)).with_decimals(1),
+        ]);
+        screen.targets = vec![1000.0, 12.5];
+        screen
+    }
+
+    #[test]
+    fn rows_count_up_one_after_another() {
+        let mut screen = screen();
+        assert_eq!(screen.value_text(0), "0");
+        assert_eq!(screen.value_text(1), "0.0");
+
+        screen.elapsed = 0.5;
+        assert_eq!(screen.value_text(0), "500");
+        assert_eq!(screen.value_text(1), "0.0");
+
+        screen.elapsed = 1.5;
+        assert_eq!(screen.value_text(0), "1000");
+        assert_eq!(screen.value_text(1), "6.2");
+        assert!(screen.is_counting());
+
+        screen.elapsed = screen.total_duration();
+        assert_eq!(screen.value_text(1), "12.5");
+        assert!(!screen.is_counting());
+    }
+
+    #[test]
+    fn zero_duration_shows_final_values() {
+        let mut screen = screen();
+        screen.count_duration = 0.0;
+        assert_eq!(screen.value_text(0), "1000");
+        assert!(!screen.is_counting());
+    }
+}
@@ -8,11 +8,18 @@
 //! The `frozen` flag allows temporarily disabling all movement calculations,
 //! useful when an entity's position is controlled externally (e.g., ball stuck
 //! to paddle).
+//!
+//! Every push the engine gives a body (its own forces, force areas, the wind
+//! and impulses) is divided by its `mass`. The default mass of `1.0` leaves
+//! them unchanged; a crate of mass `4.0` is blown a quarter as far as a leaf.
 
 use bevy_ecs::prelude::Component;
 use raylib::prelude::Vector2;
 use rustc_hash::FxHashMap;
 
+/// Smallest mass a [`RigidBody`] can have.
+pub const MIN_MASS: f32 = 0.001;
+
 /// A named acceleration force that can be toggled on/off.
 #[derive(Clone, Copy, Debug)]
 pub struct AccelerationForce {
@@ -48,6 +55,7 @@ impl AccelerationForce {
 /// - `friction` - Velocity damping factor (0.0 = no friction, higher = more drag)
 /// - `max_speed` - Optional maximum speed clamp
 /// - `frozen` - When true, movement system skips all calculations for this entity
+/// - `mass` - Divides every force and impulse applied to the body
 ///
 /// # Example
 /// ```ignore
@@ -76,6 +84,9 @@ pub struct RigidBody {
     /// When true, movement system skips all physics calculations for this entity.
     /// Position can still be modified externally (e.g., by StuckTo system).
     pub frozen: bool,
+    /// Mass dividing forces and impulses. Always positive; `1.0` by default.
+    /// Use [`set_mass`](Self::set_mass) to change it.
+    pub mass: f32,
 }
 
 impl Default for RigidBody {
@@ -93,6 +104,7 @@ impl RigidBody {
             friction: 0.0,
             max_speed: None,
             frozen: false,
+            mass: 1.0,
         }
     }
 
@@ -108,6 +120,7 @@ impl RigidBody {
             friction,
             max_speed,
             frozen: false,
+            mass: 1.0,
         }
     }
 
@@ -173,6 +186,21 @@ impl RigidBody {
         total
     }
 
+    /// Set the mass. Values below [`MIN_MASS`] are raised to it.
+    pub fn set_mass(&mut self, mass: f32) {
+        self.mass = mass.max(MIN_MASS);
+    }
+
+    /// `1 / mass`, the factor applied to forces and impulses.
+    pub fn inverse_mass(&self) -> f32 {
+        1.0 / self.mass.max(MIN_MASS)
+    }
+
+    /// Change the velocity by `impulse / mass`.
+    pub fn apply_impulse(&mut self, impulse: Vector2) {
+        self.velocity += impulse * self.inverse_mass();
+    }
+
     /// Set the velocity of the RigidBody.
     pub fn set_velocity(&mut self, velocity: Vector2) {
         self.velocity = velocity;
@@ -262,6 +290,7 @@ mod tests {
         assert!(approx_eq(rb.friction, 0.0));
         assert!(rb.max_speed.is_none());
         assert!(!rb.frozen);
+        assert!(approx_eq(rb.mass, 1.0));
     }
 
     #[test]
@@ -472,6 +501,28 @@ mod tests {
         assert!(vec_approx_eq(rb.velocity, Vector2 { x: 15.0, y: 17.0 }));
     }
 
+    // ==================== MASS TESTS ====================
+
+    #[test]
+    fn test_impulse_is_divided_by_mass() {
+        let mut rb = RigidBody::new();
+        rb.apply_impulse(Vector2 { x: 10.0, y: 0.0 });
+        assert!(vec_approx_eq(rb.velocity, Vector2 { x: 10.0, y: 0.0 }));
+
+        rb.set_mass(4.0);
+        rb.apply_impulse(Vector2 { x: 0.0, y: -20.0 });
+        assert!(vec_approx_eq(rb.velocity, Vector2 { x: 10.0, y: -5.0 }));
+    }
+
+    #[test]
+    fn test_set_mass_stays_positive() {
+        let mut rb = RigidBody::new();
+        rb.set_mass(0.0);
+        assert!(approx_eq(rb.mass, MIN_MASS));
+        rb.set_mass(-3.0);
+        assert!(rb.inverse_mass().is_finite());
+    }
+
     // ==================== FREEZE/UNFREEZE TESTS ====================
 
     #[test]
@@ -0,0 +1,305 @@
+//! Ropes and chains: a chain of points hanging between two anchors.
+//!
+//! A [`Rope`] is simulated by
+//! [`rope_system`](crate::systems::rope::rope_system) as a Verlet point
+//! chain: every point falls under `gravity`, then `iterations` passes pull
+//! neighbouring points back to at most `segment_length` apart. Segments can
+//! go slack but never stretch (with `stiffness` 1), which gives hanging
+//! lamps, bridges of planks and grappling hooks.
+//!
+//! Each end is a [`RopeAnchor`]:
+//!
+//! - a fixed world point;
+//! - an entity (plus an offset from its position). An entity with a
+//!   non-frozen [`RigidBody`](super::rigidbody::RigidBody) hangs from the
+//!   rope: the rope pulls it back and corrects its velocity, so it swings. Any
+//!   other entity drags the end along and is not pulled;
+//! - nothing, for a loose end. An end whose entity was despawned becomes
+//!   loose.
+//!
+//! Points are in world coordinates; the rope entity itself needs no
+//! position. The rope is drawn as a polyline of `thickness` and `color`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Hanging lamp swinging from the ceiling
+//! let lamp = commands
+//!     .spawn((MapPosition::new(160.0, 96.0), RigidBody::new(), lamp_sprite))
+//!     .id();
+//! commands.spawn(Rope::new(
+//!     RopeAnchor::Point(Vector2::new(160.0, 0.0)),
+//!     RopeAnchor::entity(lamp),
+//!     96.0,
+//!     12,
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::rope`] – simulation and entity coupling
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Color, Vector2};
+
+/// What an end of a [`Rope`] is attached to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RopeAnchor {
+    /// Fixed world point.
+    Point(Vector2),
+    /// `offset` from an entity's world position.
+    Entity { entity: Entity, offset: Vector2 },
+    /// Loose end.
+    Free,
+}
+
+impl RopeAnchor {
+    /// Attach to `entity`'s position.
+    pub fn entity(entity: Entity) -> Self {
+        Self::Entity {
+            entity,
+            offset: Vector2::zero(),
+        }
+    }
+}
+
+/// How an end is held during one [`Rope::step`], resolved from its
+/// [`RopeAnchor`] by the rope system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RopeEnd {
+    /// Pinned at this point; the rope cannot move it.
+    Fixed(Vector2),
+    /// Starts the step at this point and is pulled by the rope like any
+    /// other point. The end's final position is returned by `step`.
+    Driven(Vector2),
+    /// Moves freely.
+    Loose,
+}
+
+impl RopeEnd {
+    fn position(self) -> Option<Vector2> {
+        match self {
+            Self::Fixed(p) | Self::Driven(p) => Some(p),
+            Self::Loose => None,
+        }
+    }
+
+    fn weight(self) -> f32 {
+        match self {
+            Self::Fixed(_) => 0.0,
+            Self::Driven(_) | Self::Loose => 1.0,
+        }
+    }
+}
+
+/// A Verlet rope between two anchors. See the module docs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Rope {
+    pub start: RopeAnchor,
+    pub end: RopeAnchor,
+    /// Number of segments (points minus one).
+    pub segments: usize,
+    /// Rest length of each segment.
+    pub segment_length: f32,
+    /// Fraction of a stretch corrected per pass, from 0 (elastic) to 1.
+    pub stiffness: f32,
+    /// Constraint passes per step; more passes stretch less.
+    pub iterations: u32,
+    /// Acceleration applied to every point (units/s²).
+    pub gravity: Vector2,
+    /// Fraction of the points' velocity removed per second.
+    pub damping: f32,
+    /// Line thickness when drawn.
+    pub thickness: f32,
+    /// Line color when drawn.
+    pub color: Color,
+    points: Vec<Vector2>,
+    previous: Vec<Vector2>,
+}
+
+impl Rope {
+    /// Rope of total `length` split into `segments` segments between
+    /// `start` and `end`.
+    pub fn new(start: RopeAnchor, end: RopeAnchor, length: f32, segments: usize) -> Self {
+        let segments = segments.max(1);
+        Self {
+            start,
+            end,
+            segments,
+            segment_length: length.max(0.0) / segments as f32,
+            stiffness: 1.0,
+            iterations: 8,
+            gravity: Vector2::new(0.0, 980.0),
+            damping: 0.5,
+            thickness: 2.0,
+            color: Color::new(140, 100, 60, 255),
+            points: Vec::new(),
+            previous: Vec::new(),
+        }
+    }
+
+    /// Set the correction per pass and the number of passes.
+    pub fn with_stiffness(mut self, stiffness: f32, iterations: u32) -> Self {
+        self.stiffness = stiffness.clamp(0.0, 1.0);
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    /// Set the gravity applied to the points.
+    pub fn with_gravity(mut self, gravity: Vector2) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Set the fraction of velocity removed per second.
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    /// Set how the rope is drawn.
+    pub fn with_style(mut self, thickness: f32, color: Color) -> Self {
+        self.thickness = thickness;
+        self.color = color;
+        self
+    }
+
+    /// Total rest length.
+    pub fn length(&self) -> f32 {
+        self.segment_length * self.segments as f32
+    }
+
+    /// Current point positions, start to end. Empty until the first step.
+    pub fn points(&self) -> &[Vector2] {
+        &self.points
+    }
+
+    /// Lay the points out on a straight line from the known end(s). With one
+    /// known end the rope hangs straight down from it; with none it hangs
+    /// from the origin.
+    fn lay_out(&mut self, start: RopeEnd, end: RopeEnd) {
+        let count = self.segments + 1;
+        let down = Vector2::new(0.0, self.segment_length);
+        let (from, step) = match (start.position(), end.position()) {
+            (Some(a), Some(b)) => (a, (b - a) / self.segments as f32),
+            (Some(a), None) => (a, down),
+            (None, Some(b)) => (b - down * self.segments as f32, down),
+            (None, None) => (Vector2::zero(), down),
+        };
+        self.points = (0..count).map(|i| from + step * i as f32).collect();
+        self.previous = self.points.clone();
+    }
+
+    /// Advance the rope by `dt` seconds with its ends held as given. Returns
+    /// the final positions of the first and last points.
+    pub fn step(&mut self, dt: f32, start: RopeEnd, end: RopeEnd) -> (Vector2, Vector2) {
+        let last = self.segments;
+        if self.points.len() != last + 1 {
+            self.lay_out(start, end);
+        }
+        // Held ends start where their anchor is, without Verlet velocity: a
+        // driven entity moves by its own rigid body.
+        for (index, held) in [(0, start), (last, end)] {
+            if let Some(p) = held.position() {
+                self.points[index] = p;
+                self.previous[index] = p;
+            }
+        }
+
+        if dt > 0.0 {
+            let keep = (1.0 - self.damping * dt).max(0.0);
+            let fall = self.gravity * dt * dt;
+            for (point, previous) in self.points.iter_mut().zip(self.previous.iter_mut()) {
+                let velocity = (*point - *previous) * keep;
+                *previous = *point;
+                *point += velocity + fall;
+            }
+            for (index, held) in [(0, start), (last, end)] {
+                if let Some(p) = held.position() {
+                    self.points[index] = p;
+                }
+            }
+        }
+
+        let (start_weight, end_weight) = (start.weight(), end.weight());
+        let weight = |i: usize| match i {
+            0 => start_weight,
+            i if i == last => end_weight,
+            _ => 1.0,
+        };
+        for _ in 0..self.iterations {
+            for i in 0..last {
+                let (wa, wb) = (weight(i), weight(i + 1));
+                if wa + wb == 0.0 {
+                    continue;
+                }
+                let delta = self.points[i + 1] - self.points[i];
+                let distance = delta.length();
+                if distance <= self.segment_length {
+                    continue;
+                }
+                let correction =
+                    delta * ((distance - self.segment_length) / distance * self.stiffness);
+                self.points[i] += correction * (wa / (wa + wb));
+                self.points[i + 1] -= correction * (wb / (wa + wb));
+            }
+        }
+        (self.points[0], self.points[last])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: Vector2, b: Vector2) -> f32 {
+        (b - a).length()
+    }
+
+    #[test]
+    fn new_rope_hangs_from_its_fixed_end() {
+        let mut rope = Rope::new(RopeAnchor::Free, RopeAnchor::Free, 40.0, 4);
+        let top = Vector2::new(10.0, 0.0);
+        rope.step(0.0, RopeEnd::Fixed(top), RopeEnd::Loose);
+        assert_eq!(rope.points().len(), 5);
+        assert_eq!(rope.points()[0], top);
+        assert_eq!(rope.points()[4], Vector2::new(10.0, 40.0));
+    }
+
+    #[test]
+    fn segments_never_stretch_past_their_length() {
+        let mut rope =
+            Rope::new(RopeAnchor::Free, RopeAnchor::Free, 40.0, 4).with_stiffness(1.0, 40);
+        let top = Vector2::new(0.0, 0.0);
+        for _ in 0..120 {
+            rope.step(1.0 / 60.0, RopeEnd::Fixed(top), RopeEnd::Loose);
+        }
+        assert_eq!(rope.points()[0], top);
+        for pair in rope.points().windows(2) {
+            assert!(distance(pair[0], pair[1]) <= rope.segment_length + 0.01);
+        }
+        // Hanging still under gravity: the loose end is straight below.
+        let end = rope.points()[4];
+        assert!(end.x.abs() < 0.5 && end.y > 39.0);
+    }
+
+    #[test]
+    fn driven_end_is_pulled_back_within_reach() {
+        let mut rope =
+            Rope::new(RopeAnchor::Free, RopeAnchor::Free, 10.0, 1).with_gravity(Vector2::zero());
+        let (_, end) = rope.step(
+            0.0,
+            RopeEnd::Fixed(Vector2::zero()),
+            RopeEnd::Driven(Vector2::new(30.0, 0.0)),
+        );
+        assert!((end.x - 10.0).abs() < 1e-4);
+
+        // Slack ropes leave the end alone.
+        let (_, end) = rope.step(
+            0.0,
+            RopeEnd::Fixed(Vector2::zero()),
+            RopeEnd::Driven(Vector2::new(5.0, 0.0)),
+        );
+        assert_eq!(end, Vector2::new(5.0, 0.0));
+    }
+}
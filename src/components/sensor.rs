@@ -0,0 +1,125 @@
+//! Ray sensors for ground and wall detection.
+//!
+//! A [`Sensor`] holds one or more [`SensorRay`]s cast from the entity's world
+//! position every frame by
+//! [`sensor_system`](crate::systems::sensor::sensor_system). Each ray tests
+//! the [`BoxCollider`](super::boxcollider::BoxCollider)s of other entities
+//! whose group or tags match its mask, and writes the result into the
+//! entity's [`Signals`](super::signals::Signals) as a flag: set while the ray
+//! hits something, cleared otherwise.
+//!
+//! Controllers, phases and animation rules can then read `"grounded"` or
+//! `"wall_left"` like any other signal, without a custom system.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("player"),
+//!     MapPosition::new(100.0, 100.0),
+//!     BoxCollider::new(16.0, 24.0).with_origin(Vector2::new(8.0, 24.0)),
+//!     Signals::default(),
+//!     Sensor::new()
+//!         .with_ray(SensorRay::new("grounded", Vector2::zero(), Vector2::new(0.0, 1.0), 2.0)
+//!             .with_mask("ground|platform"))
+//!         .with_ray(SensorRay::new("wall_left", Vector2::new(0.0, -12.0), Vector2::new(-1.0, 0.0), 9.0)
+//!             .with_mask("ground")),
+//! ));
+//! ```
+//!
+//! The entity needs a [`Signals`](super::signals::Signals) component; sensors
+//! on entities without one are skipped.
+//!
+//! # Related
+//!
+//! - [`crate::systems::sensor`] – casts the rays and updates the flags
+//! - [`crate::components::collision::pattern_matches`] – mask syntax
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::Vector2;
+use smallvec::SmallVec;
+
+/// A single ray cast from the entity's pivot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorRay {
+    /// Signal flag set on the entity while the ray hits something.
+    pub flag: String,
+    /// Ray start relative to the entity's world position.
+    pub offset: Vector2,
+    /// Unit direction of the ray.
+    pub direction: Vector2,
+    /// Ray length in world units.
+    pub length: f32,
+    /// Group pattern of entities the ray can hit (`"*"` by default).
+    pub mask: String,
+}
+
+impl SensorRay {
+    /// Create a ray writing `flag`. `direction` is normalized; a zero
+    /// direction points down.
+    pub fn new(flag: impl Into<String>, offset: Vector2, direction: Vector2, length: f32) -> Self {
+        let direction = if direction.length_sqr() > 0.0 {
+            direction.normalized()
+        } else {
+            Vector2::new(0.0, 1.0)
+        };
+        Self {
+            flag: flag.into(),
+            offset,
+            direction,
+            length: length.max(0.0),
+            mask: "*".into(),
+        }
+    }
+
+    /// Restrict the ray to entities whose group or tags match `mask`.
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Segment start and displacement for an entity at `world_pos`.
+    pub fn segment(&self, world_pos: Vector2) -> (Vector2, Vector2) {
+        (world_pos + self.offset, self.direction * self.length)
+    }
+}
+
+/// Ray sensors attached to an entity.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Sensor {
+    pub rays: SmallVec<[SensorRay; 2]>,
+}
+
+impl Sensor {
+    /// Create a sensor with no rays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a ray.
+    pub fn with_ray(mut self, ray: SensorRay) -> Self {
+        self.rays.push(ray);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_normalizes_direction() {
+        let ray = SensorRay::new("grounded", Vector2::zero(), Vector2::new(0.0, 5.0), 3.0);
+        assert_eq!(ray.direction, Vector2::new(0.0, 1.0));
+        assert_eq!(ray.mask, "*");
+        let (start, delta) = ray.segment(Vector2::new(10.0, 10.0));
+        assert_eq!(start, Vector2::new(10.0, 10.0));
+        assert_eq!(delta, Vector2::new(0.0, 3.0));
+    }
+
+    #[test]
+    fn zero_direction_points_down() {
+        let ray = SensorRay::new("grounded", Vector2::zero(), Vector2::zero(), 1.0);
+        assert_eq!(ray.direction, Vector2::new(0.0, 1.0));
+    }
+}
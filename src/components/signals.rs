@@ -8,9 +8,10 @@
 //! - string values (`strings`)
 //! - boolean flags (`flags`)
 //!
-//! Keys are `String`s, allowing you to standardize on a small set of names
-//! across your game (e.g. "hp", "is_running"). Accessors are provided to set,
-//! query, and read views of each collection.
+//! Keys are interned [`Key`]s, allowing you to standardize on a small set of
+//! names across your game (e.g. "hp", "is_running") without allocating a
+//! `String` per entity. Maps are still looked up with `&str`. Accessors are
+//! provided to set, query, and read views of each collection.
 //!
 //! # Entity vs World Signals
 //!
@@ -43,6 +44,8 @@
 use bevy_ecs::prelude::Component;
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use crate::key::Key;
+
 #[derive(Debug, Clone, Component, Default)]
 /// Bag-of-signals component used by systems to exchange simple values.
 ///
@@ -51,30 +54,30 @@ use rustc_hash::{FxHashMap, FxHashSet};
 /// dedicated system each tick if they represent transient state.
 pub struct Signals {
     /// Floating-point numeric signals addressed by string keys.
-    pub scalars: FxHashMap<String, f32>,
+    pub scalars: FxHashMap<Key, f32>,
     /// Integer numeric signals addressed by string keys.
-    pub integers: FxHashMap<String, i32>,
+    pub integers: FxHashMap<Key, i32>,
     /// Presence-only boolean flags; a key being present means "true".
-    pub flags: FxHashSet<String>,
+    pub flags: FxHashSet<Key>,
     /// String signals addressed by string keys.
-    pub strings: FxHashMap<String, String>,
+    pub strings: FxHashMap<Key, String>,
 }
 
 impl Signals {
     /// Set a floating-point signal value.
-    pub fn set_scalar(&mut self, key: impl Into<String>, value: f32) {
+    pub fn set_scalar(&mut self, key: impl Into<Key>, value: f32) {
         self.scalars.insert(key.into(), value);
     }
     /// Set a scalar without allocating when the key already exists.
     ///
-    /// Updates the existing value in place if present; otherwise allocates the
-    /// key once on first insert. Prefer this in per-frame hot paths with fixed
+    /// Updates the existing value in place if present; otherwise interns the
+    /// key on first insert. Prefer this in per-frame hot paths with fixed
     /// `&'static str` keys (e.g. `movement`).
     pub fn update_scalar(&mut self, key: &str, value: f32) {
         if let Some(slot) = self.scalars.get_mut(key) {
             *slot = value;
         } else {
-            self.scalars.insert(key.to_string(), value);
+            self.scalars.insert(Key::new(key), value);
         }
     }
     /// Get a floating-point signal by key.
@@ -86,11 +89,11 @@ impl Signals {
         self.scalars.remove(key)
     }
     /// Read-only view of all scalar signals.
-    pub fn get_scalars(&self) -> &FxHashMap<String, f32> {
+    pub fn get_scalars(&self) -> &FxHashMap<Key, f32> {
         &self.scalars
     }
     /// Set an integer signal value.
-    pub fn set_integer(&mut self, key: impl Into<String>, value: i32) {
+    pub fn set_integer(&mut self, key: impl Into<Key>, value: i32) {
         self.integers.insert(key.into(), value);
     }
     /// Get an integer signal by key.
@@ -102,25 +105,25 @@ impl Signals {
         self.integers.remove(key)
     }
     /// Read-only view of all integer signals.
-    pub fn get_integers(&self) -> &FxHashMap<String, i32> {
+    pub fn get_integers(&self) -> &FxHashMap<Key, i32> {
         &self.integers
     }
     /// Create a Signals with a single flag set.
-    pub fn with_flag(mut self, key: impl Into<String>) -> Self {
+    pub fn with_flag(mut self, key: impl Into<Key>) -> Self {
         self.set_flag(key);
         self
     }
     /// Mark a flag as present/true.
-    pub fn set_flag(&mut self, key: impl Into<String>) {
+    pub fn set_flag(&mut self, key: impl Into<Key>) {
         self.flags.insert(key.into());
     }
     /// Mark a flag present without allocating when it is already set.
     ///
-    /// Allocates the key only on the first insert. Prefer this in per-frame hot
+    /// Interns the key only on the first insert. Prefer this in per-frame hot
     /// paths with fixed `&'static str` keys (e.g. `movement`).
     pub fn ensure_flag(&mut self, key: &str) {
         if !self.flags.contains(key) {
-            self.flags.insert(key.to_string());
+            self.flags.insert(Key::new(key));
         }
     }
     /// Remove a flag (make it false/absent).
@@ -138,15 +141,15 @@ impl Signals {
     /// Toggle a flag: remove it if present, add it if absent.
     pub fn toggle_flag(&mut self, key: &str) {
         if !self.flags.remove(key) {
-            self.flags.insert(key.to_string());
+            self.flags.insert(Key::new(key));
         }
     }
     /// Read-only view of all flags.
-    pub fn get_flags(&self) -> &FxHashSet<String> {
+    pub fn get_flags(&self) -> &FxHashSet<Key> {
         &self.flags
     }
     /// Set a string signal value.
-    pub fn set_string(&mut self, key: impl Into<String>, value: impl Into<String>) {
+    pub fn set_string(&mut self, key: impl Into<Key>, value: impl Into<String>) {
         self.strings.insert(key.into(), value.into());
     }
     /// Get a string signal by key.
@@ -158,7 +161,7 @@ impl Signals {
         self.strings.remove(key)
     }
     /// Read-only view of all string signals.
-    pub fn get_strings(&self) -> &FxHashMap<String, String> {
+    pub fn get_strings(&self) -> &FxHashMap<Key, String> {
         &self.strings
     }
 }
@@ -208,7 +211,7 @@ mod tests {
         assert_eq!(s.get_scalar("speed_sq"), Some(99.0));
         assert_eq!(
             ptr_before, ptr_after,
-            "the key String must be reused across updates (no per-call realloc)"
+            "the interned key must be reused across updates (no per-call realloc)"
         );
     }
 
@@ -225,7 +228,7 @@ mod tests {
         assert_eq!(s.flags.len(), 1);
         assert_eq!(
             ptr_before, ptr_after,
-            "the flag String must be reused when already present"
+            "the interned flag must be reused when already present"
         );
     }
 
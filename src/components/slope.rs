@@ -0,0 +1,188 @@
+//! Right-triangle slope colliders.
+//!
+//! [`Slope`] turns an entity's [`BoxCollider`](super::boxcollider::BoxCollider)
+//! into a right triangle filling the box, for ramps in platformers. The
+//! [`SlopeCorner`] names the corner holding the right angle: `BottomLeft` and
+//! `BottomRight` are floors (`◣` and `◢`), `TopLeft` and `TopRight` are
+//! ceilings (`◤` and `◥`).
+//!
+//! When a box overlaps a slope's box, the
+//! [`collision_detector`](crate::systems::collision_detector::collision_detector)
+//! only reports the pair if the box reaches the triangle, measured at the
+//! box's horizontal centre. Its [`CollisionEvent`](crate::events::collision::CollisionEvent)
+//! then pushes the box straight up out of a floor (or down out of a ceiling)
+//! to the slanted surface, so a controller that resolves collisions by
+//! `-normal * depth` walks up and down the ramp without sliding or
+//! stair-stepping. Past the triangle's tall side the box hits a plain wall.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // A 32x16 ramp rising to the right, next to a solid block.
+//! commands.spawn((
+//!     Group::new("ground"),
+//!     MapPosition::new(64.0, 112.0),
+//!     BoxCollider::new(32.0, 16.0),
+//!     Slope::new(SlopeCorner::BottomRight),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Rectangle, Vector2};
+use serde::Deserialize;
+
+/// Corner of the collider box holding the slope's right angle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlopeCorner {
+    /// Floor rising to the left (`◣`).
+    BottomLeft,
+    /// Floor rising to the right (`◢`).
+    BottomRight,
+    /// Ceiling lowering to the left (`◤`).
+    TopLeft,
+    /// Ceiling lowering to the right (`◥`).
+    TopRight,
+}
+
+impl SlopeCorner {
+    /// Parse `"bottom_left"`, `"bottom_right"`, `"top_left"` or `"top_right"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "bottom_left" => Some(SlopeCorner::BottomLeft),
+            "bottom_right" => Some(SlopeCorner::BottomRight),
+            "top_left" => Some(SlopeCorner::TopLeft),
+            "top_right" => Some(SlopeCorner::TopRight),
+            _ => None,
+        }
+    }
+
+    /// Whether the triangle is a floor, solid below its slanted side.
+    pub fn is_floor(self) -> bool {
+        matches!(self, SlopeCorner::BottomLeft | SlopeCorner::BottomRight)
+    }
+
+    fn is_left(self) -> bool {
+        matches!(self, SlopeCorner::BottomLeft | SlopeCorner::TopLeft)
+    }
+}
+
+/// Make this entity's collider a right triangle. See the module docs.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slope {
+    pub corner: SlopeCorner,
+}
+
+impl Slope {
+    pub fn new(corner: SlopeCorner) -> Self {
+        Self { corner }
+    }
+
+    /// Height of the slanted side above `x`, clamped to the box `rect`.
+    pub fn surface_y(&self, rect: &Rectangle, x: f32) -> f32 {
+        let t = if rect.width > 0.0 {
+            ((x - rect.x) / rect.width).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        // 0 at the tall side, 1 at the opposite corner.
+        let rise = if self.corner.is_left() { t } else { 1.0 - t };
+        if self.corner.is_floor() {
+            rect.y + rise * rect.height
+        } else {
+            rect.y + (1.0 - rise) * rect.height
+        }
+    }
+
+    /// Contact between this slope, with collider box `rect`, and the box
+    /// `other`.
+    ///
+    /// Returns the penetration depth and the unit normal pointing from
+    /// `other` toward the slope (moving `other` by `-normal * depth`
+    /// separates them), or `None` if `other` does not reach the triangle.
+    pub fn contact(&self, rect: &Rectangle, other: &Rectangle) -> Option<(f32, Vector2)> {
+        rect.get_collision_rec(other)?;
+        let probe = other.x + other.width * 0.5;
+        let (left, right) = (rect.x, rect.x + rect.width);
+        // Beyond the tall side the triangle is a wall.
+        if self.corner.is_left() && probe < left {
+            return Some((other.x + other.width - left, Vector2::new(1.0, 0.0)));
+        }
+        if !self.corner.is_left() && probe > right {
+            return Some((right - other.x, Vector2::new(-1.0, 0.0)));
+        }
+        let surface = self.surface_y(rect, probe);
+        let (top, bottom) = (rect.y, rect.y + rect.height);
+        let (other_top, other_bottom) = (other.y, other.y + other.height);
+        // Push out through the slanted side or the flat one, whichever is
+        // shorter.
+        let (slanted, flat, normal) = if self.corner.is_floor() {
+            (
+                other_bottom - surface,
+                bottom - other_top,
+                Vector2::new(0.0, 1.0),
+            )
+        } else {
+            (
+                surface - other_top,
+                other_bottom - top,
+                Vector2::new(0.0, -1.0),
+            )
+        };
+        if slanted <= 0.0 {
+            return None;
+        }
+        if slanted <= flat {
+            Some((slanted, normal))
+        } else {
+            Some((flat, -normal))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxes_stand_on_the_slanted_side() {
+        // 32x16 ramp rising to the right, from y 116 up to y 100.
+        let ramp = Slope::new(SlopeCorner::BottomRight);
+        let rect = Rectangle::new(0.0, 100.0, 32.0, 16.0);
+        assert_eq!(ramp.surface_y(&rect, 0.0), 116.0);
+        assert_eq!(ramp.surface_y(&rect, 16.0), 108.0);
+        assert_eq!(ramp.surface_y(&rect, 40.0), 100.0);
+
+        // A player box centred on x 16 with its feet at y 106 is above it...
+        assert_eq!(
+            ramp.contact(&rect, &Rectangle::new(12.0, 90.0, 8.0, 16.0)),
+            None
+        );
+        // ...and at y 110 sinks 2 units in and is pushed straight up.
+        assert_eq!(
+            ramp.contact(&rect, &Rectangle::new(12.0, 94.0, 8.0, 16.0)),
+            Some((2.0, Vector2::new(0.0, 1.0)))
+        );
+        // Past the tall side it hits a wall.
+        assert_eq!(
+            ramp.contact(&rect, &Rectangle::new(30.0, 104.0, 8.0, 16.0)),
+            Some((2.0, Vector2::new(-1.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn ceilings_push_down() {
+        let ceiling = Slope::new(SlopeCorner::TopLeft);
+        let rect = Rectangle::new(0.0, 0.0, 16.0, 16.0);
+        // Lowest at the left: the slanted side is at y 8 in the middle.
+        assert_eq!(ceiling.surface_y(&rect, 8.0), 8.0);
+        assert_eq!(
+            ceiling.contact(&rect, &Rectangle::new(4.0, 5.0, 8.0, 16.0)),
+            Some((3.0, Vector2::new(0.0, -1.0)))
+        );
+        assert_eq!(
+            ceiling.contact(&rect, &Rectangle::new(4.0, 9.0, 8.0, 16.0)),
+            None
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Multi-label tag component for entity categorization.
+//!
+//! [`Group`](super::group::Group) holds a single name, so an entity cannot be
+//! both "enemy" and "flying". The [`Tags`] component adds any number of extra
+//! labels. Wherever the engine matches entities by group name — collision
+//! rules, tracked group counts and aggregates — an entity matches if its
+//! `Group` **or any of its tags** equals the name.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("enemy"),
+//!     Tags::new(["flying", "boss"]),
+//!     MapPosition::new(400.0, 120.0),
+//! ));
+//!
+//! // A CollisionRule between "bullet" and "flying" now matches this entity.
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::components::group::Group`] – primary single-name label
+//! - [`crate::components::collision::CollisionRule`] – matches on group or tags
+//! - [`crate::systems::group::update_group_counts_system`] – counts tagged entities
+
+use bevy_ecs::prelude::Component;
+use smallvec::SmallVec;
+
+use super::group::Group;
+
+/// Set of extra labels attached to an entity, in insertion order.
+///
+/// Duplicate labels are ignored on insertion.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tags(pub SmallVec<[String; 4]>);
+
+impl Tags {
+    /// Create a tag set from any iterable of names. Duplicates are dropped.
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut tags = Self::default();
+        for name in names {
+            tags.insert(name);
+        }
+        tags
+    }
+
+    /// Add a tag. Returns `false` if it was already present.
+    pub fn insert(&mut self, name: impl Into<String>) -> bool {
+        let name = name.into();
+        if self.has(&name) {
+            return false;
+        }
+        self.0.push(name);
+        true
+    }
+
+    /// Remove a tag. Returns `false` if it was not present.
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.0.iter().position(|t| t == name) {
+            Some(i) => {
+                self.0.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the entity carries `name` as a tag.
+    pub fn has(&self, name: &str) -> bool {
+        self.0.iter().any(|t| t == name)
+    }
+
+    /// Iterate over the tag names.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// Number of tags.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no tags.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Iterate over every label of an entity: its [`Group`] name first (if any),
+/// followed by its [`Tags`] that differ from the group name.
+///
+/// Each label is yielded once, so counting systems can use it directly.
+pub fn entity_labels<'a>(
+    group: Option<&'a Group>,
+    tags: Option<&'a Tags>,
+) -> impl Iterator<Item = &'a str> {
+    let group_name = group.map(Group::name);
+    group_name.into_iter().chain(
+        tags.into_iter()
+            .flat_map(Tags::iter)
+            .filter(move |t| Some(*t) != group_name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_drops_duplicates() {
+        let tags = Tags::new(["enemy", "flying", "enemy"]);
+        assert_eq!(tags.len(), 2);
+        assert!(tags.has("enemy"));
+        assert!(tags.has("flying"));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut tags = Tags::default();
+        assert!(tags.insert("a"));
+        assert!(!tags.insert("a"));
+        assert!(tags.remove("a"));
+        assert!(!tags.remove("a"));
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn entity_labels_yields_group_then_unique_tags() {
+        let group = Group::new("enemy");
+        let tags = Tags::new(["flying", "enemy"]);
+        let labels: Vec<&str> = entity_labels(Some(&group), Some(&tags)).collect();
+        assert_eq!(labels, vec!["enemy", "flying"]);
+    }
+
+    #[test]
+    fn entity_labels_without_group() {
+        let tags = Tags::new(["flying"]);
+        let labels: Vec<&str> = entity_labels(None, Some(&tags)).collect();
+        assert_eq!(labels, vec!["flying"]);
+        assert_eq!(entity_labels(None, None).count(), 0);
+    }
+}
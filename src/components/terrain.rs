@@ -0,0 +1,188 @@
+//! Destructible terrain surfaces.
+//!
+//! A [`Terrain`] entity is an editable bitmap: when it spawns, the
+//! [`terrain_system`](crate::systems::terrain::terrain_system) copies the
+//! `source` texture into a texture of its own, builds its alpha mask and
+//! gives the entity a [`Sprite`](super::sprite::Sprite), a
+//! [`BoxCollider`](super::boxcollider::BoxCollider) covering the bitmap and
+//! [`PixelCollision`](super::pixelcollision::PixelCollision). Carving
+//! (see [`CarveTerrainCircle`](crate::systems::terrain::CarveTerrainCircle))
+//! clears texels in the [`TerrainSurface`] and the mask at once, so
+//! collisions follow the holes immediately and the texture catches up before
+//! the next frame is drawn.
+//!
+//! The bitmap is drawn unscaled and unrotated with its top-left corner at the
+//! entity's position.
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Color, Vector2};
+
+use crate::key::Key;
+use crate::resources::alphamask::AlphaMask;
+
+/// Make this entity a destructible bitmap copied from the texture `source`.
+/// See the module docs.
+#[derive(Component, Clone, Debug)]
+pub struct Terrain {
+    pub source: Key,
+}
+
+impl Terrain {
+    pub fn new(source: impl Into<Key>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+}
+
+/// Texture and alpha mask key of `entity`'s terrain bitmap.
+///
+/// Keyed by the entity index, so a despawned terrain's slot is reused rather
+/// than growing the texture and key registries.
+pub fn terrain_texture_key(entity: Entity) -> String {
+    format!("__terrain_{}", entity.index_u32())
+}
+
+/// Texel rectangle `[x0, x1) × [y0, y1)` of a terrain bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TexelRect {
+    pub x0: u32,
+    pub y0: u32,
+    pub x1: u32,
+    pub y1: u32,
+}
+
+impl TexelRect {
+    fn union(self, other: Self) -> Self {
+        Self {
+            x0: self.x0.min(other.x0),
+            y0: self.y0.min(other.y0),
+            x1: self.x1.max(other.x1),
+            y1: self.y1.max(other.y1),
+        }
+    }
+}
+
+/// CPU copy of a spawned terrain's pixels, added by the terrain system.
+#[derive(Component, Clone, Debug)]
+pub struct TerrainSurface {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA pixels.
+    pub pixels: Vec<Color>,
+    /// Texels changed since the texture was last updated.
+    pub dirty: Option<TexelRect>,
+}
+
+impl TerrainSurface {
+    pub fn new(width: u32, height: u32, pixels: Vec<Color>) -> Self {
+        Self {
+            width,
+            height,
+            pixels,
+            dirty: None,
+        }
+    }
+
+    /// Clear every texel whose center lies within `radius` of `center`
+    /// (bitmap coordinates), in both the pixels and `mask`. Returns `true` if
+    /// any texel changed.
+    pub fn carve_circle(&mut self, center: Vector2, radius: f32, mask: &mut AlphaMask) -> bool {
+        if radius <= 0.0 {
+            return false;
+        }
+        let x0 = (center.x - radius).floor().max(0.0) as u32;
+        let y0 = (center.y - radius).floor().max(0.0) as u32;
+        let x1 = ((center.x + radius).ceil().max(0.0) as u32).min(self.width);
+        let y1 = ((center.y + radius).ceil().max(0.0) as u32).min(self.height);
+        let mut changed = false;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let dx = x as f32 + 0.5 - center.x;
+                let dy = y as f32 + 0.5 - center.y;
+                if dx * dx + dy * dy > radius * radius {
+                    continue;
+                }
+                let pixel = &mut self.pixels[(y * self.width + x) as usize];
+                if pixel.a == 0 {
+                    continue;
+                }
+                *pixel = Color::BLANK;
+                mask.set_solid(x as i32, y as i32, false);
+                changed = true;
+            }
+        }
+        if changed {
+            let rect = TexelRect { x0, y0, x1, y1 };
+            self.dirty = Some(self.dirty.map_or(rect, |dirty| dirty.union(rect)));
+        }
+        changed
+    }
+
+    /// RGBA bytes of the texels in `rect`, row by row, as
+    /// `UpdateTextureRec` expects them.
+    pub fn rect_bytes(&self, rect: TexelRect) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(((rect.x1 - rect.x0) * (rect.y1 - rect.y0) * 4) as usize);
+        for y in rect.y0..rect.y1 {
+            let row = (y * self.width) as usize;
+            for pixel in &self.pixels[row + rect.x0 as usize..row + rect.x1 as usize] {
+                bytes.extend_from_slice(&[pixel.r, pixel.g, pixel.b, pixel.a]);
+            }
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32) -> (TerrainSurface, AlphaMask) {
+        let len = (width * height) as usize;
+        (
+            TerrainSurface::new(width, height, vec![Color::WHITE; len]),
+            AlphaMask::from_alpha(width, height, std::iter::repeat_n(255, len)),
+        )
+    }
+
+    #[test]
+    fn carve_clears_pixels_and_mask() {
+        let (mut surface, mut mask) = solid(8, 8);
+        assert!(surface.carve_circle(Vector2::new(4.0, 4.0), 1.0, &mut mask));
+        for (x, y) in [(3, 3), (4, 3), (3, 4), (4, 4)] {
+            assert_eq!(surface.pixels[y * 8 + x], Color::BLANK);
+            assert!(!mask.is_solid(x as i32, y as i32));
+        }
+        assert!(mask.is_solid(2, 4));
+        assert_eq!(
+            surface.dirty,
+            Some(TexelRect {
+                x0: 3,
+                y0: 3,
+                x1: 5,
+                y1: 5
+            })
+        );
+    }
+
+    #[test]
+    fn carving_empty_space_changes_nothing() {
+        let (mut surface, mut mask) = solid(8, 8);
+        assert!(surface.carve_circle(Vector2::new(4.0, 4.0), 2.0, &mut mask));
+        surface.dirty = None;
+        assert!(!surface.carve_circle(Vector2::new(4.0, 4.0), 1.0, &mut mask));
+        assert!(!surface.carve_circle(Vector2::new(-20.0, 4.0), 3.0, &mut mask));
+        assert_eq!(surface.dirty, None);
+    }
+
+    #[test]
+    fn dirty_rects_accumulate() {
+        let (mut surface, mut mask) = solid(8, 8);
+        surface.carve_circle(Vector2::new(1.0, 1.0), 1.0, &mut mask);
+        surface.carve_circle(Vector2::new(7.0, 6.0), 1.0, &mut mask);
+        let dirty = surface.dirty.unwrap();
+        assert_eq!((dirty.x0, dirty.y0, dirty.x1, dirty.y1), (0, 0, 8, 7));
+        assert_eq!(surface.rect_bytes(dirty).len(), 8 * 7 * 4);
+    }
+}
@@ -0,0 +1,217 @@
+//! Demo-style text scroller.
+//!
+//! A [`TextScroller`] moves a line of text along a [`ScrollerPath`], one
+//! glyph at a time: the classic intro scroller where every letter bobs on a
+//! sine wave as the message crosses the screen. The glyphs are [`DynamicText`]
+//! children of the scroller entity, drawn relative to its
+//! [`MapPosition`](super::mapposition::MapPosition).
+//!
+//! Only the glyphs on the track get an entity. The
+//! [`text_scroller_system`](crate::systems::textscroller::text_scroller_system)
+//! keeps a pool sized to the track and hands its entities to the glyphs
+//! entering it, so a long message costs as much as a short one. The message
+//! enters at the far end of the track and starts over once its last glyph
+//! has left.
+//!
+//! Glyphs are placed `spacing` units apart, which suits monospaced fonts;
+//! proportional fonts look best with a spacing close to their widest glyph.
+//!
+//! # Usage from Lua
+//!
+//! ```lua
+//! engine.spawn()
+//!     :with_position(0, 200)
+//!     :with_text_scroller("WELCOME TO THE ABERRED ENGINE", "arcade", 32, 120, "sine",
+//!         { width = 640, amplitude = 24, wavelength = 320 })
+//!     :build()
+//! ```
+//!
+//! [`DynamicText`]: super::dynamictext::DynamicText
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::{Color, Vector2};
+use std::f32::consts::TAU;
+
+/// Curve the glyphs of a [`TextScroller`] follow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollerPath {
+    /// Glyphs ride a fixed sine curve along the track: each one rises and
+    /// falls as it crosses a crest.
+    Sine { amplitude: f32, wavelength: f32 },
+    /// The sine curve itself travels one wavelength per second, so the line
+    /// ripples even where glyphs are close together.
+    Wave { amplitude: f32, wavelength: f32 },
+    /// Glyphs go round a circle centered on the entity, clockwise from its
+    /// right-hand side. The track is the circumference.
+    Circle { radius: f32 },
+}
+
+impl ScrollerPath {
+    /// Parses `"sine"`, `"wave"` or `"circle"`. `amplitude` is the radius of
+    /// a circle.
+    pub fn parse(name: &str, amplitude: f32, wavelength: f32) -> Option<Self> {
+        match name {
+            "sine" => Some(ScrollerPath::Sine {
+                amplitude,
+                wavelength,
+            }),
+            "wave" => Some(ScrollerPath::Wave {
+                amplitude,
+                wavelength,
+            }),
+            "circle" => Some(ScrollerPath::Circle { radius: amplitude }),
+            _ => None,
+        }
+    }
+
+    /// Offset from the entity of the point `distance` units along the track,
+    /// `elapsed` seconds after the scroller started.
+    pub fn point(&self, distance: f32, elapsed: f32) -> Vector2 {
+        match *self {
+            ScrollerPath::Sine {
+                amplitude,
+                wavelength,
+            } => Vector2::new(
+                distance,
+                -amplitude * (TAU * distance / wavelength.max(1.0)).sin(),
+            ),
+            ScrollerPath::Wave {
+                amplitude,
+                wavelength,
+            } => Vector2::new(
+                distance,
+                -amplitude * (TAU * (distance / wavelength.max(1.0) + elapsed)).sin(),
+            ),
+            ScrollerPath::Circle { radius } => {
+                let angle = distance / radius.max(1.0);
+                Vector2::new(radius * angle.cos(), radius * angle.sin())
+            }
+        }
+    }
+}
+
+/// Scrolls `text` glyph by glyph along a path.
+#[derive(Component, Debug, Clone)]
+pub struct TextScroller {
+    pub text: String,
+    pub font: String,
+    pub font_size: f32,
+    pub color: Color,
+    /// Units per second the text moves toward the start of the track.
+    pub speed: f32,
+    pub path: ScrollerPath,
+    /// Length of a sine or wave track; a circle's track is its circumference.
+    pub width: f32,
+    /// Distance between consecutive glyphs.
+    pub spacing: f32,
+    pub(crate) elapsed: f32,
+    /// Pooled glyph entities, children of the scroller.
+    pub(crate) glyphs: Vec<Entity>,
+}
+
+impl TextScroller {
+    pub fn new(
+        text: impl Into<String>,
+        font: impl Into<String>,
+        font_size: f32,
+        speed: f32,
+        path: ScrollerPath,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            font: font.into(),
+            font_size,
+            color: Color::WHITE,
+            speed,
+            path,
+            width: 640.0,
+            spacing: font_size,
+            elapsed: 0.0,
+            glyphs: Vec::new(),
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width.max(0.0);
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.max(1.0);
+        self
+    }
+
+    /// Length of the track the glyphs move along.
+    pub fn track_length(&self) -> f32 {
+        match self.path {
+            ScrollerPath::Circle { radius } => TAU * radius.max(1.0),
+            _ => self.width,
+        }
+    }
+
+    /// Number of glyph entities needed to fill the track.
+    pub fn pool_size(&self) -> usize {
+        let on_track = (self.track_length() / self.spacing).ceil() as usize + 1;
+        on_track.min(self.text.chars().count())
+    }
+
+    /// Glyphs on the track now as `(char, distance along the track)`, in
+    /// text order.
+    pub fn visible_glyphs(&self) -> impl Iterator<Item = (char, f32)> + '_ {
+        let track = self.track_length();
+        let count = self.text.chars().count() as f32;
+        let period = track + count * self.spacing;
+        let scrolled = if period > 0.0 {
+            (self.elapsed * self.speed).rem_euclid(period)
+        } else {
+            0.0
+        };
+        self.text
+            .chars()
+            .enumerate()
+            .map(move |(i, c)| (c, track - scrolled + i as f32 * self.spacing))
+            .filter(move |&(c, distance)| !c.is_whitespace() && (0.0..track).contains(&distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_enter_at_the_far_end_and_wrap() {
+        let path = ScrollerPath::parse("sine", 0.0, 100.0).unwrap();
+        let mut scroller = TextScroller::new("AB C", "font", 10.0, 10.0, path).with_width(30.0);
+        assert_eq!(scroller.pool_size(), 4);
+        assert_eq!(scroller.visible_glyphs().count(), 0);
+
+        scroller.elapsed = 1.5;
+        let glyphs: Vec<_> = scroller.visible_glyphs().collect();
+        assert_eq!(glyphs, vec![('A', 15.0), ('B', 25.0)]);
+
+        // Period: 30 track + 4 glyphs * 10 spacing = 70 units = 7 seconds.
+        scroller.elapsed = 8.5;
+        assert_eq!(scroller.visible_glyphs().count(), 2);
+        assert!(ScrollerPath::parse("spiral", 1.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn paths_place_points() {
+        let sine = ScrollerPath::Sine {
+            amplitude: 10.0,
+            wavelength: 40.0,
+        };
+        let crest = sine.point(10.0, 0.0);
+        assert_eq!(crest.x, 10.0);
+        assert!((crest.y + 10.0).abs() < 1e-4);
+
+        let circle = ScrollerPath::Circle { radius: 20.0 };
+        let quarter = circle.point(TAU * 20.0 / 4.0, 0.0);
+        assert!(quarter.x.abs() < 1e-4 && (quarter.y - 20.0).abs() < 1e-4);
+    }
+}
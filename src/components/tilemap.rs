@@ -1,4 +1,7 @@
-use bevy_ecs::prelude::Component;
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
 
 /// Marks an entity as a tilemap root. A system watches for `Added<TileMap>`,
 /// loads the PNG + JSON from `path`, spawns tile entities as `ChildOf` children,
@@ -6,13 +9,311 @@ use bevy_ecs::prelude::Component;
 ///
 /// The root entity can carry `MapPosition`, `Scale`, and `Rotation` to
 /// transform the whole tilemap as a unit.
+///
+/// The tiles of the `solid_layers` (and of layers marked `"solid": true` in
+/// the file) get colliders, one [`BoxCollider`] per horizontal run of tiles,
+/// in a group named after the layer.
+///
+/// [`BoxCollider`]: crate::components::boxcollider::BoxCollider
 #[derive(Component, Clone, Debug)]
 pub struct TileMap {
     pub path: String,
+    /// Overrides the `projection` field of the tilemap file.
+    pub projection: Option<TileProjection>,
+    /// Names of the layers whose tiles get colliders.
+    pub solid_layers: Vec<String>,
 }
 
 impl TileMap {
     pub fn new(path: impl Into<String>) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            projection: None,
+            solid_layers: Vec::new(),
+        }
+    }
+
+    /// Lay the tiles out with `projection` instead of the file's projection.
+    pub fn with_projection(mut self, projection: TileProjection) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Give the tiles of the layers named in `solid_layers` colliders.
+    pub fn with_solid_layers(mut self, solid_layers: Vec<String>) -> Self {
+        self.solid_layers = solid_layers;
+        self
+    }
+}
+
+/// How tilemap cells are laid out in the tilemap's local space.
+///
+/// Atlas tiles are always `tile_size` square; the projection decides where
+/// each cell's sprite goes and in which order the cells of a layer are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileProjection {
+    /// Square cells in rows and columns.
+    #[default]
+    Orthogonal,
+    /// 2:1 diamond cells (`tile_size` wide, `tile_size / 2` tall). Map x runs
+    /// down-right and map y down-left. Sprites sit on the bottom of their
+    /// diamond, so the top half of an atlas tile can hold a raised block.
+    Isometric,
+    /// Pointy-top hexagons `tile_size` wide and tall, rows `3/4` of a tile
+    /// apart, with odd rows shifted right by half a tile.
+    Hex,
+}
+
+impl TileProjection {
+    /// Parse `"orthogonal"`, `"isometric"` or `"hex"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "orthogonal" => Some(TileProjection::Orthogonal),
+            "isometric" => Some(TileProjection::Isometric),
+            "hex" => Some(TileProjection::Hex),
+            _ => None,
+        }
+    }
+}
+
+/// Cell geometry of a tilemap, in the root entity's local space.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TileGrid {
+    pub projection: TileProjection,
+    /// Side of a square atlas tile in world units.
+    pub tile_size: f32,
+    /// Map size in tiles.
+    pub map_width: u32,
+    pub map_height: u32,
+}
+
+impl TileGrid {
+    /// `true` if `(x, y)` is a cell of the map.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && x < self.map_width as i64 && y < self.map_height as i64
+    }
+
+    /// Centre of the footprint of cell `(x, y)`.
+    pub fn cell_center(&self, x: i64, y: i64) -> Vector2 {
+        let s = self.tile_size;
+        let (x, y) = (x as f32, y as f32);
+        match self.projection {
+            TileProjection::Orthogonal => Vector2::new((x + 0.5) * s, (y + 0.5) * s),
+            TileProjection::Isometric => {
+                Vector2::new((x - y + 1.0) * s * 0.5, (x + y + 1.0) * s * 0.25)
+            }
+            TileProjection::Hex => {
+                let shift = if y.rem_euclid(2.0) == 1.0 { 0.5 } else { 0.0 };
+                Vector2::new((x + 0.5 + shift) * s, (y * 0.75 + 0.5) * s)
+            }
+        }
+    }
+
+    /// Top-left corner of the tile sprite drawn for cell `(x, y)`.
+    pub fn cell_origin(&self, x: u32, y: u32) -> Vector2 {
+        let s = self.tile_size;
+        let center = self.cell_center(x as i64, y as i64);
+        match self.projection {
+            // The diamond's bottom vertex is at the bottom of the sprite.
+            TileProjection::Isometric => Vector2::new(center.x - s * 0.5, center.y - s * 0.75),
+            TileProjection::Orthogonal | TileProjection::Hex => {
+                Vector2::new(center.x - s * 0.5, center.y - s * 0.5)
+            }
+        }
+    }
+
+    /// Corners of the footprint of cell `(x, y)`, in order around the cell.
+    pub fn cell_corners(&self, x: i64, y: i64) -> Vec<Vector2> {
+        let s = self.tile_size;
+        let c = self.cell_center(x, y);
+        let offsets: &[(f32, f32)] = match self.projection {
+            TileProjection::Orthogonal => &[(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)],
+            TileProjection::Isometric => &[(0.0, -0.25), (0.5, 0.0), (0.0, 0.25), (-0.5, 0.0)],
+            TileProjection::Hex => &[
+                (0.0, -0.5),
+                (0.5, -0.25),
+                (0.5, 0.25),
+                (0.0, 0.5),
+                (-0.5, 0.25),
+                (-0.5, -0.25),
+            ],
+        };
+        offsets
+            .iter()
+            .map(|&(dx, dy)| Vector2::new(c.x + dx * s, c.y + dy * s))
+            .collect()
+    }
+
+    /// Cell whose footprint contains `point`, or `None` outside the map.
+    pub fn cell_at(&self, point: Vector2) -> Option<(u32, u32)> {
+        let s = self.tile_size;
+        if s <= 0.0 {
+            return None;
+        }
+        let (x, y) = match self.projection {
+            TileProjection::Orthogonal => ((point.x / s).floor(), (point.y / s).floor()),
+            TileProjection::Isometric => {
+                // a = x - y and b = x + y at cell centres.
+                let a = point.x / (s * 0.5) - 1.0;
+                let b = point.y / (s * 0.25) - 1.0;
+                (((a + b) * 0.5).round(), ((b - a) * 0.5).round())
+            }
+            TileProjection::Hex => {
+                // Fractional axial coordinates, rounded in cube space.
+                let r = point.y / (s * 0.75) - 2.0 / 3.0;
+                let q = (point.x - s * 0.5) / s - r * 0.5;
+                let (q, r) = hex_round(q, r);
+                (q + (r - r.rem_euclid(2.0)) * 0.5, r)
+            }
+        };
+        let (x, y) = (x as i64, y as i64);
+        self.contains(x, y).then_some((x as u32, y as u32))
+    }
+
+    /// Draw-order offset of cell `(x, y)` within its layer, in `[0, 1)`.
+    ///
+    /// Isometric and hex cells further down the screen get a larger offset so
+    /// they are drawn over the cells behind them.
+    pub fn depth(&self, x: u32, y: u32) -> f32 {
+        match self.projection {
+            TileProjection::Orthogonal => 0.0,
+            TileProjection::Isometric => {
+                (x + y) as f32 / (self.map_width + self.map_height).max(1) as f32
+            }
+            TileProjection::Hex => y as f32 / self.map_height.max(1) as f32,
+        }
+    }
+}
+
+/// Round fractional axial hex coordinates to the nearest hex.
+fn hex_round(q: f32, r: f32) -> (f32, f32) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq, rr)
+}
+
+/// Runtime layout of a spawned tilemap, inserted on the [`TileMap`] root once
+/// its tiles are spawned.
+///
+/// It is the source of truth for which tile sits in which cell:
+/// [`SetTileMapLayerVisible`](crate::systems::tilemap::SetTileMapLayerVisible)
+/// and [`SetTileMapTile`](crate::systems::tilemap::SetTileMapTile) edit it and
+/// spawn or despawn the matching tile and collider entities.
+#[derive(Component, Clone, Debug, Default)]
+pub struct TileMapLayers {
+    /// Texture key of the atlas, also the tileset name for auto-tiling rules.
+    pub tileset: String,
+    /// Projection, tile size and map size.
+    pub grid: TileGrid,
+    /// Atlas-cell template entities, indexed by tile id.
+    pub templates: Vec<Entity>,
+    /// Layers in draw order (first is drawn at the back).
+    pub layers: Vec<TileMapLayer>,
+}
+
+impl TileMapLayers {
+    /// Index of the layer called `name`.
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+}
+
+/// One layer of a [`TileMapLayers`].
+#[derive(Clone, Debug, Default)]
+pub struct TileMapLayer {
+    pub name: String,
+    /// Hidden layers keep their tiles here but have no tile entities.
+    pub visible: bool,
+    /// `ZIndex` given to the layer's tile entities.
+    pub z: f32,
+    /// Placed tiles keyed by `(x, y)` cell.
+    pub tiles: FxHashMap<(u32, u32), PlacedTile>,
+    /// The layer's tiles get colliders while it is visible.
+    pub solid: bool,
+    /// Collider entities of a solid layer, one per run of tiles, keyed by row.
+    pub colliders: FxHashMap<u32, Vec<Entity>>,
+}
+
+/// A tile placed in a [`TileMapLayer`] cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlacedTile {
+    /// Atlas tile id.
+    pub id: u32,
+    /// The tile entity, or `None` while its layer is hidden.
+    pub entity: Option<Entity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(projection: TileProjection) -> TileGrid {
+        TileGrid {
+            projection,
+            tile_size: 32.0,
+            map_width: 8,
+            map_height: 6,
+        }
+    }
+
+    #[test]
+    fn cell_at_inverts_cell_center_for_every_projection() {
+        for projection in [
+            TileProjection::Orthogonal,
+            TileProjection::Isometric,
+            TileProjection::Hex,
+        ] {
+            let grid = grid(projection);
+            for y in 0..6 {
+                for x in 0..8 {
+                    let c = grid.cell_center(x, y);
+                    for (dx, dy) in [(0.0, 0.0), (3.0, 2.0), (-3.0, -2.0)] {
+                        let p = Vector2::new(c.x + dx, c.y + dy);
+                        assert_eq!(
+                            grid.cell_at(p),
+                            Some((x as u32, y as u32)),
+                            "{projection:?} cell ({x}, {y})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn isometric_and_hex_layouts() {
+        let iso = grid(TileProjection::Isometric);
+        assert_eq!(iso.cell_center(1, 0), Vector2::new(32.0, 16.0));
+        assert_eq!(iso.cell_center(0, 1), Vector2::new(0.0, 16.0));
+        // The sprite's bottom edge touches the diamond's bottom vertex.
+        assert_eq!(iso.cell_origin(0, 0), Vector2::new(0.0, -16.0));
+        // Left of cell (0, 0) is cell (-1, 1), outside the map.
+        assert_eq!(iso.cell_at(Vector2::new(-20.0, 8.0)), None);
+
+        let hex = grid(TileProjection::Hex);
+        assert_eq!(hex.cell_center(0, 1), Vector2::new(32.0, 40.0));
+        assert_eq!(hex.cell_origin(0, 1), Vector2::new(16.0, 24.0));
+        // Between the two top hexes of row 0, just above row 1's first hex.
+        assert_eq!(hex.cell_at(Vector2::new(32.0, 26.0)), Some((0, 1)));
+    }
+
+    #[test]
+    fn depth_orders_cells_down_the_screen() {
+        let ortho = grid(TileProjection::Orthogonal);
+        assert_eq!(ortho.depth(7, 5), 0.0);
+        let iso = grid(TileProjection::Isometric);
+        assert!(iso.depth(1, 0) > iso.depth(0, 0));
+        assert_eq!(iso.depth(1, 0), iso.depth(0, 1));
+        assert!(iso.depth(7, 5) < 1.0);
+        let hex = grid(TileProjection::Hex);
+        assert!(hex.depth(0, 1) > hex.depth(7, 0));
+        assert!(hex.depth(7, 5) < 1.0);
     }
 }
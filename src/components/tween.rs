@@ -181,6 +181,15 @@ impl<T: TweenValue> Tween<T> {
         self.forward = false;
         self
     }
+
+    /// Holds the `from` value for `delay` seconds before the tween starts.
+    ///
+    /// The delay is stored as negative playback time, so it only applies to
+    /// the first forward run.
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.time = -delay.max(0.0);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -358,6 +367,15 @@ mod tests {
         assert!(!tw.forward);
     }
 
+    #[test]
+    fn test_tween_scale_with_delay() {
+        let tw: Tween<Scale> = Tween::new(scale(0.0, 0.0), scale(1.0, 1.0), 0.5).with_delay(0.25);
+        assert!(approx_eq(tw.time, -0.25));
+
+        let tw: Tween<Scale> = Tween::new(scale(0.0, 0.0), scale(1.0, 1.0), 0.5).with_delay(-1.0);
+        assert!(approx_eq(tw.time, 0.0));
+    }
+
     #[test]
     fn test_tween_scale_non_uniform() {
         let from = scale(1.0, 2.0);
@@ -0,0 +1,101 @@
+//! Shadow-cast visibility area of an entity.
+//!
+//! A [`VisibilityPolygon`] is recomputed every frame by
+//! [`visibility_polygon_system`](crate::systems::visibility::visibility_polygon_system):
+//! rays cast from the entity's position stop at the
+//! [`Occluders`](crate::resources::occluders::Occluders) segments (solid tiles
+//! and labeled colliders) or at `radius`. The result is both a gameplay shape
+//! (`engine.can_see(a, b)`, [`VisibilityPolygon::contains`]) and, with a fill
+//! colour, a light or vision cone drawn by the render system.
+//!
+//! A `fov_degrees` below 360 turns the polygon into a cone centred on the
+//! entity's rotation (0° points right).
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     MapPosition::new(100.0, 100.0),
+//!     Rotation { degrees: 90.0 },
+//!     VisibilityPolygon::new(200.0)
+//!         .with_fov(70.0)
+//!         .with_color(Color::new(255, 240, 160, 80)),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::{Color, Vector2};
+
+use crate::resources::occluders::fan_contains;
+
+/// Visible area around the entity. See the module docs.
+#[derive(Component, Debug, PartialEq)]
+pub struct VisibilityPolygon {
+    /// Sight range in world units.
+    pub radius: f32,
+    /// Opening angle in degrees, centred on the entity's rotation. 360 or
+    /// more sees all around.
+    pub fov_degrees: f32,
+    /// Fill colour of the drawn polygon; `None` computes it without drawing.
+    pub color: Option<Color>,
+    /// World-space position the polygon was cast from.
+    pub origin: Vector2,
+    /// Polygon points sorted by angle, forming a triangle fan with `origin`.
+    pub points: Vec<Vector2>,
+}
+
+impl Clone for VisibilityPolygon {
+    fn clone(&self) -> Self {
+        Self {
+            radius: self.radius,
+            fov_degrees: self.fov_degrees,
+            color: self.color,
+            origin: self.origin,
+            points: self.points.clone(),
+        }
+    }
+
+    /// Reuses the allocation of `points`, for copies refreshed every frame.
+    fn clone_from(&mut self, source: &Self) {
+        self.radius = source.radius;
+        self.fov_degrees = source.fov_degrees;
+        self.color = source.color;
+        self.origin = source.origin;
+        self.points.clone_from(&source.points);
+    }
+}
+
+impl VisibilityPolygon {
+    /// Create an all-around, undrawn visibility polygon.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius: radius.max(0.0),
+            fov_degrees: 360.0,
+            color: None,
+            origin: Vector2::zero(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Limit sight to a cone `fov_degrees` wide.
+    pub fn with_fov(mut self, fov_degrees: f32) -> Self {
+        self.fov_degrees = fov_degrees.clamp(0.0, 360.0);
+        self
+    }
+
+    /// Draw the polygon filled with `color`.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// `true` if the polygon goes all around its origin (no cone).
+    pub fn is_closed(&self) -> bool {
+        self.fov_degrees >= 360.0
+    }
+
+    /// `true` if the world point `point` is inside the last computed polygon.
+    pub fn contains(&self, point: Vector2) -> bool {
+        fan_contains(self.origin, &self.points, self.is_closed(), point)
+    }
+}
@@ -0,0 +1,44 @@
+//! Vision for the fog-of-war overlay.
+//!
+//! Every entity with a [`VisionSource`] makes the
+//! [`FogOfWar`](crate::resources::fogofwar::FogOfWar) cells within `radius` of
+//! its position visible, and marks them as revealed for the current scene.
+//! With `blocked` set, cells behind solid tiles stay hidden (see
+//! [`fog_visibility_system`](crate::systems::fogofwar::fog_visibility_system)).
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     Group::new("player"),
+//!     MapPosition::new(100.0, 100.0),
+//!     VisionSource::new(160.0).blocked(),
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Reveals fog-of-war cells around the entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct VisionSource {
+    /// Sight range in world units, measured from the entity's position.
+    pub radius: f32,
+    /// Whether solid tiles block the line of sight.
+    pub blocked: bool,
+}
+
+impl VisionSource {
+    /// Create an unobstructed vision source.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius: radius.max(0.0),
+            blocked: false,
+        }
+    }
+
+    /// Let solid tiles block this source's line of sight.
+    pub fn blocked(mut self) -> Self {
+        self.blocked = true;
+        self
+    }
+}
@@ -0,0 +1,235 @@
+//! Water surfaces: a wavy water line with splashes and buoyancy.
+//!
+//! A [`WaterSurface`] is a `width`×`depth` body of water whose top-left
+//! corner is the entity's world position. Its surface is a row of vertical
+//! springs, one column every `spacing` units, stepped each frame by
+//! [`water_system`](crate::systems::water::water_system):
+//!
+//! - each column is pulled back to the rest line by `stiffness` and slowed by
+//!   `damping`;
+//! - neighbouring columns pull on each other with `tension`, so a disturbance
+//!   travels along the surface as a wave.
+//!
+//! A [`RigidBody`](super::rigidbody::RigidBody) entering the water pushes the
+//! column under it with `splash` times its vertical speed. While submerged,
+//! bodies are lifted by `buoyancy` (an upward acceleration at full
+//! submersion, scaled by the submerged fraction of their
+//! [`BoxCollider`](super::boxcollider::BoxCollider)) and slowed by `drag`.
+//! Bodies without a collider count as fully submerged when their pivot is
+//! below the surface. Only non-frozen bodies whose group or tags match `mask`
+//! are affected.
+//!
+//! The water is drawn as a filled polygon in `color` following the surface.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     MapPosition::new(0.0, 180.0),
+//!     WaterSurface::new(320.0, 60.0)
+//!         .with_color(Color::new(30, 90, 200, 150))
+//!         .with_mask("player|crate"),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::water`] – simulation, splashes and buoyancy
+//! - [`crate::components::forcearea`] – plain currents without a surface
+
+use bevy_ecs::prelude::Component;
+use raylib::prelude::Color;
+
+/// Longest simulation substep; bigger frame deltas are split so stiff
+/// springs stay stable.
+const MAX_SUBSTEP: f32 = 1.0 / 120.0;
+
+fn column_count(width: f32, spacing: f32) -> usize {
+    (width / spacing).ceil() as usize + 1
+}
+
+/// A body of water with a simulated surface. See the module docs.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WaterSurface {
+    /// Width of the water (world units).
+    pub width: f32,
+    /// Depth of the water below the rest line (world units).
+    pub depth: f32,
+    /// Horizontal distance between surface columns.
+    pub spacing: f32,
+    /// Pull of each column back to the rest line (1/s²).
+    pub stiffness: f32,
+    /// Pull between neighbouring columns that spreads waves (1/s²).
+    pub tension: f32,
+    /// Velocity damping of the columns (1/s).
+    pub damping: f32,
+    /// Column velocity added per unit of an entering body's vertical speed.
+    pub splash: f32,
+    /// Upward acceleration of a fully submerged body (units/s²).
+    pub buoyancy: f32,
+    /// Fraction of a fully submerged body's velocity removed per second.
+    pub drag: f32,
+    /// Fill color.
+    pub color: Color,
+    /// Group pattern of bodies affected (`"*"` by default).
+    pub mask: String,
+    /// Downward displacement of each column from the rest line.
+    heights: Vec<f32>,
+    /// Vertical velocity of each column.
+    velocities: Vec<f32>,
+}
+
+impl WaterSurface {
+    /// Still water `width` wide and `depth` deep with default physics.
+    pub fn new(width: f32, depth: f32) -> Self {
+        let width = width.max(0.0);
+        let spacing = 8.0;
+        Self {
+            width,
+            depth: depth.max(0.0),
+            spacing,
+            stiffness: 60.0,
+            tension: 1500.0,
+            damping: 2.0,
+            splash: 0.6,
+            buoyancy: 1600.0,
+            drag: 3.0,
+            color: Color::new(40, 110, 210, 150),
+            mask: "*".into(),
+            heights: vec![0.0; column_count(width, spacing)],
+            velocities: vec![0.0; column_count(width, spacing)],
+        }
+    }
+
+    /// Use one surface column every `spacing` units (resets the surface).
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing.max(1.0);
+        let columns = column_count(self.width, self.spacing);
+        self.heights = vec![0.0; columns];
+        self.velocities = vec![0.0; columns];
+        self
+    }
+
+    /// Set the spring constants: return pull, neighbour pull and damping.
+    pub fn with_springs(mut self, stiffness: f32, tension: f32, damping: f32) -> Self {
+        self.stiffness = stiffness.max(0.0);
+        self.tension = tension.max(0.0);
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    /// Set how hard entering bodies disturb the surface.
+    pub fn with_splash(mut self, splash: f32) -> Self {
+        self.splash = splash;
+        self
+    }
+
+    /// Set the lift and drag applied to submerged bodies.
+    pub fn with_buoyancy(mut self, buoyancy: f32, drag: f32) -> Self {
+        self.buoyancy = buoyancy;
+        self.drag = drag.max(0.0);
+        self
+    }
+
+    /// Set the fill color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Restrict splashes and buoyancy to bodies whose group or tags match
+    /// `mask`.
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Downward displacement of each column, left to right.
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Local x of column `i`, clamped to the water's width.
+    pub fn column_x(&self, i: usize) -> f32 {
+        (i as f32 * self.spacing).min(self.width)
+    }
+
+    /// Downward displacement of the surface at local `x`, interpolated
+    /// between columns. Outside the water the nearest edge is used.
+    pub fn height_at(&self, x: f32) -> f32 {
+        let last = self.heights.len() - 1;
+        let t = (x / self.spacing).clamp(0.0, last as f32);
+        let i = (t.floor() as usize).min(last.saturating_sub(1));
+        let next = (i + 1).min(last);
+        let f = t - i as f32;
+        self.heights[i] + (self.heights[next] - self.heights[i]) * f
+    }
+
+    /// Push the column nearest local `x` down with `velocity` (negative
+    /// values pull it up).
+    pub fn splash_at(&mut self, x: f32, velocity: f32) {
+        let last = self.velocities.len() - 1;
+        let i = ((x / self.spacing).round().max(0.0) as usize).min(last);
+        self.velocities[i] += velocity;
+    }
+
+    /// Advance the springs by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+        let substeps = (dt / MAX_SUBSTEP).ceil().min(16.0) as usize;
+        let h = dt / substeps as f32;
+        for _ in 0..substeps {
+            for i in 0..self.heights.len() {
+                let left = self.heights[i.saturating_sub(1)];
+                let right = self.heights[(i + 1).min(self.heights.len() - 1)];
+                let height = self.heights[i];
+                let accel = -self.stiffness * height + self.tension * (left + right - 2.0 * height)
+                    - self.damping * self.velocities[i];
+                self.velocities[i] += accel * h;
+            }
+            for (height, velocity) in self.heights.iter_mut().zip(&self.velocities) {
+                *height += velocity * h;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_cover_the_width() {
+        let water = WaterSurface::new(20.0, 10.0);
+        assert_eq!(water.heights().len(), 4);
+        assert_eq!(water.column_x(3), 20.0);
+        let water = water.with_spacing(5.0).with_mask("crate");
+        assert_eq!(water.heights().len(), 5);
+        assert_eq!(water.mask, "crate");
+    }
+
+    #[test]
+    fn height_is_interpolated_between_columns() {
+        let mut water = WaterSurface::new(16.0, 10.0);
+        water.heights[1] = 4.0;
+        assert_eq!(water.height_at(8.0), 4.0);
+        assert_eq!(water.height_at(4.0), 2.0);
+        assert_eq!(water.height_at(-50.0), 0.0);
+        assert_eq!(water.height_at(16.0), 0.0);
+    }
+
+    #[test]
+    fn splash_spreads_and_settles() {
+        let mut water = WaterSurface::new(64.0, 10.0);
+        water.splash_at(32.0, 200.0);
+        water.step(0.05);
+        assert!(water.heights()[4] > 0.0);
+        assert!(water.heights()[3] > 0.0);
+        for _ in 0..600 {
+            water.step(1.0 / 60.0);
+        }
+        assert!(water.heights().iter().all(|h| h.abs() < 0.5));
+    }
+}
@@ -0,0 +1,32 @@
+//! Markers of the entities spawned for the [`Weather`] resource.
+//!
+//! [`weather_system`] spawns them on demand and keeps them configured; games
+//! don't add these components themselves. Removing the entities is harmless:
+//! they are spawned again on the next frame the weather needs them.
+//!
+//! - [`WeatherEmitter`] – the particle emitter of one kind of precipitation
+//! - [`WeatherParticle`] – a rain drop or snow flake (and the emitter's
+//!   template), drawn by the render system
+//! - [`Wind`] – the global force area blowing with the weather's wind
+//!
+//! [`Weather`]: crate::resources::weather::Weather
+//! [`weather_system`]: crate::systems::weather::weather_system
+
+use bevy_ecs::prelude::Component;
+
+use crate::resources::weather::WeatherKind;
+
+/// Particle emitter producing the given kind of precipitation.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherEmitter(pub WeatherKind);
+
+/// Rain drop or snow flake. The template entity carries it too, without a
+/// `MapPosition`, so it is neither moved nor drawn.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherParticle(pub WeatherKind);
+
+/// The global wind force area. Bodies with a
+/// [`WindResponse`](super::windresponse::WindResponse) component respond to
+/// it whatever its mask.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Wind;
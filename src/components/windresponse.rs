@@ -0,0 +1,45 @@
+//! Response of a rigid body to the global wind.
+//!
+//! The wind set on the [`Weather`](crate::resources::weather::Weather)
+//! resource pushes the rigid bodies matching its `wind_mask` with its full
+//! acceleration. A body with a [`WindResponse`] component is pushed whatever
+//! the mask says, by the wind times its `factor`, so falling leaves (`2.0`)
+//! and snow (`1.0`) drift at their own pace while a flag (`0.5`) only
+//! flutters. Like every force, the push is divided by the body's
+//! [`mass`](super::rigidbody::RigidBody::mass).
+//!
+//! The wind is applied by
+//! [`force_area_system`](crate::systems::forcearea::force_area_system)
+//! through the global [`Wind`](super::weather::Wind).
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     MapPosition::new(40.0, 0.0),
+//!     RigidBody::new(),
+//!     WindResponse::new(2.0),
+//!     leaf_sprite,
+//! ));
+//! ```
+
+use bevy_ecs::prelude::Component;
+
+/// Pushes the entity's rigid body with the global wind. See the module docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct WindResponse {
+    /// Multiplier of the wind acceleration; `0.0` ignores the wind.
+    pub factor: f32,
+}
+
+impl Default for WindResponse {
+    fn default() -> Self {
+        Self { factor: 1.0 }
+    }
+}
+
+impl WindResponse {
+    pub fn new(factor: f32) -> Self {
+        Self { factor }
+    }
+}
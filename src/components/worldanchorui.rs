@@ -0,0 +1,106 @@
+//! Screen-space UI pinned to a world entity.
+//!
+//! An entity with a [`WorldAnchorUI`] and a
+//! [`ScreenPosition`](super::screenposition::ScreenPosition) follows its
+//! target on screen: every frame `world_anchor_ui_system` projects the
+//! target's world position through the camera, adds the pixel `offset` and
+//! writes the result into `ScreenPosition`. Nameplates, health bars and
+//! speech bubbles use it instead of doing the projection by hand.
+//!
+//! With a clamp margin the anchored UI is kept inside the screen, `margin`
+//! pixels from the edges, while its target is off screen. The offset is in
+//! screen pixels, so it does not scale with the camera zoom.
+//!
+//! The anchored entity is a GUI root: children positioned with
+//! [`GuiOffset`](super::guioffset::GuiOffset) follow it. It is despawned,
+//! together with those children, when its target no longer exists.
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.spawn((
+//!     ScreenPosition::new(0.0, 0.0),
+//!     ZIndex(10.0),
+//!     GuiProgressBar::new(100.0),
+//!     WorldAnchorUI::new(enemy)
+//!         .with_offset(-16.0, -24.0)
+//!         .with_clamp(4.0),
+//! ));
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::worldanchorui::world_anchor_ui_system`] – updates the screen position
+//! - [`crate::resources::camera2d::Camera2DRes::world_to_screen`] – entity to screen mapping
+
+use bevy_ecs::prelude::{Component, Entity};
+use raylib::prelude::Vector2;
+
+/// Keeps this entity's `ScreenPosition` on the projection of `target`. See
+/// the module docs.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct WorldAnchorUI {
+    /// World entity followed on screen.
+    pub target: Entity,
+    /// Screen pixels added to the projected target position.
+    pub offset: Vector2,
+    /// Distance in pixels kept from the screen edges. `None` lets the UI
+    /// leave the screen with its target.
+    pub clamp_margin: Option<f32>,
+}
+
+impl WorldAnchorUI {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vector2::zero(),
+            clamp_margin: None,
+        }
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vector2::new(x, y);
+        self
+    }
+
+    /// Keeps the UI on screen, `margin` pixels from the edges.
+    pub fn with_clamp(mut self, margin: f32) -> Self {
+        self.clamp_margin = Some(margin.max(0.0));
+        self
+    }
+
+    /// Screen position for a target projected at `target_screen` on a
+    /// `screen` of that size.
+    pub fn screen_position(&self, target_screen: Vector2, screen: Vector2) -> Vector2 {
+        let pos = target_screen + self.offset;
+        let Some(margin) = self.clamp_margin else {
+            return pos;
+        };
+        // A margin wider than half the screen pins the UI to the centre.
+        let clamp = |v: f32, size: f32| {
+            let min = margin.min(size * 0.5);
+            v.clamp(min, size - min)
+        };
+        Vector2::new(clamp(pos.x, screen.x), clamp(pos.y, screen.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_is_added_and_clamping_keeps_the_ui_on_screen() {
+        let target = Entity::from_bits(1);
+        let screen = Vector2::new(320.0, 180.0);
+        let anchor = WorldAnchorUI::new(target).with_offset(0.0, -20.0);
+        let pos = anchor.screen_position(Vector2::new(100.0, 10.0), screen);
+        assert_eq!((pos.x, pos.y), (100.0, -10.0));
+
+        let anchor = anchor.with_clamp(4.0);
+        let pos = anchor.screen_position(Vector2::new(100.0, 10.0), screen);
+        assert_eq!((pos.x, pos.y), (100.0, 4.0));
+        let pos = anchor.screen_position(Vector2::new(900.0, 400.0), screen);
+        assert_eq!((pos.x, pos.y), (316.0, 176.0));
+    }
+}
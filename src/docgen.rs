@@ -0,0 +1,468 @@
+//! Lua API reference generator.
+//!
+//! Renders Markdown or HTML reference pages for every `engine.*` function,
+//! builder method, callback, type and enum. The content comes from
+//! `engine.__meta` through [`EngineMeta`], the same registration metadata
+//! the runtime fills while registering the API and that
+//! [`crate::stub_generator`] turns into `engine.lua`, so the reference cannot
+//! drift from the implementation.
+//!
+//! Both formats are rendered from one intermediate list of [`Block`]s.
+
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::stub_generator::{
+    CallbackMeta, ClassMeta, EngineMeta, EnumMeta, FnMeta, TypeMeta, category_title, class_title,
+    lua_type_annotation,
+};
+use std::fmt::Write as FmtWrite;
+use std::path::Path;
+
+/// Output format of the generated reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocFormat {
+    /// GitHub-flavored Markdown.
+    Markdown,
+    /// Standalone HTML page.
+    Html,
+}
+
+impl DocFormat {
+    /// `Html` for `.html`/`.htm` paths, `Markdown` otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                DocFormat::Html
+            }
+            _ => DocFormat::Markdown,
+        }
+    }
+}
+
+/// Extract `engine.__meta` and render the reference in the given format.
+pub fn generate_docs(runtime: &LuaRuntime, format: DocFormat) -> Result<String, String> {
+    let meta = EngineMeta::from_runtime(runtime)?;
+    let blocks = build_blocks(&meta);
+    Ok(match format {
+        DocFormat::Markdown => render_markdown(&blocks),
+        DocFormat::Html => render_html(&blocks),
+    })
+}
+
+/// Write the generated reference to a file.
+pub fn write_docs(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+// --------------- Document model ---------------
+
+/// A table cell; `Code` cells are rendered in a monospace span.
+enum Cell {
+    Text(String),
+    Code(String),
+}
+
+/// Format-independent building block of the reference.
+enum Block {
+    /// Heading; `code` renders the text as inline code.
+    Heading {
+        level: u8,
+        text: String,
+        code: bool,
+    },
+    Paragraph(String),
+    /// Bulleted list of links to headings: `(text, anchor)`.
+    Links(Vec<(String, String)>),
+    Table {
+        header: &'static [&'static str],
+        rows: Vec<Vec<Cell>>,
+    },
+}
+
+/// GitHub-style heading anchor: lowercase, spaces to `-`, punctuation dropped.
+fn anchor(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn build_blocks(meta: &EngineMeta) -> Vec<Block> {
+    let mut blocks = vec![
+        Block::Heading {
+            level: 1,
+            text: "Aberred Engine Lua API Reference".into(),
+            code: false,
+        },
+        Block::Paragraph(
+            "Generated by `aberredengine --create-lua-docs` from `engine.__meta`. \
+             Do not edit manually; regenerate instead."
+                .into(),
+        ),
+    ];
+
+    // Section titles in output order, for the table of contents
+    let mut sections: Vec<String> = vec!["Callbacks".into()];
+    let mut current = "";
+    for f in &meta.functions {
+        if f.category != current {
+            current = &f.category;
+            sections.push(category_title(current).to_string());
+        }
+    }
+    sections.extend(meta.classes.iter().map(|c| class_title(c).to_string()));
+    sections.push("Types".into());
+    sections.push("Enums".into());
+
+    blocks.push(Block::Heading {
+        level: 2,
+        text: "Contents".into(),
+        code: false,
+    });
+    blocks.push(Block::Links(
+        sections
+            .into_iter()
+            .map(|s| {
+                let a = anchor(&s);
+                (s, a)
+            })
+            .collect(),
+    ));
+
+    build_callbacks(&mut blocks, &meta.callbacks);
+    build_functions(&mut blocks, &meta.functions);
+    for class in &meta.classes {
+        build_class(&mut blocks, class);
+    }
+    build_types(&mut blocks, &meta.types);
+    build_enums(&mut blocks, &meta.enums);
+    blocks
+}
+
+fn heading(blocks: &mut Vec<Block>, level: u8, text: impl Into<String>, code: bool) {
+    blocks.push(Block::Heading {
+        level,
+        text: text.into(),
+        code,
+    });
+}
+
+/// Parameter table for `(name, type)` pairs; nothing for an empty list.
+fn params_table(blocks: &mut Vec<Block>, params: &[(String, String)]) {
+    if params.is_empty() {
+        return;
+    }
+    blocks.push(Block::Table {
+        header: &["Parameter", "Type"],
+        rows: params
+            .iter()
+            .map(|(name, ty)| {
+                vec![
+                    Cell::Code(name.clone()),
+                    Cell::Code(lua_type_annotation(ty)),
+                ]
+            })
+            .collect(),
+    });
+}
+
+fn returns_paragraph(blocks: &mut Vec<Block>, returns: &Option<String>) {
+    if let Some(ret) = returns {
+        blocks.push(Block::Paragraph(format!(
+            "Returns `{}`.",
+            lua_type_annotation(ret)
+        )));
+    }
+}
+
+fn build_callbacks(blocks: &mut Vec<Block>, callbacks: &[CallbackMeta]) {
+    heading(blocks, 2, "Callbacks", false);
+    blocks.push(Block::Paragraph(
+        "Functions your Lua scripts define; the engine calls them at the appropriate times.".into(),
+    ));
+    for cb in callbacks {
+        let names: Vec<&str> = cb.params.iter().map(|(n, _)| n.as_str()).collect();
+        heading(
+            blocks,
+            3,
+            format!("{}({})", cb.name, names.join(", ")),
+            true,
+        );
+        blocks.push(Block::Paragraph(cb.description.clone()));
+        if let Some(ctx) = &cb.context {
+            blocks.push(Block::Paragraph(format!("Context: {ctx}")));
+        }
+        if let Some(note) = &cb.note {
+            blocks.push(Block::Paragraph(format!("Note: {note}")));
+        }
+        params_table(blocks, &cb.params);
+        returns_paragraph(blocks, &cb.returns);
+    }
+}
+
+fn build_functions(blocks: &mut Vec<Block>, functions: &[FnMeta]) {
+    let mut current = "";
+    for f in functions {
+        if f.category != current {
+            current = &f.category;
+            heading(blocks, 2, category_title(current), false);
+        }
+        let names: Vec<&str> = f.params.iter().map(|(n, _)| n.as_str()).collect();
+        heading(
+            blocks,
+            3,
+            format!("engine.{}({})", f.name, names.join(", ")),
+            true,
+        );
+        blocks.push(Block::Paragraph(f.description.clone()));
+        params_table(blocks, &f.params);
+        returns_paragraph(blocks, &f.returns);
+    }
+}
+
+fn build_class(blocks: &mut Vec<Block>, class: &ClassMeta) {
+    heading(blocks, 2, class_title(class), false);
+    blocks.push(Block::Paragraph(class.description.clone()));
+    for m in &class.methods {
+        let names: Vec<&str> = m.params.iter().map(|p| p.name.as_str()).collect();
+        heading(
+            blocks,
+            3,
+            format!("{}:{}({})", class.name, m.name, names.join(", ")),
+            true,
+        );
+        blocks.push(Block::Paragraph(m.description.clone()));
+        let params: Vec<(String, String)> = m
+            .params
+            .iter()
+            .map(|p| (p.name.clone(), p.type_name.clone()))
+            .collect();
+        params_table(blocks, &params);
+        returns_paragraph(blocks, &m.returns);
+    }
+}
+
+fn build_types(blocks: &mut Vec<Block>, types: &[TypeMeta]) {
+    heading(blocks, 2, "Types", false);
+    for t in types {
+        heading(blocks, 3, t.name.clone(), true);
+        blocks.push(Block::Paragraph(t.description.clone()));
+        if t.fields.is_empty() {
+            continue;
+        }
+        blocks.push(Block::Table {
+            header: &["Field", "Type", "Description"],
+            rows: t
+                .fields
+                .iter()
+                .map(|f| {
+                    let ty = lua_type_annotation(&f.type_name);
+                    vec![
+                        Cell::Code(f.name.clone()),
+                        Cell::Code(if f.optional { format!("{ty}|nil") } else { ty }),
+                        Cell::Text(f.description.clone().unwrap_or_default()),
+                    ]
+                })
+                .collect(),
+        });
+    }
+}
+
+fn build_enums(blocks: &mut Vec<Block>, enums: &[EnumMeta]) {
+    heading(blocks, 2, "Enums", false);
+    for e in enums {
+        heading(blocks, 3, e.name.clone(), true);
+        blocks.push(Block::Paragraph(e.description.clone()));
+        blocks.push(Block::Table {
+            header: &["Value"],
+            rows: e
+                .values
+                .iter()
+                .map(|v| vec![Cell::Code(format!("\"{v}\""))])
+                .collect(),
+        });
+    }
+}
+
+// --------------- Markdown ---------------
+
+/// Escape a table cell: pipes would end the cell and newlines the row.
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(blocks: &[Block]) -> String {
+    let mut out = String::with_capacity(128 * 1024);
+    for block in blocks {
+        match block {
+            Block::Heading { level, text, code } => {
+                let hashes = "#".repeat(*level as usize);
+                if *code {
+                    writeln!(out, "{hashes} `{text}`").unwrap();
+                } else {
+                    writeln!(out, "{hashes} {text}").unwrap();
+                }
+            }
+            Block::Paragraph(text) => {
+                // Keep multi-line descriptions as separate lines of one paragraph
+                writeln!(out, "{}", text.replace('\n', "  \n")).unwrap();
+            }
+            Block::Links(links) => {
+                for (text, a) in links {
+                    writeln!(out, "- [{text}](#{a})").unwrap();
+                }
+            }
+            Block::Table { header, rows } => {
+                writeln!(out, "| {} |", header.join(" | ")).unwrap();
+                writeln!(out, "|{}", "---|".repeat(header.len())).unwrap();
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|c| match c {
+                            Cell::Text(t) => md_cell(t),
+                            Cell::Code(t) => format!("`{}`", md_cell(t)),
+                        })
+                        .collect();
+                    writeln!(out, "| {} |", cells.join(" | ")).unwrap();
+                }
+            }
+        }
+        writeln!(out).unwrap();
+    }
+    out
+}
+
+// --------------- HTML ---------------
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape `text` and turn Markdown-style `` `code` `` spans into `<code>`.
+fn html_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, part) in html_escape(text).split('`').enumerate() {
+        if i % 2 == 1 {
+            write!(out, "<code>{part}</code>").unwrap();
+        } else {
+            out.push_str(part);
+        }
+    }
+    out.replace('\n', "<br>\n")
+}
+
+fn render_html(blocks: &[Block]) -> String {
+    let mut out = String::with_capacity(192 * 1024);
+    writeln!(out, "<!DOCTYPE html>").unwrap();
+    writeln!(out, "<html lang=\"en\">").unwrap();
+    writeln!(out, "<head>").unwrap();
+    writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>Aberred Engine Lua API Reference</title>").unwrap();
+    writeln!(
+        out,
+        "<style>body{{font-family:sans-serif;max-width:60em;margin:auto;padding:1em}}\
+         table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:.2em .5em}}\
+         code{{background:#f4f4f4}}</style>"
+    )
+    .unwrap();
+    writeln!(out, "</head>").unwrap();
+    writeln!(out, "<body>").unwrap();
+    for block in blocks {
+        match block {
+            Block::Heading { level, text, code } => {
+                let escaped = html_escape(text);
+                let inner = if *code {
+                    format!("<code>{escaped}</code>")
+                } else {
+                    escaped
+                };
+                writeln!(out, "<h{level} id=\"{}\">{inner}</h{level}>", anchor(text)).unwrap();
+            }
+            Block::Paragraph(text) => {
+                writeln!(out, "<p>{}</p>", html_inline(text)).unwrap();
+            }
+            Block::Links(links) => {
+                writeln!(out, "<ul>").unwrap();
+                for (text, a) in links {
+                    writeln!(out, "<li><a href=\"#{a}\">{}</a></li>", html_escape(text)).unwrap();
+                }
+                writeln!(out, "</ul>").unwrap();
+            }
+            Block::Table { header, rows } => {
+                writeln!(out, "<table>").unwrap();
+                let ths: String = header.iter().map(|h| format!("<th>{h}</th>")).collect();
+                writeln!(out, "<tr>{ths}</tr>").unwrap();
+                for row in rows {
+                    let tds: String = row
+                        .iter()
+                        .map(|c| match c {
+                            Cell::Text(t) => format!("<td>{}</td>", html_escape(t)),
+                            Cell::Code(t) => format!("<td><code>{}</code></td>", html_escape(t)),
+                        })
+                        .collect();
+                    writeln!(out, "<tr>{tds}</tr>").unwrap();
+                }
+                writeln!(out, "</table>").unwrap();
+            }
+        }
+    }
+    writeln!(out, "</body>").unwrap();
+    writeln!(out, "</html>").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_match_github_slugs() {
+        assert_eq!(anchor("Rendering & Shaders"), "rendering--shaders");
+        assert_eq!(anchor("Entity Builder"), "entity-builder");
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(
+            DocFormat::from_path(Path::new("docs/lua-api.HTML")),
+            DocFormat::Html
+        );
+        assert_eq!(
+            DocFormat::from_path(Path::new("docs/lua-api.md")),
+            DocFormat::Markdown
+        );
+    }
+
+    #[test]
+    fn markdown_tables_escape_union_types() {
+        let blocks = vec![Block::Table {
+            header: &["Parameter", "Type"],
+            rows: vec![vec![
+                Cell::Code("fps".into()),
+                Cell::Code("integer|nil".into()),
+            ]],
+        }];
+        assert!(render_markdown(&blocks).contains("| `fps` | `integer\\|nil` |"));
+    }
+
+    #[test]
+    fn multiple_return_types_map_each_entry() {
+        assert_eq!(
+            lua_type_annotation("integer?, number?"),
+            "integer|nil, number|nil"
+        );
+    }
+
+    #[test]
+    fn html_escapes_and_converts_code_spans() {
+        assert_eq!(
+            html_inline("use `a<b` & go"),
+            "use <code>a&lt;b</code> &amp; go"
+        );
+    }
+}
@@ -73,53 +73,111 @@ use raylib::ffi::TraceLogLevel;
 
 use crate::components::mapposition::MapPosition;
 use crate::components::screenposition::ScreenPosition;
-use crate::components::persistent::Persistent;
+use crate::components::persistent::{Persistent, protect_engine_entities};
 use crate::components::rotation::Rotation;
 use crate::components::scale::Scale;
+use crate::events::audio::AudioCmd;
 use crate::events::gamestate::GameStateChangedEvent;
 use crate::events::gamestate::observe_gamestate_change_event;
+use crate::events::resolution::resolution_changed_observer;
 use crate::events::switchdebug::switch_debug_observer;
-use crate::events::switchfullscreen::switch_fullscreen_observer;
+use crate::events::switchfullscreen::{apply_window_mode_observer, switch_fullscreen_observer};
+use crate::project::ProjectConfig;
+use crate::resources::alphamask::AlphaMasks;
 use crate::resources::animationstore::AnimationStore;
 use crate::resources::appstate::AppState;
-use crate::resources::audio::{setup_audio, shutdown_audio};
+use crate::resources::assetusage::AssetUsage;
+use crate::resources::attractmode::AttractMode;
+use crate::resources::audio::{AudioBridge, setup_audio, shutdown_audio};
+use crate::resources::autotile::AutoTileStore;
 use crate::resources::camera2d::Camera2DRes;
+use crate::resources::cameraeffects::CameraEffects;
 use crate::resources::camerafollowconfig::CameraFollowConfig;
+use crate::resources::cinematic::CinematicMode;
+use crate::resources::colorgrading::ColorGrading;
 use crate::resources::debugoverlayconfig::DebugOverlayConfig;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fogofwar::FogOfWar;
 use crate::resources::fontstore::FontStore;
+use crate::resources::formation::Formations;
+use crate::resources::framearena::FrameArena;
+use crate::resources::fullscreen::Monitors;
 use crate::resources::gameconfig::GameConfig;
 use crate::resources::gamestate::{GameState, GameStates, NextGameState};
-use crate::resources::group::TrackedGroups;
+use crate::resources::group::{GroupAggregates, TrackedGroups};
 use crate::resources::guiinputstate::GuiInputState;
 use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::highscores::{DEFAULT_HIGH_SCORES_PATH, HighScores};
 use crate::systems::gui_interactable_click::gui_interactable_click_observer;
 use crate::resources::imgui_bridge::ImguiBridge;
 use crate::resources::input::InputState;
 use crate::resources::input_bindings::InputBindings;
+use crate::resources::inputcontext::InputContexts;
+use crate::resources::inputsequence::InputSequences;
+use crate::resources::inputstats::InputStats;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::layervisibility::LayerVisibility;
+use crate::resources::missingassets::MissingAssetWarnings;
+use crate::resources::movementconfig::MovementConfig;
+use crate::resources::occluders::Occluders;
+use crate::resources::playerinput::PlayerInputs;
+use crate::resources::portaltransition::PortalTransition;
 use crate::resources::postprocessshader::PostProcessShader;
+use crate::resources::qualitygovernor::QualityGovernor;
+use crate::resources::rendermode::{DirtyRegion, RenderMode};
 use crate::resources::rendertarget::RenderTarget;
+use crate::resources::savegame::SaveGame;
 use crate::resources::scenemanager::SceneManager;
+use crate::resources::scenemusic::SceneMusic;
+use crate::resources::scenestate::SceneState;
 use crate::resources::screensize::ScreenSize;
 use crate::resources::shaderstore::ShaderStore;
+use crate::resources::signal_keys as sk;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::spawnpoints::SpawnPoints;
 use crate::resources::systemsstore::SystemsStore;
+use crate::resources::systemtoggles::{ToggleableSystem, system_enabled};
 use crate::resources::texturestore::TextureStore;
 use crate::resources::windowsize::WindowSize;
+use crate::resources::weather::Weather;
+use crate::resources::worldclock::WorldClock;
+use crate::resources::worlds::Worlds;
 use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldsnapshot::WorldSnapshots;
 use crate::resources::worldtime::WorldTime;
 use crate::systems::animation::animation;
 use crate::systems::animation::animation_controller;
+use crate::systems::asset_usage::{asset_usage_system, track_audio_usage_system};
+use crate::systems::attractmode::attract_mode_system;
+use crate::systems::attractor::attractor_system;
 use crate::systems::audio::{
-    forward_audio_cmds, poll_audio_messages, update_bevy_audio_cmds, update_bevy_audio_messages,
+    emit_music_sync_events, forward_audio_cmds, poll_audio_messages, update_bevy_audio_cmds,
+    update_bevy_audio_messages, update_bevy_beat_events, update_bevy_tracker_events,
 };
+use crate::systems::audiotriggers::{
+    audio_trigger_collision_observer, audio_trigger_despawn_observer, audio_triggers_system,
+};
+use crate::systems::blackboard::blackboard_ttl_system;
+use crate::systems::camera_effects::camera_effects_system;
 use crate::systems::camera_follow::camera_follow_system;
+use crate::systems::cinematic::cinematic_system;
 use crate::systems::collision_detector::collision_detector;
+use crate::systems::colorgrading::color_grading_system;
+use crate::systems::cooldown::cooldown_system;
+use crate::systems::credits::credits_roll_system;
+use crate::systems::dirtyrects::track_dirty_regions;
 use crate::systems::dynamictext_size::dynamictext_size_system;
+use crate::systems::ecs_stats::ecs_stats_system;
+use crate::systems::fogofwar::{fog_opaque_system, fog_visibility_system};
+use crate::systems::forcearea::force_area_system;
+use crate::systems::formation::formation_system;
+use crate::systems::frame_arena::reset_frame_arena;
 use crate::systems::gameconfig::apply_gameconfig_changes;
 use crate::systems::gamestate::{
     check_pending_state, clean_all_entities, quit_game, state_is_playing,
 };
 use crate::systems::gridlayout::gridlayout_spawn_system;
-use crate::systems::group::update_group_counts_system;
+use crate::systems::group::{update_group_aggregates_system, update_group_counts_system};
 use crate::systems::gui_hit_test::gui_hit_test_system;
 use crate::systems::gui_image_state_sync::gui_image_state_sync_system;
 use crate::systems::gui_layout::gui_layout_system;
@@ -127,49 +185,112 @@ use crate::systems::gui_progressbar_signal_update::gui_progressbar_signal_update
 use crate::systems::gui_spawn::{
     gui_button_spawn_system, gui_image_spawn_system, gui_label_spawn_system,
 };
+use crate::systems::height::height_system;
+use crate::systems::hitbox::hitbox_system;
 use crate::systems::input::update_input_state;
 use crate::systems::inputaccelerationcontroller::input_acceleration_controller;
+use crate::systems::inputcontext::input_context_system;
+use crate::systems::inputsequence::input_sequence_system;
+use crate::systems::inputstats::input_stats_system;
 use crate::systems::inputsimplecontroller::input_simple_controller;
+use crate::systems::interaction::interaction_system;
+use crate::systems::joint::joint_system;
 use crate::systems::mapspawn::spawn_map_observer;
 use crate::systems::menu::menu_selection_observer;
-use crate::systems::menu::{menu_controller_observer, menu_despawn, menu_spawn_system};
+use crate::systems::menu::{
+    menu_controller_observer, menu_despawn, menu_high_contrast_system, menu_spawn_system,
+};
+use crate::systems::missing_assets::missing_asset_warning_system;
 use crate::systems::mousecontroller::mouse_controller;
 use crate::systems::movement::movement;
+use crate::systems::nameentry::name_entry_system;
+use crate::systems::ownedby::owned_by_cleanup_system;
 use crate::systems::particleemitter::particle_emitter_system;
+use crate::systems::pathfollow::path_follow_system;
 use crate::systems::phase::phase_system;
+use crate::systems::playerinput::update_player_inputs;
+use crate::systems::portal::{
+    portal_interact_observer, portal_transition_system, spawn_point_registry_system,
+};
+use crate::systems::progress::progress_signal_update_system;
 use crate::systems::propagate_transforms::{
     cleanup_orphaned_global_transforms, propagate_transforms,
 };
+use crate::systems::qualitygovernor::quality_governor_system;
 use crate::systems::render::render_system;
+use crate::systems::render::worlds::render_sub_worlds_system;
+use crate::systems::results::results_screen_system;
+use crate::systems::rope::rope_system;
 use crate::systems::rust_collision::rust_collision_observer;
 use crate::systems::scene_dispatch::{
     SceneDescriptor, scene_enter_play, scene_switch_poll, scene_switch_system, scene_update_system,
 };
+use crate::systems::scenemusic::scene_music_system;
+use crate::systems::scenestate::restore_on_return_system;
+use crate::systems::sensor::sensor_system;
 use crate::systems::signalbinding::update_world_signals_binding_system;
+use crate::systems::spatialindex::update_spatial_index_system;
 use crate::systems::stuckto::stuck_to_entity_system;
+use crate::systems::terrain::terrain_system;
+use crate::systems::textscroller::text_scroller_system;
 use crate::systems::tilemap::tilemap_spawn_system;
 use crate::systems::time::update_world_time;
 use crate::systems::timer::{timer_observer, update_timers};
 use crate::systems::ttl::ttl_system;
 use crate::systems::tween::tween_system;
+use crate::systems::visibility::{occluder_system, visibility_polygon_system};
+use crate::systems::water::water_system;
+use crate::systems::weather::weather_system;
+use crate::systems::windowfocus::window_focus_system;
+use crate::systems::worldanchorui::world_anchor_ui_system;
+use crate::systems::worldclock::world_clock_system;
+use crate::systems::worlds::update_sub_worlds_system;
 use raylib::prelude::{Camera2D, Vector2};
 
 #[cfg(feature = "lua")]
-use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::lua_runtime::{LuaRuntime, SCRIPTS_DIR, ScriptWatcher};
 #[cfg(feature = "lua")]
 use crate::systems::lua_animation_finished::lua_animation_finished_observer;
 #[cfg(feature = "lua")]
+use crate::systems::lua_commands::apply_lua_commands;
+#[cfg(feature = "lua")]
+use crate::systems::lua_hit::lua_hit_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_interact::lua_interact_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_inventory::{lua_inventory_changed_observer, lua_inventory_full_observer};
+#[cfg(feature = "lua")]
+use crate::systems::lua_land::lua_land_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_music_sync::{lua_beat_system, lua_tracker_system};
+#[cfg(feature = "lua")]
+use crate::systems::lua_quality::lua_quality_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_reload::lua_reload_system;
+#[cfg(feature = "lua")]
+use crate::systems::lua_resolution::lua_resolution_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_results::lua_results_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_highscore::lua_highscore_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_inputsequence::lua_input_sequence_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_credits::lua_credits_observer;
+#[cfg(feature = "lua")]
 use crate::systems::lua_collision::lua_collision_observer;
 #[cfg(feature = "lua")]
 use crate::systems::lua_setup_entity::lua_setup_entity_system;
 #[cfg(feature = "lua")]
 use crate::systems::lua_tween_finished::lua_tween_finished_observer;
 #[cfg(feature = "lua")]
+use crate::systems::lua_window_focus::lua_window_focus_observer;
+#[cfg(feature = "lua")]
+use crate::systems::lua_world_clock::lua_world_clock_observer;
+#[cfg(feature = "lua")]
 use crate::systems::luaphase::lua_phase_system;
 #[cfg(feature = "lua")]
 use crate::systems::luatimer::{lua_timer_observer, update_lua_timers};
-#[cfg(feature = "lua")]
-use crate::systems::mapspawn::process_lua_map_commands;
 
 /// Closure that registers a system into the world and inserts its ID into
 /// [`SystemsStore`]. Deferred until `run()` when the [`World`] exists.
@@ -198,6 +319,7 @@ pub struct EngineBuilder {
     config_path: PathBuf,
     config_str: Option<&'static str>,
     title_override: Option<String>,
+    project: Option<ProjectConfig>,
     setup_hook: Option<HookRegistrar>,
     enter_play_hook: Option<HookRegistrar>,
     update_hook: Option<UpdateRegistrar>,
@@ -208,6 +330,8 @@ pub struct EngineBuilder {
     extra_observers: Vec<ObserverRegistrar>,
     #[cfg(feature = "lua")]
     lua_script: Option<PathBuf>,
+    #[cfg(feature = "lua")]
+    lua_hot_reload: bool,
 }
 
 impl EngineBuilder {
@@ -219,6 +343,7 @@ impl EngineBuilder {
             config_path: PathBuf::from("config.ini"),
             config_str: None,
             title_override: None,
+            project: None,
             setup_hook: None,
             enter_play_hook: None,
             update_hook: None,
@@ -229,6 +354,8 @@ impl EngineBuilder {
             extra_observers: Vec::new(),
             #[cfg(feature = "lua")]
             lua_script: None,
+            #[cfg(feature = "lua")]
+            lua_hot_reload: cfg!(debug_assertions),
         }
     }
 
@@ -253,6 +380,20 @@ impl EngineBuilder {
         self
     }
 
+    /// Use the defaults from a project manifest (`aberred.toml`).
+    ///
+    /// The manifest's title, render size and volumes are applied before the
+    /// config file is read, so `config.ini` values still win. Its
+    /// `default_scene` becomes the [`initial_scene`](Self::initial_scene)
+    /// unless one was set explicitly.
+    pub fn project(mut self, project: ProjectConfig) -> Self {
+        if self.initial_scene.is_none() {
+            self.initial_scene = project.default_scene.clone();
+        }
+        self.project = Some(project);
+        self
+    }
+
     /// Register the `setup` hook (called during the `Setup` game state).
     ///
     /// The system is registered into [`SystemsStore`] under the key `"setup"`.
@@ -343,6 +484,15 @@ impl EngineBuilder {
     ///     );
     /// })
     /// ```
+    ///
+    /// The schedule runs independent systems in parallel. To rule that out
+    /// while chasing an ordering bug, switch it back to one thread:
+    ///
+    /// ```rust,ignore
+    /// .configure_schedule(|schedule| {
+    ///     schedule.set_executor(bevy_ecs::schedule::SingleThreadedExecutor::new());
+    /// })
+    /// ```
     pub fn configure_schedule(mut self, f: impl FnOnce(&mut Schedule) + 'static) -> Self {
         self.extra_systems.push(Box::new(f));
         self
@@ -397,10 +547,12 @@ impl EngineBuilder {
         self
     }
 
-    /// Set the initial scene for [`SceneManager`]-based games.
+    /// Set the initial scene.
     ///
-    /// This scene's `on_enter` callback will be the first called when the
-    /// game transitions to the `Playing` state.
+    /// For [`SceneManager`]-based games, this scene's `on_enter` callback will
+    /// be the first called when the game transitions to the `Playing` state.
+    /// For Lua and hook-based games, the name is stored in the `"scene"` world
+    /// signal before `enter_play` runs, where the game can pick it up.
     pub fn initial_scene(mut self, name: impl Into<String>) -> Self {
         self.initial_scene = Some(name.into());
         self
@@ -439,6 +591,18 @@ impl EngineBuilder {
         self
     }
 
+    /// Reload Lua scripts when they change on disk (default: on in debug
+    /// builds, off in release builds).
+    ///
+    /// Changed files under `assets/scripts/` and the entry script are
+    /// re-executed while the game keeps running; see
+    /// [`lua_reload_system`](crate::systems::lua_reload::lua_reload_system).
+    #[cfg(feature = "lua")]
+    pub fn lua_hot_reload(mut self, enabled: bool) -> Self {
+        self.lua_hot_reload = enabled;
+        self
+    }
+
     /// Build the engine and run the main loop.
     ///
     /// This consumes the builder and does not return until the game exits.
@@ -474,6 +638,7 @@ impl EngineBuilder {
         let mut world = self.setup_world(config, rl, thread, render_target)?;
         self.register_systems(&mut world, use_scene_manager)?;
         Self::spawn_observers(&mut world, has_lua, extra_observers);
+        protect_engine_entities(&mut world);
 
         let mut update = Self::build_schedule(
             update_hook,
@@ -519,6 +684,9 @@ impl EngineBuilder {
 
     fn load_config(&self) -> Result<GameConfig, String> {
         let mut config = GameConfig::with_path(&self.config_path);
+        if let Some(project) = &self.project {
+            project.apply_to(&mut config);
+        }
         if let Some(content) = &self.config_str {
             config
                 .load_from_str(content)
@@ -605,6 +773,9 @@ impl EngineBuilder {
         world.insert_resource(WorldSignals::default());
         world.insert_resource(AppState::default());
         world.insert_resource(TrackedGroups::default());
+        world.insert_resource(GroupAggregates::default());
+        world.insert_resource(SpatialIndex::default());
+        world.insert_resource(SceneState::default());
         world.insert_resource(ScreenSize {
             w: render_width as i32,
             h: render_height as i32,
@@ -613,21 +784,62 @@ impl EngineBuilder {
             w: window_width,
             h: window_height,
         });
+        world.insert_resource(Monitors::query());
+        world.insert_resource(config.system_toggles);
+        world.insert_resource(QualityGovernor::default());
         world.insert_resource(config);
         world.insert_resource(InputState::default());
         world.insert_resource(InputBindings::default());
         world.insert_non_send(render_target);
 
         setup_audio(&mut world);
+        // Queue the configured volumes ahead of anything game code may send,
+        // so the first PlayMusic already plays at the right level.
+        let (master, music, sfx) = world.resource::<GameConfig>().volumes();
+        let _ = world
+            .resource::<AudioBridge>()
+            .tx_cmd
+            .send(AudioCmd::SetVolumes { master, music, sfx });
 
         world.insert_resource(GameState::new());
         world.insert_resource(NextGameState::new());
-        world.insert_non_send(FontStore::new());
+        let mut rl = rl;
+        let mut fonts = FontStore::new();
+        fonts.load_fallback(&rl);
+        world.insert_non_send(fonts);
         let imgui_bridge = ImguiBridge::new_dark()
             .map_err(|err| format!("Failed to initialize imgui bridge: {err}"))?;
         world.insert_non_send(imgui_bridge);
         world.insert_non_send(ShaderStore::new());
-        world.insert_resource(TextureStore::new());
+        let mut textures = TextureStore::new();
+        textures.load_fallback(&mut rl, &thread);
+        world.insert_resource(textures);
+        world.insert_resource(MissingAssetWarnings::default());
+        world.insert_resource(AssetUsage::default());
+        world.insert_resource(EcsStats::default());
+        world.insert_resource(WorldSnapshots::default());
+        world.insert_resource(AutoTileStore::default());
+        world.insert_resource(FogOfWar::default());
+        world.insert_resource(Occluders::default());
+        world.insert_resource(WorldClock::default());
+        world.insert_resource(Weather::default());
+        world.insert_resource(Formations::default());
+        world.insert_resource(SceneMusic::default());
+        world.insert_resource(ItemRegistry::default());
+        world.insert_resource(HighScores::load(DEFAULT_HIGH_SCORES_PATH));
+        world.insert_resource(SaveGame::default());
+        world.insert_resource(AttractMode::default());
+        world.insert_resource(CameraEffects::default());
+        world.insert_resource(CinematicMode::default());
+        world.insert_resource(InputContexts::default());
+        world.insert_resource(InputSequences::default());
+        world.insert_resource(InputStats::default());
+        world.insert_resource(PlayerInputs::default());
+        world.insert_resource(LayerVisibility::default());
+        world.insert_resource(ColorGrading::default());
+        world.insert_resource(AlphaMasks::default());
+        world.insert_resource(PortalTransition::default());
+        world.insert_resource(SpawnPoints::default());
         world.insert_resource(Camera2DRes(Camera2D {
             target: Vector2 { x: 0.0, y: 0.0 },
             offset: Vector2 {
@@ -640,10 +852,15 @@ impl EngineBuilder {
         world.insert_resource(AnimationStore::default());
         world.insert_resource(PostProcessShader::new());
         world.insert_resource(CameraFollowConfig::default());
+        world.insert_resource(MovementConfig::default());
+        world.insert_resource(FrameArena::default());
+        world.insert_resource(RenderMode::default());
+        world.insert_resource(DirtyRegion::default());
         world.insert_resource(DebugOverlayConfig::default());
         world.insert_resource(GuiInputState::default());
         world.insert_resource(GuiThemeStore::default());
         world.insert_resource(GuiThemeWarnCache::default());
+        world.insert_resource(Worlds::default());
 
         #[cfg(feature = "lua")]
         if let Some(ref script_path) = self.lua_script {
@@ -653,6 +870,9 @@ impl EngineBuilder {
                 log::error!("Failed to load Lua script: {}", e);
             }
             world.insert_non_send(lua_runtime);
+            if self.lua_hot_reload {
+                world.insert_resource(ScriptWatcher::new(SCRIPTS_DIR, script_path.clone()));
+            }
         }
 
         world.insert_non_send(rl);
@@ -723,6 +943,12 @@ impl EngineBuilder {
             register_persistent_system(world, &mut systems_store, "enter_play", scene_enter_play);
         }
 
+        if !use_scene_manager && let Some(scene) = self.initial_scene {
+            world
+                .resource_mut::<WorldSignals>()
+                .set_string(sk::SCENE, scene);
+        }
+
         register_persistent_system(world, &mut systems_store, "quit_game", quit_game);
         register_persistent_system(
             world,
@@ -759,13 +985,31 @@ impl EngineBuilder {
         world.spawn((Observer::new(rust_collision_observer), Persistent));
         world.spawn((Observer::new(switch_debug_observer), Persistent));
         world.spawn((Observer::new(switch_fullscreen_observer), Persistent));
+        world.spawn((Observer::new(apply_window_mode_observer), Persistent));
+        world.spawn((Observer::new(resolution_changed_observer), Persistent));
         world.spawn((Observer::new(menu_controller_observer), Persistent));
         world.spawn((Observer::new(menu_selection_observer), Persistent));
         world.spawn((Observer::new(gui_interactable_click_observer), Persistent));
+        world.spawn((Observer::new(portal_interact_observer), Persistent));
+        world.spawn((Observer::new(audio_trigger_collision_observer), Persistent));
+        world.spawn((Observer::new(audio_trigger_despawn_observer), Persistent));
         #[cfg(feature = "lua")]
         if has_lua {
             world.spawn((Observer::new(lua_timer_observer), Persistent));
             world.spawn((Observer::new(lua_animation_finished_observer), Persistent));
+            world.spawn((Observer::new(lua_hit_observer), Persistent));
+            world.spawn((Observer::new(lua_land_observer), Persistent));
+            world.spawn((Observer::new(lua_interact_observer), Persistent));
+            world.spawn((Observer::new(lua_resolution_observer), Persistent));
+            world.spawn((Observer::new(lua_window_focus_observer), Persistent));
+            world.spawn((Observer::new(lua_quality_observer), Persistent));
+            world.spawn((Observer::new(lua_world_clock_observer), Persistent));
+            world.spawn((Observer::new(lua_results_observer), Persistent));
+            world.spawn((Observer::new(lua_highscore_observer), Persistent));
+            world.spawn((Observer::new(lua_credits_observer), Persistent));
+            world.spawn((Observer::new(lua_input_sequence_observer), Persistent));
+            world.spawn((Observer::new(lua_inventory_changed_observer), Persistent));
+            world.spawn((Observer::new(lua_inventory_full_observer), Persistent));
 
             fn spawn_tween_finished_observer<T: crate::components::tween::TweenValue>(
                 world: &mut World,
@@ -790,6 +1034,16 @@ impl EngineBuilder {
         world.flush();
     }
 
+    /// Build the per-frame schedule.
+    ///
+    /// With bevy_ecs's `multi_threaded` feature the schedule runs on the
+    /// multithreaded executor: systems whose data access does not conflict run
+    /// in parallel, while systems holding `NonSend` resources (raylib, Lua,
+    /// fonts, shaders) run on the main thread. Systems that read input or time
+    /// take `Res<InputState>` / `Res<WorldTime>`, which only the input and time
+    /// updates mutate, so movement, tweens, animation and collision detection
+    /// can run alongside each other. Ordering that matters must be explicit
+    /// (`.after()` / `.before()`).
     fn build_schedule(
         update_hook: Option<UpdateRegistrar>,
         extra_systems: Vec<UpdateRegistrar>,
@@ -799,10 +1053,56 @@ impl EngineBuilder {
     ) -> Result<Schedule, String> {
         let mut update = Schedule::default();
         update.add_systems(apply_gameconfig_changes.run_if(state_is_playing));
+        update.add_systems(window_focus_system);
+        update.add_systems(
+            quality_governor_system
+                .after(apply_gameconfig_changes)
+                .before(particle_emitter_system)
+                .before(render_system),
+        );
         update.add_systems(menu_spawn_system);
+        update.add_systems(menu_high_contrast_system.after(menu_spawn_system));
         update.add_systems(gridlayout_spawn_system);
         update.add_systems(tilemap_spawn_system);
         update.add_systems(update_input_state);
+        update.add_systems(
+            attract_mode_system
+                .after(update_input_state)
+                .before(check_pending_state)
+                .before(input_simple_controller)
+                .before(input_acceleration_controller),
+        );
+        update.add_systems(
+            input_stats_system
+                .after(attract_mode_system)
+                .before(cinematic_system),
+        );
+        update.add_systems(
+            cinematic_system
+                .after(attract_mode_system)
+                .before(check_pending_state)
+                .before(input_simple_controller)
+                .before(input_acceleration_controller),
+        );
+        update.add_systems(
+            input_context_system
+                .after(cinematic_system)
+                .before(check_pending_state)
+                .before(input_simple_controller)
+                .before(input_acceleration_controller),
+        );
+        update.add_systems(
+            input_sequence_system
+                .after(input_context_system)
+                .before(check_pending_state),
+        );
+        update.add_systems(
+            update_player_inputs
+                .after(input_context_system)
+                .before(check_pending_state)
+                .before(input_simple_controller)
+                .before(input_acceleration_controller),
+        );
         update.add_systems(check_pending_state);
         #[cfg(feature = "lua")]
         if has_lua {
@@ -812,18 +1112,37 @@ impl EngineBuilder {
         }
         #[cfg(not(feature = "lua"))]
         update.add_systems(update_group_counts_system);
+        #[cfg(feature = "lua")]
+        if has_lua {
+            update.add_systems(world_clock_system.before(crate::lua_plugin::update));
+        } else {
+            update.add_systems(world_clock_system);
+        }
+        #[cfg(not(feature = "lua"))]
+        update.add_systems(world_clock_system);
         update.add_systems(
             (
                 update_bevy_audio_cmds,
                 forward_audio_cmds,
                 poll_audio_messages,
                 update_bevy_audio_messages,
+                emit_music_sync_events,
+                track_audio_usage_system,
+                update_bevy_beat_events,
+                update_bevy_tracker_events,
             )
                 .chain(),
         );
-        update.add_systems(input_simple_controller);
-        update.add_systems(input_acceleration_controller);
-        update.add_systems(mouse_controller);
+        update.add_systems(scene_music_system.after(update_bevy_audio_messages));
+        update.add_systems(
+            (
+                input_simple_controller,
+                input_acceleration_controller,
+                mouse_controller,
+            )
+                .after(update_input_state)
+                .before(movement),
+        );
         update.add_systems(stuck_to_entity_system.after(collision_detector));
         update.add_systems(tween_system::<MapPosition>);
         update.add_systems(tween_system::<Rotation>);
@@ -833,6 +1152,12 @@ impl EngineBuilder {
             (gui_button_spawn_system, gui_label_spawn_system, gui_image_spawn_system)
                 .before(gui_layout_system),
         );
+        update.add_systems(
+            world_anchor_ui_system
+                .after(camera_follow_system)
+                .after(tween_system::<ScreenPosition>)
+                .before(gui_layout_system),
+        );
         update.add_systems(
             gui_layout_system
                 .after(tween_system::<ScreenPosition>)
@@ -850,9 +1175,60 @@ impl EngineBuilder {
                 .before(render_system),
         );
         update.add_systems(gui_progressbar_signal_update_system.before(render_system));
-        update.add_systems(particle_emitter_system.before(movement));
+        update.add_systems(results_screen_system.before(dynamictext_size_system));
+        update.add_systems(name_entry_system.before(dynamictext_size_system));
+        update.add_systems(
+            credits_roll_system
+                .after(dynamictext_size_system)
+                .before(render_system),
+        );
+        update.add_systems(
+            weather_system
+                .run_if(system_enabled(ToggleableSystem::Weather))
+                .before(particle_emitter_system)
+                .before(force_area_system),
+        );
+        update.add_systems(
+            particle_emitter_system
+                .run_if(system_enabled(ToggleableSystem::Particles))
+                .before(movement),
+        );
+        update.add_systems(restore_on_return_system.before(movement));
+        update.add_systems(force_area_system.before(movement));
+        update.add_systems(water_system.before(movement));
+        update.add_systems(attractor_system.before(movement));
         update.add_systems(movement);
+        update.add_systems(joint_system.after(movement).before(propagate_transforms));
+        update.add_systems(rope_system.after(movement).before(propagate_transforms));
+        update.add_systems(path_follow_system.after(movement).before(propagate_transforms));
+        update.add_systems(height_system.after(movement).before(animation_controller));
+        update.add_systems(
+            formation_system
+                .after(path_follow_system)
+                .before(propagate_transforms),
+        );
+        update.add_systems(
+            text_scroller_system
+                .before(propagate_transforms)
+                .before(dynamictext_size_system),
+        );
         update.add_systems(ttl_system.after(movement));
+        update.add_systems(
+            owned_by_cleanup_system
+                .after(ttl_system)
+                .before(render_system),
+        );
+        update.add_systems(blackboard_ttl_system.after(movement));
+        update.add_systems(
+            cooldown_system
+                .before(gui_progressbar_signal_update_system)
+                .before(update_world_signals_binding_system),
+        );
+        update.add_systems(
+            progress_signal_update_system
+                .after(cooldown_system)
+                .before(render_system),
+        );
         update.add_systems(
             propagate_transforms
                 .after(movement)
@@ -861,6 +1237,33 @@ impl EngineBuilder {
                 .after(tween_system::<Scale>)
                 .before(collision_detector),
         );
+        update.add_systems(
+            update_group_aggregates_system
+                .after(propagate_transforms)
+                .before(collision_detector),
+        );
+        update.add_systems(
+            update_spatial_index_system
+                .after(propagate_transforms)
+                .before(collision_detector),
+        );
+        update.add_systems(
+            sensor_system
+                .after(propagate_transforms)
+                .before(collision_detector),
+        );
+        update.add_systems(audio_triggers_system.before(collision_detector));
+        update.add_systems(
+            interaction_system
+                .after(propagate_transforms)
+                .before(render_system),
+        );
+        update.add_systems(
+            (spawn_point_registry_system, portal_transition_system)
+                .chain()
+                .after(interaction_system)
+                .before(render_system),
+        );
         update.add_systems(
             cleanup_orphaned_global_transforms
                 .after(propagate_transforms)
@@ -871,6 +1274,32 @@ impl EngineBuilder {
                 .after(propagate_transforms)
                 .before(render_system),
         );
+        update.add_systems(
+            camera_effects_system
+                .after(camera_follow_system)
+                .before(render_system),
+        );
+        update.add_systems(
+            track_dirty_regions
+                .after(camera_follow_system)
+                .after(camera_effects_system)
+                .after(gui_layout_system)
+                .after(animation)
+                .after(dynamictext_size_system)
+                .before(render_system),
+        );
+        update.add_systems(
+            (fog_opaque_system, fog_visibility_system)
+                .chain()
+                .after(propagate_transforms)
+                .before(render_system),
+        );
+        update.add_systems(
+            (occluder_system, visibility_polygon_system)
+                .chain()
+                .after(propagate_transforms)
+                .before(render_system),
+        );
         update.add_systems(collision_detector.after(mouse_controller).after(movement));
         update.add_systems(phase_system.after(collision_detector));
 
@@ -884,14 +1313,42 @@ impl EngineBuilder {
             );
             update.add_systems(update_lua_timers);
             update.add_systems(
-                process_lua_map_commands
-                    .after(crate::lua_plugin::update)
-                    .before(render_system),
+                crate::lua_plugin::sync_lua_tag_cache
+                    .before(collision_detector)
+                    .before(lua_phase_system)
+                    .before(crate::lua_plugin::update),
             );
             update.add_systems(
-                crate::lua_plugin::process_lua_asset_commands
+                crate::lua_plugin::sync_lua_inventory_cache
+                    .before(lua_phase_system)
+                    .before(crate::lua_plugin::update),
+            );
+            update.add_systems(
+                crate::lua_plugin::sync_lua_texture_size_cache
+                    .before(lua_phase_system)
+                    .before(crate::lua_plugin::update),
+            );
+            update.add_systems(
+                lua_reload_system
+                    .run_if(resource_exists::<ScriptWatcher>)
                     .run_if(state_is_playing)
-                    .after(crate::lua_plugin::update),
+                    .before(lua_phase_system)
+                    .before(crate::lua_plugin::update),
+            );
+            update.add_systems(
+                (lua_beat_system, lua_tracker_system)
+                    .chain()
+                    .run_if(state_is_playing)
+                    .after(update_bevy_tracker_events)
+                    .before(crate::lua_plugin::update),
+            );
+            update.add_systems(
+                apply_lua_commands
+                    .after(crate::lua_plugin::update)
+                    .before(update_sub_worlds_system)
+                    .before(occluder_system)
+                    .before(track_dirty_regions)
+                    .before(render_system),
             );
             update.add_systems(
                 lua_setup_entity_system
@@ -912,6 +1369,7 @@ impl EngineBuilder {
         }
 
         update.add_systems(animation.after(animation_controller));
+        update.add_systems(hitbox_system.after(animation));
         update.add_systems(update_timers);
         update.add_systems(update_world_signals_binding_system);
         update.add_systems(dynamictext_size_system.after(update_world_signals_binding_system));
@@ -938,7 +1396,19 @@ impl EngineBuilder {
             );
         }
 
+        update.add_systems(
+            update_sub_worlds_system
+                .after(movement)
+                .before(render_sub_worlds_system),
+        );
+        update.add_systems(render_sub_worlds_system.before(render_system));
+        update.add_systems(color_grading_system.before(render_system));
+        update.add_systems(terrain_system.after(collision_detector).before(render_system));
         update.add_systems(render_system.after(collision_detector));
+        update.add_systems(missing_asset_warning_system.after(render_system));
+        update.add_systems(asset_usage_system.after(render_system));
+        update.add_systems(ecs_stats_system.after(render_system));
+        update.add_systems(reset_frame_arena.after(render_system));
 
         update
             .initialize(world)
@@ -1161,6 +1631,7 @@ mod tests {
         assert!(builder.enter_play_hook.is_some());
         assert!(builder.update_hook.is_some());
         assert!(builder.switch_scene_hook.is_some());
+        assert!(!builder.lua_hot_reload(false).lua_hot_reload);
     }
 
     #[cfg(feature = "lua")]
@@ -1247,6 +1718,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_schedule_orders_controllers_between_input_and_movement() {
+        let mut world = World::new();
+        let schedule = EngineBuilder::build_schedule(None, Vec::new(), &mut world, false, false)
+            .expect("build_schedule should succeed");
+        let system_type_ids: Vec<_> = schedule
+            .systems()
+            .expect("build_schedule initializes the schedule")
+            .map(|(_, system)| system.system_type())
+            .collect();
+        let index_of = |type_id| {
+            system_type_ids
+                .iter()
+                .position(|t| *t == type_id)
+                .expect("system should be present")
+        };
+
+        let input_index = index_of(IntoSystem::into_system(update_input_state).system_type());
+        let movement_index = index_of(IntoSystem::into_system(movement).system_type());
+        for controller in [
+            IntoSystem::into_system(input_simple_controller).system_type(),
+            IntoSystem::into_system(input_acceleration_controller).system_type(),
+            IntoSystem::into_system(mouse_controller).system_type(),
+        ] {
+            let index = index_of(controller);
+            assert!(input_index < index && index < movement_index);
+        }
+    }
+
     #[test]
     fn test_builder_chaining() {
         let builder = EngineBuilder::new()
@@ -1308,6 +1808,32 @@ mod tests {
         assert_eq!(builder.initial_scene, Some("menu".to_string()));
     }
 
+    #[test]
+    fn test_project_sets_initial_scene_unless_explicit() {
+        let project = ProjectConfig::parse("default_scene = \"intro\"").unwrap();
+        let builder = EngineBuilder::new().project(project.clone());
+        assert_eq!(builder.initial_scene, Some("intro".to_string()));
+        assert!(builder.project.is_some());
+
+        let builder = EngineBuilder::new().initial_scene("menu").project(project);
+        assert_eq!(builder.initial_scene, Some("menu".to_string()));
+    }
+
+    #[test]
+    fn test_project_defaults_are_overridden_by_config() {
+        let project =
+            ProjectConfig::parse("title = \"Kraken\"\n[render]\nwidth = 320\nheight = 180")
+                .unwrap();
+        let config = EngineBuilder::new()
+            .config_str("[render]\nwidth = 800\n")
+            .project(project)
+            .load_config()
+            .unwrap();
+        assert_eq!(config.render_width, 800);
+        assert_eq!(config.render_height, 180);
+        assert_eq!(config.window_title, "Kraken");
+    }
+
     #[test]
     fn test_add_scene_conflicts_with_on_switch_scene() {
         let err = EngineBuilder::new()
@@ -9,8 +9,13 @@
 //! - Load long-lived streams (music) with [`AudioCmd::LoadMusic`], then start
 //!   playback with [`AudioCmd::PlayMusic`]. Use [`AudioCmd::VolumeMusic`] and
 //!   [`AudioCmd::StopMusic`] to control runtime behavior.
+//! - Fade all music with [`AudioCmd::FadeMusicIn`]/[`AudioCmd::FadeMusicOut`].
 //! - Play short, one-shot effects by first loading them with
 //!   [`AudioCmd::LoadFx`] and triggering playback with [`AudioCmd::PlayFx`].
+//! - Call [`AudioCmd::SetMusicBpm`] to have the audio thread report beat and
+//!   bar boundaries, surfaced to systems as [`BeatEvent`] messages.
+//! - Call [`AudioCmd::SetMusicTrackerEvents`] on `.xm`/`.mod` music to receive
+//!   pattern/row/order changes as [`TrackerEvent`] messages.
 //! - Subscribe/poll for [`AudioMessage`] values to react to success/failure
 //!   and lifecycle events (e.g. [`AudioMessage::MusicFinished`]).
 //!
@@ -35,7 +40,7 @@
 //! // 2) Handle events coming back from the audio thread
 //! while let Ok(msg) = audio_rx.try_recv() {
 //!     match msg {
-//!         AudioMessage::MusicLoaded { id } => log::info!("Loaded {id}"),
+//!         AudioMessage::MusicLoaded { id, .. } => log::info!("Loaded {id}"),
 //!         AudioMessage::MusicPlayStarted { id } => log::info!("Playing {id}"),
 //!         AudioMessage::MusicFinished { id } => log::info!("Finished {id}"),
 //!         _ => {}
@@ -70,6 +75,26 @@ pub enum AudioCmd {
     ResumeMusic { id: String },
     /// Set volume of a music stream `id` to `vol` in the `[0.0, 1.0]` range.
     VolumeMusic { id: String, vol: f32 },
+    /// Set the global mixer volumes, each in the `[0.0, 1.0]` range: `master`
+    /// scales all output, `music` multiplies every stream's own volume and
+    /// `sfx` applies to sound effects played from now on.
+    SetVolumes { master: f32, music: f32, sfx: f32 },
+    /// Fade the music channel in from silence over `duration` seconds. Send it
+    /// right after [`AudioCmd::PlayMusic`] to start a track quietly.
+    FadeMusicIn { duration: f32 },
+    /// Fade the music channel out over `duration` seconds, then stop all music
+    /// and restore the channel. A [`AudioCmd::PlayMusic`] sent meanwhile
+    /// cancels the fade.
+    FadeMusicOut { duration: f32 },
+    /// Configure beat tracking for music `id`: `bpm` beats per minute grouped
+    /// into bars of `beats_per_bar`. A `bpm` of `0.0` disables tracking.
+    SetMusicBpm {
+        id: String,
+        bpm: f32,
+        beats_per_bar: u32,
+    },
+    /// Enable or disable tracker row reports for a loaded `.xm`/`.mod` music `id`.
+    SetMusicTrackerEvents { id: String, enabled: bool },
     /// Load a sound effect from `path` and store it under `id`.
     LoadFx { id: String, path: String },
     /// Play a previously loaded sound effect `id` (one-shot).
@@ -90,8 +115,9 @@ pub enum AudioCmd {
 #[allow(dead_code)] // variants are forward-looking API; not all are consumed by game code yet
 #[derive(Message, Debug, Clone)]
 pub enum AudioMessage {
-    /// Music with `id` successfully loaded.
-    MusicLoaded { id: String },
+    /// Music with `id` successfully loaded. `bytes` is the size of its source
+    /// file, an estimate of the memory the stream keeps resident.
+    MusicLoaded { id: String, bytes: usize },
     /// Music with `id` successfully unloaded.
     MusicUnloaded { id: String },
     /// All music resources have been unloaded.
@@ -106,8 +132,23 @@ pub enum AudioMessage {
     MusicFinished { id: String }, // reached end for non looping
     /// Volume of music `id` changed to `vol`.
     MusicVolumeChanged { id: String, vol: f32 },
-    /// Sound effect with `id` successfully loaded.
-    FxLoaded { id: String },
+    /// Playback of music `id` crossed a beat boundary (see [`AudioCmd::SetMusicBpm`]).
+    MusicBeat {
+        id: String,
+        beat: u32,
+        bar: u32,
+        beat_in_bar: u32,
+    },
+    /// Playback of tracker music `id` entered a new row (see
+    /// [`AudioCmd::SetMusicTrackerEvents`]).
+    MusicTrackerRow {
+        id: String,
+        order: u32,
+        pattern: u32,
+        row: u32,
+    },
+    /// Sound effect with `id` successfully loaded, holding `bytes` of decoded samples.
+    FxLoaded { id: String, bytes: usize },
     /// Sound effect with `id` successfully unloaded.
     FxUnloaded { id: String },
     /// All sound effects have been unloaded.
@@ -115,3 +156,38 @@ pub enum AudioMessage {
     /// Sound effect with `id` failed to load with `error`.
     FxLoadFailed { id: String, error: String },
 }
+
+/// A beat boundary reached by a playing music track with a configured BPM.
+///
+/// Forwarded from [`AudioMessage::MusicBeat`] by
+/// [`emit_music_sync_events`](crate::systems::audio::emit_music_sync_events). Counters
+/// restart from zero whenever the track (re)starts, including loop restarts.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct BeatEvent {
+    /// Music track identifier.
+    pub id: String,
+    /// Zero-based beat index since playback started.
+    pub beat: u32,
+    /// Zero-based bar index since playback started.
+    pub bar: u32,
+    /// Zero-based beat position inside the current bar.
+    pub beat_in_bar: u32,
+}
+
+/// A tracker module (.xm/.mod) entered a new row.
+///
+/// Forwarded from [`AudioMessage::MusicTrackerRow`] by
+/// [`emit_music_sync_events`](crate::systems::audio::emit_music_sync_events).
+/// `row == 0` marks an order (pattern) change. Positions are derived from the
+/// module's pattern data; see [`crate::systems::tracker_clock`].
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct TrackerEvent {
+    /// Music track identifier.
+    pub id: String,
+    /// Index into the song's order table.
+    pub order: u32,
+    /// Pattern number playing at `order`.
+    pub pattern: u32,
+    /// Row inside the pattern.
+    pub row: u32,
+}
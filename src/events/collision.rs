@@ -1,36 +1,246 @@
 //! Collision event types.
 //!
 //! The collision system emits [`CollisionEvent`] whenever two entities with
-//! compatible colliders overlap. This event is primarily consumed by the
+//! compatible colliders overlap, followed by a single [`CollisionBatch`] with
+//! all of the frame's pairs. The batch is consumed by the
+//! [`rust_collision_observer`](crate::systems::rust_collision::rust_collision_observer) and the
 //! [`lua_collision_observer`](crate::systems::lua_collision::lua_collision_observer), which
-//! looks up matching [`LuaCollisionRule`](crate::components::luacollision::LuaCollisionRule)
-//! components and invokes their Lua callbacks.
+//! look up matching [`CollisionRule`](crate::components::collision::CollisionRule) /
+//! [`LuaCollisionRule`](crate::components::luacollision::LuaCollisionRule)
+//! components and invoke their callbacks. The per-pair events are kept for
+//! custom observers that handle one collision at a time.
 //!
 //! # Flow
 //!
 //! 1. [`collision_detector`](crate::systems::collision_detector::collision_detector) detects overlaps
-//! 2. Emits `CollisionEvent` for each collision
-//! 3. [`lua_collision_observer`](crate::systems::lua_collision::lua_collision_observer) receives the event
-//! 4. Finds matching `LuaCollisionRule` by group names
-//! 5. Invokes the rule's Lua callback with both entities
+//! 2. Emits `CollisionEvent` for each collision, then one [`CollisionBatch`]
+//!    holding every pair of the frame
+//! 3. The Rust and Lua collision observers receive the batch
+//! 4. Each matches every pair against its rules by group names and tags
+//! 5. Invokes each rule's callback for all of its pairs
 //!
 //! # Related
 //!
 //! - [`crate::systems::collision_detector`] – collision detection system
+//! - [`crate::systems::rust_collision`] – Rust collision observer
 //! - [`crate::systems::lua_collision`] – Lua collision observer
 //! - [`crate::components::luacollision::LuaCollisionRule`] – defines Lua collision handlers
 //! - [`crate::components::boxcollider::BoxCollider`] – the collider component
+//!
+//! # Contact Data
+//!
+//! Besides the two entities, each event carries the contact data computed once
+//! by the detector: the colliding [`BoxSides`] of each collider, the
+//! penetration `depth` and unit `normal` along the axis of least overlap, and
+//! the [`Group`] names of both entities. Observers that need the data from the
+//! point of view of one particular entity use [`CollisionEvent::oriented`].
 
 use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::collision::{BoxSide, BoxSides, get_colliding_sides};
+use crate::components::group::Group;
+use crate::key::Key;
 
 /// Event fired when two entities with BoxCollider overlap.
 ///
-/// The two fields, [`CollisionEvent::a`] and [`CollisionEvent::b`], are the
-/// entity IDs of the participants. No ordering guarantees are provided.
-/// Additional collision details (normals, penetration, etc.) can be added by
-/// extending this type when needed.
-#[derive(Event, Debug, Clone, Copy)]
+/// No ordering guarantees are provided between [`CollisionEvent::a`] and
+/// [`CollisionEvent::b`]; every `_a`/`_b` field refers to the matching entity.
+#[derive(Event, Debug, Clone)]
 pub struct CollisionEvent {
     pub a: Entity,
     pub b: Entity,
+    /// Sides of `a`'s collider that are touched by `b`.
+    pub sides_a: BoxSides,
+    /// Sides of `b`'s collider that are touched by `a`.
+    pub sides_b: BoxSides,
+    /// Penetration depth along [`normal`](Self::normal): the smaller extent
+    /// of the overlap rectangle.
+    pub depth: f32,
+    /// Unit contact normal pointing from `a` toward `b` along the axis of
+    /// least penetration. Moving `a` by `-normal * depth` separates the pair.
+    pub normal: Vector2,
+    /// Group name of `a`, if it has a [`Group`].
+    pub group_a: Option<Key>,
+    /// Group name of `b`, if it has a [`Group`].
+    pub group_b: Option<Key>,
+}
+
+impl CollisionEvent {
+    /// Build an event from the world-space collider rectangles of `a` and `b`.
+    ///
+    /// Returns `None` if the rectangles don't overlap. Groups are left empty;
+    /// see [`with_groups`](Self::with_groups).
+    pub fn from_rects(
+        a: Entity,
+        b: Entity,
+        rect_a: &Rectangle,
+        rect_b: &Rectangle,
+    ) -> Option<Self> {
+        let overlap = rect_a.get_collision_rec(rect_b)?;
+        let (sides_a, sides_b) = get_colliding_sides(rect_a, rect_b)?;
+        let center_delta = Vector2::new(
+            (rect_b.x + rect_b.width * 0.5) - (rect_a.x + rect_a.width * 0.5),
+            (rect_b.y + rect_b.height * 0.5) - (rect_a.y + rect_a.height * 0.5),
+        );
+        let sign = |d: f32| if d < 0.0 { -1.0 } else { 1.0 };
+        let (depth, normal) = if overlap.width < overlap.height {
+            (overlap.width, Vector2::new(sign(center_delta.x), 0.0))
+        } else {
+            (overlap.height, Vector2::new(0.0, sign(center_delta.y)))
+        };
+        Some(Self {
+            a,
+            b,
+            sides_a,
+            sides_b,
+            depth,
+            normal,
+            group_a: None,
+            group_b: None,
+        })
+    }
+
+    /// Attach the group names of `a` and `b`.
+    pub fn with_groups(mut self, group_a: Option<&Group>, group_b: Option<&Group>) -> Self {
+        self.group_a = group_a.map(|g| g.0);
+        self.group_b = group_b.map(|g| g.0);
+        self
+    }
+
+    /// Replace the contact `depth` and `normal` (pointing from `a` toward
+    /// `b`), e.g. for shapes other than boxes. The sides become the single
+    /// side of each collider facing the other along `normal`.
+    pub fn with_contact(mut self, depth: f32, normal: Vector2) -> Self {
+        let (side_a, side_b) = if normal.x > 0.0 {
+            (BoxSide::Right, BoxSide::Left)
+        } else if normal.x < 0.0 {
+            (BoxSide::Left, BoxSide::Right)
+        } else if normal.y < 0.0 {
+            (BoxSide::Top, BoxSide::Bottom)
+        } else {
+            (BoxSide::Bottom, BoxSide::Top)
+        };
+        self.sides_a = BoxSides::from_slice(&[side_a]);
+        self.sides_b = BoxSides::from_slice(&[side_b]);
+        self.depth = depth;
+        self.normal = normal;
+        self
+    }
+
+    /// Contact data seen from `first`: its sides, the other entity's sides,
+    /// and the normal pointing from `first` toward the other entity.
+    ///
+    /// `first` should be [`a`](Self::a) or [`b`](Self::b); any other entity
+    /// is treated as `a`.
+    pub fn oriented(&self, first: Entity) -> (&BoxSides, &BoxSides, Vector2) {
+        if first == self.b && first != self.a {
+            (&self.sides_b, &self.sides_a, -self.normal)
+        } else {
+            (&self.sides_a, &self.sides_b, self.normal)
+        }
+    }
+
+    /// Group name of `entity` if it is one of the pair and has a group.
+    pub fn group_of(&self, entity: Entity) -> Option<&str> {
+        if entity == self.a {
+            self.group_a.map(Key::as_str)
+        } else if entity == self.b {
+            self.group_b.map(Key::as_str)
+        } else {
+            None
+        }
+    }
+}
+
+/// Every [`CollisionEvent`] found by one run of the
+/// [`collision_detector`](crate::systems::collision_detector::collision_detector),
+/// in detection order.
+///
+/// Triggered once per frame, after the per-pair events and only when at least
+/// one pair overlaps, so observers that handle many pairs the same way (e.g.
+/// the Rust and Lua collision rules in a brick-heavy scene) run once instead
+/// of once per pair.
+#[derive(Event, Debug, Clone, Default)]
+pub struct CollisionBatch {
+    pub pairs: Vec<CollisionEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width: w,
+            height: h,
+        }
+    }
+
+    #[test]
+    fn from_rects_none_without_overlap() {
+        let a = Entity::from_bits(1);
+        let b = Entity::from_bits(2);
+        assert!(
+            CollisionEvent::from_rects(
+                a,
+                b,
+                &rect(0.0, 0.0, 10.0, 10.0),
+                &rect(20.0, 0.0, 5.0, 5.0)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn from_rects_uses_axis_of_least_penetration() {
+        let a = Entity::from_bits(1);
+        let b = Entity::from_bits(2);
+        // b overlaps a's right edge by 2 units and spans its full height.
+        let event = CollisionEvent::from_rects(
+            a,
+            b,
+            &rect(0.0, 0.0, 10.0, 10.0),
+            &rect(8.0, 0.0, 10.0, 10.0),
+        )
+        .unwrap();
+        assert_eq!(event.depth, 2.0);
+        assert_eq!(event.normal, Vector2::new(1.0, 0.0));
+        assert!(event.sides_a.contains(&BoxSide::Right));
+        assert!(event.sides_b.contains(&BoxSide::Left));
+
+        // Landing on top of b: small vertical overlap, normal points down.
+        let event = CollisionEvent::from_rects(
+            a,
+            b,
+            &rect(0.0, 0.0, 10.0, 10.0),
+            &rect(2.0, 9.0, 6.0, 10.0),
+        )
+        .unwrap();
+        assert_eq!(event.depth, 1.0);
+        assert_eq!(event.normal, Vector2::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn oriented_swaps_sides_and_flips_normal() {
+        let a = Entity::from_bits(1);
+        let b = Entity::from_bits(2);
+        let event = CollisionEvent::from_rects(
+            a,
+            b,
+            &rect(0.0, 0.0, 10.0, 10.0),
+            &rect(8.0, 0.0, 10.0, 10.0),
+        )
+        .unwrap()
+        .with_groups(Some(&Group::new("player")), None);
+
+        let (sides_first, sides_other, normal) = event.oriented(b);
+        assert!(sides_first.contains(&BoxSide::Left));
+        assert!(sides_other.contains(&BoxSide::Right));
+        assert_eq!(normal, Vector2::new(-1.0, 0.0));
+        assert_eq!(event.group_of(a), Some("player"));
+        assert_eq!(event.group_of(b), None);
+    }
 }
@@ -0,0 +1,25 @@
+//! Credits events.
+//!
+//! A [`CreditsFinished`] event is triggered when the last line of a
+//! [`CreditsRoll`](crate::components::credits::CreditsRoll) has scrolled off
+//! the screen and the roll has been despawned. With Lua, the `on_done`
+//! function given to `engine.show_credits` is called; Rust games observe it
+//! directly:
+//!
+//! ```ignore
+//! fn on_credits_finished(_trigger: On<CreditsFinished>, mut signals: ResMut<WorldSignals>) {
+//!     signals.set_string("scene", "menu");
+//!     signals.set_flag("switch_scene");
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when a credits roll has finished scrolling.
+#[derive(Event, Debug, Clone)]
+pub struct CreditsFinished {
+    /// The despawned roll entity.
+    pub roll: Entity,
+    /// Lua function given to `engine.show_credits`.
+    pub callback: Option<String>,
+}
@@ -0,0 +1,29 @@
+//! High score events.
+//!
+//! A [`HighScoreEvent`] is triggered when a submitted score has been handled:
+//! after its [`NameEntry`](crate::components::nameentry::NameEntry) is
+//! confirmed, or straight away when the score didn't make the table. With
+//! Lua, the `on_done` function given to `engine.submit_score` is called;
+//! Rust games observe it directly:
+//!
+//! ```ignore
+//! fn on_high_score(trigger: On<HighScoreEvent>) {
+//!     if let Some(rank) = trigger.event().rank {
+//!         info!("new high score at #{}", rank + 1);
+//!     }
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when a submitted score has been entered, or rejected.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct HighScoreEvent {
+    /// Name entered, or `None` when the score didn't qualify.
+    pub name: Option<String>,
+    pub score: i64,
+    /// 0-based position in the table, or `None` when the score didn't qualify.
+    pub rank: Option<usize>,
+    /// Lua function given to `engine.submit_score`.
+    pub callback: Option<String>,
+}
@@ -0,0 +1,24 @@
+//! Hitbox contact events.
+//!
+//! [`HitEvent`] is triggered by
+//! [`hitbox_system`](crate::systems::hitbox::hitbox_system) when an active
+//! [`Hitbox`](crate::components::hitbox::Hitbox) first overlaps an active
+//! [`Hurtbox`](crate::components::hitbox::Hurtbox) during a swing.
+//!
+//! Rust consumers observe it via [`EngineBuilder::add_observer`](crate::engine_app::EngineBuilder::add_observer).
+//! Lua consumers attach a [`LuaOnHit`](crate::components::lua_on_hit::LuaOnHit)
+//! component to the attacking entity (feature = "lua").
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Rectangle;
+
+/// Triggered once per swing for each entity a hitbox strikes.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HitEvent {
+    /// Owner of the hitbox.
+    pub attacker: Entity,
+    /// Owner of the hurtbox.
+    pub target: Entity,
+    /// World-space intersection of the two boxes (e.g. to place a spark).
+    pub overlap: Rectangle,
+}
@@ -46,6 +46,60 @@ pub enum InputAction {
     ToggleFullscreen,
 }
 
+impl InputAction {
+    /// Every action, in declaration order.
+    pub const ALL: [InputAction; 15] = [
+        InputAction::MainDirectionUp,
+        InputAction::MainDirectionDown,
+        InputAction::MainDirectionLeft,
+        InputAction::MainDirectionRight,
+        InputAction::SecondaryDirectionUp,
+        InputAction::SecondaryDirectionDown,
+        InputAction::SecondaryDirectionLeft,
+        InputAction::SecondaryDirectionRight,
+        InputAction::Back,
+        InputAction::Action1,
+        InputAction::Action2,
+        InputAction::Action3,
+        InputAction::Special,
+        InputAction::ToggleDebug,
+        InputAction::ToggleFullscreen,
+    ];
+
+    /// Canonical name used by Lua and replay files (e.g. `"main_up"`, `"action_1"`).
+    pub fn name(self) -> &'static str {
+        match self {
+            InputAction::MainDirectionUp => "main_up",
+            InputAction::MainDirectionDown => "main_down",
+            InputAction::MainDirectionLeft => "main_left",
+            InputAction::MainDirectionRight => "main_right",
+            InputAction::SecondaryDirectionUp => "secondary_up",
+            InputAction::SecondaryDirectionDown => "secondary_down",
+            InputAction::SecondaryDirectionLeft => "secondary_left",
+            InputAction::SecondaryDirectionRight => "secondary_right",
+            InputAction::Back => "back",
+            InputAction::Action1 => "action_1",
+            InputAction::Action2 => "action_2",
+            InputAction::Action3 => "action_3",
+            InputAction::Special => "special",
+            InputAction::ToggleDebug => "toggle_debug",
+            InputAction::ToggleFullscreen => "toggle_fullscreen",
+        }
+    }
+
+    /// Parses a canonical name; `"up"`, `"down"`, `"left"` and `"right"` are
+    /// accepted for the main directions.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "up" => Some(InputAction::MainDirectionUp),
+            "down" => Some(InputAction::MainDirectionDown),
+            "left" => Some(InputAction::MainDirectionLeft),
+            "right" => Some(InputAction::MainDirectionRight),
+            _ => Self::ALL.into_iter().find(|action| action.name() == name),
+        }
+    }
+}
+
 /// Event emitted when an input action is pressed or released.
 ///
 /// The `action` field identifies which logical action occurred, and `pressed`
@@ -56,4 +110,7 @@ pub struct InputEvent {
     pub action: InputAction,
     /// Whether the action was pressed (true) or released (false).
     pub pressed: bool,
+    /// Local player (0-based) whose device produced the event, or `None` for
+    /// the shared keyboard and mouse.
+    pub player: Option<u8>,
 }
@@ -0,0 +1,22 @@
+//! Input sequence events.
+//!
+//! An [`InputSequenceEvent`] is triggered when a pattern of
+//! [`InputSequences`](crate::resources::inputsequence::InputSequences) has
+//! been entered. With Lua, the function given to
+//! `engine.on_sequence(name, fn)` is called; Rust games observe it directly:
+//!
+//! ```ignore
+//! fn on_sequence(trigger: On<InputSequenceEvent>, mut signals: ResMut<WorldSignals>) {
+//!     if trigger.event().name == "konami" {
+//!         signals.set_flag("infinite_lives");
+//!     }
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when a named input pattern is completed.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct InputSequenceEvent {
+    pub name: String,
+}
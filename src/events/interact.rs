@@ -0,0 +1,21 @@
+//! Interaction events.
+//!
+//! [`InteractEvent`] is triggered by
+//! [`interaction_system`](crate::systems::interaction::interaction_system)
+//! when an interactor presses the action of the
+//! [`Interactable`](crate::components::interactable::Interactable) it has
+//! focused.
+//!
+//! Rust consumers observe it via [`EngineBuilder::add_observer`](crate::engine_app::EngineBuilder::add_observer).
+//! Lua consumers name a `callback` on the interactable (feature = "lua").
+
+use bevy_ecs::prelude::*;
+
+/// Triggered once per press of the interact action.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct InteractEvent {
+    /// The interactable entity.
+    pub entity: Entity,
+    /// The entity interacting with it (usually the player).
+    pub interactor: Entity,
+}
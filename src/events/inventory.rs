@@ -0,0 +1,43 @@
+//! Inventory events.
+//!
+//! [`give_item`](crate::systems::inventory::give_item) and
+//! [`take_item`](crate::systems::inventory::take_item) trigger an
+//! [`InventoryChangedEvent`] whenever items actually move, and
+//! [`give_item`](crate::systems::inventory::give_item) an
+//! [`InventoryFullEvent`] when some of them did not fit. With Lua, the
+//! functions registered with `engine.on_inventory_changed()` and
+//! `engine.on_inventory_full()` are called; Rust games observe them directly:
+//!
+//! ```ignore
+//! fn on_full(trigger: On<InventoryFullEvent>, mut commands: Commands) {
+//!     // drop the leftover on the floor
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when the item count of an inventory changes.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct InventoryChangedEvent {
+    /// Entity carrying the inventory.
+    pub entity: Entity,
+    /// Id of the item added or removed.
+    pub item: Arc<str>,
+    /// Items added (positive) or removed (negative).
+    pub delta: i64,
+    /// Total count of the item in the inventory afterwards.
+    pub count: u32,
+}
+
+/// Event triggered when items could not be added because the inventory is full.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct InventoryFullEvent {
+    /// Entity carrying the inventory.
+    pub entity: Entity,
+    /// Id of the item that did not fit.
+    pub item: Arc<str>,
+    /// Number of items left over.
+    pub leftover: u32,
+}
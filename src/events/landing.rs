@@ -0,0 +1,21 @@
+//! Faux-3D landing events.
+//!
+//! [`LandedEvent`] is triggered by
+//! [`height_system`](crate::systems::height::height_system) when an entity
+//! with a [`ZVelocity`](crate::components::height::ZVelocity) comes down to
+//! height `0`, once per landing and again after every bounce.
+//!
+//! Rust consumers observe it via [`EngineBuilder::add_observer`](crate::engine_app::EngineBuilder::add_observer).
+//! Lua consumers attach a [`LuaOnLand`](crate::components::lua_on_land::LuaOnLand)
+//! component to the entity (feature = "lua").
+
+use bevy_ecs::prelude::*;
+
+/// Triggered when an entity comes down to the ground.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct LandedEvent {
+    /// The entity that landed.
+    pub entity: Entity,
+    /// Downward speed when touching the ground, in world units per second.
+    pub impact_speed: f32,
+}
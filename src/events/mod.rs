@@ -8,27 +8,51 @@
 //! Submodules:
 //! - [`audio`] – commands and messages for the background audio thread
 //! - [`collision`] – collision notifications emitted by the physics/collision system
+//! - [`credits`] – a credits roll finished scrolling
 //! - [`gamestate`] – state transition notifications for the high-level game flow
 //! - [`gui_interactable`] – GUI interactable (button/image) click events
+//! - [`highscore`] – a submitted score entered into, or rejected by, the high score table
+//! - [`hit`] – hitbox vs hurtbox contact events
 //! - [`input`] – input action events (key press/release)
+//! - [`inputsequence`] – a named key chord/sequence pattern being entered
+//! - [`interact`] – the player pressing the interact action on a focused `Interactable`
+//! - [`inventory`] – items added to or removed from an inventory, and overflow
+//! - [`landing`] – an entity with a `ZVelocity` coming down to the ground
 //! - [`menu`] – menu selection events
+//! - [`quality`] – automatic quality level changes caused by frame time
 //! - [`luatimer`] – *(feature = "lua")* Lua timer callback events
+//! - [`resolution`] – internal render resolution change notifications
+//! - [`results`] – a results screen being closed by the player
 //! - [`switchdebug`] – toggle debug rendering and diagnostics on/off
 //! - [`switchfullscreen`] – toggle fullscreen mode on/off
+//! - [`windowfocus`] – window focus gained/lost notifications
+//! - [`worldclock`] – scheduled time-of-day events of the world clock
 //!
 //! See each submodule for concrete event data, semantics, and example usage.
 
 pub mod animation;
 pub mod audio;
 pub mod collision;
+pub mod credits;
 pub mod gamestate;
 pub mod gui_interactable;
+pub mod highscore;
+pub mod hit;
 pub mod input;
+pub mod inputsequence;
+pub mod interact;
+pub mod inventory;
+pub mod landing;
 #[cfg(feature = "lua")]
 pub mod luatimer;
 pub mod menu;
+pub mod quality;
+pub mod resolution;
+pub mod results;
 pub mod spawnmap;
 pub mod switchdebug;
 pub mod switchfullscreen;
 pub mod timer;
 pub mod tween;
+pub mod windowfocus;
+pub mod worldclock;
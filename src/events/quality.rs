@@ -0,0 +1,36 @@
+//! Automatic quality change event.
+//!
+//! [`quality_governor_system`](crate::systems::qualitygovernor::quality_governor_system)
+//! triggers [`QualityChangedEvent`] whenever the
+//! [`QualityGovernor`](crate::resources::qualitygovernor::QualityGovernor)
+//! steps quality down because frames are over budget, steps it back up, or
+//! restores it when `GameConfig::auto_quality` is turned off. Lua receives
+//! it through `engine.on_quality_changed(fn)`. Games can show a notice:
+//!
+//! ```ignore
+//! fn quality_notice(trigger: On<QualityChangedEvent>, mut signals: ResMut<WorldSignals>) {
+//!     if trigger.event().is_reduction() {
+//!         signals.set_flag("show_low_quality_notice");
+//!     }
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered after the quality governor changed level.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct QualityChangedEvent {
+    /// Level before the change.
+    pub old_level: usize,
+    /// Number of quality steps now applied; `0` is full quality.
+    pub level: usize,
+    /// Smoothed frame time that caused the change, in seconds.
+    pub frame_time: f32,
+}
+
+impl QualityChangedEvent {
+    /// Whether quality went down.
+    pub fn is_reduction(&self) -> bool {
+        self.level > self.old_level
+    }
+}
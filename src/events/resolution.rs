@@ -0,0 +1,90 @@
+//! Internal resolution change event and observer.
+//!
+//! [`apply_gameconfig_changes`](crate::systems::gameconfig::apply_gameconfig_changes)
+//! triggers [`ResolutionChangedEvent`] after it has recreated the
+//! [`RenderTarget`](crate::resources::rendertarget::RenderTarget) and updated
+//! [`ScreenSize`](crate::resources::screensize::ScreenSize) for a new
+//! `GameConfig::render_width`/`render_height` (set from Rust, or from Lua with
+//! `engine.set_internal_resolution`).
+//!
+//! [`resolution_changed_observer`] keeps the camera anchored to the same
+//! relative point of the screen. Games add their own observers to re-layout
+//! screen-space UI:
+//!
+//! ```ignore
+//! fn relayout_hud(trigger: On<ResolutionChangedEvent>, mut hud: Query<&mut ScreenPosition, With<Hud>>) {
+//!     let event = trigger.event();
+//!     for mut pos in hud.iter_mut() {
+//!         pos.pos.x = event.width as f32 - 100.0;
+//!     }
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+use log::debug;
+
+use crate::resources::camera2d::Camera2DRes;
+
+/// Event triggered after the internal render resolution changed.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolutionChangedEvent {
+    /// Previous render width in pixels.
+    pub old_width: u32,
+    /// Previous render height in pixels.
+    pub old_height: u32,
+    /// New render width in pixels.
+    pub width: u32,
+    /// New render height in pixels.
+    pub height: u32,
+}
+
+/// Observer that rescales the camera offset to the new resolution.
+///
+/// A camera centered on the old screen stays centered on the new one; an
+/// offset of `(0, 0)` stays in the top-left corner.
+pub fn resolution_changed_observer(
+    trigger: On<ResolutionChangedEvent>,
+    mut camera: ResMut<Camera2DRes>,
+) {
+    let event = trigger.event();
+    debug!(
+        "Resolution changed: {}x{} -> {}x{}",
+        event.old_width, event.old_height, event.width, event.height
+    );
+    if event.old_width > 0 {
+        camera.0.offset.x *= event.width as f32 / event.old_width as f32;
+    }
+    if event.old_height > 0 {
+        camera.0.offset.y *= event.height as f32 / event.old_height as f32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raylib::prelude::{Camera2D, Vector2};
+
+    #[test]
+    fn camera_offset_follows_resolution() {
+        let mut world = World::new();
+        world.insert_resource(Camera2DRes(Camera2D {
+            target: Vector2::zero(),
+            offset: Vector2::new(320.0, 180.0),
+            rotation: 0.0,
+            zoom: 1.0,
+        }));
+        world.add_observer(resolution_changed_observer);
+
+        world.trigger(ResolutionChangedEvent {
+            old_width: 640,
+            old_height: 360,
+            width: 1280,
+            height: 720,
+        });
+
+        assert_eq!(
+            world.resource::<Camera2DRes>().0.offset,
+            Vector2::new(640.0, 360.0)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Results screen events.
+//!
+//! [`results_screen_system`](crate::systems::results::results_screen_system)
+//! triggers a [`ResultsDoneEvent`] when the player closes a
+//! [`ResultsScreen`](crate::components::results::ResultsScreen). With Lua,
+//! the screen's `on_done` function is called; Rust games observe it directly:
+//!
+//! ```ignore
+//! fn on_results(_trigger: On<ResultsDoneEvent>, mut next: ResMut<NextGameState>) {
+//!     // go back to the title screen
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when a results screen is closed.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ResultsDoneEvent {
+    /// The despawned results screen entity.
+    pub screen: Entity,
+    /// Lua function named by the screen's `on_done`.
+    pub callback: Option<String>,
+}
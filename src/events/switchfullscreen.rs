@@ -1,15 +1,20 @@
-//! Fullscreen toggle event and observer.
+//! Fullscreen toggle and window mode events and observers.
 //!
 //! Pressing **F10** triggers [`SwitchFullScreenEvent`], which is handled by
-//! [`switch_fullscreen_observer`]. The observer toggles the window between
-//! fullscreen and windowed mode, using the [`FullScreen`] marker resource to
-//! track the current state.
+//! [`switch_fullscreen_observer`]. The observer flips
+//! [`GameConfig::fullscreen`] and switches the window between windowed mode
+//! and the configured [`FullscreenMode`] on the configured monitor.
+//!
+//! [`apply_gameconfig_changes`](crate::systems::gameconfig::apply_gameconfig_changes)
+//! triggers [`ApplyWindowModeEvent`] when `fullscreen`, `fullscreen_mode` or
+//! `monitor` no longer match the [`FullScreen`] resource, e.g. after
+//! `engine.set_window_mode("borderless", 1)` from Lua.
 
-use crate::resources::fullscreen::FullScreen;
+use crate::resources::fullscreen::{FullScreen, FullscreenMode, Monitors};
 use crate::resources::gameconfig::GameConfig;
 use bevy_ecs::observer::On;
 use bevy_ecs::prelude::*;
-use log::{debug, info};
+use log::{debug, info, warn};
 
 /// Event triggered to toggle fullscreen mode.
 ///
@@ -18,28 +23,105 @@ use log::{debug, info};
 #[derive(Event, Debug, Clone, Copy)]
 pub struct SwitchFullScreenEvent {}
 
+/// Event triggered to bring the window in line with the window mode settings
+/// of [`GameConfig`]. Handled by [`apply_window_mode_observer`].
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ApplyWindowModeEvent {}
+
 /// Observer that toggles fullscreen mode when [`SwitchFullScreenEvent`] fires.
 ///
-/// - If [`FullScreen`] resource exists: removes it and exits fullscreen.
-/// - If [`FullScreen`] resource is absent: inserts it and enters fullscreen,
-///   resizing the window to match the current monitor dimensions.
+/// Flips [`GameConfig::fullscreen`] so the choice is remembered by
+/// `GameConfig::save_to_file`, then applies it like
+/// [`apply_window_mode_observer`].
 pub fn switch_fullscreen_observer(
     _trigger: On<SwitchFullScreenEvent>,
     mut rl: NonSendMut<raylib::RaylibHandle>,
     mut commands: Commands,
     fullscreen: Option<Res<FullScreen>>,
-    config: Res<GameConfig>,
+    mut config: ResMut<GameConfig>,
 ) {
     debug!("SwitchFullScreenEvent triggered");
-    if fullscreen.is_some() {
-        commands.remove_resource::<FullScreen>();
-        rl.toggle_borderless_windowed();
-        let (w, h) = config.window_size();
-        rl.set_window_size(w as i32, h as i32);
-        info!("Full screen disabled");
-    } else {
-        commands.insert_resource(FullScreen {});
-        rl.toggle_borderless_windowed();
-        info!("Full screen enabled");
+    let current = fullscreen.map(|f| *f);
+    config.fullscreen = current.is_none();
+    sync_window_mode(&mut rl, &mut commands, current, &config);
+}
+
+/// Observer that applies the window mode settings of [`GameConfig`] when
+/// [`ApplyWindowModeEvent`] fires.
+pub fn apply_window_mode_observer(
+    _trigger: On<ApplyWindowModeEvent>,
+    mut rl: NonSendMut<raylib::RaylibHandle>,
+    mut commands: Commands,
+    fullscreen: Option<Res<FullScreen>>,
+    config: Res<GameConfig>,
+) {
+    debug!("ApplyWindowModeEvent triggered");
+    sync_window_mode(&mut rl, &mut commands, fullscreen.map(|f| *f), &config);
+}
+
+/// Switch the window from `current` to the state `config` asks for, updating
+/// the [`FullScreen`] and [`Monitors`] resources.
+///
+/// - Leaving fullscreen undoes the current mode and restores the configured
+///   window size.
+/// - Entering fullscreen moves the window to the configured monitor (falling
+///   back to the primary one if it is not connected) and enters the
+///   configured mode. Exclusive fullscreen uses the monitor's current video
+///   mode.
+/// - Changing mode or monitor while fullscreen does both.
+fn sync_window_mode(
+    rl: &mut raylib::RaylibHandle,
+    commands: &mut Commands,
+    current: Option<FullScreen>,
+    config: &GameConfig,
+) {
+    let monitors = Monitors::query();
+    let mut target = FullScreen::from_config(config);
+    if let Some(t) = target.as_mut()
+        && t.monitor as usize >= monitors.0.len()
+    {
+        warn!(
+            "Monitor {} is not connected ({} found), using monitor 0",
+            t.monitor,
+            monitors.0.len()
+        );
+        t.monitor = 0;
+    }
+    if current == target {
+        commands.insert_resource(monitors);
+        return;
+    }
+
+    match current.map(|c| c.mode) {
+        Some(FullscreenMode::Borderless) => rl.toggle_borderless_windowed(),
+        Some(FullscreenMode::Exclusive) => rl.toggle_fullscreen(),
+        None => {}
+    }
+
+    match target {
+        Some(t) => {
+            rl.set_window_monitor(t.monitor as i32);
+            if t.mode == FullscreenMode::Exclusive {
+                if let Some(m) = monitors.0.get(t.monitor as usize) {
+                    rl.set_window_size(m.width, m.height);
+                }
+                rl.toggle_fullscreen();
+            } else {
+                rl.toggle_borderless_windowed();
+            }
+            commands.insert_resource(t);
+            info!(
+                "Full screen enabled ({}, monitor {})",
+                t.mode.as_str(),
+                t.monitor
+            );
+        }
+        None => {
+            let (w, h) = config.window_size();
+            rl.set_window_size(w as i32, h as i32);
+            commands.remove_resource::<FullScreen>();
+            info!("Full screen disabled");
+        }
     }
+    commands.insert_resource(monitors);
 }
@@ -0,0 +1,20 @@
+//! Window focus change event.
+//!
+//! [`window_focus_system`](crate::systems::windowfocus::window_focus_system)
+//! triggers [`WindowFocusEvent`] whenever the window gains or loses input
+//! focus. Games observe it to auto-pause:
+//!
+//! ```ignore
+//! fn pause_on_focus_loss(trigger: On<WindowFocusEvent>, mut time: ResMut<WorldTime>) {
+//!     time.time_scale = if trigger.event().focused { 1.0 } else { 0.0 };
+//! }
+//! ```
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when the window gains or loses input focus.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowFocusEvent {
+    /// `true` if the window just gained focus, `false` if it lost it.
+    pub focused: bool,
+}
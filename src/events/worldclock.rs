@@ -0,0 +1,30 @@
+//! World clock scheduled events.
+//!
+//! [`world_clock_system`](crate::systems::worldclock::world_clock_system)
+//! triggers a [`WorldClockEvent`] each time the
+//! [`WorldClock`](crate::resources::worldclock::WorldClock) passes the time
+//! of one of its scheduled events. With Lua, the global function named after
+//! the event is called; Rust games observe it directly:
+//!
+//! ```ignore
+//! fn on_clock(trigger: On<WorldClockEvent>, mut commands: Commands) {
+//!     if &*trigger.event().name == "spawn_night_enemies" {
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+
+/// Event triggered when the world clock reaches a scheduled time of day.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct WorldClockEvent {
+    /// Name given when the event was scheduled.
+    pub name: Arc<str>,
+    /// Day the event fired on (0 is the first day).
+    pub day: u32,
+    /// Scheduled time, in minutes since midnight.
+    pub minute: f32,
+}
@@ -0,0 +1,265 @@
+//! Interned string keys.
+//!
+//! Group names, texture keys and signal keys come from a small set of strings
+//! that are cloned, hashed and compared every frame: every collision event
+//! carries the group names of both entities, every sprite names its texture,
+//! and every entity with [`Signals`](crate::components::signals::Signals)
+//! owns its keys. [`Key`] interns each distinct string once in a process-wide
+//! registry and is a `Copy` handle to it, so cloning is free, comparing is an
+//! integer compare and no `String` is allocated for a key that was seen
+//! before.
+//!
+//! A `Key` dereferences to `&'static str` and implements `Borrow<str>`, so maps
+//! keyed by `Key` are still looked up with plain `&str`. Conversions from
+//! `&str` and `String` intern the string; Lua strings convert both ways.
+//!
+//! Interned strings are never freed. Keys are meant for names chosen by the
+//! game (groups, textures, signals); building an unbounded number of distinct
+//! keys at runtime (one per spawned entity, say) grows the registry forever.
+//!
+//! # Example
+//!
+//! ```
+//! use aberredengine::key::Key;
+//!
+//! let a = Key::new("player");
+//! let b: Key = String::from("player").into();
+//! assert_eq!(a, b);
+//! assert_eq!(a.id(), b.id());
+//! assert_eq!(a.as_str(), "player");
+//! assert_eq!(Key::lookup("never_interned"), None);
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{OnceLock, RwLock};
+
+use rustc_hash::FxHashMap;
+
+/// An interned string. See the module docs.
+///
+/// Equality compares ids; hashing and ordering use the string, so a `Key`
+/// hashes like its `&str` (as `Borrow<str>` requires) and sorts alphabetically.
+#[derive(Clone, Copy)]
+pub struct Key {
+    id: u32,
+    name: &'static str,
+}
+
+#[derive(Default)]
+struct Registry {
+    keys: FxHashMap<&'static str, Key>,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(Registry::default()))
+}
+
+impl Key {
+    /// Intern `name`, returning the existing key if it was seen before.
+    pub fn new(name: &str) -> Self {
+        if let Some(key) = Self::lookup(name) {
+            return key;
+        }
+        let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+        if let Some(key) = registry.keys.get(name) {
+            return *key;
+        }
+        let name: &'static str = Box::leak(name.into());
+        let key = Key {
+            id: registry.keys.len() as u32,
+            name,
+        };
+        registry.keys.insert(name, key);
+        key
+    }
+
+    /// The key for `name` if it was interned before, without interning it.
+    pub fn lookup(name: &str) -> Option<Self> {
+        registry()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys
+            .get(name)
+            .copied()
+    }
+
+    /// Registry index, unique per distinct string for the life of the process.
+    pub fn id(self) -> u32 {
+        self.id
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.cmp(other.name)
+    }
+}
+
+impl Default for Key {
+    /// The empty string.
+    fn default() -> Self {
+        Key::new("")
+    }
+}
+
+impl Deref for Key {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.name
+    }
+}
+
+impl AsRef<str> for Key {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+impl Borrow<str> for Key {
+    fn borrow(&self) -> &str {
+        self.name
+    }
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.name, f)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl From<&str> for Key {
+    fn from(name: &str) -> Self {
+        Key::new(name)
+    }
+}
+
+impl From<&String> for Key {
+    fn from(name: &String) -> Self {
+        Key::new(name)
+    }
+}
+
+impl From<String> for Key {
+    fn from(name: String) -> Self {
+        Key::new(&name)
+    }
+}
+
+impl From<&Key> for Key {
+    fn from(key: &Key) -> Self {
+        *key
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.name.to_owned()
+    }
+}
+
+impl PartialEq<str> for Key {
+    fn eq(&self, other: &str) -> bool {
+        self.name == other
+    }
+}
+
+impl PartialEq<&str> for Key {
+    fn eq(&self, other: &&str) -> bool {
+        self.name == *other
+    }
+}
+
+impl PartialEq<String> for Key {
+    fn eq(&self, other: &String) -> bool {
+        self.name == other
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::FromLua for Key {
+    fn from_lua(value: mlua::Value, lua: &mlua::Lua) -> mlua::Result<Self> {
+        let name = mlua::String::from_lua(value, lua)?;
+        Ok(Key::new(&name.to_str()?))
+    }
+}
+
+#[cfg(feature = "lua")]
+impl mlua::IntoLua for Key {
+    fn into_lua(self, lua: &mlua::Lua) -> mlua::Result<mlua::Value> {
+        self.name.into_lua(lua)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_hash::FxHashSet;
+
+    #[test]
+    fn same_string_same_key() {
+        let a = Key::new("key_test_ball");
+        let b = Key::from(String::from("key_test_ball"));
+        assert_eq!(a, b);
+        assert_eq!(a.id(), b.id());
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_ne!(a, Key::new("key_test_brick"));
+    }
+
+    #[test]
+    fn maps_keyed_by_key_are_looked_up_by_str() {
+        let mut flags = FxHashSet::default();
+        flags.insert(Key::new("key_test_moving"));
+        assert!(flags.contains("key_test_moving"));
+        assert!(!flags.contains("key_test_stopped"));
+    }
+
+    #[test]
+    fn lookup_does_not_intern() {
+        assert_eq!(Key::lookup("key_test_unseen"), None);
+        let key = Key::new("key_test_seen");
+        assert_eq!(Key::lookup("key_test_seen"), Some(key));
+    }
+
+    #[test]
+    fn orders_alphabetically() {
+        let mut keys = vec![Key::new("key_test_c"), Key::new("key_test_a")];
+        keys.sort();
+        assert_eq!(keys[0], "key_test_a");
+    }
+}
@@ -9,13 +9,20 @@ pub use imgui;
 pub use raylib;
 
 pub mod components;
+#[cfg(feature = "lua")]
+pub mod docgen;
 pub mod engine_app;
 pub mod events;
+pub mod key;
 #[cfg(feature = "lua")]
 pub mod lua_plugin;
 #[cfg(feature = "lua")]
+pub mod lua_test_runner;
+#[cfg(feature = "lua")]
 pub mod luarc_generator;
+pub mod project;
 pub mod resources;
+pub mod stress;
 #[cfg(feature = "lua")]
 pub mod stub_generator;
 pub mod systems;
@@ -6,46 +6,74 @@
 //! - [`enter_play`] – calls `on_enter_play`, processes initial signals/groups, triggers first scene switch
 //! - [`switch_scene`] – despawns non-persistent entities, calls `on_switch_scene`, drains all command queues
 //! - [`update`] – calls `on_update_<scene>` each frame, drains command queues, handles quit/scene-switch flags
+//! - [`sync_lua_tag_cache`] – mirrors entity [`Tags`] into the cache behind `engine.entity_has_tag`
+//! - [`sync_lua_inventory_cache`] – mirrors [`Inventory`] contents into the cache behind `engine.inventory_count`
+//! - [`drain_queued_commands`] – drains command queues outside a callback (headless test runner)
 //!
 //! # SystemParam Bundles
 //!
 //! - [`ScriptingContext`] – `LuaRuntime` + audio command writer
-//! - [`GameSceneState`] – world signals, post-process, config, camera follow, stores
-//! - [`EntityProcessing`] – entity command queries + LuaPhase query
-
+//! - [`GameSceneState`] – world signals, post-process, render mode, config, camera follow, stores
+//! - [`EntityProcessing`] – entity command queries + LuaPhase and Lua collision rule queries
+//! - [`LuaReadState`] – read-only resources mirrored into Lua caches each frame
+
+use crate::components::audiotriggers::AudioTriggers;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::inventory::Inventory;
+use crate::components::luacollision::LuaCollisionRule;
 use crate::components::luaphase::LuaPhase;
 use crate::components::persistent::{CleanableEntity, Persistent};
+use crate::components::tags::Tags;
+use crate::components::tilemap::TileMapLayers;
+use crate::components::visibilitypolygon::VisibilityPolygon;
 use crate::events::audio::AudioCmd;
+use crate::resources::alphamask::AlphaMasks;
 use crate::resources::animationstore::AnimationStore;
 use crate::resources::camera2d::Camera2DRes;
+use crate::resources::cameraeffects::CameraEffects;
 use crate::resources::camerafollowconfig::CameraFollowConfig;
+use crate::resources::colorgrading::ColorGrading;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fogofwar::FogOfWar;
 use crate::resources::fontstore::FontStore;
+use crate::resources::fullscreen::Monitors;
 use crate::resources::gameconfig::GameConfig;
 use crate::resources::gamestate::{GameStates, NextGameState};
-use crate::resources::group::TrackedGroups;
+use crate::resources::group::{GroupAggregates, TrackedGroups};
 use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::highscores::HighScores;
 use crate::resources::input::InputState;
 use crate::resources::input_bindings::InputBindings;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::layervisibility::LayerVisibility;
 use crate::resources::lua_runtime::{
-    AnimationCmd, AssetCmd, CameraFollowCmd, GameConfigCmd, GroupCmd, InputCmd, InputSnapshot,
-    LuaRuntime, PhaseCmd, RenderCmd,
+    AnimationCmd, AssetCmd, CameraFollowCmd, CollisionRuleCmd, GameConfigCmd, GroupCmd, InputCmd,
+    InputSnapshot, LuaRuntime, PhaseCmd, RenderCmd,
 };
+use crate::resources::occluders::Occluders;
 use crate::resources::postprocessshader::PostProcessShader;
+use crate::resources::rendermode::RenderMode;
+use crate::resources::scenestate::SceneState;
 use crate::resources::screensize::ScreenSize;
 use crate::resources::shaderstore::ShaderStore;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::spawnpoints::SpawnPoints;
 use crate::resources::systemsstore::SystemsStore;
 use crate::resources::texturestore::TextureStore;
 
 use crate::resources::signal_keys as sk;
+use crate::resources::weather::Weather;
+use crate::resources::worldclock::WorldClock;
 use crate::resources::worldsignals::WorldSignals;
 use crate::resources::worldtime::WorldTime;
 use crate::systems::lua_commands::{
     DrainScope, EffectCmdBufs, EntityCmdQueries, drain_and_process_effect_commands,
     drain_and_process_phase_commands, process_animation_command, process_asset_command,
-    process_camera_follow_command, process_gameconfig_command, process_group_command,
-    process_input_command, process_render_command, process_signal_command,
+    process_camera_follow_command, process_collision_rule_command, process_gameconfig_command,
+    process_group_command, process_input_command, process_render_command, process_signal_command,
 };
 use crate::systems::mapspawn::load_font_with_mipmaps;
+use crate::systems::scenestate::{RestorableQuery, save_scene_state};
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
 use log::{debug, error, info};
@@ -64,6 +92,9 @@ pub struct ScriptingContext<'w> {
 pub struct GameSceneState<'w> {
     pub world_signals: ResMut<'w, WorldSignals>,
     pub post_process: ResMut<'w, PostProcessShader>,
+    pub render_mode: ResMut<'w, RenderMode>,
+    pub layer_visibility: ResMut<'w, LayerVisibility>,
+    pub color_grading: ResMut<'w, ColorGrading>,
     pub config: ResMut<'w, GameConfig>,
     pub camera_follow: ResMut<'w, CameraFollowConfig>,
     pub systems_store: Res<'w, SystemsStore>,
@@ -75,6 +106,30 @@ pub struct GameSceneState<'w> {
 pub struct EntityProcessing<'w, 's> {
     pub cmd_queries: EntityCmdQueries<'w, 's>,
     pub luaphase: Query<'w, 's, (Entity, &'static mut LuaPhase)>,
+    pub lua_collision_rules: Query<'w, 's, (Entity, &'static mut LuaCollisionRule)>,
+}
+
+/// Read-only state copied into the Lua runtime's caches before
+/// `on_update_<scene>` runs (camera, screen size, group aggregates, spatial
+/// index, tilemap grids, fog of war, line of sight, world clock, weather,
+/// items, spawn points, ECS statistics).
+#[derive(SystemParam)]
+pub struct LuaReadState<'w, 's> {
+    pub camera: Res<'w, Camera2DRes>,
+    pub screen: Res<'w, ScreenSize>,
+    pub group_aggregates: Res<'w, GroupAggregates>,
+    pub spatial_index: Res<'w, SpatialIndex>,
+    pub monitors: Res<'w, Monitors>,
+    pub tilemaps: Query<'w, 's, (Entity, &'static TileMapLayers, &'static GlobalTransform2D)>,
+    pub fog: Res<'w, FogOfWar>,
+    pub occluders: Res<'w, Occluders>,
+    pub visibility_polygons: Query<'w, 's, (Entity, &'static VisibilityPolygon)>,
+    pub clock: Res<'w, WorldClock>,
+    pub weather: Res<'w, Weather>,
+    pub items: Res<'w, ItemRegistry>,
+    pub spawn_points: Res<'w, SpawnPoints>,
+    pub high_scores: Res<'w, HighScores>,
+    pub ecs_stats: Res<'w, EcsStats>,
 }
 
 /// Persistent per-frame buffers for the command queues drained by [`drain_common_commands`].
@@ -92,6 +147,7 @@ pub(crate) struct CommonCmdBufs {
     input: Vec<InputCmd>,
     animation: Vec<AnimationCmd>,
     group: Vec<GroupCmd>,
+    collision_rule: Vec<CollisionRuleCmd>,
 }
 
 // This function is meant to load all resources
@@ -101,6 +157,7 @@ pub fn setup(
     mut raylib: crate::systems::RaylibAccess,
     mut fonts: NonSendMut<FontStore>,
     mut shaders: NonSendMut<ShaderStore>,
+    mut alpha_masks: ResMut<AlphaMasks>,
     mut scripting: ScriptingContext,
 ) {
     // This function sets up the game world, loading resources
@@ -144,6 +201,7 @@ pub fn setup(
             th,
             cmd,
             &mut tex_store,
+            &mut alpha_masks,
             &mut fonts,
             &mut shaders,
             &mut scripting.audio_cmd_writer,
@@ -177,6 +235,9 @@ pub fn enter_play(
     systems_store: Res<SystemsStore>,
     lua_runtime: NonSend<LuaRuntime>,
 ) {
+    // Expose signals seeded before play (e.g. the initial "scene") to on_enter_play
+    lua_runtime.update_signal_cache(worldsignals.snapshot());
+
     // Call Lua on_enter_play function if it exists
     if lua_runtime.has_function("on_enter_play") {
         match lua_runtime.call_function::<_, String>("on_enter_play", ()) {
@@ -267,7 +328,14 @@ fn drain_common_commands(
         // key already persisted in the resource.
         let mut gui_theme_staging = gui_theme_store.clone();
         for cmd in bufs.render.drain(..).chain(bufs.gui_theme.drain(..)) {
-            process_render_command(cmd, &mut scene_state.post_process, &mut gui_theme_staging);
+            process_render_command(
+                cmd,
+                &mut scene_state.post_process,
+                &mut scene_state.render_mode,
+                &mut scene_state.layer_visibility,
+                &mut scene_state.color_grading,
+                &mut gui_theme_staging,
+            );
         }
         // Re-validate every staged theme's button skin (not just the ones a
         // command touched this batch) -- cheap (a handful of themes, one
@@ -306,7 +374,7 @@ fn drain_common_commands(
 
     lua_runtime.drain_input_commands_into(&mut bufs.input);
     for cmd in bufs.input.drain(..) {
-        process_input_command(cmd, bindings);
+        process_input_command(cmd, bindings, commands);
     }
 
     lua_runtime.drain_group_commands_into(&mut bufs.group);
@@ -316,6 +384,11 @@ fn drain_common_commands(
         }
         lua_runtime.update_tracked_groups_cache(&tracked_groups.groups);
     }
+
+    lua_runtime.drain_collision_rule_commands_into(&mut bufs.collision_rule);
+    for cmd in bufs.collision_rule.drain(..) {
+        process_collision_rule_command(commands, &mut entities.lua_collision_rules, cmd);
+    }
 }
 
 /// Per-frame update system for scene-specific logic.
@@ -329,8 +402,7 @@ fn drain_common_commands(
 pub fn update(
     time: Res<WorldTime>,
     input: Res<InputState>,
-    camera: Res<Camera2DRes>,
-    screen: Res<ScreenSize>,
+    read_state: LuaReadState,
     mut commands: Commands,
     mut next_game_state: ResMut<NextGameState>,
     mut scripting: ScriptingContext,
@@ -362,7 +434,41 @@ pub fn update(
     // Update signal cache for Lua to read current values
     lua_runtime.update_signal_cache(scene_state.world_signals.snapshot());
     lua_runtime.update_gameconfig_cache(&scene_state.config);
-    lua_runtime.update_camera_cache(&camera, &screen, scene_state.config.pixel_snap_camera);
+    lua_runtime.update_camera_cache(
+        &read_state.camera,
+        &read_state.screen,
+        scene_state.config.pixel_snap_camera,
+    );
+    lua_runtime.update_group_aggregates_cache(&read_state.group_aggregates);
+    if read_state.spatial_index.is_changed() {
+        lua_runtime.update_spatial_index_cache(&read_state.spatial_index);
+    }
+    if read_state.monitors.is_changed() {
+        lua_runtime.update_monitors_cache(&read_state.monitors);
+    }
+    lua_runtime.update_tilemap_cache(
+        read_state
+            .tilemaps
+            .iter()
+            .map(|(entity, layers, transform)| (entity, &layers.grid, transform)),
+    );
+    lua_runtime.update_fog_cache(&read_state.fog);
+    lua_runtime
+        .update_visibility_cache(&read_state.occluders, read_state.visibility_polygons.iter());
+    lua_runtime.update_clock_cache(&read_state.clock);
+    lua_runtime.update_weather_cache(&read_state.weather);
+    if read_state.items.is_changed() {
+        lua_runtime.update_item_cache(&read_state.items);
+    }
+    if read_state.spawn_points.is_changed() {
+        lua_runtime.update_spawn_point_cache(&read_state.spawn_points);
+    }
+    if read_state.high_scores.is_changed() {
+        lua_runtime.update_high_score_cache(&read_state.high_scores);
+    }
+    if read_state.ecs_stats.is_changed() {
+        lua_runtime.update_ecs_stats_cache(&read_state.ecs_stats);
+    }
     if bindings.take_dirty() {
         lua_runtime.update_bindings_cache(&bindings);
     }
@@ -426,6 +532,8 @@ pub fn switch_scene(
     mut common_bufs: Local<CommonCmdBufs>,
     gui_theme_store: Res<GuiThemeStore>,
     mut gui_theme_warn_cache: ResMut<GuiThemeWarnCache>,
+    mut saved_scenes: ResMut<SceneState>,
+    restorables: RestorableQuery,
 ) {
     let lua_runtime = &scripting.lua_runtime;
     debug!("switch_scene: System called!");
@@ -440,7 +548,10 @@ pub fn switch_scene(
     lua_runtime.clear_function_cache();
 
     for entity in entities_to_clean.iter() {
-        commands.entity(entity).try_despawn();
+        commands
+            .entity(entity)
+            .try_remove::<AudioTriggers>()
+            .try_despawn();
     }
 
     // Clear entity registrations for despawned (non-persistent) entities
@@ -463,6 +574,14 @@ pub fn switch_scene(
         .cloned()
         .unwrap_or_else(|| sk::DEFAULT_SCENE.to_string());
 
+    save_scene_state(&mut saved_scenes, &restorables, &scene);
+
+    // The render mode is per scene: start every scene with full redraws.
+    scene_state.render_mode.set_if_neq(RenderMode::Full);
+
+    // Camera effects belong to the scene that started them.
+    commands.insert_resource(CameraEffects::default());
+
     // Call Lua on_switch_scene function if it exists
     if lua_runtime.has_function("on_switch_scene")
         && let Err(e) = lua_runtime.call_function::<_, ()>("on_switch_scene", scene.clone())
@@ -487,26 +606,51 @@ pub fn switch_scene(
     lua_runtime.update_gameconfig_cache(&scene_state.config);
 }
 
-/// Drains `asset_commands` queued from gameplay (`on_update_*`, `on_switch_scene`, phase/timer/
-/// collision callbacks) and loads them into `TextureStore`/`FontStore`/`ShaderStore`/audio.
+/// Drains the common command queues outside of any engine callback.
 ///
-/// `setup()` drains this queue once for `on_setup`-time loads; this system is the reachable
-/// drain site for any `engine.load_*` call made after setup. Mirrors
-/// [`crate::systems::mapspawn::process_lua_map_commands`].
-#[allow(clippy::too_many_arguments)]
+/// Used by the headless [`crate::lua_test_runner`] so commands queued from a
+/// spec body (e.g. by `spawn_test_entity`) take effect without running a frame.
+#[allow(clippy::too_many_arguments, private_interfaces)]
+pub fn drain_queued_commands(
+    mut commands: Commands,
+    mut scripting: ScriptingContext,
+    mut scene_state: GameSceneState,
+    mut entities: EntityProcessing,
+    mut bindings: ResMut<InputBindings>,
+    mut tracked_groups: ResMut<TrackedGroups>,
+    mut common_bufs: Local<CommonCmdBufs>,
+    gui_theme_store: Res<GuiThemeStore>,
+    mut gui_theme_warn_cache: ResMut<GuiThemeWarnCache>,
+) {
+    drain_common_commands(
+        &scripting.lua_runtime,
+        &mut commands,
+        &mut entities,
+        &mut scene_state,
+        &mut scripting.audio_cmd_writer,
+        &mut bindings,
+        &mut tracked_groups,
+        &mut common_bufs,
+        &gui_theme_store,
+        &mut gui_theme_warn_cache,
+    );
+}
+
+/// Loads `asset_commands` queued from gameplay (`on_update_*`, `on_switch_scene`, phase/timer/
+/// collision callbacks) into `TextureStore`/`AlphaMasks`/`FontStore`/`ShaderStore`/audio.
+///
+/// `setup()` drains this queue once for `on_setup`-time loads; after setup the queue is drained
+/// by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands), which runs this
+/// system first so maps and worlds applied later in the frame can use the loaded assets.
 pub fn process_lua_asset_commands(
-    lua_runtime: NonSend<LuaRuntime>,
+    InMut(buf): InMut<Vec<AssetCmd>>,
     mut raylib: crate::systems::RaylibAccess,
     mut tex_store: ResMut<TextureStore>,
+    mut alpha_masks: ResMut<AlphaMasks>,
     mut fonts: NonSendMut<FontStore>,
     mut shaders: NonSendMut<ShaderStore>,
     mut audio_cmd_writer: MessageWriter<AudioCmd>,
-    mut buf: Local<Vec<AssetCmd>>,
 ) {
-    lua_runtime.drain_asset_commands_into(&mut buf);
-    if buf.is_empty() {
-        return;
-    }
     let (rl, th) = (&mut *raylib.rl, &*raylib.th);
     for cmd in buf.drain(..) {
         process_asset_command(
@@ -514,6 +658,7 @@ pub fn process_lua_asset_commands(
             th,
             cmd,
             &mut tex_store,
+            &mut alpha_masks,
             &mut fonts,
             &mut shaders,
             &mut audio_cmd_writer,
@@ -522,6 +667,53 @@ pub fn process_lua_asset_commands(
     }
 }
 
+/// Mirrors [`Tags`] changes into the Lua tag cache read by `engine.entity_has_tag()`.
+///
+/// Incremental: only entities whose tags were added/changed or removed (including
+/// despawns) since the last run are touched.
+pub fn sync_lua_tag_cache(
+    changed: Query<(Entity, &Tags), Changed<Tags>>,
+    mut removed: RemovedComponents<Tags>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    for entity in removed.read() {
+        lua_runtime.remove_entity_tags_cache(entity.to_bits());
+    }
+    for (entity, tags) in changed.iter() {
+        lua_runtime.set_entity_tags_cache(entity.to_bits(), tags);
+    }
+}
+
+/// Mirrors [`Inventory`] changes into the Lua cache read by
+/// `engine.inventory_count()` and `engine.inventory_slots()`.
+///
+/// Incremental, like [`sync_lua_tag_cache`].
+pub fn sync_lua_inventory_cache(
+    changed: Query<(Entity, &Inventory), Changed<Inventory>>,
+    mut removed: RemovedComponents<Inventory>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    for entity in removed.read() {
+        lua_runtime.remove_inventory_cache(entity.to_bits());
+    }
+    for (entity, inventory) in changed.iter() {
+        lua_runtime.set_inventory_cache(entity.to_bits(), inventory);
+    }
+}
+
+/// Mirrors texture sizes into the Lua cache read by `engine.texture_size()`
+/// whenever the [`TextureStore`] changes.
+pub fn sync_lua_texture_size_cache(
+    tex_store: Option<Res<TextureStore>>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if let Some(tex_store) = tex_store
+        && tex_store.is_changed()
+    {
+        lua_runtime.update_texture_size_cache(&tex_store);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -529,7 +721,6 @@ mod tests {
     use crate::components::sprite::Sprite;
     use bevy_ecs::message::Messages;
     use bevy_ecs::system::{RunSystemOnce, SystemState};
-    use std::sync::Arc;
 
     /// Builds a [`World`] with all resources [`drain_common_commands`] depends on.
     fn new_drain_test_world() -> World {
@@ -545,6 +736,7 @@ mod tests {
         world.insert_resource(Messages::<AudioCmd>::default());
         world.insert_resource(GuiThemeStore::default());
         world.insert_resource(GuiThemeWarnCache::default());
+        world.insert_resource(SceneState::default());
         world.insert_non_send(LuaRuntime::new().expect("LuaRuntime::new"));
         world
     }
@@ -615,6 +807,426 @@ mod tests {
         assert!(world.resource::<TrackedGroups>().groups.contains("enemies"));
     }
 
+    #[test]
+    fn collision_rule_commands_toggle_and_remove_rules() {
+        use crate::components::luacollision::LuaCollisionCallback;
+
+        let mut world = new_drain_test_world();
+        let rule = |a: &str, b: &str| {
+            LuaCollisionRule::new(
+                a,
+                b,
+                LuaCollisionCallback {
+                    name: "on_hit".into(),
+                },
+            )
+        };
+        let ball_brick = world.spawn(rule("ball", "brick")).id();
+        let ball_wall = world.spawn((rule("ball", "wall"), Persistent)).id();
+        let player_enemy = world.spawn(rule("player", "enemy_*")).id();
+
+        {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load(
+                    "engine.set_collision_rule_enabled('brick', 'ball', false) \
+                     engine.remove_collision_rule('enemy_*', 'player')",
+                )
+                .exec()
+                .expect("queue collision rule commands");
+        }
+        run_drain_common_commands(&mut world);
+
+        assert!(!world.get::<LuaCollisionRule>(ball_brick).unwrap().enabled);
+        assert!(world.get::<LuaCollisionRule>(ball_wall).unwrap().enabled);
+        // A rule-only entity is despawned along with its rule.
+        assert!(world.get_entity(player_enemy).is_err());
+
+        {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load("engine.clear_collision_rules()")
+                .exec()
+                .expect("queue clear_collision_rules");
+        }
+        run_drain_common_commands(&mut world);
+
+        assert!(world.get_entity(ball_brick).is_err());
+        // Persistent entities keep living without the rule.
+        assert!(world.get::<LuaCollisionRule>(ball_wall).is_none());
+        assert!(world.get::<Persistent>(ball_wall).is_some());
+    }
+
+    #[test]
+    fn on_collision_scene_spawns_a_scene_scoped_rule() {
+        use crate::components::persistent::SceneScoped;
+
+        let mut world = new_drain_test_world();
+        {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load("engine.on_collision_scene('ball', {'brick', 'wall'}, 'on_hit')")
+                .exec()
+                .expect("queue on_collision_scene");
+        }
+        run_drain_common_commands(&mut world);
+
+        let mut rules = world.query_filtered::<&LuaCollisionRule, With<SceneScoped>>();
+        let rule = rules.single(&world).unwrap();
+        assert_eq!(rule.callback.name, "on_hit");
+        assert!(rule.is_for_pair("brick|wall", "ball"));
+        // Cleaned up by the next scene switch
+        let mut cleanable =
+            world.query_filtered::<Entity, (With<LuaCollisionRule>, CleanableEntity)>();
+        assert_eq!(cleanable.iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn group_bounds_reads_aggregates_cache_after_track_group_bounds() {
+        use crate::resources::group::{GroupAggregate, GroupAggregates};
+
+        let mut world = new_drain_test_world();
+        {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load("engine.track_group_bounds('enemies')")
+                .exec()
+                .expect("queue track_group_bounds");
+        }
+        run_drain_common_commands(&mut world);
+        assert!(world.resource::<TrackedGroups>().has_aggregates("enemies"));
+
+        let mut aggregates = GroupAggregates::default();
+        let mut agg = GroupAggregate {
+            members: vec![
+                (Entity::from_bits(1), Vector2 { x: 0.0, y: 0.0 }),
+                (Entity::from_bits(2), Vector2 { x: 10.0, y: 20.0 }),
+            ],
+            ..Default::default()
+        };
+        agg.recompute();
+        aggregates.groups.insert("enemies".into(), agg);
+
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        lua_runtime.update_group_aggregates_cache(&aggregates);
+        let (count, cx, max_y, nearest): (i64, f32, f32, u64) = lua_runtime
+            .lua()
+            .load(
+                "local b = engine.group_bounds('enemies') \
+                 return b.count, b.cx, b.max_y, engine.group_nearest('enemies', 9, 19)",
+            )
+            .eval()
+            .expect("read group bounds");
+        assert_eq!((count, cx, max_y), (2, 5.0, 20.0));
+        assert_eq!(nearest, Entity::from_bits(2).to_bits());
+        let missing: mlua::Value = lua_runtime
+            .lua()
+            .load("return engine.group_bounds('nobody')")
+            .eval()
+            .unwrap();
+        assert!(missing.is_nil());
+    }
+
+    #[test]
+    fn spatial_queries_read_index_cache() {
+        use crate::resources::spatialindex::SpatialIndex;
+
+        let world = new_drain_test_world();
+        let mut index = SpatialIndex::default();
+        let enemy = Entity::from_bits(7);
+        index.insert(enemy, Vector2 { x: 10.0, y: 10.0 }, ["enemy"]);
+        index.insert(
+            Entity::from_bits(8),
+            Vector2 { x: 500.0, y: 0.0 },
+            ["enemy"],
+        );
+
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        lua_runtime.update_spatial_index_cache(&index);
+        let (count, first, nearest, dist): (i64, u64, u64, f32) = lua_runtime
+            .lua()
+            .load(
+                "local ids = engine.entities_in_rect(0, 0, 20, 20, 'enemy') \
+                 local id, d = engine.nearest_entity(0, 10, 'enemy', 50) \
+                 return #ids, ids[1], id, d",
+            )
+            .eval()
+            .expect("spatial queries");
+        assert_eq!((count, first), (1, enemy.to_bits()));
+        assert_eq!((nearest, dist), (enemy.to_bits(), 10.0));
+        let none: mlua::Value = lua_runtime
+            .lua()
+            .load("return engine.nearest_entity(0, 0, 'ghost')")
+            .eval()
+            .unwrap();
+        assert!(none.is_nil());
+    }
+
+    #[test]
+    fn can_see_uses_polygons_then_line_of_sight() {
+        use crate::resources::occluders::{Segment, visibility_polygon};
+        use crate::resources::spatialindex::SpatialIndex;
+
+        let world = new_drain_test_world();
+        let mut occluders = Occluders::default();
+        occluders.set_tile_segments(vec![Segment::new(
+            Vector2 { x: 50.0, y: -20.0 },
+            Vector2 { x: 50.0, y: 20.0 },
+        )]);
+        occluders.rebuild(Vec::new());
+        let guard = Entity::from_bits(7);
+        let mut polygon = VisibilityPolygon::new(100.0);
+        polygon.points = visibility_polygon(Vector2::zero(), 100.0, None, occluders.segments());
+        let mut index = SpatialIndex::default();
+        index.insert(Entity::from_bits(8), Vector2 { x: 80.0, y: 0.0 }, ["thief"]);
+        index.insert(
+            Entity::from_bits(9),
+            Vector2 { x: -40.0, y: 0.0 },
+            ["thief"],
+        );
+        index.insert(
+            Entity::from_bits(10),
+            Vector2 { x: 0.0, y: 200.0 },
+            ["thief"],
+        );
+
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        lua_runtime.update_spatial_index_cache(&index);
+        lua_runtime.update_visibility_cache(&occluders, [(guard, &polygon)]);
+        let seen: (bool, bool, bool, bool, bool) = lua_runtime
+            .lua()
+            .load(
+                "return engine.can_see(7, 8), engine.can_see(7, 9), engine.can_see(9, 8), \
+                 engine.can_see(10, 8), engine.can_see(7, 99)",
+            )
+            .eval()
+            .expect("can_see");
+        assert_eq!(seen, (false, true, false, true, false));
+    }
+
+    #[test]
+    fn get_clock_reads_clock_cache() {
+        let world = new_drain_test_world();
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        let mut clock = WorldClock::default();
+        clock.set_time(20.0 * 60.0 + 15.0);
+        clock.day = 3;
+        lua_runtime.update_clock_cache(&clock);
+
+        let (time, hour, minute, day, daylight): (String, u32, u32, u32, f32) = lua_runtime
+            .lua()
+            .load(
+                "local c = engine.get_clock() \
+                 return c.time, c.hour, c.minute, c.day, c.daylight",
+            )
+            .eval()
+            .expect("get_clock");
+        assert_eq!((time.as_str(), hour, minute, day), ("20:15", 20, 15, 3));
+        assert_eq!(daylight, clock.daylight());
+
+        let bad: mlua::Result<()> = lua_runtime
+            .lua()
+            .load("engine.at_time('8pm', 'spawn_night_enemies')")
+            .exec();
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn get_weather_reads_weather_cache() {
+        use crate::resources::weather::WeatherKind;
+
+        let world = new_drain_test_world();
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        let mut weather = Weather::default();
+        weather.set_weather(WeatherKind::Rain, 0.7, 5.0);
+        weather.set_wind(Vector2::new(-40.0, 0.0), 0.0);
+        weather.step(2.5);
+        lua_runtime.update_weather_cache(&weather);
+
+        let (kind, intensity, target, wind_x): (String, f32, f32, f32) = lua_runtime
+            .lua()
+            .load(
+                "local w = engine.get_weather() \
+                 return w.kind, w.intensity, w.target_intensity, w.wind_x",
+            )
+            .eval()
+            .expect("get_weather");
+        assert_eq!((kind.as_str(), target, wind_x), ("rain", 0.7, -40.0));
+        assert!((intensity - 0.35).abs() < 1e-5);
+
+        let bad: mlua::Result<()> = lua_runtime
+            .lua()
+            .load("engine.set_weather('hail', 1.0)")
+            .exec();
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn get_spawn_point_reads_spawn_point_cache() {
+        use crate::components::gridlayout::GridValue;
+        use crate::components::mapposition::MapPosition;
+        use crate::components::portal::SpawnPoint;
+        use crate::systems::portal::spawn_point_registry_system;
+
+        let mut world = new_drain_test_world();
+        world.init_resource::<SpawnPoints>();
+        world.spawn((
+            SpawnPoint::new("player_start").with_property("facing", GridValue::String("up".into())),
+            MapPosition::new(48.0, 96.0),
+        ));
+        world.run_system_once(spawn_point_registry_system).unwrap();
+        let spawn_points = world.resource::<SpawnPoints>().clone();
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        lua_runtime.update_spawn_point_cache(&spawn_points);
+
+        let (x, y, facing, missing): (f32, f32, String, bool) = lua_runtime
+            .lua()
+            .load(
+                "local p = engine.get_spawn_point('player_start') \
+                 return p.x, p.y, p.properties.facing, engine.get_spawn_point('exit') == nil",
+            )
+            .eval()
+            .expect("get_spawn_point");
+        assert_eq!((x, y, facing.as_str(), missing), (48.0, 96.0, "up", true));
+    }
+
+    #[test]
+    fn inventory_reads_follow_inventory_cache() {
+        use crate::components::blackboard::BlackboardValue;
+        use crate::resources::itemregistry::ItemDef;
+
+        let mut world = new_drain_test_world();
+        let potion = ItemDef::new("potion")
+            .with_max_stack(3)
+            .with_property("heal", BlackboardValue::Scalar(25.0));
+        let mut inventory = Inventory::new(2);
+        inventory.add(&potion, 4);
+        let bag = world.spawn(inventory).id();
+        world.run_system_once(sync_lua_inventory_cache).unwrap();
+        let mut items = ItemRegistry::default();
+        items.register(potion);
+        world
+            .get_non_send::<LuaRuntime>()
+            .unwrap()
+            .update_item_cache(&items);
+
+        let read = |world: &World| -> (u32, Option<u32>, String, f32) {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load(format!(
+                    "local slots = engine.inventory_slots({id}) or {{}} \
+                     local item = engine.get_item('potion') \
+                     return engine.inventory_count({id}, 'potion'), \
+                        slots[2] and slots[2].count or nil, item.name, item.properties.heal",
+                    id = bag.to_bits()
+                ))
+                .eval()
+                .expect("inventory reads")
+        };
+        assert_eq!(read(&world), (4, Some(1), "potion".to_owned(), 25.0));
+
+        world.entity_mut(bag).remove::<Inventory>();
+        world.run_system_once(sync_lua_inventory_cache).unwrap();
+        assert_eq!(read(&world), (0, None, "potion".to_owned(), 25.0));
+
+        let bad: mlua::Result<()> = world
+            .get_non_send::<LuaRuntime>()
+            .unwrap()
+            .lua()
+            .load("engine.inventory_swap(1, 0, 1)")
+            .exec();
+        assert!(bad.is_err(), "slots are 1-based");
+    }
+
+    #[test]
+    fn screen_to_tile_reads_tilemap_cache() {
+        use crate::components::tilemap::{TileGrid, TileProjection};
+
+        let world = new_drain_test_world();
+        let map = Entity::from_bits(5);
+        let grid = TileGrid {
+            projection: TileProjection::Orthogonal,
+            tile_size: 16.0,
+            map_width: 10,
+            map_height: 10,
+        };
+        let transform = GlobalTransform2D {
+            position: Vector2 { x: 100.0, y: 0.0 },
+            rotation_degrees: 0.0,
+            scale: Vector2 { x: 2.0, y: 2.0 },
+        };
+
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        lua_runtime.update_tilemap_cache([(map, &grid, &transform)]);
+        // The default camera snapshot maps screen space 1:1 onto world space.
+        let (cx, cy, wx, wy): (u32, u32, f32, f32) = lua_runtime
+            .lua()
+            .load(
+                "local cx, cy = engine.screen_to_tile(5, 197, 65) \
+                 local wx, wy = engine.tile_to_world(5, 3, 2) \
+                 return cx, cy, wx, wy",
+            )
+            .eval()
+            .expect("tile queries");
+        assert_eq!((cx, cy), (3, 2));
+        assert_eq!((wx, wy), (212.0, 80.0));
+        let outside: mlua::Value = lua_runtime
+            .lua()
+            .load("return engine.screen_to_tile(5, 0, 0)")
+            .eval()
+            .unwrap();
+        assert!(outside.is_nil());
+    }
+
+    #[test]
+    fn is_revealed_reads_fog_cache() {
+        let world = new_drain_test_world();
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        let query = "return engine.is_revealed(20, 20), engine.is_revealed(100, 20)";
+
+        let mut fog = FogOfWar::default();
+        lua_runtime.update_fog_cache(&fog);
+        let disabled: (bool, bool) = lua_runtime.lua().load(query).eval().unwrap();
+        assert_eq!(disabled, (true, true));
+
+        fog.enable(16.0, Vec::new());
+        fog.reveal(Vector2 { x: 20.0, y: 20.0 }, 8.0, false);
+        lua_runtime.update_fog_cache(&fog);
+        let enabled: (bool, bool) = lua_runtime.lua().load(query).eval().unwrap();
+        assert_eq!(enabled, (true, false));
+    }
+
+    #[test]
+    fn entity_has_tag_follows_tag_insert_and_removal() {
+        let mut world = new_drain_test_world();
+        let entity = world.spawn(Tags::new(["enemy", "flying"])).id();
+        world.run_system_once(sync_lua_tag_cache).unwrap();
+
+        let has_tag = |world: &World, tag: &str| -> bool {
+            let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+            lua_runtime
+                .lua()
+                .load(format!(
+                    "return engine.entity_has_tag({}, '{}')",
+                    entity.to_bits(),
+                    tag
+                ))
+                .eval()
+                .expect("entity_has_tag")
+        };
+        assert!(has_tag(&world, "flying"));
+        assert!(!has_tag(&world, "ground"));
+
+        world.entity_mut(entity).remove::<Tags>();
+        world.run_system_once(sync_lua_tag_cache).unwrap();
+        assert!(!has_tag(&world, "flying"));
+    }
+
     #[test]
     fn drain_common_commands_leaves_gui_theme_store_unchanged_when_no_render_commands_queued() {
         let mut world = new_drain_test_world();
@@ -639,7 +1251,7 @@ mod tests {
         let entity = world
             .spawn((
                 Sprite {
-                    tex_key: Arc::from("old_tex"),
+                    tex_key: "old_tex".into(),
                     width: 16.0,
                     height: 16.0,
                     offset: Vector2::default(),
@@ -759,4 +1371,35 @@ mod tests {
             "on_switch_scene should see a refreshed snapshot where 'player' was already cleared"
         );
     }
+
+    #[test]
+    fn enter_play_exposes_seeded_scene_to_on_enter_play() {
+        let mut world = new_drain_test_world();
+        let noop = world.register_system(|| {});
+        world
+            .resource_mut::<SystemsStore>()
+            .insert("switch_scene", noop);
+        world
+            .resource_mut::<WorldSignals>()
+            .set_string(sk::SCENE, "intro");
+
+        world
+            .get_non_send::<LuaRuntime>()
+            .unwrap()
+            .lua()
+            .load(
+                "function on_enter_play()\n\
+                     _G.scene_seen = engine.get_string('scene')\n\
+                     return 'ok'\n\
+                 end",
+            )
+            .exec()
+            .expect("define on_enter_play");
+
+        world.run_system_once(enter_play).unwrap();
+
+        let lua_runtime = world.get_non_send::<LuaRuntime>().unwrap();
+        let scene_seen: Option<String> = lua_runtime.lua().globals().get("scene_seen").unwrap();
+        assert_eq!(scene_seen.as_deref(), Some("intro"));
+    }
 }
@@ -0,0 +1,732 @@
+//! Headless runner for Lua game-logic specs (`--run-lua-tests`).
+//!
+//! Every `*.lua` file in the spec directory (default `assets/scripts/tests/`)
+//! declares cases with `test(name, fn)`. Each case runs in a fresh headless
+//! world: `main.lua` is loaded, `on_setup` and `on_enter_play` run (asset loads
+//! are skipped; no window or audio device is opened), then the case body runs
+//! as a coroutine against a subset of the gameplay schedule (movement, tweens,
+//! timers, world clock, wind, collisions, fog of war, line of sight, phases
+//! and `on_update_<scene>`).
+//!
+//! Helpers available to spec files:
+//!
+//! - `step_frames(n)` – advance the world `n` frames (default 1) of 1/60 s
+//! - `assert_signal(key, expected[, tolerance])` – compare a world signal; the
+//!   type of `expected` selects flag (boolean), integer/scalar (number) or
+//!   string
+//! - `spawn_test_entity{...}` – spawn an entity right away and return its id;
+//!   each field `name = value` calls `:with_<name>(value)` on the builder, table
+//!   values are passed as the argument list and `key` registers the entity
+//!
+//! ```lua
+//! test("ball keeps moving", function()
+//!     local ball = spawn_test_entity{ key = "ball", position = { 0, 0 }, velocity = { 60, 0 } }
+//!     step_frames(60)
+//!     assert_signal("score", 0)
+//! end)
+//! ```
+//!
+//! A failed assertion or a Lua error fails the case. Errors raised by engine
+//! callbacks during `step_frames` are logged, as in the running game.
+
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use log::debug;
+use mlua::prelude::*;
+use raylib::prelude::{Camera2D, Vector2};
+
+use crate::components::mapposition::MapPosition;
+use crate::components::persistent::{Persistent, protect_engine_entities};
+use crate::components::rotation::Rotation;
+use crate::components::scale::Scale;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::tween::TweenValue;
+use crate::events::audio::AudioCmd;
+use crate::lua_plugin;
+use crate::resources::alphamask::AlphaMasks;
+use crate::resources::animationstore::AnimationStore;
+use crate::resources::camera2d::Camera2DRes;
+use crate::resources::camerafollowconfig::CameraFollowConfig;
+use crate::resources::colorgrading::ColorGrading;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fogofwar::FogOfWar;
+use crate::resources::formation::Formations;
+use crate::resources::framearena::FrameArena;
+use crate::resources::fullscreen::Monitors;
+use crate::resources::gameconfig::GameConfig;
+use crate::resources::gamestate::NextGameState;
+use crate::resources::group::{GroupAggregates, TrackedGroups};
+use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::input::InputState;
+use crate::resources::input_bindings::InputBindings;
+use crate::resources::highscores::HighScores;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::layervisibility::LayerVisibility;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::movementconfig::MovementConfig;
+use crate::resources::occluders::Occluders;
+use crate::resources::portaltransition::PortalTransition;
+use crate::resources::postprocessshader::PostProcessShader;
+use crate::resources::rendermode::{DirtyRegion, RenderMode};
+use crate::resources::scenemusic::SceneMusic;
+use crate::resources::scenestate::SceneState;
+use crate::resources::screensize::ScreenSize;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::spawnpoints::SpawnPoints;
+use crate::resources::systemsstore::SystemsStore;
+use crate::resources::texturestore::TextureStore;
+use crate::resources::weather::Weather;
+use crate::resources::worldclock::WorldClock;
+use crate::resources::worlds::Worlds;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+use crate::systems::attractor::attractor_system;
+use crate::systems::audio::update_bevy_audio_cmds;
+use crate::systems::blackboard::blackboard_ttl_system;
+use crate::systems::collision_detector::collision_detector;
+use crate::systems::colorgrading::color_grading_system;
+use crate::systems::credits::credits_roll_system;
+use crate::systems::fogofwar::{fog_opaque_system, fog_visibility_system};
+use crate::systems::forcearea::force_area_system;
+use crate::systems::formation::formation_system;
+use crate::systems::frame_arena::reset_frame_arena;
+use crate::systems::group::{update_group_aggregates_system, update_group_counts_system};
+use crate::systems::interaction::interaction_system;
+use crate::systems::joint::joint_system;
+use crate::systems::lua_collision::lua_collision_observer;
+use crate::systems::lua_commands::{apply_lua_commands, process_animation_command};
+use crate::systems::lua_inventory::{lua_inventory_changed_observer, lua_inventory_full_observer};
+use crate::systems::lua_setup_entity::lua_setup_entity_system;
+use crate::systems::lua_tween_finished::lua_tween_finished_observer;
+use crate::systems::lua_credits::lua_credits_observer;
+use crate::systems::lua_highscore::lua_highscore_observer;
+use crate::systems::lua_results::lua_results_observer;
+use crate::systems::lua_world_clock::lua_world_clock_observer;
+use crate::systems::luaphase::lua_phase_system;
+use crate::systems::luatimer::{lua_timer_observer, update_lua_timers};
+use crate::systems::movement::movement;
+use crate::systems::nameentry::name_entry_system;
+use crate::systems::pathfollow::path_follow_system;
+use crate::systems::portal::{
+    portal_interact_observer, portal_transition_system, spawn_point_registry_system,
+};
+use crate::systems::propagate_transforms::{
+    cleanup_orphaned_global_transforms, propagate_transforms,
+};
+use crate::systems::results::results_screen_system;
+use crate::systems::rope::rope_system;
+use crate::systems::sensor::sensor_system;
+use crate::systems::spatialindex::update_spatial_index_system;
+use crate::systems::stuckto::stuck_to_entity_system;
+use crate::systems::textscroller::text_scroller_system;
+use crate::systems::time::update_world_time;
+use crate::systems::ttl::ttl_system;
+use crate::systems::tween::tween_system;
+use crate::systems::visibility::{occluder_system, visibility_polygon_system};
+use crate::systems::water::water_system;
+use crate::systems::weather::weather_system;
+use crate::systems::worldclock::world_clock_system;
+use crate::systems::worlds::update_sub_worlds_system;
+
+/// Fixed frame time used by `step_frames`.
+const FRAME_DT: f32 = 1.0 / 60.0;
+
+/// Global table the prelude collects `test(name, fn)` cases into.
+const TESTS_GLOBAL: &str = "__aberred_tests";
+
+/// Spec helpers, loaded before `main.lua`. `step_frames` and
+/// `spawn_test_entity` yield to [`HeadlessGame::run_case`], which steps the
+/// world or drains the command queues before resuming the case.
+const PRELUDE: &str = r#"
+local tests = {}
+__aberred_tests = tests
+local unpack = unpack or table.unpack
+
+function test(name, fn)
+    assert(type(name) == "string", "test: name must be a string")
+    assert(type(fn) == "function", "test: body must be a function")
+    tests[#tests + 1] = { name = name, fn = fn }
+end
+
+function step_frames(n)
+    coroutine.yield("frames", n or 1)
+end
+
+local function show(v)
+    if type(v) == "string" then
+        return string.format("%q", v)
+    end
+    return tostring(v)
+end
+
+function assert_signal(key, expected, tolerance)
+    local kind = type(expected)
+    local actual
+    if kind == "boolean" then
+        actual = engine.has_flag(key)
+    elseif kind == "string" then
+        actual = engine.get_string(key)
+    elseif kind == "number" then
+        actual = engine.get_integer(key)
+        if actual == nil then
+            actual = engine.get_scalar(key)
+        end
+    else
+        error("assert_signal: expected value must be a boolean, number or string", 2)
+    end
+    local ok
+    if kind == "number" and actual ~= nil then
+        ok = math.abs(actual - expected) <= (tolerance or 1e-4)
+    else
+        ok = actual == expected
+    end
+    if not ok then
+        error(string.format("assert_signal(%q): expected %s, got %s", key, show(expected), show(actual)), 2)
+    end
+end
+
+local spawned = 0
+function spawn_test_entity(spec)
+    local builder = engine.spawn()
+    local key = spec.key
+    if key == nil then
+        spawned = spawned + 1
+        key = "__test_entity_" .. spawned
+    end
+    local names = {}
+    for name in pairs(spec) do
+        if name ~= "key" then
+            names[#names + 1] = name
+        end
+    end
+    table.sort(names)
+    for _, name in ipairs(names) do
+        local method = builder["with_" .. name]
+        if method == nil then
+            error("spawn_test_entity: unknown field '" .. name .. "' (no :with_" .. name .. "())", 2)
+        end
+        local value = spec[name]
+        if value == true then
+            method(builder)
+        elseif type(value) == "table" then
+            method(builder, unpack(value))
+        else
+            method(builder, value)
+        end
+    end
+    builder:register_as(key):build()
+    coroutine.yield("flush")
+    return engine.get_entity(key)
+end
+"#;
+
+/// Outcome of one `test(name, fn)` case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseResult {
+    /// Spec file the case was declared in.
+    pub file: PathBuf,
+    /// Name passed to `test`.
+    pub name: String,
+    /// Failure message, `None` if the case passed.
+    pub error: Option<String>,
+}
+
+impl CaseResult {
+    /// Whether the case passed.
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Results of a spec run.
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    /// Every case run, in spec file and declaration order.
+    pub cases: Vec<CaseResult>,
+}
+
+impl TestReport {
+    /// Number of passed cases.
+    pub fn passed(&self) -> usize {
+        self.cases.iter().filter(|c| c.passed()).count()
+    }
+
+    /// Number of failed cases.
+    pub fn failed(&self) -> usize {
+        self.cases.len() - self.passed()
+    }
+
+    /// True if at least one case ran and none failed.
+    pub fn success(&self) -> bool {
+        !self.cases.is_empty() && self.failed() == 0
+    }
+}
+
+/// Run every `*.lua` spec file in `spec_dir` (sorted by name, not recursive)
+/// against the game loaded from `main_script`.
+///
+/// Returns an error if the directory cannot be read. A spec file that fails to
+/// load is reported as a single failed case.
+pub fn run_spec_dir(main_script: &Path, spec_dir: &Path) -> Result<TestReport, String> {
+    let entries = std::fs::read_dir(spec_dir)
+        .map_err(|e| format!("Failed to read {}: {e}", spec_dir.display()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+        .collect();
+    files.sort();
+
+    let mut report = TestReport::default();
+    for file in files {
+        report.cases.extend(run_spec_file(Some(main_script), &file));
+    }
+    Ok(report)
+}
+
+/// Run the cases of one spec file, each in a fresh headless world.
+pub fn run_spec_file(main_script: Option<&Path>, spec: &Path) -> Vec<CaseResult> {
+    match std::fs::read_to_string(spec) {
+        Ok(source) => run_spec(main_script, spec, &source),
+        Err(e) => vec![CaseResult {
+            file: spec.to_path_buf(),
+            name: "<load>".into(),
+            error: Some(format!("Failed to read {}: {e}", spec.display())),
+        }],
+    }
+}
+
+fn run_spec(main_script: Option<&Path>, spec: &Path, source: &str) -> Vec<CaseResult> {
+    let load_failure = |error: String| {
+        vec![CaseResult {
+            file: spec.to_path_buf(),
+            name: "<load>".into(),
+            error: Some(error),
+        }]
+    };
+
+    // The first world only collects the case names
+    let names = match HeadlessGame::new(main_script, spec, source).and_then(|g| g.case_names()) {
+        Ok(names) => names,
+        Err(e) => return load_failure(e),
+    };
+
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let error = HeadlessGame::new(main_script, spec, source)
+                .and_then(|mut game| game.run_case(index))
+                .err();
+            CaseResult {
+                file: spec.to_path_buf(),
+                name,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// A game world without window, renderer or audio thread.
+struct HeadlessGame {
+    world: World,
+    schedule: Schedule,
+}
+
+impl HeadlessGame {
+    /// Load `main_script` (if any) and the spec, then run `on_setup` and
+    /// `on_enter_play` like the engine does when it starts.
+    fn new(main_script: Option<&Path>, spec: &Path, source: &str) -> Result<Self, String> {
+        let runtime =
+            LuaRuntime::new().map_err(|e| format!("Failed to create Lua runtime: {e}"))?;
+        runtime
+            .lua()
+            .load(PRELUDE)
+            .set_name("lua_test_runner")
+            .exec()
+            .map_err(|e| format!("Failed to load test helpers: {e}"))?;
+        if let Some(path) = main_script {
+            runtime
+                .run_script(&path.to_string_lossy())
+                .map_err(|e| format!("Failed to load {}: {e}", path.display()))?;
+        }
+        runtime
+            .lua()
+            .load(source)
+            .set_name(spec.to_string_lossy())
+            .exec()
+            .map_err(|e| format!("Failed to load spec: {e}"))?;
+
+        let mut world = Self::build_world(runtime);
+        let schedule = Self::build_schedule();
+        Self::setup(&mut world)?;
+        world
+            .run_system_once(lua_plugin::enter_play)
+            .map_err(|e| format!("Failed to run enter_play: {e}"))?;
+
+        let mut game = Self { world, schedule };
+        game.refresh_lua_caches();
+        Ok(game)
+    }
+
+    fn build_world(runtime: LuaRuntime) -> World {
+        let config = GameConfig::default();
+        let (w, h) = (config.render_width as i32, config.render_height as i32);
+
+        let mut world = World::new();
+        world.insert_resource(WorldTime::default().with_time_scale(1.0));
+        world.insert_resource(WorldSignals::default());
+        world.insert_resource(TrackedGroups::default());
+        world.insert_resource(GroupAggregates::default());
+        world.insert_resource(SpatialIndex::default());
+        world.insert_resource(SceneState::default());
+        world.insert_resource(ScreenSize { w, h });
+        world.insert_resource(Monitors::default());
+        world.insert_resource(InputState::default());
+        world.insert_resource(InputBindings::default());
+        world.insert_resource(NextGameState::new());
+        world.insert_resource(TextureStore::new());
+        world.insert_resource(Camera2DRes(Camera2D {
+            target: Vector2::zero(),
+            offset: Vector2::new(w as f32 * 0.5, h as f32 * 0.5),
+            rotation: 0.0,
+            zoom: 1.0,
+        }));
+        world.insert_resource(AnimationStore::default());
+        world.insert_resource(PostProcessShader::new());
+        world.insert_resource(CameraFollowConfig::default());
+        world.insert_resource(MovementConfig::default());
+        world.insert_resource(FrameArena::default());
+        world.insert_resource(RenderMode::default());
+        world.insert_resource(DirtyRegion::default());
+        world.insert_resource(GuiThemeStore::default());
+        world.insert_resource(GuiThemeWarnCache::default());
+        world.insert_resource(Worlds::default());
+        world.insert_resource(FogOfWar::default());
+        world.insert_resource(Occluders::default());
+        world.insert_resource(WorldClock::default());
+        world.insert_resource(Weather::default());
+        world.insert_resource(Formations::default());
+        world.insert_resource(SceneMusic::default());
+        world.insert_resource(ItemRegistry::default());
+        world.insert_resource(HighScores::default());
+        world.insert_resource(LayerVisibility::default());
+        world.insert_resource(ColorGrading::default());
+        world.insert_resource(AlphaMasks::default());
+        world.insert_resource(PortalTransition::default());
+        world.insert_resource(SpawnPoints::default());
+        world.insert_resource(EcsStats::default());
+        world.init_resource::<Messages<AudioCmd>>();
+        world.insert_resource(config);
+        world.insert_non_send(runtime);
+
+        let mut systems_store = SystemsStore::new();
+        let switch_scene = world.register_system(lua_plugin::switch_scene);
+        world.entity_mut(switch_scene.entity()).insert(Persistent);
+        systems_store.insert("switch_scene", switch_scene);
+        world.insert_resource(systems_store);
+
+        world.spawn((Observer::new(lua_collision_observer), Persistent));
+        world.spawn((Observer::new(lua_timer_observer), Persistent));
+        world.spawn((Observer::new(lua_world_clock_observer), Persistent));
+        world.spawn((Observer::new(lua_results_observer), Persistent));
+        world.spawn((Observer::new(lua_highscore_observer), Persistent));
+        world.spawn((Observer::new(lua_credits_observer), Persistent));
+        world.spawn((Observer::new(lua_inventory_changed_observer), Persistent));
+        world.spawn((Observer::new(lua_inventory_full_observer), Persistent));
+        world.spawn((Observer::new(portal_interact_observer), Persistent));
+        fn spawn_tween_finished_observer<T: TweenValue>(world: &mut World) {
+            world.spawn((Observer::new(lua_tween_finished_observer::<T>), Persistent));
+        }
+        spawn_tween_finished_observer::<MapPosition>(&mut world);
+        spawn_tween_finished_observer::<Rotation>(&mut world);
+        spawn_tween_finished_observer::<Scale>(&mut world);
+        spawn_tween_finished_observer::<ScreenPosition>(&mut world);
+        protect_engine_entities(&mut world);
+        world.flush();
+        world
+    }
+
+    /// Gameplay systems of the engine schedule that don't need raylib, with
+    /// the same ordering constraints.
+    fn build_schedule() -> Schedule {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_bevy_audio_cmds);
+        schedule.add_systems(update_group_counts_system.before(lua_phase_system));
+        schedule.add_systems(
+            lua_plugin::sync_lua_tag_cache
+                .before(collision_detector)
+                .before(lua_phase_system)
+                .before(lua_plugin::update),
+        );
+        schedule.add_systems(
+            lua_plugin::sync_lua_inventory_cache
+                .before(lua_phase_system)
+                .before(lua_plugin::update),
+        );
+        schedule.add_systems(lua_setup_entity_system);
+        schedule.add_systems(weather_system.before(force_area_system));
+        schedule.add_systems(results_screen_system);
+        schedule.add_systems(name_entry_system);
+        schedule.add_systems(credits_roll_system);
+        schedule.add_systems(text_scroller_system.before(propagate_transforms));
+        schedule.add_systems(force_area_system.before(movement));
+        schedule.add_systems(water_system.before(movement));
+        schedule.add_systems(attractor_system.before(movement));
+        schedule.add_systems(movement);
+        schedule.add_systems(joint_system.after(movement).before(propagate_transforms));
+        schedule.add_systems(rope_system.after(movement).before(propagate_transforms));
+        schedule.add_systems(path_follow_system.after(movement).before(propagate_transforms));
+        schedule.add_systems(
+            formation_system
+                .after(path_follow_system)
+                .before(propagate_transforms),
+        );
+        schedule.add_systems(ttl_system.after(movement));
+        schedule.add_systems(blackboard_ttl_system.after(movement));
+        schedule.add_systems(tween_system::<MapPosition>);
+        schedule.add_systems(tween_system::<Rotation>);
+        schedule.add_systems(tween_system::<Scale>);
+        schedule.add_systems(tween_system::<ScreenPosition>);
+        schedule.add_systems(
+            propagate_transforms
+                .after(movement)
+                .after(tween_system::<MapPosition>)
+                .after(tween_system::<Rotation>)
+                .after(tween_system::<Scale>)
+                .before(collision_detector),
+        );
+        schedule.add_systems(
+            (
+                update_group_aggregates_system,
+                update_spatial_index_system,
+                sensor_system,
+                interaction_system,
+                cleanup_orphaned_global_transforms,
+            )
+                .after(propagate_transforms)
+                .before(collision_detector),
+        );
+        schedule.add_systems(
+            (spawn_point_registry_system, portal_transition_system)
+                .chain()
+                .after(interaction_system),
+        );
+        schedule.add_systems(
+            (fog_opaque_system, fog_visibility_system)
+                .chain()
+                .after(propagate_transforms),
+        );
+        schedule.add_systems(
+            (occluder_system, visibility_polygon_system)
+                .chain()
+                .after(propagate_transforms),
+        );
+        schedule.add_systems(collision_detector.after(movement));
+        schedule.add_systems(stuck_to_entity_system.after(collision_detector));
+        schedule.add_systems(lua_phase_system.after(collision_detector));
+        schedule.add_systems(update_lua_timers);
+        schedule.add_systems(world_clock_system.before(lua_plugin::update));
+        schedule.add_systems(color_grading_system.after(apply_lua_commands));
+        schedule.add_systems(lua_plugin::update.after(lua_phase_system));
+        schedule.add_systems(
+            apply_lua_commands
+                .after(lua_plugin::update)
+                .before(occluder_system),
+        );
+        schedule.add_systems(update_sub_worlds_system.after(apply_lua_commands));
+        schedule.add_systems(
+            reset_frame_arena
+                .after(collision_detector)
+                .after(visibility_polygon_system),
+        );
+        schedule
+    }
+
+    /// Headless counterpart of [`lua_plugin::setup`]: calls `on_setup` and
+    /// registers animations; texture, font, sound and shader loads are skipped.
+    fn setup(world: &mut World) -> Result<(), String> {
+        let runtime = world.non_send::<LuaRuntime>();
+        if runtime.has_function("on_setup") {
+            runtime
+                .call_function::<_, ()>("on_setup", ())
+                .map_err(|e| format!("on_setup failed: {e}"))?;
+        }
+        let mut assets = Vec::new();
+        runtime.drain_asset_commands_into(&mut assets);
+        debug!("Skipping {} asset loads in headless mode", assets.len());
+        let mut animations = Vec::new();
+        runtime.drain_animation_commands_into(&mut animations);
+
+        let mut anim_store = world.resource_mut::<AnimationStore>();
+        for cmd in animations {
+            process_animation_command(&mut anim_store, cmd);
+        }
+        Ok(())
+    }
+
+    fn runtime(&self) -> &LuaRuntime {
+        self.world.non_send::<LuaRuntime>()
+    }
+
+    fn case_names(&self) -> Result<Vec<String>, String> {
+        let tests: LuaTable = self
+            .runtime()
+            .lua()
+            .globals()
+            .get(TESTS_GLOBAL)
+            .map_err(|e| e.to_string())?;
+        tests
+            .sequence_values::<LuaTable>()
+            .map(|case| case.and_then(|c| c.get::<String>("name")))
+            .collect::<LuaResult<_>>()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Run the body of case `index` (0-based) until it returns or fails.
+    fn run_case(&mut self, index: usize) -> Result<(), String> {
+        let thread = {
+            let lua = self.runtime().lua();
+            let tests: LuaTable = lua.globals().get(TESTS_GLOBAL).map_err(|e| e.to_string())?;
+            let case: LuaTable = tests.get(index + 1).map_err(|e| e.to_string())?;
+            let body: LuaFunction = case.get("fn").map_err(|e| e.to_string())?;
+            lua.create_thread(body).map_err(|e| e.to_string())?
+        };
+
+        loop {
+            let (request, frames): (Option<String>, Option<u32>) =
+                thread.resume(()).map_err(|e| e.to_string())?;
+            if thread.status() != LuaThreadStatus::Resumable {
+                return Ok(());
+            }
+            match request.as_deref() {
+                Some("frames") => {
+                    for _ in 0..frames.unwrap_or(1) {
+                        self.step();
+                    }
+                }
+                Some("flush") => self.flush()?,
+                other => {
+                    return Err(format!(
+                        "Unexpected coroutine.yield({other:?}) in test body; use step_frames(n)"
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Advance the world by one frame.
+    fn step(&mut self) {
+        update_world_time(&mut self.world, FRAME_DT);
+        self.schedule.run(&mut self.world);
+        self.world.clear_trackers();
+        self.refresh_lua_caches();
+    }
+
+    /// Apply the commands queued by the case body so far.
+    fn flush(&mut self) -> Result<(), String> {
+        self.world
+            .run_system_once(lua_plugin::drain_queued_commands)
+            .map_err(|e| format!("Failed to drain commands: {e}"))?;
+        self.refresh_lua_caches();
+        Ok(())
+    }
+
+    /// Make `engine.get_*` and `engine.group_bounds` in the case body see the
+    /// state at the end of the last frame (the engine only refreshes them
+    /// before `on_update_<scene>`).
+    fn refresh_lua_caches(&mut self) {
+        let snapshot = self.world.resource_mut::<WorldSignals>().snapshot();
+        let runtime = self.world.non_send::<LuaRuntime>();
+        runtime.update_signal_cache(snapshot);
+        runtime.update_group_aggregates_cache(self.world.resource::<GroupAggregates>());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str) -> Vec<CaseResult> {
+        run_spec(None, Path::new("inline_spec.lua"), source)
+    }
+
+    #[test]
+    fn signals_and_frames_are_visible_to_cases() {
+        let results = run(r#"
+            function on_enter_play()
+                engine.set_integer("score", 0)
+                engine.set_string("scene", "level")
+                return "ok"
+            end
+            function on_update_level(input, dt)
+                engine.set_integer("score", (engine.get_integer("score") or 0) + 1)
+            end
+
+            test("score counts frames", function()
+                assert_signal("scene", "level")
+                step_frames(3)
+                assert_signal("score", 3)
+                assert_signal("paused", false)
+            end)
+
+            test("wrong value fails", function()
+                assert_signal("score", 100)
+            end)
+        "#);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].passed(), "{:?}", results[0].error);
+        let error = results[1].error.as_deref().unwrap();
+        assert!(
+            error.contains(r#"assert_signal("score"): expected 100, got 0"#),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn spawned_entities_move_and_are_registered() {
+        let results = run(r#"
+            test("spawned entity moves", function()
+                engine.track_group_bounds("probe")
+                local id = spawn_test_entity{
+                    key = "probe",
+                    group = "probe",
+                    position = { 0, 0 },
+                    velocity = { 60, 0 },
+                }
+                assert(id ~= nil, "entity should be registered")
+                assert(engine.get_entity("probe") == id)
+                step_frames(60)
+                local b = engine.group_bounds("probe")
+                assert(math.abs(b.cx - 60) < 0.01, "moved to " .. b.cx)
+            end)
+
+            test("unknown field is an error", function()
+                spawn_test_entity{ wings = 2 }
+            end)
+        "#);
+
+        assert!(results[0].passed(), "{:?}", results[0].error);
+        assert!(
+            results[1]
+                .error
+                .as_deref()
+                .is_some_and(|e| e.contains("no :with_wings()"))
+        );
+    }
+
+    #[test]
+    fn load_errors_are_reported_as_a_failed_case() {
+        let results = run("test(");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "<load>");
+        assert!(!results[0].passed());
+
+        let report = TestReport { cases: results };
+        assert!(!report.success());
+        assert_eq!(report.failed(), 1);
+    }
+}
@@ -1,8 +1,13 @@
 //! Aberred Engine main entry point.
 //!
-//! Bootstraps the engine via [`EngineBuilder`]. Lua CLI tools
-//! (`--create-lua-stubs`, `--create-luarc`) are handled before the builder
-//! is invoked so the engine window is never opened for tool-only runs.
+//! Bootstraps the engine via [`EngineBuilder`]. If an `aberred.toml` project file is
+//! found (next to the executable, then in the working directory) the engine runs from
+//! its asset root with its entry script and defaults. Lua CLI tools
+//! (`--create-lua-stubs`, `--create-luarc`, `--create-lua-docs`, `--run-lua-tests`) are handled
+//! before the builder is invoked so the engine window is never opened for tool-only runs. Paths
+//! given to them resolve against the directory the engine was launched from; their defaults
+//! resolve against the asset root.
+//! `--stress <scene>` runs one of the built-in stress scenes instead of `main.lua`.
 
 // Do not create console on Windows
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
@@ -13,11 +18,11 @@ static GLOBAL: tracy_client::ProfiledAllocator<std::alloc::System> =
     tracy_client::ProfiledAllocator::new(std::alloc::System, 100);
 
 use aberredengine::engine_app::EngineBuilder;
+use aberredengine::project::ProjectConfig;
 use clap::Parser;
-#[cfg(feature = "lua")]
 use log::{error, info};
 #[cfg(feature = "lua")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Aberred Engine 2D
 #[derive(Parser)]
@@ -38,12 +43,59 @@ struct Cli {
     #[cfg(feature = "lua")]
     #[arg(long, value_name = "PATH")]
     create_luarc: Option<Option<PathBuf>>,
+
+    /// Generate the Lua API reference from engine metadata and exit.
+    /// Optionally provide a path (default: docs/lua-api.md); a .html path writes HTML.
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "PATH")]
+    create_lua_docs: Option<Option<PathBuf>>,
+
+    /// Run the Lua spec files in a headless world and exit with the result.
+    /// Optionally provide a directory (default: assets/scripts/tests).
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "DIR")]
+    run_lua_tests: Option<Option<PathBuf>>,
+
+    /// Run a built-in stress scene and log frame-time summaries.
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "SCENE")]
+    stress: Option<aberredengine::stress::StressScene>,
+}
+
+/// Resolve a path given on the command line against the directory the engine
+/// was launched from. `main` switches to the asset root before the CLI tools
+/// run, so a relative path would otherwise point into the project.
+#[cfg(feature = "lua")]
+fn from_launch_dir(launch_dir: &Path, path: PathBuf) -> PathBuf {
+    launch_dir.join(path)
 }
 
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
     let _cli = Cli::parse();
+    // Captured before entering the asset root so CLI paths keep meaning what
+    // the user typed.
+    #[cfg(feature = "lua")]
+    let launch_dir = std::env::current_dir().unwrap_or_default();
+
+    // Load aberred.toml and run from its asset root so relative asset paths resolve
+    let project = match ProjectConfig::discover() {
+        Ok(project) => project,
+        Err(e) => {
+            error!("Error: {e}");
+            std::process::exit(1);
+        }
+    };
+    if let Some(project) = &project {
+        let root = project.root_dir();
+        if let Err(e) = std::env::set_current_dir(&root) {
+            error!("Failed to enter asset root '{}': {e}", root.display());
+            std::process::exit(1);
+        }
+        info!("Running from asset root {}", root.display());
+    }
+    let project = project.unwrap_or_default();
 
     // Early-exit: generate Lua stubs and quit (no window/audio needed)
     #[cfg(feature = "lua")]
@@ -51,7 +103,9 @@ fn main() {
         use aberredengine::resources::lua_runtime::LuaRuntime;
         use aberredengine::stub_generator;
 
-        let path = maybe_path.unwrap_or_else(|| PathBuf::from("assets/scripts/engine.lua"));
+        let path = maybe_path
+            .map(|path| from_launch_dir(&launch_dir, path))
+            .unwrap_or_else(|| PathBuf::from("assets/scripts/engine.lua"));
         let runtime = LuaRuntime::new().expect("Failed to create Lua runtime for stub generation");
         match stub_generator::generate_stubs(&runtime) {
             Ok(content) => {
@@ -75,7 +129,9 @@ fn main() {
         use aberredengine::luarc_generator;
         use aberredengine::resources::lua_runtime::LuaRuntime;
 
-        let path = maybe_path.unwrap_or_else(|| PathBuf::from("assets/scripts/.luarc.json"));
+        let path = maybe_path
+            .map(|path| from_launch_dir(&launch_dir, path))
+            .unwrap_or_else(|| PathBuf::from("assets/scripts/.luarc.json"));
         let runtime =
             LuaRuntime::new().expect("Failed to create Lua runtime for .luarc.json generation");
         match luarc_generator::generate_luarc(&runtime, "engine.lua") {
@@ -94,11 +150,86 @@ fn main() {
         return;
     }
 
+    // Early-exit: generate the Lua API reference and quit (no window/audio needed)
+    #[cfg(feature = "lua")]
+    if let Some(maybe_path) = _cli.create_lua_docs {
+        use aberredengine::docgen;
+        use aberredengine::resources::lua_runtime::LuaRuntime;
+
+        let path = maybe_path
+            .map(|path| from_launch_dir(&launch_dir, path))
+            .unwrap_or_else(|| PathBuf::from("docs/lua-api.md"));
+        let runtime =
+            LuaRuntime::new().expect("Failed to create Lua runtime for API reference generation");
+        match docgen::generate_docs(&runtime, docgen::DocFormat::from_path(&path)) {
+            Ok(content) => {
+                if let Err(e) = docgen::write_docs(&path, &content) {
+                    error!("Error: {e}");
+                    std::process::exit(1);
+                }
+                info!("Lua API reference written to {}", path.display());
+            }
+            Err(e) => {
+                error!("Error generating Lua API reference: {e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Early-exit: run Lua specs headless and quit with a pass/fail exit code
+    #[cfg(feature = "lua")]
+    if let Some(maybe_dir) = _cli.run_lua_tests {
+        use aberredengine::lua_test_runner;
+
+        let dir = maybe_dir
+            .map(|dir| from_launch_dir(&launch_dir, dir))
+            .unwrap_or_else(|| PathBuf::from("assets/scripts/tests"));
+        let report = match lua_test_runner::run_spec_dir(&project.entry_script(), &dir) {
+            Ok(report) => report,
+            Err(e) => {
+                error!("Error running Lua tests: {e}");
+                std::process::exit(1);
+            }
+        };
+        for case in &report.cases {
+            match &case.error {
+                None => println!("PASS {} :: {}", case.file.display(), case.name),
+                Some(e) => println!("FAIL {} :: {}\n    {e}", case.file.display(), case.name),
+            }
+        }
+        println!("{} passed, {} failed", report.passed(), report.failed());
+        if !report.success() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Run a stress scene with frame-time reporting instead of the game
+    #[cfg(feature = "lua")]
+    if let Some(scene) = _cli.stress {
+        use aberredengine::stress;
+
+        info!("Running stress scene {scene:?}");
+        if let Err(err) = EngineBuilder::new()
+            .project(project)
+            .with_lua(scene.script_path())
+            .add_system(stress::frame_time_report_system)
+            .try_run()
+        {
+            error!("Error starting engine: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Run the engine with the Lua plugin
     #[cfg(feature = "lua")]
     {
+        let entry_script = project.entry_script();
         if let Err(err) = EngineBuilder::new()
-            .with_lua("./assets/scripts/main.lua")
+            .project(project)
+            .with_lua(entry_script)
             .try_run()
         {
             error!("Error starting engine: {err}");
@@ -118,8 +249,8 @@ fn main() {
     //       .run();
     #[cfg(not(feature = "lua"))]
     {
-        if let Err(err) = EngineBuilder::new().try_run() {
-            eprintln!("Error starting engine: {err}");
+        if let Err(err) = EngineBuilder::new().project(project).try_run() {
+            error!("Error starting engine: {err}");
             std::process::exit(1);
         }
     }
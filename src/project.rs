@@ -0,0 +1,237 @@
+//! Project manifest (`aberred.toml`).
+//!
+//! A game ships an `aberred.toml` next to the executable (or in the working
+//! directory) describing what the engine binary should run, so the same build
+//! can host different games without recompiling:
+//!
+//! ```toml
+//! title = "My Game"
+//! entry_script = "assets/scripts/main.lua"  # relative to asset_root
+//! asset_root = "."                          # relative to this file
+//! default_scene = "menu"
+//!
+//! [render]
+//! width = 640
+//! height = 360
+//!
+//! [audio]
+//! master_volume = 1.0
+//! music_volume = 0.8
+//! sfx_volume = 1.0
+//! ```
+//!
+//! Every key is optional. The working directory is switched to `asset_root`
+//! at startup, so asset paths in scripts and `config.ini` resolve against it.
+//! Render size, audio volumes and title are *defaults*: values in `config.ini`
+//! (the player's saved settings) take precedence. `default_scene` becomes the
+//! builder's [`initial_scene`](crate::engine_app::EngineBuilder::initial_scene).
+
+use std::path::{Path, PathBuf};
+
+use log::info;
+use serde::Deserialize;
+
+use crate::resources::gameconfig::GameConfig;
+
+/// File name looked up by [`ProjectConfig::discover`].
+pub const PROJECT_FILE: &str = "aberred.toml";
+
+/// Entry script used when `entry_script` is not set.
+pub const DEFAULT_ENTRY_SCRIPT: &str = "./assets/scripts/main.lua";
+
+/// Parsed `aberred.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Window title.
+    pub title: Option<String>,
+    /// Lua entry script, relative to the asset root.
+    pub entry_script: Option<PathBuf>,
+    /// Directory the engine runs from, relative to the manifest.
+    pub asset_root: Option<PathBuf>,
+    /// Scene entered first.
+    pub default_scene: Option<String>,
+    /// `[render]` section.
+    pub render: ProjectRender,
+    /// `[audio]` section.
+    pub audio: ProjectAudio,
+    /// Directory containing the manifest (set by [`load`](Self::load)).
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+}
+
+/// `[render]` section of `aberred.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectRender {
+    /// Internal render width in pixels.
+    pub width: Option<u32>,
+    /// Internal render height in pixels.
+    pub height: Option<u32>,
+}
+
+/// `[audio]` section of `aberred.toml`. Volumes are clamped to `0.0..=1.0`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectAudio {
+    pub master_volume: Option<f32>,
+    pub music_volume: Option<f32>,
+    pub sfx_volume: Option<f32>,
+}
+
+impl ProjectConfig {
+    /// Parse a manifest from a TOML string. `base_dir` is left empty.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Failed to parse {PROJECT_FILE}: {e}"))
+    }
+
+    /// Load a manifest from `path`, remembering its directory as `base_dir`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut project = Self::parse(&content)?;
+        project.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(project)
+    }
+
+    /// Find and load `aberred.toml`, looking next to the executable first and
+    /// then in the working directory. Returns `Ok(None)` if neither exists.
+    pub fn discover() -> Result<Option<Self>, String> {
+        let mut candidates = Vec::with_capacity(2);
+        if let Ok(exe) = std::env::current_exe()
+            && let Some(dir) = exe.parent()
+        {
+            candidates.push(dir.join(PROJECT_FILE));
+        }
+        candidates.push(PathBuf::from(PROJECT_FILE));
+
+        for path in candidates {
+            if path.is_file() {
+                info!("Loading project file {}", path.display());
+                return Self::load(&path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Directory the engine should run from: `asset_root` resolved against
+    /// the manifest's directory.
+    pub fn root_dir(&self) -> PathBuf {
+        match &self.asset_root {
+            Some(root) => self.base_dir.join(root),
+            None if self.base_dir.as_os_str().is_empty() => PathBuf::from("."),
+            None => self.base_dir.clone(),
+        }
+    }
+
+    /// Lua entry script, relative to [`root_dir`](Self::root_dir).
+    pub fn entry_script(&self) -> PathBuf {
+        self.entry_script
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ENTRY_SCRIPT))
+    }
+
+    /// Apply the manifest's defaults to `config`. Call before loading
+    /// `config.ini` so the player's saved settings win.
+    pub fn apply_to(&self, config: &mut GameConfig) {
+        if let Some(title) = &self.title {
+            config.window_title = title.clone();
+        }
+        if let Some(width) = self.render.width {
+            config.render_width = width;
+        }
+        if let Some(height) = self.render.height {
+            config.render_height = height;
+        }
+        if let Some(vol) = self.audio.master_volume {
+            config.master_volume = vol.clamp(0.0, 1.0);
+        }
+        if let Some(vol) = self.audio.music_volume {
+            config.music_volume = vol.clamp(0.0, 1.0);
+        }
+        if let Some(vol) = self.audio.sfx_volume {
+            config.sfx_volume = vol.clamp(0.0, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = r#"
+        title = "Kraken"
+        entry_script = "scripts/main.lua"
+        asset_root = "game"
+        default_scene = "intro"
+
+        [render]
+        width = 320
+        height = 180
+
+        [audio]
+        master_volume = 0.5
+        music_volume = 2.0
+        sfx_volume = -1.0
+    "#;
+
+    #[test]
+    fn parses_every_key() {
+        let project = ProjectConfig::parse(FULL).unwrap();
+        assert_eq!(project.title.as_deref(), Some("Kraken"));
+        assert_eq!(project.entry_script(), PathBuf::from("scripts/main.lua"));
+        assert_eq!(project.default_scene.as_deref(), Some("intro"));
+        assert_eq!(project.render.width, Some(320));
+        assert_eq!(project.render.height, Some(180));
+        assert_eq!(project.audio.master_volume, Some(0.5));
+    }
+
+    #[test]
+    fn empty_manifest_uses_defaults() {
+        let project = ProjectConfig::parse("").unwrap();
+        assert_eq!(project, ProjectConfig::default());
+        assert_eq!(project.entry_script(), PathBuf::from(DEFAULT_ENTRY_SCRIPT));
+        assert_eq!(project.root_dir(), PathBuf::from("."));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = ProjectConfig::parse("titel = \"typo\"").unwrap_err();
+        assert!(err.contains(PROJECT_FILE), "{err}");
+        assert!(ProjectConfig::parse("[render]\ndepth = 3").is_err());
+    }
+
+    #[test]
+    fn root_dir_is_relative_to_the_manifest() {
+        let mut project = ProjectConfig::parse(FULL).unwrap();
+        project.base_dir = PathBuf::from("/opt/games/kraken");
+        assert_eq!(project.root_dir(), PathBuf::from("/opt/games/kraken/game"));
+
+        project.asset_root = None;
+        assert_eq!(project.root_dir(), PathBuf::from("/opt/games/kraken"));
+    }
+
+    #[test]
+    fn apply_to_sets_defaults_and_clamps_volumes() {
+        let project = ProjectConfig::parse(FULL).unwrap();
+        let mut config = GameConfig::new();
+        project.apply_to(&mut config);
+
+        assert_eq!(config.window_title, "Kraken");
+        assert_eq!((config.render_width, config.render_height), (320, 180));
+        assert_eq!(config.volumes(), (0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn config_ini_overrides_project_defaults() {
+        let project = ProjectConfig::parse(FULL).unwrap();
+        let mut config = GameConfig::new();
+        project.apply_to(&mut config);
+        config
+            .load_from_str("[render]\nwidth = 800\n[audio]\nsfx_volume = 0.25\n")
+            .unwrap();
+
+        assert_eq!((config.render_width, config.render_height), (800, 180));
+        assert_eq!(config.volumes(), (0.5, 1.0, 0.25));
+    }
+}
@@ -0,0 +1,221 @@
+//! Accessibility settings shared by the render and menu systems.
+//!
+//! - [`ColorblindMode`] selects a built-in palette-remap shader that the
+//!   render system appends as the last pass of the post-processing chain.
+//!   The daltonization modes shift the color differences a viewer with the
+//!   given deficiency cannot see into ones they can; `Grayscale` remaps the
+//!   whole palette to luminance.
+//! - The high-contrast UI colors are used when
+//!   [`GameConfig::high_contrast_ui`](crate::resources::gameconfig::GameConfig::high_contrast_ui)
+//!   is set: screen-space [`DynamicText`](crate::components::dynamictext::DynamicText)
+//!   is drawn on an opaque plate and [`Menu`](crate::components::menu::Menu)
+//!   items use a fixed white/yellow pair.
+//!
+//! Both are configured in the `[accessibility]` section of `config.ini` and
+//! at runtime through `GameConfig` or the Lua `engine.set_colorblind_mode` /
+//! `engine.set_high_contrast_ui` functions.
+
+use raylib::prelude::Color;
+
+/// Text color of high-contrast UI text and unselected menu items.
+pub const HIGH_CONTRAST_TEXT: Color = Color::WHITE;
+/// Text color of the selected menu item in high-contrast mode.
+pub const HIGH_CONTRAST_SELECTED: Color = Color::YELLOW;
+/// Plate drawn behind screen-space text in high-contrast mode.
+pub const HIGH_CONTRAST_PLATE: Color = Color::BLACK;
+/// Padding of the high-contrast plate around the text bounds, in pixels.
+pub const HIGH_CONTRAST_PLATE_PADDING: f32 = 2.0;
+
+/// Text color to draw on a [`HIGH_CONTRAST_PLATE`].
+///
+/// Light colors are kept (fully opaque); dark ones, which would vanish on
+/// the plate, become [`HIGH_CONTRAST_TEXT`].
+pub fn high_contrast_text_color(color: Color) -> Color {
+    let luminance =
+        (0.2126 * color.r as f32 + 0.7152 * color.g as f32 + 0.0722 * color.b as f32) / 255.0;
+    if luminance < 0.5 {
+        HIGH_CONTRAST_TEXT
+    } else {
+        Color::new(color.r, color.g, color.b, 255)
+    }
+}
+
+/// Built-in color-vision palette remap applied after all post-process shaders.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ColorblindMode {
+    /// No remapping.
+    #[default]
+    None,
+    /// Daltonization for red-blind viewers.
+    Protanopia,
+    /// Daltonization for green-blind viewers.
+    Deuteranopia,
+    /// Daltonization for blue-blind viewers.
+    Tritanopia,
+    /// Remap every color to its luminance.
+    Grayscale,
+}
+
+impl ColorblindMode {
+    /// All variants, in declaration order.
+    pub const ALL: [ColorblindMode; 5] = [
+        ColorblindMode::None,
+        ColorblindMode::Protanopia,
+        ColorblindMode::Deuteranopia,
+        ColorblindMode::Tritanopia,
+        ColorblindMode::Grayscale,
+    ];
+
+    /// Canonical string form, the inverse of [`FromStr`](std::str::FromStr).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorblindMode::None => "none",
+            ColorblindMode::Protanopia => "protanopia",
+            ColorblindMode::Deuteranopia => "deuteranopia",
+            ColorblindMode::Tritanopia => "tritanopia",
+            ColorblindMode::Grayscale => "grayscale",
+        }
+    }
+
+    /// [`ShaderStore`](crate::resources::shaderstore::ShaderStore) key of the
+    /// built-in shader for this mode, or `None` when no pass is needed.
+    pub fn shader_key(self) -> Option<&'static str> {
+        match self {
+            ColorblindMode::None => None,
+            ColorblindMode::Protanopia => Some("__colorblind_protanopia"),
+            ColorblindMode::Deuteranopia => Some("__colorblind_deuteranopia"),
+            ColorblindMode::Tritanopia => Some("__colorblind_tritanopia"),
+            ColorblindMode::Grayscale => Some("__colorblind_grayscale"),
+        }
+    }
+
+    /// GLSL 330 fragment shader source for this mode, or `None` for `None`.
+    pub fn fragment_shader(self) -> Option<String> {
+        let mode = match self {
+            ColorblindMode::None => return None,
+            ColorblindMode::Protanopia => 1,
+            ColorblindMode::Deuteranopia => 2,
+            ColorblindMode::Tritanopia => 3,
+            ColorblindMode::Grayscale => 4,
+        };
+        Some(format!(
+            "#version 330\n#define CB_MODE {mode}\n{COLORBLIND_FS_BODY}"
+        ))
+    }
+
+    /// Parse an optional mode string, warning and falling back to
+    /// [`ColorblindMode::None`] if unrecognized.
+    pub fn from_opt_str_or_warn(mode: Option<&str>, context: &str) -> Self {
+        mode.map(|s| {
+            s.parse().unwrap_or_else(|_| {
+                log::warn!("Unknown colorblind mode '{s}' for '{context}', using 'none'");
+                Self::default()
+            })
+        })
+        .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for ColorblindMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColorblindMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// Shared body of the color-vision shaders; `CB_MODE` selects the remap.
+///
+/// Daltonization after Fidaner, Lin and Ozguven: the frame is converted to
+/// LMS space, the missing cone response is simulated, and the lost
+/// difference is redistributed to the channels the viewer can distinguish.
+const COLORBLIND_FS_BODY: &str = r#"
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+
+out vec4 finalColor;
+
+vec3 rgb_to_lms(vec3 c) {
+    return vec3(
+        17.8824 * c.r + 43.5161 * c.g + 4.11935 * c.b,
+        3.45565 * c.r + 27.1554 * c.g + 3.86714 * c.b,
+        0.0299566 * c.r + 0.184309 * c.g + 1.46709 * c.b);
+}
+
+vec3 lms_to_rgb(vec3 c) {
+    return vec3(
+        0.0809444479 * c.x - 0.130504409 * c.y + 0.116721066 * c.z,
+        -0.0102485335 * c.x + 0.0540193266 * c.y - 0.113614708 * c.z,
+        -0.000365296938 * c.x - 0.00412161469 * c.y + 0.693511405 * c.z);
+}
+
+vec3 simulate(vec3 lms) {
+#if CB_MODE == 1
+    return vec3(2.02344 * lms.y - 2.52581 * lms.z, lms.y, lms.z);
+#elif CB_MODE == 2
+    return vec3(lms.x, 0.494207 * lms.x + 1.24827 * lms.z, lms.z);
+#else
+    return vec3(lms.x, lms.y, -0.395913 * lms.x + 0.801109 * lms.y);
+#endif
+}
+
+void main() {
+    vec4 color = texture(texture0, fragTexCoord);
+#if CB_MODE == 4
+    float luma = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    finalColor = vec4(vec3(luma), color.a);
+#else
+    vec3 seen = lms_to_rgb(simulate(rgb_to_lms(color.rgb)));
+    vec3 lost = color.rgb - seen;
+    vec3 shift = vec3(0.0, 0.7 * lost.r + lost.g, 0.7 * lost.r + lost.b);
+    finalColor = vec4(clamp(color.rgb + shift, 0.0, 1.0), color.a);
+#endif
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_strings_round_trip() {
+        for mode in ColorblindMode::ALL {
+            assert_eq!(mode.as_str().parse(), Ok(mode));
+        }
+        assert_eq!("sepia".parse::<ColorblindMode>(), Err(()));
+        assert_eq!(
+            ColorblindMode::from_opt_str_or_warn(Some("sepia"), "test"),
+            ColorblindMode::None
+        );
+    }
+
+    #[test]
+    fn only_none_has_no_shader() {
+        for mode in ColorblindMode::ALL {
+            assert_eq!(mode.shader_key().is_some(), mode != ColorblindMode::None);
+            assert_eq!(
+                mode.fragment_shader().is_some(),
+                mode != ColorblindMode::None
+            );
+        }
+        let source = ColorblindMode::Deuteranopia.fragment_shader().unwrap();
+        assert!(source.starts_with("#version 330\n#define CB_MODE 2\n"));
+    }
+
+    #[test]
+    fn high_contrast_text_replaces_dark_colors() {
+        assert_eq!(
+            high_contrast_text_color(Color::DARKBLUE),
+            HIGH_CONTRAST_TEXT
+        );
+        assert_eq!(
+            high_contrast_text_color(Color::new(255, 255, 0, 128)),
+            Color::new(255, 255, 0, 255)
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Alpha masks for pixel-perfect collision.
+//!
+//! An [`AlphaMask`] is a one-bit-per-texel copy of a texture's alpha channel:
+//! a texel is solid when its alpha is at least [`ALPHA_THRESHOLD`]. Masks are
+//! built once, when the texture is loaded, and kept in [`AlphaMasks`] under
+//! the texture's key. The
+//! [`collision_detector`](crate::systems::collision_detector::collision_detector)
+//! tests overlapping colliders of
+//! [`PixelCollision`](crate::components::pixelcollision::PixelCollision)
+//! entities against them.
+//!
+//! Textures loaded with `engine.load_texture` get a mask automatically. Rust
+//! games loading textures themselves call [`AlphaMasks::insert_image`] with
+//! the same key. A mask costs one bit per texel, 1/32 of the texture's RGBA
+//! size.
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Image;
+use rustc_hash::FxHashMap;
+
+/// Minimum alpha of a solid texel.
+pub const ALPHA_THRESHOLD: u8 = 128;
+
+/// Solid/transparent bit per texel of a texture, row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlphaMask {
+    width: u32,
+    height: u32,
+    bits: Vec<u64>,
+}
+
+impl AlphaMask {
+    /// Build a mask from the row-major alpha values of a `width`×`height`
+    /// texture. Missing values count as transparent.
+    pub fn from_alpha(width: u32, height: u32, alpha: impl IntoIterator<Item = u8>) -> Self {
+        let len = width as usize * height as usize;
+        let mut bits = vec![0u64; len.div_ceil(64)];
+        for (i, a) in alpha.into_iter().take(len).enumerate() {
+            if a >= ALPHA_THRESHOLD {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        Self {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    /// Build a mask from the pixels of a CPU-side image.
+    pub fn from_image(image: &Image) -> Self {
+        let colors = image.get_image_data();
+        Self::from_alpha(
+            image.width().max(0) as u32,
+            image.height().max(0) as u32,
+            colors.iter().map(|c| c.a),
+        )
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// `true` if the texel at (`x`, `y`) is solid. Texels outside the
+    /// texture are transparent.
+    pub fn is_solid(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return false;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Mark the texel at (`x`, `y`) solid or transparent. Texels outside the
+    /// texture are ignored.
+    pub fn set_solid(&mut self, x: i32, y: i32, solid: bool) {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        if solid {
+            self.bits[i / 64] |= 1 << (i % 64);
+        } else {
+            self.bits[i / 64] &= !(1 << (i % 64));
+        }
+    }
+}
+
+/// Alpha masks keyed by texture key. See the module docs.
+#[derive(Resource, Debug, Default)]
+pub struct AlphaMasks {
+    masks: FxHashMap<String, AlphaMask>,
+}
+
+impl AlphaMasks {
+    /// Insert or replace the mask of texture `key`.
+    pub fn insert(&mut self, key: impl Into<String>, mask: AlphaMask) {
+        self.masks.insert(key.into(), mask);
+    }
+
+    /// Build and store the mask of texture `key` from its source image.
+    pub fn insert_image(&mut self, key: impl Into<String>, image: &Image) {
+        self.insert(key, AlphaMask::from_image(image));
+    }
+
+    /// Mask of texture `key`, if one was built.
+    pub fn get(&self, key: &str) -> Option<&AlphaMask> {
+        self.masks.get(key)
+    }
+
+    /// Mutable mask of texture `key`, for surfaces edited at runtime.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut AlphaMask> {
+        self.masks.get_mut(key)
+    }
+
+    /// Remove the mask of texture `key`, returning it if it existed.
+    pub fn remove(&mut self, key: &str) -> Option<AlphaMask> {
+        self.masks.remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_splits_solid_and_transparent() {
+        let mask = AlphaMask::from_alpha(2, 2, [0, 127, 128, 255]);
+        assert!(!mask.is_solid(0, 0));
+        assert!(!mask.is_solid(1, 0));
+        assert!(mask.is_solid(0, 1));
+        assert!(mask.is_solid(1, 1));
+    }
+
+    #[test]
+    fn outside_texels_are_transparent() {
+        let mask = AlphaMask::from_alpha(1, 1, [255]);
+        assert!(mask.is_solid(0, 0));
+        assert!(!mask.is_solid(-1, 0));
+        assert!(!mask.is_solid(1, 0));
+        assert!(!mask.is_solid(0, 1));
+    }
+
+    #[test]
+    fn masks_span_word_boundaries() {
+        let alpha = (0..100).map(|i| if i == 70 { 255 } else { 0 });
+        let mask = AlphaMask::from_alpha(10, 10, alpha);
+        assert!(mask.is_solid(0, 7));
+        assert!(!mask.is_solid(9, 6));
+        assert!(!mask.is_solid(1, 7));
+    }
+
+    #[test]
+    fn set_solid_edits_single_texels() {
+        let mut mask = AlphaMask::from_alpha(2, 1, [255, 255]);
+        mask.set_solid(0, 0, false);
+        mask.set_solid(5, 0, false);
+        assert!(!mask.is_solid(0, 0));
+        assert!(mask.is_solid(1, 0));
+        mask.set_solid(0, 0, true);
+        assert!(mask.is_solid(0, 0));
+    }
+}
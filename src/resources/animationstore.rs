@@ -4,12 +4,12 @@
 //! reused by multiple entities. Systems can look up an animation by a string
 //! key and drive playback based on the immutable parameters stored here.
 
-use std::sync::Arc;
-
 use bevy_ecs::prelude::Resource;
 use raylib::prelude::Vector2;
 use rustc_hash::FxHashMap;
 
+use crate::key::Key;
+
 /// Central registry of reusable animation definitions keyed by string IDs.
 #[derive(Resource, Default)]
 pub struct AnimationStore {
@@ -31,7 +31,7 @@ impl AnimationStore {
 #[derive(Debug, Clone, PartialEq)]
 pub struct AnimationResource {
     /// Texture key in [`crate::resources::texturestore::TextureStore`].
-    pub tex_key: Arc<str>,
+    pub tex_key: Key,
     /// Pixel origin within the texture where frame 0 starts (texture-space, not world/screen).
     pub position: Vector2,
     /// Per-frame horizontal displacement (also the frame width, as frames are packed with no gaps).
@@ -0,0 +1,393 @@
+//! Asset usage introspection.
+//!
+//! Scenes that load a new level texture on every visit and never unload the
+//! old one leak GPU memory slowly enough that nobody notices until a long play
+//! session. [`AssetUsage`] answers "what is loaded, how big is it and is
+//! anything still using it" for the [`TextureStore`], the [`FontStore`] and the
+//! audio thread:
+//!
+//! - **Memory estimate**: texture and font atlas sizes are computed from their
+//!   dimensions, pixel format and mip levels, sound effects from their decoded
+//!   sample count, and streamed music from the size of its source file.
+//! - **References**: how many entities name the texture in their `Sprite` or
+//!   the font in their `DynamicText`. Audio has no entity references.
+//! - **Last used**: the last frame an entity referenced the texture or font,
+//!   or the last frame a sound or track was played.
+//!
+//! Entity references are sampled by
+//! [`asset_usage_system`](crate::systems::asset_usage::asset_usage_system)
+//! every [`AssetUsage::SAMPLE_FRAMES`] frames, so "last used" is accurate to
+//! that interval. Audio is tracked from the [`AudioCmd`](crate::events::audio::AudioCmd)
+//! and [`AudioMessage`](crate::events::audio::AudioMessage) streams by
+//! [`track_audio_usage_system`](crate::systems::asset_usage::track_audio_usage_system).
+//!
+//! [`AssetUsage::report`] builds an [`AssetUsageReport`], which the debug
+//! overlay shows in its "Asset Usage" window and `engine.dump_asset_usage()`
+//! writes to the log.
+
+use std::fmt;
+
+use bevy_ecs::prelude::Resource;
+use raylib::ffi;
+use rustc_hash::FxHashMap;
+
+use crate::resources::fontstore::FontStore;
+use crate::resources::missingassets::AssetKind;
+use crate::resources::texturestore::TextureStore;
+
+/// Entity references and last use of one texture or font.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct EntityUsage {
+    references: usize,
+    last_used: Option<u64>,
+}
+
+/// Size and last playback of one sound effect or music track.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct AudioUsage {
+    bytes: usize,
+    last_used: Option<u64>,
+}
+
+/// Usage bookkeeping for loaded assets. See the module docs.
+#[derive(Resource, Debug, Default)]
+pub struct AssetUsage {
+    textures: FxHashMap<String, EntityUsage>,
+    fonts: FxHashMap<String, EntityUsage>,
+    audio: FxHashMap<(AssetKind, String), AudioUsage>,
+    last_sample: Option<u64>,
+    dump_requested: bool,
+}
+
+impl AssetUsage {
+    /// Frames between two samples of entity references.
+    pub const SAMPLE_FRAMES: u64 = 30;
+
+    /// Ask for the report to be logged on the next sample.
+    pub fn request_dump(&mut self) {
+        self.dump_requested = true;
+    }
+
+    /// Take a pending dump request.
+    pub fn take_dump_request(&mut self) -> bool {
+        std::mem::take(&mut self.dump_requested)
+    }
+
+    /// `true` if entity references are due to be sampled at `frame`.
+    pub fn sample_due(&self, frame: u64) -> bool {
+        self.dump_requested
+            || self
+                .last_sample
+                .is_none_or(|last| frame.saturating_sub(last) >= Self::SAMPLE_FRAMES)
+    }
+
+    /// Replace the reference counts with the keys entities name at `frame`.
+    ///
+    /// Every referenced key gets `frame` as its last use; keys no entity names
+    /// keep their previous last use with zero references.
+    pub fn record_references<'a>(
+        &mut self,
+        frame: u64,
+        textures: impl IntoIterator<Item = &'a str>,
+        fonts: impl IntoIterator<Item = &'a str>,
+    ) {
+        count_references(&mut self.textures, frame, textures);
+        count_references(&mut self.fonts, frame, fonts);
+        self.last_sample = Some(frame);
+    }
+
+    /// Drop bookkeeping for textures and fonts no longer in their stores.
+    pub fn retain_loaded(&mut self, textures: &TextureStore, fonts: &FontStore) {
+        self.textures.retain(|key, _| textures.get(key).is_some());
+        self.fonts.retain(|key, _| fonts.get(key).is_some());
+    }
+
+    /// Record that the audio thread loaded `id` holding `bytes`.
+    pub fn audio_loaded(&mut self, kind: AssetKind, id: &str, bytes: usize) {
+        let usage = self.audio.entry((kind, id.to_string())).or_default();
+        usage.bytes = bytes;
+    }
+
+    /// Record that the audio thread unloaded `id`.
+    pub fn audio_unloaded(&mut self, kind: AssetKind, id: &str) {
+        self.audio.remove(&(kind, id.to_string()));
+    }
+
+    /// Record that the audio thread unloaded every asset of `kind`.
+    pub fn audio_unloaded_all(&mut self, kind: AssetKind) {
+        self.audio.retain(|(k, _), _| *k != kind);
+    }
+
+    /// Record that `id` was played at `frame`. Ids that were never reported
+    /// as loaded are ignored.
+    pub fn audio_played(&mut self, kind: AssetKind, id: &str, frame: u64) {
+        if let Some(usage) = self.audio.get_mut(&(kind, id.to_string())) {
+            usage.last_used = Some(frame);
+        }
+    }
+
+    /// Build a report of every loaded asset, largest first within each kind.
+    pub fn report(&self, textures: &TextureStore, fonts: &FontStore) -> AssetUsageReport {
+        let entity_row = |kind, key: &str, bytes, usage: Option<&EntityUsage>| AssetUsageRow {
+            kind,
+            key: key.to_string(),
+            bytes,
+            references: Some(usage.map_or(0, |u| u.references)),
+            last_used_frame: usage.and_then(|u| u.last_used),
+        };
+        let mut rows: Vec<AssetUsageRow> = textures
+            .map
+            .iter()
+            .map(|(key, texture)| {
+                entity_row(
+                    AssetKind::Texture,
+                    key,
+                    texture_bytes(texture),
+                    self.textures.get(key),
+                )
+            })
+            .chain(fonts.iter().map(|(key, font)| {
+                entity_row(AssetKind::Font, key, font_bytes(font), self.fonts.get(key))
+            }))
+            .chain(self.audio.iter().map(|((kind, key), usage)| AssetUsageRow {
+                kind: *kind,
+                key: key.clone(),
+                bytes: usage.bytes,
+                references: None,
+                last_used_frame: usage.last_used,
+            }))
+            .collect();
+        rows.sort_by(|a, b| {
+            kind_order(a.kind)
+                .cmp(&kind_order(b.kind))
+                .then(b.bytes.cmp(&a.bytes))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        AssetUsageReport { rows }
+    }
+}
+
+fn count_references<'a>(
+    map: &mut FxHashMap<String, EntityUsage>,
+    frame: u64,
+    keys: impl IntoIterator<Item = &'a str>,
+) {
+    map.values_mut().for_each(|usage| usage.references = 0);
+    for key in keys {
+        let usage = map.entry(key.to_string()).or_default();
+        usage.references += 1;
+        usage.last_used = Some(frame);
+    }
+}
+
+fn kind_order(kind: AssetKind) -> u8 {
+    match kind {
+        AssetKind::Texture => 0,
+        AssetKind::Font => 1,
+        AssetKind::Sound => 2,
+        AssetKind::Music => 3,
+    }
+}
+
+/// Estimated GPU memory of `texture` in bytes, including its mip chain.
+pub fn texture_bytes(texture: &ffi::Texture2D) -> usize {
+    let (mut width, mut height) = (texture.width, texture.height);
+    let mut total = 0;
+    for _ in 0..texture.mipmaps.max(1) {
+        // SAFETY: GetPixelDataSize is a pure size computation.
+        total += unsafe { ffi::GetPixelDataSize(width, height, texture.format) }.max(0) as usize;
+        width = (width / 2).max(1);
+        height = (height / 2).max(1);
+    }
+    total
+}
+
+/// Estimated memory of `font` in bytes: its atlas texture plus the CPU-side
+/// glyph images.
+pub fn font_bytes(font: &ffi::Font) -> usize {
+    let glyphs = if font.glyphs.is_null() || font.glyphCount <= 0 {
+        &[][..]
+    } else {
+        // SAFETY: raylib allocates `glyphCount` glyphs for a loaded font.
+        unsafe { std::slice::from_raw_parts(font.glyphs, font.glyphCount as usize) }
+    };
+    let glyph_bytes: usize = glyphs
+        .iter()
+        .map(|glyph| {
+            let image = &glyph.image;
+            // SAFETY: GetPixelDataSize is a pure size computation.
+            unsafe { ffi::GetPixelDataSize(image.width, image.height, image.format) }.max(0)
+                as usize
+        })
+        .sum();
+    texture_bytes(&font.texture) + glyph_bytes
+}
+
+/// Format `bytes` with a binary unit, e.g. `"1.5 MiB"`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// One loaded asset in an [`AssetUsageReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetUsageRow {
+    pub kind: AssetKind,
+    pub key: String,
+    /// Estimated memory in bytes.
+    pub bytes: usize,
+    /// Entities referencing the asset at the last sample; `None` for audio.
+    pub references: Option<usize>,
+    /// Last frame the asset was referenced or played, if ever.
+    pub last_used_frame: Option<u64>,
+}
+
+/// Snapshot of every loaded asset, grouped by kind and largest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetUsageReport {
+    pub rows: Vec<AssetUsageRow>,
+}
+
+impl AssetUsageReport {
+    /// Sum of all estimated sizes in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.rows.iter().map(|row| row.bytes).sum()
+    }
+}
+
+impl fmt::Display for AssetUsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Asset usage: {} assets, {} estimated",
+            self.rows.len(),
+            format_bytes(self.total_bytes())
+        )?;
+        write!(
+            f,
+            "  {:<8} {:>10} {:>5} {:>10}  key",
+            "kind", "memory", "refs", "last used"
+        )?;
+        for row in &self.rows {
+            let refs = row.references.map_or("-".to_string(), |r| r.to_string());
+            let last = row
+                .last_used_frame
+                .map_or("never".to_string(), |frame| frame.to_string());
+            write!(
+                f,
+                "\n  {:<8} {:>10} {:>5} {:>10}  {}",
+                row.kind.label(),
+                format_bytes(row.bytes),
+                refs,
+                last,
+                row.key
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_references_counts_keys_and_keeps_last_use() {
+        let mut usage = AssetUsage::default();
+        usage.record_references(10, ["hero", "hero", "bg"], ["title"]);
+        usage.record_references(40, ["hero"], []);
+
+        assert_eq!(
+            usage.textures["hero"],
+            EntityUsage {
+                references: 1,
+                last_used: Some(40)
+            }
+        );
+        assert_eq!(
+            usage.textures["bg"],
+            EntityUsage {
+                references: 0,
+                last_used: Some(10)
+            }
+        );
+        assert_eq!(usage.fonts["title"].references, 0);
+        assert!(!usage.sample_due(69));
+        assert!(usage.sample_due(70));
+    }
+
+    #[test]
+    fn audio_rows_follow_load_play_and_unload() {
+        let mut usage = AssetUsage::default();
+        usage.audio_loaded(AssetKind::Sound, "jump", 2048);
+        usage.audio_loaded(AssetKind::Music, "level1", 4096);
+        usage.audio_played(AssetKind::Sound, "jump", 7);
+        usage.audio_played(AssetKind::Sound, "never_loaded", 7);
+
+        let report = usage.report(&TextureStore::new(), &FontStore::new());
+        assert_eq!(
+            report.rows,
+            vec![
+                AssetUsageRow {
+                    kind: AssetKind::Sound,
+                    key: "jump".to_string(),
+                    bytes: 2048,
+                    references: None,
+                    last_used_frame: Some(7),
+                },
+                AssetUsageRow {
+                    kind: AssetKind::Music,
+                    key: "level1".to_string(),
+                    bytes: 4096,
+                    references: None,
+                    last_used_frame: None,
+                },
+            ]
+        );
+        assert_eq!(report.total_bytes(), 6144);
+
+        usage.audio_unloaded_all(AssetKind::Music);
+        usage.audio_unloaded(AssetKind::Sound, "jump");
+        assert!(
+            usage
+                .report(&TextureStore::new(), &FontStore::new())
+                .rows
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn texture_bytes_includes_mip_levels() {
+        let texture = ffi::Texture2D {
+            id: 0,
+            width: 4,
+            height: 4,
+            mipmaps: 3,
+            format: ffi::PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8 as i32,
+        };
+        assert_eq!(texture_bytes(&texture), 64 + 16 + 4);
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn dump_request_forces_a_sample() {
+        let mut usage = AssetUsage::default();
+        usage.record_references(0, [], []);
+        assert!(!usage.sample_due(1));
+        usage.request_dump();
+        assert!(usage.sample_due(1));
+        assert!(usage.take_dump_request());
+        assert!(!usage.take_dump_request());
+    }
+}
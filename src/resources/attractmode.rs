@@ -0,0 +1,213 @@
+//! Attract mode: a demo that plays by itself while the menu sits idle.
+//!
+//! With an [`AttractMode`] resource holding an [`InputReplay`], the
+//! [`attract_mode_system`](crate::systems::attractmode::attract_mode_system)
+//! counts the seconds without input in the menu scene. Once `idle_seconds`
+//! pass it switches to the replay's scene and feeds the recorded actions into
+//! [`InputState`] frame by frame; any real input, or the end of the
+//! recording, switches back to the menu.
+//!
+//! A replay is a JSON file naming the scene and the actions held on each
+//! frame:
+//!
+//! ```json
+//! { "scene": "level1", "frames": [[], ["main_right"], ["main_right", "action_1"]] }
+//! ```
+//!
+//! Without `frames` the scene is a scripted demo: it plays until the player
+//! touches a key. Playback only matches the recorded game when the demo scene
+//! is deterministic (same spawns and random seed every time).
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Record in the game scene...
+//! replay.record_frame(&input);
+//! // ...save when done, then in the menu:
+//! let replay = InputReplay::load("./assets/demo.json")?;
+//! commands.insert_resource(AttractMode::new(replay, 20.0, "menu"));
+//! ```
+
+use std::path::Path;
+
+use bevy_ecs::prelude::Resource;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::events::input::InputAction;
+use crate::resources::input::{BoolState, InputState};
+
+/// Recorded input: the actions held on each frame of a scene.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputReplay {
+    /// Scene the replay was recorded in.
+    pub scene: String,
+    /// Actions held on each frame.
+    pub frames: Vec<Vec<InputAction>>,
+}
+
+/// On-disk form of an [`InputReplay`], with actions by name.
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    scene: String,
+    #[serde(default)]
+    frames: Vec<Vec<String>>,
+}
+
+impl InputReplay {
+    pub fn new(scene: impl Into<String>) -> Self {
+        Self {
+            scene: scene.into(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Reads a replay file. Unknown action names are skipped with a warning.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read replay {}: {e}", path.display()))?;
+        let file: ReplayFile = serde_json::from_str(&text)
+            .map_err(|e| format!("Failed to parse replay {}: {e}", path.display()))?;
+        let frames = file
+            .frames
+            .into_iter()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        let action = InputAction::from_name(name);
+                        if action.is_none() {
+                            warn!("Replay {}: unknown action '{name}'", path.display());
+                        }
+                        action
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            scene: file.scene,
+            frames,
+        })
+    }
+
+    /// Writes the replay to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let file = ReplayFile {
+            scene: self.scene.clone(),
+            frames: self
+                .frames
+                .iter()
+                .map(|held| held.iter().map(|a| a.name().to_string()).collect())
+                .collect(),
+        };
+        std::fs::write(path, serde_json::to_string(&file)?)?;
+        Ok(())
+    }
+
+    /// Appends the actions held in `input`. Debug and fullscreen toggles are
+    /// not recorded.
+    pub fn record_frame(&mut self, input: &InputState) {
+        self.frames.push(
+            InputAction::ALL
+                .into_iter()
+                .filter(|&action| {
+                    !matches!(
+                        action,
+                        InputAction::ToggleDebug | InputAction::ToggleFullscreen
+                    ) && input.action(action).active
+                })
+                .collect(),
+        );
+    }
+
+    /// Overwrites every action in `input` with frame `index`, deriving the
+    /// press and release edges from the frame before it.
+    pub fn apply_frame(&self, index: usize, input: &mut InputState) {
+        let held = |i: usize, action| self.frames.get(i).is_some_and(|f| f.contains(&action));
+        for action in InputAction::ALL {
+            let now = held(index, action);
+            let before = index > 0 && held(index - 1, action);
+            *input.action_mut(action) = BoolState {
+                active: now,
+                just_pressed: now && !before,
+                just_released: !now && before,
+            };
+        }
+    }
+}
+
+/// Idle timer and playback state of the attract mode.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AttractMode {
+    /// Demo to play; `None` turns attract mode off.
+    pub replay: Option<InputReplay>,
+    /// Seconds without input in `home_scene` before the demo starts.
+    pub idle_seconds: f32,
+    /// Scene the idle timer runs in and the demo returns to.
+    pub home_scene: String,
+    /// Seconds without input so far.
+    pub idle: f32,
+    /// Next replay frame while the demo plays.
+    pub(crate) frame: Option<usize>,
+}
+
+impl AttractMode {
+    pub fn new(replay: InputReplay, idle_seconds: f32, home_scene: impl Into<String>) -> Self {
+        Self {
+            replay: Some(replay),
+            idle_seconds,
+            home_scene: home_scene.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the demo is playing.
+    pub fn is_playing(&self) -> bool {
+        self.frame.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_frame_derives_edges() {
+        let replay = InputReplay {
+            scene: "level1".into(),
+            frames: vec![
+                vec![InputAction::Action1],
+                vec![InputAction::Action1, InputAction::MainDirectionLeft],
+                vec![],
+            ],
+        };
+        let mut input = InputState::default();
+
+        replay.apply_frame(0, &mut input);
+        assert!(input.action_1.active && input.action_1.just_pressed);
+
+        replay.apply_frame(1, &mut input);
+        assert!(input.action_1.active && !input.action_1.just_pressed);
+        assert!(input.maindirection_left.just_pressed);
+
+        replay.apply_frame(2, &mut input);
+        assert!(!input.action_1.active && input.action_1.just_released);
+        assert!(input.maindirection_left.just_released);
+    }
+
+    #[test]
+    fn recorded_replay_round_trips_through_a_file() {
+        let mut input = InputState::default();
+        let mut replay = InputReplay::new("level1");
+        input.action_2.active = true;
+        input.mode_debug.active = true;
+        replay.record_frame(&input);
+        replay.record_frame(&InputState::default());
+        assert_eq!(replay.frames, [vec![InputAction::Action2], vec![]]);
+
+        let path = std::env::temp_dir().join("attract_replay_round_trip_test.json");
+        replay.save(&path).unwrap();
+        assert_eq!(InputReplay::load(&path).unwrap(), replay);
+    }
+}
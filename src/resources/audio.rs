@@ -5,7 +5,7 @@
 //! [`shutdown_audio`] during teardown to gracefully stop the thread and free
 //! audio resources.
 
-use crate::events::audio::{AudioCmd, AudioMessage};
+use crate::events::audio::{AudioCmd, AudioMessage, BeatEvent, TrackerEvent};
 use crate::systems::audio::audio_thread;
 use bevy_ecs::prelude::*;
 use crossbeam_channel::{Receiver, Sender, unbounded};
@@ -29,8 +29,9 @@ pub struct AudioBridge {
 /// This function:
 /// - Creates command/event channels.
 /// - Spawns the background thread running [`audio_thread`].
-/// - Inserts [`AudioBridge`] and initializes `Messages<AudioMessage>` so that
-///   systems can send commands and poll for events.
+/// - Inserts [`AudioBridge`] and initializes `Messages<AudioMessage>` (plus the
+///   derived `Messages<BeatEvent>`/`Messages<TrackerEvent>`) so that systems can
+///   send commands and poll for events.
 pub fn setup_audio(world: &mut World) {
     let (tx_cmd, rx_cmd) = unbounded::<AudioCmd>();
     let (tx_msg, rx_msg) = unbounded::<AudioMessage>();
@@ -44,6 +45,8 @@ pub fn setup_audio(world: &mut World) {
     });
     world.insert_resource(Messages::<AudioMessage>::default());
     world.insert_resource(Messages::<AudioCmd>::default());
+    world.insert_resource(Messages::<BeatEvent>::default());
+    world.insert_resource(Messages::<TrackerEvent>::default());
 }
 
 /// Gracefully request shutdown of the audio thread and join it.
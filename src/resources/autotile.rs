@@ -0,0 +1,317 @@
+//! Auto-tiling rules for runtime tilemap edits.
+//!
+//! When a tile is placed in or cleared from a tilemap cell at runtime (see
+//! [`SetTileMapTile`](crate::systems::tilemap::SetTileMapTile)), the edited
+//! cell and its eight neighbours are re-resolved against the
+//! [`AutoTileRules`] registered for the map's tileset, so destructible terrain
+//! keeps clean edges instead of leaving a hole next to mismatched tiles.
+//!
+//! Two schemes are supported:
+//!
+//! - [`AutoTileMode::Bitmask16`]: the four edge neighbours form a 4-bit mask
+//!   (`N = 1`, `E = 2`, `S = 4`, `W = 8`), one tile per mask value.
+//! - [`AutoTileMode::Blob47`]: all eight neighbours form an 8-bit mask
+//!   (`NW = 1`, `N = 2`, `NE = 4`, `W = 8`, `E = 16`, `SW = 32`, `S = 64`,
+//!   `SE = 128`). A corner bit only counts when both edges next to it are
+//!   set, which leaves the 47 masks of the classic "blob" tileset.
+//!
+//! A neighbour is set when it holds one of the rule set's member tiles (every
+//! tile id listed in `tiles` plus any extra `members`). Cells outside the map
+//! count as set unless `border_filled` is off.
+//!
+//! Rules are registered per tileset (the tilemap directory stem) in the
+//! [`AutoTileStore`] resource: from Lua with `engine.tilemap_set_autotile()`,
+//! or from a `<stem>.autotile.json` file next to the tilemap, loaded when the
+//! tilemap is spawned. The JSON form mirrors the Lua table:
+//!
+//! ```json
+//! { "mode": "bitmask16", "tiles": { "0": 12, "1": 13, "15": 27 }, "layers": ["ground"] }
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Resource;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+
+/// Neighbour scheme of an [`AutoTileRules`] set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoTileMode {
+    /// 4 edge neighbours, 16 tiles.
+    Bitmask16,
+    /// 8 neighbours with corners masked by their edges, 47 tiles.
+    Blob47,
+}
+
+impl AutoTileMode {
+    /// Parse `"bitmask16"` or `"blob47"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "bitmask16" => Some(AutoTileMode::Bitmask16),
+            "blob47" => Some(AutoTileMode::Blob47),
+            _ => None,
+        }
+    }
+}
+
+/// Drop corner bits of an 8-neighbour mask whose adjacent edges are not both set.
+pub fn blob_reduce(mask: u8) -> u8 {
+    const CORNERS: [(u8, u8, u8); 4] = [
+        (1, 2, 8),     // NW needs N + W
+        (4, 2, 16),    // NE needs N + E
+        (32, 64, 8),   // SW needs S + W
+        (128, 64, 16), // SE needs S + E
+    ];
+    CORNERS
+        .iter()
+        .filter(|&&(corner, a, b)| mask & corner != 0 && (mask & a == 0 || mask & b == 0))
+        .fold(mask, |m, &(corner, _, _)| m & !corner)
+}
+
+/// Auto-tiling rules of one tileset. See the module docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoTileRules {
+    pub mode: AutoTileMode,
+    /// Tile id to place for each neighbour mask.
+    pub tiles: FxHashMap<u8, u32>,
+    /// Tile ids that count as this terrain.
+    members: FxHashSet<u32>,
+    /// Layer names the rules apply to; `None` applies them to every layer.
+    pub layers: Option<Vec<String>>,
+    /// Whether cells outside the map count as terrain.
+    pub border_filled: bool,
+}
+
+impl AutoTileRules {
+    /// Create rules mapping each neighbour mask in `tiles` to a tile id.
+    ///
+    /// Fails if a mask cannot occur in `mode` (16 or more for
+    /// [`Bitmask16`](AutoTileMode::Bitmask16), or a corner bit without its two
+    /// edges for [`Blob47`](AutoTileMode::Blob47)).
+    pub fn new(mode: AutoTileMode, tiles: FxHashMap<u8, u32>) -> Result<Self, String> {
+        if let Some(mask) = tiles.keys().find(|&&mask| match mode {
+            AutoTileMode::Bitmask16 => mask >= 16,
+            AutoTileMode::Blob47 => blob_reduce(mask) != mask,
+        }) {
+            return Err(format!("mask {mask} cannot occur in {mode:?} rules"));
+        }
+        Ok(Self {
+            mode,
+            members: tiles.values().copied().collect(),
+            tiles,
+            layers: None,
+            border_filled: true,
+        })
+    }
+
+    /// Also treat `ids` as terrain (e.g. decorated variants not in `tiles`).
+    pub fn with_members(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.members.extend(ids);
+        self
+    }
+
+    /// Limit the rules to the named layers.
+    pub fn with_layers(mut self, layers: Vec<String>) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Set whether cells outside the map count as terrain.
+    pub fn with_border_filled(mut self, border_filled: bool) -> Self {
+        self.border_filled = border_filled;
+        self
+    }
+
+    /// Parse rules from their JSON form (see the module docs).
+    pub fn from_json_str(json: &str) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        struct Json {
+            mode: AutoTileMode,
+            tiles: FxHashMap<String, u32>,
+            #[serde(default)]
+            members: Vec<u32>,
+            #[serde(default)]
+            layers: Option<Vec<String>>,
+            #[serde(default = "default_border_filled")]
+            border_filled: bool,
+        }
+        fn default_border_filled() -> bool {
+            true
+        }
+
+        let json: Json = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let tiles = json
+            .tiles
+            .into_iter()
+            .map(|(mask, id)| {
+                mask.parse::<u8>()
+                    .map(|mask| (mask, id))
+                    .map_err(|_| format!("tile mask '{mask}' is not a number from 0 to 255"))
+            })
+            .collect::<Result<_, _>>()?;
+        let mut rules = Self::new(json.mode, tiles)?
+            .with_members(json.members)
+            .with_border_filled(json.border_filled);
+        rules.layers = json.layers;
+        Ok(rules)
+    }
+
+    /// `true` if tile `id` counts as this terrain.
+    pub fn is_member(&self, id: u32) -> bool {
+        self.members.contains(&id)
+    }
+
+    /// `true` if the rules apply to the layer called `layer`.
+    pub fn applies_to(&self, layer: &str) -> bool {
+        self.layers
+            .as_ref()
+            .is_none_or(|layers| layers.iter().any(|l| l == layer))
+    }
+
+    /// Neighbour mask of cell `(x, y)` in a `width`×`height` map, where
+    /// `tile_at` returns the tile id in a cell, if any.
+    pub fn mask(
+        &self,
+        width: u32,
+        height: u32,
+        x: u32,
+        y: u32,
+        tile_at: impl Fn(u32, u32) -> Option<u32>,
+    ) -> u8 {
+        let set = |dx: i64, dy: i64| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+                return self.border_filled;
+            }
+            tile_at(nx as u32, ny as u32).is_some_and(|id| self.is_member(id))
+        };
+        let bit = |on: bool, value: u8| if on { value } else { 0 };
+        match self.mode {
+            AutoTileMode::Bitmask16 => {
+                bit(set(0, -1), 1) | bit(set(1, 0), 2) | bit(set(0, 1), 4) | bit(set(-1, 0), 8)
+            }
+            AutoTileMode::Blob47 => blob_reduce(
+                bit(set(-1, -1), 1)
+                    | bit(set(0, -1), 2)
+                    | bit(set(1, -1), 4)
+                    | bit(set(-1, 0), 8)
+                    | bit(set(1, 0), 16)
+                    | bit(set(-1, 1), 32)
+                    | bit(set(0, 1), 64)
+                    | bit(set(1, 1), 128),
+            ),
+        }
+    }
+
+    /// Tile id for `mask`, if the rules define one.
+    pub fn tile_for(&self, mask: u8) -> Option<u32> {
+        self.tiles.get(&mask).copied()
+    }
+}
+
+/// Auto-tiling rules keyed by tileset name (the tilemap directory stem).
+#[derive(Resource, Debug, Default)]
+pub struct AutoTileStore {
+    rules: FxHashMap<String, Arc<AutoTileRules>>,
+}
+
+impl AutoTileStore {
+    /// Register (or replace) the rules for `tileset`.
+    pub fn insert(&mut self, tileset: impl Into<String>, rules: AutoTileRules) {
+        self.rules.insert(tileset.into(), Arc::new(rules));
+    }
+
+    /// Remove the rules for `tileset`.
+    pub fn remove(&mut self, tileset: &str) -> bool {
+        self.rules.remove(tileset).is_some()
+    }
+
+    /// Rules for `tileset` that apply to `layer`, if any.
+    pub fn rules_for(&self, tileset: &str, layer: &str) -> Option<Arc<AutoTileRules>> {
+        self.rules
+            .get(tileset)
+            .filter(|rules| rules.applies_to(layer))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(mode: AutoTileMode, pairs: &[(u8, u32)]) -> AutoTileRules {
+        AutoTileRules::new(mode, pairs.iter().copied().collect()).unwrap()
+    }
+
+    #[test]
+    fn blob_reduce_drops_corners_without_both_edges() {
+        assert_eq!(blob_reduce(0xFF), 0xFF);
+        // NW set with only N: the corner does not count.
+        assert_eq!(blob_reduce(1 | 2), 2);
+        // NE with N and E stays.
+        assert_eq!(blob_reduce(4 | 2 | 16), 4 | 2 | 16);
+        let distinct: FxHashSet<u8> = (0..=255u8).map(blob_reduce).collect();
+        assert_eq!(distinct.len(), 47);
+    }
+
+    #[test]
+    fn new_rejects_masks_the_mode_cannot_produce() {
+        assert!(
+            AutoTileRules::new(AutoTileMode::Bitmask16, [(16, 1)].into_iter().collect()).is_err()
+        );
+        assert!(AutoTileRules::new(AutoTileMode::Blob47, [(1, 1)].into_iter().collect()).is_err());
+        assert!(AutoTileRules::new(AutoTileMode::Blob47, [(2, 1)].into_iter().collect()).is_ok());
+    }
+
+    #[test]
+    fn bitmask16_mask_reads_edges_and_border() {
+        let r = rules(AutoTileMode::Bitmask16, &[(0, 10), (15, 11)]);
+        // 3x1 map: only the middle cell holds terrain.
+        let tile_at = |x: u32, _y: u32| (x == 1).then_some(10);
+        // Left cell: E neighbour set, N/S/W are outside the map.
+        assert_eq!(r.mask(3, 1, 0, 0, tile_at), 1 | 2 | 4 | 8);
+        let open = r.clone().with_border_filled(false);
+        assert_eq!(open.mask(3, 1, 0, 0, tile_at), 2);
+        assert_eq!(open.mask(3, 1, 1, 0, tile_at), 0);
+    }
+
+    #[test]
+    fn blob47_mask_ignores_lonely_corners() {
+        let r = rules(AutoTileMode::Blob47, &[(0, 1)]).with_border_filled(false);
+        // 3x3 map with terrain only in the top-left corner cell.
+        let tile_at = |x: u32, y: u32| (x == 0 && y == 0).then_some(1);
+        assert_eq!(r.mask(3, 3, 1, 1, tile_at), 0);
+        let tile_at = |x: u32, y: u32| (y == 0 || x == 0).then_some(1);
+        assert_eq!(r.mask(3, 3, 1, 1, tile_at), 1 | 2 | 8);
+    }
+
+    #[test]
+    fn from_json_str_parses_masks_members_and_layers() {
+        let r = AutoTileRules::from_json_str(
+            r#"{"mode": "bitmask16", "tiles": {"0": 5, "15": 6}, "members": [7],
+                "layers": ["ground"], "border_filled": false}"#,
+        )
+        .unwrap();
+        assert_eq!(r.tile_for(15), Some(6));
+        assert!(r.is_member(5) && r.is_member(7) && !r.is_member(8));
+        assert!(r.applies_to("ground") && !r.applies_to("decor"));
+        assert!(!r.border_filled);
+        assert!(
+            AutoTileRules::from_json_str(r#"{"mode": "bitmask16", "tiles": {"x": 1}}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn store_filters_rules_by_layer() {
+        let mut store = AutoTileStore::default();
+        store.insert(
+            "level01",
+            rules(AutoTileMode::Bitmask16, &[(0, 1)]).with_layers(vec!["ground".into()]),
+        );
+        assert!(store.rules_for("level01", "ground").is_some());
+        assert!(store.rules_for("level01", "decor").is_none());
+        assert!(store.rules_for("level02", "ground").is_none());
+        assert!(store.remove("level01"));
+    }
+}
@@ -59,6 +59,18 @@ impl Camera2DRes {
             height: h,
         }
     }
+
+    /// Screen (render-target) position of a world position, matching what
+    /// `begin_mode2D` draws, rotation included.
+    pub fn world_to_screen(&self, world: Vector2) -> Vector2 {
+        let cam = &self.0;
+        let (sin, cos) = cam.rotation.to_radians().sin_cos();
+        let d = (world - cam.target) * cam.zoom;
+        Vector2 {
+            x: d.x * cos - d.y * sin + cam.offset.x,
+            y: d.x * sin + d.y * cos + cam.offset.y,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +173,21 @@ mod tests {
         assert!(r.width.is_finite());
         assert!(r.height.is_finite());
     }
+
+    #[test]
+    fn world_to_screen_applies_zoom_and_rotation() {
+        let mut cam = make_camera(
+            Vector2 { x: 100.0, y: 50.0 },
+            Vector2 { x: 320.0, y: 180.0 },
+            2.0,
+        );
+        let p = cam.world_to_screen(Vector2 { x: 110.0, y: 50.0 });
+        assert!((p.x - 340.0).abs() < 1e-4);
+        assert!((p.y - 180.0).abs() < 1e-4);
+
+        cam.0.rotation = 90.0;
+        let p = cam.world_to_screen(Vector2 { x: 110.0, y: 50.0 });
+        assert!((p.x - 320.0).abs() < 1e-4);
+        assert!((p.y - 200.0).abs() < 1e-4);
+    }
 }
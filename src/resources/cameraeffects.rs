@@ -0,0 +1,270 @@
+//! Camera shake, zoom and pan effects.
+//!
+//! [`CameraEffects`] is layered on top of [`Camera2DRes`] when the world is
+//! drawn: the render pass draws through [`CameraEffects::apply`], while
+//! `Camera2DRes` itself is left alone. Camera follow keeps tracking its
+//! target undisturbed, and `engine.get_camera()`, mouse picking and
+//! world-anchored UI keep using the plain camera.
+//!
+//! - **Shake** is trauma-based: [`CameraEffects::shake`] raises the trauma
+//!   to `intensity` (0–1), which then decays to zero over `duration`. The
+//!   camera jitters by up to [`MAX_SHAKE_OFFSET`] pixels and
+//!   [`MAX_SHAKE_ANGLE`] degrees scaled by trauma squared, so small hits
+//!   barely move it and big ones rattle it.
+//! - **Zoom** multiplies the camera zoom. [`CameraEffects::zoom_to`] eases
+//!   the factor to a new value and keeps it; [`CameraEffects::punch_zoom`]
+//!   adds a quick zoom that eases back out.
+//! - **Pan** blends the view from the camera target to a fixed world point
+//!   ([`CameraEffects::pan_to`]) and back ([`CameraEffects::release_pan`]),
+//!   for showing a door that just opened.
+//!
+//! Effects belong to the scene that started them and are reset on a scene
+//! switch. `camera_effects_system` advances them with the world time.
+//!
+//! [`Camera2DRes`]: crate::resources::camera2d::Camera2DRes
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::{Camera2D, Vector2};
+
+use crate::components::tween::Easing;
+use crate::systems::tween::ease;
+
+/// Largest shake displacement, in screen pixels, at full trauma.
+pub const MAX_SHAKE_OFFSET: f32 = 12.0;
+
+/// Largest shake rotation, in degrees, at full trauma.
+pub const MAX_SHAKE_ANGLE: f32 = 2.0;
+
+/// A value easing from `from` to `to` over `duration` seconds.
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    from: f32,
+    to: f32,
+    elapsed: f32,
+    duration: f32,
+    easing: Easing,
+}
+
+impl Transition {
+    fn settled(value: f32) -> Self {
+        Self {
+            from: value,
+            to: value,
+            elapsed: 0.0,
+            duration: 0.0,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Start easing from the current value to `to`.
+    fn start(&mut self, to: f32, duration: f32, easing: Easing) {
+        *self = Self {
+            from: self.value(),
+            to,
+            elapsed: 0.0,
+            duration: duration.max(0.0),
+            easing,
+        };
+    }
+
+    fn value(&self) -> f32 {
+        if self.is_running() {
+            let t = ease(self.easing, self.elapsed / self.duration);
+            self.from + (self.to - self.from) * t
+        } else {
+            self.to
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    fn advance(&mut self, delta: f32) {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+    }
+}
+
+/// Shake, zoom and pan layered on the camera. See the module docs.
+#[derive(Resource, Debug)]
+pub struct CameraEffects {
+    /// Current shake strength, 0–1.
+    trauma: f32,
+    /// Trauma lost per second.
+    trauma_decay: f32,
+    /// This frame's shake direction and roll, each component in -1..1.
+    jitter: Vector2,
+    roll: f32,
+    zoom: Transition,
+    /// Extra zoom factor of the running punch, eased back to 0.
+    punch: Transition,
+    /// How far the view has moved to `pan_target`, 0–1.
+    pan: Transition,
+    pan_target: Vector2,
+    rng: fastrand::Rng,
+}
+
+impl Default for CameraEffects {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            trauma_decay: 0.0,
+            jitter: Vector2::zero(),
+            roll: 0.0,
+            zoom: Transition::settled(1.0),
+            punch: Transition::settled(0.0),
+            pan: Transition::settled(0.0),
+            pan_target: Vector2::zero(),
+            rng: fastrand::Rng::new(),
+        }
+    }
+}
+
+impl CameraEffects {
+    /// Raise the trauma to `intensity` (0–1), decaying to zero over
+    /// `duration` seconds. A weaker shake never cuts a stronger one short.
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        let intensity = intensity.clamp(0.0, 1.0);
+        if intensity < self.trauma {
+            return;
+        }
+        self.trauma = intensity;
+        self.trauma_decay = if duration > 0.0 {
+            intensity / duration
+        } else {
+            f32::INFINITY
+        };
+    }
+
+    /// Current shake strength, 0–1.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Ease the zoom factor to `zoom` over `duration` seconds and keep it.
+    /// `1.0` restores the camera's own zoom.
+    pub fn zoom_to(&mut self, zoom: f32, duration: f32, easing: Easing) {
+        self.zoom.start(zoom.max(f32::EPSILON), duration, easing);
+    }
+
+    /// Zoom in by `amount` (`0.1` = 10%) at once, easing back over `duration`.
+    pub fn punch_zoom(&mut self, amount: f32, duration: f32) {
+        self.punch = Transition::settled(amount);
+        self.punch.start(0.0, duration, Easing::QuadOut);
+    }
+
+    /// Blend the view to centre on world point `target` over `duration` seconds.
+    pub fn pan_to(&mut self, target: Vector2, duration: f32, easing: Easing) {
+        self.pan_target = target;
+        self.pan.start(1.0, duration, easing);
+    }
+
+    /// Blend the view back to the camera target over `duration` seconds.
+    pub fn release_pan(&mut self, duration: f32, easing: Easing) {
+        self.pan.start(0.0, duration, easing);
+    }
+
+    /// Zoom factor applied on top of the camera zoom, punch included.
+    pub fn zoom_factor(&self) -> f32 {
+        self.zoom.value() * (1.0 + self.punch.value())
+    }
+
+    /// Whether any effect still changes from frame to frame.
+    pub fn is_animating(&self) -> bool {
+        self.trauma > 0.0
+            || self.zoom.is_running()
+            || self.punch.is_running()
+            || self.pan.is_running()
+    }
+
+    /// Whether the effects change the camera at all.
+    pub fn is_active(&self) -> bool {
+        self.is_animating() || self.zoom_factor() != 1.0 || self.pan.value() > 0.0
+    }
+
+    /// Advance every effect `delta` seconds and pick this frame's jitter.
+    pub fn advance(&mut self, delta: f32) {
+        self.trauma = (self.trauma - self.trauma_decay * delta).max(0.0);
+        if self.trauma > 0.0 {
+            let mut unit = || self.rng.f32() * 2.0 - 1.0;
+            self.jitter = Vector2::new(unit(), unit());
+            self.roll = unit();
+        } else {
+            self.jitter = Vector2::zero();
+            self.roll = 0.0;
+        }
+        self.zoom.advance(delta);
+        self.punch.advance(delta);
+        self.pan.advance(delta);
+    }
+
+    /// `camera` with the effects applied.
+    pub fn apply(&self, camera: Camera2D) -> Camera2D {
+        let zoom = (camera.zoom * self.zoom_factor()).max(f32::EPSILON);
+        let shake = self.trauma * self.trauma;
+        let target = camera.target.lerp(self.pan_target, self.pan.value())
+            + self.jitter * (MAX_SHAKE_OFFSET * shake / zoom);
+        Camera2D {
+            target,
+            zoom,
+            rotation: camera.rotation + self.roll * MAX_SHAKE_ANGLE * shake,
+            ..camera
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera2D {
+        Camera2D {
+            target: Vector2::new(100.0, 50.0),
+            offset: Vector2::new(160.0, 120.0),
+            rotation: 0.0,
+            zoom: 2.0,
+        }
+    }
+
+    #[test]
+    fn shake_decays_within_its_bounds() {
+        let mut effects = CameraEffects::default();
+        effects.shake(0.5, 1.0);
+        effects.shake(0.2, 5.0);
+        assert_eq!(effects.trauma(), 0.5);
+
+        effects.advance(0.5);
+        assert!((effects.trauma() - 0.25).abs() < 1e-5);
+        let shaken = effects.apply(camera());
+        let max = MAX_SHAKE_OFFSET * 0.25 * 0.25 / 2.0;
+        assert!((shaken.target.x - 100.0).abs() <= max);
+        assert!(shaken.rotation.abs() <= MAX_SHAKE_ANGLE * 0.25 * 0.25);
+
+        effects.advance(0.5);
+        assert!(!effects.is_active());
+        assert_eq!(effects.apply(camera()).target, camera().target);
+    }
+
+    #[test]
+    fn zoom_and_pan_ease_and_hold() {
+        let mut effects = CameraEffects::default();
+        effects.zoom_to(2.0, 1.0, Easing::Linear);
+        effects.pan_to(Vector2::new(300.0, 50.0), 1.0, Easing::Linear);
+        effects.advance(0.5);
+        let halfway = effects.apply(camera());
+        assert!((halfway.zoom - 3.0).abs() < 1e-5);
+        assert!((halfway.target.x - 200.0).abs() < 1e-3);
+
+        effects.advance(1.0);
+        assert!(!effects.is_animating());
+        assert!(effects.is_active());
+        assert_eq!(effects.apply(camera()).zoom, 4.0);
+
+        effects.punch_zoom(0.5, 0.2);
+        assert_eq!(effects.zoom_factor(), 3.0);
+        effects.release_pan(0.0, Easing::Linear);
+        effects.advance(0.2);
+        assert_eq!(effects.apply(camera()).target, camera().target);
+        assert_eq!(effects.zoom_factor(), 2.0);
+    }
+}
@@ -0,0 +1,146 @@
+//! Cinematic mode for cutscenes.
+//!
+//! While [`CinematicMode`] is active, black bars slide in from the top and
+//! bottom of the screen and the gameplay input actions are cleared from
+//! [`InputState`] every frame, so controllers, menus and Lua see no presses.
+//! `back` is kept so the player can still skip, along with the debug and
+//! fullscreen toggles. Turning it off slides the bars out and gives the input
+//! back straight away.
+//!
+//! The bars are drawn over the world and below screen-space UI, so subtitles
+//! can sit on them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! fn start_cutscene(mut cinematic: ResMut<CinematicMode>) {
+//!     cinematic.set_active(true);
+//! }
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::cinematic`] – bar animation and input suppression
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Color;
+
+use crate::events::input::InputAction;
+use crate::resources::input::InputState;
+
+/// Letterbox bars and gameplay input lock for cutscenes.
+#[derive(Resource, Debug, Clone)]
+pub struct CinematicMode {
+    active: bool,
+    /// How far the bars are in, from 0 to 1.
+    progress: f32,
+    /// Height of each bar as a fraction of the screen height.
+    pub bar_height: f32,
+    /// Seconds the bars take to slide in or out.
+    pub duration: f32,
+    pub color: Color,
+}
+
+impl Default for CinematicMode {
+    fn default() -> Self {
+        Self {
+            active: false,
+            progress: 0.0,
+            bar_height: 0.12,
+            duration: 0.4,
+            color: Color::BLACK,
+        }
+    }
+}
+
+impl CinematicMode {
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// How far the bars are in, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Whether the bars are still sliding.
+    pub fn is_animating(&self) -> bool {
+        self.progress != if self.active { 1.0 } else { 0.0 }
+    }
+
+    /// Slides the bars `delta` seconds towards in or out.
+    pub fn advance(&mut self, delta: f32) {
+        let step = if self.duration > 0.0 {
+            delta / self.duration
+        } else {
+            1.0
+        };
+        self.progress = if self.active {
+            (self.progress + step).min(1.0)
+        } else {
+            (self.progress - step).max(0.0)
+        };
+    }
+
+    /// Current height in pixels of each bar on a screen `screen_h` pixels tall.
+    pub fn bar_pixels(&self, screen_h: f32) -> f32 {
+        let t = self.progress;
+        // Smoothstep so the bars ease in and out.
+        screen_h * self.bar_height * t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Clears every gameplay action in `input`, keeping `back` (skip) and the
+    /// debug and fullscreen toggles.
+    pub fn suppress(input: &mut InputState) {
+        for action in InputAction::ALL {
+            if !matches!(
+                action,
+                InputAction::Back | InputAction::ToggleDebug | InputAction::ToggleFullscreen
+            ) {
+                *input.action_mut(action) = Default::default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_slide_in_and_out() {
+        let mut cinematic = CinematicMode {
+            bar_height: 0.25,
+            ..CinematicMode::default()
+        };
+        assert!(!cinematic.is_animating());
+        cinematic.set_active(true);
+        assert!(cinematic.is_animating());
+        cinematic.advance(0.2);
+        assert_eq!(cinematic.bar_pixels(100.0), 12.5);
+        cinematic.advance(1.0);
+        assert_eq!(cinematic.progress(), 1.0);
+        assert_eq!(cinematic.bar_pixels(100.0), 25.0);
+        assert!(!cinematic.is_animating());
+
+        cinematic.set_active(false);
+        cinematic.advance(1.0);
+        assert_eq!(cinematic.bar_pixels(100.0), 0.0);
+    }
+
+    #[test]
+    fn suppress_keeps_skip() {
+        let mut input = InputState::default();
+        input.action_1.just_pressed = true;
+        input.maindirection_left.active = true;
+        input.action_back.just_pressed = true;
+        CinematicMode::suppress(&mut input);
+        assert!(!input.action_1.just_pressed);
+        assert!(!input.maindirection_left.active);
+        assert!(input.action_back.just_pressed);
+    }
+}
@@ -0,0 +1,185 @@
+//! Color grading lookup table (LUT) selection.
+//!
+//! [`ColorGrading`] names the LUT texture the render system applies as the
+//! last step of the post-process chain, just before the built-in colorblind
+//! remap. A LUT is a 256×16 texture holding a 16×16×16 color cube as 16
+//! slices of 16×16 texels side by side: blue selects the slice, red the
+//! column inside it and green the row. Load it like any other texture, with
+//! a `"bilinear"` filter so colors between the cube's entries interpolate.
+//!
+//! Switching LUTs can crossfade over a number of seconds; the
+//! [`color_grading_system`](crate::systems::colorgrading::color_grading_system)
+//! advances the fade. A new switch during a fade starts from the LUT that
+//! was being faded to. Lua selects LUTs with
+//! `engine.set_color_lut(tex_key, fade_seconds)`; `nil` fades back to the
+//! ungraded image. The selection survives scene switches.
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Resource;
+
+/// [`ShaderStore`](crate::resources::shaderstore::ShaderStore) key of the
+/// built-in LUT shader.
+pub const COLOR_LUT_SHADER_KEY: &str = "__color_lut";
+
+/// Number of entries per axis of the color cube.
+pub const LUT_SIZE: u32 = 16;
+
+/// Crossfade from one LUT to the current one.
+#[derive(Debug, Clone, PartialEq)]
+struct LutFade {
+    from: Option<Arc<str>>,
+    duration: f32,
+    elapsed: f32,
+}
+
+/// Active color grading LUT and the crossfade towards it. See the module
+/// docs.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ColorGrading {
+    current: Option<Arc<str>>,
+    fade: Option<LutFade>,
+}
+
+impl ColorGrading {
+    /// Switch to the LUT texture `key` (`None` disables grading), crossfading
+    /// over `fade_seconds`. Selecting the current LUT again does nothing.
+    pub fn set_lut(&mut self, key: Option<&str>, fade_seconds: f32) {
+        if self.current.as_deref() == key {
+            return;
+        }
+        let from = std::mem::replace(&mut self.current, key.map(Arc::from));
+        self.fade = (fade_seconds > 0.0).then_some(LutFade {
+            from,
+            duration: fade_seconds,
+            elapsed: 0.0,
+        });
+    }
+
+    /// Advance the crossfade by `dt` seconds, ending it once complete.
+    pub fn advance(&mut self, dt: f32) {
+        if let Some(fade) = &mut self.fade {
+            fade.elapsed += dt;
+            if fade.elapsed >= fade.duration {
+                self.fade = None;
+            }
+        }
+    }
+
+    /// Texture key of the LUT being shown or faded to.
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Texture key of the LUT being faded out, if it is not the ungraded
+    /// image.
+    pub fn fading_from(&self) -> Option<&str> {
+        self.fade.as_ref().and_then(|fade| fade.from.as_deref())
+    }
+
+    /// `true` while a crossfade runs.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// Weight of the current LUT against the one being faded out, from 0 at
+    /// the start of a crossfade to 1 when it ends.
+    pub fn mix(&self) -> f32 {
+        self.fade
+            .as_ref()
+            .map_or(1.0, |fade| (fade.elapsed / fade.duration).clamp(0.0, 1.0))
+    }
+
+    /// `true` if the post-process chain needs the LUT pass this frame.
+    pub fn is_active(&self) -> bool {
+        self.current.is_some() || self.fading_from().is_some()
+    }
+}
+
+/// Fragment shader of the LUT pass.
+///
+/// `uLutFrom` and `uLutTo` are sampled only when their `Enabled` flag is set;
+/// a disabled side stands for the ungraded color. `uLutMix` blends from one
+/// to the other.
+pub const COLOR_LUT_FS: &str = r#"#version 330
+in vec2 fragTexCoord;
+in vec4 fragColor;
+
+uniform sampler2D texture0;
+uniform sampler2D uLutFrom;
+uniform sampler2D uLutTo;
+uniform int uLutFromEnabled;
+uniform int uLutToEnabled;
+uniform float uLutMix;
+
+out vec4 finalColor;
+
+vec3 lut_lookup(sampler2D lut, vec3 color) {
+    float slice = color.b * 15.0;
+    float slice0 = floor(slice);
+    float slice1 = min(slice0 + 1.0, 15.0);
+    vec2 texel = color.rg * 15.0 + 0.5;
+    vec3 low = texture(lut, vec2((slice0 * 16.0 + texel.x) / 256.0, texel.y / 16.0)).rgb;
+    vec3 high = texture(lut, vec2((slice1 * 16.0 + texel.x) / 256.0, texel.y / 16.0)).rgb;
+    return mix(low, high, slice - slice0);
+}
+
+void main() {
+    vec4 color = texture(texture0, fragTexCoord);
+    vec3 graded_from = uLutFromEnabled != 0 ? lut_lookup(uLutFrom, color.rgb) : color.rgb;
+    vec3 graded_to = uLutToEnabled != 0 ? lut_lookup(uLutTo, color.rgb) : color.rgb;
+    finalColor = vec4(mix(graded_from, graded_to, uLutMix), color.a);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instant_switch_has_no_fade() {
+        let mut grading = ColorGrading::default();
+        assert!(!grading.is_active());
+
+        grading.set_lut(Some("night"), 0.0);
+        assert_eq!(grading.current(), Some("night"));
+        assert!(!grading.is_fading());
+        assert_eq!(grading.mix(), 1.0);
+        assert!(grading.is_active());
+    }
+
+    #[test]
+    fn crossfade_advances_and_ends() {
+        let mut grading = ColorGrading::default();
+        grading.set_lut(Some("day"), 0.0);
+        grading.set_lut(Some("night"), 2.0);
+        assert_eq!(grading.fading_from(), Some("day"));
+        assert_eq!(grading.mix(), 0.0);
+
+        grading.advance(1.0);
+        assert_eq!(grading.mix(), 0.5);
+        grading.advance(1.0);
+        assert!(!grading.is_fading());
+        assert_eq!(grading.fading_from(), None);
+        assert_eq!(grading.current(), Some("night"));
+    }
+
+    #[test]
+    fn fading_out_keeps_the_pass_until_done() {
+        let mut grading = ColorGrading::default();
+        grading.set_lut(Some("night"), 0.0);
+        grading.set_lut(None, 1.0);
+        assert!(grading.is_active());
+        grading.advance(1.0);
+        assert!(!grading.is_active());
+    }
+
+    #[test]
+    fn reselecting_the_current_lut_keeps_the_fade() {
+        let mut grading = ColorGrading::default();
+        grading.set_lut(Some("night"), 1.0);
+        grading.advance(0.5);
+        grading.set_lut(Some("night"), 1.0);
+        assert_eq!(grading.mix(), 0.5);
+    }
+}
@@ -0,0 +1,129 @@
+//! ECS statistics for spotting archetype churn.
+//!
+//! A spawn loop that gives every entity a slightly different set of
+//! components (a `:with_timer()` on some, a tween on others, a signal
+//! component added later) creates a new archetype for each combination.
+//! Every query has to check each matching archetype, so a scene that grows
+//! thousands of them slows down without a single entity being added.
+//! [`EcsStats`] makes that visible:
+//!
+//! - **Entities** and **archetypes** alive at the end of the last frame.
+//! - **Spawned** and **despawned** entities during the last frame. Despawns
+//!   are derived from the change in entity count, so an entity spawned and
+//!   despawned within the same frame shows up in neither.
+//! - **Component memory** per component type: its size times the number of
+//!   entities holding it, sampled every [`EcsStats::SAMPLE_FRAMES`] frames.
+//!   Heap memory owned by a component (a `Vec`, a `String`) is not counted.
+//!   Component names need the `ecs_names` cargo feature (bevy's `debug`
+//!   feature); without it they are listed by component id.
+//!
+//! Stats are collected by
+//! [`ecs_stats_system`](crate::systems::ecs_stats::ecs_stats_system), which
+//! also logs a warning each time the archetype count doubles past
+//! [`EcsStats::ARCHETYPE_WARN`]. The debug overlay shows them in its "ECS"
+//! window and Lua reads them with `engine.ecs_stats()`.
+
+use bevy_ecs::change_detection::Tick;
+use bevy_ecs::prelude::Resource;
+
+/// Memory held by one component type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStat {
+    pub name: String,
+    /// Entities holding the component.
+    pub entities: usize,
+    /// Component size times `entities`.
+    pub bytes: usize,
+}
+
+/// Entity, archetype and component memory counters. See the module docs.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EcsStats {
+    pub entities: usize,
+    pub archetypes: usize,
+    /// Entities spawned during the last frame.
+    pub spawned: usize,
+    /// Entities despawned during the last frame.
+    pub despawned: usize,
+    /// Largest first.
+    pub components: Vec<ComponentStat>,
+    /// Tick of the previous collection; entities spawned after it are new.
+    pub(crate) last_tick: Option<Tick>,
+    last_sample: Option<u64>,
+    next_warning: usize,
+}
+
+impl EcsStats {
+    /// Frames between two samples of component memory.
+    pub const SAMPLE_FRAMES: u64 = 30;
+    /// Archetype count of the first churn warning.
+    pub const ARCHETYPE_WARN: usize = 256;
+
+    /// Record the counts of the frame that just ended.
+    pub fn record_frame(&mut self, entities: usize, archetypes: usize, spawned: usize) {
+        self.despawned = (self.entities + spawned).saturating_sub(entities);
+        self.entities = entities;
+        self.archetypes = archetypes;
+        self.spawned = spawned;
+    }
+
+    /// `true` if component memory is due to be sampled at `frame`.
+    pub fn sample_due(&self, frame: u64) -> bool {
+        self.last_sample
+            .is_none_or(|last| frame >= last + Self::SAMPLE_FRAMES)
+    }
+
+    /// Replace the component memory sample taken at `frame`.
+    pub fn record_components(&mut self, frame: u64, mut components: Vec<ComponentStat>) {
+        components.sort_unstable_by(|a, b| b.bytes.cmp(&a.bytes).then(a.name.cmp(&b.name)));
+        self.components = components;
+        self.last_sample = Some(frame);
+    }
+
+    /// Sum of the sampled component memory.
+    pub fn component_bytes(&self) -> usize {
+        self.components.iter().map(|c| c.bytes).sum()
+    }
+
+    /// The archetype count, once each time it reaches the next warning
+    /// threshold ([`ARCHETYPE_WARN`](Self::ARCHETYPE_WARN), then double).
+    pub fn take_archetype_warning(&mut self) -> Option<usize> {
+        let threshold = self.next_warning.max(Self::ARCHETYPE_WARN);
+        if self.archetypes < threshold {
+            return None;
+        }
+        let mut next = threshold;
+        while next <= self.archetypes {
+            next *= 2;
+        }
+        self.next_warning = next;
+        Some(self.archetypes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn despawns_come_from_the_entity_count() {
+        let mut stats = EcsStats::default();
+        stats.record_frame(10, 3, 10);
+        assert_eq!((stats.spawned, stats.despawned), (10, 0));
+        stats.record_frame(7, 3, 2);
+        assert_eq!((stats.spawned, stats.despawned), (2, 5));
+    }
+
+    #[test]
+    fn archetype_warning_fires_once_per_doubling() {
+        let mut stats = EcsStats::default();
+        stats.record_frame(0, 100, 0);
+        assert_eq!(stats.take_archetype_warning(), None);
+        stats.record_frame(0, 300, 0);
+        assert_eq!(stats.take_archetype_warning(), Some(300));
+        stats.record_frame(0, 400, 0);
+        assert_eq!(stats.take_archetype_warning(), None);
+        stats.record_frame(0, 512, 0);
+        assert_eq!(stats.take_archetype_warning(), Some(512));
+    }
+}
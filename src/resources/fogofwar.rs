@@ -0,0 +1,306 @@
+//! Fog of war over the map.
+//!
+//! [`FogOfWar`] splits the world into square cells of `cell_size` units. Each
+//! frame, [`fog_visibility_system`] makes the cells around every
+//! [`VisionSource`] *visible*; visible cells are also *revealed* and stay so
+//! for the rest of the scene. The render pass then darkens every cell in view:
+//! fully for cells never revealed, partly for revealed cells that are not
+//! currently visible.
+//!
+//! Revealed cells are kept per scene name, so leaving a level and coming back
+//! keeps what the player had already explored. Solid cells, which block
+//! vision sources created with `blocked`, come from the tiles of the tilemap
+//! layers listed in `solid_layers` (see [`fog_opaque_system`]).
+//!
+//! The fog is disabled by default; enable it from Rust with
+//! [`FogOfWar::enable`] or from Lua with `engine.fog_enable(cell_size, solid_layers)`.
+//!
+//! [`VisionSource`]: crate::components::visionsource::VisionSource
+//! [`fog_visibility_system`]: crate::systems::fogofwar::fog_visibility_system
+//! [`fog_opaque_system`]: crate::systems::fogofwar::fog_opaque_system
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::{Color, Vector2};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// A fog-of-war cell, in cell coordinates.
+pub type FogCell = (i32, i32);
+
+/// Fog-of-war state. See the module docs.
+#[derive(Resource, Debug)]
+pub struct FogOfWar {
+    /// Whether the fog is updated and drawn.
+    pub enabled: bool,
+    /// Overlay colour of cells never revealed.
+    pub hidden_color: Color,
+    /// Overlay colour of revealed cells outside every vision source.
+    pub explored_color: Color,
+    cell_size: f32,
+    solid_layers: Vec<String>,
+    scene: String,
+    revealed: FxHashMap<String, FxHashSet<FogCell>>,
+    visible: FxHashSet<FogCell>,
+    opaque: FxHashSet<FogCell>,
+    opaque_dirty: bool,
+    revision: u64,
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hidden_color: Color::new(0, 0, 0, 255),
+            explored_color: Color::new(0, 0, 0, 160),
+            cell_size: Self::DEFAULT_CELL_SIZE,
+            solid_layers: Vec::new(),
+            scene: String::new(),
+            revealed: FxHashMap::default(),
+            visible: FxHashSet::default(),
+            opaque: FxHashSet::default(),
+            opaque_dirty: false,
+            revision: 0,
+        }
+    }
+}
+
+impl FogOfWar {
+    /// Cell size used until [`enable`](Self::enable) sets one.
+    pub const DEFAULT_CELL_SIZE: f32 = 16.0;
+
+    /// Turn the fog on with `cell_size`-unit cells, treating the tiles of
+    /// `solid_layers` as vision blockers.
+    ///
+    /// Changing the cell size forgets every revealed cell, since the old cells
+    /// no longer line up.
+    pub fn enable(&mut self, cell_size: f32, solid_layers: Vec<String>) {
+        let cell_size = if cell_size > 0.0 {
+            cell_size
+        } else {
+            Self::DEFAULT_CELL_SIZE
+        };
+        if cell_size != self.cell_size {
+            self.revealed.clear();
+            self.visible.clear();
+            self.revision += 1;
+        }
+        self.enabled = true;
+        self.cell_size = cell_size;
+        self.solid_layers = solid_layers;
+        self.opaque_dirty = true;
+    }
+
+    /// Turn the fog off. Revealed cells are kept.
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    /// Side of a fog cell in world units.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Tilemap layers whose tiles block vision.
+    pub fn solid_layers(&self) -> &[String] {
+        &self.solid_layers
+    }
+
+    /// Cell containing the world point `pos`.
+    pub fn cell_at(&self, pos: Vector2) -> FogCell {
+        (
+            (pos.x / self.cell_size).floor() as i32,
+            (pos.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Select the scene whose revealed cells are read and updated.
+    pub fn set_scene(&mut self, scene: &str) {
+        if self.scene != scene {
+            self.scene = scene.to_string();
+            self.visible.clear();
+            self.revision += 1;
+        }
+    }
+
+    /// Forget every cell revealed in the current scene.
+    pub fn reset_scene(&mut self) {
+        self.revealed.remove(&self.scene);
+        self.visible.clear();
+        self.revision += 1;
+    }
+
+    /// Cells revealed in the current scene.
+    pub fn revealed_cells(&self) -> Option<&FxHashSet<FogCell>> {
+        self.revealed.get(&self.scene)
+    }
+
+    /// Counter bumped whenever the revealed cells of the current scene change,
+    /// so readers can skip copying them when nothing new was revealed.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// `true` if `cell` has been seen in the current scene.
+    pub fn is_revealed(&self, cell: FogCell) -> bool {
+        self.revealed_cells()
+            .is_some_and(|cells| cells.contains(&cell))
+    }
+
+    /// `true` if `cell` is in sight of a vision source this frame.
+    pub fn is_visible(&self, cell: FogCell) -> bool {
+        self.visible.contains(&cell)
+    }
+
+    /// Overlay colour of `cell`, or `None` if it is visible.
+    pub fn overlay_color(&self, cell: FogCell) -> Option<Color> {
+        if self.is_visible(cell) {
+            None
+        } else if self.is_revealed(cell) {
+            Some(self.explored_color)
+        } else {
+            Some(self.hidden_color)
+        }
+    }
+
+    /// Replace the set of cells that block vision.
+    pub fn set_opaque(&mut self, cells: impl IntoIterator<Item = FogCell>) {
+        self.opaque.clear();
+        self.opaque.extend(cells);
+        self.opaque_dirty = false;
+    }
+
+    /// `true` if `cell` blocks vision.
+    pub fn is_opaque(&self, cell: FogCell) -> bool {
+        self.opaque.contains(&cell)
+    }
+
+    /// `true` if the solid cells must be rebuilt (the settings changed).
+    pub fn opaque_dirty(&self) -> bool {
+        self.opaque_dirty
+    }
+
+    /// Start a new frame: no cell is visible until [`reveal`](Self::reveal) runs.
+    pub fn clear_visible(&mut self) {
+        self.visible.clear();
+    }
+
+    /// Make the cells whose centre lies within `radius` of `center` visible and
+    /// revealed. With `blocked`, cells hidden behind opaque cells are skipped;
+    /// the opaque cells themselves can be seen.
+    pub fn reveal(&mut self, center: Vector2, radius: f32, blocked: bool) {
+        let origin = self.cell_at(center);
+        let reach = (radius / self.cell_size).ceil() as i32 + 1;
+        let radius_sq = radius * radius;
+        let revealed = self.revealed.entry(self.scene.clone()).or_default();
+        for y in origin.1 - reach..=origin.1 + reach {
+            for x in origin.0 - reach..=origin.0 + reach {
+                let cx = (x as f32 + 0.5) * self.cell_size - center.x;
+                let cy = (y as f32 + 0.5) * self.cell_size - center.y;
+                if (x, y) != origin && cx * cx + cy * cy > radius_sq {
+                    continue;
+                }
+                if blocked && !line_of_sight(&self.opaque, origin, (x, y)) {
+                    continue;
+                }
+                self.visible.insert((x, y));
+                if revealed.insert((x, y)) {
+                    self.revision += 1;
+                }
+            }
+        }
+    }
+}
+
+/// `true` if no opaque cell lies strictly between `from` and `to` on the
+/// Bresenham line joining them.
+fn line_of_sight(opaque: &FxHashSet<FogCell>, from: FogCell, to: FogCell) -> bool {
+    let (dx, dy) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (mut x, mut y, mut err) = (from.0, from.1, dx + dy);
+    loop {
+        if (x, y) == to {
+            return true;
+        }
+        if (x, y) != from && opaque.contains(&(x, y)) {
+            return false;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fog() -> FogOfWar {
+        let mut fog = FogOfWar::default();
+        fog.enable(10.0, Vec::new());
+        fog.set_scene("level01");
+        fog
+    }
+
+    #[test]
+    fn reveal_marks_cells_within_radius() {
+        let mut fog = fog();
+        fog.reveal(Vector2::new(55.0, 55.0), 20.0, false);
+        assert!(fog.is_visible((5, 5)));
+        assert!(fog.is_visible((7, 5)));
+        assert!(!fog.is_visible((8, 5)));
+        assert!(!fog.is_visible((7, 7)));
+        assert!(fog.is_revealed((7, 5)));
+
+        fog.clear_visible();
+        assert!(!fog.is_visible((5, 5)));
+        assert!(fog.is_revealed((5, 5)));
+        assert_eq!(fog.overlay_color((5, 5)), Some(fog.explored_color));
+        assert_eq!(fog.overlay_color((20, 20)), Some(fog.hidden_color));
+    }
+
+    #[test]
+    fn opaque_cells_block_blocked_sources_only() {
+        let mut fog = fog();
+        fog.set_opaque([(6, 5)]);
+        fog.reveal(Vector2::new(55.0, 55.0), 30.0, true);
+        assert!(fog.is_visible((6, 5)), "the wall itself is seen");
+        assert!(!fog.is_visible((7, 5)), "cells behind the wall stay hidden");
+        assert!(fog.is_visible((5, 7)));
+
+        fog.clear_visible();
+        fog.reveal(Vector2::new(55.0, 55.0), 30.0, false);
+        assert!(fog.is_visible((7, 5)));
+    }
+
+    #[test]
+    fn revealed_cells_are_kept_per_scene() {
+        let mut fog = fog();
+        fog.reveal(Vector2::new(5.0, 5.0), 0.0, false);
+        fog.set_scene("level02");
+        assert!(!fog.is_revealed((0, 0)));
+        assert!(!fog.is_visible((0, 0)));
+        fog.set_scene("level01");
+        assert!(fog.is_revealed((0, 0)));
+
+        let revision = fog.revision();
+        fog.reset_scene();
+        assert!(!fog.is_revealed((0, 0)));
+        assert!(fog.revision() > revision);
+    }
+
+    #[test]
+    fn changing_cell_size_forgets_revealed_cells() {
+        let mut fog = fog();
+        fog.reveal(Vector2::new(5.0, 5.0), 0.0, false);
+        fog.enable(10.0, vec!["walls".to_string()]);
+        assert!(fog.is_revealed((0, 0)));
+        assert!(fog.opaque_dirty());
+        fog.enable(32.0, Vec::new());
+        assert!(!fog.is_revealed((0, 0)));
+    }
+}
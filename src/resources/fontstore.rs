@@ -7,10 +7,17 @@
 //!
 //! Note: This is a non-send resource because Raylib fonts must be accessed
 //! from the main thread only.
+//!
+//! Render systems look fonts up with [`FontStore::get_or_fallback`], which
+//! substitutes raylib's built-in font for unknown keys (see
+//! [`missingassets`](crate::resources::missingassets)).
 
 // use bevy_ecs::prelude::Resource; // NonSend resource: use NonSend<FontStore> in system parameters
-use raylib::prelude::Font;
+use crate::resources::missingassets::MissLog;
+use raylib::ffi;
+use raylib::prelude::{Font, RaylibHandle};
 use rustc_hash::FxHashMap;
+use std::mem::ManuallyDrop;
 
 /// Editor-facing metadata for a loaded font entry.
 #[derive(Debug)]
@@ -26,6 +33,9 @@ pub struct FontMeta {
 pub struct FontStore {
     fonts: FxHashMap<String, Font>,
     pub meta: FxHashMap<String, FontMeta>,
+    /// raylib's default font, owned by raylib itself — never unloaded here.
+    fallback: Option<ManuallyDrop<Font>>,
+    misses: MissLog,
 }
 
 impl Default for FontStore {
@@ -40,9 +50,20 @@ impl FontStore {
         Self {
             fonts: FxHashMap::default(),
             meta: FxHashMap::default(),
+            fallback: None,
+            misses: MissLog::default(),
         }
     }
 
+    /// Use raylib's built-in font as the fallback for unknown keys. Requires
+    /// an open window, which `_rl` witnesses.
+    pub fn load_fallback(&mut self, _rl: &RaylibHandle) {
+        // SAFETY: the window is initialized, so the default font is loaded.
+        // It stays owned by raylib: ManuallyDrop keeps us from unloading it.
+        let font = unsafe { Font::from_raw(ffi::GetFontDefault()) };
+        self.fallback = Some(ManuallyDrop::new(font));
+    }
+
     /// Add a font with the given key (no metadata — for engine-internal fonts).
     pub fn add(&mut self, id: impl Into<String>, font: Font) {
         self.fonts.insert(id.into(), font);
@@ -66,6 +87,23 @@ impl FontStore {
         self.fonts.get(id.as_ref())
     }
 
+    /// Get a font by its key, or raylib's default font if `id` is not loaded.
+    /// The miss is recorded for [`take_misses`](Self::take_misses).
+    ///
+    /// Returns `None` only when the key is missing and no fallback was loaded
+    /// (headless runs).
+    pub fn get_or_fallback(&self, id: impl AsRef<str>) -> Option<&Font> {
+        self.get(id.as_ref()).or_else(|| {
+            self.misses.record(id.as_ref());
+            self.fallback.as_deref()
+        })
+    }
+
+    /// Keys substituted by the fallback since the last call.
+    pub fn take_misses(&self) -> Vec<String> {
+        self.misses.drain()
+    }
+
     /// Rename a font key, moving both the font and its metadata.
     pub fn rename(&mut self, old_id: impl AsRef<str>, new_id: impl Into<String>) {
         let old_key = old_id.as_ref();
@@ -91,6 +129,11 @@ impl FontStore {
         self.meta.clear();
     }
 
+    /// Iterate over every loaded font with its key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Font)> {
+        self.fonts.iter().map(|(key, font)| (key.as_str(), font))
+    }
+
     /// Get the number of loaded fonts.
     pub fn len(&self) -> usize {
         self.fonts.len()
@@ -0,0 +1,252 @@
+//! Named formations of entities following a leader.
+//!
+//! A [`Formation`] keeps its member entities on slots around a leader entity,
+//! laid out by a [`FormationPattern`] with `spacing` units between slots:
+//! Galaga-style enemy waves, escort wings, or a squad trailing its captain.
+//! [`formation_system`](crate::systems::formation::formation_system) moves
+//! every member toward its slot each frame. Members don't jump to their slot:
+//! they close the gap at a rate set by `catch_up`, so entities joining late
+//! fly into place and the rest of the wing slides over when one is destroyed.
+//!
+//! Slots are numbered in joining order. A member that is despawned leaves its
+//! formation and the following members move up a slot; a formation whose
+//! leader is despawned is disbanded, leaving its members where they are.
+//!
+//! The formation owns its members' [`MapPosition`], so members shouldn't
+//! have a [`RigidBody`] velocity or position tweens of their own.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut wing = Formation::new(leader, FormationPattern::V, 24.0);
+//! wing.add(enemy_a);
+//! wing.add(enemy_b);
+//! formations.insert("squad1", wing);
+//! ```
+//!
+//! [`MapPosition`]: crate::components::mapposition::MapPosition
+//! [`RigidBody`]: crate::components::rigidbody::RigidBody
+
+use bevy_ecs::prelude::{Entity, Resource};
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+use std::f32::consts::TAU;
+
+/// Rate at which members close the gap to their slot by default.
+pub const DEFAULT_CATCH_UP: f32 = 6.0;
+
+/// How the slots of a [`Formation`] are laid out around the leader.
+///
+/// Offsets are given for a leader at rotation 0 and heading up the screen;
+/// a formation created with `rotate` turns them with the leader's rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormationPattern {
+    /// A row through the leader, filled outward: right, left, right, ...
+    Line,
+    /// Two diagonal lines trailing behind the leader, filled alternately.
+    V,
+    /// A ring around the leader with `spacing` as its radius. The slots are
+    /// spread evenly over the ring, so they move when the member count
+    /// changes.
+    Circle,
+}
+
+impl FormationPattern {
+    /// Parses `"line"`, `"v"` or `"circle"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "line" => Some(FormationPattern::Line),
+            "v" => Some(FormationPattern::V),
+            "circle" => Some(FormationPattern::Circle),
+            _ => None,
+        }
+    }
+
+    /// Offset from the leader of slot `index` out of `count` slots.
+    pub fn slot_offset(&self, index: usize, count: usize, spacing: f32) -> Vector2 {
+        let rank = (index / 2 + 1) as f32 * spacing;
+        let side = if index % 2 == 0 { 1.0 } else { -1.0 };
+        match self {
+            FormationPattern::Line => Vector2::new(side * rank, 0.0),
+            FormationPattern::V => Vector2::new(side * rank, rank),
+            FormationPattern::Circle => {
+                let angle = TAU * index as f32 / count.max(1) as f32 - TAU / 4.0;
+                Vector2::new(angle.cos(), angle.sin()) * spacing
+            }
+        }
+    }
+}
+
+/// A leader entity and the members keeping station around it.
+#[derive(Debug, Clone)]
+pub struct Formation {
+    pub leader: Entity,
+    pub pattern: FormationPattern,
+    /// Distance between neighbouring slots, or the radius of a circle.
+    pub spacing: f32,
+    /// How quickly members close the gap to their slot: about 1/`catch_up`
+    /// seconds to cover two thirds of it. 0 snaps members onto their slots.
+    pub catch_up: f32,
+    /// Turn the slots with the leader's rotation.
+    pub rotate: bool,
+    /// Members in slot order.
+    pub members: Vec<Entity>,
+}
+
+impl Formation {
+    pub fn new(leader: Entity, pattern: FormationPattern, spacing: f32) -> Self {
+        Self {
+            leader,
+            pattern,
+            spacing,
+            catch_up: DEFAULT_CATCH_UP,
+            rotate: false,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn with_catch_up(mut self, catch_up: f32) -> Self {
+        self.catch_up = catch_up.max(0.0);
+        self
+    }
+
+    pub fn with_rotate(mut self, rotate: bool) -> Self {
+        self.rotate = rotate;
+        self
+    }
+
+    /// Adds `entity` in the next free slot. The leader and current members
+    /// are ignored.
+    pub fn add(&mut self, entity: Entity) {
+        if entity != self.leader && !self.members.contains(&entity) {
+            self.members.push(entity);
+        }
+    }
+
+    /// Removes `entity`; the members behind it move up a slot.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let before = self.members.len();
+        self.members.retain(|&member| member != entity);
+        self.members.len() != before
+    }
+
+    /// World position of slot `index` for a leader at `leader_pos` turned
+    /// `leader_degrees`.
+    pub fn slot_position(&self, index: usize, leader_pos: Vector2, leader_degrees: f32) -> Vector2 {
+        let offset = self
+            .pattern
+            .slot_offset(index, self.members.len(), self.spacing);
+        if self.rotate {
+            leader_pos + offset.rotated(leader_degrees.to_radians())
+        } else {
+            leader_pos + offset
+        }
+    }
+}
+
+/// Formations by name.
+#[derive(Resource, Debug, Default)]
+pub struct Formations {
+    formations: FxHashMap<String, Formation>,
+}
+
+impl Formations {
+    /// Creates or replaces the formation called `name`. Members of other
+    /// formations keep their place there.
+    pub fn insert(&mut self, name: impl Into<String>, formation: Formation) {
+        self.formations.insert(name.into(), formation);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Formation> {
+        self.formations.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Formation> {
+        self.formations.get_mut(name)
+    }
+
+    /// Disbands the formation called `name`, leaving its members in place.
+    pub fn remove(&mut self, name: &str) -> Option<Formation> {
+        self.formations.remove(name)
+    }
+
+    /// Adds `entity` to the formation called `name`, taking it out of any
+    /// other formation first. Returns `false` if there is no such formation.
+    pub fn add_member(&mut self, name: &str, entity: Entity) -> bool {
+        if !self.formations.contains_key(name) {
+            return false;
+        }
+        for (other, formation) in self.formations.iter_mut() {
+            if other != name {
+                formation.remove(entity);
+            }
+        }
+        if let Some(formation) = self.formations.get_mut(name) {
+            formation.add(entity);
+        }
+        true
+    }
+
+    /// Name of the formation `entity` is a member of.
+    pub fn formation_of(&self, entity: Entity) -> Option<&str> {
+        self.formations
+            .iter()
+            .find(|(_, formation)| formation.members.contains(&entity))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Keeps only the formations for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &mut Formation) -> bool) {
+        self.formations
+            .retain(|name, formation| keep(name.as_str(), formation));
+    }
+
+    pub fn len(&self) -> usize {
+        self.formations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.formations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_lay_out_slots() {
+        let v = FormationPattern::parse("v").unwrap();
+        let slots: Vec<_> = (0..4)
+            .map(|i| v.slot_offset(i, 4, 10.0))
+            .map(|p| (p.x, p.y))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![(10.0, 10.0), (-10.0, 10.0), (20.0, 20.0), (-20.0, 20.0)]
+        );
+        let line = FormationPattern::Line.slot_offset(1, 3, 8.0);
+        assert_eq!((line.x, line.y), (-8.0, 0.0));
+        let top = FormationPattern::Circle.slot_offset(0, 4, 5.0);
+        assert!(top.x.abs() < 1e-4 && (top.y + 5.0).abs() < 1e-4);
+        assert!(FormationPattern::parse("wedge").is_none());
+    }
+
+    #[test]
+    fn entities_belong_to_one_formation() {
+        let mut world = bevy_ecs::world::World::new();
+        let [leader_a, leader_b, ship] = [(); 3].map(|_| world.spawn_empty().id());
+        let mut formations = Formations::default();
+        formations.insert("a", Formation::new(leader_a, FormationPattern::Line, 10.0));
+        formations.insert("b", Formation::new(leader_b, FormationPattern::V, 10.0));
+
+        assert!(formations.add_member("a", ship));
+        assert!(formations.add_member("b", ship));
+        assert_eq!(formations.formation_of(ship), Some("b"));
+        assert!(formations.get("a").unwrap().members.is_empty());
+        assert!(!formations.add_member("c", ship));
+
+        formations.get_mut("b").unwrap().add(leader_b);
+        assert_eq!(formations.get("b").unwrap().members, vec![ship]);
+    }
+}
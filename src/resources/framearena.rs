@@ -0,0 +1,167 @@
+//! Frame-scoped bump allocator for temporary per-frame buffers.
+//!
+//! The collision detector snapshots every collider's world rectangle and
+//! gathers the frame's collision pairs into short-lived lists. Allocating
+//! them from the global heap costs an allocation and a free per list and
+//! frame. [`FrameArena`] hands out [`BumpVec`]s from one bump allocator
+//! instead; the
+//! [`reset_frame_arena`](crate::systems::frame_arena::reset_frame_arena)
+//! system releases everything at the end of the frame while keeping the
+//! memory, so after the first frames the arena itself needs no new heap
+//! memory. Buffers that must outlive a system, or that systems running in
+//! parallel need, stay in `Local`s instead (the render sort buffers, the
+//! visibility polygons, the Lua command queues).
+//!
+//! Nothing allocated from the arena may outlive the system that allocated it;
+//! the borrow checker enforces this because every [`BumpVec`] borrows the
+//! [`ResMut<FrameArena>`](bevy_ecs::prelude::ResMut) it came from.
+
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use bevy_ecs::prelude::Resource;
+use bumpalo::Bump;
+
+/// Vector allocated in the [`FrameArena`].
+pub type BumpVec<'a, T> = bumpalo::collections::Vec<'a, T>;
+
+/// Allocation counters of one frame, reported to the profiler.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameArenaStats {
+    /// Buffers handed out by [`FrameScratch`] during the frame.
+    pub allocations: u32,
+    /// Bytes in use when the frame ended.
+    pub bytes_used: usize,
+    /// Bytes reserved from the heap by the arena.
+    pub capacity: usize,
+}
+
+struct ArenaInner {
+    bump: Bump,
+    allocations: Cell<u32>,
+}
+
+/// Bump allocator reset once per frame. See the module docs.
+///
+/// `Bump` is not `Sync`, so it sits behind a [`Mutex`]; systems reach it
+/// through `ResMut`, which gives exclusive access without locking.
+#[derive(Resource)]
+pub struct FrameArena {
+    inner: Mutex<ArenaInner>,
+    last_frame: FrameArenaStats,
+}
+
+impl Default for FrameArena {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(ArenaInner {
+                bump: Bump::new(),
+                allocations: Cell::new(0),
+            }),
+            last_frame: FrameArenaStats::default(),
+        }
+    }
+}
+
+impl FrameArena {
+    fn inner(&mut self) -> &mut ArenaInner {
+        self.inner
+            .get_mut()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Handle for allocating this frame's temporary buffers.
+    pub fn scratch(&mut self) -> FrameScratch<'_> {
+        FrameScratch {
+            inner: self.inner(),
+        }
+    }
+
+    /// Counters of the current frame so far.
+    pub fn stats(&mut self) -> FrameArenaStats {
+        let inner = self.inner();
+        let capacity = inner.bump.allocated_bytes();
+        FrameArenaStats {
+            allocations: inner.allocations.get(),
+            bytes_used: capacity - inner.bump.chunk_capacity().min(capacity),
+            capacity,
+        }
+    }
+
+    /// Counters of the last frame that was [`reset`](Self::reset).
+    pub fn last_frame(&self) -> FrameArenaStats {
+        self.last_frame
+    }
+
+    /// Free every buffer allocated since the previous reset, keep the memory
+    /// for the next frame, and return the finished frame's counters.
+    pub fn reset(&mut self) -> FrameArenaStats {
+        let stats = self.stats();
+        let inner = self.inner();
+        inner.bump.reset();
+        inner.allocations.set(0);
+        self.last_frame = stats;
+        stats
+    }
+}
+
+/// Borrowed view of the [`FrameArena`] that allocates buffers.
+pub struct FrameScratch<'a> {
+    inner: &'a ArenaInner,
+}
+
+impl<'a> FrameScratch<'a> {
+    /// Empty vector in the arena.
+    pub fn vec<T>(&self) -> BumpVec<'a, T> {
+        self.count();
+        BumpVec::new_in(&self.inner.bump)
+    }
+
+    /// Vector in the arena with room for `capacity` elements.
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> BumpVec<'a, T> {
+        self.count();
+        BumpVec::with_capacity_in(capacity, &self.inner.bump)
+    }
+
+    fn count(&self) {
+        let allocations = &self.inner.allocations;
+        allocations.set(allocations.get().saturating_add(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_reports_frame_counters_and_clears_them() {
+        let mut arena = FrameArena::default();
+        {
+            let scratch = arena.scratch();
+            let mut a = scratch.vec::<u64>();
+            a.extend(0..100);
+            let b = scratch.vec_with_capacity::<u32>(16);
+            assert_eq!(a.len(), 100);
+            assert!(b.capacity() >= 16);
+        }
+
+        let stats = arena.reset();
+        assert_eq!(stats.allocations, 2);
+        assert!(stats.bytes_used >= 100 * 8);
+        assert!(stats.capacity >= stats.bytes_used);
+        assert_eq!(arena.last_frame(), stats);
+        assert_eq!(arena.stats().allocations, 0);
+    }
+
+    #[test]
+    fn reset_keeps_memory_for_the_next_frame() {
+        let mut arena = FrameArena::default();
+        arena.scratch().vec_with_capacity::<u8>(4096);
+        arena.reset();
+        let kept = arena.stats().capacity;
+        assert!(kept >= 4096);
+
+        arena.scratch().vec_with_capacity::<u8>(4096);
+        assert_eq!(arena.reset().capacity, kept, "no new chunk is needed");
+    }
+}
@@ -1,10 +1,171 @@
-//! Full screen toggle resource.
+//! Fullscreen state, fullscreen mode, and monitor resources.
 //!
-//! The mere presence of this resource indicates that the application should run in
-//! full screen mode. Remove it to disable full screen behavior.
+//! The presence of [`FullScreen`] indicates that the window currently covers a
+//! monitor; its fields record how ([`FullscreenMode`]) and which monitor. The
+//! desired state lives in
+//! [`GameConfig`](crate::resources::gameconfig::GameConfig) (`fullscreen`,
+//! `fullscreen_mode`, `monitor`); see
+//! [`switchfullscreen`](crate::events::switchfullscreen) for how the window is
+//! brought in line with it.
 //!
+//! [`Monitors`] lists the connected monitors. It is refreshed at startup and
+//! every time the window mode is applied.
+
 use bevy_ecs::prelude::Resource;
+use raylib::ffi;
+use std::ffi::CStr;
+
+use crate::resources::gameconfig::GameConfig;
+
+/// How the window covers the monitor when fullscreen.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum FullscreenMode {
+    /// Undecorated window the size of the monitor; alt-tab friendly and the
+    /// monitor keeps its desktop video mode.
+    #[default]
+    Borderless,
+    /// Exclusive fullscreen; the monitor switches to the window's video mode.
+    Exclusive,
+}
+
+impl FullscreenMode {
+    /// All variants, in declaration order.
+    pub const ALL: [FullscreenMode; 2] = [FullscreenMode::Borderless, FullscreenMode::Exclusive];
+
+    /// Canonical string form, the inverse of [`FromStr`](std::str::FromStr).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FullscreenMode::Borderless => "borderless",
+            FullscreenMode::Exclusive => "exclusive",
+        }
+    }
+
+    /// Parse an optional mode string, warning and falling back to
+    /// [`FullscreenMode::Borderless`] if unrecognized.
+    pub fn from_opt_str_or_warn(mode: Option<&str>, context: &str) -> Self {
+        mode.map(|s| {
+            s.parse().unwrap_or_else(|_| {
+                log::warn!("Unknown fullscreen mode '{s}' for '{context}', using 'borderless'");
+                Self::default()
+            })
+        })
+        .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for FullscreenMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FullscreenMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// Present while the window is fullscreen; records the mode and monitor in use.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FullScreen {
+    /// Fullscreen mode the window is in.
+    pub mode: FullscreenMode,
+    /// Index of the monitor the window covers.
+    pub monitor: u32,
+}
+
+impl FullScreen {
+    /// The fullscreen state `config` asks for, or `None` for windowed.
+    pub fn from_config(config: &GameConfig) -> Option<Self> {
+        config.fullscreen.then_some(Self {
+            mode: config.fullscreen_mode,
+            monitor: config.monitor,
+        })
+    }
+
+    /// Window mode name as used by `engine.set_window_mode`: `"windowed"`,
+    /// `"borderless"` or `"fullscreen"` (exclusive).
+    pub fn window_mode_str(state: Option<Self>) -> &'static str {
+        match state.map(|s| s.mode) {
+            None => "windowed",
+            Some(FullscreenMode::Borderless) => "borderless",
+            Some(FullscreenMode::Exclusive) => "fullscreen",
+        }
+    }
+}
+
+/// A connected monitor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MonitorInfo {
+    /// Monitor name reported by the OS.
+    pub name: String,
+    /// Current video mode width in pixels.
+    pub width: i32,
+    /// Current video mode height in pixels.
+    pub height: i32,
+    /// Current video mode refresh rate in Hz.
+    pub refresh_rate: i32,
+}
+
+/// Connected monitors, indexed as in `GameConfig::monitor`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Monitors(pub Vec<MonitorInfo>);
+
+impl Monitors {
+    /// Query the connected monitors. Requires an open window.
+    pub fn query() -> Self {
+        let count = unsafe { ffi::GetMonitorCount() };
+        Self(
+            (0..count)
+                .map(|i| unsafe {
+                    let name = ffi::GetMonitorName(i);
+                    MonitorInfo {
+                        name: if name.is_null() {
+                            String::new()
+                        } else {
+                            CStr::from_ptr(name).to_string_lossy().into_owned()
+                        },
+                        width: ffi::GetMonitorWidth(i),
+                        height: ffi::GetMonitorHeight(i),
+                        refresh_rate: ffi::GetMonitorRefreshRate(i),
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_strings_round_trip() {
+        for mode in FullscreenMode::ALL {
+            assert_eq!(mode.as_str().parse(), Ok(mode));
+        }
+        assert_eq!(
+            FullscreenMode::from_opt_str_or_warn(Some("windowed"), "test"),
+            FullscreenMode::Borderless
+        );
+    }
+
+    #[test]
+    fn from_config_follows_fullscreen_flag() {
+        let mut config = GameConfig::new();
+        assert_eq!(FullScreen::from_config(&config), None);
+        assert_eq!(FullScreen::window_mode_str(None), "windowed");
 
-/// Marker resource: when present, the application runs in full screen mode.
-#[derive(Resource, Clone, Copy)]
-pub struct FullScreen {}
+        config.fullscreen = true;
+        config.fullscreen_mode = FullscreenMode::Exclusive;
+        config.monitor = 1;
+        let state = FullScreen::from_config(&config);
+        assert_eq!(
+            state,
+            Some(FullScreen {
+                mode: FullscreenMode::Exclusive,
+                monitor: 1
+            })
+        );
+        assert_eq!(FullScreen::window_mode_str(state), "fullscreen");
+    }
+}
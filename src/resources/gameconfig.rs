@@ -10,14 +10,33 @@
 //! width = 640
 //! height = 360
 //! pixel_snap_camera = true
+//! render_scale = 1
 //!
 //! [window]
 //! width = 1280
 //! height = 720
 //! fullscreen = false
+//! fullscreen_mode = borderless
+//! monitor = 0
 //! vsync = true
 //! target_fps = 120
+//! background_fps = 0
 //! title = Aberred Engine
+//!
+//! [audio]
+//! master_volume = 1.0
+//! music_volume = 0.8
+//! sfx_volume = 1.0
+//!
+//! [accessibility]
+//! colorblind_mode = none
+//! high_contrast_ui = false
+//!
+//! [systems]
+//! particles = true
+//! weather = true
+//! color_grading = true
+//! auto_quality = false
 //! ```
 
 use bevy_ecs::prelude::*;
@@ -26,6 +45,9 @@ use log::{debug, info};
 use raylib::prelude::Color;
 use std::path::PathBuf;
 
+use crate::resources::accessibility::ColorblindMode;
+use crate::resources::fullscreen::FullscreenMode;
+use crate::resources::systemtoggles::{SystemToggles, ToggleableSystem};
 use crate::resources::texturefilter::TextureFilter;
 
 /// Default safe values for startup
@@ -34,12 +56,19 @@ const DEFAULT_RENDER_HEIGHT: u32 = 360;
 const DEFAULT_WINDOW_WIDTH: u32 = 1280;
 const DEFAULT_WINDOW_HEIGHT: u32 = 720;
 const DEFAULT_TARGET_FPS: u32 = 120;
+const DEFAULT_BACKGROUND_FPS: u32 = 0;
 const DEFAULT_VSYNC: bool = true;
 const DEFAULT_FULLSCREEN: bool = false;
 const DEFAULT_PIXEL_SNAP_CAMERA: bool = true;
+const DEFAULT_RENDER_SCALE: u32 = 1;
+/// Largest supported supersampling factor for the render target.
+pub const MAX_RENDER_SCALE: u32 = 4;
 const DEFAULT_BACKGROUND_COLOR: Color = Color::new(80, 80, 80, 255);
 const DEFAULT_CONFIG_PATH: &str = "./config.ini";
 const DEFAULT_WINDOW_TITLE: &str = "Aberred Engine";
+const DEFAULT_VOLUME: f32 = 1.0;
+const DEFAULT_HIGH_CONTRAST_UI: bool = false;
+const DEFAULT_AUTO_QUALITY: bool = false;
 
 /// Game configuration resource.
 ///
@@ -58,12 +87,19 @@ pub struct GameConfig {
     pub window_width: u32,
     /// Window height in pixels.
     pub window_height: u32,
-    /// Target frames per second.
+    /// Target frames per second (`0` = uncapped).
     pub target_fps: u32,
+    /// Frame cap while the window is unfocused (`0` = no throttling, keep
+    /// `target_fps`).
+    pub background_fps: u32,
     /// Enable vertical sync.
     pub vsync: bool,
     /// Start in fullscreen mode.
     pub fullscreen: bool,
+    /// How the window covers the monitor when `fullscreen` is set.
+    pub fullscreen_mode: FullscreenMode,
+    /// Index of the monitor used for fullscreen (0 is the primary monitor).
+    pub monitor: u32,
     /// Snap the camera/view rect to integer pixels before rendering.
     ///
     /// Eliminates sprite atlas bleeding caused by sub-pixel sampling during
@@ -76,10 +112,36 @@ pub struct GameConfig {
     /// `Nearest` (default) gives sharp pixel-art scaling. `Bilinear` or higher
     /// produces smooth interpolation when the window is larger than the game resolution.
     pub render_target_filter: TextureFilter,
+    /// Supersampling factor of the render target, in `1..=MAX_RENDER_SCALE`.
+    ///
+    /// The scene is drawn at `render_width * render_scale` by
+    /// `render_height * render_scale` pixels and downscaled on the final blit,
+    /// which smooths rotated and scaled sprites. Logical coordinates
+    /// (`ScreenSize`, camera, mouse) do not change. `1` (default) disables
+    /// supersampling; pair higher values with a `Bilinear` or better
+    /// `render_target_filter`.
+    pub render_scale: u32,
     /// Background clear color for the render target.
     pub background_color: Color,
     /// Window title.
     pub window_title: String,
+    /// Global volume applied to all audio, in `[0.0, 1.0]`.
+    pub master_volume: f32,
+    /// Volume multiplier for all music streams, in `[0.0, 1.0]`.
+    pub music_volume: f32,
+    /// Volume multiplier for all sound effects, in `[0.0, 1.0]`.
+    pub sfx_volume: f32,
+    /// Color-vision palette remap applied as the last post-process pass.
+    pub colorblind_mode: ColorblindMode,
+    /// Draw screen-space text on opaque plates and menus in a fixed
+    /// high-contrast color pair.
+    pub high_contrast_ui: bool,
+    /// Non-essential systems switched off; copied into the
+    /// [`SystemToggles`] resource whenever the configuration changes.
+    pub system_toggles: SystemToggles,
+    /// Let the [`QualityGovernor`](crate::resources::qualitygovernor::QualityGovernor)
+    /// step quality down while frames are over budget.
+    pub auto_quality: bool,
     /// Path to the configuration file.
     pub config_path: PathBuf,
 }
@@ -99,12 +161,23 @@ impl GameConfig {
             window_width: DEFAULT_WINDOW_WIDTH,
             window_height: DEFAULT_WINDOW_HEIGHT,
             target_fps: DEFAULT_TARGET_FPS,
+            background_fps: DEFAULT_BACKGROUND_FPS,
             vsync: DEFAULT_VSYNC,
             fullscreen: DEFAULT_FULLSCREEN,
+            fullscreen_mode: FullscreenMode::default(),
+            monitor: 0,
             pixel_snap_camera: DEFAULT_PIXEL_SNAP_CAMERA,
             render_target_filter: TextureFilter::default(),
+            render_scale: DEFAULT_RENDER_SCALE,
             background_color: DEFAULT_BACKGROUND_COLOR,
             window_title: DEFAULT_WINDOW_TITLE.to_string(),
+            master_volume: DEFAULT_VOLUME,
+            music_volume: DEFAULT_VOLUME,
+            sfx_volume: DEFAULT_VOLUME,
+            colorblind_mode: ColorblindMode::default(),
+            high_contrast_ui: DEFAULT_HIGH_CONTRAST_UI,
+            system_toggles: SystemToggles::default(),
+            auto_quality: DEFAULT_AUTO_QUALITY,
             config_path: PathBuf::from(DEFAULT_CONFIG_PATH),
         }
     }
@@ -170,12 +243,22 @@ impl GameConfig {
         if let Some(fps) = config.getuint("window", "target_fps").ok().flatten() {
             self.target_fps = fps as u32;
         }
+        if let Some(fps) = config.getuint("window", "background_fps").ok().flatten() {
+            self.background_fps = fps as u32;
+        }
         if let Some(vsync) = config.getbool("window", "vsync").ok().flatten() {
             self.vsync = vsync;
         }
         if let Some(fullscreen) = config.getbool("window", "fullscreen").ok().flatten() {
             self.fullscreen = fullscreen;
         }
+        if let Some(mode) = config.get("window", "fullscreen_mode") {
+            self.fullscreen_mode =
+                FullscreenMode::from_opt_str_or_warn(Some(&mode), "fullscreen_mode");
+        }
+        if let Some(monitor) = config.getuint("window", "monitor").ok().flatten() {
+            self.monitor = monitor as u32;
+        }
         if let Some(snap) = config.getbool("render", "pixel_snap_camera").ok().flatten() {
             self.pixel_snap_camera = snap;
         }
@@ -183,9 +266,40 @@ impl GameConfig {
             self.render_target_filter =
                 TextureFilter::from_opt_str_or_warn(Some(&filter_str), "render_target_filter");
         }
+        if let Some(scale) = config.getuint("render", "render_scale").ok().flatten() {
+            self.render_scale = clamp_render_scale(scale as u32);
+        }
         if let Some(title) = config.get("window", "title") {
             self.window_title = title;
         }
+        if let Some(vol) = config.getfloat("audio", "master_volume").ok().flatten() {
+            self.master_volume = (vol as f32).clamp(0.0, 1.0);
+        }
+        if let Some(vol) = config.getfloat("audio", "music_volume").ok().flatten() {
+            self.music_volume = (vol as f32).clamp(0.0, 1.0);
+        }
+        if let Some(vol) = config.getfloat("audio", "sfx_volume").ok().flatten() {
+            self.sfx_volume = (vol as f32).clamp(0.0, 1.0);
+        }
+        if let Some(mode) = config.get("accessibility", "colorblind_mode") {
+            self.colorblind_mode =
+                ColorblindMode::from_opt_str_or_warn(Some(&mode), "colorblind_mode");
+        }
+        if let Some(high_contrast) = config
+            .getbool("accessibility", "high_contrast_ui")
+            .ok()
+            .flatten()
+        {
+            self.high_contrast_ui = high_contrast;
+        }
+        for system in ToggleableSystem::ALL {
+            if let Some(enabled) = config.getbool("systems", system.as_str()).ok().flatten() {
+                self.system_toggles.set_enabled(system, enabled);
+            }
+        }
+        if let Some(auto) = config.getbool("systems", "auto_quality").ok().flatten() {
+            self.auto_quality = auto;
+        }
         info!(
             "Loaded config: {}x{} render, {}x{} window, fps={}, vsync={}, fullscreen={}, title={}",
             self.render_width,
@@ -221,10 +335,56 @@ impl GameConfig {
         config.set("window", "width", Some(self.window_width.to_string()));
         config.set("window", "height", Some(self.window_height.to_string()));
         config.set("window", "target_fps", Some(self.target_fps.to_string()));
+        config.set(
+            "window",
+            "background_fps",
+            Some(self.background_fps.to_string()),
+        );
         config.set("window", "vsync", Some(self.vsync.to_string()));
         config.set("window", "fullscreen", Some(self.fullscreen.to_string()));
+        config.set(
+            "window",
+            "fullscreen_mode",
+            Some(self.fullscreen_mode.as_str().to_string()),
+        );
+        config.set("window", "monitor", Some(self.monitor.to_string()));
         config.set("window", "title", Some(self.window_title.clone()));
 
+        // [audio] section
+        config.set(
+            "audio",
+            "master_volume",
+            Some(self.master_volume.to_string()),
+        );
+        config.set("audio", "music_volume", Some(self.music_volume.to_string()));
+        config.set("audio", "sfx_volume", Some(self.sfx_volume.to_string()));
+
+        // [accessibility] section
+        config.set(
+            "accessibility",
+            "colorblind_mode",
+            Some(self.colorblind_mode.as_str().to_string()),
+        );
+        config.set(
+            "accessibility",
+            "high_contrast_ui",
+            Some(self.high_contrast_ui.to_string()),
+        );
+
+        // [systems] section
+        for system in ToggleableSystem::ALL {
+            config.set(
+                "systems",
+                system.as_str(),
+                Some(self.system_toggles.is_enabled(system).to_string()),
+            );
+        }
+        config.set(
+            "systems",
+            "auto_quality",
+            Some(self.auto_quality.to_string()),
+        );
+
         config
             .write(&self.config_path)
             .map_err(|e| format!("Failed to save config file: {}", e))?;
@@ -250,6 +410,27 @@ impl GameConfig {
     pub fn window_size(&self) -> (u32, u32) {
         (self.window_width, self.window_height)
     }
+
+    /// Frame cap to apply for the given window focus state.
+    ///
+    /// `background_fps` while unfocused if it is set, `target_fps` otherwise.
+    pub fn frame_cap(&self, focused: bool) -> u32 {
+        if focused || self.background_fps == 0 {
+            self.target_fps
+        } else {
+            self.background_fps
+        }
+    }
+
+    /// Master, music and sfx volumes, in that order.
+    pub fn volumes(&self) -> (f32, f32, f32) {
+        (self.master_volume, self.music_volume, self.sfx_volume)
+    }
+}
+
+/// Clamp a requested supersampling factor to `1..=MAX_RENDER_SCALE`.
+pub fn clamp_render_scale(scale: u32) -> u32 {
+    scale.clamp(1, MAX_RENDER_SCALE)
 }
 
 #[cfg(test)]
@@ -426,6 +607,142 @@ mod tests {
         std::fs::remove_file(&path).ok();
     }
 
+    #[test]
+    fn test_audio_volumes_load_and_clamp() {
+        let mut config = GameConfig::new();
+        assert_eq!(config.volumes(), (1.0, 1.0, 1.0));
+        assert!(
+            config
+                .load_from_str(
+                    "[audio]\nmaster_volume = 0.5\nmusic_volume = 1.5\nsfx_volume = -1\n"
+                )
+                .is_ok()
+        );
+        assert_eq!(config.volumes(), (0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_audio_volumes_save_and_reload_roundtrip() {
+        let dir = std::env::temp_dir().join("aberred_test_config");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test_audio_roundtrip.ini");
+
+        let mut config = GameConfig::with_path(&path);
+        config.master_volume = 0.75;
+        config.music_volume = 0.25;
+        config.sfx_volume = 0.5;
+        config.save_to_file().unwrap();
+
+        let mut loaded = GameConfig::with_path(&path);
+        loaded.load_from_file().unwrap();
+        assert_eq!(loaded.volumes(), (0.75, 0.25, 0.5));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_accessibility_loads_from_ini() {
+        let mut config = GameConfig::new();
+        assert_eq!(config.colorblind_mode, ColorblindMode::None);
+        assert!(!config.high_contrast_ui);
+
+        config
+            .load_from_str(
+                "[accessibility]\ncolorblind_mode = deuteranopia\nhigh_contrast_ui = yes\n",
+            )
+            .unwrap();
+        assert_eq!(config.colorblind_mode, ColorblindMode::Deuteranopia);
+        assert!(config.high_contrast_ui);
+
+        config
+            .load_from_str("[accessibility]\ncolorblind_mode = sepia\n")
+            .unwrap();
+        assert_eq!(config.colorblind_mode, ColorblindMode::None);
+    }
+
+    #[test]
+    fn test_accessibility_save_and_reload_roundtrip() {
+        let dir = std::env::temp_dir().join("aberred_test_config");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test_accessibility_roundtrip.ini");
+
+        let mut config = GameConfig::with_path(&path);
+        config.colorblind_mode = ColorblindMode::Tritanopia;
+        config.high_contrast_ui = true;
+        config.save_to_file().unwrap();
+
+        let mut loaded = GameConfig::with_path(&path);
+        loaded.load_from_file().unwrap();
+        assert_eq!(loaded.colorblind_mode, ColorblindMode::Tritanopia);
+        assert!(loaded.high_contrast_ui);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_system_toggles_load_and_save_roundtrip() {
+        let mut config = GameConfig::new();
+        config
+            .load_from_str("[systems]\nparticles = false\nweather = true\nauto_quality = true\n")
+            .unwrap();
+        assert!(
+            !config
+                .system_toggles
+                .is_enabled(ToggleableSystem::Particles)
+        );
+        assert!(config.system_toggles.is_enabled(ToggleableSystem::Weather));
+        assert!(config.auto_quality);
+
+        let dir = std::env::temp_dir().join("aberred_test_config");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test_systems_roundtrip.ini");
+        config.config_path = path.clone();
+        config.save_to_file().unwrap();
+
+        let mut loaded = GameConfig::with_path(&path);
+        loaded.load_from_file().unwrap();
+        assert_eq!(loaded.system_toggles, config.system_toggles);
+        assert!(loaded.auto_quality);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_frame_cap_throttles_only_when_unfocused() {
+        let mut config = GameConfig::new();
+        config.target_fps = 144;
+        assert_eq!(config.frame_cap(false), 144);
+
+        config
+            .load_from_str("[window]\nbackground_fps = 15\n")
+            .unwrap();
+        assert_eq!(config.background_fps, 15);
+        assert_eq!(config.frame_cap(true), 144);
+        assert_eq!(config.frame_cap(false), 15);
+    }
+
+    #[test]
+    fn test_window_mode_save_and_reload_roundtrip() {
+        let dir = std::env::temp_dir().join("aberred_test_config");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test_window_mode_roundtrip.ini");
+
+        let mut config = GameConfig::with_path(&path);
+        assert_eq!(config.fullscreen_mode, FullscreenMode::Borderless);
+        config.fullscreen = true;
+        config.fullscreen_mode = FullscreenMode::Exclusive;
+        config.monitor = 2;
+        config.save_to_file().unwrap();
+
+        let mut loaded = GameConfig::with_path(&path);
+        loaded.load_from_file().unwrap();
+        assert!(loaded.fullscreen);
+        assert_eq!(loaded.fullscreen_mode, FullscreenMode::Exclusive);
+        assert_eq!(loaded.monitor, 2);
+
+        std::fs::remove_file(&path).ok();
+    }
+
     #[test]
     fn test_background_color_missing_keeps_default() {
         let dir = std::env::temp_dir().join("aberred_test_config");
@@ -502,6 +819,24 @@ mod tests {
         assert_eq!(config.render_target_filter, TextureFilter::Nearest);
     }
 
+    #[test]
+    fn test_render_scale_parses_and_clamps() {
+        let mut config = GameConfig::new();
+        assert_eq!(config.render_scale, 1);
+        config
+            .load_from_str("[render]\nrender_scale = 2\n")
+            .unwrap();
+        assert_eq!(config.render_scale, 2);
+        config
+            .load_from_str("[render]\nrender_scale = 9\n")
+            .unwrap();
+        assert_eq!(config.render_scale, MAX_RENDER_SCALE);
+        config
+            .load_from_str("[render]\nrender_scale = 0\n")
+            .unwrap();
+        assert_eq!(config.render_scale, 1);
+    }
+
     #[test]
     fn test_window_title_save_and_reload_roundtrip() {
         let dir = std::env::temp_dir().join("aberred_test_config");
@@ -36,11 +36,14 @@
 //! # Related
 //!
 //! - [`crate::systems::group::update_group_counts_system`] – the counting system
+//! - [`crate::systems::group::update_group_aggregates_system`] – fills [`GroupAggregates`]
+//!   for groups registered with [`TrackedGroups::add_aggregated_group`]
 //! - [`crate::resources::worldsignals::WorldSignals`] – where counts are published
 //! - [`crate::components::group::Group`] – the group tag component
 
 use bevy_ecs::prelude::*;
-use rustc_hash::FxHashSet;
+use raylib::prelude::Vector2;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Resource that holds the set of group names to track for entity counting.
 ///
@@ -53,6 +56,8 @@ use rustc_hash::FxHashSet;
 pub struct TrackedGroups {
     /// The set of group names currently being tracked.
     pub groups: FxHashSet<String>,
+    /// Tracked groups that also get spatial aggregates in [`GroupAggregates`].
+    pub aggregated: FxHashSet<String>,
 }
 
 impl TrackedGroups {
@@ -66,14 +71,29 @@ impl TrackedGroups {
         self.groups.contains(group_name.as_ref())
     }
 
+    /// Tracks a group and additionally computes its [`GroupAggregate`]
+    /// (centroid, bounding box, member positions) every frame.
+    pub fn add_aggregated_group(&mut self, group_name: impl Into<String>) {
+        let name = group_name.into();
+        self.aggregated.insert(name.clone());
+        self.groups.insert(name);
+    }
+
+    /// Returns `true` if aggregates are computed for the given group.
+    pub fn has_aggregates(&self, group_name: impl AsRef<str>) -> bool {
+        self.aggregated.contains(group_name.as_ref())
+    }
+
     /// Removes a group name from tracking.
     pub fn remove_group(&mut self, group_name: impl AsRef<str>) {
         self.groups.remove(group_name.as_ref());
+        self.aggregated.remove(group_name.as_ref());
     }
 
     /// Clears all tracked group names.
     pub fn clear(&mut self) {
         self.groups.clear();
+        self.aggregated.clear();
     }
 
     /// Returns an iterator over all tracked group names.
@@ -81,3 +101,128 @@ impl TrackedGroups {
         self.groups.iter()
     }
 }
+
+/// Spatial summary of one tracked group's members for the current frame.
+///
+/// Positions are world-space (`GlobalTransform2D` when present, otherwise
+/// `MapPosition`). Entities without a position are not included.
+#[derive(Debug, Clone, Default)]
+pub struct GroupAggregate {
+    /// Average member position.
+    pub centroid: Vector2,
+    /// Top-left corner of the axis-aligned box enclosing all member positions.
+    pub min: Vector2,
+    /// Bottom-right corner of the axis-aligned box enclosing all member positions.
+    pub max: Vector2,
+    /// Member entities and their world positions.
+    pub members: Vec<(Entity, Vector2)>,
+}
+
+impl GroupAggregate {
+    /// Number of positioned members.
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Recompute `centroid`/`min`/`max` from `members`.
+    pub fn recompute(&mut self) {
+        let zero = Vector2 { x: 0.0, y: 0.0 };
+        let Some(&(_, first)) = self.members.first() else {
+            self.centroid = zero;
+            self.min = zero;
+            self.max = zero;
+            return;
+        };
+        let (mut min, mut max, mut sum) = (first, first, zero);
+        for &(_, p) in &self.members {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+            sum.x += p.x;
+            sum.y += p.y;
+        }
+        let n = self.members.len() as f32;
+        self.centroid = Vector2 {
+            x: sum.x / n,
+            y: sum.y / n,
+        };
+        self.min = min;
+        self.max = max;
+    }
+
+    /// The member closest to `(x, y)` and its distance, or `None` if the group is empty.
+    pub fn nearest_to(&self, x: f32, y: f32) -> Option<(Entity, f32)> {
+        self.members
+            .iter()
+            .map(|&(e, p)| (e, (p.x - x).powi(2) + (p.y - y).powi(2)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(e, d2)| (e, d2.sqrt()))
+    }
+}
+
+/// Per-group spatial aggregates for groups registered via
+/// [`TrackedGroups::add_aggregated_group`], rebuilt every frame by
+/// [`update_group_aggregates_system`](crate::systems::group::update_group_aggregates_system).
+#[derive(Debug, Clone, Resource, Default)]
+pub struct GroupAggregates {
+    pub groups: FxHashMap<String, GroupAggregate>,
+}
+
+impl GroupAggregates {
+    /// Aggregate for `group_name`, if the group has aggregates enabled.
+    pub fn get(&self, group_name: &str) -> Option<&GroupAggregate> {
+        self.groups.get(group_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregate(points: &[(f32, f32)]) -> GroupAggregate {
+        let mut agg = GroupAggregate {
+            members: points
+                .iter()
+                .enumerate()
+                .map(|(i, &(x, y))| (Entity::from_bits(i as u64 + 1), Vector2 { x, y }))
+                .collect(),
+            ..Default::default()
+        };
+        agg.recompute();
+        agg
+    }
+
+    #[test]
+    fn aggregate_computes_centroid_and_bounds() {
+        let agg = aggregate(&[(0.0, 0.0), (10.0, 4.0), (2.0, -2.0)]);
+        assert_eq!(agg.count(), 3);
+        assert_eq!((agg.centroid.x, agg.centroid.y), (4.0, 2.0 / 3.0));
+        assert_eq!((agg.min.x, agg.min.y), (0.0, -2.0));
+        assert_eq!((agg.max.x, agg.max.y), (10.0, 4.0));
+    }
+
+    #[test]
+    fn aggregate_nearest_returns_closest_member() {
+        let agg = aggregate(&[(0.0, 0.0), (10.0, 0.0)]);
+        let (entity, dist) = agg.nearest_to(7.0, 0.0).unwrap();
+        assert_eq!(entity, agg.members[1].0);
+        assert_eq!(dist, 3.0);
+    }
+
+    #[test]
+    fn empty_aggregate_has_no_nearest() {
+        let agg = aggregate(&[]);
+        assert_eq!(agg.count(), 0);
+        assert!(agg.nearest_to(0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn remove_group_drops_aggregation() {
+        let mut tracked = TrackedGroups::default();
+        tracked.add_aggregated_group("enemy");
+        assert!(tracked.has_group("enemy") && tracked.has_aggregates("enemy"));
+        tracked.remove_group("enemy");
+        assert!(!tracked.has_aggregates("enemy"));
+    }
+}
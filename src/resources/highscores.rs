@@ -0,0 +1,175 @@
+//! Persistent high score table.
+//!
+//! [`HighScores`] keeps the best scores, highest first, each with a
+//! three-letter name. A table created with [`HighScores::load`] remembers its
+//! file and writes itself back there whenever a score is inserted; the
+//! default table lives only in memory.
+//!
+//! Scores enter the table through a
+//! [`NameEntry`](crate::components::nameentry::NameEntry) screen, opened by
+//! the [`SubmitScore`](crate::systems::nameentry::SubmitScore) command (Lua:
+//! `engine.submit_score(score)`), or directly with [`HighScores::insert`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut scores = HighScores::load("./highscores.json");
+//! if scores.qualifies(12_500) {
+//!     scores.insert("ACE", 12_500);
+//! }
+//! commands.insert_resource(scores);
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::prelude::Resource;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+
+/// File the engine loads the table from at startup.
+pub const DEFAULT_HIGH_SCORES_PATH: &str = "./highscores.json";
+
+/// Number of entries kept by default.
+pub const DEFAULT_HIGH_SCORES_CAPACITY: usize = 10;
+
+/// One line of the table.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: i64,
+}
+
+/// Best scores, highest first, optionally saved to a JSON file.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+    /// Maximum number of entries kept.
+    pub capacity: usize,
+    /// File written after each insert; `None` keeps the table in memory.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for HighScores {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity: DEFAULT_HIGH_SCORES_CAPACITY,
+            path: None,
+        }
+    }
+}
+
+impl HighScores {
+    /// Reads the table from `path`, starting empty when the file is missing
+    /// or unreadable. Later inserts are saved to the same file.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let mut scores = Self {
+            path: Some(path.to_path_buf()),
+            ..Self::default()
+        };
+        if !path.exists() {
+            return scores;
+        }
+        match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                serde_json::from_str::<Vec<HighScoreEntry>>(&text).map_err(|e| e.to_string())
+            }) {
+            Ok(mut entries) => {
+                entries.sort_by(|a, b| b.score.cmp(&a.score));
+                entries.truncate(scores.capacity);
+                scores.entries = entries;
+            }
+            Err(e) => warn!("Ignoring high scores in {}: {e}", path.display()),
+        }
+        scores
+    }
+
+    /// Writes the table to its file, if it has one.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text = serde_json::to_string_pretty(&self.entries)?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Entries, highest score first.
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    /// The best `n` entries.
+    pub fn top(&self, n: usize) -> &[HighScoreEntry] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+
+    /// Whether `score` would make it into the table.
+    pub fn qualifies(&self, score: i64) -> bool {
+        self.capacity > 0
+            && (self.entries.len() < self.capacity
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Adds a score below the older entries with the same score, drops the
+    /// lowest entry past the capacity and saves the table. Returns the
+    /// 0-based rank, or `None` when the score didn't qualify.
+    pub fn insert(&mut self, name: impl Into<String>, score: i64) -> Option<usize> {
+        if !self.qualifies(score) {
+            return None;
+        }
+        let rank = self.entries.partition_point(|e| e.score >= score);
+        self.entries.insert(
+            rank,
+            HighScoreEntry {
+                name: name.into(),
+                score,
+            },
+        );
+        self.entries.truncate(self.capacity);
+        if let Err(e) = self.save() {
+            error!("Failed to save high scores: {e}");
+        }
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_keeps_order_and_capacity() {
+        let mut scores = HighScores {
+            capacity: 3,
+            ..HighScores::default()
+        };
+        assert_eq!(scores.insert("AAA", 100), Some(0));
+        assert_eq!(scores.insert("BBB", 300), Some(0));
+        assert_eq!(scores.insert("CCC", 100), Some(2));
+        assert!(!scores.qualifies(100));
+        assert_eq!(scores.insert("DDD", 50), None);
+        assert_eq!(scores.insert("EEE", 200), Some(1));
+
+        let names: Vec<&str> = scores.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["BBB", "EEE", "AAA"]);
+        assert_eq!(scores.top(2).len(), 2);
+        assert_eq!(scores.top(9).len(), 3);
+    }
+
+    #[test]
+    fn load_reads_back_saved_entries() {
+        let path = std::env::temp_dir().join("highscores_round_trip_test.json");
+        let _ = std::fs::remove_file(&path);
+        let mut scores = HighScores::load(&path);
+        assert!(scores.entries().is_empty());
+        scores.insert("ACE", 900);
+        scores.insert("BOB", 1200);
+
+        let loaded = HighScores::load(&path);
+        assert_eq!(loaded.entries(), scores.entries());
+        assert_eq!(loaded.entries()[0].name, "BOB");
+    }
+}
@@ -6,6 +6,8 @@
 //! [`InputBindings`](crate::resources::input_bindings::InputBindings).
 use bevy_ecs::prelude::*;
 
+use crate::events::input::InputAction;
+
 #[derive(Debug, Clone, Copy, Default)]
 /// Transient boolean key state for a single logical action.
 ///
@@ -60,6 +62,50 @@ pub struct InputState {
     pub mouse_left_button: BoolState,
 }
 
+impl InputState {
+    /// State of a logical action.
+    pub fn action(&self, action: InputAction) -> &BoolState {
+        match action {
+            InputAction::MainDirectionUp => &self.maindirection_up,
+            InputAction::MainDirectionDown => &self.maindirection_down,
+            InputAction::MainDirectionLeft => &self.maindirection_left,
+            InputAction::MainDirectionRight => &self.maindirection_right,
+            InputAction::SecondaryDirectionUp => &self.secondarydirection_up,
+            InputAction::SecondaryDirectionDown => &self.secondarydirection_down,
+            InputAction::SecondaryDirectionLeft => &self.secondarydirection_left,
+            InputAction::SecondaryDirectionRight => &self.secondarydirection_right,
+            InputAction::Back => &self.action_back,
+            InputAction::Action1 => &self.action_1,
+            InputAction::Action2 => &self.action_2,
+            InputAction::Action3 => &self.action_3,
+            InputAction::Special => &self.action_special,
+            InputAction::ToggleDebug => &self.mode_debug,
+            InputAction::ToggleFullscreen => &self.fullscreen_toggle,
+        }
+    }
+
+    /// Mutable state of a logical action.
+    pub fn action_mut(&mut self, action: InputAction) -> &mut BoolState {
+        match action {
+            InputAction::MainDirectionUp => &mut self.maindirection_up,
+            InputAction::MainDirectionDown => &mut self.maindirection_down,
+            InputAction::MainDirectionLeft => &mut self.maindirection_left,
+            InputAction::MainDirectionRight => &mut self.maindirection_right,
+            InputAction::SecondaryDirectionUp => &mut self.secondarydirection_up,
+            InputAction::SecondaryDirectionDown => &mut self.secondarydirection_down,
+            InputAction::SecondaryDirectionLeft => &mut self.secondarydirection_left,
+            InputAction::SecondaryDirectionRight => &mut self.secondarydirection_right,
+            InputAction::Back => &mut self.action_back,
+            InputAction::Action1 => &mut self.action_1,
+            InputAction::Action2 => &mut self.action_2,
+            InputAction::Action3 => &mut self.action_3,
+            InputAction::Special => &mut self.action_special,
+            InputAction::ToggleDebug => &mut self.mode_debug,
+            InputAction::ToggleFullscreen => &mut self.fullscreen_toggle,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +153,12 @@ mod tests {
         assert!(!input.mouse_left_button.just_pressed);
         assert!(!input.mouse_left_button.just_released);
     }
+
+    #[test]
+    fn test_action_maps_to_its_field() {
+        let mut input = InputState::default();
+        input.action_2.just_pressed = true;
+        assert!(input.action(InputAction::Action2).just_pressed);
+        assert!(!input.action(InputAction::Action1).just_pressed);
+    }
 }
@@ -222,7 +222,8 @@ const KEY_NAME_TABLE: &[(&str, KeyboardKey)] = &[
 ///
 /// Returns `None` for unknown names. Names are lowercase, e.g. `"w"`, `"space"`,
 /// `"f11"`. Common aliases (`"return"` → `KEY_ENTER`, `"esc"` → `KEY_ESCAPE`) are
-/// accepted.
+/// accepted, and so are raylib's constant names for the keys in the table
+/// (`"KEY_SPACE"`, `"KEY_LEFT_SHIFT"`).
 pub fn key_from_str(s: &str) -> Option<KeyboardKey> {
     KEY_NAME_TABLE
         .iter()
@@ -233,8 +234,16 @@ pub fn key_from_str(s: &str) -> Option<KeyboardKey> {
             "esc" => Some(KeyboardKey::KEY_ESCAPE),
             "shift" => Some(KeyboardKey::KEY_LEFT_SHIFT),
             "ctrl" => Some(KeyboardKey::KEY_LEFT_CONTROL),
+            "alt" => Some(KeyboardKey::KEY_LEFT_ALT),
             _ => None,
         })
+        .or_else(|| {
+            // raylib's constant names are the enum variant names
+            KEY_NAME_TABLE
+                .iter()
+                .find(|(_, k)| s.starts_with("KEY_") && format!("{k:?}") == s)
+                .map(|(_, k)| *k)
+        })
 }
 
 /// Serialize a [`KeyboardKey`] to a canonical lowercase string.
@@ -254,12 +263,13 @@ pub fn key_to_str(k: KeyboardKey) -> &'static str {
 
 /// Parse a mouse button name into a [`MouseButton`].
 ///
-/// Accepted names: `"mouse_left"`, `"mouse_right"`, `"mouse_middle"`.
+/// Accepted names: `"mouse_left"`, `"mouse_right"`, `"mouse_middle"`, or
+/// raylib's `"MOUSE_BUTTON_LEFT"`, `"MOUSE_BUTTON_RIGHT"`, `"MOUSE_BUTTON_MIDDLE"`.
 pub fn mouse_button_from_str(s: &str) -> Option<MouseButton> {
     match s {
-        "mouse_left" => Some(MouseButton::MOUSE_BUTTON_LEFT),
-        "mouse_right" => Some(MouseButton::MOUSE_BUTTON_RIGHT),
-        "mouse_middle" => Some(MouseButton::MOUSE_BUTTON_MIDDLE),
+        "mouse_left" | "MOUSE_BUTTON_LEFT" => Some(MouseButton::MOUSE_BUTTON_LEFT),
+        "mouse_right" | "MOUSE_BUTTON_RIGHT" => Some(MouseButton::MOUSE_BUTTON_RIGHT),
+        "mouse_middle" | "MOUSE_BUTTON_MIDDLE" => Some(MouseButton::MOUSE_BUTTON_MIDDLE),
         _ => None,
     }
 }
@@ -532,6 +542,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_binding_from_str_raylib_names() {
+        assert_eq!(
+            binding_from_str("KEY_SPACE"),
+            Some(InputBinding::Keyboard(KeyboardKey::KEY_SPACE))
+        );
+        assert_eq!(
+            binding_from_str("KEY_LEFT_SHIFT"),
+            Some(InputBinding::Keyboard(KeyboardKey::KEY_LEFT_SHIFT))
+        );
+        assert_eq!(
+            binding_from_str("MOUSE_BUTTON_RIGHT"),
+            Some(InputBinding::MouseButton(MouseButton::MOUSE_BUTTON_RIGHT))
+        );
+        assert_eq!(binding_from_str("KEY_NOT_A_KEY"), None);
+    }
+
     #[test]
     fn test_binding_from_str_unknown_returns_none() {
         assert_eq!(binding_from_str(""), None);
@@ -0,0 +1,182 @@
+//! Named input contexts managed as a stack.
+//!
+//! An input context names the [`InputAction`]s that are live while it is on
+//! top of the [`InputContexts`] stack; every other action reads as released
+//! in [`InputState`]. Opening a dialogue pushes `"dialogue"`, which silences
+//! movement without each controller checking a flag, and popping it gives
+//! the actions back. With an empty stack every action is live.
+//!
+//! Three contexts are predefined and can be redefined:
+//!
+//! | Context | Live actions |
+//! |---------|--------------|
+//! | `gameplay` | all |
+//! | `menu` | both direction sets, `action_1`, `action_2`, `back` |
+//! | `dialogue` | `action_1`, `action_2`, `back` |
+//!
+//! The debug and fullscreen toggles are live in every context. The stack is
+//! cleared when the scene changes.
+//!
+//! # Example
+//!
+//! ```ignore
+//! contexts.define("map", [InputAction::MainDirectionUp, InputAction::MainDirectionDown, InputAction::Back]);
+//! contexts.push("dialogue");
+//! // ...
+//! contexts.pop();
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::inputcontext::input_context_system`] – applies the top context
+
+use bevy_ecs::prelude::Resource;
+use log::warn;
+use rustc_hash::FxHashMap;
+
+use crate::events::input::InputAction;
+use crate::resources::input::InputState;
+
+/// Named action sets and the stack of active contexts.
+#[derive(Resource, Debug, Clone)]
+pub struct InputContexts {
+    contexts: FxHashMap<String, Vec<InputAction>>,
+    stack: Vec<String>,
+    /// Scene the stack belongs to; a different scene clears it.
+    pub(crate) scene: Option<String>,
+}
+
+impl Default for InputContexts {
+    fn default() -> Self {
+        use InputAction::*;
+        let mut contexts = Self {
+            contexts: FxHashMap::default(),
+            stack: Vec::new(),
+            scene: None,
+        };
+        contexts.define("gameplay", InputAction::ALL);
+        contexts.define(
+            "menu",
+            [
+                MainDirectionUp,
+                MainDirectionDown,
+                MainDirectionLeft,
+                MainDirectionRight,
+                SecondaryDirectionUp,
+                SecondaryDirectionDown,
+                SecondaryDirectionLeft,
+                SecondaryDirectionRight,
+                Action1,
+                Action2,
+                Back,
+            ],
+        );
+        contexts.define("dialogue", [Action1, Action2, Back]);
+        contexts
+    }
+}
+
+impl InputContexts {
+    /// Defines (or redefines) the actions live in context `name`.
+    pub fn define(
+        &mut self,
+        name: impl Into<String>,
+        actions: impl IntoIterator<Item = InputAction>,
+    ) {
+        self.contexts
+            .insert(name.into(), actions.into_iter().collect());
+    }
+
+    /// Whether context `name` is defined.
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.contexts.contains_key(name)
+    }
+
+    /// Makes `name` the active context. Unknown names are ignored with a warning.
+    pub fn push(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        if !self.is_defined(&name) {
+            warn!("push_input_context: unknown context '{name}'");
+            return;
+        }
+        self.stack.push(name);
+    }
+
+    /// Removes the active context, returning its name.
+    pub fn pop(&mut self) -> Option<String> {
+        self.stack.pop()
+    }
+
+    /// Empties the stack, making every action live.
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+
+    /// Name of the active context, or `None` when the stack is empty.
+    pub fn active(&self) -> Option<&str> {
+        self.stack.last().map(String::as_str)
+    }
+
+    /// Whether `action` is live in the active context.
+    pub fn allows(&self, action: InputAction) -> bool {
+        if matches!(
+            action,
+            InputAction::ToggleDebug | InputAction::ToggleFullscreen
+        ) {
+            return true;
+        }
+        self.active()
+            .and_then(|name| self.contexts.get(name))
+            .is_none_or(|actions| actions.contains(&action))
+    }
+
+    /// Releases every action of `input` that the active context doesn't allow.
+    pub fn apply(&self, input: &mut InputState) {
+        if self.stack.is_empty() {
+            return;
+        }
+        for action in InputAction::ALL {
+            if !self.allows(action) {
+                *input.action_mut(action) = Default::default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_context_decides_which_actions_are_live() {
+        let mut contexts = InputContexts::default();
+        assert!(contexts.allows(InputAction::MainDirectionLeft));
+
+        contexts.push("gameplay");
+        contexts.push("dialogue");
+        assert_eq!(contexts.active(), Some("dialogue"));
+        assert!(!contexts.allows(InputAction::MainDirectionLeft));
+        assert!(contexts.allows(InputAction::Action1));
+        assert!(contexts.allows(InputAction::ToggleDebug));
+
+        let mut input = InputState::default();
+        input.maindirection_left.active = true;
+        input.action_1.just_pressed = true;
+        contexts.apply(&mut input);
+        assert!(!input.maindirection_left.active);
+        assert!(input.action_1.just_pressed);
+
+        assert_eq!(contexts.pop().as_deref(), Some("dialogue"));
+        assert!(contexts.allows(InputAction::MainDirectionLeft));
+    }
+
+    #[test]
+    fn unknown_contexts_are_not_pushed() {
+        let mut contexts = InputContexts::default();
+        contexts.push("cutscene");
+        assert_eq!(contexts.active(), None);
+        contexts.define("cutscene", [InputAction::Back]);
+        contexts.push("cutscene");
+        assert!(!contexts.allows(InputAction::Action1));
+    }
+}
@@ -0,0 +1,270 @@
+//! Key chord and sequence detection for cheat codes and combos.
+//!
+//! [`InputSequences`] holds named patterns over the [`InputAction`] stream.
+//! A pattern is a list of steps, each a chord of one or more actions: a step
+//! is matched on the frame one of its actions is pressed while the others are
+//! held. Any other press restarts the pattern, and the whole pattern must be
+//! entered within its `window` seconds. A completed pattern triggers an
+//! [`InputSequenceEvent`](crate::events::inputsequence::InputSequenceEvent).
+//!
+//! In text form (Lua, [`InputSequences::parse_steps`]) the actions of a chord
+//! are joined with `+`. A fighting-game quarter-circle forward punch:
+//!
+//! ```text
+//! main_down, main_down+main_right, main_right+action_1
+//! ```
+//!
+//! `"konami"` (up up down down left right left right, `action_2`,
+//! `action_1`) is predefined on the main directions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let steps = InputSequences::parse_steps(&["main_down", "main_down+main_right", "main_right+action_1"])?;
+//! sequences.define("hadouken", steps, 0.5);
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::systems::inputsequence::input_sequence_system`] – feeds the patterns each frame
+
+use bevy_ecs::prelude::Resource;
+
+use crate::events::input::InputAction;
+use crate::resources::input::InputState;
+
+/// One chord of a pattern: actions pressed together.
+pub type InputChord = Vec<InputAction>;
+
+/// A named pattern and how far it has been entered.
+#[derive(Debug, Clone)]
+pub struct InputPattern {
+    pub name: String,
+    pub steps: Vec<InputChord>,
+    /// Seconds allowed from the first step to the last.
+    pub window: f32,
+    /// Steps matched so far.
+    progress: usize,
+    /// Seconds since the first step was matched.
+    elapsed: f32,
+}
+
+impl InputPattern {
+    /// Whether `chord` is completed this frame: one of its actions was just
+    /// pressed and all of them are down.
+    fn chord_matches(chord: &InputChord, input: &InputState) -> bool {
+        chord
+            .iter()
+            .any(|&action| input.action(action).just_pressed)
+            && chord.iter().all(|&action| {
+                let state = input.action(action);
+                state.active || state.just_pressed
+            })
+    }
+
+    /// Advances the pattern by one frame; returns `true` when it completes.
+    fn feed(&mut self, input: &InputState, pressed: bool, delta: f32) -> bool {
+        if self.progress > 0 {
+            self.elapsed += delta;
+            if self.elapsed > self.window {
+                self.progress = 0;
+            }
+        }
+        if !pressed || self.steps.is_empty() {
+            return false;
+        }
+        if Self::chord_matches(&self.steps[self.progress], input) {
+            if self.progress == 0 {
+                self.elapsed = 0.0;
+            }
+            self.progress += 1;
+        } else if Self::chord_matches(&self.steps[0], input) {
+            // A wrong press that starts the pattern over.
+            self.progress = 1;
+            self.elapsed = 0.0;
+        } else {
+            self.progress = 0;
+        }
+        if self.progress == self.steps.len() {
+            self.progress = 0;
+            return true;
+        }
+        false
+    }
+}
+
+/// Named input patterns watched every frame.
+#[derive(Resource, Debug, Clone)]
+pub struct InputSequences {
+    patterns: Vec<InputPattern>,
+}
+
+impl Default for InputSequences {
+    fn default() -> Self {
+        use InputAction::*;
+        let mut sequences = Self {
+            patterns: Vec::new(),
+        };
+        sequences.define(
+            "konami",
+            [
+                MainDirectionUp,
+                MainDirectionUp,
+                MainDirectionDown,
+                MainDirectionDown,
+                MainDirectionLeft,
+                MainDirectionRight,
+                MainDirectionLeft,
+                MainDirectionRight,
+                Action2,
+                Action1,
+            ]
+            .into_iter()
+            .map(|action| vec![action])
+            .collect(),
+            5.0,
+        );
+        sequences
+    }
+}
+
+impl InputSequences {
+    /// Defines (or redefines) pattern `name`, to be entered within `window` seconds.
+    pub fn define(&mut self, name: impl Into<String>, steps: Vec<InputChord>, window: f32) {
+        let pattern = InputPattern {
+            name: name.into(),
+            steps,
+            window,
+            progress: 0,
+            elapsed: 0.0,
+        };
+        match self.patterns.iter_mut().find(|p| p.name == pattern.name) {
+            Some(existing) => *existing = pattern,
+            None => self.patterns.push(pattern),
+        }
+    }
+
+    /// Stops watching pattern `name`.
+    pub fn remove(&mut self, name: &str) {
+        self.patterns.retain(|p| p.name != name);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&InputPattern> {
+        self.patterns.iter().find(|p| p.name == name)
+    }
+
+    /// Parses steps written as action names, with the actions of a chord
+    /// joined by `+` (`"main_down+main_right"`).
+    pub fn parse_steps<S: AsRef<str>>(steps: &[S]) -> Result<Vec<InputChord>, String> {
+        steps
+            .iter()
+            .map(|step| {
+                step.as_ref()
+                    .split('+')
+                    .map(|name| {
+                        let name = name.trim();
+                        InputAction::from_name(name)
+                            .ok_or_else(|| format!("unknown action '{name}'"))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Feeds one frame of input to every pattern and returns the names of
+    /// the ones completed. The debug and fullscreen toggles are ignored.
+    pub fn update(&mut self, input: &InputState, delta: f32) -> Vec<String> {
+        let pressed = InputAction::ALL.into_iter().any(|action| {
+            !matches!(
+                action,
+                InputAction::ToggleDebug | InputAction::ToggleFullscreen
+            ) && input.action(action).just_pressed
+        });
+        self.patterns
+            .iter_mut()
+            .filter_map(|pattern| {
+                pattern
+                    .feed(input, pressed, delta)
+                    .then(|| pattern.name.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(
+        sequences: &mut InputSequences,
+        held: &[InputAction],
+        just: InputAction,
+    ) -> Vec<String> {
+        let mut input = InputState::default();
+        for &action in held {
+            input.action_mut(action).active = true;
+        }
+        let state = input.action_mut(just);
+        state.active = true;
+        state.just_pressed = true;
+        sequences.update(&input, 0.1)
+    }
+
+    #[test]
+    fn konami_code_fires_and_wrong_press_restarts() {
+        use InputAction::*;
+        let mut sequences = InputSequences::default();
+        let code = [
+            MainDirectionUp,
+            MainDirectionUp,
+            MainDirectionDown,
+            MainDirectionDown,
+            MainDirectionLeft,
+            MainDirectionRight,
+            MainDirectionLeft,
+            MainDirectionRight,
+            Action2,
+        ];
+        for action in code {
+            assert!(press(&mut sequences, &[], action).is_empty());
+        }
+        assert!(press(&mut sequences, &[], Back).is_empty());
+        assert!(press(&mut sequences, &[], Action1).is_empty());
+
+        for action in code {
+            press(&mut sequences, &[], action);
+        }
+        assert_eq!(press(&mut sequences, &[], Action1), ["konami"]);
+        assert_eq!(sequences.get("konami").unwrap().progress, 0);
+    }
+
+    #[test]
+    fn chords_and_window() {
+        use InputAction::*;
+        let mut sequences = InputSequences {
+            patterns: Vec::new(),
+        };
+        let steps = InputSequences::parse_steps(&[
+            "main_down",
+            "main_down+main_right",
+            "main_right + action_1",
+        ])
+        .unwrap();
+        sequences.define("qcf", steps, 0.25);
+
+        press(&mut sequences, &[], MainDirectionDown);
+        press(&mut sequences, &[MainDirectionDown], MainDirectionRight);
+        assert_eq!(
+            press(&mut sequences, &[MainDirectionRight], Action1),
+            ["qcf"]
+        );
+
+        // Too slow: three frames of 0.1 s exceed the window.
+        press(&mut sequences, &[], MainDirectionDown);
+        press(&mut sequences, &[MainDirectionDown], MainDirectionRight);
+        sequences.update(&InputState::default(), 0.1);
+        assert!(press(&mut sequences, &[MainDirectionRight], Action1).is_empty());
+
+        assert!(InputSequences::parse_steps(&["main_down+jump"]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Opt-in input analytics for playtests.
+//!
+//! While enabled, [`InputStats`] aggregates per scene how often each
+//! [`InputAction`] is pressed and for how long it is held, which is enough to
+//! spot unused buttons, mashing and controls players reach for too late.
+//! Unlike an [`InputReplay`](crate::resources::attractmode::InputReplay) it
+//! keeps no per-frame data, so it can stay on for a whole session.
+//!
+//! [`InputStats::save`] writes the totals as JSON:
+//!
+//! ```json
+//! { "scenes": { "level1": { "visits": 2, "seconds": 95.4, "actions": {
+//!     "action_1": { "presses": 41, "held_seconds": 6.2, "longest_hold": 0.8, "first_press": 1.3 }
+//! } } } }
+//! ```
+//!
+//! `first_press` is the seconds from entering the scene to the first press,
+//! on the first visit with one. Actions never pressed are left out. The demo
+//! of the attract mode is not recorded.
+//!
+//! # Related
+//!
+//! - [`crate::systems::inputstats::input_stats_system`] – records each frame
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use bevy_ecs::prelude::Resource;
+use serde::Serialize;
+
+use crate::events::input::InputAction;
+use crate::resources::input::InputState;
+
+/// Totals of one action in one scene.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ActionStats {
+    pub presses: u32,
+    /// Seconds the action was held in total.
+    pub held_seconds: f32,
+    /// Longest single hold in seconds.
+    pub longest_hold: f32,
+    /// Seconds from entering the scene to the first press.
+    pub first_press: Option<f32>,
+}
+
+/// Totals of one scene.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct SceneInputStats {
+    /// Times the scene was entered while recording.
+    pub visits: u32,
+    /// Seconds spent in the scene while recording.
+    pub seconds: f32,
+    /// Totals by action name.
+    pub actions: BTreeMap<String, ActionStats>,
+}
+
+/// Input analytics recorder. Records nothing until enabled.
+#[derive(Resource, Serialize, Debug, Clone, Default)]
+pub struct InputStats {
+    #[serde(skip)]
+    enabled: bool,
+    pub scenes: BTreeMap<String, SceneInputStats>,
+    /// Scene recorded last frame.
+    #[serde(skip)]
+    current: Option<String>,
+    /// Seconds into the current visit.
+    #[serde(skip)]
+    visit_seconds: f32,
+    /// Length of the running hold of each action, by `InputAction::ALL` index.
+    #[serde(skip)]
+    holds: [f32; InputAction::ALL.len()],
+}
+
+impl InputStats {
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.current = None;
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Drops everything recorded so far.
+    pub fn reset(&mut self) {
+        self.scenes.clear();
+        self.current = None;
+    }
+
+    /// Adds one frame of `input`, `delta` seconds long, spent in `scene`.
+    pub fn record(&mut self, scene: &str, input: &InputState, delta: f32) {
+        if !self.enabled {
+            return;
+        }
+        if self.current.as_deref() != Some(scene) {
+            self.current = Some(scene.to_string());
+            self.visit_seconds = 0.0;
+            self.holds = Default::default();
+            self.scenes.entry(scene.to_string()).or_default().visits += 1;
+        }
+        let stats = self.scenes.entry(scene.to_string()).or_default();
+        stats.seconds += delta;
+        self.visit_seconds += delta;
+
+        for (index, action) in InputAction::ALL.into_iter().enumerate() {
+            let state = input.action(action);
+            if !(state.active || state.just_pressed) {
+                self.holds[index] = 0.0;
+                continue;
+            }
+            let totals = stats.actions.entry(action.name().to_string()).or_default();
+            if state.just_pressed {
+                totals.presses += 1;
+                totals.first_press.get_or_insert(self.visit_seconds);
+                self.holds[index] = 0.0;
+            }
+            self.holds[index] += delta;
+            totals.held_seconds += delta;
+            totals.longest_hold = totals.longest_hold.max(self.holds[index]);
+        }
+    }
+
+    /// Writes the totals to `path` as pretty-printed JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize input stats: {e}"))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write input stats {}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(stats: &mut InputStats, scene: &str, held: bool, pressed: bool) {
+        let mut input = InputState::default();
+        input.action_1.active = held;
+        input.action_1.just_pressed = pressed;
+        stats.record(scene, &input, 0.5);
+    }
+
+    #[test]
+    fn counts_presses_and_holds_per_scene() {
+        let mut stats = InputStats::default();
+        frame(&mut stats, "menu", true, true);
+        assert!(stats.scenes.is_empty());
+
+        stats.set_enabled(true);
+        frame(&mut stats, "level1", false, false);
+        frame(&mut stats, "level1", true, true);
+        frame(&mut stats, "level1", true, false);
+        frame(&mut stats, "level1", false, false);
+        frame(&mut stats, "level1", true, true);
+        frame(&mut stats, "menu", false, false);
+        frame(&mut stats, "level1", true, true);
+
+        let level = &stats.scenes["level1"];
+        assert_eq!(level.visits, 2);
+        assert_eq!(level.seconds, 3.0);
+        assert_eq!(
+            level.actions["action_1"],
+            ActionStats {
+                presses: 3,
+                held_seconds: 2.0,
+                longest_hold: 1.0,
+                first_press: Some(1.0),
+            }
+        );
+        assert!(stats.scenes["menu"].actions.is_empty());
+
+        let path = std::env::temp_dir().join("input_stats_save_test.json");
+        stats.save(&path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            json["scenes"]["level1"]["actions"]["action_1"]["presses"],
+            3
+        );
+    }
+}
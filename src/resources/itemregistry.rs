@@ -0,0 +1,148 @@
+//! Item definitions shared by every [`Inventory`].
+//!
+//! An [`ItemDef`] describes a kind of item once: its display name, the icon
+//! drawn in inventory grids, how many fit in one slot and free-form
+//! properties (damage, price, "consumable", ...) for game code to read.
+//! Inventories only store item ids and counts; stacking rules come from the
+//! [`ItemRegistry`], so items must be registered before they can be added.
+//!
+//! Lua registers items with `engine.register_item(id, def)`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut items = ItemRegistry::default();
+//! items.register(
+//!     ItemDef::new("potion")
+//!         .with_name("Healing potion")
+//!         .with_icon("items", Some(Rectangle::new(0.0, 0.0, 16.0, 16.0)))
+//!         .with_max_stack(10)
+//!         .with_property("heal", BlackboardValue::Scalar(25.0)),
+//! );
+//! commands.insert_resource(items);
+//! ```
+//!
+//! [`Inventory`]: crate::components::inventory::Inventory
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Rectangle;
+use rustc_hash::FxHashMap;
+
+use crate::components::blackboard::BlackboardValue;
+use crate::key::Key;
+
+/// Icon drawn for an item in inventory grids.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemIcon {
+    /// Texture key in the `TextureStore`.
+    pub tex_key: Key,
+    /// Region of the texture to draw; `None` draws the whole texture.
+    pub source: Option<Rectangle>,
+}
+
+/// Definition of one kind of item. See the module docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDef {
+    pub id: Arc<str>,
+    /// Display name; defaults to the id.
+    pub name: String,
+    pub icon: Option<ItemIcon>,
+    /// Items per inventory slot (at least 1).
+    pub max_stack: u32,
+    /// Free-form values for game code.
+    pub properties: FxHashMap<String, BlackboardValue>,
+}
+
+impl ItemDef {
+    /// An unstackable item without icon or properties.
+    pub fn new(id: impl Into<Arc<str>>) -> Self {
+        let id = id.into();
+        Self {
+            name: id.to_string(),
+            id,
+            icon: None,
+            max_stack: 1,
+            properties: FxHashMap::default(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_icon(mut self, tex_key: impl Into<Key>, source: Option<Rectangle>) -> Self {
+        self.icon = Some(ItemIcon {
+            tex_key: tex_key.into(),
+            source,
+        });
+        self
+    }
+
+    pub fn with_max_stack(mut self, max_stack: u32) -> Self {
+        self.max_stack = max_stack.max(1);
+        self
+    }
+
+    pub fn with_property(mut self, key: impl Into<String>, value: BlackboardValue) -> Self {
+        self.properties.insert(key.into(), value);
+        self
+    }
+}
+
+/// Registered item definitions, keyed by id.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ItemRegistry {
+    items: FxHashMap<Arc<str>, ItemDef>,
+}
+
+impl ItemRegistry {
+    /// Add or replace the definition with the same id.
+    pub fn register(&mut self, def: ItemDef) {
+        self.items.insert(def.id.clone(), def);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ItemDef> {
+        self.items.get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.items.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_replaces_by_id() {
+        let mut items = ItemRegistry::default();
+        items.register(ItemDef::new("potion"));
+        assert_eq!(items.get("potion").map(|d| d.name.as_str()), Some("potion"));
+        assert_eq!(items.get("potion").map(|d| d.max_stack), Some(1));
+
+        items.register(
+            ItemDef::new("potion")
+                .with_name("Healing potion")
+                .with_max_stack(0)
+                .with_property("heal", BlackboardValue::Scalar(25.0)),
+        );
+        assert_eq!(items.len(), 1);
+        let def = items.get("potion").unwrap();
+        assert_eq!(def.name, "Healing potion");
+        assert_eq!(def.max_stack, 1, "stack size is at least 1");
+        assert_eq!(def.properties["heal"], BlackboardValue::Scalar(25.0));
+        assert!(!items.contains("sword"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Per-layer visibility toggles.
+//!
+//! [`LayerVisibility`] hides whole [`RenderLayer`]s at runtime, e.g. the `fx`
+//! layer for clean screenshots or the `debug` layer while debug mode stays on.
+//! Every layer starts visible. The render system skips drawables of hidden
+//! layers; they keep updating, colliding and animating as usual.
+//!
+//! Lua toggles layers with `engine.set_layer_visible(layer, visible)`. The
+//! setting survives scene switches.
+
+use bevy_ecs::prelude::Resource;
+
+use crate::components::renderlayer::RenderLayer;
+
+/// Hidden render layers, one bit per layer number. See the module docs.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayerVisibility {
+    hidden: [u64; 4],
+}
+
+impl LayerVisibility {
+    /// `true` unless `layer` was hidden.
+    pub fn is_visible(&self, layer: RenderLayer) -> bool {
+        let (word, bit) = Self::slot(layer);
+        self.hidden[word] & bit == 0
+    }
+
+    /// Show or hide `layer`.
+    pub fn set_visible(&mut self, layer: RenderLayer, visible: bool) {
+        let (word, bit) = Self::slot(layer);
+        if visible {
+            self.hidden[word] &= !bit;
+        } else {
+            self.hidden[word] |= bit;
+        }
+    }
+
+    /// Make every layer visible again.
+    pub fn show_all(&mut self) {
+        self.hidden = [0; 4];
+    }
+
+    /// `true` if `layer`, or `default` when the entity has no layer, is
+    /// visible.
+    pub fn shows(&self, layer: Option<&RenderLayer>, default: RenderLayer) -> bool {
+        self.is_visible(layer.copied().unwrap_or(default))
+    }
+
+    fn slot(layer: RenderLayer) -> (usize, u64) {
+        (usize::from(layer.0 / 64), 1 << (layer.0 % 64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_start_visible_and_toggle_independently() {
+        let mut layers = LayerVisibility::default();
+        assert!(layers.is_visible(RenderLayer::FX));
+
+        layers.set_visible(RenderLayer::FX, false);
+        layers.set_visible(RenderLayer(200), false);
+        assert!(!layers.is_visible(RenderLayer::FX));
+        assert!(!layers.is_visible(RenderLayer(200)));
+        assert!(layers.is_visible(RenderLayer::GAMEPLAY));
+        assert!(layers.is_visible(RenderLayer(66)));
+
+        layers.set_visible(RenderLayer::FX, true);
+        assert!(layers.is_visible(RenderLayer::FX));
+        layers.show_all();
+        assert!(layers.is_visible(RenderLayer(200)));
+    }
+
+    #[test]
+    fn entities_without_a_layer_use_the_default() {
+        let mut layers = LayerVisibility::default();
+        layers.set_visible(RenderLayer::UI, false);
+        assert!(!layers.shows(None, RenderLayer::UI));
+        assert!(layers.shows(None, RenderLayer::GAMEPLAY));
+        assert!(layers.shows(Some(&RenderLayer::FX), RenderLayer::UI));
+    }
+}
@@ -6,7 +6,25 @@
 use super::commands::*;
 use super::runtime::{LuaAppData, LuaRuntime, action_to_str};
 use super::spawn_data::*;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::inventory::Inventory;
+use crate::components::tags::Tags;
+use crate::components::tilemap::TileGrid;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fogofwar::FogOfWar;
+use crate::resources::fullscreen::{FullScreen, Monitors};
+use crate::resources::group::GroupAggregates;
+use crate::resources::highscores::HighScores;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::occluders::Occluders;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::spawnpoints::SpawnPoints;
+use crate::resources::texturestore::TextureStore;
+use crate::resources::weather::Weather;
+use crate::resources::worldclock::WorldClock;
 use crate::resources::worldsignals::SignalSnapshot;
+use bevy_ecs::prelude::Entity;
 use rustc_hash::FxHashSet;
 use std::cell::RefCell;
 use std::sync::Arc;
@@ -34,6 +52,53 @@ impl LuaRuntime {
 
     crate::lua_queues!{drain_methods}
 
+    /// Drains every subsystem queue (assets, maps, worlds, visibility, fog,
+    /// clock, weather, music, formation, inventory, ui, savegame, debug) into
+    /// `bufs` with a single borrow of the app data.
+    ///
+    /// Map commands stay queued unless `with_maps` is set and asset commands
+    /// unless `with_assets` is set: both need the raylib-backed stores, which
+    /// headless worlds don't have, and assets only load while playing.
+    pub fn drain_subsystem_commands_into(
+        &self,
+        bufs: &mut SubsystemCmdBufs,
+        with_assets: bool,
+        with_maps: bool,
+    ) {
+        let Some(data) = self.lua.app_data_ref::<LuaAppData>() else {
+            return;
+        };
+        if with_assets {
+            std::mem::swap(&mut bufs.assets, &mut *data.asset_commands.borrow_mut());
+        }
+        if with_maps {
+            std::mem::swap(&mut bufs.maps, &mut *data.map_commands.borrow_mut());
+        }
+        std::mem::swap(&mut bufs.worlds, &mut *data.world_commands.borrow_mut());
+        std::mem::swap(
+            &mut bufs.visibility,
+            &mut *data.visibility_commands.borrow_mut(),
+        );
+        std::mem::swap(&mut bufs.fog, &mut *data.fog_commands.borrow_mut());
+        std::mem::swap(&mut bufs.clock, &mut *data.clock_commands.borrow_mut());
+        std::mem::swap(&mut bufs.weather, &mut *data.weather_commands.borrow_mut());
+        std::mem::swap(&mut bufs.music, &mut *data.music_commands.borrow_mut());
+        std::mem::swap(
+            &mut bufs.formation,
+            &mut *data.formation_commands.borrow_mut(),
+        );
+        std::mem::swap(
+            &mut bufs.inventory,
+            &mut *data.inventory_commands.borrow_mut(),
+        );
+        std::mem::swap(&mut bufs.ui, &mut *data.ui_commands.borrow_mut());
+        std::mem::swap(
+            &mut bufs.savegame,
+            &mut *data.savegame_commands.borrow_mut(),
+        );
+        std::mem::swap(&mut bufs.debug, &mut *data.debug_commands.borrow_mut());
+    }
+
     // -------------------------------------------------------------------------
     // Queue management
     // -------------------------------------------------------------------------
@@ -50,6 +115,48 @@ impl LuaRuntime {
         }
     }
 
+    /// `true` once after `engine.dump_asset_usage()` was called.
+    pub fn take_asset_usage_dump_request(&self) -> bool {
+        self.lua
+            .app_data_ref::<LuaAppData>()
+            .is_some_and(|data| data.asset_usage_dump.take())
+    }
+
+    // -------------------------------------------------------------------------
+    // Spawn validation
+    // -------------------------------------------------------------------------
+
+    /// `true` if a texture or font load for `id` (or the creation of a
+    /// sub-world named `id`, whose view is drawn as a texture) is queued but
+    /// not processed yet.
+    ///
+    /// Spawn validation treats such keys as present: the asset will be in its
+    /// store before the entity is first drawn.
+    pub fn has_pending_asset(&self, id: &str) -> bool {
+        self.lua.app_data_ref::<LuaAppData>().is_some_and(|data| {
+            data.asset_commands.borrow().iter().any(|cmd| match cmd {
+                AssetCmd::Texture { id: key, .. }
+                | AssetCmd::TextTexture { id: key, .. }
+                | AssetCmd::Font { id: key, .. } => key == id,
+                _ => false,
+            }) || data
+                .world_commands
+                .borrow()
+                .iter()
+                .any(|cmd| matches!(cmd, WorldCmd::Create { name, .. } if name == id))
+        })
+    }
+
+    /// `true` if an animation registration for `id` is queued but not processed yet.
+    pub fn has_pending_animation(&self, id: &str) -> bool {
+        self.lua.app_data_ref::<LuaAppData>().is_some_and(|data| {
+            data.animation_commands
+                .borrow()
+                .iter()
+                .any(|AnimationCmd::RegisterAnimation { id: key, .. }| key == id)
+        })
+    }
+
     // -------------------------------------------------------------------------
     // Cache updates
     // -------------------------------------------------------------------------
@@ -87,6 +194,182 @@ impl LuaRuntime {
         }
     }
 
+    /// Updates the cached group aggregates that Lua reads via `engine.group_bounds()` and
+    /// `engine.group_nearest()`. Reuses the cached members' allocations.
+    pub fn update_group_aggregates_cache(&self, aggregates: &GroupAggregates) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.group_aggregates.borrow_mut().clone_from(&aggregates.groups);
+        }
+    }
+
+    /// Updates the cached spatial index that Lua reads via `engine.entities_in_rect()` and
+    /// `engine.nearest_entity()`. The cache shares the index's entries instead of copying them.
+    pub fn update_spatial_index_cache(&self, index: &SpatialIndex) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            *data.spatial_index.borrow_mut() = index.clone();
+        }
+    }
+
+    /// Replaces the cached tilemap grids and root transforms that Lua reads via
+    /// `engine.screen_to_tile()` and `engine.tile_to_world()`.
+    pub fn update_tilemap_cache<'a>(
+        &self,
+        tilemaps: impl IntoIterator<Item = (Entity, &'a TileGrid, &'a GlobalTransform2D)>,
+    ) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut cache = data.tilemaps.borrow_mut();
+            cache.clear();
+            cache.extend(
+                tilemaps
+                    .into_iter()
+                    .map(|(entity, grid, transform)| (entity.to_bits(), (*grid, *transform))),
+            );
+        }
+    }
+
+    /// Updates the cached fog-of-war state that Lua reads via `engine.is_revealed()`.
+    ///
+    /// The revealed cells are only copied when [`FogOfWar::revision`] changed.
+    pub fn update_fog_cache(&self, fog: &FogOfWar) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut snap = data.fog.borrow_mut();
+            snap.enabled = fog.enabled;
+            snap.cell_size = fog.cell_size();
+            if snap.revision != fog.revision() {
+                snap.revision = fog.revision();
+                snap.revealed.clear();
+                if let Some(cells) = fog.revealed_cells() {
+                    snap.revealed.extend(cells.iter().copied());
+                }
+            }
+        }
+    }
+
+    /// Updates the cached time of day that Lua reads via `engine.get_clock()`.
+    pub fn update_clock_cache(&self, clock: &WorldClock) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut snap = data.clock.borrow_mut();
+            snap.minutes = clock.minutes;
+            snap.day = clock.day;
+            snap.speed = clock.speed;
+            snap.paused = clock.paused;
+            snap.daylight = clock.daylight();
+        }
+    }
+
+    /// Updates the cached weather state that Lua reads via `engine.get_weather()`.
+    pub fn update_weather_cache(&self, weather: &Weather) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut snap = data.weather.borrow_mut();
+            snap.kind = weather.kind();
+            snap.intensity = weather.intensity(weather.kind());
+            snap.target_intensity = weather.target_intensity();
+            snap.wind = weather.wind();
+        }
+    }
+
+    /// Updates the cached item definitions that Lua reads via `engine.get_item()`.
+    pub fn update_item_cache(&self, items: &ItemRegistry) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.items.borrow_mut().clone_from(items);
+        }
+    }
+
+    /// Updates the cached spawn points that Lua reads via `engine.get_spawn_point()`.
+    pub fn update_spawn_point_cache(&self, spawn_points: &SpawnPoints) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.spawn_points.borrow_mut().clone_from(spawn_points);
+        }
+    }
+
+    /// Updates the cached high score table that Lua reads via `engine.get_high_scores()`.
+    pub fn update_high_score_cache(&self, high_scores: &HighScores) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            *data.high_scores.borrow_mut() = high_scores.entries().to_vec();
+        }
+    }
+
+    /// Updates the cached ECS statistics that Lua reads via `engine.ecs_stats()`.
+    pub fn update_ecs_stats_cache(&self, stats: &EcsStats) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.ecs_stats.borrow_mut().clone_from(stats);
+        }
+    }
+
+    /// Updates the cached texture sizes that Lua reads via `engine.texture_size()`.
+    pub fn update_texture_size_cache(&self, textures: &TextureStore) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut sizes = data.texture_sizes.borrow_mut();
+            sizes.clear();
+            sizes.extend(textures.map.iter().map(|(key, texture)| {
+                (key.clone(), (texture.width as f32, texture.height as f32))
+            }));
+        }
+    }
+
+    /// Updates the cached occluder segments and visibility polygons that Lua
+    /// reads via `engine.can_see()`.
+    ///
+    /// Polygons already cached are copied in place, reusing their point
+    /// buffers; entries of entities that lost their polygon are dropped.
+    pub fn update_visibility_cache<'a>(
+        &self,
+        occluders: &Occluders,
+        polygons: impl IntoIterator<Item = (Entity, &'a VisibilityPolygon)>,
+    ) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            let mut snap = data.visibility.borrow_mut();
+            let snap = &mut *snap;
+            snap.segments = Arc::clone(occluders.segments());
+            snap.live.clear();
+            for (entity, polygon) in polygons {
+                let id = entity.to_bits();
+                snap.live.insert(id);
+                match snap.polygons.get_mut(&id) {
+                    Some(cached) => cached.clone_from(polygon),
+                    None => {
+                        snap.polygons.insert(id, polygon.clone());
+                    }
+                }
+            }
+            if snap.polygons.len() > snap.live.len() {
+                let live = &snap.live;
+                snap.polygons.retain(|id, _| live.contains(id));
+            }
+        }
+    }
+
+    /// Records the current tags of `entity` for `engine.entity_has_tag()`.
+    pub fn set_entity_tags_cache(&self, entity: u64, tags: &Tags) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.entity_tags.borrow_mut().insert(entity, tags.clone());
+        }
+    }
+
+    /// Forgets the cached tags of `entity` (tags removed or entity despawned).
+    pub fn remove_entity_tags_cache(&self, entity: u64) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.entity_tags.borrow_mut().remove(&entity);
+        }
+    }
+
+    /// Records the current contents of the inventory on `entity` for
+    /// `engine.inventory_count()` and `engine.inventory_slots()`.
+    pub fn set_inventory_cache(&self, entity: u64, inventory: &Inventory) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.inventories
+                .borrow_mut()
+                .insert(entity, inventory.clone());
+        }
+    }
+
+    /// Forgets the cached inventory of `entity` (component removed or entity despawned).
+    pub fn remove_inventory_cache(&self, entity: u64) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.inventories.borrow_mut().remove(&entity);
+        }
+    }
+
     /// Updates the cached camera state snapshot that Lua reads via `engine.get_camera()` and
     /// `engine.get_camera_view_rect()`.
     ///
@@ -98,7 +381,7 @@ impl LuaRuntime {
     ///
     /// Note: if a script calls both `get_camera()` and `engine.set_camera()` in the same
     /// callback, `get_camera()` returns the pre-override values because camera write commands
-    /// are queued and applied in `process_lua_map_commands`, which runs after `lua_plugin::update`.
+    /// are queued and applied after `lua_plugin::update` returns.
     pub fn update_camera_cache(
         &self,
         camera: &crate::resources::camera2d::Camera2DRes,
@@ -133,19 +416,40 @@ impl LuaRuntime {
         }
     }
 
+    /// Updates the cached monitor list that Lua reads via `engine.get_monitors()`.
+    pub fn update_monitors_cache(&self, monitors: &Monitors) {
+        if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
+            data.gameconfig_snapshot
+                .borrow_mut()
+                .monitors
+                .clone_from(&monitors.0);
+        }
+    }
+
     /// Updates the cached game configuration snapshot that Lua can read.
     pub fn update_gameconfig_cache(&self, config: &crate::resources::gameconfig::GameConfig) {
         if let Some(data) = self.lua.app_data_ref::<LuaAppData>() {
             let mut snapshot = data.gameconfig_snapshot.borrow_mut();
             snapshot.fullscreen = config.fullscreen;
+            snapshot.window_mode = FullScreen::window_mode_str(FullScreen::from_config(config));
+            snapshot.monitor = config.monitor;
             snapshot.vsync = config.vsync;
             snapshot.target_fps = config.target_fps;
+            snapshot.background_fps = config.background_fps;
             snapshot.render_width = config.render_width;
             snapshot.render_height = config.render_height;
+            snapshot.render_scale = config.render_scale;
             snapshot.background_r = config.background_color.r;
             snapshot.background_g = config.background_color.g;
             snapshot.background_b = config.background_color.b;
             snapshot.pixel_snap_camera = config.pixel_snap_camera;
+            snapshot.master_volume = config.master_volume;
+            snapshot.music_volume = config.music_volume;
+            snapshot.sfx_volume = config.sfx_volume;
+            snapshot.colorblind_mode = config.colorblind_mode.as_str();
+            snapshot.high_contrast_ui = config.high_contrast_ui;
+            snapshot.system_toggles = config.system_toggles;
+            snapshot.auto_quality = config.auto_quality;
         }
     }
 }
@@ -5,7 +5,11 @@
 
 // Re-export UniformValue from its canonical location for internal convenience.
 pub use super::spawn_data::TweenConfig;
+pub use crate::components::blackboard::BlackboardValue;
 pub use crate::resources::uniformvalue::UniformValue;
+use crate::components::renderlayer::RenderLayer;
+use crate::resources::rendermode::RenderMode;
+use crate::resources::systemtoggles::ToggleableSystem;
 
 /// Commands that Lua can queue for asset loading.
 /// These are processed by Rust systems that have access to the necessary resources.
@@ -30,6 +34,16 @@ pub enum AssetCmd {
         id: String,
         vs_path: Option<String>,
         fs_path: Option<String>,
+    },    /// Render a line of text with a loaded font into a static texture
+    TextTexture {
+        id: String,
+        text: String,
+        font: String,
+        size: f32,
+        r: u8,
+        g: u8,
+        b: u8,
+        a: u8,
     },
 }
 
@@ -44,6 +58,12 @@ pub enum RenderCmd {
     ClearPostProcessUniform { name: String },
     /// Clear all uniforms from the post-process shader
     ClearPostProcessUniforms,
+    /// Switch between full and dirty-rectangle redraws for the current scene
+    SetRenderMode { mode: RenderMode },
+    /// Show or hide every drawable on a render layer
+    SetLayerVisible { layer: RenderLayer, visible: bool },
+    /// Switch the color grading LUT texture (None = ungraded), crossfading over `fade` seconds
+    SetColorLut { key: Option<String>, fade: f32 },
     /// Set the named theme's window panel nine-patch in `GuiThemeStore`
     SetGuiThemePanel {
         theme_key: String,
@@ -138,6 +158,18 @@ pub enum AudioLuaCmd {
     ResumeMusic { id: String },
     /// Set the volume of a specific music track (0.0 – 1.0)
     SetMusicVolume { id: String, vol: f32 },
+    /// Fade the music channel in from silence over `duration` seconds
+    FadeMusicIn { duration: f32 },
+    /// Fade the music channel out over `duration` seconds, then stop all music
+    FadeMusicOut { duration: f32 },
+    /// Set the tempo used to report beat/bar events for a music track (0 disables)
+    SetMusicBpm {
+        id: String,
+        bpm: f32,
+        beats_per_bar: u32,
+    },
+    /// Enable/disable tracker pattern/row/order reports for an .xm/.mod music track
+    SetMusicTrackerEvents { id: String, enabled: bool },
     /// Unload a specific music track from memory
     UnloadMusic { id: String },
     /// Unload all music tracks from memory
@@ -319,6 +351,10 @@ pub enum EntityCmd {
         entity_id: u64,
         max_speed: Option<f32>,
     },
+    /// Set mass on entity's RigidBody
+    SetMass { entity_id: u64, mass: f32 },
+    /// Change entity velocity by impulse / mass (RigidBody)
+    ApplyImpulse { entity_id: u64, x: f32, y: f32 },
     /// Freeze entity (skip physics calculations)
     FreezeEntity { entity_id: u64 },
     /// Unfreeze entity (resume physics calculations)
@@ -347,6 +383,20 @@ pub enum EntityCmd {
     SignalClearInteger { entity_id: u64, key: String },
     /// Insert a Ttl (time-to-live) component
     InsertTtl { entity_id: u64, seconds: f32 },
+    /// Enable or disable collision detection for an entity's BoxCollider
+    SetColliderEnabled { entity_id: u64, enabled: bool },
+    /// Enable or disable an entity's ForceArea
+    SetForceAreaEnabled { entity_id: u64, enabled: bool },
+    /// Start an entity's Cooldown over from its full duration
+    StartCooldown { entity_id: u64 },
+    /// Make an entity's Cooldown ready now
+    ResetCooldown { entity_id: u64 },
+    /// Remove an entity's DistanceJoint and PinJoint
+    RemoveJoints { entity_id: u64 },
+    /// Launch an entity's ZVelocity upward at `speed`
+    Jump { entity_id: u64, speed: f32 },
+    /// Set an entity's faux-3D Height
+    SetHeight { entity_id: u64, height: f32 },
     /// Set or replace entity shader
     SetShader { entity_id: u64, key: String },
     /// Remove entity shader
@@ -418,6 +468,49 @@ pub enum EntityCmd {
     },
     /// Remove CameraTarget component from an entity
     RemoveCameraTarget { entity_id: u64 },
+    /// Write a value to the entity's Blackboard (inserting one if missing),
+    /// optionally expiring after `ttl` seconds
+    BlackboardSet {
+        entity_id: u64,
+        key: String,
+        value: BlackboardValue,
+        ttl: Option<f32>,
+    },
+    /// Remove a key from the entity's Blackboard
+    BlackboardRemove { entity_id: u64, key: String },
+    /// Remove every key from the entity's Blackboard
+    BlackboardClear { entity_id: u64 },
+    /// Show or hide a layer of the tilemap rooted at the entity
+    TilemapSetLayerVisible {
+        entity_id: u64,
+        layer: String,
+        visible: bool,
+    },
+    /// Place a tile in a tilemap layer cell (`None` clears the cell)
+    TilemapSetTile {
+        entity_id: u64,
+        layer: String,
+        x: u32,
+        y: u32,
+        tile_id: Option<u32>,
+    },
+    /// Clear a circle of a terrain bitmap (world coordinates)
+    TerrainCarveCircle {
+        entity_id: u64,
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    /// Push a water surface's column at world x (positive velocity pushes down)
+    WaterSplash {
+        entity_id: u64,
+        x: f32,
+        velocity: f32,
+    },
+    /// Move every entity of a group (by group name or tag) by (dx, dy)
+    GroupTranslate { group: String, dx: f32, dy: f32 },
+    /// Show or hide the sprites and texts of every entity of a group
+    GroupSetVisible { group: String, visible: bool },
 }
 
 /// Commands for tracked groups from Lua.
@@ -426,12 +519,38 @@ pub enum GroupCmd {
     //TODO: Rename to TrackedGroupCmd
     /// Track a group for entity counting
     TrackGroup { name: String },
+    /// Track a group for entity counting plus per-frame centroid/bounds/nearest queries
+    TrackGroupBounds { name: String },
     /// Stop tracking a group
     UntrackGroup { name: String },
     /// Clear all tracked groups
     ClearTrackedGroups,
 }
 
+/// Commands for Lua collision rules from Lua.
+///
+/// Rules are selected by the group patterns they were declared with, in
+/// either order.
+#[derive(Debug, Clone)]
+pub enum CollisionRuleCmd {
+    /// Add a rule that is removed on the next scene switch
+    AddSceneRule {
+        group_a: String,
+        group_b: String,
+        callback: String,
+    },
+    /// Remove the rules declared for a group pair
+    RemoveRule { group_a: String, group_b: String },
+    /// Enable or disable the rules declared for a group pair
+    SetRuleEnabled {
+        group_a: String,
+        group_b: String,
+        enabled: bool,
+    },
+    /// Remove every Lua collision rule
+    ClearRules,
+}
+
 /// Commands for camera operations from Lua.
 #[derive(Debug, Clone)]
 pub enum CameraCmd {
@@ -444,6 +563,28 @@ pub enum CameraCmd {
         rotation: f32,
         zoom: f32,
     },
+    /// Shake the camera with `intensity` (0-1) fading out over `duration`
+    Shake { intensity: f32, duration: f32 },
+    /// Ease the camera effects' zoom factor to `zoom`
+    ZoomTo {
+        zoom: f32,
+        duration: f32,
+        easing: Option<String>,
+    },
+    /// Zoom in by `amount` at once and ease back
+    ZoomPunch { amount: f32, duration: f32 },
+    /// Blend the view to a world point
+    PanTo {
+        x: f32,
+        y: f32,
+        duration: f32,
+        easing: Option<String>,
+    },
+    /// Blend the view back to the camera target
+    ReleasePan {
+        duration: f32,
+        easing: Option<String>,
+    },
 }
 
 /// Commands for camera follow configuration from Lua.
@@ -509,21 +650,48 @@ pub struct CloneCmd {
 pub enum GameConfigCmd {
     /// Toggle fullscreen mode
     Fullscreen { enabled: bool },
+    /// Set the window mode ("windowed", "borderless", "fullscreen") and
+    /// optionally the fullscreen monitor
+    WindowMode { mode: String, monitor: Option<u32> },
     /// Toggle vertical sync
     Vsync { enabled: bool },
     /// Set target frames per second
     TargetFps { fps: u32 },
+    /// Set the frame cap while the window is unfocused (0 disables throttling)
+    BackgroundFps { fps: u32 },
     /// Set internal render resolution
     RenderSize { width: u32, height: u32 },
+    /// Set the render target supersampling factor (clamped to 1..=4)
+    RenderScale { scale: u32 },
     /// Set background clear color
     BackgroundColor { r: u8, g: u8, b: u8 },
     /// Toggle camera/view pixel snapping
     PixelSnapCamera { enabled: bool },
     /// Set the texture filter for the render-target-to-window blit
     RenderTargetFilter { filter: String },
+    /// Set the colorblind palette remap ("none", "protanopia", ...)
+    ColorblindMode { mode: String },
+    /// Toggle the high-contrast UI mode
+    HighContrastUi { enabled: bool },
+    /// Set the master volume (clamped to 0.0..=1.0)
+    MasterVolume { vol: f32 },
+    /// Set the music volume (clamped to 0.0..=1.0)
+    MusicVolume { vol: f32 },
+    /// Set the sound effects volume (clamped to 0.0..=1.0)
+    SfxVolume { vol: f32 },
+    /// Switch a non-essential system on or off
+    SystemEnabled {
+        system: ToggleableSystem,
+        enabled: bool,
+    },
+    /// Let the quality governor step quality down while frames are slow
+    AutoQuality { enabled: bool },
+    /// Write the current configuration back to its INI file
+    Save,
 }
 
-/// Commands for runtime input rebinding from Lua.
+/// Commands for runtime input rebinding, input contexts, sequences,
+/// analytics and local multiplayer devices from Lua.
 #[derive(Debug, Clone)]
 pub enum InputCmd {
     /// Rebind a logical action to a single new key (replaces all existing bindings).
@@ -534,6 +702,31 @@ pub enum InputCmd {
     Rebind { action: String, key: String },
     /// Add an extra binding for an action without removing the existing ones.
     AddBinding { action: String, key: String },
+    /// Make a named input context the active one.
+    PushInputContext { name: String },
+    /// Return to the input context below the active one.
+    PopInputContext,
+    /// Define (or redefine) the actions live in an input context.
+    DefineInputContext {
+        name: String,
+        actions: Vec<crate::events::input::InputAction>,
+    },
+    /// Define (or redefine) a key chord/sequence pattern.
+    DefineInputSequence {
+        name: String,
+        steps: Vec<crate::resources::inputsequence::InputChord>,
+        window: f32,
+    },
+    /// Turn input analytics recording on or off.
+    RecordInputStats { enabled: bool },
+    /// Write the recorded input analytics to a JSON file.
+    DumpInputStats { path: String },
+    /// Give a device to a local player (0-based), or take the player's
+    /// device away with `None`.
+    AssignDevice {
+        player: usize,
+        device: Option<crate::resources::playerinput::InputDevice>,
+    },
 }
 
 /// Commands for loading a map file and spawning its contents from Lua.
@@ -541,4 +734,229 @@ pub enum InputCmd {
 pub enum MapLuaCmd {
     /// Read a `MapData` JSON file from `path` and trigger [`SpawnMapRequested`].
     LoadMap { path: String },
+    /// Register auto-tiling rules for `tileset`, or remove them when `rules` is `None`.
+    SetAutoTile {
+        tileset: String,
+        rules: Option<crate::resources::autotile::AutoTileRules>,
+    },
+}
+
+/// Commands for the fog-of-war overlay ([`crate::resources::fogofwar::FogOfWar`]).
+#[derive(Debug, Clone)]
+pub enum FogCmd {
+    /// Turn the fog on with `cell_size`-unit cells; tiles of `solid_layers`
+    /// block vision sources created with `blocked`.
+    Enable {
+        cell_size: f32,
+        solid_layers: Vec<String>,
+    },
+    /// Turn the fog off, keeping the revealed cells.
+    Disable,
+    /// Forget the cells revealed in the current scene.
+    Reset,
+}
+
+/// Commands for line-of-sight blockers ([`crate::resources::occluders::Occluders`]).
+#[derive(Debug, Clone)]
+pub enum VisibilityCmd {
+    /// Block sight with the colliders of entities labeled `label` and the
+    /// tiles of the `solid_layers` tilemap layers.
+    SetOccluders {
+        label: Option<String>,
+        solid_layers: Vec<String>,
+    },
+}
+
+/// Commands for the in-game clock ([`crate::resources::worldclock::WorldClock`]).
+#[derive(Debug, Clone)]
+pub enum ClockCmd {
+    /// Jump to `minutes` since midnight.
+    SetTime(f32),
+    /// Set the in-game minutes per second.
+    SetSpeed(f32),
+    /// Stop or restart the clock.
+    SetPaused(bool),
+    /// Draw the day/night overlay, fading around `sunrise` and `sunset`
+    /// (minutes since midnight) to `color` at night.
+    SetAmbient {
+        sunrise: f32,
+        sunset: f32,
+        color: (u8, u8, u8, u8),
+    },
+    /// Stop drawing the day/night overlay.
+    DisableAmbient,
+    /// Call the global Lua function `callback` every day at `minute`.
+    At { minute: f32, callback: String },
+    /// Remove the scheduled calls to `callback`.
+    Cancel { callback: String },
+}
+
+/// Commands for rain, snow and wind ([`crate::resources::weather::Weather`]).
+#[derive(Debug, Clone)]
+pub enum WeatherCmd {
+    /// Fade `kind` to `intensity` and the other kinds out over `transition`
+    /// seconds.
+    Set {
+        kind: crate::resources::weather::WeatherKind,
+        intensity: f32,
+        transition: f32,
+    },
+    /// Fade the wind acceleration to `(x, y)` over `transition` seconds and
+    /// optionally change the group pattern it pushes.
+    Wind {
+        x: f32,
+        y: f32,
+        transition: f32,
+        mask: Option<String>,
+    },
+}
+
+/// Commands for scene playlists ([`crate::resources::scenemusic::SceneMusic`]).
+#[derive(Debug, Clone)]
+pub enum MusicCmd {
+    /// Set the playlist started when `scene` is entered, or forget it with
+    /// `None`.
+    SetScene {
+        scene: String,
+        playlist: Option<crate::resources::scenemusic::Playlist>,
+    },
+}
+
+/// Commands for named formations ([`crate::resources::formation::Formations`]).
+#[derive(Debug, Clone)]
+pub enum FormationCmd {
+    /// Create or replace the formation `name` led by `leader_id`.
+    Create {
+        name: String,
+        leader_id: u64,
+        pattern: crate::resources::formation::FormationPattern,
+        spacing: f32,
+        catch_up: f32,
+        rotate: bool,
+    },
+    /// Add `entity_id` to the next free slot of `name`.
+    Add { name: String, entity_id: u64 },
+    /// Take `entity_id` out of `name`.
+    Remove { name: String, entity_id: u64 },
+    /// Disband `name`, leaving its members in place.
+    Disband { name: String },
+}
+
+/// Commands for item definitions and [`crate::components::inventory::Inventory`]
+/// components.
+#[derive(Debug, Clone)]
+pub enum InventoryCmd {
+    /// Add or replace an item definition in the `ItemRegistry`.
+    RegisterItem(Box<crate::resources::itemregistry::ItemDef>),
+    /// Add `count` of `item` to the inventory of `entity_id`.
+    Add {
+        entity_id: u64,
+        item: String,
+        count: u32,
+    },
+    /// Remove up to `count` of `item` from the inventory of `entity_id`.
+    Remove {
+        entity_id: u64,
+        item: String,
+        count: u32,
+    },
+    /// Swap two slots (0-based) of the inventory of `entity_id`.
+    Swap { entity_id: u64, a: usize, b: usize },
+    /// Empty the inventory of `entity_id`.
+    Clear { entity_id: u64 },
+}
+
+/// Commands for writing and reading save slots from Lua.
+#[derive(Debug, Clone)]
+pub enum SaveGameCmd {
+    /// Write the game state to a save slot.
+    Save { slot: String },
+    /// Restore the game state from a save slot.
+    Load { slot: String },
+    /// Drop the state `SceneState` saved for one scene.
+    ForgetSceneState { scene: String },
+    /// Drop the state `SceneState` saved for every scene.
+    ClearSceneState,
+}
+
+/// Debugging commands from Lua.
+#[derive(Debug, Clone)]
+pub enum DebugCmd {
+    /// Record the world's entities and resources under a name.
+    Snapshot { name: String },
+    /// Log what changed in the world since a named snapshot.
+    SnapshotDiff { name: String },
+}
+
+/// Commands for the engine-driven UI screens and modes.
+#[derive(Debug, Clone)]
+pub enum UiCmd {
+    /// Open a results screen.
+    ShowResults(Box<crate::components::results::ResultsScreen>),
+    /// Start a credits roll.
+    ShowCredits(Box<crate::components::credits::CreditsRoll>),
+    /// Submit a score to the high score table, opening name entry if it qualifies.
+    SubmitScore {
+        score: i64,
+        callback: Option<String>,
+    },
+    /// Turn cutscene letterbox bars and the gameplay input lock on or off.
+    CinematicMode {
+        enabled: bool,
+        duration: Option<f32>,
+    },
+    /// Arm the attract mode in the current scene, or turn it off without a replay.
+    SetAttractMode {
+        replay_path: Option<String>,
+        idle_seconds: f32,
+    },
+}
+
+/// Commands for managing secondary worlds ([`crate::resources::worlds::Worlds`]) from Lua.
+#[derive(Debug, Clone)]
+pub enum WorldCmd {
+    /// Create (or replace) a sub-world rendered at `width`×`height` pixels.
+    Create {
+        name: String,
+        width: u32,
+        height: u32,
+    },
+    /// Remove a sub-world and all its entities.
+    Destroy { name: String },
+    /// Pause or resume a sub-world's simulation.
+    SetPaused { name: String, paused: bool },
+    /// Center a sub-world's camera on `(x, y)` with the given zoom.
+    SetCamera {
+        name: String,
+        x: f32,
+        y: f32,
+        zoom: f32,
+    },
+    /// Set a sub-world's clear color.
+    SetBackground { name: String, r: u8, g: u8, b: u8 },
+    /// Spawn an entity built with `engine.world_spawn(name)` into the sub-world.
+    Spawn { world: String, cmd: Box<SpawnCmd> },
+}
+
+/// Caller-owned buffers for the per-frame subsystem command queues.
+///
+/// Filled in a single pass by
+/// [`LuaRuntime::drain_subsystem_commands_into`](super::LuaRuntime::drain_subsystem_commands_into)
+/// and emptied by `apply_lua_commands`. The vectors keep their capacity
+/// across frames.
+#[derive(Debug, Default)]
+pub struct SubsystemCmdBufs {
+    pub assets: Vec<AssetCmd>,
+    pub maps: Vec<MapLuaCmd>,
+    pub worlds: Vec<WorldCmd>,
+    pub visibility: Vec<VisibilityCmd>,
+    pub fog: Vec<FogCmd>,
+    pub clock: Vec<ClockCmd>,
+    pub weather: Vec<WeatherCmd>,
+    pub music: Vec<MusicCmd>,
+    pub formation: Vec<FormationCmd>,
+    pub inventory: Vec<InventoryCmd>,
+    pub ui: Vec<UiCmd>,
+    pub savegame: Vec<SaveGameCmd>,
+    pub debug: Vec<DebugCmd>,
 }
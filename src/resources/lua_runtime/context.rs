@@ -15,6 +15,8 @@
 //! - Sprite: `sprite` with `tex_key`, `flip_h`, `flip_v`
 //! - Animation: `animation` with `key`, `frame_index`, `elapsed`
 //! - Signals: `signals` with `flags`, `integers`, `scalars`, `strings`
+//! - AI memory: `blackboard` mapping keys to booleans, numbers, strings,
+//!   `{x, y}` tables or entity IDs (from [`Blackboard`])
 //! - Phase: `phase`, `time_in_phase`, `previous_phase`
 //! - Timer: `timer` with `duration`, `elapsed`, `callback`
 //!
@@ -22,13 +24,14 @@
 //!
 //! [`build_entity_context_pooled`] uses pre-allocated tables from
 //! [`EntityCtxTables`](super::runtime::EntityCtxTables) to reduce Lua GC pressure on hot
-//! paths (phase updates, timer callbacks). Only signal inner maps are created fresh since
-//! they have variable keys per entity.
+//! paths (phase updates, timer callbacks). Only signal inner maps and blackboard `{x, y}`
+//! values are created fresh since they have variable keys per entity.
 //!
 //! **Important**: Pooled context tables are reused. Lua scripts must not store
 //! references to `ctx` or its subtables for later use.
 
 use super::runtime::{EntityCtxTables, SignalsCtxTables};
+use crate::components::blackboard::{Blackboard, BlackboardValue};
 use crate::components::signals::Signals;
 use mlua::{Lua, Result as LuaResult, Table as LuaTable, Value as LuaValue};
 use std::cell::RefCell;
@@ -95,6 +98,7 @@ pub struct EntitySnapshot<'a> {
     pub sprite: Option<SpriteSnapshot<'a>>,
     pub animation: Option<AnimationSnapshot<'a>>,
     pub signals: Option<&'a Signals>,
+    pub blackboard: Option<&'a Blackboard>,
     pub lua_phase: Option<LuaPhaseSnapshot<'a>>,
     pub lua_timer: Option<LuaTimerSnapshot<'a>>,
     pub previous_phase: Option<&'a str>,
@@ -198,10 +202,38 @@ pub(crate) fn populate_entity_signals(
     Ok(())
 }
 
+/// Populate the pooled blackboard table with one entry per key.
+///
+/// `Vec2` values become fresh `{x, y}` tables and `Entity` values their ID bits.
+pub(crate) fn populate_entity_blackboard(
+    lua: &Lua,
+    table: &LuaTable,
+    scratch: &RefCell<Vec<LuaValue>>,
+    blackboard: &Blackboard,
+) -> LuaResult<()> {
+    clear_map_table(table, scratch)?;
+    for (key, entry) in blackboard.iter() {
+        match &entry.value {
+            BlackboardValue::Scalar(v) => table.set(key, *v)?,
+            BlackboardValue::Integer(v) => table.set(key, *v)?,
+            BlackboardValue::Bool(v) => table.set(key, *v)?,
+            BlackboardValue::Text(v) => table.set(key, v.as_str())?,
+            BlackboardValue::Vec2(v) => {
+                let xy = lua.create_table()?;
+                xy.set("x", v.x)?;
+                xy.set("y", v.y)?;
+                table.set(key, xy)?;
+            }
+            BlackboardValue::Entity(e) => table.set(key, e.to_bits())?,
+        }
+    }
+    Ok(())
+}
+
 /// Builds an entity ctx snapshot table by reusing pooled tables/subtables —
 /// callers must not retain references past the callback (see `EntityCtxTables`).
 pub fn build_entity_context_pooled<'a>(
-    _lua: &Lua,
+    lua: &Lua,
     tables: &EntityCtxTables,
     snapshot: &EntitySnapshot<'a>,
 ) -> LuaResult<LuaTable> {
@@ -292,6 +324,17 @@ pub fn build_entity_context_pooled<'a>(
         tables.ctx.set("signals", tables.signals.clone())?;
     });
 
+    // AI blackboard (variable keys, cleared and refilled in place)
+    set_opt!(tables.ctx, "blackboard", snapshot.blackboard, board, {
+        populate_entity_blackboard(
+            lua,
+            &tables.blackboard,
+            &tables.signals_inner.scratch_keys,
+            board,
+        )?;
+        tables.ctx.set("blackboard", tables.blackboard.clone())?;
+    });
+
     // Phase info from LuaPhase (sets two ctx keys — not a single-key pattern)
     if let Some(phase) = snapshot.lua_phase.as_ref() {
         tables.ctx.set("phase", phase.current)?;
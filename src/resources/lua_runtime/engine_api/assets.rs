@@ -1,4 +1,5 @@
 use super::*;
+use crate::resources::autotile::{AutoTileMode, AutoTileRules};
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_asset_api(&self) -> LuaResult<()> {
@@ -53,10 +54,61 @@ impl LuaRuntime {
             cat = "asset",
             params = [("id", "string"), ("path", "string")]
         );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "bake_text_texture",
+            |lua, (id, text, font, size, color): (String, String, String, f32, Option<LuaTable>)| {
+                let (r, g, b, a) = match color {
+                    Some(color) => {
+                        let c: Vec<u8> = color.sequence_values::<u8>().collect::<LuaResult<_>>()?;
+                        if c.len() < 3 {
+                            return Err(LuaError::runtime(
+                                "bake_text_texture: color needs {r, g, b, a?}",
+                            ));
+                        }
+                        (c[0], c[1], c[2], *c.get(3).unwrap_or(&255))
+                    }
+                    None => (255, 255, 255, 255),
+                };
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .asset_commands
+                    .borrow_mut()
+                    .push(AssetCmd::TextTexture { id, text, font, size, r, g, b, a });
+                Ok(())
+            },
+            desc = "Render text with a loaded font into a static texture usable as a sprite. `color` is {r, g, b, a?} (default white)",
+            cat = "asset",
+            params = [
+                ("id", "string"),
+                ("text", "string"),
+                ("font", "string"),
+                ("size", "number"),
+                ("color", "table?")
+            ]
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "dump_asset_usage",
+            |lua, ()| {
+                if let Some(data) = lua.app_data_ref::<LuaAppData>() {
+                    data.asset_usage_dump.set(true);
+                }
+                Ok(())
+            },
+            desc = "Log every loaded texture, font, sound and music track with its estimated memory, entity references and last-used frame",
+            cat = "asset",
+            params = []
+        );
         Ok(())
     }
 
-    /// Registers `engine.load_map(path)` in the Lua `engine` table.
+    /// Registers `engine.load_map(path)` and `engine.tilemap_set_autotile(tileset, rules)`
+    /// in the Lua `engine` table.
     pub(in crate::resources::lua_runtime) fn register_map_api(&self) -> LuaResult<()> {
         let engine: LuaTable = self.lua.globals().get("engine")?;
         let meta: LuaTable = engine.get("__meta")?;
@@ -74,7 +126,78 @@ impl LuaRuntime {
             cat = "asset",
             params = [("path", "string")]
         );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "texture_size",
+            |lua, id: String| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let Some((width, height)) = data.texture_sizes.borrow().get(&id).copied() else {
+                    return Ok(None);
+                };
+                let tbl = lua.create_table()?;
+                tbl.set("width", width)?;
+                tbl.set("height", height)?;
+                Ok(Some(tbl))
+            },
+            desc = "Get the size of a loaded texture as {width, height} (nil if unknown; \
+                    textures loaded or baked this frame show up next frame)",
+            cat = "asset",
+            params = [("id", "string")],
+            returns = "table?"
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "tilemap_set_autotile",
+            |lua, (tileset, rules): (String, Option<LuaTable>)| {
+                let rules = rules.map(parse_autotile_rules).transpose()?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .map_commands
+                    .borrow_mut()
+                    .push(MapLuaCmd::SetAutoTile { tileset, rules });
+                Ok(())
+            },
+            desc = "Set the auto-tiling rules applied when tiles of a tileset are edited at runtime \
+                    ({mode = \"bitmask16\"|\"blob47\", tiles = {[mask] = id}, members?, layers?, \
+                    border_filled?}); nil removes them",
+            cat = "asset",
+            params = [("tileset", "string"), ("rules", "table?")]
+        );
 
         Ok(())
     }
 }
+
+/// Convert an `engine.tilemap_set_autotile()` rules table into [`AutoTileRules`].
+fn parse_autotile_rules(table: LuaTable) -> LuaResult<AutoTileRules> {
+    let mode: String = table.get("mode")?;
+    let mode = AutoTileMode::parse(&mode).ok_or_else(|| {
+        LuaError::runtime(format!(
+            "autotile mode must be \"bitmask16\" or \"blob47\", got '{mode}'"
+        ))
+    })?;
+    let tiles = table
+        .get::<LuaTable>("tiles")?
+        .pairs::<u8, u32>()
+        .collect::<LuaResult<_>>()?;
+    let mut rules = AutoTileRules::new(mode, tiles)
+        .map_err(LuaError::runtime)?
+        .with_members(
+            table
+                .get::<Option<Vec<u32>>>("members")?
+                .unwrap_or_default(),
+        );
+    if let Some(layers) = table.get::<Option<Vec<String>>>("layers")? {
+        rules = rules.with_layers(layers);
+    }
+    if let Some(border_filled) = table.get::<Option<bool>>("border_filled")? {
+        rules = rules.with_border_filled(border_filled);
+    }
+    Ok(rules)
+}
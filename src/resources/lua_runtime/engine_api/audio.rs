@@ -1,4 +1,5 @@
 use super::*;
+use crate::resources::scenemusic::{DEFAULT_CROSSFADE, Playlist};
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_audio_api(&self) -> LuaResult<()> {
@@ -90,6 +91,93 @@ impl LuaRuntime {
             cat = "audio",
             params = [("id", "string"), ("vol", "number")]
         );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "fade_in_music",
+            |lua, seconds: f32| {
+                let duration = fade_seconds("fade_in_music", seconds)?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .audio_commands
+                    .borrow_mut()
+                    .push(AudioLuaCmd::FadeMusicIn { duration });
+                Ok(())
+            },
+            desc = "Fade the music channel in from silence over `seconds`; call it right after \
+                    play_music",
+            cat = "audio",
+            params = [("seconds", "number")]
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "fade_out_music",
+            |lua, seconds: f32| {
+                let duration = fade_seconds("fade_out_music", seconds)?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .audio_commands
+                    .borrow_mut()
+                    .push(AudioLuaCmd::FadeMusicOut { duration });
+                Ok(())
+            },
+            desc = "Fade all playing music out over `seconds`, then stop it. play_music during \
+                    the fade cancels it",
+            cat = "audio",
+            params = [("seconds", "number")]
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "music_set_bpm",
+            audio_commands,
+            |(id, bpm, beats_per_bar)| (String, f32, Option<u32>),
+            AudioLuaCmd::SetMusicBpm {
+                id,
+                bpm,
+                beats_per_bar: beats_per_bar.unwrap_or(4),
+            },
+            desc = "Set the tempo of a music track so its playback emits beat/bar events \
+                    (beats_per_bar defaults to 4; bpm 0 disables)",
+            cat = "audio",
+            params = [("id", "string"), ("bpm", "number"), ("beats_per_bar", "integer?")]
+        );
+        register_hook_fn(
+            &self.lua,
+            &engine,
+            &meta_fns,
+            "on_beat",
+            "beat",
+            "Register a function called as fn(id, beat, bar, beat_in_bar) on every music beat \
+             (pass nil to remove it)",
+            "audio",
+        )?;
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "music_set_tracker_events",
+            audio_commands,
+            |(id, enabled)| (String, bool),
+            AudioLuaCmd::SetMusicTrackerEvents { id, enabled },
+            desc = "Enable/disable pattern/row/order reports for a loaded .xm/.mod music track",
+            cat = "audio",
+            params = [("id", "string"), ("enabled", "boolean")]
+        );
+        register_hook_fn(
+            &self.lua,
+            &engine,
+            &meta_fns,
+            "on_tracker_row",
+            "tracker_row",
+            "Register a function called as fn(id, order, pattern, row) whenever a tracker \
+             music track enters a new row (row 0 marks an order change; pass nil to remove it)",
+            "audio",
+        )?;
         register_cmd!(
             engine,
             self.lua,
@@ -138,6 +226,57 @@ impl LuaRuntime {
             cat = "audio",
             params = []
         );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_scene_music",
+            |lua, (scene, tracks, opts): (String, Option<Vec<String>>, Option<LuaTable>)| {
+                let playlist = match tracks {
+                    Some(tracks) => {
+                        let mut playlist = Playlist::new(tracks);
+                        if let Some(opts) = opts {
+                            playlist = playlist
+                                .with_shuffle(opts.get::<Option<bool>>("shuffle")?.unwrap_or(false))
+                                .with_crossfade(
+                                    opts.get::<Option<f32>>("crossfade")?
+                                        .unwrap_or(DEFAULT_CROSSFADE),
+                                )
+                                .with_volume(opts.get::<Option<f32>>("volume")?.unwrap_or(1.0));
+                        }
+                        Some(playlist)
+                    }
+                    None => None,
+                };
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .music_commands
+                    .borrow_mut()
+                    .push(MusicCmd::SetScene { scene, playlist });
+                Ok(())
+            },
+            desc = "Play a playlist of loaded music ids whenever `scene` is entered, crossfading \
+                    from the previous music and moving to the next track when one ends. opts: \
+                    {shuffle? (default false), crossfade? (seconds, default 1), volume? (default \
+                    1)}. A scene with the same playlist keeps the music playing; an empty list \
+                    fades it out; nil forgets the scene's playlist",
+            cat = "audio",
+            params = [
+                ("scene", "string"),
+                ("tracks", "string[]?"),
+                ("opts", "table?")
+            ]
+        );
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Validates a fade duration given to a Lua audio function.
+fn fade_seconds(fn_name: &str, seconds: f32) -> LuaResult<f32> {
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(LuaError::runtime(format!(
+            "{fn_name}: seconds must be a non-negative number, got {seconds}"
+        )));
+    }
+    Ok(seconds)
+}
@@ -60,6 +60,16 @@ impl LuaRuntime {
             debug,
             "Debug level logging"
         );
+        register_hook_fn(
+            &self.lua,
+            &engine,
+            &meta_fns,
+            "on_reload",
+            "reload",
+            "Register a function called as fn(path) after a changed script is hot-reloaded \
+             (pass nil to remove it)",
+            "base",
+        )?;
 
         self.lua.globals().set("engine", engine)?;
 
@@ -7,9 +7,12 @@ impl LuaRuntime {
         let meta_fns: LuaTable = meta.get("functions")?;
         define_camera_cmd_twins!(engine, self.lua, meta_fns, "", camera_commands, "camera", "");
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_camera",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let (target_x, target_y, offset_x, offset_y, rotation, zoom) = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| {
@@ -32,25 +35,23 @@ impl LuaRuntime {
                 tbl.set("rotation", rotation)?;
                 tbl.set("zoom", zoom)?;
                 Ok(tbl)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_camera",
-            "Get the current 2D camera state (target, offset, rotation, zoom). \
+            },
+            desc = "Get the current 2D camera state (target, offset, rotation, zoom). \
              Returns values from the start of this frame after camera_follow_system has run. \
              If called in the same callback as set_camera(), returns pre-override values. \
              Only available during on_update callbacks; returns defaults (zoom=1) from on_setup / on_switch_scene. \
              Each call returns a new table; cache locally if reading multiple fields.",
-            "camera",
-            &[],
-            Some("table"),
-        )?;
+            cat = "camera",
+            params = [],
+            returns = "table"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_camera_view_rect",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let (x, y, w, h) = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| {
@@ -64,23 +65,65 @@ impl LuaRuntime {
                 tbl.set("w", w)?;
                 tbl.set("h", h)?;
                 Ok(tbl)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_camera_view_rect",
-            "Get the visible world-space rectangle for the current camera: top-left corner (x, y) \
+            },
+            desc = "Get the visible world-space rectangle for the current camera: top-left corner (x, y) \
              plus visible dimensions (w, h) in world units. \
              Assumes zero camera rotation — under non-zero rotation the result is an axis-aligned \
              approximation only. \
              Only available during on_update callbacks; returns {{ x=0, y=0, w=0, h=0 }} from \
              on_setup / on_switch_scene. \
              Each call returns a new table; cache locally if reading multiple fields.",
-            "camera",
-            &[],
-            Some("table"),
-        )?;
+            cat = "camera",
+            params = [],
+            returns = "table"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "screen_to_tile",
+            |lua, (entity_id, x, y): (u64, f32, f32)| {
+                let cell = lua.app_data_ref::<LuaAppData>().and_then(|data| {
+                    let (grid, transform) = *data.tilemaps.borrow().get(&entity_id)?;
+                    let world = data.camera_snapshot.borrow().screen_to_world(x, y);
+                    grid.cell_at(transform.inverse_transform_point(world)?)
+                });
+                Ok(match cell {
+                    Some((cx, cy)) => (Some(cx), Some(cy)),
+                    None => (None, None),
+                })
+            },
+            desc = "Get the cell (x, y) of tilemap `entity_id` under a game-screen point such as \
+             input.analog.mouse_x/mouse_y, or nil outside the map. Handles orthogonal, isometric \
+             and hex projections and the root's position, scale and rotation. Uses the camera \
+             and tilemap state from the start of the frame",
+            cat = "camera",
+            params = [("entity_id", "integer"), ("x", "number"), ("y", "number")],
+            returns = "integer?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "tile_to_world",
+            |lua, (entity_id, x, y): (u64, i64, i64)| {
+                let center = lua.app_data_ref::<LuaAppData>().and_then(|data| {
+                    let (grid, transform) = *data.tilemaps.borrow().get(&entity_id)?;
+                    Some(transform.transform_point(grid.cell_center(x, y)))
+                });
+                Ok(match center {
+                    Some(p) => (Some(p.x), Some(p.y)),
+                    None => (None, None),
+                })
+            },
+            desc = "Get the world position of the centre of cell (x, y) of tilemap `entity_id`, \
+             or nil if it is not a spawned tilemap",
+            cat = "camera",
+            params = [("entity_id", "integer"), ("x", "integer"), ("y", "integer")],
+            returns = "number?"
+        );
 
         Ok(())
     }
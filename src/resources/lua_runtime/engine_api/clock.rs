@@ -0,0 +1,169 @@
+use super::*;
+use crate::resources::worldclock::{format_clock_time, parse_clock_time};
+
+impl LuaRuntime {
+    /// Registers the `engine.clock_*` functions and the daily `engine.at_time` schedule.
+    pub(in crate::resources::lua_runtime) fn register_clock_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_set_time",
+            clock_commands,
+            |time| String,
+            ClockCmd::SetTime(parse_time_arg(&time)?),
+            desc = "Jump the world clock to `time` (\"HH:MM\") without firing the events in \
+                    between",
+            cat = "clock",
+            params = [("time", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_set_speed",
+            clock_commands,
+            |minutes_per_second| f32,
+            ClockCmd::SetSpeed(minutes_per_second),
+            desc = "Set how many in-game minutes pass per second of world time (default 1)",
+            cat = "clock",
+            params = [("minutes_per_second", "number")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_pause",
+            clock_commands,
+            |()| (),
+            ClockCmd::SetPaused(true),
+            desc = "Stop the world clock and its scheduled events",
+            cat = "clock",
+            params = []
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_resume",
+            clock_commands,
+            |()| (),
+            ClockCmd::SetPaused(false),
+            desc = "Restart the world clock after clock_pause()",
+            cat = "clock",
+            params = []
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_set_ambient",
+            clock_commands,
+            |(sunrise, sunset, r, g, b, a)| (String, String, u8, u8, u8, u8),
+            ClockCmd::SetAmbient {
+                sunrise: parse_time_arg(&sunrise)?,
+                sunset: parse_time_arg(&sunset)?,
+                color: (r, g, b, a)
+            },
+            desc = "Darken the world at night with an (r, g, b, a) overlay, faded in and out over \
+                    an hour centred on `sunset` and `sunrise` (\"HH:MM\")",
+            cat = "clock",
+            params = [
+                ("sunrise", "string"),
+                ("sunset", "string"),
+                ("r", "integer"),
+                ("g", "integer"),
+                ("b", "integer"),
+                ("a", "integer")
+            ]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clock_disable_ambient",
+            clock_commands,
+            |()| (),
+            ClockCmd::DisableAmbient,
+            desc = "Stop drawing the day/night overlay",
+            cat = "clock",
+            params = []
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "at_time",
+            clock_commands,
+            |(time, callback)| (String, String),
+            ClockCmd::At {
+                minute: parse_time_arg(&time)?,
+                callback
+            },
+            desc = "Call the global function `callback(day, time)` every in-game day when the \
+                    world clock reaches `time` (\"HH:MM\")",
+            cat = "clock",
+            params = [("time", "string"), ("callback", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "cancel_at_time",
+            clock_commands,
+            |callback| String,
+            ClockCmd::Cancel { callback },
+            desc = "Remove every engine.at_time() entry calling `callback`",
+            cat = "clock",
+            params = [("callback", "string")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_clock",
+            |lua, ()| {
+                let snap = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| *data.clock.borrow())
+                    .unwrap_or_default();
+                let total = snap.minutes as u32;
+                let tbl = lua.create_table()?;
+                tbl.set("time", format_clock_time(snap.minutes))?;
+                tbl.set("hour", total / 60)?;
+                tbl.set("minute", total % 60)?;
+                tbl.set("day", snap.day)?;
+                tbl.set("speed", snap.speed)?;
+                tbl.set("paused", snap.paused)?;
+                tbl.set("daylight", snap.daylight)?;
+                Ok(tbl)
+            },
+            desc = "Get the world clock state at the start of the frame: {time = \"HH:MM\", hour, \
+                    minute, day, speed, paused, daylight} (daylight goes from 0 at night to 1 \
+                    at day)",
+            cat = "clock",
+            params = [],
+            returns = "table"
+        );
+
+        Ok(())
+    }
+}
+
+/// Parse an `"HH:MM"` argument, raising a Lua error on bad input.
+fn parse_time_arg(time: &str) -> LuaResult<f32> {
+    parse_clock_time(time)
+        .ok_or_else(|| LuaError::runtime(format!("time must be \"HH:MM\", got '{time}'")))
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+impl LuaRuntime {
+    /// Registers `engine.ecs_stats`, `engine.snapshot` and `engine.snapshot_diff`.
+    pub(in crate::resources::lua_runtime) fn register_debug_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "ecs_stats",
+            |lua, ()| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let stats = data.ecs_stats.borrow();
+                let tbl = lua.create_table()?;
+                tbl.set("entities", stats.entities)?;
+                tbl.set("archetypes", stats.archetypes)?;
+                tbl.set("spawned", stats.spawned)?;
+                tbl.set("despawned", stats.despawned)?;
+                tbl.set("component_bytes", stats.component_bytes())?;
+                let components = lua.create_table()?;
+                for component in &stats.components {
+                    let row = lua.create_table()?;
+                    row.set("name", component.name.as_str())?;
+                    row.set("entities", component.entities)?;
+                    row.set("bytes", component.bytes)?;
+                    components.push(row)?;
+                }
+                tbl.set("components", components)?;
+                Ok(tbl)
+            },
+            desc = "Get last frame's ECS statistics as {entities, archetypes, spawned, despawned, \
+                    component_bytes, components = {{name, entities, bytes}, ...}} (components largest first)",
+            cat = "debug",
+            params = [],
+            returns = "table"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "snapshot",
+            debug_commands,
+            |name| String,
+            DebugCmd::Snapshot { name },
+            desc = "Record every entity (group and components) and resource under a name, replacing \
+                    an older snapshot of that name. Taken when this frame's commands are applied",
+            cat = "debug",
+            params = [("name", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "snapshot_diff",
+            debug_commands,
+            |name| String,
+            DebugCmd::SnapshotDiff { name },
+            desc = "Log what changed since the named snapshot: entities spawned and despawned, \
+                    components added or removed, resources inserted or removed",
+            cat = "debug",
+            params = [("name", "string")]
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use super::*;
-use super::super::entity_builder::LuaEntityBuilder;
+use super::super::entity_builder::{LuaEntityBuilder, parse_group_pattern};
+use crate::resources::occluders::line_of_sight;
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_entity_api(&self) -> LuaResult<()> {
@@ -63,37 +64,181 @@ impl LuaRuntime {
             " (collision context)"
         );
 
-        engine.set(
-            "collision_spawn",
-            self.lua
-                .create_function(|_, ()| Ok(LuaEntityBuilder::new_collision()))?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "collision_spawn",
-            "Create a new entity builder (collision context)",
-            "collision",
-            &[],
-            Some("CollisionEntityBuilder"),
-        )?;
+            |_, ()| Ok(LuaEntityBuilder::new_collision()),
+            desc = "Create a new entity builder (collision context)",
+            cat = "collision",
+            params = [],
+            returns = "CollisionEntityBuilder"
+        );
 
-        engine.set(
-            "collision_clone",
-            self.lua.create_function(|_, source_key: String| {
-                Ok(LuaEntityBuilder::new_collision_clone(source_key))
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "collision_clone",
-            "Clone a registered entity (collision context)",
-            "collision",
-            &[("source_key", "string")],
-            Some("CollisionEntityBuilder"),
-        )?;
+            |_, source_key: String| Ok(LuaEntityBuilder::new_collision_clone(source_key)),
+            desc = "Clone a registered entity (collision context)",
+            cat = "collision",
+            params = [("source_key", "string")],
+            returns = "CollisionEntityBuilder"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "on_collision_scene",
+            collision_rule_commands,
+            |(group_a, group_b, callback)| (LuaValue, LuaValue, String),
+            CollisionRuleCmd::AddSceneRule {
+                group_a: parse_group_pattern(group_a, "group_a")?,
+                group_b: parse_group_pattern(group_b, "group_b")?,
+                callback
+            },
+            desc = "Call the global function callback(ctx) when the two groups collide, until the \
+                    next scene switch removes the rule. Sides take the same patterns as \
+                    with_lua_collision_rule",
+            cat = "collision",
+            params = [
+                ("group_a", "string|string[]"),
+                ("group_b", "string|string[]"),
+                ("callback", "string")
+            ]
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "remove_collision_rule",
+            collision_rule_commands,
+            |(group_a, group_b)| (String, String),
+            CollisionRuleCmd::RemoveRule { group_a, group_b },
+            desc = "Remove the Lua collision rules declared for a group pair (either order). \
+                    Patterns are compared as written, e.g. \"enemy_*\"",
+            cat = "collision",
+            params = [("group_a", "string"), ("group_b", "string")]
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_collision_rule_enabled",
+            collision_rule_commands,
+            |(group_a, group_b, enabled)| (String, String, bool),
+            CollisionRuleCmd::SetRuleEnabled {
+                group_a,
+                group_b,
+                enabled
+            },
+            desc = "Enable or disable the Lua collision rules declared for a group pair \
+                    (either order) without removing them",
+            cat = "collision",
+            params = [
+                ("group_a", "string"),
+                ("group_b", "string"),
+                ("enabled", "boolean")
+            ]
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "clear_collision_rules",
+            collision_rule_commands,
+            |()| (),
+            CollisionRuleCmd::ClearRules,
+            desc = "Remove every Lua collision rule",
+            cat = "collision",
+            params = []
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_occluders",
+            visibility_commands,
+            |(label, solid_layers)| (Option<String>, Option<Vec<String>>),
+            VisibilityCmd::SetOccluders {
+                label,
+                solid_layers: solid_layers.unwrap_or_default()
+            },
+            desc = "Block line of sight with the colliders of entities labeled `label` (group or tag) \
+                    and the tiles of the `solid_layers` tilemap layers. Used by :with_visibility() \
+                    polygons and engine.can_see(); call with no arguments to clear",
+            cat = "collision",
+            params = [("label", "string?"), ("solid_layers", "string[]?")]
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "can_see",
+            |lua, (a, b): (u64, u64)| {
+                let Some(data) = lua.app_data_ref::<LuaAppData>() else {
+                    return Ok(false);
+                };
+                let visibility = data.visibility.borrow();
+                let index = data.spatial_index.borrow();
+                let position = |id: u64| {
+                    visibility.polygons.get(&id).map(|p| p.origin).or_else(|| {
+                        index
+                            .entries()
+                            .iter()
+                            .find(|entry| entry.entity.to_bits() == id)
+                            .map(|entry| entry.pos)
+                    })
+                };
+                let (Some(from), Some(to)) = (position(a), position(b)) else {
+                    return Ok(false);
+                };
+                Ok(match visibility.polygons.get(&a) {
+                    Some(polygon) => polygon.contains(to),
+                    None => line_of_sight(&visibility.segments, from, to),
+                })
+            },
+            desc = "Check whether entity `a` can see entity `b`: inside a's :with_visibility() \
+                    polygon if it has one, otherwise with no occluder between them. Entities need a \
+                    visibility polygon, a group or tags to be found. Uses the state from the start \
+                    of the frame",
+            cat = "collision",
+            params = [("a", "integer"), ("b", "integer")],
+            returns = "boolean"
+        );
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Convert a Lua value into a [`BlackboardValue`]: booleans, numbers, strings
+/// and `{x, y}` tables are accepted.
+///
+/// Numbers always become `Scalar` (LuaJIT has no integer subtype); `Integer`
+/// entries can only be written from Rust.
+pub(super) fn parse_blackboard_value(value: LuaValue) -> LuaResult<BlackboardValue> {
+    match value {
+        LuaValue::Boolean(b) => Ok(BlackboardValue::Bool(b)),
+        LuaValue::Integer(n) => Ok(BlackboardValue::Scalar(n as f32)),
+        LuaValue::Number(n) => Ok(BlackboardValue::Scalar(n as f32)),
+        LuaValue::String(s) => Ok(BlackboardValue::Text(s.to_str()?.to_owned())),
+        LuaValue::Table(t) => Ok(BlackboardValue::Vec2(raylib::prelude::Vector2 {
+            x: t.get("x")?,
+            y: t.get("y")?,
+        })),
+        other => Err(LuaError::runtime(format!(
+            "blackboard value must be a boolean, number, string or {{x, y}} table, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+/// Convert an entity ID from Lua into a [`BlackboardValue::Entity`].
+fn parse_blackboard_entity(target_id: u64) -> LuaResult<BlackboardValue> {
+    bevy_ecs::entity::Entity::try_from_bits(target_id)
+        .map(BlackboardValue::Entity)
+        .ok_or_else(|| LuaError::runtime(format!("invalid entity id {target_id}")))
+}
@@ -1,4 +1,25 @@
 use super::*;
+use crate::resources::systemtoggles::ToggleableSystem;
+
+fn parse_system(fn_name: &str, name: &str) -> LuaResult<ToggleableSystem> {
+    ToggleableSystem::parse(name).ok_or_else(|| {
+        LuaError::runtime(format!(
+            "{fn_name}: unknown system '{name}' (expected one of: {})",
+            ToggleableSystem::names()
+        ))
+    })
+}
+
+/// Queue a [`GameConfigCmd::SystemEnabled`].
+fn push_system_enabled(lua: &Lua, fn_name: &str, name: &str, enabled: bool) -> LuaResult<()> {
+    let system = parse_system(fn_name, name)?;
+    lua.app_data_ref::<LuaAppData>()
+        .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+        .gameconfig_commands
+        .borrow_mut()
+        .push(GameConfigCmd::SystemEnabled { system, enabled });
+    Ok(())
+}
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_gameconfig_api(&self) -> LuaResult<()> {
@@ -31,9 +52,12 @@ impl LuaRuntime {
             params = [("enabled", "boolean")]
         );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "set_target_fps",
-            self.lua.create_function(|lua, fps: Option<u32>| {
+            |lua, fps: Option<u32>| {
                 let fps = fps.unwrap_or(60);
                 lua.app_data_ref::<LuaAppData>()
                     .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
@@ -41,57 +65,146 @@ impl LuaRuntime {
                     .borrow_mut()
                     .push(GameConfigCmd::TargetFps { fps });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
+            },
+            desc = "Set target FPS (0 = uncapped, nil resets to 60)",
+            cat = "render",
+            params = [("fps", "integer?")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_background_fps",
+            gameconfig_commands,
+            |fps| u32,
+            GameConfigCmd::BackgroundFps { fps },
+            desc = "Set the frame cap used while the window is unfocused (0 disables throttling)",
+            cat = "render",
+            params = [("fps", "integer")]
+        );
+        register_hook_fn(
             &self.lua,
+            &engine,
             &meta_fns,
-            "set_target_fps",
-            "Set target FPS (nil resets to 60)",
+            "on_focus_changed",
+            "focus_changed",
+            "Register a function called as fn(focused) when the window gains or loses focus \
+             (pass nil to remove it)",
             "render",
-            &[("fps", "integer?")],
-            None,
         )?;
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_fullscreen",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| data.gameconfig_snapshot.borrow().fullscreen)
                     .unwrap_or(false);
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_fullscreen",
-            "Get current fullscreen state",
-            "render",
-            &[],
-            Some("boolean"),
-        )?;
+            },
+            desc = "Get current fullscreen state",
+            cat = "render",
+            params = [],
+            returns = "boolean"
+        );
 
-        engine.set(
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_window_mode",
+            gameconfig_commands,
+            |(mode, monitor)| (String, Option<u32>),
+            GameConfigCmd::WindowMode { mode, monitor },
+            desc = "Set the window mode (\"windowed\", \"borderless\", \"fullscreen\") and optionally the 0-based monitor index used for fullscreen",
+            cat = "render",
+            params = [("mode", "string"), ("monitor", "integer?")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_window_mode",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().window_mode)
+                    .unwrap_or("windowed");
+                Ok(value)
+            },
+            desc = "Get the current window mode (\"windowed\", \"borderless\" or \"fullscreen\")",
+            cat = "render",
+            params = [],
+            returns = "string"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_monitor",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().monitor)
+                    .unwrap_or(0);
+                Ok(value)
+            },
+            desc = "Get the 0-based index of the monitor used for fullscreen",
+            cat = "render",
+            params = [],
+            returns = "integer"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_monitors",
+            |lua, ()| {
+                let result = lua.create_table()?;
+                if let Some(data) = lua.app_data_ref::<LuaAppData>() {
+                    let snap = data.gameconfig_snapshot.borrow();
+                    for (i, monitor) in snap.monitors.iter().enumerate() {
+                        let entry = lua.create_table()?;
+                        entry.set("index", i)?;
+                        entry.set("name", monitor.name.as_str())?;
+                        entry.set("width", monitor.width)?;
+                        entry.set("height", monitor.height)?;
+                        entry.set("refresh_rate", monitor.refresh_rate)?;
+                        result.set(i + 1, entry)?;
+                    }
+                }
+                Ok(result)
+            },
+            desc = "Get the connected monitors as an array of {index, name, width, height, refresh_rate} (index is 0-based, as in set_window_mode)",
+            cat = "render",
+            params = [],
+            returns = "table"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_vsync",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| data.gameconfig_snapshot.borrow().vsync)
                     .unwrap_or(false);
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_vsync",
-            "Get current vsync state",
-            "render",
-            &[],
-            Some("boolean"),
-        )?;
+            },
+            desc = "Get current vsync state",
+            cat = "render",
+            params = [],
+            returns = "boolean"
+        );
 
         register_cmd!(
             engine,
@@ -119,49 +232,66 @@ impl LuaRuntime {
             params = [("filter", "string")]
         );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_pixel_snap_camera",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| data.gameconfig_snapshot.borrow().pixel_snap_camera)
                     .unwrap_or(true);
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_pixel_snap_camera",
-            "Get whether the camera/view rect is snapped to integer pixels",
-            "render",
-            &[],
-            Some("boolean"),
-        )?;
+            },
+            desc = "Get whether the camera/view rect is snapped to integer pixels",
+            cat = "render",
+            params = [],
+            returns = "boolean"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_target_fps",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| data.gameconfig_snapshot.borrow().target_fps)
                     .unwrap_or(60);
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_target_fps",
-            "Get current target FPS",
-            "render",
-            &[],
-            Some("integer"),
-        )?;
+            },
+            desc = "Get current target FPS",
+            cat = "render",
+            params = [],
+            returns = "integer"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_background_fps",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().background_fps)
+                    .unwrap_or(0);
+                Ok(value)
+            },
+            desc = "Get the frame cap used while the window is unfocused (0 = no throttling)",
+            cat = "render",
+            params = [],
+            returns = "integer"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "set_render_size",
-            self.lua.create_function(|lua, (width, height): (u32, u32)| {
+            |lua, (width, height): (u32, u32)| {
                 let width = width.clamp(120, 7680);
                 let height = height.clamp(120, 4320);
                 lua.app_data_ref::<LuaAppData>()
@@ -170,21 +300,49 @@ impl LuaRuntime {
                     .borrow_mut()
                     .push(GameConfigCmd::RenderSize { width, height });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "set_render_size",
-            "Set internal render resolution (min 120x120, max 7680x4320)",
-            "render",
-            &[("width", "integer"), ("height", "integer")],
-            None,
-        )?;
+            },
+            desc = "Set internal render resolution (min 120x120, max 7680x4320)",
+            cat = "render",
+            params = [("width", "integer"), ("height", "integer")]
+        );
 
-        engine.set(
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_render_scale",
+            gameconfig_commands,
+            |scale| u32,
+            GameConfigCmd::RenderScale { scale },
+            desc = "Set the render target supersampling factor (1-4): the scene is drawn at that multiple of the render size and downscaled to the window; logical coordinates do not change",
+            cat = "render",
+            params = [("scale", "integer")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_render_scale",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().render_scale)
+                    .unwrap_or(1);
+                Ok(value)
+            },
+            desc = "Get the render target supersampling factor",
+            cat = "render",
+            params = [],
+            returns = "integer"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_render_size",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let (w, h) = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| {
@@ -196,42 +354,68 @@ impl LuaRuntime {
                 table.set("width", w)?;
                 table.set("height", h)?;
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
+            },
+            desc = "Get current internal render resolution",
+            cat = "render",
+            params = [],
+            returns = "table"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_internal_resolution",
+            |lua, (width, height): (u32, u32)| {
+                let width = width.clamp(120, 7680);
+                let height = height.clamp(120, 4320);
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .gameconfig_commands
+                    .borrow_mut()
+                    .push(GameConfigCmd::RenderSize { width, height });
+                Ok(())
+            },
+            desc = "Switch the internal render resolution at runtime, recreating the render target \
+             and firing on_resolution_changed (min 120x120, max 7680x4320)",
+            cat = "render",
+            params = [("width", "integer"), ("height", "integer")]
+        );
+        register_hook_fn(
             &self.lua,
+            &engine,
             &meta_fns,
-            "get_render_size",
-            "Get current internal render resolution",
+            "on_resolution_changed",
+            "resolution_changed",
+            "Register a function called as fn(width, height, old_width, old_height) after the \
+             internal render resolution changes (pass nil to remove it)",
             "render",
-            &[],
-            Some("table"),
         )?;
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "set_background_color",
-            self.lua.create_function(|lua, (r, g, b): (u8, u8, u8)| {
+            |lua, (r, g, b): (u8, u8, u8)| {
                 lua.app_data_ref::<LuaAppData>()
                     .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
                     .gameconfig_commands
                     .borrow_mut()
                     .push(GameConfigCmd::BackgroundColor { r, g, b });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "set_background_color",
-            "Set background clear color (RGB 0-255)",
-            "render",
-            &[("r", "integer"), ("g", "integer"), ("b", "integer")],
-            None,
-        )?;
+            },
+            desc = "Set background clear color (RGB 0-255)",
+            cat = "render",
+            params = [("r", "integer"), ("g", "integer"), ("b", "integer")]
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_background_color",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let (r, g, b) = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| {
@@ -244,18 +428,269 @@ impl LuaRuntime {
                 table.set("g", g)?;
                 table.set("b", b)?;
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
+            },
+            desc = "Get current background clear color",
+            cat = "render",
+            params = [],
+            returns = "table"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_colorblind_mode",
+            gameconfig_commands,
+            |mode| String,
+            GameConfigCmd::ColorblindMode { mode },
+            desc = "Set the colorblind palette remap run after the post-process chain (\"none\", \"protanopia\", \"deuteranopia\", \"tritanopia\", \"grayscale\")",
+            cat = "render",
+            params = [("mode", "string")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_colorblind_mode",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().colorblind_mode)
+                    .unwrap_or("none");
+                Ok(value)
+            },
+            desc = "Get the current colorblind palette remap mode",
+            cat = "render",
+            params = [],
+            returns = "string"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_high_contrast_ui",
+            gameconfig_commands,
+            |enabled| bool,
+            GameConfigCmd::HighContrastUi { enabled },
+            desc = "Draw screen-space text on opaque plates and menus in a fixed high-contrast color pair",
+            cat = "render",
+            params = [("enabled", "boolean")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_high_contrast_ui",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().high_contrast_ui)
+                    .unwrap_or(false);
+                Ok(value)
+            },
+            desc = "Get whether the high-contrast UI mode is enabled",
+            cat = "render",
+            params = [],
+            returns = "boolean"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_volume_master",
+            gameconfig_commands,
+            |vol| f32,
+            GameConfigCmd::MasterVolume { vol },
+            desc = "Set the master volume (0.0-1.0, clamped; persisted by save_config)",
+            cat = "audio",
+            params = [("vol", "number")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_volume_master",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().master_volume)
+                    .unwrap_or(1.0);
+                Ok(value)
+            },
+            desc = "Get the current master volume (0.0-1.0)",
+            cat = "audio",
+            params = [],
+            returns = "number"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_volume_music",
+            gameconfig_commands,
+            |vol| f32,
+            GameConfigCmd::MusicVolume { vol },
+            desc = "Set the music volume (0.0-1.0, clamped; persisted by save_config)",
+            cat = "audio",
+            params = [("vol", "number")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_volume_music",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().music_volume)
+                    .unwrap_or(1.0);
+                Ok(value)
+            },
+            desc = "Get the current music volume (0.0-1.0)",
+            cat = "audio",
+            params = [],
+            returns = "number"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_volume_sfx",
+            gameconfig_commands,
+            |vol| f32,
+            GameConfigCmd::SfxVolume { vol },
+            desc = "Set the sound effects volume (0.0-1.0, clamped; persisted by save_config)",
+            cat = "audio",
+            params = [("vol", "number")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_volume_sfx",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().sfx_volume)
+                    .unwrap_or(1.0);
+                Ok(value)
+            },
+            desc = "Get the current sound effects volume (0.0-1.0)",
+            cat = "audio",
+            params = [],
+            returns = "number"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "disable_system",
+            |lua, name: String| push_system_enabled(lua, "disable_system", &name, false),
+            desc = "Switch off a non-essential system (\"particles\", \"weather\", \"color_grading\") from the next frame; persisted by save_config",
+            cat = "render",
+            params = [("name", "string")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "enable_system",
+            |lua, name: String| push_system_enabled(lua, "enable_system", &name, true),
+            desc = "Switch a system turned off by disable_system() or the config file back on",
+            cat = "render",
+            params = [("name", "string")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "is_system_enabled",
+            |lua, name: String| {
+                let system = parse_system("is_system_enabled", &name)?;
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().system_toggles.is_enabled(system))
+                    .unwrap_or(true);
+                Ok(value)
+            },
+            desc = "Get whether a non-essential system is enabled",
+            cat = "render",
+            params = [("name", "string")],
+            returns = "boolean"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_auto_quality",
+            gameconfig_commands,
+            |enabled| bool,
+            GameConfigCmd::AutoQuality { enabled },
+            desc = "Step particle density and post-processing down while frames are over budget, \
+             and back up when they recover; turning it off restores full quality",
+            cat = "render",
+            params = [("enabled", "boolean")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_auto_quality",
+            |lua, ()| {
+                let value = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| data.gameconfig_snapshot.borrow().auto_quality)
+                    .unwrap_or(false);
+                Ok(value)
+            },
+            desc = "Get whether automatic quality scaling is enabled",
+            cat = "render",
+            params = [],
+            returns = "boolean"
+        );
+        register_hook_fn(
             &self.lua,
+            &engine,
             &meta_fns,
-            "get_background_color",
-            "Get current background clear color",
+            "on_quality_changed",
+            "quality_changed",
+            "Register a function called as fn(level, old_level, frame_ms) when automatic quality \
+             scaling changes level; 0 is full quality (pass nil to remove it)",
             "render",
-            &[],
-            Some("table"),
         )?;
 
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "save_config",
+            |lua, ()| {
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .gameconfig_commands
+                    .borrow_mut()
+                    .push(GameConfigCmd::Save);
+                Ok(())
+            },
+            desc = "Write the current game configuration (video and audio settings) to config.ini",
+            cat = "base",
+            params = []
+        );
+
         Ok(())
     }
 }
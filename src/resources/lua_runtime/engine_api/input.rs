@@ -1,9 +1,12 @@
 use super::*;
+use crate::resources::inputsequence::InputSequences;
 use crate::resources::lua_runtime::action_from_str;
 use crate::resources::lua_runtime::runtime::action_to_str;
+use crate::resources::playerinput::{InputDevice, MAX_PLAYERS};
 
 impl LuaRuntime {
-    /// Registers the input rebinding API in the `engine` table.
+    /// Registers the input rebinding, context, sequence, analytics and local
+    /// multiplayer API in the `engine` table.
     pub(in crate::resources::lua_runtime) fn register_input_api(&self) -> LuaResult<()> {
         let engine: LuaTable = self.lua.globals().get("engine")?;
         let meta: LuaTable = engine.get("__meta")?;
@@ -22,6 +25,20 @@ impl LuaRuntime {
             params = [("action", "string"), ("key", "string")]
         );
 
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "bind_action",
+            input_commands,
+            |(action, key)| (String, String),
+            InputCmd::Rebind { action, key },
+            desc = "Bind a logical action to a key, replacing its bindings (same as rebind_action). \
+                    Keys may also use raylib names such as \"KEY_SPACE\"",
+            cat = "input",
+            params = [("action", "string"), ("key", "string")]
+        );
+
         register_cmd!(
             engine,
             self.lua,
@@ -35,9 +52,12 @@ impl LuaRuntime {
             params = [("action", "string"), ("key", "string")]
         );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_binding",
-            self.lua.create_function(|lua, action: String| {
+            |lua, action: String| {
                 let canonical = action_from_str(&action)
                     .map(action_to_str)
                     .unwrap_or(action.as_str());
@@ -45,17 +65,182 @@ impl LuaRuntime {
                     .app_data_ref::<LuaAppData>()
                     .and_then(|data| data.bindings_snapshot.borrow().get(canonical).cloned());
                 Ok(result)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_binding",
-            "Get the first key binding for an action as a string (nil if unbound)",
-            "input",
-            &[("action", "string")],
-            Some("string?"),
-        )?;
+            },
+            desc = "Get the first key binding for an action as a string (nil if unbound)",
+            cat = "input",
+            params = [("action", "string")],
+            returns = "string?"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "push_input_context",
+            input_commands,
+            |name| String,
+            InputCmd::PushInputContext { name },
+            desc = "Make an input context (\"gameplay\", \"menu\", \"dialogue\" or one from \
+                    define_input_context) the active one; actions it doesn't list read as \
+                    released until it is popped. The stack is cleared on scene change",
+            cat = "input",
+            params = [("name", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "pop_input_context",
+            input_commands,
+            |()| (),
+            InputCmd::PopInputContext,
+            desc = "Return to the input context below the active one",
+            cat = "input",
+            params = []
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "define_input_context",
+            |lua, (name, names): (String, Vec<String>)| {
+                let actions = names
+                    .iter()
+                    .filter_map(|action| {
+                        let a = action_from_str(action);
+                        if a.is_none() {
+                            log::warn!("define_input_context: unknown action '{}'", action);
+                        }
+                        a
+                    })
+                    .collect();
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .input_commands
+                    .borrow_mut()
+                    .push(InputCmd::DefineInputContext { name, actions });
+                Ok(())
+            },
+            desc = "Define (or redefine) an input context as the list of action names live \
+                    while it is active; the debug and fullscreen toggles are always live",
+            cat = "input",
+            params = [("name", "string"), ("actions", "string[]")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "define_sequence",
+            |lua, (name, steps, window): (String, Vec<String>, Option<f32>)| {
+                let steps = InputSequences::parse_steps(&steps)
+                    .map_err(|e| LuaError::runtime(format!("define_sequence: {e}")))?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .input_commands
+                    .borrow_mut()
+                    .push(InputCmd::DefineInputSequence {
+                        name,
+                        steps,
+                        window: window.unwrap_or(2.0),
+                    });
+                Ok(())
+            },
+            desc = "Define (or redefine) a named input sequence: a list of steps entered within \
+                    window seconds (default 2), each an action name or a chord of names joined \
+                    with '+' (\"main_down+main_right\"). \"konami\" is predefined",
+            cat = "input",
+            params = [
+                ("name", "string"),
+                ("steps", "string[]"),
+                ("window", "number?")
+            ]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "on_sequence",
+            |lua, (name, callback): (String, Option<LuaFunction>)| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let mut hooks = data.sequence_hooks.borrow_mut();
+                match callback {
+                    Some(f) => hooks.insert(name, f),
+                    None => hooks.remove(&name),
+                };
+                Ok(())
+            },
+            desc = "Call fn(name) whenever the named input sequence is entered; nil removes it",
+            cat = "input",
+            params = [("name", "string"), ("callback", "function?")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "record_input_stats",
+            input_commands,
+            |enabled| bool,
+            InputCmd::RecordInputStats { enabled },
+            desc = "Start (true) or stop (false) recording per-scene action press counts and hold \
+                    times for playtest analysis",
+            cat = "input",
+            params = [("enabled", "boolean")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "dump_input_stats",
+            input_commands,
+            |path| String,
+            InputCmd::DumpInputStats { path },
+            desc = "Write the input stats recorded so far to a JSON file",
+            cat = "input",
+            params = [("path", "string")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "assign_device",
+            |lua, (player, device): (usize, Option<String>)| {
+                if !(1..=MAX_PLAYERS).contains(&player) {
+                    return Err(LuaError::runtime(format!(
+                        "assign_device: player must be 1 to {MAX_PLAYERS}, got {player}"
+                    )));
+                }
+                let device = device
+                    .map(|name| {
+                        InputDevice::from_name(&name).ok_or_else(|| {
+                            LuaError::runtime(format!("assign_device: unknown device '{name}'"))
+                        })
+                    })
+                    .transpose()?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .input_commands
+                    .borrow_mut()
+                    .push(InputCmd::AssignDevice {
+                        player: player - 1,
+                        device,
+                    });
+                Ok(())
+            },
+            desc = "Drive local player 1-4 with a device: \"keyboard\", \"keyboard_left\", \
+                    \"keyboard_right\" or \"gamepad_1\" to \"gamepad_4\"; nil leaves the player \
+                    without one. Entities with that player's index read its input",
+            cat = "input",
+            params = [("player", "integer"), ("device", "string?")]
+        );
 
         Ok(())
     }
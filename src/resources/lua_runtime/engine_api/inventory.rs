@@ -0,0 +1,278 @@
+use super::entity::parse_blackboard_value;
+use super::*;
+use crate::components::blackboard::BlackboardValue;
+use crate::resources::itemregistry::ItemDef;
+use raylib::prelude::Rectangle;
+
+impl LuaRuntime {
+    /// Registers the item definition and inventory functions.
+    pub(in crate::resources::lua_runtime) fn register_inventory_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "register_item",
+            inventory_commands,
+            |(id, def)| (String, Option<LuaTable>),
+            InventoryCmd::RegisterItem(Box::new(parse_item_def(id, def)?)),
+            desc = "Define (or redefine) an item: {name?, icon? (texture key), icon_rect? \
+                    ({x, y, w, h} in the texture), max_stack? (default 1), properties? \
+                    (booleans, numbers, strings or {x, y} tables)}",
+            cat = "inventory",
+            params = [("id", "string"), ("def", "table?")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_add",
+            inventory_commands,
+            |(entity_id, item, count)| (u64, String, Option<u32>),
+            InventoryCmd::Add {
+                entity_id,
+                item,
+                count: count.unwrap_or(1)
+            },
+            desc = "Add `count` (default 1) of a registered item to an entity's inventory, topping \
+                    up its stacks first. What does not fit is reported to on_inventory_full",
+            cat = "inventory",
+            params = [
+                ("entity_id", "integer"),
+                ("item", "string"),
+                ("count", "integer?")
+            ]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_remove",
+            inventory_commands,
+            |(entity_id, item, count)| (u64, String, Option<u32>),
+            InventoryCmd::Remove {
+                entity_id,
+                item,
+                count: count.unwrap_or(1)
+            },
+            desc = "Remove up to `count` (default 1) of an item from an entity's inventory, \
+                    emptying the last stacks first",
+            cat = "inventory",
+            params = [
+                ("entity_id", "integer"),
+                ("item", "string"),
+                ("count", "integer?")
+            ]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_swap",
+            inventory_commands,
+            |(entity_id, a, b)| (u64, usize, usize),
+            InventoryCmd::Swap {
+                entity_id,
+                a: slot_index(a)?,
+                b: slot_index(b)?
+            },
+            desc = "Swap the contents of two slots (1-based) of an entity's inventory",
+            cat = "inventory",
+            params = [("entity_id", "integer"), ("a", "integer"), ("b", "integer")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_clear",
+            inventory_commands,
+            |entity_id| u64,
+            InventoryCmd::Clear { entity_id },
+            desc = "Empty every slot of an entity's inventory",
+            cat = "inventory",
+            params = [("entity_id", "integer")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_count",
+            |lua, (entity_id, item): (u64, String)| {
+                let count = lua
+                    .app_data_ref::<LuaAppData>()
+                    .and_then(|data| {
+                        data.inventories
+                            .borrow()
+                            .get(&entity_id)
+                            .map(|inventory| inventory.count(&item))
+                    })
+                    .unwrap_or(0);
+                Ok(count)
+            },
+            desc = "Count an item across an entity's inventory at the start of the frame \
+                    (0 without inventory)",
+            cat = "inventory",
+            params = [("entity_id", "integer"), ("item", "string")],
+            returns = "integer"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "inventory_slots",
+            |lua, entity_id: u64| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let inventories = data.inventories.borrow();
+                let Some(inventory) = inventories.get(&entity_id) else {
+                    return Ok(None);
+                };
+                let slots = lua.create_table_with_capacity(inventory.capacity(), 0)?;
+                for (i, slot) in inventory.slots().iter().enumerate() {
+                    match slot {
+                        Some(stack) => {
+                            let tbl = lua.create_table()?;
+                            tbl.set("item", &*stack.item)?;
+                            tbl.set("count", stack.count)?;
+                            slots.set(i + 1, tbl)?;
+                        }
+                        None => slots.set(i + 1, false)?,
+                    }
+                }
+                Ok(Some(slots))
+            },
+            desc = "Get an entity's inventory at the start of the frame as an array of \
+                    {item, count} tables, with false for empty slots (nil without inventory)",
+            cat = "inventory",
+            params = [("entity_id", "integer")],
+            returns = "table?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_item",
+            |lua, id: String| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let items = data.items.borrow();
+                let Some(def) = items.get(&id) else {
+                    return Ok(None);
+                };
+                let tbl = lua.create_table()?;
+                tbl.set("id", &*def.id)?;
+                tbl.set("name", def.name.as_str())?;
+                tbl.set("max_stack", def.max_stack)?;
+                if let Some(icon) = &def.icon {
+                    tbl.set("icon", &*icon.tex_key)?;
+                }
+                let properties = lua.create_table()?;
+                for (key, value) in &def.properties {
+                    properties.set(key.as_str(), property_to_lua(lua, value)?)?;
+                }
+                tbl.set("properties", properties)?;
+                Ok(Some(tbl))
+            },
+            desc = "Get a registered item as {id, name, icon, max_stack, properties} \
+                    (nil if unknown; items registered this frame show up next frame)",
+            cat = "inventory",
+            params = [("id", "string")],
+            returns = "table?"
+        );
+
+        register_hook_fn(
+            &self.lua,
+            &engine,
+            &meta_fns,
+            "on_inventory_changed",
+            "inventory_changed",
+            "Register a function called as fn(entity_id, item, delta, count) whenever items are \
+             added to (delta > 0) or removed from (delta < 0) an inventory (pass nil to remove it)",
+            "inventory",
+        )?;
+
+        register_hook_fn(
+            &self.lua,
+            &engine,
+            &meta_fns,
+            "on_inventory_full",
+            "inventory_full",
+            "Register a function called as fn(entity_id, item, leftover) when items did not fit \
+             in an inventory (pass nil to remove it)",
+            "inventory",
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Convert an `engine.register_item()` definition table into an [`ItemDef`].
+fn parse_item_def(id: String, def: Option<LuaTable>) -> LuaResult<ItemDef> {
+    let mut item = ItemDef::new(id);
+    let Some(def) = def else {
+        return Ok(item);
+    };
+    if let Some(name) = def.get::<Option<String>>("name")? {
+        item = item.with_name(name);
+    }
+    if let Some(max_stack) = def.get::<Option<u32>>("max_stack")? {
+        item = item.with_max_stack(max_stack);
+    }
+    if let Some(tex_key) = def.get::<Option<String>>("icon")? {
+        let source = def
+            .get::<Option<LuaTable>>("icon_rect")?
+            .map(|r| -> LuaResult<Rectangle> {
+                Ok(Rectangle::new(
+                    r.get("x")?,
+                    r.get("y")?,
+                    r.get("w")?,
+                    r.get("h")?,
+                ))
+            })
+            .transpose()?;
+        item = item.with_icon(tex_key, source);
+    }
+    if let Some(properties) = def.get::<Option<LuaTable>>("properties")? {
+        for pair in properties.pairs::<String, LuaValue>() {
+            let (key, value) = pair?;
+            item = item.with_property(key, parse_blackboard_value(value)?);
+        }
+    }
+    Ok(item)
+}
+
+/// Convert a 1-based Lua slot number into a slot index.
+fn slot_index(slot: usize) -> LuaResult<usize> {
+    slot.checked_sub(1)
+        .ok_or_else(|| LuaError::runtime("inventory slots are numbered from 1"))
+}
+
+/// Convert an item property into a Lua value; `{x, y}` tables for vectors and
+/// ID bits for entities, as in the entity blackboard.
+fn property_to_lua(lua: &Lua, value: &BlackboardValue) -> LuaResult<LuaValue> {
+    match value {
+        BlackboardValue::Scalar(v) => v.into_lua(lua),
+        BlackboardValue::Integer(v) => v.into_lua(lua),
+        BlackboardValue::Bool(v) => v.into_lua(lua),
+        BlackboardValue::Text(v) => v.as_str().into_lua(lua),
+        BlackboardValue::Vec2(v) => {
+            let xy = lua.create_table()?;
+            xy.set("x", v.x)?;
+            xy.set("y", v.y)?;
+            Ok(LuaValue::Table(xy))
+        }
+        BlackboardValue::Entity(e) => e.to_bits().into_lua(lua),
+    }
+}
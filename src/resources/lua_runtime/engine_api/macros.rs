@@ -1,3 +1,4 @@
+use super::LuaAppData;
 use mlua::prelude::*;
 
 /// Pushes function metadata to `engine.__meta.functions[name]`.
@@ -30,6 +31,35 @@ pub(super) fn push_fn_meta(
     Ok(())
 }
 
+/// Registers an `engine.on_<event>(fn)` function that stores `fn` as the hook for
+/// `event` in `LuaAppData::hooks` (passing `nil` removes it). Hooks are invoked
+/// through `LuaRuntime::call_hook`.
+pub(super) fn register_hook_fn(
+    lua: &Lua,
+    engine: &LuaTable,
+    meta_fns: &LuaTable,
+    name: &str,
+    event: &'static str,
+    desc: &str,
+    category: &str,
+) -> LuaResult<()> {
+    engine.set(
+        name,
+        lua.create_function(move |lua, callback: Option<LuaFunction>| {
+            let data = lua
+                .app_data_ref::<LuaAppData>()
+                .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+            let mut hooks = data.hooks.borrow_mut();
+            match callback {
+                Some(f) => hooks.insert(event, f),
+                None => hooks.remove(event),
+            };
+            Ok(())
+        })?,
+    )?;
+    push_fn_meta(lua, meta_fns, name, desc, category, &[("callback", "function?")], None)
+}
+
 /// Registers one of the `engine.log_*` functions.
 macro_rules! register_log_fn {
     ($engine:expr, $lua:expr, $meta_fns:expr, $name:expr, $log_macro:ident, $desc:expr) => {
@@ -52,6 +82,27 @@ macro_rules! register_log_fn {
     };
 }
 
+/// Registers an `engine.*` function together with its `engine.__meta` entry.
+///
+/// The handler is any closure accepted by `Lua::create_function`. Declaring
+/// name, handler and metadata in one place keeps the runtime API, the
+/// generated stubs (`--create-lua-stubs`) and the API reference
+/// (`--create-lua-docs`) from drifting apart.
+macro_rules! register_fn {
+    ($engine:expr, $lua:expr, $meta_fns:expr, $name:expr, $func:expr,
+     desc = $desc:expr, cat = $cat:expr,
+     params = [ $( ($pname:expr, $pty:expr) ),* $(,)? ]
+     $(, returns = $ret:expr )? $(,)?
+    ) => {
+        $engine.set($name, $lua.create_function($func)?)?;
+        push_fn_meta(
+            &$lua, &$meta_fns, $name, $desc, $cat,
+            &[ $(($pname, $pty)),* ],
+            register_cmd!(@opt_ret $($ret)?)
+        )?;
+    };
+}
+
 /// Registers a Lua function that pushes a command to a queue in `LuaAppData`.
 macro_rules! register_cmd {
     // Variant with metadata
@@ -161,6 +212,31 @@ macro_rules! define_camera_cmd_twins {
                     ("rotation", "number"),
                     ("zoom", "number")
                 ]),
+            ("camera_shake",
+                |(intensity, duration)| (f32, f32),
+                CameraCmd::Shake { intensity, duration },
+                desc = "Shake the camera: intensity 0-1 (trauma, squared into up to 12 px and 2 degrees of jitter) fading out over duration seconds. A weaker shake never cuts a stronger one short",
+                params = [("intensity", "number"), ("duration", "number")]),
+            ("camera_zoom_to",
+                |(zoom, duration, easing)| (f32, f32, Option<String>),
+                CameraCmd::ZoomTo { zoom, duration, easing },
+                desc = "Ease the camera effect zoom factor (multiplying the camera zoom) to zoom over duration seconds and keep it; 1 restores the camera zoom. easing: tween easing name (default \"linear\")",
+                params = [("zoom", "number"), ("duration", "number"), ("easing", "string?")]),
+            ("camera_zoom_punch",
+                |(amount, duration)| (f32, f32),
+                CameraCmd::ZoomPunch { amount, duration },
+                desc = "Zoom in by amount (0.1 = 10%) at once and ease back out over duration seconds",
+                params = [("amount", "number"), ("duration", "number")]),
+            ("camera_pan_to",
+                |(x, y, duration, easing)| (f32, f32, f32, Option<String>),
+                CameraCmd::PanTo { x, y, duration, easing },
+                desc = "Blend the view from the camera target to world point (x, y) over duration seconds and hold it there until camera_release_pan()",
+                params = [("x", "number"), ("y", "number"), ("duration", "number"), ("easing", "string?")]),
+            ("camera_release_pan",
+                |(duration, easing)| (f32, Option<String>),
+                CameraCmd::ReleasePan { duration, easing },
+                desc = "Blend the view back from a camera_pan_to() point to the camera target over duration seconds",
+                params = [("duration", "number"), ("easing", "string?")]),
         ]);
     };
 }
@@ -267,6 +343,31 @@ macro_rules! define_entity_cmds {
                 |(entity_id, seconds)| (u64, f32), EntityCmd::InsertTtl { entity_id, seconds },
                 desc = "Insert a time-to-live component on an entity",
                 params = [("entity_id", "integer"), ("seconds", "number")]),
+            ("entity_set_collider_enabled",
+                |(entity_id, enabled)| (u64, bool), EntityCmd::SetColliderEnabled { entity_id, enabled },
+                desc = "Enable/disable collision detection for an entity without removing its collider",
+                params = [("entity_id", "integer"), ("enabled", "boolean")]),
+            ("entity_set_force_area_enabled",
+                |(entity_id, enabled)| (u64, bool), EntityCmd::SetForceAreaEnabled { entity_id, enabled },
+                desc = "Enable/disable an entity's force area (wind zone, current, conveyor)",
+                params = [("entity_id", "integer"), ("enabled", "boolean")]),
+            ("entity_start_cooldown", |entity_id| u64, EntityCmd::StartCooldown { entity_id },
+                desc = "Start an entity's cooldown over from its full duration",
+                params = [("entity_id", "integer")]),
+            ("entity_reset_cooldown", |entity_id| u64, EntityCmd::ResetCooldown { entity_id },
+                desc = "Make an entity's cooldown ready now",
+                params = [("entity_id", "integer")]),
+            ("entity_remove_joints", |entity_id| u64, EntityCmd::RemoveJoints { entity_id },
+                desc = "Remove the distance and pin joints of an entity, setting it free",
+                params = [("entity_id", "integer")]),
+            ("entity_jump",
+                |(entity_id, speed)| (u64, f32), EntityCmd::Jump { entity_id, speed },
+                desc = "Launch an entity with :with_height() upward at speed (world units per second), in the air or not",
+                params = [("entity_id", "integer"), ("speed", "number")]),
+            ("entity_set_height",
+                |(entity_id, height)| (u64, f32), EntityCmd::SetHeight { entity_id, height },
+                desc = "Set the faux-3D height of an entity with :with_height()",
+                params = [("entity_id", "integer"), ("height", "number")]),
             ("entity_insert_tween_position",
                 |(entity_id, from_x, from_y, to_x, to_y, duration, easing, loop_mode, backwards, on_finished)|
                 (u64, f32, f32, f32, f32, f32, String, String, bool, Option<String>),
@@ -379,6 +480,14 @@ macro_rules! define_entity_cmds {
                 |(entity_id, max_speed)| (u64, Option<f32>), EntityCmd::SetMaxSpeed { entity_id, max_speed },
                 desc = "Set entity max speed (nil to remove)",
                 params = [("entity_id", "integer"), ("max_speed", "number?")]),
+            ("entity_set_mass",
+                |(entity_id, mass)| (u64, f32), EntityCmd::SetMass { entity_id, mass },
+                desc = "Set entity mass (forces, force areas, wind and impulses are divided by it)",
+                params = [("entity_id", "integer"), ("mass", "number")]),
+            ("entity_apply_impulse",
+                |(entity_id, x, y)| (u64, f32, f32), EntityCmd::ApplyImpulse { entity_id, x, y },
+                desc = "Change entity velocity by (x, y) divided by its mass",
+                params = [("entity_id", "integer"), ("x", "number"), ("y", "number")]),
             ("entity_freeze", |entity_id| u64, EntityCmd::FreezeEntity { entity_id },
                 desc = "Freeze entity (zero velocity, ignore forces)",
                 params = [("entity_id", "integer")]),
@@ -481,6 +590,63 @@ macro_rules! define_entity_cmds {
                 EntityCmd::RemoveCameraTarget { entity_id },
                 desc = "Remove CameraTarget component from an entity",
                 params = [("entity_id", "integer")]),
+            ("entity_blackboard_set",
+                |(entity_id, key, value, ttl)| (u64, String, LuaValue, Option<f32>),
+                EntityCmd::BlackboardSet { entity_id, key, value: parse_blackboard_value(value)?, ttl },
+                desc = "Write an AI blackboard value (boolean, number, string or {x, y} table) on an entity, \
+                        forgetting it after ttl seconds if given. Inserts a Blackboard if missing",
+                params = [("entity_id", "integer"), ("key", "string"),
+                          ("value", "boolean|number|string|table"), ("ttl", "number?")]),
+            ("entity_blackboard_set_entity",
+                |(entity_id, key, target_id, ttl)| (u64, String, u64, Option<f32>),
+                EntityCmd::BlackboardSet { entity_id, key, value: parse_blackboard_entity(target_id)?, ttl },
+                desc = "Remember another entity's ID on an entity's AI blackboard, \
+                        forgetting it after ttl seconds if given",
+                params = [("entity_id", "integer"), ("key", "string"),
+                          ("target_id", "integer"), ("ttl", "number?")]),
+            ("entity_blackboard_remove",
+                |(entity_id, key)| (u64, String), EntityCmd::BlackboardRemove { entity_id, key },
+                desc = "Remove a key from an entity's AI blackboard",
+                params = [("entity_id", "integer"), ("key", "string")]),
+            ("entity_blackboard_clear", |entity_id| u64, EntityCmd::BlackboardClear { entity_id },
+                desc = "Remove every key from an entity's AI blackboard",
+                params = [("entity_id", "integer")]),
+            ("tilemap_set_layer_visible",
+                |(entity_id, layer, visible)| (u64, String, bool),
+                EntityCmd::TilemapSetLayerVisible { entity_id, layer, visible },
+                desc = "Show or hide a tilemap layer by name. Hidden layers despawn their tile entities",
+                params = [("entity_id", "integer"), ("layer", "string"), ("visible", "boolean")]),
+            ("tilemap_set_tile",
+                |(entity_id, layer, x, y, tile_id)| (u64, String, u32, u32, Option<u32>),
+                EntityCmd::TilemapSetTile { entity_id, layer, x, y, tile_id },
+                desc = "Place atlas tile tile_id at cell (x, y) of a tilemap layer, replacing the tile there. \
+                        A nil tile_id clears the cell",
+                params = [("entity_id", "integer"), ("layer", "string"), ("x", "integer"),
+                          ("y", "integer"), ("tile_id", "integer?")]),
+            ("terrain_carve_circle",
+                |(entity_id, x, y, radius)| (u64, f32, f32, f32),
+                EntityCmd::TerrainCarveCircle { entity_id, x, y, radius },
+                desc = "Clear every pixel of a terrain within radius of world point (x, y). \
+                        Collisions follow the hole at once; the texture updates before the next frame is drawn",
+                params = [("entity_id", "integer"), ("x", "number"), ("y", "number"), ("radius", "number")]),
+            ("water_splash",
+                |(entity_id, x, velocity)| (u64, f32, f32),
+                EntityCmd::WaterSplash { entity_id, x, velocity },
+                desc = "Disturb a water surface at world x: a positive velocity pushes the water down, \
+                        a negative one lifts it. The wave spreads from there",
+                params = [("entity_id", "integer"), ("x", "number"), ("velocity", "number")]),
+            ("group_translate",
+                |(group, dx, dy)| (String, f32, f32),
+                EntityCmd::GroupTranslate { group, dx, dy },
+                desc = "Move every entity whose group or tag is `group` by (dx, dy). \
+                        Children of moved entities follow their parent",
+                params = [("group", "string"), ("dx", "number"), ("dy", "number")]),
+            ("group_set_visible",
+                |(group, visible)| (String, bool),
+                EntityCmd::GroupSetVisible { group, visible },
+                desc = "Show or hide the sprites and texts of every entity whose group or tag is `group`. \
+                        Hidden entities still move, collide and animate",
+                params = [("group", "string"), ("visible", "boolean")]),
         ]);
     };
 }
\ No newline at end of file
@@ -9,15 +9,22 @@ mod assets;
 mod audio;
 mod base;
 mod camera;
+mod clock;
+mod debug;
 mod entity;
 mod gameconfig;
 mod input;
+mod inventory;
 mod phase_group;
 mod render;
+mod savegame;
 mod signal;
 mod spawn;
+mod ui;
+mod weather;
+mod worlds;
 
 use super::commands::*;
 use super::runtime::{LuaAppData, LuaRuntime};
 use mlua::prelude::*;
-use macros::push_fn_meta;
+use macros::{push_fn_meta, register_hook_fn};
@@ -1,4 +1,5 @@
 use super::*;
+use crate::resources::formation::{DEFAULT_CATCH_UP, FormationPattern};
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_phase_api(&self) -> LuaResult<()> {
@@ -25,6 +26,19 @@ impl LuaRuntime {
             cat = "group",
             params = [("name", "string")]
         );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "track_group_bounds",
+            group_commands,
+            |name| String,
+            GroupCmd::TrackGroupBounds { name },
+            desc = "Track a named entity group and compute its centroid, bounding box and \
+                    member positions every frame (see group_bounds/group_nearest)",
+            cat = "group",
+            params = [("name", "string")]
+        );
         register_cmd!(
             engine,
             self.lua,
@@ -50,25 +64,245 @@ impl LuaRuntime {
             params = []
         );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "has_tracked_group",
-            self.lua.create_function(|lua, name: String| {
+            |lua, name: String| {
                 let has = lua
                     .app_data_ref::<LuaAppData>()
                     .map(|data| data.tracked_groups.borrow().contains(&name))
                     .unwrap_or(false);
                 Ok(has)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "has_tracked_group",
-            "Check if a group is being tracked",
-            "group",
-            &[("name", "string")],
-            Some("boolean"),
-        )?;
+            },
+            desc = "Check if a group is being tracked",
+            cat = "group",
+            params = [("name", "string")],
+            returns = "boolean"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "group_bounds",
+            |lua, name: String| {
+                let Some(data) = lua.app_data_ref::<LuaAppData>() else {
+                    return Ok(LuaValue::Nil);
+                };
+                let aggregates = data.group_aggregates.borrow();
+                let Some(agg) = aggregates.get(&name) else {
+                    return Ok(LuaValue::Nil);
+                };
+                let t = lua.create_table()?;
+                t.set("count", agg.count())?;
+                t.set("cx", agg.centroid.x)?;
+                t.set("cy", agg.centroid.y)?;
+                t.set("min_x", agg.min.x)?;
+                t.set("min_y", agg.min.y)?;
+                t.set("max_x", agg.max.x)?;
+                t.set("max_y", agg.max.y)?;
+                Ok(LuaValue::Table(t))
+            },
+            desc = "Get {count, cx, cy, min_x, min_y, max_x, max_y} for a group registered with \
+             track_group_bounds (nil otherwise); values are from the start of this frame's update",
+            cat = "group",
+            params = [("name", "string")],
+            returns = "table?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "group_nearest",
+            |lua, (name, x, y): (String, f32, f32)| {
+                let nearest = lua.app_data_ref::<LuaAppData>().and_then(|data| {
+                    data.group_aggregates
+                        .borrow()
+                        .get(&name)
+                        .and_then(|agg| agg.nearest_to(x, y))
+                });
+                Ok(match nearest {
+                    Some((entity, dist)) => (Some(entity.to_bits()), Some(dist)),
+                    None => (None, None),
+                })
+            },
+            desc = "Get the entity id and distance of the member of a track_group_bounds group closest \
+             to (x, y), or nil if the group is empty or not aggregated",
+            cat = "group",
+            params = [("name", "string"), ("x", "number"), ("y", "number")],
+            returns = "integer?, number?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "entity_has_tag",
+            |lua, (entity_id, tag): (u64, String)| {
+                let has = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| {
+                        data.entity_tags
+                            .borrow()
+                            .get(&entity_id)
+                            .is_some_and(|tags| tags.has(&tag))
+                    })
+                    .unwrap_or(false);
+                Ok(has)
+            },
+            desc = "Check whether an entity carries a tag added with :with_tags() (the group name is \
+             not a tag). Reflects tags as of the start of the frame",
+            cat = "group",
+            params = [("entity_id", "integer"), ("tag", "string")],
+            returns = "boolean"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "entities_in_rect",
+            |lua, (x, y, w, h, group): (f32, f32, f32, f32, Option<String>)| {
+                let mut hits = Vec::new();
+                if let Some(data) = lua.app_data_ref::<LuaAppData>() {
+                    data.spatial_index
+                        .borrow()
+                        .query_rect(x, y, w, h, group.as_deref(), &mut hits);
+                }
+                lua.create_sequence_from(hits.into_iter().map(|e| e.to_bits()))
+            },
+            desc = "Get the ids of entities with a group or tags whose position lies inside the \
+             rectangle, optionally only those matching `group` (group name or tag). Uses the \
+             spatial index as of the start of the frame",
+            cat = "group",
+            params = [
+                ("x", "number"),
+                ("y", "number"),
+                ("w", "number"),
+                ("h", "number"),
+                ("group", "string?"),
+            ],
+            returns = "integer[]"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "nearest_entity",
+            |lua, (x, y, group, max_dist): (f32, f32, Option<String>, Option<f32>)| {
+                let nearest = lua.app_data_ref::<LuaAppData>().and_then(|data| {
+                    data.spatial_index
+                        .borrow()
+                        .nearest(x, y, group.as_deref(), max_dist)
+                });
+                Ok(match nearest {
+                    Some((entity, dist)) => (Some(entity.to_bits()), Some(dist)),
+                    None => (None, None),
+                })
+            },
+            desc = "Get the id and distance of the entity closest to (x, y), optionally only those \
+             matching `group` (group name or tag) and within `max_dist`, or nil if none. Uses \
+             the spatial index as of the start of the frame",
+            cat = "group",
+            params = [
+                ("x", "number"),
+                ("y", "number"),
+                ("group", "string?"),
+                ("max_dist", "number?"),
+            ],
+            returns = "integer?, number?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "formation_create",
+            |lua, (name, leader_id, pattern, spacing, opts): (String, u64, String, f32, Option<LuaTable>)| {
+                let pattern = FormationPattern::parse(&pattern).ok_or_else(|| {
+                    LuaError::runtime(format!(
+                        "formation pattern must be \"line\", \"v\" or \"circle\", got '{pattern}'"
+                    ))
+                })?;
+                let (catch_up, rotate) = match opts {
+                    Some(opts) => (
+                        opts.get::<Option<f32>>("catch_up")?.unwrap_or(DEFAULT_CATCH_UP),
+                        opts.get::<Option<bool>>("rotate")?.unwrap_or(false),
+                    ),
+                    None => (DEFAULT_CATCH_UP, false),
+                };
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .formation_commands
+                    .borrow_mut()
+                    .push(FormationCmd::Create {
+                        name,
+                        leader_id,
+                        pattern,
+                        spacing,
+                        catch_up,
+                        rotate,
+                    });
+                Ok(())
+            },
+            desc = "Create (or replace) the formation `name` led by `leader_id`, with slots laid \
+                    out as a \"line\", \"v\" or \"circle\" `spacing` units apart. Add members \
+                    with engine.formation_add(). opts: {catch_up? (how fast members close the gap \
+                    to their slot, default 6; 0 snaps), rotate? (turn the slots with the leader's \
+                    rotation, default false)}",
+            cat = "group",
+            params = [
+                ("name", "string"),
+                ("leader_id", "integer"),
+                ("pattern", "string"),
+                ("spacing", "number"),
+                ("opts", "table?"),
+            ]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "formation_add",
+            formation_commands,
+            |(name, entity_id)| (String, u64),
+            FormationCmd::Add { name, entity_id },
+            desc = "Add an entity to the next free slot of a formation, taking it out of any \
+                    other formation. The formation moves its MapPosition from then on",
+            cat = "group",
+            params = [("name", "string"), ("entity_id", "integer")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "formation_remove",
+            formation_commands,
+            |(name, entity_id)| (String, u64),
+            FormationCmd::Remove { name, entity_id },
+            desc = "Take an entity out of a formation; the members behind it move up a slot",
+            cat = "group",
+            params = [("name", "string"), ("entity_id", "integer")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "formation_disband",
+            formation_commands,
+            |name| String,
+            FormationCmd::Disband { name },
+            desc = "Disband a formation, leaving its members where they are",
+            cat = "group",
+            params = [("name", "string")]
+        );
 
         Ok(())
     }
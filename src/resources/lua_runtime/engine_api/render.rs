@@ -1,4 +1,6 @@
 use super::*;
+use super::super::entity_builder::parse_render_layer;
+use crate::resources::rendermode::RenderMode;
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_render_api(&self) -> LuaResult<()> {
@@ -6,41 +8,43 @@ impl LuaRuntime {
         let meta: LuaTable = engine.get("__meta")?;
         let meta_fns: LuaTable = meta.get("functions")?;
 
-        engine.set(
-            "load_shader",
-            self.lua.create_function(
-                |lua, (id, vs_path, fs_path): (String, Option<String>, Option<String>)| {
-                    if vs_path.is_none() && fs_path.is_none() {
-                        return Err(LuaError::runtime(
-                            "load_shader: at least one of vs_path or fs_path must be provided",
-                        ));
-                    }
-                    lua.app_data_ref::<LuaAppData>()
-                        .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
-                        .asset_commands
-                        .borrow_mut()
-                        .push(AssetCmd::Shader {
-                            id,
-                            vs_path,
-                            fs_path,
-                        });
-                    Ok(())
-                },
-            )?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "load_shader",
-            "Load a shader (at least one of vs_path/fs_path required)",
-            "render",
-            &[("id", "string"), ("vs_path", "string?"), ("fs_path", "string?")],
-            None,
-        )?;
+            |lua, (id, vs_path, fs_path): (String, Option<String>, Option<String>)| {
+                if vs_path.is_none() && fs_path.is_none() {
+                    return Err(LuaError::runtime(
+                        "load_shader: at least one of vs_path or fs_path must be provided",
+                    ));
+                }
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .asset_commands
+                    .borrow_mut()
+                    .push(AssetCmd::Shader {
+                        id,
+                        vs_path,
+                        fs_path,
+                    });
+                Ok(())
+            },
+            desc = "Load a shader (at least one of vs_path/fs_path required)",
+            cat = "render",
+            params = [
+                ("id", "string"),
+                ("vs_path", "string?"),
+                ("fs_path", "string?")
+            ]
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "post_process_shader",
-            self.lua.create_function(|lua, value: LuaValue| {
+            |lua, value: LuaValue| {
                 let ids: Option<Vec<String>> = match value {
                     LuaValue::Nil => None,
                     LuaValue::Table(t) => {
@@ -68,17 +72,11 @@ impl LuaRuntime {
                     .borrow_mut()
                     .push(RenderCmd::SetPostProcessShader { ids });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "post_process_shader",
-            "Set active post-processing shader chain (nil to clear)",
-            "render",
-            &[("shader_ids", "string[]?")],
-            None,
-        )?;
+            },
+            desc = "Set active post-processing shader chain (nil to clear)",
+            cat = "render",
+            params = [("shader_ids", "string[]?")]
+        );
 
         register_cmd!(
             engine,
@@ -170,6 +168,74 @@ impl LuaRuntime {
             cat = "render",
             params = []
         );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_render_mode",
+            |lua, mode: String| {
+                let mode = RenderMode::parse(&mode).ok_or_else(|| {
+                    LuaError::runtime(format!(
+                        "set_render_mode: unknown mode '{mode}' (expected \"full\" or \"dirty_rects\")"
+                    ))
+                })?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .render_commands
+                    .borrow_mut()
+                    .push(RenderCmd::SetRenderMode { mode });
+                Ok(())
+            },
+            desc = "Set how the current scene is redrawn: \"full\" (every frame, the default) or \
+                    \"dirty_rects\" (only regions whose entities changed). Resets to \"full\" on \
+                    scene switch",
+            cat = "render",
+            params = [("mode", "string")]
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_layer_visible",
+            |lua, (layer, visible): (LuaValue, bool)| {
+                let layer = parse_render_layer("set_layer_visible", &layer)?;
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .render_commands
+                    .borrow_mut()
+                    .push(RenderCmd::SetLayerVisible { layer, visible });
+                Ok(())
+            },
+            desc = "Show or hide a render layer: \"background\", \"gameplay\", \"fx\", \"ui\", \"debug\" \
+                    or a layer number 0-255. Hidden entities keep updating. Survives scene switches",
+            cat = "render",
+            params = [("layer", "string|integer"), ("visible", "boolean")]
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_color_lut",
+            |lua, (tex_key, fade): (Option<String>, Option<f32>)| {
+                let fade = fade.unwrap_or(0.0);
+                if !fade.is_finite() || fade < 0.0 {
+                    return Err(LuaError::runtime(format!(
+                        "set_color_lut: fade must be a non-negative number of seconds, got {fade}"
+                    )));
+                }
+                lua.app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?
+                    .render_commands
+                    .borrow_mut()
+                    .push(RenderCmd::SetColorLut { key: tex_key, fade });
+                Ok(())
+            },
+            desc = "Grade the final image with a 256x16 LUT texture (16x16x16 color cube, load it with \
+                    \"bilinear\"), applied after the post-process chain. nil removes grading. `fade` \
+                    crossfades over that many seconds (default 0). Survives scene switches",
+            cat = "render",
+            params = [("tex_key", "string?"), ("fade", "number?")]
+        );
         register_cmd!(
             engine,
             self.lua,
@@ -422,6 +488,74 @@ impl LuaRuntime {
             ]
         );
 
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "fog_enable",
+            fog_commands,
+            |(cell_size, solid_layers)| (f32, Option<Vec<String>>),
+            FogCmd::Enable {
+                cell_size,
+                solid_layers: solid_layers.unwrap_or_default()
+            },
+            desc = "Enable the fog of war with `cell_size`-unit cells. Entities built with :with_vision() \
+                    reveal the cells around them; tiles of the `solid_layers` tilemap layers block \
+                    sources created with `blocked`. Revealed cells are kept per scene",
+            cat = "render",
+            params = [("cell_size", "number"), ("solid_layers", "string[]?")]
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "fog_disable",
+            fog_commands,
+            |()| (),
+            FogCmd::Disable,
+            desc = "Disable the fog of war, keeping the revealed cells",
+            cat = "render",
+            params = []
+        );
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "fog_reset",
+            fog_commands,
+            |()| (),
+            FogCmd::Reset,
+            desc = "Forget every fog-of-war cell revealed in the current scene",
+            cat = "render",
+            params = []
+        );
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "is_revealed",
+            |lua, (x, y): (f32, f32)| {
+                let Some(data) = lua.app_data_ref::<LuaAppData>() else {
+                    return Ok(true);
+                };
+                let fog = data.fog.borrow();
+                if !fog.enabled {
+                    return Ok(true);
+                }
+                let cell = (
+                    (x / fog.cell_size).floor() as i32,
+                    (y / fog.cell_size).floor() as i32,
+                );
+                Ok(fog.revealed.contains(&cell))
+            },
+            desc = "Check whether the world point (x, y) has been revealed by a vision source in the \
+                    current scene. Always true while the fog of war is disabled. Uses the fog state \
+                    from the start of the frame",
+            cat = "render",
+            params = [("x", "number"), ("y", "number")],
+            returns = "boolean"
+        );
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,84 @@
+use super::*;
+use crate::resources::savegame::slot_path;
+use std::path::Path;
+
+impl LuaRuntime {
+    /// Registers `engine.save_game`, `engine.load_game`, `engine.scene_state_forget`
+    /// and `engine.scene_state_clear`.
+    pub(in crate::resources::lua_runtime) fn register_savegame_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "save_game",
+            savegame_commands,
+            |slot| String,
+            SaveGameCmd::Save {
+                slot: check_slot("save_game", slot)?,
+            },
+            desc = "Write the world signals, plus the position and signals of every persistent \
+                    entity registered by name, to saves/save_<slot>.json. Slot names use \
+                    letters, digits, '_' and '-'",
+            cat = "save",
+            params = [("slot", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "load_game",
+            savegame_commands,
+            |slot| String,
+            SaveGameCmd::Load {
+                slot: check_slot("load_game", slot)?,
+            },
+            desc = "Restore a slot written by save_game: world signals are replaced (the scene \
+                    and group counts are kept) and saved entities still registered under their \
+                    name get their position and signals back. Applied at the end of the frame",
+            cat = "save",
+            params = [("slot", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "scene_state_forget",
+            savegame_commands,
+            |scene| String,
+            SaveGameCmd::ForgetSceneState { scene },
+            desc = "Forget what was saved for the with_restore_on_return entities of a scene, so \
+                    its next visit spawns them fresh. Forgetting the current scene also drops \
+                    this visit (e.g. before restarting the level)",
+            cat = "save",
+            params = [("scene", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "scene_state_clear",
+            savegame_commands,
+            |()| (),
+            SaveGameCmd::ClearSceneState,
+            desc = "Forget what was saved for the with_restore_on_return entities of every scene, \
+                    including the current visit (e.g. on \"new game\")",
+            cat = "save",
+            params = []
+        );
+
+        Ok(())
+    }
+}
+
+/// Reject slot names that [`slot_path`] would refuse when the command runs.
+fn check_slot(name: &str, slot: String) -> LuaResult<String> {
+    slot_path(Path::new(""), &slot).map_err(|e| LuaError::runtime(format!("{name}: {e}")))?;
+    Ok(slot)
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::resources::signal_keys as sk;
+use crate::systems::lua_commands::LUA_COMMAND_ORDER;
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_signal_api(&self) -> LuaResult<()> {
@@ -7,124 +8,119 @@ impl LuaRuntime {
         let meta: LuaTable = engine.get("__meta")?;
         let meta_fns: LuaTable = meta.get("functions")?;
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_scalar",
-            self.lua.create_function(|lua, key: String| {
+            |lua, key: String| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
-                    .and_then(|data| data.signal_snapshot.borrow().scalars.get(&key).copied());
+                    .and_then(|data| data.signal_snapshot.borrow().scalars.get(&*key).copied());
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_scalar",
-            "Get a world signal scalar value",
-            "signal",
-            &[("key", "string")],
-            Some("number?"),
-        )?;
+            },
+            desc = "Get a world signal scalar value",
+            cat = "signal",
+            params = [("key", "string")],
+            returns = "number?"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_integer",
-            self.lua.create_function(|lua, key: String| {
+            |lua, key: String| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
-                    .and_then(|data| data.signal_snapshot.borrow().integers.get(&key).copied());
+                    .and_then(|data| data.signal_snapshot.borrow().integers.get(&*key).copied());
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_integer",
-            "Get a world signal integer value",
-            "signal",
-            &[("key", "string")],
-            Some("integer?"),
-        )?;
+            },
+            desc = "Get a world signal integer value",
+            cat = "signal",
+            params = [("key", "string")],
+            returns = "integer?"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_string",
-            self.lua.create_function(|lua, key: String| {
+            |lua, key: String| {
                 let value = lua
                     .app_data_ref::<LuaAppData>()
-                    .and_then(|data| data.signal_snapshot.borrow().strings.get(&key).cloned());
+                    .and_then(|data| data.signal_snapshot.borrow().strings.get(&*key).cloned());
                 Ok(value)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_string",
-            "Get a world signal string value",
-            "signal",
-            &[("key", "string")],
-            Some("string?"),
-        )?;
+            },
+            desc = "Get a world signal string value",
+            cat = "signal",
+            params = [("key", "string")],
+            returns = "string?"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "has_flag",
-            self.lua.create_function(|lua, key: String| {
+            |lua, key: String| {
                 let has = lua
                     .app_data_ref::<LuaAppData>()
-                    .map(|data| data.signal_snapshot.borrow().flags.contains(&key))
+                    .map(|data| data.signal_snapshot.borrow().flags.contains(&*key))
                     .unwrap_or(false);
                 Ok(has)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "has_flag",
-            "Check if a world signal flag is set",
-            "signal",
-            &[("key", "string")],
-            Some("boolean"),
-        )?;
+            },
+            desc = "Check if a world signal flag is set",
+            cat = "signal",
+            params = [("key", "string")],
+            returns = "boolean"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_group_count",
-            self.lua.create_function(|lua, group: String| {
+            |lua, group: String| {
                 let count = lua.app_data_ref::<LuaAppData>().and_then(|data| {
-                    data.signal_snapshot.borrow().group_counts.get(&group).copied()
+                    data.signal_snapshot
+                        .borrow()
+                        .group_counts
+                        .get(&*group)
+                        .copied()
                 });
                 Ok(count)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_group_count",
-            "Get the count of entities in a tracked group",
-            "signal",
-            &[("group", "string")],
-            Some("integer?"),
-        )?;
+            },
+            desc = "Get the count of entities in a tracked group",
+            cat = "signal",
+            params = [("group", "string")],
+            returns = "integer?"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_entity",
-            self.lua.create_function(|lua, key: String| {
+            |lua, key: String| {
                 let entity_id = lua
                     .app_data_ref::<LuaAppData>()
                     .and_then(|data| data.signal_snapshot.borrow().entities.get(&key).copied());
                 Ok(entity_id)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_entity",
-            "Get a registered entity ID by key",
-            "signal",
-            &[("key", "string")],
-            Some("integer?"),
-        )?;
+            },
+            desc = "Get a registered entity ID by key",
+            cat = "signal",
+            params = [("key", "string")],
+            returns = "integer?"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_scalars",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let table = lua.create_table()?;
                 if let Some(data) = lua.app_data_ref::<LuaAppData>() {
                     let snapshot = data.signal_snapshot.borrow();
@@ -133,21 +129,19 @@ impl LuaRuntime {
                     }
                 }
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_scalars",
-            "Get all world signal scalars as a snapshot table",
-            "signal",
-            &[],
-            Some("table"),
-        )?;
+            },
+            desc = "Get all world signal scalars as a snapshot table",
+            cat = "signal",
+            params = [],
+            returns = "table"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_integers",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let table = lua.create_table()?;
                 if let Some(data) = lua.app_data_ref::<LuaAppData>() {
                     let snapshot = data.signal_snapshot.borrow();
@@ -156,21 +150,19 @@ impl LuaRuntime {
                     }
                 }
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_integers",
-            "Get all world signal integers as a snapshot table",
-            "signal",
-            &[],
-            Some("table"),
-        )?;
+            },
+            desc = "Get all world signal integers as a snapshot table",
+            cat = "signal",
+            params = [],
+            returns = "table"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_strings",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let table = lua.create_table()?;
                 if let Some(data) = lua.app_data_ref::<LuaAppData>() {
                     let snapshot = data.signal_snapshot.borrow();
@@ -179,21 +171,19 @@ impl LuaRuntime {
                     }
                 }
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_strings",
-            "Get all world signal strings as a snapshot table",
-            "signal",
-            &[],
-            Some("table"),
-        )?;
+            },
+            desc = "Get all world signal strings as a snapshot table",
+            cat = "signal",
+            params = [],
+            returns = "table"
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "get_flags",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let table = lua.create_table()?;
                 if let Some(data) = lua.app_data_ref::<LuaAppData>() {
                     let snapshot = data.signal_snapshot.borrow();
@@ -202,23 +192,21 @@ impl LuaRuntime {
                     }
                 }
                 Ok(table)
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "get_flags",
-            "Get all world signal flags as a snapshot array",
-            "signal",
-            &[],
-            Some("table"),
-        )?;
+            },
+            desc = "Get all world signal flags as a snapshot array",
+            cat = "signal",
+            params = [],
+            returns = "table"
+        );
 
         define_signal_cmd_twins!(engine, self.lua, meta_fns, "", signal_commands, "signal", "");
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "change_scene",
-            self.lua.create_function(|lua, scene_name: String| {
+            |lua, scene_name: String| {
                 let data = lua
                     .app_data_ref::<LuaAppData>()
                     .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
@@ -231,21 +219,18 @@ impl LuaRuntime {
                     key: sk::SWITCH_SCENE.into(),
                 });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "change_scene",
-            "Switch to a new scene by name (sets scene string + switch_scene flag)",
-            "base",
-            &[("scene_name", "string")],
-            None,
-        )?;
+            },
+            desc = "Switch to a new scene by name (sets scene string + switch_scene flag)",
+            cat = "base",
+            params = [("scene_name", "string")]
+        );
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "quit",
-            self.lua.create_function(|lua, ()| {
+            |lua, ()| {
                 let data = lua
                     .app_data_ref::<LuaAppData>()
                     .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
@@ -253,17 +238,24 @@ impl LuaRuntime {
                     key: sk::QUIT_GAME.into(),
                 });
                 Ok(())
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "quit",
-            "Quit the game engine (sets quit_game flag)",
-            "base",
-            &[],
-            None,
-        )?;
+            },
+            desc = "Quit the game engine (sets quit_game flag)",
+            cat = "base",
+            params = []
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "command_order",
+            |lua, ()| lua.create_sequence_from(LUA_COMMAND_ORDER),
+            desc = "Get the order in which queued subsystem commands (asset, map, world, \
+             visibility, fog, clock, weather, music, formation, inventory, ui, savegame, debug) are applied at the end of each frame",
+            cat = "base",
+            params = [],
+            returns = "string[]"
+        );
 
         Ok(())
     }
@@ -1,5 +1,8 @@
 use super::*;
 use super::super::entity_builder::LuaEntityBuilder;
+use super::super::spawn_data::SpawnCmd;
+use crate::components::gridlayout::GridValue;
+use crate::systems::spawnarea::{Distribution, SpawnArea, sample_points};
 
 impl LuaRuntime {
     pub(in crate::resources::lua_runtime) fn register_spawn_api(&self) -> LuaResult<()> {
@@ -7,37 +10,135 @@ impl LuaRuntime {
         let meta: LuaTable = engine.get("__meta")?;
         let meta_fns: LuaTable = meta.get("functions")?;
 
-        engine.set(
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "spawn",
-            self.lua
-                .create_function(|_, ()| Ok(LuaEntityBuilder::new()))?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
-            "spawn",
-            "Create a new entity builder",
-            "spawn",
-            &[],
-            Some("EntityBuilder"),
-        )?;
+            |_, ()| Ok(LuaEntityBuilder::new()),
+            desc = "Create a new entity builder",
+            cat = "spawn",
+            params = [],
+            returns = "EntityBuilder"
+        );
 
-        engine.set(
-            "clone",
-            self.lua.create_function(|_, source_key: String| {
-                Ok(LuaEntityBuilder::new_clone(source_key))
-            })?,
-        )?;
-        push_fn_meta(
-            &self.lua,
-            &meta_fns,
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
             "clone",
-            "Clone a registered entity with optional overrides",
-            "spawn",
-            &[("source_key", "string")],
-            Some("EntityBuilder"),
-        )?;
+            |_, source_key: String| Ok(LuaEntityBuilder::new_clone(source_key)),
+            desc = "Clone a registered entity with optional overrides",
+            cat = "spawn",
+            params = [("source_key", "string")],
+            returns = "EntityBuilder"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_spawn_point",
+            |lua, name: String| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let spawn_points = data.spawn_points.borrow();
+                let Some(info) = spawn_points.info(&name) else {
+                    return Ok(None);
+                };
+                let tbl = lua.create_table()?;
+                tbl.set("name", name.as_str())?;
+                tbl.set("x", info.position.x)?;
+                tbl.set("y", info.position.y)?;
+                let properties = lua.create_table()?;
+                for (key, value) in &info.properties {
+                    properties.set(key.as_str(), grid_value_to_lua(lua, value)?)?;
+                }
+                tbl.set("properties", properties)?;
+                Ok(Some(tbl))
+            },
+            desc = "Get a spawn point of the current scene as {name, x, y, properties} \
+                    (nil if unknown; points spawned this frame show up next frame)",
+            cat = "spawn",
+            params = [("name", "string")],
+            returns = "table?"
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "spawn_prefab_area",
+            |lua, (source_key, opts): (String, LuaTable)| {
+                let shape = opts.get::<Option<String>>("shape")?;
+                let shape = shape.as_deref().unwrap_or("rect");
+                let area = SpawnArea::parse(
+                    shape,
+                    opts.get::<Option<f32>>("x")?.unwrap_or(0.0),
+                    opts.get::<Option<f32>>("y")?.unwrap_or(0.0),
+                    opts.get::<Option<f32>>("w")?.unwrap_or(0.0),
+                    opts.get::<Option<f32>>("h")?.unwrap_or(0.0),
+                    opts.get::<Option<f32>>("radius")?.unwrap_or(0.0),
+                )
+                .ok_or_else(|| {
+                    LuaError::runtime(format!(
+                        "spawn_prefab_area: shape must be \"rect\" or \"circle\", got '{shape}'"
+                    ))
+                })?;
+                let distribution = match opts.get::<Option<String>>("distribution")? {
+                    Some(name) => Distribution::parse(&name).ok_or_else(|| {
+                        LuaError::runtime(format!(
+                            "spawn_prefab_area: distribution must be \"uniform\", \"poisson\" \
+                             or \"grid\", got '{name}'"
+                        ))
+                    })?,
+                    None => Distribution::Uniform,
+                };
+                let count = opts.get::<Option<usize>>("count")?.unwrap_or(1);
+                let mut rng = match opts.get::<Option<u64>>("seed")? {
+                    Some(seed) => fastrand::Rng::with_seed(seed),
+                    None => fastrand::Rng::new(),
+                };
+
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let mut clones = data.clone_commands.borrow_mut();
+                let points = sample_points(&area, count, distribution, &mut rng);
+                clones.reserve(points.len());
+                for point in &points {
+                    clones.push(CloneCmd {
+                        source_key: source_key.clone(),
+                        overrides: SpawnCmd {
+                            position: Some((point.x, point.y)),
+                            ..Default::default()
+                        },
+                    });
+                }
+                Ok(points.len())
+            },
+            desc = "Clone the entity registered as `source_key` onto `count` points of an area in \
+                    one call. opts: {shape? (\"rect\" (default): x, y top-left, w, h; \
+                    \"circle\": x, y center, radius), count? (default 1), distribution? \
+                    (\"uniform\" (default), \"poisson\" (random but kept apart) or \"grid\" \
+                    (evenly spaced)), seed? (integer, repeatable layouts)}. Returns the number \
+                    of clones queued",
+            cat = "spawn",
+            params = [("source_key", "string"), ("opts", "table")],
+            returns = "integer"
+        );
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Convert a spawn point property into a Lua value.
+fn grid_value_to_lua(lua: &Lua, value: &GridValue) -> LuaResult<LuaValue> {
+    match value {
+        GridValue::Int(v) => v.into_lua(lua),
+        GridValue::Float(v) => v.into_lua(lua),
+        GridValue::String(v) => v.as_str().into_lua(lua),
+        GridValue::Bool(v) => v.into_lua(lua),
+    }
+}
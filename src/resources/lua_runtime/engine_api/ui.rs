@@ -0,0 +1,228 @@
+use super::*;
+use crate::components::credits::{CreditsLine, CreditsRoll, CreditsStyle};
+use crate::components::results::{ResultRow, ResultValue, ResultsScreen};
+use raylib::prelude::Color;
+
+impl LuaRuntime {
+    /// Registers `engine.show_results`, `engine.show_credits`, `engine.submit_score`,
+    /// `engine.get_high_scores`, `engine.cinematic_mode` and `engine.set_attract_mode`.
+    pub(in crate::resources::lua_runtime) fn register_ui_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "show_results",
+            ui_commands,
+            |spec| LuaTable,
+            UiCmd::ShowResults(Box::new(parse_results(spec)?)),
+            desc = "Open a results screen: {rows = {{label, value? | signal?, decimals?}, ...}, \
+                    on_done?, x?, y?, value_offset?, spacing?, font?, font_size?, duration? \
+                    (seconds per row, default 1)}. Values count up row by row; confirm skips \
+                    the count, then closes the screen and calls on_done",
+            cat = "ui",
+            params = [("spec", "table")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "show_credits",
+            ui_commands,
+            |spec| LuaTable,
+            UiCmd::ShowCredits(Box::new(parse_credits(spec)?)),
+            desc = "Scroll credits up the screen: {lines = {\"text\" | {text, style? (\"title\", \
+                    \"heading\", \"normal\"), color? ({r, g, b, a?})}, ...} | file = path (\"# \" \
+                    title, \"## \" heading lines), on_done?, speed? (pixels/s, default 40), \
+                    fast_forward? (multiplier while action_1/action_2 is held, default 4), x?, \
+                    font?, font_size?, spacing?}. on_done is called when the last line is gone",
+            cat = "ui",
+            params = [("spec", "table")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "submit_score",
+            ui_commands,
+            |(score, on_done)| (i64, Option<String>),
+            UiCmd::SubmitScore {
+                score,
+                callback: on_done,
+            },
+            desc = "Submit a score to the high score table. If it qualifies, a three-letter name \
+                    entry opens (up/down picks a letter, left/right or back moves, confirm moves \
+                    on); on_done is then called with (name, rank), or (nil, nil) if the score \
+                    didn't make the table",
+            cat = "ui",
+            params = [("score", "integer"), ("on_done", "string?")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_high_scores",
+            |lua, n: Option<usize>| {
+                let data = lua
+                    .app_data_ref::<LuaAppData>()
+                    .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+                let entries = data.high_scores.borrow();
+                let n = n.unwrap_or(entries.len()).min(entries.len());
+                let list = lua.create_table()?;
+                for entry in &entries[..n] {
+                    let tbl = lua.create_table()?;
+                    tbl.set("name", entry.name.as_str())?;
+                    tbl.set("score", entry.score)?;
+                    list.push(tbl)?;
+                }
+                Ok(list)
+            },
+            desc = "Get the best n high scores (all by default) as a list of {name, score}, \
+                    highest first",
+            cat = "ui",
+            params = [("n", "integer?")],
+            returns = "table"
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "cinematic_mode",
+            ui_commands,
+            |(enabled, duration)| (bool, Option<f32>),
+            UiCmd::CinematicMode { enabled, duration },
+            desc = "Slide cutscene letterbox bars in (true) or out (false) over duration seconds \
+                    (default 0.4). While on, gameplay input actions read as released; back \
+                    (for skipping) and the debug/fullscreen toggles still work",
+            cat = "ui",
+            params = [("enabled", "boolean"), ("duration", "number?")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_attract_mode",
+            ui_commands,
+            |(replay_path, idle_seconds)| (Option<String>, Option<f32>),
+            UiCmd::SetAttractMode {
+                replay_path,
+                idle_seconds: idle_seconds.unwrap_or(30.0),
+            },
+            desc = "After idle_seconds (default 30) without input in the current scene, switch \
+                    to the scene of the replay file and play its recorded input; any input or \
+                    the end of the replay returns here. nil replay_path turns attract mode off",
+            cat = "ui",
+            params = [("replay_path", "string?"), ("idle_seconds", "number?")]
+        );
+
+        Ok(())
+    }
+}
+
+/// Build a [`CreditsRoll`] from the `engine.show_credits` table.
+fn parse_credits(spec: LuaTable) -> LuaResult<CreditsRoll> {
+    let mut roll = match spec.get::<Option<String>>("file")? {
+        Some(path) => CreditsRoll::load(&path).map_err(LuaError::runtime)?,
+        None => CreditsRoll::new(Vec::new()),
+    };
+    if let Some(lines) = spec.get::<Option<LuaTable>>("lines")? {
+        for line in lines.sequence_values::<LuaValue>() {
+            roll.lines.push(match line? {
+                LuaValue::Table(line) => {
+                    let style = line.get::<Option<String>>("style")?;
+                    let mut credit = CreditsLine::new(
+                        line.get::<String>("text")?,
+                        style.map_or(CreditsStyle::Normal, |s| CreditsStyle::from_name(&s)),
+                    );
+                    if let Some(color) = line.get::<Option<LuaTable>>("color")? {
+                        let c: Vec<u8> = color.sequence_values::<u8>().collect::<LuaResult<_>>()?;
+                        if c.len() < 3 {
+                            return Err(LuaError::runtime(
+                                "show_credits: color needs {r, g, b, a?}",
+                            ));
+                        }
+                        credit.color =
+                            Some(Color::new(c[0], c[1], c[2], *c.get(3).unwrap_or(&255)));
+                    }
+                    credit
+                }
+                LuaValue::String(text) => {
+                    CreditsLine::new(text.to_str()?.to_owned(), CreditsStyle::Normal)
+                }
+                _ => {
+                    return Err(LuaError::runtime(
+                        "show_credits: lines must be strings or {text, style?, color?} tables",
+                    ));
+                }
+            });
+        }
+    }
+
+    if let Some(callback) = spec.get::<Option<String>>("on_done")? {
+        roll = roll.with_callback(callback);
+    }
+    if let Some(speed) = spec.get::<Option<f32>>("speed")? {
+        roll.speed = speed;
+    }
+    if let Some(multiplier) = spec.get::<Option<f32>>("fast_forward")? {
+        roll.fast_forward = multiplier;
+    }
+    roll.center_x = spec.get::<Option<f32>>("x")?;
+    if let Some(font) = spec.get::<Option<String>>("font")? {
+        roll.font = font;
+    }
+    if let Some(size) = spec.get::<Option<f32>>("font_size")? {
+        roll.font_size = size;
+    }
+    if let Some(spacing) = spec.get::<Option<f32>>("spacing")? {
+        roll.line_spacing = spacing;
+    }
+    Ok(roll)
+}
+
+/// Build a [`ResultsScreen`] from the `engine.show_results` table.
+fn parse_results(spec: LuaTable) -> LuaResult<ResultsScreen> {
+    let mut rows = Vec::new();
+    for row in spec.get::<LuaTable>("rows")?.sequence_values::<LuaTable>() {
+        let row = row?;
+        let label: String = row.get("label")?;
+        let value = match row.get::<Option<String>>("signal")? {
+            Some(key) => ResultValue::Signal(key),
+            None => ResultValue::Number(row.get::<Option<f64>>("value")?.unwrap_or(0.0)),
+        };
+        let decimals = row.get::<Option<usize>>("decimals")?.unwrap_or(0);
+        rows.push(ResultRow::new(label, value).with_decimals(decimals));
+    }
+
+    let mut screen = ResultsScreen::new(rows);
+    if let Some(callback) = spec.get::<Option<String>>("on_done")? {
+        screen = screen.with_callback(callback);
+    }
+    let x = spec.get::<Option<f32>>("x")?.unwrap_or(screen.position.x);
+    let y = spec.get::<Option<f32>>("y")?.unwrap_or(screen.position.y);
+    screen = screen.with_position(x, y);
+    if let Some(offset) = spec.get::<Option<f32>>("value_offset")? {
+        screen.value_offset = offset;
+    }
+    if let Some(spacing) = spec.get::<Option<f32>>("spacing")? {
+        screen.row_spacing = spacing;
+    }
+    if let Some(font) = spec.get::<Option<String>>("font")? {
+        screen.font = font;
+    }
+    if let Some(size) = spec.get::<Option<f32>>("font_size")? {
+        screen.font_size = size;
+    }
+    if let Some(duration) = spec.get::<Option<f32>>("duration")? {
+        screen.count_duration = duration.max(0.0);
+    }
+    Ok(screen)
+}
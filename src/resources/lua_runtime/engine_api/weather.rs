@@ -0,0 +1,94 @@
+use super::*;
+use crate::resources::weather::WeatherKind;
+
+impl LuaRuntime {
+    /// Registers `engine.set_weather`, `engine.set_wind` and `engine.get_weather`.
+    pub(in crate::resources::lua_runtime) fn register_weather_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_weather",
+            weather_commands,
+            |(kind, intensity, transition)| (String, Option<f32>, Option<f32>),
+            WeatherCmd::Set {
+                kind: parse_kind_arg(&kind)?,
+                intensity: intensity.unwrap_or(1.0),
+                transition: transition.unwrap_or(0.0)
+            },
+            desc = "Fade `kind` (\"clear\", \"rain\" or \"snow\") to `intensity` (0 to 1, default 1) \
+                    and the other kinds out over `transition` seconds (default 0)",
+            cat = "weather",
+            params = [
+                ("kind", "string"),
+                ("intensity", "number?"),
+                ("transition", "number?")
+            ]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "set_wind",
+            weather_commands,
+            |(x, y, transition, mask)| (f32, f32, Option<f32>, Option<String>),
+            WeatherCmd::Wind {
+                x,
+                y,
+                transition: transition.unwrap_or(0.0),
+                mask
+            },
+            desc = "Fade the wind to an (x, y) acceleration in units/s² over `transition` seconds. \
+                    It leans the rain and snow and pushes the rigid bodies matching `mask` \
+                    (default \"*\")",
+            cat = "weather",
+            params = [
+                ("x", "number"),
+                ("y", "number"),
+                ("transition", "number?"),
+                ("mask", "string?")
+            ]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "get_weather",
+            |lua, ()| {
+                let snap = lua
+                    .app_data_ref::<LuaAppData>()
+                    .map(|data| *data.weather.borrow())
+                    .unwrap_or_default();
+                let tbl = lua.create_table()?;
+                tbl.set("kind", snap.kind.as_str())?;
+                tbl.set("intensity", snap.intensity)?;
+                tbl.set("target_intensity", snap.target_intensity)?;
+                tbl.set("wind_x", snap.wind.x)?;
+                tbl.set("wind_y", snap.wind.y)?;
+                Ok(tbl)
+            },
+            desc = "Get the weather at the start of the frame: {kind, intensity, target_intensity, \
+                    wind_x, wind_y} (intensity is the current, possibly fading, value)",
+            cat = "weather",
+            params = [],
+            returns = "table"
+        );
+
+        Ok(())
+    }
+}
+
+/// Parse a weather kind argument, raising a Lua error on bad input.
+fn parse_kind_arg(kind: &str) -> LuaResult<WeatherKind> {
+    WeatherKind::parse(kind).ok_or_else(|| {
+        LuaError::runtime(format!(
+            "weather must be \"clear\", \"rain\" or \"snow\", got '{kind}'"
+        ))
+    })
+}
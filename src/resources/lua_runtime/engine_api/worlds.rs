@@ -0,0 +1,98 @@
+use super::*;
+use super::super::entity_builder::LuaEntityBuilder;
+
+impl LuaRuntime {
+    /// Registers the `engine.world_*` functions for secondary worlds.
+    pub(in crate::resources::lua_runtime) fn register_world_api(&self) -> LuaResult<()> {
+        let engine: LuaTable = self.lua.globals().get("engine")?;
+        let meta: LuaTable = engine.get("__meta")?;
+        let meta_fns: LuaTable = meta.get("functions")?;
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_create",
+            world_commands,
+            |(name, width, height)| (String, u32, u32),
+            WorldCmd::Create { name, width, height },
+            desc = "Create (or replace) a secondary world rendered into a width x height texture. \
+             Main-world sprites whose tex_key is the world's name draw that texture.",
+            cat = "world",
+            params = [("name", "string"), ("width", "integer"), ("height", "integer")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_destroy",
+            world_commands,
+            |name| String,
+            WorldCmd::Destroy { name },
+            desc = "Remove a secondary world and all its entities",
+            cat = "world",
+            params = [("name", "string")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_set_paused",
+            world_commands,
+            |(name, paused)| (String, bool),
+            WorldCmd::SetPaused { name, paused },
+            desc = "Pause or resume a secondary world's simulation (it keeps rendering)",
+            cat = "world",
+            params = [("name", "string"), ("paused", "boolean")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_set_camera",
+            world_commands,
+            |(name, x, y, zoom)| (String, f32, f32, Option<f32>),
+            WorldCmd::SetCamera {
+                name,
+                x,
+                y,
+                zoom: zoom.unwrap_or(1.0)
+            },
+            desc = "Center a secondary world's camera on (x, y) with an optional zoom (default 1)",
+            cat = "world",
+            params = [("name", "string"), ("x", "number"), ("y", "number"), ("zoom", "number?")]
+        );
+
+        register_cmd!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_set_background",
+            world_commands,
+            |(name, r, g, b)| (String, u8, u8, u8),
+            WorldCmd::SetBackground { name, r, g, b },
+            desc = "Set the clear color of a secondary world's texture",
+            cat = "world",
+            params = [("name", "string"), ("r", "integer"), ("g", "integer"), ("b", "integer")]
+        );
+
+        register_fn!(
+            engine,
+            self.lua,
+            meta_fns,
+            "world_spawn",
+            |_, name: String| Ok(LuaEntityBuilder::new_in_world(name)),
+            desc = "Create an entity builder that spawns into a secondary world. \
+             Only position, sprite, rotation, scale, tint, z-index, velocity, tweens and TTL \
+             have an effect there; callbacks and collisions do not run.",
+            cat = "world",
+            params = [("name", "string")],
+            returns = "EntityBuilder"
+        );
+
+        Ok(())
+    }
+}
@@ -6,20 +6,196 @@
 //! The builder supports both spawning new entities and cloning existing ones,
 //! in both regular and collision contexts.
 
+use crate::components::attractor::Attractor;
+use crate::components::audiotriggers::{AudioTriggerEvent, AudioTriggers};
+use crate::components::blobshadow::BlobShadow;
+use crate::components::cooldown::Cooldown;
+use crate::components::gridlayout::{GridIntro, GridValue, IntroPreset};
 use crate::components::guibutton::GuiButton;
 use crate::components::guiimage::GuiImage;
 use crate::components::guilabel::GuiLabel;
 use crate::components::guiprogressbar::{GuiProgressBar, ProgressBarDirection};
 use crate::components::guiwindow::GuiWindow;
+use crate::components::height::{Height, ZVelocity};
+use crate::components::hitbox::FrameBoxes;
+use crate::components::interactable::Interactable;
+use crate::components::inventory::{GuiInventoryGrid, Inventory};
+use crate::components::joint::{DistanceJoint, PinJoint};
+use crate::components::nineslice::NineSlice;
+use crate::components::offscreenindicator::OffscreenIndicator;
+use crate::components::ownedby::OwnedBy;
+use crate::components::paletteswap::PaletteSwap;
+use crate::components::pathfollow::PathFollow;
+use crate::components::playerindex::PlayerIndex;
+use crate::components::portal::{Portal, PortalDestination, SpawnPoint};
+use crate::components::progress::{ProgressBar, ProgressSignal, RadialProgress};
+use crate::components::renderlayer::RenderLayer;
+use crate::components::restoreonreturn::RestoreOnReturn;
+use crate::components::rope::{Rope, RopeAnchor};
+use crate::components::sensor::SensorRay;
+use crate::components::slope::{Slope, SlopeCorner};
+use crate::components::terrain::Terrain;
+use crate::components::textscroller::{ScrollerPath, TextScroller};
+use crate::components::tilemap::TileProjection;
+use crate::components::tween::{Easing, LoopMode};
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::components::visionsource::VisionSource;
+use crate::components::watersurface::WaterSurface;
+use crate::components::worldanchorui::WorldAnchorUI;
 use crate::components::Themed;
-use raylib::prelude::Vector2;
-use super::commands::{CloneCmd, UniformValue};
+use crate::resources::playerinput::MAX_PLAYERS;
+use raylib::prelude::{Color, Rectangle, Vector2};
+use super::commands::{CloneCmd, UniformValue, WorldCmd};
 use super::runtime::LuaAppData;
 use super::spawn_data::*;
 use super::stub_meta::BuilderMethodDef;
 use mlua::prelude::*;
 use mlua::MaybeSend;
 
+/// Parse a collision rule side: a pattern string or a table of names joined
+/// into an `"a|b"` alternative pattern.
+pub(super) fn parse_group_pattern(val: LuaValue, arg: &str) -> LuaResult<String> {
+    match val {
+        LuaValue::String(s) => Ok(s.to_str()?.to_owned()),
+        LuaValue::Table(t) => {
+            let names = t
+                .sequence_values::<String>()
+                .collect::<LuaResult<Vec<_>>>()?;
+            if names.is_empty() {
+                return Err(LuaError::runtime(format!("{arg} group list is empty")));
+            }
+            Ok(names.join("|"))
+        }
+        other => Err(LuaError::runtime(format!(
+            "{arg} must be a string or a table of strings, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+/// Parse a render layer: a layer name or a number from 0 to 255.
+pub(super) fn parse_render_layer(fn_name: &str, val: &LuaValue) -> LuaResult<RenderLayer> {
+    let layer = match val {
+        LuaValue::String(s) => RenderLayer::parse(&s.to_str()?),
+        LuaValue::Integer(n) => u8::try_from(*n).ok().map(RenderLayer),
+        _ => None,
+    };
+    layer.ok_or_else(|| {
+        LuaError::runtime(format!(
+            "{fn_name}: unknown layer {val:?} (expected \"background\", \"gameplay\", \"fx\", \"ui\", \"debug\" or 0-255)"
+        ))
+    })
+}
+
+/// The rope being built, for the methods tuning it.
+fn rope_mut<'a>(this: &'a mut LuaEntityBuilder, fn_name: &str) -> LuaResult<&'a mut Rope> {
+    this.cmd
+        .rope
+        .as_mut()
+        .ok_or_else(|| LuaError::runtime(format!("{fn_name}() requires with_rope() first")))
+}
+
+/// Import the frame boxes of `slice` from the Aseprite JSON at `path`,
+/// relative to the origin of the sprite set so far.
+fn import_aseprite_boxes(
+    boxes: &mut FrameBoxes,
+    sprite: Option<&SpriteData>,
+    path: &str,
+    slice: &str,
+) -> LuaResult<()> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| LuaError::runtime(format!("cannot read '{path}': {err}")))?;
+    let origin = sprite.map_or(Vector2::zero(), |s| Vector2::new(s.origin_x, s.origin_y));
+    boxes
+        .import_aseprite(&json, slice, origin)
+        .map_err(|err| LuaError::runtime(format!("{path}: {err}")))?;
+    Ok(())
+}
+
+/// Rope anchor `(offset_x, offset_y)` from the entity `entity_id`.
+fn rope_entity_anchor(
+    entity_id: u64,
+    offset_x: Option<f32>,
+    offset_y: Option<f32>,
+) -> LuaResult<RopeAnchor> {
+    let entity = bevy_ecs::prelude::Entity::try_from_bits(entity_id)
+        .ok_or_else(|| LuaError::runtime(format!("invalid entity id {entity_id}")))?;
+    Ok(RopeAnchor::Entity {
+        entity,
+        offset: Vector2::new(offset_x.unwrap_or(0.0), offset_y.unwrap_or(0.0)),
+    })
+}
+
+/// Options shared by `with_progress_bar()` and `with_radial_progress()`.
+struct ProgressOpts {
+    value: f32,
+    max: f32,
+    fill_color: Color,
+    background_color: Option<Color>,
+    background: Option<String>,
+    offset: Vector2,
+    signal: Option<ProgressSignal>,
+}
+
+/// Parse the shared progress options. `background_color = false` leaves the
+/// empty part transparent.
+fn parse_progress_opts(fn_name: &str, opts: Option<&LuaTable>) -> LuaResult<ProgressOpts> {
+    let mut parsed = ProgressOpts {
+        value: 0.0,
+        max: 1.0,
+        fill_color: Color::LIME,
+        background_color: Some(Color::DARKGRAY),
+        background: None,
+        offset: Vector2::zero(),
+        signal: None,
+    };
+    let Some(opts) = opts else {
+        return Ok(parsed);
+    };
+    let color = |table: LuaTable, key: &str| -> LuaResult<Color> {
+        let c: Vec<u8> = table.sequence_values::<u8>().collect::<LuaResult<_>>()?;
+        if c.len() < 3 {
+            return Err(LuaError::runtime(format!("{fn_name}: {key} needs {{r, g, b, a?}}")));
+        }
+        Ok(Color::new(c[0], c[1], c[2], *c.get(3).unwrap_or(&255)))
+    };
+    parsed.value = opts.get::<Option<f32>>("value")?.unwrap_or(parsed.value);
+    parsed.max = opts.get::<Option<f32>>("max")?.unwrap_or(parsed.max);
+    if let Some(table) = opts.get::<Option<LuaTable>>("color")? {
+        parsed.fill_color = color(table, "color")?;
+    }
+    match opts.get::<LuaValue>("background_color")? {
+        LuaValue::Nil => {}
+        LuaValue::Boolean(false) => parsed.background_color = None,
+        LuaValue::Table(table) => parsed.background_color = Some(color(table, "background_color")?),
+        other => {
+            return Err(LuaError::runtime(format!(
+                "{fn_name}: background_color must be a table or false, got {}",
+                other.type_name()
+            )));
+        }
+    }
+    parsed.background = opts.get::<Option<String>>("background")?;
+    parsed.offset = Vector2::new(
+        opts.get::<Option<f32>>("offset_x")?.unwrap_or(0.0),
+        opts.get::<Option<f32>>("offset_y")?.unwrap_or(0.0),
+    );
+    let entity = opts.get::<Option<u64>>("entity")?;
+    parsed.signal = match (opts.get::<Option<String>>("signal")?, entity) {
+        (Some(key), None) => Some(ProgressSignal::world(key)),
+        (Some(key), Some(id)) => {
+            let entity = bevy_ecs::prelude::Entity::try_from_bits(id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {id}")))?;
+            Some(ProgressSignal::entity(entity, key))
+        }
+        (None, Some(_)) => {
+            return Err(LuaError::runtime(format!("{fn_name}: entity requires signal")));
+        }
+        (None, None) => None,
+    };
+    Ok(parsed)
+}
+
 /// Parse a Lua value into a UniformValue.
 ///
 /// Numbers are treated as Float, tables of length 2 as Vec2, and tables of length 4 as Vec4.
@@ -86,6 +262,8 @@ pub struct LuaEntityBuilder {
     context: BuilderContext,
     /// Only used in Clone mode - WorldSignals key for source entity
     source_key: Option<String>,
+    /// Only set by `engine.world_spawn()` - name of the secondary world to spawn into
+    target_world: Option<String>,
     cmd: SpawnCmd,
 }
 
@@ -96,6 +274,7 @@ impl LuaEntityBuilder {
             mode: BuilderMode::Spawn,
             context: BuilderContext::Regular,
             source_key: None,
+            target_world: None,
             cmd: SpawnCmd::default(),
         }
     }
@@ -106,16 +285,26 @@ impl LuaEntityBuilder {
             mode: BuilderMode::Spawn,
             context: BuilderContext::Collision,
             source_key: None,
+            target_world: None,
             cmd: SpawnCmd::default(),
         }
     }
 
+    /// Create a new spawn builder targeting the secondary world `world`.
+    pub fn new_in_world(world: String) -> Self {
+        Self {
+            target_world: Some(world),
+            ..Self::new()
+        }
+    }
+
     /// Create a new clone builder (regular context).
     pub fn new_clone(source_key: String) -> Self {
         Self {
             mode: BuilderMode::Clone,
             context: BuilderContext::Regular,
             source_key: Some(source_key),
+            target_world: None,
             cmd: SpawnCmd::default(),
         }
     }
@@ -126,9 +315,47 @@ impl LuaEntityBuilder {
             mode: BuilderMode::Clone,
             context: BuilderContext::Collision,
             source_key: Some(source_key),
+            target_world: None,
             cmd: SpawnCmd::default(),
         }
     }
+
+    /// Lua entry point that created this builder, for spawn error reports.
+    fn builder_name(&self) -> &'static str {
+        if self.target_world.is_some() {
+            return "engine.world_spawn()";
+        }
+        match (self.mode, self.context) {
+            (BuilderMode::Spawn, BuilderContext::Regular) => "engine.spawn()",
+            (BuilderMode::Spawn, BuilderContext::Collision) => "engine.collision_spawn()",
+            (BuilderMode::Clone, BuilderContext::Regular) => "engine.clone()",
+            (BuilderMode::Clone, BuilderContext::Collision) => "engine.collision_clone()",
+        }
+    }
+}
+
+/// Number of Lua frames above `:build()` recorded in a [`SpawnOrigin`].
+const SPAWN_ORIGIN_FRAMES: usize = 4;
+
+/// Capture the `file:line` of the Lua code calling `:build()` and its callers.
+///
+/// Level 0 is `build` itself (a C function), so the walk starts at level 1.
+fn capture_spawn_traceback(lua: &Lua) -> String {
+    let mut frames = Vec::with_capacity(SPAWN_ORIGIN_FRAMES);
+    for level in 1..=SPAWN_ORIGIN_FRAMES {
+        let Some(frame) = lua.inspect_stack(level, |debug| {
+            let source = debug.source();
+            let file = source.short_src.as_deref().unwrap_or("?").to_string();
+            match debug.current_line() {
+                Some(line) => format!("{file}:{line}"),
+                None => file,
+            }
+        }) else {
+            break;
+        };
+        frames.push(frame);
+    }
+    frames.join(" <- ")
 }
 
 /// Registers a `with_*` builder method and, when a metadata collector is present, records its
@@ -252,6 +479,20 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_tags", "Add extra tag labels matched by collision rules and group tracking",
+        [("tags", "string[]")],
+        |_, this: &mut LuaEntityBuilder, tags: Vec<String>| {
+            for tag in tags {
+                if !this.cmd.tags.contains(&tag) {
+                    this.cmd.tags.push(tag);
+                }
+            }
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_position", "Set world position",
@@ -405,6 +646,33 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_mass", "Set mass dividing forces, force areas, wind and impulses (creates RigidBody if needed)",
+        [("mass", "number")],
+        |_, this: &mut LuaEntityBuilder, mass: f32| {
+            if let Some(ref mut rb) = this.cmd.rigidbody {
+                rb.mass = Some(mass);
+            } else {
+                this.cmd.rigidbody = Some(RigidBodyData {
+                    mass: Some(mass),
+                    ..RigidBodyData::default()
+                });
+            }
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_wind", "Push the entity's rigid body with the global wind times `factor` (default 1), whatever the wind mask",
+        [("factor", "number?")],
+        |_, this: &mut LuaEntityBuilder, factor: Option<f32>| {
+            this.cmd.wind = Some(factor.unwrap_or(1.0));
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_frozen", "Mark entity as frozen (physics skipped)",
@@ -460,6 +728,31 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_pixel_collision", "Only report collisions where the sprite's opaque pixels overlap; the alpha mask comes from the sprite's texture as loaded by engine.load_texture",
+        [],
+        |_, this: &mut LuaEntityBuilder, (): ()| {
+            this.cmd.pixel_collision = true;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_slope", "Make the collider a right-triangle ramp with its right angle in `corner` (\"bottom_left\", \"bottom_right\", \"top_left\" or \"top_right\"); boxes touching it are pushed straight out onto the slanted side",
+        [("corner", "string")],
+        |_, this: &mut LuaEntityBuilder, corner: String| {
+            let corner = SlopeCorner::parse(&corner).ok_or_else(|| {
+                LuaError::runtime(format!(
+                    "with_slope: unknown corner '{corner}' (expected \"bottom_left\", \"bottom_right\", \"top_left\" or \"top_right\")"
+                ))
+            })?;
+            this.cmd.slope = Some(Slope::new(corner));
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_mouse_controlled", "Enable mouse position tracking",
@@ -647,7 +940,7 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
 
     builder_method!(
         methods, meta,
-        "with_gui_theme_key", "Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar (default \"default\"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar() first.",
+        "with_gui_theme_key", "Set the theme lookup key (GuiThemeStore) for a GuiWindow/GuiButton/GuiLabel/GuiProgressBar/GuiInventoryGrid (default \"default\"). Requires one of :with_gui_window()/:with_gui_button()/:with_gui_label()/:with_gui_progress_bar()/:with_gui_inventory_grid() first.",
         [("key", "string")],
         |_, this: &mut LuaEntityBuilder, key: String| {
             let key: std::sync::Arc<str> = std::sync::Arc::from(key.as_str());
@@ -658,9 +951,10 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
                 && !apply(&mut this.cmd.gui_button, &key)
                 && !apply(&mut this.cmd.gui_label, &key)
                 && !apply(&mut this.cmd.gui_progress_bar, &key)
+                && !apply(&mut this.cmd.gui_inventory_grid, &key)
             {
                 return Err(LuaError::runtime(
-                    "with_gui_theme_key() requires with_gui_window()/with_gui_button()/with_gui_label()/with_gui_progress_bar() first",
+                    "with_gui_theme_key() requires with_gui_window()/with_gui_button()/with_gui_label()/with_gui_progress_bar()/with_gui_inventory_grid() first",
                 ));
             }
             Ok(())
@@ -782,6 +1076,32 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_inventory", "Give the entity an Inventory with `slots` empty slots. Fill it with engine.inventory_add().",
+        [("slots", "integer")],
+        |_, this: &mut LuaEntityBuilder, slots: usize| {
+            this.cmd.inventory = Some(Inventory::new(slots));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_gui_inventory_grid", "Set GuiInventoryGrid component: draws the Inventory of `inventory_id` as `columns` slots per row of `cell_size` pixels, `spacing` apart (default 2), with item icons and stack counts. Slots use the theme's label nine-patch (panel if unset); see :with_gui_theme_key(). Requires :with_screen_position() (or :with_parent()+:with_gui_offset()) and :with_zindex() to render.",
+        [("inventory_id", "integer"), ("columns", "integer"), ("cell_size", "number"), ("spacing", "number?")],
+        |_, this: &mut LuaEntityBuilder, (inventory_id, columns, cell_size, spacing): (u64, u32, f32, Option<f32>)| {
+            let inventory = bevy_ecs::prelude::Entity::try_from_bits(inventory_id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {inventory_id}")))?;
+            let mut grid = GuiInventoryGrid::new(inventory, columns, cell_size);
+            if let Some(spacing) = spacing {
+                grid = grid.with_spacing(spacing);
+            }
+            this.cmd.gui_inventory_grid = Some(grid);
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_text", "Set DynamicText component",
@@ -800,6 +1120,90 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_text_scroller", "Scroll `content` glyph by glyph along a path, starting at the far end of the track and starting over when the text has left it. `path` is \"sine\" (default), \"wave\" or \"circle\" (around this entity). opts: {width? (track length, default 640), amplitude? (wave height or circle radius, default font_size), wavelength? (default 256), spacing? (between glyphs, default font_size), color? ({r, g, b, a?}, default white)}",
+        [
+            ("content", "string"),
+            ("font", "string"),
+            ("font_size", "number"),
+            ("speed", "number"),
+            ("path", "string?"),
+            ("opts", "table?"),
+        ],
+        |_, this: &mut LuaEntityBuilder, (content, font, font_size, speed, path, opts): (String, String, f32, f32, Option<String>, Option<LuaTable>)| {
+            let get = |key: &str| -> LuaResult<Option<f32>> {
+                opts.as_ref().map_or(Ok(None), |opts| opts.get(key))
+            };
+            let path_name = path.as_deref().unwrap_or("sine");
+            let path = ScrollerPath::parse(
+                path_name,
+                get("amplitude")?.unwrap_or(font_size),
+                get("wavelength")?.unwrap_or(256.0),
+            )
+            .ok_or_else(|| {
+                LuaError::runtime(format!("with_text_scroller: unknown path '{path_name}'"))
+            })?;
+            let mut scroller = TextScroller::new(content, font, font_size, speed, path);
+            if let Some(width) = get("width")? {
+                scroller = scroller.with_width(width);
+            }
+            if let Some(spacing) = get("spacing")? {
+                scroller = scroller.with_spacing(spacing);
+            }
+            if let Some(color) = opts
+                .as_ref()
+                .map(|opts| opts.get::<Option<LuaTable>>("color"))
+                .transpose()?
+                .flatten()
+            {
+                let c: Vec<u8> = color.sequence_values::<u8>().collect::<LuaResult<_>>()?;
+                if c.len() < 3 {
+                    return Err(LuaError::runtime(
+                        "with_text_scroller: color needs {r, g, b, a?}",
+                    ));
+                }
+                scroller = scroller.with_color(Color::new(c[0], c[1], c[2], *c.get(3).unwrap_or(&255)));
+            }
+            this.cmd.text_scroller = Some(scroller);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_path_follow", "Move the entity along waypoints at `speed` units per second. `points` is a list of {x, y} tables (at least 2). opts: {spline? (Catmull-Rom curve through the points, default false), closed? (join the last point to the first), loop? (\"once\" (default), \"loop\", \"ping_pong\"), easing? (tween easing over the whole path), orient? (rotate to face the direction of travel), rotation_offset? (degrees added when orienting)}",
+        [("points", "table"), ("speed", "number"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (points_table, speed, opts): (LuaTable, f32, Option<LuaTable>)| {
+            let mut points = Vec::new();
+            for value in points_table.sequence_values::<LuaTable>() {
+                let point = value?;
+                points.push(Vector2::new(point.get("x")?, point.get("y")?));
+            }
+            if points.len() < 2 {
+                return Err(LuaError::runtime(
+                    "with_path_follow: needs at least 2 points",
+                ));
+            }
+            let mut follow = PathFollow::new(points, speed);
+            if let Some(opts) = opts {
+                follow = follow
+                    .with_spline(opts.get::<Option<bool>>("spline")?.unwrap_or(false))
+                    .with_closed(opts.get::<Option<bool>>("closed")?.unwrap_or(false))
+                    .with_orient(opts.get::<Option<bool>>("orient")?.unwrap_or(false))
+                    .with_rotation_offset(opts.get::<Option<f32>>("rotation_offset")?.unwrap_or(0.0));
+                if let Some(loop_mode) = opts.get::<Option<String>>("loop")? {
+                    follow = follow.with_loop_mode(loop_mode.parse().unwrap_or(LoopMode::Once));
+                }
+                if let Some(easing) = opts.get::<Option<String>>("easing")? {
+                    follow = follow.with_easing(easing.parse().unwrap_or(Easing::Linear));
+                }
+            }
+            this.cmd.path_follow = Some(follow);
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_menu", "Add interactive menu",
@@ -1085,6 +1489,29 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_cooldown",
+        "Add a cooldown of `duration` seconds whose remaining fraction (1 just started, 0 ready) is written every frame to the entity's scalar signal `signal`, ready to bind to a bar. Start it with engine.entity_start_cooldown(). opts: {auto_reset? (start over as soon as it elapses, default false), started? (begin cooling down instead of ready, default false), world? (also write the fraction to the world signal `signal`, which :with_gui_progress_bar_signal_binding() reads, default false)}",
+        [("duration", "number"), ("signal", "string"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (duration, signal, opts): (f32, String, Option<LuaTable>)| {
+            let mut cooldown = Cooldown::new(duration, signal);
+            if let Some(opts) = opts {
+                if opts.get::<Option<bool>>("auto_reset")?.unwrap_or(false) {
+                    cooldown = cooldown.with_auto_reset();
+                }
+                if opts.get::<Option<bool>>("started")?.unwrap_or(false) {
+                    cooldown = cooldown.started();
+                }
+                if opts.get::<Option<bool>>("world")?.unwrap_or(false) {
+                    cooldown = cooldown.with_world_signal();
+                }
+            }
+            this.cmd.cooldown = Some(cooldown);
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_signal_binding", "Bind text to a WorldSignal value",
@@ -1120,6 +1547,39 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_grid_intro", "Stagger the grid cells' appearance: preset \"scale_in\" or \"drop_in\", `delay` seconds per step of `order` (\"cells\", \"rows\", \"columns\", \"diagonal\"), `height` is the drop_in fall (default 64)",
+        [
+            ("preset", "string"),
+            ("delay", "number"),
+            ("duration", "number"),
+            ("order", "string?"),
+            ("height", "number?"),
+        ],
+        |_, this: &mut LuaEntityBuilder, (preset, delay, duration, order, height): (String, f32, f32, Option<String>, Option<f32>)| {
+            if this.cmd.grid_layout.is_none() {
+                return Err(LuaError::runtime(
+                    "with_grid_intro() requires with_grid_layout() first",
+                ));
+            }
+            let preset = match preset.as_str() {
+                "scale_in" => IntroPreset::ScaleIn,
+                "drop_in" => IntroPreset::DropIn {
+                    height: height.unwrap_or(64.0),
+                },
+                other => {
+                    return Err(LuaError::runtime(format!(
+                        "with_grid_intro(): unknown preset '{other}'"
+                    )));
+                }
+            };
+            let order = order.and_then(|o| o.parse().ok()).unwrap_or_default();
+            this.cmd.grid_intro = Some(GridIntro::new(preset, delay, duration).with_order(order));
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_tween_position", "Add position tween animation",
@@ -1442,18 +1902,50 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
 
     builder_method!(
         methods, meta,
-        "with_lua_collision_rule", "Add collision callback between two groups",
-        [("group_a", "string"), ("group_b", "string"), ("callback", "string")],
-        |_, this: &mut LuaEntityBuilder, (group_a, group_b, callback): (String, String, String)| {
+        "with_lua_collision_rule", "Add collision callback between two groups. Each side is a group/tag name, a pattern (\"*\" matches anything, \"enemy_*\" matches by prefix, \"a|b\" lists alternatives) or a table of names",
+        [("group_a", "string|string[]"), ("group_b", "string|string[]"), ("callback", "string")],
+        |_, this: &mut LuaEntityBuilder, (group_a, group_b, callback): (LuaValue, LuaValue, String)| {
             this.cmd.lua_collision_rule = Some(LuaCollisionRuleData {
-                group_a,
-                group_b,
+                group_a: parse_group_pattern(group_a, "group_a")?,
+                group_b: parse_group_pattern(group_b, "group_b")?,
                 callback,
+                priority: 0,
+                exclusive: true,
             });
             Ok(())
         }
     );
 
+    builder_method!(
+        methods, meta,
+        "with_lua_collision_rule_priority", "Set the dispatch priority of the collision rule; when several rules match one collision, higher priorities run first (default 0)",
+        [("priority", "integer")],
+        |_, this: &mut LuaEntityBuilder, priority: i32| {
+            let Some(ref mut rule) = this.cmd.lua_collision_rule else {
+                return Err(LuaError::runtime(
+                    "with_lua_collision_rule_priority() requires with_lua_collision_rule() first",
+                ));
+            };
+            rule.priority = priority;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_lua_collision_rule_exclusive", "Set whether the collision rule stops lower-priority matching rules from running (default true)",
+        [("exclusive", "boolean")],
+        |_, this: &mut LuaEntityBuilder, exclusive: bool| {
+            let Some(ref mut rule) = this.cmd.lua_collision_rule else {
+                return Err(LuaError::runtime(
+                    "with_lua_collision_rule_exclusive() requires with_lua_collision_rule() first",
+                ));
+            };
+            rule.exclusive = exclusive;
+            Ok(())
+        }
+    );
+
     builder_method!(
         methods, meta,
         "with_animation", "Set animation by key",
@@ -1686,255 +2178,1764 @@ fn register_methods<M: LuaUserDataMethods<LuaEntityBuilder>>(
 
     builder_method!(
         methods, meta,
-        "with_shader", "Set per-entity shader with optional uniforms",
-        [("shader_key", "string"), ("uniforms", "table?")],
-        |_, this: &mut LuaEntityBuilder, args: mlua::MultiValue| {
-            let mut iter = args.into_iter();
-            let key_val = iter
-                .next()
-                .ok_or_else(|| LuaError::runtime("with_shader requires shader_key"))?;
-            let shader_key: String = key_val
-                .as_string()
-                .and_then(|s| s.to_str().ok())
-                .ok_or_else(|| LuaError::runtime("shader_key must be string"))?
-                .to_string();
-
-            let mut uniforms = Vec::new();
-
-            if let Some(table_val) = iter.next()
-                && let Some(table) = table_val.as_table()
-            {
-                for pair in table.pairs::<String, LuaValue>() {
-                    let (name, val) = pair?;
-                    let uniform = parse_uniform_value(val)?;
-                    uniforms.push((name, uniform));
+        "with_blob_shadow",
+        "Draw a translucent ellipse on the ground under this entity's sprite, shrinking and fading to 40% as the entity rises. opts: {opacity? (0-1, default 0.5), offset_x?, offset_y? (ellipse centre relative to the position), height_signal? (key of this entity's signals holding its height above the ground), ground_y? (world Y the ellipse stays on; the height is the distance down to it), max_height? (height of the smallest ellipse, default 64)}",
+        [("width", "number"), ("height", "number"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (width, height, opts): (f32, f32, Option<LuaTable>)| {
+            let mut blob = BlobShadow::new(width, height);
+            if let Some(opts) = &opts {
+                blob = blob.with_offset(
+                    opts.get::<Option<f32>>("offset_x")?.unwrap_or(0.0),
+                    opts.get::<Option<f32>>("offset_y")?.unwrap_or(0.0),
+                );
+                if let Some(opacity) = opts.get::<Option<f32>>("opacity")? {
+                    blob = blob.with_opacity(opacity);
+                }
+                if let Some(key) = opts.get::<Option<String>>("height_signal")? {
+                    blob = blob.with_height_signal(key);
+                }
+                if let Some(ground_y) = opts.get::<Option<f32>>("ground_y")? {
+                    blob = blob.with_ground_y(ground_y);
+                }
+                if let Some(max_height) = opts.get::<Option<f32>>("max_height")? {
+                    blob = blob.with_max_height(max_height);
                 }
             }
+            this.cmd.blob_shadow = Some(blob);
+            Ok(())
+        }
+    );
 
-            this.cmd.shader = Some(EntityShaderData { key: shader_key, uniforms });
+    builder_method!(
+        methods, meta,
+        "with_height",
+        "Give the entity a faux-3D height: the sprite is drawn height pixels above its position, which stays on the ground for collisions and sorting, and falls back under its own gravity. Landing triggers :with_on_land() and bounces. opts: {gravity? (default 980, 0 hovers), bounce? (share of the landing speed kept, 0-1, default 0), velocity? (initial upward speed)}",
+        [("height", "number"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (height, opts): (f32, Option<LuaTable>)| {
+            let mut z_velocity = ZVelocity::default();
+            if let Some(opts) = &opts {
+                if let Some(gravity) = opts.get::<Option<f32>>("gravity")? {
+                    z_velocity.gravity = gravity;
+                }
+                if let Some(bounce) = opts.get::<Option<f32>>("bounce")? {
+                    z_velocity = z_velocity.with_bounce(bounce);
+                }
+                if let Some(velocity) = opts.get::<Option<f32>>("velocity")? {
+                    z_velocity = z_velocity.launched(velocity);
+                }
+            }
+            this.cmd.height = Some((Height(height.max(0.0)), z_velocity));
             Ok(())
         }
     );
 
     builder_method!(
         methods, meta,
-        "with_parent", "Set parent entity for transform hierarchy",
-        [("parent_id", "integer")],
-        |_, this: &mut LuaEntityBuilder, parent_id: u64| {
-            this.cmd.parent = Some(parent_id);
+        "with_render_layer", "Set render layer (\"background\", \"gameplay\", \"fx\", \"ui\", \"debug\" or 0-255) toggled by engine.set_layer_visible(); draw order still follows :with_zindex()",
+        [("layer", "string|integer")],
+        |_, this: &mut LuaEntityBuilder, layer: LuaValue| {
+            this.cmd.render_layer = Some(parse_render_layer("with_render_layer", &layer)?);
             Ok(())
         }
     );
 
     builder_method!(
         methods, meta,
-        "with_tilemap",
-        "Spawn a tilemap root. All tile entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap.",
-        [("path", "string")],
-        |_, this: &mut LuaEntityBuilder, path: String| {
-            this.cmd.tilemap_path = Some(path);
+        "with_palette_swap", "Recolor the sprite through an N×2 palette texture: each color of the top row becomes the color below it. World-space sprites only; an entity shader takes precedence",
+        [("palette_tex_key", "string")],
+        |_, this: &mut LuaEntityBuilder, palette: String| {
+            this.cmd.palette_swap = Some(PaletteSwap::new(palette));
             Ok(())
         }
     );
 
     builder_method!(
         methods, meta,
-        "with_lua_setup",
-        "Attach a one-shot Lua setup callback. The named function is called once (Added<LuaSetup>) with the entity context. Fires the frame after spawn; child entities added inside the callback appear the following frame.",
-        [("callback", "string")],
-        |_, this: &mut LuaEntityBuilder, callback: String| {
-            this.cmd.lua_setup = Some(callback);
+        "with_player", "Make local player 1-4 the owner: the entity's input control and menu follow the device given to that player with engine.assign_device()",
+        [("player", "integer")],
+        |_, this: &mut LuaEntityBuilder, player: u8| {
+            if !(1..=MAX_PLAYERS as u8).contains(&player) {
+                return Err(LuaError::runtime(format!(
+                    "with_player: player must be 1 to {MAX_PLAYERS}, got {player}"
+                )));
+            }
+            this.cmd.player = Some(PlayerIndex(player - 1));
             Ok(())
         }
     );
 
     builder_method!(
         methods, meta,
-        "with_on_animation_end",
-        "Attach a callback fired exactly once when the entity's non-looped animation first reaches its last frame. Signature: fn(ctx, input). Looped animations never trigger it.",
-        [("fn_name", "string")],
-        |_, this: &mut LuaEntityBuilder, callback: String| {
-            this.cmd.lua_on_animation_end = Some(callback);
+        "with_terrain", "Make this entity destructible terrain: a copy of texture tex_key drawn with its top-left at the entity's position, with a collider and pixel collision. Carve it with engine.terrain_carve_circle()",
+        [("tex_key", "string")],
+        |_, this: &mut LuaEntityBuilder, tex_key: String| {
+            this.cmd.terrain = Some(Terrain::new(tex_key));
             Ok(())
         }
     );
 
     builder_method!(
         methods, meta,
-        "with_camera_target",
-        "Mark entity as camera follow target (higher priority wins). zoom is the desired camera zoom when this target wins (default 1.0).",
-        [("priority", "integer?"), ("zoom", "number?")],
-        |_, this: &mut LuaEntityBuilder, (priority, zoom): (Option<u8>, Option<f32>)| {
-            this.cmd.camera_target = Some(priority.unwrap_or(0));
-            this.cmd.camera_target_zoom = zoom;
+        "with_attractor", "Pull rigid bodies labeled `target` (group or tag) within `radius` toward this entity with an acceleration of `strength` units/s² (negative repels)",
+        [("radius", "number"), ("strength", "number"), ("target", "string")],
+        |_, this: &mut LuaEntityBuilder, (radius, strength, target): (f32, f32, String)| {
+            this.cmd.attractor = Some(Attractor::new(radius, strength, target));
             Ok(())
         }
     );
 
-    // Non-builder-stub methods — kept as plain registrations.
-    // These have their own entries in stub_meta's non-BUILDER_METHODS sections.
+    builder_method!(
+        methods, meta,
+        "with_audio_trigger", "Play sound effect `sound_id` on an entity event: \"spawn\", \"despawn\" (not on scene changes), \"collision\" or \"collision:<group>\" (when contact starts). The trigger can't play again for `cooldown` seconds (default 0). Can be called multiple times",
+        [("event", "string"), ("sound_id", "string"), ("cooldown", "number?")],
+        |_, this: &mut LuaEntityBuilder, (event, sound, cooldown): (String, String, Option<f32>)| {
+            let parsed = AudioTriggerEvent::parse(&event).ok_or_else(|| {
+                LuaError::runtime(format!("with_audio_trigger: unknown event '{event}'"))
+            })?;
+            this.cmd
+                .audio_triggers
+                .get_or_insert_with(AudioTriggers::default)
+                .push(parsed, sound, cooldown.unwrap_or(0.0));
+            Ok(())
+        }
+    );
 
-    methods.add_function("register_as", |_, (ud, key): (LuaAnyUserData, String)| {
-        let mut this = ud.borrow_mut::<LuaEntityBuilder>()?;
-        this.cmd.register_as = Some(key);
-        Ok(ud)
-    });
+    builder_method!(
+        methods, meta,
+        "with_vision", "Reveal fog-of-war cells within `radius` of this entity (see engine.fog_enable). With `blocked`, tiles of the fog's solid layers block the line of sight.",
+        [("radius", "number"), ("blocked", "boolean?")],
+        |_, this: &mut LuaEntityBuilder, (radius, blocked): (f32, Option<bool>)| {
+            let vision = VisionSource::new(radius);
+            this.cmd.vision = Some(if blocked.unwrap_or(false) {
+                vision.blocked()
+            } else {
+                vision
+            });
+            Ok(())
+        }
+    );
 
-    methods.add_method_mut("build", |lua, this, ()| {
-        let app_data = lua
-            .app_data_ref::<LuaAppData>()
-            .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+    builder_method!(
+        methods, meta,
+        "with_visibility", "Shadow-cast a visibility polygon of `radius` from this entity against the occluders (see engine.set_occluders). A `fov` below 360 degrees turns it into a cone facing the entity's rotation.",
+        [("radius", "number"), ("fov", "number?")],
+        |_, this: &mut LuaEntityBuilder, (radius, fov): (f32, Option<f32>)| {
+            let color = this.cmd.visibility.take().and_then(|v| v.color);
+            let mut visibility = VisibilityPolygon::new(radius).with_fov(fov.unwrap_or(360.0));
+            visibility.color = color;
+            this.cmd.visibility = Some(visibility);
+            Ok(())
+        }
+    );
 
-        // Take the built command out of the builder rather than cloning it — the
-        // builder is consumed by build() in normal (single-call) usage.
-        match (this.mode, this.context) {
-            (BuilderMode::Spawn, BuilderContext::Regular) => {
-                app_data
-                    .spawn_commands
-                    .borrow_mut()
-                    .push(std::mem::take(&mut this.cmd));
+    builder_method!(
+        methods, meta,
+        "with_visibility_fill", "Draw the visibility polygon filled with a color (RGBA 0-255), as a light or vision cone",
+        [("r", "integer"), ("g", "integer"), ("b", "integer"), ("a", "integer?")],
+        |_, this: &mut LuaEntityBuilder, (r, g, b, a): (u8, u8, u8, Option<u8>)| {
+            let visibility = this.cmd.visibility.take().unwrap_or_else(|| VisibilityPolygon::new(0.0));
+            this.cmd.visibility = Some(visibility.with_color(Color::new(r, g, b, a.unwrap_or(255))));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hitbox", "Give the entity a damage-dealing box (offset from its position) used whenever the current animation has no hitbox frame data",
+        [("x", "number"), ("y", "number"), ("w", "number"), ("h", "number")],
+        |_, this: &mut LuaEntityBuilder, (x, y, w, h): (f32, f32, f32, f32)| {
+            let mut hitbox = this.cmd.hitbox.take().unwrap_or_default();
+            hitbox.boxes.default = Some(Rectangle::new(x, y, w, h));
+            this.cmd.hitbox = Some(hitbox);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hitbox_frame", "Set the hitbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.",
+        [("animation_key", "string"), ("frame", "integer"), ("x", "number"), ("y", "number"), ("w", "number"), ("h", "number")],
+        |_, this: &mut LuaEntityBuilder, (key, frame, x, y, w, h): (String, usize, f32, f32, f32, f32)| {
+            let mut hitbox = this.cmd.hitbox.take().unwrap_or_default();
+            hitbox.boxes.set_frame(key, frame, Rectangle::new(x, y, w, h));
+            this.cmd.hitbox = Some(hitbox);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hitbox_aseprite", "Import hitbox frames from the slice named `slice` (default \"hitbox\") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.",
+        [("path", "string"), ("slice", "string?")],
+        |_, this: &mut LuaEntityBuilder, (path, slice): (String, Option<String>)| {
+            let mut hitbox = this.cmd.hitbox.take().unwrap_or_default();
+            let slice = slice.as_deref().unwrap_or("hitbox");
+            let result = import_aseprite_boxes(&mut hitbox.boxes, this.cmd.sprite.as_ref(), &path, slice);
+            this.cmd.hitbox = Some(hitbox);
+            result
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hitbox_mask", "Only hit hurtbox owners whose group or tags match `mask` (default \"*\")",
+        [("mask", "string")],
+        |_, this: &mut LuaEntityBuilder, mask: String| {
+            let Some(hitbox) = this.cmd.hitbox.as_mut() else {
+                return Err(LuaError::runtime(
+                    "with_hitbox_mask() requires with_hitbox(), with_hitbox_frame() or with_hitbox_aseprite() first",
+                ));
+            };
+            hitbox.mask = mask;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hurtbox", "Give the entity a damage-receiving box (offset from its position) used whenever the current animation has no hurtbox frame data",
+        [("x", "number"), ("y", "number"), ("w", "number"), ("h", "number")],
+        |_, this: &mut LuaEntityBuilder, (x, y, w, h): (f32, f32, f32, f32)| {
+            let mut hurtbox = this.cmd.hurtbox.take().unwrap_or_default();
+            hurtbox.boxes.default = Some(Rectangle::new(x, y, w, h));
+            this.cmd.hurtbox = Some(hurtbox);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hurtbox_frame", "Set the hurtbox for one frame (0-based) of an animation. Frames of that animation without data are inactive.",
+        [("animation_key", "string"), ("frame", "integer"), ("x", "number"), ("y", "number"), ("w", "number"), ("h", "number")],
+        |_, this: &mut LuaEntityBuilder, (key, frame, x, y, w, h): (String, usize, f32, f32, f32, f32)| {
+            let mut hurtbox = this.cmd.hurtbox.take().unwrap_or_default();
+            hurtbox.boxes.set_frame(key, frame, Rectangle::new(x, y, w, h));
+            this.cmd.hurtbox = Some(hurtbox);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_hurtbox_aseprite", "Import hurtbox frames from the slice named `slice` (default \"hurtbox\") of an Aseprite JSON export; each frame tag becomes an animation key. Bounds are made relative to the origin of :with_sprite(), so call it first.",
+        [("path", "string"), ("slice", "string?")],
+        |_, this: &mut LuaEntityBuilder, (path, slice): (String, Option<String>)| {
+            let mut hurtbox = this.cmd.hurtbox.take().unwrap_or_default();
+            let slice = slice.as_deref().unwrap_or("hurtbox");
+            let result = import_aseprite_boxes(&mut hurtbox.boxes, this.cmd.sprite.as_ref(), &path, slice);
+            this.cmd.hurtbox = Some(hurtbox);
+            result
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_force_area", "Make the entity a force area accelerating overlapping rigid bodies whose group or tags match `mask` (default \"*\"). The area is the entity's collider unless :with_force_area_rect() is used.",
+        [("accel_x", "number"), ("accel_y", "number"), ("mask", "string?")],
+        |_, this: &mut LuaEntityBuilder, (ax, ay, mask): (f32, f32, Option<String>)| {
+            let mut area = this.cmd.force_area.take().unwrap_or_default();
+            area.acceleration = Vector2::new(ax, ay);
+            if let Some(mask) = mask {
+                area.mask = mask;
             }
-            (BuilderMode::Spawn, BuilderContext::Collision) => {
-                app_data
-                    .collision_spawn_commands
-                    .borrow_mut()
-                    .push(std::mem::take(&mut this.cmd));
+            this.cmd.force_area = Some(area);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_conveyor", "Make the entity a conveyor carrying overlapping rigid bodies whose group or tags match `mask` (default \"*\") at a constant velocity without changing their own. The area is the entity's collider unless :with_force_area_rect() is used.",
+        [("vel_x", "number"), ("vel_y", "number"), ("mask", "string?")],
+        |_, this: &mut LuaEntityBuilder, (vx, vy, mask): (f32, f32, Option<String>)| {
+            let mut area = this.cmd.force_area.take().unwrap_or_default();
+            area.velocity = Vector2::new(vx, vy);
+            if let Some(mask) = mask {
+                area.mask = mask;
             }
-            (BuilderMode::Clone, BuilderContext::Regular) => {
-                let source_key = this.source_key.take().unwrap_or_default();
-                app_data.clone_commands.borrow_mut().push(CloneCmd {
-                    source_key,
-                    overrides: std::mem::take(&mut this.cmd),
-                });
+            this.cmd.force_area = Some(area);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_force_area_rect", "Set the force area's rectangle relative to the entity position instead of using its collider",
+        [("x", "number"), ("y", "number"), ("width", "number"), ("height", "number")],
+        |_, this: &mut LuaEntityBuilder, (x, y, w, h): (f32, f32, f32, f32)| {
+            let Some(ref mut area) = this.cmd.force_area else {
+                return Err(LuaError::runtime(
+                    "with_force_area_rect() requires with_force_area() or with_conveyor() first",
+                ));
+            };
+            area.rect = Some(Rectangle::new(x, y, w, h));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_distance_joint", "Keep this entity between `min_length` (default `length`, a rigid rod; 0 for a leash) and `length` from entity `target_id`. Rigid bodies are moved by the joint and keep swinging or orbiting; anything else is a fixed anchor. `stiffness` (0-1, default 1) below 1 makes it springy",
+        [("target_id", "integer"), ("length", "number"), ("min_length", "number?"), ("stiffness", "number?")],
+        |_, this: &mut LuaEntityBuilder, (target_id, length, min_length, stiffness): (u64, f32, Option<f32>, Option<f32>)| {
+            let target = bevy_ecs::prelude::Entity::try_from_bits(target_id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {target_id}")))?;
+            let joint = DistanceJoint::new(target, min_length.unwrap_or(length), length);
+            this.cmd.distance_joint = Some(joint.with_stiffness(stiffness.unwrap_or(1.0)));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_pin_joint", "Keep this entity at (offset_x, offset_y) from entity `target_id`. Unlike :with_stuckto(), both rigid bodies are moved, so pushing either drags the other. `stiffness` (0-1, default 1) below 1 makes it springy",
+        [("target_id", "integer"), ("offset_x", "number"), ("offset_y", "number"), ("stiffness", "number?")],
+        |_, this: &mut LuaEntityBuilder, (target_id, ox, oy, stiffness): (u64, f32, f32, Option<f32>)| {
+            let target = bevy_ecs::prelude::Entity::try_from_bits(target_id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {target_id}")))?;
+            let joint = PinJoint::new(target, Vector2::new(ox, oy));
+            this.cmd.pin_joint = Some(joint.with_stiffness(stiffness.unwrap_or(1.0)));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope", "Make the entity a rope of total `length` split into `segments` segments (default one per 8 units), simulated as a chain of points under gravity. Attach its ends with :with_rope_start()/:with_rope_start_entity() and :with_rope_end()/:with_rope_end_entity(); unattached ends hang loose",
+        [("length", "number"), ("segments", "integer?")],
+        |_, this: &mut LuaEntityBuilder, (length, segments): (f32, Option<usize>)| {
+            let segments = segments.unwrap_or_else(|| (length / 8.0).round().max(1.0) as usize);
+            this.cmd.rope = Some(Rope::new(RopeAnchor::Free, RopeAnchor::Free, length, segments));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_start", "Pin the rope's start to world point (x, y)",
+        [("x", "number"), ("y", "number")],
+        |_, this: &mut LuaEntityBuilder, (x, y): (f32, f32)| {
+            rope_mut(this, "with_rope_start")?.start = RopeAnchor::Point(Vector2::new(x, y));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_start_entity", "Attach the rope's start to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along",
+        [("entity_id", "integer"), ("offset_x", "number?"), ("offset_y", "number?")],
+        |_, this: &mut LuaEntityBuilder, (entity_id, ox, oy): (u64, Option<f32>, Option<f32>)| {
+            let anchor = rope_entity_anchor(entity_id, ox, oy)?;
+            rope_mut(this, "with_rope_start_entity")?.start = anchor;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_end", "Pin the rope's end to world point (x, y)",
+        [("x", "number"), ("y", "number")],
+        |_, this: &mut LuaEntityBuilder, (x, y): (f32, f32)| {
+            rope_mut(this, "with_rope_end")?.end = RopeAnchor::Point(Vector2::new(x, y));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_end_entity", "Attach the rope's end to an entity, `offset` from its position. An entity with a rigid body hangs from the rope and swings; any other entity drags the rope along",
+        [("entity_id", "integer"), ("offset_x", "number?"), ("offset_y", "number?")],
+        |_, this: &mut LuaEntityBuilder, (entity_id, ox, oy): (u64, Option<f32>, Option<f32>)| {
+            let anchor = rope_entity_anchor(entity_id, ox, oy)?;
+            rope_mut(this, "with_rope_end_entity")?.end = anchor;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_physics", "Tune the rope: fraction of a stretch corrected per pass (0-1, default 1), fraction of velocity lost per second (default 0.5), correction passes per frame (default 8) and gravity (default 0, 980)",
+        [("stiffness", "number"), ("damping", "number"), ("iterations", "integer?"), ("gravity_x", "number?"), ("gravity_y", "number?")],
+        |_, this: &mut LuaEntityBuilder, (stiffness, damping, iterations, gx, gy): (f32, f32, Option<u32>, Option<f32>, Option<f32>)| {
+            let rope = rope_mut(this, "with_rope_physics")?;
+            let gravity = Vector2::new(gx.unwrap_or(rope.gravity.x), gy.unwrap_or(rope.gravity.y));
+            let iterations = iterations.unwrap_or(rope.iterations);
+            *rope = rope
+                .clone()
+                .with_stiffness(stiffness, iterations)
+                .with_damping(damping)
+                .with_gravity(gravity);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_rope_style", "Draw the rope `thickness` wide in a color (RGBA 0-255)",
+        [("thickness", "number"), ("r", "integer"), ("g", "integer"), ("b", "integer"), ("a", "integer?")],
+        |_, this: &mut LuaEntityBuilder, (thickness, r, g, b, a): (f32, u8, u8, u8, Option<u8>)| {
+            let rope = rope_mut(this, "with_rope_style")?;
+            *rope = rope.clone().with_style(thickness, Color::new(r, g, b, a.unwrap_or(255)));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_water_surface", "Make the entity a body of water `width` wide and `depth` deep below its position, with a spring-simulated surface that splashes when rigid bodies whose group or tags match `mask` (default \"*\") enter it and floats them while submerged",
+        [("width", "number"), ("depth", "number"), ("mask", "string?")],
+        |_, this: &mut LuaEntityBuilder, (width, depth, mask): (f32, f32, Option<String>)| {
+            let mut water = WaterSurface::new(width, depth);
+            if let Some(mask) = mask {
+                water.mask = mask;
             }
-            (BuilderMode::Clone, BuilderContext::Collision) => {
-                let source_key = this.source_key.take().unwrap_or_default();
-                app_data
-                    .collision_clone_commands
-                    .borrow_mut()
-                    .push(CloneCmd {
-                        source_key,
-                        overrides: std::mem::take(&mut this.cmd),
-                    });
+            this.cmd.water_surface = Some(water);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_water_springs", "Tune the water surface: pull back to rest, pull between neighbouring columns, damping and optional column spacing (default 8)",
+        [("stiffness", "number"), ("tension", "number"), ("damping", "number"), ("spacing", "number?")],
+        |_, this: &mut LuaEntityBuilder, (stiffness, tension, damping, spacing): (f32, f32, f32, Option<f32>)| {
+            let Some(water) = this.cmd.water_surface.take() else {
+                return Err(LuaError::runtime(
+                    "with_water_springs() requires with_water_surface() first",
+                ));
+            };
+            let mut water = water.with_springs(stiffness, tension, damping);
+            if let Some(spacing) = spacing {
+                water = water.with_spacing(spacing);
             }
+            this.cmd.water_surface = Some(water);
+            Ok(())
         }
-        Ok(())
-    });
-}
+    );
 
-impl LuaUserData for LuaEntityBuilder {
-    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
-        register_methods(methods, &mut None);
+    builder_method!(
+        methods, meta,
+        "with_water_buoyancy", "Set the upward acceleration of fully submerged bodies, the fraction of their velocity removed per second and optionally how hard entering bodies splash",
+        [("buoyancy", "number"), ("drag", "number"), ("splash", "number?")],
+        |_, this: &mut LuaEntityBuilder, (buoyancy, drag, splash): (f32, f32, Option<f32>)| {
+            let Some(water) = this.cmd.water_surface.take() else {
+                return Err(LuaError::runtime(
+                    "with_water_buoyancy() requires with_water_surface() first",
+                ));
+            };
+            let mut water = water.with_buoyancy(buoyancy, drag);
+            if let Some(splash) = splash {
+                water = water.with_splash(splash);
+            }
+            this.cmd.water_surface = Some(water);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_water_color", "Set the water's fill color (RGBA 0-255)",
+        [("r", "integer"), ("g", "integer"), ("b", "integer"), ("a", "integer?")],
+        |_, this: &mut LuaEntityBuilder, (r, g, b, a): (u8, u8, u8, Option<u8>)| {
+            let Some(water) = this.cmd.water_surface.take() else {
+                return Err(LuaError::runtime(
+                    "with_water_color() requires with_water_surface() first",
+                ));
+            };
+            this.cmd.water_surface = Some(water.with_color(Color::new(r, g, b, a.unwrap_or(255))));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_sensor", "Add a sensor ray that sets signal flag `flag` while it hits a collider whose group or tags match `mask` (default \"*\"). Can be called several times; adds Signals.",
+        [("flag", "string"), ("offset_x", "number"), ("offset_y", "number"), ("dir_x", "number"), ("dir_y", "number"), ("length", "number"), ("mask", "string?")],
+        |_, this: &mut LuaEntityBuilder, (flag, ox, oy, dx, dy, length, mask): (String, f32, f32, f32, f32, f32, Option<String>)| {
+            let mut ray = SensorRay::new(flag, Vector2::new(ox, oy), Vector2::new(dx, dy), length);
+            if let Some(mask) = mask {
+                ray = ray.with_mask(mask);
+            }
+            let sensor = this.cmd.sensor.take().unwrap_or_default();
+            this.cmd.sensor = Some(sensor.with_ray(ray));
+            this.cmd.has_signals = true;
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_shader", "Set per-entity shader with optional uniforms",
+        [("shader_key", "string"), ("uniforms", "table?")],
+        |_, this: &mut LuaEntityBuilder, args: mlua::MultiValue| {
+            let mut iter = args.into_iter();
+            let key_val = iter
+                .next()
+                .ok_or_else(|| LuaError::runtime("with_shader requires shader_key"))?;
+            let shader_key: String = key_val
+                .as_string()
+                .and_then(|s| s.to_str().ok())
+                .ok_or_else(|| LuaError::runtime("shader_key must be string"))?
+                .to_string();
+
+            let mut uniforms = Vec::new();
+
+            if let Some(table_val) = iter.next()
+                && let Some(table) = table_val.as_table()
+            {
+                for pair in table.pairs::<String, LuaValue>() {
+                    let (name, val) = pair?;
+                    let uniform = parse_uniform_value(val)?;
+                    uniforms.push((name, uniform));
+                }
+            }
+
+            this.cmd.shader = Some(EntityShaderData { key: shader_key, uniforms });
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_parent", "Set parent entity for transform hierarchy",
+        [("parent_id", "integer")],
+        |_, this: &mut LuaEntityBuilder, parent_id: u64| {
+            this.cmd.parent = Some(parent_id);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_owner", "Despawn this entity when entity `owner_id` is despawned (health bars, shadows, attached effects). Ownership doesn't move the entity; combine with :with_stuckto() or :with_parent() for that",
+        [("owner_id", "integer")],
+        |_, this: &mut LuaEntityBuilder, owner_id: u64| {
+            let owner = bevy_ecs::prelude::Entity::try_from_bits(owner_id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {owner_id}")))?;
+            this.cmd.owned_by = Some(OwnedBy(owner));
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_tilemap",
+        "Spawn a tilemap root from a Tilesetter directory or a Tiled `.tmj` map. All tile and object entities become ChildOf children so the root's position/scale/rotation transforms the whole tilemap. `projection` (\"orthogonal\", \"isometric\" or \"hex\") overrides the one in the tilemap file. The tiles of the layers named in `solid_layers` get colliders (one box per horizontal run of tiles, in a group named after the layer), kept up to date by tile edits; orthogonal maps only.",
+        [("path", "string"), ("projection", "string?"), ("solid_layers", "string[]?")],
+        |_, this: &mut LuaEntityBuilder, (path, projection, solid_layers): (String, Option<String>, Option<Vec<String>>)| {
+            this.cmd.tilemap_projection = projection
+                .map(|name| {
+                    TileProjection::parse(&name).ok_or_else(|| {
+                        LuaError::runtime(format!(
+                            "with_tilemap(): unknown projection '{name}' (expected \"orthogonal\", \"isometric\" or \"hex\")"
+                        ))
+                    })
+                })
+                .transpose()?;
+            this.cmd.tilemap_path = Some(path);
+            this.cmd.tilemap_solid_layers = solid_layers.unwrap_or_default();
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_lua_setup",
+        "Attach a one-shot Lua setup callback. The named function is called once (Added<LuaSetup>) with the entity context. Fires the frame after spawn; child entities added inside the callback appear the following frame.",
+        [("callback", "string")],
+        |_, this: &mut LuaEntityBuilder, callback: String| {
+            this.cmd.lua_setup = Some(callback);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_on_animation_end",
+        "Attach a callback fired exactly once when the entity's non-looped animation first reaches its last frame. Signature: fn(ctx, input). Looped animations never trigger it.",
+        [("fn_name", "string")],
+        |_, this: &mut LuaEntityBuilder, callback: String| {
+            this.cmd.lua_on_animation_end = Some(callback);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_on_hit",
+        "Attach a callback fired once per swing for each entity this entity's hitbox strikes. Signature: fn(ctx, input, target_id).",
+        [("fn_name", "string")],
+        |_, this: &mut LuaEntityBuilder, callback: String| {
+            this.cmd.lua_on_hit = Some(callback);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_on_land",
+        "Attach a callback fired each time the entity's :with_height() comes down to the ground, bounces included. Signature: fn(ctx, input, impact_speed).",
+        [("fn_name", "string")],
+        |_, this: &mut LuaEntityBuilder, callback: String| {
+            this.cmd.lua_on_land = Some(callback);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_interactable",
+        "Show a prompt above this entity while an interactor (group or tags matching `mask`, default \"player\") is in range, and call `callback` as fn(ctx, input, interactor_id) when it presses `action` (default \"action_1\"). Table fields: prompt, callback?, radius? (default: overlap with this entity's collider), mask?, action?, icon? (texture key), offset? ({x, y}, default {0, -16}), theme? (GuiThemeStore key).",
+        [("table", "table")],
+        |_, this: &mut LuaEntityBuilder, table: LuaTable| {
+            let prompt: String = table.get::<Option<String>>("prompt")?.unwrap_or_default();
+            let mut interactable = Interactable::new(prompt);
+            if let Some(radius) = table.get::<Option<f32>>("radius")? {
+                interactable = interactable.with_radius(radius);
+            }
+            if let Some(mask) = table.get::<Option<String>>("mask")? {
+                interactable = interactable.with_mask(mask);
+            }
+            if let Some(action) = table.get::<Option<String>>("action")? {
+                let action = super::runtime::action_from_str(&action).ok_or_else(|| {
+                    LuaError::runtime(format!("with_interactable(): unknown action '{action}'"))
+                })?;
+                interactable = interactable.with_action(action);
+            }
+            if let Some(icon) = table.get::<Option<String>>("icon")? {
+                interactable = interactable.with_icon(icon);
+            }
+            if let Some(offset) = table.get::<Option<LuaTable>>("offset")? {
+                interactable = interactable.with_prompt_offset(Vector2::new(
+                    offset.get::<Option<f32>>("x")?.unwrap_or(0.0),
+                    offset.get::<Option<f32>>("y")?.unwrap_or(0.0),
+                ));
+            }
+            if let Some(theme) = table.get::<Option<String>>("theme")? {
+                interactable = interactable.with_theme_key(theme);
+            }
+            if let Some(callback) = table.get::<Option<String>>("callback")? {
+                interactable = interactable.with_callback(callback);
+            }
+            this.cmd.interactable = Some(interactable);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_portal",
+        "Make this entity a door or teleporter: confirming its interaction fades the screen out, moves the interactor to {x, y} or the named `spawn_point`, or switches to `scene` and places every entity matching the interaction mask on that scene's `spawn_point`, then fades back in. Adds an interaction prompt (`prompt`, default \"Enter\") unless :with_interactable() sets one. Table fields: x?, y?, spawn_point?, scene?, fade? (seconds per half, default 0.3), prompt?.",
+        [("table", "table")],
+        |_, this: &mut LuaEntityBuilder, table: LuaTable| {
+            let spawn_point = table.get::<Option<String>>("spawn_point")?;
+            let scene = table.get::<Option<String>>("scene")?;
+            let x = table.get::<Option<f32>>("x")?;
+            let y = table.get::<Option<f32>>("y")?;
+            let destination = match (scene, spawn_point, x, y) {
+                (Some(scene), Some(spawn_point), ..) => {
+                    PortalDestination::Scene { scene, spawn_point }
+                }
+                (Some(_), None, ..) => {
+                    return Err(LuaError::runtime(
+                        "with_portal(): `scene` needs a `spawn_point`",
+                    ));
+                }
+                (None, Some(spawn_point), ..) => PortalDestination::SpawnPoint(spawn_point),
+                (None, None, Some(x), Some(y)) => PortalDestination::Position(Vector2::new(x, y)),
+                _ => {
+                    return Err(LuaError::runtime(
+                        "with_portal(): needs x and y, a spawn_point, or a scene and spawn_point",
+                    ));
+                }
+            };
+            let mut portal = Portal::new(destination);
+            if let Some(fade) = table.get::<Option<f32>>("fade")? {
+                portal = portal.with_fade(fade);
+            }
+            this.cmd.portal = Some(portal);
+            if this.cmd.interactable.is_none() {
+                let prompt = table.get::<Option<String>>("prompt")?;
+                this.cmd.interactable =
+                    Some(Interactable::new(prompt.unwrap_or_else(|| "Enter".into())));
+            }
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_offscreen_indicator",
+        "While this entity is outside the camera view, draw an arrow on the screen edge pointing toward it. opts: {color? ({r, g, b, a?}, default yellow), size? (arrow length in pixels, default 12), margin? (gap to the screen edge, default 8), icon? (texture key drawn inside the arrow), distance? (world units per displayed unit; shows the distance from the camera centre), theme? (GuiThemeStore key for the distance font)}",
+        [("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, opts: Option<LuaTable>| {
+            let mut indicator = OffscreenIndicator::new(Color::YELLOW);
+            let Some(opts) = opts else {
+                this.cmd.offscreen_indicator = Some(indicator);
+                return Ok(());
+            };
+            if let Some(color) = opts.get::<Option<LuaTable>>("color")? {
+                let c: Vec<u8> = color.sequence_values::<u8>().collect::<LuaResult<_>>()?;
+                if c.len() < 3 {
+                    return Err(LuaError::runtime(
+                        "with_offscreen_indicator: color needs {r, g, b, a?}",
+                    ));
+                }
+                indicator.color = Color::new(c[0], c[1], c[2], *c.get(3).unwrap_or(&255));
+            }
+            if let Some(size) = opts.get::<Option<f32>>("size")? {
+                indicator = indicator.with_size(size);
+            }
+            if let Some(margin) = opts.get::<Option<f32>>("margin")? {
+                indicator = indicator.with_margin(margin);
+            }
+            if let Some(icon) = opts.get::<Option<String>>("icon")? {
+                indicator = indicator.with_icon(icon);
+            }
+            if let Some(unit) = opts.get::<Option<f32>>("distance")? {
+                indicator = indicator.with_distance(unit);
+            }
+            if let Some(theme) = opts.get::<Option<String>>("theme")? {
+                indicator = indicator.with_theme_key(theme);
+            }
+            this.cmd.offscreen_indicator = Some(indicator);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_world_anchor",
+        "Keep this UI entity's screen position on entity `target_id` as seen through the camera, updated every frame; the entity is despawned with its target. opts: {offset_x?, offset_y? (screen pixels added to the target's position), clamp? (keep the UI on screen, this many pixels from the edges)}. Requires :with_screen_position() (any value) and :with_zindex() to render.",
+        [("target_id", "integer"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (target_id, opts): (u64, Option<LuaTable>)| {
+            let target = bevy_ecs::prelude::Entity::try_from_bits(target_id)
+                .ok_or_else(|| LuaError::runtime(format!("invalid entity id {target_id}")))?;
+            let mut anchor = WorldAnchorUI::new(target);
+            if let Some(opts) = opts {
+                let x = opts.get::<Option<f32>>("offset_x")?.unwrap_or(0.0);
+                let y = opts.get::<Option<f32>>("offset_y")?.unwrap_or(0.0);
+                anchor = anchor.with_offset(x, y);
+                if let Some(margin) = opts.get::<Option<f32>>("clamp")? {
+                    anchor = anchor.with_clamp(margin);
+                }
+            }
+            this.cmd.world_anchor = Some(anchor);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_progress_bar",
+        "Draw a plain-colored bar filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). opts: {value? (default 0), max? (default 1), color? ({r, g, b, a?} fill, default lime), background_color? ({r, g, b, a?} or false, default dark gray), background? (texture key stretched under the fill), offset_x?, offset_y? (top-left corner relative to the position), direction? (\"horizontal\", \"horizontal_reversed\", \"vertical\" or \"vertical_reversed\"), signal? (key copied into value every frame, integer or scalar), entity? (entity id whose signals hold `signal`; WorldSignals when omitted)}",
+        [("width", "number"), ("height", "number"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (width, height, opts): (f32, f32, Option<LuaTable>)| {
+            let parsed = parse_progress_opts("with_progress_bar", opts.as_ref())?;
+            let mut bar = ProgressBar::new(width, height, parsed.max)
+                .with_value(parsed.value)
+                .with_offset(parsed.offset.x, parsed.offset.y)
+                .with_colors(parsed.fill_color, parsed.background_color);
+            if let Some(direction) = opts
+                .as_ref()
+                .map(|opts| opts.get::<Option<String>>("direction"))
+                .transpose()?
+                .flatten()
+            {
+                bar = bar.with_direction(match direction.as_str() {
+                    "horizontal" => ProgressBarDirection::Horizontal,
+                    "horizontal_reversed" => ProgressBarDirection::HorizontalReversed,
+                    "vertical" => ProgressBarDirection::Vertical,
+                    "vertical_reversed" => ProgressBarDirection::VerticalReversed,
+                    other => {
+                        return Err(LuaError::runtime(format!(
+                            "with_progress_bar: unknown direction '{other}'"
+                        )));
+                    }
+                });
+            }
+            if let Some(background) = parsed.background {
+                bar = bar.with_background(background);
+            }
+            if let Some(signal) = parsed.signal {
+                bar = bar.with_signal(signal);
+            }
+            this.cmd.progress_bar = Some(bar);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_radial_progress",
+        "Draw a ring (or a pie, without inner_radius) filled to value / max, in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). The position is the centre. opts: the options of :with_progress_bar() except direction, plus {inner_radius? (default 0), start_angle? (degrees clockwise from the right, default -90 = top), counter_clockwise? (boolean)}",
+        [("radius", "number"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (radius, opts): (f32, Option<LuaTable>)| {
+            let parsed = parse_progress_opts("with_radial_progress", opts.as_ref())?;
+            let mut radial = RadialProgress::new(radius, parsed.max)
+                .with_value(parsed.value)
+                .with_offset(parsed.offset.x, parsed.offset.y)
+                .with_colors(parsed.fill_color, parsed.background_color);
+            if let Some(opts) = &opts {
+                if let Some(inner) = opts.get::<Option<f32>>("inner_radius")? {
+                    radial = radial.with_inner_radius(inner);
+                }
+                if let Some(angle) = opts.get::<Option<f32>>("start_angle")? {
+                    radial = radial.with_start_angle(angle);
+                }
+                if opts.get::<Option<bool>>("counter_clockwise")?.unwrap_or(false) {
+                    radial = radial.counter_clockwise();
+                }
+            }
+            if let Some(background) = parsed.background {
+                radial = radial.with_background(background);
+            }
+            if let Some(signal) = parsed.signal {
+                radial = radial.with_signal(signal);
+            }
+            this.cmd.radial_progress = Some(radial);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_nine_slice",
+        "Stretch texture `tex_key` over a width x height panel keeping `border` pixels unscaled at the corners (NPatch), in the world (with :with_position()) or as UI (with :with_screen_position()); needs :with_zindex(). :with_tint() tints it. opts: {left?, top?, right?, bottom? (per-side borders, default `border`), source? ({x, y, w, h} region of the texture, default the whole texture), offset_x?, offset_y? (top-left corner relative to the position)}",
+        [("tex_key", "string"), ("width", "number"), ("height", "number"), ("border", "integer"), ("opts", "table?")],
+        |_, this: &mut LuaEntityBuilder, (tex_key, width, height, border, opts): (String, f32, f32, i32, Option<LuaTable>)| {
+            let mut slice = NineSlice::new(tex_key, width, height, border);
+            if let Some(opts) = &opts {
+                let side = |key: &str| -> LuaResult<i32> {
+                    Ok(opts.get::<Option<i32>>(key)?.unwrap_or(border))
+                };
+                slice = slice
+                    .with_borders(side("left")?, side("top")?, side("right")?, side("bottom")?)
+                    .with_offset(
+                        opts.get::<Option<f32>>("offset_x")?.unwrap_or(0.0),
+                        opts.get::<Option<f32>>("offset_y")?.unwrap_or(0.0),
+                    );
+                if let Some(source) = opts.get::<Option<LuaTable>>("source")? {
+                    let r: Vec<f32> = source.sequence_values::<f32>().collect::<LuaResult<_>>()?;
+                    let &[x, y, w, h] = r.as_slice() else {
+                        return Err(LuaError::runtime("with_nine_slice: source needs {x, y, w, h}"));
+                    };
+                    slice = slice.with_source(Rectangle::new(x, y, w, h));
+                }
+            }
+            this.cmd.nine_slice = Some(slice);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_spawn_point",
+        "Name this entity's position as a spawn point that portals can lead to and engine.get_spawn_point() returns, with optional `properties` (string keys; integer, number, string or boolean values).",
+        [("name", "string"), ("properties", "table?")],
+        |_, this: &mut LuaEntityBuilder, (name, properties): (String, Option<LuaTable>)| {
+            let mut point = SpawnPoint::new(name);
+            if let Some(properties) = properties {
+                for pair in properties.pairs::<String, LuaValue>() {
+                    let (key, value) = pair?;
+                    let value = match value {
+                        LuaValue::Integer(v) => GridValue::Int(v),
+                        // LuaJIT has no integer subtype.
+                        LuaValue::Number(v) if v.fract() == 0.0 => GridValue::Int(v as i64),
+                        LuaValue::Number(v) => GridValue::Float(v),
+                        LuaValue::String(v) => GridValue::String(v.to_str()?.to_owned()),
+                        LuaValue::Boolean(v) => GridValue::Bool(v),
+                        other => {
+                            return Err(LuaError::runtime(format!(
+                                "with_spawn_point(): property '{key}' has unsupported type {}",
+                                other.type_name()
+                            )));
+                        }
+                    };
+                    point = point.with_property(key, value);
+                }
+            }
+            this.cmd.spawn_point = Some(point);
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_restore_on_return",
+        "Remember this entity when its scene is left and restore it on re-entry: despawned entities stay despawned, and position and signals are restored unless disabled. `key` must be unique within the scene and the same every time it is spawned.",
+        [("key", "string"), ("restore_position", "boolean?"), ("restore_signals", "boolean?")],
+        |_, this: &mut LuaEntityBuilder, (key, position, signals): (String, Option<bool>, Option<bool>)| {
+            this.cmd.restore_on_return = Some(
+                RestoreOnReturn::new(key)
+                    .with_position(position.unwrap_or(true))
+                    .with_signals(signals.unwrap_or(true)),
+            );
+            Ok(())
+        }
+    );
+
+    builder_method!(
+        methods, meta,
+        "with_camera_target",
+        "Mark entity as camera follow target (higher priority wins). zoom is the desired camera zoom when this target wins (default 1.0).",
+        [("priority", "integer?"), ("zoom", "number?")],
+        |_, this: &mut LuaEntityBuilder, (priority, zoom): (Option<u8>, Option<f32>)| {
+            this.cmd.camera_target = Some(priority.unwrap_or(0));
+            this.cmd.camera_target_zoom = zoom;
+            Ok(())
+        }
+    );
+
+    // Non-builder-stub methods — kept as plain registrations.
+    // These have their own entries in stub_meta's non-BUILDER_METHODS sections.
+
+    methods.add_function("register_as", |_, (ud, key): (LuaAnyUserData, String)| {
+        let mut this = ud.borrow_mut::<LuaEntityBuilder>()?;
+        this.cmd.register_as = Some(key);
+        Ok(ud)
+    });
+
+    methods.add_method_mut("build", |lua, this, ()| {
+        let app_data = lua
+            .app_data_ref::<LuaAppData>()
+            .ok_or_else(|| LuaError::runtime("LuaAppData not found"))?;
+
+        this.cmd.origin = Some(SpawnOrigin {
+            builder: this.builder_name(),
+            traceback: capture_spawn_traceback(lua),
+        });
+
+        // Take the built command out of the builder rather than cloning it — the
+        // builder is consumed by build() in normal (single-call) usage.
+        if let Some(world) = this.target_world.take() {
+            app_data.world_commands.borrow_mut().push(WorldCmd::Spawn {
+                world,
+                cmd: Box::new(std::mem::take(&mut this.cmd)),
+            });
+            return Ok(());
+        }
+        match (this.mode, this.context) {
+            (BuilderMode::Spawn, BuilderContext::Regular) => {
+                app_data
+                    .spawn_commands
+                    .borrow_mut()
+                    .push(std::mem::take(&mut this.cmd));
+            }
+            (BuilderMode::Spawn, BuilderContext::Collision) => {
+                app_data
+                    .collision_spawn_commands
+                    .borrow_mut()
+                    .push(std::mem::take(&mut this.cmd));
+            }
+            (BuilderMode::Clone, BuilderContext::Regular) => {
+                let source_key = this.source_key.take().unwrap_or_default();
+                app_data.clone_commands.borrow_mut().push(CloneCmd {
+                    source_key,
+                    overrides: std::mem::take(&mut this.cmd),
+                });
+            }
+            (BuilderMode::Clone, BuilderContext::Collision) => {
+                let source_key = this.source_key.take().unwrap_or_default();
+                app_data
+                    .collision_clone_commands
+                    .borrow_mut()
+                    .push(CloneCmd {
+                        source_key,
+                        overrides: std::mem::take(&mut this.cmd),
+                    });
+            }
+        }
+        Ok(())
+    });
+}
+
+impl LuaUserData for LuaEntityBuilder {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        register_methods(methods, &mut None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::lua_runtime::LuaRuntime;
+
+    fn assert_runtime_error(script: &str, expected_msg: &str) {
+        let runtime = LuaRuntime::new().unwrap();
+        let err = runtime
+            .lua()
+            .load(script)
+            .exec()
+            .expect_err("expected script to raise an error");
+        let message = err.to_string();
+        assert!(
+            message.contains(expected_msg),
+            "expected error containing {expected_msg:?}, got {message:?}"
+        );
+    }
+
+    #[test]
+    fn with_sprite_offset_requires_with_sprite() {
+        assert_runtime_error(
+            "engine.spawn():with_sprite_offset(1, 1)",
+            "with_sprite_offset() requires with_sprite() first",
+        );
+    }
+
+    #[test]
+    fn with_collider_offset_requires_with_collider() {
+        assert_runtime_error(
+            "engine.spawn():with_collider_offset(1, 1)",
+            "with_collider_offset() requires with_collider() first",
+        );
+    }
+
+    #[test]
+    fn with_stuckto_offset_requires_with_stuckto() {
+        assert_runtime_error(
+            "engine.spawn():with_stuckto_offset(1, 1)",
+            "with_stuckto_offset() requires with_stuckto() first",
+        );
+    }
+
+    #[test]
+    fn with_signal_binding_format_requires_with_signal_binding() {
+        assert_runtime_error(
+            "engine.spawn():with_signal_binding_format('{}')",
+            "with_signal_binding_format() requires with_signal_binding() first",
+        );
+    }
+
+    #[test]
+    fn with_tween_position_easing_requires_with_tween_position() {
+        assert_runtime_error(
+            "engine.spawn():with_tween_position_easing('linear')",
+            "with_tween_position_easing() requires with_tween_position() first",
+        );
+    }
+
+    #[test]
+    fn with_tween_rotation_loop_requires_with_tween_rotation() {
+        assert_runtime_error(
+            "engine.spawn():with_tween_rotation_loop('loop')",
+            "with_tween_rotation_loop() requires with_tween_rotation() first",
+        );
+    }
+
+    #[test]
+    fn with_tween_scale_backwards_requires_with_tween_scale() {
+        assert_runtime_error(
+            "engine.spawn():with_tween_scale_backwards()",
+            "with_tween_scale_backwards() requires with_tween_scale() first",
+        );
+    }
+
+    /// `with_*` chaining must return the *same* userdata handle (in-place mutation),
+    /// not a clone, otherwise the O(n) chain cost regresses back to O(n^2).
+    #[test]
+    fn chaining_returns_same_userdata() {
+        let runtime = LuaRuntime::new().unwrap();
+        let same: bool = runtime
+            .lua()
+            .load(
+                "local b = engine.spawn() \
+                 return rawequal(b, b:with_position(1, 2):with_velocity(0, 0))",
+            )
+            .eval()
+            .unwrap();
+        assert!(same, "chained with_* calls must return the same userdata");
+    }
+
+    #[test]
+    fn long_chain_builds_expected_spawn_cmd() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_group('asteroids') \
+                    :with_position(10, 20) \
+                    :with_sprite('rock', 64, 64, 32, 32) \
+                    :with_rotation(45) \
+                    :with_velocity(1, 2) \
+                    :with_zindex(5) \
+                    :with_collider(40, 40, 20, 20) \
+                    :with_signal_integer('hp', 3) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        assert_eq!(queued.len(), 1, "expected exactly one queued spawn command");
+        let cmd = &queued[0];
+        assert_eq!(cmd.group.as_deref(), Some("asteroids"));
+        assert_eq!(cmd.position, Some((10.0, 20.0)));
+        assert!(cmd.sprite.is_some());
+        assert_eq!(cmd.rotation, Some(45.0));
+        assert_eq!(cmd.zindex, Some(5.0));
+        assert!(cmd.collider.is_some());
+        assert_eq!(cmd.signal_integers, vec![("hp".to_string(), 3)]);
+    }
+
+    #[test]
+    fn with_tags_accumulates_unique_tags() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_group('enemy') \
+                    :with_tags({'flying', 'boss'}) \
+                    :with_tags({'flying'}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        assert_eq!(queued[0].tags, vec!["flying".to_string(), "boss".to_string()]);
+    }
+
+    #[test]
+    fn with_attractor_queues_component() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load("engine.spawn():with_attractor(96, 2000, 'coin'):build()")
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let attractor = queued[0].attractor.as_ref().unwrap();
+        assert_eq!(attractor.radius, 96.0);
+        assert_eq!(attractor.strength, 2000.0);
+        assert_eq!(attractor.target, "coin");
+    }
+
+    #[test]
+    fn with_audio_trigger_accumulates_and_rejects_unknown_events() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::audiotriggers::AudioTriggerEvent;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_audio_trigger('spawn', 'pop') \
+                    :with_audio_trigger('collision:wall', 'thud', 0.2) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+        assert!(
+            runtime
+                .lua()
+                .load("engine.spawn():with_audio_trigger('hit', 'thud')")
+                .exec()
+                .is_err()
+        );
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let triggers = &queued[0].audio_triggers.as_ref().unwrap().triggers;
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].event, AudioTriggerEvent::Spawned);
+        assert_eq!(
+            triggers[1].event,
+            AudioTriggerEvent::Collided(Some("wall".into()))
+        );
+        assert_eq!(triggers[1].cooldown, 0.2);
+    }
+
+    #[test]
+    fn with_text_scroller_reads_path_and_options() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::textscroller::ScrollerPath;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_text_scroller('HI', 'arcade', 16, 60, 'circle', {amplitude = 40, color = {255, 0, 0}}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+        assert!(
+            runtime
+                .lua()
+                .load("engine.spawn():with_text_scroller('HI', 'arcade', 16, 60, 'spiral')")
+                .exec()
+                .is_err()
+        );
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let scroller = queued[0].text_scroller.as_ref().unwrap();
+        assert_eq!(scroller.path, ScrollerPath::Circle { radius: 40.0 });
+        assert_eq!(scroller.spacing, 16.0);
+        assert_eq!(scroller.color, Color::new(255, 0, 0, 255));
+    }
+
+    #[test]
+    fn with_path_follow_reads_points_and_options() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::tween::LoopMode;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_path_follow({{x = 0, y = 0}, {x = 30, y = 40}}, 50, {loop = 'ping_pong', orient = true}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+        assert!(
+            runtime
+                .lua()
+                .load("engine.spawn():with_path_follow({{x = 0, y = 0}}, 50)")
+                .exec()
+                .is_err()
+        );
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let follow = queued[0].path_follow.as_ref().unwrap();
+        assert_eq!(follow.length(), 50.0);
+        assert!(matches!(follow.loop_mode, LoopMode::PingPong));
+        assert!(follow.orient);
+    }
+
+    #[test]
+    fn hitbox_builders_merge_frames_and_mask() {
+        use super::super::runtime::LuaAppData;
+        use raylib::prelude::Rectangle;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_hitbox_frame('punch', 2, 8, -20, 14, 8) \
+                    :with_hitbox_mask('enemy') \
+                    :with_hurtbox(-8, -32, 16, 32) \
+                    :with_on_hit('on_punch_hit') \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let hitbox = queued[0].hitbox.as_ref().unwrap();
+        assert_eq!(hitbox.mask, "enemy");
+        assert_eq!(hitbox.boxes.default, None);
+        assert_eq!(
+            hitbox.boxes.active(Some(("punch", 2))),
+            Some(Rectangle::new(8.0, -20.0, 14.0, 8.0))
+        );
+        let hurtbox = queued[0].hurtbox.as_ref().unwrap();
+        assert_eq!(
+            hurtbox.boxes.default,
+            Some(Rectangle::new(-8.0, -32.0, 16.0, 32.0))
+        );
+        assert_eq!(queued[0].lua_on_hit.as_deref(), Some("on_punch_hit"));
+    }
+
+    #[test]
+    fn hitbox_aseprite_imports_slice_relative_to_sprite_origin() {
+        use super::super::runtime::LuaAppData;
+        use raylib::prelude::Rectangle;
+
+        let path = std::env::temp_dir().join("aberred_hitbox_aseprite_test.json");
+        std::fs::write(
+            &path,
+            r#"{ "meta": {
+                "frameTags": [{ "name": "kick", "from": 0, "to": 1 }],
+                "slices": [{ "name": "hitbox", "keys": [
+                    { "frame": 1, "bounds": { "x": 20, "y": 10, "w": 8, "h": 4 } }
+                ] }]
+            } }"#,
+        )
+        .unwrap();
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(format!(
+                "engine.spawn():with_sprite('hero', 32, 32, 16, 32):with_hitbox_aseprite('{}'):build()",
+                path.display()
+            ))
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let boxes = &queued[0].hitbox.as_ref().unwrap().boxes;
+        assert_eq!(boxes.active(Some(("kick", 0))), None);
+        assert_eq!(
+            boxes.active(Some(("kick", 1))),
+            Some(Rectangle::new(4.0, -22.0, 8.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn hitbox_aseprite_reports_missing_slice() {
+        let path = std::env::temp_dir().join("aberred_hitbox_aseprite_missing_test.json");
+        std::fs::write(&path, r#"{ "meta": { "slices": [] } }"#).unwrap();
+        assert_runtime_error(
+            &format!(
+                "engine.spawn():with_hitbox_aseprite('{}', 'sword'):build()",
+                path.display()
+            ),
+            "no slice named 'sword'",
+        );
+    }
+
+    #[test]
+    fn with_restore_on_return_sets_save_filter() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn():with_restore_on_return('door_3'):build() \
+                 engine.spawn():with_restore_on_return('crate_1', false):build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        assert_eq!(
+            queued[0].restore_on_return,
+            Some(RestoreOnReturn::new("door_3"))
+        );
+        assert_eq!(
+            queued[1].restore_on_return,
+            Some(RestoreOnReturn::new("crate_1").with_position(false))
+        );
+    }
+
+    #[test]
+    fn with_hitbox_mask_requires_hitbox() {
+        assert_runtime_error(
+            "engine.spawn():with_hitbox_mask('enemy'):build()",
+            "with_hitbox_mask() requires with_hitbox(), with_hitbox_frame() or with_hitbox_aseprite() first",
+        );
+    }
+
+    #[test]
+    fn force_area_builders_merge_into_one_area() {
+        use super::super::runtime::LuaAppData;
+        use raylib::prelude::{Rectangle, Vector2};
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_conveyor(30, 0, 'player') \
+                    :with_force_area(0, -10) \
+                    :with_force_area_rect(0, 0, 16, 8) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let area = queued[0].force_area.as_ref().unwrap();
+        assert_eq!(area.velocity, Vector2::new(30.0, 0.0));
+        assert_eq!(area.acceleration, Vector2::new(0.0, -10.0));
+        assert_eq!(area.mask, "player");
+        assert_eq!(area.rect, Some(Rectangle::new(0.0, 0.0, 16.0, 8.0)));
+    }
+
+    #[test]
+    fn with_force_area_rect_requires_area() {
+        assert_runtime_error(
+            "engine.spawn():with_force_area_rect(0, 0, 1, 1)",
+            "with_force_area_rect() requires",
+        );
+    }
+
+    #[test]
+    fn joint_builders_default_to_rigid_joints() {
+        use super::super::runtime::LuaAppData;
+        use bevy_ecs::prelude::Entity;
+        use raylib::prelude::Vector2;
+
+        let ball = Entity::from_raw_u32(3).unwrap();
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(format!(
+                "engine.spawn():with_distance_joint({id}, 24):build() \
+                 engine.spawn():with_distance_joint({id}, 24, 0, 0.5):build() \
+                 engine.spawn():with_pin_joint({id}, 0, -12):build()",
+                id = ball.to_bits()
+            ))
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        assert_eq!(queued[0].distance_joint, Some(DistanceJoint::rod(ball, 24.0)));
+        assert_eq!(
+            queued[1].distance_joint,
+            Some(DistanceJoint::leash(ball, 24.0).with_stiffness(0.5))
+        );
+        assert_eq!(
+            queued[2].pin_joint,
+            Some(PinJoint::new(ball, Vector2::new(0.0, -12.0)))
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::resources::lua_runtime::LuaRuntime;
+    #[test]
+    fn rope_builders_attach_and_tune_one_rope() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::rope::RopeAnchor;
+        use bevy_ecs::prelude::Entity;
+        use raylib::prelude::Vector2;
 
-    fn assert_runtime_error(script: &str, expected_msg: &str) {
+        let lamp = Entity::from_raw_u32(7).unwrap();
         let runtime = LuaRuntime::new().unwrap();
-        let err = runtime
+        runtime
             .lua()
-            .load(script)
+            .load(format!(
+                "engine.spawn() \
+                    :with_rope(48) \
+                    :with_rope_start(10, 0) \
+                    :with_rope_end_entity({}, 0, -4) \
+                    :with_rope_physics(0.5, 1, 4) \
+                    :with_rope_style(3, 255, 0, 0) \
+                    :build()",
+                lamp.to_bits()
+            ))
             .exec()
-            .expect_err("expected script to raise an error");
-        let message = err.to_string();
-        assert!(
-            message.contains(expected_msg),
-            "expected error containing {expected_msg:?}, got {message:?}"
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let rope = queued[0].rope.as_ref().unwrap();
+        assert_eq!(rope.segments, 6);
+        assert_eq!(rope.start, RopeAnchor::Point(Vector2::new(10.0, 0.0)));
+        assert_eq!(
+            rope.end,
+            RopeAnchor::Entity {
+                entity: lamp,
+                offset: Vector2::new(0.0, -4.0)
+            }
         );
+        assert_eq!((rope.stiffness, rope.damping, rope.iterations), (0.5, 1.0, 4));
+        assert_eq!(rope.gravity, Vector2::new(0.0, 980.0));
+        assert_eq!(rope.thickness, 3.0);
     }
 
     #[test]
-    fn with_sprite_offset_requires_with_sprite() {
+    fn with_rope_end_requires_rope() {
         assert_runtime_error(
-            "engine.spawn():with_sprite_offset(1, 1)",
-            "with_sprite_offset() requires with_sprite() first",
+            "engine.spawn():with_rope_end(0, 0)",
+            "with_rope_end() requires with_rope() first",
         );
     }
 
     #[test]
-    fn with_collider_offset_requires_with_collider() {
+    fn with_grid_intro_requires_with_grid_layout() {
         assert_runtime_error(
-            "engine.spawn():with_collider_offset(1, 1)",
-            "with_collider_offset() requires with_collider() first",
+            "engine.spawn():with_grid_intro('scale_in', 0.05, 0.3)",
+            "with_grid_intro() requires with_grid_layout() first",
+        );
+        assert_runtime_error(
+            "engine.spawn():with_grid_layout('l.json', 'bricks', 1):with_grid_intro('spin', 0.05, 0.3)",
+            "unknown preset 'spin'",
         );
     }
 
     #[test]
-    fn with_stuckto_offset_requires_with_stuckto() {
-        assert_runtime_error(
-            "engine.spawn():with_stuckto_offset(1, 1)",
-            "with_stuckto_offset() requires with_stuckto() first",
-        );
+    fn with_grid_intro_configures_the_layout_intro() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::gridlayout::IntroOrder;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                r#"
+                engine.spawn()
+                    :with_grid_layout("l.json", "bricks", 1)
+                    :with_grid_intro("drop_in", 0.05, 0.4, "diagonal", 100)
+                    :build()
+                "#,
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let intro = queued[0].grid_intro.unwrap();
+        assert_eq!(intro.preset, IntroPreset::DropIn { height: 100.0 });
+        assert_eq!(intro.order, IntroOrder::Diagonal);
+        assert_eq!((intro.step, intro.duration), (0.05, 0.4));
     }
 
     #[test]
-    fn with_signal_binding_format_requires_with_signal_binding() {
-        assert_runtime_error(
-            "engine.spawn():with_signal_binding_format('{}')",
-            "with_signal_binding_format() requires with_signal_binding() first",
-        );
+    fn with_mass_and_wind_configure_the_body() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                r#"
+                engine.spawn():with_mass(0.5):with_velocity(1, 2):with_wind():build()
+                engine.spawn():with_wind(3):build()
+                "#,
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let rb = queued[0].rigidbody.as_ref().unwrap();
+        assert_eq!(rb.mass, Some(0.5));
+        assert_eq!((rb.velocity_x, rb.velocity_y), (1.0, 2.0));
+        assert_eq!(queued[0].wind, Some(1.0));
+        assert_eq!(queued[1].wind, Some(3.0));
+        assert!(queued[1].rigidbody.is_none());
     }
 
     #[test]
-    fn with_tween_position_easing_requires_with_tween_position() {
-        assert_runtime_error(
-            "engine.spawn():with_tween_position_easing('linear')",
-            "with_tween_position_easing() requires with_tween_position() first",
-        );
+    fn water_builders_tune_one_surface() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_water_surface(64, 32, 'player') \
+                    :with_water_springs(10, 200, 1, 4) \
+                    :with_water_buoyancy(900, 2, 0.3) \
+                    :with_water_color(0, 0, 255) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let water = queued[0].water_surface.as_ref().unwrap();
+        assert_eq!((water.width, water.depth), (64.0, 32.0));
+        assert_eq!(water.mask, "player");
+        assert_eq!((water.stiffness, water.tension, water.damping), (10.0, 200.0, 1.0));
+        assert_eq!(water.heights().len(), 17);
+        assert_eq!((water.buoyancy, water.drag, water.splash), (900.0, 2.0, 0.3));
+        assert_eq!(water.color, Color::new(0, 0, 255, 255));
     }
 
     #[test]
-    fn with_tween_rotation_loop_requires_with_tween_rotation() {
+    fn with_water_springs_requires_water() {
         assert_runtime_error(
-            "engine.spawn():with_tween_rotation_loop('loop')",
-            "with_tween_rotation_loop() requires with_tween_rotation() first",
+            "engine.spawn():with_water_springs(1, 1, 1)",
+            "with_water_springs() requires with_water_surface() first",
         );
     }
 
     #[test]
-    fn with_tween_scale_backwards_requires_with_tween_scale() {
-        assert_runtime_error(
-            "engine.spawn():with_tween_scale_backwards()",
-            "with_tween_scale_backwards() requires with_tween_scale() first",
+    fn with_sensor_accumulates_rays_and_adds_signals() {
+        use super::super::runtime::LuaAppData;
+        use raylib::prelude::Vector2;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_sensor('grounded', 0, 0, 0, 4, 2, 'ground') \
+                    :with_sensor('wall_left', 0, -8, -1, 0, 6) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        assert!(queued[0].has_signals);
+        let rays = &queued[0].sensor.as_ref().unwrap().rays;
+        assert_eq!(rays.len(), 2);
+        assert_eq!(rays[0].mask, "ground");
+        assert_eq!(rays[0].direction, Vector2::new(0.0, 1.0));
+        assert_eq!(rays[1].flag, "wall_left");
+        assert_eq!(rays[1].mask, "*");
+    }
+
+    #[test]
+    fn with_interactable_parses_table() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::interactable::InteractRange;
+        use crate::events::input::InputAction;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_interactable({prompt = 'Open', callback = 'on_open', radius = 24, \
+                        action = 'action_2', offset = {y = -30}}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+        let err = runtime
+            .lua()
+            .load("engine.spawn():with_interactable({prompt = 'Open', action = 'jump'})")
+            .exec()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown action 'jump'"));
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let interactable = queued[0].interactable.as_ref().unwrap();
+        assert_eq!(interactable.prompt, "Open");
+        assert_eq!(interactable.callback.as_deref(), Some("on_open"));
+        assert_eq!(interactable.range, InteractRange::Radius(24.0));
+        assert_eq!(interactable.action, InputAction::Action2);
+        assert_eq!(interactable.mask, "player");
+        assert_eq!(interactable.prompt_offset.y, -30.0);
+    }
+
+    #[test]
+    fn with_offscreen_indicator_reads_options() {
+        use super::super::runtime::LuaAppData;
+        use raylib::prelude::Color;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn():with_offscreen_indicator():build() \
+                 engine.spawn() \
+                    :with_offscreen_indicator({color = {255, 0, 0}, size = 20, icon = 'skull', \
+                        distance = 16, theme = 'hud'}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+        assert!(
+            runtime
+                .lua()
+                .load("engine.spawn():with_offscreen_indicator({color = {255}})")
+                .exec()
+                .is_err()
         );
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let plain = queued[0].offscreen_indicator.as_ref().unwrap();
+        assert_eq!(plain.color, Color::YELLOW);
+        assert_eq!(plain.distance_unit, None);
+        let threat = queued[1].offscreen_indicator.as_ref().unwrap();
+        assert_eq!(threat.color, Color::new(255, 0, 0, 255));
+        assert_eq!(threat.size, 20.0);
+        assert_eq!(threat.icon.as_ref().map(|k| k.as_str()), Some("skull"));
+        assert_eq!(threat.distance_unit, Some(16.0));
+        assert_eq!(&*threat.theme_key, "hud");
     }
 
-    /// `with_*` chaining must return the *same* userdata handle (in-place mutation),
-    /// not a clone, otherwise the O(n) chain cost regresses back to O(n^2).
     #[test]
-    fn chaining_returns_same_userdata() {
+    fn with_cooldown_reads_options() {
+        use super::super::runtime::LuaAppData;
+
         let runtime = LuaRuntime::new().unwrap();
-        let same: bool = runtime
+        runtime
             .lua()
             .load(
-                "local b = engine.spawn() \
-                 return rawequal(b, b:with_position(1, 2):with_velocity(0, 0))",
+                "engine.spawn():with_cooldown(3, 'dash_cd'):build() \
+                 engine.spawn() \
+                    :with_cooldown(0.5, 'spawn_cd', {auto_reset = true, started = true, world = true}) \
+                    :build()",
             )
-            .eval()
+            .exec()
             .unwrap();
-        assert!(same, "chained with_* calls must return the same userdata");
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let dash = queued[0].cooldown.unwrap();
+        assert_eq!(dash.duration, 3.0);
+        assert_eq!(dash.signal.as_str(), "dash_cd");
+        assert!(dash.is_ready() && !dash.auto_reset && !dash.world_signal);
+        let spawner = queued[1].cooldown.unwrap();
+        assert!(spawner.auto_reset && spawner.world_signal);
+        assert_eq!(spawner.remaining, 0.5);
     }
 
     #[test]
-    fn long_chain_builds_expected_spawn_cmd() {
+    fn with_world_anchor_reads_target_and_options() {
+        use super::super::runtime::LuaAppData;
+        use bevy_ecs::prelude::Entity;
+
+        let runtime = LuaRuntime::new().unwrap();
+        let target = Entity::from_bits(7);
+        runtime.lua().globals().set("target", target.to_bits()).unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn():with_screen_position(0, 0):with_world_anchor(target):build() \
+                 engine.spawn() \
+                    :with_screen_position(0, 0) \
+                    :with_world_anchor(target, {offset_y = -24, clamp = 4}) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let plain = queued[0].world_anchor.unwrap();
+        assert_eq!(plain.target, target);
+        assert_eq!(plain.clamp_margin, None);
+        let nameplate = queued[1].world_anchor.unwrap();
+        assert_eq!((nameplate.offset.x, nameplate.offset.y), (0.0, -24.0));
+        assert_eq!(nameplate.clamp_margin, Some(4.0));
+    }
+
+    #[test]
+    fn progress_builders_read_shared_and_shape_options() {
         use super::super::runtime::LuaAppData;
+        use crate::components::signalbinding::SignalSource;
+        use bevy_ecs::prelude::Entity;
 
         let runtime = LuaRuntime::new().unwrap();
+        let player = Entity::from_bits(9);
+        runtime.lua().globals().set("player", player.to_bits()).unwrap();
         runtime
             .lua()
             .load(
                 "engine.spawn() \
-                    :with_group('asteroids') \
-                    :with_position(10, 20) \
-                    :with_sprite('rock', 64, 64, 32, 32) \
-                    :with_rotation(45) \
-                    :with_velocity(1, 2) \
-                    :with_zindex(5) \
-                    :with_collider(40, 40, 20, 20) \
-                    :with_signal_integer('hp', 3) \
+                    :with_position(0, 0) \
+                    :with_progress_bar(32, 4, {max = 100, signal = 'boss_hp', \
+                        direction = 'vertical', background_color = false}) \
+                    :build() \
+                 engine.spawn() \
+                    :with_screen_position(24, 24) \
+                    :with_radial_progress(16, {inner_radius = 12, color = {0, 0, 255}, \
+                        signal = 'dash_cd', entity = player, counter_clockwise = true}) \
                     :build()",
             )
             .exec()
@@ -1942,14 +3943,145 @@ mod tests {
 
         let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
         let queued = app_data.spawn_commands.borrow();
-        assert_eq!(queued.len(), 1, "expected exactly one queued spawn command");
-        let cmd = &queued[0];
-        assert_eq!(cmd.group.as_deref(), Some("asteroids"));
-        assert_eq!(cmd.position, Some((10.0, 20.0)));
-        assert!(cmd.sprite.is_some());
-        assert_eq!(cmd.rotation, Some(45.0));
-        assert_eq!(cmd.zindex, Some(5.0));
-        assert!(cmd.collider.is_some());
-        assert_eq!(cmd.signal_integers, vec![("hp".to_string(), 3)]);
+        let bar = queued[0].progress_bar.as_ref().unwrap();
+        assert_eq!(bar.max, 100.0);
+        assert_eq!(bar.direction, ProgressBarDirection::Vertical);
+        assert_eq!(bar.background_color, None);
+        let signal = bar.signal.as_ref().unwrap();
+        assert_eq!(signal.key.as_str(), "boss_hp");
+        assert!(matches!(signal.source, SignalSource::World));
+
+        let ring = queued[1].radial_progress.as_ref().unwrap();
+        assert_eq!(ring.inner_radius, 12.0);
+        assert_eq!(ring.fill_color, Color::new(0, 0, 255, 255));
+        assert!(!ring.clockwise);
+        let signal = ring.signal.as_ref().unwrap();
+        assert!(matches!(signal.source, SignalSource::Entity(e) if e == player));
+
+        drop(queued);
+        drop(app_data);
+        let err = runtime
+            .lua()
+            .load("engine.spawn():with_progress_bar(8, 8, {direction = 'diagonal'}):build()")
+            .exec()
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown direction"));
+    }
+
+    #[test]
+    fn with_portal_parses_destinations_and_adds_a_prompt() {
+        use super::super::runtime::LuaAppData;
+        use crate::components::gridlayout::GridValue;
+        use crate::components::portal::PortalDestination;
+        use raylib::prelude::Vector2;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn():with_portal({scene = 'cave', spawn_point = 'entrance'}):build() \
+                 engine.spawn() \
+                    :with_interactable({prompt = 'Climb'}) \
+                    :with_portal({x = 10, y = 20, fade = 0}) \
+                    :build() \
+                 engine.spawn():with_spawn_point('entrance', {facing = 'up', hp = 3}):build()",
+            )
+            .exec()
+            .unwrap();
+        let err = runtime
+            .lua()
+            .load("engine.spawn():with_portal({scene = 'cave'})")
+            .exec()
+            .unwrap_err();
+        assert!(err.to_string().contains("needs a `spawn_point`"));
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let portal = queued[0].portal.as_ref().unwrap();
+        assert_eq!(
+            portal.destination,
+            PortalDestination::Scene {
+                scene: "cave".into(),
+                spawn_point: "entrance".into()
+            }
+        );
+        assert_eq!(queued[0].interactable.as_ref().unwrap().prompt, "Enter");
+        let portal = queued[1].portal.as_ref().unwrap();
+        assert_eq!(
+            portal.destination,
+            PortalDestination::Position(Vector2::new(10.0, 20.0))
+        );
+        assert_eq!(portal.fade, 0.0);
+        assert_eq!(queued[1].interactable.as_ref().unwrap().prompt, "Climb");
+        let point = queued[2].spawn_point.as_ref().unwrap();
+        assert_eq!(point.name, "entrance");
+        assert_eq!(
+            point.properties.get("facing"),
+            Some(&GridValue::String("up".into()))
+        );
+        assert_eq!(point.properties.get("hp"), Some(&GridValue::Int(3)));
+    }
+
+    #[test]
+    fn with_lua_collision_rule_accepts_group_lists_and_flags() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.spawn() \
+                    :with_lua_collision_rule('player', {'spike', 'lava'}, 'on_hazard') \
+                    :with_lua_collision_rule_priority(5) \
+                    :with_lua_collision_rule_exclusive(false) \
+                    :build()",
+            )
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let queued = app_data.spawn_commands.borrow();
+        let rule = queued[0].lua_collision_rule.as_ref().unwrap();
+        assert_eq!(rule.group_a, "player");
+        assert_eq!(rule.group_b, "spike|lava");
+        assert_eq!(rule.priority, 5);
+        assert!(!rule.exclusive);
+    }
+
+    #[test]
+    fn with_lua_collision_rule_priority_requires_rule() {
+        let runtime = LuaRuntime::new().unwrap();
+        let result = runtime
+            .lua()
+            .load("engine.spawn():with_lua_collision_rule_priority(1)")
+            .exec();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn build_records_builder_name_and_call_site() {
+        use super::super::runtime::LuaAppData;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load("local e = engine.spawn()\n\ne:build()\nengine.clone('tpl'):build()")
+            .set_name("=level.lua")
+            .exec()
+            .unwrap();
+
+        let app_data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let spawned = app_data.spawn_commands.borrow();
+        let origin = spawned[0].origin.as_ref().unwrap();
+        assert_eq!(origin.builder, "engine.spawn()");
+        assert!(
+            origin.traceback.starts_with("level.lua:3"),
+            "unexpected traceback {:?}",
+            origin.traceback
+        );
+        let cloned = app_data.clone_commands.borrow();
+        let origin = cloned[0].overrides.origin.as_ref().unwrap();
+        assert_eq!(origin.builder, "engine.clone()");
+        assert!(origin.traceback.starts_with("level.lua:4"));
     }
 }
@@ -0,0 +1,264 @@
+//! Lua script hot reload.
+//!
+//! [`ScriptWatcher`] polls the modification times and sizes of the `.lua` files under
+//! `assets/scripts/` (and of the entry script) and reports the files that
+//! changed; [`lua_reload_system`](crate::systems::lua_reload::lua_reload_system)
+//! re-executes them without touching the ECS world:
+//!
+//! - the entry script is run again, redefining its global functions;
+//! - a module already loaded with `require` is loaded again and its new
+//!   fields are copied into the old module table, so every `local m =
+//!   require(...)` held by other scripts sees the new functions. Scene
+//!   callbacks listed in the module's `_callbacks` table that are currently
+//!   injected into `_G` are replaced by the new versions.
+//!
+//! Phase, timer and collision callbacks are looked up by name, so clearing
+//! the function cache afterwards is enough for entities that are already
+//! spawned to run the new code. Module-level locals start over with the new
+//! chunk; scripts restore what they need in `engine.on_reload(fn)`.
+//!
+//! Files that no script has loaded yet are ignored, and a script with an
+//! error leaves the previous code running.
+
+use super::runtime::LuaRuntime;
+use bevy_ecs::prelude::Resource;
+use mlua::prelude::*;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directory the watcher scans, also the root of `require` module names.
+pub const SCRIPTS_DIR: &str = "assets/scripts";
+
+/// Seconds between two scans of the scripts directory.
+pub const DEFAULT_POLL_INTERVAL: f32 = 0.5;
+
+/// Finds changed Lua scripts by polling their modification times and sizes.
+#[derive(Resource, Debug)]
+pub struct ScriptWatcher {
+    root: PathBuf,
+    main_script: PathBuf,
+    /// Modification time and size of each file seen by the last scan.
+    stamps: FxHashMap<PathBuf, (SystemTime, u64)>,
+    /// Seconds between scans.
+    pub interval: f32,
+    elapsed: f32,
+}
+
+impl ScriptWatcher {
+    /// Watches the `.lua` files under `root` and `main_script`. The current
+    /// state of the files is recorded, so nothing is reported until a file
+    /// changes.
+    pub fn new(root: impl Into<PathBuf>, main_script: impl Into<PathBuf>) -> Self {
+        let mut watcher = Self {
+            root: root.into(),
+            main_script: main_script.into(),
+            stamps: FxHashMap::default(),
+            interval: DEFAULT_POLL_INTERVAL,
+            elapsed: 0.0,
+        };
+        // The first scan only records the files.
+        watcher.scan();
+        watcher
+    }
+
+    pub fn with_interval(mut self, interval: f32) -> Self {
+        self.interval = interval.max(0.0);
+        self
+    }
+
+    /// Advances the poll timer by `delta` seconds and scans once `interval`
+    /// has passed. Returns the files that changed since the last scan.
+    pub fn tick(&mut self, delta: f32) -> Vec<PathBuf> {
+        self.elapsed += delta;
+        if self.elapsed < self.interval {
+            return Vec::new();
+        }
+        self.elapsed = 0.0;
+        self.scan()
+    }
+
+    /// Scans now. Returns the files that are new or were modified since the
+    /// last scan, the entry script first.
+    pub fn scan(&mut self) -> Vec<PathBuf> {
+        let mut files = vec![self.main_script.clone()];
+        collect_lua_files(&self.root, &mut files);
+
+        let mut changed = Vec::new();
+        for path in files {
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            // File times can be coarse; the size catches most quick edits.
+            let stamp = (
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                metadata.len(),
+            );
+            if self.stamps.insert(path.clone(), stamp) != Some(stamp) {
+                changed.push(path);
+            }
+        }
+        changed
+    }
+
+    pub fn is_main_script(&self, path: &Path) -> bool {
+        path == self.main_script
+    }
+
+    /// The `require` name of a script under the root: `scenes/menu.lua` is
+    /// `"scenes.menu"` and `lib/init.lua` is `"lib"`.
+    pub fn module_name(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?.with_extension("");
+        let mut parts: Vec<_> = relative
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect::<Option<_>>()?;
+        if parts.len() > 1 && parts.last() == Some(&"init") {
+            parts.pop();
+        }
+        Some(parts.join("."))
+    }
+}
+
+fn collect_lua_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_lua_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "lua") {
+            files.push(path);
+        }
+    }
+}
+
+impl LuaRuntime {
+    /// Loads module `name` again and patches the new version into the table
+    /// `require` returned before, see the module docs.
+    ///
+    /// Returns `Ok(false)` without loading anything if no script has
+    /// required `name`. On error the previous module stays loaded.
+    pub fn reload_module(&self, name: &str) -> LuaResult<bool> {
+        let globals = self.lua().globals();
+        let loaded: LuaTable = globals.get::<LuaTable>("package")?.get("loaded")?;
+        let old: LuaValue = loaded.get(name)?;
+        if old.is_nil() {
+            return Ok(false);
+        }
+
+        loaded.set(name, LuaNil)?;
+        let require: LuaFunction = globals.get("require")?;
+        let new = match require.call::<LuaValue>(name) {
+            Ok(new) => new,
+            Err(e) => {
+                loaded.set(name, old)?;
+                return Err(e);
+            }
+        };
+
+        let (LuaValue::Table(old_table), LuaValue::Table(new_table)) = (&old, &new) else {
+            // Not a table module: there is nothing to patch in place.
+            return Ok(true);
+        };
+        if let (Ok(old_callbacks), Ok(new_callbacks)) = (
+            old_table.get::<LuaTable>("_callbacks"),
+            new_table.get::<LuaTable>("_callbacks"),
+        ) {
+            for pair in new_callbacks.pairs::<LuaValue, LuaValue>() {
+                let (key, callback) = pair?;
+                let previous: LuaValue = old_callbacks.get(key.clone())?;
+                if !previous.is_nil() && globals.raw_get::<LuaValue>(key.clone())? == previous {
+                    globals.raw_set(key, callback)?;
+                }
+            }
+        }
+        for pair in new_table.pairs::<LuaValue, LuaValue>() {
+            let (key, value) = pair?;
+            old_table.raw_set(key, value)?;
+        }
+        loaded.set(name, old)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_names_follow_require_paths() {
+        let watcher = ScriptWatcher::new("missing/scripts", "missing/scripts/main.lua");
+        let name = |p: &str| watcher.module_name(Path::new(p));
+        assert_eq!(
+            name("missing/scripts/scenes/asteroids/level01.lua").as_deref(),
+            Some("scenes.asteroids.level01")
+        );
+        assert_eq!(name("missing/scripts/lib/init.lua").as_deref(), Some("lib"));
+        assert_eq!(name("elsewhere/x.lua"), None);
+        assert!(watcher.is_main_script(Path::new("missing/scripts/main.lua")));
+    }
+
+    #[test]
+    fn scan_reports_new_and_modified_scripts() {
+        let dir = std::env::temp_dir().join("aberred_hot_reload_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("scenes")).unwrap();
+        let main = dir.join("main.lua");
+        std::fs::write(&main, "-- main").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut watcher = ScriptWatcher::new(&dir, &main).with_interval(1.0);
+        assert!(watcher.scan().is_empty());
+
+        let scene = dir.join("scenes").join("menu.lua");
+        std::fs::write(&scene, "return {}").unwrap();
+        assert!(watcher.tick(0.5).is_empty());
+        assert_eq!(watcher.tick(0.5), vec![scene]);
+        assert!(watcher.scan().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reload_module_patches_the_loaded_table_and_injected_callbacks() {
+        let runtime = LuaRuntime::new().unwrap();
+        let lua = runtime.lua();
+        lua.load(
+            r#"
+            package.preload["hot"] = function()
+                return { value = 1, _callbacks = { on_update_hot = function() return 1 end } }
+            end
+            Hot = require("hot")
+            on_update_hot = Hot._callbacks.on_update_hot
+            package.preload["hot"] = function()
+                return { value = 2, _callbacks = { on_update_hot = function() return 2 end } }
+            end
+            "#,
+        )
+        .exec()
+        .unwrap();
+
+        assert!(runtime.reload_module("hot").unwrap());
+        assert_eq!(lua.load("return Hot.value").eval::<i32>().unwrap(), 2);
+        assert_eq!(lua.load("return on_update_hot()").eval::<i32>().unwrap(), 2);
+        assert!(
+            lua.load("return require('hot') == Hot")
+                .eval::<bool>()
+                .unwrap()
+        );
+
+        lua.load(r#"package.preload["hot"] = function() error("typo") end"#)
+            .exec()
+            .unwrap();
+        assert!(runtime.reload_module("hot").is_err());
+        assert_eq!(
+            lua.load("return require('hot').value")
+                .eval::<i32>()
+                .unwrap(),
+            2
+        );
+        assert!(!runtime.reload_module("never_required").unwrap());
+    }
+}
@@ -14,6 +14,7 @@
 //! - [`engine_api`] - `engine` table API registration (all `register_*_api` methods)
 //! - [`command_queues`] - Command queue draining and cache update methods
 //! - [`stub_meta`] - `engine.__meta` stub metadata for IDE/tooling support
+//! - [`hot_reload`] - Script change polling and in-place module reloading
 //!
 //! # Example
 //!
@@ -40,6 +41,7 @@ mod queue_registry;
 mod context;
 mod engine_api;
 mod entity_builder;
+mod hot_reload;
 mod input_snapshot;
 mod runtime;
 mod spawn_data;
@@ -53,6 +55,7 @@ pub use context::{
     SpriteSnapshot, build_entity_context_pooled,
 };
 // pub use entity_builder::{LuaCollisionEntityBuilder, LuaEntityBuilder};
+pub use hot_reload::{DEFAULT_POLL_INTERVAL, SCRIPTS_DIR, ScriptWatcher};
 pub use input_snapshot::InputSnapshot;
 pub use runtime::{LuaRuntime, SignalsCtxTables, action_from_str};
 pub use spawn_data::*;
@@ -5,7 +5,7 @@
 ///      `clear_policy` is `clear` (wiped by `clear_all_commands` on scene switch — the
 ///      default for queues whose commands may reference about-to-be-despawned entities)
 ///      or `preserve` (left untouched — for scene-agnostic queues whose only drain site
-///      runs after `switch_scene`, e.g. `map_commands`/`asset_commands`).
+///      runs after `switch_scene`, e.g. `map_commands`/`asset_commands`/`world_commands`).
 ///   2. Add the corresponding `RefCell<Vec<CmdType>>` field to `LuaAppData` in
 ///      runtime.rs (struct + Default), with the same field name.
 ///
@@ -15,7 +15,7 @@
 macro_rules! lua_queues {
     // ------------------------------------------------------------------
     // Single authoritative list of (queue_field, CmdType, clear_policy) rows.
-    // Callers prepend dispatch tokens; @master appends the 23 rows and
+    // Callers prepend dispatch tokens; @master appends the 35 rows and
     // re-invokes lua_queues! so the chosen @dispatch_* arm matches.
     // ------------------------------------------------------------------
     (@master $($rest:tt)*) => {
@@ -27,6 +27,7 @@ macro_rules! lua_queues {
             (phase_commands,            PhaseCmd,         clear),
             (entity_commands,           EntityCmd,        clear),
             (group_commands,            GroupCmd,         clear),
+            (collision_rule_commands,   CollisionRuleCmd, clear),
             (camera_commands,           CameraCmd,        clear),
             (animation_commands,        AnimationCmd,     clear),
             (render_commands,           RenderCmd,        clear),
@@ -36,6 +37,17 @@ macro_rules! lua_queues {
             (camera_follow_commands,    CameraFollowCmd,  clear),
             (input_commands,            InputCmd,         clear),
             (map_commands,              MapLuaCmd,        preserve),
+            (world_commands,            WorldCmd,         preserve),
+            (fog_commands,              FogCmd,           preserve),
+            (visibility_commands,       VisibilityCmd,    preserve),
+            (clock_commands,            ClockCmd,         preserve),
+            (weather_commands,          WeatherCmd,       preserve),
+            (music_commands,            MusicCmd,         preserve),
+            (formation_commands,        FormationCmd,     preserve),
+            (inventory_commands,        InventoryCmd,     preserve),
+            (ui_commands,               UiCmd,            preserve),
+            (savegame_commands,         SaveGameCmd,      preserve),
+            (debug_commands,            DebugCmd,         preserve),
             (collision_entity_commands, EntityCmd,        clear),
             (collision_signal_commands, SignalCmd,        clear),
             (collision_audio_commands,  AudioLuaCmd,      clear),
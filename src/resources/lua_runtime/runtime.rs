@@ -6,10 +6,25 @@
 use super::commands::*;
 use super::input_snapshot::InputSnapshot;
 use super::spawn_data::*;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::inventory::Inventory;
+use crate::components::tags::Tags;
+use crate::components::tilemap::TileGrid;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fullscreen::MonitorInfo;
+use crate::resources::group::GroupAggregate;
+use crate::resources::highscores::HighScoreEntry;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::occluders::Segment;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::spawnpoints::SpawnPoints;
+use crate::resources::systemtoggles::SystemToggles;
+use crate::resources::weather::WeatherKind;
 use crate::resources::worldsignals::SignalSnapshot;
 use mlua::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::sync::Arc;
 
 /// Cached camera state snapshot for Lua to read via `engine.get_camera()` / `engine.get_camera_view_rect()`.
@@ -47,31 +62,119 @@ impl Default for CameraSnapshot {
     }
 }
 
+impl CameraSnapshot {
+    /// Convert a point in game/render-target space to world space, undoing
+    /// the camera's offset, zoom, rotation and target.
+    pub(super) fn screen_to_world(&self, x: f32, y: f32) -> raylib::prelude::Vector2 {
+        let local = raylib::prelude::Vector2 {
+            x: (x - self.offset_x) / self.zoom,
+            y: (y - self.offset_y) / self.zoom,
+        }
+        .rotated(-self.rotation.to_radians());
+        raylib::prelude::Vector2 {
+            x: local.x + self.target_x,
+            y: local.y + self.target_y,
+        }
+    }
+}
+
+/// Cached fog-of-war state for Lua to read via `engine.is_revealed()`.
+///
+/// Updated by `update_fog_cache()`; the revealed cells are only copied again
+/// when the fog's revision changes.
+#[derive(Default)]
+pub(super) struct FogSnapshot {
+    pub enabled: bool,
+    pub cell_size: f32,
+    pub revision: u64,
+    pub revealed: FxHashSet<(i32, i32)>,
+}
+
+/// Cached line-of-sight state for Lua to read via `engine.can_see()`.
+///
+/// Updated by `update_visibility_cache()`.
+#[derive(Default)]
+pub(super) struct VisibilitySnapshot {
+    pub segments: Arc<Vec<Segment>>,
+    /// Last computed polygon of every `VisibilityPolygon` entity, keyed by
+    /// entity bits.
+    pub polygons: FxHashMap<u64, VisibilityPolygon>,
+    /// Entities seen by the last update, kept to reuse the set.
+    pub live: FxHashSet<u64>,
+}
+
+/// Cached world clock state for Lua to read via `engine.get_clock()`.
+///
+/// Updated by `update_clock_cache()`.
+#[derive(Default, Clone, Copy)]
+pub(super) struct ClockSnapshot {
+    pub minutes: f32,
+    pub day: u32,
+    pub speed: f32,
+    pub paused: bool,
+    pub daylight: f32,
+}
+
+/// Cached weather state for Lua to read via `engine.get_weather()`.
+///
+/// Updated by `update_weather_cache()`.
+#[derive(Default, Clone, Copy)]
+pub(super) struct WeatherSnapshot {
+    pub kind: WeatherKind,
+    pub intensity: f32,
+    pub target_intensity: f32,
+    pub wind: raylib::prelude::Vector2,
+}
+
 /// Cached game configuration snapshot for Lua to read.
 pub(super) struct GameConfigSnapshot {
     pub fullscreen: bool,
+    pub window_mode: &'static str,
+    pub monitor: u32,
+    pub monitors: Vec<MonitorInfo>,
     pub vsync: bool,
     pub target_fps: u32,
+    pub background_fps: u32,
     pub render_width: u32,
     pub render_height: u32,
+    pub render_scale: u32,
     pub background_r: u8,
     pub background_g: u8,
     pub background_b: u8,
     pub pixel_snap_camera: bool,
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub colorblind_mode: &'static str,
+    pub high_contrast_ui: bool,
+    pub system_toggles: SystemToggles,
+    pub auto_quality: bool,
 }
 
 impl Default for GameConfigSnapshot {
     fn default() -> Self {
         Self {
             fullscreen: false,
+            window_mode: "windowed",
+            monitor: 0,
+            monitors: Vec::new(),
             vsync: false,
             target_fps: 60,
+            background_fps: 0,
             render_width: 640,
             render_height: 360,
+            render_scale: 1,
             background_r: 80,
             background_g: 80,
             background_b: 80,
             pixel_snap_camera: true,
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            colorblind_mode: "none",
+            high_contrast_ui: false,
+            system_toggles: SystemToggles::default(),
+            auto_quality: false,
         }
     }
 }
@@ -91,6 +194,7 @@ pub(super) struct LuaAppData {
     pub(super) phase_commands: RefCell<Vec<PhaseCmd>>,
     pub(super) entity_commands: RefCell<Vec<EntityCmd>>,
     pub(super) group_commands: RefCell<Vec<GroupCmd>>,
+    pub(super) collision_rule_commands: RefCell<Vec<CollisionRuleCmd>>,
     pub(super) camera_commands: RefCell<Vec<CameraCmd>>,
     pub(super) animation_commands: RefCell<Vec<AnimationCmd>>,
     pub(super) render_commands: RefCell<Vec<RenderCmd>>,
@@ -100,6 +204,17 @@ pub(super) struct LuaAppData {
     pub(super) camera_follow_commands: RefCell<Vec<CameraFollowCmd>>,
     pub(super) input_commands: RefCell<Vec<InputCmd>>,
     pub(super) map_commands: RefCell<Vec<MapLuaCmd>>,
+    pub(super) world_commands: RefCell<Vec<WorldCmd>>,
+    pub(super) fog_commands: RefCell<Vec<FogCmd>>,
+    pub(super) visibility_commands: RefCell<Vec<VisibilityCmd>>,
+    pub(super) clock_commands: RefCell<Vec<ClockCmd>>,
+    pub(super) weather_commands: RefCell<Vec<WeatherCmd>>,
+    pub(super) music_commands: RefCell<Vec<MusicCmd>>,
+    pub(super) formation_commands: RefCell<Vec<FormationCmd>>,
+    pub(super) inventory_commands: RefCell<Vec<InventoryCmd>>,
+    pub(super) ui_commands: RefCell<Vec<UiCmd>>,
+    pub(super) savegame_commands: RefCell<Vec<SaveGameCmd>>,
+    pub(super) debug_commands: RefCell<Vec<DebugCmd>>,
     pub(super) collision_entity_commands: RefCell<Vec<EntityCmd>>,
     pub(super) collision_signal_commands: RefCell<Vec<SignalCmd>>,
     pub(super) collision_audio_commands: RefCell<Vec<AudioLuaCmd>>,
@@ -110,16 +225,56 @@ pub(super) struct LuaAppData {
     // Read-only caches — updated before each Lua callback
     pub(super) signal_snapshot: RefCell<Arc<SignalSnapshot>>,
     pub(super) tracked_groups: RefCell<FxHashSet<String>>,
+    pub(super) group_aggregates: RefCell<FxHashMap<String, GroupAggregate>>,
+    /// Tags of every entity carrying a [`Tags`](crate::components::tags::Tags)
+    /// component, keyed by entity bits. Kept in sync incrementally.
+    pub(super) entity_tags: RefCell<FxHashMap<u64, Tags>>,
+    pub(super) spatial_index: RefCell<SpatialIndex>,
+    /// Grid and root transform of every spawned tilemap, keyed by root entity
+    /// bits. Read by `engine.screen_to_tile()` and `engine.tile_to_world()`.
+    pub(super) tilemaps: RefCell<FxHashMap<u64, (TileGrid, GlobalTransform2D)>>,
+    /// Fog-of-war state of the current scene, read by `engine.is_revealed()`.
+    pub(super) fog: RefCell<FogSnapshot>,
+    /// Sight blockers and visibility polygons, read by `engine.can_see()`.
+    pub(super) visibility: RefCell<VisibilitySnapshot>,
+    /// In-game time of day, read by `engine.get_clock()`.
+    pub(super) clock: RefCell<ClockSnapshot>,
+    /// Rain, snow and wind, read by `engine.get_weather()`.
+    pub(super) weather: RefCell<WeatherSnapshot>,
+    /// Registered item definitions, read by `engine.get_item()`.
+    pub(super) items: RefCell<ItemRegistry>,
+    /// Named spawn points of the current scene, read by `engine.get_spawn_point()`.
+    pub(super) spawn_points: RefCell<SpawnPoints>,
+    /// High score table, read by `engine.get_high_scores()`.
+    pub(super) high_scores: RefCell<Vec<HighScoreEntry>>,
+    /// Entity, archetype and component memory counters, read by `engine.ecs_stats()`.
+    pub(super) ecs_stats: RefCell<EcsStats>,
+    /// Width and height of every loaded texture, read by `engine.texture_size()`.
+    pub(super) texture_sizes: RefCell<FxHashMap<String, (f32, f32)>>,
+    /// Contents of every [`Inventory`], keyed by entity bits. Kept in sync
+    /// incrementally; read by `engine.inventory_count()` and `engine.inventory_slots()`.
+    pub(super) inventories: RefCell<FxHashMap<u64, Inventory>>,
     pub(super) gameconfig_snapshot: RefCell<GameConfigSnapshot>,
     pub(super) bindings_snapshot: RefCell<std::collections::HashMap<String, String>>,
     pub(super) camera_snapshot: RefCell<CameraSnapshot>,
     /// Resolved Lua function handles, cached by global name. Cleared on
-    /// scene switch via `clear_function_cache` (see `get_function_cached`).
+    /// scene switch and hot reload via `clear_function_cache` (see
+    /// `get_function_cached`).
     pub(super) function_cache: RefCell<FxHashMap<String, LuaFunction>>,
+    /// Engine event hooks registered from Lua via `engine.on_<event>(fn)`
+    /// (e.g. `on_beat`), keyed by event name. Survive scene switches; a script
+    /// clears one by passing `nil`.
+    pub(super) hooks: RefCell<FxHashMap<&'static str, LuaFunction>>,
+    /// Functions registered with `engine.on_sequence(name, fn)`, keyed by
+    /// input sequence name. Survive scene switches like `hooks`.
+    pub(super) sequence_hooks: RefCell<FxHashMap<String, LuaFunction>>,
     /// Frame number and snapshot last written to the pooled input table, used
     /// by `update_input_table` to skip redundant writes within a frame and
     /// diff against the previous frame's values.
     pub(super) last_input: RefCell<Option<(u64, InputSnapshot)>>,
+    /// Set by `engine.dump_asset_usage()`; taken by
+    /// [`take_asset_usage_dump_request`](LuaRuntime::take_asset_usage_dump_request).
+    pub(super) asset_usage_dump: Cell<bool>,
 }
 
 /// Pooled inner tables for one entity's `signals` ctx field
@@ -167,6 +322,7 @@ pub struct CollisionCtxTables {
     pub signals_b_inner: SignalsCtxTables,
     pub sides_a: LuaTable,
     pub sides_b: LuaTable,
+    pub normal: LuaTable,
 }
 
 /// Pooled input callback tables, owned directly by `LuaRuntime` and reused across
@@ -213,6 +369,7 @@ pub struct EntityCtxTables {
     pub timer: LuaTable,
     pub signals: LuaTable,
     pub signals_inner: SignalsCtxTables,
+    pub blackboard: LuaTable,
     pub world_pos: LuaTable,
     pub world_scale: LuaTable,
 }
@@ -236,47 +393,14 @@ pub struct LuaRuntime {
 /// These strings are what Lua passes to `engine.rebind_action()` and
 /// `engine.get_binding()`.
 pub(super) fn action_to_str(action: crate::events::input::InputAction) -> &'static str {
-    use crate::events::input::InputAction;
-    match action {
-        InputAction::MainDirectionUp => "main_up",
-        InputAction::MainDirectionDown => "main_down",
-        InputAction::MainDirectionLeft => "main_left",
-        InputAction::MainDirectionRight => "main_right",
-        InputAction::SecondaryDirectionUp => "secondary_up",
-        InputAction::SecondaryDirectionDown => "secondary_down",
-        InputAction::SecondaryDirectionLeft => "secondary_left",
-        InputAction::SecondaryDirectionRight => "secondary_right",
-        InputAction::Back => "back",
-        InputAction::Action1 => "action_1",
-        InputAction::Action2 => "action_2",
-        InputAction::Action3 => "action_3",
-        InputAction::Special => "special",
-        InputAction::ToggleDebug => "toggle_debug",
-        InputAction::ToggleFullscreen => "toggle_fullscreen",
-    }
+    action.name()
 }
 
 /// Converts a canonical Lua action name string to an [`InputAction`].
+///
+/// [`InputAction`]: crate::events::input::InputAction
 pub fn action_from_str(s: &str) -> Option<crate::events::input::InputAction> {
-    use crate::events::input::InputAction;
-    match s {
-        "up" | "main_up" => Some(InputAction::MainDirectionUp),
-        "down" | "main_down" => Some(InputAction::MainDirectionDown),
-        "left" | "main_left" => Some(InputAction::MainDirectionLeft),
-        "right" | "main_right" => Some(InputAction::MainDirectionRight),
-        "secondary_up" => Some(InputAction::SecondaryDirectionUp),
-        "secondary_down" => Some(InputAction::SecondaryDirectionDown),
-        "secondary_left" => Some(InputAction::SecondaryDirectionLeft),
-        "secondary_right" => Some(InputAction::SecondaryDirectionRight),
-        "back" => Some(InputAction::Back),
-        "action_1" => Some(InputAction::Action1),
-        "action_2" => Some(InputAction::Action2),
-        "action_3" => Some(InputAction::Action3),
-        "special" => Some(InputAction::Special),
-        "toggle_debug" => Some(InputAction::ToggleDebug),
-        "toggle_fullscreen" => Some(InputAction::ToggleFullscreen),
-        _ => None,
-    }
+    crate::events::input::InputAction::from_name(s)
 }
 
 /// Invokes `$cb!(field)` for each of the 19 digital button fields shared by
@@ -365,6 +489,13 @@ impl LuaRuntime {
         runtime.register_gameconfig_api()?;
         runtime.register_input_api()?;
         runtime.register_map_api()?;
+        runtime.register_world_api()?;
+        runtime.register_clock_api()?;
+        runtime.register_weather_api()?;
+        runtime.register_inventory_api()?;
+        runtime.register_ui_api()?;
+        runtime.register_savegame_api()?;
+        runtime.register_debug_api()?;
         runtime.register_builder_meta()?;
         runtime.register_types_meta()?;
         runtime.register_enums_meta()?;
@@ -390,6 +521,7 @@ impl LuaRuntime {
         let sides = lua.create_table()?;
         let sides_a = lua.create_table()?;
         let sides_b = lua.create_table()?;
+        let normal = lua.create_table()?;
 
         // Wire up entity A structure
         entity_a.set("pos", pos_a.clone())?;
@@ -411,6 +543,7 @@ impl LuaRuntime {
         ctx.set("a", entity_a.clone())?;
         ctx.set("b", entity_b.clone())?;
         ctx.set("sides", sides.clone())?;
+        ctx.set("normal", normal.clone())?;
 
         let signals_a_inner = SignalsCtxTables::create(lua)?;
         let signals_b_inner = SignalsCtxTables::create(lua)?;
@@ -431,6 +564,7 @@ impl LuaRuntime {
             signals_b_inner,
             sides_a,
             sides_b,
+            normal,
         })
     }
 
@@ -455,6 +589,7 @@ impl LuaRuntime {
             timer: lua.create_table()?,
             signals: lua.create_table()?,
             signals_inner: SignalsCtxTables::create(lua)?,
+            blackboard: lua.create_table()?,
             world_pos: lua.create_table()?,
             world_scale: lua.create_table()?,
         })
@@ -719,8 +854,8 @@ impl LuaRuntime {
     /// Returns a global Lua function, caching the resolved handle by name.
     ///
     /// Subsequent calls with the same `name` skip the `globals()` lookup
-    /// entirely. The cache is invalidated on scene switch via
-    /// `clear_function_cache` — callbacks are re-injected per scene, so a
+    /// entirely. The cache is invalidated on scene switch and after a script
+    /// hot reload via `clear_function_cache` — callbacks are re-injected per scene, so a
     /// stale handle would otherwise keep pointing at the old scene's closure
     /// environment.
     ///
@@ -781,6 +916,46 @@ impl LuaRuntime {
         }
     }
 
+    /// Returns `true` if a Lua hook is registered for `event` (see [`call_hook`](Self::call_hook)).
+    pub fn has_hook(&self, event: &str) -> bool {
+        self.lua
+            .app_data_ref::<LuaAppData>()
+            .is_some_and(|data| data.hooks.borrow().contains_key(event))
+    }
+
+    /// Invokes the hook registered for `event` via `engine.on_<event>(fn)`, if any.
+    ///
+    /// Missing hooks are silently skipped — unlike named callbacks, hooks are
+    /// opt-in. Errors raised by the hook are logged.
+    pub fn call_hook<A>(&self, event: &str, args: A)
+    where
+        A: IntoLuaMulti,
+    {
+        let hook = self
+            .lua
+            .app_data_ref::<LuaAppData>()
+            .and_then(|data| data.hooks.borrow().get(event).cloned());
+        if let Some(func) = hook
+            && let Err(e) = func.call::<()>(args)
+        {
+            log::error!(target: "lua", "Error in on_{}() hook: {}", event, e);
+        }
+    }
+
+    /// Invokes the function registered for input sequence `name` via
+    /// `engine.on_sequence(name, fn)`, if any. Errors raised by it are logged.
+    pub fn call_sequence_hook(&self, name: &str) {
+        let hook = self
+            .lua
+            .app_data_ref::<LuaAppData>()
+            .and_then(|data| data.sequence_hooks.borrow().get(name).cloned());
+        if let Some(func) = hook
+            && let Err(e) = func.call::<()>(name)
+        {
+            log::error!(target: "lua", "Error in on_sequence(\"{}\") hook: {}", name, e);
+        }
+    }
+
     /// Clears cached function handles (see `get_function_cached`). Call on
     /// scene switch, alongside `clear_all_commands`.
     pub fn clear_function_cache(&self) {
@@ -989,4 +1164,69 @@ mod tests {
         let refreshed = runtime.get_function_cached("greet").unwrap().unwrap();
         assert_eq!(refreshed.call::<String>(()).unwrap(), "new");
     }
+
+    #[test]
+    fn spawn_prefab_area_queues_one_clone_per_point() {
+        let runtime = LuaRuntime::new().unwrap();
+        let queued: usize = runtime
+            .lua()
+            .load(
+                "return engine.spawn_prefab_area('snowflake', \
+                 { shape = 'rect', x = 0, y = 0, w = 100, h = 50, count = 12, distribution = 'grid' })",
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(queued, 12);
+        assert!(
+            runtime
+                .lua()
+                .load("engine.spawn_prefab_area('snowflake', { shape = 'star', count = 3 })")
+                .exec()
+                .is_err()
+        );
+
+        let data = runtime.lua().app_data_ref::<LuaAppData>().unwrap();
+        let clones = data.clone_commands.borrow();
+        assert_eq!(clones.len(), 12);
+        assert!(clones.iter().all(|cmd| cmd.source_key == "snowflake"));
+        let (x, y) = clones[0].overrides.position.unwrap();
+        assert!((0.0..=100.0).contains(&x) && (0.0..=50.0).contains(&y));
+    }
+
+    #[test]
+    fn every_engine_function_has_meta() {
+        let runtime = LuaRuntime::new().unwrap();
+        let engine: LuaTable = runtime.lua().globals().get("engine").unwrap();
+        let meta: LuaTable = engine.get("__meta").unwrap();
+        let meta_fns: LuaTable = meta.get("functions").unwrap();
+
+        let mut undocumented = Vec::new();
+        for pair in engine.pairs::<String, LuaValue>() {
+            let (name, value) = pair.unwrap();
+            if matches!(value, LuaValue::Function(_))
+                && !meta_fns.contains_key(name.as_str()).unwrap()
+            {
+                undocumented.push(name);
+            }
+        }
+        let mut unregistered = Vec::new();
+        for pair in meta_fns.pairs::<String, LuaTable>() {
+            let (name, _) = pair.unwrap();
+            let value: LuaValue = engine.get(name.as_str()).unwrap();
+            if !matches!(value, LuaValue::Function(_)) {
+                unregistered.push(name);
+            }
+        }
+        undocumented.sort();
+        unregistered.sort();
+
+        assert!(
+            undocumented.is_empty(),
+            "engine functions without __meta entry: {undocumented:?}"
+        );
+        assert!(
+            unregistered.is_empty(),
+            "__meta entries without engine function: {unregistered:?}"
+        );
+    }
 }
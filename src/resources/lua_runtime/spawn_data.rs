@@ -3,11 +3,42 @@
 //! These structs hold component data that Lua scripts specify when spawning entities.
 //! They are collected in the `SpawnCmd` struct and processed by Rust systems.
 
+use crate::components::attractor::Attractor;
+use crate::components::audiotriggers::AudioTriggers;
+use crate::components::blobshadow::BlobShadow;
+use crate::components::cooldown::Cooldown;
+use crate::components::forcearea::ForceArea;
+use crate::components::gridlayout::GridIntro;
 use crate::components::guibutton::GuiButton;
 use crate::components::guiimage::GuiImage;
 use crate::components::guilabel::GuiLabel;
 use crate::components::guiprogressbar::GuiProgressBar;
 use crate::components::guiwindow::GuiWindow;
+use crate::components::height::{Height, ZVelocity};
+use crate::components::hitbox::{Hitbox, Hurtbox};
+use crate::components::interactable::Interactable;
+use crate::components::inventory::{GuiInventoryGrid, Inventory};
+use crate::components::joint::{DistanceJoint, PinJoint};
+use crate::components::nineslice::NineSlice;
+use crate::components::offscreenindicator::OffscreenIndicator;
+use crate::components::ownedby::OwnedBy;
+use crate::components::paletteswap::PaletteSwap;
+use crate::components::pathfollow::PathFollow;
+use crate::components::playerindex::PlayerIndex;
+use crate::components::portal::{Portal, SpawnPoint};
+use crate::components::progress::{ProgressBar, RadialProgress};
+use crate::components::renderlayer::RenderLayer;
+use crate::components::restoreonreturn::RestoreOnReturn;
+use crate::components::rope::Rope;
+use crate::components::sensor::Sensor;
+use crate::components::slope::Slope;
+use crate::components::terrain::Terrain;
+use crate::components::textscroller::TextScroller;
+use crate::components::tilemap::TileProjection;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::components::visionsource::VisionSource;
+use crate::components::watersurface::WaterSurface;
+use crate::components::worldanchorui::WorldAnchorUI;
 use crate::resources::uniformvalue::UniformValue;
 
 /// Sprite component data for spawning.
@@ -55,6 +86,8 @@ pub struct RigidBodyData {
     pub max_speed: Option<f32>,
     /// When true, movement system skips physics calculations.
     pub frozen: bool,
+    /// Mass dividing forces and impulses (`None` keeps the default of 1).
+    pub mass: Option<f32>,
     /// Named acceleration forces to add at spawn time.
     pub forces: Vec<ForceData>,
 }
@@ -143,6 +176,8 @@ pub struct LuaCollisionRuleData {
     pub group_a: String,
     pub group_b: String,
     pub callback: String,
+    pub priority: i32,
+    pub exclusive: bool,
 }
 
 /// Animation component data for spawning.
@@ -345,12 +380,33 @@ pub struct EntityShaderData {
     pub uniforms: Vec<(String, UniformValue)>,
 }
 
+/// Lua call site that produced a [`SpawnCmd`].
+#[derive(Debug, Clone, Default)]
+pub struct SpawnOrigin {
+    /// Builder entry point, e.g. `"engine.spawn()"`.
+    pub builder: &'static str,
+    /// Short Lua traceback (`file:line` frames, innermost first).
+    pub traceback: String,
+}
+
+impl std::fmt::Display for SpawnOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.traceback.is_empty() {
+            write!(f, "{}", self.builder)
+        } else {
+            write!(f, "{} at {}", self.builder, self.traceback)
+        }
+    }
+}
+
 /// Command representing a full entity spawn request from Lua.
 /// Contains all optional component data that Lua can specify.
 #[derive(Debug, Clone, Default)]
 pub struct SpawnCmd {
     /// Group name for the entity
     pub group: Option<String>,
+    /// Extra tag labels (see [`Tags`](crate::components::tags::Tags))
+    pub tags: Vec<String>,
     /// World position (x, y)
     pub position: Option<(f32, f32)>,
     /// Screen position (x, y) - for UI elements
@@ -365,6 +421,12 @@ pub struct SpawnCmd {
     pub rigidbody: Option<RigidBodyData>,
     /// BoxCollider data
     pub collider: Option<ColliderData>,
+    /// Whether collisions are refined by the sprite's alpha mask (PixelCollision)
+    pub pixel_collision: bool,
+    /// Right-triangle shape of the collider (Slope)
+    pub slope: Option<Slope>,
+    /// Wind factor of the body (WindResponse) — `None` leaves it to the wind mask
+    pub wind: Option<f32>,
     /// Whether entity responds to mouse input
     pub mouse_controlled: Option<(bool, bool)>, // (follow_x, follow_y)
     /// Rotation in degrees
@@ -393,6 +455,8 @@ pub struct SpawnCmd {
     pub signal_binding: Option<(String, Option<String>)>,
     /// GridLayout component data (path, group, zindex)
     pub grid_layout: Option<(String, String, f32)>,
+    /// Staggered intro for the grid layout's cells
+    pub grid_intro: Option<GridIntro>,
     /// TweenPosition component data
     pub tween_position: Option<TweenPositionData>,
     /// TweenScreenPosition component data
@@ -413,6 +477,8 @@ pub struct SpawnCmd {
     pub animation_controller: Option<AnimationControllerData>,
     /// TTL (time-to-live) in seconds - entity auto-despawns after this duration
     pub ttl: Option<f32>,
+    /// Cooldown (duration, mode, signal key) — inserted as-is
+    pub cooldown: Option<Cooldown>,
     /// Particle emitter component data
     pub particle_emitter: Option<ParticleEmitterData>,
     /// Per-entity shader data
@@ -420,14 +486,62 @@ pub struct SpawnCmd {
     /// Color tint (r, g, b, a) for rendering modulation
     pub tint: Option<(u8, u8, u8, u8)>,
     pub shadow: Option<(f32, f32, u8, u8, u8, u8)>,
+    /// BlobShadow (ellipse size, offset, opacity, height source) — inserted as-is.
+    pub blob_shadow: Option<BlobShadow>,
+    /// Faux-3D Height and its ZVelocity (gravity, bounce, launch speed) — inserted as-is.
+    pub height: Option<(Height, ZVelocity)>,
+    /// RenderLayer set by `with_render_layer` — inserted as-is
+    pub render_layer: Option<RenderLayer>,
+    /// PaletteSwap set by `with_palette_swap` — inserted as-is
+    pub palette_swap: Option<PaletteSwap>,
+    /// PlayerIndex (0-based) set by `with_player` — inserted as-is
+    pub player: Option<PlayerIndex>,
+    /// Terrain set by `with_terrain` — inserted as-is
+    pub terrain: Option<Terrain>,
+    /// Sensor rays accumulated by `with_sensor` — inserted as-is; implies Signals
+    pub sensor: Option<Sensor>,
+    /// ForceArea built by `with_force_area`/`with_conveyor`/`with_force_area_rect` — inserted as-is
+    pub force_area: Option<ForceArea>,
+    /// DistanceJoint set by `with_distance_joint` — inserted as-is
+    pub distance_joint: Option<DistanceJoint>,
+    /// PinJoint set by `with_pin_joint` — inserted as-is
+    pub pin_joint: Option<PinJoint>,
+    /// Rope built by `with_rope` and its anchor/tuning methods — inserted as-is
+    pub rope: Option<Rope>,
+    /// WaterSurface built by `with_water_surface` and its tuning methods — inserted as-is
+    pub water_surface: Option<WaterSurface>,
+    /// Attractor (radius, strength, target label) — inserted as-is
+    pub attractor: Option<Attractor>,
+    /// AudioTriggers accumulated by `with_audio_trigger` — inserted as-is
+    pub audio_triggers: Option<AudioTriggers>,
+    /// TextScroller (text, font, speed, path) — inserted as-is; spawns its glyphs
+    pub text_scroller: Option<TextScroller>,
+    /// PathFollow (waypoints, speed, easing, loop mode) — inserted as-is
+    pub path_follow: Option<PathFollow>,
+    /// VisionSource (radius, blocked) — reveals fog-of-war cells; inserted as-is
+    pub vision: Option<VisionSource>,
+    /// VisibilityPolygon built by `with_visibility`/`with_visibility_fill` — inserted as-is
+    pub visibility: Option<VisibilityPolygon>,
+    /// Hitbox built by `with_hitbox`/`with_hitbox_frame`/`with_hitbox_mask` — inserted as-is
+    pub hitbox: Option<Hitbox>,
+    /// Hurtbox built by `with_hurtbox`/`with_hurtbox_frame` — inserted as-is
+    pub hurtbox: Option<Hurtbox>,
+    /// RestoreOnReturn (key, save filter) — inserted as-is
+    pub restore_on_return: Option<RestoreOnReturn>,
     /// Parent entity ID (from entity.to_bits()) — inserts ChildOf + GlobalTransform2D on spawn
     pub parent: Option<u64>,
+    /// OwnedBy — despawned by `owned_by_cleanup_system` once the owner is gone.
+    pub owned_by: Option<OwnedBy>,
     /// CameraTarget priority (marks entity as candidate for camera following)
     pub camera_target: Option<u8>,
     /// Initial zoom for the CameraTarget (default 1.0 when None)
     pub camera_target_zoom: Option<f32>,
     /// TileMap path — spawns a tilemap root entity whose tiles become `ChildOf` children
     pub tilemap_path: Option<String>,
+    /// Projection overriding the tilemap file's, set by `with_tilemap(path, projection)`
+    pub tilemap_projection: Option<TileProjection>,
+    /// Tilemap layers whose tiles get colliders, set by `with_tilemap(path, projection, solid_layers)`
+    pub tilemap_solid_layers: Vec<String>,
     /// GuiWindow component (size, theme_key) — inserted as-is; themed panel
     /// rendered via the named theme looked up in `GuiThemeStore`.
     pub gui_window: Option<GuiWindow>,
@@ -437,6 +551,30 @@ pub struct SpawnCmd {
     pub lua_setup: Option<String>,
     /// LuaOnAnimationEnd callback name — called once when the non-looped animation first finishes
     pub lua_on_animation_end: Option<String>,
+    /// LuaOnHit callback name — called for each HitEvent this entity's hitbox causes
+    pub lua_on_hit: Option<String>,
+    /// LuaOnLand callback name — called for each LandedEvent of this entity
+    pub lua_on_land: Option<String>,
+    /// Interactable component (range, mask, prompt, action, callback) —
+    /// inserted as-is.
+    pub interactable: Option<Interactable>,
+    /// Portal component (destination, fade) — inserted as-is; used together
+    /// with `interactable`.
+    pub portal: Option<Portal>,
+    /// SpawnPoint component — names this entity's position for portals.
+    pub spawn_point: Option<SpawnPoint>,
+    /// OffscreenIndicator (arrow color, size, icon, distance) — inserted as-is.
+    pub offscreen_indicator: Option<OffscreenIndicator>,
+    /// WorldAnchorUI (target, pixel offset, clamp margin) — inserted as-is;
+    /// `world_anchor_ui_system` drives the entity's ScreenPosition from it.
+    pub world_anchor: Option<WorldAnchorUI>,
+    /// ProgressBar (size, value, colors, background, signal) — inserted as-is;
+    /// `progress_signal_update_system` keeps a bound value in sync.
+    pub progress_bar: Option<ProgressBar>,
+    /// RadialProgress (radii, value, sweep, colors, signal) — inserted as-is.
+    pub radial_progress: Option<RadialProgress>,
+    /// NineSlice (texture, borders, size, source, offset) — inserted as-is.
+    pub nine_slice: Option<NineSlice>,
     /// GuiButton component (size, caption, click callback, disabled state) —
     /// inserted as-is; `gui_button_spawn_system` reacts on `Added<GuiButton>`
     /// to spawn the co-located `GuiInteractable` and caption child.
@@ -452,4 +590,12 @@ pub struct SpawnCmd {
     /// GuiProgressBar component (size, value, max, direction, theme_key, signal_binding) —
     /// inserted as-is; rendered directly by `render_system` with no spawn system.
     pub gui_progress_bar: Option<GuiProgressBar>,
+    /// Inventory component (empty slots) — inserted as-is.
+    pub inventory: Option<Inventory>,
+    /// GuiInventoryGrid component (inventory entity, columns, cell size,
+    /// spacing, theme_key) — inserted as-is; rendered directly by `render_system`.
+    pub gui_inventory_grid: Option<GuiInventoryGrid>,
+    /// Where the spawn was requested from — used to report missing assets or
+    /// dead target entities back to the Lua call site.
+    pub origin: Option<SpawnOrigin>,
 }
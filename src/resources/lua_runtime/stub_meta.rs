@@ -615,6 +615,22 @@ impl LuaRuntime {
                     "anisotropic_16x",
                 ],
             ),
+            (
+                "ColorblindMode",
+                "Color-vision palette remap applied as the last post-process pass",
+                &[
+                    "none",
+                    "protanopia",
+                    "deuteranopia",
+                    "tritanopia",
+                    "grayscale",
+                ],
+            ),
+            (
+                "WindowMode",
+                "Window mode set with engine.set_window_mode",
+                &["windowed", "borderless", "fullscreen"],
+            ),
             (
                 "Category",
                 "Function category",
@@ -631,6 +647,13 @@ impl LuaRuntime {
                     "collision",
                     "animation",
                     "render",
+                    "world",
+                    "clock",
+                    "weather",
+                    "inventory",
+                    "ui",
+                    "save",
+                    "debug",
                 ],
             ),
         ];
@@ -149,6 +149,14 @@ pub struct EntityDef {
     /// Particle emitter component data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub particle_emitter: Option<ParticleEmitterEntry>,
+    /// Names the entity's position as a spawn point for portals
+    /// (maps to [`crate::components::portal::SpawnPoint`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_point: Option<String>,
+    /// Waypoint path the entity moves along
+    /// (maps to [`crate::components::pathfollow::PathFollow`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_follow: Option<PathFollowEntry>,
 }
 
 /// Dynamic text rendering data for an entity placement.
@@ -247,6 +255,30 @@ pub struct ParticleEmitterEntry {
     pub ttl: ParticleEmitterTtlEntry,
 }
 
+/// Path following data for an entity placement.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PathFollowEntry {
+    /// World-space waypoints `[x, y]`.
+    pub points: Vec<[f32; 2]>,
+    /// Units per second along the path.
+    pub speed: f32,
+    #[serde(default)]
+    pub spline: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub orient: bool,
+    /// `"once"`, `"loop"` or `"ping_pong"`. `None` means `"once"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_mode: Option<String>,
+    /// Tween easing name. `None` means `"linear"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub easing: Option<String>,
+    /// Degrees added to the direction of travel when orienting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation_offset: Option<f32>,
+}
+
 /// Load a [`MapData`] from a JSON file at `path`.
 pub fn load_map(path: impl AsRef<Path>) -> Result<MapData, Box<dyn std::error::Error>> {
     let text = std::fs::read_to_string(path)?;
@@ -373,4 +405,16 @@ mod tests {
         assert_eq!(entity.position, Some([10.0, 20.0]));
         assert!(entity.collider.is_none());
     }
+
+    #[test]
+    fn path_follow_flags_default_to_false() {
+        let json = r#"{
+            "path_follow": { "points": [[0.0, 0.0], [64.0, 0.0]], "speed": 32.0, "loop_mode": "ping_pong" }
+        }"#;
+        let entity: EntityDef = serde_json::from_str(json).unwrap();
+        let path = entity.path_follow.unwrap();
+        assert_eq!(path.points.len(), 2);
+        assert!(!path.spline && !path.closed && !path.orient);
+        assert_eq!(path.loop_mode.as_deref(), Some("ping_pong"));
+    }
 }
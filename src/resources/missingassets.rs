@@ -0,0 +1,174 @@
+//! Missing-asset bookkeeping for fallback rendering.
+//!
+//! A typo in one `tex_key` or font name must not crash the game or make an
+//! entity silently vanish. [`TextureStore::get_or_fallback`] and
+//! [`FontStore::get_or_fallback`] hand out a built-in fallback for unknown
+//! keys (a magenta/black checkerboard and raylib's default font), and the
+//! audio thread plays silence for an unknown sound id.
+//!
+//! Each store records the keys it substituted in a [`MissLog`].
+//! [`missing_asset_warning_system`] drains those logs once per frame and
+//! reports them through [`MissingAssetWarnings`], which emits at most one
+//! warning line per frame and repeats a key at most once every
+//! [`MissingAssetWarnings::DEFAULT_REPEAT_FRAMES`] frames.
+//!
+//! [`TextureStore::get_or_fallback`]: crate::resources::texturestore::TextureStore::get_or_fallback
+//! [`FontStore::get_or_fallback`]: crate::resources::fontstore::FontStore::get_or_fallback
+//! [`missing_asset_warning_system`]: crate::systems::missing_assets::missing_asset_warning_system
+
+use bevy_ecs::prelude::Resource;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::sync::{Mutex, PoisonError};
+
+/// Kind of asset a key refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    Texture,
+    Font,
+    Sound,
+    Music,
+}
+
+impl AssetKind {
+    /// Lowercase name used in warnings.
+    pub fn label(self) -> &'static str {
+        match self {
+            AssetKind::Texture => "texture",
+            AssetKind::Font => "font",
+            AssetKind::Sound => "sound",
+            AssetKind::Music => "music",
+        }
+    }
+}
+
+/// Keys a store substituted with its fallback since the last [`drain`](Self::drain).
+///
+/// Lookups take `&self` (render systems only hold `Res<TextureStore>`), so the
+/// set sits behind a mutex. It is only locked on a miss.
+#[derive(Debug, Default)]
+pub struct MissLog(Mutex<FxHashSet<String>>);
+
+impl MissLog {
+    /// Record that `key` was missing.
+    pub fn record(&self, key: &str) {
+        let mut keys = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if !keys.contains(key) {
+            keys.insert(key.to_string());
+        }
+    }
+
+    /// Take every key recorded since the last call, sorted.
+    pub fn drain(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+}
+
+/// Throttle for missing-asset warnings.
+///
+/// Time is measured in caller-defined ticks: frames for the render path,
+/// milliseconds on the audio thread.
+#[derive(Resource, Debug)]
+pub struct MissingAssetWarnings {
+    repeat_after: u64,
+    last_warned: FxHashMap<(AssetKind, String), u64>,
+}
+
+impl Default for MissingAssetWarnings {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_REPEAT_FRAMES)
+    }
+}
+
+impl MissingAssetWarnings {
+    /// Frames between two warnings for the same key (about 5 s at 60 FPS).
+    pub const DEFAULT_REPEAT_FRAMES: u64 = 300;
+
+    /// Create a throttle that repeats a key after `repeat_after` ticks.
+    pub fn new(repeat_after: u64) -> Self {
+        Self {
+            repeat_after,
+            last_warned: FxHashMap::default(),
+        }
+    }
+
+    /// `true` if `key` should be warned about at tick `now`; marks it as warned.
+    pub fn should_warn(&mut self, kind: AssetKind, key: &str, now: u64) -> bool {
+        if let Some(last) = self.last_warned.get_mut(&(kind, key.to_string())) {
+            if now.saturating_sub(*last) < self.repeat_after {
+                return false;
+            }
+            *last = now;
+            return true;
+        }
+        self.last_warned.insert((kind, key.to_string()), now);
+        true
+    }
+
+    /// Build one warning line for every key of `misses` due at tick `now`,
+    /// or `None` if all of them were reported recently.
+    pub fn summarize(
+        &mut self,
+        misses: impl IntoIterator<Item = (AssetKind, String)>,
+        now: u64,
+    ) -> Option<String> {
+        let due: Vec<String> = misses
+            .into_iter()
+            .filter(|(kind, key)| self.should_warn(*kind, key, now))
+            .map(|(kind, key)| format!("{} '{}'", kind.label(), key))
+            .collect();
+        (!due.is_empty())
+            .then(|| format!("Missing assets drawn with fallbacks: {}", due.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn miss_log_deduplicates_and_drains() {
+        let log = MissLog::default();
+        log.record("hero");
+        log.record("hero");
+        log.record("bg");
+        assert_eq!(log.drain(), vec!["bg".to_string(), "hero".to_string()]);
+        assert!(log.drain().is_empty());
+    }
+
+    #[test]
+    fn warnings_repeat_only_after_interval() {
+        let mut warnings = MissingAssetWarnings::new(10);
+        assert!(warnings.should_warn(AssetKind::Texture, "hero", 0));
+        assert!(!warnings.should_warn(AssetKind::Texture, "hero", 9));
+        assert!(warnings.should_warn(AssetKind::Font, "hero", 9));
+        assert!(warnings.should_warn(AssetKind::Texture, "hero", 10));
+    }
+
+    #[test]
+    fn summarize_lists_due_keys_in_one_line() {
+        let mut warnings = MissingAssetWarnings::new(10);
+        let line = warnings.summarize(
+            [
+                (AssetKind::Texture, "hero".to_string()),
+                (AssetKind::Font, "title".to_string()),
+            ],
+            0,
+        );
+        assert_eq!(
+            line.as_deref(),
+            Some("Missing assets drawn with fallbacks: texture 'hero', font 'title'")
+        );
+        assert!(
+            warnings
+                .summarize([(AssetKind::Texture, "hero".to_string())], 5)
+                .is_none()
+        );
+    }
+}
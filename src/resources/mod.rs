@@ -6,61 +6,131 @@
 //! semantics and intended usage of its resource(s).
 //!
 //! Overview
+//! - [`accessibility`] – color-vision palette remap modes and high-contrast UI colors
+//! - [`alphamask`] – per-texture alpha bitmasks for pixel-perfect collision
 //! - [`animationstore`] – definitions for sprite animations reused across entities
 //! - [`appstate`] – typed state store passed to `GuiCallback`; one slot per Rust type
+//! - [`assetusage`] – per-asset memory estimates, reference counts and last-used frames
+//! - [`attractmode`] – idle timer and input replay for the menu's self-playing demo
+//! - [`autotile`] – per-tileset auto-tiling rules applied to runtime tilemap edits
 //! - [`audio`] – bridge and channels for the background audio thread
 //! - [`camera2d`] – shared 2D camera used for world/screen transforms
+//! - [`cameraeffects`] – camera shake, zoom and pan layered on the camera when drawing
 //! - [`camerafollowconfig`] – configuration for the camera-follow system
+//! - [`cinematic`] – cutscene letterbox bars and gameplay input lock
+//! - [`colorgrading`] – color grading LUT applied after the post-process chain, with crossfades
 //! - [`debugmode`] – presence toggles optional debug overlays and logs
 //! - [`debugoverlayconfig`] – per-overlay toggles for the imgui debug HUD
+//! - [`ecsstats`] – entity, archetype, spawn/despawn and component memory counters
+//! - [`fogofwar`] – revealed and visible fog-of-war cells, kept per scene
 //! - [`fontstore`] – loaded fonts keyed by string IDs
-//! - [`fullscreen`] – presence toggles fullscreen mode
+//! - [`formation`] – named formations of entities keeping station around a leader
+//! - [`framearena`] – bump allocator for temporary buffers, reset every frame
+//! - [`fullscreen`] – fullscreen state, fullscreen mode, and connected monitors
 //! - [`gamestate`] – authoritative and pending high-level game state
 //! - [`group`] – set of group names tracked for entity counting
 //! - [`guiinputstate`] – per-frame scratch state for GUI click consumption
 //! - [`guitheme`] – theme resource for GUI rendering (nine-patch window/button skins)
+//! - [`highscores`] – best scores with three-letter names, saved to a JSON file
 //! - [`imgui_bridge`] – internal Dear ImGui backend that replaces raylib's removed feature
 //! - [`input`] – per-frame keyboard state of keys relevant to the game
+//! - [`inputcontext`] – named per-scene action sets managed as a stack
+//! - [`inputsequence`] – named key chord and sequence patterns (cheat codes, combos)
+//! - [`inputstats`] – opt-in per-scene action press counts and hold times for playtests
+//! - [`itemregistry`] – item definitions (name, icon, stack size, properties) used by inventories
+//! - [`layervisibility`] – render layers hidden at runtime
+//! - [`missingassets`] – fallback bookkeeping and throttled warnings for missing asset keys
+//! - [`movementconfig`] – sub-stepping limits for fast bodies in the movement system
+//! - [`occluders`] – wall segments blocking sight, and visibility polygon casting
+//! - [`playerinput`] – per-player device assignment and input state for local multiplayer
+//! - [`portaltransition`] – fade state of the running portal transition
+//! - [`qualitygovernor`] – frame time watcher that steps quality down on weak hardware
+//! - [`rendermode`] – full or dirty-rectangle redraws, and the region to repaint this frame
 //! - [`rendertarget`] – render texture for fixed-resolution rendering with scaling
+//! - [`savegame`] – save slots holding world signals and whitelisted persistent entities
 //! - [`screensize`] – game's internal render resolution in pixels
 //! - [`scenemanager`] – scene registry for `SceneManager`-based Rust games
+//! - [`scenemusic`] – music playlists per scene, with crossfades and track progression
+//! - [`scenestate`] – saved state of `RestoreOnReturn` entities per visited scene
+//! - [`spatialindex`] – uniform-grid index of labeled entity positions for proximity queries
+//! - [`spawnpoints`] – registry of named spawn point positions in the current scene
 //! - [`systemsstore`] – registry of dynamically-lookup-able systems by name
+//! - [`systemtoggles`] – non-essential systems switched off for low-end machines or busy scenes
 //! - [`texturefilter`] – texture sampling filter mode shared by render target and texture store
 //! - [`texturestore`] – loaded textures keyed by string IDs
+//! - [`weather`] – rain, snow and wind intensities with smooth transitions
 //! - [`windowsize`] – actual window dimensions for letterbox calculations
+//! - [`worldclock`] – in-game time of day, day/night ambient light and daily event schedule
+//! - [`worlds`] – secondary simulation worlds rendered into textures (menu dioramas, sandboxes)
 //! - [`worldsignals`] – global signal storage for cross-system communication
+//! - [`worldsnapshot`] – named entity, component and resource snapshots diffed to find leaks
 //! - [`worldtime`] – simulation time and delta
 
+pub mod accessibility;
+pub mod alphamask;
 pub mod animationstore;
 pub mod appstate;
+pub mod assetusage;
 pub mod audio;
+pub mod attractmode;
+pub mod autotile;
 pub mod camera2d;
+pub mod cameraeffects;
 pub mod camerafollowconfig;
+pub mod cinematic;
+pub mod colorgrading;
 pub mod debugmode;
 pub mod debugoverlayconfig;
+pub mod ecsstats;
+pub mod fogofwar;
 pub mod fontstore;
+pub mod formation;
+pub mod framearena;
 pub mod fullscreen;
 pub mod gameconfig;
 pub mod gamestate;
 pub mod group;
 pub mod guiinputstate;
 pub mod guitheme;
+pub mod highscores;
 pub mod imgui_bridge;
 pub mod input;
 pub mod input_bindings;
+pub mod inputcontext;
+pub mod inputsequence;
+pub mod inputstats;
+pub mod itemregistry;
+pub mod layervisibility;
 #[cfg(feature = "lua")]
 pub mod lua_runtime;
 pub mod mapdata;
+pub mod missingassets;
+pub mod movementconfig;
+pub mod occluders;
+pub mod playerinput;
+pub mod portaltransition;
 pub mod postprocessshader;
+pub mod qualitygovernor;
+pub mod rendermode;
 pub mod rendertarget;
+pub mod savegame;
 pub mod scenemanager;
+pub mod scenemusic;
+pub mod scenestate;
 pub mod screensize;
 pub mod shaderstore;
 pub mod signal_keys;
+pub mod spatialindex;
+pub mod spawnpoints;
 pub mod systemsstore;
+pub mod systemtoggles;
 pub mod texturefilter;
 pub mod texturestore;
 pub mod uniformvalue;
+pub mod weather;
 pub mod windowsize;
+pub mod worldclock;
+pub mod worlds;
 pub mod worldsignals;
+pub mod worldsnapshot;
 pub mod worldtime;
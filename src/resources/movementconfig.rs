@@ -0,0 +1,72 @@
+//! Movement sub-stepping configuration.
+//!
+//! [`MovementConfig`] controls how the
+//! [`movement`](crate::systems::movement::movement) system splits the motion of
+//! fast bodies so they cannot pass through thin colliders between two frames.
+//! It is inserted by the engine with its defaults; the movement system also
+//! falls back to the defaults when the resource is missing.
+
+use bevy_ecs::prelude::Resource;
+
+/// Sub-stepping settings for the movement system.
+///
+/// A body with a [`BoxCollider`](crate::components::boxcollider::BoxCollider)
+/// whose motion in one frame is longer than `max_step` times the smaller side
+/// of its collider is swept in evenly spaced sub-steps. It stops at the first
+/// sub-step where it overlaps a collider it was not already touching and
+/// that a collision rule pairs it with, so the collision detector sees the
+/// hit instead of the body tunnelling through.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct MovementConfig {
+    /// Longest distance a body may travel per sub-step, as a fraction of the
+    /// smaller side of its collider. `0.0` disables sub-stepping.
+    pub max_step: f32,
+    /// Upper bound on sub-steps per body per frame.
+    pub max_substeps: u32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            max_step: 1.0,
+            max_substeps: 8,
+        }
+    }
+}
+
+impl MovementConfig {
+    /// Number of sub-steps needed to move `distance` with a collider whose
+    /// smaller side is `collider_extent`. Returns `1` when no sub-stepping is
+    /// needed or it is disabled.
+    pub fn substeps(&self, distance: f32, collider_extent: f32) -> u32 {
+        let max_dist = collider_extent * self.max_step;
+        if max_dist <= 0.0 || distance <= max_dist {
+            return 1;
+        }
+        ((distance / max_dist).ceil() as u32).clamp(1, self.max_substeps.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substeps_scale_with_distance_and_are_capped() {
+        let config = MovementConfig::default();
+        assert_eq!(config.substeps(4.0, 8.0), 1);
+        assert_eq!(config.substeps(8.0, 8.0), 1);
+        assert_eq!(config.substeps(20.0, 8.0), 3);
+        assert_eq!(config.substeps(1000.0, 8.0), 8);
+    }
+
+    #[test]
+    fn zero_max_step_or_extent_disables_substepping() {
+        let off = MovementConfig {
+            max_step: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(off.substeps(1000.0, 8.0), 1);
+        assert_eq!(MovementConfig::default().substeps(1000.0, 0.0), 1);
+    }
+}
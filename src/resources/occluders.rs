@@ -0,0 +1,363 @@
+//! Line-of-sight blockers and visibility polygons.
+//!
+//! [`Occluders`] holds the wall segments that block sight this frame: the
+//! outlines of the tiles of its `solid_layers` and the collider boxes of the
+//! entities carrying its `label` (group or tag). Both are rebuilt by
+//! [`occluder_system`]; nothing blocks sight until one of them is configured
+//! with [`Occluders::configure`] or `engine.set_occluders(label, solid_layers)`.
+//!
+//! [`visibility_polygon`] shadow-casts from a point against those segments and
+//! returns the visible area as a fan of points around the origin, ready to be
+//! drawn as a light or vision cone (see
+//! [`VisibilityPolygon`](crate::components::visibilitypolygon::VisibilityPolygon))
+//! or tested with [`fan_contains`].
+//!
+//! [`occluder_system`]: crate::systems::visibility::occluder_system
+
+use std::f32::consts::{PI, TAU};
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Vector2;
+
+/// Number of rays spread over a full circle to round off the edge of the
+/// visible area where nothing blocks sight.
+const ARC_STEPS: usize = 48;
+
+/// Angle offset of the extra rays cast just past each segment end, so the
+/// polygon reaches behind corners.
+const CORNER_EPSILON: f32 = 1e-4;
+
+/// Working buffers of [`cast_visibility_polygon`], kept between calls so
+/// casting every frame does not allocate once they have grown.
+#[derive(Debug, Default)]
+pub struct CastScratch {
+    near: Vec<Segment>,
+    angles: Vec<f32>,
+}
+
+/// A wall segment blocking sight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub a: Vector2,
+    pub b: Vector2,
+}
+
+impl Segment {
+    pub fn new(a: Vector2, b: Vector2) -> Self {
+        Self { a, b }
+    }
+
+    /// Distance from `point` to the closest point of the segment.
+    pub fn distance_to(&self, point: Vector2) -> f32 {
+        let ab = self.b - self.a;
+        let len_sq = ab.dot(ab);
+        let t = if len_sq > 0.0 {
+            ((point - self.a).dot(ab) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (self.a + ab * t - point).length()
+    }
+
+    /// Distance along the ray `origin + dir * t` (`dir` normalised) at which it
+    /// crosses the segment, if it does.
+    pub fn ray_hit(&self, origin: Vector2, dir: Vector2) -> Option<f32> {
+        let s = self.b - self.a;
+        let denom = cross(dir, s);
+        if denom.abs() < f32::EPSILON {
+            return None;
+        }
+        let diff = self.a - origin;
+        let t = cross(diff, s) / denom;
+        let u = cross(diff, dir) / denom;
+        (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+    }
+}
+
+fn cross(a: Vector2, b: Vector2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// The four edges of the rectangle `(x, y, w, h)`.
+pub fn rect_segments(x: f32, y: f32, w: f32, h: f32) -> [Segment; 4] {
+    let (tl, tr) = (Vector2::new(x, y), Vector2::new(x + w, y));
+    let (br, bl) = (Vector2::new(x + w, y + h), Vector2::new(x, y + h));
+    [
+        Segment::new(tl, tr),
+        Segment::new(tr, br),
+        Segment::new(br, bl),
+        Segment::new(bl, tl),
+    ]
+}
+
+/// Sight blockers for the current frame. See the module docs.
+#[derive(Resource, Debug, Default)]
+pub struct Occluders {
+    label: Option<String>,
+    solid_layers: Vec<String>,
+    tiles_dirty: bool,
+    tile_segments: Vec<Segment>,
+    segments: Arc<Vec<Segment>>,
+}
+
+impl Occluders {
+    /// Block sight with the colliders of entities labeled `label` and the
+    /// tiles of the `solid_layers` tilemap layers.
+    pub fn configure(&mut self, label: Option<String>, solid_layers: Vec<String>) {
+        self.label = label;
+        self.solid_layers = solid_layers;
+        self.tiles_dirty = true;
+    }
+
+    /// Group or tag whose collider boxes block sight.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Tilemap layers whose tiles block sight.
+    pub fn solid_layers(&self) -> &[String] {
+        &self.solid_layers
+    }
+
+    /// `true` if the tile outlines must be rebuilt (the settings changed).
+    pub fn tiles_dirty(&self) -> bool {
+        self.tiles_dirty
+    }
+
+    /// Replace the outlines of the solid tiles.
+    pub fn set_tile_segments(&mut self, segments: Vec<Segment>) {
+        self.tile_segments = segments;
+        self.tiles_dirty = false;
+    }
+
+    /// Rebuild the segment list from the tile outlines and `colliders`.
+    pub fn rebuild(&mut self, colliders: impl IntoIterator<Item = Segment>) {
+        let mut segments = Vec::with_capacity(self.tile_segments.len());
+        segments.extend_from_slice(&self.tile_segments);
+        segments.extend(colliders);
+        self.segments = Arc::new(segments);
+    }
+
+    /// Every segment blocking sight, shared so readers can keep it cheaply.
+    pub fn segments(&self) -> &Arc<Vec<Segment>> {
+        &self.segments
+    }
+
+    /// `true` if nothing blocks the straight line from `from` to `to`.
+    pub fn line_of_sight(&self, from: Vector2, to: Vector2) -> bool {
+        line_of_sight(&self.segments, from, to)
+    }
+}
+
+/// `true` if no segment crosses the straight line from `from` to `to`.
+pub fn line_of_sight(segments: &[Segment], from: Vector2, to: Vector2) -> bool {
+    let delta = to - from;
+    let dist = delta.length();
+    if dist <= f32::EPSILON {
+        return true;
+    }
+    let dir = delta / dist;
+    !segments
+        .iter()
+        .any(|seg| seg.ray_hit(from, dir).is_some_and(|t| t < dist))
+}
+
+/// Shadow-cast from `origin` and return the visible area within `radius`.
+///
+/// `cone` limits sight to `(facing, fov)` radians, facing measured like
+/// `Rotation` (0 points right, positive turns clockwise on screen). The points
+/// are sorted by angle; together with `origin` they form a triangle fan, closed
+/// from the last point back to the first when there is no cone.
+pub fn visibility_polygon(
+    origin: Vector2,
+    radius: f32,
+    cone: Option<(f32, f32)>,
+    segments: &[Segment],
+) -> Vec<Vector2> {
+    let mut points = Vec::new();
+    cast_visibility_polygon(
+        &mut CastScratch::default(),
+        origin,
+        radius,
+        cone,
+        segments,
+        &mut points,
+    );
+    points
+}
+
+/// [`visibility_polygon`] writing into `points`, which is cleared first, and
+/// reusing the working buffers in `scratch`. Used every frame by
+/// [`visibility_polygon_system`](crate::systems::visibility::visibility_polygon_system).
+pub fn cast_visibility_polygon(
+    scratch: &mut CastScratch,
+    origin: Vector2,
+    radius: f32,
+    cone: Option<(f32, f32)>,
+    segments: &[Segment],
+    points: &mut Vec<Vector2>,
+) {
+    let (start, span) = match cone {
+        Some((facing, fov)) if fov < TAU => (facing - fov * 0.5, fov.max(0.0)),
+        _ => (-PI, TAU),
+    };
+    let relative = |angle: f32| (angle - start).rem_euclid(TAU);
+
+    let CastScratch { near, angles } = scratch;
+    near.clear();
+    near.extend(
+        segments
+            .iter()
+            .filter(|seg| seg.distance_to(origin) <= radius)
+            .copied(),
+    );
+
+    angles.clear();
+    angles.reserve(ARC_STEPS + 1 + near.len() * 6);
+    angles.extend(
+        (0..ARC_STEPS)
+            .map(|i| i as f32 * TAU / ARC_STEPS as f32)
+            .filter(|&rel| rel <= span),
+    );
+    angles.push(span);
+    for seg in near.iter() {
+        for end in [seg.a, seg.b] {
+            let rel = relative((end.y - origin.y).atan2(end.x - origin.x));
+            for candidate in [rel - CORNER_EPSILON, rel, rel + CORNER_EPSILON] {
+                if (0.0..=span).contains(&candidate) {
+                    angles.push(candidate);
+                }
+            }
+        }
+    }
+    angles.sort_by(f32::total_cmp);
+    angles.dedup();
+
+    points.clear();
+    points.extend(angles.iter().map(|&rel| {
+        let angle = start + rel;
+        let dir = Vector2::new(angle.cos(), angle.sin());
+        let dist = near
+            .iter()
+            .filter_map(|seg| seg.ray_hit(origin, dir))
+            .fold(radius, f32::min);
+        origin + dir * dist
+    }));
+}
+
+/// `true` if `point` lies inside the triangle fan of `points` around `origin`
+/// (see [`visibility_polygon`]); `closed` joins the last point to the first.
+pub fn fan_contains(origin: Vector2, points: &[Vector2], closed: bool, point: Vector2) -> bool {
+    let pairs = points.windows(2).map(|w| (w[0], w[1]));
+    let wrap = match (closed, points.first(), points.last()) {
+        (true, Some(&first), Some(&last)) if points.len() > 2 => Some((last, first)),
+        _ => None,
+    };
+    pairs
+        .chain(wrap)
+        .any(|(a, b)| triangle_contains(origin, a, b, point))
+}
+
+fn triangle_contains(a: Vector2, b: Vector2, c: Vector2, p: Vector2) -> bool {
+    let d1 = cross(b - a, p - a);
+    let d2 = cross(c - b, p - b);
+    let d3 = cross(a - c, p - c);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall() -> Vec<Segment> {
+        // A vertical wall 50 units right of the origin, from y = -20 to y = 20.
+        vec![Segment::new(
+            Vector2::new(50.0, -20.0),
+            Vector2::new(50.0, 20.0),
+        )]
+    }
+
+    #[test]
+    fn line_of_sight_is_blocked_by_crossing_segments() {
+        let segments = wall();
+        let origin = Vector2::zero();
+        assert!(!line_of_sight(&segments, origin, Vector2::new(100.0, 0.0)));
+        assert!(line_of_sight(&segments, origin, Vector2::new(40.0, 0.0)));
+        assert!(line_of_sight(&segments, origin, Vector2::new(100.0, 100.0)));
+    }
+
+    #[test]
+    fn polygon_stops_at_walls_and_radius() {
+        let segments = wall();
+        let origin = Vector2::zero();
+        let points = visibility_polygon(origin, 100.0, None, &segments);
+        assert!(points.len() >= ARC_STEPS);
+        for p in &points {
+            assert!(p.length() <= 100.0 + 1e-3);
+        }
+        assert!(fan_contains(origin, &points, true, Vector2::new(40.0, 0.0)));
+        assert!(!fan_contains(
+            origin,
+            &points,
+            true,
+            Vector2::new(80.0, 0.0)
+        ));
+        assert!(fan_contains(
+            origin,
+            &points,
+            true,
+            Vector2::new(-80.0, 0.0)
+        ));
+        assert!(fan_contains(
+            origin,
+            &points,
+            true,
+            Vector2::new(60.0, 40.0)
+        ));
+    }
+
+    #[test]
+    fn cone_only_covers_its_opening_angle() {
+        let origin = Vector2::zero();
+        // Facing down (90° clockwise from the right), 90° wide.
+        let points = visibility_polygon(origin, 100.0, Some((PI * 0.5, PI * 0.5)), &[]);
+        assert!(fan_contains(
+            origin,
+            &points,
+            false,
+            Vector2::new(0.0, 50.0)
+        ));
+        assert!(fan_contains(
+            origin,
+            &points,
+            false,
+            Vector2::new(20.0, 50.0)
+        ));
+        assert!(!fan_contains(
+            origin,
+            &points,
+            false,
+            Vector2::new(0.0, -50.0)
+        ));
+        assert!(!fan_contains(
+            origin,
+            &points,
+            false,
+            Vector2::new(50.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn rebuild_combines_tile_and_collider_segments() {
+        let mut occluders = Occluders::default();
+        occluders.configure(Some("wall".to_string()), vec!["walls".to_string()]);
+        assert!(occluders.tiles_dirty());
+        occluders.set_tile_segments(wall());
+        occluders.rebuild(rect_segments(-60.0, -10.0, 10.0, 20.0));
+        assert_eq!(occluders.segments().len(), 5);
+        assert!(!occluders.line_of_sight(Vector2::zero(), Vector2::new(-100.0, 0.0)));
+    }
+}
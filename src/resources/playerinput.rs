@@ -0,0 +1,252 @@
+//! Per-player input for local multiplayer.
+//!
+//! [`PlayerInputs`] assigns an [`InputDevice`] to each of up to
+//! [`MAX_PLAYERS`] player slots and holds one [`InputState`] per player,
+//! written every frame by
+//! [`update_player_inputs`](crate::systems::playerinput::update_player_inputs).
+//! Entities tagged with a
+//! [`PlayerIndex`](crate::components::playerindex::PlayerIndex) read their
+//! player's state.
+//!
+//! | Device | Directions | `action_1` / `action_2` / `action_3` | `back` | `special` |
+//! |--------|------------|------------------|--------|-----------|
+//! | `keyboard` | the shared [`InputBindings`](crate::resources::input_bindings::InputBindings) | | | |
+//! | `keyboard_left` | WASD | F / G / H | Q | E |
+//! | `keyboard_right` | arrows | Right Ctrl / Right Shift / `/` | Backspace | Enter |
+//! | `gamepad_N` | D-pad, left stick (main), right stick (secondary) | A / B / X | Select, Start | Y |
+//!
+//! Player 1 starts on `keyboard`, whose state is a copy of the shared
+//! [`InputState`]. Every other device is polled separately, and its presses
+//! are reported as [`InputEvent`](crate::events::input::InputEvent)s tagged
+//! with the player. A device belongs to one player at a time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! players.assign(0, InputDevice::KeyboardLeft);
+//! players.assign(1, InputDevice::Gamepad(0));
+//! commands.spawn((InputControlled { .. }, PlayerIndex(1), /* ... */));
+//! ```
+
+use bevy_ecs::prelude::Resource;
+use raylib::ffi::KeyboardKey;
+
+use crate::events::input::InputAction;
+use crate::resources::input::{BoolState, InputState};
+
+/// Number of local player slots.
+pub const MAX_PLAYERS: usize = 4;
+
+/// Hardware that drives one player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// The whole keyboard and mouse, through the shared bindings.
+    Keyboard,
+    /// Left half of the keyboard (WASD) for split keyboard play.
+    KeyboardLeft,
+    /// Right half of the keyboard (arrows) for split keyboard play.
+    KeyboardRight,
+    /// A gamepad by raylib index (0-based).
+    Gamepad(i32),
+}
+
+impl InputDevice {
+    /// Lua name: `"keyboard"`, `"keyboard_left"`, `"keyboard_right"`, or
+    /// `"gamepad_1"` to `"gamepad_4"` (1-based).
+    pub fn name(self) -> String {
+        match self {
+            InputDevice::Keyboard => "keyboard".into(),
+            InputDevice::KeyboardLeft => "keyboard_left".into(),
+            InputDevice::KeyboardRight => "keyboard_right".into(),
+            InputDevice::Gamepad(index) => format!("gamepad_{}", index + 1),
+        }
+    }
+
+    /// Parses a name from [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "keyboard" => Some(InputDevice::Keyboard),
+            "keyboard_left" => Some(InputDevice::KeyboardLeft),
+            "keyboard_right" => Some(InputDevice::KeyboardRight),
+            _ => name
+                .strip_prefix("gamepad_")
+                .and_then(|n| n.parse::<i32>().ok())
+                .filter(|n| (1..=MAX_PLAYERS as i32).contains(n))
+                .map(|n| InputDevice::Gamepad(n - 1)),
+        }
+    }
+
+    /// Fixed keys of a split keyboard half; `None` for other devices.
+    pub fn keyboard_layout(self) -> Option<&'static [(InputAction, KeyboardKey)]> {
+        use InputAction::*;
+        use KeyboardKey::*;
+        match self {
+            InputDevice::KeyboardLeft => Some(&[
+                (MainDirectionUp, KEY_W),
+                (MainDirectionDown, KEY_S),
+                (MainDirectionLeft, KEY_A),
+                (MainDirectionRight, KEY_D),
+                (Action1, KEY_F),
+                (Action2, KEY_G),
+                (Action3, KEY_H),
+                (Back, KEY_Q),
+                (Special, KEY_E),
+            ]),
+            InputDevice::KeyboardRight => Some(&[
+                (MainDirectionUp, KEY_UP),
+                (MainDirectionDown, KEY_DOWN),
+                (MainDirectionLeft, KEY_LEFT),
+                (MainDirectionRight, KEY_RIGHT),
+                (Action1, KEY_RIGHT_CONTROL),
+                (Action2, KEY_RIGHT_SHIFT),
+                (Action3, KEY_SLASH),
+                (Back, KEY_BACKSPACE),
+                (Special, KEY_ENTER),
+            ]),
+            _ => None,
+        }
+    }
+}
+
+/// Device assignment and input state of every local player.
+#[derive(Resource, Debug, Clone)]
+pub struct PlayerInputs {
+    devices: [Option<InputDevice>; MAX_PLAYERS],
+    states: [InputState; MAX_PLAYERS],
+}
+
+impl Default for PlayerInputs {
+    fn default() -> Self {
+        let mut devices = [None; MAX_PLAYERS];
+        devices[0] = Some(InputDevice::Keyboard);
+        Self {
+            devices,
+            states: Default::default(),
+        }
+    }
+}
+
+impl PlayerInputs {
+    /// Gives `device` to `player`, taking it from whoever had it.
+    /// Out-of-range players are ignored.
+    pub fn assign(&mut self, player: usize, device: InputDevice) {
+        if player >= MAX_PLAYERS {
+            return;
+        }
+        for slot in self.devices.iter_mut() {
+            if *slot == Some(device) {
+                *slot = None;
+            }
+        }
+        self.devices[player] = Some(device);
+        self.states[player] = InputState::default();
+    }
+
+    /// Leaves `player` without a device; its state reads as released.
+    pub fn unassign(&mut self, player: usize) {
+        if player < MAX_PLAYERS {
+            self.devices[player] = None;
+            self.states[player] = InputState::default();
+        }
+    }
+
+    pub fn device(&self, player: usize) -> Option<InputDevice> {
+        self.devices.get(player).copied().flatten()
+    }
+
+    /// Players with a device, with the device.
+    pub fn assigned(&self) -> impl Iterator<Item = (usize, InputDevice)> + '_ {
+        self.devices
+            .iter()
+            .enumerate()
+            .filter_map(|(player, device)| device.map(|device| (player, device)))
+    }
+
+    /// Input of `player`; an unassigned or out-of-range player reads as
+    /// nothing pressed.
+    pub fn state(&self, player: usize) -> &InputState {
+        static RELEASED: std::sync::LazyLock<InputState> =
+            std::sync::LazyLock::new(InputState::default);
+        self.states.get(player).unwrap_or(&RELEASED)
+    }
+
+    pub fn state_mut(&mut self, player: usize) -> Option<&mut InputState> {
+        self.states.get_mut(player)
+    }
+
+    /// Whether an [`InputEvent`](crate::events::input::InputEvent) from
+    /// `source` (`None` for the shared keyboard) is meant for an entity owned
+    /// by `owner` (`None` for no owner).
+    ///
+    /// Owned entities take their player's events, and shared keyboard events
+    /// when the player is on `keyboard`. Unowned entities take shared keyboard
+    /// and gamepad events; split keyboard keys already reach them through
+    /// the shared bindings.
+    pub fn event_reaches(&self, owner: Option<u8>, source: Option<u8>) -> bool {
+        let device = |player: u8| self.device(player as usize);
+        match (owner, source) {
+            (Some(owner), Some(source)) => owner == source,
+            (Some(owner), None) => device(owner) == Some(InputDevice::Keyboard),
+            (None, None) => true,
+            (None, Some(source)) => matches!(device(source), Some(InputDevice::Gamepad(_))),
+        }
+    }
+
+    /// Sets whether `action` is held by `player` this frame, deriving the
+    /// press and release edges from the previous frame.
+    pub fn set_held(&mut self, player: usize, action: InputAction, held: bool) {
+        if let Some(state) = self.states.get_mut(player) {
+            let was = state.action(action).active;
+            *state.action_mut(action) = BoolState {
+                active: held,
+                just_pressed: held && !was,
+                just_released: !held && was,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn devices_move_between_players() {
+        let mut players = PlayerInputs::default();
+        assert_eq!(players.device(0), Some(InputDevice::Keyboard));
+        players.assign(1, InputDevice::Gamepad(0));
+        players.assign(2, InputDevice::Gamepad(0));
+        assert_eq!(players.device(1), None);
+        assert_eq!(players.device(2), Some(InputDevice::Gamepad(0)));
+        assert_eq!(players.assigned().count(), 2);
+        players.assign(9, InputDevice::KeyboardLeft);
+        assert_eq!(players.assigned().count(), 2);
+
+        assert_eq!(
+            InputDevice::from_name("gamepad_2"),
+            Some(InputDevice::Gamepad(1))
+        );
+        assert_eq!(InputDevice::from_name("gamepad_0"), None);
+        assert_eq!(InputDevice::Gamepad(3).name(), "gamepad_4");
+    }
+
+    #[test]
+    fn held_actions_derive_edges_and_events_reach_owners() {
+        let mut players = PlayerInputs::default();
+        players.assign(1, InputDevice::Gamepad(0));
+        players.set_held(1, InputAction::Action1, true);
+        assert!(players.state(1).action_1.just_pressed);
+        players.set_held(1, InputAction::Action1, true);
+        assert!(players.state(1).action_1.active && !players.state(1).action_1.just_pressed);
+        players.set_held(1, InputAction::Action1, false);
+        assert!(players.state(1).action_1.just_released);
+        assert!(!players.state(7).action_1.active);
+
+        assert!(players.event_reaches(Some(0), None));
+        assert!(!players.event_reaches(Some(1), None));
+        assert!(players.event_reaches(Some(1), Some(1)));
+        assert!(players.event_reaches(None, Some(1)));
+        players.assign(1, InputDevice::KeyboardRight);
+        assert!(!players.event_reaches(None, Some(1)));
+    }
+}
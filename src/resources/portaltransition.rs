@@ -0,0 +1,141 @@
+//! State of the running portal transition.
+//!
+//! [`PortalTransition`] is started by a confirmed
+//! [`Portal`](crate::components::portal::Portal) and advanced by
+//! [`portal_transition_system`](crate::systems::portal::portal_transition_system):
+//!
+//! 1. **Fading out** – the screen fades to the portal's colour.
+//! 2. **Arriving** – only for scene portals: the scene switch has been
+//!    requested and the transition waits, at most [`ARRIVAL_TIMEOUT`]
+//!    seconds, for the destination spawn point to appear in the new scene.
+//! 3. **Fading in** – the screen fades back.
+//!
+//! The render system draws the fade over everything, the GUI included. Other
+//! portals are ignored until the transition is idle again.
+
+use bevy_ecs::prelude::{Entity, Resource};
+use raylib::prelude::Color;
+
+use crate::components::portal::PortalDestination;
+
+/// Longest wait, in seconds, for a scene portal's spawn point to appear.
+pub const ARRIVAL_TIMEOUT: f32 = 2.0;
+
+/// Step of a portal transition.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PortalPhase {
+    #[default]
+    Idle,
+    FadingOut {
+        elapsed: f32,
+    },
+    Arriving {
+        waited: f32,
+    },
+    FadingIn {
+        elapsed: f32,
+    },
+}
+
+/// The running portal transition. See the module docs.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PortalTransition {
+    pub phase: PortalPhase,
+    pub destination: Option<PortalDestination>,
+    /// Entity that confirmed the portal.
+    pub interactor: Option<Entity>,
+    /// Group pattern of the entities placed on a scene portal's spawn point.
+    pub mask: String,
+    /// Duration of each half of the fade, in seconds.
+    pub fade: f32,
+    pub color: Color,
+    /// Entity the destination spawn point name resolved to when leaving the
+    /// old scene; it must not be mistaken for the new scene's point.
+    pub departed_spawn_point: Option<Entity>,
+}
+
+impl PortalTransition {
+    pub fn is_active(&self) -> bool {
+        self.phase != PortalPhase::Idle
+    }
+
+    /// Start fading out towards `destination`.
+    pub fn start(
+        &mut self,
+        destination: PortalDestination,
+        interactor: Entity,
+        mask: &str,
+        fade: f32,
+        color: Color,
+    ) {
+        *self = Self {
+            phase: PortalPhase::FadingOut { elapsed: 0.0 },
+            destination: Some(destination),
+            interactor: Some(interactor),
+            mask: mask.to_owned(),
+            fade,
+            color,
+            departed_spawn_point: None,
+        };
+    }
+
+    /// Opacity of the fade overlay, from `0` (clear) to `1` (opaque).
+    pub fn opacity(&self) -> f32 {
+        let progress = |elapsed: f32| {
+            if self.fade > 0.0 {
+                (elapsed / self.fade).clamp(0.0, 1.0)
+            } else {
+                1.0
+            }
+        };
+        match self.phase {
+            PortalPhase::Idle => 0.0,
+            PortalPhase::FadingOut { elapsed } => progress(elapsed),
+            PortalPhase::Arriving { .. } => 1.0,
+            PortalPhase::FadingIn { elapsed } => 1.0 - progress(elapsed),
+        }
+    }
+
+    /// Colour of the fade overlay, or `None` while nothing is drawn.
+    pub fn overlay_color(&self) -> Option<Color> {
+        let opacity = self.opacity();
+        (opacity > 0.0).then(|| {
+            Color::new(
+                self.color.r,
+                self.color.g,
+                self.color.b,
+                (self.color.a as f32 * opacity).round() as u8,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raylib::prelude::Vector2;
+
+    #[test]
+    fn overlay_follows_the_fade() {
+        let mut transition = PortalTransition::default();
+        assert_eq!(transition.overlay_color(), None);
+
+        let destination = PortalDestination::Position(Vector2::zero());
+        transition.start(
+            destination,
+            Entity::PLACEHOLDER,
+            "player",
+            0.5,
+            Color::BLACK,
+        );
+        assert!(transition.is_active());
+        assert_eq!(transition.overlay_color(), None);
+
+        transition.phase = PortalPhase::FadingOut { elapsed: 0.25 };
+        assert_eq!(transition.overlay_color().map(|c| c.a), Some(128));
+        transition.phase = PortalPhase::Arriving { waited: 1.0 };
+        assert_eq!(transition.overlay_color().map(|c| c.a), Some(255));
+        transition.phase = PortalPhase::FadingIn { elapsed: 0.5 };
+        assert_eq!(transition.overlay_color(), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Automatic quality scaling driven by frame time.
+//!
+//! [`QualityGovernor`] watches a smoothed frame time against a budget derived
+//! from `GameConfig::target_fps`, or the monitor refresh rate when vsync
+//! caps the frame rate lower. When frames stay over budget for
+//! `degrade_after` seconds it applies the next [`QualityStep`]; when they stay
+//! comfortably under it (below `restore_ratio` of the budget) for
+//! `restore_after` seconds it undoes the last one. Each change triggers a
+//! [`QualityChangedEvent`].
+//!
+//! The governor only runs while `GameConfig::auto_quality` is set (the
+//! `auto_quality` key of the `[systems]` section, or
+//! `engine.set_auto_quality(true)` from Lua) and the window has focus, so
+//! background frame throttling never counts as a slowdown. Turning it off
+//! restores full quality.
+//!
+//! Knobs applied at the current [`level`](QualityGovernor::level):
+//!
+//! - [`QualityStep::ParticleDensity`] scales how many particles every
+//!   `ParticleEmitter` spawns per emission, weather included;
+//! - [`QualityStep::Disable`] switches a
+//!   [`SystemToggles`](crate::resources::systemtoggles::SystemToggles) entry
+//!   off without touching the player's own settings.
+//!
+//! Games replace the steps from Rust:
+//!
+//! ```ignore
+//! fn setup(mut governor: ResMut<QualityGovernor>) {
+//!     governor.set_steps(vec![
+//!         QualityStep::Disable(ToggleableSystem::Weather),
+//!         QualityStep::ParticleDensity(0.25),
+//!     ]);
+//! }
+//! ```
+
+use bevy_ecs::prelude::Resource;
+
+use crate::events::quality::QualityChangedEvent;
+use crate::resources::systemtoggles::ToggleableSystem;
+
+/// Weight of the newest frame in the smoothed frame time.
+const SMOOTHING: f32 = 0.1;
+/// Frames longer than this (loading hitches, breakpoints) are ignored.
+const MAX_SAMPLE: f32 = 0.25;
+/// Budget headroom over the target frame time before a frame counts as slow.
+const BUDGET_TOLERANCE: f32 = 1.1;
+/// Frame cap assumed when neither a target FPS nor a refresh rate is known.
+const FALLBACK_FPS: u32 = 60;
+
+/// One quality reduction. See the module docs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QualityStep {
+    /// Multiply every emitter's particles per emission (`0.0`..`1.0`).
+    /// Stacked steps multiply.
+    ParticleDensity(f32),
+    /// Switch a non-essential system off.
+    Disable(ToggleableSystem),
+}
+
+/// Frame time governor. See the module docs.
+#[derive(Resource, Debug, Clone)]
+pub struct QualityGovernor {
+    /// Seconds over budget before stepping quality down.
+    pub degrade_after: f32,
+    /// Seconds under `restore_ratio` of the budget before stepping back up.
+    pub restore_after: f32,
+    /// Fraction of the budget frames must stay under to restore quality.
+    pub restore_ratio: f32,
+    steps: Vec<QualityStep>,
+    level: usize,
+    frame_time: f32,
+    over_budget: f32,
+    under_budget: f32,
+}
+
+impl Default for QualityGovernor {
+    fn default() -> Self {
+        Self {
+            degrade_after: 2.0,
+            restore_after: 10.0,
+            restore_ratio: 0.75,
+            steps: vec![
+                QualityStep::ParticleDensity(0.5),
+                QualityStep::Disable(ToggleableSystem::ColorGrading),
+                QualityStep::ParticleDensity(0.5),
+            ],
+            level: 0,
+            frame_time: 0.0,
+            over_budget: 0.0,
+            under_budget: 0.0,
+        }
+    }
+}
+
+impl QualityGovernor {
+    /// Frame budget in seconds for a frame cap and, with vsync on, the
+    /// monitor refresh rate. `0` means uncapped or unknown.
+    pub fn frame_budget(target_fps: u32, refresh_rate: u32) -> f32 {
+        let fps = [target_fps, refresh_rate]
+            .into_iter()
+            .filter(|&fps| fps > 0)
+            .min()
+            .unwrap_or(FALLBACK_FPS);
+        BUDGET_TOLERANCE / fps as f32
+    }
+
+    pub fn steps(&self) -> &[QualityStep] {
+        &self.steps
+    }
+
+    /// Replace the steps, restoring full quality.
+    pub fn set_steps(&mut self, steps: Vec<QualityStep>) {
+        self.steps = steps;
+        self.reset();
+    }
+
+    /// Number of steps applied; `0` is full quality.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Smoothed frame time in seconds.
+    pub fn frame_time(&self) -> f32 {
+        self.frame_time
+    }
+
+    fn applied(&self) -> impl Iterator<Item = &QualityStep> {
+        self.steps.iter().take(self.level)
+    }
+
+    /// Product of the applied [`QualityStep::ParticleDensity`] steps.
+    pub fn particle_density(&self) -> f32 {
+        self.applied()
+            .map(|step| match step {
+                QualityStep::ParticleDensity(density) => density.clamp(0.0, 1.0),
+                QualityStep::Disable(_) => 1.0,
+            })
+            .product()
+    }
+
+    /// Whether an applied step switches `system` off.
+    pub fn disables(&self, system: ToggleableSystem) -> bool {
+        self.applied()
+            .any(|step| *step == QualityStep::Disable(system))
+    }
+
+    /// Feed one frame's duration; returns the change if a step was taken.
+    pub fn observe(&mut self, dt: f32, budget: f32) -> Option<QualityChangedEvent> {
+        if !(0.0..=MAX_SAMPLE).contains(&dt) {
+            return None;
+        }
+        self.frame_time = if self.frame_time > 0.0 {
+            self.frame_time + (dt - self.frame_time) * SMOOTHING
+        } else {
+            dt
+        };
+        let old_level = self.level;
+        if self.frame_time > budget {
+            self.under_budget = 0.0;
+            self.over_budget += dt;
+            if self.over_budget >= self.degrade_after && self.level < self.steps.len() {
+                self.level += 1;
+                self.over_budget = 0.0;
+            }
+        } else if self.frame_time < budget * self.restore_ratio {
+            self.over_budget = 0.0;
+            self.under_budget += dt;
+            if self.under_budget >= self.restore_after && self.level > 0 {
+                self.level -= 1;
+                self.under_budget = 0.0;
+            }
+        } else {
+            self.over_budget = 0.0;
+            self.under_budget = 0.0;
+        }
+        (self.level != old_level).then_some(QualityChangedEvent {
+            old_level,
+            level: self.level,
+            frame_time: self.frame_time,
+        })
+    }
+
+    /// Restore full quality and forget the frame time history.
+    pub fn reset(&mut self) -> Option<QualityChangedEvent> {
+        let change = (self.level > 0).then_some(QualityChangedEvent {
+            old_level: self.level,
+            level: 0,
+            frame_time: self.frame_time,
+        });
+        self.level = 0;
+        self.frame_time = 0.0;
+        self.over_budget = 0.0;
+        self.under_budget = 0.0;
+        change
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_frames_step_down_and_fast_frames_step_back_up() {
+        let mut governor = QualityGovernor::default();
+        let budget = QualityGovernor::frame_budget(60, 0);
+        assert_eq!(QualityGovernor::frame_budget(120, 60), budget);
+        assert_eq!(QualityGovernor::frame_budget(0, 0), budget);
+
+        // One second of 30 fps is not enough; two are
+        let slow = 1.0 / 30.0;
+        assert!((0..30).all(|_| governor.observe(slow, budget).is_none()));
+        let change = (0..60)
+            .find_map(|_| governor.observe(slow, budget))
+            .unwrap();
+        assert_eq!((change.old_level, change.level), (0, 1));
+        assert_eq!(governor.particle_density(), 0.5);
+        assert!(!governor.disables(ToggleableSystem::ColorGrading));
+
+        // Hitches are ignored
+        assert!(governor.observe(2.0, budget).is_none());
+
+        (0..600).for_each(|_| {
+            governor.observe(slow, budget);
+        });
+        assert_eq!(governor.level(), 3);
+        assert_eq!(governor.particle_density(), 0.25);
+        assert!(governor.disables(ToggleableSystem::ColorGrading));
+
+        let fast = 1.0 / 120.0;
+        let change = (0..1500)
+            .find_map(|_| governor.observe(fast, budget))
+            .unwrap();
+        assert_eq!((change.old_level, change.level), (3, 2));
+
+        assert_eq!(governor.reset().map(|c| c.level), Some(0));
+        assert_eq!(governor.reset(), None);
+        assert_eq!(governor.particle_density(), 1.0);
+    }
+}
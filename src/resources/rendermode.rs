@@ -0,0 +1,157 @@
+//! Render mode and the dirty region of the current frame.
+//!
+//! [`RenderMode`] selects whether the render target is redrawn from scratch
+//! every frame or only where something changed. In
+//! [`RenderMode::DirtyRects`] the
+//! [`track_dirty_regions`](crate::systems::dirtyrects::track_dirty_regions)
+//! system compares every drawable entity with the previous frame and records
+//! the screen area that needs repainting in [`DirtyRegion`]; the render system
+//! then clips its pass to that area, or skips it when nothing changed.
+//!
+//! The mode is scene-scoped: scene switches reset it to [`RenderMode::Full`].
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Rectangle;
+
+/// How the render target is repainted each frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// Clear and redraw the whole render target every frame.
+    #[default]
+    Full,
+    /// Redraw only the regions whose entities changed since the last frame.
+    /// Meant for UI-heavy or puzzle scenes where most frames are static.
+    DirtyRects,
+}
+
+impl RenderMode {
+    /// Parse a mode name as used by the Lua API (`"full"`, `"dirty_rects"`).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(Self::Full),
+            "dirty_rects" => Some(Self::DirtyRects),
+            _ => None,
+        }
+    }
+
+    /// Name of the mode as accepted by [`parse`](Self::parse).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::DirtyRects => "dirty_rects",
+        }
+    }
+}
+
+/// Area of the render target, in logical screen pixels, that must be redrawn
+/// this frame.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub enum DirtyRegion {
+    /// Redraw everything.
+    #[default]
+    Full,
+    /// Redraw only inside this rectangle.
+    Rect(Rectangle),
+    /// Nothing changed; the previous frame's content can be reused.
+    Clean,
+}
+
+impl DirtyRegion {
+    /// Grow the region to also cover `rect`. Empty rectangles are ignored.
+    pub fn add(&mut self, rect: Rectangle) {
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+        *self = match *self {
+            Self::Full => Self::Full,
+            Self::Clean => Self::Rect(rect),
+            Self::Rect(r) => {
+                let x = r.x.min(rect.x);
+                let y = r.y.min(rect.y);
+                Self::Rect(Rectangle {
+                    x,
+                    y,
+                    width: (r.x + r.width).max(rect.x + rect.width) - x,
+                    height: (r.y + r.height).max(rect.y + rect.height) - y,
+                })
+            }
+        };
+    }
+
+    /// Clip the region to a `width` x `height` screen. A rectangle that ends
+    /// up empty becomes [`Clean`](Self::Clean); one that covers the whole
+    /// screen becomes [`Full`](Self::Full).
+    pub fn clip_to_screen(&mut self, width: f32, height: f32) {
+        let Self::Rect(r) = *self else {
+            return;
+        };
+        let x0 = r.x.max(0.0);
+        let y0 = r.y.max(0.0);
+        let x1 = (r.x + r.width).min(width);
+        let y1 = (r.y + r.height).min(height);
+        *self = if x1 <= x0 || y1 <= y0 {
+            Self::Clean
+        } else if x0 <= 0.0 && y0 <= 0.0 && x1 >= width && y1 >= height {
+            Self::Full
+        } else {
+            Self::Rect(Rectangle {
+                x: x0,
+                y: y0,
+                width: x1 - x0,
+                height: y1 - y0,
+            })
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips_names() {
+        for mode in [RenderMode::Full, RenderMode::DirtyRects] {
+            assert_eq!(RenderMode::parse(mode.as_str()), Some(mode));
+        }
+        assert_eq!(RenderMode::parse("partial"), None);
+    }
+
+    #[test]
+    fn add_unions_rectangles_and_keeps_full() {
+        let mut region = DirtyRegion::Clean;
+        region.add(Rectangle::new(10.0, 10.0, 10.0, 10.0));
+        region.add(Rectangle::new(30.0, 5.0, 5.0, 5.0));
+        assert_eq!(
+            region,
+            DirtyRegion::Rect(Rectangle::new(10.0, 5.0, 25.0, 15.0))
+        );
+
+        region.add(Rectangle::new(0.0, 0.0, 0.0, 50.0));
+        assert_eq!(
+            region,
+            DirtyRegion::Rect(Rectangle::new(10.0, 5.0, 25.0, 15.0))
+        );
+
+        let mut full = DirtyRegion::Full;
+        full.add(Rectangle::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(full, DirtyRegion::Full);
+    }
+
+    #[test]
+    fn clip_to_screen_collapses_offscreen_and_fullscreen_rects() {
+        let mut partial = DirtyRegion::Rect(Rectangle::new(-5.0, 90.0, 20.0, 20.0));
+        partial.clip_to_screen(100.0, 100.0);
+        assert_eq!(
+            partial,
+            DirtyRegion::Rect(Rectangle::new(0.0, 90.0, 15.0, 10.0))
+        );
+
+        let mut offscreen = DirtyRegion::Rect(Rectangle::new(200.0, 0.0, 10.0, 10.0));
+        offscreen.clip_to_screen(100.0, 100.0);
+        assert_eq!(offscreen, DirtyRegion::Clean);
+
+        let mut covering = DirtyRegion::Rect(Rectangle::new(-1.0, -1.0, 120.0, 120.0));
+        covering.clip_to_screen(100.0, 100.0);
+        assert_eq!(covering, DirtyRegion::Full);
+    }
+}
@@ -3,10 +3,17 @@
 //! Provides a framebuffer texture at the game's internal resolution, which is
 //! then scaled to fit the actual window size. This enables resolution-independent
 //! rendering with proper aspect ratio preservation.
+//!
+//! The framebuffer can be supersampled: with a [`RenderTarget::scale`] above
+//! `1` it is allocated at that multiple of the game resolution, the render
+//! system draws through [`RenderTarget::world_camera`] and
+//! [`RenderTarget::screen_camera`] so logical coordinates stay unchanged, and
+//! the final blit downscales it to the window.
 
 use crate::resources::texturefilter::TextureFilter;
 use raylib::ffi;
 use raylib::prelude::*;
+use rustc_hash::FxHashMap;
 
 /// Render target for fixed-resolution rendering with scaling.
 ///
@@ -31,8 +38,12 @@ pub struct RenderTarget {
     pub game_width: u32,
     /// Game's internal render height in pixels.
     pub game_height: u32,
+    /// Supersampling factor; the textures are `scale` times the game resolution.
+    pub scale: u32,
     /// Current texture filtering mode.
     pub filter: TextureFilter,
+    /// Render textures of secondary worlds, keyed by world name (lazy init).
+    pub world_views: FxHashMap<String, RenderTexture2D>,
 }
 
 impl RenderTarget {
@@ -56,7 +67,9 @@ impl RenderTarget {
             pong: None,
             game_width: width,
             game_height: height,
+            scale: 1,
             filter: TextureFilter::default(),
+            world_views: FxHashMap::default(),
         };
 
         // Apply default filter
@@ -78,7 +91,7 @@ impl RenderTarget {
 
         if self.ping.is_none() {
             let ping = rl
-                .load_render_texture(th, self.game_width, self.game_height)
+                .load_render_texture(th, self.texture_width(), self.texture_height())
                 .map_err(|e| format!("Failed to create ping buffer: {}", e))?;
             unsafe {
                 ffi::SetTextureFilter(ping.texture, filter_value);
@@ -87,7 +100,7 @@ impl RenderTarget {
         }
         if self.pong.is_none() {
             let pong = rl
-                .load_render_texture(th, self.game_width, self.game_height)
+                .load_render_texture(th, self.texture_width(), self.texture_height())
                 .map_err(|e| format!("Failed to create pong buffer: {}", e))?;
             unsafe {
                 ffi::SetTextureFilter(pong.texture, filter_value);
@@ -129,13 +142,36 @@ impl RenderTarget {
         width: u32,
         height: u32,
     ) -> Result<(), String> {
+        self.reload(rl, th, width, height, self.scale)
+    }
+
+    /// Change the supersampling factor, recreating the textures at the new size.
+    pub fn set_scale(
+        &mut self,
+        rl: &mut RaylibHandle,
+        th: &RaylibThread,
+        scale: u32,
+    ) -> Result<(), String> {
+        self.reload(rl, th, self.game_width, self.game_height, scale.max(1))
+    }
+
+    fn reload(
+        &mut self,
+        rl: &mut RaylibHandle,
+        th: &RaylibThread,
+        width: u32,
+        height: u32,
+        scale: u32,
+    ) -> Result<(), String> {
+        let (tex_w, tex_h) = (width * scale, height * scale);
         let texture = rl
-            .load_render_texture(th, width, height)
+            .load_render_texture(th, tex_w, tex_h)
             .map_err(|e| format!("Failed to recreate render texture: {}", e))?;
 
         self.texture = texture;
         self.game_width = width;
         self.game_height = height;
+        self.scale = scale;
         self.apply_filter();
 
         // Recreate ping/pong buffers if they exist
@@ -143,7 +179,7 @@ impl RenderTarget {
 
         if self.ping.is_some() {
             let ping = rl
-                .load_render_texture(th, width, height)
+                .load_render_texture(th, tex_w, tex_h)
                 .map_err(|e| format!("Failed to recreate ping buffer: {}", e))?;
             unsafe {
                 ffi::SetTextureFilter(ping.texture, filter_value);
@@ -152,7 +188,7 @@ impl RenderTarget {
         }
         if self.pong.is_some() {
             let pong = rl
-                .load_render_texture(th, width, height)
+                .load_render_texture(th, tex_w, tex_h)
                 .map_err(|e| format!("Failed to recreate pong buffer: {}", e))?;
             unsafe {
                 ffi::SetTextureFilter(pong.texture, filter_value);
@@ -163,6 +199,63 @@ impl RenderTarget {
         Ok(())
     }
 
+    /// Width of the (possibly supersampled) render textures in pixels.
+    pub fn texture_width(&self) -> u32 {
+        self.game_width * self.scale
+    }
+
+    /// Height of the (possibly supersampled) render textures in pixels.
+    pub fn texture_height(&self) -> u32 {
+        self.game_height * self.scale
+    }
+
+    /// `camera` adjusted to draw world space into the supersampled texture.
+    ///
+    /// Zoom and offset are multiplied by [`scale`](Self::scale), so the same
+    /// world rectangle fills the texture as at scale `1`.
+    pub fn world_camera(&self, camera: Camera2D) -> Camera2D {
+        scaled_camera(camera, self.scale)
+    }
+
+    /// Camera for drawing screen-space (game resolution) coordinates into the
+    /// supersampled texture. The identity transform at scale `1`.
+    pub fn screen_camera(&self) -> Camera2D {
+        scaled_camera(
+            Camera2D {
+                offset: Vector2::zero(),
+                target: Vector2::zero(),
+                rotation: 0.0,
+                zoom: 1.0,
+            },
+            self.scale,
+        )
+    }
+
+    /// Get the view texture of secondary world `name`, (re)creating it when it
+    /// is missing or its size differs from `width`×`height`.
+    pub fn ensure_world_view(
+        &mut self,
+        rl: &mut RaylibHandle,
+        th: &RaylibThread,
+        name: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<&mut RenderTexture2D, String> {
+        let stale = self.world_views.get(name).is_none_or(|view| {
+            view.texture.width != width as i32 || view.texture.height != height as i32
+        });
+        if stale {
+            let view = rl
+                .load_render_texture(th, width, height)
+                .map_err(|e| format!("Failed to create view for world '{name}': {e}"))?;
+            unsafe {
+                ffi::SetTextureFilter(view.texture, self.filter.to_ffi());
+            }
+            self.world_views.insert(name.to_owned(), view);
+        }
+        Ok(self.world_views.get_mut(name).expect("just ensured"))
+    }
+
     /// Get the source rectangle for drawing this texture.
     ///
     /// Returns a rectangle with negative height to flip the Y axis,
@@ -171,8 +264,38 @@ impl RenderTarget {
         Rectangle {
             x: 0.0,
             y: 0.0,
-            width: self.game_width as f32,
-            height: -(self.game_height as f32), // Negative to flip Y
+            width: self.texture_width() as f32,
+            height: -(self.texture_height() as f32), // Negative to flip Y
         }
     }
 }
+
+fn scaled_camera(camera: Camera2D, scale: u32) -> Camera2D {
+    let scale = scale as f32;
+    Camera2D {
+        offset: camera.offset * scale,
+        zoom: camera.zoom * scale,
+        ..camera
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_camera_keeps_world_to_screen_mapping_proportional() {
+        let camera = Camera2D {
+            offset: Vector2 { x: 320.0, y: 180.0 },
+            target: Vector2 { x: 100.0, y: 50.0 },
+            rotation: 30.0,
+            zoom: 1.5,
+        };
+        let scaled = scaled_camera(camera, 2);
+        assert_eq!(scaled.offset, Vector2 { x: 640.0, y: 360.0 });
+        assert_eq!(scaled.target, camera.target);
+        assert_eq!(scaled.rotation, camera.rotation);
+        assert_eq!(scaled.zoom, 3.0);
+        assert_eq!(scaled_camera(camera, 1).zoom, camera.zoom);
+    }
+}
@@ -0,0 +1,298 @@
+//! Save slots for game progress.
+//!
+//! A save holds every [`WorldSignals`] scalar, integer, string and flag
+//! (score, unlocked levels, options the game keeps in signals) plus the
+//! position and [`Signals`] of whitelisted entities, written as JSON to
+//! `<dir>/save_<slot>.json`. The whitelist is every
+//! [`Persistent`](crate::components::persistent::Persistent) entity
+//! registered under a name in `WorldSignals` (e.g. `"player"`): names are
+//! stable across runs, entity IDs are not.
+//!
+//! Engine-owned signals are left out of saves and untouched by loads: the
+//! current scene, the scene switch and quit requests, and group counts.
+//! Loading replaces all other world signals, so flags set since the save
+//! are cleared. A saved entity whose name is no longer registered, or no
+//! longer persistent, is skipped.
+//!
+//! Saving and loading run through the
+//! [`SaveCmd`](crate::systems::savegame::SaveCmd) and
+//! [`LoadCmd`](crate::systems::savegame::LoadCmd) commands (Lua:
+//! `engine.save_game(slot)` and `engine.load_game(slot)`).
+//!
+//! # Example
+//!
+//! ```ignore
+//! commands.insert_resource(SaveGame::new("./profiles/alice"));
+//! commands.queue(SaveCmd { slot: "autosave".into() });
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Vector2;
+use serde::{Deserialize, Serialize};
+
+use crate::components::signals::Signals;
+use crate::key::Key;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Directory the engine saves to by default.
+pub const DEFAULT_SAVE_DIR: &str = "./saves";
+
+/// Format version written to every save.
+const SAVE_VERSION: u32 = 1;
+
+/// Where save slots are written.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct SaveGame {
+    pub dir: PathBuf,
+}
+
+impl Default for SaveGame {
+    fn default() -> Self {
+        Self::new(DEFAULT_SAVE_DIR)
+    }
+}
+
+impl SaveGame {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// File of `slot`. Slot names may only use letters, digits, `_` and `-`.
+    pub fn slot_path(&self, slot: &str) -> Result<PathBuf, String> {
+        slot_path(&self.dir, slot)
+    }
+
+    /// Whether `slot` has been saved.
+    pub fn exists(&self, slot: &str) -> bool {
+        self.slot_path(slot).is_ok_and(|path| path.is_file())
+    }
+}
+
+/// File of `slot` in `dir`. See [`SaveGame::slot_path`].
+pub fn slot_path(dir: &Path, slot: &str) -> Result<PathBuf, String> {
+    let valid = !slot.is_empty()
+        && slot
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(format!(
+            "invalid save slot '{slot}' (use letters, digits, '_' and '-')"
+        ));
+    }
+    Ok(dir.join(format!("save_{slot}.json")))
+}
+
+/// Whether a world signal key belongs to the engine rather than the game.
+fn is_engine_key(key: &str) -> bool {
+    key == sk::SCENE
+        || key == sk::SWITCH_SCENE
+        || key == sk::QUIT_GAME
+        || key.starts_with(sk::GROUP_COUNT_PREFIX)
+}
+
+/// The game-owned keys among `keys`.
+fn game_keys<'a>(keys: impl Iterator<Item = &'a Key>) -> Vec<&'static str> {
+    keys.map(|k| k.as_str())
+        .filter(|k| !is_engine_key(k))
+        .collect()
+}
+
+/// Signal values of a save, sorted by key for stable files.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SavedSignals {
+    pub scalars: BTreeMap<String, f32>,
+    pub integers: BTreeMap<String, i32>,
+    pub strings: BTreeMap<String, String>,
+    pub flags: BTreeSet<String>,
+}
+
+impl SavedSignals {
+    /// Every game-owned signal of `signals`.
+    pub fn from_world(signals: &WorldSignals) -> Self {
+        let mut saved = Self::default();
+        for key in game_keys(signals.get_scalars().keys()) {
+            saved.scalars.insert(key.to_string(), signals.scalars[key]);
+        }
+        for key in game_keys(signals.get_integers().keys()) {
+            saved
+                .integers
+                .insert(key.to_string(), signals.integers[key]);
+        }
+        for key in game_keys(signals.get_strings().keys()) {
+            saved
+                .strings
+                .insert(key.to_string(), signals.strings[key].clone());
+        }
+        for key in game_keys(signals.get_flags().iter()) {
+            saved.flags.insert(key.to_string());
+        }
+        saved
+    }
+
+    /// Replace the game-owned signals of `signals` with these.
+    pub fn apply_to_world(&self, signals: &mut WorldSignals) {
+        for key in game_keys(signals.get_scalars().keys()) {
+            signals.clear_scalar(key);
+        }
+        for key in game_keys(signals.get_integers().keys()) {
+            signals.clear_integer(key);
+        }
+        for key in game_keys(signals.get_strings().keys()) {
+            signals.remove_string(key);
+        }
+        for key in game_keys(signals.get_flags().iter()) {
+            signals.clear_flag(key);
+        }
+        for (key, value) in &self.scalars {
+            if !is_engine_key(key) {
+                signals.set_scalar(key.as_str(), *value);
+            }
+        }
+        for (key, value) in &self.integers {
+            if !is_engine_key(key) {
+                signals.set_integer(key.as_str(), *value);
+            }
+        }
+        for (key, value) in &self.strings {
+            if !is_engine_key(key) {
+                signals.set_string(key.as_str(), value.clone());
+            }
+        }
+        for key in self.flags.iter().filter(|k| !is_engine_key(k)) {
+            signals.set_flag(key.as_str());
+        }
+    }
+
+    /// All signals of an entity's `Signals` component.
+    pub fn from_signals(signals: &Signals) -> Self {
+        Self {
+            scalars: signals
+                .get_scalars()
+                .iter()
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect(),
+            integers: signals
+                .get_integers()
+                .iter()
+                .map(|(k, v)| (k.to_string(), *v))
+                .collect(),
+            strings: signals
+                .get_strings()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+            flags: signals.get_flags().iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    /// A `Signals` component holding these signals.
+    pub fn to_signals(&self) -> Signals {
+        let mut signals = Signals::default();
+        for (key, value) in &self.scalars {
+            signals.set_scalar(key.as_str(), *value);
+        }
+        for (key, value) in &self.integers {
+            signals.set_integer(key.as_str(), *value);
+        }
+        for (key, value) in &self.strings {
+            signals.set_string(key.as_str(), value.clone());
+        }
+        for key in &self.flags {
+            signals.set_flag(key.as_str());
+        }
+        signals
+    }
+}
+
+/// Saved state of one whitelisted entity.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SavedEntity {
+    /// `MapPosition`, if the entity had one.
+    pub position: Option<[f32; 2]>,
+    /// `Signals`, if the entity had them.
+    pub signals: Option<SavedSignals>,
+}
+
+impl SavedEntity {
+    pub fn position(&self) -> Option<Vector2> {
+        self.position.map(|[x, y]| Vector2::new(x, y))
+    }
+}
+
+/// Contents of one save slot.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SaveData {
+    pub version: u32,
+    pub signals: SavedSignals,
+    /// Whitelisted entities by their `WorldSignals` name.
+    pub entities: BTreeMap<String, SavedEntity>,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            signals: SavedSignals::default(),
+            entities: BTreeMap::new(),
+        }
+    }
+}
+
+impl SaveData {
+    /// Read a save written by [`write`](Self::write).
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    }
+
+    /// Write the save as JSON, creating its directory if needed.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_names_cannot_leave_the_save_dir() {
+        let saves = SaveGame::new("saves");
+        assert_eq!(
+            saves.slot_path("slot-1").unwrap(),
+            Path::new("saves").join("save_slot-1.json")
+        );
+        assert!(saves.slot_path("../config").is_err());
+        assert!(saves.slot_path("").is_err());
+    }
+
+    #[test]
+    fn loading_replaces_game_signals_and_keeps_engine_ones() {
+        let mut world = WorldSignals::default();
+        world.set_integer("score", 1200);
+        world.set_flag("level2_unlocked");
+        world.set_string(sk::SCENE, "level2");
+        let saved = SavedSignals::from_world(&world);
+        assert!(!saved.strings.contains_key(sk::SCENE));
+
+        world.set_integer("score", 5);
+        world.set_flag("boss_defeated");
+        world.set_string(sk::SCENE, "level3");
+        saved.apply_to_world(&mut world);
+        assert_eq!(world.get_integer("score"), Some(1200));
+        assert!(world.has_flag("level2_unlocked"));
+        assert!(!world.has_flag("boss_defeated"));
+        assert_eq!(world.get_string(sk::SCENE).unwrap(), "level3");
+    }
+}
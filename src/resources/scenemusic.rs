@@ -0,0 +1,325 @@
+//! Music playlists started automatically on scene changes.
+//!
+//! [`SceneMusic`] maps scene names to [`Playlist`]s. When the scene changes,
+//! [`scene_music_system`](crate::systems::scenemusic::scene_music_system)
+//! crossfades from the playing track to the first track of the new scene's
+//! playlist, then moves to the next track each time one finishes. A playlist
+//! wraps around at the end, reshuffled when `shuffle` is set.
+//!
+//! - A scene with the same playlist as the previous one keeps the music
+//!   playing.
+//! - A scene without a playlist leaves the music alone, so scripts can still
+//!   drive it with `engine.play_music`.
+//! - A scene mapped to an empty playlist fades the music out.
+//!
+//! Tracks are music ids loaded with `engine.load_music` (or
+//! [`AudioCmd::LoadMusic`]).
+//!
+//! # Example
+//!
+//! ```ignore
+//! music.set("level01", Playlist::new(["track_a", "track_b"]).with_shuffle(true));
+//! music.set("title", Playlist::new(["title_theme"]).with_crossfade(2.0));
+//! ```
+
+use bevy_ecs::prelude::Resource;
+use rustc_hash::FxHashMap;
+
+use crate::events::audio::AudioCmd;
+
+/// Seconds of the crossfade between two scenes' playlists by default.
+pub const DEFAULT_CROSSFADE: f32 = 1.0;
+
+/// Ordered list of music tracks for a scene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playlist {
+    pub tracks: Vec<String>,
+    /// Play the tracks in random order, reshuffled on every pass.
+    pub shuffle: bool,
+    /// Seconds to fade the previous music out and this one in.
+    pub crossfade: f32,
+    /// Stream volume of the tracks, 0.0 to 1.0.
+    pub volume: f32,
+}
+
+impl Playlist {
+    pub fn new<S: Into<String>>(tracks: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            tracks: tracks.into_iter().map(Into::into).collect(),
+            shuffle: false,
+            crossfade: DEFAULT_CROSSFADE,
+            volume: 1.0,
+        }
+    }
+
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    pub fn with_crossfade(mut self, seconds: f32) -> Self {
+        self.crossfade = seconds.max(0.0);
+        self
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// The playlist being played.
+#[derive(Debug, Clone)]
+struct Playing {
+    playlist: Playlist,
+    /// Play order as indices into `playlist.tracks`.
+    order: Vec<usize>,
+    position: usize,
+    /// Seconds into the fade-in; `None` once at full volume.
+    fade_in: Option<f32>,
+}
+
+impl Playing {
+    fn track(&self) -> &str {
+        &self.playlist.tracks[self.order[self.position]]
+    }
+}
+
+/// A track fading out, stopped when the fade ends.
+#[derive(Debug, Clone)]
+struct FadeOut {
+    id: String,
+    from: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+/// Scene to playlist mapping and playlist playback state.
+#[derive(Resource, Debug, Default)]
+pub struct SceneMusic {
+    playlists: FxHashMap<String, Playlist>,
+    playing: Option<Playing>,
+    fading: Vec<FadeOut>,
+    /// Scene whose playlist was last applied.
+    pub(crate) scene: Option<String>,
+    /// A playlist of the current scene changed since it was applied.
+    pub(crate) dirty: bool,
+    rng: fastrand::Rng,
+}
+
+impl SceneMusic {
+    /// Sets the playlist of `scene`. Setting the current scene's playlist
+    /// applies it right away.
+    pub fn set(&mut self, scene: impl Into<String>, playlist: Playlist) {
+        let scene = scene.into();
+        self.dirty |= self.scene.as_ref() == Some(&scene);
+        self.playlists.insert(scene, playlist);
+    }
+
+    /// Forgets the playlist of `scene`; the music keeps playing.
+    pub fn remove(&mut self, scene: &str) {
+        self.playlists.remove(scene);
+    }
+
+    pub fn get(&self, scene: &str) -> Option<&Playlist> {
+        self.playlists.get(scene)
+    }
+
+    /// Music id of the playlist track playing now.
+    pub fn current_track(&self) -> Option<&str> {
+        self.playing.as_ref().map(Playing::track)
+    }
+
+    /// Switches to the playlist of `scene`, returning the audio commands to
+    /// send.
+    pub fn enter(&mut self, scene: &str) -> Vec<AudioCmd> {
+        self.scene = Some(scene.to_string());
+        self.dirty = false;
+        let mut cmds = Vec::new();
+        let Some(playlist) = self.playlists.get(scene).cloned() else {
+            return cmds;
+        };
+        if self
+            .playing
+            .as_ref()
+            .is_some_and(|playing| playing.playlist == playlist)
+        {
+            return cmds;
+        }
+
+        if let Some(old) = self.playing.take() {
+            let from = match old.fade_in {
+                Some(elapsed) if old.playlist.crossfade > 0.0 => {
+                    old.playlist.volume * (elapsed / old.playlist.crossfade).min(1.0)
+                }
+                _ => old.playlist.volume,
+            };
+            let id = old.track().to_string();
+            if playlist.crossfade > 0.0 {
+                self.fading.push(FadeOut {
+                    id,
+                    from,
+                    duration: playlist.crossfade,
+                    elapsed: 0.0,
+                });
+            } else {
+                cmds.push(AudioCmd::StopMusic { id });
+            }
+        }
+        if playlist.tracks.is_empty() {
+            return cmds;
+        }
+
+        let order = self.order(&playlist, None);
+        let fade_in = (playlist.crossfade > 0.0).then_some(0.0);
+        let playing = Playing {
+            playlist,
+            order,
+            position: 0,
+            fade_in,
+        };
+        let volume = if fade_in.is_some() {
+            0.0
+        } else {
+            playing.playlist.volume
+        };
+        self.start(playing.track().to_string(), volume, &mut cmds);
+        self.playing = Some(playing);
+        cmds
+    }
+
+    /// Moves to the next track when `id` is the playlist track that ended.
+    pub fn track_finished(&mut self, id: &str) -> Vec<AudioCmd> {
+        let mut cmds = Vec::new();
+        let Some(mut playing) = self.playing.take() else {
+            return cmds;
+        };
+        if playing.track() == id {
+            playing.position += 1;
+            if playing.position >= playing.order.len() {
+                let last = playing.order.last().copied();
+                playing.order = self.order(&playing.playlist, last);
+                playing.position = 0;
+            }
+            playing.fade_in = None;
+            self.start(
+                playing.track().to_string(),
+                playing.playlist.volume,
+                &mut cmds,
+            );
+        }
+        self.playing = Some(playing);
+        cmds
+    }
+
+    /// Advances the fades by `delta` seconds.
+    pub fn update(&mut self, delta: f32) -> Vec<AudioCmd> {
+        let mut cmds = Vec::new();
+        if let Some(playing) = &mut self.playing
+            && let Some(elapsed) = &mut playing.fade_in
+        {
+            *elapsed += delta;
+            let t = (*elapsed / playing.playlist.crossfade).min(1.0);
+            cmds.push(AudioCmd::VolumeMusic {
+                id: playing.track().to_string(),
+                vol: playing.playlist.volume * t,
+            });
+            if t >= 1.0 {
+                playing.fade_in = None;
+            }
+        }
+        self.fading.retain_mut(|fade| {
+            fade.elapsed += delta;
+            if fade.elapsed >= fade.duration {
+                cmds.push(AudioCmd::StopMusic {
+                    id: fade.id.clone(),
+                });
+                return false;
+            }
+            cmds.push(AudioCmd::VolumeMusic {
+                id: fade.id.clone(),
+                vol: fade.from * (1.0 - fade.elapsed / fade.duration),
+            });
+            true
+        });
+        cmds
+    }
+
+    /// Play order of `playlist`, not starting with `avoid` when shuffled.
+    fn order(&mut self, playlist: &Playlist, avoid: Option<usize>) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..playlist.tracks.len()).collect();
+        if playlist.shuffle {
+            self.rng.shuffle(&mut order);
+            if order.len() > 1 && order.first().copied() == avoid {
+                order.swap(0, 1);
+            }
+        }
+        order
+    }
+
+    fn start(&mut self, id: String, volume: f32, cmds: &mut Vec<AudioCmd>) {
+        // A track coming back while it fades out takes over the stream.
+        self.fading.retain(|fade| fade.id != id);
+        cmds.push(AudioCmd::PlayMusic {
+            id: id.clone(),
+            looped: false,
+        });
+        cmds.push(AudioCmd::VolumeMusic { id, vol: volume });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(cmds: &[AudioCmd]) -> Vec<String> {
+        cmds.iter()
+            .map(|cmd| match cmd {
+                AudioCmd::PlayMusic { id, .. } => format!("play {id}"),
+                AudioCmd::StopMusic { id } => format!("stop {id}"),
+                AudioCmd::VolumeMusic { id, vol } => format!("vol {id} {vol}"),
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn crossfades_between_scenes_and_keeps_shared_playlists() {
+        let mut music = SceneMusic::default();
+        music.set("title", Playlist::new(["intro"]).with_crossfade(0.0));
+        music.set("level1", Playlist::new(["a", "b"]).with_crossfade(2.0));
+        music.set("level2", Playlist::new(["a", "b"]).with_crossfade(2.0));
+
+        assert_eq!(
+            ids(&music.enter("title")),
+            vec!["play intro", "vol intro 1"]
+        );
+        assert_eq!(ids(&music.enter("level1")), vec!["play a", "vol a 0"]);
+        assert_eq!(ids(&music.update(1.0)), vec!["vol a 0.5", "vol intro 0.5"]);
+        assert_eq!(ids(&music.update(1.0)), vec!["vol a 1", "stop intro"]);
+        assert!(music.update(1.0).is_empty());
+
+        assert!(music.enter("level2").is_empty());
+        assert!(music.enter("no_music").is_empty());
+        assert_eq!(music.current_track(), Some("a"));
+    }
+
+    #[test]
+    fn finished_tracks_advance_and_wrap() {
+        let mut music = SceneMusic::default();
+        music.set("level1", Playlist::new(["a", "b"]).with_crossfade(0.0));
+        music.enter("level1");
+
+        assert!(music.track_finished("intro").is_empty());
+        assert_eq!(ids(&music.track_finished("a")), vec!["play b", "vol b 1"]);
+        assert_eq!(ids(&music.track_finished("b")), vec!["play a", "vol a 1"]);
+
+        music.set(
+            "level1",
+            Playlist::new(Vec::<String>::new()).with_crossfade(0.0),
+        );
+        assert!(music.dirty);
+        assert_eq!(ids(&music.enter("level1")), vec!["stop a"]);
+        assert_eq!(music.current_track(), None);
+    }
+}
@@ -0,0 +1,244 @@
+//! Per-scene memory of [`RestoreOnReturn`] entities.
+//!
+//! [`SceneState`] stores an [`EntitySnapshot`] for every
+//! [`RestoreOnReturn`] entity of each scene the player has left. Both scene
+//! switch systems (the Lua `switch_scene` and the Rust
+//! [`scene_switch_system`](crate::systems::scene_dispatch::scene_switch_system))
+//! call [`SceneState::switch_scene`] before despawning the old scene, and
+//! [`restore_on_return_system`](crate::systems::scenestate::restore_on_return_system)
+//! applies the saved snapshots as the new scene's entities are spawned.
+//!
+//! An entity that was spawned during a visit but no longer exists when the
+//! scene is left is remembered as destroyed and despawned again on every
+//! later visit.
+//!
+//! Re-entering the scene that is already active (e.g. a "retry" that
+//! re-runs the same scene) also restores its state; call
+//! [`SceneState::forget_scene`] or [`SceneState::clear`] first for a fresh
+//! start, e.g. when starting a new game. Forgetting the active scene also
+//! drops the current visit, so leaving it saves nothing. The
+//! [`ForgetSceneState`](crate::systems::scenestate::ForgetSceneState) and
+//! [`ClearSceneState`](crate::systems::scenestate::ClearSceneState) commands
+//! (Lua: `engine.scene_state_forget` and `engine.scene_state_clear`) call
+//! them.
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::components::mapposition::MapPosition;
+use crate::components::restoreonreturn::RestoreOnReturn;
+use crate::components::signals::Signals;
+
+/// Saved state of one [`RestoreOnReturn`] entity.
+#[derive(Debug, Clone, Default)]
+pub struct EntitySnapshot {
+    /// The entity was despawned before its scene was left.
+    pub destroyed: bool,
+    /// Saved `MapPosition`, if restored.
+    pub position: Option<Vector2>,
+    /// Saved `Signals`, if restored.
+    pub signals: Option<Signals>,
+}
+
+impl EntitySnapshot {
+    /// Capture the fields selected by `restore` from a live entity.
+    pub fn capture(
+        restore: &RestoreOnReturn,
+        position: Option<&MapPosition>,
+        signals: Option<&Signals>,
+    ) -> Self {
+        Self {
+            destroyed: false,
+            position: position.filter(|_| restore.position).map(|p| p.pos),
+            signals: signals.filter(|_| restore.signals).cloned(),
+        }
+    }
+}
+
+/// Snapshots of [`RestoreOnReturn`] entities, keyed by scene then entity key.
+#[derive(Resource, Debug, Default)]
+pub struct SceneState {
+    active: Option<String>,
+    /// Keys spawned (and not restored as destroyed) during the current visit.
+    live: FxHashSet<String>,
+    /// The active scene was forgotten: leaving it saves nothing.
+    discard_visit: bool,
+    scenes: FxHashMap<String, FxHashMap<String, EntitySnapshot>>,
+}
+
+impl SceneState {
+    /// Scene whose entities are currently being tracked.
+    pub fn active_scene(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    /// Saved snapshot of `key` in `scene`, if any.
+    pub fn get(&self, scene: &str, key: &str) -> Option<&EntitySnapshot> {
+        self.scenes.get(scene)?.get(key)
+    }
+
+    /// Register a freshly spawned entity of the active scene and return its
+    /// saved snapshot, if any.
+    ///
+    /// Entities restored as destroyed are not tracked, so they stay
+    /// destroyed on later visits.
+    pub fn track(&mut self, key: &str) -> Option<&EntitySnapshot> {
+        let snapshot = self
+            .active
+            .as_ref()
+            .and_then(|scene| self.scenes.get(scene))
+            .and_then(|entities| entities.get(key));
+        if !snapshot.is_some_and(|s| s.destroyed) {
+            self.live.insert(key.to_owned());
+        }
+        snapshot
+    }
+
+    /// Save the active scene and make `next` the active scene.
+    ///
+    /// `survivors` are the [`RestoreOnReturn`] entities still alive in the
+    /// scene being left; tracked keys missing from it are recorded as
+    /// destroyed.
+    pub fn switch_scene(
+        &mut self,
+        next: &str,
+        survivors: impl IntoIterator<Item = (String, EntitySnapshot)>,
+    ) {
+        if let Some(prev) = self.active.take()
+            && !self.discard_visit
+        {
+            let saved = self.scenes.entry(prev).or_default();
+            for (key, snapshot) in survivors {
+                self.live.remove(&key);
+                saved.insert(key, snapshot);
+            }
+            for key in self.live.drain() {
+                saved.insert(
+                    key,
+                    EntitySnapshot {
+                        destroyed: true,
+                        ..EntitySnapshot::default()
+                    },
+                );
+            }
+        }
+        self.live.clear();
+        self.discard_visit = false;
+        self.active = Some(next.to_owned());
+    }
+
+    /// Drop everything saved for `scene`, including the current visit if it
+    /// is the active scene.
+    pub fn forget_scene(&mut self, scene: &str) {
+        self.scenes.remove(scene);
+        if self.active.as_deref() == Some(scene) {
+            self.discard_active_visit();
+        }
+    }
+
+    /// Drop everything saved for all scenes, including the current visit.
+    pub fn clear(&mut self) {
+        self.scenes.clear();
+        self.discard_active_visit();
+    }
+
+    fn discard_active_visit(&mut self) {
+        self.live.clear();
+        self.discard_visit = self.active.is_some();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alive(x: f32) -> EntitySnapshot {
+        EntitySnapshot {
+            position: Some(Vector2::new(x, 0.0)),
+            ..EntitySnapshot::default()
+        }
+    }
+
+    #[test]
+    fn capture_respects_filters() {
+        let pos = MapPosition::new(3.0, 4.0);
+        let signals = Signals::default();
+        let all = EntitySnapshot::capture(&RestoreOnReturn::new("a"), Some(&pos), Some(&signals));
+        assert_eq!(all.position, Some(Vector2::new(3.0, 4.0)));
+        assert!(all.signals.is_some());
+
+        let filter = RestoreOnReturn::new("a")
+            .with_position(false)
+            .with_signals(false);
+        let none = EntitySnapshot::capture(&filter, Some(&pos), Some(&signals));
+        assert_eq!(none.position, None);
+        assert!(none.signals.is_none());
+    }
+
+    #[test]
+    fn missing_tracked_entities_are_saved_as_destroyed() {
+        let mut state = SceneState::default();
+        state.switch_scene("cave", []);
+        assert!(state.track("door").is_none());
+        assert!(state.track("coin").is_none());
+
+        // The coin was collected; only the door survives.
+        state.switch_scene("town", [("door".to_string(), alive(5.0))]);
+        assert!(state.get("cave", "coin").unwrap().destroyed);
+        assert_eq!(
+            state.get("cave", "door").unwrap().position,
+            Some(Vector2::new(5.0, 0.0))
+        );
+
+        state.switch_scene("cave", []);
+        assert!(state.track("coin").unwrap().destroyed);
+        assert!(!state.track("door").unwrap().destroyed);
+
+        // The coin was never respawned, so it stays destroyed.
+        state.switch_scene("town", [("door".to_string(), alive(6.0))]);
+        assert!(state.get("cave", "coin").unwrap().destroyed);
+        assert_eq!(
+            state.get("cave", "door").unwrap().position,
+            Some(Vector2::new(6.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn forget_scene_and_clear() {
+        let mut state = SceneState::default();
+        state.switch_scene("cave", []);
+        state.track("coin");
+        state.switch_scene("town", []);
+        assert!(state.get("cave", "coin").is_some());
+
+        state.forget_scene("cave");
+        assert!(state.get("cave", "coin").is_none());
+
+        state.switch_scene("cave", []);
+        state.track("coin");
+        state.switch_scene("town", []);
+        state.clear();
+        assert!(state.get("cave", "coin").is_none());
+        assert_eq!(state.active_scene(), Some("town"));
+    }
+
+    #[test]
+    fn forgetting_the_active_scene_drops_the_current_visit() {
+        let mut state = SceneState::default();
+        state.switch_scene("cave", []);
+        state.track("coin");
+        state.track("door");
+
+        // A retry: forget the cave while in it, then re-run it.
+        state.forget_scene("cave");
+        state.switch_scene("cave", [("door".to_string(), alive(5.0))]);
+        assert!(state.get("cave", "coin").is_none());
+        assert!(state.get("cave", "door").is_none());
+
+        // The next visit is remembered again.
+        state.track("coin");
+        state.switch_scene("town", []);
+        assert!(state.get("cave", "coin").unwrap().destroyed);
+    }
+}
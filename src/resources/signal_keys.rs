@@ -31,3 +31,11 @@ pub const DEFAULT_SCENE: &str = "menu";
 /// Prefix for integer signals that track live entity counts per group.
 /// Full key: `format!("{GROUP_COUNT_PREFIX}{group_name}")`.
 pub const GROUP_COUNT_PREFIX: &str = "group_count:";
+
+/// Scalar: height above the ground published on an entity's `Signals`
+/// component by `height_system` while the entity has a `ZVelocity`.
+pub const HEIGHT: &str = "height";
+
+/// Flag: set on an entity's `Signals` component by `height_system` while its
+/// `Height` is above the ground; cleared when it lands.
+pub const AIRBORNE: &str = "airborne";
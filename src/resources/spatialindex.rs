@@ -0,0 +1,351 @@
+//! Uniform-grid spatial index of labeled entities.
+//!
+//! [`SpatialIndex`] buckets every entity that has a position and a
+//! [`Group`](crate::components::group::Group) or
+//! [`Tags`](crate::components::tags::Tags) into fixed-size grid cells, so
+//! proximity queries ("what is inside this rectangle?", "which enemy is
+//! closest?") only visit nearby cells instead of every entity.
+//!
+//! The index is rebuilt by
+//! [`update_spatial_index_system`](crate::systems::spatialindex::update_spatial_index_system)
+//! on frames where a position, group, or tag changed.
+//!
+//! Entities are indexed by their pivot point (world-space position), not by
+//! their collider or sprite bounds.
+//!
+//! Cloning an index is cheap: the clone shares the entries with the original
+//! until the next rebuild, so the Lua runtime can keep a snapshot of it.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let mut hits = Vec::new();
+//! spatial_index.query_rect(0.0, 0.0, 128.0, 128.0, Some("enemy"), &mut hits);
+//!
+//! if let Some((target, dist)) = spatial_index.nearest(player.x, player.y, Some("enemy"), None) {
+//!     // ...
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+/// Default grid cell edge length in world units.
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+/// One indexed entity.
+#[derive(Debug, Clone)]
+pub struct SpatialEntry {
+    /// The indexed entity.
+    pub entity: Entity,
+    /// World-space position used for queries.
+    pub pos: Vector2,
+    /// Interned label ids (group name and tags), see [`SpatialIndex::label_id`].
+    labels: SmallVec<[u32; 2]>,
+}
+
+/// Grid-bucketed positions of labeled entities.
+#[derive(Debug, Clone, Resource)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    entries: Arc<Vec<SpatialEntry>>,
+    cells: Arc<FxHashMap<(i32, i32), Vec<u32>>>,
+    /// Label name → interned id. Ids stay stable across rebuilds so label
+    /// strings are only allocated the first time they are seen.
+    label_ids: Arc<FxHashMap<String, u32>>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    /// Create an empty index with the given cell size (clamped to at least 1.0).
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            entries: Arc::default(),
+            cells: Arc::default(),
+            label_ids: Arc::default(),
+        }
+    }
+
+    /// Grid cell edge length in world units.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All indexed entries.
+    pub fn entries(&self) -> &[SpatialEntry] {
+        &self.entries
+    }
+
+    /// Remove all entries, keeping interned labels. Allocations are kept
+    /// unless a clone still shares them, in which case the clone keeps the
+    /// old entries and this index starts new buffers.
+    pub fn clear(&mut self) {
+        match Arc::get_mut(&mut self.entries) {
+            Some(entries) => entries.clear(),
+            None => self.entries = Arc::default(),
+        }
+        match Arc::get_mut(&mut self.cells) {
+            Some(cells) => {
+                // Drop cells that stayed empty since the last rebuild so the
+                // map doesn't grow without bound as entities move across the
+                // world.
+                cells.retain(|_, bucket| !bucket.is_empty());
+                for bucket in cells.values_mut() {
+                    bucket.clear();
+                }
+            }
+            None => self.cells = Arc::default(),
+        }
+    }
+
+    /// Interned id of `label`, or `None` if no entity ever carried it.
+    pub fn label_id(&self, label: &str) -> Option<u32> {
+        self.label_ids.get(label).copied()
+    }
+
+    fn intern(&mut self, label: &str) -> u32 {
+        if let Some(&id) = self.label_ids.get(label) {
+            return id;
+        }
+        let id = self.label_ids.len() as u32;
+        Arc::make_mut(&mut self.label_ids).insert(label.to_owned(), id);
+        id
+    }
+
+    fn cell_of(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Add an entity at `pos` carrying `labels`.
+    pub fn insert<'a>(
+        &mut self,
+        entity: Entity,
+        pos: Vector2,
+        labels: impl IntoIterator<Item = &'a str>,
+    ) {
+        let labels = labels.into_iter().map(|l| self.intern(l)).collect();
+        let index = self.entries.len() as u32;
+        Arc::make_mut(&mut self.entries).push(SpatialEntry {
+            entity,
+            pos,
+            labels,
+        });
+        let cell = self.cell_of(pos.x, pos.y);
+        Arc::make_mut(&mut self.cells)
+            .entry(cell)
+            .or_default()
+            .push(index);
+    }
+
+    /// Resolve an optional label filter. `Ok(None)` means "any label";
+    /// `Err(())` means the label is unknown so nothing can match.
+    fn resolve_filter(&self, label: Option<&str>) -> Result<Option<u32>, ()> {
+        match label {
+            None => Ok(None),
+            Some(name) => self.label_id(name).map(Some).ok_or(()),
+        }
+    }
+
+    fn matches(entry: &SpatialEntry, filter: Option<u32>) -> bool {
+        filter.is_none_or(|id| entry.labels.contains(&id))
+    }
+
+    /// Call `f` for every entry in the cells spanning `(x0, y0)`–`(x1, y1)`.
+    ///
+    /// Falls back to scanning all entries when the span covers more cells
+    /// than are occupied, so huge query areas stay cheap.
+    fn visit_area(&self, x0: f32, y0: f32, x1: f32, y1: f32, mut f: impl FnMut(&SpatialEntry)) {
+        let (cx0, cy0) = self.cell_of(x0, y0);
+        let (cx1, cy1) = self.cell_of(x1, y1);
+        let span = (cx1 as i64 - cx0 as i64 + 1) * (cy1 as i64 - cy0 as i64 + 1);
+        if span > self.cells.len() as i64 {
+            self.entries.iter().for_each(f);
+            return;
+        }
+        for cy in cy0..=cy1 {
+            for cx in cx0..=cx1 {
+                if let Some(bucket) = self.cells.get(&(cx, cy)) {
+                    for &i in bucket {
+                        f(&self.entries[i as usize]);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Append to `out` every entity inside the rectangle `(x, y, w, h)`
+    /// (edges inclusive), optionally restricted to those carrying `label`.
+    pub fn query_rect(
+        &self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        label: Option<&str>,
+        out: &mut Vec<Entity>,
+    ) {
+        let Ok(filter) = self.resolve_filter(label) else {
+            return;
+        };
+        let (x0, x1) = (x.min(x + w), x.max(x + w));
+        let (y0, y1) = (y.min(y + h), y.max(y + h));
+        self.visit_area(x0, y0, x1, y1, |entry| {
+            let p = entry.pos;
+            if p.x >= x0 && p.x <= x1 && p.y >= y0 && p.y <= y1 && Self::matches(entry, filter) {
+                out.push(entry.entity);
+            }
+        });
+    }
+
+    /// The entity closest to `(x, y)` and its distance, optionally restricted
+    /// to those carrying `label` and no farther than `max_dist`.
+    ///
+    /// With `max_dist` only the cells overlapping that radius are visited;
+    /// without it every entry is scanned.
+    pub fn nearest(
+        &self,
+        x: f32,
+        y: f32,
+        label: Option<&str>,
+        max_dist: Option<f32>,
+    ) -> Option<(Entity, f32)> {
+        let filter = self.resolve_filter(label).ok()?;
+        let mut best: Option<(Entity, f32)> = None;
+        let consider = |entry: &SpatialEntry| {
+            if !Self::matches(entry, filter) {
+                return;
+            }
+            let d2 = (entry.pos.x - x).powi(2) + (entry.pos.y - y).powi(2);
+            if best.is_none_or(|(_, b)| d2 < b) {
+                best = Some((entry.entity, d2));
+            }
+        };
+        match max_dist {
+            Some(r) => {
+                let r = r.max(0.0);
+                self.visit_area(x - r, y - r, x + r, y + r, consider);
+                best.map(|(e, d2)| (e, d2.sqrt())).filter(|&(_, d)| d <= r)
+            }
+            None => {
+                self.entries.iter().for_each(consider);
+                best.map(|(e, d2)| (e, d2.sqrt()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32) -> Vector2 {
+        Vector2 { x, y }
+    }
+
+    fn sample() -> SpatialIndex {
+        let mut index = SpatialIndex::new(32.0);
+        index.insert(Entity::from_bits(1), v(10.0, 10.0), ["enemy"]);
+        index.insert(Entity::from_bits(2), v(100.0, 10.0), ["enemy", "flying"]);
+        index.insert(Entity::from_bits(3), v(-40.0, -40.0), ["pickup"]);
+        index
+    }
+
+    #[test]
+    fn query_rect_filters_by_label_and_bounds() {
+        let index = sample();
+        let mut out = Vec::new();
+        index.query_rect(0.0, 0.0, 200.0, 50.0, None, &mut out);
+        out.sort();
+        assert_eq!(out, vec![Entity::from_bits(1), Entity::from_bits(2)]);
+
+        out.clear();
+        index.query_rect(0.0, 0.0, 200.0, 50.0, Some("flying"), &mut out);
+        assert_eq!(out, vec![Entity::from_bits(2)]);
+
+        out.clear();
+        index.query_rect(-50.0, -50.0, 20.0, 20.0, Some("missing"), &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn query_rect_accepts_negative_size() {
+        let index = sample();
+        let mut out = Vec::new();
+        index.query_rect(-30.0, -30.0, -20.0, -20.0, None, &mut out);
+        assert_eq!(out, vec![Entity::from_bits(3)]);
+    }
+
+    #[test]
+    fn query_rect_huge_area_scans_all_entries() {
+        let index = sample();
+        let mut out = Vec::new();
+        index.query_rect(-1.0e9, -1.0e9, 2.0e9, 2.0e9, None, &mut out);
+        assert_eq!(out.len(), 3);
+    }
+
+    #[test]
+    fn nearest_respects_label_and_max_dist() {
+        let index = sample();
+        let (e, d) = index.nearest(90.0, 10.0, Some("enemy"), None).unwrap();
+        assert_eq!((e, d), (Entity::from_bits(2), 10.0));
+        assert_eq!(
+            index
+                .nearest(0.0, 0.0, Some("pickup"), Some(50.0))
+                .map(|n| n.0),
+            None
+        );
+        assert_eq!(
+            index
+                .nearest(0.0, 0.0, Some("pickup"), Some(60.0))
+                .map(|n| n.0),
+            Some(Entity::from_bits(3))
+        );
+    }
+
+    #[test]
+    fn clear_keeps_label_ids() {
+        let mut index = sample();
+        let id = index.label_id("enemy");
+        index.clear();
+        assert!(index.is_empty());
+        assert_eq!(index.label_id("enemy"), id);
+        let mut out = Vec::new();
+        index.query_rect(0.0, 0.0, 200.0, 50.0, None, &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn clone_keeps_its_entries_when_the_original_is_rebuilt() {
+        let mut index = sample();
+        let snapshot = index.clone();
+        index.clear();
+        index.insert(Entity::from_bits(9), v(500.0, 500.0), ["enemy"]);
+        assert_eq!(snapshot.len(), sample().len());
+        assert_eq!(index.len(), 1);
+        let (nearest, _) = snapshot.nearest(0.0, 0.0, Some("enemy"), None).unwrap();
+        assert_ne!(nearest, Entity::from_bits(9));
+    }
+}
@@ -0,0 +1,109 @@
+//! Registry of named spawn points.
+//!
+//! [`SpawnPoints`] maps the name of every
+//! [`SpawnPoint`](crate::components::portal::SpawnPoint) entity to its world
+//! position and properties. It is rebuilt by
+//! [`spawn_point_registry_system`](crate::systems::portal::spawn_point_registry_system)
+//! whenever a spawn point is added, moved or removed, so it only ever holds the
+//! points of the current scene. When two entities share a name, the one with
+//! the lowest entity index wins.
+//!
+//! Lua reads it with `engine.get_spawn_point(name)`, so scenes can place the
+//! player on `"player_start"` instead of hard-coding coordinates.
+
+use bevy_ecs::prelude::{Entity, Resource};
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+
+use crate::components::gridlayout::GridValue;
+
+/// A registered spawn point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPointInfo {
+    pub entity: Entity,
+    /// World position.
+    pub position: Vector2,
+    pub properties: FxHashMap<String, GridValue>,
+}
+
+/// Spawn point names to their entity, world position and properties.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SpawnPoints {
+    points: FxHashMap<String, SpawnPointInfo>,
+}
+
+impl SpawnPoints {
+    /// World position of the spawn point `name`.
+    pub fn get(&self, name: &str) -> Option<Vector2> {
+        self.points.get(name).map(|info| info.position)
+    }
+
+    /// Entity holding the spawn point `name`.
+    pub fn entity(&self, name: &str) -> Option<Entity> {
+        self.points.get(name).map(|info| info.entity)
+    }
+
+    /// Everything known about the spawn point `name`.
+    pub fn info(&self, name: &str) -> Option<&SpawnPointInfo> {
+        self.points.get(name)
+    }
+
+    /// Register `name`, keeping an existing entry with a lower entity index.
+    pub fn insert(&mut self, name: &str, info: SpawnPointInfo) {
+        match self.points.get_mut(name) {
+            Some(existing) if existing.entity.index() <= info.entity.index() => {}
+            Some(existing) => *existing = info,
+            None => {
+                self.points.insert(name.to_owned(), info);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Iterate `(name, world position)` pairs in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Vector2)> {
+        self.points
+            .iter()
+            .map(|(name, info)| (name.as_str(), info.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::world::World;
+
+    fn info(entity: Entity, x: f32) -> SpawnPointInfo {
+        SpawnPointInfo {
+            entity,
+            position: Vector2::new(x, x),
+            properties: FxHashMap::default(),
+        }
+    }
+
+    #[test]
+    fn duplicate_names_keep_the_lowest_entity() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let mut points = SpawnPoints::default();
+        points.insert("door", info(second, 2.0));
+        points.insert("door", info(first, 1.0));
+        points.insert("door", info(second, 3.0));
+        assert_eq!(points.entity("door"), Some(first));
+        assert_eq!(points.get("door"), Some(Vector2::new(1.0, 1.0)));
+        assert_eq!(points.len(), 1);
+        assert_eq!(points.get("window"), None);
+    }
+}
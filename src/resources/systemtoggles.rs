@@ -0,0 +1,132 @@
+//! Opt-out switches for non-essential systems.
+//!
+//! [`SystemToggles`] lets low-end machines, or scenes that are already busy,
+//! turn off systems that only add polish. Every system starts enabled.
+//!
+//! | Name | When disabled |
+//! |------|---------------|
+//! | `particles` | `particle_emitter_system` is skipped: no emitter spawns particles, weather included; live particles run out their TTL |
+//! | `weather` | weather transitions freeze, weather emitters stop and rain and snow are not drawn |
+//! | `color_grading` | the color LUT pass is left out of post-processing |
+//!
+//! Defaults come from the `[systems]` section of the config file
+//! (`particles = false`), stored in
+//! [`GameConfig::system_toggles`](crate::resources::gameconfig::GameConfig::system_toggles).
+//! Lua switches systems with `engine.disable_system(name)` and
+//! `engine.enable_system(name)`, which edit that config field;
+//! `apply_gameconfig_changes` copies it into this resource, and
+//! `engine.save_config()` persists it.
+//!
+//! The [`QualityGovernor`](crate::resources::qualitygovernor::QualityGovernor)
+//! switches systems off separately ([`SystemToggles::set_throttled`]), so
+//! restoring quality never re-enables a system the player turned off.
+
+use bevy_ecs::prelude::{Res, Resource};
+
+/// A system that can be switched off. See the module docs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToggleableSystem {
+    Particles,
+    Weather,
+    ColorGrading,
+}
+
+impl ToggleableSystem {
+    pub const ALL: [ToggleableSystem; 3] = [Self::Particles, Self::Weather, Self::ColorGrading];
+
+    /// Name used by Lua and the config file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Particles => "particles",
+            Self::Weather => "weather",
+            Self::ColorGrading => "color_grading",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|system| system.as_str() == name)
+    }
+
+    /// Comma-separated list of every name, for error messages.
+    pub fn names() -> String {
+        Self::ALL.map(Self::as_str).join(", ")
+    }
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Disabled systems, one bit each. See the module docs.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemToggles {
+    disabled: u32,
+    throttled: u32,
+}
+
+impl SystemToggles {
+    /// Whether `system` runs: neither disabled nor throttled.
+    pub fn is_enabled(&self, system: ToggleableSystem) -> bool {
+        (self.disabled | self.throttled) & system.bit() == 0
+    }
+
+    pub fn set_enabled(&mut self, system: ToggleableSystem, enabled: bool) {
+        if enabled {
+            self.disabled &= !system.bit();
+        } else {
+            self.disabled |= system.bit();
+        }
+    }
+
+    /// Switch `system` off (or back on) on behalf of the quality governor.
+    pub fn set_throttled(&mut self, system: ToggleableSystem, throttled: bool) {
+        if throttled {
+            self.throttled |= system.bit();
+        } else {
+            self.throttled &= !system.bit();
+        }
+    }
+
+    /// These toggles with the enabled/disabled settings of `settings`,
+    /// keeping the throttled systems.
+    pub fn with_settings_of(self, settings: SystemToggles) -> Self {
+        Self {
+            disabled: settings.disabled,
+            ..self
+        }
+    }
+}
+
+/// Run condition passing while `system` is enabled:
+/// `weather_system.run_if(system_enabled(ToggleableSystem::Weather))`.
+pub fn system_enabled(system: ToggleableSystem) -> impl FnMut(Res<SystemToggles>) -> bool + Clone {
+    move |toggles: Res<SystemToggles>| toggles.is_enabled(system)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn systems_start_enabled_and_toggle_by_name() {
+        let mut toggles = SystemToggles::default();
+        assert!(ToggleableSystem::ALL.iter().all(|&s| toggles.is_enabled(s)));
+
+        let weather = ToggleableSystem::parse("weather").unwrap();
+        toggles.set_enabled(weather, false);
+        assert!(!toggles.is_enabled(ToggleableSystem::Weather));
+        assert!(toggles.is_enabled(ToggleableSystem::Particles));
+        toggles.set_enabled(weather, true);
+        assert_eq!(toggles, SystemToggles::default());
+
+        toggles.set_throttled(ToggleableSystem::ColorGrading, true);
+        let toggles = toggles.with_settings_of(SystemToggles::default());
+        assert!(!toggles.is_enabled(ToggleableSystem::ColorGrading));
+
+        assert_eq!(ToggleableSystem::parse("trails"), None);
+        assert_eq!(
+            ToggleableSystem::names(),
+            "particles, weather, color_grading"
+        );
+    }
+}
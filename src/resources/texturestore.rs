@@ -3,6 +3,11 @@
 //! A thin wrapper around a hash map that stores `raylib::prelude::Texture2D`
 //! objects keyed by string IDs. Insert textures during setup and read them in
 //! render systems.
+//!
+//! Render systems look sprites up with [`TextureStore::get_or_fallback`], which
+//! substitutes the checkerboard stored under [`MISSING_TEXTURE_KEY`] for
+//! unknown keys (see [`missingassets`](crate::resources::missingassets)).
+use crate::resources::missingassets::MissLog;
 use crate::resources::texturefilter::TextureFilter;
 use bevy_ecs::prelude::Resource;
 use raylib::ffi;
@@ -11,6 +16,10 @@ use raylib::prelude::{Color, Font, Image, RaylibHandle, RaylibThread};
 use rustc_hash::FxHashMap;
 use std::ffi::CString;
 
+/// Key of the built-in fallback checkerboard generated by
+/// [`TextureStore::load_fallback`] and drawn for every missing texture.
+pub const MISSING_TEXTURE_KEY: &str = "__missing_texture";
+
 #[derive(Resource)]
 /// Map of texture keys to loaded textures.
 ///
@@ -23,10 +32,14 @@ use std::ffi::CString;
 /// The `filters` map stores the sampling filter each texture was last
 /// `insert()`ed with. Absence of an entry means [`TextureFilter::default`]
 /// (`Nearest`).
+///
+/// The texture [`get_or_fallback`](Self::get_or_fallback) returns for unknown
+/// keys lives in `map` under [`MISSING_TEXTURE_KEY`].
 pub struct TextureStore {
     pub map: FxHashMap<String, Texture2D>,
     pub paths: FxHashMap<String, String>,
     pub filters: FxHashMap<String, TextureFilter>,
+    misses: MissLog,
 }
 
 impl Default for TextureStore {
@@ -41,12 +54,56 @@ impl TextureStore {
             map: FxHashMap::default(),
             paths: FxHashMap::default(),
             filters: FxHashMap::default(),
+            misses: MissLog::default(),
         }
     }
     /// Get a texture by its key.
     pub fn get(&self, key: impl AsRef<str>) -> Option<&Texture2D> {
         self.map.get(key.as_ref())
     }
+    /// Get a texture by its key, or the fallback checkerboard if `key` is not
+    /// loaded. The miss is recorded for [`take_misses`](Self::take_misses).
+    ///
+    /// Returns `None` only when the key is missing and no fallback was loaded
+    /// (headless runs).
+    pub fn get_or_fallback(&self, key: impl AsRef<str>) -> Option<&Texture2D> {
+        self.get(key.as_ref())
+            .or_else(|| self.fallback_for(key.as_ref()))
+    }
+    /// Get a texture by its key, recording a miss like
+    /// [`get_or_fallback`](Self::get_or_fallback) but without substituting the
+    /// fallback. For textures sampled as data (color LUTs, palettes), where
+    /// the checkerboard would only garble the image.
+    pub fn get_or_report(&self, key: &str) -> Option<&Texture2D> {
+        let texture = self.get(key);
+        if texture.is_none() {
+            self.misses.record(key);
+        }
+        texture
+    }
+    /// Record `key` as missing and return the fallback texture.
+    ///
+    /// For callers that try other sources (e.g. secondary world views) before
+    /// giving up on a key.
+    pub fn fallback_for(&self, key: &str) -> Option<&Texture2D> {
+        self.misses.record(key);
+        self.map.get(MISSING_TEXTURE_KEY)
+    }
+    /// Keys substituted by the fallback since the last call.
+    pub fn take_misses(&self) -> Vec<String> {
+        self.misses.drain()
+    }
+    /// Generate the fallback texture: a 64×64 magenta/black checkerboard with
+    /// 8×8 pixel cells, stored under [`MISSING_TEXTURE_KEY`].
+    pub fn load_fallback(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {
+        let image = Image::gen_image_checked(64, 64, 8, 8, Color::MAGENTA, Color::BLACK);
+        match rl.load_texture_from_image(thread, &image) {
+            Ok(texture) => {
+                self.insert(MISSING_TEXTURE_KEY, texture, TextureFilter::default(), None)
+            }
+            Err(err) => log::warn!("Failed to create the fallback texture: {err}"),
+        }
+    }
     /// Sampling filter the texture at `key` was last inserted with, or
     /// [`TextureFilter::default`] (`Nearest`) if `key` is not tracked.
     pub fn filter(&self, key: impl AsRef<str>) -> TextureFilter {
@@ -99,21 +156,35 @@ impl TextureStore {
     }
 }
 
-/// Render text into a new [`Texture2D`] using the given font.
-pub fn load_texture_from_text(
-    rl: &mut RaylibHandle,
-    thread: &RaylibThread,
+/// Render text into a new [`Image`] using the given font.
+///
+/// Returns `None` if `text` contains a NUL byte.
+pub fn image_from_text(
     font: &Font,
     text: &str,
     font_size: f32,
     spacing: f32,
     color: Color,
-) -> Option<Texture2D> {
+) -> Option<Image> {
     let c_text = CString::new(text).ok()?;
     let image = unsafe {
         let raw = ffi::ImageTextEx(**font, c_text.as_ptr(), font_size, spacing, color.into());
         Image::from_raw(raw)
     };
+    Some(image)
+}
+
+/// Render text into a new [`Texture2D`] using the given font.
+pub fn load_texture_from_text(
+    rl: &mut RaylibHandle,
+    thread: &RaylibThread,
+    font: &Font,
+    text: &str,
+    font_size: f32,
+    spacing: f32,
+    color: Color,
+) -> Option<Texture2D> {
+    let image = image_from_text(font, text, font_size, spacing, color)?;
     let texture = rl.load_texture_from_image(thread, &image).ok()?;
     Some(texture)
 }
@@ -0,0 +1,256 @@
+//! Screen-wide weather: rain, snow and wind.
+//!
+//! [`Weather`] holds the intensity of each precipitation kind and the wind
+//! vector, each fading linearly towards its target so changes of weather blend
+//! in over a few seconds instead of popping. Requesting a kind fades it in and
+//! every other kind out over the same transition.
+//!
+//! [`weather_system`] turns the state into entities: a
+//! [`ParticleEmitter`](crate::components::particleemitter::ParticleEmitter) per
+//! kind kept just above the camera view, whose rate follows the intensity and
+//! whose angle leans with the wind, and a global `Wind`
+//! [`ForceArea`](crate::components::forcearea::ForceArea) pushing the rigid
+//! bodies matching `wind_mask` (the falling particles included). The particles
+//! are drawn by the render system as streaks (rain) or flakes (snow).
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut weather = Weather::default();
+//! weather.set_weather(WeatherKind::Rain, 0.7, 5.0);
+//! weather.set_wind(Vector2::new(-60.0, 0.0), 5.0);
+//! commands.insert_resource(weather);
+//! ```
+//!
+//! [`weather_system`]: crate::systems::weather::weather_system
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::{Color, Vector2};
+
+/// Kind of precipitation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Rain,
+    Snow,
+}
+
+impl WeatherKind {
+    /// Kinds that emit particles.
+    pub const PRECIPITATION: [WeatherKind; 2] = [WeatherKind::Rain, WeatherKind::Snow];
+
+    /// Parse `"clear"`, `"rain"` or `"snow"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "clear" => Some(Self::Clear),
+            "rain" => Some(Self::Rain),
+            "snow" => Some(Self::Snow),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Clear => "clear",
+            Self::Rain => "rain",
+            Self::Snow => "snow",
+        }
+    }
+}
+
+/// A value moving linearly towards a target over a fixed duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Fade {
+    /// Current value.
+    pub value: f32,
+    from: f32,
+    target: f32,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl Fade {
+    /// Start moving from the current value to `target` over `duration`
+    /// seconds; `0` jumps straight to it.
+    pub fn to(&mut self, target: f32, duration: f32) {
+        self.from = self.value;
+        self.target = target;
+        self.elapsed = 0.0;
+        self.duration = duration.max(0.0);
+        if self.duration == 0.0 {
+            self.value = target;
+        }
+    }
+
+    /// Value the fade is heading to.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Advance by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        if self.value == self.target {
+            return;
+        }
+        self.elapsed += dt;
+        if self.elapsed >= self.duration {
+            self.value = self.target;
+        } else {
+            let t = self.elapsed / self.duration;
+            self.value = self.from + (self.target - self.from) * t;
+        }
+    }
+}
+
+/// Precipitation and wind state. See the module docs.
+#[derive(Resource, Debug, Clone)]
+pub struct Weather {
+    kind: WeatherKind,
+    rain: Fade,
+    snow: Fade,
+    wind_x: Fade,
+    wind_y: Fade,
+    /// Group pattern of the rigid bodies pushed by the wind (`"*"` by default).
+    pub wind_mask: String,
+    /// Rain drops per second at full intensity.
+    pub rain_rate: f32,
+    /// Snow flakes per second at full intensity.
+    pub snow_rate: f32,
+    /// Colour of the rain streaks.
+    pub rain_color: Color,
+    /// Colour of the snow flakes.
+    pub snow_color: Color,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            kind: WeatherKind::Clear,
+            rain: Fade::default(),
+            snow: Fade::default(),
+            wind_x: Fade::default(),
+            wind_y: Fade::default(),
+            wind_mask: "*".into(),
+            rain_rate: 240.0,
+            snow_rate: 60.0,
+            rain_color: Color::new(170, 190, 230, 180),
+            snow_color: Color::new(245, 245, 255, 230),
+        }
+    }
+}
+
+impl Weather {
+    /// Fade `kind` to `intensity` (0 to 1) and every other kind out over
+    /// `transition` seconds. `Clear` fades everything out.
+    pub fn set_weather(&mut self, kind: WeatherKind, intensity: f32, transition: f32) {
+        let intensity = intensity.clamp(0.0, 1.0);
+        self.kind = kind;
+        for other in WeatherKind::PRECIPITATION {
+            let target = if other == kind { intensity } else { 0.0 };
+            if let Some(fade) = self.fade_mut(other) {
+                fade.to(target, transition);
+            }
+        }
+    }
+
+    /// Fade the wind to `wind` (acceleration in units/s²) over `transition`
+    /// seconds.
+    pub fn set_wind(&mut self, wind: Vector2, transition: f32) {
+        self.wind_x.to(wind.x, transition);
+        self.wind_y.to(wind.y, transition);
+    }
+
+    /// Last requested kind of weather.
+    pub fn kind(&self) -> WeatherKind {
+        self.kind
+    }
+
+    /// Current intensity of `kind`, from 0 to 1. `Clear` is always 0.
+    pub fn intensity(&self, kind: WeatherKind) -> f32 {
+        self.fade(kind).map_or(0.0, |fade| fade.value)
+    }
+
+    /// Intensity the requested kind is heading to.
+    pub fn target_intensity(&self) -> f32 {
+        self.fade(self.kind).map_or(0.0, Fade::target)
+    }
+
+    /// Current wind acceleration.
+    pub fn wind(&self) -> Vector2 {
+        Vector2::new(self.wind_x.value, self.wind_y.value)
+    }
+
+    /// Advance every transition by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        for fade in [
+            &mut self.rain,
+            &mut self.snow,
+            &mut self.wind_x,
+            &mut self.wind_y,
+        ] {
+            fade.step(dt);
+        }
+    }
+
+    fn fade(&self, kind: WeatherKind) -> Option<&Fade> {
+        match kind {
+            WeatherKind::Clear => None,
+            WeatherKind::Rain => Some(&self.rain),
+            WeatherKind::Snow => Some(&self.snow),
+        }
+    }
+
+    fn fade_mut(&mut self, kind: WeatherKind) -> Option<&mut Fade> {
+        match kind {
+            WeatherKind::Clear => None,
+            WeatherKind::Rain => Some(&mut self.rain),
+            WeatherKind::Snow => Some(&mut self.snow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kinds() {
+        assert_eq!(WeatherKind::parse("rain"), Some(WeatherKind::Rain));
+        assert_eq!(
+            WeatherKind::parse("snow").map(WeatherKind::as_str),
+            Some("snow")
+        );
+        assert_eq!(WeatherKind::parse("hail"), None);
+    }
+
+    #[test]
+    fn switching_kinds_crossfades() {
+        let mut weather = Weather::default();
+        weather.set_weather(WeatherKind::Rain, 0.8, 0.0);
+        assert_eq!(weather.intensity(WeatherKind::Rain), 0.8);
+
+        weather.set_weather(WeatherKind::Snow, 0.5, 4.0);
+        weather.step(1.0);
+        assert!((weather.intensity(WeatherKind::Rain) - 0.6).abs() < 1e-5);
+        assert!((weather.intensity(WeatherKind::Snow) - 0.125).abs() < 1e-5);
+        weather.step(5.0);
+        assert_eq!(weather.intensity(WeatherKind::Rain), 0.0);
+        assert_eq!(weather.intensity(WeatherKind::Snow), 0.5);
+        assert_eq!(weather.target_intensity(), 0.5);
+
+        weather.set_weather(WeatherKind::Clear, 1.0, 0.0);
+        assert_eq!(weather.intensity(WeatherKind::Snow), 0.0);
+        assert_eq!(weather.target_intensity(), 0.0);
+    }
+
+    #[test]
+    fn wind_fades_to_its_target() {
+        let mut weather = Weather::default();
+        weather.set_wind(Vector2::new(-100.0, 20.0), 2.0);
+        weather.step(1.0);
+        assert_eq!(weather.wind(), Vector2::new(-50.0, 10.0));
+        weather.step(1.0);
+        assert_eq!(weather.wind(), Vector2::new(-100.0, 20.0));
+    }
+}
@@ -0,0 +1,267 @@
+//! In-game time of day.
+//!
+//! [`WorldClock`] turns [`WorldTime`](crate::resources::worldtime::WorldTime)
+//! seconds into a 24-hour clock (`speed` in-game minutes per second) and keeps
+//! a daily schedule of named events, fired as
+//! [`WorldClockEvent`](crate::events::worldclock::WorldClockEvent)s each time
+//! the clock passes their time of day. With `ambient` set, the render system
+//! darkens the world with `night_color`, faded in and out around `sunrise`
+//! and `sunset`.
+//!
+//! The clock is advanced by
+//! [`world_clock_system`](crate::systems::worldclock::world_clock_system); it
+//! stops while `paused` is set or the world time scale is zero.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut clock = WorldClock::default();
+//! clock.set_time(parse_clock_time("19:30").unwrap());
+//! clock.schedule(parse_clock_time("20:00").unwrap(), "spawn_night_enemies");
+//! clock.ambient = true;
+//! commands.insert_resource(clock);
+//! ```
+
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Resource;
+use raylib::prelude::Color;
+
+/// Minutes in an in-game day.
+pub const MINUTES_PER_DAY: f32 = 1440.0;
+
+/// Parse an `"HH:MM"` time of day into minutes since midnight.
+pub fn parse_clock_time(text: &str) -> Option<f32> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then(|| (hours * 60 + minutes) as f32)
+}
+
+/// Format minutes since midnight as `"HH:MM"`.
+pub fn format_clock_time(minutes: f32) -> String {
+    let total = minutes.rem_euclid(MINUTES_PER_DAY) as u32;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+/// A named event fired every day at `minute`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockSchedule {
+    /// Minutes since midnight.
+    pub minute: f32,
+    /// Event name; Lua calls the global function with this name.
+    pub name: Arc<str>,
+}
+
+/// A scheduled event the clock passed, returned by [`WorldClock::advance`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiredClockEvent {
+    pub name: Arc<str>,
+    /// Day the event fired on (0 is the first day).
+    pub day: u32,
+    /// Scheduled minutes since midnight.
+    pub minute: f32,
+}
+
+/// Time of day, day counter and daily schedule. See the module docs.
+#[derive(Resource, Debug, Clone)]
+pub struct WorldClock {
+    /// Minutes since midnight, in `[0, 1440)`.
+    pub minutes: f32,
+    /// Days elapsed since the clock started.
+    pub day: u32,
+    /// In-game minutes per second of world time.
+    pub speed: f32,
+    /// Stops the clock and its schedule.
+    pub paused: bool,
+    /// Middle of the dawn fade, in minutes since midnight.
+    pub sunrise: f32,
+    /// Middle of the dusk fade, in minutes since midnight.
+    pub sunset: f32,
+    /// Length of the dawn and dusk fades, in minutes.
+    pub twilight: f32,
+    /// Overlay drawn over the world at night; its alpha is the darkest shade.
+    pub night_color: Color,
+    /// Draw the day/night overlay.
+    pub ambient: bool,
+    schedule: Vec<ClockSchedule>,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            minutes: 8.0 * 60.0,
+            day: 0,
+            speed: 1.0,
+            paused: false,
+            sunrise: 6.0 * 60.0,
+            sunset: 20.0 * 60.0,
+            twilight: 60.0,
+            night_color: Color::new(10, 10, 40, 170),
+            ambient: false,
+            schedule: Vec::new(),
+        }
+    }
+}
+
+impl WorldClock {
+    /// Jump to `minutes` since midnight without firing the events in between.
+    pub fn set_time(&mut self, minutes: f32) {
+        self.minutes = minutes.rem_euclid(MINUTES_PER_DAY);
+    }
+
+    /// Whole hour and minute of the current time.
+    pub fn hour_minute(&self) -> (u32, u32) {
+        let total = self.minutes as u32;
+        (total / 60, total % 60)
+    }
+
+    /// Fire an event called `name` every day at `minute` since midnight.
+    pub fn schedule(&mut self, minute: f32, name: impl Into<Arc<str>>) {
+        let minute = minute.rem_euclid(MINUTES_PER_DAY);
+        let at = self.schedule.partition_point(|s| s.minute <= minute);
+        self.schedule.insert(
+            at,
+            ClockSchedule {
+                minute,
+                name: name.into(),
+            },
+        );
+    }
+
+    /// Remove every scheduled event called `name`; returns how many were removed.
+    pub fn cancel(&mut self, name: &str) -> usize {
+        let before = self.schedule.len();
+        self.schedule.retain(|s| &*s.name != name);
+        before - self.schedule.len()
+    }
+
+    /// Scheduled events, sorted by time of day.
+    pub fn scheduled(&self) -> &[ClockSchedule] {
+        &self.schedule
+    }
+
+    /// Advance the clock by `seconds` of world time and return the scheduled
+    /// events passed on the way, in order.
+    ///
+    /// An event fires when the clock moves from at or before its time to past
+    /// it, so one scheduled at the current time fires on the next advance.
+    pub fn advance(&mut self, seconds: f32) -> Vec<FiredClockEvent> {
+        let mut fired = Vec::new();
+        if self.paused || self.speed <= 0.0 || seconds <= 0.0 {
+            return fired;
+        }
+        let mut remaining = seconds * self.speed;
+        while remaining > 0.0 {
+            let start = self.minutes;
+            let end = (start + remaining).min(MINUTES_PER_DAY);
+            fired.extend(
+                self.schedule
+                    .iter()
+                    .filter(|s| s.minute >= start && s.minute < end)
+                    .map(|s| FiredClockEvent {
+                        name: s.name.clone(),
+                        day: self.day,
+                        minute: s.minute,
+                    }),
+            );
+            remaining -= end - start;
+            if end >= MINUTES_PER_DAY {
+                self.minutes = 0.0;
+                self.day += 1;
+            } else {
+                self.minutes = end;
+            }
+        }
+        fired
+    }
+
+    /// Amount of daylight, from 0 (night) to 1 (day), fading linearly over
+    /// `twilight` minutes centred on sunrise and sunset.
+    pub fn daylight(&self) -> f32 {
+        let twilight = self.twilight.max(f32::EPSILON);
+        let ramp = |edge: f32| ((self.minutes - edge) / twilight + 0.5).clamp(0.0, 1.0);
+        if self.sunrise <= self.sunset {
+            (ramp(self.sunrise) - ramp(self.sunset)).clamp(0.0, 1.0)
+        } else {
+            // Daytime across midnight, e.g. sunrise 22:00 and sunset 04:00.
+            (1.0 - ramp(self.sunset) + ramp(self.sunrise)).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Overlay to draw over the world now, or `None` in full daylight or
+    /// when `ambient` is off.
+    pub fn ambient_color(&self) -> Option<Color> {
+        if !self.ambient {
+            return None;
+        }
+        let alpha = (self.night_color.a as f32 * (1.0 - self.daylight())).round() as u8;
+        (alpha > 0).then_some(Color {
+            a: alpha,
+            ..self.night_color
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_times() {
+        assert_eq!(parse_clock_time("20:00"), Some(1200.0));
+        assert_eq!(parse_clock_time(" 6:05"), Some(365.0));
+        assert_eq!(parse_clock_time("24:00"), None);
+        assert_eq!(parse_clock_time("12:60"), None);
+        assert_eq!(parse_clock_time("noon"), None);
+        assert_eq!(format_clock_time(365.0), "06:05");
+        assert_eq!(format_clock_time(1440.0 + 61.0), "01:01");
+    }
+
+    #[test]
+    fn advance_fires_events_in_order_across_midnight() {
+        let mut clock = WorldClock::default();
+        clock.set_time(23.0 * 60.0);
+        clock.speed = 60.0;
+        clock.schedule(60.0, "late");
+        clock.schedule(23.5 * 60.0, "night");
+        clock.schedule(0.0, "midnight");
+
+        let fired = clock.advance(2.0);
+        let names: Vec<&str> = fired.iter().map(|f| &*f.name).collect();
+        assert_eq!(names, ["night", "midnight"]);
+        assert_eq!((fired[0].day, fired[1].day), (0, 1));
+        assert_eq!((clock.day, clock.hour_minute()), (1, (1, 0)));
+
+        // "late" is due now and fires on the next advance.
+        assert_eq!(clock.advance(0.01)[0].name.as_ref(), "late");
+        assert_eq!(clock.cancel("night"), 1);
+        assert_eq!(clock.scheduled().len(), 2);
+
+        clock.paused = true;
+        assert!(clock.advance(100.0).is_empty());
+        assert_eq!(clock.hour_minute(), (1, 0));
+    }
+
+    #[test]
+    fn ambient_fades_around_sunrise_and_sunset() {
+        let mut clock = WorldClock {
+            ambient: true,
+            ..WorldClock::default()
+        };
+        clock.set_time(12.0 * 60.0);
+        assert_eq!(clock.daylight(), 1.0);
+        assert_eq!(clock.ambient_color(), None);
+
+        clock.set_time(clock.sunrise);
+        assert_eq!(clock.daylight(), 0.5);
+        assert_eq!(clock.ambient_color().map(|c| c.a), Some(85));
+
+        clock.set_time(23.0 * 60.0);
+        assert_eq!(clock.daylight(), 0.0);
+        assert_eq!(clock.ambient_color(), Some(clock.night_color));
+
+        clock.ambient = false;
+        assert_eq!(clock.ambient_color(), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Secondary simulation worlds ("sandboxes").
+//!
+//! A [`SubWorld`] is an independent ECS [`World`] with its own entities,
+//! resources and a reduced [`Schedule`] (movement, tweens, TTL and transform
+//! propagation). Sub-worlds are stepped once per frame by
+//! [`update_sub_worlds_system`](crate::systems::worlds::update_sub_worlds_system)
+//! and drawn into their own render texture by
+//! [`render_sub_worlds_system`](crate::systems::render::worlds::render_sub_worlds_system).
+//! A sprite in the main world whose `tex_key` is the sub-world's name shows
+//! that texture, which is how live menu backgrounds and dioramas are built.
+//!
+//! Entities in a sub-world never see the main world: no collisions, Lua
+//! phases, audio or input run there. Rust code can add systems to
+//! [`SubWorld::schedule`] (e.g. an AI training loop) and access
+//! [`SubWorld::world`] directly.
+
+use bevy_ecs::message::Messages;
+use bevy_ecs::prelude::*;
+use bevy_ecs::world::CommandQueue;
+use raylib::prelude::{Camera2D, Color, Vector2};
+use rustc_hash::FxHashMap;
+
+use crate::components::mapposition::MapPosition;
+use crate::components::rotation::Rotation;
+use crate::components::scale::Scale;
+use crate::events::audio::AudioCmd;
+use crate::resources::screensize::ScreenSize;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+use crate::systems::movement::movement;
+use crate::systems::propagate_transforms::propagate_transforms;
+use crate::systems::time::update_world_time;
+use crate::systems::ttl::ttl_system;
+use crate::systems::tween::tween_system;
+
+/// An independent world simulated alongside the main one.
+pub struct SubWorld {
+    /// The sub-world's entities and resources.
+    pub world: World,
+    /// Systems run by [`step`](Self::step).
+    pub schedule: Schedule,
+    /// Render texture width in pixels.
+    pub width: u32,
+    /// Render texture height in pixels.
+    pub height: u32,
+    /// Camera used when drawing the sub-world; centered on the origin by default.
+    pub camera: Camera2D,
+    /// Clear color of the render texture.
+    pub background: Color,
+    /// When `true`, [`step`](Self::step) does nothing (rendering continues).
+    pub paused: bool,
+}
+
+impl SubWorld {
+    /// Create an empty sub-world rendered at `width`×`height` pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut world = World::new();
+        world.insert_resource(WorldTime::default());
+        world.insert_resource(WorldSignals::default());
+        world.insert_resource(ScreenSize {
+            w: width as i32,
+            h: height as i32,
+        });
+        world.init_resource::<Messages<AudioCmd>>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            tween_system::<MapPosition>,
+            tween_system::<Rotation>,
+            tween_system::<Scale>,
+        ));
+        schedule.add_systems(movement);
+        schedule.add_systems(ttl_system.after(movement));
+        schedule.add_systems(
+            propagate_transforms
+                .after(movement)
+                .after(tween_system::<MapPosition>)
+                .after(tween_system::<Rotation>)
+                .after(tween_system::<Scale>),
+        );
+
+        Self {
+            world,
+            schedule,
+            width,
+            height,
+            camera: Camera2D {
+                target: Vector2 { x: 0.0, y: 0.0 },
+                offset: Vector2 {
+                    x: width as f32 * 0.5,
+                    y: height as f32 * 0.5,
+                },
+                rotation: 0.0,
+                zoom: 1.0,
+            },
+            background: Color::BLACK,
+            paused: false,
+        }
+    }
+
+    /// Advance the sub-world by `dt` seconds unless it is paused.
+    pub fn step(&mut self, dt: f32) {
+        if self.paused {
+            return;
+        }
+        update_world_time(&mut self.world, dt);
+        self.schedule.run(&mut self.world);
+        self.world.clear_trackers();
+    }
+
+    /// Run `f` with [`Commands`] and the sub-world's [`WorldSignals`], then
+    /// apply the queued commands. Used to spawn entities into the sub-world
+    /// with the same code paths as the main world.
+    pub fn with_commands<R>(&mut self, f: impl FnOnce(&mut Commands, &mut WorldSignals) -> R) -> R {
+        let mut queue = CommandQueue::default();
+        let result = self
+            .world
+            .resource_scope(|world, mut signals: Mut<WorldSignals>| {
+                let mut commands = Commands::new(&mut queue, world);
+                f(&mut commands, &mut signals)
+            });
+        queue.apply(&mut self.world);
+        result
+    }
+
+    /// Center the camera on `(x, y)` with the given zoom.
+    pub fn set_camera(&mut self, x: f32, y: f32, zoom: f32) {
+        self.camera.target = Vector2 { x, y };
+        self.camera.zoom = zoom;
+    }
+}
+
+/// All secondary worlds, keyed by name.
+///
+/// The name doubles as the texture key under which the sub-world's render
+/// texture can be drawn by main-world sprites.
+#[derive(Resource, Default)]
+pub struct Worlds {
+    worlds: FxHashMap<String, SubWorld>,
+}
+
+impl Worlds {
+    /// Create (or replace) the sub-world `name`. Returns it for further setup.
+    pub fn create(&mut self, name: impl Into<String>, width: u32, height: u32) -> &mut SubWorld {
+        let name = name.into();
+        self.worlds
+            .insert(name.clone(), SubWorld::new(width, height));
+        self.worlds.get_mut(&name).expect("just inserted")
+    }
+
+    /// Remove the sub-world `name`, dropping all its entities.
+    pub fn remove(&mut self, name: &str) -> Option<SubWorld> {
+        self.worlds.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SubWorld> {
+        self.worlds.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut SubWorld> {
+        self.worlds.get_mut(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.worlds.contains_key(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SubWorld)> {
+        self.worlds.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut SubWorld)> {
+        self.worlds.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.worlds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.worlds.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::rigidbody::RigidBody;
+    use crate::components::ttl::Ttl;
+
+    #[test]
+    fn step_moves_entities_in_the_sub_world_only() {
+        let mut worlds = Worlds::default();
+        let sub = worlds.create("menu_bg", 320, 180);
+        let mut body = RigidBody::new();
+        body.set_velocity(Vector2 { x: 10.0, y: 0.0 });
+        let e = sub.world.spawn((MapPosition::new(0.0, 0.0), body)).id();
+
+        sub.step(0.5);
+        sub.step(0.5);
+
+        let pos = sub.world.get::<MapPosition>(e).unwrap().pos;
+        assert!((pos.x - 10.0).abs() < 1e-4, "x = {}", pos.x);
+        assert_eq!(sub.world.resource::<WorldTime>().frame_count, 2);
+    }
+
+    #[test]
+    fn paused_sub_world_does_not_advance() {
+        let mut sub = SubWorld::new(64, 64);
+        let e = sub.world.spawn(Ttl::new(0.1)).id();
+        sub.paused = true;
+        sub.step(1.0);
+        assert!(sub.world.get_entity(e).is_ok());
+
+        sub.paused = false;
+        sub.step(1.0);
+        assert!(sub.world.get_entity(e).is_err());
+    }
+
+    #[test]
+    fn with_commands_spawns_into_the_sub_world() {
+        let mut sub = SubWorld::new(64, 64);
+        let e = sub.with_commands(|commands, signals| {
+            let e = commands.spawn(MapPosition::new(1.0, 2.0)).id();
+            signals.set_entity("diorama_hero", e);
+            e
+        });
+        assert!(sub.world.get::<MapPosition>(e).is_some());
+        assert_eq!(
+            sub.world
+                .resource::<WorldSignals>()
+                .get_entity("diorama_hero"),
+            Some(&e)
+        );
+    }
+
+    #[test]
+    fn create_replaces_and_remove_drops() {
+        let mut worlds = Worlds::default();
+        worlds.create("a", 10, 10).world.spawn_empty();
+        worlds.create("a", 20, 20);
+        assert_eq!(worlds.len(), 1);
+        let sub = worlds.get("a").unwrap();
+        assert_eq!((sub.width, sub.height), (20, 20));
+        assert_eq!(sub.world.entities().count_spawned(), 0);
+
+        assert!(worlds.remove("a").is_some());
+        assert!(worlds.is_empty());
+    }
+}
@@ -34,6 +34,7 @@
 //! }
 //! ```
 
+use crate::key::Key;
 use crate::resources::signal_keys as sk;
 use bevy_ecs::prelude::{Entity, Resource};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -51,23 +52,26 @@ use std::sync::Arc;
 #[derive(Debug, Clone, Default)]
 pub struct SignalSnapshot {
     /// Floating-point numeric signals.
-    pub scalars: Arc<FxHashMap<String, f32>>,
+    pub scalars: Arc<FxHashMap<Key, f32>>,
     /// Integer numeric signals.
-    pub integers: Arc<FxHashMap<String, i32>>,
+    pub integers: Arc<FxHashMap<Key, i32>>,
     /// String signals.
-    pub strings: Arc<FxHashMap<String, String>>,
+    pub strings: Arc<FxHashMap<Key, String>>,
     /// Presence-only boolean flags.
-    pub flags: Arc<FxHashSet<String>>,
+    pub flags: Arc<FxHashSet<Key>>,
     /// Group entity counts (derived from integers with "group_count:" prefix).
-    pub group_counts: Arc<FxHashMap<String, u32>>,
+    pub group_counts: Arc<FxHashMap<Key, u32>>,
     /// Entity IDs as u64 (from Entity::to_bits()).
     pub entities: Arc<FxHashMap<String, u64>>,
+    /// [`WorldSignals::generation`] the snapshot was built at.
+    pub generation: u64,
 }
 
 /// Global signal storage for cross-system communication.
 ///
 /// Provides maps for scalars, integers, strings, and flags accessible from
-/// any system without entity queries.
+/// any system without entity queries. Signal keys are interned [`Key`]s, so
+/// setting a signal every frame does not allocate its key.
 ///
 /// # Snapshot System
 ///
@@ -78,26 +82,28 @@ pub struct SignalSnapshot {
 #[derive(Debug, Clone, Resource)]
 pub struct WorldSignals {
     /// Floating-point numeric signals addressed by string keys.
-    pub scalars: FxHashMap<String, f32>,
+    pub scalars: FxHashMap<Key, f32>,
     /// Integer numeric signals addressed by string keys.
-    pub integers: FxHashMap<String, i32>,
+    pub integers: FxHashMap<Key, i32>,
     /// String signals addressed by string keys.
-    pub strings: FxHashMap<String, String>,
+    pub strings: FxHashMap<Key, String>,
     /// Presence-only boolean flags; a key being present means "true".
-    pub flags: FxHashSet<String>,
+    pub flags: FxHashSet<Key>,
     /// Map of entities of interest for the current game state.
     pub entities: FxHashMap<String, Entity>,
     /// Group counts maintained in parallel with the `"group_count:"` integer entries.
-    group_counts: FxHashMap<String, u32>,
+    group_counts: FxHashMap<Key, u32>,
 
     /// Per-domain cached Arcs for the snapshot.
-    scalars_arc: Arc<FxHashMap<String, f32>>,
-    integers_arc: Arc<FxHashMap<String, i32>>,
-    strings_arc: Arc<FxHashMap<String, String>>,
-    flags_arc: Arc<FxHashSet<String>>,
-    group_counts_arc: Arc<FxHashMap<String, u32>>,
+    scalars_arc: Arc<FxHashMap<Key, f32>>,
+    integers_arc: Arc<FxHashMap<Key, i32>>,
+    strings_arc: Arc<FxHashMap<Key, String>>,
+    flags_arc: Arc<FxHashSet<Key>>,
+    group_counts_arc: Arc<FxHashMap<Key, u32>>,
     entities_arc: Arc<FxHashMap<String, u64>>,
 
+    /// Bumped on every change to a signal; see [`generation`](Self::generation).
+    generation: u64,
     /// Per-domain dirty bits; set when the corresponding live map changes.
     scalars_dirty: bool,
     integers_dirty: bool,
@@ -127,6 +133,7 @@ impl Default for WorldSignals {
             group_counts_arc: Arc::new(FxHashMap::default()),
             entities_arc: Arc::new(FxHashMap::default()),
 
+            generation: 0,
             scalars_dirty: false,
             integers_dirty: false,
             strings_dirty: false,
@@ -140,35 +147,46 @@ impl Default for WorldSignals {
 }
 impl WorldSignals {
     /// Set a floating-point signal value.
-    pub fn set_scalar(&mut self, key: impl Into<String>, value: f32) {
-        self.scalars.insert(key.into(), value);
+    ///
+    /// Writing the value a key already holds is a no-op and leaves the
+    /// snapshot clean.
+    pub fn set_scalar(&mut self, key: impl Into<Key>, value: f32) {
+        let key = key.into();
+        if self.scalars.get(&key) == Some(&value) {
+            return;
+        }
+        self.scalars.insert(key, value);
         self.scalars_dirty = true;
+        self.generation += 1;
     }
     /// Get a floating-point signal by key.
     pub fn get_scalar(&self, key: &str) -> Option<f32> {
         self.scalars.get(key).copied()
     }
     /// Read-only view of all scalar signals.
-    pub fn get_scalars(&self) -> &FxHashMap<String, f32> {
+    pub fn get_scalars(&self) -> &FxHashMap<Key, f32> {
         &self.scalars
     }
-    /// Set an integer signal value.
-    pub fn set_integer(&mut self, key: impl Into<String>, value: i32) {
-        let key = key.into();
-        if let Some(group_name) = key.strip_prefix(sk::GROUP_COUNT_PREFIX) {
-            self.group_counts
-                .insert(group_name.to_string(), value as u32);
+    /// Set an integer signal value. A no-op if the key already holds it.
+    pub fn set_integer(&mut self, key: impl Into<Key>, value: i32) {
+        let key: Key = key.into();
+        if self.integers.get(&key) == Some(&value) {
+            return;
+        }
+        if let Some(group_name) = key.as_str().strip_prefix(sk::GROUP_COUNT_PREFIX) {
+            self.group_counts.insert(Key::new(group_name), value as u32);
             self.group_counts_dirty = true;
         }
         self.integers.insert(key, value);
         self.integers_dirty = true;
+        self.generation += 1;
     }
     /// Get an integer signal by key.
     pub fn get_integer(&self, key: &str) -> Option<i32> {
         self.integers.get(key).copied()
     }
     /// Read-only view of all integer signals.
-    pub fn get_integers(&self) -> &FxHashMap<String, i32> {
+    pub fn get_integers(&self) -> &FxHashMap<Key, i32> {
         &self.integers
     }
     /// Get a group count by group name. Returns `None` if not tracked.
@@ -190,29 +208,30 @@ impl WorldSignals {
         let _ = write!(buf, "{}{}", sk::GROUP_COUNT_PREFIX, group_name);
         let current = self.integers.get(buf.as_str()).copied();
         if current != Some(count) {
-            self.integers.insert(buf.to_string(), count);
-            self.group_counts
-                .insert(group_name.to_string(), count as u32);
+            self.integers.insert(Key::new(buf.as_str()), count);
+            self.group_counts.insert(Key::new(group_name), count as u32);
             self.integers_dirty = true;
             self.group_counts_dirty = true;
+            self.generation += 1;
         }
     }
     /// Remove all integer signals whose keys start with a given prefix.
     pub fn clear_integer_prefix(&mut self, prefix: &str) {
         // Collect group names to remove before mutating integers (borrow-checker split).
         // Only the suffix strings are collected, not the full keys.
-        let group_names: Vec<String> = self
+        let group_names: Vec<&'static str> = self
             .integers
             .keys()
             .filter(|k| k.starts_with(prefix))
-            .filter_map(|k| k.strip_prefix(sk::GROUP_COUNT_PREFIX).map(str::to_string))
+            .filter_map(|k| k.as_str().strip_prefix(sk::GROUP_COUNT_PREFIX))
             .collect();
         let before = self.integers.len();
         self.integers.retain(|k, _| !k.starts_with(prefix));
         if self.integers.len() != before {
             self.integers_dirty = true;
+            self.generation += 1;
             for name in &group_names {
-                self.group_counts.remove(name.as_str());
+                self.group_counts.remove(*name);
             }
             if !group_names.is_empty() {
                 self.group_counts_dirty = true;
@@ -223,10 +242,16 @@ impl WorldSignals {
     pub fn clear_group_counts(&mut self) {
         self.clear_integer_prefix(sk::GROUP_COUNT_PREFIX);
     }
-    /// Set a string signal value.
-    pub fn set_string(&mut self, key: impl Into<String>, value: impl Into<String>) {
-        self.strings.insert(key.into(), value.into());
+    /// Set a string signal value. A no-op if the key already holds it.
+    pub fn set_string(&mut self, key: impl Into<Key>, value: impl Into<String>) {
+        let key = key.into();
+        let value = value.into();
+        if self.strings.get(&key) == Some(&value) {
+            return;
+        }
+        self.strings.insert(key, value);
         self.strings_dirty = true;
+        self.generation += 1;
     }
     /// Get a string signal by key.
     /// It's recommended to clone the String if you need ownership.
@@ -238,6 +263,7 @@ impl WorldSignals {
         let result = self.strings.remove(key);
         if result.is_some() {
             self.strings_dirty = true;
+            self.generation += 1;
         }
         result
     }
@@ -246,6 +272,7 @@ impl WorldSignals {
         let result = self.scalars.remove(key);
         if result.is_some() {
             self.scalars_dirty = true;
+            self.generation += 1;
         }
         result
     }
@@ -254,6 +281,7 @@ impl WorldSignals {
         let result = self.integers.remove(key);
         if result.is_some() {
             self.integers_dirty = true;
+            self.generation += 1;
             if let Some(group_name) = key.strip_prefix(sk::GROUP_COUNT_PREFIX) {
                 self.group_counts.remove(group_name);
                 self.group_counts_dirty = true;
@@ -262,14 +290,17 @@ impl WorldSignals {
         result
     }
     /// Mark a flag as present/true.
-    pub fn set_flag(&mut self, key: impl Into<String>) {
-        self.flags.insert(key.into());
-        self.flags_dirty = true;
+    pub fn set_flag(&mut self, key: impl Into<Key>) {
+        if self.flags.insert(key.into()) {
+            self.flags_dirty = true;
+            self.generation += 1;
+        }
     }
     /// Remove a flag (make it false/absent).
     pub fn clear_flag(&mut self, key: &str) {
         if self.flags.remove(key) {
             self.flags_dirty = true;
+            self.generation += 1;
         }
     }
     /// Check whether a flag is present/true.
@@ -282,6 +313,7 @@ impl WorldSignals {
     pub fn take_flag(&mut self, key: &str) -> bool {
         if self.flags.remove(key) {
             self.flags_dirty = true;
+            self.generation += 1;
             true
         } else {
             false
@@ -292,32 +324,39 @@ impl WorldSignals {
     /// Always marks the snapshot dirty since the state always changes.
     pub fn toggle_flag(&mut self, key: &str) {
         if !self.flags.remove(key) {
-            self.flags.insert(key.to_string());
+            self.flags.insert(Key::new(key));
         }
         self.flags_dirty = true;
+        self.generation += 1;
     }
     /// Read-only view of all flags.
-    pub fn get_flags(&self) -> &FxHashSet<String> {
+    pub fn get_flags(&self) -> &FxHashSet<Key> {
         &self.flags
     }
     /// Read-only view of all string signals.
-    pub fn get_strings(&self) -> &FxHashMap<String, String> {
+    pub fn get_strings(&self) -> &FxHashMap<Key, String> {
         &self.strings
     }
     /// Get an entity by key.
     pub fn get_entity(&self, key: &str) -> Option<&Entity> {
         self.entities.get(key)
     }
-    /// Set an entity by key.
+    /// Set an entity by key. A no-op if the key already points at `entity`.
     pub fn set_entity(&mut self, key: impl Into<String>, entity: Entity) {
-        self.entities.insert(key.into(), entity);
+        let key = key.into();
+        if self.entities.get(&key) == Some(&entity) {
+            return;
+        }
+        self.entities.insert(key, entity);
         self.entities_dirty = true;
+        self.generation += 1;
     }
     /// Remove an entity by key. Returns the removed entity if it existed.
     pub fn remove_entity(&mut self, key: &str) -> Option<Entity> {
         let result = self.entities.remove(key);
         if result.is_some() {
             self.entities_dirty = true;
+            self.generation += 1;
         }
         result
     }
@@ -334,6 +373,7 @@ impl WorldSignals {
         self.entities.retain(|_, e| *e != entity);
         if self.entities.len() != before {
             self.entities_dirty = true;
+            self.generation += 1;
         }
     }
 
@@ -348,15 +388,26 @@ impl WorldSignals {
             .retain(|_, entity| persistent_entities.contains(entity));
         if self.entities.len() != before {
             self.entities_dirty = true;
+            self.generation += 1;
         }
     }
 
     /// Get a map of group counts (for caching).
     /// Returns a map from group name to count.
-    pub fn group_counts(&self) -> FxHashMap<String, u32> {
+    pub fn group_counts(&self) -> FxHashMap<Key, u32> {
         self.group_counts.clone()
     }
 
+    /// Change counter, bumped by every write that changes a signal. Writes that
+    /// store the value a key already holds do not count, so a script setting
+    /// its score every frame leaves the generation (and the snapshot) alone.
+    ///
+    /// Compare against a value saved earlier (or against
+    /// [`SignalSnapshot::generation`]) to tell whether anything changed.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Returns true if any signal domain has been modified since the last snapshot.
     ///
     /// `snapshot()` is already O(1) when nothing is dirty (six bool checks plus
@@ -443,6 +494,7 @@ impl WorldSignals {
                 flags: Arc::clone(&self.flags_arc),
                 group_counts: Arc::clone(&self.group_counts_arc),
                 entities: Arc::clone(&self.entities_arc),
+                generation: self.generation,
             });
         }
         Arc::clone(&self.snapshot)
@@ -976,4 +1028,36 @@ mod tests {
             "set_integer on a group_count key must be visible in the snapshot"
         );
     }
+
+    #[test]
+    fn test_unchanged_writes_keep_snapshot_clean() {
+        let mut ws = WorldSignals::default();
+        ws.set_scalar("x", 1.0);
+        ws.set_integer("score", 10);
+        ws.set_string("scene", "level1");
+        ws.set_flag("paused");
+        ws.set_entity("player", Entity::from_bits(3));
+        let snap1 = ws.snapshot();
+        let generation = ws.generation();
+        assert_eq!(snap1.generation, generation);
+
+        ws.set_scalar("x", 1.0);
+        ws.set_integer("score", 10);
+        ws.set_string("scene", "level1");
+        ws.set_flag("paused");
+        ws.set_entity("player", Entity::from_bits(3));
+        assert!(!ws.is_dirty());
+        assert_eq!(ws.generation(), generation);
+        assert!(Arc::ptr_eq(&snap1, &ws.snapshot()));
+    }
+
+    #[test]
+    fn test_generation_counts_changes() {
+        let mut ws = WorldSignals::default();
+        ws.set_integer("score", 1);
+        ws.set_integer("score", 2);
+        ws.clear_flag("absent");
+        assert_eq!(ws.generation(), 2);
+        assert_eq!(ws.snapshot().generation, 2);
+    }
 }
@@ -0,0 +1,255 @@
+//! Named world snapshots for tracking down leaks.
+//!
+//! A [`WorldSnapshot`] records every live entity with its group and the
+//! names of its components, plus the resources present. Comparing an older
+//! snapshot with the current world gives a [`SnapshotDiff`]: entities
+//! spawned and despawned, components added to or removed from the entities
+//! alive in both, and resources inserted or removed. Observers and
+//! registered systems are entities too, so an observer lost on a scene
+//! switch shows up as a despawned entity holding `Observer`.
+//!
+//! Snapshots are taken and compared by the
+//! [`TakeSnapshot`](crate::systems::world_snapshot::TakeSnapshot) and
+//! [`DiffSnapshot`](crate::systems::world_snapshot::DiffSnapshot) commands
+//! (Lua: `engine.snapshot(name)`, `engine.snapshot_diff(name)`), which keep
+//! them by name in [`WorldSnapshots`]. As with
+//! [`EcsStats`](crate::resources::ecsstats::EcsStats), component names need
+//! the `ecs_names` cargo feature; without it they are listed by component id.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use bevy_ecs::prelude::{Entity, Resource};
+use rustc_hash::FxHashMap;
+
+/// Entries listed per section of a [`SnapshotDiff::report`].
+const REPORT_LIMIT: usize = 20;
+
+/// What a snapshot remembers of one entity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntitySummary {
+    /// Name of the entity's `Group`, if it has one.
+    pub group: Option<String>,
+    pub components: BTreeSet<String>,
+}
+
+/// Entities and resources of the world at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldSnapshot {
+    pub entities: BTreeMap<Entity, EntitySummary>,
+    pub resources: BTreeSet<String>,
+}
+
+/// Components added to or removed from an entity alive in both snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentChange {
+    pub entity: Entity,
+    pub group: Option<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Differences between two [`WorldSnapshot`]s.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotDiff {
+    pub spawned: Vec<(Entity, EntitySummary)>,
+    pub despawned: Vec<(Entity, EntitySummary)>,
+    pub changed: Vec<ComponentChange>,
+    pub resources_added: Vec<String>,
+    pub resources_removed: Vec<String>,
+}
+
+impl WorldSnapshot {
+    /// What changed from this snapshot to `now`.
+    pub fn diff(&self, now: &WorldSnapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+        for (&entity, before) in &self.entities {
+            match now.entities.get(&entity) {
+                None => diff.despawned.push((entity, before.clone())),
+                Some(after) if after.components != before.components => {
+                    diff.changed.push(ComponentChange {
+                        entity,
+                        group: after.group.clone(),
+                        added: after
+                            .components
+                            .difference(&before.components)
+                            .cloned()
+                            .collect(),
+                        removed: before
+                            .components
+                            .difference(&after.components)
+                            .cloned()
+                            .collect(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.spawned = now
+            .entities
+            .iter()
+            .filter(|(entity, _)| !self.entities.contains_key(entity))
+            .map(|(&entity, summary)| (entity, summary.clone()))
+            .collect();
+        diff.resources_added = now.resources.difference(&self.resources).cloned().collect();
+        diff.resources_removed = self.resources.difference(&now.resources).cloned().collect();
+        diff
+    }
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.changed.is_empty()
+            && self.resources_added.is_empty()
+            && self.resources_removed.is_empty()
+    }
+
+    /// A summary line followed by one line per change: `+` spawned,
+    /// `-` despawned, `~` changed. Each section lists at most 20 entries.
+    pub fn report(&self) -> String {
+        let mut out = format!(
+            "{} spawned, {} despawned, {} changed, {} resources added, {} removed",
+            self.spawned.len(),
+            self.despawned.len(),
+            self.changed.len(),
+            self.resources_added.len(),
+            self.resources_removed.len()
+        );
+        let entity_lines = |sign: char, entities: &[(Entity, EntitySummary)]| {
+            entities
+                .iter()
+                .map(|(entity, summary)| {
+                    format!(
+                        "{sign} {}: {}",
+                        label(*entity, summary.group.as_deref()),
+                        join(&summary.components)
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let changed = self
+            .changed
+            .iter()
+            .map(|change| {
+                let added = change.added.iter().map(|name| format!("+{name}"));
+                let removed = change.removed.iter().map(|name| format!("-{name}"));
+                format!(
+                    "~ {}: {}",
+                    label(change.entity, change.group.as_deref()),
+                    join(added.chain(removed))
+                )
+            })
+            .collect();
+        let resources = |sign: char, names: &[String]| {
+            names
+                .iter()
+                .map(|name| format!("{sign} resource {name}"))
+                .collect::<Vec<_>>()
+        };
+        for section in [
+            entity_lines('+', &self.spawned),
+            entity_lines('-', &self.despawned),
+            changed,
+            resources('+', &self.resources_added),
+            resources('-', &self.resources_removed),
+        ] {
+            for line in section.iter().take(REPORT_LIMIT) {
+                let _ = write!(out, "\n  {line}");
+            }
+            if section.len() > REPORT_LIMIT {
+                let _ = write!(out, "\n  ... and {} more", section.len() - REPORT_LIMIT);
+            }
+        }
+        out
+    }
+}
+
+/// `12v3` or `12v3 [enemies]`.
+fn label(entity: Entity, group: Option<&str>) -> String {
+    match group {
+        Some(group) => format!("{entity} [{group}]"),
+        None => entity.to_string(),
+    }
+}
+
+fn join(names: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut out = String::new();
+    for (index, name) in names.into_iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(name.as_ref());
+    }
+    out
+}
+
+/// Snapshots by name, kept until replaced.
+#[derive(Resource, Debug, Default)]
+pub struct WorldSnapshots {
+    snapshots: FxHashMap<String, WorldSnapshot>,
+}
+
+impl WorldSnapshots {
+    /// Store `snapshot` as `name`, replacing an older one.
+    pub fn insert(&mut self, name: impl Into<String>, snapshot: WorldSnapshot) {
+        self.snapshots.insert(name.into(), snapshot);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WorldSnapshot> {
+        self.snapshots.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(group: Option<&str>, components: &[&str]) -> EntitySummary {
+        EntitySummary {
+            group: group.map(str::to_string),
+            components: components.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn diff_lists_spawns_despawns_and_component_changes() {
+        let kept = Entity::from_bits(1);
+        let gone = Entity::from_bits(2);
+        let new = Entity::from_bits(3);
+        let before = WorldSnapshot {
+            entities: [
+                (kept, summary(Some("player"), &["Sprite", "Timer"])),
+                (gone, summary(None, &["Observer"])),
+            ]
+            .into(),
+            resources: ["Weather".to_string()].into(),
+        };
+        let now = WorldSnapshot {
+            entities: [
+                (kept, summary(Some("player"), &["Sprite", "Tween"])),
+                (new, summary(Some("enemy"), &["Sprite"])),
+            ]
+            .into(),
+            resources: BTreeSet::new(),
+        };
+
+        let diff = before.diff(&now);
+        assert_eq!(
+            diff.spawned,
+            vec![(new, summary(Some("enemy"), &["Sprite"]))]
+        );
+        assert_eq!(diff.despawned, vec![(gone, summary(None, &["Observer"]))]);
+        assert_eq!(diff.changed[0].added, vec!["Tween".to_string()]);
+        assert_eq!(diff.changed[0].removed, vec!["Timer".to_string()]);
+        assert_eq!(diff.resources_removed, vec!["Weather".to_string()]);
+        let report = diff.report();
+        assert!(
+            report.starts_with("1 spawned, 1 despawned, 1 changed, 0 resources added, 1 removed")
+        );
+        assert!(report.contains(&format!("~ {kept} [player]: +Tween, -Timer")));
+
+        assert!(now.diff(&now).is_empty());
+    }
+}
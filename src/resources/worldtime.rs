@@ -10,8 +10,10 @@ use bevy_ecs::prelude::Resource;
 pub struct WorldTime {
     /// Total elapsed time since start (seconds).
     pub elapsed: f32,
-    /// Unscaled delta time for the last frame (seconds).
+    /// Delta time for the last frame (seconds), scaled by `time_scale`.
     pub delta: f32,
+    /// Wall-clock delta time for the last frame (seconds), ignoring `time_scale`.
+    pub real_delta: f32,
     /// Multiplier applied by systems that honor time scaling.
     pub time_scale: f32,
     /// Total number of frames since start.
@@ -23,6 +25,7 @@ impl Default for WorldTime {
         WorldTime {
             elapsed: 0.0,
             delta: 0.0,
+            real_delta: 0.0,
             time_scale: 1.0,
             frame_count: 0,
         }
@@ -0,0 +1,170 @@
+//! Built-in stress scenes and frame-time reporting (`--stress`).
+//!
+//! Each [`StressScene`] is a self-contained Lua entry script under
+//! `assets/scripts/stress/` that spawns a fixed workload and keeps it running:
+//!
+//! - `sprites` – 10 000 moving, tinted sprites (render and movement cost)
+//! - `colliders` – 5 000 moving box colliders with a Lua collision rule
+//!   (collision detection and callback cost)
+//! - `lua-phases` – 1 000 entities whose `on_update` phase callback runs in Lua
+//!   every frame (Lua call overhead)
+//! - `particles` – 50 000 small rotating sprites sharing one texture (batched
+//!   sprite submission cost)
+//!
+//! [`frame_time_report_system`] records every frame's wall-clock delta into
+//! [`FrameTimeStats`], plots it in Tracy as `frame_time_ms` (with
+//! `--features tracy`) and logs a summary every [`REPORT_INTERVAL`] seconds,
+//! so regressions show up both in the profiler and in a plain release run.
+
+use bevy_ecs::prelude::*;
+use log::info;
+
+use crate::components::mapposition::MapPosition;
+use crate::resources::worldtime::WorldTime;
+
+/// Seconds of frames aggregated into each logged summary.
+pub const REPORT_INTERVAL: f32 = 5.0;
+
+/// Built-in stress scene selectable with `--stress <scene>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum StressScene {
+    /// 10 000 moving sprites.
+    Sprites,
+    /// 5 000 moving box colliders.
+    Colliders,
+    /// 1 000 entities with a Lua `on_update` phase callback.
+    LuaPhases,
+    /// 50 000 small rotating sprites.
+    Particles,
+}
+
+impl StressScene {
+    /// Entry script that sets up and runs this scene.
+    pub fn script_path(self) -> &'static str {
+        match self {
+            StressScene::Sprites => "./assets/scripts/stress/sprites.lua",
+            StressScene::Colliders => "./assets/scripts/stress/colliders.lua",
+            StressScene::LuaPhases => "./assets/scripts/stress/lua_phases.lua",
+            StressScene::Particles => "./assets/scripts/stress/particles.lua",
+        }
+    }
+}
+
+/// Aggregated frame times over a reporting window, in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTimeSummary {
+    pub frames: usize,
+    pub avg_ms: f32,
+    pub min_ms: f32,
+    pub max_ms: f32,
+    /// 99th percentile frame time (the slowest 1% of frames start here).
+    pub p99_ms: f32,
+}
+
+/// Frame-time samples collected since the last report.
+#[derive(Debug, Default)]
+pub struct FrameTimeStats {
+    samples_ms: Vec<f32>,
+    elapsed: f32,
+}
+
+impl FrameTimeStats {
+    /// Record one frame of `dt` seconds.
+    pub fn record(&mut self, dt: f32) {
+        self.samples_ms.push(dt * 1000.0);
+        self.elapsed += dt;
+    }
+
+    /// Seconds covered by the recorded frames.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Summarize the recorded frames, or `None` if nothing was recorded.
+    pub fn summary(&self) -> Option<FrameTimeSummary> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples_ms.clone();
+        sorted.sort_by(f32::total_cmp);
+        let frames = sorted.len();
+        let p99_index = (frames * 99).div_ceil(100) - 1;
+        Some(FrameTimeSummary {
+            frames,
+            avg_ms: sorted.iter().sum::<f32>() / frames as f32,
+            min_ms: sorted[0],
+            max_ms: sorted[frames - 1],
+            p99_ms: sorted[p99_index],
+        })
+    }
+
+    /// Drop all samples, keeping the allocation for the next window.
+    pub fn reset(&mut self) {
+        self.samples_ms.clear();
+        self.elapsed = 0.0;
+    }
+}
+
+/// Record the unscaled frame time, plot it in Tracy and log a summary every
+/// [`REPORT_INTERVAL`] seconds together with the number of world entities.
+pub fn frame_time_report_system(
+    time: Res<WorldTime>,
+    world_entities: Query<(), With<MapPosition>>,
+    mut stats: Local<FrameTimeStats>,
+) {
+    crate::tracy::tracy_plot!("frame_time_ms", time.real_delta * 1000.0);
+    stats.record(time.real_delta);
+    if stats.elapsed() < REPORT_INTERVAL {
+        return;
+    }
+    if let Some(s) = stats.summary() {
+        info!(
+            "frame time over {} frames: avg {:.2} ms ({:.0} fps), min {:.2} ms, max {:.2} ms, p99 {:.2} ms, {} entities",
+            s.frames,
+            s.avg_ms,
+            1000.0 / s.avg_ms,
+            s.min_ms,
+            s.max_ms,
+            s.p99_ms,
+            world_entities.iter().count()
+        );
+    }
+    stats.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_is_none_without_samples() {
+        assert_eq!(FrameTimeStats::default().summary(), None);
+    }
+
+    #[test]
+    fn summary_reports_avg_min_max_and_p99() {
+        let mut stats = FrameTimeStats::default();
+        for _ in 0..99 {
+            stats.record(0.010);
+        }
+        stats.record(0.050);
+
+        let s = stats.summary().unwrap();
+        assert_eq!(s.frames, 100);
+        assert!((s.avg_ms - 10.4).abs() < 1e-3);
+        assert!((s.min_ms - 10.0).abs() < 1e-3);
+        assert!((s.max_ms - 50.0).abs() < 1e-3);
+        // 99th of 100 samples is still a 10 ms frame
+        assert!((s.p99_ms - 10.0).abs() < 1e-3);
+        assert!((stats.elapsed() - 1.04).abs() < 1e-4);
+    }
+
+    #[test]
+    fn reset_clears_the_window() {
+        let mut stats = FrameTimeStats::default();
+        stats.record(0.016);
+        stats.reset();
+        assert_eq!(stats.summary(), None);
+        assert_eq!(stats.elapsed(), 0.0);
+    }
+}
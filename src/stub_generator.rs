@@ -3,6 +3,10 @@
 //! Reads `engine.__meta` from the Lua runtime and emits a deterministic
 //! `engine.lua` stub file with `---@class`, `---@field`, `---@param`,
 //! and `---@return` annotations.
+//!
+//! The extraction half ([`EngineMeta::from_runtime`]) is shared with
+//! [`crate::docgen`], so the stubs and the reference pages are rendered from
+//! the same registration metadata.
 
 use crate::resources::lua_runtime::LuaRuntime;
 use mlua::prelude::*;
@@ -10,7 +14,7 @@ use std::fmt::Write as FmtWrite;
 use std::path::Path;
 
 /// Category display order for deterministic output.
-const CATEGORY_ORDER: &[&str] = &[
+pub(crate) const CATEGORY_ORDER: &[&str] = &[
     "base",
     "asset",
     "spawn",
@@ -24,10 +28,17 @@ const CATEGORY_ORDER: &[&str] = &[
     "collision",
     "animation",
     "render",
+    "world",
+    "clock",
+    "weather",
+    "inventory",
+    "ui",
+    "save",
+    "debug",
 ];
 
 /// Human-readable section titles for each category.
-fn category_title(cat: &str) -> &str {
+pub(crate) fn category_title(cat: &str) -> &str {
     match cat {
         "base" => "Logging Functions",
         "asset" => "Asset Loading",
@@ -42,12 +53,38 @@ fn category_title(cat: &str) -> &str {
         "collision" => "Collision Commands",
         "animation" => "Animation Registration",
         "render" => "Rendering & Shaders",
+        "world" => "Secondary Worlds",
+        "clock" => "World Clock",
+        "weather" => "Weather",
+        "inventory" => "Inventory",
+        "ui" => "UI Screens",
+        "save" => "Save Games",
+        "debug" => "Debugging",
         _ => cat,
     }
 }
 
+/// Human-readable section title for a builder class.
+pub(crate) fn class_title(class: &ClassMeta) -> &str {
+    match class.name.as_str() {
+        "EntityBuilder" => "Entity Builder",
+        "CollisionEntityBuilder" => "Collision Entity Builder",
+        _ => &class.name,
+    }
+}
+
 /// Maps a meta type string to the EmmyLua annotation type.
-fn lua_type_annotation(meta_type: &str) -> String {
+///
+/// A comma-separated list (e.g. `"integer?, number?"`) describes multiple
+/// return values and maps each entry.
+pub(crate) fn lua_type_annotation(meta_type: &str) -> String {
+    if meta_type.contains(',') && !meta_type.starts_with("{[") {
+        return meta_type
+            .split(',')
+            .map(|t| lua_type_annotation(t.trim()))
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
     match meta_type {
         "number" => "number".into(),
         "integer" => "integer".into(),
@@ -68,84 +105,107 @@ fn lua_type_annotation(meta_type: &str) -> String {
 }
 
 /// Extracted function metadata.
-struct FnMeta {
-    name: String,
-    description: String,
-    category: String,
-    params: Vec<(String, String)>,
-    returns: Option<String>,
+pub(crate) struct FnMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) category: String,
+    pub(crate) params: Vec<(String, String)>,
+    pub(crate) returns: Option<String>,
 }
 
 /// Extracted class metadata.
-struct ClassMeta {
-    name: String,
-    description: String,
-    methods: Vec<MethodMeta>,
+pub(crate) struct ClassMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) methods: Vec<MethodMeta>,
 }
 
-struct MethodMeta {
-    name: String,
-    description: String,
-    params: Vec<ParamMeta>,
-    returns: Option<String>,
+pub(crate) struct MethodMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) params: Vec<ParamMeta>,
+    pub(crate) returns: Option<String>,
 }
 
-struct ParamMeta {
-    name: String,
-    type_name: String,
-    schema: Option<String>,
+pub(crate) struct ParamMeta {
+    pub(crate) name: String,
+    pub(crate) type_name: String,
+    pub(crate) schema: Option<String>,
 }
 
 /// Extracted type metadata.
-struct TypeMeta {
-    name: String,
-    description: String,
-    fields: Vec<FieldMeta>,
+pub(crate) struct TypeMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) fields: Vec<FieldMeta>,
 }
 
-struct FieldMeta {
-    name: String,
-    type_name: String,
-    optional: bool,
-    description: Option<String>,
+pub(crate) struct FieldMeta {
+    pub(crate) name: String,
+    pub(crate) type_name: String,
+    pub(crate) optional: bool,
+    pub(crate) description: Option<String>,
 }
 
 /// Extracted enum metadata.
-struct EnumMeta {
-    name: String,
-    description: String,
-    values: Vec<String>,
+pub(crate) struct EnumMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) values: Vec<String>,
 }
 
 /// Extracted callback metadata.
-struct CallbackMeta {
-    name: String,
-    description: String,
-    params: Vec<(String, String)>,
-    returns: Option<String>,
-    context: Option<String>,
-    note: Option<String>,
+pub(crate) struct CallbackMeta {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) params: Vec<(String, String)>,
+    pub(crate) returns: Option<String>,
+    pub(crate) context: Option<String>,
+    pub(crate) note: Option<String>,
+}
+
+/// Everything registered in `engine.__meta`, sorted in output order.
+pub(crate) struct EngineMeta {
+    pub(crate) functions: Vec<FnMeta>,
+    pub(crate) classes: Vec<ClassMeta>,
+    pub(crate) types: Vec<TypeMeta>,
+    pub(crate) enums: Vec<EnumMeta>,
+    pub(crate) callbacks: Vec<CallbackMeta>,
+}
+
+impl EngineMeta {
+    /// Extract all metadata from `engine.__meta`.
+    pub(crate) fn from_runtime(runtime: &LuaRuntime) -> Result<Self, String> {
+        let lua = runtime.lua();
+
+        let engine: LuaTable = lua
+            .globals()
+            .get("engine")
+            .map_err(|e| format!("Failed to get engine table: {e}"))?;
+        let meta: LuaTable = engine
+            .get("__meta")
+            .map_err(|e| format!("Failed to get engine.__meta: {e}"))?;
+
+        Ok(Self {
+            functions: extract_functions(&meta).map_err(|e| format!("Functions: {e}"))?,
+            classes: extract_classes(&meta).map_err(|e| format!("Classes: {e}"))?,
+            types: extract_types(&meta).map_err(|e| format!("Types: {e}"))?,
+            enums: extract_enums(&meta).map_err(|e| format!("Enums: {e}"))?,
+            callbacks: extract_callbacks(&meta).map_err(|e| format!("Callbacks: {e}"))?,
+        })
+    }
 }
 
 /// Extract all metadata from `engine.__meta` and generate the stub file content.
 pub fn generate_stubs(runtime: &LuaRuntime) -> Result<String, String> {
-    let lua = runtime.lua();
-
-    let engine: LuaTable = lua
-        .globals()
-        .get("engine")
-        .map_err(|e| format!("Failed to get engine table: {e}"))?;
-    let meta: LuaTable = engine
-        .get("__meta")
-        .map_err(|e| format!("Failed to get engine.__meta: {e}"))?;
-
-    let functions = extract_functions(&meta).map_err(|e| format!("Functions: {e}"))?;
-    let classes = extract_classes(&meta).map_err(|e| format!("Classes: {e}"))?;
-    let types = extract_types(&meta).map_err(|e| format!("Types: {e}"))?;
-    let enums = extract_enums(&meta).map_err(|e| format!("Enums: {e}"))?;
-    let callbacks = extract_callbacks(&meta).map_err(|e| format!("Callbacks: {e}"))?;
-
-    render_stubs(&functions, &classes, &types, &enums, &callbacks)
+    let meta = EngineMeta::from_runtime(runtime)?;
+    render_stubs(
+        &meta.functions,
+        &meta.classes,
+        &meta.types,
+        &meta.enums,
+        &meta.callbacks,
+    )
 }
 
 /// Write the generated stubs to a file.
@@ -577,15 +637,10 @@ fn write_description(out: &mut String, description: &str) {
 }
 
 fn render_class(out: &mut String, class: &ClassMeta) {
-    let section_title = match class.name.as_str() {
-        "EntityBuilder" => "Entity Builder",
-        "CollisionEntityBuilder" => "Collision Entity Builder",
-        _ => &class.name,
-    };
     writeln!(
         out,
         "-- ==================== {} ====================",
-        section_title
+        class_title(class)
     )
     .unwrap();
     writeln!(out).unwrap();
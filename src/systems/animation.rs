@@ -846,7 +846,6 @@ mod tests {
     fn animation_finished_event_fires_exactly_once() {
         use crate::events::animation::AnimationFinishedEvent;
         use crate::resources::animationstore::AnimationResource;
-        use std::sync::Arc;
 
         #[derive(Resource, Default)]
         struct EventCount(u32);
@@ -854,6 +853,7 @@ mod tests {
         let mut world = World::new();
         world.insert_resource(WorldTime {
             delta: 0.11,
+            real_delta: 0.11,
             ..WorldTime::default()
         });
         world.insert_resource(TextureStore::default());
@@ -866,7 +866,7 @@ mod tests {
         anim_store.animations.insert(
             "die".to_string(),
             AnimationResource {
-                tex_key: Arc::from("t"),
+                tex_key: "t".into(),
                 position: Vector2 { x: 0.0, y: 0.0 },
                 horizontal_displacement: 32.0,
                 vertical_displacement: 0.0,
@@ -888,7 +888,7 @@ mod tests {
         world.flush();
 
         let make_sprite = || Sprite {
-            tex_key: Arc::from("t"),
+            tex_key: "t".into(),
             width: 32.0,
             height: 32.0,
             offset: Vector2 { x: 0.0, y: 0.0 },
@@ -947,12 +947,12 @@ mod tests {
     #[test]
     fn animation_break_starves_entities_after_finished_nonlooped() {
         use crate::resources::animationstore::AnimationResource;
-        use std::sync::Arc;
 
         // delta > frame_duration (0.1s at 10 fps) so every entity advances this tick.
         let mut world = World::new();
         world.insert_resource(WorldTime {
             delta: 0.11,
+            real_delta: 0.11,
             ..WorldTime::default()
         });
         world.insert_resource(TextureStore::default());
@@ -961,7 +961,7 @@ mod tests {
         anim_store.animations.insert(
             "death".to_string(),
             AnimationResource {
-                tex_key: Arc::from("t"),
+                tex_key: "t".into(),
                 position: Vector2 { x: 0.0, y: 0.0 },
                 horizontal_displacement: 32.0,
                 vertical_displacement: 0.0,
@@ -973,7 +973,7 @@ mod tests {
         anim_store.animations.insert(
             "idle".to_string(),
             AnimationResource {
-                tex_key: Arc::from("t"),
+                tex_key: "t".into(),
                 position: Vector2 { x: 0.0, y: 0.0 },
                 horizontal_displacement: 32.0,
                 vertical_displacement: 0.0,
@@ -985,7 +985,7 @@ mod tests {
         world.insert_resource(anim_store);
 
         let make_sprite = || Sprite {
-            tex_key: Arc::from("t"),
+            tex_key: "t".into(),
             width: 32.0,
             height: 32.0,
             offset: Vector2 { x: 0.0, y: 0.0 },
@@ -1049,11 +1049,11 @@ mod tests {
     fn animation_stale_signal_cleared_after_restart() {
         use crate::components::signals::Signals;
         use crate::resources::animationstore::AnimationResource;
-        use std::sync::Arc;
 
         let mut world = World::new();
         world.insert_resource(WorldTime {
             delta: 0.11,
+            real_delta: 0.11,
             ..WorldTime::default()
         });
         world.insert_resource(TextureStore::default());
@@ -1066,7 +1066,7 @@ mod tests {
         anim_store.animations.insert(
             "die".to_string(),
             AnimationResource {
-                tex_key: Arc::from("t"),
+                tex_key: "t".into(),
                 position: Vector2 { x: 0.0, y: 0.0 },
                 horizontal_displacement: 32.0,
                 vertical_displacement: 0.0,
@@ -1078,7 +1078,7 @@ mod tests {
         world.insert_resource(anim_store);
 
         let make_sprite = || Sprite {
-            tex_key: Arc::from("t"),
+            tex_key: "t".into(),
             width: 32.0,
             height: 32.0,
             offset: Vector2 { x: 0.0, y: 0.0 },
@@ -1182,7 +1182,7 @@ pub fn animation_controller(
             if let Some(anim_res) = animation_store.animations.get(owned.as_str())
                 && let Ok(mut sprite) = sprite_query.get_mut(entity)
             {
-                sprite.tex_key = anim_res.tex_key.clone();
+                sprite.tex_key = anim_res.tex_key;
             }
         }
     }
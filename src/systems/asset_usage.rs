@@ -0,0 +1,113 @@
+//! Asset usage tracking.
+//!
+//! [`asset_usage_system`] samples which textures and fonts entities reference
+//! and logs the report when `engine.dump_asset_usage()` asked for it.
+//! [`track_audio_usage_system`] mirrors the audio thread's loaded sounds and
+//! music and when they were last played. See
+//! [`assetusage`](crate::resources::assetusage).
+
+use bevy_ecs::prelude::*;
+use log::info;
+
+use crate::components::dynamictext::DynamicText;
+use crate::components::sprite::Sprite;
+use crate::events::audio::{AudioCmd, AudioMessage};
+use crate::resources::assetusage::AssetUsage;
+use crate::resources::fontstore::FontStore;
+use crate::resources::missingassets::AssetKind;
+use crate::resources::texturestore::TextureStore;
+use crate::resources::worldtime::WorldTime;
+
+/// Sample entity references every [`AssetUsage::SAMPLE_FRAMES`] frames and
+/// log the usage report on request.
+pub fn asset_usage_system(
+    sprites: Query<&Sprite>,
+    texts: Query<&DynamicText>,
+    textures: Res<TextureStore>,
+    fonts: NonSend<FontStore>,
+    world_time: Res<WorldTime>,
+    mut usage: ResMut<AssetUsage>,
+) {
+    let frame = world_time.frame_count;
+    if !usage.sample_due(frame) {
+        return;
+    }
+    usage.retain_loaded(&textures, &fonts);
+    usage.record_references(
+        frame,
+        sprites.iter().map(|sprite| &*sprite.tex_key),
+        texts.iter().map(|text| &*text.font),
+    );
+    if usage.take_dump_request() {
+        info!("{}", usage.report(&textures, &fonts));
+    }
+}
+
+/// Mirror audio loads, unloads and playback into [`AssetUsage`].
+pub fn track_audio_usage_system(
+    mut messages: MessageReader<AudioMessage>,
+    mut cmds: MessageReader<AudioCmd>,
+    world_time: Res<WorldTime>,
+    mut usage: ResMut<AssetUsage>,
+) {
+    for msg in messages.read() {
+        match msg {
+            AudioMessage::FxLoaded { id, bytes } => {
+                usage.audio_loaded(AssetKind::Sound, id, *bytes)
+            }
+            AudioMessage::MusicLoaded { id, bytes } => {
+                usage.audio_loaded(AssetKind::Music, id, *bytes)
+            }
+            AudioMessage::FxUnloaded { id } => usage.audio_unloaded(AssetKind::Sound, id),
+            AudioMessage::MusicUnloaded { id } => usage.audio_unloaded(AssetKind::Music, id),
+            AudioMessage::FxUnloadedAll => usage.audio_unloaded_all(AssetKind::Sound),
+            AudioMessage::MusicUnloadedAll => usage.audio_unloaded_all(AssetKind::Music),
+            _ => {}
+        }
+    }
+    let frame = world_time.frame_count;
+    for cmd in cmds.read() {
+        match cmd {
+            AudioCmd::PlayFx { id } | AudioCmd::PlayFxPitched { id, .. } => {
+                usage.audio_played(AssetKind::Sound, id, frame)
+            }
+            AudioCmd::PlayMusic { id, .. } => usage.audio_played(AssetKind::Music, id, frame),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::message::Messages;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn tracks_audio_loads_and_playback() {
+        let mut world = World::new();
+        world.init_resource::<Messages<AudioMessage>>();
+        world.init_resource::<Messages<AudioCmd>>();
+        world.insert_resource(WorldTime {
+            frame_count: 12,
+            ..WorldTime::default()
+        });
+        world.insert_resource(AssetUsage::default());
+        world.write_message(AudioMessage::FxLoaded {
+            id: "jump".into(),
+            bytes: 1000,
+        });
+        world.write_message(AudioCmd::PlayFx { id: "jump".into() });
+
+        world
+            .run_system_once(track_audio_usage_system)
+            .expect("system should run");
+
+        let report = world
+            .resource::<AssetUsage>()
+            .report(&TextureStore::new(), &FontStore::new());
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].bytes, 1000);
+        assert_eq!(report.rows[0].last_used_frame, Some(12));
+    }
+}
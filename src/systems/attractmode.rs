@@ -0,0 +1,202 @@
+//! Attract mode system.
+//!
+//! [`attract_mode_system`] runs right after the hardware input is read. In
+//! the [`AttractMode`] home scene it counts the seconds without input and,
+//! once `idle_seconds` pass, requests a switch to the replay's scene. While
+//! the demo plays it replaces [`InputState`] with the recorded frame; a real
+//! key press (swallowed, so it doesn't act in the demo) or the end of the
+//! recording switches back home. The switches use the same signals as
+//! `engine.change_scene()`.
+//!
+//! [`SetAttractMode`] loads a replay file and arms the attract mode in the
+//! current scene (Lua: `engine.set_attract_mode`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::attractmode`] – the replay format and idle state
+
+use bevy_ecs::prelude::*;
+use log::error;
+
+use crate::events::input::InputAction;
+use crate::resources::attractmode::{AttractMode, InputReplay};
+use crate::resources::input::InputState;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Command arming the attract mode in the current scene, or turning it off
+/// when `replay_path` is `None`.
+pub struct SetAttractMode {
+    pub replay_path: Option<String>,
+    pub idle_seconds: f32,
+}
+
+impl Command for SetAttractMode {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let Some(path) = self.replay_path else {
+            world.insert_resource(AttractMode::default());
+            return;
+        };
+        let replay = match InputReplay::load(&path) {
+            Ok(replay) => replay,
+            Err(e) => {
+                error!("{e}");
+                world.insert_resource(AttractMode::default());
+                return;
+            }
+        };
+        let home = current_scene(world.resource::<WorldSignals>());
+        world.insert_resource(AttractMode::new(replay, self.idle_seconds, home));
+    }
+}
+
+/// Starts the demo after the idle time, feeds it the replay and returns home
+/// on input or when the replay ends.
+pub fn attract_mode_system(
+    mut attract: ResMut<AttractMode>,
+    mut input: ResMut<InputState>,
+    mut signals: ResMut<WorldSignals>,
+    time: Res<WorldTime>,
+) {
+    let attract = &mut *attract;
+    let Some(replay) = &attract.replay else {
+        return;
+    };
+    if attract.idle_seconds <= 0.0 {
+        return;
+    }
+    let scene = current_scene(&signals);
+    let touched = any_input(&input);
+
+    let Some(frame) = attract.frame else {
+        if touched || scene != attract.home_scene {
+            attract.idle = 0.0;
+            return;
+        }
+        attract.idle += time.delta;
+        if attract.idle >= attract.idle_seconds {
+            attract.idle = 0.0;
+            attract.frame = Some(0);
+            switch_scene(&mut signals, &replay.scene);
+        }
+        return;
+    };
+
+    if scene != replay.scene {
+        // The demo scene switched away on its own.
+        attract.frame = None;
+        return;
+    }
+    let finished = !replay.frames.is_empty() && frame >= replay.frames.len();
+    if touched || finished {
+        for action in InputAction::ALL {
+            *input.action_mut(action) = Default::default();
+        }
+        attract.frame = None;
+        switch_scene(&mut signals, &attract.home_scene);
+        return;
+    }
+    if !replay.frames.is_empty() {
+        replay.apply_frame(frame, &mut input);
+    }
+    attract.frame = Some(frame + 1);
+}
+
+fn current_scene(signals: &WorldSignals) -> String {
+    signals
+        .get_string(sk::SCENE)
+        .map_or(sk::DEFAULT_SCENE, String::as_str)
+        .to_string()
+}
+
+fn switch_scene(signals: &mut WorldSignals, scene: &str) {
+    signals.set_string(sk::SCENE, scene);
+    signals.set_flag(sk::SWITCH_SCENE);
+}
+
+fn any_input(input: &InputState) -> bool {
+    input.mouse_left_button.active
+        || InputAction::ALL.into_iter().any(|action| {
+            let state = input.action(action);
+            state.active || state.just_pressed
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 1.0,
+            real_delta: 1.0,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.insert_resource(InputState::default());
+        let mut signals = WorldSignals::default();
+        signals.set_string(sk::SCENE, "menu");
+        world.insert_resource(signals);
+        let replay = InputReplay {
+            scene: "level1".into(),
+            frames: vec![vec![InputAction::Action1], vec![InputAction::Action1]],
+        };
+        world.insert_resource(AttractMode::new(replay, 2.0, "menu"));
+        world
+    }
+
+    fn frame(world: &mut World, press: bool) {
+        let mut input = InputState::default();
+        input.action_2.active = press;
+        input.action_2.just_pressed = press;
+        world.insert_resource(input);
+        world.run_system_once(attract_mode_system).unwrap();
+    }
+
+    fn take_switch(world: &mut World) -> Option<String> {
+        let mut signals = world.resource_mut::<WorldSignals>();
+        signals
+            .take_flag(sk::SWITCH_SCENE)
+            .then(|| signals.get_string(sk::SCENE).cloned().unwrap())
+    }
+
+    #[test]
+    fn plays_the_replay_when_idle_and_returns_when_it_ends() {
+        let mut world = world();
+        frame(&mut world, false);
+        frame(&mut world, true);
+        frame(&mut world, false);
+        assert_eq!(take_switch(&mut world), None);
+        frame(&mut world, false);
+        assert_eq!(take_switch(&mut world), Some("level1".into()));
+
+        frame(&mut world, false);
+        assert!(world.resource::<InputState>().action_1.just_pressed);
+        frame(&mut world, false);
+        assert!(world.resource::<InputState>().action_1.active);
+        assert_eq!(take_switch(&mut world), None);
+
+        frame(&mut world, false);
+        assert_eq!(take_switch(&mut world), Some("menu".into()));
+        assert!(!world.resource::<AttractMode>().is_playing());
+    }
+
+    #[test]
+    fn input_during_the_demo_returns_to_the_menu() {
+        let mut world = world();
+        world.resource_mut::<AttractMode>().idle = 2.0;
+        frame(&mut world, false);
+        assert_eq!(take_switch(&mut world), Some("level1".into()));
+
+        frame(&mut world, true);
+        assert_eq!(take_switch(&mut world), Some("menu".into()));
+        assert!(!world.resource::<InputState>().action_2.just_pressed);
+        assert!(!world.resource::<AttractMode>().is_playing());
+    }
+}
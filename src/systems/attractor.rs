@@ -0,0 +1,137 @@
+//! Attractor system.
+//!
+//! [`attractor_system`] accelerates rigid bodies toward every [`Attractor`]
+//! whose radius they are inside. Runs before
+//! [`movement`](super::movement::movement).
+//!
+//! # Related
+//!
+//! - [`crate::components::attractor`] – attractor definition
+//! - [`crate::resources::spatialindex`] – candidate lookup
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::attractor::Attractor;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::resources::spatialindex::SpatialIndex;
+use crate::resources::worldtime::WorldTime;
+
+/// Pulls labeled bodies toward attractors in range.
+///
+/// Candidates come from the [`SpatialIndex`] (last rebuilt positions); the
+/// range check and direction use current positions. Frozen bodies and the
+/// attractor itself are skipped; bodies exactly on the attractor are left
+/// alone.
+pub fn attractor_system(
+    attractors: Query<(Entity, &Attractor, &MapPosition, Option<&GlobalTransform2D>)>,
+    mut bodies: Query<(&MapPosition, Option<&GlobalTransform2D>, &mut RigidBody)>,
+    index: Res<SpatialIndex>,
+    time: Res<WorldTime>,
+    mut candidates: Local<Vec<Entity>>,
+) {
+    crate::tracy::tracy_span!("attractor_system");
+    let delta = time.delta;
+    for (attractor_entity, attractor, position, global) in attractors.iter() {
+        if attractor.radius <= 0.0 || attractor.strength == 0.0 {
+            continue;
+        }
+        let center = global.map_or(position.pos, |gt| gt.position);
+        let r = attractor.radius;
+        candidates.clear();
+        index.query_rect(
+            center.x - r,
+            center.y - r,
+            r * 2.0,
+            r * 2.0,
+            Some(&attractor.target),
+            &mut candidates,
+        );
+        for &entity in candidates.iter() {
+            if entity == attractor_entity {
+                continue;
+            }
+            let Ok((body_pos, body_global, mut rigidbody)) = bodies.get_mut(entity) else {
+                continue;
+            };
+            if rigidbody.frozen {
+                continue;
+            }
+            let pos = body_global.map_or(body_pos.pos, |gt| gt.position);
+            let to_center = center - pos;
+            let dist_sq = to_center.length_sqr();
+            if dist_sq > r * r || dist_sq <= f32::EPSILON {
+                continue;
+            }
+            let dir: Vector2 = to_center / dist_sq.sqrt();
+            rigidbody.velocity += dir * (attractor.strength * delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::group::Group;
+    use crate::systems::spatialindex::update_spatial_index_system;
+
+    fn tick(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_spatial_index_system, attractor_system).chain());
+        schedule.run(world);
+    }
+
+    #[test]
+    fn pulls_only_targets_in_range() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.5,
+            real_delta: 0.5,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.init_resource::<SpatialIndex>();
+        world.spawn((
+            Group::new("player"),
+            MapPosition::new(0.0, 0.0),
+            Attractor::new(50.0, 100.0, "coin"),
+        ));
+        let near = world
+            .spawn((
+                Group::new("coin"),
+                MapPosition::new(30.0, 0.0),
+                RigidBody::new(),
+            ))
+            .id();
+        let far = world
+            .spawn((
+                Group::new("coin"),
+                MapPosition::new(80.0, 0.0),
+                RigidBody::new(),
+            ))
+            .id();
+        let other = world
+            .spawn((
+                Group::new("rock"),
+                MapPosition::new(0.0, 30.0),
+                RigidBody::new(),
+            ))
+            .id();
+
+        tick(&mut world);
+
+        let v = world.get::<RigidBody>(near).unwrap().velocity;
+        assert!((v.x + 50.0).abs() < 1e-4 && v.y.abs() < 1e-4);
+        assert_eq!(
+            world.get::<RigidBody>(far).unwrap().velocity,
+            Vector2::zero()
+        );
+        assert_eq!(
+            world.get::<RigidBody>(other).unwrap().velocity,
+            Vector2::zero()
+        );
+    }
+}
@@ -8,6 +8,9 @@
 //!   receiver into Bevy ECS' message queue each frame.
 //! - [`update_bevy_audio_messages`] advances the ECS message queue so newly
 //!   written messages become readable by message subscribers.
+//! - [`emit_music_sync_events`] forwards beat boundaries and tracker rows
+//!   reported by the audio thread as typed [`BeatEvent`]/[`TrackerEvent`]
+//!   messages.
 //!
 //! The design keeps Raylib audio API calls isolated to a single thread, while
 //! the main game thread communicates via lock-free channels.
@@ -26,28 +29,175 @@
 //!
 //! See also: [`crate::events::audio`] and [`crate::resources::audio`].
 
-use crate::events::audio::{AudioCmd, AudioMessage};
+use crate::events::audio::{AudioCmd, AudioMessage, BeatEvent, TrackerEvent};
 use crate::resources::audio::AudioBridge;
+use crate::resources::missingassets::{AssetKind, MissingAssetWarnings};
+use crate::systems::tracker_clock::{TrackerClock, TrackerLayout};
 use bevy_ecs::prelude::Messages;
 use bevy_ecs::{
     prelude::{MessageWriter, Res},
     system::ResMut,
 };
 use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use raylib::core::audio::{Music, RaylibAudio};
 use raylib::ffi;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::ffi::CString;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// How often the audio thread wakes to pump music streams while playback is
 /// active. Raylib's `update_stream()` must be called at roughly this cadence to
 /// keep buffers fed. While idle (nothing playing) the thread blocks instead.
 const STREAM_PUMP_INTERVAL: Duration = Duration::from_millis(10);
 
+/// Milliseconds between two warnings about the same missing sound id.
+const MISSING_SOUND_REPEAT_MS: u64 = 5000;
+
 // FxPlayingState removed; we now track only the set of FX ids considered playing.
 
+/// Beat position of a single beat boundary: `(beat, bar, beat_in_bar)`.
+pub type BeatPosition = (u32, u32, u32);
+
+/// Tracks beat/bar boundaries for one music stream from its playback time.
+///
+/// Owned by the audio thread, one per music id configured through
+/// [`AudioCmd::SetMusicBpm`]. Feed it the stream's played time after each
+/// `update_stream()`; it reports a boundary the first time playback enters a
+/// new beat. Call [`reset`](Self::reset) whenever the stream restarts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeatClock {
+    bpm: f32,
+    beats_per_bar: u32,
+    last_beat: Option<u32>,
+}
+
+impl BeatClock {
+    /// Create a clock for `bpm` beats per minute and `beats_per_bar` beats per bar
+    /// (clamped to at least 1).
+    pub fn new(bpm: f32, beats_per_bar: u32) -> Self {
+        Self {
+            bpm,
+            beats_per_bar: beats_per_bar.max(1),
+            last_beat: None,
+        }
+    }
+
+    /// Forget the last reported beat so the next [`advance`](Self::advance)
+    /// reports beat 0 again.
+    pub fn reset(&mut self) {
+        self.last_beat = None;
+    }
+
+    /// Advance to `played` seconds of playback.
+    ///
+    /// Returns the beat position when playback has entered a beat that was not
+    /// yet reported. If several beats were skipped (e.g. a long hitch), only the
+    /// latest is reported.
+    pub fn advance(&mut self, played: f32) -> Option<BeatPosition> {
+        if self.bpm <= 0.0 || played < 0.0 {
+            return None;
+        }
+        let beat = (played * self.bpm / 60.0).floor() as u32;
+        if self.last_beat.is_some_and(|last| beat <= last) {
+            return None;
+        }
+        self.last_beat = Some(beat);
+        Some((beat, beat / self.beats_per_bar, beat % self.beats_per_bar))
+    }
+}
+
+/// Fade gain of the music channel, driven by [`AudioCmd::FadeMusicIn`] and
+/// [`AudioCmd::FadeMusicOut`].
+///
+/// Owned by the audio thread, which multiplies every stream's volume by
+/// [`gain`](Self::gain) and feeds it the elapsed time while music plays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MusicFade {
+    gain: f32,
+    target: f32,
+    /// Gain change per second.
+    rate: f32,
+}
+
+impl Default for MusicFade {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            target: 1.0,
+            rate: 0.0,
+        }
+    }
+}
+
+impl MusicFade {
+    /// Current gain in the `[0.0, 1.0]` range.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Whether the gain differs from full volume or is moving.
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Whether a fade-out is in progress.
+    pub fn is_fading_out(&self) -> bool {
+        self.target == 0.0
+    }
+
+    /// Restart from silence and reach full volume in `duration` seconds.
+    pub fn fade_in(&mut self, duration: f32) {
+        self.gain = 0.0;
+        self.fade_to(1.0, duration);
+    }
+
+    /// Reach silence in `duration` seconds from the current gain.
+    pub fn fade_out(&mut self, duration: f32) {
+        self.fade_to(0.0, duration);
+    }
+
+    fn fade_to(&mut self, target: f32, duration: f32) {
+        self.target = target;
+        if duration > 0.0 {
+            self.rate = (target - self.gain).abs() / duration;
+        } else {
+            self.gain = target;
+            self.rate = 0.0;
+        }
+    }
+
+    /// Advance by `delta` seconds. Returns `true` once a fade-out reaches
+    /// silence; the fade then resets to full volume, so the caller must stop
+    /// the music before applying the new gain.
+    pub fn advance(&mut self, delta: f32) -> bool {
+        let diff = self.target - self.gain;
+        let step = self.rate * delta;
+        if diff.abs() <= step {
+            self.gain = self.target;
+        } else {
+            self.gain += step.copysign(diff);
+        }
+        if self.target == 0.0 && self.gain == 0.0 {
+            *self = Self::default();
+            return true;
+        }
+        false
+    }
+}
+
+/// Set every loaded stream to its own volume times the `channel` volume.
+fn apply_music_volumes(
+    musics: &FxHashMap<String, Music>,
+    music_volumes: &FxHashMap<String, f32>,
+    channel: f32,
+) {
+    for (id, stream) in musics.iter() {
+        let own = music_volumes.get(id).copied().unwrap_or(1.0);
+        stream.set_volume(own * channel);
+    }
+}
+
 /// Drain any pending events from the audio thread and enqueue them into the
 /// ECS [`Messages<AudioMessage>`] mailbox.
 ///
@@ -71,6 +221,58 @@ pub fn update_bevy_audio_messages(mut msgs: ResMut<Messages<AudioMessage>>) {
     msgs.update();
 }
 
+/// Forward [`AudioMessage::MusicBeat`] and [`AudioMessage::MusicTrackerRow`]
+/// reports into the typed [`BeatEvent`] and [`TrackerEvent`] streams.
+///
+/// Run after [`update_bevy_audio_messages`] so reports polled this frame are seen.
+pub fn emit_music_sync_events(
+    mut reader: bevy_ecs::prelude::MessageReader<AudioMessage>,
+    mut beats: MessageWriter<BeatEvent>,
+    mut rows: MessageWriter<TrackerEvent>,
+) {
+    for msg in reader.read() {
+        match msg {
+            AudioMessage::MusicBeat {
+                id,
+                beat,
+                bar,
+                beat_in_bar,
+            } => {
+                beats.write(BeatEvent {
+                    id: id.clone(),
+                    beat: *beat,
+                    bar: *bar,
+                    beat_in_bar: *beat_in_bar,
+                });
+            }
+            AudioMessage::MusicTrackerRow {
+                id,
+                order,
+                pattern,
+                row,
+            } => {
+                rows.write(TrackerEvent {
+                    id: id.clone(),
+                    order: *order,
+                    pattern: *pattern,
+                    row: *row,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Advance the ECS message queue for [`BeatEvent`].
+pub fn update_bevy_beat_events(mut msgs: ResMut<Messages<BeatEvent>>) {
+    msgs.update();
+}
+
+/// Advance the ECS message queue for [`TrackerEvent`].
+pub fn update_bevy_tracker_events(mut msgs: ResMut<Messages<TrackerEvent>>) {
+    msgs.update();
+}
+
 /// Forward ECS AudioCmd messages to the audio thread via the AudioBridge sender.
 pub fn forward_audio_cmds(
     bridge: Res<AudioBridge>,
@@ -124,6 +326,20 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
     let mut looped: FxHashSet<String> = FxHashSet::default();
     let mut sounds: FxHashMap<String, ffi::Sound> = FxHashMap::default();
     let mut active_aliases: Vec<ffi::Sound> = Vec::new();
+    let mut beat_clocks: FxHashMap<String, BeatClock> = FxHashMap::default();
+    let mut tracker_layouts: FxHashMap<String, TrackerLayout> = FxHashMap::default();
+    let mut tracker_clocks: FxHashMap<String, TrackerClock> = FxHashMap::default();
+    // Per-stream volumes set via VolumeMusic; the applied volume is this times
+    // `music_volume` and the fade gain.
+    let mut music_volumes: FxHashMap<String, f32> = FxHashMap::default();
+    let mut music_volume: f32 = 1.0;
+    let mut music_fade = MusicFade::default();
+    let mut last_tick = Instant::now();
+    let mut sfx_volume: f32 = 1.0;
+    // An unknown sound id plays as silence; warn about it at most once per
+    // MISSING_SOUND_REPEAT_MS instead of on every play call.
+    let started = Instant::now();
+    let mut missing_sounds = MissingAssetWarnings::new(MISSING_SOUND_REPEAT_MS);
 
     'run: loop {
         // Block waiting for work instead of busy-polling on a fixed sleep.
@@ -151,6 +367,25 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
             }
         };
 
+        // 0) Advance the music fade by the time spent playing since the last
+        //    pass. While idle the thread blocks, so nothing is advanced.
+        let now = Instant::now();
+        let delta = now.duration_since(last_tick).as_secs_f32();
+        last_tick = now;
+        if !playing.is_empty() && music_fade.is_active() {
+            if music_fade.advance(delta) {
+                debug!(target: "audio", "fade out finished");
+                for id in playing.drain() {
+                    if let Some(music) = musics.get(&id) {
+                        music.stop_stream();
+                        let _ = tx_evt.send(AudioMessage::MusicStopped { id: id.clone() });
+                    }
+                }
+                looped.clear();
+            }
+            apply_music_volumes(&musics, &music_volumes, music_volume * music_fade.gain());
+        }
+
         // 1) Drain commands: the one we just blocked for (if any), plus any
         //    others already queued behind it.
         for cmd in first.into_iter().chain(rx_cmd.try_iter()) {
@@ -159,8 +394,23 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                     Ok(music) => {
                         // log then insert/send
                         debug!(target: "audio", "loaded id='{}' path='{}'", id, path);
+                        music.set_volume(music_volume * music_fade.gain());
+                        music_volumes.remove(&id);
                         musics.insert(id.clone(), music);
-                        let _ = tx_evt.send(AudioMessage::MusicLoaded { id });
+                        tracker_clocks.remove(&id);
+                        match std::fs::read(&path)
+                            .ok()
+                            .and_then(|bytes| TrackerLayout::from_file_bytes(&path, &bytes))
+                        {
+                            Some(layout) => {
+                                tracker_layouts.insert(id.clone(), layout);
+                            }
+                            None => {
+                                tracker_layouts.remove(&id);
+                            }
+                        }
+                        let bytes = std::fs::metadata(&path).map_or(0, |m| m.len() as usize);
+                        let _ = tx_evt.send(AudioMessage::MusicLoaded { id, bytes });
                     }
                     Err(e) => {
                         error!(
@@ -177,11 +427,22 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                     id,
                     looped: want_loop,
                 } => {
+                    if music_fade.is_fading_out() {
+                        debug!(target: "audio", "fade out cancelled by play id='{}'", id);
+                        music_fade = MusicFade::default();
+                        apply_music_volumes(&musics, &music_volumes, music_volume);
+                    }
                     if let Some(music) = musics.get(&id) {
                         debug!(target: "audio", "play start id='{}' looped={}", id, want_loop);
                         music.seek_stream(0.0);
                         music.play_stream();
                         playing.insert(id.clone());
+                        if let Some(clock) = beat_clocks.get_mut(&id) {
+                            clock.reset();
+                        }
+                        if let Some(clock) = tracker_clocks.get_mut(&id) {
+                            clock.reset();
+                        }
                         if want_loop {
                             looped.insert(id.clone());
                         } else {
@@ -228,11 +489,71 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                 AudioCmd::VolumeMusic { id, vol } => {
                     if let Some(music) = musics.get(&id) {
                         debug!(target: "audio", "volume id='{}' vol={}", id, vol);
-                        music.set_volume(vol);
+                        music.set_volume(vol * music_volume * music_fade.gain());
+                        music_volumes.insert(id.clone(), vol);
                         let _ = tx_evt.send(AudioMessage::MusicVolumeChanged { id, vol });
                     }
                 }
+                AudioCmd::SetVolumes { master, music, sfx } => {
+                    debug!(
+                        target: "audio", "volumes master={} music={} sfx={}",
+                        master, music, sfx
+                    );
+                    unsafe { ffi::SetMasterVolume(master.clamp(0.0, 1.0)) };
+                    music_volume = music.clamp(0.0, 1.0);
+                    sfx_volume = sfx.clamp(0.0, 1.0);
+                    apply_music_volumes(&musics, &music_volumes, music_volume * music_fade.gain());
+                }
+                AudioCmd::FadeMusicIn { duration } => {
+                    debug!(target: "audio", "fade in duration={}", duration);
+                    music_fade.fade_in(duration);
+                    apply_music_volumes(&musics, &music_volumes, music_volume * music_fade.gain());
+                }
+                AudioCmd::FadeMusicOut { duration } => {
+                    // Nothing to fade; a later PlayMusic must not start silent.
+                    if !playing.is_empty() {
+                        debug!(target: "audio", "fade out duration={}", duration);
+                        music_fade.fade_out(duration);
+                        apply_music_volumes(
+                            &musics,
+                            &music_volumes,
+                            music_volume * music_fade.gain(),
+                        );
+                    }
+                }
+                AudioCmd::SetMusicBpm {
+                    id,
+                    bpm,
+                    beats_per_bar,
+                } => {
+                    debug!(
+                        target: "audio", "bpm id='{}' bpm={} beats_per_bar={}",
+                        id, bpm, beats_per_bar
+                    );
+                    if bpm > 0.0 {
+                        beat_clocks.insert(id, BeatClock::new(bpm, beats_per_bar));
+                    } else {
+                        beat_clocks.remove(&id);
+                    }
+                }
+                AudioCmd::SetMusicTrackerEvents { id, enabled } => {
+                    if !enabled {
+                        tracker_clocks.remove(&id);
+                    } else if let Some(layout) = tracker_layouts.get(&id) {
+                        debug!(target: "audio", "tracker events id='{}'", id);
+                        tracker_clocks.insert(id, TrackerClock::new(layout.clone()));
+                    } else {
+                        error!(
+                            target: "audio", "tracker events failed id='{}' reason='not a loaded .xm/.mod'",
+                            id
+                        );
+                    }
+                }
                 AudioCmd::UnloadMusic { id } => {
+                    beat_clocks.remove(&id);
+                    tracker_layouts.remove(&id);
+                    tracker_clocks.remove(&id);
+                    music_volumes.remove(&id);
                     if let Some(music) = musics.remove(&id) {
                         debug!(target: "audio", "unload id='{}'", id);
                         drop(music);
@@ -242,8 +563,12 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                 AudioCmd::UnloadAllMusic => {
                     debug!(target: "audio", "unload all");
                     musics.clear();
+                    music_volumes.clear();
                     playing.clear();
                     looped.clear();
+                    beat_clocks.clear();
+                    tracker_layouts.clear();
+                    tracker_clocks.clear();
                     let _ = tx_evt.send(AudioMessage::MusicUnloadedAll);
                 }
                 AudioCmd::LoadFx { id, path } => {
@@ -273,18 +598,27 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                         });
                     } else {
                         debug!(target: "audio", "fx loaded id='{}' path='{}'", id, path);
+                        let bytes = sound.frameCount as usize
+                            * sound.stream.channels as usize
+                            * sound.stream.sampleSize as usize
+                            / 8;
                         sounds.insert(id.clone(), sound);
-                        let _ = tx_evt.send(AudioMessage::FxLoaded { id });
+                        let _ = tx_evt.send(AudioMessage::FxLoaded { id, bytes });
                     }
                 }
                 AudioCmd::PlayFx { id } => {
                     if let Some(sound) = sounds.get(&id) {
                         debug!(target: "audio", "fx play id='{}'", id);
                         let alias = unsafe { ffi::LoadSoundAlias(*sound) };
+                        unsafe { ffi::SetSoundVolume(alias, sfx_volume) };
                         unsafe { ffi::PlaySound(alias) };
                         active_aliases.push(alias);
-                    } else {
-                        error!(target: "audio", "fx play failed id='{}' reason='not loaded'", id);
+                    } else if missing_sounds.should_warn(
+                        AssetKind::Sound,
+                        &id,
+                        started.elapsed().as_millis() as u64,
+                    ) {
+                        warn!(target: "audio", "fx play id='{}' not loaded, playing silence", id);
                     }
                 }
                 AudioCmd::PlayFxPitched { id, pitch } => {
@@ -292,10 +626,15 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                         debug!(target: "audio", "fx play pitched id='{}' pitch={}", id, pitch);
                         let alias = unsafe { ffi::LoadSoundAlias(*sound) };
                         unsafe { ffi::SetSoundPitch(alias, pitch) };
+                        unsafe { ffi::SetSoundVolume(alias, sfx_volume) };
                         unsafe { ffi::PlaySound(alias) };
                         active_aliases.push(alias);
-                    } else {
-                        error!(target: "audio", "fx play pitched failed id='{}' reason='not loaded'", id);
+                    } else if missing_sounds.should_warn(
+                        AssetKind::Sound,
+                        &id,
+                        started.elapsed().as_millis() as u64,
+                    ) {
+                        warn!(target: "audio", "fx play pitched id='{}' not loaded, playing silence", id);
                     }
                 }
                 AudioCmd::StopAllFx => {
@@ -355,6 +694,24 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                 music.update_stream();
                 let len = music.get_time_length();
                 let played = music.get_time_played();
+                if let Some((beat, bar, beat_in_bar)) =
+                    beat_clocks.get_mut(id).and_then(|clock| clock.advance(played))
+                {
+                    let _ = tx_evt.send(AudioMessage::MusicBeat {
+                        id: id.clone(),
+                        beat,
+                        bar,
+                        beat_in_bar,
+                    });
+                }
+                if let Some(pos) = tracker_clocks.get_mut(id).and_then(|clock| clock.advance(played)) {
+                    let _ = tx_evt.send(AudioMessage::MusicTrackerRow {
+                        id: id.clone(),
+                        order: pos.order,
+                        pattern: pos.pattern,
+                        row: pos.row,
+                    });
+                }
                 if played >= len - 0.01 {
                     ended.push(id.clone());
                 }
@@ -368,6 +725,12 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
                     music.stop_stream();
                     music.seek_stream(0.0);
                     music.play_stream();
+                    if let Some(clock) = beat_clocks.get_mut(id) {
+                        clock.reset();
+                    }
+                    if let Some(clock) = tracker_clocks.get_mut(id) {
+                        clock.reset();
+                    }
                     let _ = tx_evt.send(AudioMessage::MusicPlayStarted { id: id.clone() });
                 }
             } else {
@@ -397,3 +760,79 @@ pub fn audio_thread(rx_cmd: Receiver<AudioCmd>, tx_evt: Sender<AudioMessage>) {
 
     // On exit, musics and sounds drop before `audio`, satisfying lifetimes
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beat_clock_reports_first_beat_immediately() {
+        let mut clock = BeatClock::new(120.0, 4);
+        assert_eq!(clock.advance(0.0), Some((0, 0, 0)));
+        assert_eq!(clock.advance(0.2), None);
+    }
+
+    #[test]
+    fn beat_clock_reports_each_boundary_once() {
+        // 120 BPM -> one beat every 0.5s
+        let mut clock = BeatClock::new(120.0, 4);
+        clock.advance(0.0);
+        assert_eq!(clock.advance(0.5), Some((1, 0, 1)));
+        assert_eq!(clock.advance(0.6), None);
+        assert_eq!(clock.advance(2.0), Some((4, 1, 0)));
+    }
+
+    #[test]
+    fn beat_clock_skips_to_latest_beat_after_hitch() {
+        let mut clock = BeatClock::new(60.0, 3);
+        clock.advance(0.0);
+        assert_eq!(clock.advance(5.1), Some((5, 1, 2)));
+    }
+
+    #[test]
+    fn beat_clock_reset_restarts_counting() {
+        let mut clock = BeatClock::new(120.0, 4);
+        clock.advance(3.0);
+        clock.reset();
+        assert_eq!(clock.advance(0.0), Some((0, 0, 0)));
+    }
+
+    #[test]
+    fn beat_clock_zero_bpm_is_silent() {
+        let mut clock = BeatClock::new(0.0, 4);
+        assert_eq!(clock.advance(1.0), None);
+    }
+
+    #[test]
+    fn music_fade_in_ramps_from_silence() {
+        let mut fade = MusicFade::default();
+        fade.fade_in(2.0);
+        assert_eq!(fade.gain(), 0.0);
+        assert!(!fade.advance(1.0));
+        assert_eq!(fade.gain(), 0.5);
+        assert!(!fade.advance(5.0));
+        assert_eq!(fade.gain(), 1.0);
+        assert!(!fade.is_active());
+    }
+
+    #[test]
+    fn music_fade_out_reports_silence_once_and_resets() {
+        let mut fade = MusicFade::default();
+        fade.fade_in(4.0);
+        fade.advance(2.0);
+        // From half volume, reaches silence in the requested time.
+        fade.fade_out(1.0);
+        assert!(fade.is_fading_out());
+        assert!(!fade.advance(0.5));
+        assert_eq!(fade.gain(), 0.25);
+        assert!(fade.advance(0.5));
+        assert_eq!(fade, MusicFade::default());
+        assert!(!fade.advance(0.5));
+    }
+
+    #[test]
+    fn beat_clock_clamps_beats_per_bar() {
+        let mut clock = BeatClock::new(60.0, 0);
+        assert_eq!(clock.advance(2.0), Some((2, 2, 0)));
+    }
+}
@@ -0,0 +1,116 @@
+//! Audio trigger systems.
+//!
+//! Play the sound effects of [`AudioTriggers`] components:
+//!
+//! - [`audio_triggers_system`] – advances cooldowns and plays spawn sounds
+//!   of newly added triggers; runs before the collision detector so each
+//!   frame starts a fresh contact check
+//! - [`audio_trigger_collision_observer`] – plays collision sounds from the
+//!   frame's [`CollisionBatch`] when contact with a matching group starts
+//! - [`audio_trigger_despawn_observer`] – plays despawn sounds
+//!
+//! The scene cleanup removes [`AudioTriggers`] before despawning, so leaving
+//! a scene is silent.
+//!
+//! # Related
+//!
+//! - [`crate::components::audiotriggers`] – trigger definitions and cooldowns
+
+use bevy_ecs::prelude::*;
+
+use crate::components::audiotriggers::{AudioTriggerEvent, AudioTriggers};
+use crate::events::audio::AudioCmd;
+use crate::events::collision::CollisionBatch;
+use crate::resources::worldtime::WorldTime;
+
+/// Advances trigger cooldowns and plays the spawn sounds of new triggers.
+pub fn audio_triggers_system(
+    mut query: Query<&mut AudioTriggers>,
+    time: Res<WorldTime>,
+    mut audio_cmds: MessageWriter<AudioCmd>,
+) {
+    for mut triggers in query.iter_mut() {
+        triggers.tick(time.delta);
+        if triggers.is_added() {
+            for id in triggers.fire(&AudioTriggerEvent::Spawned) {
+                audio_cmds.write(AudioCmd::PlayFx { id });
+            }
+        }
+    }
+}
+
+/// Plays collision sounds for both entities of every pair in the batch.
+pub fn audio_trigger_collision_observer(
+    trigger: On<CollisionBatch>,
+    mut query: Query<&mut AudioTriggers>,
+    mut audio_cmds: MessageWriter<AudioCmd>,
+) {
+    if query.is_empty() {
+        return;
+    }
+    for pair in &trigger.event().pairs {
+        for (entity, other) in [(pair.a, pair.b), (pair.b, pair.a)] {
+            let Ok(mut triggers) = query.get_mut(entity) else {
+                continue;
+            };
+            for id in triggers.touch(pair.group_of(other)) {
+                audio_cmds.write(AudioCmd::PlayFx { id });
+            }
+        }
+    }
+}
+
+/// Plays the despawn sounds of an entity being despawned.
+pub fn audio_trigger_despawn_observer(
+    trigger: On<Despawn, AudioTriggers>,
+    mut query: Query<&mut AudioTriggers>,
+    mut audio_cmds: MessageWriter<AudioCmd>,
+) {
+    let Ok(mut triggers) = query.get_mut(trigger.event().entity) else {
+        return;
+    };
+    for id in triggers.fire(&AudioTriggerEvent::Despawned) {
+        audio_cmds.write(AudioCmd::PlayFx { id });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::message::Messages;
+
+    fn played(world: &mut World) -> Vec<String> {
+        world
+            .resource_mut::<Messages<AudioCmd>>()
+            .drain()
+            .filter_map(|cmd| match cmd {
+                AudioCmd::PlayFx { id } => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plays_spawn_and_despawn_sounds() {
+        let mut world = World::new();
+        world.init_resource::<Messages<AudioCmd>>();
+        world.insert_resource(WorldTime::default());
+        world.add_observer(audio_trigger_despawn_observer);
+
+        let entity = world
+            .spawn(
+                AudioTriggers::default()
+                    .with(AudioTriggerEvent::Spawned, "appear", 0.0)
+                    .with(AudioTriggerEvent::Despawned, "vanish", 0.0),
+            )
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(audio_triggers_system);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(played(&mut world), vec!["appear"]);
+
+        world.despawn(entity);
+        assert_eq!(played(&mut world), vec!["vanish"]);
+    }
+}
@@ -0,0 +1,47 @@
+//! Blackboard TTL system.
+//!
+//! [`blackboard_ttl_system`] counts down timed entries of every
+//! [`Blackboard`](crate::components::blackboard::Blackboard) and removes the
+//! expired ones. The countdown uses the scaled frame delta, so slow motion
+//! also slows down AI forgetting.
+
+use bevy_ecs::prelude::*;
+
+use crate::components::blackboard::Blackboard;
+use crate::resources::worldtime::WorldTime;
+
+/// Expire timed blackboard entries.
+///
+/// Boards without timed entries are skipped without being marked as changed,
+/// so `Changed<Blackboard>` filters only fire on real writes and expiries.
+pub fn blackboard_ttl_system(world_time: Res<WorldTime>, mut query: Query<&mut Blackboard>) {
+    let dt = world_time.delta;
+    for mut board in query.iter_mut() {
+        if board.has_expiring() {
+            board.tick(dt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn expired_entries_are_removed() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime::default());
+        let mut board = Blackboard::default();
+        board.set("home", true);
+        board.set_with_ttl("last_seen_player", 3, 0.25);
+        let e = world.spawn(board).id();
+
+        world.resource_mut::<WorldTime>().delta = 0.5;
+        world.run_system_once(blackboard_ttl_system).unwrap();
+
+        let board = world.get::<Blackboard>(e).unwrap();
+        assert!(board.contains("home"));
+        assert!(!board.contains("last_seen_player"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Camera effects system.
+//!
+//! [`camera_effects_system`] advances the [`CameraEffects`] shake, zoom and
+//! pan every frame; the render system draws the world through them.
+//! [`CameraEffect`] starts one (Lua: `engine.camera_shake`,
+//! `engine.camera_zoom_to`, `engine.camera_zoom_punch`,
+//! `engine.camera_pan_to`, `engine.camera_release_pan`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::cameraeffects`] – effect state and how it is layered on the camera
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::tween::Easing;
+use crate::resources::cameraeffects::CameraEffects;
+use crate::resources::worldtime::WorldTime;
+
+/// Command starting a camera effect.
+#[derive(Debug, Clone, Copy)]
+pub enum CameraEffect {
+    Shake {
+        intensity: f32,
+        duration: f32,
+    },
+    ZoomTo {
+        zoom: f32,
+        duration: f32,
+        easing: Easing,
+    },
+    ZoomPunch {
+        amount: f32,
+        duration: f32,
+    },
+    PanTo {
+        target: Vector2,
+        duration: f32,
+        easing: Easing,
+    },
+    ReleasePan {
+        duration: f32,
+        easing: Easing,
+    },
+}
+
+impl Command for CameraEffect {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let mut effects = world.get_resource_or_insert_with(CameraEffects::default);
+        match self {
+            CameraEffect::Shake {
+                intensity,
+                duration,
+            } => effects.shake(intensity, duration),
+            CameraEffect::ZoomTo {
+                zoom,
+                duration,
+                easing,
+            } => effects.zoom_to(zoom, duration, easing),
+            CameraEffect::ZoomPunch { amount, duration } => effects.punch_zoom(amount, duration),
+            CameraEffect::PanTo {
+                target,
+                duration,
+                easing,
+            } => effects.pan_to(target, duration, easing),
+            CameraEffect::ReleasePan { duration, easing } => effects.release_pan(duration, easing),
+        }
+    }
+}
+
+/// Advances the camera effects while any of them is running.
+///
+/// Scheduling: runs after `camera_follow_system` and before `render_system`.
+pub fn camera_effects_system(mut effects: ResMut<CameraEffects>, time: Res<WorldTime>) {
+    if effects.is_animating() {
+        effects.advance(time.delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn effects_run_with_world_time() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.25,
+            real_delta: 0.25,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.commands().queue(CameraEffect::Shake {
+            intensity: 1.0,
+            duration: 0.5,
+        });
+        world.commands().queue(CameraEffect::ZoomTo {
+            zoom: 2.0,
+            duration: 0.5,
+            easing: Easing::Linear,
+        });
+        world.flush();
+
+        world.run_system_once(camera_effects_system).unwrap();
+        assert_eq!(world.resource::<CameraEffects>().trauma(), 0.5);
+        world.run_system_once(camera_effects_system).unwrap();
+        let effects = world.resource::<CameraEffects>();
+        assert_eq!(effects.trauma(), 0.0);
+        assert_eq!(effects.zoom_factor(), 2.0);
+        assert!(!effects.is_animating());
+    }
+}
@@ -0,0 +1,94 @@
+//! Cinematic mode system.
+//!
+//! [`cinematic_system`] runs right after the hardware input is read: it
+//! slides the [`CinematicMode`] bars and, while the mode is active, clears the
+//! gameplay actions from [`InputState`] before anything else reads them.
+//! The bars themselves are drawn by the render system.
+//!
+//! [`SetCinematicMode`] turns the mode on or off (Lua: `engine.cinematic_mode`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::cinematic`] – bar state and the list of kept actions
+
+use bevy_ecs::prelude::*;
+
+use crate::resources::cinematic::CinematicMode;
+use crate::resources::input::InputState;
+use crate::resources::worldtime::WorldTime;
+
+/// Command turning cinematic mode on or off, optionally changing how long the
+/// bars take to slide.
+pub struct SetCinematicMode {
+    pub enabled: bool,
+    pub duration: Option<f32>,
+}
+
+impl Command for SetCinematicMode {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let mut cinematic = world.get_resource_or_insert_with(CinematicMode::default);
+        cinematic.set_active(self.enabled);
+        if let Some(duration) = self.duration {
+            cinematic.duration = duration.max(0.0);
+        }
+    }
+}
+
+/// Slides the letterbox bars and locks gameplay input while active.
+pub fn cinematic_system(
+    mut cinematic: ResMut<CinematicMode>,
+    mut input: ResMut<InputState>,
+    time: Res<WorldTime>,
+) {
+    if cinematic.is_animating() {
+        cinematic.advance(time.delta);
+    }
+    if cinematic.is_active() {
+        CinematicMode::suppress(&mut input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn locks_input_until_turned_off() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.1,
+            real_delta: 0.1,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.init_resource::<CinematicMode>();
+        world.commands().queue(SetCinematicMode {
+            enabled: true,
+            duration: Some(0.2),
+        });
+        world.flush();
+
+        let mut input = InputState::default();
+        input.action_1.just_pressed = true;
+        input.action_back.just_pressed = true;
+        world.insert_resource(input.clone());
+        world.run_system_once(cinematic_system).unwrap();
+        assert!(!world.resource::<InputState>().action_1.just_pressed);
+        assert!(world.resource::<InputState>().action_back.just_pressed);
+        assert_eq!(world.resource::<CinematicMode>().progress(), 0.5);
+
+        world.commands().queue(SetCinematicMode {
+            enabled: false,
+            duration: None,
+        });
+        world.flush();
+        world.insert_resource(input);
+        world.run_system_once(cinematic_system).unwrap();
+        assert!(world.resource::<InputState>().action_1.just_pressed);
+        assert_eq!(world.resource::<CinematicMode>().progress(), 0.0);
+    }
+}
@@ -16,12 +16,16 @@
 
 use bevy_ecs::prelude::*;
 use raylib::prelude::Rectangle;
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
 
 use crate::components::boxcollider::BoxCollider;
-use crate::components::collision::{BoxSides, get_colliding_sides};
+use crate::components::collision::{BoxSides, CollisionRule, get_colliding_sides};
 use crate::components::globaltransform2d::GlobalTransform2D;
 use crate::components::group::Group;
 use crate::components::mapposition::MapPosition;
+use crate::components::tags::{Tags, entity_labels};
+use crate::events::collision::CollisionEvent;
 
 /// Resolve the world position of an entity.
 ///
@@ -77,6 +81,77 @@ pub fn resolve_groups<'q>(
     Some((ga.name(), gb.name()))
 }
 
+/// Labels an entity can be matched by: its [`Group`] name plus its [`Tags`].
+pub type EntityLabels<'q> = SmallVec<[&'q str; 4]>;
+
+/// Collect the group name and tags of an entity for rule matching.
+///
+/// Returns an empty list if the entity has neither a [`Group`] nor [`Tags`].
+pub fn resolve_labels<'q>(
+    groups: &'q Query<&Group>,
+    tags: &'q Query<&Tags>,
+    entity: Entity,
+) -> EntityLabels<'q> {
+    entity_labels(groups.get(entity).ok(), tags.get(entity).ok()).collect()
+}
+
+/// One rule invocation planned by [`plan_collision_batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedCall {
+    /// Index into the rule slice passed to [`plan_collision_batch`].
+    pub rule: usize,
+    /// Index of the pair in the batch.
+    pub pair: usize,
+    /// Entity matching the rule's `group_a`.
+    pub a: Entity,
+    /// Entity matching the rule's `group_b`.
+    pub b: Entity,
+}
+
+/// Match every pair of a [`CollisionBatch`](crate::events::collision::CollisionBatch)
+/// against `rules` in one pass, writing the calls to make into `out`.
+///
+/// `rules` must be sorted by descending priority. Labels are resolved once per
+/// entity. For each pair, matching stops after the first exclusive rule, just
+/// as for a single [`CollisionEvent`]. `out` is grouped by rule in `rules`
+/// order and keeps the detection order of the pairs within a rule.
+pub fn plan_collision_batch<C>(
+    rules: &[&CollisionRule<C>],
+    pairs: &[CollisionEvent],
+    groups: &Query<&Group>,
+    tags: &Query<&Tags>,
+    out: &mut Vec<PlannedCall>,
+) {
+    out.clear();
+    let mut labels: FxHashMap<Entity, EntityLabels> = FxHashMap::default();
+    for (pair, event) in pairs.iter().enumerate() {
+        for entity in [event.a, event.b] {
+            labels
+                .entry(entity)
+                .or_insert_with(|| resolve_labels(groups, tags, entity));
+        }
+        let (labels_a, labels_b) = (&labels[&event.a], &labels[&event.b]);
+        if labels_a.is_empty() || labels_b.is_empty() {
+            continue;
+        }
+        for (index, rule) in rules.iter().enumerate() {
+            if let Some((a, b)) = rule.match_labels_and_order(event.a, event.b, labels_a, labels_b)
+            {
+                out.push(PlannedCall {
+                    rule: index,
+                    pair,
+                    a,
+                    b,
+                });
+                if rule.exclusive {
+                    break;
+                }
+            }
+        }
+    }
+    out.sort_by_key(|call| call.rule);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +209,28 @@ mod tests {
         assert_eq!(resolve_groups(&groups, a, b), None);
     }
 
+    // --- resolve_labels tests ---
+
+    #[test]
+    fn resolve_labels_group_and_tags() {
+        let mut world = World::new();
+        let a = world
+            .spawn((Group::new("enemy"), Tags::new(["flying", "enemy"])))
+            .id();
+        let b = world.spawn(Tags::new(["pickup"])).id();
+        let c = world.spawn_empty().id();
+
+        let mut state = SystemState::<(Query<&Group>, Query<&Tags>)>::new(&mut world);
+        let (groups, tags) = state.get(&world).expect("label queries should fetch");
+
+        assert_eq!(
+            resolve_labels(&groups, &tags, a).as_slice(),
+            &["enemy", "flying"]
+        );
+        assert_eq!(resolve_labels(&groups, &tags, b).as_slice(), &["pickup"]);
+        assert!(resolve_labels(&groups, &tags, c).is_empty());
+    }
+
     // --- compute_sides tests ---
 
     #[test]
@@ -303,4 +400,56 @@ mod tests {
         assert!((r.width - 30.0).abs() < f32::EPSILON);
         assert!((r.height - 40.0).abs() < f32::EPSILON);
     }
+
+    // --- plan_collision_batch tests ---
+
+    fn touching(a: Entity, b: Entity) -> CollisionEvent {
+        let rect = Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 10.0,
+            height: 10.0,
+        };
+        CollisionEvent::from_rects(a, b, &rect, &rect).unwrap()
+    }
+
+    #[test]
+    fn plan_collision_batch_groups_by_rule_and_honours_exclusive() {
+        let mut world = World::new();
+        let ball = world.spawn(Group::new("ball")).id();
+        let brick_1 = world.spawn(Group::new("brick")).id();
+        let brick_2 = world.spawn(Group::new("brick")).id();
+        let wall = world.spawn(Group::new("wall")).id();
+
+        let sound = CollisionRule::new("ball", "*", "sound")
+            .with_priority(10)
+            .with_exclusive(false);
+        let brick = CollisionRule::new("ball", "brick", "brick");
+        let fallback = CollisionRule::new("ball", "*", "fallback").with_priority(-1);
+        let rules = [&sound, &brick, &fallback];
+        // Detector order is arbitrary, so the pair may come in either order.
+        let pairs = [
+            touching(brick_1, ball),
+            touching(ball, wall),
+            touching(ball, brick_2),
+        ];
+
+        let mut state = SystemState::<(Query<&Group>, Query<&Tags>)>::new(&mut world);
+        let (groups, tags) = state.get(&world).expect("label queries should fetch");
+        let mut plan = Vec::new();
+        plan_collision_batch(&rules, &pairs, &groups, &tags, &mut plan);
+
+        let calls: Vec<_> = plan.iter().map(|c| (c.rule, c.pair, c.a, c.b)).collect();
+        assert_eq!(
+            calls,
+            vec![
+                (0, 0, ball, brick_1),
+                (0, 1, ball, wall),
+                (0, 2, ball, brick_2),
+                (1, 0, ball, brick_1),
+                (1, 2, ball, brick_2),
+                (2, 1, ball, wall),
+            ]
+        );
+    }
 }
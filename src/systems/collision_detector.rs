@@ -2,57 +2,380 @@
 //!
 //! This module provides the [`collision_detector`] system which performs pairwise
 //! AABB overlap checks and emits [`CollisionEvent`](crate::events::collision::CollisionEvent)
-//! for each detected collision.
+//! for each detected collision, followed by one
+//! [`CollisionBatch`](crate::events::collision::CollisionBatch) with all of them.
+//!
+//! Pairs where either entity has
+//! [`PixelCollision`](crate::components::pixelcollision::PixelCollision) go
+//! through a narrow phase against the sprites' alpha masks before an event is
+//! emitted. Pairs with one [`Slope`](crate::components::slope::Slope) entity
+//! are tested against its triangle and carry its contact instead of the
+//! boxes' one.
 //!
 //! This system is pure Rust with no Lua dependency and is shared by both
 //! the Lua and Rust game paths.
 //!
 //! # Related
 //!
+//! - [`crate::systems::rust_collision`] – Rust collision observer
 //! - [`crate::systems::lua_collision`] – Lua-based collision observer
 //! - [`crate::components::boxcollider::BoxCollider`] – axis-aligned collider
+//! - [`crate::resources::alphamask::AlphaMasks`] – texture alpha masks for the narrow phase
 //! - [`crate::events::collision::CollisionEvent`] – emitted on each collision
 
 use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
 
-use crate::components::boxcollider::BoxCollider;
+use crate::components::boxcollider::{BoxCollider, ColliderDisabled};
 use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
 use crate::components::mapposition::MapPosition;
-use crate::events::collision::CollisionEvent;
+use crate::components::pixelcollision::PixelCollision;
+use crate::components::rotation::Rotation;
+use crate::components::scale::Scale;
+use crate::components::slope::Slope;
+use crate::components::sprite::Sprite;
+use crate::events::collision::{CollisionBatch, CollisionEvent};
+use crate::resources::alphamask::{AlphaMask, AlphaMasks};
+use crate::resources::framearena::FrameArena;
+use crate::systems::render::geometry::{
+    SpriteRenderGeometry, compute_sprite_geometry, resolve_world_transform,
+};
 
 /// Broad-phase pairwise overlap test with event emission.
 ///
-/// Uses ECS `iter_combinations_mut()` to efficiently iterate unique pairs,
-/// checks overlap, and triggers an event for each collision. Observers can
-/// react to despawn, apply damage, or play sounds.
+/// Snapshots every collider's world rectangle into the [`FrameArena`] once,
+/// so rectangles and pixel shapes are computed per collider rather than per
+/// pair, then checks each unique pair of the snapshot for overlap and
+/// triggers an event for each collision carrying the colliding sides,
+/// penetration depth, contact normal and group names. Pairs come in the
+/// query's iteration order, as with `iter_combinations()`. The events are
+/// gathered in the arena too and, once all pairs are checked, triggered
+/// again as a single [`CollisionBatch`], which the Rust and Lua collision
+/// rules are dispatched from; the per-pair events are for custom observers.
+/// Copying the pairs into the batch is the system's only heap allocation,
+/// and only on frames with collisions. Entities marked [`ColliderDisabled`]
+/// are skipped.
+///
+/// Overlapping pairs involving a [`PixelCollision`] entity are only reported
+/// if [`pixels_overlap`] finds a shared solid point; see its docs. Pairs
+/// with exactly one [`Slope`] entity are only reported if [`Slope::contact`]
+/// finds one, and their event takes its depth and normal. Two slopes collide
+/// as boxes.
+///
+/// Without an [`AlphaMasks`] resource every pair is tested as boxes only.
+#[allow(clippy::type_complexity)]
 pub fn collision_detector(
-    mut query: Query<(
-        Entity,
-        &MapPosition,
-        &BoxCollider,
-        Option<&GlobalTransform2D>,
-    )>,
+    query: Query<
+        (
+            Entity,
+            &MapPosition,
+            &BoxCollider,
+            Option<&GlobalTransform2D>,
+            Option<&Group>,
+            Option<(&Sprite, Option<&Scale>, Option<&Rotation>)>,
+            Has<PixelCollision>,
+            Option<&Slope>,
+        ),
+        Without<ColliderDisabled>,
+    >,
+    masks: Option<Res<AlphaMasks>>,
+    mut arena: ResMut<FrameArena>,
     mut commands: Commands,
 ) {
     crate::tracy::tracy_span!("collision_detector");
-    let mut combos = query.iter_combinations_mut();
-    while let Some(
-        [
-            (entity_a, position_a, collider_a, maybe_gt_a),
-            (entity_b, position_b, collider_b, maybe_gt_b),
-        ],
-    ) = combos.fetch_next()
-    {
+    let scratch = arena.scratch();
+    let mut colliders = scratch.vec_with_capacity(query.iter().len());
+    for (entity, position, collider, maybe_gt, group, sprite, pixel, slope) in query.iter() {
         // Use world position from GlobalTransform2D when available, fall back to local
-        let world_pos_a = maybe_gt_a.map_or(position_a.pos, |gt| gt.position);
-        let world_pos_b = maybe_gt_b.map_or(position_b.pos, |gt| gt.position);
-        let rect_a = collider_a.as_rectangle(world_pos_a);
-        let rect_b = collider_b.as_rectangle(world_pos_b);
-        if rect_a.check_collision_recs(&rect_b) {
-            commands.trigger(CollisionEvent {
-                a: entity_a,
-                b: entity_b,
-            });
+        let world_pos = maybe_gt.map_or(position.pos, |gt| gt.position);
+        let shape = sprite.filter(|_| pixel).and_then(|(sprite, scale, rot)| {
+            let mask = masks.as_ref()?.get(&sprite.tex_key)?;
+            let (pos, scale, rot) =
+                resolve_world_transform(*position, scale.copied(), rot.copied(), maybe_gt.copied());
+            Some(PixelShape::new(
+                mask,
+                sprite,
+                &pos,
+                scale.as_ref(),
+                rot.as_ref(),
+            ))
+        });
+        colliders.push((
+            entity,
+            collider.as_rectangle(world_pos),
+            group.copied(),
+            shape,
+            slope.copied(),
+        ));
+    }
+
+    let mut batch = scratch.vec();
+    for (i, (entity_a, rect_a, group_a, shape_a, slope_a)) in colliders.iter().enumerate() {
+        for (entity_b, rect_b, group_b, shape_b, slope_b) in &colliders[i + 1..] {
+            if let Some(event) = CollisionEvent::from_rects(*entity_a, *entity_b, rect_a, rect_b) {
+                if (shape_a.is_some() || shape_b.is_some())
+                    && !pixels_overlap(rect_a, shape_a.as_ref(), rect_b, shape_b.as_ref())
+                {
+                    continue;
+                }
+                // Slope::contact's normal points toward the slope.
+                let event = match (slope_a, slope_b) {
+                    (Some(slope), None) => match slope.contact(rect_a, rect_b) {
+                        Some((depth, normal)) => event.with_contact(depth, -normal),
+                        None => continue,
+                    },
+                    (None, Some(slope)) => match slope.contact(rect_b, rect_a) {
+                        Some((depth, normal)) => event.with_contact(depth, normal),
+                        None => continue,
+                    },
+                    _ => event,
+                };
+                let event = event.with_groups(group_a.as_ref(), group_b.as_ref());
+                commands.trigger(event.clone());
+                batch.push(event);
+            }
+        }
+    }
+    if !batch.is_empty() {
+        commands.trigger(CollisionBatch {
+            pairs: batch.to_vec(),
+        });
+    }
+}
+
+/// A sprite's current frame placed in the world, for sampling its alpha mask.
+pub struct PixelShape<'m> {
+    mask: &'m AlphaMask,
+    geom: SpriteRenderGeometry,
+    src: Rectangle,
+    flip_h: bool,
+    flip_v: bool,
+}
+
+impl<'m> PixelShape<'m> {
+    /// Place `sprite`'s frame as the render system draws it.
+    pub fn new(
+        mask: &'m AlphaMask,
+        sprite: &Sprite,
+        pos: &MapPosition,
+        scale: Option<&Scale>,
+        rot: Option<&Rotation>,
+    ) -> Self {
+        Self {
+            mask,
+            geom: compute_sprite_geometry(pos, sprite, scale, rot),
+            src: Rectangle::new(
+                sprite.offset.x,
+                sprite.offset.y,
+                sprite.width,
+                sprite.height,
+            ),
+            flip_h: sprite.flip_h,
+            flip_v: sprite.flip_v,
+        }
+    }
+
+    /// `true` if the world point `p` lands on a solid texel of the frame.
+    pub fn is_solid_at(&self, p: Vector2) -> bool {
+        let SpriteRenderGeometry {
+            dest,
+            origin,
+            rotation,
+        } = &self.geom;
+        if dest.width == 0.0 || dest.height == 0.0 {
+            return false;
+        }
+        // Undo the rotation around the anchor, then normalize into the quad.
+        let (sin, cos) = (-rotation.to_radians()).sin_cos();
+        let (dx, dy) = (p.x - dest.x, p.y - dest.y);
+        let local_x = dx * cos - dy * sin + origin.x;
+        let local_y = dx * sin + dy * cos + origin.y;
+        let mut u = local_x / dest.width;
+        let mut v = local_y / dest.height;
+        if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) {
+            return false;
+        }
+        if self.flip_h {
+            u = 1.0 - u;
+        }
+        if self.flip_v {
+            v = 1.0 - v;
+        }
+        let tx = (self.src.x + u * self.src.width).floor() as i32;
+        let ty = (self.src.y + v * self.src.height).floor() as i32;
+        self.mask.is_solid(tx, ty)
+    }
+}
+
+/// Narrow-phase test of two overlapping colliders.
+///
+/// Samples the center of every world unit inside both rectangles. A point
+/// counts as solid for a side with a [`PixelShape`] if it lands on a solid
+/// texel, and always for a side without one. Returns `true` at the first
+/// point solid for both.
+pub fn pixels_overlap(
+    rect_a: &Rectangle,
+    shape_a: Option<&PixelShape>,
+    rect_b: &Rectangle,
+    shape_b: Option<&PixelShape>,
+) -> bool {
+    let min_x = rect_a.x.max(rect_b.x);
+    let min_y = rect_a.y.max(rect_b.y);
+    let max_x = (rect_a.x + rect_a.width).min(rect_b.x + rect_b.width);
+    let max_y = (rect_a.y + rect_a.height).min(rect_b.y + rect_b.height);
+    let solid = |shape: Option<&PixelShape>, p| shape.is_none_or(|s| s.is_solid_at(p));
+    // First world-unit center at or after each minimum.
+    let first_x = (min_x - 0.5).ceil() + 0.5;
+    let mut y = (min_y - 0.5).ceil() + 0.5;
+    while y < max_y {
+        let mut x = first_x;
+        while x < max_x {
+            let p = Vector2::new(x, y);
+            if solid(shape_a, p) && solid(shape_b, p) {
+                return true;
+            }
+            x += 1.0;
         }
+        y += 1.0;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::observer::Observer;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Hits(u32);
+
+    /// 4×4 mask solid only in its left half.
+    fn left_half() -> AlphaMask {
+        AlphaMask::from_alpha(4, 4, (0..16).map(|i| if i % 4 < 2 { 255 } else { 0 }))
+    }
+
+    fn sprite() -> Sprite {
+        Sprite {
+            tex_key: "mask".into(),
+            width: 4.0,
+            height: 4.0,
+            offset: Vector2::zero(),
+            origin: Vector2::zero(),
+            flip_h: false,
+            flip_v: false,
+        }
+    }
+
+    #[test]
+    fn transparent_texels_do_not_collide() {
+        let mask = left_half();
+        let shape = PixelShape::new(&mask, &sprite(), &MapPosition::new(0.0, 0.0), None, None);
+        let own = Rectangle::new(0.0, 0.0, 4.0, 4.0);
+        assert!(!pixels_overlap(
+            &own,
+            Some(&shape),
+            &Rectangle::new(2.0, 0.0, 4.0, 4.0),
+            None
+        ));
+        assert!(pixels_overlap(
+            &own,
+            Some(&shape),
+            &Rectangle::new(1.0, 0.0, 4.0, 4.0),
+            None
+        ));
+    }
+
+    #[test]
+    fn flips_mirror_the_mask() {
+        let mask = left_half();
+        let mut flipped = sprite();
+        flipped.flip_h = true;
+        let shape = PixelShape::new(&mask, &flipped, &MapPosition::new(0.0, 0.0), None, None);
+        assert!(!shape.is_solid_at(Vector2::new(0.5, 0.5)));
+        assert!(shape.is_solid_at(Vector2::new(3.5, 0.5)));
+    }
+
+    #[test]
+    fn scale_stretches_the_mask() {
+        let mask = left_half();
+        let scale = Scale::new(2.0, 2.0);
+        let shape = PixelShape::new(
+            &mask,
+            &sprite(),
+            &MapPosition::new(0.0, 0.0),
+            Some(&scale),
+            None,
+        );
+        assert!(shape.is_solid_at(Vector2::new(3.5, 7.5)));
+        assert!(!shape.is_solid_at(Vector2::new(4.5, 0.5)));
+    }
+
+    #[test]
+    fn both_masks_must_be_solid() {
+        let mask = left_half();
+        let a = PixelShape::new(&mask, &sprite(), &MapPosition::new(0.0, 0.0), None, None);
+        let b = PixelShape::new(&mask, &sprite(), &MapPosition::new(1.0, 0.0), None, None);
+        let c = PixelShape::new(&mask, &sprite(), &MapPosition::new(-1.0, 0.0), None, None);
+        let rect_a = Rectangle::new(0.0, 0.0, 4.0, 4.0);
+        assert!(pixels_overlap(
+            &rect_a,
+            Some(&a),
+            &Rectangle::new(1.0, 0.0, 4.0, 4.0),
+            Some(&b)
+        ));
+        // c's solid half is x in [-1, 1), a's is [0, 2): they share [0, 1).
+        assert!(pixels_overlap(
+            &rect_a,
+            Some(&a),
+            &Rectangle::new(-1.0, 0.0, 4.0, 4.0),
+            Some(&c)
+        ));
+        let d = PixelShape::new(&mask, &sprite(), &MapPosition::new(2.0, 0.0), None, None);
+        assert!(!pixels_overlap(
+            &rect_a,
+            Some(&a),
+            &Rectangle::new(2.0, 0.0, 4.0, 4.0),
+            Some(&d)
+        ));
+    }
+
+    /// Run the detector once with a left-half mask entity at the origin and a
+    /// plain box at `x`, returning how many collisions were reported.
+    fn detect(x: f32, with_masks: bool) -> u32 {
+        let mut world = World::new();
+        world.init_resource::<FrameArena>();
+        world.init_resource::<Hits>();
+        if with_masks {
+            let mut masks = AlphaMasks::default();
+            masks.insert("mask", left_half());
+            world.insert_resource(masks);
+        }
+        world.spawn(Observer::new(
+            |_trigger: On<CollisionEvent>, mut hits: ResMut<Hits>| hits.0 += 1,
+        ));
+        world.spawn((
+            MapPosition::new(0.0, 0.0),
+            BoxCollider::new(4.0, 4.0),
+            sprite(),
+            PixelCollision,
+        ));
+        world.spawn((MapPosition::new(x, 0.0), BoxCollider::new(4.0, 4.0)));
+        world
+            .run_system_once(collision_detector)
+            .expect("collision_detector");
+        world.resource::<Hits>().0
+    }
+
+    #[test]
+    fn detector_skips_pairs_touching_only_transparent_texels() {
+        assert_eq!(detect(2.0, true), 0);
+        assert_eq!(detect(1.0, true), 1);
+    }
+
+    #[test]
+    fn detector_without_masks_tests_boxes_only() {
+        assert_eq!(detect(2.0, false), 1);
     }
 }
@@ -0,0 +1,17 @@
+//! Color grading crossfade.
+//!
+//! [`color_grading_system`] advances the crossfade between color grading
+//! LUTs (see [`colorgrading`](crate::resources::colorgrading)). The render
+//! system applies the LUTs in the post-process chain.
+
+use bevy_ecs::prelude::*;
+
+use crate::resources::colorgrading::ColorGrading;
+use crate::resources::worldtime::WorldTime;
+
+/// Advance a running LUT crossfade by the frame's delta time.
+pub fn color_grading_system(mut grading: ResMut<ColorGrading>, time: Res<WorldTime>) {
+    if grading.is_fading() {
+        grading.advance(time.delta);
+    }
+}
@@ -0,0 +1,81 @@
+//! Cooldown countdown system.
+//!
+//! [`cooldown_system`] advances every [`Cooldown`] by the scaled frame time
+//! and writes its remaining fraction to the entity's `Signals` (inserted when
+//! missing) and, for cooldowns that ask for it, to `WorldSignals`.
+//!
+//! # Related
+//!
+//! - [`crate::components::cooldown::Cooldown`] – duration, mode and signal key
+
+use bevy_ecs::prelude::*;
+
+use crate::components::cooldown::Cooldown;
+use crate::components::signals::Signals;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Counts cooldowns down and mirrors their remaining fraction to signals.
+///
+/// Scheduling: runs before `gui_progressbar_signal_update_system` and
+/// `update_world_signals_binding_system` so bound widgets show the current
+/// frame's value.
+pub fn cooldown_system(
+    time: Res<WorldTime>,
+    mut cooldowns: Query<(Entity, &mut Cooldown, Option<&mut Signals>)>,
+    mut world_signals: ResMut<WorldSignals>,
+    mut commands: Commands,
+) {
+    for (entity, mut cooldown, signals) in cooldowns.iter_mut() {
+        cooldown.tick(time.delta);
+        let fraction = cooldown.fraction();
+        match signals {
+            Some(mut signals) => {
+                let key = cooldown.signal.as_str();
+                // Ready cooldowns leave `Signals` untouched by change detection.
+                if signals.get_scalar(key) != Some(fraction) {
+                    signals.update_scalar(key, fraction);
+                }
+            }
+            None => {
+                let mut signals = Signals::default();
+                signals.set_scalar(cooldown.signal, fraction);
+                commands.entity(entity).insert(signals);
+            }
+        }
+        if cooldown.world_signal {
+            world_signals.set_scalar(cooldown.signal, fraction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn fraction_is_mirrored_to_entity_and_world_signals() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime::default());
+        world.insert_resource(WorldSignals::default());
+        world.resource_mut::<WorldTime>().delta = 0.5;
+        let entity = world
+            .spawn(Cooldown::new(2.0, "dash_cd").with_world_signal().started())
+            .id();
+
+        world.run_system_once(cooldown_system).unwrap();
+        let signals = world.get::<Signals>(entity).unwrap();
+        assert_eq!(signals.get_scalar("dash_cd"), Some(0.75));
+        assert_eq!(
+            world.resource::<WorldSignals>().get_scalar("dash_cd"),
+            Some(0.75)
+        );
+
+        world.resource_mut::<WorldTime>().delta = 2.0;
+        world.run_system_once(cooldown_system).unwrap();
+        assert!(world.get::<Cooldown>(entity).unwrap().is_ready());
+        let signals = world.get::<Signals>(entity).unwrap();
+        assert_eq!(signals.get_scalar("dash_cd"), Some(0.0));
+    }
+}
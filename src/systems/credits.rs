@@ -0,0 +1,161 @@
+//! Credits roll system.
+//!
+//! [`credits_roll_system`] drives every [`CreditsRoll`]: on its first frame
+//! it spawns a [`DynamicText`] per non-empty line below the bottom of the
+//! screen, then scrolls them up, faster while `action_1` or `action_2` is
+//! held, keeping each line centred on its measured width. When the last line
+//! has left the screen it despawns the roll and triggers [`CreditsFinished`].
+//!
+//! # Related
+//!
+//! - [`crate::components::credits`] – lines, styles and layout
+//! - [`crate::events::credits`] – the finish event
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::credits::CreditsRoll;
+use crate::components::dynamictext::DynamicText;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::zindex::ZIndex;
+use crate::events::credits::CreditsFinished;
+use crate::resources::input::InputState;
+use crate::resources::screensize::ScreenSize;
+use crate::resources::worldtime::WorldTime;
+
+/// Opens, scrolls and closes credits rolls.
+pub fn credits_roll_system(
+    mut commands: Commands,
+    mut rolls: Query<(Entity, &mut CreditsRoll)>,
+    mut texts: Query<(&mut ScreenPosition, &DynamicText)>,
+    screen: Res<ScreenSize>,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+) {
+    let fast = input.action_1.active || input.action_2.active;
+    let screen_w = screen.w as f32;
+    for (entity, mut roll) in rolls.iter_mut() {
+        if !roll.opened {
+            open_roll(&mut commands, &mut roll, screen.h as f32);
+            continue;
+        }
+
+        let speed = if fast {
+            roll.speed * roll.fast_forward
+        } else {
+            roll.speed
+        };
+        roll.scroll += speed * time.delta;
+        if roll.is_finished() {
+            for text in roll.texts.iter().flatten() {
+                commands.entity(*text).try_despawn();
+            }
+            commands.entity(entity).despawn();
+            commands.trigger(CreditsFinished {
+                roll: entity,
+                callback: roll.callback.clone(),
+            });
+            continue;
+        }
+
+        let center_x = roll.center_x.unwrap_or(screen_w / 2.0);
+        let top = roll.start_y - roll.scroll;
+        for (offset, text) in roll.line_offsets().into_iter().zip(&roll.texts) {
+            let Some(text) = text else {
+                continue;
+            };
+            if let Ok((mut pos, dynamic_text)) = texts.get_mut(*text) {
+                pos.set_pos(Vector2::new(
+                    center_x - dynamic_text.size().x / 2.0,
+                    top + offset,
+                ));
+            }
+        }
+    }
+}
+
+/// Spawns the line texts below the screen.
+fn open_roll(commands: &mut Commands, roll: &mut CreditsRoll, screen_h: f32) {
+    roll.start_y = screen_h;
+    let offsets = roll.line_offsets();
+    roll.texts = (0..roll.lines.len())
+        .map(|index| {
+            let line = &roll.lines[index];
+            if line.text.is_empty() {
+                return None;
+            }
+            let text = DynamicText::new(
+                line.text.as_str(),
+                roll.font.as_str(),
+                roll.line_font_size(index),
+                roll.line_color(index),
+            );
+            let pos = ScreenPosition::new(0.0, roll.start_y + offsets[index]);
+            Some(commands.spawn((pos, ZIndex(roll.z_index), text)).id())
+        })
+        .collect();
+    roll.opened = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::credits::{CreditsLine, CreditsStyle};
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Finished(Vec<Option<String>>);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 1.0,
+            real_delta: 1.0,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.insert_resource(InputState::default());
+        world.insert_resource(ScreenSize { w: 200, h: 100 });
+        world.init_resource::<Finished>();
+        world.add_observer(|trigger: On<CreditsFinished>, mut done: ResMut<Finished>| {
+            done.0.push(trigger.event().callback.clone());
+        });
+        world
+    }
+
+    #[test]
+    fn scrolls_fast_forwards_and_finishes() {
+        let mut world = world();
+        let mut roll = CreditsRoll::new(vec![
+            CreditsLine::new("Thanks", CreditsStyle::Normal),
+            CreditsLine::new("", CreditsStyle::Normal),
+        ])
+        .with_speed(10.0)
+        .with_callback("credits_done");
+        roll.font_size = 10.0;
+        roll.line_spacing = 1.0;
+        world.spawn(roll);
+
+        world.run_system_once(credits_roll_system).unwrap();
+        assert_eq!(world.query::<&DynamicText>().iter(&world).count(), 1);
+
+        world.run_system_once(credits_roll_system).unwrap();
+        let y = world.query::<&ScreenPosition>().single(&world).unwrap().y();
+        assert_eq!(y, 90.0);
+
+        // start 100 + height 20 = 120; 10 scrolled, fast forward covers the rest.
+        world.resource_mut::<InputState>().action_1.active = true;
+        world.run_system_once(credits_roll_system).unwrap();
+        world.run_system_once(credits_roll_system).unwrap();
+        assert!(world.resource::<Finished>().0.is_empty());
+        world.run_system_once(credits_roll_system).unwrap();
+
+        assert_eq!(
+            world.resource::<Finished>().0,
+            [Some("credits_done".to_string())]
+        );
+        assert_eq!(world.query::<&CreditsRoll>().iter(&world).count(), 0);
+        assert_eq!(world.query::<&DynamicText>().iter(&world).count(), 0);
+    }
+}
@@ -0,0 +1,663 @@
+//! Dirty-region tracking for [`RenderMode::DirtyRects`].
+//!
+//! [`track_dirty_regions`] remembers the screen rectangle every sprite and
+//! dynamic text covered last frame. An entity whose rectangle moved, or whose
+//! appearance changed, dirties both its old and new rectangle; despawned
+//! and hidden entities dirty their last rectangle. The union is written to
+//! [`DirtyRegion`] for the render system, which clips its pass to it.
+//!
+//! Anything whose look is not tied to one entity rectangle forces a full
+//! redraw instead: camera moves and camera effects, debug overlays, weather, the day/night
+//! ambient, fog of war, visibility polygons, water surfaces, ropes, blob shadows, portal
+//! fades, sliding cinematic bars, entity shaders, changes to GUI widgets (hover, press, progress,
+//! layout), interaction prompts and off-screen indicators. Progress bars, rings and
+//! nine-slice panels are tracked by their drawn rectangle like sprites.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+use raylib::prelude::*;
+use rustc_hash::FxHashMap;
+
+use crate::components::blobshadow::BlobShadow;
+use crate::components::dynamictext::DynamicText;
+use crate::components::entityshader::EntityShader;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::guibutton::GuiButton;
+use crate::components::guiinteractable::GuiInteractable;
+use crate::components::guilabel::GuiLabel;
+use crate::components::guiprogressbar::GuiProgressBar;
+use crate::components::guiwindow::GuiWindow;
+use crate::components::height::Height;
+use crate::components::interactable::Interactable;
+use crate::components::inventory::{GuiInventoryGrid, Inventory};
+use crate::components::mapposition::MapPosition;
+use crate::components::nineslice::NineSlice;
+use crate::components::offscreenindicator::OffscreenIndicator;
+use crate::components::paletteswap::PaletteSwap;
+use crate::components::progress::{ProgressBar, RadialProgress};
+use crate::components::rope::Rope;
+use crate::components::rotation::Rotation;
+use crate::components::scale::Scale;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::shadow::Shadow;
+use crate::components::sprite::Sprite;
+use crate::components::tint::Tint;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::components::watersurface::WaterSurface;
+use crate::components::weather::WeatherParticle;
+use crate::components::zindex::ZIndex;
+use crate::resources::accessibility::HIGH_CONTRAST_PLATE_PADDING;
+use crate::resources::camera2d::Camera2DRes;
+use crate::resources::cameraeffects::CameraEffects;
+use crate::resources::cinematic::CinematicMode;
+use crate::resources::debugmode::DebugMode;
+use crate::resources::fogofwar::FogOfWar;
+use crate::resources::gameconfig::GameConfig;
+use crate::resources::layervisibility::LayerVisibility;
+use crate::resources::portaltransition::PortalTransition;
+use crate::resources::rendermode::{DirtyRegion, RenderMode};
+use crate::resources::screensize::ScreenSize;
+use crate::resources::worldclock::WorldClock;
+use crate::systems::render::{ProgressFilter, ScreenPassFilter, WorldPassFilter};
+use crate::systems::render::geometry::{compute_sprite_cull_bounds, resolve_world_transform};
+
+/// Extra pixels added around every dirty rectangle, covering filtering
+/// bleed and the high-contrast text plates.
+const DIRTY_PADDING: f32 = HIGH_CONTRAST_PLATE_PADDING + 2.0;
+
+/// Which draw list an entity rectangle belongs to. An entity can carry both
+/// a sprite and a text, so the kind is part of the cache key.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum DrawKind {
+    WorldSprite,
+    WorldText,
+    ScreenSprite,
+    ScreenText,
+    WorldProgress,
+    ScreenProgress,
+    WorldNineSlice,
+    ScreenNineSlice,
+}
+
+/// Per-system memory of the previous frame.
+#[derive(Default)]
+pub struct DirtyTrackerState {
+    /// Last screen rectangle of every drawn entity, with the frame it was
+    /// last seen in.
+    rects: FxHashMap<(Entity, DrawKind), (Rectangle, u64)>,
+    frame: u64,
+    camera: Option<Camera2D>,
+    ambient: Option<Color>,
+    portal_was_active: bool,
+}
+
+type WorldSpriteData = (
+    Entity,
+    Ref<'static, Sprite>,
+    &'static MapPosition,
+    Ref<'static, ZIndex>,
+    Option<&'static Scale>,
+    Option<&'static Rotation>,
+    Option<&'static GlobalTransform2D>,
+    Option<Ref<'static, Tint>>,
+    Option<Ref<'static, Shadow>>,
+    Option<Ref<'static, PaletteSwap>>,
+    Option<&'static Height>,
+);
+
+type WorldTextData = (
+    Entity,
+    Ref<'static, DynamicText>,
+    &'static MapPosition,
+    Ref<'static, ZIndex>,
+    Option<&'static GlobalTransform2D>,
+    Option<Ref<'static, Tint>>,
+    Option<Ref<'static, Shadow>>,
+);
+
+type ScreenSpriteData = (
+    Entity,
+    Ref<'static, Sprite>,
+    &'static ScreenPosition,
+    Ref<'static, ZIndex>,
+    Option<Ref<'static, Tint>>,
+    Option<Ref<'static, Shadow>>,
+);
+
+type ScreenTextData = (
+    Entity,
+    Ref<'static, DynamicText>,
+    &'static ScreenPosition,
+    Ref<'static, ZIndex>,
+    Option<Ref<'static, Tint>>,
+    Option<Ref<'static, Shadow>>,
+);
+
+type WorldProgressData = (
+    Entity,
+    Option<Ref<'static, ProgressBar>>,
+    Option<Ref<'static, RadialProgress>>,
+    &'static MapPosition,
+    Option<&'static GlobalTransform2D>,
+    Ref<'static, ZIndex>,
+);
+
+type ScreenProgressData = (
+    Entity,
+    Option<Ref<'static, ProgressBar>>,
+    Option<Ref<'static, RadialProgress>>,
+    &'static ScreenPosition,
+    Ref<'static, ZIndex>,
+);
+
+type WorldNineSliceData = (
+    Entity,
+    Ref<'static, NineSlice>,
+    &'static MapPosition,
+    Option<&'static GlobalTransform2D>,
+    Ref<'static, ZIndex>,
+    Option<Ref<'static, Tint>>,
+);
+
+type ScreenNineSliceData = (
+    Entity,
+    Ref<'static, NineSlice>,
+    &'static ScreenPosition,
+    Ref<'static, ZIndex>,
+    Option<Ref<'static, Tint>>,
+);
+
+/// GUI widgets and inventories whose look changed.
+type ChangedGuiFilter = Or<(
+    Changed<GuiWindow>,
+    Changed<GuiButton>,
+    Changed<GuiInteractable>,
+    Changed<GuiLabel>,
+    Changed<GuiProgressBar>,
+    Changed<GuiInventoryGrid>,
+    Changed<Inventory>,
+)>;
+
+/// GUI widgets that moved on screen.
+type MovedGuiFilter = (
+    Changed<ScreenPosition>,
+    Or<(
+        With<GuiWindow>,
+        With<GuiButton>,
+        With<GuiLabel>,
+        With<GuiProgressBar>,
+        With<GuiInventoryGrid>,
+    )>,
+);
+
+/// Drawables whose rectangles are tracked.
+#[derive(SystemParam)]
+pub struct DirtyDrawables<'w, 's> {
+    world_sprites: Query<'w, 's, WorldSpriteData, WorldPassFilter>,
+    world_texts: Query<'w, 's, WorldTextData, WorldPassFilter>,
+    screen_sprites: Query<'w, 's, ScreenSpriteData, ScreenPassFilter>,
+    screen_texts: Query<'w, 's, ScreenTextData, ScreenPassFilter>,
+    world_progress: Query<'w, 's, WorldProgressData, (ProgressFilter, WorldPassFilter)>,
+    screen_progress: Query<'w, 's, ScreenProgressData, (ProgressFilter, ScreenPassFilter)>,
+    world_nine_slices: Query<'w, 's, WorldNineSliceData, WorldPassFilter>,
+    screen_nine_slices: Query<'w, 's, ScreenNineSliceData, ScreenPassFilter>,
+    removed_tints: RemovedComponents<'w, 's, Tint>,
+    removed_shadows: RemovedComponents<'w, 's, Shadow>,
+    removed_palette_swaps: RemovedComponents<'w, 's, PaletteSwap>,
+    removed_progress_bars: RemovedComponents<'w, 's, ProgressBar>,
+    removed_radial_progress: RemovedComponents<'w, 's, RadialProgress>,
+}
+
+/// Everything that forces a full redraw when present or changed.
+#[derive(SystemParam)]
+pub struct FullRedrawTriggers<'w, 's> {
+    camera: Res<'w, Camera2DRes>,
+    camera_effects: Res<'w, CameraEffects>,
+    screensize: Res<'w, ScreenSize>,
+    config: Res<'w, GameConfig>,
+    layers: Res<'w, LayerVisibility>,
+    debug: Option<Res<'w, DebugMode>>,
+    clock: Res<'w, WorldClock>,
+    fog: Res<'w, FogOfWar>,
+    portal: Res<'w, PortalTransition>,
+    cinematic: Res<'w, CinematicMode>,
+    weather_particles: Query<'w, 's, (), With<WeatherParticle>>,
+    visibility_polygons: Query<'w, 's, (), With<VisibilityPolygon>>,
+    water_surfaces: Query<'w, 's, (), With<WaterSurface>>,
+    ropes: Query<'w, 's, (), With<Rope>>,
+    blob_shadows: Query<'w, 's, (), With<BlobShadow>>,
+    entity_shaders: Query<'w, 's, (), With<EntityShader>>,
+    interactables: Query<'w, 's, (), With<Interactable>>,
+    offscreen_indicators: Query<'w, 's, (), With<OffscreenIndicator>>,
+    changed_gui: Query<'w, 's, (), ChangedGuiFilter>,
+    moved_gui: Query<'w, 's, (), MovedGuiFilter>,
+    removed_gui: RemovedComponents<'w, 's, GuiInteractable>,
+    removed_windows: RemovedComponents<'w, 's, GuiWindow>,
+}
+
+impl FullRedrawTriggers<'_, '_> {
+    /// Whether this frame must be redrawn completely. Updates the last
+    /// camera, ambient color and portal state in `state`.
+    fn needs_full_redraw(&mut self, state: &mut DirtyTrackerState) -> bool {
+        let camera = self.camera.0;
+        let camera_moved = state.camera.is_none_or(|last| !same_camera(&last, &camera));
+        state.camera = Some(camera);
+
+        let ambient = self.clock.ambient_color();
+        let ambient_changed = state.ambient != ambient;
+        state.ambient = ambient;
+
+        let portal_active = self.portal.is_active();
+        let portal_fading = portal_active || state.portal_was_active;
+        state.portal_was_active = portal_active;
+
+        // Drain the removal readers every frame so stale events do not
+        // trigger a redraw later.
+        let gui_removed = self.removed_gui.read().count() + self.removed_windows.read().count() > 0;
+
+        camera_moved
+            || self.camera_effects.is_active()
+            || self.camera_effects.is_changed()
+            || ambient_changed
+            || portal_fading
+            || self.cinematic.is_changed()
+            || gui_removed
+            || self.screensize.is_changed()
+            || self.config.is_changed()
+            || self.layers.is_changed()
+            || self.debug.is_some()
+            || self.fog.enabled
+            || !self.weather_particles.is_empty()
+            || !self.visibility_polygons.is_empty()
+            || !self.water_surfaces.is_empty()
+            || !self.ropes.is_empty()
+            || !self.blob_shadows.is_empty()
+            || !self.entity_shaders.is_empty()
+            || !self.interactables.is_empty()
+            || !self.offscreen_indicators.is_empty()
+            || !self.changed_gui.is_empty()
+            || !self.moved_gui.is_empty()
+    }
+}
+
+fn same_camera(a: &Camera2D, b: &Camera2D) -> bool {
+    a.target == b.target && a.offset == b.offset && a.rotation == b.rotation && a.zoom == b.zoom
+}
+
+/// Screen rectangle covering a world-space AABB seen through `camera`.
+fn world_rect_to_screen(camera: &Camera2DRes, min: Vector2, max: Vector2) -> Rectangle {
+    let corners = [
+        min,
+        Vector2 { x: max.x, y: min.y },
+        Vector2 { x: min.x, y: max.y },
+        max,
+    ]
+    .map(|corner| camera.world_to_screen(corner));
+    let (mut lo, mut hi) = (corners[0], corners[0]);
+    for c in &corners[1..] {
+        lo = Vector2 {
+            x: lo.x.min(c.x),
+            y: lo.y.min(c.y),
+        };
+        hi = Vector2 {
+            x: hi.x.max(c.x),
+            y: hi.y.max(c.y),
+        };
+    }
+    Rectangle {
+        x: lo.x,
+        y: lo.y,
+        width: hi.x - lo.x,
+        height: hi.y - lo.y,
+    }
+}
+
+/// Grow `rect` to also cover its copy shifted by `offset` (a drop shadow),
+/// then pad it by [`DIRTY_PADDING`].
+fn with_shadow_and_padding(rect: Rectangle, offset: Option<Vector2>) -> Rectangle {
+    let offset = offset.unwrap_or(Vector2::zero());
+    let x0 = rect.x.min(rect.x + offset.x) - DIRTY_PADDING;
+    let y0 = rect.y.min(rect.y + offset.y) - DIRTY_PADDING;
+    let x1 = (rect.x + rect.width).max(rect.x + rect.width + offset.x) + DIRTY_PADDING;
+    let y1 = (rect.y + rect.height).max(rect.y + rect.height + offset.y) + DIRTY_PADDING;
+    Rectangle {
+        x: x0,
+        y: y0,
+        width: x1 - x0,
+        height: y1 - y0,
+    }
+}
+
+/// Corners of the area covered by a progress bar and/or ring drawn at `pos`.
+fn progress_bounds(
+    bar: Option<&ProgressBar>,
+    radial: Option<&RadialProgress>,
+    pos: Vector2,
+) -> (Vector2, Vector2) {
+    let bar = bar.map(|bar| {
+        let rect = bar.rect(pos);
+        let min = Vector2 {
+            x: rect.x,
+            y: rect.y,
+        };
+        let size = Vector2 {
+            x: rect.width,
+            y: rect.height,
+        };
+        (min, min + size)
+    });
+    let ring = radial.map(|radial| {
+        let center = pos + radial.offset;
+        let reach = Vector2 {
+            x: radial.radius,
+            y: radial.radius,
+        };
+        (center - reach, center + reach)
+    });
+    match (bar, ring) {
+        (Some((lo_a, hi_a)), Some((lo_b, hi_b))) => (
+            Vector2 {
+                x: lo_a.x.min(lo_b.x),
+                y: lo_a.y.min(lo_b.y),
+            },
+            Vector2 {
+                x: hi_a.x.max(hi_b.x),
+                y: hi_a.y.max(hi_b.y),
+            },
+        ),
+        (Some(bounds), None) | (None, Some(bounds)) => bounds,
+        (None, None) => (pos, pos),
+    }
+}
+
+/// Record `rect` for `key` and dirty the region when it moved or `changed`.
+fn note(
+    state: &mut DirtyTrackerState,
+    region: &mut DirtyRegion,
+    key: (Entity, DrawKind),
+    rect: Rectangle,
+    changed: bool,
+) {
+    let frame = state.frame;
+    match state.rects.insert(key, (rect, frame)) {
+        Some((old, _)) if old == rect && !changed => {}
+        Some((old, _)) => {
+            region.add(old);
+            region.add(rect);
+        }
+        None => region.add(rect),
+    }
+}
+
+/// Collect the screen area to repaint this frame into [`DirtyRegion`].
+///
+/// In [`RenderMode::Full`] the region is always [`DirtyRegion::Full`] and the
+/// rectangle cache is dropped. Switching modes, or any full-redraw trigger
+/// (see the module docs), also yields a full redraw for that frame while the
+/// cache keeps being refreshed.
+///
+/// Scheduling: runs after `propagate_transforms`, camera follow and every
+/// system that moves or restyles drawables, and before `render_system`.
+pub fn track_dirty_regions(
+    mode: Res<RenderMode>,
+    mut region: ResMut<DirtyRegion>,
+    mut drawables: DirtyDrawables,
+    mut triggers: FullRedrawTriggers,
+    mut state: Local<DirtyTrackerState>,
+) {
+    crate::tracy::tracy_span!("track_dirty_regions");
+    if *mode == RenderMode::Full {
+        if !state.rects.is_empty() {
+            *state = DirtyTrackerState::default();
+        }
+        region.set_if_neq(DirtyRegion::Full);
+        return;
+    }
+
+    let state = &mut *state;
+    state.frame += 1;
+    let full = triggers.needs_full_redraw(state) || mode.is_changed();
+    let mut next = if full {
+        DirtyRegion::Full
+    } else {
+        DirtyRegion::Clean
+    };
+
+    let restyled: Vec<Entity> = drawables
+        .removed_tints
+        .read()
+        .chain(drawables.removed_shadows.read())
+        .chain(drawables.removed_palette_swaps.read())
+        .chain(drawables.removed_progress_bars.read())
+        .chain(drawables.removed_radial_progress.read())
+        .collect();
+    let changed =
+        |entity: Entity, any_ref_changed: bool| any_ref_changed || restyled.contains(&entity);
+
+    let camera = &*triggers.camera;
+    for (entity, sprite, pos, z, scale, rot, gt, tint, shadow, palette, height) in
+        &drawables.world_sprites
+    {
+        let (mut pos, scale, rot) =
+            resolve_world_transform(*pos, scale.copied(), rot.copied(), gt.copied());
+        // Drawn lifted like in the render pass, with the drop shadow left on
+        // the ground, so a height change moves the rectangle.
+        let lift = height.map_or(0.0, |h| h.0);
+        pos.pos.y -= lift;
+        let (min, max) = compute_sprite_cull_bounds(&pos, &sprite, scale.as_ref(), rot.as_ref());
+        let offset = shadow
+            .as_ref()
+            .map(|s| (s.offset + Vector2 { x: 0.0, y: lift }) * camera.0.zoom);
+        let rect = with_shadow_and_padding(world_rect_to_screen(camera, min, max), offset);
+        let touched = sprite.is_changed()
+            || z.is_changed()
+            || tint.is_some_and(|t| t.is_changed())
+            || shadow.is_some_and(|s| s.is_changed())
+            || palette.is_some_and(|p| p.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::WorldSprite),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, text, pos, z, gt, tint, shadow) in &drawables.world_texts {
+        let min = gt.map_or(pos.pos, |gt| gt.position);
+        let max = min + text.size();
+        let offset = shadow.as_ref().map(|s| s.offset * camera.0.zoom);
+        let rect = with_shadow_and_padding(world_rect_to_screen(camera, min, max), offset);
+        let touched = text.is_changed()
+            || z.is_changed()
+            || tint.is_some_and(|t| t.is_changed())
+            || shadow.is_some_and(|s| s.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::WorldText),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, sprite, pos, z, tint, shadow) in &drawables.screen_sprites {
+        let rect = Rectangle {
+            x: pos.pos.x - sprite.origin.x,
+            y: pos.pos.y - sprite.origin.y,
+            width: sprite.width,
+            height: sprite.height,
+        };
+        let rect = with_shadow_and_padding(rect, shadow.as_ref().map(|s| s.offset));
+        let touched = sprite.is_changed()
+            || z.is_changed()
+            || tint.is_some_and(|t| t.is_changed())
+            || shadow.is_some_and(|s| s.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::ScreenSprite),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, text, pos, z, tint, shadow) in &drawables.screen_texts {
+        let size = text.size();
+        let rect = Rectangle {
+            x: pos.pos.x,
+            y: pos.pos.y,
+            width: size.x,
+            height: size.y,
+        };
+        let rect = with_shadow_and_padding(rect, shadow.as_ref().map(|s| s.offset));
+        let touched = text.is_changed()
+            || z.is_changed()
+            || tint.is_some_and(|t| t.is_changed())
+            || shadow.is_some_and(|s| s.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::ScreenText),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, bar, radial, pos, gt, z) in &drawables.world_progress {
+        let at = gt.map_or(pos.pos, |gt| gt.position);
+        let (min, max) = progress_bounds(bar.as_deref(), radial.as_deref(), at);
+        let rect = with_shadow_and_padding(world_rect_to_screen(camera, min, max), None);
+        let touched = bar.is_some_and(|b| b.is_changed())
+            || radial.is_some_and(|r| r.is_changed())
+            || z.is_changed();
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::WorldProgress),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, bar, radial, pos, z) in &drawables.screen_progress {
+        let (min, max) = progress_bounds(bar.as_deref(), radial.as_deref(), pos.pos);
+        let rect = Rectangle {
+            x: min.x,
+            y: min.y,
+            width: max.x - min.x,
+            height: max.y - min.y,
+        };
+        let rect = with_shadow_and_padding(rect, None);
+        let touched = bar.is_some_and(|b| b.is_changed())
+            || radial.is_some_and(|r| r.is_changed())
+            || z.is_changed();
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::ScreenProgress),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, slice, pos, gt, z, tint) in &drawables.world_nine_slices {
+        let dest = slice.dest(gt.map_or(pos.pos, |gt| gt.position));
+        let min = Vector2 {
+            x: dest.x,
+            y: dest.y,
+        };
+        let max = Vector2 {
+            x: dest.x + dest.width,
+            y: dest.y + dest.height,
+        };
+        let rect = with_shadow_and_padding(world_rect_to_screen(camera, min, max), None);
+        let touched = slice.is_changed() || z.is_changed() || tint.is_some_and(|t| t.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::WorldNineSlice),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    for (entity, slice, pos, z, tint) in &drawables.screen_nine_slices {
+        let rect = with_shadow_and_padding(slice.dest(pos.pos), None);
+        let touched = slice.is_changed() || z.is_changed() || tint.is_some_and(|t| t.is_changed());
+        note(
+            state,
+            &mut next,
+            (entity, DrawKind::ScreenNineSlice),
+            rect,
+            changed(entity, touched),
+        );
+    }
+
+    // Entities not drawn this frame (despawned or lost a component) leave
+    // their last rectangle behind.
+    let frame = state.frame;
+    state.rects.retain(|_, (rect, seen)| {
+        if *seen != frame {
+            next.add(*rect);
+        }
+        *seen == frame
+    });
+
+    next.clip_to_screen(triggers.screensize.w as f32, triggers.screensize.h as f32);
+    region.set_if_neq(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadow_offset_extends_rect_toward_the_shadow() {
+        let rect = with_shadow_and_padding(
+            Rectangle::new(10.0, 10.0, 20.0, 20.0),
+            Some(Vector2 { x: 5.0, y: -3.0 }),
+        );
+        let p = DIRTY_PADDING;
+        assert_eq!(rect.x, 10.0 - p);
+        assert_eq!(rect.y, 7.0 - p);
+        assert_eq!(rect.width, 25.0 + 2.0 * p);
+        assert_eq!(rect.height, 23.0 + 2.0 * p);
+    }
+
+    #[test]
+    fn moved_rect_dirties_old_and_new_position() {
+        let mut state = DirtyTrackerState::default();
+        let key = (Entity::from_bits(1), DrawKind::ScreenSprite);
+        let mut region = DirtyRegion::Clean;
+        note(
+            &mut state,
+            &mut region,
+            key,
+            Rectangle::new(0.0, 0.0, 10.0, 10.0),
+            false,
+        );
+
+        let mut region = DirtyRegion::Clean;
+        note(
+            &mut state,
+            &mut region,
+            key,
+            Rectangle::new(0.0, 0.0, 10.0, 10.0),
+            false,
+        );
+        assert_eq!(region, DirtyRegion::Clean);
+
+        note(
+            &mut state,
+            &mut region,
+            key,
+            Rectangle::new(20.0, 0.0, 10.0, 10.0),
+            false,
+        );
+        assert_eq!(
+            region,
+            DirtyRegion::Rect(Rectangle::new(0.0, 0.0, 30.0, 10.0))
+        );
+    }
+}
@@ -24,7 +24,7 @@ pub fn dynamictext_size_system(
 ) {
     for mut text in query.iter_mut() {
         debug!("Calculating size for DynamicText: '{}'", text.text);
-        let Some(font) = fonts.get(&*text.font) else {
+        let Some(font) = fonts.get_or_fallback(&*text.font) else {
             warn!(
                 "Font '{}' not found in FontStore, text size will be zero",
                 text.font
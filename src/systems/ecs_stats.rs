@@ -0,0 +1,150 @@
+//! ECS statistics collection.
+//!
+//! [`ecs_stats_system`] counts entities, archetypes and the entities spawned
+//! and despawned since its last run, samples component memory and warns when
+//! the archetype count keeps growing. See
+//! [`ecsstats`](crate::resources::ecsstats).
+
+use bevy_ecs::archetype::Archetype;
+use bevy_ecs::component::{ComponentId, ComponentInfo};
+use bevy_ecs::prelude::*;
+use bevy_ecs::resource::IS_RESOURCE;
+use log::warn;
+use rustc_hash::FxHashMap;
+
+use crate::resources::ecsstats::{ComponentStat, EcsStats};
+use crate::resources::worldtime::WorldTime;
+
+/// Collect [`EcsStats`] for the frame that just ended. Exclusive, since it
+/// walks the world's archetypes and component metadata. Resources, which
+/// bevy stores as entities, are left out.
+pub fn ecs_stats_system(world: &mut World) {
+    let this_tick = world.change_tick();
+    let frame = world
+        .get_resource::<WorldTime>()
+        .map_or(0, |time| time.frame_count);
+    let Some(stats) = world.get_resource::<EcsStats>() else {
+        return;
+    };
+    let last_tick = stats.last_tick;
+    let sample = stats.sample_due(frame);
+
+    let entities = world.entities();
+    let mut archetype_count = 0;
+    let mut entity_count = 0;
+    let mut spawned = 0;
+    for archetype in game_archetypes(world) {
+        archetype_count += 1;
+        entity_count += archetype.len() as usize;
+        spawned += archetype
+            .entities()
+            .iter()
+            .filter(|entity| {
+                entities
+                    .entity_get_spawn_or_despawn_tick(entity.id())
+                    .is_some_and(|tick| {
+                        last_tick.is_none_or(|last| tick.is_newer_than(last, this_tick))
+                    })
+            })
+            .count();
+    }
+    let components = sample.then(|| component_stats(world));
+
+    let mut stats = world.resource_mut::<EcsStats>();
+    stats.last_tick = Some(this_tick);
+    stats.record_frame(entity_count, archetype_count, spawned);
+    if let Some(components) = components {
+        stats.record_components(frame, components);
+    }
+    if let Some(count) = stats.take_archetype_warning() {
+        warn!(
+            "{} archetypes: every query checks each one it matches. Spawn loops that \
+             insert varying component sets create a new archetype per combination.",
+            count
+        );
+    }
+}
+
+/// Archetypes of entities, not resources.
+fn game_archetypes(world: &World) -> impl Iterator<Item = &Archetype> {
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| !archetype.contains(IS_RESOURCE))
+}
+
+/// Memory held by each component type across all archetypes.
+fn component_stats(world: &World) -> Vec<ComponentStat> {
+    let mut totals: Vec<ComponentStat> = Vec::new();
+    let mut index_of = FxHashMap::default();
+    for archetype in game_archetypes(world) {
+        let len = archetype.len() as usize;
+        if len == 0 {
+            continue;
+        }
+        for &id in archetype.components() {
+            let Some(info) = world.components().get_info(id) else {
+                continue;
+            };
+            let slot = *index_of.entry(id).or_insert_with(|| {
+                totals.push(ComponentStat {
+                    name: component_name(info, id),
+                    entities: 0,
+                    bytes: 0,
+                });
+                totals.len() - 1
+            });
+            totals[slot].entities += len;
+            totals[slot].bytes += info.layout().size() * len;
+        }
+    }
+    totals
+}
+
+#[cfg(feature = "ecs_names")]
+pub(crate) fn component_name(info: &ComponentInfo, _id: ComponentId) -> String {
+    info.name().shortname().to_string()
+}
+
+#[cfg(not(feature = "ecs_names"))]
+pub(crate) fn component_name(_info: &ComponentInfo, id: ComponentId) -> String {
+    format!("component #{}", id.index())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Small(#[allow(dead_code)] u32);
+
+    #[derive(Component)]
+    struct Tag;
+
+    #[test]
+    fn counts_spawns_despawns_and_component_memory() {
+        let mut world = World::new();
+        world.insert_resource(EcsStats::default());
+        let first = world.spawn(Small(1)).id();
+        world.spawn((Small(2), Tag));
+        ecs_stats_system(&mut world);
+
+        let stats = world.resource::<EcsStats>();
+        assert_eq!(stats.entities, 2);
+        assert_eq!(stats.spawned, 2);
+        let small = stats
+            .components
+            .iter()
+            .find(|c| c.bytes == 8)
+            .expect("Small is 4 bytes on two entities");
+        assert_eq!(small.entities, 2);
+
+        world.increment_change_tick();
+        world.despawn(first);
+        world.spawn(Tag);
+        ecs_stats_system(&mut world);
+        let stats = world.resource::<EcsStats>();
+        assert_eq!(stats.entities, 2);
+        assert_eq!((stats.spawned, stats.despawned), (1, 1));
+    }
+}
@@ -0,0 +1,248 @@
+//! Fog-of-war systems.
+//!
+//! - [`fog_opaque_system`] rebuilds the solid cells of the
+//!   [`FogOfWar`] from the tiles of its `solid_layers` whenever a tilemap or
+//!   the fog settings change.
+//! - [`fog_visibility_system`] follows the current scene and reveals the cells
+//!   around every [`VisionSource`].
+//! - [`process_lua_fog_commands`] *(feature = "lua")* applies
+//!   `engine.fog_enable()`, `engine.fog_disable()` and `engine.fog_reset()`.
+//!
+//! The overlay itself is drawn by the render system.
+//!
+//! # Related
+//!
+//! - [`crate::resources::fogofwar`] – the fog state and line-of-sight rules
+//! - [`crate::components::visionsource`] – the vision component
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::tilemap::{TileMapLayers, TileProjection};
+use crate::components::visionsource::VisionSource;
+use crate::resources::fogofwar::{FogCell, FogOfWar};
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::FogCmd;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Rebuilds the solid fog cells from the visible tiles of the fog's
+/// `solid_layers` when the fog was (re)enabled or a tilemap changed.
+///
+/// Each tile marks every fog cell its footprint overlaps, so walls stay solid
+/// when fog cells are smaller than tiles. Run after transform propagation.
+pub fn fog_opaque_system(
+    mut fog: ResMut<FogOfWar>,
+    tilemaps: Query<(Ref<TileMapLayers>, Ref<GlobalTransform2D>)>,
+    mut removed: RemovedComponents<TileMapLayers>,
+) {
+    let removed = removed.read().count() > 0;
+    if !fog.enabled {
+        return;
+    }
+    let changed = tilemaps
+        .iter()
+        .any(|(layers, transform)| layers.is_changed() || transform.is_changed());
+    if !fog.opaque_dirty() && !changed && !removed {
+        return;
+    }
+
+    let mut opaque: Vec<FogCell> = Vec::new();
+    for (layers, transform) in tilemaps.iter() {
+        let grid = layers.grid;
+        let samples = footprint_samples(grid.projection, grid.tile_size, &transform, &fog);
+        for name in fog.solid_layers() {
+            let Some(layer) = layers.layer_index(name).map(|i| &layers.layers[i]) else {
+                continue;
+            };
+            if !layer.visible {
+                continue;
+            }
+            for &(x, y) in layer.tiles.keys() {
+                let center = grid.cell_center(x as i64, y as i64);
+                opaque.extend(
+                    samples
+                        .iter()
+                        .map(|&offset| fog.cell_at(transform.transform_point(center + offset))),
+                );
+            }
+        }
+    }
+    fog.set_opaque(opaque);
+}
+
+/// Local-space offsets from a tile's centre that cover its footprint at fog
+/// cell resolution.
+fn footprint_samples(
+    projection: TileProjection,
+    tile_size: f32,
+    transform: &GlobalTransform2D,
+    fog: &FogOfWar,
+) -> Vec<Vector2> {
+    let (width, height) = match projection {
+        TileProjection::Isometric => (tile_size, tile_size * 0.5),
+        TileProjection::Orthogonal | TileProjection::Hex => (tile_size, tile_size),
+    };
+    let scale = transform.scale.x.abs().max(transform.scale.y.abs());
+    let steps = ((tile_size * scale / fog.cell_size()).ceil() as usize).max(1);
+    let mut samples = Vec::with_capacity(steps * steps);
+    for j in 0..steps {
+        for i in 0..steps {
+            let u = (i as f32 + 0.5) / steps as f32 - 0.5;
+            let v = (j as f32 + 0.5) / steps as f32 - 0.5;
+            // Isometric footprints are diamonds inside their bounding box.
+            if projection == TileProjection::Isometric && u.abs() + v.abs() > 0.5 {
+                continue;
+            }
+            samples.push(Vector2::new(u * width, v * height));
+        }
+    }
+    if samples.is_empty() {
+        samples.push(Vector2::zero());
+    }
+    samples
+}
+
+/// Switches the fog to the current scene (`sk::SCENE`) and reveals the cells
+/// around every [`VisionSource`].
+///
+/// Positions come from [`GlobalTransform2D`] when present, otherwise from
+/// [`MapPosition`]. Run after [`fog_opaque_system`].
+pub fn fog_visibility_system(
+    mut fog: ResMut<FogOfWar>,
+    signals: Res<WorldSignals>,
+    sources: Query<(
+        &VisionSource,
+        Option<&MapPosition>,
+        Option<&GlobalTransform2D>,
+    )>,
+) {
+    if !fog.enabled {
+        return;
+    }
+    let fog = fog.as_mut();
+    fog.set_scene(signals.get_string(sk::SCENE).map_or("", String::as_str));
+    fog.clear_visible();
+    for (source, map_pos, global) in sources.iter() {
+        let pos = match (global, map_pos) {
+            (Some(gt), _) => gt.position,
+            (None, Some(mp)) => mp.pos,
+            (None, None) => continue,
+        };
+        fog.reveal(pos, source.radius, source.blocked);
+    }
+}
+
+/// Applies fog commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_fog_commands(InMut(buf): InMut<Vec<FogCmd>>, mut fog: ResMut<FogOfWar>) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            FogCmd::Enable {
+                cell_size,
+                solid_layers,
+            } => fog.enable(cell_size, solid_layers),
+            FogCmd::Disable => fog.disable(),
+            FogCmd::Reset => fog.reset_scene(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::tilemap::{PlacedTile, TileGrid, TileMapLayer};
+    use crate::systems::propagate_transforms::propagate_transforms;
+    use bevy_ecs::hierarchy::ChildOf;
+    use bevy_ecs::system::RunSystemOnce;
+    use rustc_hash::FxHashMap;
+
+    fn wall_map(world: &mut World) -> Entity {
+        let mut tiles = FxHashMap::default();
+        tiles.insert(
+            (2, 0),
+            PlacedTile {
+                id: 0,
+                entity: None,
+            },
+        );
+        let layers = TileMapLayers {
+            tileset: "walls".to_string(),
+            grid: TileGrid {
+                projection: TileProjection::Orthogonal,
+                tile_size: 16.0,
+                map_width: 4,
+                map_height: 1,
+            },
+            templates: Vec::new(),
+            layers: vec![TileMapLayer {
+                name: "walls".to_string(),
+                visible: true,
+                z: 0.0,
+                tiles,
+                ..TileMapLayer::default()
+            }],
+        };
+        world.spawn((layers, GlobalTransform2D::default())).id()
+    }
+
+    #[test]
+    fn solid_layer_tiles_block_blocked_sources() {
+        let mut world = World::new();
+        let mut fog = FogOfWar::default();
+        fog.enable(8.0, vec!["walls".to_string()]);
+        world.insert_resource(fog);
+        world.insert_resource(WorldSignals::default());
+        wall_map(&mut world);
+        world.spawn((
+            MapPosition::new(4.0, 4.0),
+            VisionSource::new(64.0).blocked(),
+        ));
+
+        world
+            .run_system_once(fog_opaque_system)
+            .expect("fog_opaque_system");
+        world
+            .run_system_once(fog_visibility_system)
+            .expect("fog_visibility_system");
+
+        let fog = world.resource::<FogOfWar>();
+        // The 16-unit wall tile at column 2 covers fog cells 4 and 5.
+        assert!(fog.is_opaque((4, 0)) && fog.is_opaque((5, 1)));
+        assert!(fog.is_visible((3, 0)));
+        assert!(fog.is_visible((4, 0)));
+        assert!(!fog.is_visible((6, 0)));
+        assert!(!fog.is_revealed((7, 0)));
+    }
+
+    #[test]
+    fn unchanged_tilemaps_are_not_rebuilt() {
+        let mut world = World::new();
+        let mut fog = FogOfWar::default();
+        fog.enable(8.0, vec!["walls".to_string()]);
+        world.insert_resource(fog);
+        // A tilemap root with a child, as spawned by tilemap_spawn_system.
+        let root = wall_map(&mut world);
+        world.entity_mut(root).insert(MapPosition::new(0.0, 0.0));
+        world.spawn((MapPosition::new(0.0, 0.0), ChildOf(root)));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((propagate_transforms, fog_opaque_system).chain());
+
+        schedule.run(&mut world);
+        assert!(world.resource::<FogOfWar>().is_opaque((4, 0)));
+
+        // Nothing moved or was edited: the cleared cells must stay cleared.
+        world.resource_mut::<FogOfWar>().set_opaque([]);
+        schedule.run(&mut world);
+        assert!(!world.resource::<FogOfWar>().is_opaque((4, 0)));
+
+        world.get_mut::<MapPosition>(root).unwrap().pos = Vector2::new(16.0, 0.0);
+        schedule.run(&mut world);
+        assert!(world.resource::<FogOfWar>().is_opaque((6, 0)));
+    }
+}
@@ -0,0 +1,313 @@
+//! Force-field area system.
+//!
+//! [`force_area_system`] applies every enabled [`ForceArea`] to the
+//! overlapping, non-frozen [`RigidBody`] entities whose group or tags match
+//! the area's mask. Runs before [`movement`](super::movement::movement) so the
+//! added acceleration is integrated the same frame.
+//!
+//! Accelerations are divided by the body's mass. The global [`Wind`] area
+//! also pushes every body with a [`WindResponse`] component, whatever its
+//! mask, scaled by the body's wind factor.
+//!
+//! # Related
+//!
+//! - [`crate::components::forcearea`] – area definition and examples
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::boxcollider::BoxCollider;
+use crate::components::collision::pattern_matches;
+use crate::components::forcearea::ForceArea;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::components::tags::{Tags, entity_labels};
+use crate::components::weather::Wind;
+use crate::components::windresponse::WindResponse;
+use crate::resources::worldtime::WorldTime;
+
+/// An enabled area resolved to world space for the current frame.
+pub struct ActiveArea {
+    entity: Entity,
+    rect: Rectangle,
+    acceleration: Vector2,
+    velocity: Vector2,
+    mask: String,
+    /// Whether this is the global wind area.
+    wind: bool,
+}
+
+fn rects_overlap(a: &Rectangle, b: &Rectangle) -> bool {
+    a.x < b.x + b.width && a.x + a.width > b.x && a.y < b.y + b.height && a.y + a.height > b.y
+}
+
+fn rect_contains(r: &Rectangle, p: Vector2) -> bool {
+    p.x >= r.x && p.x <= r.x + r.width && p.y >= r.y && p.y <= r.y + r.height
+}
+
+/// Pushes rigid bodies inside force areas.
+///
+/// For each body overlapping an area: `velocity += acceleration / mass * dt`
+/// and `position += velocity_offset * dt`. Overlapping areas stack.
+#[allow(clippy::type_complexity)]
+pub fn force_area_system(
+    mut queries: ParamSet<(
+        Query<(
+            Entity,
+            &ForceArea,
+            &MapPosition,
+            Option<&GlobalTransform2D>,
+            Option<&BoxCollider>,
+            Has<Wind>,
+        )>,
+        Query<(
+            Entity,
+            &mut MapPosition,
+            &mut RigidBody,
+            Option<&GlobalTransform2D>,
+            Option<&BoxCollider>,
+            Option<&Group>,
+            Option<&Tags>,
+            Option<&WindResponse>,
+        )>,
+    )>,
+    time: Res<WorldTime>,
+    mut active: Local<Vec<ActiveArea>>,
+) {
+    crate::tracy::tracy_span!("force_area_system");
+    active.clear();
+    for (entity, area, position, global, collider, wind) in queries.p0().iter() {
+        if !area.enabled {
+            continue;
+        }
+        let world_pos = global.map_or(position.pos, |gt| gt.position);
+        let Some(rect) = area.world_rect(world_pos, collider.map(|c| c.as_rectangle(world_pos)))
+        else {
+            continue;
+        };
+        active.push(ActiveArea {
+            entity,
+            rect,
+            acceleration: area.acceleration,
+            velocity: area.velocity,
+            mask: area.mask.clone(),
+            wind,
+        });
+    }
+    if active.is_empty() {
+        return;
+    }
+
+    let delta = time.delta;
+    for (entity, mut position, mut rigidbody, global, collider, group, tags, wind) in
+        queries.p1().iter_mut()
+    {
+        if rigidbody.frozen {
+            continue;
+        }
+        let world_pos = global.map_or(position.pos, |gt| gt.position);
+        let body_rect = collider.map(|c| c.as_rectangle(world_pos));
+        let mut acceleration = Vector2::zero();
+        let mut velocity = Vector2::zero();
+        for area in active.iter() {
+            if area.entity == entity {
+                continue;
+            }
+            let factor = match wind {
+                Some(wind) if area.wind => wind.factor,
+                _ if entity_labels(group, tags).any(|l| pattern_matches(&area.mask, l)) => 1.0,
+                _ => continue,
+            };
+            let inside = match &body_rect {
+                Some(r) => rects_overlap(r, &area.rect),
+                None => rect_contains(&area.rect, world_pos),
+            };
+            if inside {
+                acceleration += area.acceleration * factor;
+                velocity += area.velocity;
+            }
+        }
+        if acceleration != Vector2::zero() {
+            rigidbody.velocity += acceleration * (rigidbody.inverse_mass() * delta);
+        }
+        if velocity != Vector2::zero() {
+            position.pos += velocity * delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world_with_delta(delta: f32) -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta,
+            real_delta: delta,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world
+    }
+
+    #[test]
+    fn acceleration_and_conveyor_apply_only_inside_matching_areas() {
+        let mut world = world_with_delta(0.5);
+        world.spawn((
+            MapPosition::new(0.0, 0.0),
+            ForceArea::acceleration(Vector2::new(10.0, 0.0))
+                .with_rect(Rectangle::new(0.0, 0.0, 100.0, 100.0))
+                .with_mask("player"),
+        ));
+        world.spawn((
+            MapPosition::new(0.0, 50.0),
+            BoxCollider::new(100.0, 10.0),
+            ForceArea::conveyor(Vector2::new(0.0, -4.0)),
+        ));
+        let player = world
+            .spawn((
+                Group::new("player"),
+                MapPosition::new(50.0, 45.0),
+                BoxCollider::new(10.0, 10.0),
+                RigidBody::new(),
+            ))
+            .id();
+        let coin = world
+            .spawn((
+                Group::new("coin"),
+                MapPosition::new(10.0, 10.0),
+                RigidBody::new(),
+            ))
+            .id();
+        let outside = world
+            .spawn((
+                Group::new("player"),
+                MapPosition::new(500.0, 500.0),
+                RigidBody::new(),
+            ))
+            .id();
+
+        world.run_system_once(force_area_system).unwrap();
+
+        assert_eq!(
+            world.get::<RigidBody>(player).unwrap().velocity,
+            Vector2::new(5.0, 0.0)
+        );
+        assert_eq!(
+            world.get::<MapPosition>(player).unwrap().pos,
+            Vector2::new(50.0, 43.0)
+        );
+        assert_eq!(
+            world.get::<RigidBody>(coin).unwrap().velocity,
+            Vector2::zero()
+        );
+        assert_eq!(
+            world.get::<RigidBody>(outside).unwrap().velocity,
+            Vector2::zero()
+        );
+    }
+
+    #[test]
+    fn mass_and_wind_factor_scale_the_push() {
+        let mut world = world_with_delta(1.0);
+        world.spawn((
+            Wind,
+            MapPosition::new(0.0, 0.0),
+            ForceArea::acceleration(Vector2::new(8.0, 0.0))
+                .with_rect(Rectangle::new(0.0, 0.0, 100.0, 100.0))
+                .with_mask("weather"),
+        ));
+        let mut heavy = RigidBody::new();
+        heavy.set_mass(4.0);
+        let crate_ = world
+            .spawn((Group::new("crate"), MapPosition::new(10.0, 10.0), heavy))
+            .id();
+        let mut light = RigidBody::new();
+        light.set_mass(0.5);
+        let leaf = world
+            .spawn((
+                Group::new("leaf"),
+                MapPosition::new(20.0, 20.0),
+                light,
+                WindResponse::new(2.0),
+            ))
+            .id();
+        let flake = world
+            .spawn((
+                Group::new("weather"),
+                MapPosition::new(30.0, 30.0),
+                RigidBody::new(),
+            ))
+            .id();
+
+        world.run_system_once(force_area_system).unwrap();
+
+        // The crate doesn't match the mask and has no WindResponse component.
+        assert_eq!(
+            world.get::<RigidBody>(crate_).unwrap().velocity,
+            Vector2::zero()
+        );
+        assert_eq!(
+            world.get::<RigidBody>(leaf).unwrap().velocity,
+            Vector2::new(32.0, 0.0)
+        );
+        assert_eq!(
+            world.get::<RigidBody>(flake).unwrap().velocity,
+            Vector2::new(8.0, 0.0)
+        );
+
+        world.entity_mut(crate_).insert(WindResponse::default());
+        world.run_system_once(force_area_system).unwrap();
+        assert_eq!(
+            world.get::<RigidBody>(crate_).unwrap().velocity,
+            Vector2::new(2.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn disabled_area_and_frozen_body_are_skipped() {
+        let mut world = world_with_delta(1.0);
+        let area = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                ForceArea::acceleration(Vector2::new(0.0, 10.0))
+                    .with_rect(Rectangle::new(0.0, 0.0, 100.0, 100.0)),
+            ))
+            .id();
+        let mut frozen = RigidBody::new();
+        frozen.freeze();
+        let frozen = world
+            .spawn((Group::new("box"), MapPosition::new(10.0, 10.0), frozen))
+            .id();
+        let body = world
+            .spawn((
+                Group::new("box"),
+                MapPosition::new(20.0, 20.0),
+                RigidBody::new(),
+            ))
+            .id();
+
+        world.get_mut::<ForceArea>(area).unwrap().enabled = false;
+        world.run_system_once(force_area_system).unwrap();
+        assert_eq!(
+            world.get::<RigidBody>(body).unwrap().velocity,
+            Vector2::zero()
+        );
+
+        world.get_mut::<ForceArea>(area).unwrap().enabled = true;
+        world.run_system_once(force_area_system).unwrap();
+        assert_eq!(
+            world.get::<RigidBody>(body).unwrap().velocity,
+            Vector2::new(0.0, 10.0)
+        );
+        assert_eq!(
+            world.get::<RigidBody>(frozen).unwrap().velocity,
+            Vector2::zero()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Formation system.
+//!
+//! [`formation_system`] moves the members of every [`Formation`] toward their
+//! slot around the leader, drops despawned members and disbands formations
+//! whose leader is gone. It runs after movement and path following, so the
+//! slots are laid out around where the leader ends up this frame.
+//!
+//! - [`process_lua_formation_commands`] *(feature = "lua")* applies
+//!   `engine.formation_create` and the other `engine.formation_*` commands.
+//!
+//! # Related
+//!
+//! - [`crate::resources::formation`] – patterns, slots and catch-up
+
+use bevy_ecs::prelude::*;
+use log::debug;
+#[cfg(feature = "lua")]
+use log::warn;
+
+use crate::components::mapposition::MapPosition;
+use crate::components::rotation::Rotation;
+#[cfg(feature = "lua")]
+use crate::resources::formation::Formation;
+use crate::resources::formation::Formations;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::FormationCmd;
+use crate::resources::worldtime::WorldTime;
+
+/// Moves formation members toward their slots.
+pub fn formation_system(
+    mut formations: ResMut<Formations>,
+    mut positions: Query<&mut MapPosition>,
+    rotations: Query<&Rotation>,
+    time: Res<WorldTime>,
+) {
+    if formations.is_empty() {
+        return;
+    }
+    formations.retain(|name, formation| {
+        let Ok(leader) = positions.get(formation.leader) else {
+            debug!("formation '{name}' disbanded: its leader is gone");
+            return false;
+        };
+        let leader_pos = leader.pos;
+        let leader_degrees = rotations.get(formation.leader).map_or(0.0, |r| r.degrees);
+        formation
+            .members
+            .retain(|&member| positions.contains(member));
+
+        let blend = if formation.catch_up > 0.0 {
+            1.0 - (-formation.catch_up * time.delta).exp()
+        } else {
+            1.0
+        };
+        for (index, &member) in formation.members.iter().enumerate() {
+            let slot = formation.slot_position(index, leader_pos, leader_degrees);
+            if let Ok(mut position) = positions.get_mut(member) {
+                position.pos = position.pos.lerp(slot, blend);
+            }
+        }
+        true
+    });
+}
+
+/// Applies formation commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_formation_commands(
+    InMut(buf): InMut<Vec<FormationCmd>>,
+    mut formations: ResMut<Formations>,
+) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            FormationCmd::Create {
+                name,
+                leader_id,
+                pattern,
+                spacing,
+                catch_up,
+                rotate,
+            } => {
+                let Some(leader) = Entity::try_from_bits(leader_id) else {
+                    warn!(target: "lua", "formation_create: invalid leader id {leader_id}");
+                    continue;
+                };
+                let formation = Formation::new(leader, pattern, spacing)
+                    .with_catch_up(catch_up)
+                    .with_rotate(rotate);
+                formations.insert(name, formation);
+            }
+            FormationCmd::Add { name, entity_id } => {
+                let Some(entity) = Entity::try_from_bits(entity_id) else {
+                    continue;
+                };
+                if !formations.add_member(&name, entity) {
+                    warn!(target: "lua", "formation_add: no formation named '{name}'");
+                }
+            }
+            FormationCmd::Remove { name, entity_id } => {
+                if let (Some(formation), Some(entity)) =
+                    (formations.get_mut(&name), Entity::try_from_bits(entity_id))
+                {
+                    formation.remove(entity);
+                }
+            }
+            FormationCmd::Disband { name } => {
+                formations.remove(&name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::formation::{Formation, FormationPattern};
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn members_catch_up_and_despawns_are_dropped() {
+        let mut world = World::new();
+        let mut time = WorldTime::default();
+        time.delta = 0.5;
+        world.insert_resource(time);
+        let leader = world.spawn(MapPosition::new(100.0, 100.0)).id();
+        let near = world.spawn(MapPosition::new(110.0, 110.0)).id();
+        let far = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let lost = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let mut wing = Formation::new(leader, FormationPattern::V, 10.0).with_catch_up(0.0);
+        wing.add(lost);
+        wing.add(near);
+        wing.add(far);
+        let mut formations = Formations::default();
+        formations.insert("wing", wing);
+        world.insert_resource(formations);
+        world.despawn(lost);
+
+        world.run_system_once(formation_system).unwrap();
+        let members = world
+            .resource::<Formations>()
+            .get("wing")
+            .unwrap()
+            .members
+            .clone();
+        assert_eq!(members, vec![near, far]);
+        let far_pos = world.get::<MapPosition>(far).unwrap().pos;
+        assert_eq!((far_pos.x, far_pos.y), (90.0, 110.0));
+
+        world
+            .resource_mut::<Formations>()
+            .get_mut("wing")
+            .unwrap()
+            .catch_up = 2.0;
+        world.get_mut::<MapPosition>(leader).unwrap().pos.x += 20.0;
+        world.run_system_once(formation_system).unwrap();
+        let near_pos = world.get::<MapPosition>(near).unwrap().pos;
+        assert!(near_pos.x > 110.0 && near_pos.x < 130.0);
+
+        world.despawn(leader);
+        world.run_system_once(formation_system).unwrap();
+        assert!(world.resource::<Formations>().is_empty());
+    }
+
+    #[cfg(feature = "lua")]
+    #[test]
+    fn lua_commands_build_the_formation() {
+        use crate::resources::lua_runtime::LuaRuntime;
+
+        let mut world = World::new();
+        let leader = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let ship = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(format!(
+                "engine.formation_create('squad1', {}, 'circle', 32, {{ rotate = true }})\n\
+                 engine.formation_add('squad1', {})",
+                leader.to_bits(),
+                ship.to_bits()
+            ))
+            .exec()
+            .expect("queue formation commands");
+        assert!(
+            runtime
+                .lua()
+                .load("engine.formation_create('squad2', 1, 'wedge', 32)")
+                .exec()
+                .is_err()
+        );
+        world.insert_non_send(runtime);
+        world.insert_resource(Formations::default());
+        world
+            .run_system_once(crate::systems::lua_commands::apply_lua_commands)
+            .unwrap();
+
+        let formations = world.resource::<Formations>();
+        let squad = formations.get("squad1").unwrap();
+        assert_eq!(squad.pattern, FormationPattern::Circle);
+        assert!(squad.rotate);
+        assert_eq!(squad.members, vec![ship]);
+    }
+}
@@ -0,0 +1,43 @@
+//! Frame arena reset.
+//!
+//! [`reset_frame_arena`] runs last in the frame, frees every buffer the hot
+//! systems took from the [`FrameArena`] and reports the frame's allocation
+//! counters to Tracy (see [`framearena`](crate::resources::framearena)).
+
+use bevy_ecs::prelude::*;
+
+use crate::resources::framearena::FrameArena;
+
+/// Release this frame's temporary buffers and publish the arena counters.
+pub fn reset_frame_arena(mut arena: ResMut<FrameArena>) {
+    #[cfg_attr(not(feature = "tracy"), allow(unused_variables))]
+    let stats = arena.reset();
+    crate::tracy::tracy_plot!("frame_arena_allocations", stats.allocations);
+    crate::tracy::tracy_plot!("frame_arena_bytes", stats.bytes_used as f64);
+    crate::tracy::tracy_plot!("frame_arena_capacity", stats.capacity as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn reset_keeps_last_frame_counters() {
+        let mut world = World::new();
+        world.insert_resource(FrameArena::default());
+        {
+            let mut arena = world.resource_mut::<FrameArena>();
+            let scratch = arena.scratch();
+            let mut buffer = scratch.vec::<u32>();
+            buffer.push(1);
+        }
+        world
+            .run_system_once(reset_frame_arena)
+            .expect("reset_frame_arena");
+
+        let mut arena = world.resource_mut::<FrameArena>();
+        assert_eq!(arena.last_frame().allocations, 1);
+        assert_eq!(arena.stats().allocations, 0);
+    }
+}
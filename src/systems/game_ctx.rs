@@ -42,6 +42,7 @@ use crate::components::screenposition::ScreenPosition;
 use crate::components::signals::Signals;
 use crate::components::sprite::Sprite;
 use crate::components::stuckto::StuckTo;
+use crate::components::tags::Tags;
 use crate::events::audio::AudioCmd;
 use crate::resources::appstate::AppState;
 use crate::resources::camerafollowconfig::CameraFollowConfig;
@@ -93,6 +94,8 @@ pub struct GameCtx<'w, 's> {
     // Read-only queries
     /// Read-only access to entity groups.
     pub groups: Query<'w, 's, &'static Group>,
+    /// Read-only access to entity tags (extra labels beyond the group).
+    pub tags: Query<'w, 's, &'static Tags>,
     /// Read-only access to screen-space positions.
     pub screen_positions: Query<'w, 's, &'static ScreenPosition>,
     /// Read-only access to box colliders.
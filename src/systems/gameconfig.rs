@@ -4,13 +4,18 @@
 //! render target, and screen size resources.
 //!
 //! On initial insertion, loads configuration from the INI file. On subsequent
-//! changes, applies the new settings to the running game.
+//! changes, applies the new settings to the running game, including the audio
+//! mixer volumes and the built-in colorblind shader.
 
-use crate::events::switchfullscreen::SwitchFullScreenEvent;
+use crate::events::audio::AudioCmd;
+use crate::events::resolution::ResolutionChangedEvent;
+use crate::events::switchfullscreen::ApplyWindowModeEvent;
 use crate::resources::fullscreen::FullScreen;
-use crate::resources::gameconfig::GameConfig;
+use crate::resources::gameconfig::{GameConfig, clamp_render_scale};
 use crate::resources::rendertarget::RenderTarget;
 use crate::resources::screensize::ScreenSize;
+use crate::resources::shaderstore::ShaderStore;
+use crate::resources::systemtoggles::SystemToggles;
 use bevy_ecs::prelude::*;
 use log::{debug, error};
 use raylib::ffi;
@@ -19,7 +24,17 @@ use raylib::ffi;
 ///
 /// This system detects when [`GameConfig`] is added or modified and:
 /// 1. On first addition: loads settings from the config file
-/// 2. On any change: applies render size, window size, and FPS settings
+/// 2. On any change: applies render size (triggering [`ResolutionChangedEvent`]
+///    when the render target is recreated), supersampling scale, window mode (triggering
+///    [`ApplyWindowModeEvent`] when fullscreen, mode or monitor differ from the
+///    [`FullScreen`] resource), and FPS settings, and
+///    sends [`AudioCmd::SetVolumes`] when the volumes differ from the last ones sent
+/// 3. Compiles the shader of the selected
+///    [`ColorblindMode`](crate::resources::accessibility::ColorblindMode) into
+///    the [`ShaderStore`] the first time that mode is used
+/// 4. Copies `system_toggles` into the [`SystemToggles`] resource read by the
+///    run conditions of the non-essential systems, keeping the systems
+///    throttled by the quality governor
 ///
 /// # Resource Dependencies
 /// - `GameConfig` (optional, mutable) - the configuration to monitor
@@ -27,13 +42,20 @@ use raylib::ffi;
 /// - `RaylibThread` (non-send) - required for render texture recreation
 /// - `RenderTarget` (non-send, mutable) - for render resolution changes
 /// - `ScreenSize` (mutable) - updated to match render resolution
+/// - `ShaderStore` (non-send, mutable) - receives the built-in colorblind shaders
+/// - `SystemToggles` (mutable) - mirrors `GameConfig::system_toggles`
+#[allow(clippy::too_many_arguments)]
 pub fn apply_gameconfig_changes(
     maybe_config: Option<Res<GameConfig>>,
     mut raylib: crate::systems::RaylibAccess,
     mut render_target: NonSendMut<RenderTarget>,
+    mut shader_store: NonSendMut<ShaderStore>,
     mut screen_size: ResMut<ScreenSize>,
+    mut system_toggles: ResMut<SystemToggles>,
     fullscreen: Option<Res<FullScreen>>,
     mut commands: Commands,
+    mut audio_cmd_writer: MessageWriter<AudioCmd>,
+    mut sent_volumes: Local<Option<(f32, f32, f32)>>,
 ) {
     let (rl, th) = (&mut *raylib.rl, &*raylib.th);
     let Some(config) = maybe_config else {
@@ -53,6 +75,7 @@ pub fn apply_gameconfig_changes(
                 config.render_width,
                 config.render_height
             );
+            let (old_width, old_height) = (render_target.game_width, render_target.game_height);
             if let Err(e) =
                 render_target.recreate(rl, th, config.render_width, config.render_height)
             {
@@ -60,6 +83,24 @@ pub fn apply_gameconfig_changes(
             } else {
                 screen_size.w = config.render_width as i32;
                 screen_size.h = config.render_height as i32;
+                commands.trigger(ResolutionChangedEvent {
+                    old_width,
+                    old_height,
+                    width: config.render_width,
+                    height: config.render_height,
+                });
+            }
+        }
+
+        // Apply supersampling factor if changed
+        let scale = clamp_render_scale(config.render_scale);
+        if scale != render_target.scale {
+            debug!(
+                "Render target supersampling: {}x -> {}x",
+                render_target.scale, scale
+            );
+            if let Err(e) = render_target.set_scale(rl, th, scale) {
+                error!("Failed to change render target scale: {}", e);
             }
         }
 
@@ -68,15 +109,16 @@ pub fn apply_gameconfig_changes(
             render_target.set_filter(config.render_target_filter);
         }
 
-        // Synchronize fullscreen state between config and window
-        let is_fullscreen = fullscreen.is_some();
-        if config.fullscreen != is_fullscreen {
-            // Config and window state don't match - fire event to toggle
+        // Synchronize fullscreen state, mode and monitor between config and window
+        let current = fullscreen.map(|f| *f);
+        let wanted = FullScreen::from_config(&config);
+        if wanted != current {
+            // Config and window state don't match - fire event to apply the config
             debug!(
-                "Fullscreen mismatch: config={}, window={} - triggering toggle",
-                config.fullscreen, is_fullscreen
+                "Window mode mismatch: config={:?}, window={:?} - applying config",
+                wanted, current
             );
-            commands.trigger(SwitchFullScreenEvent {});
+            commands.trigger(ApplyWindowModeEvent {});
         }
 
         // Note: resizing the OS window to match config.window_size() when not
@@ -98,8 +140,40 @@ pub fn apply_gameconfig_changes(
             }
         }
 
-        // Apply target FPS
-        rl.set_target_fps(config.target_fps);
+        // Apply target FPS (or the background cap while unfocused)
+        rl.set_target_fps(config.frame_cap(rl.is_window_focused()));
+
+        // Apply audio volumes (the startup values are sent by EngineBuilder
+        // before the first frame, so PlayMusic during setup already uses them)
+        let volumes = config.volumes();
+        if *sent_volumes != Some(volumes) {
+            let (master, music, sfx) = volumes;
+            audio_cmd_writer.write(AudioCmd::SetVolumes { master, music, sfx });
+            *sent_volumes = Some(volumes);
+        }
+
+        // Compile the colorblind shader on first use; the render system
+        // appends it to the post-process chain
+        if let (Some(key), Some(source)) = (
+            config.colorblind_mode.shader_key(),
+            config.colorblind_mode.fragment_shader(),
+        ) && !shader_store.contains(key)
+        {
+            match rl.load_shader_from_memory(th, None, Some(&source)) {
+                Ok(shader) if shader.is_shader_valid() => {
+                    debug!("Loaded built-in shader '{}'", key);
+                    shader_store.add(key, shader);
+                }
+                Ok(_) => error!("Built-in shader '{}' loaded but is invalid", key),
+                Err(e) => error!("Built-in shader '{}' failed to load: {e}", key),
+            }
+        }
+
+        let toggles = system_toggles.with_settings_of(config.system_toggles);
+        if *system_toggles != toggles {
+            *system_toggles = toggles;
+            debug!("System toggles: {:?}", toggles);
+        }
 
         debug!("GameConfig changes applied.");
     }
@@ -9,6 +9,7 @@
 //! - [`clean_all_entities`] despawns all entities that are not marked
 //!   [`Persistent`](crate::components::persistent::Persistent).
 
+use crate::components::audiotriggers::AudioTriggers;
 use crate::components::persistent::CleanableEntity;
 use crate::events::gamestate::GameStateChangedEvent;
 use crate::resources::gamestate::{GameState, GameStates, NextGameState, NextGameStates};
@@ -49,6 +50,10 @@ pub fn clean_all_entities(
     query: Query<Entity, CleanableEntity>,
 ) {
     for entity in query.iter() {
-        commands.entity(entity).try_despawn();
+        // Leaving a scene is not a despawn worth a sound.
+        commands
+            .entity(entity)
+            .try_remove::<AudioTriggers>()
+            .try_despawn();
     }
 }
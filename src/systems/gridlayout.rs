@@ -3,7 +3,14 @@
 //! The [`gridlayout_spawn_system`] processes newly added [`GridLayout`]
 //! components, loads their JSON data, and spawns child entities for each
 //! cell. Spawned entities receive [`MapPosition`], [`Sprite`], [`BoxCollider`],
-//! [`Signals`], [`Group`], and [`ZIndex`] components based on the layout data.
+//! [`Signals`](crate::components::signals::Signals), [`Group`], and [`ZIndex`] components based on the layout data.
+//! Each entry of the optional `spawn_points` list becomes a
+//! [`SpawnPoint`](crate::components::portal::SpawnPoint) entity at the centre
+//! of its cell (`x` is the column, `y` the row).
+//!
+//! When the layout carries a [`GridIntro`], every cell also gets a delayed
+//! [`Tween`] of its [`Scale`] or [`MapPosition`], so the level builds itself
+//! up cell by cell instead of popping in at once.
 //!
 //! # JSON Format
 //!
@@ -18,7 +25,10 @@
 //!   "grid": ["RRGGBB", "YYPPMM"],
 //!   "legend": {
 //!     "R": { "texture_key": "brick_red", "properties": { "hp": 1, "points": 10 } }
-//!   }
+//!   },
+//!   "spawn_points": [
+//!     { "name": "player_start", "x": 3, "y": 12, "properties": { "facing": "up" } }
+//!   ]
 //! }
 //! ```
 //!
@@ -27,17 +37,18 @@
 //! - [`crate::components::gridlayout::GridLayout`] – the trigger component
 //! - [`crate::components::gridlayout::GridLayoutData`] – the parsed JSON structure
 
-use std::sync::Arc;
-
 use bevy_ecs::prelude::*;
 use raylib::prelude::Vector2;
 
 use crate::components::boxcollider::BoxCollider;
-use crate::components::gridlayout::{GridLayout, GridLayoutData, GridValue};
+use crate::components::gridlayout::{
+    GridIntro, GridLayout, GridLayoutData, IntroPreset, properties_to_signals,
+};
 use crate::components::group::Group;
 use crate::components::mapposition::MapPosition;
-use crate::components::signals::Signals;
+use crate::components::scale::Scale;
 use crate::components::sprite::Sprite;
+use crate::components::tween::Tween;
 use crate::components::zindex::ZIndex;
 use log::{error, info};
 
@@ -65,35 +76,16 @@ pub fn gridlayout_spawn_system(
         };
 
         // Spawn entities for each cell in the grid
-        for (x, y, cell) in layout_data.iter_cells() {
-            let mut signals = Signals::default();
-
+        for (index, (col, row, x, y, cell)) in layout_data.iter_cells_indexed().enumerate() {
             // Copy all properties from the cell to signals
-            for (key, value) in &cell.properties {
-                match value {
-                    GridValue::Int(v) => {
-                        signals.set_integer(key, *v as i32);
-                    }
-                    GridValue::Float(v) => {
-                        signals.set_scalar(key, *v as f32);
-                    }
-                    GridValue::String(v) => {
-                        signals.set_string(key, v.clone());
-                    }
-                    GridValue::Bool(v) => {
-                        if *v {
-                            signals.set_flag(key);
-                        }
-                    }
-                }
-            }
+            let signals = properties_to_signals(&cell.properties);
 
-            commands.spawn((
+            let mut entity = commands.spawn((
                 Group::new(&grid_layout.group),
                 MapPosition::new(x, y),
                 ZIndex(grid_layout.z_index),
                 Sprite {
-                    tex_key: Arc::from(cell.texture_key.clone()),
+                    tex_key: cell.texture_key.as_str().into(),
                     width: layout_data.cell_width,
                     height: layout_data.cell_height,
                     offset: Vector2::zero(),
@@ -117,6 +109,13 @@ pub fn gridlayout_spawn_system(
                 },
                 signals,
             ));
+            if let Some(intro) = &grid_layout.intro {
+                insert_intro(&mut entity, intro, intro.delay(index, col, row), x, y);
+            }
+        }
+        for point in &layout_data.spawn_points {
+            let (x, y) = layout_data.cell_center(point.x, point.y);
+            commands.spawn((point.to_spawn_point(), MapPosition::new(x, y)));
         }
         grid_layout.spawned = true;
 
@@ -126,3 +125,71 @@ pub fn gridlayout_spawn_system(
         );
     }
 }
+
+/// Starts a cell hidden by the intro preset and tweens it to its resting state.
+fn insert_intro(entity: &mut EntityCommands, intro: &GridIntro, delay: f32, x: f32, y: f32) {
+    match intro.preset {
+        IntroPreset::ScaleIn => {
+            entity.insert((
+                Scale::new(0.0, 0.0),
+                Tween::new(Scale::new(0.0, 0.0), Scale::new(1.0, 1.0), intro.duration)
+                    .with_easing(intro.easing)
+                    .with_delay(delay),
+            ));
+        }
+        IntroPreset::DropIn { height } => {
+            let from = MapPosition::new(x, y - height);
+            entity.insert((
+                from,
+                Tween::new(from, MapPosition::new(x, y), intro.duration)
+                    .with_easing(intro.easing)
+                    .with_delay(delay),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::gridlayout::IntroOrder;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn intro_staggers_cell_tweens() {
+        let path = std::env::temp_dir().join("gridlayout_intro_test.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "offset_x": 0.0, "offset_y": 0.0, "cell_width": 10.0, "cell_height": 10.0,
+                "grid": ["R.R", "RR."], "legend": {"R": {"texture_key": "brick"}, ".": null}
+            }"#,
+        )
+        .unwrap();
+        let mut world = World::new();
+        world.spawn(
+            GridLayout::new(path.to_string_lossy(), "bricks", 1.0).with_intro(
+                GridIntro::new(IntroPreset::DropIn { height: 40.0 }, 0.5, 0.25)
+                    .with_order(IntroOrder::Columns),
+            ),
+        );
+
+        world.run_system_once(gridlayout_spawn_system).unwrap();
+
+        let mut cells: Vec<(f32, f32, f32)> = world
+            .query::<(&MapPosition, &Tween<MapPosition>)>()
+            .iter(&world)
+            .map(|(pos, tween)| (tween.to.pos.x, pos.pos.y, tween.time))
+            .collect();
+        cells.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            cells,
+            vec![
+                (5.0, -35.0, 0.0),
+                (5.0, -25.0, 0.0),
+                (15.0, -25.0, -0.5),
+                (25.0, -35.0, -1.0),
+            ]
+        );
+    }
+}
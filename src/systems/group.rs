@@ -21,24 +21,46 @@
 //! Counts are stored with the key format `"group_count:{name}"`. Use
 //! `world_signals.get_group_count("name")` for convenient access.
 //!
+//! # Aggregates
+//!
+//! Groups registered with [`TrackedGroups::add_aggregated_group`] also get a
+//! per-frame [`GroupAggregate`](crate::resources::group::GroupAggregate)
+//! (centroid, bounding box, member positions) in the
+//! [`GroupAggregates`] resource, filled by [`update_group_aggregates_system`].
+//!
+//! # Batch operations
+//!
+//! [`TranslateGroup`] and [`SetGroupVisible`] are world [`Command`]s that
+//! move or hide every member of a group (matched by [`Group`] name or
+//! [`Tags`]) in one query pass, for `engine.group_translate()` and
+//! `engine.group_set_visible()`.
+//!
 //! # Related
 //!
 //! - [`TrackedGroups`](crate::resources::group::TrackedGroups) – configures which groups to count
 //! - [`WorldSignals`](crate::resources::worldsignals::WorldSignals) – where counts are published
 //! - [`Group`](crate::components::group::Group) – the group tag component
+//! - [`Tags`](crate::components::tags::Tags) – extra labels, counted like group names
 
+use crate::components::globaltransform2d::GlobalTransform2D;
 use crate::components::group::Group;
-use crate::resources::group::TrackedGroups;
+use crate::components::hidden::Hidden;
+use crate::components::mapposition::MapPosition;
+use crate::components::tags::{Tags, entity_labels};
+use crate::resources::group::{GroupAggregates, TrackedGroups};
 use crate::resources::worldsignals::WorldSignals;
+use bevy_ecs::hierarchy::ChildOf;
 use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 /// Counts entities for each tracked group and updates [`WorldSignals`].
 ///
 /// For each group name registered in [`TrackedGroups`], this system counts
-/// how many entities have a matching [`Group`] component and stores the
-/// result as an integer signal with the key `group_count:{name}`.
+/// how many entities have a matching [`Group`] component or carry the name in
+/// their [`Tags`], and stores the result as an integer signal with the key
+/// `group_count:{name}`. An entity is counted at most once per name.
 ///
 /// Groups with zero entities are correctly reported as `0`, which is
 /// essential for detecting when all entities of a group have been despawned.
@@ -57,7 +79,7 @@ use rustc_hash::FxHashMap;
 /// }
 /// ```
 pub fn update_group_counts_system(
-    query_group: Query<&Group>,
+    query_labels: Query<(Option<&Group>, Option<&Tags>), Or<(With<Group>, With<Tags>)>>,
     mut world_signals: ResMut<WorldSignals>,
     tracked_groups: Res<TrackedGroups>,
     mut counts: Local<FxHashMap<String, i32>>,
@@ -76,9 +98,11 @@ pub fn update_group_counts_system(
         }
     }
 
-    for group in query_group.iter() {
-        if let Some(c) = counts.get_mut(group.name()) {
-            *c += 1;
+    for (group, tags) in query_labels.iter() {
+        for label in entity_labels(group, tags) {
+            if let Some(c) = counts.get_mut(label) {
+                *c += 1;
+            }
         }
     }
 
@@ -86,3 +110,199 @@ pub fn update_group_counts_system(
         world_signals.set_group_count(name, *count);
     }
 }
+
+/// Rebuilds [`GroupAggregates`] for every group with aggregates enabled.
+///
+/// Members are matched by [`Group`] name or [`Tags`]. Member positions come
+/// from [`GlobalTransform2D`] when present (parented entities), otherwise from
+/// [`MapPosition`]. Member vectors are reused across frames to avoid
+/// reallocating.
+#[allow(clippy::type_complexity)]
+pub fn update_group_aggregates_system(
+    query: Query<
+        (
+            Entity,
+            Option<&Group>,
+            Option<&Tags>,
+            Option<&MapPosition>,
+            Option<&GlobalTransform2D>,
+        ),
+        Or<(With<Group>, With<Tags>)>,
+    >,
+    tracked_groups: Res<TrackedGroups>,
+    mut aggregates: ResMut<GroupAggregates>,
+) {
+    crate::tracy::tracy_span!("update_group_aggregates");
+    if tracked_groups.aggregated.is_empty() {
+        if !aggregates.groups.is_empty() {
+            aggregates.groups.clear();
+        }
+        return;
+    }
+    let aggregates = aggregates.as_mut();
+    aggregates
+        .groups
+        .retain(|name, _| tracked_groups.aggregated.contains(name));
+    for name in tracked_groups.aggregated.iter() {
+        match aggregates.groups.get_mut(name) {
+            Some(agg) => agg.members.clear(),
+            None => {
+                aggregates.groups.insert(name.clone(), Default::default());
+            }
+        }
+    }
+
+    for (entity, group, tags, map_pos, global) in query.iter() {
+        let pos = match (global, map_pos) {
+            (Some(gt), _) => gt.position,
+            (None, Some(mp)) => mp.pos,
+            (None, None) => continue,
+        };
+        for label in entity_labels(group, tags) {
+            if let Some(agg) = aggregates.groups.get_mut(label) {
+                agg.members.push((entity, pos));
+            }
+        }
+    }
+
+    for agg in aggregates.groups.values_mut() {
+        agg.recompute();
+    }
+}
+
+/// Entities whose [`Group`] name or [`Tags`] include `label`.
+fn group_members(world: &mut World, label: &str) -> FxHashSet<Entity> {
+    let mut query = world
+        .query_filtered::<(Entity, Option<&Group>, Option<&Tags>), Or<(With<Group>, With<Tags>)>>();
+    query
+        .iter(world)
+        .filter(|(_, group, tags)| entity_labels(*group, *tags).any(|l| l == label))
+        .map(|(entity, _, _)| entity)
+        .collect()
+}
+
+/// [`Command`] moving every member of a group by `offset`.
+///
+/// Members with another member among their ancestors are left alone: they
+/// follow it, and moving both would move them twice.
+pub struct TranslateGroup {
+    pub label: String,
+    pub offset: Vector2,
+}
+
+impl Command for TranslateGroup {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let members = group_members(world, &self.label);
+        if members.is_empty() || self.offset == Vector2::zero() {
+            return;
+        }
+        let mut parents = world.query::<&ChildOf>();
+        let parents = parents.query(world);
+        let top_most: Vec<Entity> = members
+            .iter()
+            .copied()
+            .filter(|&entity| !parents.iter_ancestors(entity).any(|a| members.contains(&a)))
+            .collect();
+        for entity in top_most {
+            if let Some(mut position) = world.get_mut::<MapPosition>(entity) {
+                position.pos += self.offset;
+            }
+        }
+    }
+}
+
+/// [`Command`] showing or hiding every member of a group by removing or
+/// inserting [`Hidden`].
+pub struct SetGroupVisible {
+    pub label: String,
+    pub visible: bool,
+}
+
+impl Command for SetGroupVisible {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        for entity in group_members(world, &self.label) {
+            let mut entity = world.entity_mut(entity);
+            if self.visible {
+                entity.remove::<Hidden>();
+            } else if !entity.contains::<Hidden>() {
+                entity.insert(Hidden);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_group_moves_members_once() {
+        let mut world = World::new();
+        let brick = world
+            .spawn((Group::new("bricks"), MapPosition::new(10.0, 10.0)))
+            .id();
+        let tagged = world
+            .spawn((Tags::new(["bricks"]), MapPosition::new(0.0, 0.0)))
+            .id();
+        let child = world
+            .spawn((
+                Group::new("bricks"),
+                MapPosition::new(2.0, 0.0),
+                ChildOf(brick),
+            ))
+            .id();
+        let frame = world
+            .spawn((MapPosition::new(0.0, 0.0), ChildOf(brick)))
+            .id();
+        let grandchild = world
+            .spawn((
+                Tags::new(["bricks"]),
+                MapPosition::new(4.0, 0.0),
+                ChildOf(frame),
+            ))
+            .id();
+        let ball = world
+            .spawn((Group::new("ball"), MapPosition::new(5.0, 5.0)))
+            .id();
+
+        TranslateGroup {
+            label: "bricks".into(),
+            offset: Vector2::new(0.0, 16.0),
+        }
+        .apply(&mut world);
+
+        let pos = |world: &World, e| world.get::<MapPosition>(e).unwrap().pos;
+        assert_eq!(pos(&world, brick), Vector2::new(10.0, 26.0));
+        assert_eq!(pos(&world, tagged), Vector2::new(0.0, 16.0));
+        assert_eq!(pos(&world, child), Vector2::new(2.0, 0.0));
+        assert_eq!(pos(&world, frame), Vector2::new(0.0, 0.0));
+        assert_eq!(pos(&world, grandchild), Vector2::new(4.0, 0.0));
+        assert_eq!(pos(&world, ball), Vector2::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn set_group_visible_toggles_hidden() {
+        let mut world = World::new();
+        let brick = world.spawn(Group::new("bricks")).id();
+        let ball = world.spawn(Group::new("ball")).id();
+
+        SetGroupVisible {
+            label: "bricks".into(),
+            visible: false,
+        }
+        .apply(&mut world);
+        assert!(world.get::<Hidden>(brick).is_some());
+        assert!(world.get::<Hidden>(ball).is_none());
+
+        SetGroupVisible {
+            label: "bricks".into(),
+            visible: true,
+        }
+        .apply(&mut world);
+        assert!(world.get::<Hidden>(brick).is_none());
+    }
+}
@@ -31,6 +31,12 @@ fn contains_point(pos: Vector2, size: Vector2, point: Vector2) -> bool {
     Rectangle::new(pos.x, pos.y, size.x, size.y).check_collision_point_rec(point)
 }
 
+fn set_state(interactable: &mut Mut<GuiInteractable>, state: GuiWidgetState) {
+    if interactable.state != state {
+        interactable.state = state;
+    }
+}
+
 /// Resolves hover/press/click state for every `GuiInteractable` with a
 /// `ScreenPosition` (hidden widgets are automatically excluded, consistent
 /// with the engine's "presence of `ScreenPosition`" visibility idiom).
@@ -75,8 +81,10 @@ pub fn gui_hit_test_system(
             continue;
         }
 
+        // Writes go through `set_state` so widgets whose state did not change
+        // are not flagged as `Changed<GuiInteractable>` every frame.
         if !is_winner {
-            interactable.state = GuiWidgetState::Normal;
+            set_state(&mut interactable, GuiWidgetState::Normal);
             continue;
         }
 
@@ -84,11 +92,14 @@ pub fn gui_hit_test_system(
         let mouse_down = input.mouse_left_button.active;
         let released = input.mouse_left_button.just_released;
 
-        interactable.state = if mouse_down {
-            GuiWidgetState::Pressed
-        } else {
-            GuiWidgetState::Hovered
-        };
+        set_state(
+            &mut interactable,
+            if mouse_down {
+                GuiWidgetState::Pressed
+            } else {
+                GuiWidgetState::Hovered
+            },
+        );
 
         if was_pressed && released {
             commands.trigger(GuiInteractableClickEvent { entity });
@@ -373,4 +384,23 @@ mod tests {
         );
         assert!(world.resource::<GuiInputState>().click_consumed_this_frame);
     }
+
+    #[test]
+    fn unchanged_state_does_not_mark_interactable_changed() {
+        let mut world = new_world();
+        spawn_interactable(&mut world, 10.0, 10.0, 50.0, 20.0, 0.0);
+        world.resource_mut::<InputState>().mouse_x = 20.0;
+        world.resource_mut::<InputState>().mouse_y = 15.0;
+
+        tick(&mut world);
+        world.clear_trackers();
+        tick(&mut world);
+
+        let mut changed = world.query_filtered::<(), Changed<GuiInteractable>>();
+        assert_eq!(
+            changed.iter(&world).count(),
+            0,
+            "a hovered widget that stays hovered must not be marked Changed"
+        );
+    }
 }
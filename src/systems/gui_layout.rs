@@ -85,7 +85,11 @@ fn layout_children(
 
         if let Some(pos) = new_pos {
             if let Ok(mut screen_pos) = screen_positions.get_mut(child_entity) {
-                screen_pos.set_pos(pos);
+                // Only touch the component when the position actually moved,
+                // so `Changed<ScreenPosition>` stays meaningful for static GUIs.
+                if screen_pos.pos() != pos {
+                    screen_pos.set_pos(pos);
+                }
             } else {
                 commands
                     .entity(child_entity)
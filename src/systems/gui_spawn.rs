@@ -186,7 +186,7 @@ pub fn gui_image_spawn_system(
         commands.entity(entity).insert_if_new((
             interactable,
             Sprite {
-                tex_key: Arc::from(image.tex_key.as_str()),
+                tex_key: image.tex_key.as_str().into(),
                 width: image.size.x,
                 height: image.size.y,
                 offset: image.offset,
@@ -704,7 +704,7 @@ mod tests {
             GuiImage::new(32.0, 32.0, "item_sword", 0.0, 0.0),
             GuiInteractable::rust(32.0, 32.0, dummy_callback),
             Sprite {
-                tex_key: Arc::from("custom_override"),
+                tex_key: "custom_override".into(),
                 width: 32.0,
                 height: 32.0,
                 offset: Vector2::new(0.0, 0.0),
@@ -0,0 +1,94 @@
+//! Faux-3D height system.
+//!
+//! [`height_system`] moves every [`Height`] with a [`ZVelocity`], lands it on
+//! the ground and triggers a [`LandedEvent`] for each landing. See
+//! [`crate::components::height`] for how heights are drawn.
+
+use bevy_ecs::prelude::*;
+
+use crate::components::height::{Height, ZVelocity};
+use crate::components::signals::Signals;
+use crate::events::landing::LandedEvent;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldtime::WorldTime;
+
+/// Applies each entity's height gravity and lands it on the ground.
+///
+/// Entities resting on the ground are skipped, so their `Height` and
+/// `Signals` are not touched. Publishes the `height` scalar and `airborne`
+/// flag on entities with `Signals`.
+///
+/// Scheduling: runs after `movement` and before `animation_controller`, so
+/// animation rules see this frame's `airborne` flag.
+pub fn height_system(
+    mut commands: Commands,
+    time: Res<WorldTime>,
+    mut query: Query<(Entity, &mut Height, &mut ZVelocity, Option<&mut Signals>)>,
+) {
+    for (entity, mut height, mut z_velocity, maybe_signals) in query.iter_mut() {
+        if z_velocity.is_resting(height.0) {
+            continue;
+        }
+        let landed = z_velocity.step(&mut height.0, time.delta);
+        if let Some(mut signals) = maybe_signals {
+            signals.update_scalar(sk::HEIGHT, height.0);
+            if height.0 > 0.0 {
+                signals.ensure_flag(sk::AIRBORNE);
+            } else {
+                signals.clear_flag(sk::AIRBORNE);
+            }
+        }
+        if let Some(impact_speed) = landed {
+            commands.trigger(LandedEvent {
+                entity,
+                impact_speed,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Landings(Vec<f32>);
+
+    #[test]
+    fn landing_triggers_event_and_clears_airborne() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.5,
+            real_delta: 0.5,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.init_resource::<Landings>();
+        world.add_observer(|trigger: On<LandedEvent>, mut landings: ResMut<Landings>| {
+            landings.0.push(trigger.event().impact_speed);
+        });
+        let entity = world
+            .spawn((
+                Height(0.0),
+                ZVelocity::new(100.0).launched(100.0),
+                Signals::default(),
+            ))
+            .id();
+
+        // Up to 25 and at the top of the arc, then down
+        for _ in 0..2 {
+            world.run_system_once(height_system).unwrap();
+            let signals = world.get::<Signals>(entity).unwrap();
+            assert!(signals.has_flag(sk::AIRBORNE));
+            assert_eq!(signals.get_scalar(sk::HEIGHT), Some(25.0));
+        }
+        assert!(world.resource::<Landings>().0.is_empty());
+
+        world.run_system_once(height_system).unwrap();
+        assert_eq!(world.get::<Height>(entity), Some(&Height(0.0)));
+        assert!(!world.get::<Signals>(entity).unwrap().has_flag(sk::AIRBORNE));
+        assert_eq!(world.resource::<Landings>().0, vec![50.0]);
+    }
+}
@@ -0,0 +1,264 @@
+//! Hitbox vs hurtbox detection.
+//!
+//! [`hitbox_system`] resolves the active rectangle of every [`Hitbox`] and
+//! [`Hurtbox`] from the entity's current [`Animation`] frame and
+//! [`Sprite`] flip, then triggers a [`HitEvent`] for each hitbox/hurtbox
+//! pair that overlaps for the first time in the current swing.
+//!
+//! # Related
+//!
+//! - [`crate::components::hitbox`] – frame data and swing semantics
+//! - [`crate::events::hit`] – the emitted event
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::animation::Animation;
+use crate::components::collision::pattern_matches;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::hitbox::{FrameBoxes, Hitbox, Hurtbox};
+use crate::components::mapposition::MapPosition;
+use crate::components::sprite::Sprite;
+use crate::components::tags::{Tags, entity_labels};
+use crate::events::hit::HitEvent;
+
+/// Intersection of two rectangles, or `None` if they don't overlap
+/// (touching edges don't count).
+pub fn rect_intersection(a: &Rectangle, b: &Rectangle) -> Option<Rectangle> {
+    let x0 = a.x.max(b.x);
+    let y0 = a.y.max(b.y);
+    let x1 = (a.x + a.width).min(b.x + b.width);
+    let y1 = (a.y + a.height).min(b.y + b.height);
+    (x1 > x0 && y1 > y0).then(|| Rectangle::new(x0, y0, x1 - x0, y1 - y0))
+}
+
+fn resolve(
+    boxes: &FrameBoxes,
+    position: &MapPosition,
+    global: Option<&GlobalTransform2D>,
+    animation: Option<&Animation>,
+    sprite: Option<&Sprite>,
+) -> Option<Rectangle> {
+    let world_pos: Vector2 = global.map_or(position.pos, |gt| gt.position);
+    let (flip_h, flip_v) = sprite.map_or((false, false), |s| (s.flip_h, s.flip_v));
+    boxes.world_rect(
+        animation.map(|a| (a.animation_key.as_str(), a.frame_index)),
+        world_pos,
+        flip_h,
+        flip_v,
+    )
+}
+
+/// Matches active hitboxes against active hurtboxes and triggers [`HitEvent`]s.
+///
+/// Run after animation and transform propagation so frame indices and
+/// positions are current.
+#[allow(clippy::type_complexity)]
+pub fn hitbox_system(
+    mut commands: Commands,
+    mut hitboxes: Query<(
+        Entity,
+        &mut Hitbox,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+        Option<&Animation>,
+        Option<&Sprite>,
+    )>,
+    hurtboxes: Query<(
+        Entity,
+        &Hurtbox,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+        Option<&Animation>,
+        Option<&Sprite>,
+        Option<&Group>,
+        Option<&Tags>,
+    )>,
+    mut active_hurt: Local<Vec<(Entity, Rectangle)>>,
+) {
+    crate::tracy::tracy_span!("hitbox_system");
+    if hitboxes.is_empty() {
+        return;
+    }
+    active_hurt.clear();
+    for (entity, hurtbox, position, global, animation, sprite, _, _) in hurtboxes.iter() {
+        if let Some(rect) = resolve(&hurtbox.boxes, position, global, animation, sprite) {
+            active_hurt.push((entity, rect));
+        }
+    }
+
+    for (attacker, mut hitbox, position, global, animation, sprite) in hitboxes.iter_mut() {
+        let key = animation.map(|a| a.animation_key.as_str());
+        let frame = animation.map_or(0, |a| a.frame_index);
+        if hitbox.swing_key.as_deref() != key {
+            hitbox.swing_key = key.map(str::to_owned);
+            hitbox.hits.clear();
+        } else if frame < hitbox.swing_frame {
+            // A looping animation started its next cycle.
+            hitbox.hits.clear();
+        }
+        if hitbox.swing_frame != frame {
+            hitbox.swing_frame = frame;
+        }
+        let Some(rect) = resolve(&hitbox.boxes, position, global, animation, sprite) else {
+            if !hitbox.hits.is_empty() {
+                hitbox.hits.clear();
+            }
+            continue;
+        };
+        for &(target, hurt_rect) in active_hurt.iter() {
+            if target == attacker || hitbox.hits.contains(&target) {
+                continue;
+            }
+            let Some(overlap) = rect_intersection(&rect, &hurt_rect) else {
+                continue;
+            };
+            let Ok((.., group, tags)) = hurtboxes.get(target) else {
+                continue;
+            };
+            if !entity_labels(group, tags).any(|l| pattern_matches(&hitbox.mask, l)) {
+                continue;
+            }
+            hitbox.hits.push(target);
+            commands.trigger(HitEvent {
+                attacker,
+                target,
+                overlap,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Hits(Vec<(Entity, Entity)>);
+
+    fn setup() -> (World, Entity, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Hits>();
+        world.add_observer(|hit: On<HitEvent>, mut hits: ResMut<Hits>| {
+            hits.0.push((hit.event().attacker, hit.event().target));
+        });
+        let attacker = world
+            .spawn((
+                Group::new("player"),
+                MapPosition::new(0.0, 0.0),
+                Animation::new("idle"),
+                Hitbox::new().with_mask("enemy").with_frame(
+                    "punch",
+                    1,
+                    Rectangle::new(5.0, -5.0, 10.0, 4.0),
+                ),
+                Hurtbox::new(Rectangle::new(-4.0, -8.0, 8.0, 8.0)),
+            ))
+            .id();
+        let enemy = world
+            .spawn((
+                Group::new("enemy"),
+                MapPosition::new(12.0, 0.0),
+                Hurtbox::new(Rectangle::new(-4.0, -8.0, 8.0, 8.0)),
+            ))
+            .id();
+        (world, attacker, enemy)
+    }
+
+    fn set_frame(world: &mut World, entity: Entity, key: &str, frame: usize) {
+        let mut anim = world.get_mut::<Animation>(entity).unwrap();
+        anim.animation_key = key.to_string();
+        anim.frame_index = frame;
+    }
+
+    fn run(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(hitbox_system);
+        schedule.run(world);
+        world.flush();
+    }
+
+    #[test]
+    fn rect_intersection_excludes_touching_edges() {
+        let a = Rectangle::new(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(
+            rect_intersection(&a, &Rectangle::new(5.0, 5.0, 10.0, 10.0)),
+            Some(Rectangle::new(5.0, 5.0, 5.0, 5.0))
+        );
+        assert_eq!(
+            rect_intersection(&a, &Rectangle::new(10.0, 0.0, 5.0, 5.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn hits_once_per_swing_on_active_frames_only() {
+        let (mut world, attacker, enemy) = setup();
+
+        // Idle: no frame data for "idle" and no default → inactive.
+        run(&mut world);
+        assert!(world.resource::<Hits>().0.is_empty());
+
+        set_frame(&mut world, attacker, "punch", 0);
+        run(&mut world);
+        assert!(world.resource::<Hits>().0.is_empty());
+
+        set_frame(&mut world, attacker, "punch", 1);
+        run(&mut world);
+        run(&mut world);
+        assert_eq!(world.resource::<Hits>().0, vec![(attacker, enemy)]);
+
+        // Next swing of the same animation hits again.
+        set_frame(&mut world, attacker, "punch", 0);
+        run(&mut world);
+        set_frame(&mut world, attacker, "punch", 1);
+        run(&mut world);
+        assert_eq!(world.resource::<Hits>().0.len(), 2);
+    }
+
+    #[test]
+    fn looping_animation_wrap_starts_a_new_swing() {
+        let (mut world, attacker, enemy) = setup();
+        world.get_mut::<Hitbox>(attacker).unwrap().boxes.set_frame(
+            "punch",
+            0,
+            Rectangle::new(5.0, -5.0, 10.0, 4.0),
+        );
+
+        // Live on every frame, so the hitbox never goes inactive.
+        set_frame(&mut world, attacker, "punch", 0);
+        run(&mut world);
+        set_frame(&mut world, attacker, "punch", 1);
+        run(&mut world);
+        assert_eq!(world.resource::<Hits>().0, vec![(attacker, enemy)]);
+
+        set_frame(&mut world, attacker, "punch", 0);
+        run(&mut world);
+        assert_eq!(world.resource::<Hits>().0.len(), 2);
+    }
+
+    #[test]
+    fn flipped_sprite_mirrors_hitbox() {
+        let (mut world, attacker, enemy) = setup();
+        world.entity_mut(attacker).insert(Sprite {
+            tex_key: "t".into(),
+            width: 16.0,
+            height: 16.0,
+            offset: Vector2::zero(),
+            origin: Vector2::zero(),
+            flip_h: true,
+            flip_v: false,
+        });
+        set_frame(&mut world, attacker, "punch", 1);
+        run(&mut world);
+        assert!(world.resource::<Hits>().0.is_empty());
+
+        world.entity_mut(enemy).insert(MapPosition::new(-12.0, 0.0));
+        set_frame(&mut world, attacker, "punch", 0);
+        run(&mut world);
+        set_frame(&mut world, attacker, "punch", 1);
+        run(&mut world);
+        assert_eq!(world.resource::<Hits>().0, vec![(attacker, enemy)]);
+    }
+}
@@ -82,6 +82,7 @@ pub fn update_input_state(
                 commands.trigger(InputEvent {
                     action: $action,
                     pressed: true,
+                    player: None,
                 });
             } else {
                 $state.just_pressed = false;
@@ -91,6 +92,7 @@ pub fn update_input_state(
                 commands.trigger(InputEvent {
                     action: $action,
                     pressed: false,
+                    player: None,
                 });
             } else {
                 $state.just_released = false;
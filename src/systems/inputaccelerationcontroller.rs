@@ -16,8 +16,10 @@ use bevy_ecs::prelude::*;
 use raylib::prelude::Vector2;
 
 use crate::components::inputcontrolled::AccelerationControlled;
+use crate::components::playerindex::PlayerIndex;
 use crate::components::rigidbody::RigidBody;
 use crate::resources::input::InputState;
+use crate::resources::playerinput::PlayerInputs;
 
 /// The force name used by the input acceleration controller.
 pub const INPUT_FORCE_NAME: &str = "input";
@@ -27,11 +29,19 @@ pub const INPUT_FORCE_NAME: &str = "input";
 /// When no input is pressed, the input force is set to zero (friction handles
 /// deceleration). When input is pressed, the input force is set to the
 /// accumulated directional accelerations from the component.
+///
+/// Entities with a [`PlayerIndex`] read their player's input from
+/// [`PlayerInputs`] instead of the shared state.
 pub fn input_acceleration_controller(
-    mut query: Query<(&AccelerationControlled, &mut RigidBody)>,
-    input_state: Res<InputState>,
+    mut query: Query<(&AccelerationControlled, &mut RigidBody, Option<&PlayerIndex>)>,
+    shared_input: Res<InputState>,
+    players: Option<Res<PlayerInputs>>,
 ) {
-    for (accel_controlled, mut rigidbody) in query.iter_mut() {
+    for (accel_controlled, mut rigidbody, owner) in query.iter_mut() {
+        let input_state = match (&players, owner) {
+            (Some(players), Some(owner)) => players.state(owner.0 as usize),
+            _ => &*shared_input,
+        };
         // Calculate acceleration from input
         let mut acceleration = Vector2 { x: 0.0, y: 0.0 };
 
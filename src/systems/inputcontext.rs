@@ -0,0 +1,135 @@
+//! Input context system.
+//!
+//! [`input_context_system`] runs right after the hardware input is read and
+//! releases, in [`InputState`], every action the top of the [`InputContexts`]
+//! stack doesn't list. The stack is cleared when the scene changes, so a
+//! context pushed in one scene never leaks into the next.
+//!
+//! [`PushInputContext`], [`PopInputContext`] and [`DefineInputContext`]
+//! manage the stack (Lua: `engine.push_input_context`,
+//! `engine.pop_input_context`, `engine.define_input_context`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::inputcontext`] – context definitions and the stack
+
+use bevy_ecs::prelude::*;
+
+use crate::events::input::InputAction;
+use crate::resources::input::InputState;
+use crate::resources::inputcontext::InputContexts;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Command making a context the active one.
+pub struct PushInputContext {
+    pub name: String,
+}
+
+impl Command for PushInputContext {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let scene = world
+            .resource::<WorldSignals>()
+            .get_string(sk::SCENE)
+            .cloned();
+        let mut contexts = world.get_resource_or_insert_with(InputContexts::default);
+        // Claim the stack for the current scene so the system doesn't clear
+        // a context pushed from a scene's setup.
+        contexts.scene = scene;
+        contexts.push(self.name);
+    }
+}
+
+/// Command returning to the context below the active one.
+pub struct PopInputContext;
+
+impl Command for PopInputContext {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        if let Some(mut contexts) = world.get_resource_mut::<InputContexts>() {
+            contexts.pop();
+        }
+    }
+}
+
+/// Command defining (or redefining) the actions live in a context.
+pub struct DefineInputContext {
+    pub name: String,
+    pub actions: Vec<InputAction>,
+}
+
+impl Command for DefineInputContext {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        world
+            .get_resource_or_insert_with(InputContexts::default)
+            .define(self.name, self.actions);
+    }
+}
+
+/// Clears the stack on scene change and releases the actions the active
+/// context doesn't allow.
+pub fn input_context_system(
+    mut contexts: ResMut<InputContexts>,
+    mut input: ResMut<InputState>,
+    signals: Res<WorldSignals>,
+) {
+    let scene = signals.get_string(sk::SCENE);
+    if contexts.scene.as_ref() != scene {
+        contexts.scene = scene.cloned();
+        contexts.clear();
+    }
+    if contexts.active().is_some() {
+        contexts.apply(&mut input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn frame(world: &mut World) -> InputState {
+        let mut input = InputState::default();
+        input.maindirection_left.active = true;
+        input.action_1.just_pressed = true;
+        world.insert_resource(input);
+        world.run_system_once(input_context_system).unwrap();
+        world.resource::<InputState>().clone()
+    }
+
+    #[test]
+    fn dialogue_context_holds_until_popped_or_scene_change() {
+        let mut world = World::new();
+        let mut signals = WorldSignals::default();
+        signals.set_string(sk::SCENE, "level1");
+        world.insert_resource(signals);
+        world.init_resource::<InputContexts>();
+
+        world.commands().queue(PushInputContext {
+            name: "dialogue".into(),
+        });
+        world.flush();
+        let input = frame(&mut world);
+        assert!(!input.maindirection_left.active);
+        assert!(input.action_1.just_pressed);
+
+        world.commands().queue(PopInputContext);
+        world.flush();
+        assert!(frame(&mut world).maindirection_left.active);
+
+        world.commands().queue(PushInputContext {
+            name: "dialogue".into(),
+        });
+        world.flush();
+        world
+            .resource_mut::<WorldSignals>()
+            .set_string(sk::SCENE, "menu");
+        assert!(frame(&mut world).maindirection_left.active);
+        assert_eq!(world.resource::<InputContexts>().active(), None);
+    }
+}
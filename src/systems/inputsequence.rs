@@ -0,0 +1,93 @@
+//! Input sequence system.
+//!
+//! [`input_sequence_system`] feeds each frame's [`InputState`] to the
+//! [`InputSequences`] patterns and triggers an [`InputSequenceEvent`] for
+//! every one completed. It runs after the input context system, so actions
+//! the active context releases don't count.
+//!
+//! [`DefineInputSequence`] adds or replaces a pattern (Lua:
+//! `engine.define_sequence`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::inputsequence`] – pattern format and matching rules
+
+use bevy_ecs::prelude::*;
+
+use crate::events::inputsequence::InputSequenceEvent;
+use crate::resources::input::InputState;
+use crate::resources::inputsequence::{InputChord, InputSequences};
+use crate::resources::worldtime::WorldTime;
+
+/// Command defining (or redefining) an input pattern.
+pub struct DefineInputSequence {
+    pub name: String,
+    pub steps: Vec<InputChord>,
+    pub window: f32,
+}
+
+impl Command for DefineInputSequence {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        world
+            .get_resource_or_insert_with(InputSequences::default)
+            .define(self.name, self.steps, self.window);
+    }
+}
+
+/// Matches the frame's presses against every pattern.
+pub fn input_sequence_system(
+    mut sequences: ResMut<InputSequences>,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+    mut commands: Commands,
+) {
+    for name in sequences.update(&input, time.delta) {
+        commands.trigger(InputSequenceEvent { name });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::input::InputAction;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Fired(Vec<String>);
+
+    #[test]
+    fn completed_pattern_triggers_event() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.1,
+            real_delta: 0.1,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.init_resource::<InputSequences>();
+        world.init_resource::<Fired>();
+        world.add_observer(
+            |trigger: On<InputSequenceEvent>, mut fired: ResMut<Fired>| {
+                fired.0.push(trigger.event().name.clone());
+            },
+        );
+        world.commands().queue(DefineInputSequence {
+            name: "double_tap".into(),
+            steps: vec![vec![InputAction::Action1], vec![InputAction::Action1]],
+            window: 0.5,
+        });
+        world.flush();
+
+        for _ in 0..2 {
+            let mut input = InputState::default();
+            input.action_1.active = true;
+            input.action_1.just_pressed = true;
+            world.insert_resource(input);
+            world.run_system_once(input_sequence_system).unwrap();
+        }
+        assert_eq!(world.resource::<Fired>().0, ["double_tap"]);
+    }
+}
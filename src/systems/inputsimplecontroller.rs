@@ -8,15 +8,25 @@ use bevy_ecs::prelude::*;
 use raylib::prelude::Vector2;
 
 use crate::components::inputcontrolled::InputControlled;
+use crate::components::playerindex::PlayerIndex;
 use crate::components::rigidbody::RigidBody;
 use crate::resources::input::InputState;
+use crate::resources::playerinput::PlayerInputs;
 
 /// Update each controlled entity's `RigidBody` velocity based on input.
+///
+/// Entities with a [`PlayerIndex`] read their player's input from
+/// [`PlayerInputs`] instead of the shared state.
 pub fn input_simple_controller(
-    mut query: Query<(&InputControlled, &mut RigidBody)>,
-    input_state: Res<InputState>,
+    mut query: Query<(&InputControlled, &mut RigidBody, Option<&PlayerIndex>)>,
+    shared_input: Res<InputState>,
+    players: Option<Res<PlayerInputs>>,
 ) {
-    for (keyboard_controlled, mut rigidbody) in query.iter_mut() {
+    for (keyboard_controlled, mut rigidbody, owner) in query.iter_mut() {
+        let input_state = match (&players, owner) {
+            (Some(players), Some(owner)) => players.state(owner.0 as usize),
+            _ => &*shared_input,
+        };
         // Reset velocity
         rigidbody.velocity = Vector2 { x: 0.0, y: 0.0 };
 
@@ -0,0 +1,75 @@
+//! Input analytics system.
+//!
+//! [`input_stats_system`] adds each frame of player input to [`InputStats`]
+//! under the current scene. It runs before the cinematic and input context
+//! systems, so presses they swallow still count, and skips the frames of the
+//! attract mode demo.
+//!
+//! [`SetInputStats`] turns recording on or off and [`DumpInputStats`] writes
+//! the totals to a JSON file (Lua: `engine.record_input_stats`,
+//! `engine.dump_input_stats`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::inputstats`] – what is recorded and the file format
+
+use bevy_ecs::prelude::*;
+use log::{error, info};
+
+use crate::resources::attractmode::AttractMode;
+use crate::resources::input::InputState;
+use crate::resources::inputstats::InputStats;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Command turning input analytics on or off.
+pub struct SetInputStats {
+    pub enabled: bool,
+}
+
+impl Command for SetInputStats {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        world
+            .get_resource_or_insert_with(InputStats::default)
+            .set_enabled(self.enabled);
+    }
+}
+
+/// Command writing the recorded input analytics to `path`.
+pub struct DumpInputStats {
+    pub path: String,
+}
+
+impl Command for DumpInputStats {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let Some(stats) = world.get_resource::<InputStats>() else {
+            return;
+        };
+        match stats.save(&self.path) {
+            Ok(()) => info!("Input stats written to {}", self.path),
+            Err(e) => error!("{e}"),
+        }
+    }
+}
+
+/// Records the frame's input while analytics are on.
+pub fn input_stats_system(
+    mut stats: ResMut<InputStats>,
+    input: Res<InputState>,
+    signals: Res<WorldSignals>,
+    time: Res<WorldTime>,
+    attract: Option<Res<AttractMode>>,
+) {
+    if !stats.is_enabled() || attract.is_some_and(|attract| attract.is_playing()) {
+        return;
+    }
+    let scene = signals
+        .get_string(sk::SCENE)
+        .map_or(sk::DEFAULT_SCENE, String::as_str);
+    stats.record(scene, &input, time.delta);
+}
@@ -0,0 +1,188 @@
+//! Interaction prompts.
+//!
+//! [`interaction_system`] finds, for every interactor, the closest
+//! [`Interactable`] it is in range of, marks it `focused` (so the render
+//! system shows its prompt) and triggers an [`InteractEvent`] when the
+//! interactable's action is pressed.
+//!
+//! # Related
+//!
+//! - [`crate::components::interactable`] – the component and its range modes
+//! - [`crate::systems::lua_interact`] – *(feature = "lua")* Lua callbacks
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::boxcollider::BoxCollider;
+use crate::components::collision::pattern_matches;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::interactable::{InteractRange, Interactable};
+use crate::components::mapposition::MapPosition;
+use crate::components::tags::{Tags, entity_labels};
+use crate::events::interact::InteractEvent;
+use crate::resources::input::InputState;
+
+/// Squared distance between an interactable at `pos` and an interactor at
+/// `other_pos`, if the interactor is in range.
+pub fn interact_distance_sq(
+    range: InteractRange,
+    pos: Vector2,
+    collider: Option<&BoxCollider>,
+    other_pos: Vector2,
+    other_collider: Option<&BoxCollider>,
+) -> Option<f32> {
+    let distance_sq = (other_pos - pos).length_sqr();
+    let in_range = match range {
+        InteractRange::Radius(radius) => distance_sq <= radius * radius,
+        InteractRange::Collider => match (collider, other_collider) {
+            (Some(c), Some(o)) => c.overlaps(pos, o, other_pos),
+            (Some(c), None) => c.contains_point(pos, other_pos),
+            (None, _) => false,
+        },
+    };
+    in_range.then_some(distance_sq)
+}
+
+/// Focuses the closest interactable of each interactor and triggers
+/// [`InteractEvent`]s on action presses.
+///
+/// Positions are world positions ([`GlobalTransform2D`] when present,
+/// otherwise [`MapPosition`]). Interactors are entities with a [`Group`] or
+/// [`Tags`] matching an interactable's mask. Run after transform propagation.
+#[allow(clippy::type_complexity)]
+pub fn interaction_system(
+    input: Res<InputState>,
+    mut interactables: Query<(
+        Entity,
+        &mut Interactable,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+        Option<&BoxCollider>,
+    )>,
+    interactors: Query<
+        (
+            Entity,
+            &MapPosition,
+            Option<&GlobalTransform2D>,
+            Option<&BoxCollider>,
+            Option<&Group>,
+            Option<&Tags>,
+        ),
+        Or<(With<Group>, With<Tags>)>,
+    >,
+    mut commands: Commands,
+    mut focus: Local<Vec<(Entity, Entity)>>,
+) {
+    crate::tracy::tracy_span!("interaction_system");
+    if interactables.is_empty() {
+        return;
+    }
+    focus.clear();
+    for (interactor, pos, gt, collider, group, tags) in interactors.iter() {
+        let other_pos = gt.map_or(pos.pos, |gt| gt.position);
+        let closest = interactables
+            .iter()
+            .filter(|(entity, interactable, ..)| {
+                *entity != interactor
+                    && entity_labels(group, tags).any(|l| pattern_matches(&interactable.mask, l))
+            })
+            .filter_map(|(entity, interactable, p, g, c)| {
+                let world_pos = g.map_or(p.pos, |g| g.position);
+                interact_distance_sq(interactable.range, world_pos, c, other_pos, collider)
+                    .map(|d| (entity, d))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((entity, _)) = closest {
+            focus.push((entity, interactor));
+        }
+    }
+
+    for (entity, mut interactable, ..) in interactables.iter_mut() {
+        let interactor = focus.iter().find(|(e, _)| *e == entity).map(|(_, i)| *i);
+        if interactable.focused != interactor.is_some() {
+            interactable.focused = interactor.is_some();
+        }
+        if let Some(interactor) = interactor
+            && input.action(interactable.action).just_pressed
+        {
+            commands.trigger(InteractEvent { entity, interactor });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::observer::Observer;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Interactions(Vec<(Entity, Entity)>);
+
+    fn world_with_player() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<InputState>();
+        world.init_resource::<Interactions>();
+        world.spawn(Observer::new(
+            |trigger: On<InteractEvent>, mut seen: ResMut<Interactions>| {
+                let event = trigger.event();
+                seen.0.push((event.entity, event.interactor));
+            },
+        ));
+        let player = world
+            .spawn((Group::new("player"), MapPosition::new(0.0, 0.0)))
+            .id();
+        (world, player)
+    }
+
+    #[test]
+    fn focuses_only_the_closest_interactable_in_range() {
+        let (mut world, _) = world_with_player();
+        let near = world
+            .spawn((
+                Interactable::new("Talk").with_radius(20.0),
+                MapPosition::new(10.0, 0.0),
+            ))
+            .id();
+        let far = world
+            .spawn((
+                Interactable::new("Open").with_radius(20.0),
+                MapPosition::new(-15.0, 0.0),
+            ))
+            .id();
+        let out = world
+            .spawn((
+                Interactable::new("Read").with_radius(20.0),
+                MapPosition::new(50.0, 0.0),
+            ))
+            .id();
+
+        world.run_system_once(interaction_system).unwrap();
+        assert!(world.get::<Interactable>(near).unwrap().focused);
+        assert!(!world.get::<Interactable>(far).unwrap().focused);
+        assert!(!world.get::<Interactable>(out).unwrap().focused);
+        assert!(world.resource::<Interactions>().0.is_empty());
+    }
+
+    #[test]
+    fn action_press_triggers_interact_event() {
+        let (mut world, player) = world_with_player();
+        let chest = world
+            .spawn((
+                Interactable::new("Open"),
+                MapPosition::new(0.0, 0.0),
+                BoxCollider::new(16.0, 16.0).with_origin(Vector2::new(8.0, 8.0)),
+            ))
+            .id();
+        world.spawn((
+            Interactable::new("Open").with_mask("npc"),
+            MapPosition::new(0.0, 0.0),
+            BoxCollider::new(16.0, 16.0).with_origin(Vector2::new(8.0, 8.0)),
+        ));
+
+        world.resource_mut::<InputState>().action_1.just_pressed = true;
+        world.run_system_once(interaction_system).unwrap();
+        assert_eq!(world.resource::<Interactions>().0, vec![(chest, player)]);
+    }
+}
@@ -0,0 +1,204 @@
+//! Inventory systems and helpers.
+//!
+//! - [`give_item`] / [`take_item`] change an [`Inventory`] and trigger the
+//!   [`InventoryChangedEvent`] / [`InventoryFullEvent`] that describe the
+//!   change. Rust games call them from their own systems.
+//! - [`process_lua_inventory_commands`] *(feature = "lua")* applies
+//!   `engine.register_item()` and the `engine.inventory_*()` commands.
+//!
+//! Inventory grids are drawn by the render system.
+//!
+//! # Related
+//!
+//! - [`crate::components::inventory`] – the component and its grid widget
+//! - [`crate::resources::itemregistry`] – item definitions
+//! - [`crate::events::inventory`] – the events
+
+use bevy_ecs::prelude::*;
+#[cfg(feature = "lua")]
+use log::warn;
+
+use crate::components::inventory::Inventory;
+use crate::events::inventory::{InventoryChangedEvent, InventoryFullEvent};
+use crate::resources::itemregistry::ItemDef;
+#[cfg(feature = "lua")]
+use crate::resources::itemregistry::ItemRegistry;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::InventoryCmd;
+
+/// Add `count` of `def` to the `inventory` of `entity`, triggering an
+/// [`InventoryChangedEvent`] for what fit and an [`InventoryFullEvent`] for
+/// what did not. Returns how many were added.
+pub fn give_item(
+    commands: &mut Commands,
+    entity: Entity,
+    inventory: &mut Inventory,
+    def: &ItemDef,
+    count: u32,
+) -> u32 {
+    let added = inventory.add(def, count);
+    if added > 0 {
+        commands.trigger(InventoryChangedEvent {
+            entity,
+            item: def.id.clone(),
+            delta: i64::from(added),
+            count: inventory.count(&def.id),
+        });
+    }
+    if added < count {
+        commands.trigger(InventoryFullEvent {
+            entity,
+            item: def.id.clone(),
+            leftover: count - added,
+        });
+    }
+    added
+}
+
+/// Remove up to `count` of `item` from the `inventory` of `entity`,
+/// triggering an [`InventoryChangedEvent`] if any were removed. Returns how
+/// many were removed.
+pub fn take_item(
+    commands: &mut Commands,
+    entity: Entity,
+    inventory: &mut Inventory,
+    item: &str,
+    count: u32,
+) -> u32 {
+    let removed = inventory.remove(item, count);
+    if removed > 0 {
+        commands.trigger(InventoryChangedEvent {
+            entity,
+            item: item.into(),
+            delta: -i64::from(removed),
+            count: inventory.count(item),
+        });
+    }
+    removed
+}
+
+/// Applies item and inventory commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame. Commands naming an entity
+/// without [`Inventory`] or an unregistered item are dropped with a warning.
+#[cfg(feature = "lua")]
+pub fn process_lua_inventory_commands(
+    InMut(buf): InMut<Vec<InventoryCmd>>,
+    mut items: ResMut<ItemRegistry>,
+    mut inventories: Query<&mut Inventory>,
+    mut commands: Commands,
+) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            InventoryCmd::RegisterItem(def) => items.register(*def),
+            InventoryCmd::Add {
+                entity_id,
+                item,
+                count,
+            } => {
+                let Some((entity, mut inventory)) = inventory_of(&mut inventories, entity_id)
+                else {
+                    continue;
+                };
+                match items.get(&item) {
+                    Some(def) => {
+                        give_item(&mut commands, entity, &mut inventory, def, count);
+                    }
+                    None => warn!(target: "lua", "inventory_add: item '{item}' is not registered"),
+                }
+            }
+            InventoryCmd::Remove {
+                entity_id,
+                item,
+                count,
+            } => {
+                if let Some((entity, mut inventory)) = inventory_of(&mut inventories, entity_id) {
+                    take_item(&mut commands, entity, &mut inventory, &item, count);
+                }
+            }
+            InventoryCmd::Swap { entity_id, a, b } => {
+                if let Some((_, mut inventory)) = inventory_of(&mut inventories, entity_id) {
+                    inventory.swap(a, b);
+                }
+            }
+            InventoryCmd::Clear { entity_id } => {
+                let Some((entity, mut inventory)) = inventory_of(&mut inventories, entity_id)
+                else {
+                    continue;
+                };
+                for stack in inventory.clear() {
+                    commands.trigger(InventoryChangedEvent {
+                        entity,
+                        item: stack.item,
+                        delta: -i64::from(stack.count),
+                        count: 0,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Resolve a Lua entity ID to its inventory, warning if there is none.
+#[cfg(feature = "lua")]
+fn inventory_of<'a>(
+    inventories: &'a mut Query<&mut Inventory>,
+    entity_id: u64,
+) -> Option<(Entity, Mut<'a, Inventory>)> {
+    let entity = Entity::try_from_bits(entity_id)?;
+    match inventories.get_mut(entity) {
+        Ok(inventory) => Some((entity, inventory)),
+        Err(_) => {
+            warn!(target: "lua", "inventory command on entity {entity_id} without Inventory");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::observer::Observer;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Seen {
+        changed: Vec<(i64, u32)>,
+        full: Vec<u32>,
+    }
+
+    #[test]
+    fn give_and_take_trigger_events() {
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        world.spawn(Observer::new(
+            |trigger: On<InventoryChangedEvent>, mut seen: ResMut<Seen>| {
+                let event = trigger.event();
+                seen.changed.push((event.delta, event.count));
+            },
+        ));
+        world.spawn(Observer::new(
+            |trigger: On<InventoryFullEvent>, mut seen: ResMut<Seen>| {
+                seen.full.push(trigger.event().leftover);
+            },
+        ));
+        let bag = world.spawn(Inventory::new(1)).id();
+
+        world
+            .run_system_once(
+                move |mut commands: Commands, mut inventories: Query<&mut Inventory>| {
+                    let def = ItemDef::new("arrow").with_max_stack(10);
+                    let mut inventory = inventories.get_mut(bag).unwrap();
+                    assert_eq!(give_item(&mut commands, bag, &mut inventory, &def, 12), 10);
+                    assert_eq!(take_item(&mut commands, bag, &mut inventory, "arrow", 3), 3);
+                    assert_eq!(take_item(&mut commands, bag, &mut inventory, "bolt", 3), 0);
+                },
+            )
+            .expect("give/take");
+
+        let seen = world.resource::<Seen>();
+        assert_eq!(seen.changed, vec![(10, 10), (-3, 7)]);
+        assert_eq!(seen.full, vec![2]);
+    }
+}
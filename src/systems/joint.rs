@@ -0,0 +1,209 @@
+//! Joint solver system.
+//!
+//! [`joint_system`] solves every [`DistanceJoint`] and [`PinJoint`] in a few
+//! passes after [`movement`](super::movement::movement), then adds each
+//! body's total correction to its velocity. Chained joints (a chain of
+//! links, a paddle carrying a shield carrying a bumper) settle together.
+//!
+//! # Related
+//!
+//! - [`crate::components::joint`] – joint definitions and examples
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+
+use crate::components::joint::{DistanceJoint, PinJoint};
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::resources::worldtime::WorldTime;
+
+/// Solver passes per frame.
+pub const JOINT_ITERATIONS: usize = 4;
+
+/// A joint between two entities, collected for the current frame.
+pub enum JointLink {
+    Distance(Entity, DistanceJoint),
+    Pin(Entity, PinJoint),
+}
+
+/// Per-system memory reused across frames.
+#[derive(Default)]
+pub struct JointSolverState {
+    links: Vec<JointLink>,
+    /// Position of every moved body before the first pass.
+    start: FxHashMap<Entity, Vector2>,
+}
+
+/// Share of a correction taken by a body: movable rigid bodies take one,
+/// anything else is an immovable anchor.
+fn weight(rigidbody: Option<&RigidBody>) -> f32 {
+    match rigidbody {
+        Some(rb) if !rb.frozen => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Solves the joints and updates the velocities of the bodies they moved.
+///
+/// A body moved by `d` in total has `d / dt` added to its velocity.
+pub fn joint_system(
+    distance_joints: Query<(Entity, &DistanceJoint)>,
+    pin_joints: Query<(Entity, &PinJoint)>,
+    mut bodies: Query<(&mut MapPosition, Option<&mut RigidBody>)>,
+    time: Res<WorldTime>,
+    mut state: Local<JointSolverState>,
+) {
+    crate::tracy::tracy_span!("joint_system");
+    let state = &mut *state;
+    state.links.clear();
+    state.links.extend(
+        distance_joints
+            .iter()
+            .map(|(entity, joint)| JointLink::Distance(entity, *joint)),
+    );
+    state.links.extend(
+        pin_joints
+            .iter()
+            .map(|(entity, joint)| JointLink::Pin(entity, *joint)),
+    );
+    if state.links.is_empty() {
+        return;
+    }
+
+    state.start.clear();
+    for _ in 0..JOINT_ITERATIONS {
+        for link in &state.links {
+            let (entity, target) = match link {
+                JointLink::Distance(entity, joint) => (*entity, joint.target),
+                JointLink::Pin(entity, joint) => (*entity, joint.target),
+            };
+            let Ok([(mut pos_a, rb_a), (mut pos_b, rb_b)]) = bodies.get_many_mut([entity, target])
+            else {
+                continue;
+            };
+            let (wa, wb) = (weight(rb_a.as_deref()), weight(rb_b.as_deref()));
+            if wa + wb == 0.0 {
+                continue;
+            }
+            state.start.entry(entity).or_insert(pos_a.pos);
+            state.start.entry(target).or_insert(pos_b.pos);
+            let (mut a, mut b) = (pos_a.pos, pos_b.pos);
+            match link {
+                JointLink::Distance(_, joint) => joint.solve(&mut a, &mut b, wa, wb),
+                JointLink::Pin(_, joint) => joint.solve(&mut a, &mut b, wa, wb),
+            }
+            if a != pos_a.pos {
+                pos_a.pos = a;
+            }
+            if b != pos_b.pos {
+                pos_b.pos = b;
+            }
+        }
+    }
+
+    let delta = time.delta;
+    if delta <= 0.0 {
+        return;
+    }
+    for (&entity, &start) in &state.start {
+        let Ok((position, Some(mut rigidbody))) = bodies.get_mut(entity) else {
+            continue;
+        };
+        let moved = position.pos - start;
+        if moved != Vector2::zero() && !rigidbody.frozen {
+            rigidbody.velocity += moved / delta;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world_with_delta(delta: f32) -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta,
+            real_delta: delta,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world
+    }
+
+    #[test]
+    fn rod_keeps_an_orbiting_body_at_its_length() {
+        let mut world = world_with_delta(0.1);
+        let ball = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let mut drifting = RigidBody::new();
+        drifting.velocity = Vector2::new(40.0, 0.0);
+        let shield = world
+            .spawn((
+                MapPosition::new(30.0, 0.0),
+                drifting,
+                DistanceJoint::rod(ball, 20.0),
+            ))
+            .id();
+
+        world.run_system_once(joint_system).unwrap();
+
+        assert_eq!(
+            world.get::<MapPosition>(shield).unwrap().pos,
+            Vector2::new(20.0, 0.0)
+        );
+        assert_eq!(world.get::<MapPosition>(ball).unwrap().pos, Vector2::zero());
+        // The outward 40 u/s is cancelled by the -10 units correction.
+        let velocity = world.get::<RigidBody>(shield).unwrap().velocity;
+        assert!((velocity.x - (40.0 - 100.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pinned_bodies_share_the_correction() {
+        let mut world = world_with_delta(0.5);
+        let paddle = world
+            .spawn((MapPosition::new(0.0, 0.0), RigidBody::new()))
+            .id();
+        let bumper = world
+            .spawn((
+                MapPosition::new(10.0, 0.0),
+                RigidBody::new(),
+                PinJoint::new(paddle, Vector2::new(0.0, -10.0)),
+            ))
+            .id();
+
+        world.run_system_once(joint_system).unwrap();
+
+        let paddle_pos = world.get::<MapPosition>(paddle).unwrap().pos;
+        let bumper_pos = world.get::<MapPosition>(bumper).unwrap().pos;
+        assert_eq!(bumper_pos - paddle_pos, Vector2::new(0.0, -10.0));
+        assert_eq!(paddle_pos, Vector2::new(5.0, 5.0));
+        assert_eq!(
+            world.get::<RigidBody>(paddle).unwrap().velocity,
+            Vector2::new(10.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn joints_to_despawned_targets_are_ignored() {
+        let mut world = world_with_delta(0.1);
+        let gone = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        world.despawn(gone);
+        let body = world
+            .spawn((
+                MapPosition::new(50.0, 0.0),
+                RigidBody::new(),
+                DistanceJoint::leash(gone, 10.0),
+            ))
+            .id();
+
+        world.run_system_once(joint_system).unwrap();
+
+        assert_eq!(
+            world.get::<MapPosition>(body).unwrap().pos,
+            Vector2::new(50.0, 0.0)
+        );
+    }
+}
@@ -2,16 +2,26 @@
 //!
 //! This module provides the Lua-specific collision handling:
 //!
-//! - [`lua_collision_observer`] – receives [`CollisionEvent`](crate::events::collision::CollisionEvent)s
+//! - [`lua_collision_observer`] – receives the frame's [`CollisionBatch`](crate::events::collision::CollisionBatch)
 //!   and dispatches to [`LuaCollisionRule`](crate::components::luacollision::LuaCollisionRule) callbacks
 //!
 //! # Collision Flow
 //!
 //! 1. [`collision_detector`](crate::systems::collision_detector::collision_detector) detects overlaps
-//!    and emits `CollisionEvent`s
-//! 2. `lua_collision_observer` looks up matching Lua collision rules by
-//!    [`Group`](crate::components::group::Group) names
-//! 3. For each match, calls [`call_lua_collision_callback`] with pooled context tables
+//!    and emits one `CollisionBatch` with every overlapping pair
+//! 2. `lua_collision_observer` matches all pairs against the Lua collision
+//!    rules by [`Group`](crate::components::group::Group) names and
+//!    [`Tags`](crate::components::tags::Tags) in one pass
+//!    ([`plan_collision_batch`](crate::systems::collision::plan_collision_batch)),
+//!    keeping, per pair, the matches in descending priority up to the first
+//!    exclusive rule
+//! 3. Rule by rule (highest priority first), calls
+//!    [`call_lua_collision_callback`] for each of the rule's pairs in
+//!    detection order, with pooled context tables
+//!
+//! Running one observer per frame instead of one per pair keeps brick-heavy
+//! scenes cheap: labels are resolved once per entity and each callback
+//! function once per rule.
 //!
 //! # Lua Collision Callbacks
 //!
@@ -22,6 +32,7 @@
 //! function on_player_enemy(ctx)
 //!     -- ctx.a and ctx.b contain entity data
 //!     -- ctx.sides.a and ctx.sides.b contain collision sides
+//!     -- ctx.depth and ctx.normal ({x, y}, pointing from a to b) describe the overlap
 //! end
 //! ```
 //!
@@ -34,18 +45,21 @@
 //! - [`crate::systems::collision_detector`] – pure Rust collision detection
 //! - [`crate::components::luacollision::LuaCollisionRule`] – defines Lua collision handlers
 //! - [`crate::components::boxcollider::BoxCollider`] – axis-aligned collider
-//! - [`crate::events::collision::CollisionEvent`] – emitted on each collision
+//! - [`crate::events::collision::CollisionBatch`] – all collisions of a frame
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
+use smallvec::SmallVec;
+use std::cmp::Reverse;
 
 use crate::components::boxcollider::BoxCollider;
 use crate::components::group::Group;
 use crate::components::luacollision::LuaCollisionRule;
 use crate::components::luaphase::LuaPhase;
 use crate::components::signals::Signals;
+use crate::components::tags::Tags;
 use crate::events::audio::AudioCmd;
-use crate::events::collision::CollisionEvent;
+use crate::events::collision::CollisionBatch;
 use crate::resources::animationstore::AnimationStore;
 use crate::resources::lua_runtime::{
     LuaRuntime, PhaseCmd, SignalsCtxTables, clear_array_table, populate_entity_signals, set_opt,
@@ -53,7 +67,7 @@ use crate::resources::lua_runtime::{
 use crate::resources::systemsstore::SystemsStore;
 use crate::resources::worldsignals::WorldSignals;
 use crate::systems::collision::{
-    compute_sides, resolve_collider_rect, resolve_groups, resolve_world_pos,
+    PlannedCall, plan_collision_batch, resolve_collider_rect, resolve_world_pos,
 };
 use crate::systems::lua_commands::{
     DrainScope, EffectCmdBufs, EntityCmdQueries, drain_and_process_effect_commands,
@@ -66,6 +80,7 @@ use log::{error, warn};
 pub struct LuaCollisionObserverParams<'w, 's> {
     pub commands: Commands<'w, 's>,
     pub groups: Query<'w, 's, &'static Group>,
+    pub tags: Query<'w, 's, &'static Tags>,
     pub lua_rules: Query<'w, 's, &'static LuaCollisionRule>,
     pub box_colliders: Query<'w, 's, &'static BoxCollider>,
     pub luaphase_query: Query<'w, 's, (Entity, &'static mut LuaPhase)>,
@@ -77,29 +92,57 @@ pub struct LuaCollisionObserverParams<'w, 's> {
     pub animation_store: Res<'w, AnimationStore>,
 }
 
-/// Observes `CollisionEvent`, invokes the matching Lua collision callback, and
-/// queues any phase/animation/timer effects it requests.
+/// Observes the frame's [`CollisionBatch`], invokes the matching Lua collision
+/// callbacks and queues any phase/animation/timer effects they request.
+///
+/// Rules are collected and sorted by descending priority once per batch, and
+/// every pair is matched with [`plan_collision_batch`] (labels are resolved
+/// once per entity; per pair, matching stops after the first exclusive rule).
+/// Callbacks then run rule by rule, each rule's function is looked up once,
+/// and its pairs are handled in detection order. Positions, velocities,
+/// rects and signals are read when each callback runs, so a callback sees the
+/// changes made by the ones before it. Pairs with an entity despawned by an
+/// earlier callback of the batch are skipped.
 pub fn lua_collision_observer(
-    trigger: On<CollisionEvent>,
+    trigger: On<CollisionBatch>,
     mut params: LuaCollisionObserverParams,
     mut phase_buf: Local<Vec<PhaseCmd>>,
     mut effect_bufs: Local<EffectCmdBufs>,
+    mut plan: Local<Vec<PlannedCall>>,
 ) {
     if params.lua_rules.is_empty() {
         return;
     }
 
-    let a = trigger.event().a;
-    let b = trigger.event().b;
+    let pairs = &trigger.event().pairs;
+    let mut rules: SmallVec<[&LuaCollisionRule; 8]> = params.lua_rules.iter().collect();
+    rules.sort_by_key(|rule| Reverse(rule.priority));
+    plan_collision_batch(&rules, pairs, &params.groups, &params.tags, &mut plan);
+    effect_bufs.despawned.clear();
+
+    for calls in plan.chunk_by(|x, y| x.rule == y.rule) {
+        let callback_name = rules[calls[0].rule].callback.name.as_str();
+        let func = match params.lua_runtime.get_function_cached(callback_name) {
+            Ok(Some(func)) => func,
+            Ok(None) => {
+                warn!(target: "lua", "Collision callback '{}' not found", callback_name);
+                continue;
+            }
+            Err(e) => {
+                error!(target: "lua", "Collision callback '{}' error: {}", callback_name, e);
+                continue;
+            }
+        };
 
-    let (ga, gb) = match resolve_groups(&params.groups, a, b) {
-        Some(names) => names,
-        None => return,
-    };
+        for call in calls {
+            let (ent_a, ent_b) = (call.a, call.b);
+            if effect_bufs.despawned.contains(&ent_a.to_bits())
+                || effect_bufs.despawned.contains(&ent_b.to_bits())
+            {
+                continue;
+            }
+            let event = &pairs[call.pair];
 
-    for lua_rule in params.lua_rules.iter() {
-        if let Some((ent_a, ent_b)) = lua_rule.match_and_order(a, b, ga, gb) {
-            let callback_name = lua_rule.callback.name.as_str();
             let pos_a = resolve_world_pos(
                 &params.entity_cmds.positions.as_readonly(),
                 &params.entity_cmds.global_transforms,
@@ -150,11 +193,12 @@ pub fn lua_collision_observer(
                 &params.box_colliders,
                 ent_b,
             );
-            let (sides_a, sides_b) = compute_sides(rect_a, rect_b);
+            let (sides_a, sides_b, normal) = event.oriented(ent_a);
 
             let signals_a = params.entity_cmds.signals.get(ent_a).ok();
             let signals_b = params.entity_cmds.signals.get(ent_b).ok();
-            let (group_a, group_b) = if ent_a == a { (ga, gb) } else { (gb, ga) };
+            let group_a = event.group_of(ent_a);
+            let group_b = event.group_of(ent_b);
 
             // Refresh the cached world-signal snapshot only when something has
             // changed since the last refresh. lua_plugin::update primes the
@@ -169,7 +213,7 @@ pub fn lua_collision_observer(
 
             let callback_result = call_lua_collision_callback(
                 &params.lua_runtime,
-                callback_name,
+                &func,
                 ent_a.to_bits(),
                 ent_b.to_bits(),
                 pos_a,
@@ -180,12 +224,14 @@ pub fn lua_collision_observer(
                 speed_sq_b,
                 rect_a.map(|r| (r.x, r.y, r.width, r.height)),
                 rect_b.map(|r| (r.x, r.y, r.width, r.height)),
-                &sides_a,
-                &sides_b,
+                sides_a,
+                sides_b,
+                event.depth,
+                (normal.x, normal.y),
                 signals_a,
                 signals_b,
-                Some(group_a),
-                Some(group_b),
+                group_a,
+                group_b,
             );
 
             params
@@ -210,8 +256,6 @@ pub fn lua_collision_observer(
             if let Err(e) = callback_result {
                 error!(target: "lua", "Collision callback '{}' error: {}", callback_name, e);
             }
-
-            return;
         }
     }
 }
@@ -283,7 +327,7 @@ fn populate_collision_entity(
 #[allow(clippy::too_many_arguments)]
 fn call_lua_collision_callback(
     lua_runtime: &LuaRuntime,
-    callback_name: &str,
+    func: &mlua::Function,
     entity_a_id: u64,
     entity_b_id: u64,
     pos_a: Option<(f32, f32)>,
@@ -296,6 +340,8 @@ fn call_lua_collision_callback(
     rect_b: Option<(f32, f32, f32, f32)>,
     sides_a: &[crate::components::collision::BoxSide],
     sides_b: &[crate::components::collision::BoxSide],
+    depth: f32,
+    normal: (f32, f32),
     signals_a: Option<&Signals>,
     signals_b: Option<&Signals>,
     group_a: Option<&str>,
@@ -345,16 +391,11 @@ fn call_lua_collision_callback(
         tables.sides_b.set(i + 1, box_side_to_str(side))?;
     }
 
-    match lua_runtime.get_function_cached(callback_name)? {
-        Some(func) => {
-            func.call::<()>(tables.ctx)?;
-        }
-        None => {
-            warn!(target: "lua", "Collision callback '{}' not found", callback_name);
-        }
-    }
+    tables.ctx.set("depth", depth)?;
+    tables.normal.set("x", normal.0)?;
+    tables.normal.set("y", normal.1)?;
 
-    Ok(())
+    func.call::<()>(tables.ctx)
 }
 
 #[cfg(test)]
@@ -0,0 +1,177 @@
+//! Per-frame application of the Lua subsystem command queues.
+//!
+//! Lua commands reach Rust in two ways:
+//!
+//! - **Callback effects** (signals, entities, spawns, clones, audio, camera,
+//!   phases) are applied right after the callback that queued them returns,
+//!   so the next callback in the same frame already sees them.
+//! - **Subsystem commands** (assets, maps, worlds, visibility, fog, clock,
+//!   weather, music, formation, inventory, ui, savegame, debug) are applied
+//!   once per frame by
+//!   [`apply_lua_commands`], an exclusive system that runs after
+//!   `lua_plugin::update` and before rendering.
+//!
+//! [`apply_lua_commands`] borrows the Lua app data once, drains every
+//! subsystem queue and then applies the domains in [`LUA_COMMAND_ORDER`]:
+//! a domain sees the effects of every domain listed before it. Assets come
+//! first so a map or sub-world applied in the same frame can use textures
+//! loaded by that frame's `engine.load_*` calls. Within a domain, commands
+//! are applied in the order scripts queued them. Scripts can read the order
+//! with `engine.command_order()`.
+//!
+//! Asset commands are only applied while the game is playing and, like map
+//! commands, only when raylib is present; otherwise they stay queued.
+
+use bevy_ecs::prelude::*;
+use log::error;
+use raylib::RaylibHandle;
+
+use crate::lua_plugin::process_lua_asset_commands;
+use crate::resources::assetusage::AssetUsage;
+use crate::resources::gamestate::{GameState, GameStates};
+use crate::resources::lua_runtime::{LuaRuntime, SubsystemCmdBufs};
+use crate::systems::fogofwar::process_lua_fog_commands;
+use crate::systems::formation::process_lua_formation_commands;
+use crate::systems::inventory::process_lua_inventory_commands;
+use crate::systems::mapspawn::process_lua_map_commands;
+use crate::systems::results::process_lua_ui_commands;
+use crate::systems::savegame::process_lua_savegame_commands;
+use crate::systems::scenemusic::process_lua_music_commands;
+use crate::systems::visibility::process_lua_visibility_commands;
+use crate::systems::weather::process_lua_weather_commands;
+use crate::systems::world_snapshot::process_lua_debug_commands;
+use crate::systems::worldclock::process_lua_clock_commands;
+use crate::systems::worlds::process_lua_world_commands;
+
+/// Order in which [`apply_lua_commands`] applies the subsystem command domains.
+pub const LUA_COMMAND_ORDER: [&str; 13] = [
+    "asset",
+    "map",
+    "world",
+    "visibility",
+    "fog",
+    "clock",
+    "weather",
+    "music",
+    "formation",
+    "inventory",
+    "ui",
+    "savegame",
+    "debug",
+];
+
+/// Runs `$system` with the drained `$buf` when it is non-empty. A system that
+/// cannot run (e.g. a missing resource) drops its commands with an error.
+macro_rules! apply_domain {
+    ($world:expr, $system:path, $buf:expr, $domain:literal) => {
+        if !$buf.is_empty() {
+            if let Err(e) = $world.run_system_cached_with($system, &mut $buf) {
+                error!(
+                    "apply_lua_commands: dropped {} {} command(s): {e}",
+                    $buf.len(),
+                    $domain
+                );
+            }
+            $buf.clear();
+        }
+    };
+}
+
+/// Drains all Lua subsystem command queues and applies them in
+/// [`LUA_COMMAND_ORDER`].
+///
+/// Registered by [`crate::engine_app::EngineBuilder::with_lua`] after
+/// `lua_plugin::update` and before the systems that read the affected
+/// resources (`update_sub_worlds_system`, `occluder_system`, `render_system`).
+pub fn apply_lua_commands(world: &mut World, mut bufs: Local<SubsystemCmdBufs>) {
+    let with_maps = world.contains_non_send::<RaylibHandle>();
+    let with_assets = with_maps
+        && world
+            .get_resource::<GameState>()
+            .is_some_and(|state| matches!(state.get(), GameStates::Playing));
+
+    let Some(lua) = world.get_non_send::<LuaRuntime>() else {
+        return;
+    };
+    let dump_requested = with_assets && lua.take_asset_usage_dump_request();
+    lua.drain_subsystem_commands_into(&mut bufs, with_assets, with_maps);
+
+    if dump_requested && let Some(mut usage) = world.get_resource_mut::<AssetUsage>() {
+        usage.request_dump();
+    }
+
+    apply_domain!(world, process_lua_asset_commands, bufs.assets, "asset");
+    apply_domain!(world, process_lua_map_commands, bufs.maps, "map");
+    apply_domain!(world, process_lua_world_commands, bufs.worlds, "world");
+    apply_domain!(
+        world,
+        process_lua_visibility_commands,
+        bufs.visibility,
+        "visibility"
+    );
+    apply_domain!(world, process_lua_fog_commands, bufs.fog, "fog");
+    apply_domain!(world, process_lua_clock_commands, bufs.clock, "clock");
+    apply_domain!(world, process_lua_weather_commands, bufs.weather, "weather");
+    apply_domain!(world, process_lua_music_commands, bufs.music, "music");
+    apply_domain!(
+        world,
+        process_lua_formation_commands,
+        bufs.formation,
+        "formation"
+    );
+    apply_domain!(
+        world,
+        process_lua_inventory_commands,
+        bufs.inventory,
+        "inventory"
+    );
+    apply_domain!(world, process_lua_ui_commands, bufs.ui, "ui");
+    apply_domain!(
+        world,
+        process_lua_savegame_commands,
+        bufs.savegame,
+        "savegame"
+    );
+    apply_domain!(world, process_lua_debug_commands, bufs.debug, "debug");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::worldclock::WorldClock;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn applies_queued_domains_and_leaves_map_commands_without_raylib() {
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.clock_set_time('10:00')\n\
+                 engine.load_map('maps/dummy.json')",
+            )
+            .exec()
+            .expect("queue clock and map commands");
+
+        let mut world = World::new();
+        world.insert_non_send(runtime);
+        world.insert_resource(WorldClock::default());
+        world.run_system_once(apply_lua_commands).unwrap();
+
+        assert_eq!(world.resource::<WorldClock>().minutes, 600.0);
+        let mut maps = Vec::new();
+        world
+            .non_send::<LuaRuntime>()
+            .drain_map_commands_into(&mut maps);
+        assert_eq!(maps.len(), 1, "map commands need raylib and stay queued");
+    }
+
+    #[test]
+    fn command_order_covers_every_domain_once() {
+        let mut seen = LUA_COMMAND_ORDER.to_vec();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), LUA_COMMAND_ORDER.len());
+        assert_eq!(LUA_COMMAND_ORDER[0], "asset");
+    }
+}
@@ -71,7 +71,7 @@ pub(crate) fn build_entity_context(
         .get(entity)
         .ok()
         .map(|s| SpriteSnapshot {
-            tex_key: s.tex_key.as_ref(),
+            tex_key: s.tex_key.as_str(),
             flip_h: s.flip_h,
             flip_v: s.flip_v,
         });
@@ -88,6 +88,8 @@ pub(crate) fn build_entity_context(
 
     let signals_ref = cmd_queries.signals.get(entity).ok();
 
+    let blackboard = ctx_queries.blackboards.get(entity).ok();
+
     let lua_timer = ctx_queries
         .lua_timers
         .get(entity)
@@ -119,6 +121,7 @@ pub(crate) fn build_entity_context(
         sprite,
         animation,
         signals: signals_ref,
+        blackboard,
         lua_phase,
         lua_timer,
         previous_phase,
@@ -11,10 +11,16 @@ use bevy_ecs::hierarchy::ChildOf;
 use bevy_ecs::prelude::*;
 use raylib::prelude::Vector2;
 
+use crate::components::blackboard::Blackboard;
+use crate::components::boxcollider::ColliderDisabled;
 use crate::components::cameratarget::CameraTarget;
+use crate::components::cooldown::Cooldown;
 use crate::components::entityshader::EntityShader;
+use crate::components::forcearea::ForceArea;
 use crate::components::globaltransform2d::GlobalTransform2D;
 use crate::components::guiinteractable::GuiWidgetState;
+use crate::components::height::{Height, ZVelocity};
+use crate::components::joint::{DistanceJoint, PinJoint};
 use crate::components::luatimer::{LuaTimer, LuaTimerCallback};
 use crate::components::mapposition::MapPosition;
 use crate::components::rotation::Rotation;
@@ -25,11 +31,15 @@ use crate::components::shadow::Shadow;
 use crate::components::tint::Tint;
 use crate::components::ttl::Ttl;
 use crate::components::tween::{Tween, TweenValue};
+use crate::components::watersurface::WaterSurface;
 
 use crate::resources::animationstore::AnimationStore;
 use crate::resources::lua_runtime::{EntityCmd, TweenConfig, UniformValue};
 use crate::resources::systemsstore::SystemsStore;
 use crate::resources::worldsignals::WorldSignals;
+use crate::systems::group::{SetGroupVisible, TranslateGroup};
+use crate::systems::terrain::CarveTerrainCircle;
+use crate::systems::tilemap::{SetTileMapLayerVisible, SetTileMapTile};
 
 use super::EntityCmdQueries;
 
@@ -93,6 +103,8 @@ pub fn process_entity_commands(
             | EntityCmd::SetSpeed { .. }
             | EntityCmd::SetFriction { .. }
             | EntityCmd::SetMaxSpeed { .. }
+            | EntityCmd::SetMass { .. }
+            | EntityCmd::ApplyImpulse { .. }
             | EntityCmd::FreezeEntity { .. }
             | EntityCmd::UnfreezeEntity { .. }
             | EntityCmd::AddForce { .. }
@@ -158,10 +170,154 @@ pub fn process_entity_commands(
                 process_lifecycle_cmd(cmd, commands, world_signals, systems_store)
             }
 
+            cmd @ (EntityCmd::BlackboardSet { .. }
+            | EntityCmd::BlackboardRemove { .. }
+            | EntityCmd::BlackboardClear { .. }) => process_blackboard_cmd(cmd, commands),
+
+            EntityCmd::TilemapSetLayerVisible {
+                entity_id,
+                layer,
+                visible,
+            } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(SetTileMapLayerVisible { layer, visible });
+                });
+            }
+
+            EntityCmd::TilemapSetTile {
+                entity_id,
+                layer,
+                x,
+                y,
+                tile_id,
+            } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(SetTileMapTile {
+                        layer,
+                        x,
+                        y,
+                        tile_id,
+                    });
+                });
+            }
+
+            EntityCmd::TerrainCarveCircle {
+                entity_id,
+                x,
+                y,
+                radius,
+            } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(CarveTerrainCircle {
+                        center: Vector2::new(x, y),
+                        radius,
+                    });
+                });
+            }
+
+            EntityCmd::WaterSplash {
+                entity_id,
+                x,
+                velocity,
+            } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(move |mut entity: EntityWorldMut| {
+                        let origin_x = entity
+                            .get::<GlobalTransform2D>()
+                            .map(|gt| gt.position.x)
+                            .or_else(|| entity.get::<MapPosition>().map(|p| p.pos.x))
+                            .unwrap_or(0.0);
+                        if let Some(mut water) = entity.get_mut::<WaterSurface>() {
+                            water.splash_at(x - origin_x, velocity);
+                        }
+                    });
+                });
+            }
+
+            EntityCmd::GroupTranslate { group, dx, dy } => {
+                commands.queue(TranslateGroup {
+                    label: group,
+                    offset: Vector2::new(dx, dy),
+                });
+            }
+
+            EntityCmd::GroupSetVisible { group, visible } => {
+                commands.queue(SetGroupVisible {
+                    label: group,
+                    visible,
+                });
+            }
+
             EntityCmd::SetGuiDisabled { entity_id, disabled } => {
                 process_gui_interactable_cmd(entity_id, disabled, queries)
             }
 
+            EntityCmd::SetColliderEnabled { entity_id, enabled } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    if enabled {
+                        ec.try_remove::<ColliderDisabled>();
+                    } else {
+                        ec.try_insert(ColliderDisabled);
+                    }
+                });
+            }
+
+            EntityCmd::SetForceAreaEnabled { entity_id, enabled } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(move |mut entity: EntityWorldMut| {
+                        if let Some(mut area) = entity.get_mut::<ForceArea>() {
+                            area.enabled = enabled;
+                        }
+                    });
+                });
+            }
+
+            EntityCmd::StartCooldown { entity_id } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(|mut entity: EntityWorldMut| {
+                        if let Some(mut cooldown) = entity.get_mut::<Cooldown>() {
+                            cooldown.start();
+                        }
+                    });
+                });
+            }
+
+            EntityCmd::ResetCooldown { entity_id } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(|mut entity: EntityWorldMut| {
+                        if let Some(mut cooldown) = entity.get_mut::<Cooldown>() {
+                            cooldown.reset();
+                        }
+                    });
+                });
+            }
+
+            EntityCmd::RemoveJoints { entity_id } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.try_remove::<(DistanceJoint, PinJoint)>();
+                });
+            }
+
+            EntityCmd::Jump { entity_id, speed } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(move |mut entity: EntityWorldMut| {
+                        if let Some(mut z_velocity) = entity.get_mut::<ZVelocity>() {
+                            z_velocity.velocity = speed;
+                        }
+                    });
+                });
+            }
+
+            EntityCmd::SetHeight { entity_id, height } => {
+                with_entity_cmd(commands, entity_id, |ec| {
+                    ec.queue(move |mut entity: EntityWorldMut| {
+                        if let Some(mut current) = entity.get_mut::<Height>() {
+                            current.0 = height.max(0.0);
+                        }
+                    });
+                });
+            }
+
             EntityCmd::SetGuiProgress { entity_id, value } => {
                 let Some(entity) = resolve_entity(entity_id) else { continue; };
                 if let Ok(mut bar) = queries.gui_progress_bars.get_mut(entity) {
@@ -180,6 +336,56 @@ pub fn process_entity_commands(
     }
 }
 
+/// Blackboard writes are queued on the entity so a `Blackboard` can be
+/// inserted on first use without a mutable query that would conflict with the
+/// read-only context queries.
+fn process_blackboard_cmd(cmd: EntityCmd, commands: &mut Commands) {
+    match cmd {
+        EntityCmd::BlackboardSet {
+            entity_id,
+            key,
+            value,
+            ttl,
+        } => {
+            with_entity_cmd(commands, entity_id, |ec| {
+                ec.queue(move |mut entity: EntityWorldMut| {
+                    let write = |board: &mut Blackboard| match ttl {
+                        Some(ttl) => board.set_with_ttl(key, value, ttl),
+                        None => board.set(key, value),
+                    };
+                    match entity.get_mut::<Blackboard>() {
+                        Some(mut board) => write(&mut board),
+                        None => {
+                            let mut board = Blackboard::default();
+                            write(&mut board);
+                            entity.insert(board);
+                        }
+                    }
+                });
+            });
+        }
+        EntityCmd::BlackboardRemove { entity_id, key } => {
+            with_entity_cmd(commands, entity_id, |ec| {
+                ec.queue(move |mut entity: EntityWorldMut| {
+                    if let Some(mut board) = entity.get_mut::<Blackboard>() {
+                        board.remove(&key);
+                    }
+                });
+            });
+        }
+        EntityCmd::BlackboardClear { entity_id } => {
+            with_entity_cmd(commands, entity_id, |ec| {
+                ec.queue(move |mut entity: EntityWorldMut| {
+                    if let Some(mut board) = entity.get_mut::<Blackboard>() {
+                        board.clear();
+                    }
+                });
+            });
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn process_physics_cmd(cmd: EntityCmd, queries: &mut EntityCmdQueries) {
     match cmd {
         EntityCmd::SetVelocity { entity_id, vx, vy } => {
@@ -212,6 +418,18 @@ fn process_physics_cmd(cmd: EntityCmd, queries: &mut EntityCmdQueries) {
                 rb.max_speed = max_speed;
             }
         }
+        EntityCmd::SetMass { entity_id, mass } => {
+            let Some(entity) = resolve_entity(entity_id) else { return; };
+            if let Ok(mut rb) = queries.rigid_bodies.get_mut(entity) {
+                rb.set_mass(mass);
+            }
+        }
+        EntityCmd::ApplyImpulse { entity_id, x, y } => {
+            let Some(entity) = resolve_entity(entity_id) else { return; };
+            if let Ok(mut rb) = queries.rigid_bodies.get_mut(entity) {
+                rb.apply_impulse(Vector2 { x, y });
+            }
+        }
         EntityCmd::FreezeEntity { entity_id } => {
             let Some(entity) = resolve_entity(entity_id) else { return; };
             if let Ok(mut rb) = queries.rigid_bodies.get_mut(entity) {
@@ -382,7 +600,7 @@ fn process_animation_cmd(
             if let Some(anim_res) = anim_store.animations.get(&animation_key)
                 && let Ok(mut sprite) = queries.sprites.get_mut(entity)
             {
-                sprite.tex_key = anim_res.tex_key.clone();
+                sprite.tex_key = anim_res.tex_key;
             }
         }
         EntityCmd::SetSpriteFlip {
@@ -789,6 +1007,7 @@ mod tests {
     use super::*;
     use crate::components::guiinteractable::GuiInteractable;
     use crate::components::lua_on_tween_finished::LuaOnTweenFinished;
+    use crate::components::rigidbody::RigidBody;
 
     #[test]
     fn resolve_entity_rejects_invalid_bits() {
@@ -847,6 +1066,52 @@ mod tests {
         assert!(world_signals.get_entity("tpl").is_none());
     }
 
+    #[test]
+    fn blackboard_set_inserts_board_and_remove_deletes_key() {
+        use crate::resources::lua_runtime::BlackboardValue;
+
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let mut world_signals = WorldSignals::default();
+
+        run_entity_cmd(
+            &mut world,
+            &mut world_signals,
+            EntityCmd::BlackboardSet {
+                entity_id: entity.to_bits(),
+                key: "state".into(),
+                value: BlackboardValue::Text("patrol".into()),
+                ttl: None,
+            },
+        );
+        run_entity_cmd(
+            &mut world,
+            &mut world_signals,
+            EntityCmd::BlackboardSet {
+                entity_id: entity.to_bits(),
+                key: "heard_noise".into(),
+                value: BlackboardValue::Bool(true),
+                ttl: Some(1.5),
+            },
+        );
+
+        let board = world.get::<Blackboard>(entity).unwrap();
+        assert_eq!(board.get_str("state"), Some("patrol"));
+        assert_eq!(board.remaining_ttl("heard_noise"), Some(1.5));
+
+        run_entity_cmd(
+            &mut world,
+            &mut world_signals,
+            EntityCmd::BlackboardRemove {
+                entity_id: entity.to_bits(),
+                key: "state".into(),
+            },
+        );
+        let board = world.get::<Blackboard>(entity).unwrap();
+        assert!(!board.contains("state"));
+        assert_eq!(board.len(), 1);
+    }
+
     fn run_camera_target_cmd(world: &mut World, cmd: EntityCmd) {
         run_entity_cmd(world, &mut WorldSignals::default(), cmd);
     }
@@ -971,6 +1236,191 @@ mod tests {
         assert!(world.get::<GuiInteractable>(entity).is_none());
     }
 
+    #[test]
+    fn set_collider_enabled_toggles_marker() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::SetColliderEnabled {
+                entity_id: entity.to_bits(),
+                enabled: false,
+            },
+        );
+        assert!(world.get::<ColliderDisabled>(entity).is_some());
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::SetColliderEnabled {
+                entity_id: entity.to_bits(),
+                enabled: true,
+            },
+        );
+        assert!(world.get::<ColliderDisabled>(entity).is_none());
+    }
+
+    #[test]
+    fn set_force_area_enabled_toggles_flag() {
+        let mut world = World::new();
+        let entity = world
+            .spawn(ForceArea::conveyor(Vector2::new(10.0, 0.0)))
+            .id();
+        let plain = world.spawn_empty().id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::SetForceAreaEnabled {
+                entity_id: entity.to_bits(),
+                enabled: false,
+            },
+        );
+        assert!(!world.get::<ForceArea>(entity).unwrap().enabled);
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::SetForceAreaEnabled {
+                entity_id: plain.to_bits(),
+                enabled: false,
+            },
+        );
+        assert!(world.get::<ForceArea>(plain).is_none());
+    }
+
+    #[test]
+    fn start_and_reset_cooldown() {
+        let mut world = World::new();
+        let entity = world.spawn(Cooldown::new(2.0, "cd")).id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::StartCooldown {
+                entity_id: entity.to_bits(),
+            },
+        );
+        assert_eq!(world.get::<Cooldown>(entity).unwrap().remaining, 2.0);
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::ResetCooldown {
+                entity_id: entity.to_bits(),
+            },
+        );
+        assert!(world.get::<Cooldown>(entity).unwrap().is_ready());
+    }
+
+    #[test]
+    fn impulse_is_divided_by_the_new_mass() {
+        let mut world = World::new();
+        let entity = world.spawn(RigidBody::new()).id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::SetMass {
+                entity_id: entity.to_bits(),
+                mass: 2.0,
+            },
+        );
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::ApplyImpulse {
+                entity_id: entity.to_bits(),
+                x: 10.0,
+                y: -4.0,
+            },
+        );
+        assert_eq!(
+            world.get::<RigidBody>(entity).unwrap().velocity,
+            Vector2::new(5.0, -2.0)
+        );
+    }
+
+    #[test]
+    fn remove_joints_frees_the_entity() {
+        let mut world = World::new();
+        let anchor = world.spawn_empty().id();
+        let entity = world
+            .spawn((
+                DistanceJoint::rod(anchor, 10.0),
+                PinJoint::new(anchor, Vector2::zero()),
+            ))
+            .id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::RemoveJoints {
+                entity_id: entity.to_bits(),
+            },
+        );
+        assert!(world.get::<DistanceJoint>(entity).is_none());
+        assert!(world.get::<PinJoint>(entity).is_none());
+    }
+
+    #[test]
+    fn group_commands_reach_every_member() {
+        use crate::components::group::Group;
+        use crate::components::hidden::Hidden;
+
+        let mut world = World::new();
+        let brick = world
+            .spawn((Group::new("bricks"), MapPosition::new(0.0, 0.0)))
+            .id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::GroupTranslate {
+                group: "bricks".into(),
+                dx: 4.0,
+                dy: -2.0,
+            },
+        );
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::GroupSetVisible {
+                group: "bricks".into(),
+                visible: false,
+            },
+        );
+        assert_eq!(
+            world.get::<MapPosition>(brick).unwrap().pos,
+            Vector2::new(4.0, -2.0)
+        );
+        assert!(world.get::<Hidden>(brick).is_some());
+    }
+
+    #[test]
+    fn water_splash_uses_world_x() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((MapPosition::new(100.0, 0.0), WaterSurface::new(64.0, 16.0)))
+            .id();
+
+        run_entity_cmd(
+            &mut world,
+            &mut WorldSignals::default(),
+            EntityCmd::WaterSplash {
+                entity_id: entity.to_bits(),
+                x: 116.0,
+                velocity: 50.0,
+            },
+        );
+        let mut water = world.get::<WaterSurface>(entity).unwrap().clone();
+        water.step(0.005);
+        assert!(water.heights()[2] > 0.0);
+        assert_eq!(water.heights()[0], 0.0);
+    }
+
     fn run_screen_position_cmd(world: &mut World, cmd: EntityCmd) {
         run_entity_cmd(world, &mut WorldSignals::default(), cmd);
     }
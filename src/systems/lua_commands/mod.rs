@@ -5,10 +5,12 @@
 //!
 //! # Sub-modules
 //!
+//! - [`apply`] – [`apply_lua_commands`]: per-frame application of subsystem command queues
 //! - [`context`] – [`build_entity_context`]: entity context table construction
 //! - [`entity_cmd`] – [`process_entity_commands`]: runtime entity manipulation
 //! - [`processors`] – small per-command-domain `process_*` functions
 //! - [`spawn_cmd`] – [`process_spawn_command`], [`process_clone_command`]: entity creation
+//! - [`spawn_validate`] – dead-target and missing-asset checks for spawned entities
 //! - [`parse`] – animation condition conversion helpers
 //!
 //! # SystemParam bundles
@@ -16,27 +18,32 @@
 //! - [`EntityCmdQueries`] – mutable queries needed by `process_entity_commands`
 //! - [`ContextQueries`] – read-only queries for building entity context tables
 
+mod apply;
 mod context;
 mod entity_cmd;
 mod parse;
 mod processors;
 mod spawn_cmd;
+mod spawn_validate;
 
+pub use apply::{LUA_COMMAND_ORDER, apply_lua_commands};
 pub(crate) use context::build_entity_context;
 pub use entity_cmd::process_entity_commands;
 pub use processors::{
     process_animation_command, process_asset_command, process_audio_command,
-    process_camera_command, process_camera_follow_command, process_gameconfig_command,
-    process_group_command, process_input_command, process_phase_command, process_render_command,
-    process_signal_command,
+    process_camera_command, process_camera_follow_command, process_collision_rule_command,
+    process_gameconfig_command, process_group_command, process_input_command,
+    process_phase_command, process_render_command, process_signal_command,
 };
 pub use spawn_cmd::{process_clone_command, process_spawn_command};
 
 use bevy_ecs::hierarchy::ChildOf;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
+use rustc_hash::FxHashSet;
 
 use crate::components::animation::Animation;
+use crate::components::blackboard::Blackboard;
 use crate::components::boxcollider::BoxCollider;
 use crate::components::cameratarget::CameraTarget;
 use crate::components::entityshader::EntityShader;
@@ -78,6 +85,10 @@ pub struct EffectCmdBufs {
     pub(crate) clones: Vec<CloneCmd>,
     pub(crate) audios: Vec<AudioLuaCmd>,
     pub(crate) cameras: Vec<CameraCmd>,
+    /// Entity ids targeted by collision-scope `Despawn`/`MenuDespawn`
+    /// commands. Cleared by the collision observer at the start of each batch
+    /// so it can skip pairs whose entity is already on its way out.
+    pub(crate) despawned: FxHashSet<u64>,
 }
 
 /// Selects which set of command queues to drain from the Lua runtime.
@@ -126,6 +137,13 @@ pub(crate) fn drain_and_process_effect_commands(
             lua_runtime.drain_collision_clone_commands_into(&mut bufs.clones);
             lua_runtime.drain_collision_audio_commands_into(&mut bufs.audios);
             lua_runtime.drain_collision_camera_commands_into(&mut bufs.cameras);
+            bufs.despawned
+                .extend(bufs.entities.iter().filter_map(|cmd| match cmd {
+                    EntityCmd::Despawn { entity_id } | EntityCmd::MenuDespawn { entity_id } => {
+                        Some(*entity_id)
+                    }
+                    _ => None,
+                }));
         }
     }
 
@@ -270,4 +288,5 @@ pub struct ContextQueries<'w, 's> {
     pub lua_timers: Query<'w, 's, &'static LuaTimer>,
     pub global_transforms: Query<'w, 's, &'static GlobalTransform2D>,
     pub child_of: Query<'w, 's, &'static ChildOf>,
+    pub blackboards: Query<'w, 's, &'static Blackboard>,
 }
@@ -7,29 +7,43 @@ use std::sync::Arc;
 
 use bevy_ecs::prelude::*;
 use log::{debug, error, warn};
-use raylib::prelude::{Camera2D, Color, Rectangle, Vector2};
+use raylib::prelude::{Camera2D, Color, Image, Rectangle, Vector2};
 
+use crate::components::luacollision::{LuaCollisionCallback, LuaCollisionRule};
+use crate::components::persistent::{Persistent, SceneScoped};
 use crate::components::phase::Phase;
 use crate::components::shadow::Shadow;
+use crate::components::tween::Easing;
 use crate::events::audio::AudioCmd;
+use crate::resources::accessibility::ColorblindMode;
+use crate::resources::alphamask::AlphaMasks;
 use crate::resources::animationstore::{AnimationResource, AnimationStore};
 use crate::resources::camera2d::Camera2DRes;
 use crate::resources::camerafollowconfig::{CameraFollowConfig, EasingCurve, FollowMode};
+use crate::resources::colorgrading::ColorGrading;
 use crate::resources::fontstore::FontStore;
-use crate::resources::gameconfig::GameConfig;
+use crate::resources::fullscreen::FullscreenMode;
+use crate::resources::gameconfig::{GameConfig, clamp_render_scale};
 use crate::resources::guitheme::{GuiButtonSkin, GuiNinePatch, GuiProgressBarSkin, GuiTheme, GuiThemeStore};
 use crate::resources::group::TrackedGroups;
 use crate::resources::input_bindings::{InputBindings, binding_from_str};
+use crate::resources::layervisibility::LayerVisibility;
 use crate::resources::lua_runtime::{
-    AnimationCmd, AssetCmd, AudioLuaCmd, CameraCmd, CameraFollowCmd, GameConfigCmd, GroupCmd,
-    InputCmd, PhaseCmd, RenderCmd, SignalCmd,
+    AnimationCmd, AssetCmd, AudioLuaCmd, CameraCmd, CameraFollowCmd, CollisionRuleCmd,
+    GameConfigCmd, GroupCmd, InputCmd, PhaseCmd, RenderCmd, SignalCmd,
 };
 use crate::resources::postprocessshader::PostProcessShader;
+use crate::resources::rendermode::RenderMode;
 use crate::resources::shaderstore::ShaderStore;
 use crate::resources::texturefilter::TextureFilter;
-use crate::resources::texturestore::TextureStore;
+use crate::resources::texturestore::{TextureStore, image_from_text};
 use crate::resources::worldsignals::WorldSignals;
+use crate::systems::camera_effects::CameraEffect;
+use crate::systems::inputcontext::{DefineInputContext, PopInputContext, PushInputContext};
+use crate::systems::inputsequence::DefineInputSequence;
+use crate::systems::inputstats::{DumpInputStats, SetInputStats};
 use crate::systems::phase_core::queue_phase_transition;
+use crate::systems::playerinput::AssignDevice;
 
 /// Process a single audio command from Lua and write to the audio command channel.
 pub fn process_audio_command(audio_cmd_writer: &mut MessageWriter<AudioCmd>, cmd: AudioLuaCmd) {
@@ -58,6 +72,26 @@ pub fn process_audio_command(audio_cmd_writer: &mut MessageWriter<AudioCmd>, cmd
         AudioLuaCmd::SetMusicVolume { id, vol } => {
             audio_cmd_writer.write(AudioCmd::VolumeMusic { id, vol });
         }
+        AudioLuaCmd::FadeMusicIn { duration } => {
+            audio_cmd_writer.write(AudioCmd::FadeMusicIn { duration });
+        }
+        AudioLuaCmd::FadeMusicOut { duration } => {
+            audio_cmd_writer.write(AudioCmd::FadeMusicOut { duration });
+        }
+        AudioLuaCmd::SetMusicBpm {
+            id,
+            bpm,
+            beats_per_bar,
+        } => {
+            audio_cmd_writer.write(AudioCmd::SetMusicBpm {
+                id,
+                bpm,
+                beats_per_bar,
+            });
+        }
+        AudioLuaCmd::SetMusicTrackerEvents { id, enabled } => {
+            audio_cmd_writer.write(AudioCmd::SetMusicTrackerEvents { id, enabled });
+        }
         AudioLuaCmd::UnloadMusic { id } => {
             audio_cmd_writer.write(AudioCmd::UnloadMusic { id });
         }
@@ -123,6 +157,9 @@ pub fn process_group_command(tracked_groups: &mut TrackedGroups, cmd: GroupCmd)
         GroupCmd::TrackGroup { name } => {
             tracked_groups.add_group(&name);
         }
+        GroupCmd::TrackGroupBounds { name } => {
+            tracked_groups.add_aggregated_group(name);
+        }
         GroupCmd::UntrackGroup { name } => {
             tracked_groups.remove_group(&name);
         }
@@ -132,6 +169,65 @@ pub fn process_group_command(tracked_groups: &mut TrackedGroups, cmd: GroupCmd)
     }
 }
 
+/// Process a single collision rule command from Lua.
+///
+/// Scene rules are spawned as [`SceneScoped`] entities, despawned with the
+/// scene. Removing a rule strips the [`LuaCollisionRule`] component; the
+/// entity is despawned only if the rule was all it carried (besides
+/// [`Persistent`] or [`SceneScoped`]).
+pub fn process_collision_rule_command(
+    commands: &mut Commands,
+    rules: &mut Query<(Entity, &mut LuaCollisionRule)>,
+    cmd: CollisionRuleCmd,
+) {
+    match cmd {
+        CollisionRuleCmd::AddSceneRule {
+            group_a,
+            group_b,
+            callback,
+        } => {
+            commands.spawn((
+                LuaCollisionRule::new(group_a, group_b, LuaCollisionCallback { name: callback }),
+                SceneScoped,
+            ));
+        }
+        CollisionRuleCmd::RemoveRule { group_a, group_b } => {
+            for (entity, rule) in rules.iter() {
+                if rule.is_for_pair(&group_a, &group_b) {
+                    remove_lua_collision_rule(commands, entity);
+                }
+            }
+        }
+        CollisionRuleCmd::SetRuleEnabled {
+            group_a,
+            group_b,
+            enabled,
+        } => {
+            for (_, mut rule) in rules.iter_mut() {
+                if rule.enabled != enabled && rule.is_for_pair(&group_a, &group_b) {
+                    rule.enabled = enabled;
+                }
+            }
+        }
+        CollisionRuleCmd::ClearRules => {
+            for (entity, _) in rules.iter() {
+                remove_lua_collision_rule(commands, entity);
+            }
+        }
+    }
+}
+
+fn remove_lua_collision_rule(commands: &mut Commands, entity: Entity) {
+    commands.entity(entity).queue(|mut entity: EntityWorldMut| {
+        entity.remove::<LuaCollisionRule>();
+        let leftover = usize::from(entity.contains::<Persistent>())
+            + usize::from(entity.contains::<SceneScoped>());
+        if entity.archetype().component_count() == leftover {
+            entity.despawn();
+        }
+    });
+}
+
 /// Process a single camera command from Lua and update the camera resource.
 pub fn process_camera_command(commands: &mut Commands, cmd: CameraCmd) {
     match cmd {
@@ -156,9 +252,47 @@ pub fn process_camera_command(commands: &mut Commands, cmd: CameraCmd) {
                 zoom,
             }));
         }
+        CameraCmd::Shake {
+            intensity,
+            duration,
+        } => commands.queue(CameraEffect::Shake {
+            intensity,
+            duration,
+        }),
+        CameraCmd::ZoomTo {
+            zoom,
+            duration,
+            easing,
+        } => commands.queue(CameraEffect::ZoomTo {
+            zoom,
+            duration,
+            easing: parse_easing(easing),
+        }),
+        CameraCmd::ZoomPunch { amount, duration } => {
+            commands.queue(CameraEffect::ZoomPunch { amount, duration })
+        }
+        CameraCmd::PanTo {
+            x,
+            y,
+            duration,
+            easing,
+        } => commands.queue(CameraEffect::PanTo {
+            target: Vector2 { x, y },
+            duration,
+            easing: parse_easing(easing),
+        }),
+        CameraCmd::ReleasePan { duration, easing } => commands.queue(CameraEffect::ReleasePan {
+            duration,
+            easing: parse_easing(easing),
+        }),
     }
 }
 
+/// Tween easing name, `linear` when omitted or unknown.
+fn parse_easing(easing: Option<String>) -> Easing {
+    easing.map_or(Easing::Linear, |easing| easing.parse().unwrap())
+}
+
 /// Process a single phase command from Lua and apply it to the appropriate entity.
 pub fn process_phase_command<C>(phase_query: &mut Query<(Entity, &mut Phase<C>)>, cmd: PhaseCmd)
 where
@@ -182,6 +316,7 @@ pub fn process_asset_command<F1>(
     th: &raylib::RaylibThread,
     cmd: AssetCmd,
     tex_store: &mut TextureStore,
+    alpha_masks: &mut AlphaMasks,
     fonts: &mut FontStore,
     shader_store: &mut ShaderStore,
     audio_cmd_writer: &mut MessageWriter<AudioCmd>,
@@ -195,16 +330,24 @@ pub fn process_asset_command<F1>(
     ) -> Result<raylib::prelude::Font, String>,
 {
     match cmd {
-        AssetCmd::Texture { id, path, filter } => match rl.load_texture(th, &path) {
-            Ok(tex) => {
-                debug!("Loaded texture '{}' from '{}'", id, path);
-                let filter = TextureFilter::from_opt_str_or_warn(filter.as_deref(), &id);
-                tex_store.insert(&id, tex, filter, None);
-            }
-            Err(e) => {
-                error!("Failed to load texture '{}': {}", path, e);
+        AssetCmd::Texture { id, path, filter } => {
+            // Load through an Image so the alpha mask for pixel collision is
+            // built from the same pixels.
+            match Image::load_image(&path).and_then(|image| {
+                let tex = rl.load_texture_from_image(th, &image)?;
+                Ok((image, tex))
+            }) {
+                Ok((image, tex)) => {
+                    debug!("Loaded texture '{}' from '{}'", id, path);
+                    let filter = TextureFilter::from_opt_str_or_warn(filter.as_deref(), &id);
+                    alpha_masks.insert_image(&id, &image);
+                    tex_store.insert(&id, tex, filter, None);
+                }
+                Err(e) => {
+                    error!("Failed to load texture '{}': {}", path, e);
+                }
             }
-        },
+        }
         AssetCmd::Font { id, path, size } => match load_font_fn(rl, th, &path, size) {
             Ok(font) => {
                 debug!("Loaded font '{}' from '{}'", id, path);
@@ -251,6 +394,36 @@ pub fn process_asset_command<F1>(
                 }
             }
         }
+        AssetCmd::TextTexture {
+            id,
+            text,
+            font,
+            size,
+            r,
+            g,
+            b,
+            a,
+        } => {
+            let Some(font_handle) = fonts.get(&font) else {
+                error!("Failed to bake text texture '{}': font '{}' is not loaded", id, font);
+                return;
+            };
+            let Some(image) = image_from_text(font_handle, &text, size, 1.0, Color::new(r, g, b, a))
+            else {
+                error!("Failed to bake text texture '{}': text contains a NUL byte", id);
+                return;
+            };
+            match rl.load_texture_from_image(th, &image) {
+                Ok(tex) => {
+                    debug!("Baked text texture '{}' ({}x{})", id, tex.width, tex.height);
+                    alpha_masks.insert_image(&id, &image);
+                    tex_store.insert(&id, tex, TextureFilter::Nearest, None);
+                }
+                Err(e) => {
+                    error!("Failed to bake text texture '{}': {}", id, e);
+                }
+            }
+        }
     }
 }
 
@@ -293,6 +466,9 @@ fn build_nine_patch(
 pub fn process_render_command(
     cmd: RenderCmd,
     post_process: &mut PostProcessShader,
+    render_mode: &mut RenderMode,
+    layers: &mut LayerVisibility,
+    color_grading: &mut ColorGrading,
     gui_theme_staging: &mut GuiThemeStore,
 ) {
     match cmd {
@@ -322,6 +498,18 @@ pub fn process_render_command(
         RenderCmd::ClearPostProcessUniforms => {
             post_process.clear_uniforms();
         }
+        RenderCmd::SetRenderMode { mode } => {
+            *render_mode = mode;
+            debug!("Render mode: {}", mode.as_str());
+        }
+        RenderCmd::SetLayerVisible { layer, visible } => {
+            layers.set_visible(layer, visible);
+            debug!("Render layer {} visible: {}", layer.0, visible);
+        }
+        RenderCmd::SetColorLut { key, fade } => {
+            debug!("Color LUT: {} (fade {fade}s)", key.as_deref().unwrap_or("none"));
+            color_grading.set_lut(key.as_deref(), fade);
+        }
         RenderCmd::SetGuiThemePanel {
             theme_key,
             tex_key,
@@ -456,16 +644,42 @@ pub fn process_gameconfig_command(cmd: GameConfigCmd, config: &mut GameConfig) {
         GameConfigCmd::Fullscreen { enabled } => {
             config.fullscreen = enabled;
         }
+        GameConfigCmd::WindowMode { mode, monitor } => {
+            match mode.as_str() {
+                "windowed" => config.fullscreen = false,
+                "borderless" => {
+                    config.fullscreen = true;
+                    config.fullscreen_mode = FullscreenMode::Borderless;
+                }
+                "fullscreen" | "exclusive" => {
+                    config.fullscreen = true;
+                    config.fullscreen_mode = FullscreenMode::Exclusive;
+                }
+                _ => {
+                    warn!("set_window_mode: unknown mode '{}'", mode);
+                    return;
+                }
+            }
+            if let Some(monitor) = monitor {
+                config.monitor = monitor;
+            }
+        }
         GameConfigCmd::Vsync { enabled } => {
             config.vsync = enabled;
         }
         GameConfigCmd::TargetFps { fps } => {
             config.target_fps = fps;
         }
+        GameConfigCmd::BackgroundFps { fps } => {
+            config.background_fps = fps;
+        }
         GameConfigCmd::RenderSize { width, height } => {
             config.render_width = width;
             config.render_height = height;
         }
+        GameConfigCmd::RenderScale { scale } => {
+            config.render_scale = clamp_render_scale(scale);
+        }
         GameConfigCmd::BackgroundColor { r, g, b } => {
             config.background_color = Color::new(r, g, b, 255);
         }
@@ -476,6 +690,34 @@ pub fn process_gameconfig_command(cmd: GameConfigCmd, config: &mut GameConfig) {
             config.render_target_filter =
                 TextureFilter::from_opt_str_or_warn(Some(&filter), "set_render_target_filter");
         }
+        GameConfigCmd::ColorblindMode { mode } => {
+            config.colorblind_mode =
+                ColorblindMode::from_opt_str_or_warn(Some(&mode), "set_colorblind_mode");
+        }
+        GameConfigCmd::HighContrastUi { enabled } => {
+            config.high_contrast_ui = enabled;
+        }
+        GameConfigCmd::MasterVolume { vol } => {
+            config.master_volume = vol.clamp(0.0, 1.0);
+        }
+        GameConfigCmd::MusicVolume { vol } => {
+            config.music_volume = vol.clamp(0.0, 1.0);
+        }
+        GameConfigCmd::SfxVolume { vol } => {
+            config.sfx_volume = vol.clamp(0.0, 1.0);
+        }
+        GameConfigCmd::SystemEnabled { system, enabled } => {
+            config.system_toggles.set_enabled(system, enabled);
+            debug!("System '{}' enabled: {}", system.as_str(), enabled);
+        }
+        GameConfigCmd::AutoQuality { enabled } => {
+            config.auto_quality = enabled;
+        }
+        GameConfigCmd::Save => {
+            if let Err(e) = config.save_to_file() {
+                error!("save_config: {}", e);
+            }
+        }
     }
 }
 
@@ -536,8 +778,12 @@ pub fn process_camera_follow_command(cmd: CameraFollowCmd, config: &mut CameraFo
     }
 }
 
-/// Process a single input rebinding command from Lua.
-pub fn process_input_command(cmd: InputCmd, bindings: &mut InputBindings) {
+/// Process a single input rebinding, context, sequence, analytics or device
+/// command from Lua.
+///
+/// All but the rebinding commands are queued on `commands` and take effect when they are
+/// applied.
+pub fn process_input_command(cmd: InputCmd, bindings: &mut InputBindings, commands: &mut Commands) {
     use crate::resources::lua_runtime::action_from_str;
 
     match cmd {
@@ -563,6 +809,35 @@ pub fn process_input_command(cmd: InputCmd, bindings: &mut InputBindings) {
             };
             bindings.add_binding(a, b);
         }
+        InputCmd::PushInputContext { name } => {
+            commands.queue(PushInputContext { name });
+        }
+        InputCmd::PopInputContext => {
+            commands.queue(PopInputContext);
+        }
+        InputCmd::DefineInputContext { name, actions } => {
+            commands.queue(DefineInputContext { name, actions });
+        }
+        InputCmd::DefineInputSequence {
+            name,
+            steps,
+            window,
+        } => {
+            commands.queue(DefineInputSequence {
+                name,
+                steps,
+                window,
+            });
+        }
+        InputCmd::RecordInputStats { enabled } => {
+            commands.queue(SetInputStats { enabled });
+        }
+        InputCmd::DumpInputStats { path } => {
+            commands.queue(DumpInputStats { path });
+        }
+        InputCmd::AssignDevice { player, device } => {
+            commands.queue(AssignDevice { player, device });
+        }
     }
 }
 
@@ -583,7 +858,7 @@ pub fn process_animation_command(anim_store: &mut AnimationStore, cmd: Animation
             anim_store.insert(
                 id.clone(),
                 AnimationResource {
-                    tex_key: Arc::from(tex_key),
+                    tex_key: tex_key.into(),
                     position: Vector2 { x: pos_x, y: pos_y },
                     horizontal_displacement,
                     vertical_displacement,
@@ -608,14 +883,22 @@ mod tests {
     use raylib::prelude::{Color, Vector2};
 
     use super::{
-        process_animation_command, process_audio_command, process_render_command,
-        process_signal_command,
+        process_animation_command, process_audio_command, process_gameconfig_command,
+        process_render_command, process_signal_command,
     };
     use crate::events::audio::AudioCmd;
     use crate::resources::animationstore::AnimationStore;
+    use crate::resources::colorgrading::ColorGrading;
+    use crate::resources::fullscreen::FullscreenMode;
+    use crate::resources::gameconfig::GameConfig;
     use crate::resources::guitheme::GuiThemeStore;
-    use crate::resources::lua_runtime::{AnimationCmd, AudioLuaCmd, RenderCmd, SignalCmd};
+    use crate::components::renderlayer::RenderLayer;
+    use crate::resources::layervisibility::LayerVisibility;
+    use crate::resources::lua_runtime::{
+        AnimationCmd, AudioLuaCmd, GameConfigCmd, RenderCmd, SignalCmd,
+    };
     use crate::resources::postprocessshader::PostProcessShader;
+    use crate::resources::rendermode::RenderMode;
     use crate::resources::worldsignals::WorldSignals;
 
     fn set_button_cmd(theme_key: &str, state: &str) -> RenderCmd {
@@ -649,14 +932,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_render_mode_updates_mode() {
+        let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
+        let mut staging = GuiThemeStore::default();
+
+        process_render_command(
+            RenderCmd::SetRenderMode {
+                mode: RenderMode::DirtyRects,
+            },
+            &mut post_process,
+            &mut render_mode,
+            &mut layers,
+            &mut grading,
+            &mut staging,
+        );
+        assert_eq!(render_mode, RenderMode::DirtyRects);
+    }
+
+    #[test]
+    fn set_layer_visible_toggles_only_that_layer() {
+        let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
+        let mut staging = GuiThemeStore::default();
+
+        process_render_command(
+            RenderCmd::SetLayerVisible {
+                layer: RenderLayer::FX,
+                visible: false,
+            },
+            &mut post_process,
+            &mut render_mode,
+            &mut layers,
+            &mut grading,
+            &mut staging,
+        );
+        assert!(!layers.is_visible(RenderLayer::FX));
+        assert!(layers.is_visible(RenderLayer::GAMEPLAY));
+    }
+
+    #[test]
+    fn set_color_lut_starts_crossfade() {
+        let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
+        let mut staging = GuiThemeStore::default();
+
+        process_render_command(
+            RenderCmd::SetColorLut {
+                key: Some("night".to_string()),
+                fade: 2.0,
+            },
+            &mut post_process,
+            &mut render_mode,
+            &mut layers,
+            &mut grading,
+            &mut staging,
+        );
+        assert_eq!(grading.current(), Some("night"));
+        assert!(grading.is_fading());
+        assert_eq!(grading.mix(), 0.0);
+    }
+
     #[test]
     fn gui_theme_staging_panel_then_all_button_states_survive() {
         let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
         let mut staging = GuiThemeStore::default();
 
-        process_render_command(set_panel_cmd("default", "panel_tex"), &mut post_process, &mut staging);
+        process_render_command(set_panel_cmd("default", "panel_tex"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
         for state in ["normal", "hover", "pressed", "disabled"] {
-            process_render_command(set_button_cmd("default", state), &mut post_process, &mut staging);
+            process_render_command(set_button_cmd("default", state), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
         }
         process_render_command(
             RenderCmd::SetGuiThemeLabel {
@@ -672,6 +1026,9 @@ mod tests {
                 bottom: 6,
             },
             &mut post_process,
+            &mut render_mode,
+            &mut layers,
+            &mut grading,
             &mut staging,
         );
         process_render_command(
@@ -685,6 +1042,9 @@ mod tests {
                 a: 255,
             },
             &mut post_process,
+            &mut render_mode,
+            &mut layers,
+            &mut grading,
             &mut staging,
         );
 
@@ -705,12 +1065,15 @@ mod tests {
     #[test]
     fn gui_theme_staging_button_states_then_panel_survive_reverse_order() {
         let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
         let mut staging = GuiThemeStore::default();
 
         for state in ["normal", "hover", "pressed", "disabled"] {
-            process_render_command(set_button_cmd("default", state), &mut post_process, &mut staging);
+            process_render_command(set_button_cmd("default", state), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
         }
-        process_render_command(set_panel_cmd("default", "panel_tex"), &mut post_process, &mut staging);
+        process_render_command(set_panel_cmd("default", "panel_tex"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
 
         let theme = staging.themes.get("default").expect("theme should be staged");
         assert_eq!(&*theme.panel.tex_key, "panel_tex");
@@ -722,9 +1085,12 @@ mod tests {
     #[test]
     fn gui_theme_staging_button_normal_only_leaves_other_states_none() {
         let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
         let mut staging = GuiThemeStore::default();
 
-        process_render_command(set_button_cmd("default", "normal"), &mut post_process, &mut staging);
+        process_render_command(set_button_cmd("default", "normal"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
 
         let theme = staging.themes.get("default").expect("theme should be staged");
         let skin = theme.button.clone().expect("button skin should be staged");
@@ -737,11 +1103,14 @@ mod tests {
     #[test]
     fn gui_theme_staging_two_keys_do_not_interfere() {
         let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
         let mut staging = GuiThemeStore::default();
 
-        process_render_command(set_panel_cmd("theme_a", "panel_a"), &mut post_process, &mut staging);
-        process_render_command(set_panel_cmd("theme_b", "panel_b"), &mut post_process, &mut staging);
-        process_render_command(set_button_cmd("theme_b", "normal"), &mut post_process, &mut staging);
+        process_render_command(set_panel_cmd("theme_a", "panel_a"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
+        process_render_command(set_panel_cmd("theme_b", "panel_b"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
+        process_render_command(set_button_cmd("theme_b", "normal"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
 
         let theme_a = staging.themes.get("theme_a").expect("theme_a should be staged");
         assert_eq!(&*theme_a.panel.tex_key, "panel_a");
@@ -755,12 +1124,15 @@ mod tests {
     #[test]
     fn gui_theme_staging_existing_other_key_preserved_across_drain() {
         let mut post_process = PostProcessShader::default();
+        let mut render_mode = RenderMode::default();
+        let mut layers = LayerVisibility::default();
+        let mut grading = ColorGrading::default();
         let mut staging = GuiThemeStore::default();
-        process_render_command(set_panel_cmd("theme_a", "panel_a"), &mut post_process, &mut staging);
+        process_render_command(set_panel_cmd("theme_a", "panel_a"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
 
         // Simulate a later frame's staging seeded from the persisted resource,
         // draining only a "theme_b" command.
-        process_render_command(set_panel_cmd("theme_b", "panel_b"), &mut post_process, &mut staging);
+        process_render_command(set_panel_cmd("theme_b", "panel_b"), &mut post_process, &mut render_mode, &mut layers, &mut grading, &mut staging);
 
         let theme_a = staging.themes.get("theme_a").expect("theme_a should survive");
         assert_eq!(&*theme_a.panel.tex_key, "panel_a");
@@ -847,4 +1219,96 @@ mod tests {
         );
         assert!(!world_signals.has_flag("paused"));
     }
+
+    #[test]
+    fn volume_commands_are_clamped() {
+        let mut config = GameConfig::new();
+
+        process_gameconfig_command(GameConfigCmd::MasterVolume { vol: 0.5 }, &mut config);
+        process_gameconfig_command(GameConfigCmd::MusicVolume { vol: 2.0 }, &mut config);
+        process_gameconfig_command(GameConfigCmd::SfxVolume { vol: -1.0 }, &mut config);
+
+        assert_eq!(config.volumes(), (0.5, 1.0, 0.0));
+    }
+
+    #[test]
+    fn accessibility_commands_update_config() {
+        use crate::resources::accessibility::ColorblindMode;
+
+        let mut config = GameConfig::new();
+        process_gameconfig_command(
+            GameConfigCmd::ColorblindMode {
+                mode: "protanopia".to_string(),
+            },
+            &mut config,
+        );
+        process_gameconfig_command(GameConfigCmd::HighContrastUi { enabled: true }, &mut config);
+        assert_eq!(config.colorblind_mode, ColorblindMode::Protanopia);
+        assert!(config.high_contrast_ui);
+
+        // Unknown modes fall back to no remapping
+        process_gameconfig_command(
+            GameConfigCmd::ColorblindMode {
+                mode: "sepia".to_string(),
+            },
+            &mut config,
+        );
+        assert_eq!(config.colorblind_mode, ColorblindMode::None);
+    }
+
+    #[test]
+    fn window_mode_command_updates_config() {
+        let mut config = GameConfig::new();
+        process_gameconfig_command(
+            GameConfigCmd::WindowMode {
+                mode: "fullscreen".to_string(),
+                monitor: Some(1),
+            },
+            &mut config,
+        );
+        assert!(config.fullscreen);
+        assert_eq!(config.fullscreen_mode, FullscreenMode::Exclusive);
+        assert_eq!(config.monitor, 1);
+
+        // Omitted monitor keeps the current one
+        process_gameconfig_command(
+            GameConfigCmd::WindowMode {
+                mode: "borderless".to_string(),
+                monitor: None,
+            },
+            &mut config,
+        );
+        assert_eq!(config.fullscreen_mode, FullscreenMode::Borderless);
+        assert_eq!(config.monitor, 1);
+
+        // Unknown modes leave the config untouched
+        process_gameconfig_command(
+            GameConfigCmd::WindowMode {
+                mode: "maximized".to_string(),
+                monitor: Some(3),
+            },
+            &mut config,
+        );
+        assert!(config.fullscreen);
+        assert_eq!(config.monitor, 1);
+
+        process_gameconfig_command(
+            GameConfigCmd::WindowMode {
+                mode: "windowed".to_string(),
+                monitor: None,
+            },
+            &mut config,
+        );
+        assert!(!config.fullscreen);
+        assert_eq!(config.fullscreen_mode, FullscreenMode::Borderless);
+    }
+
+    #[test]
+    fn background_fps_command_sets_unfocused_cap() {
+        let mut config = GameConfig::new();
+        process_gameconfig_command(GameConfigCmd::BackgroundFps { fps: 10 }, &mut config);
+        assert_eq!(config.background_fps, 10);
+        assert_eq!(config.frame_cap(false), 10);
+        assert_eq!(config.frame_cap(true), config.target_fps);
+    }
 }
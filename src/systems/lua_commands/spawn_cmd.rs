@@ -14,6 +14,7 @@ use crate::components::boxcollider::BoxCollider;
 use crate::components::cameratarget::CameraTarget;
 use crate::components::dynamictext::DynamicText;
 use crate::components::entityshader::EntityShader;
+use crate::components::gridlayout::GridIntro;
 use crate::components::group::Group;
 use crate::components::guioffset::GuiOffset;
 use crate::components::luaphase::{LuaPhase, PhaseCallbacks};
@@ -21,6 +22,7 @@ use crate::components::luasetup::LuaSetup;
 use crate::components::luatimer::{LuaTimer, LuaTimerCallback};
 use crate::components::mapposition::MapPosition;
 use crate::components::persistent::Persistent;
+use crate::components::pixelcollision::PixelCollision;
 use crate::components::rigidbody::RigidBody;
 use crate::components::rotation::Rotation;
 use crate::components::scale::Scale;
@@ -29,10 +31,12 @@ use crate::components::signalbinding::SignalBinding;
 use crate::components::signals::Signals;
 use crate::components::sprite::Sprite;
 use crate::components::stuckto::StuckTo;
+use crate::components::tags::Tags;
 use crate::components::tilemap::TileMap;
 use crate::components::shadow::Shadow;
 use crate::components::tint::Tint;
 use crate::components::ttl::Ttl;
+use crate::components::windresponse::WindResponse;
 use crate::components::zindex::ZIndex;
 
 use crate::resources::lua_runtime::{
@@ -45,6 +49,7 @@ use crate::resources::worldsignals::WorldSignals;
 use crate::systems::propagate_transforms::ComputeInitialGlobalTransform;
 
 use super::parse::convert_animation_condition;
+use super::spawn_validate::{ValidateSpawnAssets, strip_dead_targets};
 
 use log::warn;
 /// Process a spawn command from Lua and create the corresponding entity.
@@ -55,14 +60,22 @@ use log::warn;
 /// are spawned by `gui_button_spawn_system`/`gui_label_spawn_system`/
 /// `gui_image_spawn_system` (`systems/gui_spawn.rs`) reacting on
 /// `Added<T>`, not by this function.
+///
+/// Dead `parent`/`stuckto` targets are dropped and missing asset keys are
+/// reported with the command's Lua call site (see [`super::spawn_validate`]).
 pub fn process_spawn_command(
     commands: &mut Commands,
-    cmd: SpawnCmd,
+    mut cmd: SpawnCmd,
     world_signals: &mut WorldSignals,
 ) {
+    strip_dead_targets(commands, &mut cmd);
+    let validate = ValidateSpawnAssets::from_cmd(&cmd);
     let mut entity_commands = commands.spawn_empty();
     let entity = entity_commands.id();
     apply_components(&mut entity_commands, cmd, world_signals, entity);
+    if let Some(validate) = validate {
+        entity_commands.queue(validate);
+    }
 }
 
 pub(super) fn apply_components(
@@ -75,14 +88,89 @@ pub(super) fn apply_components(
     if let Some(group_name) = cmd.group {
         entity_commands.insert(Group::new(&group_name));
     }
+    if !cmd.tags.is_empty() {
+        entity_commands.insert(Tags::new(cmd.tags));
+    }
     if cmd.persistent {
         entity_commands.insert(Persistent);
     }
+    if cmd.pixel_collision {
+        entity_commands.insert(PixelCollision);
+    }
+    if let Some(slope) = cmd.slope {
+        entity_commands.insert(slope);
+    }
+    if let Some(factor) = cmd.wind {
+        entity_commands.insert(WindResponse::new(factor));
+    }
     if let Some(seconds) = cmd.ttl {
         entity_commands.insert(Ttl::new(seconds));
     }
+    if let Some(cooldown) = cmd.cooldown {
+        entity_commands.insert(cooldown);
+    }
     if let Some(path) = cmd.tilemap_path {
-        entity_commands.insert(TileMap::new(path));
+        let mut tilemap = TileMap::new(path);
+        tilemap.projection = cmd.tilemap_projection;
+        tilemap.solid_layers = cmd.tilemap_solid_layers;
+        entity_commands.insert(tilemap);
+    }
+    if let Some(sensor) = cmd.sensor {
+        entity_commands.insert(sensor);
+    }
+    if let Some(area) = cmd.force_area {
+        entity_commands.insert(area);
+    }
+    if let Some(joint) = cmd.distance_joint {
+        entity_commands.insert(joint);
+    }
+    if let Some(joint) = cmd.pin_joint {
+        entity_commands.insert(joint);
+    }
+    if let Some(rope) = cmd.rope {
+        entity_commands.insert(rope);
+    }
+    if let Some(water) = cmd.water_surface {
+        entity_commands.insert(water);
+    }
+    if let Some(attractor) = cmd.attractor {
+        entity_commands.insert(attractor);
+    }
+    if let Some(triggers) = cmd.audio_triggers {
+        entity_commands.insert(triggers);
+    }
+    if let Some(scroller) = cmd.text_scroller {
+        entity_commands.insert(scroller);
+    }
+    if let Some(follow) = cmd.path_follow {
+        entity_commands.insert(follow);
+    }
+    if let Some(layer) = cmd.render_layer {
+        entity_commands.insert(layer);
+    }
+    if let Some(swap) = cmd.palette_swap {
+        entity_commands.insert(swap);
+    }
+    if let Some(player) = cmd.player {
+        entity_commands.insert(player);
+    }
+    if let Some(terrain) = cmd.terrain {
+        entity_commands.insert(terrain);
+    }
+    if let Some(vision) = cmd.vision {
+        entity_commands.insert(vision);
+    }
+    if let Some(visibility) = cmd.visibility {
+        entity_commands.insert(visibility);
+    }
+    if let Some(hitbox) = cmd.hitbox {
+        entity_commands.insert(hitbox);
+    }
+    if let Some(hurtbox) = cmd.hurtbox {
+        entity_commands.insert(hurtbox);
+    }
+    if let Some(restore) = cmd.restore_on_return {
+        entity_commands.insert(restore);
     }
     if let Some(window) = cmd.gui_window {
         entity_commands.insert(window);
@@ -91,7 +179,7 @@ pub(super) fn apply_components(
     // co-located GuiInteractable/caption/Sprite are spawned by
     // gui_button_spawn_system/gui_label_spawn_system/gui_image_spawn_system
     // (systems/gui_spawn.rs) reacting on Added<T>.
-    // GuiProgressBar is inserted as-is; rendered directly by render_system.
+    // GuiProgressBar and GuiInventoryGrid are inserted as-is; rendered directly by render_system.
     if let Some(btn) = cmd.gui_button {
         entity_commands.insert(btn);
     }
@@ -104,6 +192,45 @@ pub(super) fn apply_components(
     if let Some(bar) = cmd.gui_progress_bar {
         entity_commands.insert(bar);
     }
+    if let Some(grid) = cmd.gui_inventory_grid {
+        entity_commands.insert(grid);
+    }
+    if let Some(inventory) = cmd.inventory {
+        entity_commands.insert(inventory);
+    }
+    if let Some(interactable) = cmd.interactable {
+        entity_commands.insert(interactable);
+    }
+    if let Some(portal) = cmd.portal {
+        entity_commands.insert(portal);
+    }
+    if let Some(indicator) = cmd.offscreen_indicator {
+        entity_commands.insert(indicator);
+    }
+    if let Some(anchor) = cmd.world_anchor {
+        entity_commands.insert(anchor);
+    }
+    if let Some(owned_by) = cmd.owned_by {
+        entity_commands.insert(owned_by);
+    }
+    if let Some(bar) = cmd.progress_bar {
+        entity_commands.insert(bar);
+    }
+    if let Some(radial) = cmd.radial_progress {
+        entity_commands.insert(radial);
+    }
+    if let Some(slice) = cmd.nine_slice {
+        entity_commands.insert(slice);
+    }
+    if let Some(blob) = cmd.blob_shadow {
+        entity_commands.insert(blob);
+    }
+    if let Some(height) = cmd.height {
+        entity_commands.insert(height);
+    }
+    if let Some(spawn_point) = cmd.spawn_point {
+        entity_commands.insert(spawn_point);
+    }
 
     apply_transform_components(
         entity_commands,
@@ -154,6 +281,8 @@ pub(super) fn apply_components(
             lua_collision_rule: cmd.lua_collision_rule,
             lua_setup: cmd.lua_setup,
             lua_on_animation_end: cmd.lua_on_animation_end,
+            lua_on_hit: cmd.lua_on_hit,
+            lua_on_land: cmd.lua_on_land,
         },
     );
     apply_ui_components(
@@ -162,6 +291,7 @@ pub(super) fn apply_components(
         cmd.text,
         cmd.menu,
         cmd.grid_layout,
+        cmd.grid_intro,
         cmd.mouse_controlled,
     );
     apply_particle_emitter(entity_commands, world_signals, cmd.particle_emitter);
@@ -247,6 +377,9 @@ fn apply_physics_components(
             y: rb_data.velocity_y,
         };
         rb.frozen = rb_data.frozen;
+        if let Some(mass) = rb_data.mass {
+            rb.set_mass(mass);
+        }
         for force in rb_data.forces {
             rb.add_force_with_state(
                 &force.name,
@@ -287,7 +420,7 @@ fn apply_render_components(
 ) {
     if let Some(sprite_data) = sprite {
         entity_commands.insert(Sprite {
-            tex_key: Arc::from(sprite_data.tex_key),
+            tex_key: sprite_data.tex_key.into(),
             width: sprite_data.width,
             height: sprite_data.height,
             origin: Vector2 {
@@ -431,6 +564,8 @@ struct BehaviorComponents {
     lua_collision_rule: Option<LuaCollisionRuleData>,
     lua_setup: Option<String>,
     lua_on_animation_end: Option<String>,
+    lua_on_hit: Option<String>,
+    lua_on_land: Option<String>,
 }
 
 fn apply_behavior_components(entity_commands: &mut EntityCommands, b: BehaviorComponents) {
@@ -440,6 +575,8 @@ fn apply_behavior_components(entity_commands: &mut EntityCommands, b: BehaviorCo
         lua_collision_rule,
         lua_setup,
         lua_on_animation_end,
+        lua_on_hit,
+        lua_on_land,
     } = b;
     if let Some(phase_data) = phase_data {
         let phases = phase_data
@@ -469,13 +606,17 @@ fn apply_behavior_components(entity_commands: &mut EntityCommands, b: BehaviorCo
     if let Some(rule_data) = lua_collision_rule {
         use crate::components::collision::CollisionRule;
         use crate::components::luacollision::LuaCollisionCallback;
-        entity_commands.insert(CollisionRule::new(
-            rule_data.group_a,
-            rule_data.group_b,
-            LuaCollisionCallback {
-                name: rule_data.callback,
-            },
-        ));
+        entity_commands.insert(
+            CollisionRule::new(
+                rule_data.group_a,
+                rule_data.group_b,
+                LuaCollisionCallback {
+                    name: rule_data.callback,
+                },
+            )
+            .with_priority(rule_data.priority)
+            .with_exclusive(rule_data.exclusive),
+        );
     }
     if let Some(callback) = lua_setup {
         entity_commands.insert(LuaSetup::new(callback));
@@ -484,6 +625,14 @@ fn apply_behavior_components(entity_commands: &mut EntityCommands, b: BehaviorCo
         use crate::components::lua_on_animation_end::LuaOnAnimationEnd;
         entity_commands.insert(LuaOnAnimationEnd::new(callback));
     }
+    if let Some(callback) = lua_on_hit {
+        use crate::components::lua_on_hit::LuaOnHit;
+        entity_commands.insert(LuaOnHit::new(callback));
+    }
+    if let Some(callback) = lua_on_land {
+        use crate::components::lua_on_land::LuaOnLand;
+        entity_commands.insert(LuaOnLand::new(callback));
+    }
 }
 
 fn apply_ui_components(
@@ -492,6 +641,7 @@ fn apply_ui_components(
     text: Option<TextData>,
     menu: Option<MenuData>,
     grid_layout: Option<(String, String, f32)>,
+    grid_intro: Option<GridIntro>,
     mouse_controlled: Option<(bool, bool)>,
 ) {
     if let Some(text_data) = text {
@@ -561,7 +711,9 @@ fn apply_ui_components(
     }
     if let Some((path, group, zindex)) = grid_layout {
         use crate::components::gridlayout::GridLayout;
-        entity_commands.insert(GridLayout::new(path, group, zindex));
+        let mut layout = GridLayout::new(path, group, zindex);
+        layout.intro = grid_intro;
+        entity_commands.insert(layout);
     }
     if let Some((follow_x, follow_y)) = mouse_controlled {
         use crate::components::inputcontrolled::MouseControlled;
@@ -658,7 +810,8 @@ impl bevy_ecs::system::EntityCommand for ResetAnimationCommand {
 ///
 /// Clones an existing entity (looked up by [`WorldSignals`] key) and applies
 /// component overrides from the [`CloneCmd`]. Animation is always reset to frame 0
-/// unless an animation override is explicitly provided.
+/// unless an animation override is explicitly provided. Overrides are validated
+/// the same way as [`process_spawn_command`] input.
 pub fn process_clone_command(
     commands: &mut Commands,
     cmd: CloneCmd,
@@ -682,26 +835,34 @@ pub fn process_clone_command(
         return;
     }
 
-    // 2. Clone entity using Bevy's clone_and_spawn API
+    // 2. Drop dead targets and collect override asset keys to validate
+    let mut overrides = cmd.overrides;
+    strip_dead_targets(commands, &mut overrides);
+    let validate = ValidateSpawnAssets::from_cmd(&overrides);
+
+    // 3. Clone entity using Bevy's clone_and_spawn API
     let mut source_commands = commands.entity(source_entity);
     let mut entity_commands = source_commands.clone_and_spawn();
     let cloned_entity = entity_commands.id();
 
-    // 3. Check if animation override is provided before moving overrides
-    let has_animation_override = cmd.overrides.animation.is_some();
+    // 4. Check if animation override is provided before moving overrides
+    let has_animation_override = overrides.animation.is_some();
 
-    // 4. Apply all component overrides (same logic as spawn)
+    // 5. Apply all component overrides (same logic as spawn)
     apply_components(
         &mut entity_commands,
-        cmd.overrides,
+        overrides,
         world_signals,
         cloned_entity,
     );
 
-    // 5. If no animation override was provided, reset to frame 0
+    // 6. If no animation override was provided, reset to frame 0
     if !has_animation_override {
         entity_commands.queue(ResetAnimationCommand);
     }
+    if let Some(validate) = validate {
+        entity_commands.queue(validate);
+    }
 }
 
 #[cfg(test)]
@@ -769,4 +930,34 @@ mod tests {
         let mut query = world.query::<&MapPosition>();
         assert_eq!(query.iter(&world).count(), 2);
     }
+
+    #[test]
+    fn spawn_with_despawned_parent_spawns_unparented() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        world.despawn(parent);
+
+        let mut world_signals = WorldSignals::default();
+        let mut system_state = SystemState::<Commands>::new(&mut world);
+        {
+            let mut commands = system_state
+                .get_mut(&mut world)
+                .expect("Commands should fetch in spawn test");
+            process_spawn_command(
+                &mut commands,
+                SpawnCmd {
+                    position: Some((1.0, 2.0)),
+                    parent: Some(parent.to_bits()),
+                    ..SpawnCmd::default()
+                },
+                &mut world_signals,
+            );
+        }
+        system_state.apply(&mut world);
+
+        let mut positions = world.query::<&MapPosition>();
+        assert_eq!(positions.iter(&world).count(), 1);
+        let mut children = world.query::<&ChildOf>();
+        assert_eq!(children.iter(&world).count(), 0);
+    }
 }
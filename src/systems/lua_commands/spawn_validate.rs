@@ -0,0 +1,257 @@
+//! Spawn-time validation of Lua-built entities.
+//!
+//! A [`SpawnCmd`] refers to textures, fonts, animations and target entities by
+//! key. A typo in any of them used to produce an invisible sprite, a text
+//! entity that never draws or a `ChildOf` pointing at a dead entity, with
+//! nothing in the log to say which `build()` call was at fault.
+//! [`process_spawn_command`](super::process_spawn_command) now runs two checks:
+//!
+//! - [`strip_dead_targets`] drops a `parent`/`stuckto` target that is not alive
+//!   before any component is inserted.
+//! - [`ValidateSpawnAssets`] is queued on the new entity and looks every asset
+//!   key up in its store once the command buffer is applied. Keys whose load is
+//!   still queued in the [`LuaRuntime`] count as present. A store that is not
+//!   in the world (headless tests, secondary worlds) skips its check.
+//!
+//! Every problem is logged with the [`SpawnOrigin`] of the command, e.g.
+//! `engine.spawn() at level.lua:12 <- level.lua:40: sprite texture 'playr' is not loaded`.
+//! A sprite whose texture is missing still draws the [`TextureStore`]
+//! fallback checkerboard.
+
+use std::fmt;
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::EntityCommand;
+use bevy_ecs::world::EntityWorldMut;
+use log::error;
+
+use crate::resources::animationstore::AnimationStore;
+use crate::resources::fontstore::FontStore;
+use crate::resources::lua_runtime::{LuaRuntime, SpawnCmd, SpawnOrigin};
+use crate::resources::texturestore::TextureStore;
+use crate::resources::worlds::Worlds;
+
+/// Label used in reports for commands without a recorded origin.
+fn origin_label(origin: Option<&SpawnOrigin>) -> String {
+    origin.map_or_else(|| "spawn".to_string(), ToString::to_string)
+}
+
+/// Clear `cmd.parent` and `cmd.stuckto` when they name an entity that does
+/// not exist, logging an error for each.
+///
+/// Entities spawned earlier in the same command batch count as alive.
+pub(super) fn strip_dead_targets(commands: &mut Commands, cmd: &mut SpawnCmd) {
+    let mut alive = |bits: u64| {
+        super::entity_cmd::resolve_entity(bits).is_some_and(|e| commands.get_entity(e).is_ok())
+    };
+    if let Some(parent) = cmd.parent
+        && !alive(parent)
+    {
+        error!(
+            "{}: parent entity {} does not exist; spawning without a parent",
+            origin_label(cmd.origin.as_ref()),
+            parent
+        );
+        cmd.parent = None;
+    }
+    if let Some(target) = cmd.stuckto.as_ref().map(|s| s.target_entity_id)
+        && !alive(target)
+    {
+        error!(
+            "{}: stuckto target entity {} does not exist; spawning unstuck",
+            origin_label(cmd.origin.as_ref()),
+            target
+        );
+        cmd.stuckto = None;
+    }
+}
+
+/// An asset key a spawned entity refers to but no store holds.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum MissingAsset {
+    Texture(String),
+    Font(String),
+    Animation(String),
+}
+
+impl fmt::Display for MissingAsset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissingAsset::Texture(key) => write!(f, "sprite texture '{key}' is not loaded"),
+            MissingAsset::Font(key) => write!(f, "text font '{key}' is not loaded"),
+            MissingAsset::Animation(key) => write!(f, "animation '{key}' is not registered"),
+        }
+    }
+}
+
+/// EntityCommand that checks the asset keys of a freshly spawned entity.
+/// See the module docs.
+pub(super) struct ValidateSpawnAssets {
+    origin: Option<SpawnOrigin>,
+    texture: Option<String>,
+    font: Option<String>,
+    animations: Vec<String>,
+}
+
+impl ValidateSpawnAssets {
+    /// Collect the asset keys of `cmd`, or `None` if it references none.
+    pub(super) fn from_cmd(cmd: &SpawnCmd) -> Option<Self> {
+        let mut animations = Vec::new();
+        if let Some(animation) = &cmd.animation {
+            animations.push(animation.animation_key.clone());
+        }
+        if let Some(controller) = &cmd.animation_controller {
+            animations.push(controller.fallback_key.clone());
+            animations.extend(controller.rules.iter().map(|r| r.set_key.clone()));
+        }
+        animations.sort_unstable();
+        animations.dedup();
+
+        let check = Self {
+            origin: cmd.origin.clone(),
+            texture: cmd.sprite.as_ref().map(|s| s.tex_key.clone()),
+            font: cmd.text.as_ref().map(|t| t.font.clone()),
+            animations,
+        };
+        (check.texture.is_some() || check.font.is_some() || !check.animations.is_empty())
+            .then_some(check)
+    }
+
+    /// Every referenced key that is neither loaded nor queued for loading.
+    pub(super) fn find_missing(&self, world: &World) -> Vec<MissingAsset> {
+        let lua = world.get_non_send::<LuaRuntime>();
+        let mut missing = Vec::new();
+
+        if let (Some(key), Some(textures)) = (&self.texture, world.get_resource::<TextureStore>())
+            && textures.get(key).is_none()
+            && !world
+                .get_resource::<Worlds>()
+                .is_some_and(|w| w.contains(key))
+            && !lua.is_some_and(|l| l.has_pending_asset(key))
+        {
+            missing.push(MissingAsset::Texture(key.clone()));
+        }
+        if let (Some(key), Some(fonts)) = (&self.font, world.get_non_send::<FontStore>())
+            && fonts.get(key).is_none()
+            && !lua.is_some_and(|l| l.has_pending_asset(key))
+        {
+            missing.push(MissingAsset::Font(key.clone()));
+        }
+        if let Some(store) = world.get_resource::<AnimationStore>() {
+            missing.extend(
+                self.animations
+                    .iter()
+                    .filter(|key| !store.animations.contains_key(key.as_str()))
+                    .filter(|key| !lua.is_some_and(|l| l.has_pending_animation(key)))
+                    .map(|key| MissingAsset::Animation(key.clone())),
+            );
+        }
+        missing
+    }
+
+    fn report(self, world: &mut World, entity: Entity) {
+        let missing = self.find_missing(world);
+        if missing.is_empty() {
+            return;
+        }
+        let origin = origin_label(self.origin.as_ref());
+        for problem in &missing {
+            error!("{origin}: {problem} (entity {entity:?})");
+        }
+    }
+}
+
+impl EntityCommand for ValidateSpawnAssets {
+    type Out = ();
+
+    fn apply(self, mut entity: EntityWorldMut) {
+        let id = entity.id();
+        entity.world_scope(|world| self.report(world, id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::lua_runtime::{AnimationData, SpriteData, StuckToData};
+    use bevy_ecs::system::SystemState;
+
+    fn sprite_cmd(tex_key: &str) -> SpawnCmd {
+        SpawnCmd {
+            sprite: Some(SpriteData {
+                tex_key: tex_key.to_string(),
+                ..SpriteData::default()
+            }),
+            animation: Some(AnimationData {
+                animation_key: "hero_walk".to_string(),
+            }),
+            ..SpawnCmd::default()
+        }
+    }
+
+    #[test]
+    fn from_cmd_skips_commands_without_asset_keys() {
+        assert!(ValidateSpawnAssets::from_cmd(&SpawnCmd::default()).is_none());
+        assert!(ValidateSpawnAssets::from_cmd(&sprite_cmd("hero")).is_some());
+    }
+
+    #[test]
+    fn find_missing_reports_unloaded_keys_against_present_stores() {
+        let mut world = World::new();
+        world.insert_resource(TextureStore::new());
+        world.insert_resource(AnimationStore::default());
+
+        let check = ValidateSpawnAssets::from_cmd(&sprite_cmd("hero")).unwrap();
+        assert_eq!(
+            check.find_missing(&world),
+            vec![
+                MissingAsset::Texture("hero".to_string()),
+                MissingAsset::Animation("hero_walk".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_missing_skips_absent_stores_and_pending_lua_loads() {
+        let mut world = World::new();
+        let check = ValidateSpawnAssets::from_cmd(&sprite_cmd("hero")).unwrap();
+        assert!(check.find_missing(&world).is_empty());
+
+        world.insert_resource(TextureStore::new());
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load("engine.load_texture('hero', 'hero.png')")
+            .exec()
+            .unwrap();
+        world.insert_non_send(runtime);
+        assert!(check.find_missing(&world).is_empty());
+    }
+
+    #[test]
+    fn strip_dead_targets_drops_despawned_parent_and_stuckto() {
+        let mut world = World::new();
+        let alive = world.spawn_empty().id();
+        let dead = world.spawn_empty().id();
+        world.despawn(dead);
+
+        let mut cmd = SpawnCmd {
+            parent: Some(alive.to_bits()),
+            stuckto: Some(StuckToData {
+                target_entity_id: dead.to_bits(),
+                offset_x: 0.0,
+                offset_y: 0.0,
+                follow_x: true,
+                follow_y: true,
+                stored_velocity: None,
+            }),
+            ..SpawnCmd::default()
+        };
+        let mut state = SystemState::<Commands>::new(&mut world);
+        let mut commands = state.get_mut(&mut world).expect("Commands should fetch");
+        strip_dead_targets(&mut commands, &mut cmd);
+
+        assert_eq!(cmd.parent, Some(alive.to_bits()));
+        assert!(cmd.stuckto.is_none());
+    }
+}
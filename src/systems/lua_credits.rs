@@ -0,0 +1,35 @@
+//! Lua observer for credits events.
+//!
+//! When a [`CreditsFinished`] event names a callback (the `on_done` of
+//! `engine.show_credits`), this observer calls that global function with no
+//! arguments. Commands queued by the callback are drained by the next
+//! `lua_plugin::update`.
+//!
+//! # Lua Callback Signature
+//!
+//! ```lua
+//! engine.show_credits{file = "./assets/credits.txt", on_done = "credits_done"}
+//!
+//! function credits_done()
+//!     engine.change_scene("menu")
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::credits::CreditsFinished;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the `on_done` function of a finished credits roll.
+pub fn lua_credits_observer(
+    trigger: On<CreditsFinished>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    let Some(callback) = &trigger.event().callback else {
+        return;
+    };
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_named(callback, "Credits", |func| func.call::<()>(()));
+}
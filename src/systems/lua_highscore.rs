@@ -0,0 +1,40 @@
+//! Lua observer for high score events.
+//!
+//! When a [`HighScoreEvent`] names a callback (the `on_done` of
+//! `engine.submit_score`), this observer calls that global function with the
+//! entered name and the 1-based rank, or `nil, nil` when the score didn't make
+//! the table. Commands queued by the callback are drained by the next
+//! `lua_plugin::update`.
+//!
+//! # Lua Callback Signature
+//!
+//! ```lua
+//! engine.submit_score(engine.get_integer("score"), "score_done")
+//!
+//! function score_done(name, rank)
+//!     if rank then engine.log_info(name .. " placed #" .. rank) end
+//!     engine.change_scene("menu")
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::highscore::HighScoreEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the `on_done` function of a submitted score.
+pub fn lua_highscore_observer(
+    trigger: On<HighScoreEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    let event = trigger.event();
+    let Some(callback) = &event.callback else {
+        return;
+    };
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    let name = event.name.clone();
+    let rank = event.rank.map(|rank| rank + 1);
+    lua_runtime.call_named(callback, "HighScore", |func| func.call::<()>((name, rank)));
+}
@@ -0,0 +1,109 @@
+//! Lua observer for hitbox events.
+//!
+//! When a [`HitEvent`] fires and the attacking entity has a [`LuaOnHit`]
+//! component, this observer calls the named Lua function with
+//! `(ctx, input, target_id)`, where `ctx` describes the attacker.
+//!
+//! Attackers without [`LuaOnHit`] are silently skipped.
+//!
+//! # Lua callback signature
+//!
+//! ```lua
+//! function on_punch_hit(ctx, input, target_id)
+//!     engine.entity_signal_set_flag(target_id, "stunned")
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::components::lua_on_hit::LuaOnHit;
+use crate::components::luaphase::LuaPhase;
+use crate::events::audio::AudioCmd;
+use crate::events::hit::HitEvent;
+use crate::resources::animationstore::AnimationStore;
+use crate::resources::input::InputState;
+use crate::resources::lua_runtime::{InputSnapshot, LuaPhaseSnapshot, LuaRuntime, PhaseCmd};
+use crate::resources::systemsstore::SystemsStore;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+use crate::systems::lua_commands::{
+    ContextQueries, EffectCmdBufs, EntityCmdQueries, build_entity_context, drain_phase_and_effects,
+};
+use log::error;
+
+/// Observer that calls a Lua function when an entity's hitbox strikes a hurtbox.
+#[allow(clippy::too_many_arguments)]
+pub fn lua_hit_observer(
+    trigger: On<HitEvent>,
+    mut commands: Commands,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+    on_hit_query: Query<&LuaOnHit>,
+    ctx_queries: ContextQueries,
+    mut cmd_queries: EntityCmdQueries,
+    mut luaphase_query: Query<(Entity, &mut LuaPhase)>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+    mut audio_cmd_writer: MessageWriter<AudioCmd>,
+    systems_store: Res<SystemsStore>,
+    animation_store: Res<AnimationStore>,
+    mut phase_buf: Local<Vec<PhaseCmd>>,
+    mut effect_bufs: Local<EffectCmdBufs>,
+) {
+    let event = *trigger.event();
+
+    // Only proceed if the attacker opted in with LuaOnHit.
+    let callback_name = match on_hit_query.get(event.attacker) {
+        Ok(c) => c.callback.clone(),
+        Err(_) => return,
+    };
+
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+
+    let input_snapshot = InputSnapshot::from_input_state(&input);
+    let input_table = match lua_runtime.update_input_table(&input_snapshot, time.frame_count) {
+        Ok(t) => t,
+        Err(e) => {
+            error!("Error creating input table for on_hit callback: {}", e);
+            return;
+        }
+    };
+
+    let lua_phase_snapshot = luaphase_query
+        .get(event.attacker)
+        .ok()
+        .map(|(_, p)| LuaPhaseSnapshot::from(p));
+
+    let ctx_table = match build_entity_context(
+        &lua_runtime,
+        event.attacker,
+        &ctx_queries,
+        &cmd_queries,
+        lua_phase_snapshot,
+        None,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            error!("Error building context for on_hit callback: {}", e);
+            return;
+        }
+    };
+
+    let target_id = event.target.to_bits();
+    lua_runtime.call_named(&callback_name, "on_hit", |func| {
+        func.call::<()>((ctx_table, input_table, target_id))
+    });
+
+    drain_phase_and_effects(
+        &lua_runtime,
+        &mut phase_buf,
+        &mut luaphase_query,
+        &mut effect_bufs,
+        &mut commands,
+        &mut world_signals,
+        &mut cmd_queries,
+        &mut audio_cmd_writer,
+        &systems_store,
+        &animation_store,
+    );
+}
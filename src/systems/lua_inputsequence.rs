@@ -0,0 +1,29 @@
+//! Lua observer for input sequence events.
+//!
+//! Forwards each [`InputSequenceEvent`] to the function registered for its
+//! pattern with `engine.on_sequence(name, fn)`. Commands queued by the
+//! function are drained by the next `lua_plugin::update`.
+//!
+//! # Lua Hook Signature
+//!
+//! ```lua
+//! engine.on_sequence("konami", function(name)
+//!     engine.set_integer("lives", 30)
+//! end)
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::inputsequence::InputSequenceEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua function registered for the completed pattern.
+pub fn lua_input_sequence_observer(
+    trigger: On<InputSequenceEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_sequence_hook(&trigger.event().name);
+}
@@ -0,0 +1,112 @@
+//! Lua observer for interaction events.
+//!
+//! When an [`InteractEvent`] fires and the interactable names a `callback`,
+//! this observer calls that Lua function with `(ctx, input, interactor_id)`,
+//! where `ctx` describes the interactable entity.
+//!
+//! Interactables without a callback are silently skipped.
+//!
+//! # Lua callback signature
+//!
+//! ```lua
+//! function on_open_chest(ctx, input, interactor_id)
+//!     engine.inventory_add(interactor_id, "key")
+//!     engine.entity_despawn(ctx.id)
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::components::interactable::Interactable;
+use crate::components::luaphase::LuaPhase;
+use crate::events::audio::AudioCmd;
+use crate::events::interact::InteractEvent;
+use crate::resources::animationstore::AnimationStore;
+use crate::resources::input::InputState;
+use crate::resources::lua_runtime::{InputSnapshot, LuaPhaseSnapshot, LuaRuntime, PhaseCmd};
+use crate::resources::systemsstore::SystemsStore;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+use crate::systems::lua_commands::{
+    ContextQueries, EffectCmdBufs, EntityCmdQueries, build_entity_context, drain_phase_and_effects,
+};
+use log::error;
+
+/// Observer that calls an interactable's Lua callback when it is interacted with.
+#[allow(clippy::too_many_arguments)]
+pub fn lua_interact_observer(
+    trigger: On<InteractEvent>,
+    mut commands: Commands,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+    interactables: Query<&Interactable>,
+    ctx_queries: ContextQueries,
+    mut cmd_queries: EntityCmdQueries,
+    mut luaphase_query: Query<(Entity, &mut LuaPhase)>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+    mut audio_cmd_writer: MessageWriter<AudioCmd>,
+    systems_store: Res<SystemsStore>,
+    animation_store: Res<AnimationStore>,
+    mut phase_buf: Local<Vec<PhaseCmd>>,
+    mut effect_bufs: Local<EffectCmdBufs>,
+) {
+    let event = *trigger.event();
+
+    let Some(callback_name) = interactables
+        .get(event.entity)
+        .ok()
+        .and_then(|i| i.callback.clone())
+    else {
+        return;
+    };
+
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+
+    let input_snapshot = InputSnapshot::from_input_state(&input);
+    let input_table = match lua_runtime.update_input_table(&input_snapshot, time.frame_count) {
+        Ok(t) => t,
+        Err(e) => {
+            error!("Error creating input table for interact callback: {}", e);
+            return;
+        }
+    };
+
+    let lua_phase_snapshot = luaphase_query
+        .get(event.entity)
+        .ok()
+        .map(|(_, p)| LuaPhaseSnapshot::from(p));
+
+    let ctx_table = match build_entity_context(
+        &lua_runtime,
+        event.entity,
+        &ctx_queries,
+        &cmd_queries,
+        lua_phase_snapshot,
+        None,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            error!("Error building context for interact callback: {}", e);
+            return;
+        }
+    };
+
+    let interactor_id = event.interactor.to_bits();
+    lua_runtime.call_named(&callback_name, "on_interact", |func| {
+        func.call::<()>((ctx_table, input_table, interactor_id))
+    });
+
+    drain_phase_and_effects(
+        &lua_runtime,
+        &mut phase_buf,
+        &mut luaphase_query,
+        &mut effect_bufs,
+        &mut commands,
+        &mut world_signals,
+        &mut cmd_queries,
+        &mut audio_cmd_writer,
+        &systems_store,
+        &animation_store,
+    );
+}
@@ -0,0 +1,65 @@
+//! Lua observers for inventory events.
+//!
+//! Forward each [`InventoryChangedEvent`] and [`InventoryFullEvent`] to the
+//! functions registered with `engine.on_inventory_changed(fn)` and
+//! `engine.on_inventory_full(fn)`. Commands queued by the hooks are drained by
+//! the next `lua_plugin::update`.
+//!
+//! # Lua Hook Signatures
+//!
+//! ```lua
+//! engine.on_inventory_changed(function(entity_id, item, delta, count)
+//!     if item == "coin" then
+//!         engine.set_integer("coins", count)
+//!     end
+//! end)
+//!
+//! engine.on_inventory_full(function(entity_id, item, leftover)
+//!     engine.play_sound("bag_full")
+//! end)
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::inventory::{InventoryChangedEvent, InventoryFullEvent};
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua `on_inventory_changed` hook.
+pub fn lua_inventory_changed_observer(
+    trigger: On<InventoryChangedEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if !lua_runtime.has_hook("inventory_changed") {
+        return;
+    }
+    let event = trigger.event();
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_hook(
+        "inventory_changed",
+        (
+            event.entity.to_bits(),
+            &*event.item,
+            event.delta,
+            event.count,
+        ),
+    );
+}
+
+/// Observer that calls the Lua `on_inventory_full` hook.
+pub fn lua_inventory_full_observer(
+    trigger: On<InventoryFullEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if !lua_runtime.has_hook("inventory_full") {
+        return;
+    }
+    let event = trigger.event();
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_hook(
+        "inventory_full",
+        (event.entity.to_bits(), &*event.item, event.leftover),
+    );
+}
@@ -0,0 +1,108 @@
+//! Lua observer for landing events.
+//!
+//! When a [`LandedEvent`] fires and the entity has a [`LuaOnLand`]
+//! component, this observer calls the named Lua function with
+//! `(ctx, input, impact_speed)`.
+//!
+//! Entities without [`LuaOnLand`] are silently skipped.
+//!
+//! # Lua callback signature
+//!
+//! ```lua
+//! function on_player_land(ctx, input, impact_speed)
+//!     engine.play_sound("land")
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::components::lua_on_land::LuaOnLand;
+use crate::components::luaphase::LuaPhase;
+use crate::events::audio::AudioCmd;
+use crate::events::landing::LandedEvent;
+use crate::resources::animationstore::AnimationStore;
+use crate::resources::input::InputState;
+use crate::resources::lua_runtime::{InputSnapshot, LuaPhaseSnapshot, LuaRuntime, PhaseCmd};
+use crate::resources::systemsstore::SystemsStore;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+use crate::systems::lua_commands::{
+    ContextQueries, EffectCmdBufs, EntityCmdQueries, build_entity_context, drain_phase_and_effects,
+};
+use log::error;
+
+/// Observer that calls a Lua function when an entity lands.
+#[allow(clippy::too_many_arguments)]
+pub fn lua_land_observer(
+    trigger: On<LandedEvent>,
+    mut commands: Commands,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+    on_land_query: Query<&LuaOnLand>,
+    ctx_queries: ContextQueries,
+    mut cmd_queries: EntityCmdQueries,
+    mut luaphase_query: Query<(Entity, &mut LuaPhase)>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+    mut audio_cmd_writer: MessageWriter<AudioCmd>,
+    systems_store: Res<SystemsStore>,
+    animation_store: Res<AnimationStore>,
+    mut phase_buf: Local<Vec<PhaseCmd>>,
+    mut effect_bufs: Local<EffectCmdBufs>,
+) {
+    let event = *trigger.event();
+
+    // Only proceed if the entity opted in with LuaOnLand.
+    let callback_name = match on_land_query.get(event.entity) {
+        Ok(c) => c.callback.clone(),
+        Err(_) => return,
+    };
+
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+
+    let input_snapshot = InputSnapshot::from_input_state(&input);
+    let input_table = match lua_runtime.update_input_table(&input_snapshot, time.frame_count) {
+        Ok(t) => t,
+        Err(e) => {
+            error!("Error creating input table for on_land callback: {}", e);
+            return;
+        }
+    };
+
+    let lua_phase_snapshot = luaphase_query
+        .get(event.entity)
+        .ok()
+        .map(|(_, p)| LuaPhaseSnapshot::from(p));
+
+    let ctx_table = match build_entity_context(
+        &lua_runtime,
+        event.entity,
+        &ctx_queries,
+        &cmd_queries,
+        lua_phase_snapshot,
+        None,
+    ) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            error!("Error building context for on_land callback: {}", e);
+            return;
+        }
+    };
+
+    lua_runtime.call_named(&callback_name, "on_land", |func| {
+        func.call::<()>((ctx_table, input_table, event.impact_speed))
+    });
+
+    drain_phase_and_effects(
+        &lua_runtime,
+        &mut phase_buf,
+        &mut luaphase_query,
+        &mut effect_bufs,
+        &mut commands,
+        &mut world_signals,
+        &mut cmd_queries,
+        &mut audio_cmd_writer,
+        &systems_store,
+        &animation_store,
+    );
+}
@@ -0,0 +1,87 @@
+//! Lua music sync dispatch.
+//!
+//! - [`lua_beat_system`] forwards each [`BeatEvent`] to the function registered
+//!   with `engine.on_beat(fn)`.
+//! - [`lua_tracker_system`] forwards each [`TrackerEvent`] to the function
+//!   registered with `engine.on_tracker_row(fn)`.
+//!
+//! Both run before `lua_plugin::update`, so commands queued by the hooks are
+//! drained in the same frame.
+//!
+//! # Lua Hook Signatures
+//!
+//! ```lua
+//! engine.music_set_bpm("bgm", 128)
+//! engine.on_beat(function(id, beat, bar, beat_in_bar)
+//!     if beat_in_bar == 0 then
+//!         engine.play_sound("kick")
+//!     end
+//! end)
+//!
+//! engine.music_set_tracker_events("bgm", true)
+//! engine.on_tracker_row(function(id, order, pattern, row)
+//!     if row == 0 and order == 4 then
+//!         engine.set_flag("boss_section")
+//!     end
+//! end)
+//! ```
+
+use bevy_ecs::prelude::*;
+use mlua::IntoLuaMulti;
+
+use crate::events::audio::{BeatEvent, TrackerEvent};
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Call the `hook` Lua hook once per message, refreshing the signal cache
+/// before the first call. Drops the messages when no hook is registered so
+/// stale ones aren't replayed once a hook is set.
+fn dispatch_to_hook<M, A>(
+    hook: &str,
+    reader: &mut MessageReader<M>,
+    world_signals: &mut WorldSignals,
+    lua_runtime: &LuaRuntime,
+    args: impl Fn(&M) -> A,
+) where
+    M: Message,
+    A: IntoLuaMulti,
+{
+    if !lua_runtime.has_hook(hook) {
+        reader.clear();
+        return;
+    }
+    let mut cache_updated = false;
+    for msg in reader.read() {
+        if !cache_updated {
+            lua_runtime.update_signal_cache(world_signals.snapshot());
+            cache_updated = true;
+        }
+        lua_runtime.call_hook(hook, args(msg));
+    }
+}
+
+/// Call the Lua `on_beat` hook once per [`BeatEvent`] received this frame.
+pub fn lua_beat_system(
+    mut reader: MessageReader<BeatEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    dispatch_to_hook("beat", &mut reader, &mut world_signals, &lua_runtime, |e| {
+        (e.id.clone(), e.beat, e.bar, e.beat_in_bar)
+    });
+}
+
+/// Call the Lua `on_tracker_row` hook once per [`TrackerEvent`] received this frame.
+pub fn lua_tracker_system(
+    mut reader: MessageReader<TrackerEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    dispatch_to_hook(
+        "tracker_row",
+        &mut reader,
+        &mut world_signals,
+        &lua_runtime,
+        |e| (e.id.clone(), e.order, e.pattern, e.row),
+    );
+}
@@ -0,0 +1,38 @@
+//! Lua observer for automatic quality changes.
+//!
+//! Forwards each [`QualityChangedEvent`] to the function registered with
+//! `engine.on_quality_changed(fn)`. Commands queued by the hook are drained
+//! by the next `lua_plugin::update`.
+//!
+//! # Lua Hook Signature
+//!
+//! ```lua
+//! engine.on_quality_changed(function(level, old_level, frame_ms)
+//!     if level > old_level then
+//!         engine.set_flag("show_low_quality_notice")
+//!     end
+//! end)
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::quality::QualityChangedEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua `on_quality_changed` hook.
+pub fn lua_quality_observer(
+    trigger: On<QualityChangedEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if !lua_runtime.has_hook("quality_changed") {
+        return;
+    }
+    let event = *trigger.event();
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_hook(
+        "quality_changed",
+        (event.level, event.old_level, event.frame_time * 1000.0),
+    );
+}
@@ -0,0 +1,121 @@
+//! Lua script hot reload.
+//!
+//! [`lua_reload_system`] polls the [`ScriptWatcher`] and re-executes the
+//! scripts that changed on disk, then calls the function registered with
+//! `engine.on_reload(fn)` once per reloaded file. The world is left as it
+//! is: spawned entities keep their phase, timer and collision callback names
+//! and run the new functions from the next call on. Commands queued by the
+//! hook are drained by the `lua_plugin::update` that follows.
+//!
+//! # Lua Hook Signature
+//!
+//! ```lua
+//! engine.on_reload(function(path)
+//!     engine.log_info("reloaded " .. path)
+//!     enemies_left = engine.group_count("enemy")
+//! end)
+//! ```
+//!
+//! # Related
+//!
+//! - [`crate::resources::lua_runtime::ScriptWatcher`] – change polling and module reloading
+
+use bevy_ecs::prelude::*;
+use log::{error, info};
+
+use crate::resources::lua_runtime::{LuaRuntime, ScriptWatcher};
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Re-executes the Lua scripts changed since the last poll.
+pub fn lua_reload_system(
+    mut watcher: ResMut<ScriptWatcher>,
+    time: Res<WorldTime>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    let changed = watcher.tick(time.delta);
+    if changed.is_empty() {
+        return;
+    }
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+
+    let mut reloaded = Vec::new();
+    for path in changed {
+        let shown = path.to_string_lossy().into_owned();
+        let result = if watcher.is_main_script(&path) {
+            lua_runtime.run_script(&shown).map(|()| true)
+        } else if let Some(module) = watcher.module_name(&path) {
+            lua_runtime.reload_module(&module)
+        } else {
+            Ok(false)
+        };
+        match result {
+            Ok(true) => {
+                info!(target: "lua", "Reloaded {shown}");
+                reloaded.push(shown);
+            }
+            Ok(false) => {}
+            Err(e) => error!(target: "lua", "Failed to reload {shown}: {e}"),
+        }
+    }
+    if reloaded.is_empty() {
+        return;
+    }
+
+    // Callbacks are resolved by name; drop the handles to the old functions.
+    lua_runtime.clear_function_cache();
+    for path in reloaded {
+        lua_runtime.call_hook("reload", path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn changed_entry_script_is_rerun_and_the_hook_called() {
+        let dir = std::env::temp_dir().join("aberred_lua_reload_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let main = dir.join("main.lua");
+        std::fs::write(&main, "function on_update_level() return 1 end").unwrap();
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime.run_script(&main.to_string_lossy()).unwrap();
+        runtime
+            .lua()
+            .load("engine.on_reload(function(path) Reloaded = path end)")
+            .exec()
+            .unwrap();
+        // Resolve the callback once so the old handle is cached.
+        assert!(
+            runtime
+                .get_function_cached("on_update_level")
+                .unwrap()
+                .is_some()
+        );
+
+        let mut world = World::new();
+        world.insert_resource(ScriptWatcher::new(&dir, &main).with_interval(0.0));
+        world.insert_resource(WorldTime::default());
+        world.insert_resource(WorldSignals::default());
+        world.insert_non_send(runtime);
+
+        std::fs::write(&main, "function on_update_level() return 2 end -- edited").unwrap();
+        world.run_system_once(lua_reload_system).unwrap();
+
+        let runtime = world.non_send::<LuaRuntime>();
+        let callback = runtime
+            .get_function_cached("on_update_level")
+            .unwrap()
+            .unwrap();
+        assert_eq!(callback.call::<i32>(()).unwrap(), 2);
+        let reloaded: String = runtime.lua().globals().get("Reloaded").unwrap();
+        assert_eq!(reloaded, main.to_string_lossy());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
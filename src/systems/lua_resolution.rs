@@ -0,0 +1,37 @@
+//! Lua observer for internal resolution changes.
+//!
+//! Forwards each [`ResolutionChangedEvent`] to the function registered with
+//! `engine.on_resolution_changed(fn)`. Commands queued by the hook are
+//! drained by the next `lua_plugin::update`.
+//!
+//! # Lua Hook Signature
+//!
+//! ```lua
+//! engine.on_resolution_changed(function(width, height, old_width, old_height)
+//!     engine.entity_set_screen_position(hud_id, width - 100, 8)
+//! end)
+//! engine.set_internal_resolution(640, 360)
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::resolution::ResolutionChangedEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua `on_resolution_changed` hook.
+pub fn lua_resolution_observer(
+    trigger: On<ResolutionChangedEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if !lua_runtime.has_hook("resolution_changed") {
+        return;
+    }
+    let event = *trigger.event();
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_hook(
+        "resolution_changed",
+        (event.width, event.height, event.old_width, event.old_height),
+    );
+}
@@ -0,0 +1,35 @@
+//! Lua observer for results screen events.
+//!
+//! When a [`ResultsDoneEvent`] names a callback (the `on_done` of
+//! `engine.show_results`), this observer calls that global function with no
+//! arguments. Commands queued by the callback are drained by the next
+//! `lua_plugin::update`.
+//!
+//! # Lua Callback Signature
+//!
+//! ```lua
+//! engine.show_results{rows = {{label = "Score", signal = "score"}}, on_done = "results_done"}
+//!
+//! function results_done()
+//!     engine.change_scene("menu")
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::results::ResultsDoneEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the `on_done` function of a closed results screen.
+pub fn lua_results_observer(
+    trigger: On<ResultsDoneEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    let Some(callback) = &trigger.event().callback else {
+        return;
+    };
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_named(callback, "Results", |func| func.call::<()>(()));
+}
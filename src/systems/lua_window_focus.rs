@@ -0,0 +1,34 @@
+//! Lua observer for window focus changes.
+//!
+//! Forwards each [`WindowFocusEvent`] to the function registered with
+//! `engine.on_focus_changed(fn)`. Commands queued by the hook are drained by
+//! the next `lua_plugin::update`.
+//!
+//! # Lua Hook Signature
+//!
+//! ```lua
+//! engine.on_focus_changed(function(focused)
+//!     if not focused then
+//!         engine.set_flag("paused")
+//!     end
+//! end)
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::windowfocus::WindowFocusEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua `on_focus_changed` hook.
+pub fn lua_window_focus_observer(
+    trigger: On<WindowFocusEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    if !lua_runtime.has_hook("focus_changed") {
+        return;
+    }
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_hook("focus_changed", trigger.event().focused);
+}
@@ -0,0 +1,36 @@
+//! Lua observer for world clock events.
+//!
+//! Calls the global function named by each [`WorldClockEvent`], as scheduled
+//! with `engine.at_time(time, callback)`, passing the day and the `"HH:MM"`
+//! time. Commands queued by the callback are drained by the next
+//! `lua_plugin::update`.
+//!
+//! # Lua Callback Signature
+//!
+//! ```lua
+//! engine.at_time("20:00", "spawn_night_enemies")
+//!
+//! function spawn_night_enemies(day, time)
+//!     engine.log_info("Night " .. day .. " falls at " .. time)
+//! end
+//! ```
+
+use bevy_ecs::prelude::*;
+
+use crate::events::worldclock::WorldClockEvent;
+use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::worldclock::format_clock_time;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Observer that calls the Lua function scheduled with `engine.at_time`.
+pub fn lua_world_clock_observer(
+    trigger: On<WorldClockEvent>,
+    mut world_signals: ResMut<WorldSignals>,
+    lua_runtime: NonSend<LuaRuntime>,
+) {
+    let event = trigger.event();
+    lua_runtime.update_signal_cache(world_signals.snapshot());
+    lua_runtime.call_named(&event.name, "Clock", |func| {
+        func.call::<()>((event.day, format_clock_time(event.minute)))
+    });
+}
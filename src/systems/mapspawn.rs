@@ -9,8 +9,6 @@
 //! fine-grained control (e.g. editor preview systems that already hold the
 //! required system params).
 
-use std::sync::Arc;
-
 use bevy_ecs::prelude::*;
 use raylib::ffi;
 use raylib::ffi::TextureFilter::TEXTURE_FILTER_ANISOTROPIC_8X;
@@ -23,18 +21,23 @@ use crate::components::group::Group;
 #[cfg(feature = "lua")]
 use crate::components::luasetup::LuaSetup;
 use crate::components::mapposition::MapPosition;
+use crate::components::pathfollow::PathFollow;
 use crate::components::particleemitter::{EmitterShape, ParticleEmitter, TtlSpec};
+use crate::components::portal::SpawnPoint;
 use crate::components::rotation::Rotation;
 use crate::components::scale::Scale;
 use crate::components::sprite::Sprite;
 use crate::components::tilemap::TileMap;
 use crate::components::tint::Tint;
+use crate::components::tween::{Easing, LoopMode};
 use crate::components::zindex::ZIndex;
 use crate::events::spawnmap::SpawnMapRequested;
 use crate::resources::animationstore::{AnimationResource, AnimationStore};
+#[cfg(feature = "lua")]
+use crate::resources::autotile::AutoTileStore;
 use crate::resources::fontstore::FontStore;
 #[cfg(feature = "lua")]
-use crate::resources::lua_runtime::{LuaRuntime, MapLuaCmd};
+use crate::resources::lua_runtime::MapLuaCmd;
 #[cfg(feature = "lua")]
 use crate::resources::mapdata::load_map;
 use crate::resources::mapdata::{
@@ -91,7 +94,7 @@ pub fn spawn_map(
 
     for entry in &map.animations {
         let anim = AnimationResource {
-            tex_key: Arc::from(entry.texture_key.as_str()),
+            tex_key: entry.texture_key.as_str().into(),
             position: Vector2 {
                 x: entry.position[0],
                 y: entry.position[1],
@@ -136,7 +139,7 @@ fn spawn_entity(commands: &mut Commands, def: &EntityDef) -> Entity {
     }
     if let Some(ref s) = def.sprite {
         ec.insert(Sprite {
-            tex_key: Arc::from(s.texture_key.as_str()),
+            tex_key: s.texture_key.as_str().into(),
             width: s.width,
             height: s.height,
             offset: Vector2 {
@@ -212,6 +215,23 @@ fn spawn_entity(commands: &mut Commands, def: &EntityDef) -> Entity {
     if let Some([r, g, b, a]) = def.tint {
         ec.insert(Tint::new(r, g, b, a));
     }
+    if let Some(ref name) = def.spawn_point {
+        ec.insert(SpawnPoint::new(name.as_str()));
+    }
+    if let Some(ref path) = def.path_follow {
+        let points = path.points.iter().map(|&[x, y]| Vector2::new(x, y)).collect();
+        let loop_mode = path.loop_mode.as_deref().unwrap_or("once");
+        let easing = path.easing.as_deref().unwrap_or("linear");
+        ec.insert(
+            PathFollow::new(points, path.speed)
+                .with_spline(path.spline)
+                .with_closed(path.closed)
+                .with_loop_mode(loop_mode.parse().unwrap_or(LoopMode::Once))
+                .with_easing(easing.parse().unwrap_or(Easing::Linear))
+                .with_orient(path.orient)
+                .with_rotation_offset(path.rotation_offset.unwrap_or(0.0)),
+        );
+    }
     #[cfg(feature = "lua")]
     if let Some(ref callback) = def.lua_setup {
         ec.insert(LuaSetup::new(callback.clone()));
@@ -320,25 +340,32 @@ pub fn spawn_map_observer(
     );
 }
 
-/// Drains `engine.load_map()` commands queued by Lua and fires
+/// Applies `engine.load_map()` commands queued by Lua by firing
 /// [`SpawnMapRequested`] for each, letting [`spawn_map_observer`] handle the
 /// Raylib-dependent asset loading and entity spawning.
 ///
-/// Registered by [`crate::engine_app::EngineBuilder::with_lua`] and runs
-/// every frame during the Playing state, after `lua_plugin::update`.
+/// `engine.tilemap_set_autotile()` commands update the [`AutoTileStore`].
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
 #[cfg(feature = "lua")]
 pub fn process_lua_map_commands(
+    InMut(buf): InMut<Vec<MapLuaCmd>>,
     mut commands: Commands,
-    lua: NonSend<LuaRuntime>,
-    mut buf: Local<Vec<MapLuaCmd>>,
+    mut autotiles: ResMut<AutoTileStore>,
 ) {
-    lua.drain_map_commands_into(&mut buf);
     for cmd in buf.drain(..) {
         match cmd {
             MapLuaCmd::LoadMap { path } => match load_map(&path) {
                 Ok(map) => commands.trigger(SpawnMapRequested { map }),
                 Err(e) => log::error!("engine.load_map: failed to read '{path}': {e}"),
             },
+            MapLuaCmd::SetAutoTile { tileset, rules } => match rules {
+                Some(rules) => autotiles.insert(tileset, rules),
+                None => {
+                    autotiles.remove(&tileset);
+                }
+            },
         }
     }
 }
@@ -5,15 +5,15 @@
 //! - [`menu_despawn`] – despawns menu entities and their items
 //! - [`menu_controller_observer`] – handles input to navigate and select items
 //! - [`menu_selection_observer`] – performs actions when items are selected
+//! - [`menu_high_contrast_system`] – recolors menu text when the high-contrast UI flag changes
 //!
 //! Callbacks receive `&mut `[`GameCtx`](crate::systems::GameCtx) for full ECS access.
 
-use std::sync::Arc;
-
 use crate::components::dynamictext::DynamicText;
 use crate::components::group::Group;
 use crate::components::mapposition::MapPosition;
 use crate::components::menu::{Menu, MenuAction, MenuActions};
+use crate::components::playerindex::PlayerIndex;
 use crate::components::screenposition::ScreenPosition;
 use crate::components::signals::Signals;
 use crate::components::sprite::Sprite;
@@ -22,10 +22,12 @@ use crate::events::audio::AudioCmd;
 use crate::events::input::{InputAction, InputEvent};
 use crate::events::menu::MenuSelectionEvent;
 use crate::resources::fontstore::FontStore;
+use crate::resources::gameconfig::GameConfig;
 use crate::resources::gamestate::GameStates::Quitting;
 use crate::resources::gamestate::NextGameState;
 #[cfg(feature = "lua")]
 use crate::resources::lua_runtime::LuaRuntime;
+use crate::resources::playerinput::PlayerInputs;
 use crate::resources::signal_keys as sk;
 use crate::resources::systemsstore::SystemsStore;
 use crate::resources::texturefilter::TextureFilter;
@@ -84,13 +86,14 @@ pub fn menu_spawn_system(
     mut texture_store: ResMut<TextureStore>,
     mut rl: NonSendMut<raylib::RaylibHandle>,
     th: NonSend<raylib::RaylibThread>,
+    config: Option<Res<GameConfig>>,
 ) {
+    let high_contrast = config.is_some_and(|c| c.high_contrast_ui);
     for (entity, mut menu) in query.iter_mut() {
         // Cache immutable data before mutable iteration to satisfy borrow rules
         let font_string = menu.font.clone();
         let font_size = menu.font_size;
-        let normal_color = menu.normal_color;
-        let selected_color = menu.selected_color;
+        let (normal_color, selected_color) = menu.item_colors(high_contrast);
         let selected_index = menu.selected_index;
         let use_screen_space = menu.use_screen_space;
         let origin = menu.origin;
@@ -134,7 +137,7 @@ pub fn menu_spawn_system(
                 );
             } else {
                 // Static text sprite
-                let Some(font_handle) = font_store.get(&font_string) else {
+                let Some(font_handle) = font_store.get_or_fallback(&font_string) else {
                     warn!(
                         "menu_spawn_system: skipping menu item '{}' because font '{}' is missing",
                         menu_item.id, font_string
@@ -161,7 +164,7 @@ pub fn menu_spawn_system(
                 let key = format!("menu_{}", menu_item.id);
                 texture_store.insert(&key, texture_handle, TextureFilter::Nearest, None);
                 ecmd.insert(Sprite {
-                    tex_key: Arc::from(key),
+                    tex_key: key.into(),
                     width,
                     height,
                     offset: Vector2 { x: 0.0, y: 0.0 },
@@ -341,14 +344,20 @@ pub fn menu_despawn(
 ///
 /// When `visible_count` is set, navigation is bounded (no wrap-around) and
 /// scrolling occurs when selection moves outside the visible window.
+///
+/// A menu with a [`PlayerIndex`] only reacts to that player's input (see
+/// [`PlayerInputs::event_reaches`]).
 pub fn menu_controller_observer(
     trigger: On<InputEvent>,
-    mut query: Query<(Entity, &mut Menu, &mut Signals)>,
+    mut query: Query<(Entity, &mut Menu, &mut Signals, Option<&PlayerIndex>)>,
     mut dynamic_text_query: Query<&mut DynamicText>,
     mut commands: Commands,
     mut audio_cmds: MessageWriter<AudioCmd>,
+    config: Option<Res<GameConfig>>,
+    players: Option<Res<PlayerInputs>>,
 ) {
-    for (entity, mut menu, mut signals) in query.iter_mut() {
+    let high_contrast = config.is_some_and(|c| c.high_contrast_ui);
+    for (entity, mut menu, mut signals, owner) in query.iter_mut() {
         debug!(
             "menu_controller_observer: Handling input for menu entity {:?}",
             entity
@@ -365,6 +374,14 @@ pub fn menu_controller_observer(
             debug!("menu_controller_observer: Input event is a release, skipping");
             continue; // Only handle key press, not release
         }
+        let owner = owner.map(|p| p.0);
+        let reaches = match &players {
+            Some(players) => players.event_reaches(owner, event.player),
+            None => event.player.is_none(),
+        };
+        if !reaches {
+            continue;
+        }
 
         let mut changed_selection = false;
         let mut needs_reposition = false;
@@ -435,17 +452,18 @@ pub fn menu_controller_observer(
         // Update cursor position and colors if applicable
         if changed_selection {
             // Update colors for old and new selected items (only for DynamicText)
+            let (normal_color, selected_color) = menu.item_colors(high_contrast);
             if let Some(old_item) = menu.items.get(old_selected_index)
                 && let Some(entity) = old_item.entity
                 && let Ok(mut text) = dynamic_text_query.get_mut(entity)
             {
-                text.color = menu.normal_color;
+                text.color = normal_color;
             }
             if let Some(new_item) = menu.items.get(menu.selected_index)
                 && let Some(entity) = new_item.entity
                 && let Ok(mut text) = dynamic_text_query.get_mut(entity)
             {
-                text.color = menu.selected_color;
+                text.color = selected_color;
             }
 
             if let Some(cursor_entity) = menu.cursor_entity {
@@ -528,6 +546,47 @@ fn reposition_menu_items(commands: &mut Commands, menu: &Menu) {
     }
 }
 
+/// Recolors the [`DynamicText`] items and "..." indicators of every menu
+/// when [`GameConfig::high_contrast_ui`] changes.
+///
+/// Menus spawned or navigated afterwards pick the colors up on their own
+/// through [`Menu::item_colors`].
+pub fn menu_high_contrast_system(
+    config: Option<Res<GameConfig>>,
+    menus: Query<&Menu>,
+    mut dynamic_text_query: Query<&mut DynamicText>,
+    mut applied: Local<bool>,
+) {
+    let high_contrast = config.is_some_and(|c| c.high_contrast_ui);
+    if high_contrast == *applied {
+        return;
+    }
+    *applied = high_contrast;
+
+    for menu in menus.iter() {
+        let (normal_color, selected_color) = menu.item_colors(high_contrast);
+        for (i, item) in menu.items.iter().enumerate() {
+            if let Some(entity) = item.entity
+                && let Ok(mut text) = dynamic_text_query.get_mut(entity)
+            {
+                text.color = if i == menu.selected_index {
+                    selected_color
+                } else {
+                    normal_color
+                };
+            }
+        }
+        for indicator in [menu.top_indicator_entity, menu.bottom_indicator_entity]
+            .into_iter()
+            .flatten()
+        {
+            if let Ok(mut text) = dynamic_text_query.get_mut(indicator) {
+                text.color = normal_color;
+            }
+        }
+    }
+}
+
 /// Executes the action associated with a selected menu item.
 ///
 /// Priority chain: Lua callback → Rust callback → [`MenuActions`].
@@ -0,0 +1,68 @@
+//! Missing-asset warnings.
+//!
+//! [`missing_asset_warning_system`] runs after rendering, drains the keys the
+//! [`TextureStore`] and [`FontStore`] substituted with their fallbacks this
+//! frame and logs them as a single throttled warning (see
+//! [`missingassets`](crate::resources::missingassets)).
+
+use bevy_ecs::prelude::*;
+use log::warn;
+
+use crate::resources::fontstore::FontStore;
+use crate::resources::missingassets::{AssetKind, MissingAssetWarnings};
+use crate::resources::texturestore::TextureStore;
+use crate::resources::worldtime::WorldTime;
+
+/// Log this frame's fallback substitutions, at most one line per frame.
+pub fn missing_asset_warning_system(
+    textures: Res<TextureStore>,
+    fonts: NonSend<FontStore>,
+    world_time: Res<WorldTime>,
+    mut warnings: ResMut<MissingAssetWarnings>,
+) {
+    let misses = textures
+        .take_misses()
+        .into_iter()
+        .map(|key| (AssetKind::Texture, key))
+        .chain(
+            fonts
+                .take_misses()
+                .into_iter()
+                .map(|key| (AssetKind::Font, key)),
+        );
+    if let Some(line) = warnings.summarize(misses, world_time.frame_count) {
+        warn!("{line}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn drains_store_misses_each_frame() {
+        let mut world = World::new();
+        world.insert_resource(TextureStore::new());
+        world.insert_non_send(FontStore::new());
+        world.insert_resource(WorldTime::default());
+        world.insert_resource(MissingAssetWarnings::default());
+
+        assert!(
+            world
+                .resource::<TextureStore>()
+                .get_or_fallback("hero")
+                .is_none()
+        );
+        world
+            .run_system_once(missing_asset_warning_system)
+            .expect("system should run");
+
+        assert!(world.resource::<TextureStore>().take_misses().is_empty());
+        assert!(!world.resource_mut::<MissingAssetWarnings>().should_warn(
+            AssetKind::Texture,
+            "hero",
+            1
+        ));
+    }
+}
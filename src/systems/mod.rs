@@ -5,10 +5,26 @@
 //!
 //! Submodules overview
 //! - [`animation`] – advance sprite animations and select tracks via rules
+//! - [`asset_usage`] – sample asset references and mirror audio loads for usage reports
+//! - [`attractmode`] – start the idle demo, feed it the replay and return to the menu on input
+//! - [`attractor`] – accelerate labeled rigid bodies toward attractors in range
+//! - [`blackboard`] – expire timed entries in AI `Blackboard` components
+//! - [`camera_effects`] – advance camera shake, zoom and pan effects
 //! - [`camera_follow`] – move the camera to track entities with `CameraTarget`
+//! - [`cinematic`] – slide the cutscene letterbox bars and clear gameplay input while active
 //! - [`audio`] – bridge with the audio thread (poll/update message queues)
+//! - [`audiotriggers`] – play `AudioTriggers` sounds on spawn, despawn and collision
 //! - [`collision_detector`] – broad/simple overlap checks and event emission
+//! - [`colorgrading`] – advance the color grading LUT crossfade
+//! - [`cooldown`] – count `Cooldown`s down and publish their remaining fraction
 //! - [`lua_collision`] – *(feature = "lua")* Lua-based collision observer and callback dispatch
+//! - [`credits`] – spawn, scroll and close credits rolls
+//! - [`dirtyrects`] – collect the screen regions to repaint in dirty-rectangle render mode
+//! - [`ecs_stats`] – collect entity, archetype and component memory statistics
+//! - [`fogofwar`] – rebuild solid fog cells from tilemaps and reveal cells around vision sources
+//! - [`forcearea`] – push rigid bodies inside enabled force areas
+//! - [`formation`] – move formation members toward their slots around the leader
+//! - [`frame_arena`] – free the frame's temporary buffers and report the arena counters
 //! - [`gamestate`] – check for pending state transitions and trigger events
 //! - [`gridlayout`] – spawn entities from JSON-defined grid layouts
 //! - [`group`] – count entities per tracked group and publish to [`WorldSignals`](crate::resources::worldsignals::WorldSignals)
@@ -17,23 +33,72 @@
 //! - [`gui_layout`] – resolve GUI children's `ScreenPosition` from parent `ScreenPosition` + `GuiOffset`
 //! - [`gui_progressbar_signal_update`] – keep `GuiProgressBar.value` in sync with `WorldSignals` for signal-bound bars
 //! - [`gui_spawn`] – spawn a `GuiButton`/`GuiLabel`/`GuiImage`'s `GuiInteractable`/caption/`Sprite` on `Added<T>`
+//! - [`height`] – apply faux-3D height gravity, land entities and trigger `LandedEvent`s
+//! - [`hitbox`] – match active hitboxes against hurtboxes and trigger `HitEvent`s
 //! - [`input`] – read hardware input and update [`crate::resources::input::InputState`]
+//! - [`inputcontext`] – clear the context stack on scene change and release actions the active context disallows
+//! - [`inputsequence`] – match the frame's presses against named chord/sequence patterns
+//! - [`inputstats`] – add each frame of player input to the opt-in per-scene analytics
 //! - [`inputsimplecontroller`] – translate input state into velocity on entities
 //! - [`inputaccelerationcontroller`] – translate input state into acceleration on entities
+//! - [`interaction`] – focus the `Interactable` closest to each interactor and trigger `InteractEvent`s
+//! - [`inventory`] – add/remove items on `Inventory` components and trigger the inventory events
+//! - [`joint`] – solve distance and pin joints after movement and fix up the bodies' velocities
 //! - [`lua_commands`] – *(feature = "lua")* shared command processing for Lua-Rust communication
+//! - [`lua_credits`] – *(feature = "lua")* call the `on_done` function of a finished credits roll
+//! - [`lua_highscore`] – *(feature = "lua")* call the `on_done` function of a submitted score
+//! - [`lua_inputsequence`] – *(feature = "lua")* call the `engine.on_sequence` function of a completed pattern
+//! - [`lua_interact`] – *(feature = "lua")* call the Lua callback of an `Interactable` on interaction
+//! - [`lua_inventory`] – *(feature = "lua")* forward inventory changes and overflows to the Lua hooks
+//! - [`lua_land`] – *(feature = "lua")* call the Lua callback of an entity landing
+//! - [`lua_music_sync`] – *(feature = "lua")* dispatch music beat and tracker row events to Lua hooks
+//! - [`lua_quality`] – *(feature = "lua")* forward automatic quality changes to the Lua hook
+//! - [`lua_reload`] – *(feature = "lua")* hot-reload changed Lua scripts and call the `engine.on_reload` hook
+//! - [`lua_resolution`] – *(feature = "lua")* forward internal resolution changes to the Lua hook
+//! - [`lua_results`] – *(feature = "lua")* call the `on_done` function of a closed results screen
+//! - [`lua_window_focus`] – *(feature = "lua")* forward window focus changes to the Lua hook
+//! - [`lua_world_clock`] – *(feature = "lua")* call the Lua functions scheduled with `engine.at_time`
 //! - [`menu`] – menu spawning, input handling, and selection
+//! - [`missing_assets`] – throttled warnings for asset keys drawn with fallbacks
 //! - [`mousecontroller`] – update entity positions based on mouse position
 //! - [`movement`] – integrate positions from rigid body velocities and time
+//! - [`nameentry`] – the `SubmitScore` command and three-letter high score name entry
+//! - [`ownedby`] – despawn entities whose owner is gone
 //! - [`lua_setup_entity`] – *(feature = "lua")* one-shot entity setup callback on `Added<LuaSetup>`
 //! - [`luaphase`] – *(feature = "lua")* process Lua phase state machine transitions and callbacks
+//! - [`pathfollow`] – move entities along their waypoint paths and orient them
 //! - [`phase`] – process Rust phase state machine transitions and callbacks
+//! - [`playerinput`] – poll split keyboard halves and gamepads into per-player input
+//! - [`portal`] – portal fade transitions, scene-switch arrivals and the spawn point registry
+//! - [`progress`] – copy bound signals into `ProgressBar`/`RadialProgress` values
+//! - [`qualitygovernor`] – step quality down or up from the frame time
 //! - [`rust_collision`] – Rust-native collision observer and callback dispatch
 //! - [`scene_dispatch`] – scene switch and update systems for `SceneManager`-based games
 //! - [`render`] – draw world and debug overlays using Raylib
+//! - [`rope`] – step rope point chains and pull the bodies hanging from them
+//! - [`results`] – open, count up and close end-of-level results screens
+//! - [`savegame`] – save and load commands for world signals and persistent entities
+//! - [`scenemusic`] – start the scene's playlist, crossfade and move to the next track
+//! - [`scenestate`] – restore `RestoreOnReturn` entities from `SceneState` on scene re-entry
+//! - [`sensor`] – cast sensor rays and write hit flags into Signals
 //! - [`signalbinding`] – update DynamicText components based on signal values
+//! - [`spatialindex`] – rebuild the grid index of labeled entity positions
+//! - [`spawnarea`] – spread spawn positions over rect and circle areas (uniform, poisson, grid)
 //! - [`stuckto`] – keep entities attached to other entities
+//! - [`terrain`] – set up destructible terrain bitmaps, carve them and upload the changes
+//! - [`textscroller`] – scroll text scrollers and lay out their pooled glyph entities
+//! - [`tiled`] – import Tiled JSON (`.tmj`) maps, their object layers and properties
 //! - [`time`] – update simulation time and delta
+//! - [`tracker_clock`] – derive pattern/row/order positions of playing tracker modules
 //! - [`tween`] – animate position, rotation, and scale over time
+//! - [`visibility`] – rebuild sight-blocking segments and cast `VisibilityPolygon`s
+//! - [`water`] – step water surfaces, splash entering bodies and float submerged ones
+//! - [`weather`] – drive the rain/snow emitters and the global wind area from `Weather`
+//! - [`windowfocus`] – trigger `WindowFocusEvent` and throttle the frame rate while unfocused
+//! - [`world_snapshot`] – take named world snapshots and log what changed since
+//! - [`worldanchorui`] – project world-anchored UI onto the screen each frame
+//! - [`worldclock`] – advance the in-game clock and trigger its scheduled events
+//! - [`worlds`] – step secondary worlds and apply Lua `engine.world_*` commands
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
@@ -48,11 +113,27 @@ pub struct RaylibAccess<'w> {
 }
 
 pub mod animation;
+pub mod asset_usage;
+pub mod attractmode;
+pub mod attractor;
 pub mod audio;
+pub mod audiotriggers;
+pub mod blackboard;
+pub mod camera_effects;
 pub mod camera_follow;
+pub mod cinematic;
 pub mod collision;
 pub mod collision_detector;
+pub mod colorgrading;
+pub mod cooldown;
+pub mod credits;
+pub mod dirtyrects;
 pub mod dynamictext_size;
+pub mod ecs_stats;
+pub mod fogofwar;
+pub mod forcearea;
+pub mod formation;
+pub mod frame_arena;
 pub mod game_ctx;
 pub mod gameconfig;
 pub mod gamestate;
@@ -64,9 +145,17 @@ pub mod gui_image_state_sync;
 pub mod gui_layout;
 pub mod gui_progressbar_signal_update;
 pub mod gui_spawn;
+pub mod height;
+pub mod hitbox;
 pub mod input;
 pub mod inputaccelerationcontroller;
+pub mod inputcontext;
+pub mod inputsequence;
+pub mod inputstats;
 pub mod inputsimplecontroller;
+pub mod interaction;
+pub mod inventory;
+pub mod joint;
 #[cfg(feature = "lua")]
 pub mod lua_animation_finished;
 #[cfg(feature = "lua")]
@@ -74,30 +163,86 @@ pub mod lua_collision;
 #[cfg(feature = "lua")]
 pub mod lua_commands;
 #[cfg(feature = "lua")]
+pub mod lua_credits;
+#[cfg(feature = "lua")]
+pub mod lua_hit;
+#[cfg(feature = "lua")]
+pub mod lua_highscore;
+#[cfg(feature = "lua")]
+pub mod lua_inputsequence;
+#[cfg(feature = "lua")]
+pub mod lua_interact;
+#[cfg(feature = "lua")]
+pub mod lua_inventory;
+#[cfg(feature = "lua")]
+pub mod lua_land;
+#[cfg(feature = "lua")]
+pub mod lua_music_sync;
+#[cfg(feature = "lua")]
+pub mod lua_quality;
+#[cfg(feature = "lua")]
+pub mod lua_reload;
+#[cfg(feature = "lua")]
+pub mod lua_resolution;
+#[cfg(feature = "lua")]
+pub mod lua_results;
+#[cfg(feature = "lua")]
 pub mod lua_setup_entity;
 #[cfg(feature = "lua")]
 pub mod lua_tween_finished;
 #[cfg(feature = "lua")]
+pub mod lua_window_focus;
+#[cfg(feature = "lua")]
+pub mod lua_world_clock;
+#[cfg(feature = "lua")]
 pub mod luaphase;
 #[cfg(feature = "lua")]
 pub mod luatimer;
 pub mod mapspawn;
 pub mod menu;
+pub mod missing_assets;
 pub mod mousecontroller;
 pub mod movement;
+pub mod nameentry;
+pub mod ownedby;
 pub mod particleemitter;
+pub mod pathfollow;
 pub mod phase;
 mod phase_core;
+pub mod playerinput;
+pub mod portal;
+pub mod progress;
 pub mod propagate_transforms;
+pub mod qualitygovernor;
 pub mod render;
+pub mod results;
+pub mod rope;
 pub mod rust_collision;
+pub mod savegame;
 pub mod scene_dispatch;
+pub mod scenemusic;
+pub mod scenestate;
+pub mod sensor;
 pub mod signalbinding;
+pub mod spatialindex;
+pub mod spawnarea;
 pub mod stuckto;
+pub mod terrain;
+pub mod textscroller;
+pub mod tiled;
 pub mod tilemap;
 pub mod time;
 pub mod timer;
 mod timer_core;
+pub mod tracker_clock;
 pub mod transform_compose;
 pub mod ttl;
 pub mod tween;
+pub mod visibility;
+pub mod water;
+pub mod weather;
+pub mod windowfocus;
+pub mod world_snapshot;
+pub mod worldanchorui;
+pub mod worldclock;
+pub mod worlds;
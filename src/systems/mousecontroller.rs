@@ -7,41 +7,57 @@
 
 use crate::components::inputcontrolled::MouseControlled;
 use crate::components::mapposition::MapPosition;
-use crate::resources::camera2d::Camera2DRes;
-use crate::resources::screensize::ScreenSize;
-use crate::resources::windowsize::WindowSize;
+use crate::resources::input::InputState;
 use bevy_ecs::prelude::*;
 
 /// Update each mouse-controlled entity's `MapPosition` position based on mouse's world position.
 ///
-/// The mouse position is transformed from window space → game space → world space
-/// to correctly handle letterboxing/pillarboxing when the window is resized.
+/// Reads the world-space mouse position that
+/// [`update_input_state`](crate::systems::input::update_input_state) already
+/// corrected for letterboxing and projected through the camera, so this system
+/// needs no raylib handle and can run on any thread.
 pub fn mouse_controller(
     mut query: Query<(&MouseControlled, &mut MapPosition)>,
-    camera_res: Res<Camera2DRes>,
-    window_size: Res<WindowSize>,
-    screen_size: Res<ScreenSize>,
-    rl: NonSend<raylib::RaylibHandle>,
+    input: Res<InputState>,
 ) {
-    // Get mouse position in window coordinates
-    let window_mouse_pos = rl.get_mouse_position();
-
-    // Transform from window space to game/render-target space (accounting for letterboxing)
-    let game_mouse_pos = window_size.window_to_game_pos(
-        window_mouse_pos,
-        screen_size.w as u32,
-        screen_size.h as u32,
-    );
-
-    // Transform from game/screen space to world space using the camera
-    let world_position = rl.get_screen_to_world2D(game_mouse_pos, camera_res.0);
-
     for (mouse_controlled, mut map_position) in query.iter_mut() {
         if mouse_controlled.follow_x {
-            map_position.pos.x = world_position.x;
+            map_position.pos.x = input.mouse_world_x;
         }
         if mouse_controlled.follow_y {
-            map_position.pos.y = world_position.y;
+            map_position.pos.y = input.mouse_world_y;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn follows_the_world_mouse_position_on_enabled_axes() {
+        let mut world = World::new();
+        world.insert_resource(InputState {
+            mouse_world_x: 120.0,
+            mouse_world_y: 48.0,
+            ..InputState::default()
+        });
+        let entity = world
+            .spawn((
+                MouseControlled {
+                    follow_x: true,
+                    follow_y: false,
+                },
+                MapPosition::new(0.0, 5.0),
+            ))
+            .id();
+
+        world
+            .run_system_once(mouse_controller)
+            .expect("system should run");
+
+        let pos = world.get::<MapPosition>(entity).unwrap().pos;
+        assert_eq!((pos.x, pos.y), (120.0, 5.0));
+    }
+}
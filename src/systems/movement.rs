@@ -6,41 +6,108 @@
 //!
 //! Entities with `frozen = true` are skipped entirely, allowing external systems
 //! to control their position directly.
+//!
+//! Bodies with a [`BoxCollider`] that would travel further than their collider
+//! in one frame are swept in sub-steps (see [`MovementConfig`]) and stop at the
+//! first collider they newly overlap, so thin walls and bricks cannot be
+//! skipped at high speed. Only colliders that a [`CollisionRule`] or
+//! `LuaCollisionRule` pairs with the body stop it; triggers and decorations
+//! without a rule are passed through. The number of sub-steps taken each
+//! frame is reported on the `movement_substeps` Tracy plot.
 
 use bevy_ecs::prelude::*;
-use raylib::prelude::Vector2;
+use raylib::prelude::{Rectangle, Vector2};
+use smallvec::SmallVec;
 
+use crate::components::boxcollider::{BoxCollider, ColliderDisabled};
+use crate::components::collision::CollisionRule;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+#[cfg(feature = "lua")]
+use crate::components::luacollision::LuaCollisionRule;
 use crate::components::mapposition::MapPosition;
 use crate::components::rigidbody::RigidBody;
 use crate::components::signals::Signals;
+use crate::components::tags::{Tags, entity_labels};
 use crate::events::audio::AudioCmd;
+use crate::resources::movementconfig::MovementConfig;
 use crate::resources::screensize::ScreenSize;
 use crate::resources::signal_keys as sk;
 use crate::resources::worldtime::WorldTime;
 
+/// Bodies integrated by [`movement`].
+type Bodies<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut MapPosition,
+        &'static mut RigidBody,
+        Option<&'static mut Signals>,
+        Option<&'static BoxCollider>,
+        Option<&'static GlobalTransform2D>,
+        Has<ColliderDisabled>,
+    ),
+>;
+
+/// Colliders that sub-stepped bodies are swept against.
+type Obstacles<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static MapPosition,
+        &'static BoxCollider,
+        Option<&'static GlobalTransform2D>,
+        Option<&'static Group>,
+        Option<&'static Tags>,
+    ),
+    Without<ColliderDisabled>,
+>;
+
+/// A body whose motion this frame is split into sub-steps.
+pub struct Sweep {
+    entity: Entity,
+    /// World-space collider rectangle before moving.
+    rect: Rectangle,
+    displacement: Vector2,
+    substeps: u32,
+    /// Fraction of `displacement` to apply, filled in by the sweep.
+    fraction: f32,
+}
+
 /// Apply acceleration forces and velocity to `MapPosition` using the frame's delta time.
 ///
 /// This system performs physics integration in the following order:
 /// 1. Skip if entity is frozen
 /// 2. Sum all enabled acceleration forces
-/// 3. Integrate acceleration into velocity: `velocity += total_acceleration * delta`
+/// 3. Integrate acceleration into velocity:
+///    `velocity += total_acceleration / mass * delta`
 /// 4. Apply friction damping: `velocity *= (1 - friction * delta)`
 /// 5. Clamp velocity to max_speed if configured
-/// 6. Integrate velocity into position: `position += velocity * delta`
+/// 6. Integrate velocity into position: `position += velocity * delta`, in
+///    sub-steps for fast bodies with a collider (see [`MovementConfig`]),
+///    stopping at colliders a collision rule pairs them with
 /// 7. Update movement signals for animation/audio systems
 pub fn movement(
-    mut query: Query<(
-        Entity,
-        &mut MapPosition,
-        &mut RigidBody,
-        Option<&mut Signals>,
-    )>,
+    mut bodies: ParamSet<(Bodies, Obstacles)>,
     time: Res<WorldTime>,
+    config: Option<Res<MovementConfig>>,
     _screensize: Res<ScreenSize>,
     mut _audio_cmd_writer: MessageWriter<AudioCmd>,
+    rules: Query<&CollisionRule>,
+    #[cfg(feature = "lua")] lua_rules: Query<&LuaCollisionRule>,
+    mut sweeps: Local<Vec<Sweep>>,
+    mut obstacles: Local<Vec<(Entity, Rectangle)>>,
 ) {
     crate::tracy::tracy_span!("movement");
-    for (_entity, mut position, mut rigidbody, mut maybe_signals) in query.iter_mut() {
+    let config = config.map_or_else(MovementConfig::default, |c| *c);
+    let delta = time.delta;
+    sweeps.clear();
+
+    for (entity, mut position, mut rigidbody, mut maybe_signals, collider, gt, disabled) in
+        bodies.p0().iter_mut()
+    {
         // Step 1: Skip frozen entities
         if rigidbody.frozen {
             // Still update signals for frozen entities (they might still be "moving" via external control)
@@ -51,13 +118,11 @@ pub fn movement(
             continue;
         }
 
-        let delta = time.delta;
-
         // Step 2: Calculate total acceleration from all enabled forces
         let total_acceleration = rigidbody.total_acceleration();
 
-        // Step 3: Integrate acceleration into velocity
-        rigidbody.velocity += total_acceleration * delta;
+        // Step 3: Integrate acceleration into velocity, scaled by inverse mass
+        rigidbody.velocity += total_acceleration * (rigidbody.inverse_mass() * delta);
 
         // Step 4: Apply friction damping
         // Using linear damping: velocity *= (1 - friction * delta)
@@ -82,8 +147,24 @@ pub fn movement(
             }
         }
 
-        // Step 6: Integrate velocity into position
-        position.pos += rigidbody.velocity * delta;
+        // Step 6: Integrate velocity into position, deferring fast colliders
+        // to the sweep below
+        let displacement = rigidbody.velocity * delta;
+        let sweep = collider.filter(|_| !disabled).and_then(|collider| {
+            let extent = collider.size.x.abs().min(collider.size.y.abs());
+            let substeps = config.substeps(displacement.length(), extent);
+            (substeps > 1).then(|| Sweep {
+                entity,
+                rect: collider.as_rectangle(gt.map_or(position.pos, |gt| gt.position)),
+                displacement,
+                substeps,
+                fraction: 1.0,
+            })
+        });
+        match sweep {
+            Some(sweep) => sweeps.push(sweep),
+            None => position.pos += displacement,
+        }
 
         // Step 7: Update movement signals
         if let Some(signals) = maybe_signals.as_mut() {
@@ -96,4 +177,116 @@ pub fn movement(
             signals.update_scalar(sk::SPEED_SQ, speed_sq);
         }
     }
+
+    if sweeps.is_empty() {
+        crate::tracy::tracy_plot!("movement_substeps", 0u32);
+        return;
+    }
+
+    let colliders = bodies.p1();
+    obstacles.clear();
+    obstacles.extend(colliders.iter().map(|(entity, pos, collider, gt, ..)| {
+        (
+            entity,
+            collider.as_rectangle(gt.map_or(pos.pos, |gt| gt.position)),
+        )
+    }));
+    let labels = |entity: Entity| {
+        colliders
+            .get(entity)
+            .map(|(.., group, tags)| entity_labels(group, tags).collect::<SmallVec<[&str; 4]>>())
+            .unwrap_or_default()
+    };
+
+    let mut total_substeps = 0u32;
+    for sweep in sweeps.iter_mut() {
+        let body = labels(sweep.entity);
+        let blocks = |other: Entity| {
+            let other = labels(other);
+            let paired = has_rule(&rules, &body, &other);
+            #[cfg(feature = "lua")]
+            let paired = paired || has_rule(&lua_rules, &body, &other);
+            paired
+        };
+        let (fraction, taken) = sweep_fraction(sweep, &obstacles, blocks);
+        sweep.fraction = fraction;
+        total_substeps += taken;
+    }
+
+    let mut movers = bodies.p0();
+    for sweep in sweeps.iter() {
+        if let Ok((_, mut position, ..)) = movers.get_mut(sweep.entity) {
+            position.pos += sweep.displacement * sweep.fraction;
+        }
+    }
+    crate::tracy::tracy_plot!("movement_substeps", total_substeps);
+}
+
+/// Walk `sweep` in its sub-steps and return the fraction of the displacement
+/// to apply, plus the number of sub-steps taken.
+///
+/// The body stops at the first sub-step where it overlaps a collider it did
+/// not already overlap before moving and for which `blocks` returns `true`.
+/// Colliders it starts inside (e.g. the floor it stands on) never stop it.
+fn sweep_fraction(
+    sweep: &Sweep,
+    obstacles: &[(Entity, Rectangle)],
+    blocks: impl Fn(Entity) -> bool,
+) -> (f32, u32) {
+    let end = translate(&sweep.rect, sweep.displacement);
+    let swept = union(&sweep.rect, &end);
+    let candidates: SmallVec<[&Rectangle; 8]> = obstacles
+        .iter()
+        .filter(|(entity, rect)| {
+            *entity != sweep.entity
+                && overlaps(&swept, rect)
+                && !overlaps(&sweep.rect, rect)
+                && blocks(*entity)
+        })
+        .map(|(_, rect)| rect)
+        .collect();
+    if candidates.is_empty() {
+        return (1.0, sweep.substeps);
+    }
+
+    for step in 1..=sweep.substeps {
+        let fraction = step as f32 / sweep.substeps as f32;
+        let rect = translate(&sweep.rect, sweep.displacement * fraction);
+        if candidates.iter().any(|other| overlaps(&rect, other)) {
+            return (fraction, step);
+        }
+    }
+    (1.0, sweep.substeps)
+}
+
+/// Whether an enabled rule in `rules` pairs entities labelled `a` and `b`.
+fn has_rule<C: Send + Sync + 'static>(
+    rules: &Query<&CollisionRule<C>>,
+    a: &[&str],
+    b: &[&str],
+) -> bool {
+    rules.iter().any(|rule| {
+        rule.match_labels_and_order(Entity::PLACEHOLDER, Entity::PLACEHOLDER, a, b)
+            .is_some()
+    })
+}
+
+/// Strict AABB overlap; touching edges do not count.
+fn overlaps(a: &Rectangle, b: &Rectangle) -> bool {
+    a.x < b.x + b.width && a.x + a.width > b.x && a.y < b.y + b.height && a.y + a.height > b.y
+}
+
+fn translate(rect: &Rectangle, by: Vector2) -> Rectangle {
+    Rectangle::new(rect.x + by.x, rect.y + by.y, rect.width, rect.height)
+}
+
+fn union(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let x = a.x.min(b.x);
+    let y = a.y.min(b.y);
+    Rectangle::new(
+        x,
+        y,
+        (a.x + a.width).max(b.x + b.width) - x,
+        (a.y + a.height).max(b.y + b.height) - y,
+    )
 }
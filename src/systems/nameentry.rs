@@ -0,0 +1,223 @@
+//! High score name entry.
+//!
+//! [`SubmitScore`] checks a score against the
+//! [`HighScores`] table: a qualifying score spawns a [`NameEntry`], any other
+//! score triggers a [`HighScoreEvent`] with no name or rank straight away.
+//!
+//! [`name_entry_system`] spawns the letter texts on the entry's first frame and
+//! then reads the input actions: up/down cycles the selected letter,
+//! left/right and `back` move between letters, and confirm (`action_1` or
+//! `action_2`) moves on; confirming the last letter inserts the score, despawns
+//! the entry and triggers the [`HighScoreEvent`].
+//!
+//! # Related
+//!
+//! - [`crate::components::nameentry`] – the letter selector
+//! - [`crate::resources::highscores`] – the persistent table
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::dynamictext::DynamicText;
+use crate::components::nameentry::{NAME_LENGTH, NameEntry};
+use crate::components::screenposition::ScreenPosition;
+use crate::components::zindex::ZIndex;
+use crate::events::highscore::HighScoreEvent;
+use crate::resources::highscores::HighScores;
+use crate::resources::input::InputState;
+
+/// Command submitting a score to the [`HighScores`] table.
+pub struct SubmitScore {
+    pub score: i64,
+    /// Lua function called once the score is handled.
+    pub callback: Option<String>,
+}
+
+impl Command for SubmitScore {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let qualifies = world
+            .get_resource::<HighScores>()
+            .is_some_and(|scores| scores.qualifies(self.score));
+        if qualifies {
+            let mut entry = NameEntry::new(self.score);
+            entry.callback = self.callback;
+            world.spawn(entry);
+        } else {
+            world.trigger(HighScoreEvent {
+                name: None,
+                score: self.score,
+                rank: None,
+                callback: self.callback,
+            });
+        }
+    }
+}
+
+/// Reads input for every [`NameEntry`] and records the confirmed names.
+pub fn name_entry_system(
+    mut commands: Commands,
+    mut entries: Query<(Entity, &mut NameEntry)>,
+    mut texts: Query<&mut DynamicText>,
+    mut high_scores: ResMut<HighScores>,
+    input: Res<InputState>,
+) {
+    let up = input.maindirection_up.just_pressed || input.secondarydirection_up.just_pressed;
+    let down = input.maindirection_down.just_pressed || input.secondarydirection_down.just_pressed;
+    let left = input.maindirection_left.just_pressed
+        || input.secondarydirection_left.just_pressed
+        || input.action_back.just_pressed;
+    let right =
+        input.maindirection_right.just_pressed || input.secondarydirection_right.just_pressed;
+    let confirm = input.action_1.just_pressed || input.action_2.just_pressed;
+
+    for (entity, mut entry) in entries.iter_mut() {
+        // Input is ignored on the opening frame so the press that ended the
+        // game doesn't pick the first letter.
+        if entry.texts.is_empty() {
+            open_entry(&mut commands, &mut entry);
+            continue;
+        }
+
+        if confirm && entry.cursor == NAME_LENGTH - 1 {
+            let name = entry.name();
+            let rank = high_scores.insert(name.clone(), entry.score);
+            for &text in &entry.texts {
+                commands.entity(text).try_despawn();
+            }
+            commands.entity(entity).despawn();
+            commands.trigger(HighScoreEvent {
+                name: Some(name),
+                score: entry.score,
+                rank,
+                callback: entry.callback.clone(),
+            });
+            continue;
+        }
+
+        if up {
+            entry.cycle(1);
+        }
+        if down {
+            entry.cycle(-1);
+        }
+        if left {
+            entry.move_cursor(-1);
+        }
+        if right || confirm {
+            entry.move_cursor(1);
+        }
+
+        for (index, &text) in entry.texts.iter().enumerate() {
+            if let Ok(mut text) = texts.get_mut(text) {
+                text.set_text(entry.letter(index).to_string());
+                text.color = if index == entry.cursor {
+                    entry.highlight
+                } else {
+                    entry.color
+                };
+            }
+        }
+    }
+}
+
+/// Spawns one text per letter, spaced by the font size.
+fn open_entry(commands: &mut Commands, entry: &mut NameEntry) {
+    entry.texts = (0..NAME_LENGTH)
+        .map(|index| {
+            let pos = entry.position + Vector2::new(index as f32 * entry.font_size, 0.0);
+            let color = if index == entry.cursor {
+                entry.highlight
+            } else {
+                entry.color
+            };
+            commands
+                .spawn((
+                    ScreenPosition::new(pos.x, pos.y),
+                    ZIndex(entry.z_index),
+                    DynamicText::new(
+                        entry.letter(index).to_string(),
+                        entry.font.as_str(),
+                        entry.font_size,
+                        color,
+                    ),
+                ))
+                .id()
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Done(Vec<HighScoreEvent>);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(InputState::default());
+        world.insert_resource(HighScores {
+            capacity: 1,
+            ..HighScores::default()
+        });
+        world.init_resource::<Done>();
+        world.add_observer(|trigger: On<HighScoreEvent>, mut done: ResMut<Done>| {
+            done.0.push(trigger.event().clone());
+        });
+        world
+    }
+
+    fn frame(world: &mut World, set: impl Fn(&mut InputState)) {
+        let mut input = InputState::default();
+        set(&mut input);
+        world.insert_resource(input);
+        world.run_system_once(name_entry_system).unwrap();
+    }
+
+    #[test]
+    fn enters_a_name_and_records_the_score() {
+        let mut world = world();
+        world.commands().queue(SubmitScore {
+            score: 500,
+            callback: Some("on_done".into()),
+        });
+        world.flush();
+
+        frame(&mut world, |i| i.action_1.just_pressed = true);
+        frame(&mut world, |i| i.maindirection_up.just_pressed = true);
+        frame(&mut world, |i| i.action_1.just_pressed = true);
+        frame(&mut world, |i| i.maindirection_down.just_pressed = true);
+        frame(&mut world, |i| i.action_2.just_pressed = true);
+        assert!(world.resource::<Done>().0.is_empty());
+        frame(&mut world, |i| i.action_1.just_pressed = true);
+
+        let done = &world.resource::<Done>().0;
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].name.as_deref(), Some("B.A"));
+        assert_eq!(done[0].rank, Some(0));
+        assert_eq!(done[0].callback.as_deref(), Some("on_done"));
+        assert_eq!(world.resource::<HighScores>().entries()[0].score, 500);
+        assert_eq!(world.query::<&NameEntry>().iter(&world).count(), 0);
+        assert_eq!(world.query::<&DynamicText>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn low_score_is_rejected_without_name_entry() {
+        let mut world = world();
+        world.resource_mut::<HighScores>().insert("TOP", 1000);
+        world.commands().queue(SubmitScore {
+            score: 10,
+            callback: None,
+        });
+        world.flush();
+
+        let done = &world.resource::<Done>().0;
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].name, None);
+        assert_eq!(done[0].rank, None);
+        assert_eq!(world.query::<&NameEntry>().iter(&world).count(), 0);
+    }
+}
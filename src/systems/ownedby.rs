@@ -0,0 +1,66 @@
+//! Owned-entity cleanup.
+//!
+//! [`owned_by_cleanup_system`] despawns every entity whose
+//! [`OwnedBy`] owner no longer exists.
+//!
+//! # Related
+//!
+//! - [`crate::components::ownedby::OwnedBy`] – the owner link
+
+use bevy_ecs::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::components::ownedby::OwnedBy;
+
+/// Despawns entities whose owner is gone, following chains of ownership so
+/// an entity owned by a despawned entity's belongings goes in the same frame.
+///
+/// Scheduling: runs after `ttl_system` and before `render_system`, so
+/// owned entities are not drawn for a frame after their owner dies.
+pub fn owned_by_cleanup_system(
+    owned: Query<(Entity, &OwnedBy)>,
+    all: Query<()>,
+    mut commands: Commands,
+) {
+    let mut doomed: FxHashSet<Entity> = FxHashSet::default();
+    loop {
+        let before = doomed.len();
+        for (entity, owned_by) in owned.iter() {
+            let owner = owned_by.owner();
+            if !all.contains(owner) || doomed.contains(&owner) {
+                doomed.insert(entity);
+            }
+        }
+        if doomed.len() == before {
+            break;
+        }
+    }
+    for entity in doomed {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn owned_entities_go_with_their_owner() {
+        let mut world = World::new();
+        let enemy = world.spawn_empty().id();
+        let health_bar = world.spawn(OwnedBy(enemy)).id();
+        let bar_label = world.spawn(OwnedBy(health_bar)).id();
+        let player = world.spawn_empty().id();
+        let shadow = world.spawn(OwnedBy(player)).id();
+
+        world.run_system_once(owned_by_cleanup_system).unwrap();
+        assert!(world.get_entity(health_bar).is_ok());
+
+        world.despawn(enemy);
+        world.run_system_once(owned_by_cleanup_system).unwrap();
+        assert!(world.get_entity(health_bar).is_err());
+        assert!(world.get_entity(bar_label).is_err());
+        assert!(world.get_entity(shadow).is_ok());
+    }
+}
@@ -12,6 +12,8 @@
 //! - Preserves template's RigidBody fields (friction, max_speed, forces)
 //! - Optionally inserts TTL component based on emitter configuration
 //! - Stops emitting when `emissions_remaining` reaches 0
+//! - Leaves weather emitters idle while the `weather` system toggle is off
+//! - Scales particles per emission by the quality governor's particle density
 //!
 //! # Coordinate System
 //!
@@ -30,6 +32,9 @@ use crate::components::particleemitter::{EmitterShape, ParticleEmitter, TtlSpec}
 use crate::components::rigidbody::RigidBody;
 use crate::components::rotation::Rotation;
 use crate::components::ttl::Ttl;
+use crate::components::weather::WeatherEmitter;
+use crate::resources::qualitygovernor::QualityGovernor;
+use crate::resources::systemtoggles::{SystemToggles, ToggleableSystem};
 use crate::resources::worldtime::WorldTime;
 
 /// System that processes particle emitters and spawns particles.
@@ -46,9 +51,12 @@ pub fn particle_emitter_system(
         &MapPosition,
         &mut ParticleEmitter,
         Option<&GlobalTransform2D>,
+        Has<WeatherEmitter>,
     )>,
     rigidbody_query: Query<&RigidBody>,
     time: Res<WorldTime>,
+    toggles: Res<SystemToggles>,
+    governor: Res<QualityGovernor>,
     mut commands: Commands,
     mut rng: Local<Rng>,
 ) {
@@ -57,7 +65,12 @@ pub fn particle_emitter_system(
         return;
     }
 
-    for (emitter_entity, owner_pos, mut emitter, maybe_gt) in emitter_query.iter_mut() {
+    let weather_enabled = toggles.is_enabled(ToggleableSystem::Weather);
+    let density = governor.particle_density();
+    for (emitter_entity, owner_pos, mut emitter, maybe_gt, is_weather) in emitter_query.iter_mut() {
+        if is_weather && !weather_enabled {
+            continue;
+        }
         // Skip if no templates, no emissions remaining, or rate is zero/negative
         if emitter.templates.is_empty()
             || emitter.emissions_remaining == 0
@@ -74,11 +87,13 @@ pub fn particle_emitter_system(
 
         // Catch-up loop: emit multiple times if dt is large
         while emitter.time_since_emit >= period && emitter.emissions_remaining > 0 {
+            let count = scaled_count(emitter.particles_per_emission, density, &mut rng);
             emit_particles(
                 &mut commands,
                 emitter_entity,
                 &emit_pos,
                 &emitter,
+                count,
                 &rigidbody_query,
                 &mut rng,
             );
@@ -99,18 +114,31 @@ fn random_f32_range(rng: &mut Rng, min: f32, max: f32) -> f32 {
     min + rng.f32() * range
 }
 
-/// Emit particles for a single emission event.
+/// `count` scaled by `density`, rounding the fraction up or down at random so
+/// the average is kept (an emitter of one particle still emits at half density).
+#[inline]
+fn scaled_count(count: u32, density: f32, rng: &mut Rng) -> u32 {
+    if density >= 1.0 {
+        return count;
+    }
+    let scaled = count as f32 * density.max(0.0);
+    let whole = scaled.floor();
+    whole as u32 + u32::from(rng.f32() < scaled - whole)
+}
+
+/// Emit `count` particles for a single emission event.
 fn emit_particles(
     commands: &mut Commands,
     emitter_entity: Entity,
     owner_pos: &MapPosition,
     emitter: &ParticleEmitter,
+    count: u32,
     rigidbody_query: &Query<&RigidBody>,
     rng: &mut Rng,
 ) {
     let base_pos = owner_pos.pos + emitter.offset;
 
-    for _ in 0..emitter.particles_per_emission {
+    for _ in 0..count {
         // Pick a random template
         let template_idx = rng.usize(0..emitter.templates.len());
         let template = emitter.templates[template_idx];
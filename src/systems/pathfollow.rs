@@ -0,0 +1,82 @@
+//! Path following system.
+//!
+//! [`path_follow_system`] advances every [`PathFollow`] by the frame time,
+//! moves the entity's [`MapPosition`] to the new point on the path and, when
+//! `orient` is set, turns its [`Rotation`] to the direction of travel. It
+//! runs after movement and before transform propagation, so the path wins
+//! over any velocity the entity has.
+//!
+//! # Related
+//!
+//! - [`crate::components::pathfollow`] – paths, easing and loop modes
+
+use bevy_ecs::prelude::*;
+
+use crate::components::mapposition::MapPosition;
+use crate::components::pathfollow::PathFollow;
+use crate::components::rotation::Rotation;
+use crate::resources::worldtime::WorldTime;
+
+/// Moves path-following entities along their paths.
+pub fn path_follow_system(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut PathFollow,
+        &mut MapPosition,
+        Option<&mut Rotation>,
+    )>,
+    time: Res<WorldTime>,
+) {
+    for (entity, mut follow, mut position, rotation) in query.iter_mut() {
+        if follow.is_finished() {
+            continue;
+        }
+        let (point, direction) = follow.advance(time.delta);
+        position.pos = point;
+        if !follow.orient || direction.length_sqr() == 0.0 {
+            continue;
+        }
+        let degrees = direction.y.atan2(direction.x).to_degrees() + follow.rotation_offset;
+        match rotation {
+            Some(mut rotation) => rotation.degrees = degrees,
+            None => {
+                commands.entity(entity).insert(Rotation { degrees });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::tween::LoopMode;
+    use bevy_ecs::system::RunSystemOnce;
+    use raylib::prelude::Vector2;
+
+    #[test]
+    fn moves_and_orients_along_the_path() {
+        let mut world = World::new();
+        let mut time = WorldTime::default();
+        time.delta = 1.5;
+        world.insert_resource(time);
+        let route = vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(10.0, 0.0),
+            Vector2::new(10.0, 10.0),
+        ];
+        let entity = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                PathFollow::new(route, 10.0)
+                    .with_loop_mode(LoopMode::Once)
+                    .with_orient(true),
+            ))
+            .id();
+
+        world.run_system_once(path_follow_system).unwrap();
+        let position = world.get::<MapPosition>(entity).unwrap().pos;
+        assert_eq!((position.x, position.y), (10.0, 5.0));
+        assert!((world.get::<Rotation>(entity).unwrap().degrees - 90.0).abs() < 1e-4);
+    }
+}
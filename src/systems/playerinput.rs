@@ -0,0 +1,156 @@
+//! Per-player input polling.
+//!
+//! [`update_player_inputs`] fills the [`PlayerInputs`] state of every player
+//! with a device. A `keyboard` player gets a copy of the shared
+//! [`InputState`]; split keyboard halves and gamepads are polled from raylib
+//! and their presses and releases are triggered as [`InputEvent`]s tagged
+//! with the player. It runs after the cinematic and input context systems
+//! and applies the same locks to every player.
+//!
+//! [`AssignDevice`] gives a device to a player (Lua: `engine.assign_device`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::playerinput`] – device layouts and slot rules
+//! - [`crate::components::playerindex`] – entities owned by a player
+
+use bevy_ecs::prelude::*;
+use raylib::ffi::{GamepadAxis, GamepadButton};
+
+use crate::events::input::{InputAction, InputEvent};
+use crate::resources::cinematic::CinematicMode;
+use crate::resources::input::InputState;
+use crate::resources::inputcontext::InputContexts;
+use crate::resources::playerinput::{InputDevice, PlayerInputs};
+
+/// Stick deflection that counts as a direction held.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Command giving `device` to `player` (0-based), or leaving the player
+/// without one when `device` is `None`.
+pub struct AssignDevice {
+    pub player: usize,
+    pub device: Option<InputDevice>,
+}
+
+impl Command for AssignDevice {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let mut players = world.get_resource_or_insert_with(PlayerInputs::default);
+        match self.device {
+            Some(device) => players.assign(self.player, device),
+            None => players.unassign(self.player),
+        }
+    }
+}
+
+/// Whether a gamepad holds `action`.
+fn gamepad_held(rl: &raylib::RaylibHandle, pad: i32, action: InputAction) -> bool {
+    use GamepadButton::*;
+    let button = |b| rl.is_gamepad_button_down(pad, b);
+    let axis = |a| rl.get_gamepad_axis_movement(pad, a);
+    match action {
+        InputAction::MainDirectionUp => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_UP)
+                || axis(GamepadAxis::GAMEPAD_AXIS_LEFT_Y) < -STICK_THRESHOLD
+        }
+        InputAction::MainDirectionDown => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_DOWN)
+                || axis(GamepadAxis::GAMEPAD_AXIS_LEFT_Y) > STICK_THRESHOLD
+        }
+        InputAction::MainDirectionLeft => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_LEFT)
+                || axis(GamepadAxis::GAMEPAD_AXIS_LEFT_X) < -STICK_THRESHOLD
+        }
+        InputAction::MainDirectionRight => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_RIGHT)
+                || axis(GamepadAxis::GAMEPAD_AXIS_LEFT_X) > STICK_THRESHOLD
+        }
+        InputAction::SecondaryDirectionUp => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_UP)
+                || axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_Y) < -STICK_THRESHOLD
+        }
+        InputAction::SecondaryDirectionDown => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_DOWN)
+                || axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_Y) > STICK_THRESHOLD
+        }
+        InputAction::SecondaryDirectionLeft => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_LEFT)
+                || axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_X) < -STICK_THRESHOLD
+        }
+        InputAction::SecondaryDirectionRight => {
+            button(GAMEPAD_BUTTON_LEFT_FACE_RIGHT)
+                || axis(GamepadAxis::GAMEPAD_AXIS_RIGHT_X) > STICK_THRESHOLD
+        }
+        InputAction::Action1 => button(GAMEPAD_BUTTON_RIGHT_FACE_DOWN),
+        InputAction::Action2 => button(GAMEPAD_BUTTON_RIGHT_FACE_RIGHT),
+        InputAction::Action3 => button(GAMEPAD_BUTTON_RIGHT_FACE_LEFT),
+        InputAction::Special => button(GAMEPAD_BUTTON_RIGHT_FACE_UP),
+        InputAction::Back => {
+            button(GAMEPAD_BUTTON_MIDDLE_LEFT) || button(GAMEPAD_BUTTON_MIDDLE_RIGHT)
+        }
+        InputAction::ToggleDebug | InputAction::ToggleFullscreen => false,
+    }
+}
+
+/// Polls every assigned device into its player's [`InputState`].
+pub fn update_player_inputs(
+    mut players: ResMut<PlayerInputs>,
+    shared: Res<InputState>,
+    rl: NonSend<raylib::RaylibHandle>,
+    cinematic: Option<Res<CinematicMode>>,
+    contexts: Option<Res<InputContexts>>,
+    mut commands: Commands,
+) {
+    let assigned: Vec<(usize, InputDevice)> = players.assigned().collect();
+    for (player, device) in assigned {
+        match device {
+            InputDevice::Keyboard => {
+                if let Some(state) = players.state_mut(player) {
+                    state.clone_from(&shared);
+                }
+                // Shared keyboard events were already triggered, untagged.
+                continue;
+            }
+            InputDevice::KeyboardLeft | InputDevice::KeyboardRight => {
+                let layout = device.keyboard_layout().unwrap_or_default();
+                for action in InputAction::ALL {
+                    let held = layout
+                        .iter()
+                        .any(|&(bound, key)| bound == action && rl.is_key_down(key));
+                    players.set_held(player, action, held);
+                }
+            }
+            InputDevice::Gamepad(pad) => {
+                let available = rl.is_gamepad_available(pad);
+                for action in InputAction::ALL {
+                    let held = available && gamepad_held(&rl, pad, action);
+                    players.set_held(player, action, held);
+                }
+            }
+        }
+
+        let Some(state) = players.state_mut(player) else {
+            continue;
+        };
+        if cinematic.as_ref().is_some_and(|c| c.is_active()) {
+            CinematicMode::suppress(state);
+        }
+        if let Some(contexts) = &contexts {
+            contexts.apply(state);
+        }
+        for action in InputAction::ALL {
+            let edge = state.action(action);
+            for (happened, pressed) in [(edge.just_pressed, true), (edge.just_released, false)] {
+                if happened {
+                    commands.trigger(InputEvent {
+                        action,
+                        pressed,
+                        player: Some(player as u8),
+                    });
+                }
+            }
+        }
+    }
+}
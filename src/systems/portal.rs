@@ -0,0 +1,329 @@
+//! Portal transitions and the spawn point registry.
+//!
+//! - [`spawn_point_registry_system`] keeps the [`SpawnPoints`] registry in
+//!   sync with the [`SpawnPoint`] entities of the current scene.
+//! - [`portal_interact_observer`] starts the [`PortalTransition`] when a
+//!   [`Portal`] is confirmed.
+//! - [`portal_transition_system`] advances the fade, moves the interactor or
+//!   requests the scene switch, and places the arriving entities on the
+//!   destination spawn point.
+//!
+//! Scene switches use the same signals as `engine.change_scene()`, so both
+//! Lua scenes and [`SceneManager`](crate::resources::scenemanager::SceneManager)
+//! games follow them.
+//!
+//! # Related
+//!
+//! - [`crate::components::portal`] – portals, destinations and spawn points
+//! - [`crate::resources::portaltransition`] – the fade state
+
+use bevy_ecs::prelude::*;
+use log::warn;
+use raylib::prelude::Vector2;
+
+use crate::components::collision::pattern_matches;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::interactable::Interactable;
+use crate::components::mapposition::MapPosition;
+use crate::components::portal::{Portal, PortalDestination, SpawnPoint};
+use crate::components::tags::{Tags, entity_labels};
+use crate::events::interact::InteractEvent;
+use crate::resources::portaltransition::{ARRIVAL_TIMEOUT, PortalPhase, PortalTransition};
+use crate::resources::signal_keys as sk;
+use crate::resources::spawnpoints::{SpawnPointInfo, SpawnPoints};
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Rebuild the [`SpawnPoints`] registry from world positions when a spawn
+/// point was added, moved or removed. Run after transform propagation.
+///
+/// The registry is only written when the rebuilt one differs, so readers
+/// checking it for changes (the Lua cache) don't copy it for nothing.
+#[allow(clippy::type_complexity)]
+pub fn spawn_point_registry_system(
+    mut spawn_points: ResMut<SpawnPoints>,
+    query: Query<(
+        Entity,
+        &SpawnPoint,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+    )>,
+    changed: Query<
+        (),
+        (
+            With<SpawnPoint>,
+            Or<(
+                Changed<SpawnPoint>,
+                Changed<MapPosition>,
+                Changed<GlobalTransform2D>,
+            )>,
+        ),
+    >,
+    mut removed: RemovedComponents<SpawnPoint>,
+) {
+    let removed_any = removed.read().count() > 0;
+    if changed.is_empty() && !removed_any {
+        return;
+    }
+    let mut rebuilt = SpawnPoints::default();
+    for (entity, point, pos, gt) in query.iter() {
+        rebuilt.insert(
+            &point.name,
+            SpawnPointInfo {
+                entity,
+                position: gt.map_or(pos.pos, |gt| gt.position),
+                properties: point.properties.clone(),
+            },
+        );
+    }
+    spawn_points.set_if_neq(rebuilt);
+}
+
+/// Start a transition when the confirmed interactable is a [`Portal`].
+/// Ignored while another transition is running.
+pub fn portal_interact_observer(
+    trigger: On<InteractEvent>,
+    portals: Query<(&Portal, &Interactable)>,
+    mut transition: ResMut<PortalTransition>,
+) {
+    let event = trigger.event();
+    let Ok((portal, interactable)) = portals.get(event.entity) else {
+        return;
+    };
+    if transition.is_active() {
+        return;
+    }
+    transition.start(
+        portal.destination.clone(),
+        event.interactor,
+        &interactable.mask,
+        portal.fade,
+        portal.fade_color,
+    );
+}
+
+/// Advance the running [`PortalTransition`]. Run after
+/// [`spawn_point_registry_system`].
+pub fn portal_transition_system(
+    time: Res<WorldTime>,
+    mut transition: ResMut<PortalTransition>,
+    spawn_points: Res<SpawnPoints>,
+    mut world_signals: ResMut<WorldSignals>,
+    mut positions: Query<(Entity, &mut MapPosition, Option<&Group>, Option<&Tags>)>,
+) {
+    let dt = time.delta;
+    match transition.phase {
+        PortalPhase::Idle => {}
+        PortalPhase::FadingOut { elapsed } => {
+            let elapsed = elapsed + dt;
+            if elapsed < transition.fade {
+                transition.phase = PortalPhase::FadingOut { elapsed };
+                return;
+            }
+            transition.phase = PortalPhase::FadingIn { elapsed: 0.0 };
+            let interactor = transition.interactor;
+            let target = match transition.destination.as_ref() {
+                Some(PortalDestination::Position(pos)) => Some(*pos),
+                Some(PortalDestination::SpawnPoint(name)) => {
+                    let pos = spawn_points.get(name);
+                    if pos.is_none() {
+                        warn!("Portal: no spawn point named '{name}' in this scene");
+                    }
+                    pos
+                }
+                Some(PortalDestination::Scene { scene, spawn_point }) => {
+                    let departed = spawn_points.entity(spawn_point);
+                    world_signals.set_string(sk::SCENE, scene.clone());
+                    world_signals.set_flag(sk::SWITCH_SCENE);
+                    transition.departed_spawn_point = departed;
+                    transition.phase = PortalPhase::Arriving { waited: 0.0 };
+                    None
+                }
+                None => None,
+            };
+            if let (Some(target), Some(interactor)) = (target, interactor)
+                && let Ok((_, mut pos, ..)) = positions.get_mut(interactor)
+            {
+                pos.pos = target;
+            }
+        }
+        PortalPhase::Arriving { waited } => {
+            let Some(PortalDestination::Scene { spawn_point, .. }) = &transition.destination else {
+                transition.phase = PortalPhase::FadingIn { elapsed: 0.0 };
+                return;
+            };
+            let arrival = spawn_points
+                .entity(spawn_point)
+                .filter(|e| Some(*e) != transition.departed_spawn_point)
+                .and_then(|_| spawn_points.get(spawn_point));
+            if let Some(target) = arrival {
+                place_matching(&mut positions, &transition.mask, target);
+                transition.phase = PortalPhase::FadingIn { elapsed: 0.0 };
+            } else if waited + dt >= ARRIVAL_TIMEOUT {
+                warn!("Portal: spawn point '{spawn_point}' did not appear after the scene switch");
+                transition.phase = PortalPhase::FadingIn { elapsed: 0.0 };
+            } else {
+                transition.phase = PortalPhase::Arriving {
+                    waited: waited + dt,
+                };
+            }
+        }
+        PortalPhase::FadingIn { elapsed } => {
+            let elapsed = elapsed + dt;
+            if elapsed < transition.fade {
+                transition.phase = PortalPhase::FadingIn { elapsed };
+            } else {
+                *transition = PortalTransition::default();
+            }
+        }
+    }
+}
+
+/// Move every entity whose group or tags match `mask` to `target`.
+fn place_matching(
+    positions: &mut Query<(Entity, &mut MapPosition, Option<&Group>, Option<&Tags>)>,
+    mask: &str,
+    target: Vector2,
+) {
+    for (_, mut pos, group, tags) in positions.iter_mut() {
+        if entity_labels(group, tags).any(|l| pattern_matches(mask, l)) {
+            pos.pos = target;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::propagate_transforms::propagate_transforms;
+    use bevy_ecs::hierarchy::ChildOf;
+    use bevy_ecs::observer::Observer;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            delta: 0.2,
+            real_delta: 0.2,
+            ..Default::default()
+        });
+        world.init_resource::<PortalTransition>();
+        world.init_resource::<SpawnPoints>();
+        world.init_resource::<WorldSignals>();
+        world.spawn(Observer::new(portal_interact_observer));
+        world
+    }
+
+    fn step(world: &mut World) {
+        world.run_system_once(spawn_point_registry_system).unwrap();
+        world.run_system_once(portal_transition_system).unwrap();
+    }
+
+    fn confirm(world: &mut World, portal: Portal, interactor: Entity) {
+        let entity = world.spawn((Interactable::new("Enter"), portal)).id();
+        world.trigger(InteractEvent { entity, interactor });
+    }
+
+    #[test]
+    fn position_portal_moves_the_interactor_behind_the_fade() {
+        let mut world = world();
+        let player = world
+            .spawn((Group::new("player"), MapPosition::new(0.0, 0.0)))
+            .id();
+        let destination = PortalDestination::Position(Vector2::new(50.0, 60.0));
+        confirm(&mut world, Portal::new(destination).with_fade(0.3), player);
+
+        step(&mut world);
+        assert_eq!(
+            world.get::<MapPosition>(player).unwrap().pos,
+            Vector2::zero()
+        );
+        step(&mut world);
+        assert_eq!(
+            world.get::<MapPosition>(player).unwrap().pos,
+            Vector2::new(50.0, 60.0)
+        );
+        assert!(matches!(
+            world.resource::<PortalTransition>().phase,
+            PortalPhase::FadingIn { .. }
+        ));
+        step(&mut world);
+        step(&mut world);
+        assert!(!world.resource::<PortalTransition>().is_active());
+    }
+
+    #[test]
+    fn scene_portal_switches_and_waits_for_the_new_spawn_point() {
+        let mut world = world();
+        let player = world
+            .spawn((Group::new("player"), MapPosition::new(0.0, 0.0)))
+            .id();
+        let old_point = world
+            .spawn((SpawnPoint::new("entrance"), MapPosition::new(5.0, 5.0)))
+            .id();
+        let destination = PortalDestination::Scene {
+            scene: "cave".into(),
+            spawn_point: "entrance".into(),
+        };
+        confirm(&mut world, Portal::new(destination).with_fade(0.0), player);
+
+        step(&mut world);
+        let signals = world.resource::<WorldSignals>();
+        assert_eq!(
+            signals.get_string(sk::SCENE).map(String::as_str),
+            Some("cave")
+        );
+        assert!(signals.has_flag(sk::SWITCH_SCENE));
+
+        // The old scene's point is still around: keep waiting.
+        step(&mut world);
+        assert_eq!(
+            world.get::<MapPosition>(player).unwrap().pos,
+            Vector2::zero()
+        );
+
+        world.despawn(old_point);
+        world.spawn((SpawnPoint::new("entrance"), MapPosition::new(80.0, 40.0)));
+        step(&mut world);
+        assert_eq!(
+            world.get::<MapPosition>(player).unwrap().pos,
+            Vector2::new(80.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn registry_is_only_written_when_a_point_moves() {
+        let mut world = world();
+        let root = world.spawn(MapPosition::new(100.0, 0.0)).id();
+        let point = world
+            .spawn((
+                SpawnPoint::new("entrance"),
+                MapPosition::new(8.0, 8.0),
+                ChildOf(root),
+            ))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((propagate_transforms, spawn_point_registry_system).chain());
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<SpawnPoints>().get("entrance"),
+            Some(Vector2::new(108.0, 8.0))
+        );
+
+        // A write that keeps the same position rebuilds to the same registry.
+        world.clear_trackers();
+        world.get_mut::<MapPosition>(point).unwrap().pos = Vector2::new(8.0, 8.0);
+        schedule.run(&mut world);
+        assert!(!world.is_resource_changed::<SpawnPoints>());
+
+        world.get_mut::<MapPosition>(root).unwrap().pos = Vector2::new(0.0, 0.0);
+        schedule.run(&mut world);
+        assert!(world.is_resource_changed::<SpawnPoints>());
+        assert_eq!(
+            world.resource::<SpawnPoints>().get("entrance"),
+            Some(Vector2::new(8.0, 8.0))
+        );
+    }
+}
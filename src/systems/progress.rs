@@ -0,0 +1,98 @@
+//! Keeps signal-bound [`ProgressBar`] and [`RadialProgress`] values in sync.
+//!
+//! Runs every frame before `render_system`. For each progress component with
+//! a [`ProgressSignal`], reads the key from `WorldSignals` or from the source
+//! entity's `Signals` (integer preferred over scalar, matching
+//! `gui_progressbar_signal_update_system`) and writes it into `value`. A
+//! missing key or source entity leaves `value` as it is.
+
+use bevy_ecs::prelude::*;
+
+use crate::components::progress::{ProgressBar, ProgressSignal, RadialProgress};
+use crate::components::signalbinding::SignalSource;
+use crate::components::signals::Signals;
+use crate::resources::worldsignals::WorldSignals;
+
+/// Writes each bound progress component's signal into its `value`.
+///
+/// Scheduling: runs after `cooldown_system` so cooldown rings show the
+/// current frame's fraction.
+pub fn progress_signal_update_system(
+    mut bars: Query<&mut ProgressBar>,
+    mut radials: Query<&mut RadialProgress>,
+    entity_signals: Query<&Signals>,
+    world_signals: Res<WorldSignals>,
+) {
+    let read = |signal: &ProgressSignal| match signal.source {
+        SignalSource::World => world_signals
+            .get_integer(&signal.key)
+            .map(|i| i as f32)
+            .or_else(|| world_signals.get_scalar(&signal.key)),
+        SignalSource::Entity(entity) => entity_signals.get(entity).ok().and_then(|s| {
+            s.get_integer(&signal.key)
+                .map(|i| i as f32)
+                .or_else(|| s.get_scalar(&signal.key))
+        }),
+    };
+    for mut bar in &mut bars {
+        if let Some(v) = bar.signal.as_ref().and_then(read)
+            && bar.value != v
+        {
+            bar.value = v;
+        }
+    }
+    for mut radial in &mut radials {
+        if let Some(v) = radial.signal.as_ref().and_then(read)
+            && radial.value != v
+        {
+            radial.value = v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn values_follow_world_and_entity_signals() {
+        let mut world = World::new();
+        world.insert_resource(WorldSignals::default());
+        world
+            .resource_mut::<WorldSignals>()
+            .set_integer("boss_hp", 40);
+        let mut signals = Signals::default();
+        signals.set_scalar("dash_cd", 0.25);
+        let player = world.spawn(signals).id();
+
+        let bar = world
+            .spawn(
+                ProgressBar::new(100.0, 8.0, 100.0).with_signal(ProgressSignal::world("boss_hp")),
+            )
+            .id();
+        let ring = world
+            .spawn(
+                RadialProgress::new(16.0, 1.0)
+                    .with_value(1.0)
+                    .with_signal(ProgressSignal::entity(player, "dash_cd")),
+            )
+            .id();
+        let unbound = world
+            .spawn(ProgressBar::new(10.0, 2.0, 1.0).with_value(0.5))
+            .id();
+
+        world
+            .run_system_once(progress_signal_update_system)
+            .unwrap();
+        assert_eq!(world.get::<ProgressBar>(bar).unwrap().value, 40.0);
+        assert_eq!(world.get::<RadialProgress>(ring).unwrap().value, 0.25);
+        assert_eq!(world.get::<ProgressBar>(unbound).unwrap().value, 0.5);
+
+        world.despawn(player);
+        world
+            .run_system_once(progress_signal_update_system)
+            .unwrap();
+        assert_eq!(world.get::<RadialProgress>(ring).unwrap().value, 0.25);
+    }
+}
@@ -77,7 +77,8 @@ fn compose_child_transform(
 /// 1. Compute its [`GlobalTransform2D`] from local components.
 /// 2. Recursively traverse children, composing transforms at each level.
 ///
-/// Entities that already have a `GlobalTransform2D` are updated in place.
+/// Entities that already have a `GlobalTransform2D` are updated in place, and
+/// only when the value changes, so change detection on it stays meaningful.
 /// Entities missing the component get it inserted via deferred [`Commands`]
 /// (visible next frame).
 pub fn propagate_transforms(
@@ -96,7 +97,7 @@ pub fn propagate_transforms(
 
         // Update or insert root's GlobalTransform2D
         if let Ok(mut gt) = globals.get_mut(root_entity) {
-            *gt = root_gt;
+            gt.set_if_neq(root_gt);
         } else {
             commands.entity(root_entity).insert(root_gt);
         }
@@ -130,7 +131,7 @@ fn propagate_children(
         let child_gt = compose_child_transform(parent_gt, pos.pos, local_rot, local_scale);
 
         if let Ok(mut gt) = globals.get_mut(child_entity) {
-            *gt = child_gt;
+            gt.set_if_neq(child_gt);
         } else {
             commands.entity(child_entity).insert(child_gt);
         }
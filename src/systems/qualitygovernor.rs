@@ -0,0 +1,64 @@
+//! Automatic quality scaling system.
+//!
+//! [`quality_governor_system`] feeds the frame time to the
+//! [`QualityGovernor`] and mirrors its level into [`SystemToggles`]; the
+//! particle density is read by `particle_emitter_system` directly.
+//!
+//! # Related
+//!
+//! - [`crate::resources::qualitygovernor::QualityGovernor`] – budget, steps and level
+//! - [`crate::events::quality::QualityChangedEvent`] – triggered on each step
+
+use bevy_ecs::prelude::*;
+use log::info;
+use raylib::ffi;
+
+use crate::resources::gameconfig::GameConfig;
+use crate::resources::qualitygovernor::QualityGovernor;
+use crate::resources::systemtoggles::{SystemToggles, ToggleableSystem};
+
+/// Steps quality down or up from the frame time and triggers
+/// [`QualityChangedEvent`](crate::events::quality::QualityChangedEvent).
+///
+/// Does nothing but restore full quality while `GameConfig::auto_quality` is
+/// off, and skips frames while the window is unfocused.
+pub fn quality_governor_system(
+    rl: NonSend<raylib::RaylibHandle>,
+    config: Option<Res<GameConfig>>,
+    mut governor: ResMut<QualityGovernor>,
+    mut toggles: ResMut<SystemToggles>,
+    mut commands: Commands,
+) {
+    let change = match config {
+        Some(config) if config.auto_quality => {
+            if !rl.is_window_focused() {
+                return;
+            }
+            let refresh_rate = if config.vsync {
+                unsafe { ffi::GetMonitorRefreshRate(ffi::GetCurrentMonitor()) }.max(0) as u32
+            } else {
+                0
+            };
+            let budget = QualityGovernor::frame_budget(config.target_fps, refresh_rate);
+            governor.observe(rl.get_frame_time(), budget)
+        }
+        _ => governor.reset(),
+    };
+    if let Some(event) = change {
+        info!(
+            "Quality level {} -> {} (frame time {:.1} ms)",
+            event.old_level,
+            event.level,
+            event.frame_time * 1000.0
+        );
+        commands.trigger(event);
+    }
+    // Steps may also have been replaced with `set_steps`
+    let mut throttled = *toggles;
+    for system in ToggleableSystem::ALL {
+        throttled.set_throttled(system, governor.disables(system));
+    }
+    if *toggles != throttled {
+        *toggles = throttled;
+    }
+}
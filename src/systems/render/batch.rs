@@ -0,0 +1,241 @@
+//! Batched quad submission for runs of plain world sprites.
+//!
+//! `DrawTexturePro` opens and closes an rlgl primitive, binds the texture and
+//! checks the batch limit for every quad it draws. Particles and tilemap tiles
+//! come in long runs of sprites that share one atlas texture and need no
+//! entity shader, so the render system collects those runs in a
+//! [`QuadBatch`] and submits each run inside a single `rlBegin(RL_QUADS)`
+//! block. Tens of thousands of particles or tiles then cost one texture bind
+//! per run and only the draw calls rlgl needs when its vertex buffer fills up.
+//!
+//! Vertices are computed exactly like `DrawTexturePro`, so batched and
+//! unbatched sprites look identical.
+
+use raylib::ffi;
+use raylib::prelude::*;
+
+const RL_QUADS: i32 = 0x0007;
+
+/// One textured quad, corners in `DrawTexturePro` order (top-left,
+/// bottom-left, bottom-right, top-right).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct Quad {
+    pub(super) corners: [Vector2; 4],
+    pub(super) uvs: [Vector2; 4],
+    pub(super) color: Color,
+}
+
+/// Quads waiting to be submitted, all sharing one texture.
+#[derive(Default)]
+pub(super) struct QuadBatch {
+    texture_id: u32,
+    quads: Vec<Quad>,
+    /// Number of runs submitted since the last [`take_runs`](Self::take_runs).
+    runs: u32,
+}
+
+impl QuadBatch {
+    /// Queue `tex` drawn from `src` into `dest`, as `draw_texture_pro` would.
+    ///
+    /// Flushes the pending run first when it uses a different texture.
+    pub(super) fn push(
+        &mut self,
+        tex: &ffi::Texture2D,
+        src: Rectangle,
+        dest: Rectangle,
+        origin: Vector2,
+        rotation: f32,
+        color: Color,
+    ) {
+        if tex.id == 0 {
+            return;
+        }
+        if tex.id != self.texture_id {
+            self.flush();
+            self.texture_id = tex.id;
+        }
+        self.quads.push(textured_quad(
+            tex.width as f32,
+            tex.height as f32,
+            src,
+            dest,
+            origin,
+            rotation,
+            color,
+        ));
+    }
+
+    /// Submit the pending run to rlgl. Must be called inside the draw mode the
+    /// quads were pushed for, before anything that has to appear above them.
+    pub(super) fn flush(&mut self) {
+        if self.quads.is_empty() {
+            return;
+        }
+        let texture_id = self.texture_id;
+        unsafe {
+            ffi::rlSetTexture(texture_id);
+            ffi::rlBegin(RL_QUADS);
+            ffi::rlNormal3f(0.0, 0.0, 1.0);
+        }
+        for quad in &self.quads {
+            unsafe {
+                if ffi::rlCheckRenderBatchLimit(4) {
+                    ffi::rlSetTexture(texture_id);
+                    ffi::rlBegin(RL_QUADS);
+                    ffi::rlNormal3f(0.0, 0.0, 1.0);
+                }
+                ffi::rlColor4ub(quad.color.r, quad.color.g, quad.color.b, quad.color.a);
+            }
+            for (corner, uv) in quad.corners.iter().zip(&quad.uvs) {
+                unsafe {
+                    ffi::rlTexCoord2f(uv.x, uv.y);
+                    ffi::rlVertex2f(corner.x, corner.y);
+                }
+            }
+        }
+        unsafe {
+            ffi::rlEnd();
+            ffi::rlSetTexture(0);
+        }
+        self.quads.clear();
+        self.runs += 1;
+    }
+
+    /// Runs submitted since the previous call, for the profiler.
+    pub(super) fn take_runs(&mut self) -> u32 {
+        std::mem::take(&mut self.runs)
+    }
+}
+
+/// Corners and texture coordinates of `src` drawn into `dest`, rotated by
+/// `rotation` degrees around `origin` — the geometry of `DrawTexturePro`.
+pub(super) fn textured_quad(
+    tex_width: f32,
+    tex_height: f32,
+    mut src: Rectangle,
+    mut dest: Rectangle,
+    origin: Vector2,
+    rotation: f32,
+    color: Color,
+) -> Quad {
+    let flip_x = src.width < 0.0;
+    if flip_x {
+        src.width = -src.width;
+    }
+    if src.height < 0.0 {
+        src.y -= src.height;
+    }
+    dest.width = dest.width.abs();
+    dest.height = dest.height.abs();
+
+    let (top_left, top_right, bottom_left, bottom_right) = if rotation == 0.0 {
+        let x = dest.x - origin.x;
+        let y = dest.y - origin.y;
+        (
+            Vector2 { x, y },
+            Vector2 {
+                x: x + dest.width,
+                y,
+            },
+            Vector2 {
+                x,
+                y: y + dest.height,
+            },
+            Vector2 {
+                x: x + dest.width,
+                y: y + dest.height,
+            },
+        )
+    } else {
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let (dx, dy) = (-origin.x, -origin.y);
+        let corner = |ox: f32, oy: f32| Vector2 {
+            x: dest.x + ox * cos - oy * sin,
+            y: dest.y + ox * sin + oy * cos,
+        };
+        (
+            corner(dx, dy),
+            corner(dx + dest.width, dy),
+            corner(dx, dy + dest.height),
+            corner(dx + dest.width, dy + dest.height),
+        )
+    };
+
+    let left = src.x / tex_width;
+    let right = (src.x + src.width) / tex_width;
+    let top = src.y / tex_height;
+    let bottom = (src.y + src.height) / tex_height;
+    let (left, right) = if flip_x { (right, left) } else { (left, right) };
+
+    Quad {
+        corners: [top_left, bottom_left, bottom_right, top_right],
+        uvs: [
+            Vector2 { x: left, y: top },
+            Vector2 { x: left, y: bottom },
+            Vector2 {
+                x: right,
+                y: bottom,
+            },
+            Vector2 { x: right, y: top },
+        ],
+        color,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: Vector2, b: Vector2) -> bool {
+        (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4
+    }
+
+    #[test]
+    fn unrotated_quad_is_offset_by_origin_and_maps_source_uvs() {
+        let quad = textured_quad(
+            64.0,
+            32.0,
+            Rectangle::new(16.0, 8.0, 16.0, 8.0),
+            Rectangle::new(100.0, 50.0, 32.0, 16.0),
+            Vector2 { x: 16.0, y: 8.0 },
+            0.0,
+            Color::WHITE,
+        );
+        assert_eq!(quad.corners[0], Vector2 { x: 84.0, y: 42.0 });
+        assert_eq!(quad.corners[2], Vector2 { x: 116.0, y: 58.0 });
+        assert_eq!(quad.uvs[0], Vector2 { x: 0.25, y: 0.25 });
+        assert_eq!(quad.uvs[2], Vector2 { x: 0.5, y: 0.5 });
+    }
+
+    #[test]
+    fn negative_source_size_flips_uvs() {
+        let quad = textured_quad(
+            64.0,
+            32.0,
+            Rectangle::new(16.0, 8.0, -16.0, -8.0),
+            Rectangle::new(0.0, 0.0, 16.0, 8.0),
+            Vector2::zero(),
+            0.0,
+            Color::WHITE,
+        );
+        // Horizontal flip swaps left/right, vertical flip swaps top/bottom.
+        assert_eq!(quad.uvs[0], Vector2 { x: 0.5, y: 0.5 });
+        assert_eq!(quad.uvs[2], Vector2 { x: 0.25, y: 0.25 });
+    }
+
+    #[test]
+    fn rotated_quad_turns_around_origin() {
+        let quad = textured_quad(
+            16.0,
+            16.0,
+            Rectangle::new(0.0, 0.0, 16.0, 16.0),
+            Rectangle::new(10.0, 10.0, 16.0, 16.0),
+            Vector2 { x: 8.0, y: 8.0 },
+            90.0,
+            Color::WHITE,
+        );
+        // 90° clockwise (screen space): top-left corner (-8,-8) -> (8,-8).
+        assert!(approx(quad.corners[0], Vector2 { x: 18.0, y: 2.0 }));
+        assert!(approx(quad.corners[2], Vector2 { x: 2.0, y: 18.0 }));
+    }
+}
@@ -1,9 +1,12 @@
 use raylib::prelude::Vector2;
 
+use crate::resources::assetusage::{AssetUsage, format_bytes};
 use crate::resources::camera2d::Camera2DRes;
 use crate::resources::camerafollowconfig::CameraFollowConfig;
 use crate::resources::debugoverlayconfig::DebugOverlayConfig;
+use crate::resources::ecsstats::EcsStats;
 use crate::resources::fontstore::FontStore;
+use crate::resources::framearena::FrameArenaStats;
 use crate::resources::gameconfig::GameConfig;
 use crate::resources::input::InputState;
 use crate::resources::scenemanager::SceneManager;
@@ -26,6 +29,9 @@ pub(super) fn draw_imgui_debug(
     scene_manager: Option<&SceneManager>,
     textures: &TextureStore,
     fonts: &FontStore,
+    asset_usage: &AssetUsage,
+    ecs_stats: &EcsStats,
+    frame_arena: FrameArenaStats,
     shader_count: usize,
     screensize: &ScreenSize,
     window_size: &WindowSize,
@@ -41,9 +47,10 @@ pub(super) fn draw_imgui_debug(
     game_mouse_pos: Vector2,
     mouse_world: Vector2,
 ) {
-    draw_performance_panel(ui, fps, world_time);
+    draw_performance_panel(ui, fps, world_time, frame_arena);
     draw_ecs_panel(
         ui,
+        ecs_stats,
         sprite_count,
         collider_count,
         position_count,
@@ -54,6 +61,7 @@ pub(super) fn draw_imgui_debug(
         fonts.len(),
         shader_count,
     );
+    draw_asset_usage_panel(ui, asset_usage, textures, fonts);
     draw_camera_panel(ui, camera, camera_follow);
     draw_world_signals_panel(ui, world_signals);
     draw_input_panel(ui, input_state);
@@ -69,7 +77,12 @@ pub(super) fn draw_imgui_debug(
     );
 }
 
-pub(super) fn draw_performance_panel(ui: &ImguiUi, fps: u32, world_time: &WorldTime) {
+pub(super) fn draw_performance_panel(
+    ui: &ImguiUi,
+    fps: u32,
+    world_time: &WorldTime,
+    frame_arena: FrameArenaStats,
+) {
     ui.window("Performance")
         .collapsed(false, Condition::FirstUseEver)
         .build(|| {
@@ -78,6 +91,12 @@ pub(super) fn draw_performance_panel(ui: &ImguiUi, fps: u32, world_time: &WorldT
             ui.text(format!("Elapsed: {:.2} s", world_time.elapsed));
             ui.text(format!("Frame: {}", world_time.frame_count));
             ui.text(format!("Time scale: {:.2}x", world_time.time_scale));
+            ui.text(format!(
+                "Frame arena: {} buffers, {} / {}",
+                frame_arena.allocations,
+                format_bytes(frame_arena.bytes_used),
+                format_bytes(frame_arena.capacity)
+            ));
             ui.separator();
             ui.text("Press F11 to toggle debug");
         });
@@ -86,6 +105,7 @@ pub(super) fn draw_performance_panel(ui: &ImguiUi, fps: u32, world_time: &WorldT
 #[allow(clippy::too_many_arguments)]
 pub(super) fn draw_ecs_panel(
     ui: &ImguiUi,
+    ecs_stats: &EcsStats,
     sprite_count: usize,
     collider_count: usize,
     position_count: usize,
@@ -99,6 +119,14 @@ pub(super) fn draw_ecs_panel(
     ui.window("ECS")
         .collapsed(true, Condition::FirstUseEver)
         .build(|| {
+            ui.text(format!(
+                "Entities: {}  Archetypes: {}",
+                ecs_stats.entities, ecs_stats.archetypes
+            ));
+            ui.text(format!(
+                "Last frame: +{} spawned, -{} despawned",
+                ecs_stats.spawned, ecs_stats.despawned
+            ));
             if ui.collapsing_header("Entities", TreeNodeFlags::empty()) {
                 ui.text(format!("  Map sprites:    {}", sprite_count));
                 ui.text(format!("  Colliders:      {}", collider_count));
@@ -112,6 +140,62 @@ pub(super) fn draw_ecs_panel(
                 ui.text(format!("  Fonts:    {}", font_count));
                 ui.text(format!("  Shaders:  {}", shader_count));
             }
+            if ui.collapsing_header(
+                format!(
+                    "Component memory ({})",
+                    format_bytes(ecs_stats.component_bytes())
+                ),
+                TreeNodeFlags::empty(),
+            ) {
+                for component in &ecs_stats.components {
+                    ui.text(format!(
+                        "  {:>10} x{:<6} {}",
+                        format_bytes(component.bytes),
+                        component.entities,
+                        component.name
+                    ));
+                }
+            }
+        });
+}
+
+/// Lists loaded assets with their memory estimate, entity references and
+/// last use. The report is only built while the window is expanded.
+pub(super) fn draw_asset_usage_panel(
+    ui: &ImguiUi,
+    asset_usage: &AssetUsage,
+    textures: &TextureStore,
+    fonts: &FontStore,
+) {
+    ui.window("Asset Usage")
+        .collapsed(true, Condition::FirstUseEver)
+        .build(|| {
+            let report = asset_usage.report(textures, fonts);
+            ui.text(format!(
+                "{} assets, {} estimated",
+                report.rows.len(),
+                format_bytes(report.total_bytes())
+            ));
+            ui.text("Unreferenced textures and fonts are leak candidates.");
+            for row in &report.rows {
+                let refs = row.references.map_or("-".to_string(), |r| r.to_string());
+                let last = row
+                    .last_used_frame
+                    .map_or("never".to_string(), |frame| frame.to_string());
+                let line = format!(
+                    "{:<8} {:>10} refs {:>3} last {:>7}  {}",
+                    row.kind.label(),
+                    format_bytes(row.bytes),
+                    refs,
+                    last,
+                    row.key
+                );
+                if row.references == Some(0) {
+                    ui.text_colored([1.0, 0.6, 0.2, 1.0], line);
+                } else {
+                    ui.text(line);
+                }
+            }
         });
 }
 
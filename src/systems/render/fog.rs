@@ -0,0 +1,45 @@
+use raylib::prelude::*;
+
+use crate::resources::fogofwar::FogOfWar;
+
+/// Darken the fog-of-war cells inside the view, in world space.
+///
+/// Adjacent cells of a row with the same overlay colour are merged into one
+/// rectangle.
+pub(super) fn draw_fog(
+    d: &mut impl RaylibDraw,
+    fog: &FogOfWar,
+    view_min: Vector2,
+    view_max: Vector2,
+) {
+    let size = fog.cell_size();
+    let (min_x, min_y) = fog.cell_at(view_min);
+    let (max_x, max_y) = fog.cell_at(view_max);
+    for y in min_y..=max_y {
+        let mut run: Option<(i32, Color)> = None;
+        for x in min_x..=max_x + 1 {
+            let color = if x <= max_x {
+                fog.overlay_color((x, y))
+            } else {
+                None
+            };
+            if let Some((start, run_color)) = run
+                && color != Some(run_color)
+            {
+                d.draw_rectangle_rec(
+                    Rectangle {
+                        x: start as f32 * size,
+                        y: y as f32 * size,
+                        width: (x - start) as f32 * size,
+                        height: size,
+                    },
+                    run_color,
+                );
+                run = None;
+            }
+            if run.is_none() {
+                run = color.map(|color| (x, color));
+            }
+        }
+    }
+}
@@ -156,7 +156,7 @@ pub(super) fn compute_view_bounds(
 /// For rotated sprites, uses a bounding circle (conservative but fast): the radius is the
 /// distance from the anchor to the farthest corner of the scaled sprite, and the AABB is
 /// expanded to contain that circle. For non-rotated sprites, returns the tight scaled AABB.
-pub(super) fn compute_sprite_cull_bounds(
+pub fn compute_sprite_cull_bounds(
     pos: &MapPosition,
     sprite: &Sprite,
     scale: Option<&Scale>,
@@ -275,7 +275,6 @@ pub(super) fn draw_rotated_rect_lines(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Arc;
 
     const EPSILON: f32 = 1e-6;
 
@@ -285,7 +284,7 @@ mod tests {
 
     fn make_sprite(w: f32, h: f32, origin_x: f32, origin_y: f32) -> Sprite {
         Sprite {
-            tex_key: Arc::from("test"),
+            tex_key: "test".into(),
             width: w,
             height: h,
             offset: Vector2 { x: 0.0, y: 0.0 },
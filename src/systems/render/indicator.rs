@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use super::inventory::measure_text;
+use super::{
+    ScreenArrowBufferItem, ScreenDrawItem, ScreenSpriteBufferItem, ScreenTextBufferItem,
+    warn_missing_theme,
+};
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::offscreenindicator::OffscreenIndicator;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::sprite::Sprite;
+use crate::components::zindex::ZIndex;
+use crate::resources::camera2d::Camera2DRes;
+use crate::resources::fontstore::FontStore;
+use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::texturestore::TextureStore;
+
+/// Indicators draw above gameplay sprites, just below interaction prompts.
+const INDICATOR_Z_INDEX: ZIndex = ZIndex(f32::MAX / 2.0);
+
+/// Space between the arrow base and the icon or distance text.
+const INDICATOR_GAP: f32 = 2.0;
+
+/// Queue the arrow, icon and distance of every [`OffscreenIndicator`] whose
+/// entity is outside the `screen` area.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn push_offscreen_indicator_items(
+    buffer: &mut Vec<ScreenDrawItem>,
+    indicators: &Query<(
+        &OffscreenIndicator,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+    )>,
+    camera: &Camera2DRes,
+    screen: Vector2,
+    gui_theme_store: &GuiThemeStore,
+    gui_theme_warn_cache: &mut GuiThemeWarnCache,
+    textures: &TextureStore,
+    fonts: &FontStore,
+) {
+    for (indicator, p, gt) in indicators.iter() {
+        let world_pos = gt.map_or(p.pos, |gt| gt.position);
+        let Some((tip, dir)) = indicator.edge_placement(camera.world_to_screen(world_pos), screen)
+        else {
+            continue;
+        };
+        let base = tip - dir * indicator.size;
+        let side = Vector2::new(-dir.y, dir.x) * (indicator.size * 0.5);
+        buffer.push(ScreenDrawItem::Arrow(ScreenArrowBufferItem {
+            points: counter_clockwise([tip, base + side, base - side]),
+            color: indicator.color,
+            z_index: INDICATOR_Z_INDEX,
+        }));
+
+        // Icon and distance stack inward from the arrow base.
+        let mut inner = base - dir * INDICATOR_GAP;
+        if let Some((key, tex)) = indicator
+            .icon
+            .as_ref()
+            .and_then(|key| textures.get(key).map(|tex| (key, tex)))
+        {
+            let size = Vector2::new(tex.width as f32, tex.height as f32);
+            let center = inner - dir * (size.x.max(size.y) * 0.5);
+            buffer.push(ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
+                sprite: Sprite {
+                    tex_key: *key,
+                    width: size.x,
+                    height: size.y,
+                    offset: Vector2::zero(),
+                    origin: Vector2::zero(),
+                    flip_h: false,
+                    flip_v: false,
+                },
+                z_index: INDICATOR_Z_INDEX,
+                pos: ScreenPosition::new(center.x - size.x * 0.5, center.y - size.y * 0.5),
+                maybe_tint: None,
+                maybe_shadow: None,
+            }));
+            inner = center - dir * (size.x.max(size.y) * 0.5 + INDICATOR_GAP);
+        }
+
+        let Some(unit) = indicator.distance_unit else {
+            continue;
+        };
+        let Some(theme) = gui_theme_store.get(&indicator.theme_key) else {
+            warn_missing_theme(
+                gui_theme_warn_cache,
+                "OffscreenIndicator",
+                &indicator.theme_key,
+                " — skipping distance text",
+            );
+            continue;
+        };
+        let distance = (world_pos - camera.0.target).length() / unit;
+        let text: Arc<str> = Arc::from(format!("{distance:.0}").as_str());
+        let size = measure_text(fonts, &theme.font, &text, theme.font_size);
+        let center = inner - dir * (size.x.max(size.y) * 0.5);
+        buffer.push(ScreenDrawItem::Text(ScreenTextBufferItem {
+            text,
+            font: Arc::clone(&theme.font),
+            font_size: theme.font_size,
+            color: theme.text_color,
+            size,
+            z_index: INDICATOR_Z_INDEX,
+            pos: ScreenPosition::new(center.x - size.x * 0.5, center.y - size.y * 0.5),
+            maybe_tint: None,
+            maybe_shadow: theme.text_shadow,
+        }));
+    }
+}
+
+/// Orders a triangle's corners counter-clockwise on screen (y down), as
+/// raylib's `DrawTriangle` requires.
+fn counter_clockwise([a, b, c]: [Vector2; 3]) -> [Vector2; 3] {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    if cross > 0.0 { [a, c, b] } else { [a, b, c] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangles_are_wound_like_raylib_expects() {
+        // raylib's own example triangle: top, bottom-left, bottom-right.
+        let top = Vector2::new(100.0, 80.0);
+        let left = Vector2::new(40.0, 150.0);
+        let right = Vector2::new(160.0, 150.0);
+        assert_eq!(counter_clockwise([top, left, right]), [top, left, right]);
+        assert_eq!(counter_clockwise([top, right, left]), [top, left, right]);
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use super::inventory::measure_text;
+use super::{
+    ScreenDrawItem, ScreenSpriteBufferItem, ScreenTextBufferItem, screen_panel_item,
+    warn_missing_theme,
+};
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::interactable::Interactable;
+use crate::components::mapposition::MapPosition;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::sprite::Sprite;
+use crate::components::zindex::ZIndex;
+use crate::resources::camera2d::Camera2DRes;
+use crate::resources::fontstore::FontStore;
+use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::texturestore::TextureStore;
+
+/// Space around the prompt contents and between its icon and text.
+const PROMPT_PADDING: f32 = 4.0;
+
+/// Prompts draw above every other screen-space item.
+const PROMPT_Z_INDEX: ZIndex = ZIndex(f32::MAX);
+
+/// Queue the prompt of every focused [`Interactable`]: the theme's `label`
+/// nine-patch (its `panel` when the theme has none) sized to the optional
+/// icon and the text, centred above the entity's prompt anchor.
+pub(super) fn push_interaction_prompt_items(
+    buffer: &mut Vec<ScreenDrawItem>,
+    interactables: &Query<(&Interactable, &MapPosition, Option<&GlobalTransform2D>)>,
+    camera: &Camera2DRes,
+    gui_theme_store: &GuiThemeStore,
+    gui_theme_warn_cache: &mut GuiThemeWarnCache,
+    textures: &TextureStore,
+    fonts: &FontStore,
+) {
+    for (interactable, p, gt) in interactables.iter().filter(|(i, ..)| i.focused) {
+        let Some(theme) = gui_theme_store.get(&interactable.theme_key) else {
+            warn_missing_theme(
+                gui_theme_warn_cache,
+                "Interactable",
+                &interactable.theme_key,
+                " — skipping interaction prompt",
+            );
+            continue;
+        };
+        let world_pos = gt.map_or(p.pos, |gt| gt.position);
+        let anchor = camera.world_to_screen(world_pos + interactable.prompt_offset);
+
+        let icon_size = interactable
+            .icon
+            .as_ref()
+            .and_then(|key| textures.get(key))
+            .map(|tex| Vector2::new(tex.width as f32, tex.height as f32));
+        let text: Arc<str> = Arc::from(interactable.prompt.as_str());
+        let text_size = measure_text(fonts, &theme.font, &text, theme.font_size);
+        let icon_w = icon_size.map_or(0.0, |s| s.x + PROMPT_PADDING);
+        let content = Vector2::new(
+            icon_w + text_size.x,
+            text_size.y.max(icon_size.map_or(0.0, |s| s.y)),
+        );
+        let dest = Rectangle {
+            x: anchor.x - content.x * 0.5 - PROMPT_PADDING,
+            y: anchor.y - content.y - 2.0 * PROMPT_PADDING,
+            width: content.x + 2.0 * PROMPT_PADDING,
+            height: content.y + 2.0 * PROMPT_PADDING,
+        };
+        let patch = theme.label.as_ref().unwrap_or(&theme.panel);
+        buffer.push(screen_panel_item(
+            patch.clone(),
+            dest,
+            PROMPT_Z_INDEX,
+            theme.panel_shadow,
+        ));
+
+        let left = dest.x + PROMPT_PADDING;
+        let middle = dest.y + dest.height * 0.5;
+        if let (Some(key), Some(size)) = (&interactable.icon, icon_size) {
+            buffer.push(ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
+                sprite: Sprite {
+                    tex_key: *key,
+                    width: size.x,
+                    height: size.y,
+                    offset: Vector2::zero(),
+                    origin: Vector2::zero(),
+                    flip_h: false,
+                    flip_v: false,
+                },
+                z_index: PROMPT_Z_INDEX,
+                pos: ScreenPosition::new(left, middle - size.y * 0.5),
+                maybe_tint: None,
+                maybe_shadow: None,
+            }));
+        }
+        buffer.push(ScreenDrawItem::Text(ScreenTextBufferItem {
+            text,
+            font: Arc::clone(&theme.font),
+            font_size: theme.font_size,
+            color: theme.text_color,
+            size: text_size,
+            z_index: PROMPT_Z_INDEX,
+            pos: ScreenPosition::new(left + icon_w, middle - text_size.y * 0.5),
+            maybe_tint: None,
+            maybe_shadow: theme.text_shadow,
+        }));
+    }
+}
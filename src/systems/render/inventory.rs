@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use bevy_ecs::prelude::Query;
+use raylib::ffi;
+use raylib::prelude::*;
+
+use super::{
+    ScreenDrawItem, ScreenSpriteBufferItem, ScreenTextBufferItem, screen_panel_item,
+    warn_missing_theme,
+};
+use crate::components::inventory::{GuiInventoryGrid, Inventory};
+use crate::components::screenposition::ScreenPosition;
+use crate::components::sprite::Sprite;
+use crate::components::zindex::ZIndex;
+use crate::resources::fontstore::FontStore;
+use crate::resources::guitheme::{GuiThemeStore, GuiThemeWarnCache};
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::texturestore::TextureStore;
+
+/// Gap between a stack count and the bottom-right corner of its slot.
+const COUNT_MARGIN: f32 = 2.0;
+
+/// Queue the draw items of every [`GuiInventoryGrid`]: one nine-patch per
+/// slot, the item icons centred at their native size and the stack counts
+/// (above 1) in the bottom-right corner, all at the grid's [`ZIndex`] so the
+/// panel/sprite/text tie-break stacks them correctly.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn push_inventory_grid_items(
+    buffer: &mut Vec<ScreenDrawItem>,
+    grids: &Query<(&GuiInventoryGrid, &ScreenPosition, &ZIndex)>,
+    inventories: &Query<&Inventory>,
+    items: &ItemRegistry,
+    gui_theme_store: &GuiThemeStore,
+    gui_theme_warn_cache: &mut GuiThemeWarnCache,
+    textures: &TextureStore,
+    fonts: &FontStore,
+) {
+    for (grid, p, z) in grids.iter() {
+        let Ok(inventory) = inventories.get(grid.inventory) else {
+            continue;
+        };
+        let Some(theme) = gui_theme_store.get(&grid.theme_key) else {
+            warn_missing_theme(
+                gui_theme_warn_cache,
+                "GuiInventoryGrid",
+                &grid.theme_key,
+                " — skipping inventory grid",
+            );
+            continue;
+        };
+        let patch = theme.label.as_ref().unwrap_or(&theme.panel);
+        for (index, slot) in inventory.slots().iter().enumerate() {
+            let cell = grid.cell_rect(p.pos, index);
+            buffer.push(screen_panel_item(
+                patch.clone(),
+                cell,
+                *z,
+                theme.panel_shadow,
+            ));
+            let Some(stack) = slot else {
+                continue;
+            };
+            if let Some(icon) = items.get(&stack.item).and_then(|def| def.icon.as_ref()) {
+                let source = icon.source.or_else(|| {
+                    textures
+                        .get(&icon.tex_key)
+                        .map(|tex| Rectangle::new(0.0, 0.0, tex.width as f32, tex.height as f32))
+                });
+                if let Some(source) = source {
+                    buffer.push(ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
+                        sprite: Sprite {
+                            tex_key: icon.tex_key,
+                            width: source.width,
+                            height: source.height,
+                            offset: Vector2::new(source.x, source.y),
+                            origin: Vector2::zero(),
+                            flip_h: false,
+                            flip_v: false,
+                        },
+                        z_index: *z,
+                        pos: ScreenPosition::new(
+                            cell.x + (cell.width - source.width) * 0.5,
+                            cell.y + (cell.height - source.height) * 0.5,
+                        ),
+                        maybe_tint: None,
+                        maybe_shadow: None,
+                    }));
+                }
+            }
+            if stack.count > 1 {
+                let text: Arc<str> = Arc::from(stack.count.to_string());
+                let size = measure_text(fonts, &theme.font, &text, theme.font_size);
+                buffer.push(ScreenDrawItem::Text(ScreenTextBufferItem {
+                    text,
+                    font: Arc::clone(&theme.font),
+                    font_size: theme.font_size,
+                    color: theme.text_color,
+                    size,
+                    z_index: *z,
+                    pos: ScreenPosition::new(
+                        cell.x + cell.width - size.x - COUNT_MARGIN,
+                        cell.y + cell.height - size.y - COUNT_MARGIN,
+                    ),
+                    maybe_tint: None,
+                    maybe_shadow: theme.text_shadow,
+                }));
+            }
+        }
+    }
+}
+
+/// Size of `text` in `font`, zero if the font is missing.
+pub(super) fn measure_text(fonts: &FontStore, font: &str, text: &str, font_size: f32) -> Vector2 {
+    let Some(font) = fonts.get_or_fallback(font) else {
+        return Vector2::zero();
+    };
+    let Ok(c_text) = std::ffi::CString::new(text) else {
+        return Vector2::zero();
+    };
+    let measured = unsafe { ffi::MeasureTextEx(**font, c_text.as_ptr(), font_size, 1.0) };
+    Vector2::new(measured.x, measured.y)
+}
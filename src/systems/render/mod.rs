@@ -5,37 +5,91 @@
 //! letterboxing/pillarboxing to preserve aspect ratio.
 //!
 //! World-space rendering uses the shared [`Camera2DRes`] to transform between
-//! world and screen coordinates.
+//! world and screen coordinates, with the [`CameraEffects`] shake, zoom and
+//! pan layered on top.
+//! World sprites without an entity shader (particles, tilemap tiles) are
+//! submitted in batched runs per texture (see [`batch`]); sprites sharing a
+//! [`ZIndex`] are sorted by texture so each texture forms one run. The number
+//! of runs per frame is reported on the `render_sprite_batches` Tracy plot.
+//! A sprite's [`BlobShadow`] ellipse is drawn right before the sprite.
+//! World-space [`NineSlice`] panels, then [`ProgressBar`]s and
+//! [`RadialProgress`] rings, are drawn over world sprites, texts, ropes and
+//! water, each sorted by [`ZIndex`] among themselves. Rain and snow
+//! particles of the [`Weather`] are drawn over those unless the `weather`
+//! [`SystemToggles`] entry is off. The [`WorldClock`] day/night overlay
+//! darkens all of those, and filled [`VisibilityPolygon`]s (light and vision
+//! cones) are drawn over it.
+//! When the [`FogOfWar`] is enabled, its overlay is drawn over those, below
+//! debug shapes and screen-space UI. [`CinematicMode`] letterbox bars are
+//! drawn between the world and screen-space passes. The fade of a running
+//! [`PortalTransition`] covers everything, screen-space UI included.
+//!
+//! Screen-space entities ([`ScreenPosition`]) are drawn in their own pass
+//! after the whole world pass, sorted by [`ZIndex`] among themselves. A
+//! screen-space `ZIndex` never interleaves with world sprites: UI with a
+//! negative `ZIndex` still draws over the world, and an entity that also
+//! carries a [`MapPosition`] is left out of the world pass (see
+//! [`WorldPassFilter`]). Sprites and texts marked [`Hidden`] are left out of
+//! both passes.
+//!
+//! In [`RenderMode::DirtyRects`](crate::resources::rendermode::RenderMode)
+//! the render target keeps last frame's content: the pass is clipped to the
+//! [`DirtyRegion`] with a scissor rectangle, or skipped entirely when the
+//! region is clean. Post-processing and the final blit still run every frame.
 //!
 //! When the active scene descriptor provides a [`GuiCallback`], an ImGui frame
 //! is opened every render pass and the callback is invoked. This path is
 //! independent of [`DebugMode`] and is intended for persistent game-developer UI
 //! (HUDs, in-game editors, tool windows).
 
+mod batch;
 mod debug_overlay;
+mod fog;
 pub mod geometry;
 mod gui_panel;
+mod indicator;
+mod interaction;
+mod inventory;
+mod nineslice;
 mod postprocess;
+mod progress;
+mod rope;
 mod sprite;
 mod text;
+mod visibility;
+mod water;
+mod weather;
+pub mod worlds;
 
 use std::sync::Arc;
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemParam;
+use raylib::ffi;
 use raylib::prelude::*;
 
+use crate::components::blobshadow::{BlobShadow, BlobShadowShape};
 use crate::components::boxcollider::BoxCollider;
 use crate::components::dynamictext::DynamicText;
 use crate::components::entityshader::EntityShader;
 use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::height::Height;
 use crate::components::guibutton::GuiButton;
 use crate::components::guiinteractable::{GuiInteractable, GuiWidgetState};
 use crate::components::guilabel::GuiLabel;
 use crate::components::guiprogressbar::{GuiProgressBar, ProgressBarDirection};
 use crate::components::guiwindow::GuiWindow;
+use crate::components::hidden::Hidden;
+use crate::components::interactable::Interactable;
+use crate::components::inventory::{GuiInventoryGrid, Inventory};
 use crate::components::mapposition::MapPosition;
+use crate::components::offscreenindicator::OffscreenIndicator;
+use crate::components::paletteswap::{PALETTE_SWAP_FS, PALETTE_SWAP_SHADER_KEY, PaletteSwap};
+use crate::components::nineslice::NineSlice;
+use crate::components::progress::{ProgressBar, RadialProgress};
+use crate::components::renderlayer::RenderLayer;
 use crate::components::rigidbody::RigidBody;
+use crate::components::rope::Rope;
 use crate::components::rotation::Rotation;
 use crate::components::scale::Scale;
 use crate::components::screenposition::ScreenPosition;
@@ -43,40 +97,65 @@ use crate::components::signals::Signals;
 use crate::components::sprite::Sprite;
 use crate::components::shadow::Shadow;
 use crate::components::tint::Tint;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+use crate::components::watersurface::WaterSurface;
+use crate::components::weather::WeatherParticle;
 use crate::components::zindex::ZIndex;
 use crate::resources::appstate::AppState;
+use crate::resources::assetusage::AssetUsage;
 use crate::resources::camera2d::Camera2DRes;
+use crate::resources::cameraeffects::CameraEffects;
 use crate::resources::camerafollowconfig::CameraFollowConfig;
+use crate::resources::cinematic::CinematicMode;
+use crate::resources::colorgrading::{COLOR_LUT_FS, COLOR_LUT_SHADER_KEY, ColorGrading};
 use crate::resources::debugmode::DebugMode;
 use crate::resources::debugoverlayconfig::DebugOverlayConfig;
+use crate::resources::ecsstats::EcsStats;
+use crate::resources::fogofwar::FogOfWar;
+use crate::resources::framearena::FrameArena;
 use crate::resources::fontstore::FontStore;
 use crate::resources::gameconfig::GameConfig;
 use crate::resources::guitheme::{GuiButtonSkin, GuiNinePatch, GuiThemeStore, GuiThemeWarnCache};
 use crate::resources::imgui_bridge::ImguiBridge;
 use crate::resources::input::InputState;
+use crate::resources::itemregistry::ItemRegistry;
+use crate::resources::layervisibility::LayerVisibility;
+use crate::resources::portaltransition::PortalTransition;
 use crate::resources::postprocessshader::PostProcessShader;
+use crate::resources::rendermode::DirtyRegion;
 use crate::resources::rendertarget::RenderTarget;
 use crate::resources::scenemanager::SceneManager;
 use crate::resources::screensize::ScreenSize;
 use crate::resources::shaderstore::ShaderStore;
+use crate::resources::systemtoggles::{SystemToggles, ToggleableSystem};
 use crate::resources::texturestore::TextureStore;
+use crate::resources::weather::Weather;
 use crate::resources::windowsize::WindowSize;
+use crate::resources::worldclock::WorldClock;
 use crate::resources::worldsignals::WorldSignals;
 use crate::resources::worldtime::WorldTime;
 use crate::systems::scene_dispatch::GuiCallback;
 use log::warn;
 
+use self::batch::QuadBatch;
 use self::debug_overlay::draw_imgui_debug;
 use self::geometry::{
     compute_sprite_cull_bounds, compute_sprite_geometry, compute_view_bounds,
     draw_rotated_rect_lines, resolve_world_transform,
 };
 use self::postprocess::{
-    apply_postprocess_passes, set_entity_uniforms, set_standard_uniforms, set_uniform_value,
+    ColorLutPass, SamplerBinding, apply_postprocess_passes, ensure_builtin_shader, sampler_binding,
+    set_entity_uniforms, set_standard_uniforms, set_uniform_value,
 };
 use self::gui_panel::draw_screen_panel_item;
+use self::indicator::push_offscreen_indicator_items;
+use self::interaction::push_interaction_prompt_items;
+use self::progress::{ProgressBufferItem, draw_progress_item};
+use self::inventory::push_inventory_grid_items;
+use self::nineslice::{NineSliceBufferItem, draw_nine_slice_item};
 use self::sprite::draw_screen_sprite_item;
 use self::text::draw_screen_text_item;
+use self::worlds::resolve_sprite_texture;
 
 type MapSpriteQueryData = (
     Entity,
@@ -89,6 +168,10 @@ type MapSpriteQueryData = (
     Option<&'static Tint>,
     Option<&'static Shadow>,
     Option<&'static GlobalTransform2D>,
+    Option<&'static RenderLayer>,
+    Option<&'static PaletteSwap>,
+    Option<&'static BlobShadow>,
+    Option<&'static Height>,
 );
 
 type MapTextQueryData = (
@@ -100,13 +183,73 @@ type MapTextQueryData = (
     Option<&'static Tint>,
     Option<&'static Shadow>,
     Option<&'static GlobalTransform2D>,
+    Option<&'static RenderLayer>,
 );
 
-type ScreenSpriteQueryData =
-    (&'static Sprite, &'static ScreenPosition, &'static ZIndex, Option<&'static Tint>, Option<&'static Shadow>);
+/// Keeps screen-space and [`Hidden`] entities out of the world pass, so an
+/// entity carrying both a [`MapPosition`] and a [`ScreenPosition`] is drawn
+/// once, as UI. Shared with the dirty-rectangle tracker, which must agree on
+/// what is drawn where.
+pub(crate) type WorldPassFilter = (Without<ScreenPosition>, Without<Hidden>);
+
+/// Keeps [`Hidden`] entities out of the screen-space pass. Shared with the
+/// dirty-rectangle tracker like [`WorldPassFilter`].
+pub(crate) type ScreenPassFilter = Without<Hidden>;
+
+type ScreenSpriteQueryData = (
+    &'static Sprite,
+    &'static ScreenPosition,
+    &'static ZIndex,
+    Option<&'static Tint>,
+    Option<&'static Shadow>,
+    Option<&'static RenderLayer>,
+);
 
-type ScreenTextQueryData =
-    (&'static DynamicText, &'static ScreenPosition, &'static ZIndex, Option<&'static Tint>, Option<&'static Shadow>);
+/// Entities with a [`ProgressBar`], a [`RadialProgress`] or both.
+pub(crate) type ProgressFilter = Or<(With<ProgressBar>, With<RadialProgress>)>;
+
+type MapProgressQueryData = (
+    Option<&'static ProgressBar>,
+    Option<&'static RadialProgress>,
+    &'static MapPosition,
+    Option<&'static GlobalTransform2D>,
+    &'static ZIndex,
+    Option<&'static RenderLayer>,
+);
+
+type ScreenProgressQueryData = (
+    Option<&'static ProgressBar>,
+    Option<&'static RadialProgress>,
+    &'static ScreenPosition,
+    &'static ZIndex,
+    Option<&'static RenderLayer>,
+);
+
+type MapNineSliceQueryData = (
+    &'static NineSlice,
+    &'static MapPosition,
+    Option<&'static GlobalTransform2D>,
+    &'static ZIndex,
+    Option<&'static Tint>,
+    Option<&'static RenderLayer>,
+);
+
+type ScreenNineSliceQueryData = (
+    &'static NineSlice,
+    &'static ScreenPosition,
+    &'static ZIndex,
+    Option<&'static Tint>,
+    Option<&'static RenderLayer>,
+);
+
+type ScreenTextQueryData = (
+    &'static DynamicText,
+    &'static ScreenPosition,
+    &'static ZIndex,
+    Option<&'static Tint>,
+    Option<&'static Shadow>,
+    Option<&'static RenderLayer>,
+);
 
 pub(super) struct SpriteBufferItem {
     entity: Entity,
@@ -118,6 +261,29 @@ pub(super) struct SpriteBufferItem {
     maybe_shader: Option<EntityShader>,
     maybe_tint: Option<Tint>,
     maybe_shadow: Option<Shadow>,
+    maybe_palette_swap: Option<PaletteSwap>,
+    maybe_blob: Option<BlobShadowShape>,
+}
+
+impl SpriteBufferItem {
+    /// Whether the sprite can join a batched run: entity shaders and palette
+    /// swaps are drawn on their own.
+    fn is_batchable(&self) -> bool {
+        self.maybe_shader.is_none() && self.maybe_palette_swap.is_none()
+    }
+
+    /// Draw-order comparator: ascending `z_index`; sprites at the same
+    /// `z_index` have no defined order, so they are grouped by texture with
+    /// batchable sprites first. A tilemap whose layers mix several tilesets
+    /// then draws in one run per tileset and layer instead of breaking the run
+    /// at every texture change.
+    fn cmp_draw_order(a: &Self, b: &Self) -> std::cmp::Ordering {
+        a.z_index
+            .partial_cmp(&b.z_index)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.is_batchable().cmp(&a.is_batchable()))
+            .then_with(|| a.sprite.tex_key.id().cmp(&b.sprite.tex_key.id()))
+    }
 }
 
 pub(super) struct TextBufferItem {
@@ -163,6 +329,14 @@ pub(super) struct ScreenTextBufferItem {
     maybe_shadow: Option<Shadow>,
 }
 
+/// Screen-space filled triangle, such as an off-screen indicator arrow.
+/// `points` are ordered counter-clockwise, as `draw_triangle` requires.
+pub(super) struct ScreenArrowBufferItem {
+    points: [Vector2; 3],
+    color: Color,
+    z_index: ZIndex,
+}
+
 /// Screen-space GUI window panel draw item. Window backgrounds sit below
 /// sprites/text drawn on top of them (see [`ScreenDrawItem::variant_rank`]).
 pub(super) struct ScreenPanelBufferItem {
@@ -196,6 +370,9 @@ pub(super) enum ScreenDrawItem {
     Panel(ScreenPanelBufferItem),
     ProgressBar(ScreenProgressBarBufferItem),
     Sprite(ScreenSpriteBufferItem),
+    Arrow(ScreenArrowBufferItem),
+    Progress(ProgressBufferItem),
+    NineSlice(NineSliceBufferItem),
     Text(ScreenTextBufferItem),
 }
 
@@ -205,6 +382,9 @@ impl ScreenDrawItem {
             ScreenDrawItem::Panel(p) => p.z_index,
             ScreenDrawItem::ProgressBar(pb) => pb.z_index,
             ScreenDrawItem::Sprite(s) => s.z_index,
+            ScreenDrawItem::Arrow(a) => a.z_index,
+            ScreenDrawItem::Progress(p) => p.z_index,
+            ScreenDrawItem::NineSlice(n) => n.z_index,
             ScreenDrawItem::Text(t) => t.z_index,
         }
     }
@@ -218,11 +398,17 @@ impl ScreenDrawItem {
     ///
     /// `ProgressBar` shares rank 0 with `Panel`: the bar is an opaque
     /// background element and should appear beneath any screen-space sprite or
-    /// text at the same `ZIndex`.
+    /// text at the same `ZIndex`. A plain `Progress` bar or ring ranks with
+    /// sprites, so it can sit on a window background and under its caption.
+    /// A `NineSlice` panel is a background too and ranks with `Panel`.
     fn variant_rank(&self) -> u8 {
         match self {
-            ScreenDrawItem::Panel(_) | ScreenDrawItem::ProgressBar(_) => 0,
-            ScreenDrawItem::Sprite(_) => 1,
+            ScreenDrawItem::Panel(_)
+            | ScreenDrawItem::ProgressBar(_)
+            | ScreenDrawItem::NineSlice(_) => 0,
+            ScreenDrawItem::Sprite(_) | ScreenDrawItem::Arrow(_) | ScreenDrawItem::Progress(_) => {
+                1
+            }
             ScreenDrawItem::Text(_) => 2,
         }
     }
@@ -241,8 +427,14 @@ impl ScreenDrawItem {
 #[derive(Default)]
 pub struct RenderLocals {
     sprite_buffer: Vec<SpriteBufferItem>,
+    sprite_batch: QuadBatch,
     text_buffer: Vec<TextBufferItem>,
+    progress_buffer: Vec<ProgressBufferItem>,
+    nine_slice_buffer: Vec<NineSliceBufferItem>,
     screen_draw_buffer: Vec<ScreenDrawItem>,
+    /// Framebuffer id of the render target drawn last frame. A recreated
+    /// target has lost its content and must be redrawn in full.
+    last_target_id: u32,
 }
 
 /// Bundled render resources to reduce system parameter count.
@@ -259,12 +451,23 @@ pub struct RenderResources<'w> {
     pub fonts: NonSend<'w, FontStore>,
     pub gui_theme_store: Res<'w, GuiThemeStore>,
     pub gui_theme_warn_cache: ResMut<'w, GuiThemeWarnCache>,
+    pub fog: Res<'w, FogOfWar>,
+    pub clock: Res<'w, WorldClock>,
+    pub weather: Res<'w, Weather>,
+    pub items: Res<'w, ItemRegistry>,
+    pub portal_transition: Res<'w, PortalTransition>,
+    pub cinematic: Res<'w, CinematicMode>,
+    pub camera_effects: Res<'w, CameraEffects>,
+    pub dirty_region: Res<'w, DirtyRegion>,
+    pub layers: Res<'w, LayerVisibility>,
+    pub color_grading: Res<'w, ColorGrading>,
+    pub toggles: Res<'w, SystemToggles>,
 }
 
 /// Bundled queries for the render system.
 #[derive(SystemParam)]
 pub struct RenderQueries<'w, 's> {
-    pub map_sprites: Query<'w, 's, MapSpriteQueryData>,
+    pub map_sprites: Query<'w, 's, MapSpriteQueryData, WorldPassFilter>,
     pub colliders: Query<
         'w,
         's,
@@ -283,10 +486,15 @@ pub struct RenderQueries<'w, 's> {
             Option<&'static GlobalTransform2D>,
         ),
     >,
-    pub map_texts: Query<'w, 's, MapTextQueryData>,
+    pub map_texts: Query<'w, 's, MapTextQueryData, WorldPassFilter>,
     pub rigidbodies: Query<'w, 's, &'static RigidBody>,
-    pub screen_texts: Query<'w, 's, ScreenTextQueryData>,
-    pub screen_sprites: Query<'w, 's, ScreenSpriteQueryData>,
+    pub palette_swaps: Query<'w, 's, (), (With<PaletteSwap>, WorldPassFilter)>,
+    pub screen_texts: Query<'w, 's, ScreenTextQueryData, ScreenPassFilter>,
+    pub screen_sprites: Query<'w, 's, ScreenSpriteQueryData, ScreenPassFilter>,
+    pub map_progress: Query<'w, 's, MapProgressQueryData, (ProgressFilter, WorldPassFilter)>,
+    pub screen_progress: Query<'w, 's, ScreenProgressQueryData, (ProgressFilter, ScreenPassFilter)>,
+    pub map_nine_slices: Query<'w, 's, MapNineSliceQueryData, WorldPassFilter>,
+    pub screen_nine_slices: Query<'w, 's, ScreenNineSliceQueryData, ScreenPassFilter>,
     pub gui_windows: Query<'w, 's, (&'static GuiWindow, &'static ScreenPosition, &'static ZIndex)>,
     pub gui_buttons: Query<
         'w,
@@ -300,6 +508,54 @@ pub struct RenderQueries<'w, 's> {
     >,
     pub gui_labels: Query<'w, 's, (&'static GuiLabel, &'static ScreenPosition, &'static ZIndex)>,
     pub gui_progress_bars: Query<'w, 's, (&'static GuiProgressBar, &'static ScreenPosition, &'static ZIndex)>,
+    pub gui_inventory_grids: Query<
+        'w,
+        's,
+        (
+            &'static GuiInventoryGrid,
+            &'static ScreenPosition,
+            &'static ZIndex,
+        ),
+    >,
+    pub inventories: Query<'w, 's, &'static Inventory>,
+    pub interactables: Query<
+        'w,
+        's,
+        (
+            &'static Interactable,
+            &'static MapPosition,
+            Option<&'static GlobalTransform2D>,
+        ),
+    >,
+    pub offscreen_indicators: Query<
+        'w,
+        's,
+        (
+            &'static OffscreenIndicator,
+            &'static MapPosition,
+            Option<&'static GlobalTransform2D>,
+        ),
+    >,
+    pub ropes: Query<'w, 's, &'static Rope>,
+    pub visibility_polygons: Query<'w, 's, &'static VisibilityPolygon>,
+    pub water_surfaces: Query<
+        'w,
+        's,
+        (
+            &'static WaterSurface,
+            &'static MapPosition,
+            Option<&'static GlobalTransform2D>,
+        ),
+    >,
+    pub weather_particles: Query<
+        'w,
+        's,
+        (
+            &'static WeatherParticle,
+            &'static MapPosition,
+            &'static RigidBody,
+        ),
+    >,
 }
 
 /// Extra resources needed for the imgui debug panels.
@@ -311,6 +567,9 @@ pub(crate) struct DebugResources<'w> {
     pub camera_follow: Res<'w, CameraFollowConfig>,
     pub scene_manager: Option<Res<'w, SceneManager>>,
     pub overlay_config: ResMut<'w, DebugOverlayConfig>,
+    pub asset_usage: Res<'w, AssetUsage>,
+    pub ecs_stats: Res<'w, EcsStats>,
+    pub frame_arena: Res<'w, FrameArena>,
 }
 
 /// Tracks which render buffer is the current source during multi-pass
@@ -328,6 +587,18 @@ fn needs_imgui(debug_active: bool, has_gui_callback: bool) -> bool {
     debug_active || has_gui_callback
 }
 
+/// Scissor rectangle `(x, y, width, height)` in render texture pixels for a
+/// dirty rectangle in logical screen pixels, rounded outward so partially
+/// covered pixels are repainted too.
+fn dirty_scissor(rect: Rectangle, scale: u32) -> (i32, i32, i32, i32) {
+    let scale = scale as f32;
+    let x0 = (rect.x * scale).floor() as i32;
+    let y0 = (rect.y * scale).floor() as i32;
+    let x1 = ((rect.x + rect.width) * scale).ceil() as i32;
+    let y1 = ((rect.y + rect.height) * scale).ceil() as i32;
+    (x0, y0, x1 - x0, y1 - y0)
+}
+
 /// Main render pass.
 ///
 /// Contract
@@ -365,8 +636,12 @@ pub fn render_system(
     let fonts = &res.fonts;
     let RenderLocals {
         sprite_buffer,
+        sprite_batch,
         text_buffer,
+        progress_buffer,
+        nine_slice_buffer,
         screen_draw_buffer,
+        last_target_id,
     } = &mut *locals;
 
     // Unpack bundled resources for easier access
@@ -375,29 +650,77 @@ pub fn render_system(
     let window_size = &res.window_size;
     let textures = &res.textures;
     let maybe_debug = &res.maybe_debug;
+    // Debug shapes and bounds are drawn on the debug layer.
+    let debug_shapes = maybe_debug.is_some() && res.layers.is_visible(RenderLayer::DEBUG);
 
-    // ========== PHASE 1: Render game content to the render target ==========
+    // Scene draw callbacks, secondary world views and a recreated target can
+    // change pixels the dirty-region tracker knows nothing about.
+    let target_id = render_target.texture.id;
+    let has_world_draw_callback = debug_res
+        .scene_manager
+        .as_deref()
+        .and_then(|sm| sm.active_scene.as_deref().and_then(|name| sm.get(name)))
+        .is_some_and(|desc| desc.world_draw_callback.is_some());
+    let dirty_region = if std::mem::replace(last_target_id, target_id) != target_id
+        || has_world_draw_callback
+        || !render_target.world_views.is_empty()
     {
+        DirtyRegion::Full
+    } else {
+        *res.dirty_region
+    };
+
+    // Palette swaps draw through a built-in shader compiled on first use.
+    let palette_swap_ready = !queries.palette_swaps.is_empty()
+        && ensure_builtin_shader(
+            rl,
+            th,
+            &mut shader_store,
+            PALETTE_SWAP_SHADER_KEY,
+            PALETTE_SWAP_FS,
+        );
+
+    // ========== PHASE 1: Render game content to the render target ==========
+    if dirty_region != DirtyRegion::Clean {
         crate::tracy::tracy_span!("render/to_texture");
-        let mut d = rl.begin_texture_mode(th, &mut render_target.texture);
+        // Supersampled targets draw through scaled cameras so world and
+        // screen coordinates keep their logical meaning.
+        // Shake, zoom and pan are layered on before snapping.
+        let effect_cam = Camera2DRes(res.camera_effects.apply(camera.0));
+        let render_cam = render_target.world_camera(if res.config.pixel_snap_camera {
+            effect_cam.pixel_snapped()
+        } else {
+            effect_cam.0
+        });
+        let screen_cam = render_target.screen_camera();
+        let (target_w, target_h) = (
+            render_target.texture_width() as f32,
+            render_target.texture_height() as f32,
+        );
+        let render_target_scale = render_target.scale;
+        let RenderTarget {
+            texture: main_texture,
+            world_views,
+            ..
+        } = &mut *render_target;
+        let mut d = rl.begin_texture_mode(th, main_texture);
+        // The clear honours the scissor, so pixels outside it keep last
+        // frame's content.
+        if let DirtyRegion::Rect(rect) = dirty_region {
+            let (x, y, w, h) = dirty_scissor(rect, render_target_scale);
+            unsafe { ffi::BeginScissorMode(x, y, w, h) };
+        }
         d.clear_background(res.config.background_color);
 
         {
             // Draw in world coordinates using Camera2D.
             crate::tracy::tracy_span!("render/world_space");
-            let render_cam = if res.config.pixel_snap_camera {
-                camera.pixel_snapped()
-            } else {
-                camera.0
-            };
             let mut d2 = d.begin_mode2D(render_cam);
 
-            let (view_min, view_max) = compute_view_bounds(
-                screensize.w as f32,
-                screensize.h as f32,
-                render_cam,
-                |pos, cam| d2.get_screen_to_world2D(pos, cam),
-            );
+            let (view_min, view_max) =
+                compute_view_bounds(target_w, target_h, render_cam, |pos, cam| {
+                    d2.get_screen_to_world2D(pos, cam)
+                });
 
             {
                 crate::tracy::tracy_span!("render/build_sprite_buffer");
@@ -414,13 +737,33 @@ pub fn render_system(
                         maybe_tint,
                         maybe_shadow,
                         maybe_gt,
+                        maybe_layer,
+                        maybe_palette_swap,
+                        maybe_blob,
+                        maybe_height,
                     )| {
-                        let (resolved_pos, resolved_scale, resolved_rot) = resolve_world_transform(
-                            *p,
-                            maybe_scale.copied(),
-                            maybe_rot.copied(),
-                            maybe_gt.copied(),
-                        );
+                        if !res.layers.shows(maybe_layer, RenderLayer::GAMEPLAY) {
+                            return None;
+                        }
+                        let (mut resolved_pos, resolved_scale, resolved_rot) =
+                            resolve_world_transform(
+                                *p,
+                                maybe_scale.copied(),
+                                maybe_rot.copied(),
+                                maybe_gt.copied(),
+                            );
+                        let ground = resolved_pos.pos;
+                        let lift = maybe_height.map_or(0.0, |height| height.0);
+                        // Lifted sprites are drawn higher; their drop shadow
+                        // stays on the ground.
+                        resolved_pos.pos.y -= lift;
+                        let maybe_shadow = maybe_shadow.map(|shadow| Shadow {
+                            offset: Vector2 {
+                                x: shadow.offset.x,
+                                y: shadow.offset.y + lift,
+                            },
+                            ..*shadow
+                        });
                         let (min, max) = compute_sprite_cull_bounds(
                             &resolved_pos,
                             s,
@@ -441,22 +784,35 @@ pub fn render_system(
                             resolved_rot,
                             maybe_shader: maybe_shader.cloned(),
                             maybe_tint: maybe_tint.copied(),
-                            maybe_shadow: maybe_shadow.copied(),
+                            maybe_shadow,
+                            maybe_palette_swap: maybe_palette_swap.cloned(),
+                            maybe_blob: maybe_blob.map(|blob| {
+                                if maybe_height.is_some() {
+                                    return blob.shape_at_height(ground, lift);
+                                }
+                                let signals = query_positions
+                                    .get(entity)
+                                    .ok()
+                                    .and_then(|(_, signals, _)| signals);
+                                blob.shape(ground, signals)
+                            }),
                         })
                     },
                 ));
 
-                // sprite_buffer.sort_unstable_by_key(|item| item.z_index);
-                sprite_buffer.sort_unstable_by(|a, b| {
-                    a.z_index
-                        .partial_cmp(&b.z_index)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                });
+                sprite_buffer.sort_unstable_by(SpriteBufferItem::cmp_draw_order);
             } // build_sprite_buffer
             {
                 crate::tracy::tracy_span!("render/draw_world_sprites");
                 for item in sprite_buffer.iter() {
-                    if let Some(tex) = textures.get(&item.sprite.tex_key) {
+                    if let Some(blob) = &item.maybe_blob {
+                        // Blobs are shapes, so they break the batch
+                        sprite_batch.flush();
+                        d2.draw_ellipse_v(blob.center, blob.radius_h, blob.radius_v, blob.color);
+                    }
+                    if let Some((tex, is_world_view)) =
+                        resolve_sprite_texture(textures, world_views, &item.sprite.tex_key)
+                    {
                         let mut src = Rectangle {
                             x: item.sprite.offset.x,
                             y: item.sprite.offset.y,
@@ -466,7 +822,8 @@ pub fn render_system(
                         if item.sprite.flip_h {
                             src.width = -src.width;
                         }
-                        if item.sprite.flip_v {
+                        // World views are render textures, stored upside down.
+                        if item.sprite.flip_v != is_world_view {
                             src.height = -src.height;
                         }
 
@@ -481,18 +838,22 @@ pub fn render_system(
                         let rotation = geom.rotation;
 
                         let tint_color = item.maybe_tint.map(|t| t.color).unwrap_or(Color::WHITE);
-
-                        if let Some(shadow) = item.maybe_shadow {
+                        let shadow_dest = item.maybe_shadow.map(|shadow| {
                             let shadow_dest = Rectangle {
                                 x: dest.x + shadow.offset.x,
                                 y: dest.y + shadow.offset.y,
                                 ..dest
                             };
-                            d2.draw_texture_pro(tex, src, shadow_dest, origin_scaled, rotation, shadow.color);
-                        }
+                            (shadow_dest, shadow.color)
+                        });
 
                         // Apply entity shader if present
                         if let Some(entity_shader) = &item.maybe_shader {
+                            // Shader sprites break the batch to keep draw order
+                            sprite_batch.flush();
+                            if let Some((shadow_dest, shadow_color)) = shadow_dest {
+                                d2.draw_texture_pro(tex, src, shadow_dest, origin_scaled, rotation, shadow_color);
+                            }
                             if let Some(entry) = shader_store.get_mut(&entity_shader.shader_key) {
                                 if entry.shader.is_shader_valid() {
                                     // Set standard uniforms
@@ -567,8 +928,23 @@ pub fn render_system(
                                     tint_color,
                                 );
                             }
-                        } else {
-                            d2.draw_texture_pro(
+                        } else if let Some(palette) = item
+                            .maybe_palette_swap
+                            .as_ref()
+                            .filter(|_| palette_swap_ready)
+                            .and_then(|swap| textures.get_or_report(&swap.palette))
+                            && let Some(entry) = shader_store.get_mut(PALETTE_SWAP_SHADER_KEY)
+                        {
+                            // Palette swaps break the batch like shader sprites
+                            sprite_batch.flush();
+                            if let Some((shadow_dest, shadow_color)) = shadow_dest {
+                                d2.draw_texture_pro(tex, src, shadow_dest, origin_scaled, rotation, shadow_color);
+                            }
+                            let sampler =
+                                sampler_binding(&entry.shader, &mut entry.locations, "uPalette", palette);
+                            let mut d_shader = d2.begin_shader_mode(&mut entry.shader);
+                            sampler.iter().for_each(SamplerBinding::bind);
+                            d_shader.draw_texture_pro(
                                 tex,
                                 src,
                                 dest,
@@ -576,9 +952,23 @@ pub fn render_system(
                                 rotation,
                                 tint_color,
                             );
+                        } else {
+                            // Plain sprites (particles, tiles) share batched runs
+                            if let Some((shadow_dest, shadow_color)) = shadow_dest {
+                                sprite_batch.push(tex.as_ref(), src, shadow_dest, origin_scaled, rotation, shadow_color);
+                            }
+                            sprite_batch.push(
+                                tex.as_ref(),
+                                src,
+                                dest,
+                                origin_scaled,
+                                rotation,
+                                tint_color,
+                            );
                         }
 
-                        if maybe_debug.is_some() && debug_res.overlay_config.show_sprite_bounds {
+                        if debug_shapes && debug_res.overlay_config.show_sprite_bounds {
+                            sprite_batch.flush();
                             draw_rotated_rect_lines(
                                 &mut d2,
                                 dest,
@@ -589,13 +979,28 @@ pub fn render_system(
                         }
                     }
                 }
+                sprite_batch.flush();
+                crate::tracy::tracy_plot!("render_sprite_batches", sprite_batch.take_runs());
             } // draw_world_sprites
 
             {
                 crate::tracy::tracy_span!("render/build_text_buffer");
                 text_buffer.clear();
                 text_buffer.extend(query_map_dynamic_texts.iter().filter_map(
-                    |(entity, t, p, z, maybe_shader, maybe_tint, maybe_shadow, maybe_gt)| {
+                    |(
+                        entity,
+                        t,
+                        p,
+                        z,
+                        maybe_shader,
+                        maybe_tint,
+                        maybe_shadow,
+                        maybe_gt,
+                        maybe_layer,
+                    )| {
+                        if !res.layers.shows(maybe_layer, RenderLayer::GAMEPLAY) {
+                            return None;
+                        }
                         let resolved_pos =
                             MapPosition::from_vec(maybe_gt.map_or(p.pos, |gt| gt.position));
                         let text_size = t.size();
@@ -630,7 +1035,7 @@ pub fn render_system(
             {
                 crate::tracy::tracy_span!("render/draw_world_texts");
                 for item in text_buffer.iter() {
-                    if let Some(font) = fonts.get(&item.text.font) {
+                    if let Some(font) = fonts.get_or_fallback(&item.text.font) {
                         let final_color = item
                             .maybe_tint
                             .map(|t| t.multiply(item.text.color))
@@ -727,7 +1132,7 @@ pub fn render_system(
                             );
                         }
 
-                        if maybe_debug.is_some() && debug_res.overlay_config.show_text_bounds {
+                        if debug_shapes && debug_res.overlay_config.show_text_bounds {
                             d2.draw_rectangle_lines(
                                 item.resolved_pos.pos.x as i32,
                                 item.resolved_pos.pos.y as i32,
@@ -740,7 +1145,109 @@ pub fn render_system(
                 }
             } // draw_world_texts
 
-            if maybe_debug.is_some() {
+            if res.layers.is_visible(RenderLayer::GAMEPLAY) {
+                crate::tracy::tracy_span!("render/draw_ropes");
+                rope::draw_ropes(&mut d2, &queries.ropes, view_min, view_max);
+            }
+
+            if res.layers.is_visible(RenderLayer::GAMEPLAY) {
+                crate::tracy::tracy_span!("render/draw_water_surfaces");
+                water::draw_water_surfaces(
+                    &mut d2,
+                    &queries.water_surfaces,
+                    view_min,
+                    view_max,
+                );
+            }
+
+            {
+                crate::tracy::tracy_span!("render/draw_world_nine_slices");
+                nine_slice_buffer.clear();
+                for (slice, p, maybe_gt, z, maybe_tint, maybe_layer) in queries.map_nine_slices.iter() {
+                    if !res.layers.shows(maybe_layer, RenderLayer::GAMEPLAY) {
+                        continue;
+                    }
+                    let pos = maybe_gt.map_or(p.pos, |gt| gt.position);
+                    let item = NineSliceBufferItem::new(slice, pos, *z, maybe_tint);
+                    if item.overlaps(view_min, view_max) {
+                        nine_slice_buffer.push(item);
+                    }
+                }
+                nine_slice_buffer.sort_unstable_by(|a, b| {
+                    a.z_index
+                        .partial_cmp(&b.z_index)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                for item in nine_slice_buffer.iter() {
+                    draw_nine_slice_item(&mut d2, item, textures);
+                }
+            }
+
+            {
+                crate::tracy::tracy_span!("render/draw_world_progress");
+                progress_buffer.clear();
+                for (maybe_bar, maybe_radial, p, maybe_gt, z, maybe_layer) in queries.map_progress.iter() {
+                    if !res.layers.shows(maybe_layer, RenderLayer::GAMEPLAY) {
+                        continue;
+                    }
+                    let pos = maybe_gt.map_or(p.pos, |gt| gt.position);
+                    let items = maybe_bar
+                        .map(|bar| ProgressBufferItem::bar(bar, pos, *z))
+                        .into_iter()
+                        .chain(maybe_radial.map(|radial| ProgressBufferItem::radial(radial, pos, *z)));
+                    progress_buffer.extend(items.filter(|item| item.overlaps(view_min, view_max)));
+                }
+                progress_buffer.sort_unstable_by(|a, b| {
+                    a.z_index
+                        .partial_cmp(&b.z_index)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                for item in progress_buffer.iter() {
+                    draw_progress_item(&mut d2, item, textures);
+                }
+            }
+
+            if res.layers.is_visible(RenderLayer::FX)
+                && res.toggles.is_enabled(ToggleableSystem::Weather)
+            {
+                crate::tracy::tracy_span!("render/draw_weather");
+                weather::draw_weather(
+                    &mut d2,
+                    &queries.weather_particles,
+                    &res.weather,
+                    view_min,
+                    view_max,
+                );
+            }
+
+            if let Some(color) = res.clock.ambient_color() {
+                d2.draw_rectangle_rec(
+                    Rectangle {
+                        x: view_min.x,
+                        y: view_min.y,
+                        width: view_max.x - view_min.x,
+                        height: view_max.y - view_min.y,
+                    },
+                    color,
+                );
+            }
+
+            {
+                crate::tracy::tracy_span!("render/draw_visibility_polygons");
+                visibility::draw_visibility_polygons(
+                    &mut d2,
+                    &queries.visibility_polygons,
+                    view_min,
+                    view_max,
+                );
+            }
+
+            if res.fog.enabled {
+                crate::tracy::tracy_span!("render/draw_fog");
+                fog::draw_fog(&mut d2, &res.fog, view_min, view_max);
+            }
+
+            if debug_shapes {
                 if debug_res.overlay_config.show_collider_boxes {
                     for (collider, position, maybe_gt) in query_colliders.iter() {
                         let world_pos = maybe_gt.map_or(position.pos, |gt| gt.position);
@@ -832,33 +1339,72 @@ pub fn render_system(
         }
 
         // Draw in screen coordinates (UI layer) - still on the render target
-        let debug = maybe_debug.is_some();
-        let debug_sprites = debug && debug_res.overlay_config.show_sprite_bounds;
-        let debug_texts = debug && debug_res.overlay_config.show_text_bounds;
+        let debug_sprites = debug_shapes && debug_res.overlay_config.show_sprite_bounds;
+        let debug_texts = debug_shapes && debug_res.overlay_config.show_text_bounds;
+        let high_contrast = res.config.high_contrast_ui;
+        let mut d = d.begin_mode2D(screen_cam);
+        let bar = res.cinematic.bar_pixels(res.screensize.h as f32).round() as i32;
+        if bar > 0 {
+            let (w, h) = (res.screensize.w, res.screensize.h);
+            d.draw_rectangle(0, 0, w, bar, res.cinematic.color);
+            d.draw_rectangle(0, h - bar, w, bar, res.cinematic.color);
+        }
         {
             crate::tracy::tracy_span!("render/screen_space");
             draw_screen_space(
                 &mut d,
                 &queries.screen_sprites,
                 &queries.screen_texts,
+                &queries.screen_progress,
+                &queries.screen_nine_slices,
                 &queries.gui_windows,
                 &queries.gui_buttons,
                 &queries.gui_labels,
                 &queries.gui_progress_bars,
+                &queries.gui_inventory_grids,
+                &queries.inventories,
+                &queries.interactables,
+                &queries.offscreen_indicators,
+                camera,
+                Vector2::new(res.screensize.w as f32, res.screensize.h as f32),
+                &res.items,
                 &res.gui_theme_store,
                 &mut res.gui_theme_warn_cache,
                 textures,
                 fonts,
+                &res.layers,
                 screen_draw_buffer,
                 debug_sprites,
                 debug_texts,
+                high_contrast,
             );
         }
+
+        if let Some(color) = res.portal_transition.overlay_color() {
+            d.draw_rectangle(0, 0, res.screensize.w, res.screensize.h, color);
+        }
+        if matches!(dirty_region, DirtyRegion::Rect(_)) {
+            unsafe { ffi::EndScissorMode() };
+        }
     }
 
     // ========== PHASE 2: Multi-pass post-processing and final blit ==========
     crate::tracy::tracy_span!("render/postprocess");
     let debug_active = maybe_debug.is_some();
+    let colorblind_pass = res.config.colorblind_mode.shader_key();
+    let grading = &res.color_grading;
+    let color_lut = (grading.is_active()
+        && res.toggles.is_enabled(ToggleableSystem::ColorGrading)
+        && ensure_builtin_shader(rl, th, &mut shader_store, COLOR_LUT_SHADER_KEY, COLOR_LUT_FS))
+        .then(|| {
+            // A missing LUT leaves that side ungraded.
+            let lut_texture = |key| textures.get_or_report(key);
+            ColorLutPass {
+                from: grading.fading_from().and_then(lut_texture),
+                to: grading.current().and_then(lut_texture),
+                mix: grading.mix(),
+            }
+        });
 
     // Extract gui_callback from the active scene (fn pointer is Copy — no borrow held).
     // Must be done before taking mutable borrows of other debug_res fields below.
@@ -924,6 +1470,9 @@ pub fn render_system(
         let input_state = &*debug_res.input_state;
         let camera_follow = &*debug_res.camera_follow;
         let scene_manager = debug_res.scene_manager.as_deref();
+        let asset_usage = &*debug_res.asset_usage;
+        let ecs_stats = &*debug_res.ecs_stats;
+        let frame_arena = debug_res.frame_arena.last_frame();
         let world_time = &*res.world_time;
         let config = &*res.config;
 
@@ -940,6 +1489,9 @@ pub fn render_system(
                         scene_manager,
                         textures,
                         fonts,
+                        asset_usage,
+                        ecs_stats,
+                        frame_arena,
                         shader_count,
                         screensize,
                         window_size,
@@ -968,6 +1520,8 @@ pub fn render_system(
             &mut render_target,
             &mut shader_store,
             &res.post_process,
+            color_lut,
+            colorblind_pass,
             world_time,
             &res.screensize,
             &res.window_size,
@@ -980,6 +1534,8 @@ pub fn render_system(
             &mut render_target,
             &mut shader_store,
             &res.post_process,
+            color_lut,
+            colorblind_pass,
             &res.world_time,
             &res.screensize,
             &res.window_size,
@@ -988,17 +1544,6 @@ pub fn render_system(
     }
 }
 
-/// Collects screen-space sprites and texts into one merged buffer, sorts by
-/// [`ZIndex`], and dispatches draw calls in that order.
-///
-/// Uses the same in-place `sort_unstable_by` as the world-space buffers — the
-/// equal-z tie-break (text drawn on top of a same-z sprite, the sane default
-/// for UI captions over panel backgrounds) is encoded directly in the
-/// comparator via [`ScreenDrawItem::variant_rank`] instead of relying on
-/// `sort_by`'s stability and a fixed collection order. This keeps the merged,
-/// heterogeneous buffer on the cheaper allocation-free sort even though it
-/// holds two item types, which matters once this buffer holds tens of
-/// thousands of items (e.g. a screen-space bunnymark-style stress scene).
 /// Selects the nine-patch for a `GuiButton`'s current state from its skin,
 /// falling back to `normal` for any state whose patch was never set.
 fn resolve_button_patch(skin: &GuiButtonSkin, state: GuiWidgetState) -> &GuiNinePatch {
@@ -1046,164 +1591,247 @@ fn warn_missing_theme(
     }
 }
 
+/// Collects screen-space sprites and texts into one merged buffer, sorts by
+/// [`ZIndex`], and dispatches draw calls in that order.
+///
+/// Uses the same in-place `sort_unstable_by` as the world-space buffers — the
+/// equal-z tie-break (text drawn on top of a same-z sprite, the sane default
+/// for UI captions over panel backgrounds) is encoded directly in the
+/// comparator via [`ScreenDrawItem::variant_rank`] instead of relying on
+/// `sort_by`'s stability and a fixed collection order. This keeps the merged,
+/// heterogeneous buffer on the cheaper allocation-free sort even though it
+/// holds two item types, which matters once this buffer holds tens of
+/// thousands of items (e.g. a screen-space bunnymark-style stress scene).
+///
+/// Runs after the world pass has finished, so every item lands on top of
+/// the world whatever its `ZIndex`.
 #[allow(clippy::too_many_arguments)]
 fn draw_screen_space(
     d: &mut impl RaylibDraw,
-    screen_sprites: &Query<ScreenSpriteQueryData>,
-    screen_texts: &Query<ScreenTextQueryData>,
+    screen_sprites: &Query<ScreenSpriteQueryData, ScreenPassFilter>,
+    screen_texts: &Query<ScreenTextQueryData, ScreenPassFilter>,
+    screen_progress: &Query<ScreenProgressQueryData, (ProgressFilter, ScreenPassFilter)>,
+    screen_nine_slices: &Query<ScreenNineSliceQueryData, ScreenPassFilter>,
     gui_windows: &Query<(&GuiWindow, &ScreenPosition, &ZIndex)>,
     gui_buttons: &Query<(&GuiButton, &GuiInteractable, &ScreenPosition, &ZIndex)>,
     gui_labels: &Query<(&GuiLabel, &ScreenPosition, &ZIndex)>,
     gui_progress_bars: &Query<(&GuiProgressBar, &ScreenPosition, &ZIndex)>,
+    gui_inventory_grids: &Query<(&GuiInventoryGrid, &ScreenPosition, &ZIndex)>,
+    inventories: &Query<&Inventory>,
+    interactables: &Query<(&Interactable, &MapPosition, Option<&GlobalTransform2D>)>,
+    offscreen_indicators: &Query<(&OffscreenIndicator, &MapPosition, Option<&GlobalTransform2D>)>,
+    camera: &Camera2DRes,
+    screen: Vector2,
+    items: &ItemRegistry,
     gui_theme_store: &GuiThemeStore,
     gui_theme_warn_cache: &mut GuiThemeWarnCache,
     textures: &TextureStore,
     fonts: &FontStore,
+    layers: &LayerVisibility,
     buffer: &mut Vec<ScreenDrawItem>,
     debug_sprites: bool,
     debug_texts: bool,
+    high_contrast: bool,
 ) {
     buffer.clear();
-    for (window, p, z) in gui_windows.iter() {
-        match gui_theme_store.get(&window.theme_key) {
-            Some(theme) => buffer.push(screen_panel_item(
-                theme.panel.clone(),
-                Rectangle { x: p.pos.x, y: p.pos.y, width: window.size.x, height: window.size.y },
-                *z,
-                theme.panel_shadow,
-            )),
-            None => warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiWindow",
-                &window.theme_key,
-                " — skipping themed background",
-            ),
+    // GUI widgets, interaction prompts and off-screen indicators belong to the UI layer.
+    if layers.is_visible(RenderLayer::UI) {
+        for (window, p, z) in gui_windows.iter() {
+            match gui_theme_store.get(&window.theme_key) {
+                Some(theme) => buffer.push(screen_panel_item(
+                    theme.panel.clone(),
+                    Rectangle { x: p.pos.x, y: p.pos.y, width: window.size.x, height: window.size.y },
+                    *z,
+                    theme.panel_shadow,
+                )),
+                None => warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiWindow",
+                    &window.theme_key,
+                    " — skipping themed background",
+                ),
+            }
         }
-    }
-    for (button, interactable, p, z) in gui_buttons.iter() {
-        let Some(theme) = gui_theme_store.get(&button.theme_key) else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiButton",
-                &button.theme_key,
-                " — skipping themed background",
-            );
-            continue;
-        };
-        if let Some(skin) = theme.button.as_ref() {
-            buffer.push(screen_panel_item(
-                resolve_button_patch(skin, interactable.state).clone(),
-                Rectangle { x: p.pos.x, y: p.pos.y, width: interactable.size.x, height: interactable.size.y },
-                *z,
-                resolve_button_shadow(skin, interactable.state, theme.panel_shadow),
-            ));
-        } else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiButton",
-                &button.theme_key,
-                " has no button skin — skipping themed background",
-            );
+        for (button, interactable, p, z) in gui_buttons.iter() {
+            let Some(theme) = gui_theme_store.get(&button.theme_key) else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiButton",
+                    &button.theme_key,
+                    " — skipping themed background",
+                );
+                continue;
+            };
+            if let Some(skin) = theme.button.as_ref() {
+                buffer.push(screen_panel_item(
+                    resolve_button_patch(skin, interactable.state).clone(),
+                    Rectangle { x: p.pos.x, y: p.pos.y, width: interactable.size.x, height: interactable.size.y },
+                    *z,
+                    resolve_button_shadow(skin, interactable.state, theme.panel_shadow),
+                ));
+            } else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiButton",
+                    &button.theme_key,
+                    " has no button skin — skipping themed background",
+                );
+            }
         }
-    }
-    for (label, p, z) in gui_labels.iter() {
-        let Some(theme) = gui_theme_store.get(&label.theme_key) else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiLabel",
-                &label.theme_key,
-                " — skipping themed background",
-            );
-            continue;
-        };
-        if let Some(patch) = theme.label.as_ref() {
-            buffer.push(screen_panel_item(
-                patch.clone(),
-                Rectangle { x: p.pos.x, y: p.pos.y, width: label.size.x, height: label.size.y },
-                *z,
-                theme.panel_shadow,
-            ));
-        } else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiLabel",
-                &label.theme_key,
-                " has no label patch — skipping themed background",
-            );
+        for (label, p, z) in gui_labels.iter() {
+            let Some(theme) = gui_theme_store.get(&label.theme_key) else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiLabel",
+                    &label.theme_key,
+                    " — skipping themed background",
+                );
+                continue;
+            };
+            if let Some(patch) = theme.label.as_ref() {
+                buffer.push(screen_panel_item(
+                    patch.clone(),
+                    Rectangle { x: p.pos.x, y: p.pos.y, width: label.size.x, height: label.size.y },
+                    *z,
+                    theme.panel_shadow,
+                ));
+            } else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiLabel",
+                    &label.theme_key,
+                    " has no label patch — skipping themed background",
+                );
+            }
         }
+        for (bar, p, z) in gui_progress_bars.iter() {
+            let Some(theme) = gui_theme_store.get(&bar.theme_key) else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiProgressBar",
+                    &bar.theme_key,
+                    " (or that theme has no progress_bar skin) — skipping bar",
+                );
+                continue;
+            };
+            let Some(skin) = theme.progress_bar.as_ref() else {
+                warn_missing_theme(
+                    gui_theme_warn_cache,
+                    "GuiProgressBar",
+                    &bar.theme_key,
+                    " (or that theme has no progress_bar skin) — skipping bar",
+                );
+                continue;
+            };
+            let x = p.pos.x;
+            let y = p.pos.y;
+            let w = bar.size.x;
+            let h = bar.size.y;
+            let ratio = if bar.max > 0.0 { (bar.value / bar.max).clamp(0.0, 1.0) } else { 0.0 };
+            let track_dest = Rectangle { x, y, width: w, height: h };
+            let fill_dest = match bar.direction {
+                ProgressBarDirection::Horizontal => {
+                    Rectangle { x, y, width: w * ratio, height: h }
+                }
+                ProgressBarDirection::HorizontalReversed => {
+                    let fill_w = w * ratio;
+                    Rectangle { x: x + w - fill_w, y, width: fill_w, height: h }
+                }
+                ProgressBarDirection::Vertical => {
+                    let fill_h = h * ratio;
+                    Rectangle { x, y: y + h - fill_h, width: w, height: fill_h }
+                }
+                ProgressBarDirection::VerticalReversed => {
+                    Rectangle { x, y, width: w, height: h * ratio }
+                }
+            };
+            buffer.push(ScreenDrawItem::ProgressBar(ScreenProgressBarBufferItem {
+                track: skin.track.clone(),
+                fill: skin.fill.clone(),
+                track_dest,
+                fill_dest,
+                z_index: *z,
+                maybe_shadow: theme.panel_shadow,
+            }));
+        }
+        push_inventory_grid_items(
+            buffer,
+            gui_inventory_grids,
+            inventories,
+            items,
+            gui_theme_store,
+            gui_theme_warn_cache,
+            textures,
+            fonts,
+        );
+        push_interaction_prompt_items(
+            buffer,
+            interactables,
+            camera,
+            gui_theme_store,
+            gui_theme_warn_cache,
+            textures,
+            fonts,
+        );
+        push_offscreen_indicator_items(
+            buffer,
+            offscreen_indicators,
+            camera,
+            screen,
+            gui_theme_store,
+            gui_theme_warn_cache,
+            textures,
+            fonts,
+        );
     }
-    for (bar, p, z) in gui_progress_bars.iter() {
-        let Some(theme) = gui_theme_store.get(&bar.theme_key) else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiProgressBar",
-                &bar.theme_key,
-                " (or that theme has no progress_bar skin) — skipping bar",
-            );
-            continue;
-        };
-        let Some(skin) = theme.progress_bar.as_ref() else {
-            warn_missing_theme(
-                gui_theme_warn_cache,
-                "GuiProgressBar",
-                &bar.theme_key,
-                " (or that theme has no progress_bar skin) — skipping bar",
-            );
+    buffer.extend(screen_sprites.iter().filter_map(
+        |(s, p, z, maybe_tint, maybe_shadow, maybe_layer)| {
+            layers.shows(maybe_layer, RenderLayer::UI).then(|| {
+                ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
+                    sprite: s.clone(),
+                    z_index: *z,
+                    pos: *p,
+                    maybe_tint: maybe_tint.copied(),
+                    maybe_shadow: maybe_shadow.copied(),
+                })
+            })
+        },
+    ));
+    for (maybe_bar, maybe_radial, p, z, maybe_layer) in screen_progress.iter() {
+        if !layers.shows(maybe_layer, RenderLayer::UI) {
             continue;
-        };
-        let x = p.pos.x;
-        let y = p.pos.y;
-        let w = bar.size.x;
-        let h = bar.size.y;
-        let ratio = if bar.max > 0.0 { (bar.value / bar.max).clamp(0.0, 1.0) } else { 0.0 };
-        let track_dest = Rectangle { x, y, width: w, height: h };
-        let fill_dest = match bar.direction {
-            ProgressBarDirection::Horizontal => {
-                Rectangle { x, y, width: w * ratio, height: h }
-            }
-            ProgressBarDirection::HorizontalReversed => {
-                let fill_w = w * ratio;
-                Rectangle { x: x + w - fill_w, y, width: fill_w, height: h }
-            }
-            ProgressBarDirection::Vertical => {
-                let fill_h = h * ratio;
-                Rectangle { x, y: y + h - fill_h, width: w, height: fill_h }
-            }
-            ProgressBarDirection::VerticalReversed => {
-                Rectangle { x, y, width: w, height: h * ratio }
-            }
-        };
-        buffer.push(ScreenDrawItem::ProgressBar(ScreenProgressBarBufferItem {
-            track: skin.track.clone(),
-            fill: skin.fill.clone(),
-            track_dest,
-            fill_dest,
-            z_index: *z,
-            maybe_shadow: theme.panel_shadow,
-        }));
+        }
+        if let Some(bar) = maybe_bar {
+            buffer.push(ScreenDrawItem::Progress(ProgressBufferItem::bar(bar, p.pos, *z)));
+        }
+        if let Some(radial) = maybe_radial {
+            buffer.push(ScreenDrawItem::Progress(ProgressBufferItem::radial(radial, p.pos, *z)));
+        }
     }
-    buffer.extend(screen_sprites.iter().map(|(s, p, z, maybe_tint, maybe_shadow)| {
-        ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
-            sprite: s.clone(),
-            z_index: *z,
-            pos: *p,
-            maybe_tint: maybe_tint.copied(),
-            maybe_shadow: maybe_shadow.copied(),
-        })
-    }));
-    buffer.extend(screen_texts.iter().map(|(t, p, z, maybe_tint, maybe_shadow)| {
-        ScreenDrawItem::Text(ScreenTextBufferItem {
-            text: Arc::clone(&t.text),
-            font: Arc::clone(&t.font),
-            font_size: t.font_size,
-            color: t.color,
-            size: t.size(),
-            z_index: *z,
-            pos: *p,
-            maybe_tint: maybe_tint.copied(),
-            maybe_shadow: maybe_shadow.copied(),
-        })
-    }));
+    buffer.extend(screen_nine_slices.iter().filter_map(
+        |(slice, p, z, maybe_tint, maybe_layer)| {
+            layers.shows(maybe_layer, RenderLayer::UI).then(|| {
+                ScreenDrawItem::NineSlice(NineSliceBufferItem::new(slice, p.pos, *z, maybe_tint))
+            })
+        },
+    ));
+    buffer.extend(screen_texts.iter().filter_map(
+        |(t, p, z, maybe_tint, maybe_shadow, maybe_layer)| {
+            layers.shows(maybe_layer, RenderLayer::UI).then(|| {
+                ScreenDrawItem::Text(ScreenTextBufferItem {
+                    text: Arc::clone(&t.text),
+                    font: Arc::clone(&t.font),
+                    font_size: t.font_size,
+                    color: t.color,
+                    size: t.size(),
+                    z_index: *z,
+                    pos: *p,
+                    maybe_tint: maybe_tint.copied(),
+                    maybe_shadow: maybe_shadow.copied(),
+                })
+            })
+        },
+    ));
 
     buffer.sort_unstable_by(ScreenDrawItem::cmp_draw_order);
 
@@ -1212,7 +1840,15 @@ fn draw_screen_space(
             ScreenDrawItem::Panel(p) => draw_screen_panel_item(d, p, textures),
             ScreenDrawItem::ProgressBar(pb) => gui_panel::draw_screen_progress_bar_item(d, pb, textures),
             ScreenDrawItem::Sprite(s) => draw_screen_sprite_item(d, s, textures, debug_sprites),
-            ScreenDrawItem::Text(t) => draw_screen_text_item(d, t, fonts, debug_texts),
+            ScreenDrawItem::Arrow(a) => {
+                let [a0, a1, a2] = a.points;
+                d.draw_triangle(a0, a1, a2, a.color);
+            }
+            ScreenDrawItem::Progress(p) => draw_progress_item(d, p, textures),
+            ScreenDrawItem::NineSlice(n) => draw_nine_slice_item(d, n, textures),
+            ScreenDrawItem::Text(t) => {
+                draw_screen_text_item(d, t, fonts, debug_texts, high_contrast)
+            }
         }
     }
 }
@@ -1240,6 +1876,13 @@ mod needs_imgui_tests {
     fn both_debug_and_gui_callback_needs_imgui() {
         assert!(needs_imgui(true, true));
     }
+
+    #[test]
+    fn dirty_scissor_rounds_outward_and_scales() {
+        let rect = Rectangle::new(10.5, 4.2, 20.0, 5.5);
+        assert_eq!(dirty_scissor(rect, 1), (10, 4, 21, 6));
+        assert_eq!(dirty_scissor(rect, 2), (21, 8, 40, 12));
+    }
 }
 
 #[cfg(test)]
@@ -1250,7 +1893,7 @@ mod screen_draw_buffer_tests {
     fn sprite_item(z: f32) -> ScreenDrawItem {
         ScreenDrawItem::Sprite(ScreenSpriteBufferItem {
             sprite: Sprite {
-                tex_key: std::sync::Arc::from("tex"),
+                tex_key: "tex".into(),
                 width: 1.0,
                 height: 1.0,
                 offset: Vector2::zero(),
@@ -1309,6 +1952,87 @@ mod screen_draw_buffer_tests {
         assert!(matches!(sorted[0], ScreenDrawItem::Sprite(_)));
         assert!(matches!(sorted[1], ScreenDrawItem::Text(_)));
     }
+
+    #[test]
+    fn world_pass_leaves_out_screen_space_entities() {
+        let mut world = World::new();
+        let world_only = world.spawn((MapPosition::new(0.0, 0.0), ZIndex(0.0))).id();
+        world.spawn((
+            MapPosition::new(0.0, 0.0),
+            ScreenPosition::new(0.0, 0.0),
+            ZIndex(-5.0),
+        ));
+        let mut query = world.query_filtered::<Entity, (With<MapPosition>, WorldPassFilter)>();
+        let matched: Vec<Entity> = query.iter(&world).collect();
+        assert_eq!(matched, vec![world_only]);
+    }
+
+    #[test]
+    fn hidden_entities_are_left_out_of_both_passes() {
+        let mut world = World::new();
+        let shown = world.spawn((MapPosition::new(0.0, 0.0), ZIndex(0.0))).id();
+        world.spawn((MapPosition::new(0.0, 0.0), ZIndex(0.0), Hidden));
+        let ui = world.spawn((ScreenPosition::new(0.0, 0.0), ZIndex(0.0))).id();
+        world.spawn((ScreenPosition::new(0.0, 0.0), ZIndex(0.0), Hidden));
+
+        let mut query = world.query_filtered::<Entity, (With<MapPosition>, WorldPassFilter)>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![shown]);
+        let mut query =
+            world.query_filtered::<Entity, (With<ScreenPosition>, ScreenPassFilter)>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![ui]);
+    }
+}
+
+#[cfg(test)]
+mod sprite_buffer_tests {
+    use super::*;
+
+    fn item(z: f32, tex_key: &str, shader: bool) -> SpriteBufferItem {
+        SpriteBufferItem {
+            entity: Entity::from_bits(1),
+            sprite: Sprite {
+                tex_key: tex_key.into(),
+                width: 16.0,
+                height: 16.0,
+                offset: Vector2::zero(),
+                origin: Vector2::zero(),
+                flip_h: false,
+                flip_v: false,
+            },
+            z_index: ZIndex(z),
+            resolved_pos: MapPosition::new(0.0, 0.0),
+            resolved_scale: None,
+            resolved_rot: None,
+            maybe_shader: shader.then(|| EntityShader::new("glow")),
+            maybe_tint: None,
+            maybe_shadow: None,
+            maybe_palette_swap: None,
+            maybe_blob: None,
+        }
+    }
+
+    #[test]
+    fn equal_zindex_sprites_group_by_texture_for_batching() {
+        let mut buffer = vec![
+            item(0.0, "grass", false),
+            item(0.0, "water", false),
+            item(0.0, "grass", true),
+            item(0.0, "grass", false),
+            item(-1.0, "water", false),
+            item(0.0, "water", false),
+        ];
+        buffer.sort_unstable_by(SpriteBufferItem::cmp_draw_order);
+
+        assert_eq!(buffer[0].z_index, ZIndex(-1.0));
+        // Plain sprites form one run per texture; the shader sprite comes last.
+        let texture_changes = buffer[1..5]
+            .windows(2)
+            .filter(|w| w[0].sprite.tex_key != w[1].sprite.tex_key)
+            .count();
+        assert_eq!(texture_changes, 1);
+        assert!(buffer[1..5].iter().all(SpriteBufferItem::is_batchable));
+        assert!(!buffer[5].is_batchable());
+    }
 }
 
 #[cfg(test)]
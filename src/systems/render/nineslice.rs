@@ -0,0 +1,99 @@
+use raylib::prelude::*;
+
+use crate::components::nineslice::NineSlice;
+use crate::components::tint::Tint;
+use crate::components::zindex::ZIndex;
+use crate::key::Key;
+use crate::resources::texturestore::TextureStore;
+
+/// One [`NineSlice`] resolved to its drawing position. Used as is by the
+/// world pass and wrapped in `ScreenDrawItem::NineSlice` by the screen pass,
+/// hence as visible as that enum.
+pub(crate) struct NineSliceBufferItem {
+    tex_key: Key,
+    source: Option<Rectangle>,
+    borders: [i32; 4],
+    dest: Rectangle,
+    color: Color,
+    pub(super) z_index: ZIndex,
+}
+
+impl NineSliceBufferItem {
+    pub(super) fn new(
+        slice: &NineSlice,
+        pos: Vector2,
+        z_index: ZIndex,
+        maybe_tint: Option<&Tint>,
+    ) -> Self {
+        Self {
+            tex_key: slice.tex_key,
+            source: slice.source,
+            borders: [slice.left, slice.top, slice.right, slice.bottom],
+            dest: slice.dest(pos),
+            color: maybe_tint.map_or(Color::WHITE, |tint| tint.color),
+            z_index,
+        }
+    }
+
+    /// Whether the panel overlaps the `view_min`..`view_max` area.
+    pub(super) fn overlaps(&self, view_min: Vector2, view_max: Vector2) -> bool {
+        let b = self.dest;
+        !(b.x + b.width < view_min.x
+            || b.x > view_max.x
+            || b.y + b.height < view_min.y
+            || b.y > view_max.y)
+    }
+}
+
+/// Draw one nine-slice panel. A missing texture draws nothing.
+pub(super) fn draw_nine_slice_item(
+    d: &mut impl RaylibDraw,
+    item: &NineSliceBufferItem,
+    textures: &TextureStore,
+) {
+    let Some(tex) = textures.get_or_fallback(item.tex_key) else {
+        return;
+    };
+    let source = item
+        .source
+        .unwrap_or_else(|| Rectangle::new(0.0, 0.0, tex.width as f32, tex.height as f32));
+    let [left, top, right, bottom] = item.borders;
+    d.draw_texture_n_patch(
+        tex,
+        NPatchInfo {
+            source,
+            left,
+            top,
+            right,
+            bottom,
+            layout: NPatchLayout::NPATCH_NINE_PATCH,
+        },
+        item.dest,
+        Vector2::zero(),
+        0.0,
+        item.color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_resolve_dest_tint_and_cull() {
+        let slice = NineSlice::new("frame", 40.0, 20.0, 4)
+            .with_borders(4, 2, 4, 6)
+            .with_offset(-20.0, -30.0);
+        let tint = Tint::new(255, 0, 0, 128);
+        let item =
+            NineSliceBufferItem::new(&slice, Vector2::new(100.0, 100.0), ZIndex(1.0), Some(&tint));
+        assert_eq!(item.dest, Rectangle::new(80.0, 70.0, 40.0, 20.0));
+        assert_eq!(item.borders, [4, 2, 4, 6]);
+        assert_eq!(item.color, Color::new(255, 0, 0, 128));
+        assert!(item.overlaps(Vector2::new(0.0, 0.0), Vector2::new(85.0, 75.0)));
+        assert!(!item.overlaps(Vector2::new(0.0, 0.0), Vector2::new(75.0, 200.0)));
+
+        let item = NineSliceBufferItem::new(&slice, Vector2::zero(), ZIndex(0.0), None);
+        assert_eq!(item.color, Color::WHITE);
+    }
+}
@@ -1,13 +1,15 @@
 use bevy_ecs::prelude::*;
-use log::{error, warn};
+use log::{debug, error, warn};
 use raylib::ffi;
 use raylib::prelude::*;
 use rustc_hash::FxHashMap;
+use std::sync::Arc;
 
 use crate::components::mapposition::MapPosition;
 use crate::components::rigidbody::RigidBody;
 use crate::components::rotation::Rotation;
 use crate::components::scale::Scale;
+use crate::resources::colorgrading::COLOR_LUT_SHADER_KEY;
 use crate::resources::postprocessshader::PostProcessShader;
 use crate::resources::rendertarget::RenderTarget;
 use crate::resources::screensize::ScreenSize;
@@ -18,12 +20,53 @@ use crate::resources::worldtime::WorldTime;
 
 use super::SourceBuffer;
 
+/// Textures and blend of the color grading LUT pass. A `None` texture stands
+/// for the ungraded image.
+pub(super) struct ColorLutPass<'a> {
+    pub from: Option<&'a Texture2D>,
+    pub to: Option<&'a Texture2D>,
+    pub mix: f32,
+}
+
+/// Compile the built-in shader `key` from `fragment_source` on first use.
+/// Returns `false` if it is not available.
+pub(super) fn ensure_builtin_shader(
+    rl: &mut RaylibHandle,
+    th: &RaylibThread,
+    shader_store: &mut ShaderStore,
+    key: &str,
+    fragment_source: &str,
+) -> bool {
+    if shader_store.contains(key) {
+        return true;
+    }
+    match rl.load_shader_from_memory(th, None, Some(fragment_source)) {
+        Ok(shader) if shader.is_shader_valid() => {
+            debug!("Loaded built-in shader '{}'", key);
+            shader_store.add(key, shader);
+            true
+        }
+        Ok(_) => {
+            error!("Built-in shader '{}' loaded but is invalid", key);
+            false
+        }
+        Err(e) => {
+            error!("Built-in shader '{}' failed to load: {e}", key);
+            false
+        }
+    }
+}
+
 /// Apply post-processing shader passes and blit the final image to the window.
 ///
 /// Handles three cases: no shaders (direct blit), single shader, and multi-pass
 /// ping-pong. Always guarantees a frame is presented even if shaders are missing
 /// or invalid.
 ///
+/// `color_lut` adds the color grading pass after the user chain.
+/// `colorblind_pass` is the built-in colorblind shader key, run after both
+/// so the remap sees the final image.
+///
 /// `post_blit` is an optional callback invoked inside `begin_drawing()` after
 /// the final blit, used to draw imgui overlays at window resolution.
 #[allow(clippy::too_many_arguments)]
@@ -33,6 +76,8 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
     render_target: &mut RenderTarget,
     shader_store: &mut ShaderStore,
     post_process: &PostProcessShader,
+    color_lut: Option<ColorLutPass<'_>>,
+    colorblind_pass: Option<&str>,
     world_time: &WorldTime,
     screensize: &ScreenSize,
     window_size: &WindowSize,
@@ -44,16 +89,23 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
     // Destination rectangle (letterboxed to fit window)
     let dest = window_size.calculate_letterbox(render_target.game_width, render_target.game_height);
 
-    // Full-screen destination for intermediate passes (no letterboxing)
+    // Full-screen destination for intermediate passes (no letterboxing),
+    // at the supersampled texture size
     let full_dest = Rectangle {
         x: 0.0,
         y: 0.0,
-        width: render_target.game_width as f32,
-        height: render_target.game_height as f32,
+        width: render_target.texture_width() as f32,
+        height: render_target.texture_height() as f32,
     };
 
     // Clone shader chain to avoid borrowing issues
-    let shader_chain: Vec<_> = post_process.keys.to_vec();
+    let shader_chain: Vec<Arc<str>> = post_process
+        .keys
+        .iter()
+        .cloned()
+        .chain(color_lut.as_ref().map(|_| Arc::from(COLOR_LUT_SHADER_KEY)))
+        .chain(colorblind_pass.map(Arc::from))
+        .collect();
 
     if shader_chain.is_empty() {
         // No post-processing - draw directly to window
@@ -116,6 +168,7 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
             let uniform_dest = if is_last_pass { &dest } else { &full_dest };
 
             // Set uniforms
+            let mut samplers = Vec::new();
             if let Some(entry) = shader_store.get_mut(shader_key.as_ref()) {
                 set_standard_uniforms(
                     &mut entry.shader,
@@ -128,6 +181,11 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
                 for (name, value) in post_process.uniforms.iter() {
                     set_uniform_value(&mut entry.shader, &mut entry.locations, name, value);
                 }
+                if let Some(lut) = color_lut.as_ref()
+                    && shader_key.as_ref() == COLOR_LUT_SHADER_KEY
+                {
+                    samplers = set_color_lut_uniforms(&mut entry.shader, &mut entry.locations, lut);
+                }
             }
 
             // SAFETY: We're only reading from source_tex and writing to dest_tex,
@@ -149,6 +207,7 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
 
                     if let Some(entry) = shader_store.get_mut(shader_key.as_ref()) {
                         let mut d_shader = d.begin_shader_mode(&mut entry.shader);
+                        samplers.iter().for_each(SamplerBinding::bind);
                         d_shader.draw_texture_pro(
                             source_tex,
                             src,
@@ -187,6 +246,7 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
 
                     if let Some(entry) = shader_store.get_mut(shader_key.as_ref()) {
                         let mut d_shader = d.begin_shader_mode(&mut entry.shader);
+                        samplers.iter().for_each(SamplerBinding::bind);
                         d_shader.draw_texture_pro(
                             source_tex,
                             src,
@@ -210,6 +270,7 @@ pub(super) fn apply_postprocess_passes<F: FnOnce(&RaylibDrawHandle<'_>)>(
 
                     if let Some(entry) = shader_store.get_mut(shader_key.as_ref()) {
                         let mut d_shader = d.begin_shader_mode(&mut entry.shader);
+                        samplers.iter().for_each(SamplerBinding::bind);
                         d_shader.draw_texture_pro(
                             source_tex,
                             src,
@@ -365,7 +426,8 @@ fn set_vec4(shader: &mut Shader, locations: &mut FxHashMap<String, i32>, name: &
 /// Standard uniforms:
 /// - uTime: elapsed time in seconds
 /// - uDeltaTime: frame delta time in seconds
-/// - uResolution: render target resolution (game resolution)
+/// - uResolution: render target resolution (game resolution, not multiplied
+///   by the supersampling scale)
 /// - uFrame: frame count
 /// - uWindowResolution: window resolution
 /// - uLetterbox: letterbox destination rectangle (x, y, w, h)
@@ -411,6 +473,61 @@ pub(super) fn set_standard_uniforms(
     );
 }
 
+/// Set the blend of the color grading pass and return the bindings of its
+/// LUT textures.
+fn set_color_lut_uniforms(
+    shader: &mut Shader,
+    locations: &mut FxHashMap<String, i32>,
+    lut: &ColorLutPass<'_>,
+) -> Vec<SamplerBinding> {
+    let mut samplers = Vec::new();
+    for (sampler, flag, texture) in [
+        ("uLutFrom", "uLutFromEnabled", lut.from),
+        ("uLutTo", "uLutToEnabled", lut.to),
+    ] {
+        set_int(shader, locations, flag, &i32::from(texture.is_some()));
+        samplers.extend(
+            texture.and_then(|texture| sampler_binding(shader, locations, sampler, texture)),
+        );
+    }
+    set_float(shader, locations, "uLutMix", &lut.mix);
+    samplers
+}
+
+/// A texture to bind to a `sampler2D` uniform of a shader.
+pub(super) struct SamplerBinding {
+    shader: ffi::Shader,
+    loc: i32,
+    texture: ffi::Texture2D,
+}
+
+impl SamplerBinding {
+    /// Bind the texture. Call inside shader mode, right before drawing:
+    /// raylib forgets texture bindings whenever it flushes its draw batch,
+    /// which entering shader mode does.
+    pub(super) fn bind(&self) {
+        unsafe {
+            ffi::SetShaderValueTexture(self.shader, self.loc, self.texture);
+        }
+    }
+}
+
+/// Prepare binding `texture` to the `sampler2D` uniform `name`, or `None` if
+/// the shader does not use it.
+pub(super) fn sampler_binding(
+    shader: &Shader,
+    locations: &mut FxHashMap<String, i32>,
+    name: &str,
+    texture: &Texture2D,
+) -> Option<SamplerBinding> {
+    let loc = get_uniform_loc(shader, locations, name);
+    (loc >= 0).then(|| SamplerBinding {
+        shader: **shader,
+        loc,
+        texture: **texture,
+    })
+}
+
 /// Set a user-defined uniform value on a shader. Silently skips uniforms
 /// not found in the shader (handled by the `set_*` helpers).
 pub(super) fn set_uniform_value(
@@ -0,0 +1,173 @@
+use raylib::prelude::*;
+
+use crate::components::progress::{ProgressBar, RADIAL_SEGMENTS, RadialProgress};
+use crate::components::zindex::ZIndex;
+use crate::key::Key;
+use crate::resources::texturestore::TextureStore;
+
+/// Resolved geometry of a [`ProgressBar`] or [`RadialProgress`].
+enum ProgressShape {
+    Bar {
+        rect: Rectangle,
+        fill: Rectangle,
+    },
+    Radial {
+        center: Vector2,
+        radius: f32,
+        inner_radius: f32,
+        /// Start and end angle of the fill; equal when empty.
+        fill_angles: (f32, f32),
+    },
+}
+
+/// One progress bar or ring resolved to its drawing position. Used as is by
+/// the world pass and wrapped in `ScreenDrawItem::Progress` by the screen pass,
+/// hence as visible as that enum.
+pub(crate) struct ProgressBufferItem {
+    shape: ProgressShape,
+    fill_color: Color,
+    background_color: Option<Color>,
+    background: Option<Key>,
+    pub(super) z_index: ZIndex,
+}
+
+impl ProgressBufferItem {
+    pub(super) fn bar(bar: &ProgressBar, pos: Vector2, z_index: ZIndex) -> Self {
+        Self {
+            shape: ProgressShape::Bar {
+                rect: bar.rect(pos),
+                fill: bar.fill_rect(pos),
+            },
+            fill_color: bar.fill_color,
+            background_color: bar.background_color,
+            background: bar.background,
+            z_index,
+        }
+    }
+
+    pub(super) fn radial(radial: &RadialProgress, pos: Vector2, z_index: ZIndex) -> Self {
+        Self {
+            shape: ProgressShape::Radial {
+                center: pos + radial.offset,
+                radius: radial.radius,
+                inner_radius: radial.inner_radius,
+                fill_angles: radial.fill_angles(),
+            },
+            fill_color: radial.fill_color,
+            background_color: radial.background_color,
+            background: radial.background,
+            z_index,
+        }
+    }
+
+    /// Axis-aligned area covered, for view culling.
+    pub(super) fn bounds(&self) -> Rectangle {
+        match self.shape {
+            ProgressShape::Bar { rect, .. } => rect,
+            ProgressShape::Radial { center, radius, .. } => Rectangle::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            ),
+        }
+    }
+
+    /// Whether the item overlaps the `view_min`..`view_max` area.
+    pub(super) fn overlaps(&self, view_min: Vector2, view_max: Vector2) -> bool {
+        let b = self.bounds();
+        !(b.x + b.width < view_min.x
+            || b.x > view_max.x
+            || b.y + b.height < view_min.y
+            || b.y > view_max.y)
+    }
+}
+
+/// Draw one progress item: background color, background texture stretched
+/// over [`ProgressBufferItem::bounds`], then the fill.
+pub(super) fn draw_progress_item(
+    d: &mut impl RaylibDraw,
+    item: &ProgressBufferItem,
+    textures: &TextureStore,
+) {
+    let bounds = item.bounds();
+    match item.shape {
+        ProgressShape::Bar { rect, .. } => {
+            if let Some(color) = item.background_color {
+                d.draw_rectangle_rec(rect, color);
+            }
+        }
+        ProgressShape::Radial {
+            center,
+            radius,
+            inner_radius,
+            ..
+        } => {
+            if let Some(color) = item.background_color {
+                draw_arc(d, center, inner_radius, radius, 0.0, 360.0, color);
+            }
+        }
+    }
+    if let Some(tex) = item
+        .background
+        .as_ref()
+        .and_then(|key| textures.get_or_fallback(key))
+    {
+        let src = Rectangle::new(0.0, 0.0, tex.width as f32, tex.height as f32);
+        d.draw_texture_pro(tex, src, bounds, Vector2::zero(), 0.0, Color::WHITE);
+    }
+    match item.shape {
+        ProgressShape::Bar { fill, .. } => {
+            if fill.width > 0.0 && fill.height > 0.0 {
+                d.draw_rectangle_rec(fill, item.fill_color);
+            }
+        }
+        ProgressShape::Radial {
+            center,
+            radius,
+            inner_radius,
+            fill_angles: (start, end),
+        } => {
+            if end > start {
+                draw_arc(d, center, inner_radius, radius, start, end, item.fill_color);
+            }
+        }
+    }
+}
+
+/// A pie slice when `inner_radius` is zero, a ring arc otherwise, with a
+/// share of [`RADIAL_SEGMENTS`] matching the sweep.
+fn draw_arc(
+    d: &mut impl RaylibDraw,
+    center: Vector2,
+    inner_radius: f32,
+    radius: f32,
+    start: f32,
+    end: f32,
+    color: Color,
+) {
+    let segments = ((RADIAL_SEGMENTS as f32 * (end - start) / 360.0).ceil() as i32).max(1);
+    if inner_radius > 0.0 {
+        d.draw_ring(center, inner_radius, radius, start, end, segments, color);
+    } else {
+        d.draw_circle_sector(center, radius, start, end, segments, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_cull_by_their_bounds() {
+        let ring = RadialProgress::new(10.0, 1.0).with_offset(0.0, -20.0);
+        let item = ProgressBufferItem::radial(&ring, Vector2::new(100.0, 100.0), ZIndex(0.0));
+        assert_eq!(item.bounds(), Rectangle::new(90.0, 70.0, 20.0, 20.0));
+        assert!(item.overlaps(Vector2::new(0.0, 0.0), Vector2::new(95.0, 75.0)));
+        assert!(!item.overlaps(Vector2::new(0.0, 0.0), Vector2::new(85.0, 200.0)));
+
+        let bar = ProgressBar::new(32.0, 4.0, 1.0);
+        let item = ProgressBufferItem::bar(&bar, Vector2::new(-40.0, 0.0), ZIndex(0.0));
+        assert!(!item.overlaps(Vector2::new(0.0, 0.0), Vector2::new(100.0, 100.0)));
+    }
+}
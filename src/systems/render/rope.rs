@@ -0,0 +1,41 @@
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use crate::components::rope::Rope;
+
+/// Draw the ropes inside the view as polylines, in world space.
+///
+/// Ropes whose points all lie outside the view are skipped.
+pub(super) fn draw_ropes(
+    d: &mut impl RaylibDraw,
+    ropes: &Query<&Rope>,
+    view_min: Vector2,
+    view_max: Vector2,
+) {
+    for rope in ropes.iter() {
+        let points = rope.points();
+        let (min, max) = points.iter().fold(
+            (
+                Vector2::new(f32::MAX, f32::MAX),
+                Vector2::new(f32::MIN, f32::MIN),
+            ),
+            |(min, max), p| {
+                (
+                    Vector2::new(min.x.min(p.x), min.y.min(p.y)),
+                    Vector2::new(max.x.max(p.x), max.y.max(p.y)),
+                )
+            },
+        );
+        let margin = rope.thickness;
+        if max.x + margin < view_min.x
+            || min.x - margin > view_max.x
+            || max.y + margin < view_min.y
+            || min.y - margin > view_max.y
+        {
+            continue;
+        }
+        for pair in points.windows(2) {
+            d.draw_line_ex(pair[0], pair[1], rope.thickness, rope.color);
+        }
+    }
+}
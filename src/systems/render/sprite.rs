@@ -11,7 +11,7 @@ pub(super) fn draw_screen_sprite_item(
 ) {
     let sprite = &item.sprite;
     let pos = item.pos;
-    if let Some(tex) = textures.get(&sprite.tex_key) {
+    if let Some(tex) = textures.get_or_fallback(&sprite.tex_key) {
         let mut src = Rectangle {
             x: sprite.offset.x,
             y: sprite.offset.y,
@@ -1,6 +1,9 @@
 use raylib::prelude::*;
 
 use super::ScreenTextBufferItem;
+use crate::resources::accessibility::{
+    HIGH_CONTRAST_PLATE, HIGH_CONTRAST_PLATE_PADDING, high_contrast_text_color,
+};
 use crate::resources::fontstore::FontStore;
 
 /// Draw one already-resolved screen-space text item (UI layer).
@@ -9,14 +12,27 @@ pub(super) fn draw_screen_text_item(
     item: &ScreenTextBufferItem,
     fonts: &FontStore,
     debug: bool,
+    high_contrast: bool,
 ) {
     let pos = item.pos;
-    if let Some(font) = fonts.get(&item.font) {
-        let final_color = item
+    if let Some(font) = fonts.get_or_fallback(&item.font) {
+        let mut final_color = item
             .maybe_tint
             .map(|t| t.multiply(item.color))
             .unwrap_or(item.color);
-        if let Some(shadow) = item.maybe_shadow {
+        if high_contrast {
+            // Opaque plate instead of the shadow, so the text reads on any background
+            d.draw_rectangle_rec(
+                Rectangle {
+                    x: pos.pos.x - HIGH_CONTRAST_PLATE_PADDING,
+                    y: pos.pos.y - HIGH_CONTRAST_PLATE_PADDING,
+                    width: item.size.x + 2.0 * HIGH_CONTRAST_PLATE_PADDING,
+                    height: item.size.y + 2.0 * HIGH_CONTRAST_PLATE_PADDING,
+                },
+                HIGH_CONTRAST_PLATE,
+            );
+            final_color = high_contrast_text_color(final_color);
+        } else if let Some(shadow) = item.maybe_shadow {
             let shadow_pos = Vector2 {
                 x: pos.pos.x + shadow.offset.x,
                 y: pos.pos.y + shadow.offset.y,
@@ -0,0 +1,37 @@
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use crate::components::visibilitypolygon::VisibilityPolygon;
+
+/// Fill the coloured visibility polygons inside the view, in world space.
+///
+/// Each polygon is drawn as a triangle fan around its origin; polygons whose
+/// range lies entirely outside the view are skipped.
+pub(super) fn draw_visibility_polygons(
+    d: &mut impl RaylibDraw,
+    polygons: &Query<&VisibilityPolygon>,
+    view_min: Vector2,
+    view_max: Vector2,
+) {
+    for polygon in polygons.iter() {
+        let Some(color) = polygon.color else {
+            continue;
+        };
+        let (o, r) = (polygon.origin, polygon.radius);
+        if o.x + r < view_min.x
+            || o.x - r > view_max.x
+            || o.y + r < view_min.y
+            || o.y - r > view_max.y
+        {
+            continue;
+        }
+        let points = &polygon.points;
+        let wrap = (polygon.is_closed() && points.len() > 2)
+            .then(|| (points[points.len() - 1], points[0]));
+        let pairs = points.windows(2).map(|w| (w[0], w[1])).chain(wrap);
+        for (a, b) in pairs {
+            // Points go clockwise on screen; raylib wants counter-clockwise.
+            d.draw_triangle(o, b, a, color);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::watersurface::WaterSurface;
+
+/// Fill the water surfaces inside the view, in world space.
+///
+/// Each span between two surface columns is drawn as a quad down to the
+/// water's bottom; waters entirely outside the view are skipped.
+pub(super) fn draw_water_surfaces(
+    d: &mut impl RaylibDraw,
+    waters: &Query<(&WaterSurface, &MapPosition, Option<&GlobalTransform2D>)>,
+    view_min: Vector2,
+    view_max: Vector2,
+) {
+    for (water, position, global) in waters.iter() {
+        let origin = global.map_or(position.pos, |gt| gt.position);
+        let bottom = origin.y + water.depth;
+        let crest = water.heights().iter().copied().fold(0.0, f32::min);
+        if origin.x + water.width < view_min.x
+            || origin.x > view_max.x
+            || bottom < view_min.y
+            || origin.y + crest > view_max.y
+        {
+            continue;
+        }
+        let top =
+            |i: usize| Vector2::new(origin.x + water.column_x(i), origin.y + water.heights()[i]);
+        for i in 1..water.heights().len() {
+            let (a, b) = (top(i - 1), top(i));
+            let (a_bottom, b_bottom) = (Vector2::new(a.x, bottom), Vector2::new(b.x, bottom));
+            // Counter-clockwise on screen, as raylib wants.
+            d.draw_triangle(a, a_bottom, b, water.color);
+            d.draw_triangle(b, a_bottom, b_bottom, water.color);
+        }
+    }
+}
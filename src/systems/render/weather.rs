@@ -0,0 +1,42 @@
+use bevy_ecs::prelude::Query;
+use raylib::prelude::*;
+
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::components::weather::WeatherParticle;
+use crate::resources::weather::{Weather, WeatherKind};
+
+/// Seconds of motion a rain streak trails behind its drop.
+const RAIN_STREAK: f32 = 0.02;
+
+/// Radius of a snow flake in world units.
+const SNOW_RADIUS: f32 = 1.5;
+
+/// Draw the rain drops and snow flakes inside the view, in world space.
+///
+/// Rain is a short line trailing along the drop's velocity, snow a small dot.
+pub(super) fn draw_weather(
+    d: &mut impl RaylibDraw,
+    particles: &Query<(&WeatherParticle, &MapPosition, &RigidBody)>,
+    weather: &Weather,
+    view_min: Vector2,
+    view_max: Vector2,
+) {
+    for (particle, position, body) in particles.iter() {
+        let p = position.pos;
+        if p.x < view_min.x - SNOW_RADIUS
+            || p.x > view_max.x + SNOW_RADIUS
+            || p.y < view_min.y - SNOW_RADIUS
+            || p.y > view_max.y + SNOW_RADIUS
+        {
+            continue;
+        }
+        match particle.0 {
+            WeatherKind::Rain => {
+                d.draw_line_v(p - body.velocity * RAIN_STREAK, p, weather.rain_color)
+            }
+            WeatherKind::Snow => d.draw_circle_v(p, SNOW_RADIUS, weather.snow_color),
+            WeatherKind::Clear => {}
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Secondary world rendering.
+//!
+//! [`render_sub_worlds_system`] draws every [`SubWorld`](crate::resources::worlds::SubWorld)
+//! into its view texture in [`RenderTarget::world_views`] before the main
+//! [`render_system`](super::render_system) runs. Main-world sprites whose
+//! `tex_key` is a world's name then sample that view (see
+//! [`resolve_sprite_texture`]).
+//!
+//! Only sprites (with scale, rotation, tint and z-index) are drawn inside a
+//! sub-world; text, shaders, shadows and debug overlays are main-world only.
+
+use bevy_ecs::prelude::*;
+use raylib::ffi;
+use raylib::prelude::*;
+use rustc_hash::FxHashMap;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::rotation::Rotation;
+use crate::components::scale::Scale;
+use crate::components::sprite::Sprite;
+use crate::components::tint::Tint;
+use crate::components::zindex::ZIndex;
+use crate::resources::rendertarget::RenderTarget;
+use crate::resources::texturestore::TextureStore;
+use crate::resources::worlds::Worlds;
+
+use super::geometry::{compute_sprite_geometry, resolve_world_transform};
+
+/// One resolved sub-world sprite, ready to draw.
+pub struct SubWorldSpriteItem {
+    sprite: Sprite,
+    z_index: ZIndex,
+    pos: MapPosition,
+    scale: Option<Scale>,
+    rot: Option<Rotation>,
+    tint: Option<Tint>,
+}
+
+/// Draw every secondary world into its view texture.
+///
+/// Views are created lazily, recreated when a world's size changes and
+/// dropped when the world is destroyed.
+pub fn render_sub_worlds_system(
+    mut raylib: crate::systems::RaylibAccess,
+    mut render_target: NonSendMut<RenderTarget>,
+    mut worlds: ResMut<Worlds>,
+    textures: Res<TextureStore>,
+    mut buffer: Local<Vec<SubWorldSpriteItem>>,
+) {
+    crate::tracy::tracy_span!("render_sub_worlds");
+    let (rl, th) = (&mut *raylib.rl, &*raylib.th);
+    render_target
+        .world_views
+        .retain(|name, _| worlds.contains(name));
+
+    for (name, sub) in worlds.iter_mut() {
+        buffer.clear();
+        let mut query = sub.world.query::<(
+            &Sprite,
+            &MapPosition,
+            &ZIndex,
+            Option<&Scale>,
+            Option<&Rotation>,
+            Option<&Tint>,
+            Option<&GlobalTransform2D>,
+        )>();
+        buffer.extend(
+            query
+                .iter(&sub.world)
+                .map(|(s, p, z, scale, rot, tint, gt)| {
+                    let (pos, scale, rot) =
+                        resolve_world_transform(*p, scale.copied(), rot.copied(), gt.copied());
+                    SubWorldSpriteItem {
+                        sprite: s.clone(),
+                        z_index: *z,
+                        pos,
+                        scale,
+                        rot,
+                        tint: tint.copied(),
+                    }
+                }),
+        );
+        buffer.sort_unstable_by(|a, b| {
+            a.z_index
+                .partial_cmp(&b.z_index)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let view = match render_target.ensure_world_view(rl, th, name, sub.width, sub.height) {
+            Ok(view) => view,
+            Err(e) => {
+                log::warn!("{e}");
+                continue;
+            }
+        };
+        let mut d = rl.begin_texture_mode(th, view);
+        d.clear_background(sub.background);
+        let mut d2 = d.begin_mode2D(sub.camera);
+        for item in buffer.iter() {
+            let Some(tex) = textures.get_or_fallback(&item.sprite.tex_key) else {
+                continue;
+            };
+            let mut src = Rectangle {
+                x: item.sprite.offset.x,
+                y: item.sprite.offset.y,
+                width: item.sprite.width,
+                height: item.sprite.height,
+            };
+            if item.sprite.flip_h {
+                src.width = -src.width;
+            }
+            if item.sprite.flip_v {
+                src.height = -src.height;
+            }
+            let geom = compute_sprite_geometry(
+                &item.pos,
+                &item.sprite,
+                item.scale.as_ref(),
+                item.rot.as_ref(),
+            );
+            let tint = item.tint.map(|t| t.color).unwrap_or(Color::WHITE);
+            d2.draw_texture_pro(tex, src, geom.dest, geom.origin, geom.rotation, tint);
+        }
+    }
+}
+
+/// Look up the texture a world-space sprite draws: a loaded texture, else
+/// the view of the secondary world named `key`, else the store's fallback.
+///
+/// The second value is `true` for world views, which are stored bottom-up
+/// like every render texture and must be drawn with a flipped source rect.
+pub(super) fn resolve_sprite_texture<'a>(
+    textures: &'a TextureStore,
+    world_views: &'a FxHashMap<String, RenderTexture2D>,
+    key: &str,
+) -> Option<(&'a dyn AsRef<ffi::Texture2D>, bool)> {
+    if let Some(tex) = textures.get(key) {
+        return Some((tex as &dyn AsRef<ffi::Texture2D>, false));
+    }
+    if let Some(view) = world_views.get(key) {
+        return Some((view as &dyn AsRef<ffi::Texture2D>, true));
+    }
+    textures
+        .fallback_for(key)
+        .map(|tex| (tex as &dyn AsRef<ffi::Texture2D>, false))
+}
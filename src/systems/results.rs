@@ -0,0 +1,238 @@
+//! Results screen system.
+//!
+//! [`results_screen_system`] drives every [`ResultsScreen`]: on its first
+//! frame it reads the row values and spawns a label and a value
+//! [`DynamicText`] per row, then counts the values up row by row. A confirm
+//! press (`action_1` or `action_2`) while counting jumps to the final values;
+//! once done, it despawns the screen and triggers a [`ResultsDoneEvent`].
+//!
+//! - [`process_lua_ui_commands`] *(feature = "lua")* opens the screens
+//!   requested with `engine.show_results`, starts the rolls of
+//!   `engine.show_credits`, submits the scores of `engine.submit_score`,
+//!   toggles `engine.cinematic_mode` and arms `engine.set_attract_mode`.
+//!
+//! # Related
+//!
+//! - [`crate::components::results`] – screen definition and count-up maths
+//! - [`crate::events::results`] – the close event
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::dynamictext::DynamicText;
+use crate::components::results::{ResultValue, ResultsScreen};
+use crate::components::screenposition::ScreenPosition;
+use crate::components::zindex::ZIndex;
+use crate::events::results::ResultsDoneEvent;
+use crate::resources::input::InputState;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::UiCmd;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+#[cfg(feature = "lua")]
+use crate::systems::attractmode::SetAttractMode;
+#[cfg(feature = "lua")]
+use crate::systems::cinematic::SetCinematicMode;
+#[cfg(feature = "lua")]
+use crate::systems::nameentry::SubmitScore;
+
+/// Opens, counts up and closes results screens.
+pub fn results_screen_system(
+    mut commands: Commands,
+    mut screens: Query<(Entity, &mut ResultsScreen)>,
+    mut texts: Query<&mut DynamicText>,
+    world_signals: Res<WorldSignals>,
+    input: Res<InputState>,
+    time: Res<WorldTime>,
+) {
+    let confirm = input.action_1.just_pressed || input.action_2.just_pressed;
+    for (entity, mut screen) in screens.iter_mut() {
+        if !screen.opened {
+            open_screen(&mut commands, &mut screen, &world_signals);
+            continue;
+        }
+
+        if confirm {
+            if !screen.is_counting() {
+                for &(label, value) in &screen.texts {
+                    commands.entity(label).try_despawn();
+                    commands.entity(value).try_despawn();
+                }
+                commands.entity(entity).despawn();
+                commands.trigger(ResultsDoneEvent {
+                    screen: entity,
+                    callback: screen.callback.clone(),
+                });
+                continue;
+            }
+            screen.elapsed = screen.total_duration();
+        } else {
+            screen.elapsed += time.delta;
+        }
+
+        for (index, &(_, value)) in screen.texts.iter().enumerate() {
+            if let Ok(mut text) = texts.get_mut(value) {
+                text.set_text(screen.value_text(index));
+            }
+        }
+    }
+}
+
+/// Resolves the row values and spawns the row texts. Input is ignored on this
+/// frame so the press that ended the level doesn't skip the count.
+fn open_screen(commands: &mut Commands, screen: &mut ResultsScreen, signals: &WorldSignals) {
+    screen.targets = screen
+        .rows
+        .iter()
+        .map(|row| match &row.value {
+            ResultValue::Number(v) => *v,
+            ResultValue::Signal(key) => signals
+                .get_integer(key)
+                .map(f64::from)
+                .or_else(|| signals.get_scalar(key).map(f64::from))
+                .unwrap_or(0.0),
+        })
+        .collect();
+    screen.texts = (0..screen.rows.len())
+        .map(|index| {
+            let pos = screen.position + Vector2::new(0.0, index as f32 * screen.row_spacing);
+            let text = |content: String| {
+                DynamicText::new(
+                    content,
+                    screen.font.as_str(),
+                    screen.font_size,
+                    screen.color,
+                )
+            };
+            let label = commands
+                .spawn((
+                    ScreenPosition::new(pos.x, pos.y),
+                    ZIndex(screen.z_index),
+                    text(screen.rows[index].label.clone()),
+                ))
+                .id();
+            let value = commands
+                .spawn((
+                    ScreenPosition::new(pos.x + screen.value_offset, pos.y),
+                    ZIndex(screen.z_index),
+                    text(screen.value_text(index)),
+                ))
+                .id();
+            (label, value)
+        })
+        .collect();
+    screen.opened = true;
+}
+
+/// Applies UI commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_ui_commands(InMut(buf): InMut<Vec<UiCmd>>, mut commands: Commands) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            UiCmd::ShowResults(screen) => {
+                commands.spawn(*screen);
+            }
+            UiCmd::ShowCredits(roll) => {
+                commands.spawn(*roll);
+            }
+            UiCmd::SubmitScore { score, callback } => {
+                commands.queue(SubmitScore { score, callback });
+            }
+            UiCmd::CinematicMode { enabled, duration } => {
+                commands.queue(SetCinematicMode { enabled, duration });
+            }
+            UiCmd::SetAttractMode {
+                replay_path,
+                idle_seconds,
+            } => {
+                commands.queue(SetAttractMode {
+                    replay_path,
+                    idle_seconds,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::results::ResultRow;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Closed(Vec<Option<String>>);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 0.5,
+            real_delta: 0.5,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.insert_resource(InputState::default());
+        world.init_resource::<Closed>();
+        world.add_observer(
+            |trigger: On<ResultsDoneEvent>, mut closed: ResMut<Closed>| {
+                closed.0.push(trigger.event().callback.clone());
+            },
+        );
+        let mut signals = WorldSignals::default();
+        signals.set_integer("score", 300);
+        world.insert_resource(signals);
+        world
+    }
+
+    fn press_confirm(world: &mut World, pressed: bool) {
+        world.resource_mut::<InputState>().action_1.just_pressed = pressed;
+    }
+
+    fn value_texts(world: &mut World) -> Vec<String> {
+        let screen = world
+            .query::<&ResultsScreen>()
+            .single(world)
+            .unwrap()
+            .clone();
+        screen
+            .texts
+            .iter()
+            .map(|&(_, value)| world.get::<DynamicText>(value).unwrap().text.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn counts_up_skips_on_confirm_then_closes() {
+        let mut world = world();
+        world.spawn(
+            ResultsScreen::new(vec![
+                ResultRow::new("Score", ResultValue::Signal("score".into())),
+                ResultRow::new("Bonus", ResultValue::Number(50.0)),
+            ])
+            .with_callback("on_done"),
+        );
+
+        // The press that ended the level is ignored while the screen opens.
+        press_confirm(&mut world, true);
+        world.run_system_once(results_screen_system).unwrap();
+        press_confirm(&mut world, false);
+        assert_eq!(value_texts(&mut world), ["0", "0"]);
+
+        world.run_system_once(results_screen_system).unwrap();
+        assert_eq!(value_texts(&mut world), ["150", "0"]);
+
+        press_confirm(&mut world, true);
+        world.run_system_once(results_screen_system).unwrap();
+        assert_eq!(value_texts(&mut world), ["300", "50"]);
+        assert!(world.resource::<Closed>().0.is_empty());
+
+        world.run_system_once(results_screen_system).unwrap();
+        assert_eq!(world.resource::<Closed>().0, [Some("on_done".to_string())]);
+        assert_eq!(world.query::<&ResultsScreen>().iter(&world).count(), 0);
+        assert_eq!(world.query::<&DynamicText>().iter(&world).count(), 0);
+    }
+}
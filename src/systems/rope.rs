@@ -0,0 +1,168 @@
+//! Rope system.
+//!
+//! [`rope_system`] resolves the anchors of every [`Rope`], steps its point
+//! chain and pulls the rigid bodies hanging from it back within reach. Runs
+//! after [`movement`](super::movement::movement) so bodies have moved for the
+//! frame before the rope corrects them, and before transforms are propagated.
+//!
+//! # Related
+//!
+//! - [`crate::components::rope`] – rope definition and examples
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::components::rope::{Rope, RopeAnchor, RopeEnd};
+use crate::resources::worldtime::WorldTime;
+
+type AnchorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut MapPosition,
+        Option<&'static GlobalTransform2D>,
+        Option<&'static mut RigidBody>,
+    ),
+>;
+
+/// How `anchor` holds its end this frame.
+fn resolve_anchor(anchor: RopeAnchor, anchors: &AnchorQuery) -> RopeEnd {
+    match anchor {
+        RopeAnchor::Point(p) => RopeEnd::Fixed(p),
+        RopeAnchor::Entity { entity, offset } => match anchors.get(entity) {
+            Ok((position, global, rigidbody)) => {
+                let world_pos = global.map_or(position.pos, |gt| gt.position) + offset;
+                if rigidbody.is_some_and(|rb| !rb.frozen) {
+                    RopeEnd::Driven(world_pos)
+                } else {
+                    RopeEnd::Fixed(world_pos)
+                }
+            }
+            Err(_) => RopeEnd::Loose,
+        },
+        RopeAnchor::Free => RopeEnd::Loose,
+    }
+}
+
+/// Steps every rope and moves the bodies hanging from it.
+///
+/// A body pulled by `d` this frame has its position moved by `d` and its
+/// velocity changed by `d / dt`, so it swings instead of bouncing back.
+pub fn rope_system(mut ropes: Query<&mut Rope>, mut anchors: AnchorQuery, time: Res<WorldTime>) {
+    crate::tracy::tracy_span!("rope_system");
+    let delta = time.delta;
+    for mut rope in ropes.iter_mut() {
+        let start = resolve_anchor(rope.start, &anchors);
+        let end = resolve_anchor(rope.end, &anchors);
+        let (start_pos, end_pos) = rope.step(delta, start, end);
+
+        for (anchor, held, reached) in [(rope.start, start, start_pos), (rope.end, end, end_pos)] {
+            let (RopeAnchor::Entity { entity, .. }, RopeEnd::Driven(from)) = (anchor, held) else {
+                continue;
+            };
+            let pull = reached - from;
+            if pull == Vector2::zero() {
+                continue;
+            }
+            let Ok((mut position, _, Some(mut rigidbody))) = anchors.get_mut(entity) else {
+                continue;
+            };
+            position.pos += pull;
+            if delta > 0.0 {
+                rigidbody.velocity += pull / delta;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world_with_delta(delta: f32) -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta,
+            real_delta: delta,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world
+    }
+
+    #[test]
+    fn hanging_body_is_pulled_within_reach() {
+        let mut world = world_with_delta(0.1);
+        let mut falling = RigidBody::new();
+        falling.velocity = Vector2::new(0.0, 50.0);
+        let lamp = world.spawn((MapPosition::new(0.0, 30.0), falling)).id();
+        world.spawn(
+            Rope::new(
+                RopeAnchor::Point(Vector2::zero()),
+                RopeAnchor::entity(lamp),
+                10.0,
+                1,
+            )
+            .with_gravity(Vector2::zero()),
+        );
+
+        world.run_system_once(rope_system).unwrap();
+
+        let position = world.get::<MapPosition>(lamp).unwrap().pos;
+        assert!((position.y - 10.0).abs() < 1e-3);
+        let velocity = world.get::<RigidBody>(lamp).unwrap().velocity;
+        assert!((velocity.y - (50.0 - 200.0)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn plain_entities_drag_the_end_and_are_not_pulled() {
+        let mut world = world_with_delta(0.1);
+        let hand = world.spawn(MapPosition::new(100.0, 0.0)).id();
+        let rope = world
+            .spawn(Rope::new(
+                RopeAnchor::Point(Vector2::zero()),
+                RopeAnchor::Entity {
+                    entity: hand,
+                    offset: Vector2::new(0.0, 5.0),
+                },
+                10.0,
+                2,
+            ))
+            .id();
+
+        world.run_system_once(rope_system).unwrap();
+
+        assert_eq!(
+            world.get::<MapPosition>(hand).unwrap().pos,
+            Vector2::new(100.0, 0.0)
+        );
+        let points = world.get::<Rope>(rope).unwrap().points().to_vec();
+        assert_eq!(points[2], Vector2::new(100.0, 5.0));
+    }
+
+    #[test]
+    fn despawned_anchor_leaves_a_loose_end() {
+        let mut world = world_with_delta(0.1);
+        let gone = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        world.despawn(gone);
+        let rope = world
+            .spawn(Rope::new(
+                RopeAnchor::Point(Vector2::zero()),
+                RopeAnchor::entity(gone),
+                20.0,
+                2,
+            ))
+            .id();
+
+        world.run_system_once(rope_system).unwrap();
+
+        let points = world.get::<Rope>(rope).unwrap().points().to_vec();
+        assert_eq!(points[0], Vector2::zero());
+        assert!(points[2].y > 0.0);
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides the Rust-native collision handling:
 //!
-//! - [`rust_collision_observer`] – receives [`CollisionEvent`](crate::events::collision::CollisionEvent)s
+//! - [`rust_collision_observer`] – receives the frame's [`CollisionBatch`](crate::events::collision::CollisionBatch)
 //!   and dispatches to [`CollisionRule`](crate::components::collision::CollisionRule) callbacks
 //!
 //! Callbacks receive `&mut `[`GameCtx`](crate::systems::GameCtx) for full ECS access.
@@ -10,10 +10,15 @@
 //! # Collision Flow
 //!
 //! 1. [`collision_detector`](crate::systems::collision_detector::collision_detector) detects overlaps
-//!    and emits `CollisionEvent`s
-//! 2. `rust_collision_observer` looks up matching Rust collision rules by
-//!    [`Group`](crate::components::group::Group) names
-//! 3. For each match, computes collision sides and calls the Rust callback
+//!    and emits one `CollisionBatch` with every overlapping pair
+//! 2. `rust_collision_observer` matches all pairs against the Rust collision
+//!    rules by [`Group`](crate::components::group::Group) names and
+//!    [`Tags`](crate::components::tags::Tags) in one pass
+//!    ([`plan_collision_batch`](crate::systems::collision::plan_collision_batch)),
+//!    keeping, per pair, the matches in descending priority up to the first
+//!    exclusive rule
+//! 3. Rule by rule (highest priority first), orients each pair's collision
+//!    sides to the rule and calls the Rust callback in detection order
 //!
 //! # Callback Signature
 //!
@@ -35,59 +40,51 @@
 //! - [`crate::components::collision::CollisionRule`] – defines Rust collision handlers
 //! - [`crate::components::collision::CollisionCallback`] – callback type alias
 //! - [`crate::components::boxcollider::BoxCollider`] – axis-aligned collider
-//! - [`crate::events::collision::CollisionEvent`] – emitted on each collision
+//! - [`crate::events::collision::CollisionBatch`] – all collisions of a frame
 
 use bevy_ecs::prelude::*;
+use smallvec::SmallVec;
+use std::cmp::Reverse;
 
 use crate::components::collision::CollisionRule;
-use crate::events::collision::CollisionEvent;
+use crate::events::collision::CollisionBatch;
 use crate::systems::GameCtx;
-use crate::systems::collision::{compute_sides, resolve_collider_rect, resolve_groups};
+use crate::systems::collision::{PlannedCall, plan_collision_batch};
 
 /// Observer that handles Rust collision rules.
 ///
-/// When a [`CollisionEvent`] is triggered:
+/// When the frame's [`CollisionBatch`] is triggered:
 ///
-/// 1. Looks up [`Group`] names for both entities (returns early if missing)
-/// 2. Queries all [`CollisionRule`] entities for a matching rule
-/// 3. Computes collision sides via [`compute_sides`]
-/// 4. Calls the matched callback with `(ent_a, ent_b, &sides_a, &sides_b, &mut ctx)`
+/// 1. Collects every [`CollisionRule`] and sorts them by descending priority
+/// 2. Matches all pairs against them with [`plan_collision_batch`]: labels
+///    ([`Group`](crate::components::group::Group) names and [`Tags`](crate::components::tags::Tags)) are
+///    resolved once per entity, pairs where either entity has no labels are
+///    skipped, and per pair matching stops after the first exclusive rule
+/// 3. Rule by rule, takes each pair's collision sides ordered to match the
+///    rule via [`CollisionEvent::oriented`](crate::events::collision::CollisionEvent::oriented)
+///    and calls the callback with `(ent_a, ent_b, &sides_a, &sides_b, &mut ctx)`
+///
+/// Commands queued by a callback are applied after the whole batch, so a
+/// callback still sees entities that an earlier one of the same frame
+/// despawned; use `try_despawn` when two pairs can despawn the same entity.
 pub fn rust_collision_observer(
-    trigger: On<CollisionEvent>,
+    trigger: On<CollisionBatch>,
     rules: Query<&CollisionRule>,
     mut ctx: GameCtx,
+    mut plan: Local<Vec<PlannedCall>>,
 ) {
     if rules.is_empty() {
         return;
     }
 
-    let a = trigger.event().a;
-    let b = trigger.event().b;
-
-    let (ga, gb) = match resolve_groups(&ctx.groups, a, b) {
-        Some(names) => names,
-        None => return,
-    };
-
-    for rule in rules.iter() {
-        if let Some((ent_a, ent_b)) = rule.match_and_order(a, b, ga, gb) {
-            let rect_a = resolve_collider_rect(
-                &ctx.positions.as_readonly(),
-                &ctx.global_transforms,
-                &ctx.box_colliders,
-                ent_a,
-            );
-            let rect_b = resolve_collider_rect(
-                &ctx.positions.as_readonly(),
-                &ctx.global_transforms,
-                &ctx.box_colliders,
-                ent_b,
-            );
-            let (sides_a, sides_b) = compute_sides(rect_a, rect_b);
+    let pairs = &trigger.event().pairs;
+    let mut rules: SmallVec<[&CollisionRule; 8]> = rules.iter().collect();
+    rules.sort_by_key(|rule| Reverse(rule.priority));
+    plan_collision_batch(&rules, pairs, &ctx.groups, &ctx.tags, &mut plan);
 
-            let callback = rule.callback;
-            callback(ent_a, ent_b, &sides_a, &sides_b, &mut ctx);
-            return;
-        }
+    for call in plan.iter() {
+        let (sides_a, sides_b, _) = pairs[call.pair].oriented(call.a);
+        let callback = rules[call.rule].callback;
+        callback(call.a, call.b, sides_a, sides_b, &mut ctx);
     }
 }
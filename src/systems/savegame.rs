@@ -0,0 +1,203 @@
+//! Save and load commands.
+//!
+//! [`SaveCmd`] writes the game's world signals and its whitelisted
+//! persistent entities to a save slot; [`LoadCmd`] reads a slot back. Both
+//! run when commands are applied, so a load takes effect before the next
+//! frame's systems.
+//!
+//! - [`process_lua_savegame_commands`] *(feature = "lua")* queues them for
+//!   `engine.save_game` and `engine.load_game`, and the
+//!   [`scenestate`](super::scenestate) commands for `engine.scene_state_forget`
+//!   and `engine.scene_state_clear`.
+//!
+//! # Related
+//!
+//! - [`crate::resources::savegame`] – slot files, the whitelist and the file format
+
+use bevy_ecs::prelude::*;
+use log::{error, info, warn};
+
+use crate::components::mapposition::MapPosition;
+use crate::components::persistent::Persistent;
+use crate::components::signals::Signals;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::SaveGameCmd;
+use crate::resources::savegame::{SaveData, SaveGame, SavedEntity, SavedSignals};
+use crate::resources::worldsignals::WorldSignals;
+#[cfg(feature = "lua")]
+use crate::systems::scenestate::{ClearSceneState, ForgetSceneState};
+
+/// Command writing the game state to a save slot.
+pub struct SaveCmd {
+    pub slot: String,
+}
+
+impl Command for SaveCmd {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let path = match world
+            .get_resource_or_insert_with(SaveGame::default)
+            .slot_path(&self.slot)
+        {
+            Ok(path) => path,
+            Err(e) => {
+                error!("save_game: {e}");
+                return;
+            }
+        };
+        let Some(signals) = world.get_resource::<WorldSignals>() else {
+            return;
+        };
+        let mut data = SaveData {
+            signals: SavedSignals::from_world(signals),
+            ..SaveData::default()
+        };
+        for (name, &entity) in &signals.entities {
+            let Ok(entity) = world.get_entity(entity) else {
+                continue;
+            };
+            if !entity.contains::<Persistent>() {
+                continue;
+            }
+            let saved = SavedEntity {
+                position: entity.get::<MapPosition>().map(|p| [p.pos.x, p.pos.y]),
+                signals: entity.get::<Signals>().map(SavedSignals::from_signals),
+            };
+            data.entities.insert(name.clone(), saved);
+        }
+        match data.write(&path) {
+            Ok(()) => info!("Game saved to {}", path.display()),
+            Err(e) => error!("save_game: cannot write {}: {e}", path.display()),
+        }
+    }
+}
+
+/// Command restoring the game state from a save slot.
+pub struct LoadCmd {
+    pub slot: String,
+}
+
+impl Command for LoadCmd {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let path = match world
+            .get_resource_or_insert_with(SaveGame::default)
+            .slot_path(&self.slot)
+        {
+            Ok(path) => path,
+            Err(e) => {
+                error!("load_game: {e}");
+                return;
+            }
+        };
+        let data = match SaveData::read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("load_game: cannot read {}: {e}", path.display());
+                return;
+            }
+        };
+        let targets: Vec<(Entity, &SavedEntity)> = {
+            let Some(mut signals) = world.get_resource_mut::<WorldSignals>() else {
+                return;
+            };
+            data.signals.apply_to_world(&mut signals);
+            data.entities
+                .iter()
+                .filter_map(|(name, saved)| Some((*signals.get_entity(name)?, saved)))
+                .collect()
+        };
+        for (entity, saved) in targets {
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            if !entity.contains::<Persistent>() {
+                continue;
+            }
+            if let (Some(pos), Some(mut position)) =
+                (saved.position(), entity.get_mut::<MapPosition>())
+            {
+                position.set_pos(pos);
+            }
+            if let Some(saved_signals) = &saved.signals {
+                entity.insert(saved_signals.to_signals());
+            }
+        }
+        info!("Game loaded from {}", path.display());
+    }
+}
+
+/// Applies the save and load commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// after every other Lua domain, so a save sees the frame's other commands.
+#[cfg(feature = "lua")]
+pub fn process_lua_savegame_commands(InMut(buf): InMut<Vec<SaveGameCmd>>, mut commands: Commands) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            SaveGameCmd::Save { slot } => commands.queue(SaveCmd { slot }),
+            SaveGameCmd::Load { slot } => commands.queue(LoadCmd { slot }),
+            SaveGameCmd::ForgetSceneState { scene } => commands.queue(ForgetSceneState { scene }),
+            SaveGameCmd::ClearSceneState => commands.queue(ClearSceneState),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raylib::prelude::Vector2;
+
+    #[test]
+    fn load_restores_what_save_wrote() {
+        let dir = std::env::temp_dir().join(format!("aberred_savegame_{}", std::process::id()));
+        let mut world = World::new();
+        world.insert_resource(SaveGame::new(&dir));
+        world.insert_resource(WorldSignals::default());
+        let player = world
+            .spawn((Persistent, MapPosition::new(10.0, 20.0), Signals::default()))
+            .id();
+        let enemy = world.spawn(MapPosition::new(1.0, 1.0)).id();
+        {
+            let mut signals = world.resource_mut::<WorldSignals>();
+            signals.set_integer("score", 300);
+            signals.set_entity("player", player);
+            signals.set_entity("enemy", enemy);
+        }
+        world
+            .get_mut::<Signals>(player)
+            .unwrap()
+            .set_integer("hp", 3);
+        SaveCmd { slot: "1".into() }.apply(&mut world);
+
+        world.resource_mut::<WorldSignals>().set_integer("score", 0);
+        world
+            .get_mut::<MapPosition>(player)
+            .unwrap()
+            .set_pos(Vector2::zero());
+        world
+            .get_mut::<MapPosition>(enemy)
+            .unwrap()
+            .set_pos(Vector2::zero());
+        world
+            .get_mut::<Signals>(player)
+            .unwrap()
+            .set_integer("hp", 1);
+        LoadCmd { slot: "1".into() }.apply(&mut world);
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(
+            world.resource::<WorldSignals>().get_integer("score"),
+            Some(300)
+        );
+        assert_eq!(world.get::<MapPosition>(player).unwrap().pos.x, 10.0);
+        assert_eq!(
+            world.get::<Signals>(player).unwrap().get_integer("hp"),
+            Some(3)
+        );
+        // Not persistent, so not in the save
+        assert_eq!(world.get::<MapPosition>(enemy).unwrap().pos.x, 0.0);
+    }
+}
@@ -30,12 +30,14 @@ use log::{debug, error, info};
 use raylib::prelude::{Camera2D, Color, Vector2};
 use rustc_hash::FxHashSet;
 
+use crate::components::audiotriggers::AudioTriggers;
 use crate::components::persistent::{CleanableEntity, Persistent};
 use crate::resources::appstate::AppState;
 use crate::resources::fontstore::FontStore;
 use crate::resources::group::TrackedGroups;
 use crate::resources::input::InputState;
 use crate::resources::scenemanager::SceneManager;
+use crate::resources::scenestate::SceneState;
 use crate::resources::screensize::ScreenSize;
 use crate::resources::signal_keys as sk;
 use crate::resources::systemsstore::SystemsStore;
@@ -43,6 +45,7 @@ use crate::resources::texturestore::TextureStore;
 use crate::resources::worldsignals::WorldSignals;
 use crate::resources::worldtime::WorldTime;
 use crate::systems::GameCtx;
+use crate::systems::scenestate::{RestorableQuery, save_scene_state};
 
 // ---------------------------------------------------------------------------
 // Callback type aliases
@@ -181,7 +184,9 @@ pub struct SceneDescriptor {
 /// Flow:
 /// 1. Despawn all non-[`Persistent`] entities
 /// 2. Clear tracked groups and group counts
-/// 3. Read `WorldSignals["scene"]` for the target scene name
+/// 3. Read `WorldSignals["scene"]` for the target scene name and save the
+///    [`RestoreOnReturn`](crate::components::restoreonreturn::RestoreOnReturn)
+///    entities of the scene being left into [`SceneState`]
 /// 4. Call `on_exit` on the previous scene (if any)
 /// 5. Write previous scene name to `WorldSignals["previous_scene"]` (if any)
 /// 6. Update `SceneManager.active_scene`
@@ -192,13 +197,18 @@ pub fn scene_switch_system(
     persistent_entities: Query<Entity, With<Persistent>>,
     mut tracked_groups: ResMut<TrackedGroups>,
     mut scene_manager: ResMut<SceneManager>,
+    mut scene_state: ResMut<SceneState>,
+    restorables: RestorableQuery,
 ) {
     debug!("scene_switch_system: System called!");
 
     let prev_scene = scene_manager.active_scene.clone();
 
     for entity in entities_to_clean.iter() {
-        ctx.commands.entity(entity).try_despawn();
+        ctx.commands
+            .entity(entity)
+            .try_remove::<AudioTriggers>()
+            .try_despawn();
     }
 
     // Clear entity registrations for despawned (non-persistent) entities
@@ -215,6 +225,8 @@ pub fn scene_switch_system(
         .cloned()
         .unwrap_or_else(|| sk::DEFAULT_SCENE.to_string());
 
+    save_scene_state(&mut scene_state, &restorables, &scene_name);
+
     // Call on_exit for the previous scene
     if let Some(ref prev_name) = prev_scene
         && let Some(descriptor) = scene_manager.get(prev_name)
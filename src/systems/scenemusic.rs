@@ -0,0 +1,101 @@
+//! Scene music playlist system.
+//!
+//! [`scene_music_system`] switches [`SceneMusic`] to the playlist of the
+//! current scene when the scene changes, moves to the next track when one
+//! finishes, advances the crossfades and sends the resulting commands to the
+//! audio thread. It runs after the audio messages of the frame are polled.
+//!
+//! - [`process_lua_music_commands`] *(feature = "lua")* applies the playlists
+//!   of `engine.set_scene_music`.
+//!
+//! # Related
+//!
+//! - [`crate::resources::scenemusic`] – playlists and crossfade rules
+
+use bevy_ecs::prelude::*;
+
+use crate::events::audio::{AudioCmd, AudioMessage};
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::MusicCmd;
+use crate::resources::scenemusic::SceneMusic;
+use crate::resources::signal_keys as sk;
+use crate::resources::worldsignals::WorldSignals;
+use crate::resources::worldtime::WorldTime;
+
+/// Plays the current scene's playlist and advances it.
+pub fn scene_music_system(
+    mut music: ResMut<SceneMusic>,
+    signals: Res<WorldSignals>,
+    time: Res<WorldTime>,
+    mut messages: MessageReader<AudioMessage>,
+    mut audio_cmds: MessageWriter<AudioCmd>,
+) {
+    let scene = signals
+        .get_string(sk::SCENE)
+        .map_or(sk::DEFAULT_SCENE, String::as_str);
+    if music.dirty || music.scene.as_deref() != Some(scene) {
+        audio_cmds.write_batch(music.enter(scene));
+    }
+    for msg in messages.read() {
+        if let AudioMessage::MusicFinished { id } = msg {
+            audio_cmds.write_batch(music.track_finished(id));
+        }
+    }
+    audio_cmds.write_batch(music.update(time.delta));
+}
+
+/// Applies scene music commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_music_commands(InMut(buf): InMut<Vec<MusicCmd>>, mut music: ResMut<SceneMusic>) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            MusicCmd::SetScene { scene, playlist } => match playlist {
+                Some(playlist) => music.set(scene, playlist),
+                None => music.remove(&scene),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::scenemusic::Playlist;
+    use bevy_ecs::message::Messages;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn played(world: &mut World) -> Vec<String> {
+        world
+            .resource_mut::<Messages<AudioCmd>>()
+            .drain()
+            .filter_map(|cmd| match cmd {
+                AudioCmd::PlayMusic { id, .. } => Some(id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn starts_the_scene_playlist_and_follows_finished_tracks() {
+        let mut world = World::new();
+        world.init_resource::<Messages<AudioCmd>>();
+        world.init_resource::<Messages<AudioMessage>>();
+        world.insert_resource(WorldTime::default());
+        let mut signals = WorldSignals::default();
+        signals.set_string(sk::SCENE, "level1");
+        world.insert_resource(signals);
+        let mut music = SceneMusic::default();
+        music.set("level1", Playlist::new(["a", "b"]));
+        world.insert_resource(music);
+
+        world.run_system_once(scene_music_system).unwrap();
+        assert_eq!(played(&mut world), vec!["a"]);
+
+        world.write_message(AudioMessage::MusicFinished { id: "a".into() });
+        world.run_system_once(scene_music_system).unwrap();
+        assert_eq!(played(&mut world), vec!["b"]);
+    }
+}
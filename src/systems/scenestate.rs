@@ -0,0 +1,266 @@
+//! Scene re-entry restoration.
+//!
+//! [`restore_on_return_system`] applies the snapshots saved in
+//! [`SceneState`] to newly spawned [`RestoreOnReturn`] entities, and
+//! [`save_scene_state`] is the helper the scene switch systems call before
+//! despawning the scene being left.
+//!
+//! [`ForgetSceneState`] and [`ClearSceneState`] discard saved state for a
+//! fresh start (Lua: `engine.scene_state_forget` and
+//! `engine.scene_state_clear`).
+//!
+//! # Related
+//!
+//! - [`crate::resources::scenestate`] – the snapshot store
+//! - [`crate::components::restoreonreturn`] – the marker and its save filter
+
+use bevy_ecs::prelude::*;
+
+use crate::components::mapposition::MapPosition;
+use crate::components::persistent::{CleanableEntity, Persistent};
+use crate::components::restoreonreturn::RestoreOnReturn;
+use crate::components::signals::Signals;
+use crate::resources::scenestate::{EntitySnapshot, SceneState};
+
+/// Query of the [`RestoreOnReturn`] entities a scene switch will despawn.
+pub type RestorableQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static RestoreOnReturn,
+        Option<&'static MapPosition>,
+        Option<&'static Signals>,
+    ),
+    CleanableEntity,
+>;
+
+/// Snapshot the scene being left and make `next_scene` the active one.
+///
+/// Must run before the old scene's entities are despawned.
+pub fn save_scene_state(
+    scene_state: &mut SceneState,
+    restorables: &RestorableQuery,
+    next_scene: &str,
+) {
+    scene_state.switch_scene(
+        next_scene,
+        restorables.iter().map(|(restore, position, signals)| {
+            (
+                restore.key.clone(),
+                EntitySnapshot::capture(restore, position, signals),
+            )
+        }),
+    );
+}
+
+/// Command dropping everything [`SceneState`] saved for one scene.
+pub struct ForgetSceneState {
+    pub scene: String,
+}
+
+impl Command for ForgetSceneState {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        if let Some(mut scene_state) = world.get_resource_mut::<SceneState>() {
+            scene_state.forget_scene(&self.scene);
+        }
+    }
+}
+
+/// Command dropping everything [`SceneState`] saved, e.g. for a new game.
+pub struct ClearSceneState;
+
+impl Command for ClearSceneState {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        if let Some(mut scene_state) = world.get_resource_mut::<SceneState>() {
+            scene_state.clear();
+        }
+    }
+}
+
+/// Restores newly spawned [`RestoreOnReturn`] entities from [`SceneState`].
+///
+/// Entities saved as destroyed are despawned; the others get their saved
+/// position and signals back, as selected by their save filter.
+pub fn restore_on_return_system(
+    mut commands: Commands,
+    mut scene_state: ResMut<SceneState>,
+    mut query: Query<
+        (
+            Entity,
+            &RestoreOnReturn,
+            Option<&mut MapPosition>,
+            Option<&mut Signals>,
+        ),
+        (Added<RestoreOnReturn>, Without<Persistent>),
+    >,
+) {
+    for (entity, restore, position, signals) in query.iter_mut() {
+        let Some(snapshot) = scene_state.track(&restore.key) else {
+            continue;
+        };
+        if snapshot.destroyed {
+            commands.entity(entity).try_despawn();
+            continue;
+        }
+        if restore.position
+            && let (Some(saved), Some(mut position)) = (snapshot.position, position)
+        {
+            position.pos = saved;
+        }
+        if restore.signals
+            && let (Some(saved), Some(mut signals)) = (snapshot.signals.as_ref(), signals)
+        {
+            *signals = saved.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raylib::prelude::Vector2;
+
+    fn leave(world: &mut World, next: &str) {
+        let mut schedule = Schedule::default();
+        let next = next.to_owned();
+        schedule.add_systems(
+            move |mut commands: Commands,
+                  mut state: ResMut<SceneState>,
+                  restorables: RestorableQuery,
+                  all: Query<Entity, CleanableEntity>| {
+                save_scene_state(&mut state, &restorables, &next);
+                for entity in all.iter() {
+                    commands.entity(entity).try_despawn();
+                }
+            },
+        );
+        schedule.run(world);
+    }
+
+    fn restore(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(restore_on_return_system);
+        schedule.run(world);
+    }
+
+    fn spawn_cave(world: &mut World) -> (Entity, Entity) {
+        let door = world
+            .spawn((
+                MapPosition::new(10.0, 0.0),
+                Signals::default(),
+                RestoreOnReturn::new("door"),
+            ))
+            .id();
+        let coin = world
+            .spawn((MapPosition::new(20.0, 0.0), RestoreOnReturn::new("coin")))
+            .id();
+        (door, coin)
+    }
+
+    #[test]
+    fn reentering_scene_restores_opened_door_and_collected_coin() {
+        let mut world = World::new();
+        world.init_resource::<SceneState>();
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+
+        let (door, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+
+        // Open the door, collect the coin, leave.
+        world.get_mut::<MapPosition>(door).unwrap().pos = Vector2::new(10.0, -32.0);
+        world.get_mut::<Signals>(door).unwrap().set_flag("open");
+        world.despawn(coin);
+        leave(&mut world, "town");
+
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+        let (door, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+
+        assert!(world.get_entity(coin).is_err());
+        assert_eq!(
+            world.get::<MapPosition>(door).unwrap().pos,
+            Vector2::new(10.0, -32.0)
+        );
+        assert!(world.get::<Signals>(door).unwrap().has_flag("open"));
+    }
+
+    #[test]
+    fn reentering_a_forgotten_scene_spawns_fresh_entities() {
+        let mut world = World::new();
+        world.init_resource::<SceneState>();
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+
+        let (door, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+        world.get_mut::<MapPosition>(door).unwrap().pos = Vector2::new(10.0, -32.0);
+        world.despawn(coin);
+        leave(&mut world, "town");
+
+        world.commands().queue(ForgetSceneState {
+            scene: "cave".to_string(),
+        });
+        world.flush();
+
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+        let (door, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+        assert!(world.get_entity(coin).is_ok());
+        assert_eq!(
+            world.get::<MapPosition>(door).unwrap().pos,
+            Vector2::new(10.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn clear_scene_state_forgets_the_active_visit() {
+        let mut world = World::new();
+        world.init_resource::<SceneState>();
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+
+        let (_, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+        world.despawn(coin);
+
+        // "Restart level": clear, then re-run the same scene.
+        world.commands().queue(ClearSceneState);
+        world.flush();
+        leave(&mut world, "cave");
+
+        let (_, coin) = spawn_cave(&mut world);
+        restore(&mut world);
+        assert!(world.get_entity(coin).is_ok());
+    }
+
+    #[test]
+    fn save_filter_skips_position() {
+        let mut world = World::new();
+        world.init_resource::<SceneState>();
+        world.resource_mut::<SceneState>().switch_scene("cave", []);
+
+        let crate_entity = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                RestoreOnReturn::new("crate").with_position(false),
+            ))
+            .id();
+        restore(&mut world);
+        world.get_mut::<MapPosition>(crate_entity).unwrap().pos = Vector2::new(50.0, 0.0);
+        leave(&mut world, "cave");
+
+        let crate_entity = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                RestoreOnReturn::new("crate").with_position(false),
+            ))
+            .id();
+        restore(&mut world);
+        assert_eq!(
+            world.get::<MapPosition>(crate_entity).unwrap().pos,
+            Vector2::zero()
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Sensor ray casting.
+//!
+//! [`sensor_system`] casts every [`SensorRay`] of every [`Sensor`] against the
+//! [`BoxCollider`]s of other labeled entities and mirrors the hit state into
+//! the sensor entity's [`Signals`] flags. A collider with a [`Slope`] is hit
+//! only on its triangle.
+//!
+//! Flags are only written when the hit state changes, so `Changed<Signals>`
+//! consumers are not woken up every frame.
+//!
+//! # Related
+//!
+//! - [`crate::components::sensor`] – sensor and ray definitions
+//! - [`crate::systems::collision_detector`] – AABB overlap detection
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::boxcollider::{BoxCollider, ColliderDisabled};
+use crate::components::collision::pattern_matches;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::sensor::{Sensor, SensorRay};
+use crate::components::signals::Signals;
+use crate::components::slope::Slope;
+use crate::components::tags::{Tags, entity_labels};
+
+/// Fraction `t` in `0..=1` along the segment `start + delta * t` where it
+/// first enters `rect`, or `None` if it misses. A segment starting inside
+/// the rectangle hits at `t = 0`.
+pub fn segment_rect_hit(start: Vector2, delta: Vector2, rect: &Rectangle) -> Option<f32> {
+    segment_rect_span(start, delta, rect).map(|(t_min, _)| t_min)
+}
+
+/// Like [`segment_rect_hit`], but against the right triangle a [`Slope`]
+/// makes of `rect`.
+pub fn segment_slope_hit(
+    start: Vector2,
+    delta: Vector2,
+    rect: &Rectangle,
+    slope: &Slope,
+) -> Option<f32> {
+    let (mut t_min, mut t_max) = segment_rect_span(start, delta, rect)?;
+    // Clip to the solid side of the slanted edge: n · (p - a) <= 0.
+    let a = Vector2::new(rect.x, slope.surface_y(rect, rect.x));
+    let b = Vector2::new(
+        rect.x + rect.width,
+        slope.surface_y(rect, rect.x + rect.width),
+    );
+    let mut n = Vector2::new(b.y - a.y, a.x - b.x);
+    if !slope.corner.is_floor() {
+        n = -n;
+    }
+    let f0 = n.dot(start - a);
+    let fd = n.dot(delta);
+    if fd.abs() <= f32::EPSILON {
+        return (f0 <= 0.0).then_some(t_min);
+    }
+    let t = -f0 / fd;
+    if fd < 0.0 {
+        t_min = t_min.max(t);
+    } else {
+        t_max = t_max.min(t);
+    }
+    (t_min <= t_max).then_some(t_min)
+}
+
+/// Range `(t_min, t_max)` of the segment `start + delta * t`, `t` in `0..=1`,
+/// inside `rect`.
+fn segment_rect_span(start: Vector2, delta: Vector2, rect: &Rectangle) -> Option<(f32, f32)> {
+    let mut t_min = 0.0f32;
+    let mut t_max = 1.0f32;
+    let axes = [
+        (start.x, delta.x, rect.x, rect.x + rect.width),
+        (start.y, delta.y, rect.y, rect.y + rect.height),
+    ];
+    for (origin, d, lo, hi) in axes {
+        if d.abs() <= f32::EPSILON {
+            if origin < lo || origin > hi {
+                return None;
+            }
+            continue;
+        }
+        let inv = 1.0 / d;
+        let (t0, t1) = ((lo - origin) * inv, (hi - origin) * inv);
+        t_min = t_min.max(t0.min(t1));
+        t_max = t_max.min(t0.max(t1));
+        if t_min > t_max {
+            return None;
+        }
+    }
+    Some((t_min, t_max))
+}
+
+fn ray_hits_any<'a>(
+    ray: &SensorRay,
+    self_entity: Entity,
+    world_pos: Vector2,
+    targets: impl IntoIterator<
+        Item = (
+            Entity,
+            Rectangle,
+            Option<&'a Slope>,
+            Option<&'a Group>,
+            Option<&'a Tags>,
+        ),
+    >,
+) -> bool {
+    let (start, delta) = ray.segment(world_pos);
+    targets
+        .into_iter()
+        .any(|(entity, rect, slope, group, tags)| {
+            entity != self_entity
+                && entity_labels(group, tags).any(|l| pattern_matches(&ray.mask, l))
+                && match slope {
+                    Some(slope) => segment_slope_hit(start, delta, &rect, slope).is_some(),
+                    None => segment_rect_hit(start, delta, &rect).is_some(),
+                }
+        })
+}
+
+/// Casts sensor rays and updates the matching [`Signals`] flags.
+///
+/// Rays start at the entity's world position ([`GlobalTransform2D`] when
+/// present, otherwise [`MapPosition`]) plus the ray offset. Only entities with
+/// a [`Group`] or [`Tags`] and an enabled collider can be hit. Run after
+/// transform propagation.
+#[allow(clippy::type_complexity)]
+pub fn sensor_system(
+    mut sensors: Query<(
+        Entity,
+        &Sensor,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+        &mut Signals,
+    )>,
+    targets: Query<
+        (
+            Entity,
+            &MapPosition,
+            &BoxCollider,
+            Option<&Slope>,
+            Option<&GlobalTransform2D>,
+            Option<&Group>,
+            Option<&Tags>,
+        ),
+        (Without<ColliderDisabled>, Or<(With<Group>, With<Tags>)>),
+    >,
+) {
+    crate::tracy::tracy_span!("sensor_system");
+    if sensors.is_empty() {
+        return;
+    }
+    for (entity, sensor, position, global, mut signals) in sensors.iter_mut() {
+        let world_pos = global.map_or(position.pos, |gt| gt.position);
+        for ray in &sensor.rays {
+            let hit = ray_hits_any(
+                ray,
+                entity,
+                world_pos,
+                targets
+                    .iter()
+                    .map(|(e, pos, collider, slope, gt, group, tags)| {
+                        let p = gt.map_or(pos.pos, |gt| gt.position);
+                        (e, collider.as_rectangle(p), slope, group, tags)
+                    }),
+            );
+            if signals.has_flag(&ray.flag) != hit {
+                if hit {
+                    signals.set_flag(&ray.flag);
+                } else {
+                    signals.clear_flag(&ray.flag);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::slope::SlopeCorner;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width: w,
+            height: h,
+        }
+    }
+
+    #[test]
+    fn segment_rect_hit_cases() {
+        let r = rect(0.0, 10.0, 20.0, 5.0);
+        // Straight down into the top edge.
+        let t = segment_rect_hit(Vector2::new(5.0, 0.0), Vector2::new(0.0, 20.0), &r).unwrap();
+        assert!((t - 0.5).abs() < 1e-6);
+        // Too short.
+        assert!(segment_rect_hit(Vector2::new(5.0, 0.0), Vector2::new(0.0, 5.0), &r).is_none());
+        // Parallel and outside.
+        assert!(segment_rect_hit(Vector2::new(30.0, 0.0), Vector2::new(0.0, 20.0), &r).is_none());
+        // Starting inside.
+        assert_eq!(
+            segment_rect_hit(Vector2::new(5.0, 12.0), Vector2::new(1.0, 0.0), &r),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn segment_slope_hit_only_hits_the_triangle() {
+        // 20x10 ramp rising to the right: surface at y 5 over x 10.
+        let slope = Slope::new(SlopeCorner::BottomRight);
+        let r = rect(0.0, 0.0, 20.0, 10.0);
+        let down = Vector2::new(0.0, 4.0);
+        // Inside the box but above the surface: too short to reach it.
+        assert!(segment_slope_hit(Vector2::new(10.0, 0.0), down, &r, &slope).is_none());
+        let t = segment_slope_hit(Vector2::new(10.0, 2.0), down, &r, &slope).unwrap();
+        assert!((t - 0.75).abs() < 1e-6);
+        // Sideways into the tall side.
+        assert_eq!(
+            segment_slope_hit(Vector2::new(24.0, 1.0), Vector2::new(-8.0, 0.0), &r, &slope),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn sets_and_clears_flag_from_masked_targets() {
+        let mut world = World::new();
+        let player = world
+            .spawn((
+                MapPosition::new(5.0, 9.0),
+                Signals::default(),
+                Sensor::new().with_ray(
+                    SensorRay::new("grounded", Vector2::zero(), Vector2::new(0.0, 1.0), 2.0)
+                        .with_mask("ground"),
+                ),
+            ))
+            .id();
+        let ground = world
+            .spawn((
+                Group::new("ground"),
+                MapPosition::new(0.0, 10.0),
+                BoxCollider::new(20.0, 5.0),
+            ))
+            .id();
+        world.spawn((
+            Group::new("coin"),
+            MapPosition::new(0.0, 10.0),
+            BoxCollider::new(20.0, 5.0),
+        ));
+
+        world.run_system_once(sensor_system).unwrap();
+        assert!(world.get::<Signals>(player).unwrap().has_flag("grounded"));
+
+        world.entity_mut(ground).insert(ColliderDisabled);
+        world.run_system_once(sensor_system).unwrap();
+        assert!(!world.get::<Signals>(player).unwrap().has_flag("grounded"));
+    }
+}
@@ -0,0 +1,112 @@
+//! Spatial index maintenance.
+//!
+//! [`update_spatial_index_system`] rebuilds the
+//! [`SpatialIndex`](crate::resources::spatialindex::SpatialIndex) resource
+//! from every entity with a position and a [`Group`] or [`Tags`]. Frames where
+//! nothing relevant moved, changed label, or was removed skip the rebuild, so
+//! static scenes (e.g. large tilemaps) cost only the change check.
+//!
+//! # Related
+//!
+//! - [`crate::resources::spatialindex`] – the grid index and its queries
+//! - [`crate::systems::group::update_group_aggregates_system`] – per-group summaries
+
+use bevy_ecs::prelude::*;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::tags::{Tags, entity_labels};
+use crate::resources::spatialindex::SpatialIndex;
+
+/// Rebuilds [`SpatialIndex`] when any labeled entity moved, changed labels,
+/// or lost its position/labels (including despawns).
+///
+/// Positions come from [`GlobalTransform2D`] when present, otherwise from
+/// [`MapPosition`]. Run after transform propagation.
+#[allow(clippy::type_complexity)]
+pub fn update_spatial_index_system(
+    query: Query<
+        (
+            Entity,
+            Option<&Group>,
+            Option<&Tags>,
+            Option<&MapPosition>,
+            Option<&GlobalTransform2D>,
+        ),
+        Or<(With<Group>, With<Tags>)>,
+    >,
+    changed: Query<
+        (),
+        Or<(
+            Changed<MapPosition>,
+            Changed<GlobalTransform2D>,
+            Changed<Group>,
+            Changed<Tags>,
+        )>,
+    >,
+    mut removed_positions: RemovedComponents<MapPosition>,
+    mut removed_groups: RemovedComponents<Group>,
+    mut removed_tags: RemovedComponents<Tags>,
+    mut index: ResMut<SpatialIndex>,
+) {
+    crate::tracy::tracy_span!("update_spatial_index");
+    // Drain every reader so removals aren't reported again next frame.
+    let removed = removed_positions.read().count()
+        + removed_groups.read().count()
+        + removed_tags.read().count();
+    if removed == 0 && changed.is_empty() {
+        return;
+    }
+
+    let index = index.as_mut();
+    index.clear();
+    for (entity, group, tags, map_pos, global) in query.iter() {
+        let pos = match (global, map_pos) {
+            (Some(gt), _) => gt.position,
+            (None, Some(mp)) => mp.pos,
+            (None, None) => continue,
+        };
+        index.insert(entity, pos, entity_labels(group, tags));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn rebuilds_from_labeled_positioned_entities() {
+        let mut world = World::new();
+        world.insert_resource(SpatialIndex::new(16.0));
+        let enemy = world
+            .spawn((Group::new("enemy"), MapPosition::new(5.0, 5.0)))
+            .id();
+        world.spawn(Group::new("no_position"));
+        world.spawn(MapPosition::new(6.0, 6.0)); // unlabeled
+
+        world.run_system_once(update_spatial_index_system).unwrap();
+
+        let index = world.resource::<SpatialIndex>();
+        assert_eq!(index.len(), 1);
+        let mut out = Vec::new();
+        index.query_rect(0.0, 0.0, 10.0, 10.0, Some("enemy"), &mut out);
+        assert_eq!(out, vec![enemy]);
+    }
+
+    #[test]
+    fn despawn_triggers_rebuild() {
+        let mut world = World::new();
+        world.insert_resource(SpatialIndex::default());
+        let enemy = world
+            .spawn((Group::new("enemy"), MapPosition::new(5.0, 5.0)))
+            .id();
+        world.run_system_once(update_spatial_index_system).unwrap();
+        assert_eq!(world.resource::<SpatialIndex>().len(), 1);
+
+        world.despawn(enemy);
+        world.run_system_once(update_spatial_index_system).unwrap();
+        assert!(world.resource::<SpatialIndex>().is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! Spawn positions spread over an area.
+//!
+//! [`sample_points`] places `count` points inside a [`SpawnArea`] following a
+//! [`Distribution`]: the "scatter N things over this region" step behind
+//! snowfields, pickups and enemy waves. `engine.spawn_prefab_area` clones a
+//! registered entity onto each point in one call, so scripts don't loop over
+//! `engine.clone()` with their own random numbers.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let area = SpawnArea::Rect(Rectangle::new(0.0, 0.0, 640.0, 120.0));
+//! for point in sample_points(&area, 50, Distribution::Poisson, &mut fastrand::Rng::new()) {
+//!     commands.spawn((MapPosition::from_vec(point), /* ... */));
+//! }
+//! ```
+
+use fastrand::Rng;
+use raylib::prelude::{Rectangle, Vector2};
+use std::f32::consts::{PI, TAU};
+
+/// Candidates tried for each point of a [`Distribution::Poisson`] layout.
+const POISSON_CANDIDATES: usize = 12;
+
+/// Region spawn positions are taken from, in world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnArea {
+    /// Axis-aligned rectangle from its top-left corner.
+    Rect(Rectangle),
+    /// Disc around `center`.
+    Circle { center: Vector2, radius: f32 },
+}
+
+impl SpawnArea {
+    /// Parses `"rect"` or `"circle"`. `x`, `y` are the top-left corner of a
+    /// rect and the center of a circle.
+    pub fn parse(shape: &str, x: f32, y: f32, w: f32, h: f32, radius: f32) -> Option<Self> {
+        match shape {
+            "rect" => Some(SpawnArea::Rect(Rectangle::new(
+                x,
+                y,
+                w.max(0.0),
+                h.max(0.0),
+            ))),
+            "circle" => Some(SpawnArea::Circle {
+                center: Vector2::new(x, y),
+                radius: radius.max(0.0),
+            }),
+            _ => None,
+        }
+    }
+
+    /// A uniformly random point inside the area.
+    pub fn random_point(&self, rng: &mut Rng) -> Vector2 {
+        match *self {
+            SpawnArea::Rect(rect) => Vector2::new(
+                rect.x + rng.f32() * rect.width,
+                rect.y + rng.f32() * rect.height,
+            ),
+            SpawnArea::Circle { center, radius } => {
+                // sqrt keeps the density even instead of bunching at the center.
+                let distance = radius * rng.f32().sqrt();
+                let angle = rng.f32() * TAU;
+                center + Vector2::new(angle.cos(), angle.sin()) * distance
+            }
+        }
+    }
+}
+
+/// How points are spread over a [`SpawnArea`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Distribution {
+    /// Independent random points; clumps and gaps are likely.
+    #[default]
+    Uniform,
+    /// Random points kept apart from each other (best-candidate sampling),
+    /// for natural-looking scatter without overlaps.
+    Poisson,
+    /// Evenly spaced: rows and columns in a rect, a sunflower spiral in a
+    /// circle. No randomness.
+    Grid,
+}
+
+impl Distribution {
+    /// Parses `"uniform"`, `"poisson"` or `"grid"`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "uniform" => Some(Distribution::Uniform),
+            "poisson" => Some(Distribution::Poisson),
+            "grid" => Some(Distribution::Grid),
+            _ => None,
+        }
+    }
+}
+
+/// `count` points inside `area` spread by `distribution`.
+pub fn sample_points(
+    area: &SpawnArea,
+    count: usize,
+    distribution: Distribution,
+    rng: &mut Rng,
+) -> Vec<Vector2> {
+    match distribution {
+        Distribution::Uniform => (0..count).map(|_| area.random_point(rng)).collect(),
+        Distribution::Poisson => best_candidate(area, count, rng),
+        Distribution::Grid => grid(area, count),
+    }
+}
+
+/// Mitchell's best-candidate algorithm: each point is the candidate farthest
+/// from the points placed so far.
+fn best_candidate(area: &SpawnArea, count: usize, rng: &mut Rng) -> Vec<Vector2> {
+    let mut points: Vec<Vector2> = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut best = area.random_point(rng);
+        let mut best_distance = -1.0;
+        for _ in 0..POISSON_CANDIDATES {
+            let candidate = area.random_point(rng);
+            let nearest = points
+                .iter()
+                .map(|p| (*p - candidate).length_sqr())
+                .fold(f32::INFINITY, f32::min);
+            if nearest > best_distance {
+                best = candidate;
+                best_distance = nearest;
+            }
+        }
+        points.push(best);
+    }
+    points
+}
+
+fn grid(area: &SpawnArea, count: usize) -> Vec<Vector2> {
+    if count == 0 {
+        return Vec::new();
+    }
+    match *area {
+        SpawnArea::Rect(rect) => {
+            // Pick the column count that makes the cells closest to square.
+            let aspect = if rect.height > 0.0 {
+                rect.width / rect.height
+            } else {
+                count as f32
+            };
+            let columns = ((count as f32 * aspect).sqrt().round() as usize).clamp(1, count);
+            let rows = count.div_ceil(columns);
+            let cell = Vector2::new(rect.width / columns as f32, rect.height / rows as f32);
+            (0..count)
+                .map(|i| {
+                    let (column, row) = (i % columns, i / columns);
+                    Vector2::new(
+                        rect.x + (column as f32 + 0.5) * cell.x,
+                        rect.y + (row as f32 + 0.5) * cell.y,
+                    )
+                })
+                .collect()
+        }
+        SpawnArea::Circle { center, radius } => {
+            let golden_angle = PI * (3.0 - 5.0_f32.sqrt());
+            (0..count)
+                .map(|i| {
+                    let distance = radius * ((i as f32 + 0.5) / count as f32).sqrt();
+                    let angle = i as f32 * golden_angle;
+                    center + Vector2::new(angle.cos(), angle.sin()) * distance
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inside(area: &SpawnArea, p: Vector2) -> bool {
+        match *area {
+            SpawnArea::Rect(r) => {
+                p.x >= r.x && p.x <= r.x + r.width && p.y >= r.y && p.y <= r.y + r.height
+            }
+            SpawnArea::Circle { center, radius } => (p - center).length() <= radius + 1e-3,
+        }
+    }
+
+    #[test]
+    fn every_distribution_stays_inside_the_area() {
+        let areas = [
+            SpawnArea::parse("rect", 10.0, 20.0, 200.0, 50.0, 0.0).unwrap(),
+            SpawnArea::parse("circle", -40.0, 0.0, 0.0, 0.0, 30.0).unwrap(),
+        ];
+        let mut rng = Rng::with_seed(7);
+        for area in &areas {
+            for distribution in [
+                Distribution::Uniform,
+                Distribution::Poisson,
+                Distribution::Grid,
+            ] {
+                let points = sample_points(area, 25, distribution, &mut rng);
+                assert_eq!(points.len(), 25);
+                assert!(points.iter().all(|&p| inside(area, p)), "{distribution:?}");
+            }
+        }
+        assert!(SpawnArea::parse("hexagon", 0.0, 0.0, 1.0, 1.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn poisson_spreads_points_further_apart_than_uniform() {
+        let area = SpawnArea::Rect(Rectangle::new(0.0, 0.0, 100.0, 100.0));
+        let closest_pair = |points: &[Vector2]| {
+            let mut closest = f32::INFINITY;
+            for (i, a) in points.iter().enumerate() {
+                for b in &points[i + 1..] {
+                    closest = closest.min((*a - *b).length());
+                }
+            }
+            closest
+        };
+        let mut rng = Rng::with_seed(3);
+        let poisson = sample_points(&area, 30, Distribution::Poisson, &mut rng);
+        let uniform = sample_points(&area, 30, Distribution::Uniform, &mut rng);
+        assert!(closest_pair(&poisson) > closest_pair(&uniform));
+    }
+
+    #[test]
+    fn grid_fills_rows_of_a_rect() {
+        let area = SpawnArea::Rect(Rectangle::new(0.0, 0.0, 40.0, 20.0));
+        let points = sample_points(&area, 8, Distribution::Grid, &mut Rng::with_seed(1));
+        let first_row: Vec<_> = points[..4].iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(
+            first_row,
+            vec![(5.0, 5.0), (15.0, 5.0), (25.0, 5.0), (35.0, 5.0)]
+        );
+        assert_eq!((points[4].x, points[4].y), (5.0, 15.0));
+    }
+}
@@ -0,0 +1,224 @@
+//! Destructible terrain systems.
+//!
+//! [`terrain_system`] turns newly added [`Terrain`] components into editable
+//! bitmaps and keeps their textures in sync with carving. [`CarveTerrainCircle`]
+//! cuts holes into a spawned terrain; it is queued on the terrain entity like
+//! the tilemap edit commands in [`crate::systems::tilemap`].
+//!
+//! These functions are always compiled (no feature gates) so Rust-only
+//! downstream crates can use them without enabling the `lua` feature.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::EntityCommand;
+use log::warn;
+use raylib::prelude::{PixelFormat, RaylibTexture2D, Rectangle, Vector2};
+
+use crate::components::boxcollider::BoxCollider;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::pixelcollision::PixelCollision;
+use crate::components::sprite::Sprite;
+use crate::components::terrain::{Terrain, TerrainSurface, terrain_texture_key};
+use crate::resources::alphamask::AlphaMasks;
+use crate::resources::texturestore::TextureStore;
+use crate::systems::RaylibAccess;
+
+/// Sets up new terrains, uploads carved texels and frees despawned terrains.
+///
+/// For each added [`Terrain`], copies its source texture into a texture of
+/// its own in the [`TextureStore`], stores the copy's alpha mask in
+/// [`AlphaMasks`] under the same key (see [`terrain_texture_key`]) and
+/// inserts [`TerrainSurface`], [`Sprite`], a full-size [`BoxCollider`] and
+/// [`PixelCollision`]. A default `(0, 0)` [`MapPosition`] is inserted if the
+/// entity has none.
+///
+/// Terrains carved since the last run get their dirty texels uploaded to the
+/// texture, and their [`Sprite`] is marked changed so dirty-rectangle
+/// rendering repaints them.
+#[allow(clippy::type_complexity)]
+pub fn terrain_system(
+    mut commands: Commands,
+    added: Query<(Entity, &Terrain, Has<MapPosition>), Added<Terrain>>,
+    mut carved: Query<(Entity, &mut TerrainSurface, Option<&mut Sprite>), Changed<TerrainSurface>>,
+    mut removed: RemovedComponents<TerrainSurface>,
+    mut raylib: RaylibAccess,
+    mut texture_store: ResMut<TextureStore>,
+    mut alpha_masks: ResMut<AlphaMasks>,
+) {
+    // Free despawned terrains first: a terrain spawned this frame may reuse
+    // the key of one despawned this frame.
+    for entity in removed.read() {
+        let key = terrain_texture_key(entity);
+        texture_store.remove(&key);
+        alpha_masks.remove(&key);
+    }
+
+    for (entity, terrain, has_map_pos) in added.iter() {
+        let Some(source) = texture_store.get(&terrain.source) else {
+            warn!(
+                "terrain_system: source texture '{}' of terrain {:?} is not loaded",
+                terrain.source, entity
+            );
+            continue;
+        };
+        let mut image = match source.load_image() {
+            Ok(image) => image,
+            Err(err) => {
+                warn!(
+                    "terrain_system: failed to read texture '{}': {}",
+                    terrain.source, err
+                );
+                continue;
+            }
+        };
+        image.set_format(PixelFormat::PIXELFORMAT_UNCOMPRESSED_R8G8B8A8);
+        let texture = match raylib.rl.load_texture_from_image(&raylib.th, &image) {
+            Ok(texture) => texture,
+            Err(err) => {
+                warn!(
+                    "terrain_system: failed to create texture of terrain {:?}: {}",
+                    entity, err
+                );
+                continue;
+            }
+        };
+        let (width, height) = (image.width() as u32, image.height() as u32);
+        let key = terrain_texture_key(entity);
+        let filter = texture_store.filter(&terrain.source);
+        texture_store.insert(&key, texture, filter, None);
+        alpha_masks.insert_image(&key, &image);
+
+        let mut entity_commands = commands.entity(entity);
+        if !has_map_pos {
+            entity_commands.insert(MapPosition::new(0.0, 0.0));
+        }
+        entity_commands.insert((
+            TerrainSurface::new(width, height, image.get_image_data().to_vec()),
+            Sprite {
+                tex_key: key.into(),
+                width: width as f32,
+                height: height as f32,
+                offset: Vector2::zero(),
+                origin: Vector2::zero(),
+                flip_h: false,
+                flip_v: false,
+            },
+            BoxCollider::new(width as f32, height as f32),
+            PixelCollision,
+        ));
+    }
+
+    for (entity, mut surface, sprite) in carved.iter_mut() {
+        let Some(rect) = surface.bypass_change_detection().dirty.take() else {
+            continue;
+        };
+        let Some(texture) = texture_store.map.get_mut(&terrain_texture_key(entity)) else {
+            continue;
+        };
+        let dest = Rectangle::new(
+            rect.x0 as f32,
+            rect.y0 as f32,
+            (rect.x1 - rect.x0) as f32,
+            (rect.y1 - rect.y0) as f32,
+        );
+        if let Err(err) = texture.update_texture_rec(dest, &surface.rect_bytes(rect)) {
+            warn!(
+                "terrain_system: failed to update terrain {:?}: {}",
+                entity, err
+            );
+        }
+        if let Some(mut sprite) = sprite {
+            sprite.set_changed();
+        }
+    }
+}
+
+/// [`EntityCommand`] clearing a circle of a spawned terrain: every texel
+/// whose center lies within `radius` of `center` (world coordinates) becomes
+/// transparent and stops colliding at once. The texture is updated by the
+/// next [`terrain_system`] run.
+pub struct CarveTerrainCircle {
+    pub center: Vector2,
+    pub radius: f32,
+}
+
+impl EntityCommand for CarveTerrainCircle {
+    type Out = ();
+
+    fn apply(self, mut entity: EntityWorldMut) {
+        let id = entity.id();
+        let origin = entity
+            .get::<GlobalTransform2D>()
+            .map(|gt| gt.position)
+            .or_else(|| entity.get::<MapPosition>().map(|p| p.pos))
+            .unwrap_or(Vector2::zero());
+        let key = terrain_texture_key(id);
+        entity.resource_scope(|entity, mut masks: Mut<AlphaMasks>| {
+            let (Some(mut surface), Some(mask)) =
+                (entity.get_mut::<TerrainSurface>(), masks.get_mut(&key))
+            else {
+                warn!("Entity {id:?} is not a spawned terrain");
+                return;
+            };
+            surface.carve_circle(self.center - origin, self.radius, mask);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::alphamask::AlphaMask;
+    use raylib::prelude::Color;
+
+    fn spawn_terrain(world: &mut World, x: f32, y: f32) -> Entity {
+        let entity = world
+            .spawn((
+                MapPosition::new(x, y),
+                TerrainSurface::new(4, 4, vec![Color::WHITE; 16]),
+            ))
+            .id();
+        let mut masks = AlphaMasks::default();
+        masks.insert(
+            terrain_texture_key(entity),
+            AlphaMask::from_alpha(4, 4, std::iter::repeat_n(255, 16)),
+        );
+        world.insert_resource(masks);
+        entity
+    }
+
+    #[test]
+    fn carve_uses_world_coordinates() {
+        let mut world = World::new();
+        let terrain = spawn_terrain(&mut world, 100.0, 50.0);
+
+        CarveTerrainCircle {
+            center: Vector2::new(100.0, 50.0),
+            radius: 1.0,
+        }
+        .apply(world.entity_mut(terrain));
+
+        let key = terrain_texture_key(terrain);
+        let mask = world.resource::<AlphaMasks>().get(&key).unwrap();
+        assert!(!mask.is_solid(0, 0));
+        assert!(mask.is_solid(1, 1));
+        let surface = world.get::<TerrainSurface>(terrain).unwrap();
+        assert_eq!(surface.pixels[0], Color::BLANK);
+        assert!(surface.dirty.is_some());
+    }
+
+    #[test]
+    fn carving_a_plain_entity_is_a_no_op() {
+        let mut world = World::new();
+        world.insert_resource(AlphaMasks::default());
+        let plain = world.spawn(MapPosition::new(0.0, 0.0)).id();
+
+        CarveTerrainCircle {
+            center: Vector2::zero(),
+            radius: 4.0,
+        }
+        .apply(world.entity_mut(plain));
+
+        assert!(world.get::<TerrainSurface>(plain).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+//! Text scroller system.
+//!
+//! [`text_scroller_system`] advances every [`TextScroller`], grows its glyph
+//! pool to fit the track and moves the pooled [`DynamicText`] children to the
+//! glyphs on the track. Pool entities without a glyph this frame are
+//! [`Hidden`]. It runs before transform propagation so the glyphs are drawn
+//! where they were placed this frame.
+//!
+//! # Related
+//!
+//! - [`crate::components::textscroller`] – paths and track layout
+
+use bevy_ecs::hierarchy::ChildOf;
+use bevy_ecs::prelude::*;
+
+use crate::components::dynamictext::DynamicText;
+use crate::components::hidden::Hidden;
+use crate::components::mapposition::MapPosition;
+use crate::components::renderlayer::RenderLayer;
+use crate::components::textscroller::TextScroller;
+use crate::components::zindex::ZIndex;
+use crate::resources::worldtime::WorldTime;
+
+/// Scrolls the text of every [`TextScroller`] and lays out its glyphs.
+pub fn text_scroller_system(
+    mut commands: Commands,
+    mut scrollers: Query<(
+        Entity,
+        &mut TextScroller,
+        Option<&ZIndex>,
+        Option<&RenderLayer>,
+    )>,
+    mut glyphs: Query<(&mut MapPosition, &mut DynamicText, Has<Hidden>), Without<TextScroller>>,
+    time: Res<WorldTime>,
+) {
+    for (entity, mut scroller, z_index, layer) in scrollers.iter_mut() {
+        scroller.elapsed += time.delta;
+
+        let pool_size = scroller.pool_size();
+        while scroller.glyphs.len() < pool_size {
+            let mut glyph = commands.spawn((
+                DynamicText::new(
+                    "",
+                    scroller.font.as_str(),
+                    scroller.font_size,
+                    scroller.color,
+                ),
+                MapPosition::new(0.0, 0.0),
+                z_index.copied().unwrap_or(ZIndex(0.0)),
+                Hidden,
+                ChildOf(entity),
+            ));
+            if let Some(layer) = layer {
+                glyph.insert(*layer);
+            }
+            let id = glyph.id();
+            scroller.glyphs.push(id);
+        }
+
+        let mut pool = scroller.glyphs.iter();
+        for (c, distance) in scroller.visible_glyphs() {
+            let Some(&glyph) = pool.next() else {
+                break;
+            };
+            let Ok((mut position, mut text, hidden)) = glyphs.get_mut(glyph) else {
+                continue;
+            };
+            let point = scroller.path.point(distance, scroller.elapsed);
+            position.pos = point;
+            let mut buf = [0u8; 4];
+            let glyph_text = c.encode_utf8(&mut buf);
+            // Only touch the text when the glyph changes, so its size isn't
+            // measured again every frame.
+            if &*text.text != glyph_text {
+                text.set_text(glyph_text);
+            }
+            if hidden {
+                commands.entity(glyph).remove::<Hidden>();
+            }
+        }
+        for &glyph in pool {
+            if let Ok((_, _, false)) = glyphs.get(glyph) {
+                commands.entity(glyph).insert(Hidden);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::textscroller::ScrollerPath;
+
+    #[test]
+    fn pools_glyphs_and_hides_the_ones_off_track() {
+        let mut world = World::new();
+        world.insert_resource(WorldTime::default());
+        let path = ScrollerPath::Sine {
+            amplitude: 0.0,
+            wavelength: 100.0,
+        };
+        let scroller = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                TextScroller::new("ABCDEFGH", "font", 10.0, 10.0, path).with_width(30.0),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(text_scroller_system);
+        world.resource_mut::<WorldTime>().delta = 1.5;
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let pool = world.get::<TextScroller>(scroller).unwrap().glyphs.clone();
+        assert_eq!(pool.len(), 4);
+        let shown: Vec<_> = pool
+            .iter()
+            .filter(|&&glyph| world.get::<Hidden>(glyph).is_none())
+            .map(|&glyph| world.get::<DynamicText>(glyph).unwrap().text.to_string())
+            .collect();
+        // 3 seconds in: 30 units scrolled, glyphs at 0, 10, 20.
+        assert_eq!(shown, vec!["A", "B", "C"]);
+    }
+}
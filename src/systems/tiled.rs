@@ -0,0 +1,346 @@
+//! Tiled JSON map import.
+//!
+//! [`load_tiled_map`] converts a map saved by [Tiled](https://www.mapeditor.org)
+//! in its JSON format (`.tmj`) into the engine's [`Tilemap`], so
+//! `TileMap::new("maps/level1.tmj")` (Lua: `:with_tilemap("maps/level1.tmj")`)
+//! spawns it like a Tilesetter map:
+//!
+//! - every tile layer becomes a tilemap layer of the same name, hidden if it
+//!   is hidden in Tiled; group layers are flattened into their children;
+//! - every object of an object layer becomes a [`TileObject`] in a group
+//!   named after the layer. Objects whose class (Tiled 1.9+) or type is
+//!   `collision` get a `BoxCollider` covering their rectangle, made a ramp
+//!   by a string property `slope` naming the right-angle corner
+//!   (`bottom_left`, `bottom_right`, `top_left` or `top_right`);
+//! - custom object properties become the object entity's `Signals`: `int`
+//!   as integers, `float` as scalars, `bool` as flags (when true), and
+//!   `string`, `color` and `file` as strings.
+//!
+//! The engine draws a map from a single square-tile atlas, so the map must
+//! use exactly one tileset, embedded in the map, with one image and no
+//! margin or spacing. Tile layers must use the CSV layer format, and
+//! infinite maps are not supported. Flipped and rotated tiles are drawn
+//! unflipped. Object positions are kept in Tiled's pixels, which line up
+//! with the tiles of orthogonal maps.
+
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::components::gridlayout::GridValue;
+use crate::components::slope::SlopeCorner;
+use crate::components::tilemap::TileProjection;
+use crate::systems::tilemap::{TileLayer, TileObject, TilePosition, Tilemap};
+
+/// File extension of Tiled JSON maps.
+pub const TILED_EXTENSION: &str = ".tmj";
+
+/// Class or type marking an object as a collider.
+const COLLISION_CLASS: &str = "collision";
+
+/// Object property naming the [`SlopeCorner`] of a collider.
+const SLOPE_PROPERTY: &str = "slope";
+
+/// Flip and rotation bits Tiled stores in the top of each tile GID.
+const GID_FLAGS: u32 = 0xF000_0000;
+
+#[derive(Deserialize)]
+struct TiledMap {
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    orientation: String,
+    #[serde(default)]
+    infinite: bool,
+    layers: Vec<TiledLayer>,
+    tilesets: Vec<TiledTileset>,
+}
+
+#[derive(Deserialize)]
+struct TiledLayer {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default)]
+    data: Option<Value>,
+    #[serde(default)]
+    objects: Vec<TiledObject>,
+    #[serde(default)]
+    layers: Vec<TiledLayer>,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    #[serde(default)]
+    class: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    /// Tile objects are anchored at their bottom-left corner.
+    #[serde(default)]
+    gid: Option<u32>,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    #[serde(default, rename = "type")]
+    kind: String,
+    value: Value,
+}
+
+#[derive(Deserialize)]
+struct TiledTileset {
+    firstgid: u32,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+}
+
+/// Load the Tiled map at `path`; returns the tileset image path (relative
+/// to the working directory) and the map.
+pub fn load_tiled_map(path: &str) -> Result<(String, Tilemap), String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to load Tiled map '{path}': {err}"))?;
+    let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+    parse_tiled_map(&json, dir).map_err(|err| format!("Tiled map '{path}': {err}"))
+}
+
+/// Convert Tiled map JSON; tileset images are resolved against `dir`.
+pub fn parse_tiled_map(json: &str, dir: &Path) -> Result<(String, Tilemap), String> {
+    let map: TiledMap = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    if map.infinite {
+        return Err("infinite maps are not supported".into());
+    }
+    let projection = match map.orientation.as_str() {
+        "" | "orthogonal" => TileProjection::Orthogonal,
+        "isometric" => TileProjection::Isometric,
+        "hexagonal" => TileProjection::Hex,
+        other => return Err(format!("unsupported orientation '{other}'")),
+    };
+    let [tileset] = map.tilesets.as_slice() else {
+        return Err(format!(
+            "uses {} tilesets; the engine draws a map from exactly one",
+            map.tilesets.len()
+        ));
+    };
+    if let Some(source) = &tileset.source {
+        return Err(format!(
+            "external tileset '{source}' is not supported; embed it in the map"
+        ));
+    }
+    let Some(image) = &tileset.image else {
+        return Err("image collection tilesets are not supported".into());
+    };
+    if tileset.tilewidth == 0 || tileset.tilewidth != tileset.tileheight {
+        return Err(format!(
+            "tileset tiles must be square, got {}x{}",
+            tileset.tilewidth, tileset.tileheight
+        ));
+    }
+    if tileset.margin != 0 || tileset.spacing != 0 {
+        return Err("tileset margin and spacing must be 0".into());
+    }
+
+    let mut tilemap = Tilemap {
+        tile_size: tileset.tilewidth,
+        map_width: map.width,
+        map_height: map.height,
+        projection,
+        layers: Vec::new(),
+        spawn_points: Vec::new(),
+        objects: Vec::new(),
+    };
+    add_layers(&mut tilemap, &map.layers, map.width, tileset.firstgid)?;
+    let image = dir.join(image).to_string_lossy().into_owned();
+    Ok((image, tilemap))
+}
+
+/// Append the tile layers and objects of `layers`, recursing into groups.
+fn add_layers(
+    tilemap: &mut Tilemap,
+    layers: &[TiledLayer],
+    width: u32,
+    firstgid: u32,
+) -> Result<(), String> {
+    for layer in layers {
+        match layer.kind.as_str() {
+            "tilelayer" => {
+                let gids: Vec<u64> = match &layer.data {
+                    Some(Value::Array(gids)) => {
+                        gids.iter().map(|gid| gid.as_u64().unwrap_or(0)).collect()
+                    }
+                    Some(_) => {
+                        return Err(format!(
+                            "layer '{}' is compressed; set its layer format to CSV",
+                            layer.name
+                        ));
+                    }
+                    None => Vec::new(),
+                };
+                let columns = width.max(1);
+                let positions = gids
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, &gid)| {
+                        // GID 0 is an empty cell
+                        let id = (gid as u32 & !GID_FLAGS).checked_sub(firstgid)?;
+                        Some(TilePosition {
+                            x: index as u32 % columns,
+                            y: index as u32 / columns,
+                            id,
+                        })
+                    })
+                    .collect();
+                tilemap.layers.push(TileLayer {
+                    name: layer.name.clone(),
+                    positions,
+                    visible: layer.visible,
+                    solid: false,
+                });
+            }
+            "objectgroup" => {
+                for object in &layer.objects {
+                    let y = match object.gid {
+                        Some(_) => object.y - object.height,
+                        None => object.y,
+                    };
+                    tilemap.objects.push(TileObject {
+                        group: layer.name.clone(),
+                        x: object.x,
+                        y,
+                        width: object.width,
+                        height: object.height,
+                        collision: object.class == COLLISION_CLASS
+                            || object.kind == COLLISION_CLASS,
+                        slope: object
+                            .properties
+                            .iter()
+                            .find(|property| property.name == SLOPE_PROPERTY)
+                            .and_then(|property| property.value.as_str())
+                            .and_then(SlopeCorner::parse),
+                        properties: object_properties(&object.properties),
+                    });
+                }
+            }
+            "group" => add_layers(tilemap, &layer.layers, width, firstgid)?,
+            // Image layers are not drawn
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Custom properties as grid values; `object` and class properties are skipped.
+fn object_properties(properties: &[TiledProperty]) -> FxHashMap<String, GridValue> {
+    properties
+        .iter()
+        .filter_map(|property| {
+            let value = match (property.kind.as_str(), &property.value) {
+                ("int", value) => GridValue::Int(value.as_i64()?),
+                ("float", value) => GridValue::Float(value.as_f64()?),
+                ("bool", value) => GridValue::Bool(value.as_bool()?),
+                ("" | "string" | "color" | "file", Value::String(value)) => {
+                    GridValue::String(value.clone())
+                }
+                _ => return None,
+            };
+            Some((property.name.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = r#"{
+        "width": 3, "height": 2, "tilewidth": 16, "tileheight": 16,
+        "orientation": "orthogonal", "infinite": false,
+        "tilesets": [{"firstgid": 1, "image": "tiles.png", "tilewidth": 16, "tileheight": 16}],
+        "layers": [
+            {"name": "ground", "type": "tilelayer", "data": [1, 0, 3, 0, 2147483650, 0]},
+            {"name": "extras", "type": "group", "layers": [
+                {"name": "secret", "type": "tilelayer", "visible": false, "data": [0, 0, 0, 0, 0, 4]}
+            ]},
+            {"name": "walls", "type": "objectgroup", "objects": [
+                {"class": "collision", "x": 0, "y": 16, "width": 48, "height": 16,
+                 "properties": [
+                    {"name": "hp", "type": "int", "value": 3},
+                    {"name": "breakable", "type": "bool", "value": true},
+                    {"name": "target", "type": "object", "value": 7}
+                 ]},
+                {"class": "collision", "x": 48, "y": 16, "width": 16, "height": 16,
+                 "properties": [{"name": "slope", "type": "string", "value": "bottom_right"}]},
+                {"type": "door", "gid": 2, "x": 32, "y": 16, "width": 16, "height": 16}
+            ]}
+        ]
+    }"#;
+
+    #[test]
+    fn tiled_maps_convert_layers_and_objects() {
+        let (image, map) = parse_tiled_map(MAP, Path::new("maps")).unwrap();
+        assert_eq!(Path::new(&image), Path::new("maps").join("tiles.png"));
+        assert_eq!((map.tile_size, map.map_width, map.map_height), (16, 3, 2));
+
+        let cells = |layer: &TileLayer| -> Vec<(u32, u32, u32)> {
+            layer.positions.iter().map(|p| (p.x, p.y, p.id)).collect()
+        };
+        assert_eq!(map.layers.len(), 2);
+        // GIDs are 1-based; the flipped tile keeps its id
+        assert_eq!(cells(&map.layers[0]), vec![(0, 0, 0), (2, 0, 2), (1, 1, 1)]);
+        assert_eq!(map.layers[1].name, "secret");
+        assert!(!map.layers[1].visible);
+        assert_eq!(cells(&map.layers[1]), vec![(2, 1, 3)]);
+
+        let [wall, ramp, door] = map.objects.as_slice() else {
+            panic!("expected three objects");
+        };
+        assert_eq!(wall.group, "walls");
+        assert!(wall.collision);
+        assert_eq!(wall.properties.get("hp"), Some(&GridValue::Int(3)));
+        assert_eq!(
+            wall.properties.get("breakable"),
+            Some(&GridValue::Bool(true))
+        );
+        assert!(!wall.properties.contains_key("target"));
+        assert_eq!(wall.slope, None);
+        assert_eq!(ramp.slope, Some(SlopeCorner::BottomRight));
+        assert!(!door.collision);
+        assert_eq!((door.x, door.y), (32.0, 0.0));
+    }
+
+    #[test]
+    fn unsupported_tiled_maps_are_rejected() {
+        let external = MAP.replace(r#""image": "tiles.png""#, r#""source": "tiles.tsx""#);
+        assert!(parse_tiled_map(&external, Path::new("")).is_err());
+        let base64 = MAP.replace("[1, 0, 3, 0, 2147483650, 0]", r#""AQAAAA==""#);
+        assert!(parse_tiled_map(&base64, Path::new("")).is_err());
+    }
+}
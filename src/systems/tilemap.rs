@@ -2,24 +2,54 @@
 //!
 //! These functions are always compiled (no feature gates) so Rust-only downstream
 //! crates can use them without enabling the `lua` feature.
-
-use std::sync::Arc;
+//!
+//! A spawned tilemap keeps its layout in a [`TileMapLayers`] component on the
+//! root. [`SetTileMapLayerVisible`] and [`SetTileMapTile`] edit that layout at
+//! runtime (secret-area reveals, destructible terrain) and spawn or despawn the
+//! affected tile entities, so rendering and any system that queries the
+//! `"tiles"` group see the change from the next frame on. Tile edits also
+//! apply the tileset's auto-tiling rules (see [`crate::resources::autotile`]).
+//!
+//! Tiles of solid layers (see [`TileMap::solid_layers`]) get colliders: one
+//! [`BoxCollider`] entity per horizontal run of tiles, in a group named after
+//! the layer. Edits rebuild the colliders of the edited row; hiding a layer
+//! removes its colliders and showing it builds them again. Only orthogonal
+//! maps get tile colliders.
+//!
+//! Maps come from a Tilesetter 2.1.0 directory or a Tiled JSON (`.tmj`)
+//! file (see [`crate::systems::tiled`]). Object layers become entities in
+//! their layer's group, with a collider for collision objects.
+//!
+//! Cells are laid out by the map's [`TileProjection`]: orthogonal, isometric
+//! or hex. Isometric and hex tiles get a per-row [`ZIndex`] offset within their
+//! layer so cells lower on screen are drawn over the ones behind them.
 
 use bevy_ecs::hierarchy::ChildOf;
 use bevy_ecs::prelude::*;
+use bevy_ecs::system::EntityCommand;
 use log::warn;
 use raylib::prelude::{Texture2D, Vector2};
+use rustc_hash::FxHashMap;
 use serde::Deserialize;
 
+use crate::components::boxcollider::BoxCollider;
+use crate::components::gridlayout::{GridValue, properties_to_signals};
 use crate::components::group::Group;
 use crate::components::mapposition::MapPosition;
+use crate::components::portal::SpawnPointCell;
+use crate::components::slope::{Slope, SlopeCorner};
 use crate::components::sprite::Sprite;
-use crate::components::tilemap::TileMap;
+use crate::components::tilemap::{
+    PlacedTile, TileGrid, TileMap, TileMapLayer, TileMapLayers, TileProjection,
+};
 use crate::components::zindex::ZIndex;
+use crate::key::Key;
+use crate::resources::autotile::{AutoTileRules, AutoTileStore};
 use crate::resources::texturefilter::TextureFilter;
 use crate::resources::texturestore::TextureStore;
 use crate::systems::RaylibAccess;
 use crate::systems::propagate_transforms::ComputeInitialGlobalTransform;
+use crate::systems::tiled::{TILED_EXTENSION, load_tiled_map};
 
 pub const TILES_GROUP: &str = "tiles";
 pub const TILES_TEMPLATES_GROUP: &str = "tiles-templates";
@@ -37,15 +67,61 @@ pub struct TilePosition {
 pub struct TileLayer {
     pub name: String,
     pub positions: Vec<TilePosition>,
+    /// Hidden layers keep their cells but spawn no tiles until shown.
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    /// Tiles of solid layers get colliders (see [`TileMap::solid_layers`]).
+    #[serde(default)]
+    pub solid: bool,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+/// A rectangle from an object layer, in the map's local pixels:
+/// `{"group": "walls", "x": 0, "y": 32, "width": 64, "height": 16,
+/// "collision": true, "properties": {...}}`.
+///
+/// Spawned as an entity in `group` at the rectangle's top-left, with a
+/// [`BoxCollider`] covering it when `collision` is set and [`Signals`]
+/// holding its `properties`. A collision object with a `slope` corner
+/// (`"bottom_left"`, `"bottom_right"`, `"top_left"` or `"top_right"`) also
+/// gets a [`Slope`], making it a ramp.
+///
+/// [`Signals`]: crate::components::signals::Signals
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TileObject {
+    pub group: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub collision: bool,
+    pub slope: Option<SlopeCorner>,
+    pub properties: FxHashMap<String, GridValue>,
 }
 
-/// Tilemap metadata and layer data, as parsed from Tilesetter 2.1.0 JSON.
+/// Tilemap metadata and layer data, as parsed from Tilesetter 2.1.0 JSON or
+/// imported from a Tiled map (see [`crate::systems::tiled`]).
+///
+/// `projection` is not part of the Tilesetter format; add it to the file (or
+/// set [`TileMap::projection`]) for isometric or hex maps. Neither are
+/// `spawn_points`, a list of named cells (see [`SpawnPointCell`]), and
+/// `objects` (see [`TileObject`]).
 #[derive(Debug, Deserialize)]
 pub struct Tilemap {
     pub tile_size: u32,
     pub map_width: u32,
     pub map_height: u32,
+    #[serde(default)]
+    pub projection: TileProjection,
     pub layers: Vec<TileLayer>,
+    #[serde(default)]
+    pub spawn_points: Vec<SpawnPointCell>,
+    #[serde(default)]
+    pub objects: Vec<TileObject>,
 }
 
 /// Returns the last `/`-separated segment of `path` (the directory stem).
@@ -53,37 +129,81 @@ fn path_stem(path: &str) -> &str {
     path.split('/').next_back().unwrap_or(path)
 }
 
-/// Load a tilemap from a directory produced by Tilesetter 2.1.0.
+/// Tileset texture key of the tilemap at `path`: the directory name of a
+/// Tilesetter map, or the file name of a Tiled map without `.tmj`.
+pub fn tilemap_key(path: &str) -> &str {
+    let stem = path_stem(path);
+    stem.strip_suffix(TILED_EXTENSION).unwrap_or(stem)
+}
+
+/// Load a tilemap from a directory produced by Tilesetter 2.1.0, or from a
+/// Tiled JSON map if `path` ends in `.tmj`.
 ///
-/// `path` is a directory; the last path segment is used as the stem for
-/// `<stem>.png` (texture) and `<stem>.txt` (JSON data).
+/// For Tilesetter, `path` is a directory; the last path segment is used as
+/// the stem for `<stem>.png` (texture) and `<stem>.txt` (JSON data). A Tiled
+/// map brings its tileset image path (see [`load_tiled_map`]).
 pub fn load_tilemap(
     rl: &mut raylib::RaylibHandle,
     thread: &raylib::RaylibThread,
     path: &str,
 ) -> Result<(Texture2D, Tilemap), String> {
-    let dirname = path_stem(path);
-    let json_path = format!("{}/{}.txt", path, dirname);
-    let png_path = format!("{}/{}.png", path, dirname);
+    let (png_path, tilemap) = if path.ends_with(TILED_EXTENSION) {
+        load_tiled_map(path)?
+    } else {
+        let dirname = path_stem(path);
+        let json_path = format!("{}/{}.txt", path, dirname);
+        let json_string = std::fs::read_to_string(&json_path)
+            .map_err(|err| format!("Failed to load tilemap JSON '{}': {err}", json_path))?;
+        let tilemap: Tilemap = serde_json::from_str(&json_string)
+            .map_err(|err| format!("Failed to parse tilemap JSON '{}': {err}", json_path))?;
+        (format!("{}/{}.png", path, dirname), tilemap)
+    };
     let texture = rl
         .load_texture(thread, &png_path)
         .map_err(|err| format!("Failed to load tilemap texture '{}': {err}", png_path))?;
-    let json_string = std::fs::read_to_string(&json_path)
-        .map_err(|err| format!("Failed to load tilemap JSON '{}': {err}", json_path))?;
-    let tilemap: Tilemap = serde_json::from_str(&json_string)
-        .map_err(|err| format!("Failed to parse tilemap JSON '{}': {err}", json_path))?;
     Ok((texture, tilemap))
 }
 
+/// Load the auto-tiling rules of the tilemap at `path` from
+/// `<stem>.autotile.json` (inside a Tilesetter directory, next to a Tiled
+/// map), or `Ok(None)` if the file does not exist.
+pub fn load_autotile_rules(path: &str) -> Result<Option<AutoTileRules>, String> {
+    let json_path = match path.strip_suffix(TILED_EXTENSION) {
+        Some(base) => format!("{base}.autotile.json"),
+        None => format!("{}/{}.autotile.json", path, path_stem(path)),
+    };
+    let json_string = match std::fs::read_to_string(&json_path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(format!(
+                "Failed to load autotile rules '{json_path}': {err}"
+            ));
+        }
+    };
+    AutoTileRules::from_json_str(&json_string)
+        .map(Some)
+        .map_err(|err| format!("Failed to parse autotile rules '{json_path}': {err}"))
+}
+
 /// Spawn tile entities from a loaded tilemap.
 ///
 /// Phase 1 — create one template entity per atlas cell (`Group("tiles-templates")` + `Sprite`).
 /// Templates are kept alive in the world (no `MapPosition`, so they are not rendered).
 ///
 /// Phase 2 — clone the matching template for each tile placement and insert
-/// `Group("tiles")`, `MapPosition`, and `ZIndex`. When `parent` is `Some`,
+/// `Group("tiles")`, `MapPosition` (from the tilemap's [`TileGrid`]), and `ZIndex`. When `parent` is `Some`,
 /// each tile clone also gets `ChildOf(parent)` and `ComputeInitialGlobalTransform`
 /// is queued so children render at the correct world position on the first frame.
+///
+/// Layers marked `solid` also get their tile colliders (see the module docs).
+///
+/// Each of the tilemap's `spawn_points` becomes a
+/// [`SpawnPoint`](crate::components::portal::SpawnPoint) entity at its cell
+/// centre, parented the same way.
+///
+/// Returns the resulting [`TileMapLayers`]; insert it on `parent` to make the
+/// tilemap editable at runtime.
 pub fn spawn_tiles(
     commands: &mut Commands,
     tilemap_tex_key: impl Into<String>,
@@ -91,8 +211,8 @@ pub fn spawn_tiles(
     tex_height: i32,
     tilemap: &Tilemap,
     parent: Option<Entity>,
-) {
-    let tilemap_tex_key: Arc<str> = Arc::from(tilemap_tex_key.into());
+) -> TileMapLayers {
+    let tilemap_tex_key = Key::from(tilemap_tex_key.into());
     let tile_size = tilemap.tile_size as f32;
     let tiles_per_row = ((tex_width as f32 / tile_size).floor() as u32).max(1);
     let tiles_per_col = ((tex_height as f32 / tile_size).floor() as u32).max(1);
@@ -107,7 +227,7 @@ pub fn spawn_tiles(
                 .spawn((
                     Group::new(TILES_TEMPLATES_GROUP),
                     Sprite {
-                        tex_key: tilemap_tex_key.clone(),
+                        tex_key: tilemap_tex_key,
                         width: tile_size,
                         height: tile_size,
                         offset: Vector2 {
@@ -125,41 +245,190 @@ pub fn spawn_tiles(
 
     // Phase 2: clone the matching template for each tile placement.
     let layer_count = tilemap.layers.len() as f32;
+    let mut layers = TileMapLayers {
+        tileset: tilemap_tex_key.to_string(),
+        grid: TileGrid {
+            projection: tilemap.projection,
+            tile_size,
+            map_width: tilemap.map_width,
+            map_height: tilemap.map_height,
+        },
+        templates,
+        layers: Vec::with_capacity(tilemap.layers.len()),
+    };
     for (layer_index, layer) in tilemap.layers.iter().enumerate() {
         let z = -(layer_count - layer_index as f32);
+        let mut tiles = FxHashMap::default();
         for pos in &layer.positions {
-            let id = pos.id as usize;
-            if id >= templates.len() {
-                warn!(
-                    "Tile id {} out of range (atlas has {} tiles), skipping",
-                    id,
-                    templates.len()
-                );
+            let Some(template) = template_for(&layers, pos.id) else {
                 continue;
+            };
+            let entity = layer
+                .visible
+                .then(|| spawn_tile(commands, template, &layers.grid, pos.x, pos.y, z, parent));
+            tiles.insert((pos.x, pos.y), PlacedTile { id: pos.id, entity });
+        }
+        let solid = layer.solid && tilemap.projection == TileProjection::Orthogonal;
+        if layer.solid && !solid {
+            warn!(
+                "Solid layer '{}' ignored: only orthogonal tilemaps get tile colliders",
+                layer.name
+            );
+        }
+        let mut layer = TileMapLayer {
+            name: layer.name.clone(),
+            visible: layer.visible,
+            z,
+            tiles,
+            solid,
+            colliders: FxHashMap::default(),
+        };
+        rebuild_layer_colliders(&mut layer, &layers.grid, commands, parent);
+        layers.layers.push(layer);
+    }
+    for point in &tilemap.spawn_points {
+        let center = layers.grid.cell_center(point.x, point.y);
+        let entity = commands
+            .spawn((point.to_spawn_point(), MapPosition::new(center.x, center.y)))
+            .id();
+        attach_to_root(commands, entity, parent);
+    }
+    for object in &tilemap.objects {
+        let mut entity = commands.spawn((
+            Group::new(object.group.as_str()),
+            MapPosition::new(object.x, object.y),
+        ));
+        if object.collision {
+            entity.insert(BoxCollider::new(object.width, object.height));
+            if let Some(corner) = object.slope {
+                entity.insert(Slope::new(corner));
             }
-            let wx = pos.x as f32 * tile_size;
-            let wy = pos.y as f32 * tile_size;
-            let clone_id = commands
-                .entity(templates[id])
-                .clone_and_spawn()
-                .insert(Group::new(TILES_GROUP))
-                .insert(MapPosition::new(wx, wy))
-                .insert(ZIndex(z))
-                .id();
-            if let Some(p) = parent {
-                commands
-                    .entity(clone_id)
-                    .insert(ChildOf(p))
-                    .queue(ComputeInitialGlobalTransform);
-            }
         }
+        if !object.properties.is_empty() {
+            entity.insert(properties_to_signals(&object.properties));
+        }
+        let entity = entity.id();
+        attach_to_root(commands, entity, parent);
+    }
+    layers
+}
+
+/// Make `entity` a child of the tilemap root, if there is one.
+fn attach_to_root(commands: &mut Commands, entity: Entity, parent: Option<Entity>) {
+    if let Some(p) = parent {
+        commands
+            .entity(entity)
+            .insert(ChildOf(p))
+            .queue(ComputeInitialGlobalTransform);
     }
 }
 
+/// Despawn the colliders of row `y` of `layer` and, if the layer is solid and
+/// visible, spawn one [`BoxCollider`] per run of adjacent tiles in that row.
+fn rebuild_row_colliders(
+    layer: &mut TileMapLayer,
+    grid: &TileGrid,
+    commands: &mut Commands,
+    parent: Option<Entity>,
+    y: u32,
+) {
+    for old in layer.colliders.remove(&y).unwrap_or_default() {
+        commands.entity(old).try_despawn();
+    }
+    if !layer.solid || !layer.visible {
+        return;
+    }
+    let mut row = Vec::new();
+    let mut x = 0;
+    while x < grid.map_width {
+        if !layer.tiles.contains_key(&(x, y)) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < grid.map_width && layer.tiles.contains_key(&(x, y)) {
+            x += 1;
+        }
+        let origin = grid.cell_origin(start, y);
+        let entity = commands
+            .spawn((
+                Group::new(layer.name.as_str()),
+                MapPosition::new(origin.x, origin.y),
+                BoxCollider::new((x - start) as f32 * grid.tile_size, grid.tile_size),
+            ))
+            .id();
+        attach_to_root(commands, entity, parent);
+        row.push(entity);
+    }
+    if !row.is_empty() {
+        layer.colliders.insert(y, row);
+    }
+}
+
+/// Rebuild the colliders of every row of `layer`.
+fn rebuild_layer_colliders(
+    layer: &mut TileMapLayer,
+    grid: &TileGrid,
+    commands: &mut Commands,
+    parent: Option<Entity>,
+) {
+    for old in layer.colliders.drain().flat_map(|(_, row)| row) {
+        commands.entity(old).try_despawn();
+    }
+    for y in 0..grid.map_height {
+        rebuild_row_colliders(layer, grid, commands, parent, y);
+    }
+}
+
+/// Template entity for tile `id`, or `None` (with a warning) if the atlas has
+/// no such tile.
+fn template_for(layers: &TileMapLayers, id: u32) -> Option<Entity> {
+    let template = layers.templates.get(id as usize).copied();
+    if template.is_none() {
+        warn!(
+            "Tile id {} out of range (atlas has {} tiles), skipping",
+            id,
+            layers.templates.len()
+        );
+    }
+    template
+}
+
+/// Clone `template` into a tile entity at cell `(x, y)` of a layer drawn at
+/// `layer_z`.
+fn spawn_tile(
+    commands: &mut Commands,
+    template: Entity,
+    grid: &TileGrid,
+    x: u32,
+    y: u32,
+    layer_z: f32,
+    parent: Option<Entity>,
+) -> Entity {
+    let origin = grid.cell_origin(x, y);
+    let clone_id = commands
+        .entity(template)
+        .clone_and_spawn()
+        .insert(Group::new(TILES_GROUP))
+        .insert(MapPosition::new(origin.x, origin.y))
+        .insert(ZIndex(layer_z + grid.depth(x, y)))
+        .id();
+    if let Some(p) = parent {
+        commands
+            .entity(clone_id)
+            .insert(ChildOf(p))
+            .queue(ComputeInitialGlobalTransform);
+    }
+    clone_id
+}
+
 /// Watches for newly added [`TileMap`] components, loads the tilemap from disk,
 /// stores the texture in [`TextureStore`], and spawns tile entities as `ChildOf`
 /// children of the root entity.
 ///
+/// A `<stem>.autotile.json` file next to the tilemap, if present, is loaded
+/// into the [`AutoTileStore`] (see [`load_autotile_rules`]).
+///
 /// If the root entity has no [`MapPosition`], a default `(0, 0)` one is inserted
 /// so that [`crate::systems::propagate_transforms`] can compute child transforms.
 pub fn tilemap_spawn_system(
@@ -167,12 +436,13 @@ pub fn tilemap_spawn_system(
     query: Query<(Entity, &TileMap, Has<MapPosition>), Added<TileMap>>,
     mut raylib: RaylibAccess,
     mut texture_store: ResMut<TextureStore>,
+    mut autotiles: ResMut<AutoTileStore>,
 ) {
     for (entity, tilemap_comp, has_map_pos) in query.iter() {
         let path = &tilemap_comp.path;
-        let key: String = path_stem(path).to_owned();
+        let key: String = tilemap_key(path).to_owned();
 
-        let (texture, tilemap_data) = match load_tilemap(&mut raylib.rl, &raylib.th, path) {
+        let (texture, mut tilemap_data) = match load_tilemap(&mut raylib.rl, &raylib.th, path) {
             Ok(loaded) => loaded,
             Err(err) => {
                 warn!(
@@ -182,17 +452,28 @@ pub fn tilemap_spawn_system(
                 continue;
             }
         };
+        if let Some(projection) = tilemap_comp.projection {
+            tilemap_data.projection = projection;
+        }
+        for layer in tilemap_data.layers.iter_mut() {
+            layer.solid |= tilemap_comp.solid_layers.contains(&layer.name);
+        }
         let tex_w = texture.width;
         let tex_h = texture.height;
         if texture_store.get(&key).is_none() {
             texture_store.insert(&key, texture, TextureFilter::Nearest, None);
         }
+        match load_autotile_rules(path) {
+            Ok(Some(rules)) => autotiles.insert(key.as_str(), rules),
+            Ok(None) => {}
+            Err(err) => warn!("tilemap_spawn_system: {err}"),
+        }
 
         if !has_map_pos {
             commands.entity(entity).insert(MapPosition::new(0.0, 0.0));
         }
 
-        spawn_tiles(
+        let layers = spawn_tiles(
             &mut commands,
             &key,
             tex_w,
@@ -200,5 +481,544 @@ pub fn tilemap_spawn_system(
             &tilemap_data,
             Some(entity),
         );
+        commands.entity(entity).insert(layers);
+    }
+}
+
+/// Run `edit` on the [`TileMapLayers`] of the tilemap root `entity` with a
+/// [`Commands`] for spawning and despawning tiles, then apply those commands.
+fn edit_tilemap(
+    mut entity: EntityWorldMut,
+    edit: impl FnOnce(&mut TileMapLayers, &mut Commands, Entity),
+) {
+    let root = entity.id();
+    let Some(mut layers) = entity
+        .get_mut::<TileMapLayers>()
+        .map(|mut l| std::mem::take(&mut *l))
+    else {
+        warn!("Entity {root:?} is not a spawned tilemap");
+        return;
+    };
+    entity.world_scope(|world| {
+        edit(&mut layers, &mut world.commands(), root);
+        world.flush();
+    });
+    if let Some(mut slot) = entity.get_mut::<TileMapLayers>() {
+        *slot = layers;
+    }
+}
+
+/// Index of layer `name`, warning if the tilemap has no such layer.
+fn find_layer(layers: &TileMapLayers, name: &str, root: Entity) -> Option<usize> {
+    let index = layers.layer_index(name);
+    if index.is_none() {
+        warn!("Tilemap {root:?} has no layer named '{name}'");
+    }
+    index
+}
+
+/// [`EntityCommand`] that shows or hides one layer of a tilemap root.
+///
+/// Hiding despawns the layer's tile and collider entities; showing spawns them
+/// again from the layer's current cells, including edits made while it was
+/// hidden.
+pub struct SetTileMapLayerVisible {
+    pub layer: String,
+    pub visible: bool,
+}
+
+impl EntityCommand for SetTileMapLayerVisible {
+    type Out = ();
+
+    fn apply(self, entity: EntityWorldMut) {
+        edit_tilemap(entity, |layers, commands, root| {
+            let Some(index) = find_layer(layers, &self.layer, root) else {
+                return;
+            };
+            let TileMapLayers {
+                grid,
+                templates,
+                layers,
+                ..
+            } = layers;
+            let layer = &mut layers[index];
+            if layer.visible == self.visible {
+                return;
+            }
+            layer.visible = self.visible;
+            let z = layer.z;
+            for (&(x, y), tile) in layer.tiles.iter_mut() {
+                if let Some(old) = tile.entity.take() {
+                    commands.entity(old).try_despawn();
+                }
+                if self.visible
+                    && let Some(&template) = templates.get(tile.id as usize)
+                {
+                    tile.entity = Some(spawn_tile(commands, template, grid, x, y, z, Some(root)));
+                }
+            }
+            rebuild_layer_colliders(layer, grid, commands, Some(root));
+        });
+    }
+}
+
+/// [`EntityCommand`] that places tile `tile_id` in cell `(x, y)` of a tilemap
+/// layer, or clears the cell when `tile_id` is `None`.
+///
+/// The old tile entity in that cell is despawned and, on a solid layer, the
+/// colliders of the cell's row are rebuilt. On a hidden layer only the layout
+/// changes; the tile appears when the layer is shown again.
+///
+/// If the [`AutoTileStore`] has rules for the map's tileset and this layer,
+/// terrain tiles in the edited cell and its eight neighbours are then swapped
+/// for the variant matching their new neighbourhood.
+pub struct SetTileMapTile {
+    pub layer: String,
+    pub x: u32,
+    pub y: u32,
+    pub tile_id: Option<u32>,
+}
+
+impl EntityCommand for SetTileMapTile {
+    type Out = ();
+
+    fn apply(self, entity: EntityWorldMut) {
+        let rules = entity.get::<TileMapLayers>().and_then(|layers| {
+            entity
+                .world()
+                .get_resource::<AutoTileStore>()?
+                .rules_for(&layers.tileset, &self.layer)
+        });
+        edit_tilemap(entity, |layers, commands, root| {
+            let (x, y) = (self.x, self.y);
+            if !layers.grid.contains(x as i64, y as i64) {
+                warn!(
+                    "Tile cell ({x}, {y}) is outside tilemap {root:?} ({}x{} tiles)",
+                    layers.grid.map_width, layers.grid.map_height
+                );
+                return;
+            }
+            if let Some(id) = self.tile_id
+                && template_for(layers, id).is_none()
+            {
+                return;
+            }
+            let Some(index) = find_layer(layers, &self.layer, root) else {
+                return;
+            };
+            replace_tile(layers, index, commands, root, (x, y), self.tile_id);
+            if let Some(rules) = rules {
+                autotile_around(layers, index, commands, root, &rules, (x, y));
+            }
+            let TileMapLayers { grid, layers, .. } = layers;
+            rebuild_row_colliders(&mut layers[index], grid, commands, Some(root), y);
+        });
+    }
+}
+
+/// Put tile `tile_id` (already checked against the atlas) in `cell` of layer
+/// `index`, despawning the old tile entity and spawning the new one if the
+/// layer is visible.
+fn replace_tile(
+    layers: &mut TileMapLayers,
+    index: usize,
+    commands: &mut Commands,
+    root: Entity,
+    cell: (u32, u32),
+    tile_id: Option<u32>,
+) {
+    let TileMapLayers {
+        grid,
+        templates,
+        layers,
+        ..
+    } = layers;
+    let layer = &mut layers[index];
+    if let Some(old) = layer.tiles.remove(&cell).and_then(|tile| tile.entity) {
+        commands.entity(old).try_despawn();
+    }
+    let Some(id) = tile_id else {
+        return;
+    };
+    let (x, y) = cell;
+    let z = layer.z;
+    let entity = layer
+        .visible
+        .then(|| spawn_tile(commands, templates[id as usize], grid, x, y, z, Some(root)));
+    layer.tiles.insert(cell, PlacedTile { id, entity });
+}
+
+/// Re-resolve the terrain tiles in the 3x3 block around `cell` against
+/// `rules`, so the edit leaves clean edges.
+fn autotile_around(
+    layers: &mut TileMapLayers,
+    index: usize,
+    commands: &mut Commands,
+    root: Entity,
+    rules: &AutoTileRules,
+    cell: (u32, u32),
+) {
+    let (cx, cy) = (cell.0 as i64, cell.1 as i64);
+    let (width, height) = (layers.grid.map_width, layers.grid.map_height);
+    // Collect the swaps first: masks read the layer while it is borrowed.
+    let mut changes = Vec::new();
+    for y in (cy - 1).max(0)..=(cy + 1).min(height as i64 - 1) {
+        for x in (cx - 1).max(0)..=(cx + 1).min(width as i64 - 1) {
+            let (x, y) = (x as u32, y as u32);
+            let tiles = &layers.layers[index].tiles;
+            let Some(current) = tiles.get(&(x, y)).map(|tile| tile.id) else {
+                continue;
+            };
+            if !rules.is_member(current) {
+                continue;
+            }
+            let mask = rules.mask(width, height, x, y, |nx, ny| {
+                tiles.get(&(nx, ny)).map(|tile| tile.id)
+            });
+            if let Some(id) = rules.tile_for(mask)
+                && id != current
+                && template_for(layers, id).is_some()
+            {
+                changes.push(((x, y), id));
+            }
+        }
+    }
+    for (cell, id) in changes {
+        replace_tile(layers, index, commands, root, cell, Some(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::portal::SpawnPoint;
+    use crate::components::signals::Signals;
+    use crate::resources::autotile::AutoTileMode;
+    use bevy_ecs::system::SystemState;
+
+    /// Spawn a 4x4 map with a 6-tile atlas, one solid "walls" layer holding
+    /// tile 1 at (0, 0) and one collision object; returns the root entity.
+    fn spawn_map(world: &mut World) -> Entity {
+        let root = world.spawn(MapPosition::new(0.0, 0.0)).id();
+        let tilemap = Tilemap {
+            tile_size: 16,
+            map_width: 4,
+            map_height: 4,
+            projection: TileProjection::Orthogonal,
+            layers: vec![TileLayer {
+                name: "walls".to_string(),
+                positions: vec![TilePosition { x: 0, y: 0, id: 1 }],
+                visible: true,
+                solid: true,
+            }],
+            spawn_points: vec![SpawnPointCell {
+                name: "door".to_string(),
+                x: 2,
+                y: 1,
+                properties: [("facing".to_string(), GridValue::String("up".into()))]
+                    .into_iter()
+                    .collect(),
+            }],
+            objects: vec![TileObject {
+                group: "spikes".to_string(),
+                x: 16.0,
+                y: 48.0,
+                width: 32.0,
+                height: 8.0,
+                collision: true,
+                slope: None,
+                properties: [("damage".to_string(), GridValue::Int(2))]
+                    .into_iter()
+                    .collect(),
+            }],
+        };
+        let mut state = SystemState::<Commands>::new(world);
+        let mut commands = state.get_mut(world).expect("Commands should fetch");
+        let layers = spawn_tiles(&mut commands, "tiles", 96, 16, &tilemap, Some(root));
+        commands.entity(root).insert(layers);
+        state.apply(world);
+        root
+    }
+
+    fn cell(world: &World, root: Entity, x: u32, y: u32) -> Option<PlacedTile> {
+        world.get::<TileMapLayers>(root).unwrap().layers[0]
+            .tiles
+            .get(&(x, y))
+            .copied()
+    }
+
+    #[test]
+    fn spawn_points_are_children_at_cell_centres() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let mut query = world.query::<(&SpawnPoint, &MapPosition, &ChildOf)>();
+        let (point, pos, parent) = query.single(&world).unwrap();
+        assert_eq!(point.name, "door");
+        assert_eq!(
+            point.properties.get("facing"),
+            Some(&GridValue::String("up".into()))
+        );
+        assert_eq!(pos.pos, Vector2::new(40.0, 24.0));
+        assert_eq!(parent.parent(), root);
+    }
+
+    #[test]
+    fn objects_are_children_with_colliders_and_signals() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let mut query = world.query::<(&Group, &MapPosition, &BoxCollider, &Signals, &ChildOf)>();
+        let (group, pos, collider, signals, parent) = query.single(&world).unwrap();
+        assert_eq!(group.name(), "spikes");
+        assert_eq!(pos.pos, Vector2::new(16.0, 48.0));
+        assert_eq!(collider.size, Vector2::new(32.0, 8.0));
+        assert_eq!(signals.get_integer("damage"), Some(2));
+        assert_eq!(parent.parent(), root);
+    }
+
+    #[test]
+    fn set_tile_replaces_and_clears_tile_entities() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let old = cell(&world, root, 0, 0).unwrap().entity.unwrap();
+
+        SetTileMapTile {
+            layer: "walls".to_string(),
+            x: 0,
+            y: 0,
+            tile_id: Some(0),
+        }
+        .apply(world.entity_mut(root));
+        let placed = cell(&world, root, 0, 0).unwrap();
+        assert_eq!(placed.id, 0);
+        assert!(world.get_entity(old).is_err());
+        let new = placed.entity.unwrap();
+        assert_eq!(world.get::<ChildOf>(new).map(|c| c.parent()), Some(root));
+
+        SetTileMapTile {
+            layer: "walls".to_string(),
+            x: 2,
+            y: 3,
+            tile_id: Some(1),
+        }
+        .apply(world.entity_mut(root));
+        let placed = world.get::<MapPosition>(cell(&world, root, 2, 3).unwrap().entity.unwrap());
+        assert_eq!(placed.map(|p| (p.x(), p.y())), Some((32.0, 48.0)));
+
+        SetTileMapTile {
+            layer: "walls".to_string(),
+            x: 0,
+            y: 0,
+            tile_id: None,
+        }
+        .apply(world.entity_mut(root));
+        assert!(cell(&world, root, 0, 0).is_none());
+        assert!(world.get_entity(new).is_err());
+    }
+
+    /// `(x, width)` of the tile colliders in row `y` of the "walls" layer.
+    fn row_colliders(world: &World, root: Entity, y: u32) -> Vec<(f32, f32)> {
+        let layer = &world.get::<TileMapLayers>(root).unwrap().layers[0];
+        let mut row: Vec<(f32, f32)> = layer
+            .colliders
+            .get(&y)
+            .into_iter()
+            .flatten()
+            .map(|&e| {
+                assert_eq!(world.get::<Group>(e).unwrap().name(), "walls");
+                assert_eq!(world.get::<ChildOf>(e).map(|c| c.parent()), Some(root));
+                let pos = world.get::<MapPosition>(e).unwrap();
+                assert_eq!(pos.y(), y as f32 * 16.0);
+                (pos.x(), world.get::<BoxCollider>(e).unwrap().size.x)
+            })
+            .collect();
+        row.sort_by(|a, b| a.0.total_cmp(&b.0));
+        row
+    }
+
+    #[test]
+    fn solid_layer_colliders_follow_tile_edits() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        assert_eq!(row_colliders(&world, root, 0), vec![(0.0, 16.0)]);
+        let set = |world: &mut World, x: u32, tile_id: Option<u32>| {
+            SetTileMapTile {
+                layer: "walls".to_string(),
+                x,
+                y: 0,
+                tile_id,
+            }
+            .apply(world.entity_mut(root));
+        };
+
+        // Adjacent tiles share one collider.
+        set(&mut world, 1, Some(0));
+        set(&mut world, 2, Some(0));
+        assert_eq!(row_colliders(&world, root, 0), vec![(0.0, 48.0)]);
+
+        // Clearing a cell removes its part of the collider.
+        let merged = world.get::<TileMapLayers>(root).unwrap().layers[0].colliders[&0][0];
+        set(&mut world, 1, None);
+        assert!(world.get_entity(merged).is_err());
+        assert_eq!(
+            row_colliders(&world, root, 0),
+            vec![(0.0, 16.0), (32.0, 16.0)]
+        );
+        set(&mut world, 0, None);
+        set(&mut world, 2, None);
+        assert!(row_colliders(&world, root, 0).is_empty());
+        let mut colliders = world.query_filtered::<(), (With<BoxCollider>, Without<Signals>)>();
+        assert_eq!(colliders.iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn hiding_a_solid_layer_removes_its_colliders() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let set_visible = |world: &mut World, visible: bool| {
+            SetTileMapLayerVisible {
+                layer: "walls".to_string(),
+                visible,
+            }
+            .apply(world.entity_mut(root));
+        };
+
+        set_visible(&mut world, false);
+        assert!(row_colliders(&world, root, 0).is_empty());
+        let mut colliders = world.query_filtered::<(), (With<BoxCollider>, Without<Signals>)>();
+        assert_eq!(colliders.iter(&world).count(), 0);
+
+        set_visible(&mut world, true);
+        assert_eq!(row_colliders(&world, root, 0), vec![(0.0, 16.0)]);
+    }
+
+    #[test]
+    fn set_tile_rejects_unknown_ids_cells_and_layers() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let before = cell(&world, root, 0, 0);
+
+        for (layer, x, tile_id) in [
+            ("walls", 0, Some(9)),
+            ("walls", 4, Some(0)),
+            ("fg", 0, None),
+        ] {
+            SetTileMapTile {
+                layer: layer.to_string(),
+                x,
+                y: 0,
+                tile_id,
+            }
+            .apply(world.entity_mut(root));
+        }
+        assert_eq!(cell(&world, root, 0, 0), before);
+    }
+
+    #[test]
+    fn hidden_layer_despawns_tiles_and_keeps_edits_for_reveal() {
+        let mut world = World::new();
+        let root = spawn_map(&mut world);
+        let shown = cell(&world, root, 0, 0).unwrap().entity.unwrap();
+
+        SetTileMapLayerVisible {
+            layer: "walls".to_string(),
+            visible: false,
+        }
+        .apply(world.entity_mut(root));
+        assert!(world.get_entity(shown).is_err());
+        assert_eq!(cell(&world, root, 0, 0).unwrap().entity, None);
+
+        SetTileMapTile {
+            layer: "walls".to_string(),
+            x: 1,
+            y: 1,
+            tile_id: Some(0),
+        }
+        .apply(world.entity_mut(root));
+        assert_eq!(cell(&world, root, 1, 1).unwrap().entity, None);
+
+        SetTileMapLayerVisible {
+            layer: "walls".to_string(),
+            visible: true,
+        }
+        .apply(world.entity_mut(root));
+        for (x, y) in [(0, 0), (1, 1)] {
+            let tile = cell(&world, root, x, y).unwrap().entity.unwrap();
+            assert!(world.get::<Sprite>(tile).is_some());
+        }
+    }
+
+    #[test]
+    fn set_tile_applies_autotile_rules_to_cell_and_neighbours() {
+        let mut world = World::new();
+        // Isolated = 2, east neighbour = 3, west neighbour = 4, both = 5.
+        let rules = AutoTileRules::new(
+            AutoTileMode::Bitmask16,
+            [(0, 2), (2, 3), (8, 4), (10, 5)].into_iter().collect(),
+        )
+        .unwrap()
+        .with_border_filled(false);
+        let mut store = AutoTileStore::default();
+        store.insert("tiles", rules);
+        world.insert_resource(store);
+        let root = spawn_map(&mut world);
+        let set = |world: &mut World, x: u32, tile_id: Option<u32>| {
+            SetTileMapTile {
+                layer: "walls".to_string(),
+                x,
+                y: 1,
+                tile_id,
+            }
+            .apply(world.entity_mut(root));
+        };
+        let id_at = |world: &World, x: u32| cell(world, root, x, 1).map(|tile| tile.id);
+
+        set(&mut world, 1, Some(2));
+        assert_eq!(id_at(&world, 1), Some(2));
+        set(&mut world, 2, Some(2));
+        assert_eq!((id_at(&world, 1), id_at(&world, 2)), (Some(3), Some(4)));
+        let sprite = world
+            .get::<Sprite>(cell(&world, root, 1, 1).unwrap().entity.unwrap())
+            .unwrap();
+        assert_eq!(sprite.offset.x, 48.0);
+
+        set(&mut world, 2, None);
+        assert_eq!((id_at(&world, 1), id_at(&world, 2)), (Some(2), None));
+        // Non-terrain tiles are left alone.
+        assert_eq!(cell(&world, root, 0, 0).unwrap().id, 1);
+    }
+
+    #[test]
+    fn isometric_tiles_are_placed_on_diamonds_and_y_sorted() {
+        let mut world = World::new();
+        let tilemap = Tilemap {
+            tile_size: 16,
+            map_width: 4,
+            map_height: 4,
+            projection: TileProjection::Isometric,
+            layers: vec![TileLayer {
+                name: "ground".to_string(),
+                positions: vec![
+                    TilePosition { x: 0, y: 0, id: 0 },
+                    TilePosition { x: 1, y: 2, id: 0 },
+                ],
+                visible: true,
+                solid: false,
+            }],
+            spawn_points: Vec::new(),
+            objects: Vec::new(),
+        };
+        let mut state = SystemState::<Commands>::new(&mut world);
+        let mut commands = state.get_mut(&mut world).expect("Commands should fetch");
+        let layers = spawn_tiles(&mut commands, "tiles", 16, 16, &tilemap, None);
+        state.apply(&mut world);
+
+        let tile = |x: u32, y: u32| layers.layers[0].tiles[&(x, y)].entity.unwrap();
+        let back = tile(0, 0);
+        let front = tile(1, 2);
+        let pos = world.get::<MapPosition>(front).unwrap();
+        assert_eq!((pos.x(), pos.y()), (-8.0, 4.0));
+        let z = |e: Entity| world.get::<ZIndex>(e).unwrap().0;
+        assert!(z(front) > z(back));
+        assert!(z(front) < 0.0);
     }
 }
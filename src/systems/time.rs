@@ -9,12 +9,13 @@ use crate::resources::worldtime::WorldTime;
 /// Update elapsed and delta seconds on the `WorldTime` resource.
 ///
 /// `dt` is expected to be the unscaled frame delta in seconds. The system
-/// applies the current `time_scale` and writes both `elapsed` and `delta`.
-/// Also increments the frame counter.
+/// applies the current `time_scale` and writes both `elapsed` and `delta`;
+/// `real_delta` keeps `dt` as given. Also increments the frame counter.
 pub fn update_world_time(world: &mut World, dt: f32) {
     let mut wt = world.resource_mut::<WorldTime>();
     let scaled_dt = dt * wt.time_scale;
     wt.elapsed += scaled_dt;
     wt.delta = scaled_dt;
+    wt.real_delta = dt;
     wt.frame_count += 1;
 }
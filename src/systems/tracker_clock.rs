@@ -0,0 +1,519 @@
+//! Tracker module (.xm/.mod) song layout and playback-position tracking.
+//!
+//! Raylib plays tracker modules through an opaque decoder, so the engine cannot
+//! ask it which pattern row is currently playing. Instead, the audio thread
+//! parses the module once at load time into a [`TrackerLayout`] (initial
+//! speed/BPM, order table, rows per pattern) and a [`TrackerClock`] maps the
+//! stream's played time back to an `(order, pattern, row)` position.
+//!
+//! While parsing, the pattern data is scanned for the effects that change the
+//! song's timing or flow: speed/tempo (`Fxx`), position jumps (`Bxx`) and
+//! pattern breaks (`Dxx`). The layout plays the song through once with them
+//! applied and records when each row starts. The walk stops at the end of the
+//! order table or when a jump returns to a row already played (a song loop).
+//!
+//! # Limitations
+//!
+//! Pattern loops (`E6x`) and pattern delays (`EEx`) are not interpreted, so
+//! modules that rely on them drift.
+
+use rustc_hash::FxHashSet;
+
+/// A playback position inside a tracker module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackerPosition {
+    /// Index into the song's order table.
+    pub order: u32,
+    /// Pattern number played at `order`.
+    pub pattern: u32,
+    /// Row inside the pattern.
+    pub row: u32,
+}
+
+/// Song layout extracted from a tracker module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerLayout {
+    /// Initial ticks per row.
+    pub speed: u16,
+    /// Initial tempo (BPM in tracker terms; one tick lasts `2.5 / bpm` seconds).
+    pub bpm: u16,
+    /// Pattern numbers in play order.
+    pub orders: Vec<u8>,
+    /// Row count for each pattern number.
+    pub pattern_rows: Vec<u16>,
+    /// Start time in seconds of every row played, in play order.
+    timeline: Vec<(f32, TrackerPosition)>,
+    /// Time at which the last row of `timeline` ends.
+    length: f32,
+}
+
+/// Rows per pattern in ProTracker modules.
+const MOD_ROWS_PER_PATTERN: u16 = 64;
+
+/// Offset of the first pattern in a 31-sample ProTracker module.
+const MOD_PATTERN_DATA: usize = 1084;
+
+/// Upper bound on the rows walked when building the timeline, against
+/// malformed modules.
+const MAX_TIMELINE_ROWS: usize = 1 << 16;
+
+/// Timing and flow effects found on one pattern row, across all channels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct RowEffects {
+    /// `Fxx` below `0x20`: new ticks per row.
+    speed: Option<u8>,
+    /// `Fxx` from `0x20`: new tempo.
+    bpm: Option<u8>,
+    /// `Bxx`: order to continue at.
+    jump: Option<u8>,
+    /// `Dxx`: row of the next pattern to continue at.
+    break_row: Option<u8>,
+}
+
+impl RowEffects {
+    /// Record one channel's effect. Effect numbers are the same in .mod and .xm.
+    fn add(&mut self, effect: u8, param: u8) {
+        match effect {
+            0x0B => self.jump = Some(param),
+            // The row is stored as two decimal digits.
+            0x0D => self.break_row = Some((param >> 4) * 10 + (param & 0x0F)),
+            0x0F if param == 0 => {}
+            0x0F if param < 0x20 => self.speed = Some(param),
+            0x0F => self.bpm = Some(param),
+            _ => {}
+        }
+    }
+}
+
+/// Effects of each row of a packed .xm pattern.
+fn xm_pattern_effects(data: &[u8], rows: usize, channels: usize) -> Vec<RowEffects> {
+    let mut effects = vec![RowEffects::default(); rows];
+    let mut at = 0;
+    'rows: for row in effects.iter_mut() {
+        for _ in 0..channels {
+            let Some(&first) = data.get(at) else {
+                break 'rows;
+            };
+            // A set high bit marks a compressed cell: the low bits say which of
+            // note, instrument, volume, effect and parameter follow.
+            // Otherwise all five bytes follow, starting with the note.
+            let flags = if first & 0x80 != 0 {
+                at += 1;
+                first
+            } else {
+                0x1F
+            };
+            let mut fields = [0u8; 5];
+            for (bit, field) in fields.iter_mut().enumerate() {
+                if flags & (1 << bit) != 0 {
+                    *field = data.get(at).copied().unwrap_or(0);
+                    at += 1;
+                }
+            }
+            row.add(fields[3], fields[4]);
+        }
+    }
+    effects
+}
+
+/// Number of channels of a 31-sample .mod from its signature at offset 1080.
+fn mod_channels(signature: &[u8]) -> usize {
+    let digit = |b: u8| b.is_ascii_digit().then(|| (b - b'0') as usize);
+    match signature {
+        b"FLT8" | b"OCTA" | b"CD81" => 8,
+        [a, b, b'C', b'H'] => match (digit(*a), digit(*b)) {
+            (Some(tens), Some(ones)) => tens * 10 + ones,
+            _ => 4,
+        },
+        [n, b'C', b'H', b'N'] => digit(*n).unwrap_or(4),
+        _ => 4,
+    }
+}
+
+/// Effects of each row of .mod pattern `pattern`. Missing data reads as empty.
+fn mod_pattern_effects(bytes: &[u8], pattern: usize, channels: usize) -> Vec<RowEffects> {
+    let rows = MOD_ROWS_PER_PATTERN as usize;
+    let start = MOD_PATTERN_DATA + pattern * rows * channels * 4;
+    (0..rows)
+        .map(|row| {
+            let mut effects = RowEffects::default();
+            for channel in 0..channels {
+                let cell = start + (row * channels + channel) * 4;
+                if let Some(&[_, _, effect, param]) = bytes.get(cell..cell + 4) {
+                    effects.add(effect & 0x0F, param);
+                }
+            }
+            effects
+        })
+        .collect()
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+impl TrackerLayout {
+    /// Parse a module by file extension (`.xm` or `.mod`, case-insensitive).
+    ///
+    /// Returns `None` for other formats or malformed headers.
+    pub fn from_file_bytes(path: &str, bytes: &[u8]) -> Option<Self> {
+        let lower = path.to_ascii_lowercase();
+        if lower.ends_with(".xm") {
+            Self::parse_xm(bytes)
+        } else if lower.ends_with(".mod") {
+            Self::parse_mod(bytes)
+        } else {
+            None
+        }
+    }
+
+    /// Parse a FastTracker II extended module (.xm) header.
+    pub fn parse_xm(bytes: &[u8]) -> Option<Self> {
+        if !bytes.starts_with(b"Extended Module: ") {
+            return None;
+        }
+        let header_size = read_u32(bytes, 60)? as usize;
+        let song_len = read_u16(bytes, 64)?.min(256) as usize;
+        let channels = read_u16(bytes, 68)? as usize;
+        let num_patterns = read_u16(bytes, 70)? as usize;
+        let speed = read_u16(bytes, 76)?;
+        let bpm = read_u16(bytes, 78)?;
+        let orders = bytes.get(80..80 + song_len)?.to_vec();
+
+        // Pattern headers follow the song header, each followed by its packed
+        // rows; a packed size of 0 means the pattern is empty.
+        let mut pattern_rows = Vec::with_capacity(num_patterns);
+        let mut effects = Vec::with_capacity(num_patterns);
+        let mut at = 60 + header_size;
+        for _ in 0..num_patterns {
+            let pattern_header_len = read_u32(bytes, at)? as usize;
+            let rows = read_u16(bytes, at + 5)?;
+            let packed_size = read_u16(bytes, at + 7)? as usize;
+            let data_start = at + pattern_header_len;
+            let data = bytes
+                .get(data_start..data_start + packed_size)
+                .unwrap_or_default();
+            pattern_rows.push(rows);
+            effects.push(xm_pattern_effects(data, rows as usize, channels));
+            at = data_start + packed_size;
+        }
+
+        Some(Self::new(speed, bpm, orders, pattern_rows, &effects))
+    }
+
+    /// Parse a 31-sample ProTracker module (.mod) header.
+    pub fn parse_mod(bytes: &[u8]) -> Option<Self> {
+        let song_len = (*bytes.get(950)?).min(128) as usize;
+        let orders = bytes.get(952..952 + song_len)?.to_vec();
+        let max_pattern = bytes.get(952..1080)?.iter().copied().max().unwrap_or(0);
+        let patterns = max_pattern as usize + 1;
+        let channels = mod_channels(bytes.get(1080..1084)?);
+        let effects: Vec<_> = (0..patterns)
+            .map(|pattern| mod_pattern_effects(bytes, pattern, channels))
+            .collect();
+        Some(Self::new(
+            6,
+            125,
+            orders,
+            vec![MOD_ROWS_PER_PATTERN; patterns],
+            &effects,
+        ))
+    }
+
+    /// Build the layout and its timeline from each pattern's row effects.
+    fn new(
+        speed: u16,
+        bpm: u16,
+        orders: Vec<u8>,
+        pattern_rows: Vec<u16>,
+        effects: &[Vec<RowEffects>],
+    ) -> Self {
+        let mut layout = Self {
+            speed,
+            bpm,
+            orders,
+            pattern_rows,
+            timeline: Vec::new(),
+            length: 0.0,
+        };
+        layout.build_timeline(effects);
+        layout
+    }
+
+    /// Row count of `pattern`, at least 1.
+    fn rows_of(&self, pattern: u8) -> u32 {
+        self.pattern_rows
+            .get(pattern as usize)
+            .copied()
+            .unwrap_or(MOD_ROWS_PER_PATTERN)
+            .max(1) as u32
+    }
+
+    /// Play the song through once, applying speed/tempo changes, position
+    /// jumps and pattern breaks, and record when each row starts.
+    fn build_timeline(&mut self, effects: &[Vec<RowEffects>]) {
+        let mut speed = self.speed.max(1) as f32;
+        let mut bpm = self.bpm.max(1) as f32;
+        let mut time = 0.0;
+        let mut visited = FxHashSet::default();
+        let (mut order, mut row) = (0usize, 0u32);
+        while let Some(&pattern) = self.orders.get(order)
+            && self.timeline.len() < MAX_TIMELINE_ROWS
+            && visited.insert((order, row))
+        {
+            let fx = effects
+                .get(pattern as usize)
+                .and_then(|rows| rows.get(row as usize))
+                .copied()
+                .unwrap_or_default();
+            if let Some(s) = fx.speed {
+                speed = s as f32;
+            }
+            if let Some(b) = fx.bpm {
+                bpm = b as f32;
+            }
+            self.timeline.push((
+                time,
+                TrackerPosition {
+                    order: order as u32,
+                    pattern: pattern as u32,
+                    row,
+                },
+            ));
+            time += 2.5 * speed / bpm;
+
+            if fx.jump.is_none() && fx.break_row.is_none() {
+                row += 1;
+                if row < self.rows_of(pattern) {
+                    continue;
+                }
+            }
+            order = fx.jump.map_or(order + 1, usize::from);
+            // A break past the end of the next pattern starts it at row 0.
+            row = fx.break_row.map_or(0, u32::from);
+            if self
+                .orders
+                .get(order)
+                .is_some_and(|&next| row >= self.rows_of(next))
+            {
+                row = 0;
+            }
+        }
+        self.length = time;
+    }
+
+    /// Duration of a single row in seconds at the initial speed/BPM.
+    pub fn row_seconds(&self) -> f32 {
+        2.5 * self.speed.max(1) as f32 / self.bpm.max(1) as f32
+    }
+
+    /// Map `played` seconds of playback to a song position.
+    ///
+    /// Returns `None` once the song has played through (see the module docs).
+    pub fn position_at(&self, played: f32) -> Option<TrackerPosition> {
+        if !(0.0..self.length).contains(&played) {
+            return None;
+        }
+        let next = self.timeline.partition_point(|&(start, _)| start <= played);
+        Some(self.timeline[next.checked_sub(1)?].1)
+    }
+}
+
+/// Reports each new row reached by a playing tracker module.
+///
+/// Owned by the audio thread, one per music id with tracker events enabled.
+/// Call [`reset`](Self::reset) whenever the stream restarts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackerClock {
+    layout: TrackerLayout,
+    last: Option<TrackerPosition>,
+}
+
+impl TrackerClock {
+    /// Create a clock for `layout`.
+    pub fn new(layout: TrackerLayout) -> Self {
+        Self { layout, last: None }
+    }
+
+    /// Forget the last reported position.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Advance to `played` seconds of playback, returning the position if a
+    /// new row was entered. Skipped rows are not reported individually.
+    pub fn advance(&mut self, played: f32) -> Option<TrackerPosition> {
+        let pos = self.layout.position_at(played)?;
+        if self.last == Some(pos) {
+            return None;
+        }
+        self.last = Some(pos);
+        Some(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xm_bytes(orders: &[u8], rows: &[u16], speed: u16, bpm: u16) -> Vec<u8> {
+        let patterns: Vec<_> = rows.iter().map(|&r| (r, vec![0x80, 0x80])).collect();
+        xm_bytes_with(orders, &patterns, speed, bpm)
+    }
+
+    /// XM with the given `(rows, packed data)` patterns.
+    fn xm_bytes_with(orders: &[u8], patterns: &[(u16, Vec<u8>)], speed: u16, bpm: u16) -> Vec<u8> {
+        let mut b = Vec::new();
+        b.extend_from_slice(b"Extended Module: ");
+        b.resize(60, 0);
+        b.extend_from_slice(&276u32.to_le_bytes()); // header size
+        b.extend_from_slice(&(orders.len() as u16).to_le_bytes()); // song length
+        b.extend_from_slice(&0u16.to_le_bytes()); // restart
+        b.extend_from_slice(&4u16.to_le_bytes()); // channels
+        b.extend_from_slice(&(patterns.len() as u16).to_le_bytes()); // patterns
+        b.extend_from_slice(&0u16.to_le_bytes()); // instruments
+        b.extend_from_slice(&1u16.to_le_bytes()); // flags
+        b.extend_from_slice(&speed.to_le_bytes());
+        b.extend_from_slice(&bpm.to_le_bytes());
+        let mut table = [0u8; 256];
+        table[..orders.len()].copy_from_slice(orders);
+        b.extend_from_slice(&table);
+        for (rows, data) in patterns {
+            b.extend_from_slice(&9u32.to_le_bytes());
+            b.push(0);
+            b.extend_from_slice(&rows.to_le_bytes());
+            b.extend_from_slice(&(data.len() as u16).to_le_bytes());
+            b.extend_from_slice(data);
+        }
+        b
+    }
+
+    /// Packed rows of a 4-channel XM pattern with `effects[row] = (effect, param)`
+    /// in the first channel.
+    fn packed_rows(effects: &[Option<(u8, u8)>]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for effect in effects {
+            match effect {
+                Some((effect, param)) => data.extend_from_slice(&[0x98, *effect, *param]),
+                None => data.push(0x80),
+            }
+            data.extend_from_slice(&[0x80; 3]);
+        }
+        data
+    }
+
+    fn pos(order: u32, pattern: u32, row: u32) -> Option<TrackerPosition> {
+        Some(TrackerPosition {
+            order,
+            pattern,
+            row,
+        })
+    }
+
+    #[test]
+    fn parses_xm_header_and_pattern_rows() {
+        let layout = TrackerLayout::parse_xm(&xm_bytes(&[0, 1, 0], &[64, 32], 6, 125)).unwrap();
+        assert_eq!(layout.speed, 6);
+        assert_eq!(layout.bpm, 125);
+        assert_eq!(layout.orders, vec![0, 1, 0]);
+        assert_eq!(layout.pattern_rows, vec![64, 32]);
+    }
+
+    #[test]
+    fn rejects_non_xm_bytes() {
+        assert!(TrackerLayout::parse_xm(b"RIFF....WAVE").is_none());
+        assert!(TrackerLayout::from_file_bytes("song.ogg", &[0; 2000]).is_none());
+    }
+
+    #[test]
+    fn parses_mod_order_table() {
+        let mut b = vec![0u8; 1084];
+        b[950] = 3;
+        b[952] = 0;
+        b[953] = 2;
+        b[954] = 1;
+        b[1080..1084].copy_from_slice(b"M.K.");
+        let layout = TrackerLayout::from_file_bytes("SONG.MOD", &b).unwrap();
+        assert_eq!(layout.orders, vec![0, 2, 1]);
+        assert_eq!(layout.pattern_rows.len(), 3);
+        assert_eq!((layout.speed, layout.bpm), (6, 125));
+    }
+
+    #[test]
+    fn position_walks_order_table() {
+        let layout = TrackerLayout::parse_xm(&xm_bytes(&[0, 1], &[4, 2], 6, 125)).unwrap();
+        let row = layout.row_seconds();
+        assert_eq!(
+            layout.position_at(0.0),
+            Some(TrackerPosition {
+                order: 0,
+                pattern: 0,
+                row: 0
+            })
+        );
+        assert_eq!(
+            layout.position_at(row * 4.5),
+            Some(TrackerPosition {
+                order: 1,
+                pattern: 1,
+                row: 0
+            })
+        );
+        assert_eq!(layout.position_at(row * 6.5), None);
+    }
+
+    #[test]
+    fn speed_change_shortens_following_rows() {
+        // F03 on row 1 halves the row length from there on.
+        let data = packed_rows(&[None, Some((0x0F, 3)), None, None]);
+        let layout = TrackerLayout::parse_xm(&xm_bytes_with(&[0], &[(4, data)], 6, 125)).unwrap();
+        let row = layout.row_seconds();
+        assert_eq!(layout.position_at(row * 1.6), pos(0, 0, 2));
+        assert_eq!(layout.position_at(row * 2.1), pos(0, 0, 3));
+        assert_eq!(layout.position_at(row * 2.6), None);
+    }
+
+    #[test]
+    fn pattern_break_and_jump_change_the_song_flow() {
+        // D02 on order 0 row 1 continues at order 1 row 2; B00 on its last
+        // row loops back to the start, which ends the timeline.
+        let first = packed_rows(&[None, Some((0x0D, 0x02)), None, None]);
+        let second = packed_rows(&[None, None, None, Some((0x0B, 0))]);
+        let layout = TrackerLayout::parse_xm(&xm_bytes_with(
+            &[0, 1, 0],
+            &[(4, first), (4, second)],
+            6,
+            125,
+        ))
+        .unwrap();
+        let row = layout.row_seconds();
+        assert_eq!(layout.position_at(row * 1.5), pos(0, 0, 1));
+        assert_eq!(layout.position_at(row * 2.5), pos(1, 1, 2));
+        assert_eq!(layout.position_at(row * 3.5), pos(1, 1, 3));
+        assert_eq!(layout.position_at(row * 4.5), None);
+    }
+
+    #[test]
+    fn mod_channel_count_follows_signature() {
+        assert_eq!(mod_channels(b"M.K."), 4);
+        assert_eq!(mod_channels(b"6CHN"), 6);
+        assert_eq!(mod_channels(b"OCTA"), 8);
+        assert_eq!(mod_channels(b"12CH"), 12);
+    }
+
+    #[test]
+    fn clock_reports_each_row_once() {
+        let layout = TrackerLayout::parse_xm(&xm_bytes(&[0], &[8], 6, 125)).unwrap();
+        let row = layout.row_seconds();
+        let mut clock = TrackerClock::new(layout);
+        assert!(clock.advance(0.0).is_some());
+        assert!(clock.advance(row * 0.5).is_none());
+        assert_eq!(clock.advance(row * 1.1).map(|p| p.row), Some(1));
+        clock.reset();
+        assert_eq!(clock.advance(row * 1.1).map(|p| p.row), Some(1));
+    }
+}
@@ -0,0 +1,316 @@
+//! Line-of-sight systems.
+//!
+//! - [`occluder_system`] rebuilds the [`Occluders`] segments: the outlines of
+//!   the solid tiles (when a tilemap or the settings change) and the collider
+//!   boxes of the labeled entities (every frame, since they move).
+//! - [`visibility_polygon_system`] shadow-casts every [`VisibilityPolygon`]
+//!   against those segments.
+//! - [`process_lua_visibility_commands`] *(feature = "lua")* applies
+//!   `engine.set_occluders()`.
+//!
+//! Filled polygons are drawn by the render system.
+//!
+//! # Related
+//!
+//! - [`crate::resources::occluders`] – segments and polygon casting
+//! - [`crate::components::visibilitypolygon`] – the polygon component
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashMap;
+
+use crate::components::boxcollider::BoxCollider;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::rotation::Rotation;
+use crate::components::tags::Tags;
+use crate::components::tilemap::TileMapLayers;
+use crate::components::visibilitypolygon::VisibilityPolygon;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::VisibilityCmd;
+use crate::resources::occluders::{
+    CastScratch, Occluders, Segment, cast_visibility_polygon, rect_segments,
+};
+
+/// Rebuilds the [`Occluders`] segments from the visible tiles of its
+/// `solid_layers` and the colliders of the entities carrying its `label`.
+///
+/// Edges shared by two solid tiles are dropped, so a wall made of many tiles
+/// only blocks along its outline. Collider segments are gathered in a buffer
+/// kept between frames. Run after transform propagation.
+#[allow(clippy::type_complexity)]
+pub fn occluder_system(
+    mut occluders: ResMut<Occluders>,
+    mut segments: Local<Vec<Segment>>,
+    tilemaps: Query<(Ref<TileMapLayers>, Ref<GlobalTransform2D>)>,
+    mut removed: RemovedComponents<TileMapLayers>,
+    colliders: Query<(
+        &BoxCollider,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+        Option<&Group>,
+        Option<&Tags>,
+    )>,
+) {
+    let removed = removed.read().count() > 0;
+    if occluders.label().is_none()
+        && occluders.solid_layers().is_empty()
+        && !occluders.tiles_dirty()
+        && occluders.segments().is_empty()
+    {
+        return;
+    }
+
+    let changed = tilemaps
+        .iter()
+        .any(|(layers, transform)| layers.is_changed() || transform.is_changed());
+    if occluders.tiles_dirty() || changed || removed {
+        let segments = tile_outlines(&occluders, &tilemaps);
+        occluders.set_tile_segments(segments);
+    }
+
+    if let Some(label) = occluders.label() {
+        for (collider, position, maybe_gt, group, tags) in colliders.iter() {
+            let labeled =
+                group.is_some_and(|g| g.name() == label) || tags.is_some_and(|t| t.has(label));
+            if !labeled {
+                continue;
+            }
+            let world_pos = maybe_gt.map_or(position.pos, |gt| gt.position);
+            let (x, y, w, h) = collider.get_aabb(world_pos);
+            segments.extend(rect_segments(x, y, w, h));
+        }
+    }
+    occluders.rebuild(segments.drain(..));
+}
+
+/// World-space outline segments of the solid tiles, without the edges shared
+/// by two solid tiles.
+fn tile_outlines(
+    occluders: &Occluders,
+    tilemaps: &Query<(Ref<TileMapLayers>, Ref<GlobalTransform2D>)>,
+) -> Vec<Segment> {
+    // Endpoints are quantised so edges shared by neighbouring tiles compare
+    // equal despite float rounding.
+    let key = |p: Vector2| ((p.x * 16.0).round() as i64, (p.y * 16.0).round() as i64);
+    let mut edges: FxHashMap<((i64, i64), (i64, i64)), (Segment, u32)> = FxHashMap::default();
+    for (layers, transform) in tilemaps.iter() {
+        for name in occluders.solid_layers() {
+            let Some(layer) = layers.layer_index(name).map(|i| &layers.layers[i]) else {
+                continue;
+            };
+            if !layer.visible {
+                continue;
+            }
+            for &(x, y) in layer.tiles.keys() {
+                let corners: Vec<Vector2> = layers
+                    .grid
+                    .cell_corners(x as i64, y as i64)
+                    .into_iter()
+                    .map(|corner| transform.transform_point(corner))
+                    .collect();
+                for (i, &a) in corners.iter().enumerate() {
+                    let b = corners[(i + 1) % corners.len()];
+                    let (ka, kb) = (key(a), key(b));
+                    let edge_key = if ka <= kb { (ka, kb) } else { (kb, ka) };
+                    edges.entry(edge_key).or_insert((Segment::new(a, b), 0)).1 += 1;
+                }
+            }
+        }
+    }
+    edges
+        .into_values()
+        .filter(|&(_, count)| count == 1)
+        .map(|(segment, _)| segment)
+        .collect()
+}
+
+/// Recomputes every [`VisibilityPolygon`] from the entity's position and
+/// rotation.
+///
+/// Position and rotation come from [`GlobalTransform2D`] when present,
+/// otherwise from [`MapPosition`] and [`Rotation`]. Run after
+/// [`occluder_system`]. Each polygon's point list and the casting buffers are
+/// reused between frames.
+pub fn visibility_polygon_system(
+    occluders: Res<Occluders>,
+    mut scratch: Local<CastScratch>,
+    mut query: Query<(
+        &mut VisibilityPolygon,
+        Option<&MapPosition>,
+        Option<&Rotation>,
+        Option<&GlobalTransform2D>,
+    )>,
+) {
+    for (mut polygon, map_pos, rotation, global) in query.iter_mut() {
+        let (origin, facing) = match (global, map_pos) {
+            (Some(gt), _) => (gt.position, gt.rotation_degrees),
+            (None, Some(mp)) => (mp.pos, rotation.map_or(0.0, |r| r.degrees)),
+            (None, None) => continue,
+        };
+        let cone =
+            (!polygon.is_closed()).then(|| (facing.to_radians(), polygon.fov_degrees.to_radians()));
+        let radius = polygon.radius;
+        polygon.origin = origin;
+        cast_visibility_polygon(
+            &mut scratch,
+            origin,
+            radius,
+            cone,
+            occluders.segments(),
+            &mut polygon.points,
+        );
+    }
+}
+
+/// Applies visibility commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_visibility_commands(
+    InMut(buf): InMut<Vec<VisibilityCmd>>,
+    mut occluders: ResMut<Occluders>,
+) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            VisibilityCmd::SetOccluders {
+                label,
+                solid_layers,
+            } => occluders.configure(label, solid_layers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::tilemap::{PlacedTile, TileGrid, TileMapLayer, TileProjection};
+    use crate::systems::propagate_transforms::propagate_transforms;
+    use bevy_ecs::hierarchy::ChildOf;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn spawn_wall(world: &mut World) -> Entity {
+        // Two stacked solid tiles in column 2: x 32..48, y 0..32.
+        let mut tiles = FxHashMap::default();
+        for y in 0..2 {
+            tiles.insert(
+                (2, y),
+                PlacedTile {
+                    id: 0,
+                    entity: None,
+                },
+            );
+        }
+        world
+            .spawn((
+                TileMapLayers {
+                    tileset: "walls".to_string(),
+                    grid: TileGrid {
+                        projection: TileProjection::Orthogonal,
+                        tile_size: 16.0,
+                        map_width: 4,
+                        map_height: 2,
+                    },
+                    templates: Vec::new(),
+                    layers: vec![TileMapLayer {
+                        name: "walls".to_string(),
+                        visible: true,
+                        z: 0.0,
+                        tiles,
+                        ..TileMapLayer::default()
+                    }],
+                },
+                GlobalTransform2D::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn tiles_and_labeled_colliders_block_sight() {
+        let mut world = World::new();
+        let mut occluders = Occluders::default();
+        occluders.configure(Some("crate".to_string()), vec!["walls".to_string()]);
+        world.insert_resource(occluders);
+        spawn_wall(&mut world);
+        world.spawn((
+            Group::new("crate"),
+            MapPosition::new(0.0, -40.0),
+            BoxCollider::new(16.0, 16.0),
+        ));
+        let viewer = world
+            .spawn((MapPosition::new(8.0, 16.0), VisibilityPolygon::new(100.0)))
+            .id();
+
+        world
+            .run_system_once(occluder_system)
+            .expect("occluder_system");
+        world
+            .run_system_once(visibility_polygon_system)
+            .expect("visibility_polygon_system");
+
+        // The shared edge of the two wall tiles is dropped: 6 wall edges plus
+        // 4 for the crate.
+        assert_eq!(world.resource::<Occluders>().segments().len(), 10);
+        let polygon = world.get::<VisibilityPolygon>(viewer).unwrap();
+        assert!(polygon.contains(Vector2::new(24.0, 16.0)));
+        assert!(
+            !polygon.contains(Vector2::new(60.0, 16.0)),
+            "behind the wall"
+        );
+        assert!(
+            !polygon.contains(Vector2::new(8.0, -70.0)),
+            "behind the crate"
+        );
+        assert!(polygon.contains(Vector2::new(-40.0, 16.0)));
+    }
+
+    #[test]
+    fn unchanged_tilemaps_keep_their_outlines() {
+        let mut world = World::new();
+        let mut occluders = Occluders::default();
+        occluders.configure(None, vec!["walls".to_string()]);
+        world.insert_resource(occluders);
+        // A tilemap root with a child, as spawned by tilemap_spawn_system.
+        let root = spawn_wall(&mut world);
+        world.entity_mut(root).insert(MapPosition::new(0.0, 0.0));
+        world.spawn((MapPosition::new(0.0, 0.0), ChildOf(root)));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((propagate_transforms, occluder_system).chain());
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Occluders>().segments().len(), 6);
+
+        // Nothing moved or was edited: the outlines are not rebuilt.
+        world
+            .resource_mut::<Occluders>()
+            .set_tile_segments(Vec::new());
+        schedule.run(&mut world);
+        assert!(world.resource::<Occluders>().segments().is_empty());
+
+        world.get_mut::<MapPosition>(root).unwrap().pos = Vector2::new(16.0, 0.0);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Occluders>().segments().len(), 6);
+    }
+
+    #[test]
+    fn cones_follow_rotation() {
+        let mut world = World::new();
+        world.insert_resource(Occluders::default());
+        let viewer = world
+            .spawn((
+                MapPosition::new(0.0, 0.0),
+                Rotation { degrees: 180.0 },
+                VisibilityPolygon::new(100.0).with_fov(60.0),
+            ))
+            .id();
+        world
+            .run_system_once(visibility_polygon_system)
+            .expect("visibility_polygon_system");
+
+        let polygon = world.get::<VisibilityPolygon>(viewer).unwrap();
+        assert!(polygon.contains(Vector2::new(-50.0, 0.0)));
+        assert!(!polygon.contains(Vector2::new(50.0, 0.0)));
+    }
+}
@@ -0,0 +1,186 @@
+//! Water surface system.
+//!
+//! [`water_system`] steps every [`WaterSurface`]'s springs, splashes the
+//! surface where matching [`RigidBody`] entities enter the water and applies
+//! buoyancy and drag to the submerged ones. Runs before
+//! [`movement`](super::movement::movement) so the lift is integrated the same
+//! frame.
+//!
+//! # Related
+//!
+//! - [`crate::components::watersurface`] – water definition and examples
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+use rustc_hash::FxHashSet;
+
+use crate::components::boxcollider::BoxCollider;
+use crate::components::collision::pattern_matches;
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::rigidbody::RigidBody;
+use crate::components::tags::{Tags, entity_labels};
+use crate::components::watersurface::WaterSurface;
+use crate::resources::worldtime::WorldTime;
+
+/// `(water, body)` pairs that were in contact last frame and this frame.
+#[derive(Default)]
+pub struct WaterContacts {
+    previous: FxHashSet<(Entity, Entity)>,
+    current: FxHashSet<(Entity, Entity)>,
+}
+
+/// Simulates water surfaces and floats the bodies inside them.
+///
+/// For each body overlapping a water: a body that was not in it last frame
+/// pushes the surface column under its center with
+/// `splash * velocity.y`. Then, with `f` the submerged fraction of the body,
+/// `velocity.y -= buoyancy * f * dt` and the velocity is scaled by
+/// `1 - drag * f * dt`.
+#[allow(clippy::type_complexity)]
+pub fn water_system(
+    mut waters: Query<(
+        Entity,
+        &mut WaterSurface,
+        &MapPosition,
+        Option<&GlobalTransform2D>,
+    )>,
+    mut bodies: Query<(
+        Entity,
+        &MapPosition,
+        &mut RigidBody,
+        Option<&GlobalTransform2D>,
+        Option<&BoxCollider>,
+        Option<&Group>,
+        Option<&Tags>,
+    )>,
+    time: Res<WorldTime>,
+    mut contacts: Local<WaterContacts>,
+) {
+    crate::tracy::tracy_span!("water_system");
+    let contacts = &mut *contacts;
+    std::mem::swap(&mut contacts.previous, &mut contacts.current);
+    contacts.current.clear();
+
+    let delta = time.delta;
+    for (water_entity, mut water, position, global) in waters.iter_mut() {
+        water.step(delta);
+        let origin = global.map_or(position.pos, |gt| gt.position);
+        let bottom = origin.y + water.depth;
+
+        for (entity, position, mut rigidbody, global, collider, group, tags) in bodies.iter_mut() {
+            if entity == water_entity
+                || rigidbody.frozen
+                || !entity_labels(group, tags).any(|l| pattern_matches(&water.mask, l))
+            {
+                continue;
+            }
+            let world_pos = global.map_or(position.pos, |gt| gt.position);
+            let (min, max) = match collider {
+                Some(c) => {
+                    let r = c.as_rectangle(world_pos);
+                    (
+                        Vector2::new(r.x, r.y),
+                        Vector2::new(r.x + r.width, r.y + r.height),
+                    )
+                }
+                None => (world_pos, world_pos),
+            };
+            if max.x < origin.x || min.x > origin.x + water.width || min.y > bottom {
+                continue;
+            }
+            let center_x = (min.x + max.x) * 0.5 - origin.x;
+            let surface = origin.y + water.height_at(center_x);
+            let submerged = if max.y > min.y {
+                ((max.y - surface) / (max.y - min.y)).clamp(0.0, 1.0)
+            } else if min.y >= surface {
+                1.0
+            } else {
+                0.0
+            };
+            if submerged <= 0.0 {
+                continue;
+            }
+
+            contacts.current.insert((water_entity, entity));
+            if !contacts.previous.contains(&(water_entity, entity)) {
+                let splash = water.splash * rigidbody.velocity.y;
+                water.splash_at(center_x, splash);
+            }
+            rigidbody.velocity.y -= water.buoyancy * submerged * delta;
+            rigidbody.velocity *= (1.0 - water.drag * submerged * delta).max(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn world_with_delta(delta: f32) -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta,
+            real_delta: delta,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world
+    }
+
+    fn body(world: &mut World, group: &str, x: f32, y: f32, vy: f32) -> Entity {
+        let mut rigidbody = RigidBody::new();
+        rigidbody.velocity = Vector2::new(0.0, vy);
+        world
+            .spawn((
+                Group::new(group),
+                MapPosition::new(x, y),
+                BoxCollider::new(10.0, 10.0),
+                rigidbody,
+            ))
+            .id()
+    }
+
+    #[test]
+    fn buoyancy_scales_with_submerged_fraction() {
+        let mut world = world_with_delta(0.1);
+        world.spawn((
+            MapPosition::new(0.0, 100.0),
+            WaterSurface::new(200.0, 50.0)
+                .with_buoyancy(100.0, 0.0)
+                .with_splash(0.0),
+        ));
+        let half = body(&mut world, "crate", 50.0, 95.0, 0.0);
+        let deep = body(&mut world, "crate", 100.0, 120.0, 0.0);
+        let dry = body(&mut world, "crate", 150.0, 50.0, 0.0);
+
+        world.run_system_once(water_system).unwrap();
+
+        let vy = |world: &World, e| world.get::<RigidBody>(e).unwrap().velocity.y;
+        assert!((vy(&world, half) + 5.0).abs() < 1e-4);
+        assert!((vy(&world, deep) + 10.0).abs() < 1e-4);
+        assert_eq!(vy(&world, dry), 0.0);
+    }
+
+    #[test]
+    fn entering_bodies_splash_where_masks_match() {
+        let mut world = world_with_delta(0.0);
+        let water = world
+            .spawn((
+                MapPosition::new(0.0, 100.0),
+                WaterSurface::new(64.0, 50.0).with_mask("player"),
+            ))
+            .id();
+        body(&mut world, "player", 27.0, 98.0, 100.0);
+        body(&mut world, "crate", 3.0, 98.0, 100.0);
+
+        world.run_system_once(water_system).unwrap();
+        let mut water_surface = world.get::<WaterSurface>(water).unwrap().clone();
+        water_surface.step(0.01);
+        assert!(water_surface.heights()[4] > 0.0);
+        assert_eq!(water_surface.heights()[0], 0.0);
+    }
+}
@@ -0,0 +1,310 @@
+//! Weather systems.
+//!
+//! - [`weather_system`] advances the [`Weather`] transitions and keeps its
+//!   entities in sync: one [`ParticleEmitter`] per kind of precipitation
+//!   spanning the top edge of the camera view, and the global [`Wind`]
+//!   [`ForceArea`].
+//! - [`process_lua_weather_commands`] *(feature = "lua")* applies
+//!   `engine.set_weather()` and `engine.set_wind()`.
+//!
+//! Rain drops and snow flakes are drawn by the render system.
+//!
+//! # Related
+//!
+//! - [`crate::resources::weather`] – intensities, wind and transitions
+//! - [`crate::components::weather`] – markers of the spawned entities
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::{Rectangle, Vector2};
+
+use crate::components::forcearea::ForceArea;
+use crate::components::group::Group;
+use crate::components::mapposition::MapPosition;
+use crate::components::particleemitter::{EmitterShape, ParticleEmitter, TtlSpec};
+use crate::components::rigidbody::RigidBody;
+use crate::components::weather::{WeatherEmitter, WeatherParticle, Wind};
+use crate::resources::camera2d::Camera2DRes;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::WeatherCmd;
+use crate::resources::screensize::ScreenSize;
+use crate::resources::weather::{Weather, WeatherKind};
+use crate::resources::worldtime::WorldTime;
+
+/// Distance above the view at which particles are spawned.
+const SPAWN_MARGIN: f32 = 16.0;
+
+/// Seconds of wind acceleration folded into the initial lean of the particles.
+const WIND_LEAN: f32 = 0.5;
+
+/// Half-size of the wind area, centred on the world origin.
+const WIND_EXTENT: f32 = 1.0e7;
+
+/// Group of the rain drops and snow flakes, so the wind mask can select them.
+pub const WEATHER_GROUP: &str = "weather";
+
+/// Fall speed range, spread around the wind direction in degrees and speed
+/// cap of each kind of precipitation.
+fn fall_params(kind: WeatherKind) -> ((f32, f32), f32, f32) {
+    match kind {
+        WeatherKind::Snow => ((35.0, 70.0), 25.0, 90.0),
+        _ => ((450.0, 600.0), 3.0, 900.0),
+    }
+}
+
+/// Advances the weather transitions and updates the weather entities.
+///
+/// Emitters, particle templates and the wind area are spawned the first
+/// frame they are needed (and again if a scene change despawned them).
+/// Emitters are never despawned: at zero intensity they stop emitting and the
+/// particles already falling run out their TTL. Run before
+/// `particle_emitter_system` and `force_area_system`.
+#[allow(clippy::type_complexity)]
+pub fn weather_system(
+    time: Res<WorldTime>,
+    camera: Res<Camera2DRes>,
+    screensize: Res<ScreenSize>,
+    mut weather: ResMut<Weather>,
+    mut emitters: Query<(&WeatherEmitter, &mut ParticleEmitter, &mut MapPosition)>,
+    templates: Query<(Entity, &WeatherParticle), Without<MapPosition>>,
+    mut wind_areas: Query<&mut ForceArea, With<Wind>>,
+    mut commands: Commands,
+) {
+    weather.step(time.delta);
+    let wind = weather.wind();
+
+    if wind_areas.is_empty() {
+        if wind != Vector2::zero() {
+            commands.spawn((
+                Wind,
+                MapPosition::new(0.0, 0.0),
+                ForceArea::acceleration(wind)
+                    .with_rect(Rectangle::new(
+                        -WIND_EXTENT,
+                        -WIND_EXTENT,
+                        WIND_EXTENT * 2.0,
+                        WIND_EXTENT * 2.0,
+                    ))
+                    .with_mask(weather.wind_mask.clone()),
+            ));
+        }
+    } else {
+        for mut area in wind_areas.iter_mut() {
+            area.acceleration = wind;
+            area.enabled = wind != Vector2::zero();
+            if area.mask != weather.wind_mask {
+                area.mask.clone_from(&weather.wind_mask);
+            }
+        }
+    }
+
+    let view = camera.world_visible_rect(&screensize);
+    for kind in WeatherKind::PRECIPITATION {
+        let intensity = weather.intensity(kind);
+        let rate = match kind {
+            WeatherKind::Snow => weather.snow_rate,
+            _ => weather.rain_rate,
+        };
+        if let Some((_, mut emitter, mut position)) =
+            emitters.iter_mut().find(|(marker, _, _)| marker.0 == kind)
+        {
+            configure_emitter(
+                &mut emitter,
+                &mut position,
+                kind,
+                intensity * rate,
+                view,
+                wind,
+            );
+            continue;
+        }
+        if intensity <= 0.0 {
+            continue;
+        }
+        let template = templates
+            .iter()
+            .find(|(_, particle)| particle.0 == kind)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| {
+                let (_, _, max_speed) = fall_params(kind);
+                let mut body = RigidBody::new();
+                body.max_speed = Some(max_speed);
+                commands
+                    .spawn((WeatherParticle(kind), Group::new(WEATHER_GROUP), body))
+                    .id()
+            });
+        let mut emitter = ParticleEmitter {
+            templates: vec![template],
+            ..ParticleEmitter::default()
+        };
+        let mut position = MapPosition::new(0.0, 0.0);
+        configure_emitter(
+            &mut emitter,
+            &mut position,
+            kind,
+            intensity * rate,
+            view,
+            wind,
+        );
+        commands.spawn((WeatherEmitter(kind), emitter, position));
+    }
+}
+
+/// Stretch an emitter along the top of `view`, emitting `rate` particles per
+/// second over the visible width, leaning with `wind`.
+fn configure_emitter(
+    emitter: &mut ParticleEmitter,
+    position: &mut MapPosition,
+    kind: WeatherKind,
+    rate: f32,
+    view: Rectangle,
+    wind: Vector2,
+) {
+    let ((speed_min, speed_max), spread, _) = fall_params(kind);
+    let fall = (speed_min + speed_max) * 0.5;
+    // 0° points up and angles grow clockwise, so straight down is 180°.
+    let lean = (wind.x * WIND_LEAN).atan2(fall).to_degrees();
+    let angle = 180.0 - lean;
+    // Cover the sides the wind blows particles in from.
+    let width = view.width + view.height * 2.0;
+
+    position.pos = Vector2::new(view.x + view.width * 0.5, view.y - SPAWN_MARGIN);
+    emitter.shape = EmitterShape::Rect {
+        width,
+        height: SPAWN_MARGIN,
+    };
+    emitter.emissions_per_second = rate * width / view.width.max(1.0);
+    emitter.emissions_remaining = u32::MAX;
+    emitter.initial_emissions_remaining = u32::MAX;
+    emitter.arc_degrees = (angle - spread, angle + spread);
+    emitter.speed_range = (speed_min, speed_max);
+    emitter.ttl = TtlSpec::Fixed((view.height + SPAWN_MARGIN * 2.0) / speed_min);
+    if rate <= 0.0 {
+        emitter.time_since_emit = 0.0;
+    }
+}
+
+/// Applies weather commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_weather_commands(
+    InMut(buf): InMut<Vec<WeatherCmd>>,
+    mut weather: ResMut<Weather>,
+) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            WeatherCmd::Set {
+                kind,
+                intensity,
+                transition,
+            } => weather.set_weather(kind, intensity, transition),
+            WeatherCmd::Wind {
+                x,
+                y,
+                transition,
+                mask,
+            } => {
+                weather.set_wind(Vector2::new(x, y), transition);
+                if let Some(mask) = mask {
+                    weather.wind_mask = mask;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use raylib::prelude::Camera2D;
+
+    fn weather_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(WorldTime {
+            elapsed: 0.0,
+            delta: 1.0,
+            real_delta: 1.0,
+            time_scale: 1.0,
+            frame_count: 0,
+        });
+        world.insert_resource(ScreenSize { w: 320, h: 240 });
+        world.insert_resource(Camera2DRes(Camera2D {
+            target: Vector2::new(160.0, 120.0),
+            offset: Vector2::new(160.0, 120.0),
+            rotation: 0.0,
+            zoom: 1.0,
+        }));
+        world.insert_resource(Weather::default());
+        world
+    }
+
+    #[test]
+    fn rain_spawns_an_emitter_above_the_view() {
+        let mut world = weather_world();
+        world
+            .resource_mut::<Weather>()
+            .set_weather(WeatherKind::Rain, 0.5, 0.0);
+        world
+            .run_system_once(weather_system)
+            .expect("weather_system");
+
+        let mut emitters = world.query::<(&WeatherEmitter, &ParticleEmitter, &MapPosition)>();
+        let (marker, emitter, position) = emitters.single(&world).expect("one emitter");
+        assert_eq!(marker.0, WeatherKind::Rain);
+        assert_eq!(position.pos, Vector2::new(160.0, -SPAWN_MARGIN));
+        assert_eq!(emitter.arc_degrees, (177.0, 183.0));
+        let template = emitter.templates[0];
+        assert_eq!(
+            world.get::<WeatherParticle>(template),
+            Some(&WeatherParticle(WeatherKind::Rain))
+        );
+        assert!(world.get::<MapPosition>(template).is_none());
+
+        // Clearing stops the emitter without despawning it.
+        world
+            .resource_mut::<Weather>()
+            .set_weather(WeatherKind::Clear, 0.0, 0.0);
+        world
+            .run_system_once(weather_system)
+            .expect("weather_system");
+        let (_, emitter, _) = emitters.single(&world).expect("emitter kept");
+        assert_eq!(emitter.emissions_per_second, 0.0);
+    }
+
+    #[test]
+    fn wind_feeds_a_global_force_area_and_leans_the_emitter() {
+        let mut world = weather_world();
+        {
+            let mut weather = world.resource_mut::<Weather>();
+            weather.set_weather(WeatherKind::Snow, 1.0, 0.0);
+            weather.set_wind(Vector2::new(105.0, 0.0), 0.0);
+        }
+        world
+            .run_system_once(weather_system)
+            .expect("weather_system");
+        world
+            .run_system_once(weather_system)
+            .expect("weather_system");
+
+        let mut areas = world.query_filtered::<&ForceArea, With<Wind>>();
+        let area = areas.single(&world).expect("one wind area");
+        assert_eq!(area.acceleration, Vector2::new(105.0, 0.0));
+        assert!(area.enabled);
+
+        let mut emitters = world.query::<&ParticleEmitter>();
+        let emitter = emitters.single(&world).expect("one emitter");
+        let (min, max) = emitter.arc_degrees;
+        // Blowing right tilts the fall from straight down (180°) towards 90°.
+        assert!((min + max) * 0.5 < 180.0);
+
+        world
+            .resource_mut::<Weather>()
+            .set_wind(Vector2::zero(), 0.0);
+        world
+            .run_system_once(weather_system)
+            .expect("weather_system");
+        assert!(!areas.single(&world).expect("wind area kept").enabled);
+    }
+}
@@ -0,0 +1,34 @@
+//! Window focus tracking and background frame throttling.
+//!
+//! [`window_focus_system`] polls the window focus every frame. When it
+//! changes, the system triggers a [`WindowFocusEvent`] and switches the frame
+//! cap between `GameConfig::target_fps` and `GameConfig::background_fps` (see
+//! [`GameConfig::frame_cap`]).
+
+use bevy_ecs::prelude::*;
+use log::debug;
+
+use crate::events::windowfocus::WindowFocusEvent;
+use crate::resources::gameconfig::GameConfig;
+
+/// Trigger [`WindowFocusEvent`] and apply the matching frame cap when the
+/// window gains or loses focus.
+///
+/// The first frame only records the initial focus state; no event is fired.
+pub fn window_focus_system(
+    mut rl: NonSendMut<raylib::RaylibHandle>,
+    config: Option<Res<GameConfig>>,
+    mut commands: Commands,
+    mut was_focused: Local<Option<bool>>,
+) {
+    let focused = rl.is_window_focused();
+    let previous = was_focused.replace(focused);
+    if previous.is_none_or(|prev| prev == focused) {
+        return;
+    }
+    debug!("Window focus changed: focused={}", focused);
+    if let Some(config) = config {
+        rl.set_target_fps(config.frame_cap(focused));
+    }
+    commands.trigger(WindowFocusEvent { focused });
+}
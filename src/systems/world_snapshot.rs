@@ -0,0 +1,155 @@
+//! World snapshot commands.
+//!
+//! [`TakeSnapshot`] records the entities and resources of the world under a
+//! name and [`DiffSnapshot`] logs what changed since.
+//!
+//! - [`process_lua_debug_commands`] *(feature = "lua")* queues them for
+//!   `engine.snapshot(name)` and `engine.snapshot_diff(name)`.
+//!
+//! # Related
+//!
+//! - [`crate::resources::worldsnapshot`] – what a snapshot holds and the report format
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::resource::IS_RESOURCE;
+use log::{info, warn};
+
+use crate::components::group::Group;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::DebugCmd;
+use crate::resources::worldsnapshot::{EntitySummary, WorldSnapshot, WorldSnapshots};
+use crate::systems::ecs_stats::component_name;
+
+/// Record every entity and resource of `world`.
+pub fn capture_snapshot(world: &World) -> WorldSnapshot {
+    let mut snapshot = WorldSnapshot::default();
+    for archetype in world.archetypes().iter() {
+        if archetype.is_empty() {
+            continue;
+        }
+        let names: Vec<String> = archetype
+            .components()
+            .iter()
+            .filter(|&&id| id != IS_RESOURCE)
+            .filter_map(|&id| Some(component_name(world.components().get_info(id)?, id)))
+            .collect();
+        if archetype.contains(IS_RESOURCE) {
+            snapshot.resources.extend(names);
+            continue;
+        }
+        for entity in archetype.entities() {
+            let entity = entity.id();
+            let summary = EntitySummary {
+                group: world
+                    .get::<Group>(entity)
+                    .map(|group| group.name().to_string()),
+                components: names.iter().cloned().collect(),
+            };
+            snapshot.entities.insert(entity, summary);
+        }
+    }
+    snapshot
+}
+
+/// Command recording the world as snapshot `name`, replacing an older one.
+pub struct TakeSnapshot {
+    pub name: String,
+}
+
+impl Command for TakeSnapshot {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        world.init_resource::<WorldSnapshots>();
+        let snapshot = capture_snapshot(world);
+        info!(
+            "Snapshot '{}': {} entities, {} resources",
+            self.name,
+            snapshot.entities.len(),
+            snapshot.resources.len()
+        );
+        world
+            .resource_mut::<WorldSnapshots>()
+            .insert(self.name, snapshot);
+    }
+}
+
+/// Command logging what changed in the world since snapshot `name`.
+pub struct DiffSnapshot {
+    pub name: String,
+}
+
+impl Command for DiffSnapshot {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let now = capture_snapshot(world);
+        let Some(before) = world
+            .get_resource::<WorldSnapshots>()
+            .and_then(|snapshots| snapshots.get(&self.name))
+        else {
+            warn!("snapshot_diff: no snapshot named '{}'", self.name);
+            return;
+        };
+        let diff = before.diff(&now);
+        if diff.is_empty() {
+            info!("Snapshot '{}': no changes", self.name);
+        } else {
+            info!("Snapshot '{}': {}", self.name, diff.report());
+        }
+    }
+}
+
+/// Applies the debugging commands queued from Lua.
+///
+/// Run last by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands),
+/// so a snapshot sees the frame's other commands.
+#[cfg(feature = "lua")]
+pub fn process_lua_debug_commands(InMut(buf): InMut<Vec<DebugCmd>>, mut commands: Commands) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            DebugCmd::Snapshot { name } => commands.queue(TakeSnapshot { name }),
+            DebugCmd::SnapshotDiff { name } => commands.queue(DiffSnapshot { name }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[derive(Resource)]
+    struct Level;
+
+    #[test]
+    fn diff_sees_spawns_despawns_components_and_resources() {
+        let mut world = World::new();
+        let kept = world.spawn(Group::new("player")).id();
+        let gone = world.spawn(Marker).id();
+        TakeSnapshot {
+            name: "before".into(),
+        }
+        .apply(&mut world);
+        let before = world
+            .resource::<WorldSnapshots>()
+            .get("before")
+            .unwrap()
+            .clone();
+        assert_eq!(before.entities[&kept].group.as_deref(), Some("player"));
+
+        world.despawn(gone);
+        world.entity_mut(kept).insert(Marker);
+        let new = world.spawn_empty().id();
+        world.insert_resource(Level);
+
+        let diff = before.diff(&capture_snapshot(&world));
+        assert_eq!(diff.despawned[0].0, gone);
+        assert_eq!(diff.spawned[0].0, new);
+        assert_eq!(diff.changed[0].entity, kept);
+        assert_eq!(diff.changed[0].added.len(), 1);
+        assert_eq!(diff.resources_added.len(), 1);
+    }
+}
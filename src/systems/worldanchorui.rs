@@ -0,0 +1,83 @@
+//! World-anchored UI positioning.
+//!
+//! [`world_anchor_ui_system`] moves every entity with a
+//! [`WorldAnchorUI`] to the screen projection of its target entity. The
+//! anchored entity must already have a `ScreenPosition`: one without it is
+//! hidden (see `gui_layout_system`) and is left alone.
+//!
+//! # Related
+//!
+//! - [`crate::components::worldanchorui::WorldAnchorUI`] – offset and clamping
+
+use bevy_ecs::prelude::*;
+use raylib::prelude::Vector2;
+
+use crate::components::globaltransform2d::GlobalTransform2D;
+use crate::components::mapposition::MapPosition;
+use crate::components::screenposition::ScreenPosition;
+use crate::components::worldanchorui::WorldAnchorUI;
+use crate::resources::camera2d::Camera2DRes;
+use crate::resources::screensize::ScreenSize;
+
+/// Projects each anchor target through the camera into the anchored
+/// entity's `ScreenPosition`, and despawns anchored entities whose target is
+/// gone.
+///
+/// Scheduling: runs after `camera_follow_system` and
+/// `tween_system::<ScreenPosition>`, and before `gui_layout_system` so GUI
+/// children follow the anchored entity in the same frame.
+pub fn world_anchor_ui_system(
+    mut anchors: Query<(Entity, &WorldAnchorUI, &mut ScreenPosition)>,
+    targets: Query<(&MapPosition, Option<&GlobalTransform2D>)>,
+    camera: Res<Camera2DRes>,
+    screensize: Res<ScreenSize>,
+    mut commands: Commands,
+) {
+    let screen = Vector2::new(screensize.w as f32, screensize.h as f32);
+    for (entity, anchor, mut screen_pos) in anchors.iter_mut() {
+        let Ok((pos, maybe_gt)) = targets.get(anchor.target) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let world_pos = maybe_gt.map_or(pos.pos, |gt| gt.position);
+        let new_pos = anchor.screen_position(camera.world_to_screen(world_pos), screen);
+        // Only write on change so a still label doesn't dirty its region.
+        if screen_pos.pos() != new_pos {
+            screen_pos.set_pos(new_pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use raylib::prelude::Camera2D;
+
+    #[test]
+    fn anchored_ui_follows_its_target_and_goes_with_it() {
+        let mut world = World::new();
+        world.insert_resource(Camera2DRes(Camera2D {
+            target: Vector2::new(100.0, 100.0),
+            offset: Vector2::new(160.0, 90.0),
+            rotation: 0.0,
+            zoom: 2.0,
+        }));
+        world.insert_resource(ScreenSize { w: 320, h: 180 });
+        let target = world.spawn(MapPosition::new(110.0, 100.0)).id();
+        let label = world
+            .spawn((
+                ScreenPosition::new(0.0, 0.0),
+                WorldAnchorUI::new(target).with_offset(-8.0, -16.0),
+            ))
+            .id();
+
+        world.run_system_once(world_anchor_ui_system).unwrap();
+        let pos = world.get::<ScreenPosition>(label).unwrap().pos();
+        assert_eq!((pos.x, pos.y), (172.0, 74.0));
+
+        world.despawn(target);
+        world.run_system_once(world_anchor_ui_system).unwrap();
+        assert!(world.get_entity(label).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! World clock systems.
+//!
+//! - [`world_clock_system`] advances the [`WorldClock`] by the scaled frame
+//!   delta and triggers a [`WorldClockEvent`] for every scheduled time passed.
+//! - [`process_lua_clock_commands`] *(feature = "lua")* applies the
+//!   `engine.clock_*()`, `engine.at_time()` and `engine.cancel_at_time()`
+//!   commands.
+//!
+//! The day/night overlay is drawn by the render system.
+//!
+//! # Related
+//!
+//! - [`crate::resources::worldclock`] – time of day, schedule and ambient light
+//! - [`crate::events::worldclock`] – the scheduled event
+
+use bevy_ecs::prelude::*;
+#[cfg(feature = "lua")]
+use raylib::prelude::Color;
+
+use crate::events::worldclock::WorldClockEvent;
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::ClockCmd;
+use crate::resources::worldclock::WorldClock;
+use crate::resources::worldtime::WorldTime;
+
+/// Advances the world clock and triggers its scheduled events.
+///
+/// Uses the scaled [`WorldTime`] delta, so the clock stops with the world
+/// time scale as well as with [`WorldClock::paused`].
+pub fn world_clock_system(
+    time: Res<WorldTime>,
+    mut clock: ResMut<WorldClock>,
+    mut commands: Commands,
+) {
+    for fired in clock.advance(time.delta) {
+        commands.trigger(WorldClockEvent {
+            name: fired.name,
+            day: fired.day,
+            minute: fired.minute,
+        });
+    }
+}
+
+/// Applies clock commands queued from Lua.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_clock_commands(InMut(buf): InMut<Vec<ClockCmd>>, mut clock: ResMut<WorldClock>) {
+    for cmd in buf.drain(..) {
+        match cmd {
+            ClockCmd::SetTime(minutes) => clock.set_time(minutes),
+            ClockCmd::SetSpeed(speed) => clock.speed = speed.max(0.0),
+            ClockCmd::SetPaused(paused) => clock.paused = paused,
+            ClockCmd::SetAmbient {
+                sunrise,
+                sunset,
+                color: (r, g, b, a),
+            } => {
+                clock.sunrise = sunrise;
+                clock.sunset = sunset;
+                clock.night_color = Color::new(r, g, b, a);
+                clock.ambient = true;
+            }
+            ClockCmd::DisableAmbient => clock.ambient = false,
+            ClockCmd::At { minute, callback } => clock.schedule(minute, callback),
+            ClockCmd::Cancel { callback } => {
+                clock.cancel(&callback);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::observer::Observer;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Fired(Vec<(String, u32)>);
+
+    #[test]
+    fn scheduled_times_trigger_events() {
+        let mut world = World::new();
+        let mut clock = WorldClock::default();
+        clock.set_time(19.0 * 60.0 + 59.0);
+        clock.schedule(20.0 * 60.0, "spawn_night_enemies");
+        world.insert_resource(clock);
+        world.insert_resource(WorldTime {
+            delta: 0.75,
+            real_delta: 0.75,
+            ..WorldTime::default()
+        });
+        world.init_resource::<Fired>();
+        world.spawn(Observer::new(
+            |trigger: On<WorldClockEvent>, mut fired: ResMut<Fired>| {
+                let event = trigger.event();
+                fired.0.push((event.name.to_string(), event.day));
+            },
+        ));
+
+        world.run_system_once(world_clock_system).unwrap();
+        assert!(world.resource::<Fired>().0.is_empty());
+        world.run_system_once(world_clock_system).unwrap();
+        assert_eq!(
+            world.resource::<Fired>().0,
+            [("spawn_night_enemies".to_string(), 0)]
+        );
+        assert_eq!(world.resource::<WorldClock>().hour_minute(), (20, 0));
+    }
+}
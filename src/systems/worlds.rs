@@ -0,0 +1,219 @@
+//! Secondary world systems.
+//!
+//! [`update_sub_worlds_system`] steps every [`SubWorld`](crate::resources::worlds::SubWorld)
+//! with the main world's frame delta. With the `lua` feature,
+//! [`process_lua_world_commands`] applies the `engine.world_*` commands queued
+//! by scripts, including entities built with `engine.world_spawn(name)`.
+
+use bevy_ecs::prelude::*;
+
+use crate::resources::worlds::Worlds;
+use crate::resources::worldtime::WorldTime;
+
+#[cfg(feature = "lua")]
+use crate::resources::lua_runtime::WorldCmd;
+#[cfg(feature = "lua")]
+use crate::resources::worlds::SubWorld;
+#[cfg(feature = "lua")]
+use crate::systems::lua_commands::process_spawn_command;
+#[cfg(feature = "lua")]
+use raylib::prelude::Color;
+
+/// Step every sub-world by this frame's (already time-scaled) delta.
+pub fn update_sub_worlds_system(mut worlds: ResMut<Worlds>, time: Res<WorldTime>) {
+    crate::tracy::tracy_span!("update_sub_worlds");
+    for (_, sub) in worlds.iter_mut() {
+        sub.step(time.delta);
+    }
+}
+
+/// Applies `engine.world_*` commands queued by Lua to [`Worlds`]. Commands
+/// naming an unknown world are logged and dropped.
+///
+/// Run by [`apply_lua_commands`](crate::systems::lua_commands::apply_lua_commands)
+/// with the commands Lua queued this frame.
+#[cfg(feature = "lua")]
+pub fn process_lua_world_commands(InMut(buf): InMut<Vec<WorldCmd>>, mut worlds: ResMut<Worlds>) {
+    for cmd in buf.drain(..) {
+        apply_world_command(&mut worlds, cmd);
+    }
+}
+
+#[cfg(feature = "lua")]
+fn apply_world_command(worlds: &mut Worlds, cmd: WorldCmd) {
+    match cmd {
+        WorldCmd::Create {
+            name,
+            width,
+            height,
+        } => {
+            if width == 0 || height == 0 {
+                log::error!(
+                    "engine.world_create: '{name}' needs a non-zero size, got {width}x{height}"
+                );
+                return;
+            }
+            worlds.create(name, width, height);
+        }
+        WorldCmd::Destroy { name } => {
+            if worlds.remove(&name).is_none() {
+                log::warn!("engine.world_destroy: no world named '{name}'");
+            }
+        }
+        WorldCmd::SetPaused { name, paused } => {
+            if let Some(sub) = lookup(worlds, &name) {
+                sub.paused = paused;
+            }
+        }
+        WorldCmd::SetCamera { name, x, y, zoom } => {
+            if let Some(sub) = lookup(worlds, &name) {
+                sub.set_camera(x, y, zoom);
+            }
+        }
+        WorldCmd::SetBackground { name, r, g, b } => {
+            if let Some(sub) = lookup(worlds, &name) {
+                sub.background = Color::new(r, g, b, 255);
+            }
+        }
+        WorldCmd::Spawn { world, cmd } => {
+            if let Some(sub) = lookup(worlds, &world) {
+                sub.with_commands(|commands, signals| {
+                    process_spawn_command(commands, *cmd, signals)
+                });
+            }
+        }
+    }
+}
+
+#[cfg(feature = "lua")]
+fn lookup<'a>(worlds: &'a mut Worlds, name: &str) -> Option<&'a mut SubWorld> {
+    let sub = worlds.get_mut(name);
+    if sub.is_none() {
+        log::warn!("engine.world_*: no world named '{name}'");
+    }
+    sub
+}
+
+#[cfg(all(test, feature = "lua"))]
+mod tests {
+    use super::*;
+    use crate::components::mapposition::MapPosition;
+    use crate::resources::lua_runtime::{LuaRuntime, SpawnCmd};
+    use crate::resources::worldsignals::WorldSignals;
+
+    fn spawn_at(world: &str, x: f32, y: f32) -> WorldCmd {
+        WorldCmd::Spawn {
+            world: world.into(),
+            cmd: Box::new(SpawnCmd {
+                position: Some((x, y)),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn spawn_goes_into_the_named_world() {
+        let mut worlds = Worlds::default();
+        apply_world_command(
+            &mut worlds,
+            WorldCmd::Create {
+                name: "menu_bg".into(),
+                width: 320,
+                height: 180,
+            },
+        );
+        apply_world_command(&mut worlds, spawn_at("menu_bg", 5.0, 6.0));
+        apply_world_command(&mut worlds, spawn_at("missing", 0.0, 0.0));
+
+        let sub = worlds.get_mut("menu_bg").unwrap();
+        let positions: Vec<_> = sub
+            .world
+            .query::<&MapPosition>()
+            .iter(&sub.world)
+            .map(|p| (p.pos.x, p.pos.y))
+            .collect();
+        assert_eq!(positions, vec![(5.0, 6.0)]);
+    }
+
+    #[test]
+    fn settings_and_destroy_apply_to_existing_worlds() {
+        let mut worlds = Worlds::default();
+        worlds.create("sandbox", 64, 64);
+        apply_world_command(
+            &mut worlds,
+            WorldCmd::SetPaused {
+                name: "sandbox".into(),
+                paused: true,
+            },
+        );
+        apply_world_command(
+            &mut worlds,
+            WorldCmd::SetCamera {
+                name: "sandbox".into(),
+                x: 10.0,
+                y: 20.0,
+                zoom: 2.0,
+            },
+        );
+        let sub = worlds.get("sandbox").unwrap();
+        assert!(sub.paused);
+        assert_eq!(
+            (sub.camera.target.x, sub.camera.target.y, sub.camera.zoom),
+            (10.0, 20.0, 2.0)
+        );
+
+        apply_world_command(
+            &mut worlds,
+            WorldCmd::Destroy {
+                name: "sandbox".into(),
+            },
+        );
+        assert!(worlds.is_empty());
+    }
+
+    #[test]
+    fn zero_sized_world_is_rejected() {
+        let mut worlds = Worlds::default();
+        apply_world_command(
+            &mut worlds,
+            WorldCmd::Create {
+                name: "empty".into(),
+                width: 0,
+                height: 32,
+            },
+        );
+        assert!(!worlds.contains("empty"));
+    }
+
+    #[test]
+    fn lua_world_spawn_reaches_the_sub_world() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        let runtime = LuaRuntime::new().unwrap();
+        runtime
+            .lua()
+            .load(
+                "engine.world_create('diorama', 64, 32)\n\
+                 engine.world_spawn('diorama'):with_position(3, 4):register_as('hero'):build()",
+            )
+            .exec()
+            .expect("queue world commands");
+
+        let mut world = World::new();
+        world.insert_non_send(runtime);
+        world.insert_resource(Worlds::default());
+        world
+            .run_system_once(crate::systems::lua_commands::apply_lua_commands)
+            .unwrap();
+
+        let mut worlds = world.resource_mut::<Worlds>();
+        let sub = worlds.get_mut("diorama").unwrap();
+        let hero = *sub
+            .world
+            .resource::<WorldSignals>()
+            .get_entity("hero")
+            .unwrap();
+        let pos = sub.world.get::<MapPosition>(hero).unwrap().pos;
+        assert_eq!((pos.x, pos.y), (3.0, 4.0));
+    }
+}
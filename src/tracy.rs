@@ -20,5 +20,17 @@ macro_rules! tracy_frame_mark {
     };
 }
 
+/// Record a value on a named Tracy plot.
+///
+/// Usage: `tracy_plot!("frame_time_ms", ms);` — the value is converted to `f64`.
+/// Expands to nothing when `feature = "tracy"` is not active.
+macro_rules! tracy_plot {
+    ($name:literal, $value:expr) => {
+        #[cfg(feature = "tracy")]
+        ::tracy_client::plot!($name, f64::from($value));
+    };
+}
+
 pub(crate) use tracy_frame_mark;
+pub(crate) use tracy_plot;
 pub(crate) use tracy_span;
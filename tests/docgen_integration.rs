@@ -0,0 +1,85 @@
+#![cfg(feature = "lua")]
+
+use aberredengine::docgen::{self, DocFormat};
+use aberredengine::resources::lua_runtime::LuaRuntime;
+
+#[test]
+fn markdown_reference_covers_registered_api() {
+    let rt = LuaRuntime::new().unwrap();
+    let content = docgen::generate_docs(&rt, DocFormat::Markdown).unwrap();
+
+    assert!(
+        content.starts_with("# Aberred Engine Lua API Reference"),
+        "Should start with the page title"
+    );
+
+    // Functions, builder methods, callbacks, types and enums
+    assert!(
+        content.contains("### `engine.play_sound(id)`"),
+        "Missing engine.play_sound()"
+    );
+    assert!(
+        content.contains("### `engine.set_window_mode(mode, monitor)`"),
+        "Missing engine.set_window_mode()"
+    );
+    assert!(
+        content.contains("| `monitor` | `integer\\|nil` |"),
+        "Optional params should be escaped in tables"
+    );
+    assert!(
+        content.contains("### `EntityBuilder:build()`"),
+        "Missing EntityBuilder:build()"
+    );
+    assert!(content.contains("### `on_setup()`"), "Missing on_setup");
+    assert!(content.contains("### `Vector2`"), "Missing Vector2 type");
+    assert!(
+        content.contains("`\"ping_pong\"`"),
+        "Missing LoopMode values"
+    );
+
+    // Table of contents links to category sections
+    assert!(content.contains("- [Rendering & Shaders](#rendering--shaders)"));
+    assert!(content.contains("## Rendering & Shaders"));
+}
+
+#[test]
+fn every_engine_function_is_documented() {
+    let rt = LuaRuntime::new().unwrap();
+    let content = docgen::generate_docs(&rt, DocFormat::Markdown).unwrap();
+
+    let names: Vec<String> = rt
+        .lua()
+        .load(
+            r#"
+            local names = {}
+            for name, _ in pairs(engine.__meta.functions) do
+                table.insert(names, name)
+            end
+            return names
+            "#,
+        )
+        .eval()
+        .unwrap();
+    assert!(!names.is_empty());
+    for name in names {
+        assert!(
+            content.contains(&format!("### `engine.{name}(")),
+            "engine.{name} missing from the reference"
+        );
+    }
+}
+
+#[test]
+fn html_reference_is_escaped_and_anchored() {
+    let rt = LuaRuntime::new().unwrap();
+    let content = docgen::generate_docs(&rt, DocFormat::Html).unwrap();
+
+    assert!(content.starts_with("<!DOCTYPE html>"));
+    assert!(content.trim_end().ends_with("</html>"));
+    assert!(content.contains("<h2 id=\"rendering--shaders\">Rendering &amp; Shaders</h2>"));
+    assert!(content.contains("<code>engine.play_sound(id)</code>"));
+    assert!(
+        !content.contains("on_update_<scene>"),
+        "Callback names must be HTML-escaped"
+    );
+}
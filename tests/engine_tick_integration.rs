@@ -4,11 +4,12 @@
 
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemState;
-use raylib::prelude::Vector2;
+use raylib::prelude::{Camera2D, Vector2};
 
 use aberredengine::components::animation::{Animation, AnimationController, Condition};
-use aberredengine::components::boxcollider::BoxCollider;
+use aberredengine::components::boxcollider::{BoxCollider, ColliderDisabled};
 use aberredengine::components::collision::{BoxSides, CollisionCallback, CollisionRule};
+use aberredengine::components::forcearea::ForceArea;
 use aberredengine::components::group::Group;
 #[cfg(feature = "lua")]
 use aberredengine::components::luacollision::{LuaCollisionCallback, LuaCollisionRule};
@@ -17,15 +18,21 @@ use aberredengine::components::luaphase::{LuaPhase, PhaseCallbacks};
 #[cfg(feature = "lua")]
 use aberredengine::components::luatimer::{LuaTimer, LuaTimerCallback};
 use aberredengine::components::mapposition::MapPosition;
+use aberredengine::components::nineslice::NineSlice;
+use aberredengine::components::progress::ProgressBar;
 use aberredengine::components::rigidbody::RigidBody;
 use aberredengine::components::rotation::Rotation;
 use aberredengine::components::scale::Scale;
+use aberredengine::components::screenposition::ScreenPosition;
+use aberredengine::components::sensor::{Sensor, SensorRay};
 use aberredengine::components::signals::Signals;
 use aberredengine::components::sprite::Sprite;
 use aberredengine::components::stuckto::StuckTo;
+use aberredengine::components::tags::Tags;
 use aberredengine::components::timer::{Timer, TimerCallback};
 use aberredengine::components::ttl::Ttl;
 use aberredengine::components::tween::{Easing, LoopMode, Tween};
+use aberredengine::components::zindex::ZIndex;
 use aberredengine::events::audio::AudioCmd;
 use aberredengine::events::collision::CollisionEvent;
 #[cfg(feature = "lua")]
@@ -33,21 +40,31 @@ use aberredengine::events::luatimer::LuaTimerEvent;
 use aberredengine::events::timer::TimerEvent;
 use aberredengine::resources::animationstore::{AnimationResource, AnimationStore};
 use aberredengine::resources::appstate::AppState;
+use aberredengine::resources::camera2d::Camera2DRes;
 use aberredengine::resources::camerafollowconfig::CameraFollowConfig;
+use aberredengine::resources::cinematic::CinematicMode;
+use aberredengine::resources::fogofwar::FogOfWar;
 use aberredengine::resources::gameconfig::GameConfig;
 use aberredengine::resources::group::TrackedGroups;
 use aberredengine::resources::input::InputState;
 use aberredengine::resources::input_bindings::InputBindings;
 #[cfg(feature = "lua")]
 use aberredengine::resources::lua_runtime::LuaRuntime;
+use aberredengine::resources::framearena::FrameArena;
+use aberredengine::resources::movementconfig::MovementConfig;
+use aberredengine::resources::portaltransition::PortalTransition;
 use aberredengine::resources::postprocessshader::PostProcessShader;
+use aberredengine::resources::rendermode::{DirtyRegion, RenderMode};
 use aberredengine::resources::screensize::ScreenSize;
 use aberredengine::resources::systemsstore::SystemsStore;
 use aberredengine::resources::texturestore::TextureStore;
+use aberredengine::resources::worldclock::WorldClock;
 use aberredengine::resources::worldsignals::WorldSignals;
 use aberredengine::resources::worldtime::WorldTime;
 use aberredengine::systems::animation::{animation, animation_controller};
 use aberredengine::systems::collision_detector::collision_detector;
+use aberredengine::systems::dirtyrects::track_dirty_regions;
+use aberredengine::systems::forcearea::force_area_system;
 use aberredengine::systems::group::update_group_counts_system;
 #[cfg(feature = "lua")]
 use aberredengine::systems::lua_collision::lua_collision_observer;
@@ -57,6 +74,7 @@ use aberredengine::systems::luaphase::lua_phase_system;
 use aberredengine::systems::luatimer::{lua_timer_observer, update_lua_timers};
 use aberredengine::systems::movement::movement;
 use aberredengine::systems::rust_collision::rust_collision_observer;
+use aberredengine::systems::sensor::sensor_system;
 use aberredengine::systems::stuckto::stuck_to_entity_system;
 use aberredengine::systems::time::update_world_time;
 use aberredengine::systems::timer::{timer_observer, update_timers};
@@ -74,6 +92,7 @@ fn make_world(delta: f32) -> World {
     world.insert_resource(WorldTime {
         elapsed: 0.0,
         delta,
+        real_delta: delta,
         time_scale: 1.0,
         frame_count: 0,
     });
@@ -104,6 +123,7 @@ fn tick_ttl(world: &mut World) {
 }
 
 fn tick_collision_detector(world: &mut World) {
+    world.init_resource::<FrameArena>();
     let mut schedule = Schedule::default();
     schedule.add_systems(collision_detector);
     schedule.run(world);
@@ -185,6 +205,101 @@ fn movement_skips_frozen_but_clears_signals() {
     assert!(approx_eq(signals.get_scalar("speed_sq").unwrap(), 0.0));
 }
 
+fn ball_hits_wall(_: Entity, _: Entity, _: &BoxSides, _: &BoxSides, _: &mut GameCtx) {}
+
+/// A fast "ball" heading right at a 4 px wall of `wall_group`, with a
+/// ball/wall collision rule.
+fn spawn_fast_ball_and(world: &mut World, wall_group: &str) -> Entity {
+    let mut rb = RigidBody::new();
+    rb.velocity = Vector2 { x: 2000.0, y: 0.0 };
+    world.spawn(CollisionRule::rust("ball", "wall", ball_hits_wall));
+    world.spawn((
+        Group::new(wall_group),
+        MapPosition::new(16.0, 0.0),
+        BoxCollider::new(4.0, 8.0),
+    ));
+    world
+        .spawn((
+            Group::new("ball"),
+            MapPosition::new(0.0, 0.0),
+            rb,
+            BoxCollider::new(8.0, 8.0),
+        ))
+        .id()
+}
+
+fn spawn_fast_ball_and_wall(world: &mut World) -> Entity {
+    spawn_fast_ball_and(world, "wall")
+}
+
+#[test]
+fn movement_substeps_fast_collider_into_thin_wall() {
+    let mut world = make_world(0.0);
+    let ball = spawn_fast_ball_and_wall(&mut world);
+
+    update_world_time(&mut world, 1.0 / 60.0);
+    tick_movement(&mut world);
+
+    // ~33 px in one frame would skip the 4 px wall at x=16..20.
+    let pos = world.get::<MapPosition>(ball).unwrap();
+    assert!(pos.pos.x > 8.0 && pos.pos.x < 20.0, "x = {}", pos.pos.x);
+}
+
+#[test]
+fn movement_without_substeps_tunnels_through_thin_wall() {
+    let mut world = make_world(0.0);
+    world.insert_resource(MovementConfig {
+        max_step: 0.0,
+        ..Default::default()
+    });
+    let ball = spawn_fast_ball_and_wall(&mut world);
+
+    update_world_time(&mut world, 1.0 / 60.0);
+    tick_movement(&mut world);
+
+    let pos = world.get::<MapPosition>(ball).unwrap();
+    assert!(pos.pos.x > 20.0);
+}
+
+#[test]
+fn movement_substeps_pass_colliders_without_a_rule() {
+    let mut world = make_world(0.0);
+    let ball = spawn_fast_ball_and(&mut world, "trigger");
+
+    update_world_time(&mut world, 1.0 / 60.0);
+    tick_movement(&mut world);
+
+    let pos = world.get::<MapPosition>(ball).unwrap();
+    assert!((pos.pos.x - 2000.0 / 60.0).abs() < 1e-3);
+}
+
+#[test]
+fn movement_substeps_ignore_colliders_already_overlapping() {
+    let mut world = make_world(0.0);
+    let mut rb = RigidBody::new();
+    rb.velocity = Vector2 { x: 2000.0, y: 0.0 };
+    world.spawn(CollisionRule::rust("ball", "wall", ball_hits_wall));
+    world.spawn((
+        Group::new("wall"),
+        MapPosition::new(0.0, 4.0),
+        BoxCollider::new(200.0, 8.0),
+    ));
+    let ball = world
+        .spawn((
+            Group::new("ball"),
+            MapPosition::new(0.0, 0.0),
+            rb,
+            BoxCollider::new(8.0, 8.0),
+        ))
+        .id();
+
+    update_world_time(&mut world, 1.0 / 60.0);
+    tick_movement(&mut world);
+
+    let pos = world.get::<MapPosition>(ball).unwrap();
+    assert!((pos.pos.x - 2000.0 / 60.0).abs() < 1e-3);
+}
+
 #[test]
 fn ttl_decrements_and_despawns() {
     let mut world = make_world(0.5);
@@ -547,6 +662,28 @@ fn group_counts_ignores_untracked_groups() {
     assert_eq!(signals.get_group_count("bullet"), None); // Not tracked
 }
 
+#[test]
+fn group_counts_include_tagged_entities() {
+    let mut world = make_world(0.0);
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(AppState::default());
+
+    let mut tracked = TrackedGroups::default();
+    tracked.add_group("enemy");
+    tracked.add_group("flying");
+    world.insert_resource(tracked);
+
+    world.spawn((Group::new("enemy"), Tags::new(["flying"])));
+    world.spawn((Group::new("enemy"), Tags::new(["enemy"]))); // counted once
+    world.spawn((Tags::new(["flying"]),));
+
+    tick_group_counts(&mut world);
+
+    let signals = world.resource::<WorldSignals>();
+    assert_eq!(signals.get_group_count("enemy"), Some(2));
+    assert_eq!(signals.get_group_count("flying"), Some(2));
+}
+
 // =============================================================================
 // Animation Controller System Tests
 // =============================================================================
@@ -1664,7 +1801,8 @@ fn meta_enums_table_is_populated() {
         -- Hard-code expected Category values
         local expected_cats = {"base", "asset", "spawn", "audio", "signal", "phase",
                                "entity", "group", "camera", "collision",
-                               "animation", "render"}
+                               "animation", "render", "world", "clock", "weather",
+                               "inventory", "ui", "save", "debug"}
         assert(#enums.Category.values == #expected_cats,
             "Category value count mismatch: expected " .. #expected_cats ..
             " got " .. #enums.Category.values)
@@ -1726,8 +1864,10 @@ fn meta_functions_complete() {
         local expected = {
             -- base
             "log", "log_info", "log_warn", "log_error",
+            "command_order",
             -- asset
-            "load_texture", "load_font", "load_music", "load_sound",
+            "load_texture", "load_font", "load_music", "load_sound", "dump_asset_usage",
+            "tilemap_set_autotile",
             -- spawn
             "spawn", "clone",
             -- audio
@@ -1746,14 +1886,17 @@ fn meta_functions_complete() {
             -- group
             "track_group", "untrack_group", "clear_tracked_groups", "has_tracked_group",
             -- camera
-            "set_camera",
+            "set_camera", "screen_to_tile", "tile_to_world",
             -- render
             "load_shader", "post_process_shader",
             "post_process_set_float", "post_process_set_int",
             "post_process_set_vec2", "post_process_set_vec4",
             "post_process_clear_uniform", "post_process_clear_uniforms",
+            "fog_enable", "fog_disable", "fog_reset", "is_revealed",
             -- animation
             "register_animation",
+            -- spawn
+            "spawn", "clone", "get_spawn_point",
             -- collision context
             "collision_spawn", "collision_clone",
             "collision_play_sound",
@@ -1761,6 +1904,24 @@ fn meta_functions_complete() {
             "collision_set_flag", "collision_clear_flag", "collision_toggle_flag",
             "collision_clear_scalar", "collision_clear_integer", "collision_clear_string",
             "collision_phase_transition", "collision_set_camera",
+            "set_occluders", "can_see",
+            -- world clock
+            "clock_set_time", "clock_set_speed", "clock_pause", "clock_resume",
+            "clock_set_ambient", "clock_disable_ambient",
+            "at_time", "cancel_at_time", "get_clock",
+            -- weather
+            "set_weather", "set_wind", "get_weather",
+            -- inventory
+            "register_item", "get_item", "inventory_add", "inventory_remove",
+            "inventory_swap", "inventory_clear", "inventory_count", "inventory_slots",
+            "on_inventory_changed", "on_inventory_full",
+            -- ui
+            "show_results", "show_credits", "submit_score", "get_high_scores", "set_attract_mode",
+            "cinematic_mode",
+            -- save
+            "save_game", "load_game", "scene_state_forget", "scene_state_clear",
+            -- debug
+            "ecs_stats", "snapshot", "snapshot_diff",
         }
 
         local missing = {}
@@ -1795,6 +1956,9 @@ fn meta_functions_complete() {
             "entity_shader_set_float", "entity_shader_set_int",
             "entity_shader_set_vec2", "entity_shader_set_vec4",
             "entity_shader_clear_uniform", "entity_shader_clear_uniforms",
+            "entity_blackboard_set", "entity_blackboard_set_entity",
+            "entity_blackboard_remove", "entity_blackboard_clear",
+            "tilemap_set_layer_visible", "tilemap_set_tile",
         }
 
         -- Check regular entity commands exist
@@ -2032,6 +2196,7 @@ fn context_builder_passes_snapshot_strings_to_lua() {
         sprite: Some(sprite_snap),
         animation: Some(anim_snap),
         signals: None,
+        blackboard: None,
         lua_phase: Some(phase_snap),
         lua_timer: Some(timer_snap),
         previous_phase: None,
@@ -2076,6 +2241,7 @@ fn context_builder_nil_when_no_snapshots() {
         sprite: None,
         animation: None,
         signals: None,
+        blackboard: None,
         lua_phase: None,
         lua_timer: None,
         previous_phase: None,
@@ -2094,12 +2260,69 @@ fn context_builder_nil_when_no_snapshots() {
         assert(ctx.animation == nil, "animation should be nil")
         assert(ctx.phase     == nil, "phase should be nil")
         assert(ctx.timer     == nil, "timer should be nil")
+        assert(ctx.blackboard == nil, "blackboard should be nil")
     "#,
     )
     .call::<()>(ctx)
     .expect("Lua nil assertions");
 }
 
+#[cfg(feature = "lua")]
+#[test]
+fn context_builder_exposes_blackboard_values() {
+    use aberredengine::components::blackboard::Blackboard;
+    use aberredengine::resources::lua_runtime::{EntitySnapshot, build_entity_context_pooled};
+
+    let runtime = LuaRuntime::new().expect("LuaRuntime init");
+    let tables = runtime.get_entity_ctx_pool();
+    let lua = runtime.lua();
+
+    let target = Entity::from_raw_u32(7).unwrap();
+    let mut board = Blackboard::default();
+    board.set("alert", true);
+    board.set("state", "chase");
+    board.set("last_seen_player", Vector2 { x: 12.0, y: -4.0 });
+    board.set_with_ttl("target", target, 2.0);
+
+    let snapshot = EntitySnapshot {
+        entity_id: 1_u64,
+        group: None,
+        map_pos: None,
+        screen_pos: None,
+        rigid_body: None,
+        rotation: None,
+        scale: None,
+        rect: None,
+        sprite: None,
+        animation: None,
+        signals: None,
+        blackboard: Some(&board),
+        lua_phase: None,
+        lua_timer: None,
+        previous_phase: None,
+        world_pos: None,
+        world_rotation: None,
+        world_scale: None,
+        parent_id: None,
+    };
+    let ctx =
+        build_entity_context_pooled(lua, &tables, &snapshot).expect("build_entity_context_pooled");
+
+    lua.load(
+        r#"
+        local ctx, target = ...
+        local bb = ctx.blackboard
+        assert(bb ~= nil, "blackboard is nil")
+        assert(bb.alert == true, "wrong alert")
+        assert(bb.state == "chase", "wrong state: " .. tostring(bb.state))
+        assert(bb.last_seen_player.x == 12 and bb.last_seen_player.y == -4, "wrong vec2")
+        assert(bb.target == target, "wrong target id")
+    "#,
+    )
+    .call::<()>((ctx, target.to_bits()))
+    .expect("Lua blackboard assertions");
+}
+
 // =============================================================================
 // Rust Phase System Tests
 // =============================================================================
@@ -2765,6 +2988,260 @@ fn collision_rule_callback_not_fired_on_non_matching_groups() {
     assert!(!signals.has_flag("should_not_fire"));
 }
 
+#[test]
+fn collision_event_carries_depth_normal_and_groups() {
+    let mut world = make_world(0.0);
+    let player = world
+        .spawn((
+            Group::new("player"),
+            MapPosition::new(0.0, 0.0),
+            BoxCollider::new(10.0, 10.0),
+        ))
+        .id();
+    world.spawn((MapPosition::new(7.0, 2.0), BoxCollider::new(10.0, 10.0)));
+
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(None::<CollisionEvent>));
+    let seen_clone = seen.clone();
+    world.add_observer(move |trigger: On<CollisionEvent>| {
+        *seen_clone.lock().unwrap() = Some(trigger.event().clone());
+    });
+    world.flush();
+
+    tick_collision_detector(&mut world);
+
+    let event = seen.lock().unwrap().take().expect("collision event");
+    let (sides_player, _, normal) = event.oriented(player);
+    assert_eq!(event.depth, 3.0);
+    assert_eq!(normal, Vector2 { x: 1.0, y: 0.0 });
+    assert!(!sides_player.is_empty());
+    assert_eq!(event.group_of(player), Some("player"));
+    let other = if event.a == player { event.b } else { event.a };
+    assert_eq!(event.group_of(other), None);
+}
+
+#[test]
+fn disabled_collider_is_ignored_by_detector() {
+    let mut world = make_world(0.0);
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(AppState::default());
+    world.insert_resource(InputState::default());
+
+    fn on_collision(
+        ent_a: Entity,
+        _ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_a) {
+            signals.set_flag("hit");
+        }
+    }
+
+    let player = world
+        .spawn((
+            Group::new("player"),
+            MapPosition::new(0.0, 0.0),
+            BoxCollider::new(10.0, 10.0),
+            Signals::default(),
+            ColliderDisabled,
+        ))
+        .id();
+    world.spawn((
+        Group::new("enemy"),
+        MapPosition::new(5.0, 0.0),
+        BoxCollider::new(10.0, 10.0),
+    ));
+    world.spawn(CollisionRule::rust("player", "enemy", on_collision));
+
+    world.add_observer(rust_collision_observer);
+    world.flush();
+
+    tick_collision_detector(&mut world);
+    assert!(!world.get::<Signals>(player).unwrap().has_flag("hit"));
+
+    world.entity_mut(player).remove::<ColliderDisabled>();
+    tick_collision_detector(&mut world);
+    assert!(world.get::<Signals>(player).unwrap().has_flag("hit"));
+}
+
+#[test]
+fn collision_rule_matches_any_tag() {
+    let mut world = make_world(0.0);
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(AppState::default());
+    world.insert_resource(InputState::default());
+
+    fn on_collision(
+        _ent_a: Entity,
+        ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_b) {
+            signals.set_flag("hit_flyer");
+        }
+    }
+
+    world.spawn((
+        Group::new("bullet"),
+        MapPosition::new(0.0, 0.0),
+        BoxCollider::new(10.0, 10.0),
+    ));
+    let flyer = world
+        .spawn((
+            Group::new("enemy"),
+            Tags::new(["flying"]),
+            MapPosition::new(5.0, 0.0),
+            BoxCollider::new(10.0, 10.0),
+            Signals::default(),
+        ))
+        .id();
+    world.spawn((CollisionRule::rust("bullet", "flying", on_collision),));
+
+    world.add_observer(rust_collision_observer);
+    world.flush();
+
+    tick_collision_detector(&mut world);
+
+    let signals = world.get::<Signals>(flyer).unwrap();
+    assert!(signals.has_flag("hit_flyer"));
+}
+
+#[test]
+fn collision_rule_runs_for_every_pair_of_the_batch() {
+    let mut world = make_world(0.0);
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(AppState::default());
+    world.insert_resource(InputState::default());
+
+    fn on_collision(
+        ent_a: Entity,
+        ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_a) {
+            let hits = signals.get_scalar("hits").unwrap_or(0.0);
+            signals.set_scalar("hits", hits + 1.0);
+        }
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_b) {
+            signals.set_flag("hit");
+        }
+    }
+
+    let player = world
+        .spawn((
+            Group::new("player"),
+            MapPosition::new(0.0, 0.0),
+            BoxCollider::new(10.0, 10.0),
+            Signals::default(),
+        ))
+        .id();
+    let enemies = [-5.0, 5.0].map(|x| {
+        world
+            .spawn((
+                Group::new("enemy"),
+                MapPosition::new(x, 0.0),
+                BoxCollider::new(8.0, 10.0),
+                Signals::default(),
+            ))
+            .id()
+    });
+    world.spawn(CollisionRule::rust("player", "enemy", on_collision));
+
+    world.add_observer(rust_collision_observer);
+    world.flush();
+
+    tick_collision_detector(&mut world);
+
+    let signals = world.get::<Signals>(player).unwrap();
+    assert!(approx_eq(signals.get_scalar("hits").unwrap(), 2.0));
+    for enemy in enemies {
+        assert!(world.get::<Signals>(enemy).unwrap().has_flag("hit"));
+    }
+}
+
+#[test]
+fn collision_rules_match_wildcards_in_priority_order() {
+    let mut world = make_world(0.0);
+    world.insert_resource(WorldSignals::default());
+    world.insert_resource(AppState::default());
+    world.insert_resource(InputState::default());
+
+    fn on_any(
+        _ent_a: Entity,
+        ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_b) {
+            signals.set_flag("any");
+        }
+    }
+
+    fn on_enemy(
+        _ent_a: Entity,
+        ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_b) {
+            // Runs after the non-exclusive higher-priority rule.
+            let order = if signals.has_flag("any") { 2 } else { 1 };
+            signals.set_integer("enemy_order", order);
+        }
+    }
+
+    fn on_fallback(
+        _ent_a: Entity,
+        ent_b: Entity,
+        _sides_a: &BoxSides,
+        _sides_b: &BoxSides,
+        ctx: &mut GameCtx,
+    ) {
+        if let Ok(mut signals) = ctx.signals.get_mut(ent_b) {
+            signals.set_flag("fallback");
+        }
+    }
+
+    world.spawn((
+        Group::new("player_projectile"),
+        MapPosition::new(0.0, 0.0),
+        BoxCollider::new(10.0, 10.0),
+    ));
+    let enemy = world
+        .spawn((
+            Group::new("enemy_bat"),
+            MapPosition::new(5.0, 0.0),
+            BoxCollider::new(10.0, 10.0),
+            Signals::default(),
+        ))
+        .id();
+    world.spawn(CollisionRule::rust("player_projectile", "enemy_*", on_enemy));
+    world.spawn(CollisionRule::rust("*", "*", on_fallback).with_priority(-5));
+    world.spawn(
+        CollisionRule::rust("player_projectile", "*", on_any)
+            .with_priority(10)
+            .with_exclusive(false),
+    );
+
+    world.add_observer(rust_collision_observer);
+    world.flush();
+
+    tick_collision_detector(&mut world);
+
+    let signals = world.get::<Signals>(enemy).unwrap();
+    assert!(signals.has_flag("any"));
+    assert_eq!(signals.get_integer("enemy_order"), Some(2));
+    // The exclusive "enemy_*" rule stops the lower-priority fallback.
+    assert!(!signals.has_flag("fallback"));
+}
+
 #[test]
 fn collision_rule_entities_ordered_correctly_when_groups_swapped() {
     let mut world = make_world(0.0);
@@ -2961,7 +3438,7 @@ fn make_animation_resource(
     looped: bool,
 ) -> AnimationResource {
     AnimationResource {
-        tex_key: Arc::from(tex_key),
+        tex_key: tex_key.into(),
         position: Vector2 {
             x: position.0,
             y: position.1,
@@ -3002,7 +3479,7 @@ fn drain_textures(world: &mut World) {
 
 fn make_sprite(tex_key: &str) -> Sprite {
     Sprite {
-        tex_key: Arc::from(tex_key),
+        tex_key: tex_key.into(),
         width: 64.0,
         height: 64.0,
         offset: Vector2 { x: 0.0, y: 0.0 },
@@ -3644,3 +4121,257 @@ fn collision_callback_phase_plus_signal_all_processed() {
         "world signal flag should be set"
     );
 }
+
+// =============================================================================
+// Sensor
+// =============================================================================
+
+#[test]
+fn falling_entity_becomes_grounded_and_drives_animation() {
+    let mut world = make_world(0.0);
+
+    let mut rb = RigidBody::new();
+    rb.velocity = Vector2 { x: 0.0, y: 10.0 };
+    let player = world
+        .spawn((
+            Group::new("player"),
+            MapPosition::new(5.0, 0.0),
+            rb,
+            Signals::default(),
+            Sensor::new().with_ray(
+                SensorRay::new("grounded", Vector2::zero(), Vector2::new(0.0, 1.0), 1.0)
+                    .with_mask("ground"),
+            ),
+            Animation::new("fall"),
+            AnimationController::new("fall").with_rule(
+                Condition::HasFlag {
+                    key: "grounded".to_string(),
+                },
+                "idle",
+            ),
+        ))
+        .id();
+    world.spawn((
+        Group::new("ground"),
+        MapPosition::new(0.0, 10.0),
+        BoxCollider::new(20.0, 5.0),
+    ));
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems((movement, sensor_system, animation_controller).chain());
+
+    update_world_time(&mut world, 0.5);
+    schedule.run(&mut world);
+    assert!(!world.get::<Signals>(player).unwrap().has_flag("grounded"));
+    assert_eq!(
+        world.get::<Animation>(player).unwrap().animation_key,
+        "fall"
+    );
+
+    update_world_time(&mut world, 0.45);
+    schedule.run(&mut world);
+    assert!(world.get::<Signals>(player).unwrap().has_flag("grounded"));
+    assert_eq!(
+        world.get::<Animation>(player).unwrap().animation_key,
+        "idle"
+    );
+}
+
+// =============================================================================
+// Force areas
+// =============================================================================
+
+#[test]
+fn conveyor_carries_body_and_wind_accelerates_it() {
+    let mut world = make_world(0.0);
+
+    world.spawn((
+        Group::new("ground"),
+        MapPosition::new(0.0, 10.0),
+        BoxCollider::new(100.0, 10.0),
+        ForceArea::conveyor(Vector2::new(20.0, 0.0)).with_mask("player"),
+    ));
+    world.spawn((
+        MapPosition::new(0.0, 0.0),
+        ForceArea::acceleration(Vector2::new(0.0, -40.0))
+            .with_rect(raylib::prelude::Rectangle::new(0.0, 0.0, 100.0, 15.0)),
+    ));
+    let player = world
+        .spawn((
+            Group::new("player"),
+            MapPosition::new(10.0, 5.0),
+            BoxCollider::new(4.0, 6.0),
+            RigidBody::new(),
+        ))
+        .id();
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems((force_area_system, movement).chain());
+
+    update_world_time(&mut world, 0.5);
+    schedule.run(&mut world);
+
+    // Conveyor: +20 * 0.5 in x without touching velocity.
+    // Wind: velocity += -40 * 0.5 = -20, integrated by movement: -20 * 0.5 = -10.
+    let pos = world.get::<MapPosition>(player).unwrap().pos;
+    assert!(approx_eq(pos.x, 20.0));
+    assert!(approx_eq(pos.y, -5.0));
+    let rb = world.get::<RigidBody>(player).unwrap();
+    assert!(approx_eq(rb.velocity.x, 0.0));
+    assert!(approx_eq(rb.velocity.y, -20.0));
+}
+
+fn make_dirty_rect_world(mode: RenderMode) -> World {
+    let mut world = make_world(0.0);
+    world.insert_resource(Camera2DRes(Camera2D {
+        target: Vector2 { x: 0.0, y: 0.0 },
+        offset: Vector2 { x: 0.0, y: 0.0 },
+        rotation: 0.0,
+        zoom: 1.0,
+    }));
+    world.insert_resource(WorldClock::default());
+    world.insert_resource(FogOfWar::default());
+    world.insert_resource(PortalTransition::default());
+    world.insert_resource(CinematicMode::default());
+    world.insert_resource(mode);
+    world.insert_resource(DirtyRegion::default());
+    world
+}
+
+fn dirty_region_schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(track_dirty_regions);
+    schedule
+}
+
+#[test]
+fn dirty_rects_idle_frame_is_clean_and_moved_sprite_dirties_both_rects() {
+    let mut world = make_dirty_rect_world(RenderMode::DirtyRects);
+    let mut schedule = dirty_region_schedule();
+    let sprite = world
+        .spawn((
+            make_sprite("tile"),
+            ScreenPosition::new(100.0, 100.0),
+            ZIndex(0.0),
+        ))
+        .id();
+
+    // The first frame after entering the mode is always a full redraw.
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Full);
+
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Clean);
+
+    world.get_mut::<ScreenPosition>(sprite).unwrap().pos.x = 200.0;
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("moved sprite should dirty a rectangle");
+    };
+    assert!(rect.x <= 100.0 && rect.y <= 100.0);
+    assert!(rect.x + rect.width >= 264.0 && rect.y + rect.height >= 164.0);
+    assert!(rect.x > 90.0 && rect.x + rect.width < 280.0);
+}
+
+#[test]
+fn dirty_rects_despawn_dirties_last_rect_and_camera_move_forces_full() {
+    let mut world = make_dirty_rect_world(RenderMode::DirtyRects);
+    let mut schedule = dirty_region_schedule();
+    let sprite = world
+        .spawn((
+            make_sprite("tile"),
+            MapPosition::new(50.0, 50.0),
+            ZIndex(0.0),
+        ))
+        .id();
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Clean);
+
+    world.despawn(sprite);
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("despawned sprite should dirty its last rectangle");
+    };
+    assert!(rect.x <= 50.0 && rect.x + rect.width >= 114.0);
+
+    world.resource_mut::<Camera2DRes>().0.target.x = 10.0;
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Full);
+}
+
+#[test]
+fn dirty_rects_track_progress_bars_by_their_rectangle() {
+    let mut world = make_dirty_rect_world(RenderMode::DirtyRects);
+    let mut schedule = dirty_region_schedule();
+    let bar = world
+        .spawn((
+            ProgressBar::new(40.0, 6.0, 1.0),
+            MapPosition::new(100.0, 100.0),
+            ZIndex(0.0),
+        ))
+        .id();
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Clean);
+
+    world.get_mut::<ProgressBar>(bar).unwrap().value = 0.5;
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("a new progress value should dirty the bar only");
+    };
+    assert!(rect.x <= 100.0 && rect.x + rect.width >= 140.0);
+    assert!(rect.x > 90.0 && rect.x + rect.width < 150.0);
+
+    world.get_mut::<MapPosition>(bar).unwrap().pos.x = 200.0;
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("a moved progress bar should dirty its old and new rectangle");
+    };
+    assert!(rect.x <= 100.0 && rect.x + rect.width >= 240.0);
+}
+
+#[test]
+fn dirty_rects_track_nine_slices_by_their_rectangle() {
+    let mut world = make_dirty_rect_world(RenderMode::DirtyRects);
+    let mut schedule = dirty_region_schedule();
+    let panel = world
+        .spawn((
+            NineSlice::new("frame", 60.0, 30.0, 4),
+            ScreenPosition::new(100.0, 100.0),
+            ZIndex(0.0),
+        ))
+        .id();
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Clean);
+
+    world.get_mut::<ScreenPosition>(panel).unwrap().pos.x = 200.0;
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("a moved panel should dirty its old and new rectangle");
+    };
+    assert!(rect.x <= 100.0 && rect.x + rect.width >= 260.0);
+    assert!(rect.x > 90.0 && rect.x + rect.width < 270.0);
+
+    world.entity_mut(panel).remove::<NineSlice>();
+    schedule.run(&mut world);
+    let DirtyRegion::Rect(rect) = *world.resource::<DirtyRegion>() else {
+        panic!("a removed panel should dirty its last rectangle");
+    };
+    assert!(rect.x <= 200.0 && rect.x + rect.width >= 260.0);
+}
+
+#[test]
+fn full_render_mode_always_redraws_everything() {
+    let mut world = make_dirty_rect_world(RenderMode::Full);
+    let mut schedule = dirty_region_schedule();
+    world.spawn((
+        make_sprite("tile"),
+        ScreenPosition::new(10.0, 10.0),
+        ZIndex(0.0),
+    ));
+    schedule.run(&mut world);
+    schedule.run(&mut world);
+    assert_eq!(*world.resource::<DirtyRegion>(), DirtyRegion::Full);
+}
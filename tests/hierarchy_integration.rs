@@ -8,8 +8,6 @@
 //! cargo test --test hierarchy_integration
 //! ```
 
-use std::sync::Arc;
-
 use bevy_ecs::hierarchy::ChildOf;
 use bevy_ecs::prelude::*;
 #[cfg(feature = "lua")]
@@ -1031,7 +1029,7 @@ fn render_query_includes_global_transform() {
     let entity = world
         .spawn((
             Sprite {
-                tex_key: Arc::from("test"),
+                tex_key: "test".into(),
                 width: 32.0,
                 height: 32.0,
                 offset: Vector2 { x: 0.0, y: 0.0 },
@@ -1078,7 +1076,7 @@ fn render_query_works_without_global_transform() {
     let entity = world
         .spawn((
             Sprite {
-                tex_key: Arc::from("test"),
+                tex_key: "test".into(),
                 width: 32.0,
                 height: 32.0,
                 offset: Vector2 { x: 0.0, y: 0.0 },
@@ -1120,6 +1118,7 @@ fn render_query_works_without_global_transform() {
 
 use aberredengine::components::boxcollider::BoxCollider;
 use aberredengine::events::collision::CollisionEvent;
+use aberredengine::resources::framearena::FrameArena;
 use aberredengine::systems::collision_detector::collision_detector;
 
 /// Resource to collect collision events via observer.
@@ -1138,6 +1137,7 @@ fn setup_collision_world(world: &mut World) {
 }
 
 fn tick_collision(world: &mut World) {
+    world.init_resource::<FrameArena>();
     let mut schedule = Schedule::default();
     schedule.add_systems(collision_detector);
     schedule.run(world);
@@ -1257,6 +1257,7 @@ fn entity_context_includes_world_transform_fields() {
         sprite: None,
         animation: None,
         signals: None,
+        blackboard: None,
         lua_phase: None,
         lua_timer: None,
         previous_phase: None,
@@ -1304,6 +1305,7 @@ fn entity_context_nil_world_fields_without_hierarchy() {
         sprite: None,
         animation: None,
         signals: None,
+        blackboard: None,
         lua_phase: None,
         lua_timer: None,
         previous_phase: None,
@@ -1794,6 +1796,7 @@ fn collision_uses_live_map_position_after_child_despawn() {
         .id();
 
     // Run the full pipeline: propagate → cleanup → collision
+    world.init_resource::<FrameArena>();
     let mut schedule = Schedule::default();
     schedule.add_systems(propagate_transforms);
     schedule.add_systems(cleanup_orphaned_global_transforms.after(propagate_transforms));
@@ -16,9 +16,13 @@ use aberredengine::resources::input_bindings::{
 use bevy_ecs::prelude::*;
 use raylib::ffi::{KeyboardKey, MouseButton};
 
+#[cfg(feature = "lua")]
+use aberredengine::resources::inputcontext::InputContexts;
 #[cfg(feature = "lua")]
 use aberredengine::resources::lua_runtime::{InputCmd, action_from_str};
 #[cfg(feature = "lua")]
+use aberredengine::resources::worldsignals::WorldSignals;
+#[cfg(feature = "lua")]
 use aberredengine::systems::lua_commands::process_input_command;
 
 // ---------------------------------------------------------------------------
@@ -275,6 +279,7 @@ fn test_process_input_cmd_rebind_updates_binding() {
             key: "z".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     let keys = bindings.get_bindings(InputAction::Action1);
@@ -294,6 +299,7 @@ fn test_process_input_cmd_add_binding_appends() {
             key: "x".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     assert_eq!(
@@ -319,6 +325,7 @@ fn test_process_input_cmd_unknown_action_does_not_panic() {
             key: "a".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     assert_eq!(bindings.map.len(), snapshot.len());
@@ -338,6 +345,7 @@ fn test_process_input_cmd_unknown_key_does_not_panic() {
             key: "not_a_real_key".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     assert_eq!(
@@ -450,6 +458,7 @@ fn test_process_input_cmd_rebind_to_mouse_button() {
             key: "mouse_left".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     let bl = bindings.get_bindings(InputAction::Action3);
@@ -472,6 +481,7 @@ fn test_process_input_cmd_add_mouse_binding() {
             key: "mouse_middle".to_string(),
         },
         &mut bindings,
+        &mut World::new().commands(),
     );
 
     assert_eq!(
@@ -497,3 +507,26 @@ fn test_binding_from_str_in_integration() {
     );
     assert_eq!(binding_from_str("not_a_binding"), None);
 }
+
+// ---------------------------------------------------------------------------
+// process_input_command – input contexts
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "lua")]
+#[test]
+fn test_process_input_cmd_pushes_context() {
+    let mut world = World::new();
+    world.insert_resource(WorldSignals::default());
+    let mut bindings = InputBindings::default();
+
+    process_input_command(
+        InputCmd::PushInputContext {
+            name: "dialogue".to_string(),
+        },
+        &mut bindings,
+        &mut world.commands(),
+    );
+    world.flush();
+
+    assert_eq!(world.resource::<InputContexts>().active(), Some("dialogue"));
+}
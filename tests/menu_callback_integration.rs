@@ -404,3 +404,51 @@ fn lua_callback_takes_priority_over_rust_callback() {
         "Rust callback should be skipped when Lua callback is set"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Test: toggling the high-contrast UI flag recolors spawned menu items
+// ---------------------------------------------------------------------------
+
+#[test]
+fn high_contrast_flag_recolors_menu_items() {
+    use aberredengine::components::dynamictext::DynamicText;
+    use aberredengine::resources::accessibility::{HIGH_CONTRAST_SELECTED, HIGH_CONTRAST_TEXT};
+    use aberredengine::systems::menu::menu_high_contrast_system;
+    use raylib::prelude::Color;
+
+    let mut world = setup_world();
+    let texts: Vec<Entity> = (0..2)
+        .map(|_| {
+            world
+                .spawn(DynamicText::new("item", "test_font", 16.0, Color::RED))
+                .id()
+        })
+        .collect();
+    let mut menu = Menu::new(
+        &[("play", "Play"), ("quit", "Quit")],
+        raylib::prelude::Vector2::zero(),
+        "test_font",
+        16.0,
+        20.0,
+        true,
+    )
+    .with_colors(Color::RED, Color::GREEN);
+    for (item, text) in menu.items.iter_mut().zip(&texts) {
+        item.entity = Some(*text);
+    }
+    world.spawn(menu);
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(menu_high_contrast_system);
+    let color = |world: &World, i: usize| world.get::<DynamicText>(texts[i]).unwrap().color;
+
+    world.resource_mut::<GameConfig>().high_contrast_ui = true;
+    schedule.run(&mut world);
+    assert_eq!(color(&world, 0), HIGH_CONTRAST_SELECTED);
+    assert_eq!(color(&world, 1), HIGH_CONTRAST_TEXT);
+
+    world.resource_mut::<GameConfig>().high_contrast_ui = false;
+    schedule.run(&mut world);
+    assert_eq!(color(&world, 0), Color::GREEN);
+    assert_eq!(color(&world, 1), Color::RED);
+}
@@ -13,6 +13,7 @@ use aberredengine::resources::input::InputState;
 use aberredengine::resources::input_bindings::InputBindings;
 use aberredengine::resources::postprocessshader::PostProcessShader;
 use aberredengine::resources::scenemanager::SceneManager;
+use aberredengine::resources::scenestate::SceneState;
 use aberredengine::resources::systemsstore::SystemsStore;
 use aberredengine::resources::texturestore::TextureStore;
 use aberredengine::resources::worldsignals::WorldSignals;
@@ -22,12 +23,16 @@ use aberredengine::systems::scene_dispatch::{
     GuiCallback, SceneDescriptor, scene_enter_play, scene_switch_poll, scene_switch_system,
     scene_update_system,
 };
+use aberredengine::systems::scenestate::restore_on_return_system;
 use bevy_ecs::message::MessageReader;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::RunSystemOnce;
 use bevy_ecs::system::SystemState;
 
+use aberredengine::components::mapposition::MapPosition;
 use aberredengine::components::persistent::Persistent;
+use aberredengine::components::restoreonreturn::RestoreOnReturn;
+use aberredengine::components::signals::Signals;
 use aberredengine::events::audio::AudioCmd;
 use aberredengine::resources::gamestate::{GameState, NextGameState};
 
@@ -49,6 +54,7 @@ fn setup_world() -> World {
     world.init_resource::<PostProcessShader>();
     world.insert_resource(CameraFollowConfig::default());
     world.insert_resource(InputBindings::default());
+    world.insert_resource(SceneState::default());
     world
 }
 
@@ -929,3 +935,80 @@ fn scene_with_gui_callback_enters_correctly() {
         "gui_callback fn pointer must be unchanged after scene activation"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Test 16: RestoreOnReturn entities remember their state across scene visits
+// ---------------------------------------------------------------------------
+
+fn cave_enter(ctx: &mut GameCtx) {
+    ctx.commands.spawn((
+        MapPosition::new(10.0, 0.0),
+        Signals::default(),
+        RestoreOnReturn::new("door"),
+    ));
+    ctx.commands
+        .spawn((MapPosition::new(20.0, 0.0), RestoreOnReturn::new("coin")));
+}
+
+fn town_enter(_ctx: &mut GameCtx) {}
+
+fn switch_to(world: &mut World, scene: &str) {
+    world
+        .resource_mut::<WorldSignals>()
+        .set_string("scene", scene.to_string());
+    world.run_system_once(scene_switch_system).unwrap();
+    world.flush();
+    world.run_system_once(restore_on_return_system).unwrap();
+    world.flush();
+}
+
+fn find_restorable(world: &mut World, key: &str) -> Option<Entity> {
+    world
+        .query::<(Entity, &RestoreOnReturn)>()
+        .iter(world)
+        .find(|(_, r)| r.key == key)
+        .map(|(e, _)| e)
+}
+
+#[test]
+fn backtracking_restores_opened_door_and_collected_coin() {
+    clear_logs();
+    let mut world = setup_world();
+
+    let mut sm = SceneManager::new();
+    sm.initial_scene = Some("cave".to_string());
+    for (name, on_enter) in [
+        ("cave", cave_enter as fn(&mut GameCtx)),
+        ("town", town_enter),
+    ] {
+        sm.insert(
+            name,
+            SceneDescriptor {
+                on_enter,
+                on_update: None,
+                on_exit: None,
+                gui_callback: None,
+                world_draw_callback: None,
+            },
+        );
+    }
+    world.insert_resource(sm);
+    register_switch_system(&mut world);
+
+    switch_to(&mut world, "cave");
+    let door = find_restorable(&mut world, "door").expect("door spawned");
+    let coin = find_restorable(&mut world, "coin").expect("coin spawned");
+    world.get_mut::<Signals>(door).unwrap().set_flag("open");
+    world.despawn(coin);
+
+    switch_to(&mut world, "town");
+    assert!(find_restorable(&mut world, "door").is_none());
+
+    switch_to(&mut world, "cave");
+    let door = find_restorable(&mut world, "door").expect("door respawned");
+    assert!(world.get::<Signals>(door).unwrap().has_flag("open"));
+    assert!(
+        find_restorable(&mut world, "coin").is_none(),
+        "collected coin must stay collected"
+    );
+}